@@ -0,0 +1,549 @@
+//! Headless command-line mode
+//!
+//! `media-organizer --scan-duplicates <path>` and the other subcommands run
+//! the core services without starting the GUI. Progress goes to stderr and
+//! results to stdout. Anything that changes files is a dry run unless
+//! `--apply` is given, and duplicates are only ever moved to the trash.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use thiserror::Error;
+
+use crate::services::duplicate_detection::{
+    ComparisonMethod, DetectionProgress, DetectionProgressCallback, DuplicateDetectionConfig,
+    DuplicateDetectionError, DuplicateDetector, PrimarySelectionStrategy,
+};
+use crate::services::file_system::{
+    FileEntry, FileSystemError, FileSystemService, NativeFileSystemService, TraversalOptions,
+};
+use crate::services::hashing::{HashingError, HashingService};
+use crate::services::operation_log::{LogAction, OperationLog, OperationLogRecord};
+use crate::services::operations::{Command, HistoryEntry, MoveCommand, TrashCommand};
+use crate::services::classifier::{default_rules_dir, Classifier};
+use crate::services::organize::{plan_by_date, plan_by_type, OrganizeMove};
+use crate::utils::format_size;
+
+pub const EXIT_SUCCESS: i32 = 0;
+pub const EXIT_FAILURE: i32 = 1;
+pub const EXIT_USAGE: i32 = 2;
+
+pub const USAGE: &str = "\
+Usage:
+  media-organizer                                   Start the GUI
+  media-organizer --scan-duplicates <path> [--report <file.json>] [--method <method>] [--apply]
+  media-organizer --organize-by-date <source> <destination> [--apply]
+  media-organizer --organize-by-type <source> <destination> [--apply]
+  media-organizer --hash <path>
+
+Options:
+  --report <file>   Write the duplicate report as JSON
+  --method <name>   content (default), content-size, size, name, size-name or perceptual
+  --apply           Move duplicates to the trash (content methods only) / move files;
+                    without it nothing is changed
+  --log <file>      Operation log to record applied changes in
+  -h, --help        Show this help";
+
+/// Errors from parsing or running a headless command
+#[derive(Debug, Error)]
+pub enum CliError {
+    #[error("{0}")]
+    Usage(String),
+
+    #[error(transparent)]
+    FileSystem(#[from] FileSystemError),
+
+    #[error(transparent)]
+    Detection(#[from] DuplicateDetectionError),
+
+    #[error(transparent)]
+    Hashing(#[from] HashingError),
+
+    #[error("Could not write report: {0}")]
+    Report(String),
+
+    #[error("{0} of {1} changes failed")]
+    PartialFailure(usize, usize),
+}
+
+/// A headless subcommand
+#[derive(Debug, Clone, PartialEq)]
+pub enum CliCommand {
+    ScanDuplicates {
+        root: PathBuf,
+        report: Option<PathBuf>,
+        method: ComparisonMethod,
+        apply: bool,
+        log: Option<PathBuf>,
+        /// Folder used in place of the system trash; a hidden option for tests
+        trash_dir: Option<PathBuf>,
+    },
+    OrganizeByDate {
+        source: PathBuf,
+        destination: PathBuf,
+        apply: bool,
+        log: Option<PathBuf>,
+    },
+    /// Sort into one folder per category, using the user's classifier rules
+    OrganizeByType {
+        source: PathBuf,
+        destination: PathBuf,
+        apply: bool,
+        log: Option<PathBuf>,
+    },
+    Hash {
+        path: PathBuf,
+    },
+    Help,
+}
+
+/// Parse arguments (without the program name)
+///
+/// Returns `Ok(None)` when none of the headless subcommands were requested,
+/// in which case the GUI should start.
+pub fn parse_args(args: &[String]) -> Result<Option<CliCommand>, CliError> {
+    const SUBCOMMANDS: [&str; 6] =
+        ["--scan-duplicates", "--organize-by-date", "--organize-by-type", "--hash", "--help", "-h"];
+    if !args.iter().any(|arg| SUBCOMMANDS.contains(&arg.as_str())) {
+        return Ok(None);
+    }
+
+    let mut command = None;
+    let mut report = None;
+    let mut method = ComparisonMethod::default();
+    let mut apply = false;
+    let mut log = None;
+    let mut trash_dir = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        // A following option is never taken as the value, so `--report --apply` is an error
+        let mut value = |name: &str| {
+            args.next()
+                .filter(|value| !value.starts_with("--"))
+                .map(PathBuf::from)
+                .ok_or_else(|| CliError::Usage(format!("{} needs a value", name)))
+        };
+        let parsed = match arg.as_str() {
+            "-h" | "--help" => Some(CliCommand::Help),
+            "--scan-duplicates" => Some(CliCommand::ScanDuplicates {
+                root: value(arg)?,
+                report: None,
+                method: ComparisonMethod::default(),
+                apply: false,
+                log: None,
+                trash_dir: None,
+            }),
+            "--organize-by-date" => {
+                let source = value(arg)?;
+                let destination = value(arg)?;
+                Some(CliCommand::OrganizeByDate { source, destination, apply: false, log: None })
+            }
+            "--organize-by-type" => {
+                let source = value(arg)?;
+                let destination = value(arg)?;
+                Some(CliCommand::OrganizeByType { source, destination, apply: false, log: None })
+            }
+            "--hash" => Some(CliCommand::Hash { path: value(arg)? }),
+            "--report" => {
+                report = Some(value(arg)?);
+                None
+            }
+            "--method" => {
+                let name = value(arg)?;
+                method = parse_method(&name.to_string_lossy())?;
+                None
+            }
+            "--log" => {
+                log = Some(value(arg)?);
+                None
+            }
+            "--trash-dir" => {
+                trash_dir = Some(value(arg)?);
+                None
+            }
+            "--apply" => {
+                apply = true;
+                None
+            }
+            other => return Err(CliError::Usage(format!("Unknown argument: {}", other))),
+        };
+        if let Some(parsed) = parsed {
+            if command.is_some() {
+                return Err(CliError::Usage("Only one command can be run at a time".to_string()));
+            }
+            command = Some(parsed);
+        }
+    }
+
+    let command = match command {
+        Some(CliCommand::ScanDuplicates { root, .. }) => {
+            // Files that only share a size, a name or a look aren't proven to be copies
+            if apply && !matches!(method, ComparisonMethod::Content | ComparisonMethod::ContentAndSize) {
+                return Err(CliError::Usage(format!(
+                    "--apply needs --method content or content-size; {} matches aren't necessarily identical",
+                    method.display_name()
+                )));
+            }
+            CliCommand::ScanDuplicates { root, report, method, apply, log, trash_dir }
+        }
+        Some(CliCommand::OrganizeByDate { source, destination, .. }) => {
+            if report.is_some() {
+                return Err(CliError::Usage("--report only applies to --scan-duplicates".to_string()));
+            }
+            CliCommand::OrganizeByDate { source, destination, apply, log }
+        }
+        Some(CliCommand::OrganizeByType { source, destination, .. }) => {
+            if report.is_some() {
+                return Err(CliError::Usage("--report only applies to --scan-duplicates".to_string()));
+            }
+            CliCommand::OrganizeByType { source, destination, apply, log }
+        }
+        Some(other) => other,
+        None => return Err(CliError::Usage("No command given".to_string())),
+    };
+    Ok(Some(command))
+}
+
+fn parse_method(name: &str) -> Result<ComparisonMethod, CliError> {
+    match name {
+        "content" => Ok(ComparisonMethod::Content),
+        "content-size" => Ok(ComparisonMethod::ContentAndSize),
+        "size" => Ok(ComparisonMethod::Size),
+        "name" => Ok(ComparisonMethod::Name),
+        "size-name" => Ok(ComparisonMethod::SizeAndName),
+        "perceptual" => Ok(ComparisonMethod::Perceptual),
+        other => Err(CliError::Usage(format!("Unknown comparison method: {}", other))),
+    }
+}
+
+/// Run a headless command to completion and return the process exit code
+pub fn run(command: CliCommand) -> i32 {
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("error: could not start runtime: {}", e);
+            return EXIT_FAILURE;
+        }
+    };
+
+    let result = runtime.block_on(async {
+        match command {
+            CliCommand::Help => {
+                println!("{}", USAGE);
+                Ok(())
+            }
+            CliCommand::ScanDuplicates { root, report, method, apply, log, trash_dir } => {
+                scan_duplicates(&root, report.as_deref(), method, apply, open_log(log), trash_dir.as_deref()).await
+            }
+            CliCommand::OrganizeByDate { source, destination, apply, log } => {
+                organize_by_date(&source, &destination, apply, open_log(log)).await
+            }
+            CliCommand::OrganizeByType { source, destination, apply, log } => {
+                organize_by_type(&source, &destination, apply, open_log(log)).await
+            }
+            CliCommand::Hash { path } => hash(&path).await,
+        }
+    });
+
+    match result {
+        Ok(()) => EXIT_SUCCESS,
+        Err(CliError::Usage(message)) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            EXIT_USAGE
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            EXIT_FAILURE
+        }
+    }
+}
+
+fn open_log(path: Option<PathBuf>) -> OperationLog {
+    OperationLog::new(path.unwrap_or_else(OperationLog::default_path))
+}
+
+/// Duplicate report written by `--report`
+#[derive(Debug, Serialize)]
+pub struct DuplicateReport {
+    pub root: PathBuf,
+    pub method: ComparisonMethod,
+    pub files_analyzed: usize,
+    pub potential_savings: u64,
+    pub applied: bool,
+    pub groups: Vec<DuplicateReportGroup>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DuplicateReportGroup {
+    pub key: String,
+    pub size: u64,
+    /// File that is kept
+    pub keep: PathBuf,
+    /// Files that are (or with `--apply` were) removed
+    pub duplicates: Vec<PathBuf>,
+}
+
+async fn list_files(root: &Path) -> Result<Vec<FileEntry>, CliError> {
+    let fs = NativeFileSystemService::new();
+    let entries = fs.traverse_directory(root, TraversalOptions::recursive()).await?;
+    Ok(entries.into_iter().filter(|entry| !entry.is_directory).collect())
+}
+
+async fn scan_duplicates(
+    root: &Path,
+    report_path: Option<&Path>,
+    method: ComparisonMethod,
+    apply: bool,
+    log: OperationLog,
+    trash_dir: Option<&Path>,
+) -> Result<(), CliError> {
+    eprintln!("Scanning {}", root.display());
+    let files = list_files(root).await?;
+    if files.is_empty() {
+        println!("No files found in {}", root.display());
+        return Ok(());
+    }
+
+    let mut config = DuplicateDetectionConfig {
+        comparison_method: method,
+        ..DuplicateDetectionConfig::default()
+    };
+    // Among visually similar copies the largest image is the one worth keeping
+    if method == ComparisonMethod::Perceptual {
+        config.primary_selection = PrimarySelectionStrategy::HighestResolution;
+    }
+    let progress: DetectionProgressCallback = Arc::new(|progress: DetectionProgress| {
+        eprintln!("[{:?}] {:.0}%", progress.phase, progress.progress_percentage);
+    });
+    let results = DuplicateDetector::with_config(config).detect_duplicates(files, Some(progress)).await?;
+
+    let groups: Vec<DuplicateReportGroup> = results
+        .duplicate_groups()
+        .into_iter()
+        .filter_map(|group| {
+            let keep = group.primary_file()?.path().to_path_buf();
+            let duplicates = group
+                .files
+                .iter()
+                .map(|file| file.path().to_path_buf())
+                .filter(|path| *path != keep)
+                .collect();
+            Some(DuplicateReportGroup {
+                key: group.group_key.clone(),
+                size: group.files.first().map(|file| file.size()).unwrap_or_default(),
+                keep,
+                duplicates,
+            })
+        })
+        .collect();
+
+    for group in &groups {
+        println!("keep   {}", group.keep.display());
+        for duplicate in &group.duplicates {
+            println!("{} {}", if apply { "trash " } else { "dup   " }, duplicate.display());
+        }
+    }
+    println!(
+        "{} duplicate groups in {} files, {} reclaimable",
+        groups.len(),
+        results.total_files_analyzed,
+        format_size(results.total_potential_savings)
+    );
+
+    let outcome = if apply {
+        let trashed = groups
+            .iter()
+            .flat_map(|group| group.duplicates.iter())
+            .map(|path| match trash_dir {
+                Some(dir) => TrashCommand::new(path.clone()).with_trash_dir(dir.to_path_buf()),
+                None => TrashCommand::new(path.clone()),
+            })
+            .collect();
+        apply_commands(trashed, &log).await
+    } else {
+        if !groups.is_empty() {
+            eprintln!("Dry run: nothing was changed. Re-run with --apply to move the duplicates to the trash.");
+        }
+        Ok(())
+    };
+
+    if let Some(report_path) = report_path {
+        let report = DuplicateReport {
+            root: root.to_path_buf(),
+            method,
+            files_analyzed: results.total_files_analyzed,
+            potential_savings: results.total_potential_savings,
+            applied: apply,
+            groups,
+        };
+        let json = serde_json::to_string_pretty(&report).map_err(|e| CliError::Report(e.to_string()))?;
+        std::fs::write(report_path, json).map_err(|e| CliError::Report(e.to_string()))?;
+        eprintln!("Report written to {}", report_path.display());
+    }
+    outcome
+}
+
+async fn organize_by_date(source: &Path, destination: &Path, apply: bool, log: OperationLog) -> Result<(), CliError> {
+    eprintln!("Planning moves from {} to {}", source.display(), destination.display());
+    let files = list_files(source).await?;
+    let plan = plan_by_date(&files, destination);
+    apply_plan(plan, apply, log).await
+}
+
+async fn organize_by_type(source: &Path, destination: &Path, apply: bool, log: OperationLog) -> Result<(), CliError> {
+    let classifier = Classifier::load_dir(&default_rules_dir());
+    for error in classifier.errors() {
+        eprintln!("warning: {}", error);
+    }
+    eprintln!("Planning moves from {} to {}", source.display(), destination.display());
+    let files = list_files(source).await?;
+    let plan = plan_by_type(&files, destination, &classifier);
+    apply_plan(plan, apply, log).await
+}
+
+/// Print the planned moves and, with `apply`, carry them out
+async fn apply_plan(plan: Vec<OrganizeMove>, apply: bool, log: OperationLog) -> Result<(), CliError> {
+    for OrganizeMove { source, destination } in &plan {
+        println!("{} -> {}", source.display(), destination.display());
+    }
+    println!("{} files to organize", plan.len());
+
+    if !apply {
+        if !plan.is_empty() {
+            eprintln!("Dry run: nothing was moved. Re-run with --apply to move the files.");
+        }
+        return Ok(());
+    }
+
+    let fs = NativeFileSystemService::new();
+    for planned in &plan {
+        if let Some(parent) = planned.destination.parent() {
+            fs.create_directory(parent).await?;
+        }
+    }
+    let moves = plan
+        .into_iter()
+        .map(|planned| MoveCommand::new(planned.source, planned.destination))
+        .collect();
+    apply_commands(moves, &log).await
+}
+
+/// Execute commands one at a time, recording each outcome in the operation log
+async fn apply_commands<C: Command>(commands: Vec<C>, log: &OperationLog) -> Result<(), CliError> {
+    let fs: Arc<dyn FileSystemService> = Arc::new(NativeFileSystemService::new());
+    let total = commands.len();
+    let mut failed = 0;
+
+    for (index, mut command) in commands.into_iter().enumerate() {
+        eprintln!("[{}/{}] {}", index + 1, total, command.description());
+        let result = command.execute(fs.clone()).await;
+        let entry = HistoryEntry::new(&command);
+        let mut record = OperationLogRecord::new(LogAction::Execute, &entry, &command);
+        if let Err(e) = &result {
+            eprintln!("  failed: {}", e);
+            failed += 1;
+            record = record.with_error(e);
+        }
+        if let Err(e) = log.append(&record) {
+            eprintln!("  warning: could not write operation log: {}", e);
+        }
+    }
+
+    if failed > 0 {
+        return Err(CliError::PartialFailure(failed, total));
+    }
+    Ok(())
+}
+
+async fn hash(path: &Path) -> Result<(), CliError> {
+    let hashing = HashingService::new();
+    let paths: Vec<PathBuf> = if path.is_dir() {
+        list_files(path).await?.into_iter().map(|entry| entry.path).collect()
+    } else {
+        vec![path.to_path_buf()]
+    };
+
+    let mut failed = 0;
+    for (index, file) in paths.iter().enumerate() {
+        eprintln!("[{}/{}] {}", index + 1, paths.len(), file.display());
+        match hashing.hash_file(file).await {
+            // Same layout as sha256sum so the output can be checked with it
+            Ok(hash) => println!("{}  {}", hash.hash, file.display()),
+            Err(e) if paths.len() == 1 => return Err(e.into()),
+            Err(e) => {
+                eprintln!("  failed: {}", e);
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(CliError::PartialFailure(failed, paths.len()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args_defaults_to_dry_run_and_gui() {
+        assert_eq!(parse_args(&[]).unwrap(), None);
+        assert_eq!(parse_args(&args(&["-psn_0_12345"])).unwrap(), None);
+
+        let command = parse_args(&args(&["--scan-duplicates", "/photos", "--report", "out.json"])).unwrap();
+        assert_eq!(
+            command,
+            Some(CliCommand::ScanDuplicates {
+                root: PathBuf::from("/photos"),
+                report: Some(PathBuf::from("out.json")),
+                method: ComparisonMethod::Content,
+                apply: false,
+                log: None,
+                trash_dir: None,
+            })
+        );
+
+        assert_eq!(parse_method("perceptual").unwrap(), ComparisonMethod::Perceptual);
+
+        let command = parse_args(&args(&["--apply", "--organize-by-date", "/in", "/out"])).unwrap();
+        assert!(matches!(command, Some(CliCommand::OrganizeByDate { apply: true, .. })));
+        let command = parse_args(&args(&["--organize-by-type", "/in", "/out"])).unwrap();
+        assert!(matches!(command, Some(CliCommand::OrganizeByType { apply: false, .. })));
+
+        // Only exact matches may be trashed
+        let command = parse_args(&args(&["--scan-duplicates", "/photos", "--method", "content-size", "--apply"])).unwrap();
+        assert!(matches!(command, Some(CliCommand::ScanDuplicates { apply: true, .. })));
+        for method in ["size", "name", "size-name", "perceptual"] {
+            let parsed = parse_args(&args(&["--scan-duplicates", "/photos", "--method", method, "--apply"]));
+            assert!(matches!(parsed, Err(CliError::Usage(_))), "{}", method);
+            assert!(parse_args(&args(&["--scan-duplicates", "/photos", "--method", method])).is_ok());
+        }
+
+        assert!(matches!(parse_args(&args(&["--hash"])), Err(CliError::Usage(_))));
+        assert!(matches!(parse_args(&args(&["--hash", "a", "--hash", "b"])), Err(CliError::Usage(_))));
+        assert!(matches!(parse_args(&args(&["--hash", "a", "--bogus"])), Err(CliError::Usage(_))));
+    }
+
+    #[test]
+    fn test_parse_args_does_not_take_an_option_as_a_value() {
+        let parsed = parse_args(&args(&["--scan-duplicates", "/photos", "--report", "--apply"]));
+        assert!(matches!(parsed, Err(CliError::Usage(message)) if message == "--report needs a value"));
+        let parsed = parse_args(&args(&["--scan-duplicates", "--apply", "/photos"]));
+        assert!(matches!(parsed, Err(CliError::Usage(_))));
+        let parsed = parse_args(&args(&["--organize-by-date", "/in", "--log", "log.jsonl"]));
+        assert!(matches!(parsed, Err(CliError::Usage(_))));
+
+        let command = parse_args(&args(&["--scan-duplicates", "/photos", "--trash-dir", "/tmp/trash", "--apply"])).unwrap();
+        assert!(matches!(
+            command,
+            Some(CliCommand::ScanDuplicates { apply: true, trash_dir: Some(dir), .. }) if dir == PathBuf::from("/tmp/trash")
+        ));
+    }
+}
@@ -0,0 +1,251 @@
+//! Localization of UI strings
+//!
+//! Strings are looked up by key with [`t!`](crate::t) from per-locale JSON
+//! resources in `assets/locales`. Keys missing from the active locale fall
+//! back to English, and keys missing everywhere render as the key itself so
+//! gaps are visible rather than blank.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// Languages the UI can be displayed in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Locale {
+    #[default]
+    English,
+    German,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::English, Locale::German];
+
+    /// BCP 47 language tag, also the resource file name
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::German => "de",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Self {
+        match code {
+            "de" => Locale::German,
+            _ => Locale::English,
+        }
+    }
+
+    /// Language of the operating system, from the POSIX locale variables
+    pub fn system() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .map(|value| Self::from_code(value.get(..2).unwrap_or_default()))
+            .unwrap_or_default()
+    }
+
+    /// Name of the language in that language, as shown in the language picker
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Locale::English => "English",
+            Locale::German => "Deutsch",
+        }
+    }
+
+    /// Plural category for `count` under this locale's rules
+    fn plural_category(&self, count: u64) -> PluralCategory {
+        match self {
+            Locale::English | Locale::German => {
+                if count == 1 {
+                    PluralCategory::One
+                } else {
+                    PluralCategory::Other
+                }
+            }
+        }
+    }
+
+    /// Digit group separator for counts
+    fn group_separator(&self) -> char {
+        match self {
+            Locale::English => ',',
+            Locale::German => '.',
+        }
+    }
+
+    fn resource(&self) -> &'static str {
+        match self {
+            Locale::English => include_str!("../assets/locales/en.json"),
+            Locale::German => include_str!("../assets/locales/de.json"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PluralCategory {
+    One,
+    Other,
+}
+
+/// A translated message: plain text, or one form per plural category
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+enum Message {
+    Text(String),
+    Plural {
+        #[serde(default)]
+        zero: Option<String>,
+        one: String,
+        other: String,
+    },
+}
+
+impl Message {
+    fn form(&self, locale: Locale, count: Option<u64>) -> &str {
+        match self {
+            Message::Text(text) => text,
+            Message::Plural { zero, one, other } => match count {
+                Some(0) if zero.is_some() => zero.as_deref().unwrap_or(other),
+                Some(n) if locale.plural_category(n) == PluralCategory::One => one,
+                _ => other,
+            },
+        }
+    }
+}
+
+type Catalogs = HashMap<Locale, HashMap<String, Message>>;
+
+static CATALOGS: Lazy<Catalogs> = Lazy::new(|| {
+    Locale::ALL
+        .iter()
+        .map(|locale| (*locale, parse_resource(*locale, locale.resource())))
+        .collect()
+});
+
+fn parse_resource(locale: Locale, json: &str) -> HashMap<String, Message> {
+    serde_json::from_str(json).unwrap_or_else(|e| {
+        tracing::error!("Invalid {} locale resource: {}", locale.code(), e);
+        HashMap::new()
+    })
+}
+
+static CURRENT_LOCALE: Lazy<RwLock<Locale>> = Lazy::new(|| RwLock::new(Locale::default()));
+
+/// Switch the language used by [`translate`]
+pub fn set_locale(locale: Locale) {
+    if let Ok(mut current) = CURRENT_LOCALE.write() {
+        *current = locale;
+    }
+}
+
+pub fn current_locale() -> Locale {
+    CURRENT_LOCALE.read().map(|locale| *locale).unwrap_or_default()
+}
+
+/// Format a count with the locale's digit grouping, e.g. "12,345"
+pub fn format_count(locale: Locale, count: u64) -> String {
+    let digits = count.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push(locale.group_separator());
+        }
+        grouped.push(c);
+    }
+    grouped
+}
+
+/// Resolve `key` in the current locale; see [`translate_in`]
+pub fn translate(key: &str, count: Option<u64>, args: &[(&str, String)]) -> String {
+    translate_in(current_locale(), key, count, args)
+}
+
+/// Resolve `key` in `locale`, falling back to English and then to the key itself
+///
+/// `count` picks the plural form and fills `{count}`; `args` fill the other
+/// `{name}` placeholders.
+pub fn translate_in(locale: Locale, key: &str, count: Option<u64>, args: &[(&str, String)]) -> String {
+    resolve(&CATALOGS, locale, key, count, args)
+}
+
+fn resolve(catalogs: &Catalogs, locale: Locale, key: &str, count: Option<u64>, args: &[(&str, String)]) -> String {
+    let message = catalogs
+        .get(&locale)
+        .and_then(|messages| messages.get(key))
+        .or_else(|| catalogs.get(&Locale::English).and_then(|messages| messages.get(key)));
+    let Some(message) = message else {
+        return key.to_string();
+    };
+
+    let mut text = message.form(locale, count).to_string();
+    if let Some(count) = count {
+        text = text.replace("{count}", &format_count(locale, count));
+    }
+    for (name, value) in args {
+        text = text.replace(&format!("{{{}}}", name), value);
+    }
+    text
+}
+
+/// Look up a localized UI string
+///
+/// ```ignore
+/// t!("menu.file")
+/// t!("status.files", count = 3)
+/// t!("dialog.rename.message", name = file_name)
+/// ```
+///
+/// A `count` argument must come first; it selects the plural form.
+#[macro_export]
+macro_rules! t {
+    ($key:expr) => {
+        $crate::i18n::translate($key, None, &[])
+    };
+    ($key:expr, count = $count:expr $(, $name:ident = $value:expr)* $(,)?) => {
+        $crate::i18n::translate($key, Some($count as u64), &[$((stringify!($name), $value.to_string())),*])
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::translate($key, None, &[$((stringify!($name), $value.to_string())),+])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_keys_fall_back_to_english_then_key() {
+        let catalogs: Catalogs = HashMap::from([
+            (Locale::English, parse_resource(Locale::English, r#"{ "greeting": "Hello", "only.english": "Fallback" }"#)),
+            (Locale::German, parse_resource(Locale::German, r#"{ "greeting": "Hallo" }"#)),
+        ]);
+        assert_eq!(resolve(&catalogs, Locale::German, "greeting", None, &[]), "Hallo");
+        assert_eq!(resolve(&catalogs, Locale::German, "only.english", None, &[]), "Fallback");
+        assert_eq!(resolve(&catalogs, Locale::German, "no.such.key", None, &[]), "no.such.key");
+        assert_eq!(t!("no.such.key"), "no.such.key");
+    }
+
+    #[test]
+    fn test_each_locale_resolves_its_own_strings() {
+        assert_eq!(translate_in(Locale::English, "menu.file", None, &[]), "File");
+        assert_eq!(translate_in(Locale::English, "status.files", Some(1), &[]), "1 file");
+        assert_eq!(translate_in(Locale::English, "status.files", Some(12345), &[]), "12,345 files");
+
+        assert_eq!(translate_in(Locale::German, "menu.file", None, &[]), "Datei");
+        assert_eq!(translate_in(Locale::German, "status.files", Some(1), &[]), "1 Datei");
+        assert_eq!(translate_in(Locale::German, "status.files", Some(12345), &[]), "12.345 Dateien");
+    }
+
+    #[test]
+    fn test_every_resource_parses_and_names_fill_in() {
+        for locale in Locale::ALL {
+            assert!(CATALOGS.get(&locale).is_some_and(|messages| !messages.is_empty()), "{:?}", locale);
+        }
+        assert_eq!(
+            translate_in(Locale::English, "dialog.rename.message", None, &[("name", "a.jpg".to_string())]),
+            "Enter a new name for 'a.jpg':"
+        );
+    }
+}
@@ -3,6 +3,9 @@
 //! Cross-platform media/file management application built with Dioxus.
 
 
+#[macro_use]
+pub mod i18n;
+pub mod cli;
 pub mod models;
 pub mod performance;
 pub mod services;
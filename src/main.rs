@@ -1,7 +1,10 @@
-use tracing::info;
-use std::path::PathBuf;
-use dioxus::prelude::{component, Element};
+use tracing::{info, warn};
+use std::path::{Path, PathBuf};
+use dioxus::prelude::{component, Element, Signal};
 
+#[macro_use]
+mod i18n;
+mod cli;
 mod models;
 mod performance;
 mod services;
@@ -14,8 +17,31 @@ use models::AppConfig;
 use state::AppStateProvider;
 use ui::phase2_app;
 use services::FileEntry;
+use services::file_system::{ConflictResolution, DirectoryWatcher, WATCH_DEBOUNCE};
+use services::{DeleteSummary, FileOperationProgress, FileOperationResult, FileOperationService, TransferProgress};
+use services::operations::{CancellationToken, Command as _};
+use services::organize::{OrganizeCommand, OrganizeRule};
+use services::classifier::{self, Classifier};
+use services::open_with::{self, OpenWithApp, OpenWithHistory};
+use services::single_instance::{self, InstanceRequest, InstanceRequests, InstanceRole};
+use state::recent_folders::RecentFoldersState;
+use ui::components::{ConfirmationResult, DialogResult, InputDialog, InputDialogRequest, OpenWithDialog, OpenWithRequest};
 
 fn main() {
+    // Headless subcommands run without the GUI; logs go to stderr so stdout stays parseable
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match cli::parse_args(&args) {
+        Ok(Some(command)) => {
+            tracing_subscriber::fmt().with_writer(std::io::stderr).init();
+            std::process::exit(cli::run(command));
+        }
+        Ok(None) => {}
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, cli::USAGE);
+            std::process::exit(cli::EXIT_USAGE);
+        }
+    }
+
     // Initialize logging
     tracing_subscriber::fmt::init();
 
@@ -24,35 +50,230 @@ fn main() {
     // Load configuration
     let _config = AppConfig::default();
 
+    // The menu bar is built once, so pick the saved language before creating it
+    let settings = state::load_settings();
+    i18n::set_locale(settings.language);
+    utils::format::set_format_settings(settings.format_settings());
+
+    // A folder passed on the command line, e.g. from "Open with" in the OS file manager
+    let requested_folder = args
+        .iter()
+        .filter(|arg| !arg.starts_with('-'))
+        .map(PathBuf::from)
+        .find(|path| path.is_dir());
+
+    // Hand the launch to an already running instance unless several are allowed
+    let (request_sender, instance_requests) = single_instance::request_channel();
+    let mut _primary_instance = None;
+    if !settings.allow_multiple_instances {
+        let request = match &requested_folder {
+            Some(folder) => InstanceRequest::OpenFolder(folder.clone()),
+            None => InstanceRequest::Activate,
+        };
+        match single_instance::acquire(&single_instance::default_lock_path(), &request) {
+            Ok(InstanceRole::Forwarded) => {
+                info!("Forwarded launch to the running instance");
+                return;
+            }
+            Ok(InstanceRole::Primary(mut primary)) => {
+                primary.listen(request_sender.clone());
+                _primary_instance = Some(primary);
+            }
+            Err(e) => warn!("Could not acquire the single-instance lock, continuing anyway: {}", e),
+        }
+    }
+    if let Some(folder) = requested_folder {
+        let _ = request_sender.send(InstanceRequest::OpenFolder(folder));
+    }
+
+    // User classification rules; bad ones are skipped and listed in the Output panel
+    let classifier = Classifier::load_dir(&classifier::default_rules_dir());
+    for error in classifier.errors() {
+        warn!("Skipped classification rule: {}", error);
+    }
+    classifier::install(classifier);
+
+    // User themes; invalid files are skipped
+    let custom_themes = theme::CustomThemes::load_dir(&theme::custom_theme::default_themes_dir());
+    for error in custom_themes.errors() {
+        warn!("Skipped custom theme: {}", error);
+    }
+    theme::custom_theme::install(custom_themes);
+
     // Create custom menu bar
-    let menu = create_menu_bar();
+    let menu = create_menu_bar(&settings.recent_folders);
 
     // Launch Dioxus desktop application with custom menu
     dioxus::LaunchBuilder::desktop()
+        .with_context(instance_requests)
         .with_cfg(
             dioxus::desktop::Config::default()
                 .with_menu(menu)
-                .with_window(
-                    dioxus::desktop::WindowBuilder::new()
-                        .with_title("Media Organizer")
-                )
+                .with_window(main_window_builder(&state::persistence::load_layout_state().unwrap_or_default()))
         )
         .launch(app);
 }
 
-fn create_menu_bar() -> dioxus::desktop::muda::Menu {
+/// The main window at the size it had when the app last closed
+///
+/// The position is applied once the window exists, see `restore_window_position`,
+/// since the monitors it has to fit on are not known before then.
+fn main_window_builder(layout: &state::LayoutState) -> dioxus::desktop::WindowBuilder {
+    use dioxus::desktop::tao::dpi::LogicalSize;
+
+    let builder = dioxus::desktop::WindowBuilder::new().with_title("Media Organizer");
+    if !layout.persistence.restore_window_state {
+        return builder;
+    }
+    builder
+        .with_inner_size(LogicalSize::new(layout.viewport.width, layout.viewport.height))
+        .with_maximized(layout.viewport.is_maximized)
+}
+
+/// Move the window back where it was, fitted onto the monitors connected now
+fn restore_window_position(layout: &state::LayoutState) {
+    use dioxus::desktop::tao::dpi::{LogicalPosition, LogicalSize};
+    use state::window_geometry::{clamp_to_monitors, ScreenRect};
+
+    if !layout.persistence.restore_window_state || layout.viewport.is_maximized {
+        return;
+    }
+    let Some(saved) = layout.viewport.window_rect() else {
+        return;
+    };
+    let window = dioxus::desktop::window();
+    let scale = window.scale_factor();
+    let primary = window.primary_monitor();
+    let others = window.available_monitors().filter(|monitor| Some(monitor) != primary.as_ref());
+    let monitors: Vec<ScreenRect> = primary
+        .iter()
+        .cloned()
+        .chain(others)
+        .map(|monitor| {
+            let position = monitor.position().to_logical::<f64>(scale);
+            let size = monitor.size().to_logical::<f64>(scale);
+            ScreenRect::new(position.x, position.y, size.width, size.height)
+        })
+        .collect();
+    let rect = clamp_to_monitors(saved, &monitors);
+    if rect != saved {
+        info!("Moved saved window geometry {:?} on screen as {:?}", saved, rect);
+    }
+    window.set_outer_position(LogicalPosition::new(rect.x, rect.y));
+    window.set_inner_size(LogicalSize::new(rect.width, rect.height));
+}
+
+/// Note where the window is and how big, to be restored on the next launch
+fn record_window_geometry(mut layout_state: Signal<state::LayoutState>) {
+    use state::window_geometry::ScreenRect;
+
+    let window = dioxus::desktop::window();
+    let scale = window.scale_factor();
+    let mut layout = layout_state.write();
+    layout.viewport.pixel_ratio = scale;
+    layout.viewport.is_maximized = window.is_maximized();
+    layout.viewport.is_fullscreen = window.fullscreen().is_some();
+    // Keep the normal geometry so un-maximizing after a restart goes back to it
+    if !layout.viewport.is_maximized && !layout.viewport.is_fullscreen {
+        if let Ok(position) = window.outer_position() {
+            let position = position.to_logical::<f64>(scale);
+            let size = window.inner_size().to_logical::<f64>(scale);
+            layout.viewport.set_window_rect(ScreenRect::new(position.x, position.y, size.width, size.height));
+        }
+    }
+    if layout.persistence.persist_layout {
+        state::persistence::save_layout_state_debounced(layout.clone());
+    }
+}
+
+/// Bring back the undo history saved when the last session ended
+fn restore_operation_history(history: &tokio::sync::Mutex<services::operations::OperationHistory>) {
+    let Ok(mut history) = history.try_lock() else {
+        return;
+    };
+    let path = history.get_default_history_path();
+    if !path.exists() {
+        return;
+    }
+    if let Err(e) = history.load_from_file_blocking(&path) {
+        warn!("Failed to restore operation history from {}: {}", path.display(), e);
+    }
+}
+
+/// Save the undo history for the next session
+fn save_operation_history(history: &tokio::sync::Mutex<services::operations::OperationHistory>) {
+    // Held by an operation still running; its history was saved last time round
+    let Ok(history) = history.try_lock() else {
+        warn!("Operation history is busy, not saved");
+        return;
+    };
+    if let Err(e) = history.save_to_file_blocking(&history.get_default_history_path()) {
+        warn!("Failed to save operation history: {}", e);
+    }
+}
+
+/// Menu item ids for File > Open Recent are the folder after one of these prefixes
+const OPEN_RECENT_PREFIX: &str = "open_recent:";
+const REMOVE_RECENT_PREFIX: &str = "remove_recent:";
+
+thread_local! {
+    /// File > Open Recent, kept to be refilled when the recent folders change
+    static OPEN_RECENT_MENU: std::cell::RefCell<Option<dioxus::desktop::muda::Submenu>> = const { std::cell::RefCell::new(None) };
+}
+
+/// Replace the items of File > Open Recent with `recent`
+///
+/// A folder that has gone missing is listed disabled, followed by an item
+/// removing it from the list.
+fn fill_open_recent_menu(menu: &dioxus::desktop::muda::Submenu, recent: &RecentFoldersState) {
+    use dioxus::desktop::muda::{MenuItem, PredefinedMenuItem};
+
+    while menu.remove_at(0).is_some() {}
+    if recent.is_empty() {
+        let _ = menu.append(&MenuItem::new(t!("menu.no_recent_folders"), false, None));
+        return;
+    }
+    for folder in recent.folders() {
+        let path = folder.to_string_lossy();
+        let label = utils::normalize_path_display(folder);
+        if folder.is_dir() {
+            let _ = menu.append(&MenuItem::with_id(format!("{}{}", OPEN_RECENT_PREFIX, path), &label, true, None));
+        } else {
+            let _ = menu.append(&MenuItem::new(t!("menu.recent_folder_missing", path = label.clone()), false, None));
+            let _ = menu.append(&MenuItem::with_id(
+                format!("{}{}", REMOVE_RECENT_PREFIX, path),
+                t!("menu.remove_from_recents", path = label),
+                true,
+                None,
+            ));
+        }
+    }
+    let _ = menu.append(&PredefinedMenuItem::separator());
+    let _ = menu.append(&MenuItem::with_id("clear_recent_folders", t!("menu.clear_recent_folders"), true, None));
+}
+
+/// Refill File > Open Recent after the recent folders changed
+fn refresh_open_recent_menu(recent: &RecentFoldersState) {
+    OPEN_RECENT_MENU.with(|menu| {
+        if let Some(menu) = menu.borrow().as_ref() {
+            fill_open_recent_menu(menu, recent);
+        }
+    });
+}
+
+fn create_menu_bar(recent_folders: &RecentFoldersState) -> dioxus::desktop::muda::Menu {
     use dioxus::desktop::muda::{Menu, Submenu, MenuItem, PredefinedMenuItem};
     use dioxus::desktop::muda::accelerator::{Accelerator, Modifiers, Code};
 
     let menu = Menu::new();
 
     // App menu (media-organizer)
-    let app_menu = Submenu::new("Media Organizer", true);
+    let app_menu = Submenu::new(t!("menu.app"), true);
     app_menu.append_items(&[
-        &MenuItem::with_id("about_app", "About Media Organizer", true, None),
-        &MenuItem::with_id("check_updates", "Check for Updates...", true, None),
+        &MenuItem::with_id("about_app", t!("menu.about"), true, None),
+        &MenuItem::with_id("check_updates", t!("menu.check_updates"), true, None),
         &PredefinedMenuItem::separator(),
-        &MenuItem::with_id("preferences", "Preferences...", true, Some(Accelerator::new(Some(Modifiers::SUPER), Code::Comma))),
+        &MenuItem::with_id("preferences", t!("menu.preferences"), true, Some(Accelerator::new(Some(Modifiers::SUPER), Code::Comma))),
         &PredefinedMenuItem::separator(),
         &PredefinedMenuItem::services(None),
         &PredefinedMenuItem::separator(),
@@ -65,25 +286,34 @@ fn create_menu_bar() -> dioxus::desktop::muda::Menu {
     menu.append(&app_menu).unwrap();
 
     // File menu
-    let file_menu = Submenu::new("File", true);
+    let open_recent_menu = Submenu::with_id("open_recent", t!("menu.open_recent"), true);
+    fill_open_recent_menu(&open_recent_menu, recent_folders);
+    let file_menu = Submenu::new(t!("menu.file"), true);
     file_menu.append_items(&[
-        &MenuItem::with_id("open_folder", "Open Folder...", true, Some(Accelerator::new(Some(Modifiers::SUPER), Code::KeyO))),
-        &MenuItem::with_id("new_window", "New Window", true, Some(Accelerator::new(Some(Modifiers::SUPER), Code::KeyN))),
+        &MenuItem::with_id("open_folder", t!("menu.open_folder"), true, Some(Accelerator::new(Some(Modifiers::SUPER), Code::KeyO))),
+        &open_recent_menu,
+        &MenuItem::with_id("new_window", t!("menu.new_window"), true, Some(Accelerator::new(Some(Modifiers::SUPER), Code::KeyN))),
         &PredefinedMenuItem::separator(),
-        &MenuItem::with_id("new_folder", "New Folder", true, Some(Accelerator::new(Some(Modifiers::SUPER | Modifiers::SHIFT), Code::KeyN))),
-        &MenuItem::with_id("new_file", "New File", true, Some(Accelerator::new(Some(Modifiers::SUPER | Modifiers::SHIFT), Code::KeyT))),
+        &MenuItem::with_id("new_folder", t!("menu.new_folder"), true, Some(Accelerator::new(Some(Modifiers::SUPER | Modifiers::SHIFT), Code::KeyN))),
+        &MenuItem::with_id("new_file", t!("menu.new_file"), true, Some(Accelerator::new(Some(Modifiers::SUPER | Modifiers::SHIFT), Code::KeyT))),
         &PredefinedMenuItem::separator(),
-        &MenuItem::with_id("refresh", "Refresh", true, Some(Accelerator::new(Some(Modifiers::SUPER), Code::KeyR))),
-        &MenuItem::with_id("show_hidden", "Show Hidden Files", true, Some(Accelerator::new(Some(Modifiers::SUPER | Modifiers::SHIFT), Code::Period))),
+        &MenuItem::with_id("refresh", t!("menu.refresh"), true, Some(Accelerator::new(Some(Modifiers::SUPER), Code::KeyR))),
+        &MenuItem::with_id("show_hidden", t!("menu.show_hidden"), true, Some(Accelerator::new(Some(Modifiers::SUPER | Modifiers::SHIFT), Code::Period))),
         &PredefinedMenuItem::separator(),
-        &MenuItem::with_id("open", "Open", true, Some(Accelerator::new(Some(Modifiers::SUPER), Code::Enter))),
-        &MenuItem::with_id("open_with", "Open With...", true, Some(Accelerator::new(Some(Modifiers::SUPER | Modifiers::SHIFT), Code::Enter))),
-        &MenuItem::with_id("show_in_finder", "Show in Finder", true, Some(Accelerator::new(Some(Modifiers::SUPER | Modifiers::ALT), Code::KeyR))),
+        &MenuItem::with_id("open", t!("menu.open"), true, Some(Accelerator::new(Some(Modifiers::SUPER), Code::Enter))),
+        &MenuItem::with_id("open_with", t!("menu.open_with"), true, Some(Accelerator::new(Some(Modifiers::SUPER | Modifiers::SHIFT), Code::Enter))),
+        &MenuItem::with_id("show_in_finder", t!("menu.show_in_finder"), true, Some(Accelerator::new(Some(Modifiers::SUPER | Modifiers::ALT), Code::KeyR))),
+        &PredefinedMenuItem::separator(),
+        &MenuItem::with_id("export_zip", t!("menu.export_zip"), true, Some(Accelerator::new(Some(Modifiers::SUPER | Modifiers::SHIFT), Code::KeyE))),
+        &MenuItem::with_id("extract_here", t!("menu.extract_here"), true, None),
+        &MenuItem::with_id("extract_to", t!("menu.extract_to"), true, None),
+        &MenuItem::with_id("cancel_operation", t!("menu.cancel_operation"), true, None),
     ]).unwrap();
     menu.append(&file_menu).unwrap();
+    OPEN_RECENT_MENU.with(|menu| *menu.borrow_mut() = Some(open_recent_menu));
 
     // Edit menu
-    let edit_menu = Submenu::new("Edit", true);
+    let edit_menu = Submenu::new(t!("menu.edit"), true);
     edit_menu.append_items(&[
         &PredefinedMenuItem::undo(None),
         &PredefinedMenuItem::redo(None),
@@ -93,36 +323,57 @@ fn create_menu_bar() -> dioxus::desktop::muda::Menu {
         &PredefinedMenuItem::paste(None),
         &PredefinedMenuItem::separator(),
         &PredefinedMenuItem::select_all(None),
-        &MenuItem::with_id("clear_selection", "Clear Selection", true, None),
+        &MenuItem::with_id("clear_selection", t!("menu.clear_selection"), true, None),
         &PredefinedMenuItem::separator(),
-        &MenuItem::with_id("copy_to", "Copy to...", true, Some(Accelerator::new(Some(Modifiers::SUPER | Modifiers::SHIFT), Code::KeyC))),
-        &MenuItem::with_id("move_to", "Move to...", true, Some(Accelerator::new(Some(Modifiers::SUPER | Modifiers::SHIFT), Code::KeyM))),
+        &MenuItem::with_id("copy_to", t!("menu.copy_to"), true, Some(Accelerator::new(Some(Modifiers::SUPER | Modifiers::SHIFT), Code::KeyC))),
+        &MenuItem::with_id("move_to", t!("menu.move_to"), true, Some(Accelerator::new(Some(Modifiers::SUPER | Modifiers::SHIFT), Code::KeyM))),
         &PredefinedMenuItem::separator(),
-        &MenuItem::with_id("delete", "Delete", true, Some(Accelerator::new(None, Code::Delete))),
-        &MenuItem::with_id("rename", "Rename", true, Some(Accelerator::new(None, Code::Enter))),
-        &MenuItem::with_id("duplicate", "Duplicate", true, Some(Accelerator::new(Some(Modifiers::SUPER), Code::KeyD))),
+        &MenuItem::with_id("delete", t!("menu.delete"), true, Some(Accelerator::new(None, Code::Delete))),
+        &MenuItem::with_id("delete_permanently", t!("menu.delete_permanently"), true, Some(Accelerator::new(Some(Modifiers::SHIFT), Code::Delete))),
+        &MenuItem::with_id("rename", t!("menu.rename"), true, Some(Accelerator::new(None, Code::Enter))),
+        &MenuItem::with_id("duplicate", t!("menu.duplicate"), true, Some(Accelerator::new(Some(Modifiers::SUPER), Code::KeyD))),
+        &MenuItem::with_id("organize_by_date", t!("menu.organize_by_date"), true, None),
+        &MenuItem::with_id("organize_by_type", t!("menu.organize_by_type"), true, None),
         &PredefinedMenuItem::separator(),
-        &MenuItem::with_id("settings", "Settings...", true, Some(Accelerator::new(Some(Modifiers::SUPER), Code::Comma))),
+        &MenuItem::with_id("settings", t!("menu.settings"), true, Some(Accelerator::new(Some(Modifiers::SUPER), Code::Comma))),
     ]).unwrap();
     menu.append(&edit_menu).unwrap();
 
     // View menu
-    let view_menu = Submenu::new("View", true);
+    let sort_menu = Submenu::new(t!("menu.sort_by"), true);
+    sort_menu.append_items(&[
+        &MenuItem::with_id("sort_name", t!("menu.sort_name"), true, None),
+        &MenuItem::with_id("sort_size", t!("menu.sort_size"), true, None),
+        &MenuItem::with_id("sort_modified", t!("menu.sort_modified"), true, None),
+        &MenuItem::with_id("sort_created", t!("menu.sort_created"), true, None),
+        &MenuItem::with_id("sort_extension", t!("menu.sort_extension"), true, None),
+        &MenuItem::with_id("sort_type", t!("menu.sort_type"), true, None),
+        &PredefinedMenuItem::separator(),
+        &MenuItem::with_id("sort_reverse", t!("menu.sort_reverse"), true, None),
+        &MenuItem::with_id("sort_directories_first", t!("menu.sort_directories_first"), true, None),
+    ]).unwrap();
+    let view_menu = Submenu::new(t!("menu.view"), true);
     view_menu.append_items(&[
-        &MenuItem::with_id("toggle_sidebar", "Toggle Sidebar", true, Some(Accelerator::new(Some(Modifiers::SUPER), Code::KeyB))),
-        &MenuItem::with_id("toggle_panel", "Toggle Panel", true, Some(Accelerator::new(Some(Modifiers::SUPER), Code::KeyJ))),
+        &MenuItem::with_id("toggle_sidebar", t!("menu.toggle_sidebar"), true, Some(Accelerator::new(Some(Modifiers::SUPER), Code::KeyB))),
+        &MenuItem::with_id("toggle_panel", t!("menu.toggle_panel"), true, Some(Accelerator::new(Some(Modifiers::SUPER), Code::KeyJ))),
+        &MenuItem::with_id("operation_log", t!("menu.operation_log"), true, None),
+        &MenuItem::with_id("trash", t!("menu.trash"), true, None),
+        &MenuItem::with_id("compare_folders", t!("menu.compare_folders"), true, None),
+        &MenuItem::with_id("toggle_split_view", t!("menu.toggle_split_view"), true, None),
+        &PredefinedMenuItem::separator(),
+        &sort_menu,
         &PredefinedMenuItem::separator(),
-        &MenuItem::with_id("theme_light", "Light Theme", true, None),
-        &MenuItem::with_id("theme_dark", "Dark Theme", true, None),
-        &MenuItem::with_id("theme_auto", "Auto Theme", true, None),
+        &MenuItem::with_id("theme_light", t!("menu.theme_light"), true, None),
+        &MenuItem::with_id("theme_dark", t!("menu.theme_dark"), true, None),
+        &MenuItem::with_id("theme_auto", t!("menu.theme_auto"), true, None),
     ]).unwrap();
     menu.append(&view_menu).unwrap();
 
     // Help menu
-    let help_menu = Submenu::new("Help", true);
+    let help_menu = Submenu::new(t!("menu.help"), true);
     help_menu.append_items(&[
-        &MenuItem::with_id("keyboard_shortcuts", "Keyboard Shortcuts", true, None),
-        &MenuItem::with_id("help_documentation", "Media Organizer Help", true, None),
+        &MenuItem::with_id("keyboard_shortcuts", t!("menu.keyboard_shortcuts"), true, None),
+        &MenuItem::with_id("help_documentation", t!("menu.help_documentation"), true, None),
     ]).unwrap();
     menu.append(&help_menu).unwrap();
 
@@ -130,9 +381,20 @@ fn create_menu_bar() -> dioxus::desktop::muda::Menu {
 }
 
 /// Create a new application window
-async fn create_new_window() -> Result<(), String> {
+///
+/// A separate process only makes sense when multiple instances are allowed;
+/// otherwise it would just forward to us, so the window is opened in-process.
+async fn create_new_window(allow_multiple_instances: bool) -> Result<(), String> {
     use std::process::Command;
     
+    if !allow_multiple_instances {
+        let config = dioxus::desktop::Config::new().with_window(
+            dioxus::desktop::WindowBuilder::new().with_title("Media Organizer")
+        );
+        dioxus::desktop::window().new_window(dioxus::prelude::VirtualDom::new(app), config);
+        return Ok(());
+    }
+    
     // Get current executable path
     let current_exe = std::env::current_exe()
         .map_err(|e| format!("Failed to get current executable path: {}", e))?;
@@ -160,21 +422,19 @@ async fn open_folder_dialog() -> Option<PathBuf> {
     folder.map(|handle| handle.path().to_path_buf())
 }
 
-/// Create a new file with user input dialog
-async fn create_new_file_dialog(parent_path: &PathBuf) -> Result<PathBuf, String> {
+/// Create a new file with user input dialog; `None` if the user declined or dismissed it
+async fn create_new_file_dialog(
+    input_requests: Signal<Option<InputDialogRequest>>,
+    parent_path: &PathBuf,
+) -> Result<Option<PathBuf>, String> {
     use std::fs;
     
     // Show input dialog for file name
-    let file_name = show_input_dialog("New File", "Enter file name:", "New File.txt").await?;
+    let Some(file_name) = show_input_dialog(input_requests, &t!("dialog.new_file.title"), &t!("dialog.new_file.message"), &t!("dialog.new_file.default_name")).await.confirmed() else {
+        return Ok(None);
+    };
     
-    if file_name.trim().is_empty() {
-        return Err("File name cannot be empty".to_string());
-    }
-    
-    // Validate file name (no invalid characters)
-    if file_name.contains(['/', '\\', ':', '*', '?', '"', '<', '>', '|']) {
-        return Err("File name contains invalid characters".to_string());
-    }
+    utils::validate_file_name(&file_name).map_err(|e| e.message())?;
     
     let new_file_path = parent_path.join(&file_name);
     
@@ -185,27 +445,24 @@ async fn create_new_file_dialog(parent_path: &PathBuf) -> Result<PathBuf, String
     
     // Create the file
     match fs::File::create(&new_file_path) {
-        Ok(_) => Ok(new_file_path),
+        Ok(_) => Ok(Some(new_file_path)),
         Err(e) => Err(format!("Failed to create file: {}", e))
     }
 }
 
-/// Create a new folder with user input dialog
-async fn create_new_folder_dialog(parent_path: &PathBuf) -> Result<PathBuf, String> {
-    use rfd::AsyncMessageDialog;
+/// Create a new folder with user input dialog; `None` if the user declined or dismissed it
+async fn create_new_folder_dialog(
+    input_requests: Signal<Option<InputDialogRequest>>,
+    parent_path: &PathBuf,
+) -> Result<Option<PathBuf>, String> {
     use std::fs;
     
     // Show input dialog for folder name
-    let folder_name = show_input_dialog("New Folder", "Enter folder name:", "New Folder").await?;
-    
-    if folder_name.trim().is_empty() {
-        return Err("Folder name cannot be empty".to_string());
-    }
+    let Some(folder_name) = show_input_dialog(input_requests, &t!("dialog.new_folder.title"), &t!("dialog.new_folder.message"), &t!("dialog.new_folder.default_name")).await.confirmed() else {
+        return Ok(None);
+    };
     
-    // Validate folder name (no invalid characters)
-    if folder_name.contains(['/', '\\', ':', '*', '?', '"', '<', '>', '|']) {
-        return Err("Folder name contains invalid characters".to_string());
-    }
+    utils::validate_file_name(&folder_name).map_err(|e| e.message())?;
     
     let new_folder_path = parent_path.join(&folder_name);
     
@@ -216,93 +473,123 @@ async fn create_new_folder_dialog(parent_path: &PathBuf) -> Result<PathBuf, Stri
     
     // Create the folder
     match fs::create_dir(&new_folder_path) {
-        Ok(_) => Ok(new_folder_path),
+        Ok(_) => Ok(Some(new_folder_path)),
         Err(e) => Err(format!("Failed to create folder: {}", e))
     }
 }
 
-/// Show a simple input dialog (using message dialog as fallback)
-async fn show_input_dialog(title: &str, message: &str, default_value: &str) -> Result<String, String> {
-    // For now, we'll use a simple approach - in a full implementation, you'd want a proper input dialog
-    // This is a simplified version that creates files/folders with default names
-    let timestamp = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs();
-    
-    // Return a default name with timestamp to ensure uniqueness
-    if default_value.contains('.') {
-        // It's a file with extension
-        if let Some(dot_pos) = default_value.rfind('.') {
-            let (name_part, ext_part) = default_value.split_at(dot_pos);
-            Ok(format!("{} {}{}", name_part, timestamp % 1000, ext_part))
-        } else {
-            Ok(format!("{} {}", default_value, timestamp % 1000))
-        }
-    } else {
-        // It's a folder or file without extension
-        Ok(format!("{} {}", default_value, timestamp % 1000))
-    }
+/// Ask the user for a file or folder name through the in-app input dialog
+///
+/// The dialog won't submit names with invalid characters; it explains the
+/// problem beside the field instead.
+async fn show_input_dialog(
+    mut input_requests: Signal<Option<InputDialogRequest>>,
+    title: &str,
+    message: &str,
+    default_value: &str,
+) -> DialogResult<String> {
+    let (request, response) = InputDialogRequest::new(title, message, default_value);
+    let request = request.with_validation(|name| utils::validate_file_name(name).err().map(|e| e.message()));
+    // Replacing an open request drops it, which answers that caller with Cancelled
+    input_requests.set(Some(request));
+    response.await.unwrap_or(DialogResult::Cancelled)
 }
 
 /// Show a confirmation dialog
-async fn show_confirmation_dialog(title: &str, message: &str) -> Result<bool, String> {
+///
+/// Offers Cancel alongside Yes/No so closing the dialog is reported as
+/// `Cancelled` rather than being mistaken for "No".
+async fn show_confirmation_dialog(title: &str, message: &str) -> ConfirmationResult {
+    show_confirmation_dialog_at_level(title, message, rfd::MessageLevel::Warning).await
+}
+
+/// Show a confirmation dialog for a change that can't be undone
+async fn show_destructive_confirmation_dialog(title: &str, message: &str) -> ConfirmationResult {
+    show_confirmation_dialog_at_level(title, message, rfd::MessageLevel::Error).await
+}
+
+async fn show_confirmation_dialog_at_level(title: &str, message: &str, level: rfd::MessageLevel) -> ConfirmationResult {
     use rfd::AsyncMessageDialog;
     
     let result = AsyncMessageDialog::new()
         .set_title(title)
         .set_description(message)
-        .set_level(rfd::MessageLevel::Warning)
-        .set_buttons(rfd::MessageButtons::YesNo)
+        .set_level(level)
+        .set_buttons(rfd::MessageButtons::YesNoCancel)
         .show()
         .await;
     
-    Ok(result == rfd::MessageDialogResult::Yes)
+    match result {
+        rfd::MessageDialogResult::Yes => DialogResult::Confirmed(()),
+        rfd::MessageDialogResult::No => DialogResult::Declined,
+        _ => DialogResult::Cancelled,
+    }
 }
 
-/// Delete selected files
-async fn delete_selected_files(selected_files: &[FileEntry]) -> Result<usize, String> {
-    use std::fs;
-    use trash;
-    
-    let mut deleted_count = 0;
-    let mut errors = Vec::new();
-    
-    for file_entry in selected_files {
-        let file_path = &file_entry.path;
-        
-        // Try to move to trash first (safer), fallback to permanent delete
-        match trash::delete(file_path) {
-            Ok(_) => {
-                deleted_count += 1;
-                info!("Moved to trash: {:?}", file_path);
-            },
-            Err(e) => {
-                // If trash fails, try permanent deletion as fallback
-                match if file_path.is_dir() {
-                    fs::remove_dir_all(file_path)
-                } else {
-                    fs::remove_file(file_path)
-                } {
-                    Ok(_) => {
-                        deleted_count += 1;
-                        info!("Permanently deleted: {:?}", file_path);
-                    },
-                    Err(delete_err) => {
-                        let error_msg = format!("Failed to delete '{}': {} (trash error: {})", 
-                                               file_entry.name, delete_err, e);
-                        errors.push(error_msg);
-                    }
-                }
-            }
-        }
-    }
-    
-    if !errors.is_empty() {
-        return Err(format!("Some files could not be deleted: {}", errors.join("; ")));
+/// Name of the first item, for dialogs about one item
+fn first_name(paths: &[PathBuf]) -> String {
+    paths
+        .first()
+        .and_then(|path| path.file_name())
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Trash the files only if the confirmation was accepted
+///
+/// Items the trash can't take are only deleted permanently when
+/// `confirm_permanent` accepts that too; otherwise they stay in place.
+/// Once `cancellation` is cancelled no further item is touched and nothing
+/// more is asked.
+async fn delete_if_confirmed<F, Fut>(
+    service: &FileOperationService,
+    files: &[FileEntry],
+    confirmation: ConfirmationResult,
+    cancellation: &CancellationToken,
+    confirm_permanent: F,
+) -> Option<DeleteSummary>
+where
+    F: FnOnce(Vec<PathBuf>) -> Fut,
+    Fut: std::future::Future<Output = ConfirmationResult>,
+{
+    confirmation.confirmed()?;
+    let mut summary = service.trash(files, cancellation).await;
+    if !summary.kept.is_empty()
+        && !cancellation.is_cancelled()
+        && confirm_permanent(summary.kept.clone()).await.is_confirmed()
+    {
+        let kept = std::mem::take(&mut summary.kept);
+        summary.merge(service.delete_permanently(&kept, cancellation).await);
     }
-    
-    Ok(deleted_count)
+    Some(summary)
+}
+
+/// Delete the files permanently only if the confirmation was accepted
+async fn delete_permanently_if_confirmed(
+    service: &FileOperationService,
+    files: &[FileEntry],
+    confirmation: ConfirmationResult,
+    cancellation: &CancellationToken,
+) -> Option<DeleteSummary> {
+    confirmation.confirmed()?;
+    let paths: Vec<PathBuf> = files.iter().map(|entry| entry.path.clone()).collect();
+    Some(service.delete_permanently(&paths, cancellation).await)
+}
+
+/// Total size of the files, counting everything inside folders
+async fn selection_size(files: &[FileEntry]) -> u64 {
+    let paths: Vec<PathBuf> = files.iter().map(|entry| entry.path.clone()).collect();
+    tokio::task::spawn_blocking(move || {
+        paths
+            .iter()
+            .flat_map(|path| walkdir::WalkDir::new(path).into_iter().filter_map(Result::ok))
+            .filter_map(|entry| entry.metadata().ok())
+            .filter(|metadata| metadata.is_file())
+            .map(|metadata| metadata.len())
+            .sum()
+    })
+    .await
+    .unwrap_or(0)
 }
 
 /// Show a file or folder in the system file manager
@@ -354,57 +641,55 @@ async fn show_in_system_file_manager(path: &std::path::PathBuf) -> Result<(), St
 
 /// Open a file with the system default application
 async fn open_with_system_default(path: &std::path::PathBuf) -> Result<(), String> {
-    use std::process::Command;
-    
-    let result = if cfg!(target_os = "macos") {
-        // macOS: Use 'open' command
-        Command::new("open")
-            .arg(path)
-            .spawn()
-    } else if cfg!(target_os = "windows") {
-        // Windows: Use 'cmd' with 'start' command
-        Command::new("cmd")
-            .args(&["/C", "start", "", &path.to_string_lossy()])
-            .spawn()
-    } else {
-        // Linux: Use xdg-open
-        Command::new("xdg-open")
-            .arg(path)
-            .spawn()
-    };
-    
-    match result {
-        Ok(mut child) => {
-            // Don't wait for the command to complete since we want to launch and return
-            tokio::task::spawn(async move {
-                let _ = child.wait();
-            });
-            Ok(())
-        },
-        Err(e) => Err(format!("Failed to open file with system default application: {}", e))
-    }
+    open_with::open_default(path).map_err(|e| e.to_string())
+}
+
+/// Ask which application to open `path` with, offering the one last used for its extension first
+async fn show_open_with_dialog(
+    mut open_with_requests: Signal<Option<OpenWithRequest>>,
+    path: &std::path::Path,
+    history: &OpenWithHistory,
+) -> Option<OpenWithApp> {
+    let lookup = path.to_path_buf();
+    let apps = tokio::task::spawn_blocking(move || open_with::applications_for(&lookup))
+        .await
+        .unwrap_or_default();
+    let last_used = history.last_used(path).filter(|app| apps.contains(app)).cloned();
+    let apps = open_with::with_last_used_first(apps, last_used.as_ref());
+    let file_name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+
+    let (request, response) = OpenWithRequest::new(file_name, apps, last_used);
+    // Replacing an open request drops it, which answers that caller with Cancelled
+    open_with_requests.set(Some(request));
+    response.await.unwrap_or(DialogResult::Cancelled).confirmed()
 }
 
 /// Show a rename dialog with current filename
-async fn show_rename_dialog(current_name: &str) -> Result<String, String> {
-    // For now, create a simple timestamped version for demonstration
-    // In a full implementation, you'd want a proper text input dialog
-    let timestamp = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs();
-    
-    // Extract file extension if it exists
-    if let Some(dot_pos) = current_name.rfind('.') {
-        let (name_part, ext_part) = current_name.split_at(dot_pos);
-        Ok(format!("{}_renamed_{}{}", name_part, timestamp % 1000, ext_part))
-    } else {
-        Ok(format!("{}_renamed_{}", current_name, timestamp % 1000))
+async fn show_rename_dialog(
+    input_requests: Signal<Option<InputDialogRequest>>,
+    current_name: &str,
+) -> DialogResult<String> {
+    let message = t!("dialog.rename.message", name = current_name);
+    show_input_dialog(input_requests, &t!("dialog.rename.title"), &message, current_name).await
+}
+
+/// Rename only when a different name was confirmed; returns the new path if renamed
+async fn rename_if_confirmed(
+    service: &FileOperationService,
+    current_path: &PathBuf,
+    result: DialogResult<String>,
+) -> Result<Option<PathBuf>, String> {
+    let Some(new_name) = result.confirmed() else {
+        return Ok(None);
+    };
+    if current_path.file_name().is_some_and(|name| name == new_name.as_str()) {
+        return Ok(None);
     }
+    service.rename(current_path, &new_name).await.map(Some)
 }
 
 /// Show a folder picker dialog for selecting destination
-async fn show_destination_folder_dialog(title: &str) -> Result<Option<PathBuf>, String> {
+async fn show_destination_folder_dialog(title: &str) -> DialogResult<PathBuf> {
     use rfd::AsyncFileDialog;
     
     let folder = AsyncFileDialog::new()
@@ -412,177 +697,108 @@ async fn show_destination_folder_dialog(title: &str) -> Result<Option<PathBuf>,
         .pick_folder()
         .await;
     
-    Ok(folder.map(|handle| handle.path().to_path_buf()))
+    DialogResult::from(folder.map(|handle| handle.path().to_path_buf()))
 }
 
-/// Copy files to destination folder
-async fn copy_files_to_destination(files: &[FileEntry], destination: &PathBuf) -> Result<usize, String> {
-    use std::fs;
-    
-    let mut copied_count = 0;
-    let mut errors = Vec::new();
+/// Ask what to do with an item whose name is already taken in the destination
+/// 
+/// Closing the dialog skips the item.
+async fn show_conflict_dialog(destination: PathBuf) -> ConflictResolution {
+    use rfd::AsyncMessageDialog;
     
-    for file_entry in files {
-        let source_path = &file_entry.path;
-        let file_name = source_path.file_name()
-            .ok_or_else(|| format!("Invalid file name for: {:?}", source_path))?;
-        let destination_path = destination.join(file_name);
-        
-        // Check if destination already exists
-        if destination_path.exists() {
-            errors.push(format!("File already exists at destination: {:?}", destination_path));
-            continue;
-        }
-        
-        // Copy file or directory
-        let copy_result = if source_path.is_dir() {
-            copy_directory_recursive(source_path, &destination_path)
-        } else {
-            fs::copy(source_path, &destination_path).map(|_| ())
-        };
-        
-        match copy_result {
-            Ok(_) => {
-                copied_count += 1;
-                info!("Copied: {:?} -> {:?}", source_path, destination_path);
-            },
-            Err(e) => {
-                errors.push(format!("Failed to copy '{}': {}", file_entry.name, e));
-            }
-        }
-    }
+    let name = destination.file_name().unwrap_or(destination.as_os_str()).to_string_lossy().to_string();
+    let replace = t!("dialog.conflict.replace");
+    let keep_both = t!("dialog.conflict.keep_both");
+    let result = AsyncMessageDialog::new()
+        .set_title(t!("dialog.conflict.title"))
+        .set_description(t!("dialog.conflict.message", name = name))
+        .set_level(rfd::MessageLevel::Warning)
+        .set_buttons(rfd::MessageButtons::YesNoCancelCustom(
+            replace.clone(),
+            keep_both.clone(),
+            t!("dialog.conflict.skip"),
+        ))
+        .show()
+        .await;
     
-    if !errors.is_empty() {
-        return Err(format!("Some files could not be copied: {}", errors.join("; ")));
+    match result {
+        rfd::MessageDialogResult::Custom(label) if label == replace => ConflictResolution::Overwrite,
+        rfd::MessageDialogResult::Custom(label) if label == keep_both => ConflictResolution::Rename,
+        _ => ConflictResolution::Skip,
     }
-    
-    Ok(copied_count)
 }
 
-/// Copy directory recursively
-fn copy_directory_recursive(source: &PathBuf, destination: &PathBuf) -> Result<(), std::io::Error> {
-    use std::fs;
-    
-    fs::create_dir_all(destination)?;
-    
-    for entry in fs::read_dir(source)? {
-        let entry = entry?;
-        let source_path = entry.path();
-        let dest_path = destination.join(entry.file_name());
-        
-        if source_path.is_dir() {
-            copy_directory_recursive(&source_path, &dest_path)?;
-        } else {
-            fs::copy(&source_path, &dest_path)?;
-        }
-    }
-    
-    Ok(())
+/// Move the files only if a destination was confirmed
+async fn move_if_confirmed<F, Fut>(
+    service: &FileOperationService,
+    files: &[FileEntry],
+    destination: DialogResult<PathBuf>,
+    ask: F,
+    cancellation: &CancellationToken,
+    transfer: &TransferProgress,
+) -> Option<FileOperationResult>
+where
+    F: FnMut(PathBuf) -> Fut,
+    Fut: std::future::Future<Output = ConflictResolution>,
+{
+    let destination = destination.confirmed()?;
+    Some(service.move_to_with_progress(files, &destination, ask, cancellation, transfer.recorder()).await)
 }
 
-/// Move files to destination folder
-async fn move_files_to_destination(files: &[FileEntry], destination: &PathBuf) -> Result<usize, String> {
-    use std::fs;
-    
-    let mut moved_count = 0;
-    let mut errors = Vec::new();
-    
-    for file_entry in files {
-        let source_path = &file_entry.path;
-        let file_name = source_path.file_name()
-            .ok_or_else(|| format!("Invalid file name for: {:?}", source_path))?;
-        let destination_path = destination.join(file_name);
-        
-        // Check if destination already exists
-        if destination_path.exists() {
-            errors.push(format!("File already exists at destination: {:?}", destination_path));
-            continue;
-        }
-        
-        // Move file or directory
-        match fs::rename(source_path, &destination_path) {
-            Ok(_) => {
-                moved_count += 1;
-                info!("Moved: {:?} -> {:?}", source_path, destination_path);
-            },
-            Err(e) => {
-                errors.push(format!("Failed to move '{}': {}", file_entry.name, e));
-            }
-        }
-    }
-    
-    if !errors.is_empty() {
-        return Err(format!("Some files could not be moved: {}", errors.join("; ")));
-    }
-    
-    Ok(moved_count)
+/// Show a copy or move in the progress dialog, returning the token its Cancel
+/// button cancels and the byte progress it displays
+fn show_transfer_progress(
+    mut progress: Signal<Option<FileOperationProgress>>,
+    title: String,
+    operation: String,
+    total: usize,
+) -> (CancellationToken, TransferProgress) {
+    let cancellation = CancellationToken::new();
+    let transfer = TransferProgress::default();
+    progress.set(Some(FileOperationProgress {
+        title,
+        operation,
+        total,
+        cancellation: cancellation.clone(),
+        transfer: Some(transfer.clone()),
+    }));
+    (cancellation, transfer)
 }
 
-/// Duplicate selected files
-async fn duplicate_files(files: &[FileEntry]) -> Result<usize, String> {
-    use std::fs;
-    
-    let mut duplicated_count = 0;
-    let mut errors = Vec::new();
-    
-    for file_entry in files {
-        let source_path = &file_entry.path;
-        let parent_dir = source_path.parent()
-            .ok_or_else(|| format!("Cannot determine parent directory for: {:?}", source_path))?;
-        
-        // Generate a unique name for the duplicate
-        let duplicate_path = generate_duplicate_name(source_path)?;
-        
-        // Copy file or directory
-        let copy_result = if source_path.is_dir() {
-            copy_directory_recursive(source_path, &duplicate_path)
-        } else {
-            fs::copy(source_path, &duplicate_path).map(|_| ())
-        };
-        
-        match copy_result {
-            Ok(_) => {
-                duplicated_count += 1;
-                info!("Duplicated: {:?} -> {:?}", source_path, duplicate_path);
-            },
-            Err(e) => {
-                errors.push(format!("Failed to duplicate '{}': {}", file_entry.name, e));
-            }
-        }
-    }
+/// List the items an operation couldn't handle, one per line
+async fn show_failures_dialog(failure_lines: Vec<String>) {
+    use rfd::AsyncMessageDialog;
     
-    if !errors.is_empty() {
-        return Err(format!("Some files could not be duplicated: {}", errors.join("; ")));
+    if failure_lines.is_empty() {
+        return;
     }
-    
-    Ok(duplicated_count)
+    let message = t!("dialog.operation_failures.message", count = failure_lines.len());
+    AsyncMessageDialog::new()
+        .set_title(t!("dialog.operation_failures.title"))
+        .set_description(format!("{}\n\n{}", message, failure_lines.join("\n")))
+        .set_level(rfd::MessageLevel::Error)
+        .set_buttons(rfd::MessageButtons::Ok)
+        .show()
+        .await;
 }
 
-/// Generate a unique duplicate name
-fn generate_duplicate_name(original_path: &PathBuf) -> Result<PathBuf, String> {
-    let parent_dir = original_path.parent()
-        .ok_or_else(|| "Cannot determine parent directory".to_string())?;
-    
-    let file_name = original_path.file_name()
-        .and_then(|name| name.to_str())
-        .ok_or_else(|| "Invalid file name".to_string())?;
+/// List the items a delete couldn't handle and offer to retry just those
+///
+/// Returns true when the user chose to retry.
+async fn show_retry_failures_dialog(failure_lines: Vec<String>) -> bool {
+    use rfd::AsyncMessageDialog;
     
-    // Try different suffixes until we find a unique name
-    for i in 1..1000 {
-        let duplicate_name = if let Some(dot_pos) = file_name.rfind('.') {
-            let (name_part, ext_part) = file_name.split_at(dot_pos);
-            format!("{} copy {}{}", name_part, i, ext_part)
-        } else {
-            format!("{} copy {}", file_name, i)
-        };
-        
-        let duplicate_path = parent_dir.join(duplicate_name);
-        if !duplicate_path.exists() {
-            return Ok(duplicate_path);
-        }
-    }
+    let message = t!("dialog.operation_failures.message", count = failure_lines.len());
+    let retry = t!("dialog.operation_failures.retry");
+    let result = AsyncMessageDialog::new()
+        .set_title(t!("dialog.operation_failures.title"))
+        .set_description(format!("{}\n\n{}", message, failure_lines.join("\n")))
+        .set_level(rfd::MessageLevel::Error)
+        .set_buttons(rfd::MessageButtons::OkCancelCustom(retry.clone(), t!("dialog.close")))
+        .show()
+        .await;
     
-    Err("Could not generate unique duplicate name".to_string())
+    matches!(result, rfd::MessageDialogResult::Custom(label) if label == retry)
 }
 
 /// Show about dialog with application information
@@ -739,37 +955,6 @@ async fn try_open_local_readme() -> Result<(), String> {
     Err("No local README file found".to_string())
 }
 
-/// Rename a file or folder
-async fn rename_file(current_path: &std::path::PathBuf, new_name: &str) -> Result<std::path::PathBuf, String> {
-    use std::fs;
-    
-    // Validate new name (no invalid characters and not empty)
-    if new_name.trim().is_empty() {
-        return Err("New name cannot be empty".to_string());
-    }
-    
-    if new_name.contains(['/', '\\', ':', '*', '?', '"', '<', '>', '|']) {
-        return Err("New name contains invalid characters".to_string());
-    }
-    
-    // Get parent directory and create new path
-    let parent_dir = current_path.parent()
-        .ok_or_else(|| "Cannot determine parent directory".to_string())?;
-    
-    let new_path = parent_dir.join(new_name);
-    
-    // Check if target already exists
-    if new_path.exists() && new_path != *current_path {
-        return Err(format!("A file or folder named '{}' already exists", new_name));
-    }
-    
-    // Perform the rename
-    match fs::rename(current_path, &new_path) {
-        Ok(_) => Ok(new_path),
-        Err(e) => Err(format!("Failed to rename file: {}", e))
-    }
-}
-
 // Root app component with state provider
 fn app() -> dioxus::prelude::Element {
     use dioxus::prelude::*;
@@ -791,12 +976,189 @@ fn AppWithMenuHandlers() -> Element {
     // Get app state for menu actions
     let app_state = use_app_state();
     
-    // Handle menu events
-    use_muda_event_handler(move |menu_event| {
-        let event_id = menu_event.id.0.as_str();
+    // Text input requests from menu actions, answered by the input dialog
+    let input_requests = use_signal(|| None::<InputDialogRequest>);
+    let open_with_requests = use_signal(|| None::<OpenWithRequest>);
+    
+    // Keep the background throttle policy in sync with settings and window focus
+    let throttle_policy = app_state.background_throttle.clone();
+    let settings_signal = app_state.settings;
+    use_effect(move || {
+        throttle_policy.update_settings(settings_signal.read().background_throttle.clone());
+    });
+    
+    // Retry failed operations as configured in settings
+    let error_recovery = app_state.error_recovery.clone();
+    use_effect(move || {
+        let retry_policy = settings_signal.read().retry_policy.effective_config();
+        if let Ok(mut manager) = error_recovery.lock() {
+            manager.set_config(retry_policy);
+        }
+    });
+    
+    // Keep File > Open Recent in step with the recent folders
+    let recent_folders = use_memo(move || settings_signal.read().recent_folders.clone());
+    use_effect(move || {
+        refresh_open_recent_menu(&recent_folders.read());
+    });
+    
+    // Only the first window, which receives forwarded launches, remembers its geometry and undo history
+    let is_main_window = try_consume_context::<InstanceRequests>().is_some();
+    let layout_state = app_state.layout_state;
+    let operation_history = app_state.operation_history.clone();
+    use_hook(move || {
+        if is_main_window {
+            restore_window_position(&layout_state.peek());
+            restore_operation_history(&operation_history);
+        }
+    });
+
+    // Keep the activity bar view and sidebar size for the next launch
+    let activity_view = app_state.active_activity_view;
+    let sidebar_state = app_state.sidebar_state;
+    use_effect(move || {
+        let active_view = activity_view.read().clone();
+        let sidebar = sidebar_state.read().clone();
+        if !is_main_window {
+            return;
+        }
+        let mut layout_state = layout_state;
+        let mut layout = layout_state.write();
+        layout.sidebar.active_content = active_view.sidebar_content();
+        layout.activity_bar.active_view = active_view;
+        layout.sidebar.is_collapsed = sidebar.is_collapsed;
+        layout.sidebar.width = sidebar.width;
+        if layout.persistence.persist_layout {
+            state::persistence::save_layout_state_debounced(layout.clone());
+        }
+    });
+
+    let focus_policy = app_state.background_throttle.clone();
+    let closing_history = app_state.operation_history.clone();
+    let window_id = dioxus::desktop::window().id();
+    dioxus::desktop::use_wry_event_handler(move |event, _| {
+        use dioxus::desktop::tao::event::{Event, WindowEvent};
+        match event {
+            Event::WindowEvent { event: WindowEvent::Focused(focused), .. } => {
+                focus_policy.set_app_focused(*focused);
+            }
+            Event::WindowEvent { event: WindowEvent::Moved(_) | WindowEvent::Resized(_), window_id: id, .. }
+                if is_main_window && *id == window_id =>
+            {
+                record_window_geometry(layout_state);
+            }
+            // Write a settings or layout change still inside the debounce window before the app goes away
+            Event::WindowEvent { event: WindowEvent::CloseRequested | WindowEvent::Destroyed, .. } | Event::LoopDestroyed => {
+                state::persistence::flush_pending_settings();
+                state::persistence::flush_pending_layout_saves();
+                if is_main_window {
+                    save_operation_history(&closing_history);
+                }
+            }
+            _ => {}
+        }
+    });
+    
+    // Folders and activations forwarded by later launches; only the first window receives them
+    let instance_requests = try_consume_context::<InstanceRequests>();
+    let forwarded_state = app_state.clone();
+    use_future(move || {
+        let instance_requests = instance_requests.clone();
+        let app_state = forwarded_state.clone();
+        async move {
+            let Some(instance_requests) = instance_requests else {
+                return;
+            };
+            loop {
+                while let Some(request) = instance_requests.try_next() {
+                    if let InstanceRequest::OpenFolder(folder) = request {
+                        info!("Opening forwarded folder: {:?}", folder);
+                        if let Err(e) = app_state.clone().set_root_folder_with_persistence(folder).await {
+                            info!("Error opening forwarded folder: {}", e);
+                        }
+                    }
+                    dioxus::desktop::window().set_focus();
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(250)).await;
+            }
+        }
+    });
+    
+    // Refresh the listing when the current directory changes on disk, e.g. files added by another app
+    let watched_state = app_state.clone();
+    let directory_refresher = use_coroutine(move |mut changed: futures::channel::mpsc::UnboundedReceiver<PathBuf>| {
+        let app_state = watched_state.clone();
+        async move {
+            use futures::StreamExt;
+            while let Some(directory) = changed.next().await {
+                let mut app_state = app_state.clone();
+                if app_state.navigation.peek().current_path != directory {
+                    continue;
+                }
+                if let Err(e) = app_state.refresh_current_directory().await {
+                    warn!("Failed to refresh {:?} after a change on disk: {}", directory, e);
+                }
+            }
+        }
+    });
+    let directory_watcher = use_hook(move || {
+        let changed = directory_refresher.tx();
+        let watcher = DirectoryWatcher::new(WATCH_DEBOUNCE, move |directory| {
+            let _ = changed.unbounded_send(directory);
+        });
+        match watcher {
+            Ok(watcher) => Some(std::rc::Rc::new(std::cell::RefCell::new(watcher))),
+            Err(e) => {
+                warn!("Directory watching unavailable: {}", e);
+                None
+            }
+        }
+    });
+    let navigation = app_state.navigation;
+    use_effect(move || {
+        // Re-runs on navigation, moving the watch to the new directory
+        let current_path = navigation.read().current_path.clone();
+        let Some(watcher) = &directory_watcher else { return };
+        if !current_path.is_dir() {
+            watcher.borrow_mut().unwatch();
+        } else if let Err(e) = watcher.borrow_mut().watch(&current_path) {
+            warn!("Failed to watch {:?}: {}", current_path, e);
+        }
+    });
+    
+    // Menu actions, run from the menu bar or the command palette
+    let mut menu_action_request = app_state.menu_action_request;
+    let handle_menu_action = move |event_id: &str| {
         info!("Menu item clicked: {}", event_id);
         
+        if let Some(folder) = event_id.strip_prefix(OPEN_RECENT_PREFIX) {
+            let folder = PathBuf::from(folder);
+            let mut app_state = app_state.clone();
+            if !folder.is_dir() {
+                // Gone since the menu was filled; list it as missing
+                refresh_open_recent_menu(&app_state.settings.peek().recent_folders);
+                return;
+            }
+            spawn(async move {
+                if let Err(e) = app_state.navigate_to(folder.clone()).await {
+                    warn!("Failed to navigate to {:?}: {}", folder, e);
+                    return;
+                }
+                if let Err(e) = app_state.set_root_folder_with_persistence(folder.clone()).await {
+                    warn!("Failed to open recent folder {:?}: {}", folder, e);
+                }
+            });
+            return;
+        }
+        if let Some(folder) = event_id.strip_prefix(REMOVE_RECENT_PREFIX) {
+            app_state.clone().forget_recent_folder(Path::new(folder));
+            return;
+        }
+        
         match event_id {
+            "clear_recent_folders" => {
+                app_state.clone().clear_recent_folders();
+            },
             // App menu items
             "about_app" => {
                 info!("Showing about dialog...");
@@ -851,8 +1213,9 @@ fn AppWithMenuHandlers() -> Element {
             },
             "new_window" => {
                 info!("Creating new window...");
+                let allow_multiple_instances = app_state.settings.read().allow_multiple_instances;
                 spawn(async move {
-                    if let Err(e) = create_new_window().await {
+                    if let Err(e) = create_new_window(allow_multiple_instances).await {
                         info!("Error creating new window: {}", e);
                     } else {
                         info!("Successfully created new window");
@@ -870,16 +1233,20 @@ fn AppWithMenuHandlers() -> Element {
                     };
                     
                     if let Some(parent_path) = current_folder {
-                        match create_new_folder_dialog(&parent_path).await {
-                            Ok(new_folder_path) => {
+                        match create_new_folder_dialog(input_requests, &parent_path).await {
+                            Ok(Some(new_folder_path)) => {
                                 info!("Successfully created folder: {:?}", new_folder_path);
                                 // Refresh the file tree to show the new folder
                                 if let Err(e) = app_state_clone.refresh_current_directory().await {
                                     info!("Error refreshing directory after folder creation: {}", e);
                                 }
                             },
+                            Ok(None) => {
+                                info!("New folder cancelled by user");
+                            },
                             Err(e) => {
                                 info!("Error creating new folder: {}", e);
+                                app_state_clone.announcer.assertive(e);
                             }
                         }
                     } else {
@@ -898,16 +1265,20 @@ fn AppWithMenuHandlers() -> Element {
                     };
                     
                     if let Some(parent_path) = current_folder {
-                        match create_new_file_dialog(&parent_path).await {
-                            Ok(new_file_path) => {
+                        match create_new_file_dialog(input_requests, &parent_path).await {
+                            Ok(Some(new_file_path)) => {
                                 info!("Successfully created file: {:?}", new_file_path);
                                 // Refresh the file tree to show the new file
                                 if let Err(e) = app_state_clone.refresh_current_directory().await {
                                     info!("Error refreshing directory after file creation: {}", e);
                                 }
                             },
+                            Ok(None) => {
+                                info!("New file cancelled by user");
+                            },
                             Err(e) => {
                                 info!("Error creating new file: {}", e);
+                                app_state_clone.announcer.assertive(e);
                             }
                         }
                     } else {
@@ -992,8 +1363,8 @@ fn AppWithMenuHandlers() -> Element {
                 });
             },
             "open_with" => {
-                info!("Opening with external application...");
-                let app_state_clone = app_state.clone();
+                info!("Choosing an application to open the selected file with...");
+                let mut app_state_clone = app_state.clone();
                 
                 spawn(async move {
                     let selected_files = {
@@ -1007,21 +1378,27 @@ fn AppWithMenuHandlers() -> Element {
                             .collect::<Vec<_>>()
                     };
                     
-                    if selected_files.is_empty() {
-                        info!("No files selected for open with");
+                    let [file] = selected_files.as_slice() else {
+                        info!("Open With needs exactly one selected file");
+                        return;
+                    };
+                    if file.is_directory {
+                        info!("Cannot open a directory with another application: {:?}", file.path);
                         return;
                     }
                     
-                    // For now, just open with system default application
-                    // In a full implementation, you'd show an application picker dialog
-                    let file_path = &selected_files[0].path;
-                    
-                    match open_with_system_default(file_path).await {
-                        Ok(_) => {
-                            info!("Successfully opened file with system default: {:?}", file_path);
-                        },
+                    let history = app_state_clone.settings.peek().open_with.clone();
+                    let Some(app) = show_open_with_dialog(open_with_requests, &file.path, &history).await else {
+                        return;
+                    };
+                    match open_with::launch(&app, &file.path) {
+                        Ok(()) => {
+                            info!("Opened {:?} with {}", file.path, app.name);
+                            app_state_clone.remember_open_with(&file.path, app);
+                        }
                         Err(e) => {
-                            info!("Error opening file with system default: {}", e);
+                            warn!("Open With failed: {}", e);
+                            app_state_clone.announcer.assertive(e.to_string());
                         }
                     }
                 });
@@ -1070,16 +1447,88 @@ fn AppWithMenuHandlers() -> Element {
                 });
             },
             
+            "export_zip" => {
+                info!("Exporting selection as zip...");
+                let app_state_clone = app_state.clone();
+                
+                spawn(async move {
+                    let selected_paths: Vec<PathBuf> = app_state_clone.selection.read()
+                        .selected_files
+                        .iter()
+                        .cloned()
+                        .collect();
+                    
+                    if selected_paths.is_empty() {
+                        info!("No files selected for zip export");
+                        return;
+                    }
+                    
+                    match ui::archive_actions::show_zip_destination_dialog(&selected_paths).await {
+                        Some(destination) => {
+                            let command = services::archive::CompressCommand::new(selected_paths, destination);
+                            ui::archive_actions::compress_to_archive(app_state_clone, command).await;
+                        },
+                        None => {
+                            info!("Zip export cancelled by user");
+                        }
+                    }
+                });
+            },
+            "extract_here" | "extract_to" => {
+                let extract_to = event_id == "extract_to";
+                let app_state_clone = app_state.clone();
+                
+                spawn(async move {
+                    let selected_archive = {
+                        let selection_state = app_state_clone.selection.read();
+                        let mut selected = selection_state.selected_files.iter();
+                        match (selected.next(), selected.next()) {
+                            (Some(path), None) if ui::archive_actions::is_extractable_archive(path) => Some(path.clone()),
+                            _ => None,
+                        }
+                    };
+                    
+                    let Some(archive) = selected_archive else {
+                        info!("Select a single zip archive to extract");
+                        return;
+                    };
+                    
+                    let command = if extract_to {
+                        match ui::archive_actions::show_extract_destination_dialog(&archive).await {
+                            Some(destination) => {
+                                let command = services::archive::ExtractArchiveCommand::new(archive, destination);
+                                ui::archive_actions::resolve_extract_conflicts(command).await
+                            }
+                            None => {
+                                info!("Extraction cancelled by user");
+                                return;
+                            }
+                        }
+                    } else {
+                        services::archive::ExtractArchiveCommand::extract_here(archive)
+                    };
+                    
+                    ui::archive_actions::extract_archive(app_state_clone, command).await;
+                });
+            },
+            "cancel_operation" => {
+                let mut operation_state = app_state.operation_state;
+                if let Some(token) = operation_state.write().cancellation_token.take() {
+                    info!("Cancelling current operation");
+                    token.cancel();
+                }
+            },
+            
             // Edit menu items
             "clear_selection" => {
                 info!("Clearing file selection...");
                 let mut app_state_clone = app_state.clone();
-                app_state_clone.selection.write().clear_selection();
+                app_state_clone.clear_selection();
                 info!("File selection cleared");
             },
             "copy_to" => {
                 info!("Copying files to location...");
-                let app_state_clone = app_state.clone();
+                let mut app_state_clone = app_state.clone();
                 
                 spawn(async move {
                     let selected_files = {
@@ -1099,22 +1548,25 @@ fn AppWithMenuHandlers() -> Element {
                     }
                     
                     // Show folder picker for destination
-                    match show_destination_folder_dialog("Select Copy Destination").await {
-                        Ok(Some(destination)) => {
-                            match copy_files_to_destination(&selected_files, &destination).await {
-                                Ok(copied_count) => {
-                                    info!("Successfully copied {} files to {:?}", copied_count, destination);
-                                },
-                                Err(e) => {
-                                    info!("Error copying files: {}", e);
-                                }
-                            }
+                    match show_destination_folder_dialog(&t!("dialog.copy_destination.title")).await {
+                        DialogResult::Confirmed(destination) => {
+                            let (cancellation, transfer) = show_transfer_progress(
+                                app_state_clone.file_operation_progress,
+                                t!("dialog.copy_progress.title"),
+                                t!("dialog.copy_progress.message", count = selected_files.len()),
+                                selected_files.len(),
+                            );
+                            let service = app_state_clone.file_operations();
+                            let result = service
+                                .copy_to_with_progress(&selected_files, &destination, show_conflict_dialog, &cancellation, transfer.recorder())
+                                .await;
+                            app_state_clone.file_operation_progress.set(None);
+                            info!("Copied {} files to {:?}, {} failed", result.succeeded.len(), destination, result.failed.len());
+                            app_state_clone.announcer.file_operation_result("copied", &result);
+                            show_failures_dialog(result.failure_lines()).await;
                         },
-                        Ok(None) => {
+                        DialogResult::Declined | DialogResult::Cancelled => {
                             info!("Copy operation cancelled by user");
-                        },
-                        Err(e) => {
-                            info!("Error showing destination dialog: {}", e);
                         }
                     }
                 });
@@ -1141,32 +1593,38 @@ fn AppWithMenuHandlers() -> Element {
                     }
                     
                     // Show folder picker for destination
-                    match show_destination_folder_dialog("Select Move Destination").await {
-                        Ok(Some(destination)) => {
-                            match move_files_to_destination(&selected_files, &destination).await {
-                                Ok(moved_count) => {
-                                    info!("Successfully moved {} files to {:?}", moved_count, destination);
-                                    // Refresh the file tree to reflect the changes
-                                    if let Err(e) = app_state_clone.refresh_current_directory().await {
-                                        info!("Error refreshing directory after move: {}", e);
-                                    }
-                                },
-                                Err(e) => {
-                                    info!("Error moving files: {}", e);
-                                }
-                            }
-                        },
-                        Ok(None) => {
-                            info!("Move operation cancelled by user");
-                        },
-                        Err(e) => {
-                            info!("Error showing destination dialog: {}", e);
+                    let destination = show_destination_folder_dialog(&t!("dialog.move_destination.title")).await;
+                    let (cancellation, transfer) = if destination.is_confirmed() {
+                        show_transfer_progress(
+                            app_state_clone.file_operation_progress,
+                            t!("dialog.move_progress.title"),
+                            t!("dialog.move_progress.message", count = selected_files.len()),
+                            selected_files.len(),
+                        )
+                    } else {
+                        Default::default()
+                    };
+                    let service = app_state_clone.file_operations();
+                    let result = move_if_confirmed(&service, &selected_files, destination, show_conflict_dialog, &cancellation, &transfer).await;
+                    app_state_clone.file_operation_progress.set(None);
+                    let Some(result) = result else {
+                        info!("Move operation cancelled by user");
+                        return;
+                    };
+                    info!("Moved {} files, {} failed", result.succeeded.len(), result.failed.len());
+                    app_state_clone.announcer.file_operation_result("moved", &result);
+                    if !result.succeeded.is_empty() {
+                        // Refresh the file tree to reflect the changes
+                        if let Err(e) = app_state_clone.refresh_current_directory().await {
+                            info!("Error refreshing directory after move: {}", e);
                         }
                     }
+                    show_failures_dialog(result.failure_lines()).await;
                 });
             },
-            "delete" => {
-                info!("Deleting selected files...");
+            "delete" | "delete_permanently" => {
+                let permanently = event_id == "delete_permanently";
+                info!("Deleting selected files (permanently: {})...", permanently);
                 let mut app_state_clone = app_state.clone();
                 
                 spawn(async move {
@@ -1186,35 +1644,85 @@ fn AppWithMenuHandlers() -> Element {
                         return;
                     }
                     
-                    // Show confirmation dialog
-                    let file_count = selected_files.len();
-                    let confirmation_message = if file_count == 1 {
-                        format!("Are you sure you want to delete '{}'?", selected_files[0].name)
+                    // Show confirmation dialog; permanent deletion gets the stronger one
+                    let total_size = selection_size(&selected_files).await;
+                    let (title_key, message_key) = if permanently {
+                        ("dialog.delete_permanently.title", "dialog.delete_permanently.message")
                     } else {
-                        format!("Are you sure you want to delete {} files?", file_count)
+                        ("dialog.delete_files.title", "dialog.delete_files.message")
                     };
+                    let confirmation_message = t!(
+                        message_key,
+                        count = selected_files.len(),
+                        name = selected_files[0].name,
+                        size = utils::format::format_size(total_size)
+                    );
                     
-                    match show_confirmation_dialog("Delete Files", &confirmation_message).await {
-                        Ok(true) => {
-                            match delete_selected_files(&selected_files).await {
-                                Ok(deleted_count) => {
-                                    info!("Successfully deleted {} files", deleted_count);
-                                    // Refresh the file tree to reflect changes
-                                    if let Err(e) = app_state_clone.refresh_current_directory().await {
-                                        info!("Error refreshing directory after deletion: {}", e);
-                                    }
-                                },
-                                Err(e) => {
-                                    info!("Error deleting files: {}", e);
-                                }
+                    let confirmation = if permanently {
+                        show_destructive_confirmation_dialog(&t!(title_key), &confirmation_message).await
+                    } else {
+                        show_confirmation_dialog(&t!(title_key), &confirmation_message).await
+                    };
+                    match &confirmation {
+                        DialogResult::Declined => info!("File deletion declined by user"),
+                        DialogResult::Cancelled => info!("File deletion dialog dismissed"),
+                        DialogResult::Confirmed(()) => {}
+                    }
+                    // Retrying acts on the failed items only, without asking again
+                    let mut files = selected_files;
+                    let mut confirmation = confirmation;
+                    loop {
+                        // Show progress with a Cancel button while the items are removed
+                        let cancellation = CancellationToken::new();
+                        if confirmation.is_confirmed() {
+                            let operation_key = if permanently {
+                                "dialog.delete_progress.message_permanent"
+                            } else {
+                                "dialog.delete_progress.message"
+                            };
+                            app_state_clone.file_operation_progress.set(Some(FileOperationProgress {
+                                title: t!("dialog.delete_progress.title"),
+                                operation: t!(operation_key, count = files.len()),
+                                total: files.len(),
+                                cancellation: cancellation.clone(),
+                                transfer: None,
+                            }));
+                        }
+                        let service = app_state_clone.file_operations();
+                        let summary = if permanently {
+                            delete_permanently_if_confirmed(&service, &files, confirmation, &cancellation).await
+                        } else {
+                            delete_if_confirmed(&service, &files, confirmation, &cancellation, |kept| async move {
+                                let message = t!("dialog.trash_unavailable.message", count = kept.len(), name = first_name(&kept));
+                                show_destructive_confirmation_dialog(&t!("dialog.trash_unavailable.title"), &message).await
+                            })
+                            .await
+                        };
+                        app_state_clone.file_operation_progress.set(None);
+                        let Some(summary) = summary else {
+                            return;
+                        };
+                        
+                        // Say which happened, so a permanent deletion never passes for a trashed one
+                        info!("Delete finished: {:?}", summary.outcomes());
+                        if summary.is_complete() {
+                            app_state_clone.announcer.polite(summary.message());
+                        } else {
+                            app_state_clone.announcer.assertive(summary.message());
+                        }
+                        if summary.removed_any() {
+                            // Refresh the file tree to reflect changes
+                            if let Err(e) = app_state_clone.refresh_current_directory().await {
+                                info!("Error refreshing directory after deletion: {}", e);
                             }
-                        },
-                        Ok(false) => {
-                            info!("File deletion cancelled by user");
-                        },
-                        Err(e) => {
-                            info!("Error showing confirmation dialog: {}", e);
                         }
+                        
+                        let failed = summary.failed_paths();
+                        if failed.is_empty() || !show_retry_failures_dialog(summary.failure_lines()).await {
+                            break;
+                        }
+                        files.retain(|entry| failed.contains(&entry.path));
+                        confirmation = DialogResult::Confirmed(());
                     }
                 });
             },
@@ -1240,7 +1748,8 @@ fn AppWithMenuHandlers() -> Element {
                     }
                     
                     if selected_files.len() > 1 {
-                        info!("Multiple files selected - rename only works with single file selection");
+                        // Several files go through the batch rename preview
+                        app_state_clone.batch_rename_files.set(Some(selected_files));
                         return;
                     }
                     
@@ -1248,27 +1757,22 @@ fn AppWithMenuHandlers() -> Element {
                     let current_name = &file_to_rename.name;
                     
                     // Show input dialog for new name
-                    match show_rename_dialog(current_name).await {
-                        Ok(new_name) => {
-                            if new_name != *current_name {
-                                match rename_file(&file_to_rename.path, &new_name).await {
-                                    Ok(new_path) => {
-                                        info!("Successfully renamed '{}' to '{}' (path: {:?})", current_name, new_name, new_path);
-                                        // Refresh the file tree to reflect the rename
-                                        if let Err(e) = app_state_clone.refresh_current_directory().await {
-                                            info!("Error refreshing directory after rename: {}", e);
-                                        }
-                                    },
-                                    Err(e) => {
-                                        info!("Error renaming file: {}", e);
-                                    }
-                                }
-                            } else {
-                                info!("Rename cancelled - same name provided");
+                    let result = show_rename_dialog(input_requests, current_name).await;
+                    match rename_if_confirmed(&app_state_clone.file_operations(), &file_to_rename.path, result).await {
+                        Ok(Some(new_path)) => {
+                            info!("Successfully renamed '{}' (path: {:?})", current_name, new_path);
+                            app_state_clone.follow_rename(&file_to_rename.path, &new_path);
+                            // Refresh the file tree to reflect the rename
+                            if let Err(e) = app_state_clone.refresh_current_directory().await {
+                                info!("Error refreshing directory after rename: {}", e);
                             }
                         },
+                        Ok(None) => {
+                            info!("Rename cancelled - no new name confirmed");
+                        },
                         Err(e) => {
-                            info!("Error showing rename dialog: {}", e);
+                            info!("Error renaming file: {}", e);
+                            app_state_clone.announcer.assertive(e);
                         }
                     }
                 });
@@ -1294,16 +1798,65 @@ fn AppWithMenuHandlers() -> Element {
                         return;
                     }
                     
-                    match duplicate_files(&selected_files).await {
-                        Ok(duplicated_count) => {
-                            info!("Successfully duplicated {} files", duplicated_count);
-                            // Refresh the file tree to show the duplicated files
+                    let result = app_state_clone.file_operations().duplicate(&selected_files).await;
+                    info!("Duplicated {} files, {} failed", result.succeeded.len(), result.failed.len());
+                    app_state_clone.announcer.file_operation_result("duplicated", &result);
+                    if !result.succeeded.is_empty() {
+                        // Refresh the file tree to show the duplicated files
+                        if let Err(e) = app_state_clone.refresh_current_directory().await {
+                            info!("Error refreshing directory after duplication: {}", e);
+                        }
+                    }
+                    show_failures_dialog(result.failure_lines()).await;
+                });
+            },
+            "organize_by_date" | "organize_by_type" => {
+                let rule = if event_id == "organize_by_date" {
+                    OrganizeRule::by_month()
+                } else {
+                    OrganizeRule::ByType
+                };
+                info!("Organizing selected files ({:?})...", rule);
+                let mut app_state_clone = app_state.clone();
+                
+                spawn(async move {
+                    let selected_files = {
+                        let selection_state = app_state_clone.selection.read();
+                        let file_entries = app_state_clone.file_entries.read();
+                        
+                        // Filter file entries to get only the selected ones
+                        file_entries.iter()
+                            .filter(|entry| selection_state.selected_files.contains(&entry.path))
+                            .cloned()
+                            .collect::<Vec<_>>()
+                    };
+                    
+                    if selected_files.is_empty() {
+                        info!("No files selected for organizing");
+                        return;
+                    }
+                    
+                    // Show folder picker for the folder to organize into
+                    let DialogResult::Confirmed(destination) = show_destination_folder_dialog(&t!("dialog.organize_destination.title")).await else {
+                        info!("Organize cancelled by user");
+                        return;
+                    };
+                    let mut command = OrganizeCommand::new(destination, rule).with_entries(&selected_files);
+                    let moved = command.planned_moves().len();
+                    match command.execute(app_state_clone.file_service.clone()).await {
+                        Ok(()) => {
+                            info!("Organized {} files", moved);
+                            app_state_clone.announcer.polite(t!("organize.done", count = moved));
+                            if let Err(e) = app_state_clone.operation_history.lock().await.add_executed_command(Box::new(command)).await {
+                                info!("Failed to record organize in history: {}", e);
+                            }
                             if let Err(e) = app_state_clone.refresh_current_directory().await {
-                                info!("Error refreshing directory after duplication: {}", e);
+                                info!("Error refreshing directory after organizing: {}", e);
                             }
-                        },
+                        }
                         Err(e) => {
-                            info!("Error duplicating files: {}", e);
+                            app_state_clone.operation_history.lock().await.log_failed_command(&command, &e);
+                            app_state_clone.announcer.assertive(t!("organize.failed", error = e));
                         }
                     }
                 });
@@ -1362,6 +1915,53 @@ fn AppWithMenuHandlers() -> Element {
                 });
             },
             
+            "sort_name" | "sort_size" | "sort_modified" | "sort_created" | "sort_extension" | "sort_type" => {
+                let key = crate::state::SortKey::from_str(event_id.trim_start_matches("sort_"));
+                let mut sort = app_state.file_tree_state.read().sort;
+                // Picking the current key again flips the order
+                if sort.key == key {
+                    sort.order = sort.order.reversed();
+                } else {
+                    sort.key = key;
+                    sort.order = crate::state::SortOrder::Asc;
+                }
+                info!("Sorting file listing by {:?} {:?}", sort.key, sort.order);
+                app_state.clone().set_file_sort(sort);
+            },
+            "sort_reverse" => {
+                let mut sort = app_state.file_tree_state.read().sort;
+                sort.order = sort.order.reversed();
+                app_state.clone().set_file_sort(sort);
+            },
+            "sort_directories_first" => {
+                let mut sort = app_state.file_tree_state.read().sort;
+                sort.directories_first = !sort.directories_first;
+                app_state.clone().set_file_sort(sort);
+            },
+            
+            "operation_log" => {
+                info!("Showing operation log...");
+                let mut app_state_clone = app_state.clone();
+                app_state_clone.operation_log_visible.set(true);
+            },
+            "trash" => {
+                info!("Showing trash...");
+                let mut app_state_clone = app_state.clone();
+                app_state_clone.trash_visible.set(true);
+            },
+            "compare_folders" => {
+                info!("Showing folder comparison...");
+                let mut app_state_clone = app_state.clone();
+                app_state_clone.folder_compare_visible.set(true);
+            },
+            "toggle_split_view" => {
+                info!("Toggling split view...");
+                let mut app_state_clone = app_state.clone();
+                spawn(async move {
+                    app_state_clone.toggle_split_view().await;
+                });
+            },
+            
             // Help menu items
             "keyboard_shortcuts" => {
                 info!("Showing keyboard shortcuts...");
@@ -1384,9 +1984,213 @@ fn AppWithMenuHandlers() -> Element {
                 info!("Unhandled menu item: {}", event_id);
             }
         }
+    };
+    
+    // Handle menu events
+    let on_menu_event = handle_menu_action.clone();
+    use_muda_event_handler(move |menu_event| on_menu_event(menu_event.id.0.as_str()));
+    
+    // File operations picked in the command palette
+    use_effect(move || {
+        let request = *menu_action_request.read();
+        if let Some(event_id) = request {
+            menu_action_request.set(None);
+            handle_menu_action(event_id);
+        }
     });
     
     rsx! {
         phase2_app {}
+        InputDialog { request: input_requests }
+        OpenWithDialog { request: open_with_requests }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::file_system::{FileSystemService, NativeFileSystemService};
+    use crate::services::operations::OperationHistory;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    async fn entry(path: &std::path::Path) -> FileEntry {
+        NativeFileSystemService::new().get_metadata(path).await.unwrap()
+    }
+
+    fn service() -> (FileOperationService, Arc<tokio::sync::Mutex<OperationHistory>>) {
+        let fs: Arc<dyn FileSystemService> = Arc::new(NativeFileSystemService::new());
+        let history = Arc::new(tokio::sync::Mutex::new(OperationHistory::new(fs.clone())));
+        (FileOperationService::new(fs, history.clone()), history)
+    }
+
+    #[tokio::test]
+    async fn test_declined_or_cancelled_dialogs_leave_files_alone() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("photo.jpg");
+        std::fs::write(&file, b"image data").unwrap();
+        let destination = temp_dir.path().join("archive");
+        std::fs::create_dir(&destination).unwrap();
+        let files = vec![entry(&file).await];
+        let (service, history) = service();
+
+        for outcome in [DialogResult::Declined, DialogResult::Cancelled] {
+            let prompt = |_| async { DialogResult::Confirmed(()) };
+            assert!(delete_if_confirmed(&service, &files, outcome.clone(), &CancellationToken::new(), prompt).await.is_none());
+            assert!(delete_permanently_if_confirmed(&service, &files, outcome.clone(), &CancellationToken::new()).await.is_none());
+            let ask = |_| async { ConflictResolution::Overwrite };
+            let moved = move_if_confirmed(
+                &service,
+                &files,
+                outcome.clone().map(|_| destination.clone()),
+                ask,
+                &CancellationToken::new(),
+                &TransferProgress::default(),
+            );
+            assert!(moved.await.is_none());
+            assert_eq!(rename_if_confirmed(&service, &file, outcome.map(|_| "other.jpg".to_string())).await, Ok(None));
+        }
+
+        assert!(file.exists());
+        assert!(!history.lock().await.can_undo());
+        assert!(!temp_dir.path().join("other.jpg").exists());
+        assert!(!destination.join("photo.jpg").exists());
+    }
+
+    #[tokio::test]
+    async fn test_confirmed_dialogs_act_on_the_confirmed_value() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("photo.jpg");
+        std::fs::write(&file, b"image data").unwrap();
+        let (service, _) = service();
+
+        // Confirming the unchanged name is not a rename
+        let unchanged = rename_if_confirmed(&service, &file, DialogResult::Confirmed("photo.jpg".to_string())).await;
+        assert_eq!(unchanged, Ok(None));
+
+        let renamed = temp_dir.path().join("holiday.jpg");
+        let result = rename_if_confirmed(&service, &file, DialogResult::Confirmed("holiday.jpg".to_string())).await;
+        assert_eq!(result, Ok(Some(renamed.clone())));
+        assert!(renamed.exists() && !file.exists());
+
+        let destination = temp_dir.path().join("archive");
+        std::fs::create_dir(&destination).unwrap();
+        let files = vec![entry(&renamed).await];
+        let ask = |_| async { ConflictResolution::Overwrite };
+        let confirmed = DialogResult::Confirmed(destination.clone());
+        let result = move_if_confirmed(&service, &files, confirmed, ask, &CancellationToken::new(), &TransferProgress::default()).await;
+        assert_eq!(result.unwrap().succeeded, vec![destination.join("holiday.jpg")]);
+        assert!(destination.join("holiday.jpg").exists());
+    }
+
+    #[tokio::test]
+    async fn test_delete_moves_files_to_the_trash_without_prompting() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("photo.jpg");
+        std::fs::write(&file, b"image data").unwrap();
+        let trash_dir = temp_dir.path().join(".trash");
+        let files = vec![entry(&file).await];
+        let (service, history) = service();
+        let service = service.with_trash_dir(trash_dir.clone());
+
+        let asked = std::sync::atomic::AtomicBool::new(false);
+        let summary = delete_if_confirmed(&service, &files, DialogResult::Confirmed(()), &CancellationToken::new(), |_| {
+            asked.store(true, std::sync::atomic::Ordering::SeqCst);
+            async { DialogResult::Confirmed(()) }
+        })
+        .await
+        .unwrap();
+
+        // The trash took everything, so permanent deletion never came up
+        assert!(!asked.load(std::sync::atomic::Ordering::SeqCst));
+        assert_eq!(summary, DeleteSummary { trashed: vec![file.clone()], ..DeleteSummary::default() });
+        assert!(!file.exists());
+        assert_eq!(std::fs::read_dir(&trash_dir).unwrap().count(), 1);
+        history.lock().await.undo().await.unwrap();
+        assert!(file.exists());
+    }
+
+    #[tokio::test]
+    async fn test_delete_permanently_skips_the_trash_and_cannot_be_undone() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("photo.jpg");
+        std::fs::write(&file, b"image data").unwrap();
+        let folder = temp_dir.path().join("album");
+        std::fs::create_dir(&folder).unwrap();
+        std::fs::write(folder.join("a.jpg"), b"a").unwrap();
+        let files = vec![entry(&file).await, entry(&folder).await];
+        let (service, history) = service();
+
+        let summary = delete_permanently_if_confirmed(&service, &files, DialogResult::Confirmed(()), &CancellationToken::new())
+            .await
+            .unwrap();
+
+        assert_eq!(summary, DeleteSummary { deleted_permanently: vec![file.clone(), folder.clone()], ..DeleteSummary::default() });
+        assert!(!file.exists() && !folder.exists());
+        assert!(!history.lock().await.can_undo());
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_trash_asks_before_deleting_permanently() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("photo.jpg");
+        std::fs::write(&file, b"image data").unwrap();
+        // A file where the trash folder should be makes the trash unusable
+        let blocked = temp_dir.path().join("not-a-folder");
+        std::fs::write(&blocked, b"").unwrap();
+        let files = vec![entry(&file).await];
+        let (service, history) = service();
+        let service = service.with_trash_dir(blocked.join("trash"));
+
+        for answer in [DialogResult::Declined, DialogResult::Cancelled] {
+            let asked = std::sync::Mutex::new(Vec::new());
+            let summary = delete_if_confirmed(&service, &files, DialogResult::Confirmed(()), &CancellationToken::new(), |kept| {
+                asked.lock().unwrap().extend(kept);
+                async move { answer }
+            })
+            .await
+            .unwrap();
+            assert_eq!(*asked.lock().unwrap(), vec![file.clone()]);
+            assert_eq!(summary, DeleteSummary { kept: vec![file.clone()], ..DeleteSummary::default() });
+            assert!(!summary.is_complete());
+            assert!(file.exists());
+        }
+
+        let summary = delete_if_confirmed(&service, &files, DialogResult::Confirmed(()), &CancellationToken::new(), |_| async {
+            DialogResult::Confirmed(())
+        })
+        .await
+        .unwrap();
+        assert_eq!(summary, DeleteSummary { deleted_permanently: vec![file.clone()], ..DeleteSummary::default() });
+        assert!(!file.exists());
+        assert!(!history.lock().await.can_undo());
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_delete_leaves_the_rest_and_asks_nothing() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("photo.jpg");
+        std::fs::write(&file, b"image data").unwrap();
+        let blocked = temp_dir.path().join("not-a-folder");
+        std::fs::write(&blocked, b"").unwrap();
+        let files = vec![entry(&file).await];
+        let (service, history) = service();
+        let service = service.with_trash_dir(blocked.join("trash"));
+
+        let cancellation = CancellationToken::new();
+        cancellation.cancel();
+        let asked = std::sync::atomic::AtomicBool::new(false);
+        let summary = delete_if_confirmed(&service, &files, DialogResult::Confirmed(()), &cancellation, |_| {
+            asked.store(true, std::sync::atomic::Ordering::SeqCst);
+            async { DialogResult::Confirmed(()) }
+        })
+        .await
+        .unwrap();
+
+        assert!(!asked.load(std::sync::atomic::Ordering::SeqCst));
+        assert_eq!(summary, DeleteSummary { not_started: vec![file.clone()], ..DeleteSummary::default() });
+        assert!(summary.was_cancelled());
+        assert!(file.exists());
+        assert!(!history.lock().await.can_undo());
     }
 }
@@ -260,6 +260,11 @@ impl VirtualScrollOptimizer {
         self.total_items = count;
     }
 
+    /// Update the height of one row
+    pub fn set_item_height(&mut self, height: f64) {
+        self.item_height = height.max(1.0);
+    }
+
     /// Calculate visible item range for rendering
     pub fn get_visible_range(&self) -> (usize, usize) {
         let items_per_viewport = (self.viewport_height / self.item_height).ceil() as usize;
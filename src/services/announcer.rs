@@ -0,0 +1,228 @@
+use std::fmt::Display;
+use std::path::Path;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use super::file_operations::FileOperationResult;
+
+/// Identical announcements within this window are dropped
+pub const DEDUP_WINDOW: Duration = Duration::from_secs(2);
+
+/// How urgently a screen reader should speak an announcement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Politeness {
+    /// Spoken when the user is idle (navigation, selection, success)
+    Polite,
+    /// Interrupts current speech (failures)
+    Assertive,
+}
+
+impl Politeness {
+    /// Value for the `aria-live` attribute
+    pub fn aria_live(&self) -> &'static str {
+        match self {
+            Politeness::Polite => "polite",
+            Politeness::Assertive => "assertive",
+        }
+    }
+}
+
+/// A message posted to the live region
+#[derive(Debug, Clone, PartialEq)]
+pub struct Announcement {
+    /// Increases with every post, so repeated text still re-renders
+    pub id: u64,
+    pub message: String,
+    pub politeness: Politeness,
+    pub posted_at: Instant,
+}
+
+#[derive(Debug, Default)]
+struct AnnouncerState {
+    next_id: u64,
+    polite: Option<Announcement>,
+    assertive: Option<Announcement>,
+}
+
+/// Queue of screen-reader announcements feeding the ARIA live regions
+#[derive(Debug, Default)]
+pub struct Announcer {
+    state: RwLock<AnnouncerState>,
+}
+
+impl Announcer {
+    /// Create an empty announcer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Post a message; returns false if it was empty or a recent duplicate
+    pub fn announce(&self, message: impl Into<String>, politeness: Politeness) -> bool {
+        let message = message.into();
+        let message = message.trim();
+        if message.is_empty() {
+            return false;
+        }
+
+        let Ok(mut state) = self.state.write() else {
+            return false;
+        };
+        let slot = match politeness {
+            Politeness::Polite => &state.polite,
+            Politeness::Assertive => &state.assertive,
+        };
+        if slot
+            .as_ref()
+            .is_some_and(|last| last.message == message && last.posted_at.elapsed() < DEDUP_WINDOW)
+        {
+            return false;
+        }
+
+        state.next_id += 1;
+        let announcement = Announcement {
+            id: state.next_id,
+            message: message.to_string(),
+            politeness,
+            posted_at: Instant::now(),
+        };
+        match politeness {
+            Politeness::Polite => state.polite = Some(announcement),
+            Politeness::Assertive => state.assertive = Some(announcement),
+        }
+        true
+    }
+
+    /// Post a polite message
+    pub fn polite(&self, message: impl Into<String>) -> bool {
+        self.announce(message, Politeness::Polite)
+    }
+
+    /// Post an assertive message
+    pub fn assertive(&self, message: impl Into<String>) -> bool {
+        self.announce(message, Politeness::Assertive)
+    }
+
+    /// Latest announcement at the given level
+    pub fn latest(&self, politeness: Politeness) -> Option<Announcement> {
+        self.state.read().ok().and_then(|state| match politeness {
+            Politeness::Polite => state.polite.clone(),
+            Politeness::Assertive => state.assertive.clone(),
+        })
+    }
+
+    /// Announce that a folder was opened
+    pub fn folder_changed(&self, path: &Path) -> bool {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.to_string_lossy().to_string());
+        self.polite(format!("Opened folder {}", name))
+    }
+
+    /// Announce the number of selected items
+    pub fn selection_changed(&self, count: usize) -> bool {
+        let message = match count {
+            0 => "Selection cleared".to_string(),
+            1 => "1 item selected".to_string(),
+            n => format!("{} items selected", n),
+        };
+        self.polite(message)
+    }
+
+    /// Announce a finished operation, e.g. "3 files copied"
+    pub fn operation_completed(&self, past_tense_verb: &str, count: usize) -> bool {
+        let noun = if count == 1 { "file" } else { "files" };
+        self.polite(format!("{} {} {}", count, noun, past_tense_verb))
+    }
+
+    /// Announce a failed operation, interrupting current speech
+    pub fn operation_failed(&self, operation: &str, error: impl Display) -> bool {
+        self.assertive(format!("{} failed: {}", operation, error))
+    }
+
+    /// Announce the outcome of an operation that processed `count` files
+    pub fn operation_result<E: Display>(
+        &self,
+        operation: &str,
+        past_tense_verb: &str,
+        result: &Result<usize, E>,
+    ) -> bool {
+        match result {
+            Ok(count) => self.operation_completed(past_tense_verb, *count),
+            Err(e) => self.operation_failed(operation, e),
+        }
+    }
+
+    /// Announce a per-item outcome, e.g. "2 files moved, 1 failed"
+    ///
+    /// Any failure makes the announcement assertive.
+    pub fn file_operation_result(&self, past_tense_verb: &str, result: &FileOperationResult) -> bool {
+        if result.is_complete() {
+            return self.operation_completed(past_tense_verb, result.succeeded.len());
+        }
+        let count = result.succeeded.len();
+        let noun = if count == 1 { "file" } else { "files" };
+        self.assertive(format!("{} {} {}, {} failed", count, noun, past_tense_verb, result.failed.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::file_system::{FileSystemService, NativeFileSystemService};
+    use crate::services::operations::{Command, CopyCommand};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_copy_posts_polite_completion_message() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("photo.jpg");
+        std::fs::write(&source, b"image data").unwrap();
+        let destination = temp_dir.path().join("copy.jpg");
+
+        let fs: Arc<dyn FileSystemService> = Arc::new(NativeFileSystemService::new());
+        let mut copy = CopyCommand::new(source, destination.clone());
+        let result = copy.execute(fs).await.map(|_| 1);
+        assert!(destination.exists());
+
+        let announcer = Announcer::new();
+        assert!(announcer.operation_result("Copy", "copied", &result));
+
+        let announcement = announcer.latest(Politeness::Polite).unwrap();
+        assert_eq!(announcement.message, "1 file copied");
+        assert!(announcer.latest(Politeness::Assertive).is_none());
+    }
+
+    #[test]
+    fn test_duplicates_are_dropped_and_failures_are_assertive() {
+        let announcer = Announcer::new();
+        assert!(announcer.selection_changed(3));
+        assert!(!announcer.selection_changed(3));
+        assert!(announcer.selection_changed(0));
+        assert_eq!(announcer.latest(Politeness::Polite).unwrap().message, "Selection cleared");
+
+        let result: Result<usize, String> = Err("disk full".to_string());
+        assert!(announcer.operation_result("Copy", "copied", &result));
+        assert_eq!(
+            announcer.latest(Politeness::Assertive).unwrap().message,
+            "Copy failed: disk full"
+        );
+        assert!(!announcer.announce("   ", Politeness::Polite));
+    }
+
+    #[test]
+    fn test_partial_file_operation_is_assertive() {
+        let announcer = Announcer::new();
+        let mut result = FileOperationResult {
+            succeeded: vec!["/archive/a.jpg".into(), "/archive/b.jpg".into()],
+            ..FileOperationResult::default()
+        };
+        assert!(announcer.file_operation_result("moved", &result));
+        assert_eq!(announcer.latest(Politeness::Polite).unwrap().message, "2 files moved");
+
+        result.failed.push(("/photos/c.jpg".into(), "Permission denied".to_string()));
+        assert!(announcer.file_operation_result("moved", &result));
+        assert_eq!(announcer.latest(Politeness::Assertive).unwrap().message, "2 files moved, 1 failed");
+    }
+}
@@ -0,0 +1,1132 @@
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+use async_trait::async_trait;
+use tracing::{info, warn};
+use walkdir::WalkDir;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::services::file_system::{ConflictResolution, FileSystemError, FileSystemService};
+use crate::services::operations::{
+    partial_copy_path, Command, CommandMetadata, CommandStatus, OperationError, OperationKind,
+    OperationResult, ProgressTracker,
+};
+use crate::utils::format::format_size;
+
+/// Buffer size used when streaming file contents into an archive (256KB)
+const ZIP_BUFFER_SIZE: usize = 256 * 1024;
+
+/// Default cap on the total uncompressed size of an extraction (8GB)
+pub const DEFAULT_MAX_EXTRACT_SIZE: u64 = 8 * 1024 * 1024 * 1024;
+
+/// A single entry planned for a zip export
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZipEntryPlan {
+    /// File or directory on disk
+    pub source: PathBuf,
+    /// Entry name inside the archive, using `/` separators
+    pub name: String,
+    /// Whether the entry is a directory
+    pub is_dir: bool,
+    /// Uncompressed size in bytes
+    pub size: u64,
+}
+
+/// Summary reported when a zip export completes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZipExportSummary {
+    /// Archive that was written
+    pub archive_path: PathBuf,
+    /// Number of files stored
+    pub file_count: usize,
+    /// Total size of the source files
+    pub original_size: u64,
+    /// Size of the written archive
+    pub compressed_size: u64,
+}
+
+impl ZipExportSummary {
+    /// Archive size as a fraction of the original size
+    pub fn compression_ratio(&self) -> f64 {
+        if self.original_size == 0 {
+            1.0
+        } else {
+            self.compressed_size as f64 / self.original_size as f64
+        }
+    }
+
+    /// Human-readable completion message
+    pub fn describe(&self) -> String {
+        format!(
+            "Exported {} file{} to {}: {} → {} ({:.0}%)",
+            self.file_count,
+            if self.file_count == 1 { "" } else { "s" },
+            self.archive_path.display(),
+            format_size(self.original_size),
+            format_size(self.compressed_size),
+            self.compression_ratio() * 100.0
+        )
+    }
+}
+
+/// Plan the archive entries for a selection
+///
+/// Selected folders keep their relative structure under the folder name;
+/// top-level names that collide get a ` (n)` suffix.
+pub fn plan_zip_entries(selection: &[PathBuf]) -> OperationResult<Vec<ZipEntryPlan>> {
+    let mut used_roots = HashSet::new();
+    let mut entries = Vec::new();
+
+    for path in selection {
+        let metadata = std::fs::metadata(path)
+            .map_err(|e| OperationError::FileSystem(FileSystemError::from_io_error(e, path)))?;
+        let base_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .ok_or_else(|| OperationError::ValidationFailed(format!("Invalid file name: {}", path.display())))?;
+        let root = unique_root_name(&base_name, metadata.is_dir(), &mut used_roots);
+
+        if !metadata.is_dir() {
+            entries.push(ZipEntryPlan {
+                source: path.clone(),
+                name: root,
+                is_dir: false,
+                size: metadata.len(),
+            });
+            continue;
+        }
+
+        entries.push(ZipEntryPlan {
+            source: path.clone(),
+            name: format!("{}/", root),
+            is_dir: true,
+            size: 0,
+        });
+
+        for entry in WalkDir::new(path).min_depth(1).sort_by_file_name() {
+            let entry = entry.map_err(|e| OperationError::ExecutionFailed(format!("Failed to walk {}: {}", path.display(), e)))?;
+            let relative = entry.path().strip_prefix(path).unwrap_or(entry.path());
+            let relative_name = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+                .join("/");
+            let file_type = entry.file_type();
+
+            if file_type.is_dir() {
+                entries.push(ZipEntryPlan {
+                    source: entry.path().to_path_buf(),
+                    name: format!("{}/{}/", root, relative_name),
+                    is_dir: true,
+                    size: 0,
+                });
+            } else if file_type.is_file() {
+                let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                entries.push(ZipEntryPlan {
+                    source: entry.path().to_path_buf(),
+                    name: format!("{}/{}", root, relative_name),
+                    is_dir: false,
+                    size,
+                });
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Pick a top-level archive name not yet used, e.g. `photo (2).jpg`
+fn unique_root_name(base_name: &str, is_dir: bool, used: &mut HashSet<String>) -> String {
+    let (stem, extension) = match base_name.rfind('.') {
+        Some(dot_pos) if dot_pos > 0 && !is_dir => base_name.split_at(dot_pos),
+        _ => (base_name, ""),
+    };
+
+    let mut candidate = base_name.to_string();
+    let mut counter = 2;
+    while !used.insert(candidate.to_lowercase()) {
+        candidate = format!("{} ({}){}", stem, counter, extension);
+        counter += 1;
+    }
+    candidate
+}
+
+/// Write the selection to a zip archive at `destination`
+pub fn export_selection_as_zip(
+    selection: &[PathBuf],
+    destination: &Path,
+    progress: Option<&mut ProgressTracker>,
+) -> OperationResult<ZipExportSummary> {
+    let entries = plan_zip_entries(selection)?;
+    export_zip_entries(&entries, destination, progress)
+}
+
+/// Write planned entries to a zip archive at `destination`
+///
+/// Contents are streamed through a fixed buffer into a `.part` file that is
+/// renamed into place on success and removed on failure or cancellation.
+pub fn export_zip_entries(
+    entries: &[ZipEntryPlan],
+    destination: &Path,
+    mut progress: Option<&mut ProgressTracker>,
+) -> OperationResult<ZipExportSummary> {
+    let entries: Vec<ZipEntryPlan> = entries
+        .iter()
+        .filter(|entry| entry.source != destination)
+        .cloned()
+        .collect();
+    let part_path = partial_copy_path(destination);
+
+    let original_size = match write_zip(&entries, &part_path, progress.as_deref_mut()) {
+        Ok(size) => size,
+        Err(e) => {
+            if let Err(remove_err) = std::fs::remove_file(&part_path) {
+                warn!("Failed to remove partial archive {}: {}", part_path.display(), remove_err);
+            }
+            return Err(e);
+        }
+    };
+
+    std::fs::rename(&part_path, destination)
+        .map_err(|e| OperationError::FileSystem(FileSystemError::from_io_error(e, destination)))?;
+
+    let compressed_size = std::fs::metadata(destination).map(|m| m.len()).unwrap_or(0);
+    let summary = ZipExportSummary {
+        archive_path: destination.to_path_buf(),
+        file_count: entries.iter().filter(|e| !e.is_dir).count(),
+        original_size,
+        compressed_size,
+    };
+
+    if let Some(tracker) = progress {
+        let _ = tracker.complete();
+    }
+
+    info!("{}", summary.describe());
+    Ok(summary)
+}
+
+/// Stream all planned entries into the archive, returning the bytes read
+fn write_zip(
+    entries: &[ZipEntryPlan],
+    archive_path: &Path,
+    mut progress: Option<&mut ProgressTracker>,
+) -> OperationResult<u64> {
+    let io_error = |e: std::io::Error, path: &Path| {
+        OperationError::FileSystem(FileSystemError::from_io_error(e, path))
+    };
+    let zip_error = |e: zip::result::ZipError| {
+        OperationError::ExecutionFailed(format!("Failed to write archive: {}", e))
+    };
+
+    let file = File::create(archive_path).map_err(|e| io_error(e, archive_path))?;
+    let mut writer = ZipWriter::new(BufWriter::new(file));
+    let mut buffer = vec![0u8; ZIP_BUFFER_SIZE];
+    let mut files_done = 0u64;
+    let mut bytes_done = 0u64;
+
+    for entry in entries {
+        let mut options = FileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .large_file(entry.size >= u32::MAX as u64);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            if let Ok(metadata) = std::fs::metadata(&entry.source) {
+                options = options.unix_permissions(metadata.permissions().mode());
+            }
+        }
+
+        if entry.is_dir {
+            writer.add_directory(entry.name.as_str(), options).map_err(zip_error)?;
+            continue;
+        }
+
+        writer.start_file(entry.name.as_str(), options).map_err(zip_error)?;
+        let mut reader = File::open(&entry.source).map_err(|e| io_error(e, &entry.source))?;
+        let operation = format!("Zipping {}", entry.name);
+
+        loop {
+            let read = reader.read(&mut buffer).map_err(|e| io_error(e, &entry.source))?;
+            if read == 0 {
+                break;
+            }
+
+            writer.write_all(&buffer[..read]).map_err(|e| io_error(e, archive_path))?;
+            bytes_done += read as u64;
+
+            if let Some(tracker) = progress.as_deref_mut() {
+                tracker.update(files_done, bytes_done, operation.clone())?;
+            }
+        }
+
+        files_done += 1;
+        if let Some(tracker) = progress.as_deref_mut() {
+            tracker.update(files_done, bytes_done, operation)?;
+        }
+    }
+
+    let mut inner = writer.finish().map_err(zip_error)?;
+    inner.flush().map_err(|e| io_error(e, archive_path))?;
+
+    Ok(bytes_done)
+}
+
+/// How extraction handles entries that already exist at the destination
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExtractConflictPolicy {
+    /// Extract alongside the existing file as `name (2).ext`
+    #[default]
+    KeepBoth,
+    /// Leave the existing file and skip the entry
+    Skip,
+    /// Replace the existing file, keeping its content to restore on undo
+    Overwrite,
+    /// Abort the extraction
+    Fail,
+}
+
+impl From<ConflictResolution> for ExtractConflictPolicy {
+    /// `Ask` stops at the first conflict, as it does for copies
+    fn from(resolution: ConflictResolution) -> Self {
+        match resolution {
+            ConflictResolution::Skip => ExtractConflictPolicy::Skip,
+            ConflictResolution::Overwrite => ExtractConflictPolicy::Overwrite,
+            ConflictResolution::Rename => ExtractConflictPolicy::KeepBoth,
+            ConflictResolution::Ask => ExtractConflictPolicy::Fail,
+        }
+    }
+}
+
+/// Resolve an archive entry name to a path inside `destination`
+///
+/// Rejects absolute paths, drive prefixes and `..` components (zip-slip).
+pub fn safe_entry_path(destination: &Path, entry_name: &str) -> OperationResult<PathBuf> {
+    let mut relative = PathBuf::new();
+
+    for component in Path::new(entry_name).components() {
+        match component {
+            Component::Normal(part) => relative.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                return Err(OperationError::ValidationFailed(format!(
+                    "Archive entry escapes the destination folder: {}",
+                    entry_name
+                )));
+            }
+        }
+    }
+
+    if relative.as_os_str().is_empty() {
+        return Err(OperationError::ValidationFailed(format!("Invalid archive entry name: {:?}", entry_name)));
+    }
+
+    Ok(destination.join(relative))
+}
+
+/// Sibling folder named after the archive, e.g. `photos.zip` → `photos`, or `photos (2)` if taken
+pub fn default_extract_destination(archive: &Path) -> PathBuf {
+    let parent = archive.parent().unwrap_or_else(|| Path::new("."));
+    let stem = archive
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "Archive".to_string());
+
+    let mut candidate = parent.join(&stem);
+    let mut counter = 2;
+    while candidate.exists() {
+        candidate = parent.join(format!("{} ({})", stem, counter));
+        counter += 1;
+    }
+    candidate
+}
+
+/// Pick a free path next to `path`, e.g. `photo (2).jpg`
+fn unique_sibling_path(path: &Path) -> PathBuf {
+    let file_name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let mut used = HashSet::new();
+    let mut candidate = path.to_path_buf();
+
+    while candidate.exists() {
+        candidate = path.with_file_name(unique_root_name(&file_name, false, &mut used));
+    }
+    candidate
+}
+
+/// Extract archive command
+///
+/// Extracts a zip archive into a destination folder.
+/// Undo removes every file and folder the extraction created.
+#[derive(Debug, Clone)]
+pub struct ExtractArchiveCommand {
+    pub archive: PathBuf,
+    pub destination: PathBuf,
+    pub conflict_policy: ExtractConflictPolicy,
+    /// Abort once the uncompressed total would exceed this many bytes
+    pub max_total_size: u64,
+
+    // State for undo, in creation order
+    created_paths: Vec<PathBuf>,
+    /// Files replaced under `Overwrite`, with their previous content
+    replaced_files: Vec<(PathBuf, Vec<u8>)>,
+
+    metadata: CommandMetadata,
+}
+
+impl ExtractArchiveCommand {
+    /// Create a new extract command targeting `destination`
+    pub fn new(archive: PathBuf, destination: PathBuf) -> Self {
+        Self {
+            archive,
+            destination,
+            conflict_policy: ExtractConflictPolicy::default(),
+            max_total_size: DEFAULT_MAX_EXTRACT_SIZE,
+            created_paths: Vec::new(),
+            replaced_files: Vec::new(),
+            metadata: CommandMetadata::default(),
+        }
+    }
+
+    /// Extract into a sibling folder named after the archive
+    pub fn extract_here(archive: PathBuf) -> Self {
+        let destination = default_extract_destination(&archive);
+        Self::new(archive, destination)
+    }
+
+    /// Set the conflict policy
+    pub fn with_conflict_policy(mut self, conflict_policy: ExtractConflictPolicy) -> Self {
+        self.conflict_policy = conflict_policy;
+        self
+    }
+
+    /// Set the total-size threshold for archive bomb protection
+    pub fn with_max_total_size(mut self, max_total_size: u64) -> Self {
+        self.max_total_size = max_total_size;
+        self
+    }
+
+    /// Paths created by the last execution, in creation order
+    pub fn created_paths(&self) -> &[PathBuf] {
+        &self.created_paths
+    }
+
+    /// Files in the archive that already exist at the destination
+    pub fn conflicts(&self) -> OperationResult<Vec<PathBuf>> {
+        let file = File::open(&self.archive)
+            .map_err(|e| OperationError::FileSystem(FileSystemError::from_io_error(e, &self.archive)))?;
+        let mut archive = ZipArchive::new(file)
+            .map_err(|e| OperationError::ExecutionFailed(format!("Failed to read archive: {}", e)))?;
+
+        let mut conflicts = Vec::new();
+        for index in 0..archive.len() {
+            let Ok(entry) = archive.by_index_raw(index) else {
+                continue;
+            };
+            if entry.is_dir() {
+                continue;
+            }
+            let target = safe_entry_path(&self.destination, entry.name())?;
+            if target.is_file() {
+                conflicts.push(target);
+            }
+        }
+        Ok(conflicts)
+    }
+
+    /// Run the extraction synchronously, rolling back created paths on failure
+    pub fn run(&mut self, progress: Option<&mut ProgressTracker>) -> OperationResult<()> {
+        if self.is_executed() {
+            return Err(OperationError::AlreadyExecuted);
+        }
+
+        self.created_paths.clear();
+        self.replaced_files.clear();
+        match self.extract(progress) {
+            Ok(()) => {
+                self.metadata.status = CommandStatus::Executed;
+                self.metadata.executed_at = Some(SystemTime::now());
+                info!(
+                    "Extracted {} into {} ({} paths created)",
+                    self.archive.display(),
+                    self.destination.display(),
+                    self.created_paths.len()
+                );
+                Ok(())
+            }
+            Err(e) => {
+                warn!("Extraction of {} failed, rolling back: {}", self.archive.display(), e);
+                if let Err(rollback_err) = self.roll_back() {
+                    warn!("Rollback incomplete: {}", rollback_err);
+                }
+                self.metadata.status = CommandStatus::Failed;
+                self.metadata.error_message = Some(e.to_string());
+                Err(e)
+            }
+        }
+    }
+
+    fn extract(&mut self, mut progress: Option<&mut ProgressTracker>) -> OperationResult<()> {
+        let io_error = |e: std::io::Error, path: &Path| {
+            OperationError::FileSystem(FileSystemError::from_io_error(e, path))
+        };
+        let zip_error = |e: zip::result::ZipError| {
+            OperationError::ExecutionFailed(format!("Failed to read archive: {}", e))
+        };
+
+        let file = File::open(&self.archive).map_err(|e| io_error(e, &self.archive))?;
+        let mut archive = ZipArchive::new(file).map_err(zip_error)?;
+
+        // Validate every entry name and the declared total before touching the disk
+        let mut declared_total = 0u64;
+        for index in 0..archive.len() {
+            let entry = archive.by_index_raw(index).map_err(zip_error)?;
+            safe_entry_path(&self.destination, entry.name())?;
+            declared_total = declared_total.saturating_add(entry.size());
+        }
+        self.check_size_limit(declared_total)?;
+
+        if !self.destination.exists() {
+            std::fs::create_dir_all(&self.destination).map_err(|e| io_error(e, &self.destination))?;
+            self.created_paths.push(self.destination.clone());
+        }
+
+        let mut buffer = vec![0u8; ZIP_BUFFER_SIZE];
+        let mut files_done = 0u64;
+        let mut bytes_done = 0u64;
+
+        for index in 0..archive.len() {
+            let mut entry = archive.by_index(index).map_err(zip_error)?;
+            let target = safe_entry_path(&self.destination, entry.name())?;
+            let operation = format!("Extracting {}", entry.name());
+
+            if entry.is_dir() {
+                self.create_dirs(&target)?;
+                continue;
+            }
+
+            if let Some(parent) = target.parent() {
+                self.create_dirs(parent)?;
+            }
+
+            let mut replaced = false;
+            let target = if target.exists() {
+                match self.conflict_policy {
+                    ExtractConflictPolicy::KeepBoth => unique_sibling_path(&target),
+                    ExtractConflictPolicy::Skip => {
+                        files_done += 1;
+                        continue;
+                    }
+                    ExtractConflictPolicy::Overwrite if target.is_file() => {
+                        let previous = std::fs::read(&target).map_err(|e| io_error(e, &target))?;
+                        self.replaced_files.push((target.clone(), previous));
+                        replaced = true;
+                        target
+                    }
+                    // A folder in the way can't be replaced by a file
+                    ExtractConflictPolicy::Overwrite | ExtractConflictPolicy::Fail => {
+                        return Err(OperationError::FileSystem(FileSystemError::FileAlreadyExists {
+                            path: target.clone(),
+                        }));
+                    }
+                }
+            } else {
+                target
+            };
+
+            let mut writer = File::create(&target).map_err(|e| io_error(e, &target))?;
+            if !replaced {
+                self.created_paths.push(target.clone());
+            }
+
+            loop {
+                let read = entry.read(&mut buffer).map_err(|e| io_error(e, &self.archive))?;
+                if read == 0 {
+                    break;
+                }
+
+                // Declared sizes can lie; enforce the limit on actual output too
+                bytes_done += read as u64;
+                self.check_size_limit(bytes_done)?;
+                writer.write_all(&buffer[..read]).map_err(|e| io_error(e, &target))?;
+
+                if let Some(tracker) = progress.as_deref_mut() {
+                    tracker.update(files_done, bytes_done, operation.clone())?;
+                }
+            }
+
+            #[cfg(unix)]
+            if let Some(mode) = entry.unix_mode() {
+                use std::os::unix::fs::PermissionsExt;
+                let _ = std::fs::set_permissions(&target, std::fs::Permissions::from_mode(mode & 0o777));
+            }
+
+            files_done += 1;
+            if let Some(tracker) = progress.as_deref_mut() {
+                tracker.update(files_done, bytes_done, operation)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Create a directory and any missing parents, recording the ones created
+    fn create_dirs(&mut self, dir: &Path) -> OperationResult<()> {
+        let mut missing = Vec::new();
+        let mut current = Some(dir);
+        while let Some(path) = current {
+            if path.exists() {
+                break;
+            }
+            missing.push(path.to_path_buf());
+            current = path.parent();
+        }
+
+        for path in missing.into_iter().rev() {
+            std::fs::create_dir(&path)
+                .map_err(|e| OperationError::FileSystem(FileSystemError::from_io_error(e, &path)))?;
+            self.created_paths.push(path);
+        }
+        Ok(())
+    }
+
+    fn check_size_limit(&self, total: u64) -> OperationResult<()> {
+        if total > self.max_total_size {
+            warn!(
+                "Refusing to extract {}: expands past the {} limit",
+                self.archive.display(),
+                format_size(self.max_total_size)
+            );
+            return Err(OperationError::ValidationFailed(format!(
+                "Archive expands to more than {} (possible archive bomb)",
+                format_size(self.max_total_size)
+            )));
+        }
+        Ok(())
+    }
+
+    /// Remove what the extraction created and put replaced files back
+    fn roll_back(&mut self) -> Result<(), String> {
+        let removed = self.remove_created_paths();
+        let restored = self.restore_replaced_files();
+        match (removed, restored) {
+            (Ok(()), Ok(())) => Ok(()),
+            (Err(e), Ok(())) | (Ok(()), Err(e)) => Err(e),
+            (Err(removed), Err(restored)) => Err(format!("{}; {}", removed, restored)),
+        }
+    }
+
+    /// Write back the previous content of every replaced file
+    fn restore_replaced_files(&mut self) -> Result<(), String> {
+        let failures: Vec<String> = self
+            .replaced_files
+            .drain(..)
+            .filter_map(|(path, content)| std::fs::write(&path, content).err().map(|e| format!("{}: {}", path.display(), e)))
+            .collect();
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures.join("; "))
+        }
+    }
+
+    /// Remove created paths newest-first; directories are only removed if empty
+    fn remove_created_paths(&mut self) -> Result<(), String> {
+        let mut failures = Vec::new();
+
+        while let Some(path) = self.created_paths.pop() {
+            let result = if path.is_dir() {
+                std::fs::remove_dir(&path)
+            } else {
+                std::fs::remove_file(&path)
+            };
+            if let Err(e) = result {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    failures.push(format!("{}: {}", path.display(), e));
+                }
+            }
+        }
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures.join("; "))
+        }
+    }
+}
+
+#[async_trait]
+impl Command for ExtractArchiveCommand {
+    async fn execute(&mut self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        self.execute_with_progress(fs, None).await
+    }
+
+    async fn undo(&mut self, _fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        if !self.can_undo() {
+            return Err(OperationError::NotExecuted);
+        }
+
+        self.roll_back().map_err(OperationError::UndoFailed)?;
+
+        // Update metadata
+        self.metadata.status = CommandStatus::Undone;
+        self.metadata.undone_at = Some(SystemTime::now());
+
+        Ok(())
+    }
+
+    async fn validate(&self, _fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        if !self.archive.is_file() {
+            return Err(OperationError::ValidationFailed(
+                format!("Archive does not exist: {}", self.archive.display())
+            ));
+        }
+
+        if self.destination.exists() && !self.destination.is_dir() {
+            return Err(OperationError::ValidationFailed(
+                format!("Destination is not a folder: {}", self.destination.display())
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn metadata(&self) -> &CommandMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut CommandMetadata {
+        &mut self.metadata
+    }
+
+    fn description(&self) -> String {
+        format!("Extract {} to {}", self.archive.display(), self.destination.display())
+    }
+
+    fn kind(&self) -> OperationKind {
+        OperationKind::Extract
+    }
+
+    fn source_path(&self) -> Option<&Path> {
+        Some(&self.archive)
+    }
+
+    fn destination_path(&self) -> Option<&Path> {
+        Some(&self.destination)
+    }
+
+    async fn execute_with_progress(
+        &mut self,
+        fs: Arc<dyn FileSystemService>,
+        progress: Option<&mut ProgressTracker>,
+    ) -> OperationResult<()> {
+        self.validate(fs).await?;
+        self.run(progress)
+    }
+
+    async fn estimate_work(&self, _fs: Arc<dyn FileSystemService>) -> OperationResult<(u64, u64)> {
+        let file = File::open(&self.archive)
+            .map_err(|e| OperationError::FileSystem(FileSystemError::from_io_error(e, &self.archive)))?;
+        let mut archive = ZipArchive::new(file)
+            .map_err(|e| OperationError::ExecutionFailed(format!("Failed to read archive: {}", e)))?;
+
+        let mut files = 0;
+        let mut bytes = 0u64;
+        for index in 0..archive.len() {
+            if let Ok(entry) = archive.by_index_raw(index) {
+                if !entry.is_dir() {
+                    files += 1;
+                    bytes = bytes.saturating_add(entry.size());
+                }
+            }
+        }
+        Ok((files, bytes))
+    }
+
+    fn supports_progress(&self) -> bool {
+        true
+    }
+
+    fn must_run_serially(&self) -> bool {
+        true
+    }
+}
+
+/// Zip next to the selection named after a single item, or `Archive.zip`, made unique
+pub fn default_archive_path(selection: &[PathBuf]) -> Option<PathBuf> {
+    let parent = selection.first()?.parent()?;
+    let name = match selection {
+        [single] => single
+            .file_stem()
+            .map(|stem| format!("{}.zip", stem.to_string_lossy()))
+            .unwrap_or_else(|| "Archive.zip".to_string()),
+        _ => "Archive.zip".to_string(),
+    };
+    Some(unique_sibling_path(&parent.join(name)))
+}
+
+/// Compress command
+///
+/// Streams the selected files and folders into a new zip archive, keeping
+/// folder structure and renaming clashing top-level names. Undo removes the
+/// archive.
+#[derive(Debug, Clone)]
+pub struct CompressCommand {
+    pub selection: Vec<PathBuf>,
+    pub destination: PathBuf,
+
+    // Result of the last execution
+    summary: Option<ZipExportSummary>,
+
+    metadata: CommandMetadata,
+}
+
+impl CompressCommand {
+    /// Create a new compress command writing `destination`
+    pub fn new(selection: Vec<PathBuf>, destination: PathBuf) -> Self {
+        Self {
+            selection,
+            destination,
+            summary: None,
+            metadata: CommandMetadata::default(),
+        }
+    }
+
+    /// What the last execution wrote
+    pub fn summary(&self) -> Option<&ZipExportSummary> {
+        self.summary.as_ref()
+    }
+
+    /// Write the archive synchronously; a partial archive is removed on failure
+    pub fn run(&mut self, progress: Option<&mut ProgressTracker>) -> OperationResult<()> {
+        if self.is_executed() {
+            return Err(OperationError::AlreadyExecuted);
+        }
+
+        match export_selection_as_zip(&self.selection, &self.destination, progress) {
+            Ok(summary) => {
+                self.summary = Some(summary);
+                self.metadata.status = CommandStatus::Executed;
+                self.metadata.executed_at = Some(SystemTime::now());
+                Ok(())
+            }
+            Err(e) => {
+                self.metadata.status = CommandStatus::Failed;
+                self.metadata.error_message = Some(e.to_string());
+                Err(e)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Command for CompressCommand {
+    async fn execute(&mut self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        self.execute_with_progress(fs, None).await
+    }
+
+    async fn undo(&mut self, _fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        if !self.can_undo() {
+            return Err(OperationError::NotExecuted);
+        }
+
+        std::fs::remove_file(&self.destination)
+            .map_err(|e| OperationError::UndoFailed(format!("{}: {}", self.destination.display(), e)))?;
+
+        // Update metadata
+        self.metadata.status = CommandStatus::Undone;
+        self.metadata.undone_at = Some(SystemTime::now());
+
+        Ok(())
+    }
+
+    async fn validate(&self, _fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        if self.selection.is_empty() {
+            return Err(OperationError::ValidationFailed("Nothing selected to compress".to_string()));
+        }
+
+        if let Some(missing) = self.selection.iter().find(|path| !path.exists()) {
+            return Err(OperationError::ValidationFailed(
+                format!("Source does not exist: {}", missing.display())
+            ));
+        }
+
+        // Undo deletes the archive, so never write over an existing file
+        if self.destination.exists() {
+            return Err(OperationError::FileSystem(FileSystemError::FileAlreadyExists {
+                path: self.destination.clone(),
+            }));
+        }
+
+        Ok(())
+    }
+
+    fn metadata(&self) -> &CommandMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut CommandMetadata {
+        &mut self.metadata
+    }
+
+    fn description(&self) -> String {
+        format!("Compress {} items to {}", self.selection.len(), self.destination.display())
+    }
+
+    fn kind(&self) -> OperationKind {
+        OperationKind::Compress
+    }
+
+    fn source_path(&self) -> Option<&Path> {
+        self.selection.first().map(PathBuf::as_path)
+    }
+
+    fn destination_path(&self) -> Option<&Path> {
+        Some(&self.destination)
+    }
+
+    async fn execute_with_progress(
+        &mut self,
+        fs: Arc<dyn FileSystemService>,
+        progress: Option<&mut ProgressTracker>,
+    ) -> OperationResult<()> {
+        self.validate(fs).await?;
+        self.run(progress)
+    }
+
+    async fn estimate_work(&self, _fs: Arc<dyn FileSystemService>) -> OperationResult<(u64, u64)> {
+        let entries = plan_zip_entries(&self.selection)?;
+        let files = entries.iter().filter(|entry| !entry.is_dir).count() as u64;
+        let bytes = entries.iter().map(|entry| entry.size).sum();
+        Ok((files, bytes))
+    }
+
+    fn supports_progress(&self) -> bool {
+        true
+    }
+
+    fn must_run_serially(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use zip::ZipArchive;
+
+    fn write(path: &Path, contents: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn test_zip_export_contains_expected_entries_and_round_trips() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        write(&root.join("album/a.txt"), "alpha");
+        write(&root.join("album/nested/b.txt"), "bravo");
+        write(&root.join("one/photo.jpg"), "first photo");
+        write(&root.join("two/photo.jpg"), "second photo");
+
+        let selection = vec![
+            root.join("album"),
+            root.join("one/photo.jpg"),
+            root.join("two/photo.jpg"),
+        ];
+        let archive_path = root.join("export.zip");
+        let mut tracker = ProgressTracker::new(4, 0, "Export".to_string());
+
+        let summary = export_selection_as_zip(&selection, &archive_path, Some(&mut tracker)).unwrap();
+        assert_eq!(summary.file_count, 4);
+        assert_eq!(summary.original_size, 5 + 5 + 11 + 12);
+        assert!(summary.compressed_size > 0);
+        assert!(!partial_copy_path(&archive_path).exists());
+
+        let mut archive = ZipArchive::new(File::open(&archive_path).unwrap()).unwrap();
+        let mut names: Vec<String> = archive.file_names().map(String::from).collect();
+        names.sort();
+        assert_eq!(names, vec![
+            "album/",
+            "album/a.txt",
+            "album/nested/",
+            "album/nested/b.txt",
+            "photo (2).jpg",
+            "photo.jpg",
+        ]);
+
+        let extract_dir = TempDir::new().unwrap();
+        archive.extract(extract_dir.path()).unwrap();
+        let read = |rel: &str| std::fs::read_to_string(extract_dir.path().join(rel)).unwrap();
+        assert_eq!(read("album/a.txt"), "alpha");
+        assert_eq!(read("album/nested/b.txt"), "bravo");
+        assert_eq!(read("photo.jpg"), "first photo");
+        assert_eq!(read("photo (2).jpg"), "second photo");
+    }
+
+    fn write_archive(path: &Path, entries: &[(&str, &str)]) {
+        let mut writer = ZipWriter::new(File::create(path).unwrap());
+        for (name, contents) in entries {
+            writer.start_file(*name, FileOptions::default()).unwrap();
+            writer.write_all(contents.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    #[test]
+    fn test_safe_entry_path_rejects_zip_slip() {
+        let destination = Path::new("/tmp/extract");
+        assert_eq!(safe_entry_path(destination, "a/b.txt").unwrap(), destination.join("a/b.txt"));
+        assert_eq!(safe_entry_path(destination, "./a.txt").unwrap(), destination.join("a.txt"));
+        assert!(safe_entry_path(destination, "../evil.txt").is_err());
+        assert!(safe_entry_path(destination, "a/../../evil.txt").is_err());
+        assert!(safe_entry_path(destination, "/etc/passwd").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_extract_rejects_zip_slip_archive_without_writing() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("evil.zip");
+        write_archive(&archive_path, &[("ok.txt", "fine"), ("../escaped.txt", "gotcha")]);
+
+        let fs: Arc<dyn FileSystemService> = Arc::new(crate::services::file_system::NativeFileSystemService::new());
+        let mut command = ExtractArchiveCommand::extract_here(archive_path.clone());
+        let result = command.execute(fs).await;
+
+        assert!(matches!(result, Err(OperationError::ValidationFailed(_))));
+        assert!(!temp_dir.path().join("escaped.txt").exists());
+        assert!(!command.destination.exists());
+    }
+
+    #[tokio::test]
+    async fn test_extract_undo_removes_created_files_only() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("photos.zip");
+        write_archive(&archive_path, &[("a.txt", "alpha"), ("nested/b.txt", "bravo")]);
+
+        // Extract into an existing folder that already holds a conflicting file
+        let destination = temp_dir.path().join("target");
+        std::fs::create_dir(&destination).unwrap();
+        std::fs::write(destination.join("a.txt"), "existing").unwrap();
+
+        let fs: Arc<dyn FileSystemService> = Arc::new(crate::services::file_system::NativeFileSystemService::new());
+        let mut command = ExtractArchiveCommand::new(archive_path, destination.clone());
+        let mut tracker = ProgressTracker::new(2, 10, "Extract".to_string());
+        command.execute_with_progress(fs.clone(), Some(&mut tracker)).await.unwrap();
+
+        assert_eq!(std::fs::read_to_string(destination.join("a.txt")).unwrap(), "existing");
+        assert_eq!(std::fs::read_to_string(destination.join("a (2).txt")).unwrap(), "alpha");
+        assert_eq!(std::fs::read_to_string(destination.join("nested/b.txt")).unwrap(), "bravo");
+        assert_eq!(tracker.progress().bytes_processed, 10);
+
+        command.undo(fs).await.unwrap();
+        assert!(!destination.join("a (2).txt").exists());
+        assert!(!destination.join("nested").exists());
+        assert_eq!(std::fs::read_to_string(destination.join("a.txt")).unwrap(), "existing");
+    }
+
+    #[tokio::test]
+    async fn test_extract_overwrite_reports_conflicts_and_undo_restores_them() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("photos.zip");
+        write_archive(&archive_path, &[("a.txt", "alpha"), ("nested/b.txt", "bravo")]);
+
+        let destination = temp_dir.path().join("target");
+        write(&destination.join("a.txt"), "existing");
+
+        let fs: Arc<dyn FileSystemService> = Arc::new(crate::services::file_system::NativeFileSystemService::new());
+        let mut command = ExtractArchiveCommand::new(archive_path, destination.clone())
+            .with_conflict_policy(ConflictResolution::Overwrite.into());
+        assert_eq!(command.conflicts().unwrap(), vec![destination.join("a.txt")]);
+
+        command.execute(fs.clone()).await.unwrap();
+        assert_eq!(std::fs::read_to_string(destination.join("a.txt")).unwrap(), "alpha");
+        assert!(!command.created_paths().contains(&destination.join("a.txt")));
+
+        command.undo(fs).await.unwrap();
+        assert_eq!(std::fs::read_to_string(destination.join("a.txt")).unwrap(), "existing");
+        assert!(!destination.join("nested").exists());
+        assert_eq!(ExtractConflictPolicy::from(ConflictResolution::Ask), ExtractConflictPolicy::Fail);
+    }
+
+    #[tokio::test]
+    async fn test_extract_aborts_past_size_threshold() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("bomb.zip");
+        let payload = "0".repeat(4096);
+        write_archive(&archive_path, &[("big.txt", payload.as_str())]);
+
+        let fs: Arc<dyn FileSystemService> = Arc::new(crate::services::file_system::NativeFileSystemService::new());
+        let mut command = ExtractArchiveCommand::extract_here(archive_path).with_max_total_size(1024);
+
+        assert!(matches!(command.execute(fs).await, Err(OperationError::ValidationFailed(_))));
+        assert!(!command.destination.exists());
+    }
+
+    #[test]
+    fn test_zip_export_cancellation_removes_partial_archive() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("data.bin");
+        std::fs::write(&source, vec![7u8; 1024]).unwrap();
+
+        let archive_path = temp_dir.path().join("out.zip");
+        let mut tracker = ProgressTracker::new(1, 1024, "Export".to_string());
+        tracker.cancellation_token().cancel();
+
+        let result = export_selection_as_zip(&[source], &archive_path, Some(&mut tracker));
+        assert!(matches!(result, Err(OperationError::Cancelled)));
+        assert!(!archive_path.exists());
+        assert!(!partial_copy_path(&archive_path).exists());
+    }
+
+    #[tokio::test]
+    async fn test_compress_command_keeps_structure_and_undo_removes_archive() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        write(&root.join("trip/day1/beach.jpg"), "sand");
+        write(&root.join("trip/notes.txt"), "notes");
+        write(&root.join("a/notes.txt"), "other notes");
+        std::fs::create_dir_all(root.join("trip/empty")).unwrap();
+
+        let selection = vec![root.join("trip"), root.join("a/notes.txt")];
+        let destination = default_archive_path(&selection[..1]).unwrap();
+        assert_eq!(destination, root.join("trip.zip"));
+
+        let fs: Arc<dyn FileSystemService> = Arc::new(crate::services::file_system::NativeFileSystemService::new());
+        let mut command = CompressCommand::new(selection, destination.clone());
+        assert_eq!(command.estimate_work(fs.clone()).await.unwrap(), (3, 4 + 5 + 11));
+        let mut tracker = ProgressTracker::new(3, 20, "Compress".to_string());
+        command.execute_with_progress(fs.clone(), Some(&mut tracker)).await.unwrap();
+        assert_eq!(command.summary().unwrap().file_count, 3);
+        assert_eq!(tracker.progress().bytes_processed, 20);
+
+        let mut archive = ZipArchive::new(File::open(&destination).unwrap()).unwrap();
+        let mut names: Vec<String> = archive.file_names().map(String::from).collect();
+        names.sort();
+        assert_eq!(names, vec![
+            "notes.txt",
+            "trip/",
+            "trip/day1/",
+            "trip/day1/beach.jpg",
+            "trip/empty/",
+            "trip/notes.txt",
+        ]);
+        let mut contents = String::new();
+        archive.by_name("trip/day1/beach.jpg").unwrap().read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, "sand");
+
+        // A second run would overwrite the archive, so it is refused
+        let mut again = CompressCommand::new(vec![root.join("trip")], destination.clone());
+        assert!(matches!(again.execute(fs.clone()).await, Err(OperationError::FileSystem(_))));
+        assert_eq!(default_archive_path(&[root.join("trip")]).unwrap(), root.join("trip (2).zip"));
+
+        command.undo(fs).await.unwrap();
+        assert!(!destination.exists());
+        assert!(root.join("trip/day1/beach.jpg").exists());
+    }
+}
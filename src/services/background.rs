@@ -1,6 +1,9 @@
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
+use chrono::Timelike;
+use serde::{Deserialize, Serialize};
 use tokio::sync::{Mutex, RwLock};
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
@@ -156,6 +159,357 @@ impl ProgressInfo {
 /// Progress callback function type
 pub type ProgressCallback = Arc<dyn Fn(ProgressInfo) + Send + Sync>;
 
+/// Scheduling priority of a background task
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TaskPriority {
+    /// Work the user is waiting on; never throttled
+    #[default]
+    Foreground,
+    /// Low-priority work such as indexing; subject to the throttle policy
+    Background,
+}
+
+/// Operating system I/O priority of hashing worker threads
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum IoPriority {
+    #[default]
+    Normal,
+    /// Idle I/O class and lowest CPU priority where the platform supports it,
+    /// so interactive reads such as previews go first
+    Low,
+}
+
+/// Lower the I/O and CPU priority of the calling thread
+///
+/// Best effort: failures are logged and the thread keeps its priority.
+fn lower_current_thread_priority() {
+    #[cfg(target_os = "linux")]
+    unsafe {
+        // ioprio_set(IOPRIO_WHO_PROCESS, tid, IOPRIO_CLASS_IDLE << IOPRIO_CLASS_SHIFT)
+        const IOPRIO_WHO_PROCESS: libc::c_int = 1;
+        const IOPRIO_CLASS_IDLE: libc::c_int = 3;
+        let tid = libc::gettid();
+        if libc::syscall(libc::SYS_ioprio_set, IOPRIO_WHO_PROCESS, tid, IOPRIO_CLASS_IDLE << 13) != 0 {
+            debug!("ioprio_set failed: {}", std::io::Error::last_os_error());
+        }
+        if libc::setpriority(libc::PRIO_PROCESS as _, tid as libc::id_t, 19) != 0 {
+            debug!("setpriority failed: {}", std::io::Error::last_os_error());
+        }
+    }
+    
+    #[cfg(target_os = "macos")]
+    unsafe {
+        extern "C" {
+            fn setiopolicy_np(iotype: libc::c_int, scope: libc::c_int, policy: libc::c_int) -> libc::c_int;
+        }
+        const IOPOL_TYPE_DISK: libc::c_int = 0;
+        const IOPOL_SCOPE_THREAD: libc::c_int = 1;
+        const IOPOL_THROTTLE: libc::c_int = 3;
+        if setiopolicy_np(IOPOL_TYPE_DISK, IOPOL_SCOPE_THREAD, IOPOL_THROTTLE) != 0 {
+            debug!("setiopolicy_np failed: {}", std::io::Error::last_os_error());
+        }
+    }
+    
+    #[cfg(windows)]
+    unsafe {
+        use winapi::um::processthreadsapi::{GetCurrentThread, SetThreadPriority};
+        use winapi::um::winbase::THREAD_MODE_BACKGROUND_BEGIN;
+        if SetThreadPriority(GetCurrentThread(), THREAD_MODE_BACKGROUND_BEGIN as i32) == 0 {
+            debug!("SetThreadPriority failed: {}", std::io::Error::last_os_error());
+        }
+    }
+}
+
+/// How low-priority work is held back while a throttle condition applies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ThrottleMode {
+    /// Stop starting new work until the condition clears
+    #[default]
+    Pause,
+    /// Keep working, but sleep between files
+    Throttle,
+}
+
+/// Daily quiet-hours window in local time, e.g. 22 → 7
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuietHours {
+    /// First hour (0-23) inside the window
+    pub start_hour: u8,
+    /// First hour (0-23) after the window
+    pub end_hour: u8,
+}
+
+impl QuietHours {
+    /// Check whether the given hour falls inside the window, wrapping past midnight
+    pub fn contains(&self, hour: u32) -> bool {
+        let (start, end) = (self.start_hour as u32 % 24, self.end_hour as u32 % 24);
+        if start == end {
+            false
+        } else if start < end {
+            hour >= start && hour < end
+        } else {
+            hour >= start || hour < end
+        }
+    }
+}
+
+/// User-facing settings for the background throttle policy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackgroundThrottleSettings {
+    /// Master switch for the policy
+    pub enabled: bool,
+    /// Optional do-not-disturb window
+    pub quiet_hours: Option<QuietHours>,
+    /// Hold back low-priority work while running on battery
+    pub pause_on_battery: bool,
+    /// Hold back low-priority work while the app is not the foreground window
+    pub pause_when_unfocused: bool,
+    /// Pause entirely or just slow down
+    pub mode: ThrottleMode,
+    /// Delay between files in throttle mode
+    pub throttle_delay_ms: u64,
+}
+
+impl Default for BackgroundThrottleSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            quiet_hours: None,
+            pause_on_battery: true,
+            pause_when_unfocused: false,
+            mode: ThrottleMode::Pause,
+            throttle_delay_ms: 250,
+        }
+    }
+}
+
+/// Why low-priority work is being held back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThrottleReason {
+    QuietHours,
+    OnBattery,
+    Unfocused,
+}
+
+impl ThrottleReason {
+    /// Short human-readable description
+    pub fn label(&self) -> &'static str {
+        match self {
+            ThrottleReason::QuietHours => "quiet hours",
+            ThrottleReason::OnBattery => "on battery",
+            ThrottleReason::Unfocused => "app in background",
+        }
+    }
+}
+
+/// Current effect of the throttle policy on low-priority work
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThrottleState {
+    Running,
+    Throttled(ThrottleReason),
+    Paused(ThrottleReason),
+}
+
+impl ThrottleState {
+    /// Status text for the background tasks display
+    pub fn label(&self) -> String {
+        match self {
+            ThrottleState::Running => "Background tasks running".to_string(),
+            ThrottleState::Throttled(reason) => format!("Background tasks throttled ({})", reason.label()),
+            ThrottleState::Paused(reason) => format!("Background tasks paused ({})", reason.label()),
+        }
+    }
+}
+
+/// How often a paused task re-checks the policy
+const THROTTLE_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Shortest time between two reads of the power source
+const POWER_SOURCE_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Shared policy deciding when low-priority background work may run
+#[derive(Debug)]
+pub struct ThrottlePolicy {
+    settings: std::sync::RwLock<BackgroundThrottleSettings>,
+    app_focused: AtomicBool,
+    on_battery: AtomicBool,
+    last_power_check: std::sync::Mutex<Option<Instant>>,
+}
+
+impl Default for ThrottlePolicy {
+    fn default() -> Self {
+        Self::new(BackgroundThrottleSettings::default())
+    }
+}
+
+impl ThrottlePolicy {
+    /// Create a policy with the given settings, assuming a focused app on mains power
+    pub fn new(settings: BackgroundThrottleSettings) -> Self {
+        Self {
+            settings: std::sync::RwLock::new(settings),
+            app_focused: AtomicBool::new(true),
+            on_battery: AtomicBool::new(false),
+            last_power_check: std::sync::Mutex::new(None),
+        }
+    }
+
+    /// Replace the policy settings
+    pub fn update_settings(&self, settings: BackgroundThrottleSettings) {
+        if let Ok(mut current) = self.settings.write() {
+            *current = settings;
+        }
+    }
+
+    /// Get a copy of the policy settings
+    pub fn settings(&self) -> BackgroundThrottleSettings {
+        self.settings.read().map(|s| s.clone()).unwrap_or_default()
+    }
+
+    /// Record whether the main window currently has focus
+    pub fn set_app_focused(&self, focused: bool) {
+        self.app_focused.store(focused, Ordering::SeqCst);
+    }
+
+    /// Record whether the machine is running on battery
+    pub fn set_on_battery(&self, on_battery: bool) {
+        self.on_battery.store(on_battery, Ordering::SeqCst);
+    }
+
+    /// Re-read the power source from the operating system, at most every few seconds
+    pub fn refresh_power_source(&self) {
+        if let Ok(mut last_check) = self.last_power_check.lock() {
+            if last_check.is_some_and(|checked| checked.elapsed() < POWER_SOURCE_CHECK_INTERVAL) {
+                return;
+            }
+            *last_check = Some(Instant::now());
+        }
+        if let Some(on_battery) = detect_on_battery() {
+            self.set_on_battery(on_battery);
+        }
+    }
+
+    /// Evaluate the policy now, re-reading the power source first when it matters
+    pub fn refreshed_state(&self) -> ThrottleState {
+        if self.settings().pause_on_battery {
+            self.refresh_power_source();
+        }
+        self.current_state()
+    }
+
+    /// Evaluate the policy at the current local time
+    pub fn current_state(&self) -> ThrottleState {
+        self.evaluate(chrono::Local::now().hour())
+    }
+
+    /// Evaluate the policy for the given local hour
+    pub fn evaluate(&self, hour: u32) -> ThrottleState {
+        let settings = self.settings();
+        if !settings.enabled {
+            return ThrottleState::Running;
+        }
+
+        let reason = if settings.quiet_hours.map_or(false, |q| q.contains(hour)) {
+            Some(ThrottleReason::QuietHours)
+        } else if settings.pause_on_battery && self.on_battery.load(Ordering::SeqCst) {
+            Some(ThrottleReason::OnBattery)
+        } else if settings.pause_when_unfocused && !self.app_focused.load(Ordering::SeqCst) {
+            Some(ThrottleReason::Unfocused)
+        } else {
+            None
+        };
+
+        match (reason, settings.mode) {
+            (None, _) => ThrottleState::Running,
+            (Some(reason), ThrottleMode::Pause) => ThrottleState::Paused(reason),
+            (Some(reason), ThrottleMode::Throttle) => ThrottleState::Throttled(reason),
+        }
+    }
+
+    /// Wait until low-priority work may proceed; returns false if cancelled while waiting
+    pub async fn wait_until_allowed(&self, cancellation_token: &CancellationToken) -> bool {
+        loop {
+            let delay = match self.refreshed_state() {
+                ThrottleState::Running => return true,
+                ThrottleState::Throttled(_) => Duration::from_millis(self.settings().throttle_delay_ms),
+                ThrottleState::Paused(_) => THROTTLE_POLL_INTERVAL,
+            };
+
+            tokio::select! {
+                _ = cancellation_token.cancelled() => return false,
+                _ = tokio::time::sleep(delay) => {}
+            }
+
+            if matches!(self.current_state(), ThrottleState::Throttled(_)) {
+                return true;
+            }
+        }
+    }
+}
+
+/// Best-effort check of whether the machine is on battery power
+#[cfg(target_os = "linux")]
+pub fn detect_on_battery() -> Option<bool> {
+    let entries = std::fs::read_dir("/sys/class/power_supply").ok()?;
+    let mut saw_battery = false;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let kind = std::fs::read_to_string(path.join("type")).unwrap_or_default();
+        match kind.trim() {
+            "Mains" => {
+                let online = std::fs::read_to_string(path.join("online")).unwrap_or_default();
+                if online.trim() == "1" {
+                    return Some(false);
+                }
+            }
+            "Battery" => saw_battery = true,
+            _ => {}
+        }
+    }
+
+    if saw_battery { Some(true) } else { None }
+}
+
+/// Best-effort check of whether the machine is on battery power
+#[cfg(target_os = "macos")]
+pub fn detect_on_battery() -> Option<bool> {
+    let output = std::process::Command::new("pmset").args(["-g", "batt"]).output().ok()?;
+    parse_pmset_power_source(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Power source from `pmset -g batt`, whose first line reads "Now drawing from 'AC Power'"
+#[cfg(any(target_os = "macos", test))]
+fn parse_pmset_power_source(output: &str) -> Option<bool> {
+    let source = output.lines().next()?.split('\'').nth(1)?;
+    match source {
+        "Battery Power" => Some(true),
+        "AC Power" | "UPS Power" => Some(false),
+        _ => None,
+    }
+}
+
+/// Best-effort check of whether the machine is on battery power
+#[cfg(windows)]
+pub fn detect_on_battery() -> Option<bool> {
+    use winapi::um::winbase::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+    let mut status: SYSTEM_POWER_STATUS = unsafe { std::mem::zeroed() };
+    if unsafe { GetSystemPowerStatus(&mut status) } == 0 {
+        return None;
+    }
+    match status.ACLineStatus {
+        0 => Some(true),
+        1 => Some(false),
+        _ => None,
+    }
+}
+
+/// Best-effort check of whether the machine is on battery power
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+pub fn detect_on_battery() -> Option<bool> {
+    None
+}
+
 /// Background task for file hashing
 pub struct HashingTask {
     /// Unique task identifier
@@ -168,6 +522,14 @@ pub struct HashingTask {
     pub progress_callback: ProgressCallback,
     /// Cancellation token
     pub cancellation_token: CancellationToken,
+    /// Scheduling priority
+    pub priority: TaskPriority,
+    /// Number of files hashed at once
+    pub concurrency: usize,
+    /// I/O priority of the hashing threads
+    pub io_priority: IoPriority,
+    /// Hash in chunks with no size limit, stopping mid-file when cancelled
+    pub streaming: bool,
 }
 
 impl std::fmt::Debug for HashingTask {
@@ -177,6 +539,10 @@ impl std::fmt::Debug for HashingTask {
             .field("files", &self.files)
             .field("total_size", &self.total_size)
             .field("cancellation_token", &self.cancellation_token)
+            .field("priority", &self.priority)
+            .field("concurrency", &self.concurrency)
+            .field("io_priority", &self.io_priority)
+            .field("streaming", &self.streaming)
             .finish()
     }
 }
@@ -194,15 +560,49 @@ impl HashingTask {
             total_size,
             progress_callback,
             cancellation_token: CancellationToken::new(),
+            priority: TaskPriority::default(),
+            concurrency: 1,
+            io_priority: IoPriority::default(),
+            streaming: false,
         }
     }
     
+    /// Set the scheduling priority
+    pub fn with_priority(mut self, priority: TaskPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+    
+    /// Set the number of worker threads, at least one
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+    
+    /// Set the I/O priority of the worker threads
+    pub fn with_io_priority(mut self, io_priority: IoPriority) -> Self {
+        self.io_priority = io_priority;
+        self
+    }
+    
+    /// Hash files chunk by chunk so large files neither hit the size limit nor delay cancelling
+    pub fn with_streaming(mut self, streaming: bool) -> Self {
+        self.streaming = streaming;
+        self
+    }
+    
     /// Get cancellation token for this task
     pub fn cancellation_token(&self) -> CancellationToken {
         self.cancellation_token.clone()
     }
 }
 
+/// What a worker thread found for one file
+enum HashOutcome {
+    NoMetadata(String),
+    Hashed(u64, Result<FileHash, String>),
+}
+
 /// Results from a completed hashing task
 #[derive(Debug, Clone)]
 pub struct HashingTaskResult {
@@ -225,6 +625,8 @@ pub struct BackgroundProcessor {
     running_tasks: Arc<RwLock<std::collections::HashMap<Uuid, CancellationToken>>>,
     /// Task results
     completed_tasks: Arc<Mutex<std::collections::HashMap<Uuid, HashingTaskResult>>>,
+    /// Policy gating low-priority tasks
+    throttle_policy: Arc<ThrottlePolicy>,
 }
 
 impl BackgroundProcessor {
@@ -234,9 +636,26 @@ impl BackgroundProcessor {
             hashing_service: Arc::new(hashing_service),
             running_tasks: Arc::new(RwLock::new(std::collections::HashMap::new())),
             completed_tasks: Arc::new(Mutex::new(std::collections::HashMap::new())),
+            throttle_policy: Arc::new(ThrottlePolicy::default()),
         }
     }
     
+    /// Use a shared throttle policy
+    pub fn with_throttle_policy(mut self, throttle_policy: Arc<ThrottlePolicy>) -> Self {
+        self.throttle_policy = throttle_policy;
+        self
+    }
+    
+    /// Get the throttle policy
+    pub fn throttle_policy(&self) -> Arc<ThrottlePolicy> {
+        self.throttle_policy.clone()
+    }
+    
+    /// Get the current throttle state for low-priority tasks
+    pub fn throttle_state(&self) -> ThrottleState {
+        self.throttle_policy.current_state()
+    }
+    
     /// Start a hashing task in the background
     pub async fn start_hashing_task(&self, task: HashingTask) -> BackgroundResult<Uuid> {
         let task_id = task.id;
@@ -258,10 +677,11 @@ impl BackgroundProcessor {
         let hashing_service = self.hashing_service.clone();
         let running_tasks = self.running_tasks.clone();
         let completed_tasks = self.completed_tasks.clone();
+        let throttle_policy = self.throttle_policy.clone();
         
         // Spawn the background task
         tokio::spawn(async move {
-            let result = Self::execute_hashing_task(hashing_service, task).await;
+            let result = Self::execute_hashing_task(hashing_service, throttle_policy, task).await;
             
             // Remove from running tasks
             {
@@ -283,6 +703,7 @@ impl BackgroundProcessor {
     /// Execute a hashing task
     async fn execute_hashing_task(
         hashing_service: Arc<HashingService>,
+        throttle_policy: Arc<ThrottlePolicy>,
         task: HashingTask,
     ) -> BackgroundResult<HashingTaskResult> {
         let mut progress = ProgressInfo::new(task.id, task.files.len(), task.total_size);
@@ -294,42 +715,97 @@ impl BackgroundProcessor {
         let mut successful_hashes = Vec::new();
         let mut failed_files = Vec::new();
         
-        for file_path in &task.files {
+        // Files are hashed on dedicated threads so their priority can be
+        // lowered without affecting the shared runtime
+        let (work_tx, work_rx) = tokio::sync::mpsc::channel::<PathBuf>(task.concurrency);
+        let work_rx = Arc::new(std::sync::Mutex::new(work_rx));
+        let (result_tx, mut result_rx) = tokio::sync::mpsc::unbounded_channel();
+        for worker in 0..task.concurrency {
+            let work_rx = work_rx.clone();
+            let result_tx = result_tx.clone();
+            let hashing_service = hashing_service.clone();
+            let io_priority = task.io_priority;
+            let streaming = task.streaming;
+            let cancellation_token = task.cancellation_token.clone();
+            std::thread::Builder::new()
+                .name(format!("hash-worker-{}", worker))
+                .spawn(move || {
+                    if io_priority == IoPriority::Low {
+                        lower_current_thread_priority();
+                    }
+                    loop {
+                        let next = work_rx.lock().unwrap_or_else(|e| e.into_inner()).blocking_recv();
+                        let Some(file_path) = next else { break };
+                        let outcome = match std::fs::metadata(&file_path) {
+                            Ok(metadata) => {
+                                let hashed = if streaming {
+                                    hashing_service.hash_file_streaming(&file_path, &cancellation_token)
+                                } else {
+                                    hashing_service.hash_file_blocking(&file_path)
+                                };
+                                HashOutcome::Hashed(metadata.len(), hashed.map_err(|e| e.to_string()))
+                            }
+                            Err(e) => HashOutcome::NoMetadata(e.to_string()),
+                        };
+                        if result_tx.send((file_path, outcome)).is_err() {
+                            break;
+                        }
+                    }
+                })?;
+        }
+        drop(result_tx);
+        
+        // Feed the workers; closing the channel lets them exit
+        let files = task.files.clone();
+        let cancellation_token = task.cancellation_token.clone();
+        let priority = task.priority;
+        tokio::spawn(async move {
+            for file_path in files {
+                if cancellation_token.is_cancelled() {
+                    break;
+                }
+                // Low-priority work waits out quiet hours, battery and unfocused periods
+                if priority == TaskPriority::Background
+                    && !throttle_policy.wait_until_allowed(&cancellation_token).await
+                {
+                    break;
+                }
+                if work_tx.send(file_path).await.is_err() {
+                    break;
+                }
+            }
+        });
+        
+        while let Some((file_path, outcome)) = result_rx.recv().await {
             // Check for cancellation
             if task.cancellation_token.is_cancelled() {
-                progress.mark_cancelled();
-                (task.progress_callback)(progress.clone());
-                return Err(BackgroundError::Cancelled);
+                break;
             }
             
-            // Get file size for progress tracking
-            let file_size = match tokio::fs::metadata(file_path).await {
-                Ok(metadata) => metadata.len(),
-                Err(e) => {
+            match outcome {
+                HashOutcome::NoMetadata(e) => {
                     warn!("Failed to get metadata for {}: {}", file_path.display(), e);
-                    failed_files.push((file_path.clone(), e.to_string()));
-                    continue;
+                    failed_files.push((file_path, e));
                 }
-            };
-            
-            // Hash the file
-            match hashing_service.hash_file(file_path).await {
-                Ok(file_hash) => {
+                HashOutcome::Hashed(file_size, Ok(file_hash)) => {
                     debug!("Successfully hashed: {}", file_path.display());
                     successful_hashes.push(file_hash);
+                    progress.update_progress(file_path, file_size);
+                    (task.progress_callback)(progress.clone());
                 }
-                Err(e) => {
+                HashOutcome::Hashed(file_size, Err(e)) => {
                     warn!("Failed to hash {}: {}", file_path.display(), e);
-                    failed_files.push((file_path.clone(), e.to_string()));
+                    failed_files.push((file_path.clone(), e));
+                    progress.update_progress(file_path, file_size);
+                    (task.progress_callback)(progress.clone());
                 }
             }
-            
-            // Update progress
-            progress.update_progress(file_path.clone(), file_size);
+        }
+        
+        if task.cancellation_token.is_cancelled() {
+            progress.mark_cancelled();
             (task.progress_callback)(progress.clone());
-            
-            // Yield control to allow other tasks to run
-            tokio::task::yield_now().await;
+            return Err(BackgroundError::Cancelled);
         }
         
         // Mark as completed
@@ -577,4 +1053,119 @@ mod tests {
         progress.mark_cancelled();
         assert_eq!(progress.status, TaskStatus::Cancelled);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_quiet_hours_wrap_past_midnight() {
+        let quiet = QuietHours { start_hour: 22, end_hour: 7 };
+        assert!(quiet.contains(23));
+        assert!(quiet.contains(3));
+        assert!(!quiet.contains(7));
+        assert!(!quiet.contains(12));
+        
+        let policy = ThrottlePolicy::new(BackgroundThrottleSettings {
+            quiet_hours: Some(quiet),
+            pause_on_battery: false,
+            ..Default::default()
+        });
+        assert_eq!(policy.evaluate(23), ThrottleState::Paused(ThrottleReason::QuietHours));
+        assert_eq!(policy.evaluate(12), ThrottleState::Running);
+    }
+    
+    #[test]
+    fn test_pmset_power_source() {
+        let on_battery = "Now drawing from 'Battery Power'\n -InternalBattery-0 (id=1234)\t87%; discharging;";
+        assert_eq!(parse_pmset_power_source(on_battery), Some(true));
+        assert_eq!(parse_pmset_power_source("Now drawing from 'AC Power'\n"), Some(false));
+        assert_eq!(parse_pmset_power_source(""), None);
+    }
+    
+    #[tokio::test]
+    async fn test_low_priority_task_deferred_while_paused() {
+        let temp_file = NamedTempFile::new().unwrap();
+        {
+            let mut file = tokio::fs::File::create(temp_file.path()).await.unwrap();
+            file.write_all(b"deferred").await.unwrap();
+            file.flush().await.unwrap();
+        }
+        
+        let policy = Arc::new(ThrottlePolicy::new(BackgroundThrottleSettings {
+            pause_on_battery: false,
+            pause_when_unfocused: true,
+            ..Default::default()
+        }));
+        policy.set_app_focused(false);
+        
+        let processor = BackgroundProcessor::default().with_throttle_policy(policy.clone());
+        assert_eq!(processor.throttle_state(), ThrottleState::Paused(ThrottleReason::Unfocused));
+        
+        let files_processed = Arc::new(AtomicUsize::new(0));
+        let files_processed_clone = files_processed.clone();
+        let callback = Arc::new(move |progress: ProgressInfo| {
+            files_processed_clone.store(progress.files_processed, Ordering::SeqCst);
+        });
+        
+        let task = HashingTask::new(vec![temp_file.path().to_path_buf()], 8, callback)
+            .with_priority(TaskPriority::Background);
+        let task_id = processor.start_hashing_task(task).await.unwrap();
+        
+        // Deferred while the pause predicate holds
+        tokio::time::sleep(Duration::from_millis(600)).await;
+        assert!(processor.is_task_running(task_id).await);
+        assert_eq!(files_processed.load(Ordering::SeqCst), 0);
+        
+        // Resumes automatically once the app regains focus
+        policy.set_app_focused(true);
+        for _ in 0..50 {
+            if !processor.is_task_running(task_id).await {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        
+        let result = processor.get_task_result(task_id).await.unwrap();
+        assert_eq!(result.successful_hashes.len(), 1);
+        assert_eq!(files_processed.load(Ordering::SeqCst), 1);
+    }
+    
+    #[tokio::test]
+    async fn test_parallel_low_priority_hashing_matches_sequential() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let files: Vec<PathBuf> = (0..8)
+            .map(|i| {
+                let path = dir.path().join(format!("file{}.bin", i));
+                std::fs::write(&path, vec![i as u8 % 3; 4096]).unwrap();
+                path
+            })
+            .collect();
+        
+        let processor = BackgroundProcessor::default();
+        let mut hashes = Vec::new();
+        for (concurrency, io_priority) in [(1, IoPriority::Normal), (4, IoPriority::Low)] {
+            let task = HashingTask::new(files.clone(), 8 * 4096, Arc::new(|_progress: ProgressInfo| {}))
+                .with_concurrency(concurrency)
+                .with_io_priority(io_priority);
+            let task_id = processor.start_hashing_task(task).await.unwrap();
+            for _ in 0..50 {
+                if !processor.is_task_running(task_id).await {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+            
+            let result = processor.get_task_result(task_id).await.unwrap();
+            assert_eq!(result.final_progress.status, TaskStatus::Completed);
+            assert_eq!(result.final_progress.files_processed, 8);
+            assert_eq!(result.final_progress.bytes_processed, 8 * 4096);
+            let mut by_path: Vec<(PathBuf, String)> = result
+                .successful_hashes
+                .into_iter()
+                .map(|hash| (hash.path, hash.hash))
+                .collect();
+            by_path.sort();
+            hashes.push(by_path);
+        }
+        assert_eq!(hashes[0], hashes[1]);
+        assert_eq!(hashes[0][0].1, hashes[0][3].1);
+        assert_ne!(hashes[0][0].1, hashes[0][1].1);
+    }
+}
@@ -0,0 +1,453 @@
+//! User-defined file classification rules
+//!
+//! Built-in classification comes from [`FileType`]. Users can add rules in
+//! `*.toml` files under the classifiers config directory; the first matching
+//! rule wins over the built-in category. Rules look like:
+//!
+//! ```toml
+//! [[rule]]
+//! extensions = ["cr2", "nef"]
+//! category = "Images"
+//! label = "Camera RAW"
+//! accent = "#e67e22"
+//!
+//! [[rule]]
+//! name = "Screenshot*"
+//! category = "Screenshots"
+//! ```
+//!
+//! A file or rule that fails to load is recorded in [`Classifier::errors`]
+//! and skipped; the remaining rules still apply.
+
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+use super::file_system::{FileEntry, FileType};
+
+/// Problems found while loading classification rules
+#[derive(Debug, Error)]
+pub enum ClassifierError {
+    #[error("Could not read {path}: {source}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Could not parse {path}: {message}")]
+    Parse { path: PathBuf, message: String },
+
+    #[error("Rule {index} in {path} is invalid: {reason}")]
+    InvalidRule { path: PathBuf, index: usize, reason: String },
+}
+
+/// Category and label a file is shown and organized under
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Classification {
+    /// Group used for organize-by-type folders and category filters
+    pub category: String,
+    /// Short description, e.g. "Camera RAW"
+    pub label: String,
+    /// Icon color set by a user rule
+    pub accent: Option<String>,
+}
+
+impl Classification {
+    fn builtin(category: &str, label: &str) -> Self {
+        Self {
+            category: category.to_string(),
+            label: label.to_string(),
+            accent: None,
+        }
+    }
+}
+
+/// Categories the built-in classification produces
+pub const BUILTIN_CATEGORIES: [&str; 8] =
+    ["Folders", "Images", "Videos", "Audio", "Documents", "Text", "Archives", "Other"];
+
+/// Built-in classification from the file type
+pub fn builtin_classification(path: &Path, is_directory: bool) -> Classification {
+    if is_directory {
+        return Classification::builtin("Folders", "Folder");
+    }
+    if path.extension().is_none() {
+        return Classification::builtin("Other", "File");
+    }
+    type_classification(&FileType::from_path(path))
+}
+
+/// Built-in classification of a known file type
+pub fn type_classification(file_type: &FileType) -> Classification {
+    match file_type {
+        FileType::Directory => Classification::builtin("Folders", "Folder"),
+        FileType::Image(_) => Classification::builtin("Images", "Image"),
+        FileType::Video(_) => Classification::builtin("Videos", "Video"),
+        FileType::Audio(_) => Classification::builtin("Audio", "Audio"),
+        FileType::Document(_) => Classification::builtin("Documents", "Document"),
+        FileType::Text(_) => Classification::builtin("Text", "Text"),
+        FileType::Other(extension) => match extension.to_lowercase().as_str() {
+            "zip" | "tar" | "gz" | "bz2" | "xz" | "rar" | "7z" => Classification::builtin("Archives", "Archive"),
+            _ => Classification::builtin("Other", "File"),
+        },
+    }
+}
+
+/// One user rule as written in a rules file
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ClassifierRule {
+    /// Extensions matched case-insensitively, with or without the leading dot
+    #[serde(default)]
+    pub extensions: Vec<String>,
+    /// File name glob (`*` and `?`), matched case-insensitively
+    #[serde(default)]
+    pub name: Option<String>,
+    pub category: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    /// CSS color for the file icon, e.g. `#e67e22`
+    #[serde(default)]
+    pub accent: Option<String>,
+}
+
+impl ClassifierRule {
+    /// Rule matching the given extensions
+    pub fn for_extensions(extensions: &[&str], category: impl Into<String>) -> Self {
+        Self {
+            extensions: extensions.iter().map(|extension| extension.to_string()).collect(),
+            name: None,
+            category: category.into(),
+            label: None,
+            accent: None,
+        }
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn with_accent(mut self, accent: impl Into<String>) -> Self {
+        self.accent = Some(accent.into());
+        self
+    }
+
+    /// Check the rule and normalize its extensions
+    fn validate(mut self) -> Result<Self, String> {
+        if self.category.trim().is_empty() {
+            return Err("category is empty".to_string());
+        }
+        if self.extensions.is_empty() && self.name.is_none() {
+            return Err("needs `extensions` or `name`".to_string());
+        }
+        if let Some(accent) = &self.accent {
+            if !is_hex_color(accent) {
+                return Err(format!("accent `{}` is not a #rgb or #rrggbb color", accent));
+            }
+        }
+        for extension in &mut self.extensions {
+            *extension = extension.trim_start_matches('.').to_lowercase();
+        }
+        Ok(self)
+    }
+
+    fn matches(&self, file_name: &str) -> bool {
+        let file_name = file_name.to_lowercase();
+        let extension_matches = self.extensions.is_empty()
+            || Path::new(&file_name)
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .is_some_and(|extension| self.extensions.iter().any(|wanted| wanted == extension));
+        let name_matches = self
+            .name
+            .as_ref()
+            .is_none_or(|pattern| glob_matches(&pattern.to_lowercase(), &file_name));
+        extension_matches && name_matches
+    }
+
+    fn classification(&self) -> Classification {
+        Classification {
+            category: self.category.clone(),
+            label: self.label.clone().unwrap_or_else(|| self.category.clone()),
+            accent: self.accent.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RulesFile {
+    #[serde(default)]
+    rule: Vec<toml::Value>,
+}
+
+/// Built-in classification plus the user's rules
+#[derive(Debug, Default)]
+pub struct Classifier {
+    rules: Vec<ClassifierRule>,
+    errors: Vec<ClassifierError>,
+}
+
+impl Classifier {
+    /// Classifier with only the built-in rules
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a rule after the existing ones
+    pub fn with_rule(mut self, rule: ClassifierRule) -> Result<Self, String> {
+        self.rules.push(rule.validate()?);
+        Ok(self)
+    }
+
+    /// Load every `*.toml` rules file in `dir`, in file name order
+    ///
+    /// A missing directory means no user rules.
+    pub fn load_dir(dir: &Path) -> Self {
+        let mut classifier = Self::new();
+        let mut paths: Vec<PathBuf> = match fs::read_dir(dir) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().is_some_and(|extension| extension == "toml"))
+                .collect(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return classifier,
+            Err(source) => {
+                classifier.errors.push(ClassifierError::Read { path: dir.to_path_buf(), source });
+                return classifier;
+            }
+        };
+        paths.sort();
+        for path in paths {
+            classifier.load_file(&path);
+        }
+        classifier
+    }
+
+    fn load_file(&mut self, path: &Path) {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(source) => {
+                self.errors.push(ClassifierError::Read { path: path.to_path_buf(), source });
+                return;
+            }
+        };
+        let file: RulesFile = match toml::from_str(&contents) {
+            Ok(file) => file,
+            Err(e) => {
+                self.errors.push(ClassifierError::Parse { path: path.to_path_buf(), message: e.to_string() });
+                return;
+            }
+        };
+        // Rules are parsed one at a time so a bad one does not hide the rest
+        for (index, value) in file.rule.into_iter().enumerate() {
+            let rule = value
+                .try_into::<ClassifierRule>()
+                .map_err(|e| e.to_string())
+                .and_then(ClassifierRule::validate);
+            match rule {
+                Ok(rule) => self.rules.push(rule),
+                Err(reason) => self.errors.push(ClassifierError::InvalidRule {
+                    path: path.to_path_buf(),
+                    index: index + 1,
+                    reason,
+                }),
+            }
+        }
+    }
+
+    /// Classification from the first matching user rule, if any
+    pub fn user_classification(&self, file_name: &str) -> Option<Classification> {
+        self.rules
+            .iter()
+            .find(|rule| rule.matches(file_name))
+            .map(ClassifierRule::classification)
+    }
+
+    /// Classify a file; folders always keep the built-in category
+    pub fn classify(&self, path: &Path, is_directory: bool) -> Classification {
+        if !is_directory {
+            let file_name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+            if let Some(classification) = self.user_classification(&file_name) {
+                return classification;
+            }
+        }
+        builtin_classification(path, is_directory)
+    }
+
+    /// Classify a listed entry by the type it was listed with, which may
+    /// come from its contents rather than its extension
+    pub fn classify_entry(&self, entry: &FileEntry) -> Classification {
+        if entry.is_directory {
+            return builtin_classification(&entry.path, true);
+        }
+        let file_name = entry.path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+        self.user_classification(&file_name)
+            .unwrap_or_else(|| type_classification(&entry.file_type))
+    }
+
+    /// Built-in categories followed by the ones user rules add
+    pub fn categories(&self) -> Vec<String> {
+        let mut categories: Vec<String> = BUILTIN_CATEGORIES.iter().map(|category| category.to_string()).collect();
+        for rule in &self.rules {
+            if !categories.contains(&rule.category) {
+                categories.push(rule.category.clone());
+            }
+        }
+        categories
+    }
+
+    /// Problems found while loading the rules
+    pub fn errors(&self) -> &[ClassifierError] {
+        &self.errors
+    }
+}
+
+static CLASSIFIER: OnceCell<Classifier> = OnceCell::new();
+
+/// Directory user rules are loaded from
+pub fn default_rules_dir() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("MediaOrganizer")
+        .join("classifiers")
+}
+
+/// Make `classifier` the one used app-wide; returns false if one was already set
+pub fn install(classifier: Classifier) -> bool {
+    CLASSIFIER.set(classifier).is_ok()
+}
+
+/// The app-wide classifier, built-in rules only until [`install`] is called
+pub fn classifier() -> &'static Classifier {
+    CLASSIFIER.get_or_init(Classifier::new)
+}
+
+fn is_hex_color(value: &str) -> bool {
+    value
+        .strip_prefix('#')
+        .is_some_and(|hex| matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Match `name` against a pattern where `*` is any run of characters and `?` any one
+pub(crate) fn glob_matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Position after the last `*` and the name position it was tried at
+    let mut backtrack = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p + 1, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star_p, star_n)) => {
+                    p = star_p;
+                    n = star_n + 1;
+                    backtrack = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_user_rule_overrides_builtin_extension() {
+        let path = Path::new("/photos/track.mp3");
+        assert_eq!(builtin_classification(path, false).category, "Audio");
+
+        let classifier = Classifier::new()
+            .with_rule(
+                ClassifierRule::for_extensions(&[".MP3"], "Podcasts")
+                    .with_label("Episode")
+                    .with_accent("#8e44ad"),
+            )
+            .unwrap();
+
+        let classification = classifier.classify(path, false);
+        assert_eq!(classification.category, "Podcasts");
+        assert_eq!(classification.label, "Episode");
+        assert_eq!(classification.accent.as_deref(), Some("#8e44ad"));
+        // Other files and folders keep the built-in classification
+        assert_eq!(classifier.classify(Path::new("/photos/a.jpg"), false).category, "Images");
+        assert_eq!(classifier.classify(Path::new("/photos/old.mp3"), true).category, "Folders");
+        assert!(classifier.categories().contains(&"Podcasts".to_string()));
+    }
+
+    #[test]
+    fn test_entries_classify_by_their_listed_type() {
+        use crate::services::file_system::{FilePermissions, ImageFormat};
+
+        // A `.txt` whose header showed it is a JPEG
+        let entry = FileEntry {
+            path: PathBuf::from("/photos/holiday.txt"),
+            name: "holiday.txt".to_string(),
+            file_type: FileType::Image(ImageFormat::Jpeg),
+            size: 10,
+            modified: std::time::SystemTime::now(),
+            created: std::time::SystemTime::now(),
+            is_directory: false,
+            is_hidden: false,
+            permissions: FilePermissions::default(),
+            preview_metadata: None,
+        };
+        let classifier = Classifier::new();
+        assert_eq!(classifier.classify_entry(&entry).category, "Images");
+
+        // User rules still match on the name
+        let classifier = classifier.with_rule(ClassifierRule::for_extensions(&["txt"], "Notes")).unwrap();
+        assert_eq!(classifier.classify_entry(&entry).category, "Notes");
+    }
+
+    #[test]
+    fn test_invalid_rules_are_reported_without_blocking_others() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("a.toml"),
+            r##"
+[[rule]]
+extensions = ["cr2", "nef"]
+category = "Raw Photos"
+
+[[rule]]
+category = "Nothing to match"
+
+[[rule]]
+name = "screenshot*.png"
+category = "Screenshots"
+accent = "orange"
+
+[[rule]]
+name = "Screenshot ??.png"
+category = "Screenshots"
+"##,
+        )
+        .unwrap();
+        std::fs::write(temp_dir.path().join("b.toml"), "[[rule]\nbroken").unwrap();
+        std::fs::write(temp_dir.path().join("notes.txt"), "not a rules file").unwrap();
+
+        let classifier = Classifier::load_dir(temp_dir.path());
+
+        assert_eq!(classifier.errors().len(), 3);
+        assert!(matches!(classifier.errors()[0], ClassifierError::InvalidRule { index: 2, .. }));
+        assert!(matches!(classifier.errors()[1], ClassifierError::InvalidRule { index: 3, .. }));
+        assert!(matches!(classifier.errors()[2], ClassifierError::Parse { .. }));
+        assert_eq!(classifier.classify(Path::new("IMG_1.CR2"), false).category, "Raw Photos");
+        assert_eq!(classifier.classify(Path::new("Screenshot 01.png"), false).category, "Screenshots");
+        assert_eq!(classifier.classify(Path::new("Screenshot 123.png"), false).category, "Images");
+    }
+}
@@ -0,0 +1,347 @@
+//! Putting paths and files on the system clipboard
+//!
+//! Text is assembled here so each copy mode can be tested without a clipboard;
+//! the platform tools (`pbcopy`, PowerShell, `wl-copy`/`xclip`) only receive the
+//! finished text or file list.
+
+use std::io::{self, Write};
+use std::path::{Component, Path, PathBuf};
+use std::process::{Command, Stdio};
+use thiserror::Error;
+
+/// Errors while writing to the system clipboard
+#[derive(Debug, Error)]
+pub enum ClipboardError {
+    #[error("Failed to run {tool}: {source}")]
+    Spawn {
+        tool: &'static str,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("{tool} exited with {status}")]
+    Failed { tool: &'static str, status: std::process::ExitStatus },
+
+    #[error("No clipboard tool found; install wl-clipboard or xclip")]
+    NoTool,
+}
+
+pub type ClipboardResult<T> = Result<T, ClipboardError>;
+
+/// What is copied for each selected entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathCopyMode {
+    AbsolutePath,
+    /// Path below the current root; entries outside it keep their absolute path
+    RelativePath,
+    Name,
+    NameWithoutExtension,
+    /// Full path of the folder holding the entry, once per folder
+    ParentDirectory,
+}
+
+impl PathCopyMode {
+    pub const ALL: [PathCopyMode; 5] = [
+        PathCopyMode::AbsolutePath,
+        PathCopyMode::RelativePath,
+        PathCopyMode::Name,
+        PathCopyMode::NameWithoutExtension,
+        PathCopyMode::ParentDirectory,
+    ];
+
+    /// Whether the copied text contains path separators, so the path style matters
+    pub fn has_separators(&self) -> bool {
+        !matches!(self, PathCopyMode::Name | PathCopyMode::NameWithoutExtension)
+    }
+}
+
+/// Separator and line-ending conventions of the clipboard's platform
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathStyle {
+    Posix,
+    Windows,
+}
+
+impl PathStyle {
+    pub fn native() -> Self {
+        if cfg!(windows) {
+            PathStyle::Windows
+        } else {
+            PathStyle::Posix
+        }
+    }
+
+    fn separator(&self) -> &'static str {
+        match self {
+            PathStyle::Posix => "/",
+            PathStyle::Windows => "\\",
+        }
+    }
+
+    fn line_ending(&self) -> &'static str {
+        match self {
+            PathStyle::Posix => "\n",
+            PathStyle::Windows => "\r\n",
+        }
+    }
+}
+
+/// Text for `paths` in `mode`, one entry per line
+///
+/// Entries in the same folder share one line in
+/// [`PathCopyMode::ParentDirectory`].
+pub fn clipboard_text(paths: &[PathBuf], root: Option<&Path>, mode: PathCopyMode, style: PathStyle) -> String {
+    let mut lines: Vec<String> = Vec::with_capacity(paths.len());
+    for line in paths.iter().map(|path| entry_text(path, root, mode, style)) {
+        if mode != PathCopyMode::ParentDirectory || !lines.contains(&line) {
+            lines.push(line);
+        }
+    }
+    lines.join(style.line_ending())
+}
+
+fn entry_text(path: &Path, root: Option<&Path>, mode: PathCopyMode, style: PathStyle) -> String {
+    match mode {
+        PathCopyMode::AbsolutePath => styled_path(path, style),
+        PathCopyMode::RelativePath => {
+            match root.and_then(|root| path.strip_prefix(root).ok()) {
+                Some(relative) if relative.as_os_str().is_empty() => ".".to_string(),
+                Some(relative) => styled_path(relative, style),
+                None => styled_path(path, style),
+            }
+        }
+        PathCopyMode::Name => path.file_name().unwrap_or(path.as_os_str()).to_string_lossy().to_string(),
+        PathCopyMode::NameWithoutExtension => path
+            .file_stem()
+            .unwrap_or(path.as_os_str())
+            .to_string_lossy()
+            .to_string(),
+        PathCopyMode::ParentDirectory => styled_path(path.parent().unwrap_or(path), style),
+    }
+}
+
+/// `path` written with the separators of `style`
+fn styled_path(path: &Path, style: PathStyle) -> String {
+    let mut text = String::new();
+    for component in path.components() {
+        match component {
+            Component::Prefix(prefix) => text.push_str(&prefix.as_os_str().to_string_lossy()),
+            Component::RootDir => text.push_str(style.separator()),
+            other => {
+                if !text.is_empty() && !text.ends_with(style.separator()) {
+                    text.push_str(style.separator());
+                }
+                text.push_str(&other.as_os_str().to_string_lossy());
+            }
+        }
+    }
+    text
+}
+
+/// `file://` URI of an absolute path, percent-encoding everything but unreserved characters
+pub fn file_uri(path: &Path, style: PathStyle) -> String {
+    let mut uri = String::from("file://");
+    if style == PathStyle::Windows {
+        uri.push('/');
+    }
+    // Drive letters come out as `/C:/...`, which is the URI form
+    for byte in styled_path(path, PathStyle::Posix).bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => uri.push(byte as char),
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    uri
+}
+
+/// Put `text` on the clipboard
+pub fn copy_text(text: &str) -> ClipboardResult<()> {
+    if cfg!(target_os = "macos") {
+        pipe_to("pbcopy", Command::new("pbcopy"), text)
+    } else if cfg!(windows) {
+        let mut command = Command::new("powershell");
+        command.args([
+            "-NoProfile",
+            "-Command",
+            "[Console]::InputEncoding = [Text.Encoding]::UTF8; Set-Clipboard -Value ([Console]::In.ReadToEnd())",
+        ]);
+        pipe_to("powershell", command, text)
+    } else {
+        linux_copy(None, text)
+    }
+}
+
+/// Put the files themselves on the clipboard so other apps can paste them
+pub fn copy_file_references(paths: &[PathBuf]) -> ClipboardResult<()> {
+    if cfg!(target_os = "macos") {
+        let files = paths
+            .iter()
+            .map(|path| format!("POSIX file \"{}\"", applescript_escape(&path.to_string_lossy())))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut command = Command::new("osascript");
+        command.args(["-e", &format!("set the clipboard to {{{}}}", files)]);
+        run("osascript", command)
+    } else if cfg!(windows) {
+        let files = paths
+            .iter()
+            .map(|path| powershell_quote(&path.to_string_lossy()))
+            .collect::<Vec<_>>()
+            .join(",");
+        let mut command = Command::new("powershell");
+        command.args(["-NoProfile", "-Command", &format!("Set-Clipboard -Path {}", files)]);
+        run("powershell", command)
+    } else {
+        // RFC 2483 lists end every line with CRLF
+        let uris = paths
+            .iter()
+            .map(|path| format!("{}\r\n", file_uri(path, PathStyle::Posix)))
+            .collect::<String>();
+        linux_copy(Some("text/uri-list"), &uris)
+    }
+}
+
+fn linux_copy(mime_type: Option<&str>, text: &str) -> ClipboardResult<()> {
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        let mut command = Command::new("wl-copy");
+        if let Some(mime_type) = mime_type {
+            command.args(["--type", mime_type]);
+        }
+        match pipe_to("wl-copy", command, text) {
+            Err(ClipboardError::Spawn { source, .. }) if source.kind() == io::ErrorKind::NotFound => {}
+            result => return result,
+        }
+    }
+    let mut command = Command::new("xclip");
+    command.args(["-selection", "clipboard"]);
+    if let Some(mime_type) = mime_type {
+        command.args(["-t", mime_type]);
+    }
+    match pipe_to("xclip", command, text) {
+        Err(ClipboardError::Spawn { source, .. }) if source.kind() == io::ErrorKind::NotFound => {
+            Err(ClipboardError::NoTool)
+        }
+        result => result,
+    }
+}
+
+fn pipe_to(tool: &'static str, mut command: Command, text: &str) -> ClipboardResult<()> {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|source| ClipboardError::Spawn { tool, source })?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(text.as_bytes())
+            .map_err(|source| ClipboardError::Spawn { tool, source })?;
+    }
+    let status = child.wait().map_err(|source| ClipboardError::Spawn { tool, source })?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(ClipboardError::Failed { tool, status })
+    }
+}
+
+fn run(tool: &'static str, mut command: Command) -> ClipboardResult<()> {
+    let status = command
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(|source| ClipboardError::Spawn { tool, source })?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(ClipboardError::Failed { tool, status })
+    }
+}
+
+fn applescript_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn powershell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn selection() -> Vec<PathBuf> {
+        vec![
+            PathBuf::from("/photos/2024/beach day.jpg"),
+            PathBuf::from("/photos/notes"),
+            PathBuf::from("/elsewhere/archive.tar.gz"),
+        ]
+    }
+
+    #[test]
+    fn test_clipboard_text_for_each_mode() {
+        let root = Some(Path::new("/photos"));
+        let posix = PathStyle::Posix;
+        assert_eq!(
+            clipboard_text(&selection(), root, PathCopyMode::AbsolutePath, posix),
+            "/photos/2024/beach day.jpg\n/photos/notes\n/elsewhere/archive.tar.gz"
+        );
+        assert_eq!(
+            clipboard_text(&selection(), root, PathCopyMode::RelativePath, posix),
+            "2024/beach day.jpg\nnotes\n/elsewhere/archive.tar.gz"
+        );
+        assert_eq!(
+            clipboard_text(&selection(), root, PathCopyMode::Name, posix),
+            "beach day.jpg\nnotes\narchive.tar.gz"
+        );
+        assert_eq!(
+            clipboard_text(&selection(), root, PathCopyMode::NameWithoutExtension, posix),
+            "beach day\nnotes\narchive.tar"
+        );
+        assert_eq!(
+            clipboard_text(&[PathBuf::from("/photos")], root, PathCopyMode::RelativePath, posix),
+            "."
+        );
+    }
+
+    #[test]
+    fn test_parent_directory_lists_each_folder_once() {
+        let entries = [
+            PathBuf::from("/photos/2024/beach.jpg"),
+            PathBuf::from("/photos/notes"),
+            PathBuf::from("/photos/2024/dunes.jpg"),
+        ];
+        assert_eq!(
+            clipboard_text(&entries, None, PathCopyMode::ParentDirectory, PathStyle::Posix),
+            "/photos/2024\n/photos"
+        );
+        assert_eq!(
+            clipboard_text(&entries, None, PathCopyMode::ParentDirectory, PathStyle::Windows),
+            "\\photos\\2024\r\n\\photos"
+        );
+        assert_eq!(
+            clipboard_text(&[PathBuf::from("/")], None, PathCopyMode::ParentDirectory, PathStyle::Posix),
+            "/"
+        );
+        assert!(PathCopyMode::ParentDirectory.has_separators());
+        assert!(!PathCopyMode::Name.has_separators());
+    }
+
+    #[test]
+    fn test_windows_style_uses_backslashes_and_crlf() {
+        let root = Some(Path::new("/photos"));
+        assert_eq!(
+            clipboard_text(&selection(), root, PathCopyMode::RelativePath, PathStyle::Windows),
+            "2024\\beach day.jpg\r\nnotes\r\n\\elsewhere\\archive.tar.gz"
+        );
+    }
+
+    #[test]
+    fn test_file_uri_percent_encodes() {
+        assert_eq!(
+            file_uri(Path::new("/photos/2024/beach day#1.jpg"), PathStyle::Posix),
+            "file:///photos/2024/beach%20day%231.jpg"
+        );
+        assert_eq!(powershell_quote("C:\\it's here"), "'C:\\it''s here'");
+    }
+}
@@ -0,0 +1,273 @@
+//! Search inside the text files of a tree
+//!
+//! The tree is walked like a name search, binary and oversized files are
+//! skipped, and the remaining files are read line by line on a bounded number
+//! of blocking threads. Each file with a hit is reported with the numbers and
+//! text of its matching lines, so the preview can jump straight to them.
+
+use futures::stream::{self, StreamExt};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use super::file_system::FileEntry;
+use super::operations::CancellationToken;
+use super::preview::is_text_file;
+use super::smart_folders::{walk_tree, SmartFolderError, SmartFolderResult};
+
+/// Largest file read by default; bigger files are skipped
+pub const DEFAULT_MAX_CONTENT_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Characters of a matching line kept as its snippet
+const SNIPPET_MAX_CHARS: usize = 200;
+
+/// Matching lines kept per file; the rest are only counted
+const MAX_LINES_PER_FILE: usize = 100;
+
+/// One matching line of a file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LineMatch {
+    /// 1-based line number
+    pub line_number: usize,
+    /// The trimmed line, cut at `SNIPPET_MAX_CHARS`
+    pub snippet: String,
+}
+
+/// A file containing the query and where
+#[derive(Clone, Debug)]
+pub struct ContentMatch {
+    pub entry: FileEntry,
+    pub lines: Vec<LineMatch>,
+    /// Matching lines in the file, including those past the kept ones
+    pub total_lines: usize,
+}
+
+/// A text search over the files under `root`
+#[derive(Clone, Debug)]
+pub struct ContentSearch {
+    pub root: PathBuf,
+    /// Text looked for, ignoring case
+    pub query: String,
+    pub include_hidden: bool,
+    /// Files larger than this are not read
+    pub max_file_size: u64,
+    /// The search stops once this many files matched
+    pub max_results: usize,
+    /// Files read at once
+    pub workers: usize,
+}
+
+impl ContentSearch {
+    pub fn new(root: impl Into<PathBuf>, query: &str) -> Self {
+        Self {
+            root: root.into(),
+            query: query.to_string(),
+            include_hidden: false,
+            max_file_size: DEFAULT_MAX_CONTENT_FILE_SIZE,
+            max_results: super::smart_folders::DEFAULT_MAX_SEARCH_RESULTS,
+            workers: 4,
+        }
+    }
+
+    pub fn with_hidden(mut self, include_hidden: bool) -> Self {
+        self.include_hidden = include_hidden;
+        self
+    }
+
+    pub fn with_max_file_size(mut self, max_file_size: u64) -> Self {
+        self.max_file_size = max_file_size;
+        self
+    }
+
+    pub fn with_max_results(mut self, max_results: usize) -> Self {
+        self.max_results = max_results;
+        self
+    }
+
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = workers;
+        self
+    }
+}
+
+/// Search the text files under the search root for the query and pass each
+/// file with a hit to `on_match`
+///
+/// Unreadable files are skipped with a warning. Returns the number of
+/// matching files.
+pub async fn search_file_contents(
+    search: ContentSearch,
+    token: &CancellationToken,
+    mut on_match: impl FnMut(ContentMatch),
+) -> SmartFolderResult<usize> {
+    let needle = search.query.trim().to_lowercase();
+    if needle.is_empty() || search.max_results == 0 {
+        return Ok(0);
+    }
+
+    let (root, include_hidden, max_file_size) = (search.root.clone(), search.include_hidden, search.max_file_size);
+    let walk_token = token.clone();
+    let walk = tokio::task::spawn_blocking(move || {
+        let mut candidates = Vec::new();
+        walk_tree(&root, include_hidden, &walk_token, |entry| {
+            if !entry.is_directory && entry.size <= max_file_size && is_text_file(&entry.path) {
+                candidates.push(entry);
+            }
+            true
+        })
+        .map(|_| candidates)
+    });
+    let candidates = walk.await.map_err(|e| SmartFolderError::Read {
+        path: search.root.clone(),
+        source: io::Error::other(e),
+    })??;
+
+    let mut results = stream::iter(candidates)
+        .map(|entry| {
+            let needle = needle.clone();
+            let token = token.clone();
+            tokio::task::spawn_blocking(move || {
+                let found = grep_file(&entry.path, &needle, &token);
+                (entry, found)
+            })
+        })
+        .buffer_unordered(search.workers.max(1));
+
+    let mut matches = 0;
+    while let Some(result) = results.next().await {
+        if token.is_cancelled() {
+            return Err(SmartFolderError::Cancelled);
+        }
+        let (entry, found) = match result {
+            Ok(result) => result,
+            Err(e) => {
+                tracing::warn!("Content search task failed: {}", e);
+                continue;
+            }
+        };
+        match found {
+            Ok(Some((lines, total_lines))) => {
+                matches += 1;
+                on_match(ContentMatch {
+                    entry,
+                    lines,
+                    total_lines,
+                });
+                if matches >= search.max_results {
+                    break;
+                }
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Content search skipped {:?}: {}", entry.path, e),
+        }
+    }
+    Ok(matches)
+}
+
+/// Lines of `path` containing the lowercase `needle`, with the total count,
+/// or `None` without a hit
+fn grep_file(path: &Path, needle: &str, token: &CancellationToken) -> io::Result<Option<(Vec<LineMatch>, usize)>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut buffer = Vec::new();
+    let mut lines = Vec::new();
+    let mut total_lines = 0;
+    let mut line_number = 0;
+    while !token.is_cancelled() {
+        buffer.clear();
+        if reader.read_until(b'\n', &mut buffer)? == 0 {
+            break;
+        }
+        line_number += 1;
+        let line = String::from_utf8_lossy(&buffer);
+        if !line.to_lowercase().contains(needle) {
+            continue;
+        }
+        total_lines += 1;
+        if lines.len() < MAX_LINES_PER_FILE {
+            lines.push(LineMatch {
+                line_number,
+                snippet: line.trim().chars().take(SNIPPET_MAX_CHARS).collect(),
+            });
+        }
+    }
+    Ok((total_lines > 0).then_some((lines, total_lines)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    async fn search(search: ContentSearch) -> Vec<(String, Vec<usize>)> {
+        let root = search.root.clone();
+        let mut found = Vec::new();
+        search_file_contents(search, &CancellationToken::new(), |found_match| {
+            let path = found_match.entry.path.strip_prefix(&root).unwrap().to_string_lossy().into_owned();
+            let lines = found_match.lines.iter().map(|line| line.line_number).collect();
+            found.push((path, lines));
+        })
+        .await
+        .unwrap();
+        found.sort();
+        found
+    }
+
+    #[tokio::test]
+    async fn test_reports_every_matching_line_of_a_file() {
+        let root = TempDir::new().unwrap();
+        fs::create_dir(root.path().join("src")).unwrap();
+        fs::write(
+            root.path().join("src/main.rs"),
+            "fn main() {\n    // TODO: parse args\n    run();\n}\n// todo: tidy up\n",
+        )
+        .unwrap();
+        fs::write(root.path().join("notes.md"), "Nothing left to do\n").unwrap();
+        fs::write(root.path().join("README.txt"), "TODO list\n").unwrap();
+
+        let found = search(ContentSearch::new(root.path(), "todo").with_workers(2)).await;
+        assert_eq!(
+            found,
+            vec![("README.txt".to_string(), vec![1]), ("src/main.rs".to_string(), vec![2, 5])]
+        );
+
+        let mut snippets = Vec::new();
+        search_file_contents(ContentSearch::new(root.path(), "parse"), &CancellationToken::new(), |found| {
+            snippets.extend(found.lines.into_iter().map(|line| line.snippet))
+        })
+        .await
+        .unwrap();
+        assert_eq!(snippets, vec!["// TODO: parse args"]);
+    }
+
+    #[tokio::test]
+    async fn test_skips_binary_oversized_and_hidden_files() {
+        let root = TempDir::new().unwrap();
+        fs::write(root.path().join("photo.jpg"), b"\xFF\xD8needle").unwrap();
+        fs::write(root.path().join("dump.log"), b"needle\0\x01\x02").unwrap();
+        fs::write(root.path().join("big.txt"), format!("needle\n{}", "x".repeat(4096))).unwrap();
+        fs::write(root.path().join(".secret.txt"), "needle\n").unwrap();
+        fs::write(root.path().join("small.txt"), "needle\n").unwrap();
+
+        let found = search(ContentSearch::new(root.path(), "needle").with_max_file_size(1024)).await;
+        assert_eq!(found, vec![("small.txt".to_string(), vec![1])]);
+
+        let found = search(
+            ContentSearch::new(root.path(), "needle")
+                .with_max_file_size(1024)
+                .with_hidden(true),
+        )
+        .await;
+        assert_eq!(
+            found,
+            vec![(".secret.txt".to_string(), vec![1]), ("small.txt".to_string(), vec![1])]
+        );
+
+        let token = CancellationToken::new();
+        token.cancel();
+        assert!(matches!(
+            search_file_contents(ContentSearch::new(root.path(), "needle"), &token, |_| {}).await,
+            Err(SmartFolderError::Cancelled)
+        ));
+    }
+}
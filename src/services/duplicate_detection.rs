@@ -5,7 +5,12 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tracing::{debug, info, warn, error};
 
-use crate::services::{HashingService, FileHash, BackgroundProcessor, HashingTask, FileEntry};
+use crate::services::{
+    HashingService, FileHash, BackgroundProcessor, HashingTask, FileEntry, IoPriority, TaskPriority, ThrottlePolicy,
+};
+use crate::services::hashing::DEFAULT_QUICK_HASH_SAMPLE;
+use crate::services::perceptual_hash::ImageFingerprint;
+use crate::utils::format::format_size;
 
 /// Errors that can occur during duplicate detection
 #[derive(Debug, Error)]
@@ -42,6 +47,8 @@ pub enum ComparisonMethod {
     SizeAndName,
     /// Compare by content hash and size (most reliable)
     ContentAndSize,
+    /// Compare images by perceptual hash, so resized or re-encoded copies match
+    Perceptual,
 }
 
 impl ComparisonMethod {
@@ -53,6 +60,7 @@ impl ComparisonMethod {
             ComparisonMethod::Name => "File Name",
             ComparisonMethod::SizeAndName => "Size + Name",
             ComparisonMethod::ContentAndSize => "Content + Size",
+            ComparisonMethod::Perceptual => "Visually Similar",
         }
     }
     
@@ -79,6 +87,8 @@ impl ComparisonMethod {
             ComparisonMethod::ContentAndSize => {
                 file_hash.map(|h| format!("{}_{}", h.hash, file_entry.size))
             }
+            // Similar images have different hashes, so they're grouped by distance instead
+            ComparisonMethod::Perceptual => None,
         }
     }
 }
@@ -100,6 +110,8 @@ pub struct DuplicateFile {
     pub is_primary: bool,
     /// Whether this file is selected for action (delete, move, etc.)
     pub is_selected: bool,
+    /// Perceptual hash and dimensions, for images compared visually
+    pub fingerprint: Option<ImageFingerprint>,
 }
 
 impl DuplicateFile {
@@ -110,8 +122,15 @@ impl DuplicateFile {
             hash,
             is_primary: false,
             is_selected: false,
+            fingerprint: None,
         }
     }
+
+    /// Attach the image's perceptual fingerprint
+    pub fn with_fingerprint(mut self, fingerprint: ImageFingerprint) -> Self {
+        self.fingerprint = Some(fingerprint);
+        self
+    }
     
     /// Get the file path
     pub fn path(&self) -> &Path {
@@ -234,6 +253,13 @@ impl DuplicateGroup {
                     .map(|(i, _)| i)
             }
             PrimarySelectionStrategy::First => Some(0),
+            PrimarySelectionStrategy::HighestResolution => {
+                // Files without a fingerprint fall back to the largest file
+                self.files.iter()
+                    .enumerate()
+                    .max_by_key(|(_, f)| (f.fingerprint.map(|fp| fp.pixel_count()), f.size()))
+                    .map(|(i, _)| i)
+            }
         };
         
         if let Some(index) = primary_index {
@@ -287,6 +313,8 @@ pub enum PrimarySelectionStrategy {
     LongestPath,
     /// Keep the first file found
     First,
+    /// Keep the image with the most pixels, which is usually the original
+    HighestResolution,
 }
 
 impl Default for PrimarySelectionStrategy {
@@ -304,6 +332,10 @@ pub enum FileSortCriteria {
     Modified,
 }
 
+/// Default `perceptual_threshold`: out of 64 bits, loose enough for re-encoded
+/// copies without matching unrelated photos
+pub const DEFAULT_PERCEPTUAL_THRESHOLD: u32 = 10;
+
 /// Configuration for duplicate detection
 #[derive(Debug, Clone, PartialEq)]
 pub struct DuplicateDetectionConfig {
@@ -323,6 +355,19 @@ pub struct DuplicateDetectionConfig {
     pub primary_selection: PrimarySelectionStrategy,
     /// Maximum number of files to process
     pub max_files: Option<usize>,
+    /// Number of files hashed at once
+    pub workers: usize,
+    /// I/O priority of the hashing threads; `Low` also defers the scan
+    /// while the background throttle policy is paused
+    pub io_priority: IoPriority,
+    /// Largest Hamming distance between perceptual hashes still counted as a match
+    pub perceptual_threshold: u32,
+    /// Hash files in chunks with no size limit, so multi-GB videos are included
+    pub streaming_hash: bool,
+    /// Only fully hash files whose size and quick hash match another file's
+    pub quick_hash_prefilter: bool,
+    /// Bytes the quick hash reads from each end of a file
+    pub quick_hash_sample_size: usize,
 }
 
 impl Default for DuplicateDetectionConfig {
@@ -341,6 +386,12 @@ impl Default for DuplicateDetectionConfig {
             include_hidden: false,
             primary_selection: PrimarySelectionStrategy::default(),
             max_files: None,
+            workers: 2,
+            io_priority: IoPriority::Normal,
+            perceptual_threshold: DEFAULT_PERCEPTUAL_THRESHOLD,
+            streaming_hash: true,
+            quick_hash_prefilter: true,
+            quick_hash_sample_size: DEFAULT_QUICK_HASH_SAMPLE,
         }
     }
 }
@@ -425,6 +476,8 @@ pub struct DuplicateDetectionResults {
     pub total_duplicates: usize,
     /// Total potential space savings in bytes
     pub total_potential_savings: u64,
+    /// Files whose full content hash was computed; the pre-filter skips the rest
+    pub files_fully_hashed: usize,
     /// Time taken for detection
     pub detection_time_ms: u64,
     /// Configuration used for detection
@@ -444,7 +497,7 @@ impl DuplicateDetectionResults {
     
     /// Get total space savings in a human-readable format
     pub fn format_savings(&self) -> String {
-        format_file_size(self.total_potential_savings)
+        format_size(self.total_potential_savings)
     }
 }
 
@@ -478,6 +531,12 @@ impl DuplicateDetector {
         }
     }
     
+    /// Hash with a shared throttle policy
+    pub fn with_throttle_policy(mut self, throttle_policy: std::sync::Arc<ThrottlePolicy>) -> Self {
+        self.background_processor = BackgroundProcessor::default().with_throttle_policy(throttle_policy);
+        self
+    }
+    
     /// Update the detection configuration
     pub fn set_config(&mut self, config: DuplicateDetectionConfig) {
         self.config = config;
@@ -538,7 +597,25 @@ impl DuplicateDetector {
                 callback(progress.clone());
             }
             
-            self.hash_files(&filtered_files, progress_callback.clone()).await?
+            let candidates = if self.config.quick_hash_prefilter {
+                self.quick_hash_candidates(&filtered_files).await
+            } else {
+                filtered_files.clone()
+            };
+            debug!("{} of {} files need a full hash", candidates.len(), filtered_files.len());
+            self.hash_files(&candidates, progress_callback.clone()).await?
+        } else {
+            HashMap::new()
+        };
+        let files_fully_hashed = file_hashes.len();
+        let fingerprints = if self.config.comparison_method == ComparisonMethod::Perceptual {
+            progress.phase = DetectionPhase::Hashing;
+            progress.progress_percentage = 20.0;
+            if let Some(ref callback) = progress_callback {
+                callback(progress.clone());
+            }
+            
+            self.fingerprint_images(&filtered_files, progress_callback.clone()).await
         } else {
             HashMap::new()
         };
@@ -551,7 +628,11 @@ impl DuplicateDetector {
         }
         
         let filtered_files_count = filtered_files.len();
-        let groups = self.group_files(filtered_files, file_hashes).await?;
+        let groups = if self.config.comparison_method == ComparisonMethod::Perceptual {
+            group_similar_images(filtered_files, &fingerprints, self.config.perceptual_threshold)
+        } else {
+            self.group_files(filtered_files, file_hashes).await?
+        };
         
         // Phase 4: Analyze results and set primary files
         progress.phase = DetectionPhase::Analyzing;
@@ -586,7 +667,7 @@ impl DuplicateDetector {
             detection_time,
             analyzed_groups.len(),
             total_duplicates,
-            format_file_size(total_potential_savings)
+            format_size(total_potential_savings)
         );
         
         Ok(DuplicateDetectionResults {
@@ -594,6 +675,7 @@ impl DuplicateDetector {
             total_files_analyzed: filtered_files_count,
             total_duplicates,
             total_potential_savings,
+            files_fully_hashed,
             detection_time_ms: detection_time,
             config: self.config.clone(),
         })
@@ -605,6 +687,9 @@ impl DuplicateDetector {
         files: &[FileEntry],
         progress_callback: Option<DetectionProgressCallback>,
     ) -> DuplicateDetectionResult<HashMap<PathBuf, FileHash>> {
+        if files.is_empty() {
+            return Ok(HashMap::new());
+        }
         let file_paths: Vec<PathBuf> = files.iter().map(|f| f.path.clone()).collect();
         let total_size: u64 = files.iter().map(|f| f.size).sum();
         
@@ -629,11 +714,19 @@ impl DuplicateDetector {
             };
         
         // Create hashing task
+        let priority = match self.config.io_priority {
+            IoPriority::Normal => TaskPriority::Foreground,
+            IoPriority::Low => TaskPriority::Background,
+        };
         let hashing_task = HashingTask::new(
             file_paths,
             total_size,
             hash_progress_callback,
-        );
+        )
+        .with_concurrency(self.config.workers)
+        .with_priority(priority)
+        .with_io_priority(self.config.io_priority)
+        .with_streaming(self.config.streaming_hash);
         
         let task_id = self.background_processor
             .start_hashing_task(hashing_task)
@@ -665,6 +758,102 @@ impl DuplicateDetector {
         Ok(file_hashes)
     }
     
+    /// The files that could have an identical twin, judged without reading them whole
+    /// 
+    /// Files with a unique size are dropped first, then files whose quick hash
+    /// (size plus both ends) is unique. Files the quick hash can't read are kept
+    /// so the full hash reports their error.
+    async fn quick_hash_candidates(&self, files: &[FileEntry]) -> Vec<FileEntry> {
+        use futures::stream::{self, StreamExt};
+        
+        let mut by_size: HashMap<u64, Vec<&FileEntry>> = HashMap::new();
+        for file in files {
+            by_size.entry(file.size).or_default().push(file);
+        }
+        let same_size: Vec<FileEntry> = by_size
+            .into_values()
+            .filter(|bucket| bucket.len() > 1)
+            .flatten()
+            .cloned()
+            .collect();
+        
+        let sample_size = self.config.quick_hash_sample_size;
+        let mut quick_hashed = stream::iter(same_size)
+            .map(|file| {
+                let hashing_service = self.hashing_service.clone();
+                async move {
+                    let path = file.path.clone();
+                    let quick_hash =
+                        tokio::task::spawn_blocking(move || hashing_service.quick_hash_blocking(&path, sample_size)).await;
+                    (file, quick_hash)
+                }
+            })
+            .buffer_unordered(self.config.workers.max(1));
+        
+        let mut buckets: HashMap<String, Vec<FileEntry>> = HashMap::new();
+        let mut candidates = Vec::new();
+        while let Some((file, quick_hash)) = quick_hashed.next().await {
+            match quick_hash {
+                Ok(Ok(quick_hash)) => buckets.entry(quick_hash.hash).or_default().push(file),
+                Ok(Err(e)) => {
+                    debug!("Quick hash failed for {}: {}", file.path.display(), e);
+                    candidates.push(file);
+                }
+                Err(e) => {
+                    warn!("Quick hash task for {} failed: {}", file.path.display(), e);
+                    candidates.push(file);
+                }
+            }
+        }
+        candidates.extend(buckets.into_values().filter(|bucket| bucket.len() > 1).flatten());
+        candidates
+    }
+    
+    /// Decode and perceptually hash the images among `files`
+    ///
+    /// Files that aren't decodable images are left out of the result.
+    async fn fingerprint_images(
+        &self,
+        files: &[FileEntry],
+        progress_callback: Option<DetectionProgressCallback>,
+    ) -> HashMap<PathBuf, ImageFingerprint> {
+        use futures::stream::{self, StreamExt};
+
+        let total_files = files.len();
+        let mut fingerprints = HashMap::new();
+        let mut decoded = stream::iter(files.iter().map(|file| file.path.clone()))
+            .map(|path| async move {
+                let job_path = path.clone();
+                let fingerprint = tokio::task::spawn_blocking(move || ImageFingerprint::from_path(&job_path)).await;
+                (path, fingerprint)
+            })
+            .buffer_unordered(self.config.workers.max(1));
+
+        let mut files_processed = 0;
+        while let Some((path, fingerprint)) = decoded.next().await {
+            files_processed += 1;
+            match fingerprint {
+                Ok(Ok(fingerprint)) => {
+                    fingerprints.insert(path.clone(), fingerprint);
+                }
+                Ok(Err(e)) => debug!("Skipping {} for visual comparison: {}", path.display(), e),
+                Err(e) => warn!("Fingerprint task for {} failed: {}", path.display(), e),
+            }
+            if let Some(ref callback) = progress_callback {
+                callback(DetectionProgress {
+                    phase: DetectionPhase::Hashing,
+                    files_processed,
+                    total_files,
+                    groups_found: 0,
+                    current_file: Some(path),
+                    progress_percentage: 20.0 + (files_processed as f64 / total_files.max(1) as f64) * 50.0, // 20-70%
+                });
+            }
+        }
+        
+        fingerprints
+    }
+    
     /// Group files based on the comparison method
     async fn group_files(
         &self,
@@ -712,32 +901,51 @@ impl DuplicateDetector {
     }
 }
 
-impl Default for DuplicateDetector {
-    fn default() -> Self {
-        Self::new()
-    }
-}
+/// Group images whose fingerprints are within `threshold` of each other
+///
+/// Matches are transitive: if A is close to B and B to C, all three share a group.
+/// Files without a fingerprint are left out.
+fn group_similar_images(
+    files: Vec<FileEntry>,
+    fingerprints: &HashMap<PathBuf, ImageFingerprint>,
+    threshold: u32,
+) -> Vec<DuplicateGroup> {
+    let images: Vec<(FileEntry, ImageFingerprint)> = files
+        .into_iter()
+        .filter_map(|file| fingerprints.get(&file.path).map(|fingerprint| (file, *fingerprint)))
+        .collect();
 
-/// Format file size in human-readable format
-fn format_file_size(bytes: u64) -> String {
-    const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
-    
-    if bytes == 0 {
-        return "0 B".to_string();
+    // Union-find over every close pair
+    fn root(parents: &mut [usize], mut i: usize) -> usize {
+        while parents[i] != i {
+            parents[i] = parents[parents[i]];
+            i = parents[i];
+        }
+        i
     }
-    
-    let mut size = bytes as f64;
-    let mut unit_index = 0;
-    
-    while size >= 1024.0 && unit_index < UNITS.len() - 1 {
-        size /= 1024.0;
-        unit_index += 1;
+    let mut parents: Vec<usize> = (0..images.len()).collect();
+    for i in 0..images.len() {
+        for j in i + 1..images.len() {
+            if images[i].1.distance(&images[j].1) <= threshold {
+                let (a, b) = (root(&mut parents, i), root(&mut parents, j));
+                parents[b] = a;
+            }
+        }
     }
-    
-    if unit_index == 0 {
-        format!("{} {}", bytes, UNITS[unit_index])
-    } else {
-        format!("{:.1} {}", size, UNITS[unit_index])
+
+    let mut groups: HashMap<usize, DuplicateGroup> = HashMap::new();
+    for (i, (file_entry, fingerprint)) in images.into_iter().enumerate() {
+        let group = groups.entry(root(&mut parents, i)).or_insert_with(|| {
+            DuplicateGroup::new(format!("{:016x}", fingerprint.hash), ComparisonMethod::Perceptual)
+        });
+        group.add_file(DuplicateFile::new(file_entry, None).with_fingerprint(fingerprint));
+    }
+    groups.into_values().collect()
+}
+
+impl Default for DuplicateDetector {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -777,6 +985,7 @@ mod tests {
             is_directory: false,
             is_hidden: false,
             permissions: FilePermissions::read_write(),
+            preview_metadata: None,
         };
         
         (file_entry, temp_file)
@@ -939,13 +1148,15 @@ mod tests {
     
     #[test]
     fn test_format_file_size() {
-        assert_eq!(format_file_size(0), "0 B");
-        assert_eq!(format_file_size(500), "500 B");
-        assert_eq!(format_file_size(1024), "1.0 KB");
-        assert_eq!(format_file_size(1536), "1.5 KB");
-        assert_eq!(format_file_size(1024 * 1024), "1.0 MB");
-        assert_eq!(format_file_size(1024 * 1024 * 1024), "1.0 GB");
-        assert_eq!(format_file_size(1024_u64.pow(4)), "1.0 TB");
+        use crate::utils::format::{format_size_with, FormatSettings};
+        let settings = FormatSettings::default();
+        assert_eq!(format_size_with(0, &settings), "0 B");
+        assert_eq!(format_size_with(500, &settings), "500 B");
+        assert_eq!(format_size_with(1024, &settings), "1.0 KiB");
+        assert_eq!(format_size_with(1536, &settings), "1.5 KiB");
+        assert_eq!(format_size_with(1024 * 1024, &settings), "1.0 MiB");
+        assert_eq!(format_size_with(1024 * 1024 * 1024, &settings), "1.0 GiB");
+        assert_eq!(format_size_with(1024_u64.pow(4), &settings), "1.0 TiB");
     }
     
     #[tokio::test]
@@ -1013,6 +1224,153 @@ mod tests {
         assert!(progress_count.load(Ordering::SeqCst) > 0);
     }
     
+    /// Write `image` to `dir/name` and describe it as a file entry
+    fn image_file_entry(dir: &Path, name: &str, image: &image::DynamicImage, jpeg_quality: Option<u8>) -> FileEntry {
+        let path = dir.join(name);
+        match jpeg_quality {
+            Some(quality) => {
+                let mut jpeg = Vec::new();
+                image::codecs::jpeg::JpegEncoder::new_with_quality(&mut jpeg, quality)
+                    .encode_image(image)
+                    .unwrap();
+                std::fs::write(&path, jpeg).unwrap();
+            }
+            None => image.save(&path).unwrap(),
+        }
+        let (mut file_entry, _) = create_test_file_entry(name, std::fs::metadata(&path).unwrap().len(), None);
+        file_entry.path = path;
+        file_entry
+    }
+
+    /// A gradient with a dark block, so its perceptual hash has structure
+    fn test_photo(width: u32, height: u32) -> image::DynamicImage {
+        image::DynamicImage::ImageRgb8(image::RgbImage::from_fn(width, height, |x, y| {
+            if x > width / 4 && x < width / 2 && y > height / 3 && y < height * 2 / 3 {
+                image::Rgb([20, 30, 40])
+            } else {
+                let shade = ((x * 255 / width + y * 255 / height) / 2) as u8;
+                image::Rgb([shade, 255 - shade, shade / 2])
+            }
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_perceptual_detection_groups_resaved_jpeg_with_original() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let photo = test_photo(400, 300);
+        let original = image_file_entry(dir.path(), "original.png", &photo, None);
+        let resaved = image_file_entry(
+            dir.path(),
+            "resaved.jpg",
+            &photo.resize(200, 150, image::imageops::FilterType::Lanczos3),
+            Some(60),
+        );
+        let unrelated = image_file_entry(dir.path(), "unrelated.png", &photo.fliph(), None);
+        let (notes, _notes_file) = create_test_file_entry("notes.txt", 5, Some("notes"));
+
+        let detector = DuplicateDetector::with_config(DuplicateDetectionConfig {
+            comparison_method: ComparisonMethod::Perceptual,
+            primary_selection: PrimarySelectionStrategy::HighestResolution,
+            ..Default::default()
+        });
+        let hashing_updates = std::sync::Arc::new(AtomicUsize::new(0));
+        let callback = {
+            let hashing_updates = hashing_updates.clone();
+            std::sync::Arc::new(move |progress: DetectionProgress| {
+                if progress.phase == DetectionPhase::Hashing && progress.current_file.is_some() {
+                    hashing_updates.fetch_add(1, Ordering::SeqCst);
+                }
+            })
+        };
+
+        let result = detector
+            .detect_duplicates(vec![resaved, unrelated, original, notes], Some(callback))
+            .await
+            .unwrap();
+
+        let duplicate_groups = result.duplicate_groups();
+        assert_eq!(duplicate_groups.len(), 1);
+        let group = duplicate_groups[0];
+        assert_eq!(group.comparison_method, ComparisonMethod::Perceptual);
+        let mut names: Vec<&str> = group.files.iter().map(|f| f.file_entry.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["original.png", "resaved.jpg"]);
+        assert_eq!(group.primary_file().unwrap().file_entry.name, "original.png");
+        // Every file reports progress, including the one that isn't an image
+        assert_eq!(hashing_updates.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_quick_hash_prefilter_fully_hashes_fewer_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut files = Vec::new();
+        let mut add_file = |name: String, data: Vec<u8>| {
+            let path = dir.path().join(&name);
+            std::fs::write(&path, &data).unwrap();
+            let (mut file_entry, _) = create_test_file_entry(&name, data.len() as u64, None);
+            file_entry.path = path;
+            files.push(file_entry);
+        };
+        // 40 same-sized "videos" that differ in their first bytes
+        for i in 0..40u8 {
+            let mut data = vec![0u8; 256 * 1024];
+            data[0] = i;
+            add_file(format!("clip{}.mp4", i), data);
+        }
+        // One real pair, and a decoy that only differs in the middle
+        add_file("copy_a.mp4".to_string(), vec![1u8; 300 * 1024]);
+        add_file("copy_b.mp4".to_string(), vec![1u8; 300 * 1024]);
+        let mut decoy = vec![1u8; 300 * 1024];
+        decoy[150 * 1024] = 2;
+        add_file("decoy.mp4".to_string(), decoy);
+        
+        let detect = |quick_hash_prefilter| {
+            let files = files.clone();
+            async move {
+                let detector = DuplicateDetector::with_config(DuplicateDetectionConfig {
+                    quick_hash_prefilter,
+                    ..Default::default()
+                });
+                let start = std::time::Instant::now();
+                let results = detector.detect_duplicates(files, None).await.unwrap();
+                (results, start.elapsed())
+            }
+        };
+        let (full, full_time) = detect(false).await;
+        let (prefiltered, prefiltered_time) = detect(true).await;
+        println!(
+            "full hash: {} files in {:?}; quick hash pre-filter: {} files in {:?}",
+            full.files_fully_hashed, full_time, prefiltered.files_fully_hashed, prefiltered_time
+        );
+        
+        assert_eq!(full.files_fully_hashed, 43);
+        // Only the pair and the decoy share a quick hash
+        assert_eq!(prefiltered.files_fully_hashed, 3);
+        for results in [&full, &prefiltered] {
+            let groups = results.duplicate_groups();
+            assert_eq!(groups.len(), 1);
+            let mut names: Vec<&str> = groups[0].files.iter().map(|f| f.file_entry.name.as_str()).collect();
+            names.sort();
+            assert_eq!(names, vec!["copy_a.mp4", "copy_b.mp4"]);
+        }
+    }
+    
+    #[test]
+    fn test_perceptual_threshold_controls_grouping() {
+        let (first, _) = create_test_file_entry("a.jpg", 100, None);
+        let (second, _) = create_test_file_entry("b.jpg", 100, None);
+        let fingerprint = |hash| ImageFingerprint { hash, width: 10, height: 10 };
+        let fingerprints = HashMap::from([
+            (first.path.clone(), fingerprint(0)),
+            (second.path.clone(), fingerprint(0b111)),
+        ]);
+
+        let groups = group_similar_images(vec![first.clone(), second.clone()], &fingerprints, 3);
+        assert_eq!(groups.len(), 1);
+        let groups = group_similar_images(vec![first, second], &fingerprints, 2);
+        assert_eq!(groups.len(), 2);
+    }
+
     #[tokio::test]
     async fn test_duplicate_detection_name_comparison() {
         let detector = DuplicateDetector::with_config(DuplicateDetectionConfig {
@@ -0,0 +1,957 @@
+//! File operations behind the Edit menu
+//!
+//! Each operation works through the selection item by item and reports every
+//! item's outcome, so one failing file neither stops the rest nor hides which
+//! files went wrong.
+
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use super::file_system::{generate_duplicate_name, ConflictResolution, FileSystemService};
+use super::operations::{
+    BatchOperation, CancellationToken, Command, CopyCommand, CopyDirectoryCommand, ErrorRecoveryManager, MoveCommand,
+    MoveDirectoryCommand, OperationError, OperationHistory, OperationResult, PermanentDeleteCommand, ProgressCallback, ProgressInfo,
+    ProgressTracker, RenameCommand, RestoreFromTrashCommand, RetryConfig, TrashCommand, LARGE_FILE_THRESHOLD,
+};
+use super::rename_template::{RenamePlanRow, RenameRule, TemplateRenameCommand};
+use super::trash_bin::TrashedItem;
+use super::FileEntry;
+
+/// Per-item outcome of a copy, move or duplicate
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FileOperationResult {
+    /// Where each successful item ended up
+    pub succeeded: Vec<PathBuf>,
+    /// Items left alone because the user chose to skip a name conflict
+    pub skipped: Vec<PathBuf>,
+    /// Items that failed, with the reason
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+impl FileOperationResult {
+    /// Whether no item failed
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+
+    /// One line per failed item, e.g. "photo.jpg: Permission denied"
+    pub fn failure_lines(&self) -> Vec<String> {
+        failure_lines(&self.failed)
+    }
+}
+
+/// What happened to one item of a delete
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeleteOutcome {
+    Trashed,
+    PermanentlyDeleted,
+    /// Left in place because the trash couldn't take it
+    Kept,
+    Failed(String),
+    /// Not reached because the delete was cancelled
+    NotStarted,
+}
+
+/// What a delete did, keeping trashed and permanently deleted items apart
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DeleteSummary {
+    pub trashed: Vec<PathBuf>,
+    pub deleted_permanently: Vec<PathBuf>,
+    /// Items the trash couldn't take, left where they were
+    pub kept: Vec<PathBuf>,
+    pub failed: Vec<(PathBuf, String)>,
+    /// Items not reached because the delete was cancelled
+    pub not_started: Vec<PathBuf>,
+}
+
+impl DeleteSummary {
+    pub fn merge(&mut self, other: DeleteSummary) {
+        self.trashed.extend(other.trashed);
+        self.deleted_permanently.extend(other.deleted_permanently);
+        self.kept.extend(other.kept);
+        self.failed.extend(other.failed);
+        self.not_started.extend(other.not_started);
+    }
+
+    /// Whether everything asked for was trashed or deleted
+    pub fn is_complete(&self) -> bool {
+        self.kept.is_empty() && self.failed.is_empty() && self.not_started.is_empty()
+    }
+
+    /// Every item with its outcome
+    pub fn outcomes(&self) -> Vec<(PathBuf, DeleteOutcome)> {
+        let tagged = |paths: &[PathBuf], outcome: DeleteOutcome| {
+            paths.iter().map(move |path| (path.clone(), outcome.clone())).collect::<Vec<_>>()
+        };
+        let mut outcomes = tagged(&self.trashed, DeleteOutcome::Trashed);
+        outcomes.extend(tagged(&self.deleted_permanently, DeleteOutcome::PermanentlyDeleted));
+        outcomes.extend(tagged(&self.kept, DeleteOutcome::Kept));
+        outcomes.extend(
+            self.failed
+                .iter()
+                .map(|(path, error)| (path.clone(), DeleteOutcome::Failed(error.clone()))),
+        );
+        outcomes.extend(tagged(&self.not_started, DeleteOutcome::NotStarted));
+        outcomes
+    }
+
+    /// Items that failed, for retrying just those
+    pub fn failed_paths(&self) -> Vec<PathBuf> {
+        self.failed.iter().map(|(path, _)| path.clone()).collect()
+    }
+
+    /// Whether cancelling left some items untouched
+    pub fn was_cancelled(&self) -> bool {
+        !self.not_started.is_empty()
+    }
+
+    /// Number of items removed from their folder
+    pub fn removed_count(&self) -> usize {
+        self.trashed.len() + self.deleted_permanently.len()
+    }
+
+    /// Number of items the delete was asked to remove
+    pub fn total_count(&self) -> usize {
+        self.removed_count() + self.kept.len() + self.failed.len() + self.not_started.len()
+    }
+
+    /// Whether anything was removed from its folder
+    pub fn removed_any(&self) -> bool {
+        !self.trashed.is_empty() || !self.deleted_permanently.is_empty()
+    }
+
+    /// One line per failed item, e.g. "photo.jpg: Permission denied"
+    pub fn failure_lines(&self) -> Vec<String> {
+        failure_lines(&self.failed)
+    }
+
+    /// Result summary, e.g. "2 items moved to the trash. 1 item deleted permanently"
+    ///
+    /// A cancelled delete leads with "Deleted N of M (cancelled)".
+    pub fn message(&self) -> String {
+        let mut parts = Vec::new();
+        if self.was_cancelled() {
+            parts.push(t!("delete_summary.cancelled", done = self.removed_count(), total = self.total_count()));
+        }
+        if !self.trashed.is_empty() {
+            parts.push(t!("delete_summary.trashed", count = self.trashed.len()));
+        }
+        if !self.deleted_permanently.is_empty() {
+            parts.push(t!("delete_summary.deleted_permanently", count = self.deleted_permanently.len()));
+        }
+        if !self.kept.is_empty() {
+            parts.push(t!("delete_summary.kept", count = self.kept.len()));
+        }
+        if !self.failed.is_empty() {
+            parts.push(t!("delete_summary.failed", errors = self.failure_lines().join("; ")));
+        }
+        parts.join(". ")
+    }
+}
+
+/// A running file operation, as shown in the progress dialog
+#[derive(Debug, Clone)]
+pub struct FileOperationProgress {
+    pub title: String,
+    pub operation: String,
+    pub total: usize,
+    /// Cancelled by the dialog; its progress counter counts finished items
+    pub cancellation: CancellationToken,
+    /// Byte counts of a copy or move, shown in place of the item count once known
+    pub transfer: Option<TransferProgress>,
+}
+
+/// Latest byte-level progress of a copy or move, written by the operation
+/// and read by the progress dialog
+#[derive(Debug, Clone, Default)]
+pub struct TransferProgress(Arc<std::sync::Mutex<Option<ProgressInfo>>>);
+
+impl TransferProgress {
+    pub fn latest(&self) -> Option<ProgressInfo> {
+        self.0.lock().ok().and_then(|latest| latest.clone())
+    }
+
+    /// A progress callback that keeps the latest update here
+    pub fn recorder(&self) -> ProgressCallback {
+        let latest = self.0.clone();
+        Arc::new(move |info| {
+            if let Ok(mut latest) = latest.lock() {
+                *latest = Some(info);
+            }
+        })
+    }
+}
+
+fn failure_lines(failed: &[(PathBuf, String)]) -> Vec<String> {
+    failed
+        .iter()
+        .map(|(path, error)| format!("{}: {}", display_name(path), error))
+        .collect()
+}
+
+fn display_name(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Copy, move, duplicate, rename and delete selected files
+///
+/// Trashing and restoring from the trash are recorded in the shared history
+/// as undoable batches and permanent deletion in its log only.
+pub struct FileOperationService {
+    fs: Arc<dyn FileSystemService>,
+    history: Arc<Mutex<OperationHistory>>,
+    /// Folder used in place of the system trash
+    trash_dir: Option<PathBuf>,
+    /// Files at or above this size are copied in chunks with byte progress
+    chunked_copy_threshold: u64,
+    /// Where failures are counted for the status bar
+    error_recovery: Option<Arc<std::sync::Mutex<ErrorRecoveryManager>>>,
+    /// How failed items in trash, delete and restore batches are retried
+    retry_policy: Option<RetryConfig>,
+    /// Check copied files against their source by hashing both
+    verify_integrity: bool,
+}
+
+impl FileOperationService {
+    pub fn new(fs: Arc<dyn FileSystemService>, history: Arc<Mutex<OperationHistory>>) -> Self {
+        Self {
+            fs,
+            history,
+            trash_dir: None,
+            chunked_copy_threshold: LARGE_FILE_THRESHOLD,
+            error_recovery: None,
+            retry_policy: None,
+            verify_integrity: false,
+        }
+    }
+
+    /// Move trashed items into `dir` instead of the system trash
+    pub fn with_trash_dir(mut self, dir: PathBuf) -> Self {
+        self.trash_dir = Some(dir);
+        self
+    }
+
+    /// Set the file size at which copies and moves report progress within a file
+    pub fn with_chunked_copy_threshold(mut self, threshold: u64) -> Self {
+        self.chunked_copy_threshold = threshold;
+        self
+    }
+
+    /// Record every failed item in `manager`
+    pub fn with_error_recovery(mut self, manager: Arc<std::sync::Mutex<ErrorRecoveryManager>>) -> Self {
+        self.error_recovery = Some(manager);
+        self
+    }
+
+    /// Retry items that fail for a transient reason as `config` describes
+    pub fn with_retry_policy(mut self, config: RetryConfig) -> Self {
+        self.retry_policy = Some(config);
+        self
+    }
+
+    /// Hash each copied or moved file against its source and roll back on a mismatch
+    pub fn with_verify_integrity(mut self, verify: bool) -> Self {
+        self.verify_integrity = verify;
+        self
+    }
+
+    /// Apply the retry policy, if any, to `batch`
+    fn with_retries(&self, batch: BatchOperation) -> BatchOperation {
+        match &self.retry_policy {
+            Some(config) => batch.with_retry_policy(config.clone()),
+            None => batch,
+        }
+    }
+
+    /// Copy `files` into `destination`, folders included
+    ///
+    /// `ask` is called for each item whose name is already taken there.
+    pub async fn copy_to<F, Fut>(&self, files: &[FileEntry], destination: &Path, ask: F) -> FileOperationResult
+    where
+        F: FnMut(PathBuf) -> Fut,
+        Fut: Future<Output = ConflictResolution>,
+    {
+        self.copy_to_with_progress(files, destination, ask, &CancellationToken::new(), Arc::new(|_| {})).await
+    }
+
+    /// Copy `files` into `destination`, reporting byte progress to `on_progress`
+    ///
+    /// Large files are copied in chunks so the progress moves within a file.
+    /// `cancellation` is checked between chunks and between items; a file cut
+    /// off mid-copy is removed again and reported as failed, and the items
+    /// after it are left alone.
+    pub async fn copy_to_with_progress<F, Fut>(
+        &self,
+        files: &[FileEntry],
+        destination: &Path,
+        ask: F,
+        cancellation: &CancellationToken,
+        on_progress: ProgressCallback,
+    ) -> FileOperationResult
+    where
+        F: FnMut(PathBuf) -> Fut,
+        Fut: Future<Output = ConflictResolution>,
+    {
+        self.transfer(Transfer::Copy, files, destination, ask, cancellation, on_progress).await
+    }
+
+    /// Move `files` into `destination`, folders included
+    ///
+    /// `ask` is called for each item whose name is already taken there.
+    pub async fn move_to<F, Fut>(&self, files: &[FileEntry], destination: &Path, ask: F) -> FileOperationResult
+    where
+        F: FnMut(PathBuf) -> Fut,
+        Fut: Future<Output = ConflictResolution>,
+    {
+        self.move_to_with_progress(files, destination, ask, &CancellationToken::new(), Arc::new(|_| {})).await
+    }
+
+    /// Move `files` into `destination`, reporting byte progress to `on_progress`
+    ///
+    /// Only moves across file systems copy any data; cancellation works as in
+    /// [`Self::copy_to_with_progress`] and never removes a source whose copy
+    /// didn't finish.
+    pub async fn move_to_with_progress<F, Fut>(
+        &self,
+        files: &[FileEntry],
+        destination: &Path,
+        ask: F,
+        cancellation: &CancellationToken,
+        on_progress: ProgressCallback,
+    ) -> FileOperationResult
+    where
+        F: FnMut(PathBuf) -> Fut,
+        Fut: Future<Output = ConflictResolution>,
+    {
+        self.transfer(Transfer::Move, files, destination, ask, cancellation, on_progress).await
+    }
+
+    /// Copy each item next to itself as "name copy N", folders included
+    pub async fn duplicate(&self, files: &[FileEntry]) -> FileOperationResult {
+        let mut result = FileOperationResult::default();
+        for file_entry in files {
+            let source = &file_entry.path;
+            let outcome = match generate_duplicate_name(source) {
+                Ok(duplicate) => self.copy_item(source, &duplicate, ConflictResolution::Ask, None).await,
+                Err(e) => Err(OperationError::FileSystem(e)),
+            };
+            self.note_failure(&outcome);
+            record(&mut result, source, outcome, "Duplicated");
+        }
+        result
+    }
+
+    /// Rename a file or folder as one undoable step, returning its new path
+    pub async fn rename(&self, current_path: &Path, new_name: &str) -> Result<PathBuf, String> {
+        crate::utils::validate_file_name(new_name).map_err(|e| e.message())?;
+
+        let parent_dir = current_path
+            .parent()
+            .ok_or_else(|| "Cannot determine parent directory".to_string())?;
+        let new_path = parent_dir.join(new_name);
+
+        if new_path.exists() && new_path != current_path {
+            return Err(format!("A file or folder named '{}' already exists", new_name));
+        }
+
+        let mut command = RenameCommand::new(current_path.to_path_buf(), new_name.to_string())
+            .map_err(|e| e.to_string())?;
+        command
+            .execute(self.fs.clone())
+            .await
+            .map_err(|e| format!("Failed to rename file: {}", e))?;
+        if let Err(e) = self.history.lock().await.add_executed_command(Box::new(command)).await {
+            tracing::warn!("Failed to record rename in history: {}", e);
+        }
+        Ok(new_path)
+    }
+
+    /// Rename the files from a template such as `{date:%Y-%m-%d}_{seq}.{ext}`
+    /// as one undoable step, returning the new paths
+    ///
+    /// An invalid template fails before any file is renamed, and a failed
+    /// rename rolls back the ones before it.
+    pub async fn rename_with_template(&self, files: &[FileEntry], template: &str) -> Result<Vec<PathBuf>, String> {
+        let mut command = TemplateRenameCommand::new(template, files).map_err(|e| e.to_string())?;
+        let renamed: Vec<PathBuf> = command.planned_renames().into_iter().map(|(_, new_path)| new_path).collect();
+        if renamed.is_empty() {
+            return Ok(renamed);
+        }
+        command.execute(self.fs.clone()).await.map_err(|e| e.to_string())?;
+        if let Err(e) = self.history.lock().await.add_executed_command(Box::new(command)).await {
+            tracing::warn!("Failed to record rename in history: {}", e);
+        }
+        Ok(renamed)
+    }
+
+    /// Run a previewed batch rename as one undoable step, returning the new paths
+    ///
+    /// Only rows that change a name are renamed; a plan with collisions or
+    /// invalid names fails before any file is renamed.
+    pub async fn rename_with_plan(&self, rule: &RenameRule, rows: &[RenamePlanRow]) -> Result<Vec<PathBuf>, String> {
+        let mut command = TemplateRenameCommand::from_plan(rule, rows).map_err(|e| e.to_string())?;
+        let renamed: Vec<PathBuf> = command.planned_renames().into_iter().map(|(_, new_path)| new_path).collect();
+        if renamed.is_empty() {
+            return Ok(renamed);
+        }
+        command.execute(self.fs.clone()).await.map_err(|e| e.to_string())?;
+        if let Err(e) = self.history.lock().await.add_executed_command(Box::new(command)).await {
+            tracing::warn!("Failed to record rename in history: {}", e);
+        }
+        Ok(renamed)
+    }
+
+    /// Move the files to the trash as one undoable batch
+    ///
+    /// Files that can't be trashed are reported without stopping the rest, and a
+    /// single Undo restores everything that was trashed. Nothing is deleted
+    /// permanently here: items the trash refuses are returned as `kept`.
+    ///
+    /// `cancellation` is checked between files; files trashed before it was
+    /// cancelled stay trashed and the rest are returned as `not_started`.
+    pub async fn trash(&self, files: &[FileEntry], cancellation: &CancellationToken) -> DeleteSummary {
+        let mut batch = self.with_retries(
+            BatchOperation::new("Delete".to_string(), format!("Move {} items to trash", files.len()))
+                .with_partial_failure(true)
+                .with_stop_token(cancellation.clone()),
+        );
+        for file_entry in files {
+            let mut command = TrashCommand::new(file_entry.path.clone());
+            if let Some(dir) = &self.trash_dir {
+                command = command.with_trash_dir(dir.clone());
+            }
+            batch.add_command(Box::new(command));
+        }
+
+        let result = batch.execute(self.fs.clone()).await;
+        let mut history = self.history.lock().await;
+        let mut summary = DeleteSummary::default();
+        for (command, error) in batch.failed_commands() {
+            history.log_failed_command(command, error);
+            let Some(path) = command.source_path() else {
+                continue;
+            };
+            if matches!(error, OperationError::TrashUnavailable(_)) {
+                summary.kept.push(path.to_path_buf());
+            } else {
+                self.note_error(error);
+                summary.failed.push((path.to_path_buf(), error.to_string()));
+            }
+        }
+        summary.trashed = executed_paths(&batch);
+        summary.not_started = not_started_paths(&batch);
+
+        if result.is_ok() && !summary.trashed.is_empty() {
+            tracing::info!("Moved {} items to trash", summary.trashed.len());
+            if let Err(e) = history.add_executed_command(Box::new(batch)).await {
+                tracing::warn!("Failed to record trash in history: {}", e);
+            }
+        }
+
+        summary
+    }
+
+    /// Delete the items permanently, skipping the trash
+    ///
+    /// The batch is written to the operation log but not to the undo stack,
+    /// since there is nothing to restore. `cancellation` is checked between items.
+    pub async fn delete_permanently(&self, paths: &[PathBuf], cancellation: &CancellationToken) -> DeleteSummary {
+        let mut batch = self.with_retries(
+            BatchOperation::new("Delete Permanently".to_string(), format!("Permanently delete {} items", paths.len()))
+                .with_partial_failure(true)
+                .with_stop_token(cancellation.clone()),
+        );
+        for path in paths {
+            batch.add_command(Box::new(PermanentDeleteCommand::new(path.clone())));
+        }
+
+        let _ = batch.execute(self.fs.clone()).await;
+        let history = self.history.lock().await;
+        let mut summary = DeleteSummary::default();
+        for (command, error) in batch.failed_commands() {
+            history.log_failed_command(command, error);
+            self.note_error(error);
+            if let Some(path) = command.source_path() {
+                summary.failed.push((path.to_path_buf(), error.to_string()));
+            }
+        }
+        summary.deleted_permanently = executed_paths(&batch);
+        summary.not_started = not_started_paths(&batch);
+
+        if !summary.deleted_permanently.is_empty() {
+            tracing::info!("Permanently deleted {} items", summary.deleted_permanently.len());
+            history.log_irreversible_command(&batch);
+        }
+
+        summary
+    }
+
+    /// Put items from the system trash back where they came from, as one undoable batch
+    ///
+    /// `succeeded` holds the restored paths; items whose original path is taken
+    /// again are reported as failed and stay in the trash.
+    pub async fn restore_from_trash(&self, items: &[TrashedItem]) -> FileOperationResult {
+        let mut batch = self.with_retries(
+            BatchOperation::new("Restore".to_string(), format!("Restore {} items from trash", items.len()))
+                .with_partial_failure(true),
+        );
+        for item in items {
+            batch.add_command(Box::new(RestoreFromTrashCommand::new(item.clone())));
+        }
+
+        let outcome = batch.execute(self.fs.clone()).await;
+        let mut history = self.history.lock().await;
+        let mut result = FileOperationResult::default();
+        for (command, error) in batch.failed_commands() {
+            history.log_failed_command(command, error);
+            self.note_error(error);
+            if let Some(path) = command.destination_path() {
+                result.failed.push((path.to_path_buf(), error.to_string()));
+            }
+        }
+        result.succeeded = batch
+            .commands
+            .iter()
+            .filter(|command| command.is_executed())
+            .filter_map(|command| command.destination_path().map(Path::to_path_buf))
+            .collect();
+
+        if outcome.is_ok() && !result.succeeded.is_empty() {
+            tracing::info!("Restored {} items from trash", result.succeeded.len());
+            if let Err(e) = history.add_executed_command(Box::new(batch)).await {
+                tracing::warn!("Failed to record restore in history: {}", e);
+            }
+        }
+
+        result
+    }
+
+    /// Count a failure in the error statistics; a cancellation is not one
+    fn note_error(&self, error: &OperationError) {
+        if matches!(error, OperationError::Cancelled) {
+            return;
+        }
+        if let Some(manager) = &self.error_recovery {
+            if let Ok(mut manager) = manager.lock() {
+                manager.record_error(error.clone());
+            }
+        }
+    }
+
+    fn note_failure<T>(&self, outcome: &OperationResult<T>) {
+        if let Err(e) = outcome {
+            self.note_error(e);
+        }
+    }
+
+    /// Copy or move each item into `destination` under one progress tracker
+    async fn transfer<F, Fut>(
+        &self,
+        transfer: Transfer,
+        files: &[FileEntry],
+        destination: &Path,
+        mut ask: F,
+        cancellation: &CancellationToken,
+        on_progress: ProgressCallback,
+    ) -> FileOperationResult
+    where
+        F: FnMut(PathBuf) -> Fut,
+        Fut: Future<Output = ConflictResolution>,
+    {
+        let (total_items, total_bytes) = self.estimate_transfer(files).await;
+        let mut tracker = ProgressTracker::with_cancellation(
+            total_items,
+            total_bytes,
+            transfer.action().to_string(),
+            cancellation.clone(),
+        )
+        .with_callback(on_progress);
+
+        let mut result = FileOperationResult::default();
+        for file_entry in files {
+            if cancellation.is_cancelled() {
+                tracing::info!("{} cancelled before {:?}", transfer.action(), file_entry.path);
+                break;
+            }
+            let source = &file_entry.path;
+            let Some(file_name) = source.file_name() else {
+                result.failed.push((source.clone(), format!("Invalid file name for: {:?}", source)));
+                continue;
+            };
+            let target = destination.join(file_name);
+
+            let mut outcome = self.transfer_item(transfer, source, &target, ConflictResolution::Ask, Some(&mut tracker)).await;
+            if let Err(OperationError::DestinationExists(existing)) = outcome {
+                let resolution = ask(existing).await;
+                outcome = self.transfer_item(transfer, source, &target, resolution, Some(&mut tracker)).await;
+            }
+            self.note_failure(&outcome);
+            record(&mut result, source, outcome, transfer.verb());
+            cancellation.increment_progress();
+        }
+        result
+    }
+
+    /// Number of files and bytes the progress of copying `files` is measured in
+    async fn estimate_transfer(&self, files: &[FileEntry]) -> (u64, u64) {
+        let mut totals = (0, 0);
+        for file_entry in files {
+            let (items, bytes) = if file_entry.path.is_dir() {
+                CopyDirectoryCommand::new(file_entry.path.clone(), PathBuf::new())
+                    .estimate_work(self.fs.clone())
+                    .await
+                    .unwrap_or((1, 0))
+            } else {
+                (1, file_entry.size)
+            };
+            totals = (totals.0 + items, totals.1 + bytes);
+        }
+        totals
+    }
+
+    async fn transfer_item(
+        &self,
+        transfer: Transfer,
+        source: &Path,
+        target: &Path,
+        resolution: ConflictResolution,
+        progress: Option<&mut ProgressTracker>,
+    ) -> OperationResult<Option<PathBuf>> {
+        match transfer {
+            Transfer::Copy => self.copy_item(source, target, resolution, progress).await,
+            Transfer::Move => self.move_item(source, target, resolution, progress).await,
+        }
+    }
+
+    /// Copy one item, returning where it landed unless skipped
+    async fn copy_item(
+        &self,
+        source: &Path,
+        target: &Path,
+        resolution: ConflictResolution,
+        progress: Option<&mut ProgressTracker>,
+    ) -> OperationResult<Option<PathBuf>> {
+        if source.is_dir() {
+            let mut command = CopyDirectoryCommand::new(source.to_path_buf(), target.to_path_buf())
+                .with_conflict_resolution(resolution);
+            command.execute_with_progress(self.fs.clone(), progress).await?;
+            Ok((!command.was_skipped()).then_some(command.destination))
+        } else {
+            let mut command = CopyCommand::new(source.to_path_buf(), target.to_path_buf())
+                .with_conflict_resolution(resolution)
+                .with_chunked_copy_threshold(self.chunked_copy_threshold)
+                .with_verify_integrity(self.verify_integrity);
+            command.execute_with_progress(self.fs.clone(), progress).await?;
+            Ok((!command.was_skipped()).then_some(command.destination))
+        }
+    }
+
+    /// Move one item, returning where it landed unless skipped
+    async fn move_item(
+        &self,
+        source: &Path,
+        target: &Path,
+        resolution: ConflictResolution,
+        progress: Option<&mut ProgressTracker>,
+    ) -> OperationResult<Option<PathBuf>> {
+        if source.is_dir() {
+            let mut command = MoveDirectoryCommand::new(source.to_path_buf(), target.to_path_buf())
+                .with_conflict_resolution(resolution);
+            command.execute_with_progress(self.fs.clone(), progress).await?;
+            Ok((!command.was_skipped()).then_some(command.destination))
+        } else {
+            let mut command = MoveCommand::new(source.to_path_buf(), target.to_path_buf())
+                .with_conflict_resolution(resolution)
+                .with_chunked_copy_threshold(self.chunked_copy_threshold)
+                .with_verify_integrity(self.verify_integrity);
+            command.execute_with_progress(self.fs.clone(), progress).await?;
+            Ok((!command.was_skipped()).then_some(command.destination))
+        }
+    }
+}
+
+/// Which of copy and move a transfer is
+#[derive(Debug, Clone, Copy)]
+enum Transfer {
+    Copy,
+    Move,
+}
+
+impl Transfer {
+    fn action(self) -> &'static str {
+        match self {
+            Transfer::Copy => "Copying",
+            Transfer::Move => "Moving",
+        }
+    }
+
+    fn verb(self) -> &'static str {
+        match self {
+            Transfer::Copy => "Copied",
+            Transfer::Move => "Moved",
+        }
+    }
+}
+
+/// Add one item's outcome to `result`
+fn record(result: &mut FileOperationResult, source: &Path, outcome: OperationResult<Option<PathBuf>>, verb: &str) {
+    match outcome {
+        Ok(Some(target)) => {
+            tracing::info!("{}: {:?} -> {:?}", verb, source, target);
+            result.succeeded.push(target);
+        }
+        Ok(None) => {
+            tracing::info!("Skipped {:?}: destination exists", source);
+            result.skipped.push(source.to_path_buf());
+        }
+        Err(e) => result.failed.push((source.to_path_buf(), e.to_string())),
+    }
+}
+
+/// Sources of the commands in `batch` that executed
+fn executed_paths(batch: &BatchOperation) -> Vec<PathBuf> {
+    batch
+        .commands
+        .iter()
+        .filter(|command| command.is_executed())
+        .filter_map(|command| command.source_path().map(Path::to_path_buf))
+        .collect()
+}
+
+/// Sources of the commands in `batch` that never ran because it was stopped
+fn not_started_paths(batch: &BatchOperation) -> Vec<PathBuf> {
+    if !batch.was_stopped() {
+        return Vec::new();
+    }
+    // Commands run in order, so everything after the attempted ones was skipped
+    let executed = batch.commands.iter().filter(|command| command.is_executed()).count();
+    let attempted = executed + batch.failures.len();
+    batch
+        .commands
+        .iter()
+        .skip(attempted)
+        .filter_map(|command| command.source_path().map(Path::to_path_buf))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::{translate_in, Locale};
+    use crate::services::file_system::NativeFileSystemService;
+    use tempfile::TempDir;
+
+    async fn entry(path: &Path) -> FileEntry {
+        NativeFileSystemService::new().get_metadata(path).await.unwrap()
+    }
+
+    fn service() -> FileOperationService {
+        let fs: Arc<dyn FileSystemService> = Arc::new(NativeFileSystemService::new());
+        let history = Arc::new(Mutex::new(OperationHistory::new(fs.clone())));
+        FileOperationService::new(fs, history)
+    }
+
+    #[test]
+    fn test_cancelled_delete_summary_counts_what_was_done() {
+        let summary = DeleteSummary {
+            trashed: vec![PathBuf::from("/photos/a.jpg")],
+            failed: vec![(PathBuf::from("/photos/b.jpg"), "Permission denied".to_string())],
+            not_started: vec![PathBuf::from("/photos/c.jpg"), PathBuf::from("/photos/d.jpg")],
+            ..DeleteSummary::default()
+        };
+        assert!(summary.was_cancelled());
+        assert!(!summary.is_complete());
+        assert_eq!((summary.removed_count(), summary.total_count()), (1, 4));
+        assert_eq!((summary.trashed.len(), summary.failed.len(), summary.not_started.len()), (1, 1, 2));
+        assert_eq!(
+            translate_in(Locale::English, "delete_summary.cancelled", None, &[("done", "1".to_string()), ("total", "4".to_string())]),
+            "Deleted 1 of 4 (cancelled)"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_trash_reports_each_item_when_some_fail() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let trash_dir = temp_dir.path().join(".trash");
+        let good = temp_dir.path().join("good.jpg");
+        let locked_dir = temp_dir.path().join("locked");
+        let locked = locked_dir.join("locked.jpg");
+        std::fs::create_dir(&locked_dir).unwrap();
+        std::fs::write(&good, b"good").unwrap();
+        std::fs::write(&locked, b"locked").unwrap();
+        let files = vec![entry(&good).await, entry(&locked).await];
+        // A read-only folder keeps its items from being removed
+        std::fs::set_permissions(&locked_dir, std::fs::Permissions::from_mode(0o555)).unwrap();
+
+        let summary = service()
+            .with_trash_dir(trash_dir)
+            .trash(&files, &CancellationToken::new())
+            .await;
+        std::fs::set_permissions(&locked_dir, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let outcomes = summary.outcomes();
+        assert_eq!(outcomes.len(), 2);
+        assert_eq!(outcomes[0], (good.clone(), DeleteOutcome::Trashed));
+        assert_eq!(outcomes[1].0, locked);
+        assert!(matches!(&outcomes[1].1, DeleteOutcome::Failed(reason) if reason.contains("write permission")));
+        assert_eq!(summary.failed_paths(), vec![locked.clone()]);
+        assert!(!good.exists());
+        assert!(locked.exists());
+    }
+
+    #[tokio::test]
+    async fn test_partial_failure_reports_each_item() {
+        let temp_dir = TempDir::new().unwrap();
+        let destination = temp_dir.path().join("archive");
+        std::fs::create_dir(&destination).unwrap();
+        let good = temp_dir.path().join("good.jpg");
+        let vanished = temp_dir.path().join("vanished.jpg");
+        std::fs::write(&good, b"good").unwrap();
+        std::fs::write(&vanished, b"gone soon").unwrap();
+        let files = vec![entry(&good).await, entry(&vanished).await];
+        std::fs::remove_file(&vanished).unwrap();
+
+        let service = service();
+        let ask = |_| async { ConflictResolution::Skip };
+        let result = service.copy_to(&files, &destination, ask).await;
+        assert_eq!(result.succeeded, vec![destination.join("good.jpg")]);
+        assert_eq!(result.failed.len(), 1);
+        assert_eq!(result.failed[0].0, vanished);
+        assert!(result.failure_lines()[0].starts_with("vanished.jpg: "));
+        assert!(!result.is_complete());
+
+        let result = service.duplicate(&files).await;
+        assert_eq!(result.succeeded, vec![temp_dir.path().join("good copy 1.jpg")]);
+        assert_eq!(result.failed.len(), 1);
+
+        let result = service.move_to(&files, &destination, ask).await;
+        assert_eq!(result.skipped, vec![good.clone()]);
+        assert_eq!(result.failed.len(), 1);
+        assert!(good.exists());
+    }
+
+    #[tokio::test]
+    async fn test_folders_are_copied_and_moved_with_their_contents() {
+        let temp_dir = TempDir::new().unwrap();
+        let folder = temp_dir.path().join("album");
+        std::fs::create_dir_all(folder.join("raw")).unwrap();
+        std::fs::write(folder.join("raw/beach.nef"), b"raw").unwrap();
+        let copies = temp_dir.path().join("copies");
+        let archive = temp_dir.path().join("archive");
+        std::fs::create_dir(&copies).unwrap();
+        std::fs::create_dir(&archive).unwrap();
+        let files = vec![entry(&folder).await];
+        let ask = |_| async { ConflictResolution::Skip };
+
+        let service = service();
+        assert_eq!(service.copy_to(&files, &copies, ask).await.succeeded, vec![copies.join("album")]);
+        assert_eq!(std::fs::read(copies.join("album/raw/beach.nef")).unwrap(), b"raw");
+        assert_eq!(service.move_to(&files, &archive, ask).await.succeeded, vec![archive.join("album")]);
+        assert!(!folder.exists());
+        assert_eq!(std::fs::read(archive.join("album/raw/beach.nef")).unwrap(), b"raw");
+    }
+
+    #[tokio::test]
+    async fn test_existing_destinations_are_resolved_per_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let destination = temp_dir.path().join("archive");
+        std::fs::create_dir(&destination).unwrap();
+        let taken = temp_dir.path().join("taken.jpg");
+        let free = temp_dir.path().join("free.jpg");
+        std::fs::write(&taken, b"new").unwrap();
+        std::fs::write(&free, b"free").unwrap();
+        std::fs::write(destination.join("taken.jpg"), b"old").unwrap();
+        let files = vec![entry(&taken).await, entry(&free).await];
+        let service = service();
+
+        // Only the taken name is asked about
+        let asked = std::sync::Mutex::new(Vec::new());
+        let ask = |path: PathBuf| {
+            asked.lock().unwrap().push(path);
+            async { ConflictResolution::Rename }
+        };
+        let result = service.copy_to(&files, &destination, ask).await;
+        assert_eq!(result.succeeded, vec![destination.join("taken copy 1.jpg"), destination.join("free.jpg")]);
+        assert_eq!(*asked.lock().unwrap(), vec![destination.join("taken.jpg")]);
+        assert_eq!(std::fs::read(destination.join("taken.jpg")).unwrap(), b"old");
+        assert_eq!(std::fs::read(destination.join("taken copy 1.jpg")).unwrap(), b"new");
+
+        // Skipping leaves the source where it was
+        let files = vec![entry(&taken).await];
+        let result = service.move_to(&files, &destination, |_| async { ConflictResolution::Skip }).await;
+        assert_eq!(result.skipped, vec![taken.clone()]);
+        assert!(taken.exists());
+        assert_eq!(std::fs::read(destination.join("taken.jpg")).unwrap(), b"old");
+
+        let result = service.move_to(&files, &destination, |_| async { ConflictResolution::Overwrite }).await;
+        assert_eq!(result.succeeded, vec![destination.join("taken.jpg")]);
+        assert!(!taken.exists());
+        assert_eq!(std::fs::read(destination.join("taken.jpg")).unwrap(), b"new");
+    }
+
+    #[tokio::test]
+    async fn test_large_copies_report_byte_progress_and_cancel_cleanly() {
+        let temp_dir = TempDir::new().unwrap();
+        let video = temp_dir.path().join("holiday.mp4");
+        let destination = temp_dir.path().join("backup");
+        std::fs::create_dir(&destination).unwrap();
+        std::fs::write(&video, vec![7u8; 12 * 1024 * 1024]).unwrap();
+        let files = vec![entry(&video).await];
+        let service = service().with_chunked_copy_threshold(1024 * 1024);
+        let ask = |_| async { ConflictResolution::Skip };
+
+        let updates = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = updates.clone();
+        let on_progress: ProgressCallback = Arc::new(move |info: ProgressInfo| recorded.lock().unwrap().push(info));
+        let result = service
+            .copy_to_with_progress(&files, &destination, ask, &CancellationToken::new(), on_progress)
+            .await;
+        assert_eq!(result.succeeded, vec![destination.join("holiday.mp4")]);
+
+        let bytes: Vec<u64> = updates.lock().unwrap().iter().map(|info| info.bytes_processed).collect();
+        assert!(bytes.len() > 2, "expected progress within the file, got {:?}", bytes);
+        assert!(bytes.windows(2).all(|pair| pair[0] <= pair[1]), "{:?}", bytes);
+        assert!(bytes.first() < bytes.last());
+        assert_eq!(bytes.last(), Some(&(12 * 1024 * 1024)));
+        assert!(updates.lock().unwrap().iter().all(|info| info.total_bytes == 12 * 1024 * 1024));
+
+        // Cancelling mid-file removes the partial copy
+        let cancellation = CancellationToken::new();
+        let token = cancellation.clone();
+        let on_progress: ProgressCallback = Arc::new(move |_| token.cancel());
+        let elsewhere = temp_dir.path().join("elsewhere");
+        std::fs::create_dir(&elsewhere).unwrap();
+        let result = service
+            .copy_to_with_progress(&files, &elsewhere, ask, &cancellation, on_progress)
+            .await;
+        assert!(result.succeeded.is_empty());
+        assert_eq!(result.failed.len(), 1);
+        assert_eq!(std::fs::read_dir(&elsewhere).unwrap().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_rename_rejects_taken_and_invalid_names() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("photo.jpg");
+        std::fs::write(&file, b"image").unwrap();
+        std::fs::write(temp_dir.path().join("taken.jpg"), b"other").unwrap();
+        let service = service();
+
+        assert!(service.rename(&file, "taken.jpg").await.is_err());
+        assert!(service.rename(&file, "a/b.jpg").await.is_err());
+        assert_eq!(service.rename(&file, "beach.jpg").await, Ok(temp_dir.path().join("beach.jpg")));
+        assert!(!file.exists());
+
+        service.history.lock().await.undo().await.unwrap();
+        assert!(file.exists());
+        assert!(!temp_dir.path().join("beach.jpg").exists());
+    }
+}
@@ -0,0 +1,446 @@
+//! Comparing two folder trees
+//!
+//! The walk reports each difference as soon as it is found, so large trees can
+//! be shown while they are still being compared. Folders present on only one
+//! side are reported once instead of file by file.
+
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::{self, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use thiserror::Error;
+
+use super::file_system::ConflictResolution;
+use super::operations::{
+    directory_copy_batch, CancellationToken, Command, CopyCommand, DeleteCommand, OperationError, OperationResult,
+};
+
+/// Buffer size used when comparing file contents
+const CONTENT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Errors that stop a folder comparison
+#[derive(Debug, Error)]
+pub enum FolderDiffError {
+    #[error("Failed to read {path}: {source}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("Comparison cancelled")]
+    Cancelled,
+}
+
+pub type FolderDiffResult<T> = Result<T, FolderDiffError>;
+
+/// How closely files present on both sides are compared
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CompareDepth {
+    /// Size and modification time (to the second)
+    #[default]
+    Metadata,
+    /// Size, then the full contents
+    Content,
+}
+
+impl CompareDepth {
+    pub const ALL: [CompareDepth; 2] = [CompareDepth::Metadata, CompareDepth::Content];
+}
+
+/// How a path differs between the two folders
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffStatus {
+    OnlyInA,
+    OnlyInB,
+    /// Present on both sides with different size, time or contents
+    Differs,
+}
+
+/// An action offered for a difference
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffAction {
+    CopyAToB,
+    CopyBToA,
+    /// Delete the side the path exists on
+    Delete,
+}
+
+/// One differing path, relative to both roots
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffEntry {
+    pub relative_path: PathBuf,
+    pub status: DiffStatus,
+    pub is_directory: bool,
+    pub size_a: Option<u64>,
+    pub size_b: Option<u64>,
+}
+
+impl DiffEntry {
+    /// Actions that make both sides agree on this path
+    pub fn actions(&self) -> &'static [DiffAction] {
+        match (self.status, self.is_directory) {
+            (DiffStatus::OnlyInA, false) => &[DiffAction::CopyAToB, DiffAction::Delete],
+            (DiffStatus::OnlyInB, false) => &[DiffAction::CopyBToA, DiffAction::Delete],
+            // Folders can be copied file by file, but there is no undoable folder delete
+            (DiffStatus::OnlyInA, true) => &[DiffAction::CopyAToB],
+            (DiffStatus::OnlyInB, true) => &[DiffAction::CopyBToA],
+            (DiffStatus::Differs, _) => &[DiffAction::CopyAToB, DiffAction::CopyBToA],
+        }
+    }
+
+    /// Undoable commands that perform `action` for this entry
+    pub fn action_commands(&self, action: DiffAction, root_a: &Path, root_b: &Path) -> OperationResult<Vec<Box<dyn Command>>> {
+        let in_a = root_a.join(&self.relative_path);
+        let in_b = root_b.join(&self.relative_path);
+        let (source, destination) = match action {
+            DiffAction::CopyAToB => (in_a, in_b),
+            DiffAction::CopyBToA => (in_b, in_a),
+            DiffAction::Delete => {
+                let path = match self.status {
+                    DiffStatus::OnlyInA => in_a,
+                    DiffStatus::OnlyInB => in_b,
+                    DiffStatus::Differs => {
+                        return Err(OperationError::ValidationFailed(
+                            "Pick a side to copy instead of deleting a file present in both folders".to_string(),
+                        ))
+                    }
+                };
+                if self.is_directory {
+                    return Err(OperationError::ValidationFailed(format!(
+                        "Deleting folders is not supported: {}",
+                        path.display()
+                    )));
+                }
+                return Ok(vec![Box::new(DeleteCommand::new(path))]);
+            }
+        };
+
+        if self.is_directory {
+            Ok(directory_copy_batch(&source, &destination)?.commands)
+        } else {
+            Ok(vec![Box::new(CopyCommand::new(source, destination).with_conflict_resolution(ConflictResolution::Overwrite))])
+        }
+    }
+}
+
+/// Totals of a finished comparison
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiffSummary {
+    /// Paths checked on both sides
+    pub compared: u64,
+    pub only_in_a: u64,
+    pub only_in_b: u64,
+    pub differs: u64,
+}
+
+impl DiffSummary {
+    fn record(&mut self, status: DiffStatus) {
+        match status {
+            DiffStatus::OnlyInA => self.only_in_a += 1,
+            DiffStatus::OnlyInB => self.only_in_b += 1,
+            DiffStatus::Differs => self.differs += 1,
+        }
+    }
+}
+
+/// Recursively compare `root_a` with `root_b`, passing each difference to `on_entry`
+///
+/// Entries arrive depth first in name order, so a folder's differences follow
+/// the differences of the folders listed before it.
+pub fn compare_folders(
+    root_a: &Path,
+    root_b: &Path,
+    depth: CompareDepth,
+    cancellation: &CancellationToken,
+    mut on_entry: impl FnMut(DiffEntry),
+) -> FolderDiffResult<DiffSummary> {
+    let mut summary = DiffSummary::default();
+    compare_dir(root_a, root_b, Path::new(""), depth, cancellation, &mut summary, &mut on_entry)?;
+    Ok(summary)
+}
+
+fn compare_dir(
+    root_a: &Path,
+    root_b: &Path,
+    relative: &Path,
+    depth: CompareDepth,
+    cancellation: &CancellationToken,
+    summary: &mut DiffSummary,
+    on_entry: &mut impl FnMut(DiffEntry),
+) -> FolderDiffResult<()> {
+    let listing_a = list_dir(&root_a.join(relative))?;
+    let listing_b = list_dir(&root_b.join(relative))?;
+
+    let mut names: Vec<&OsString> = listing_a.keys().chain(listing_b.keys()).collect();
+    names.sort();
+    names.dedup();
+
+    for name in names {
+        if cancellation.is_cancelled() {
+            return Err(FolderDiffError::Cancelled);
+        }
+        summary.compared += 1;
+        let relative_path = relative.join(name);
+        let a = listing_a.get(name);
+        let b = listing_b.get(name);
+
+        let status = match (a, b) {
+            (Some(a), Some(b)) if a.is_dir() && b.is_dir() => {
+                compare_dir(root_a, root_b, &relative_path, depth, cancellation, summary, on_entry)?;
+                continue;
+            }
+            (Some(a), Some(b)) if a.is_dir() == b.is_dir() => {
+                if files_match(&root_a.join(&relative_path), a, &root_b.join(&relative_path), b, depth)? {
+                    continue;
+                }
+                DiffStatus::Differs
+            }
+            // A file on one side and a folder on the other
+            (Some(_), Some(_)) => DiffStatus::Differs,
+            (Some(_), None) => DiffStatus::OnlyInA,
+            (None, Some(_)) => DiffStatus::OnlyInB,
+            (None, None) => continue,
+        };
+
+        summary.record(status);
+        on_entry(DiffEntry {
+            relative_path,
+            status,
+            is_directory: a.is_some_and(|m| m.is_dir()) || b.is_some_and(|m| m.is_dir()),
+            size_a: a.filter(|m| m.is_file()).map(|m| m.len()),
+            size_b: b.filter(|m| m.is_file()).map(|m| m.len()),
+        });
+    }
+    Ok(())
+}
+
+/// Metadata of the direct children of `dir`, by name
+fn list_dir(dir: &Path) -> FolderDiffResult<BTreeMap<OsString, fs::Metadata>> {
+    let read_error = |source| FolderDiffError::Read { path: dir.to_path_buf(), source };
+    let mut listing = BTreeMap::new();
+    for entry in fs::read_dir(dir).map_err(read_error)? {
+        let entry = entry.map_err(read_error)?;
+        // Follows symlinks so a linked file compares like the file itself
+        if let Ok(metadata) = fs::metadata(entry.path()) {
+            listing.insert(entry.file_name(), metadata);
+        }
+    }
+    Ok(listing)
+}
+
+fn files_match(
+    path_a: &Path,
+    a: &fs::Metadata,
+    path_b: &Path,
+    b: &fs::Metadata,
+    depth: CompareDepth,
+) -> FolderDiffResult<bool> {
+    if a.len() != b.len() {
+        return Ok(false);
+    }
+    match depth {
+        CompareDepth::Metadata => Ok(modified_secs(a) == modified_secs(b)),
+        CompareDepth::Content => contents_match(path_a, path_b),
+    }
+}
+
+/// Modification time truncated to whole seconds, which copies and backups usually keep
+fn modified_secs(metadata: &fs::Metadata) -> Option<u64> {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs())
+}
+
+fn contents_match(path_a: &Path, path_b: &Path) -> FolderDiffResult<bool> {
+    let open = |path: &Path| {
+        File::open(path)
+            .map(|file| BufReader::with_capacity(CONTENT_CHUNK_SIZE, file))
+            .map_err(|source| FolderDiffError::Read { path: path.to_path_buf(), source })
+    };
+    let mut reader_a = open(path_a)?;
+    let mut reader_b = open(path_b)?;
+    let mut chunk_a = vec![0; CONTENT_CHUNK_SIZE];
+    let mut chunk_b = vec![0; CONTENT_CHUNK_SIZE];
+
+    loop {
+        let read_a = read_chunk(&mut reader_a, &mut chunk_a, path_a)?;
+        let read_b = read_chunk(&mut reader_b, &mut chunk_b, path_b)?;
+        if read_a != read_b || chunk_a[..read_a] != chunk_b[..read_b] {
+            return Ok(false);
+        }
+        if read_a == 0 {
+            return Ok(true);
+        }
+    }
+}
+
+/// Fill `buffer` as far as possible, so both sides are compared in equal chunks
+fn read_chunk(reader: &mut impl Read, buffer: &mut [u8], path: &Path) -> FolderDiffResult<usize> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        match reader.read(&mut buffer[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(source) => return Err(FolderDiffError::Read { path: path.to_path_buf(), source }),
+        }
+    }
+    Ok(filled)
+}
+
+/// A row of the results tree: either a difference or a folder containing some
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffTreeRow {
+    pub depth: usize,
+    pub name: String,
+    /// Index into the entries the rows were built from; `None` for folder headers
+    pub entry: Option<usize>,
+}
+
+/// Arrange depth-first `entries` as an indented tree with headers for their folders
+pub fn diff_tree_rows(entries: &[DiffEntry]) -> Vec<DiffTreeRow> {
+    let mut rows = Vec::new();
+    let mut open_folders: Vec<OsString> = Vec::new();
+
+    for (index, entry) in entries.iter().enumerate() {
+        let components: Vec<OsString> = entry
+            .relative_path
+            .iter()
+            .map(|component| component.to_os_string())
+            .collect();
+        let Some((name, parents)) = components.split_last() else {
+            continue;
+        };
+
+        let shared = open_folders
+            .iter()
+            .zip(parents)
+            .take_while(|(open, parent)| open == parent)
+            .count();
+        open_folders.truncate(shared);
+        for parent in &parents[shared..] {
+            rows.push(DiffTreeRow {
+                depth: open_folders.len(),
+                name: parent.to_string_lossy().into_owned(),
+                entry: None,
+            });
+            open_folders.push(parent.clone());
+        }
+
+        rows.push(DiffTreeRow {
+            depth: open_folders.len(),
+            name: name.to_string_lossy().into_owned(),
+            entry: Some(index),
+        });
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, relative: &str, contents: &str) {
+        let path = root.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    fn copy_modified(from: &Path, to: &Path) {
+        let modified = fs::metadata(from).unwrap().modified().unwrap();
+        File::options().write(true).open(to).unwrap().set_modified(modified).unwrap();
+    }
+
+    fn collect(root_a: &Path, root_b: &Path, depth: CompareDepth) -> (Vec<(PathBuf, DiffStatus)>, DiffSummary) {
+        let mut entries = Vec::new();
+        let summary = compare_folders(root_a, root_b, depth, &CancellationToken::new(), |entry| {
+            entries.push((entry.relative_path, entry.status));
+        })
+        .unwrap();
+        (entries, summary)
+    }
+
+    #[test]
+    fn test_compare_folders_classifies_known_trees() {
+        let temp_dir = TempDir::new().unwrap();
+        let root_a = temp_dir.path().join("a");
+        let root_b = temp_dir.path().join("b");
+
+        write(&root_a, "same.txt", "same");
+        write(&root_b, "same.txt", "same");
+        copy_modified(&root_a.join("same.txt"), &root_b.join("same.txt"));
+        write(&root_a, "photos/only_a.jpg", "a");
+        write(&root_b, "photos/only_b.jpg", "b");
+        write(&root_a, "photos/resized.jpg", "large original");
+        write(&root_b, "photos/resized.jpg", "small");
+        write(&root_a, "archive/old.txt", "old");
+        write(&root_a, "archive/nested/older.txt", "older");
+        // Same size and timestamp, different bytes: only a content comparison notices
+        write(&root_a, "notes.txt", "abc");
+        write(&root_b, "notes.txt", "xyz");
+        copy_modified(&root_a.join("notes.txt"), &root_b.join("notes.txt"));
+
+        let (entries, summary) = collect(&root_a, &root_b, CompareDepth::Metadata);
+        assert_eq!(
+            entries,
+            vec![
+                (PathBuf::from("archive"), DiffStatus::OnlyInA),
+                (PathBuf::from("photos/only_a.jpg"), DiffStatus::OnlyInA),
+                (PathBuf::from("photos/only_b.jpg"), DiffStatus::OnlyInB),
+                (PathBuf::from("photos/resized.jpg"), DiffStatus::Differs),
+            ]
+        );
+        assert_eq!((summary.only_in_a, summary.only_in_b, summary.differs), (2, 1, 1));
+
+        let (entries, _) = collect(&root_a, &root_b, CompareDepth::Content);
+        assert!(entries.contains(&(PathBuf::from("notes.txt"), DiffStatus::Differs)));
+        assert!(!entries.iter().any(|(path, _)| path == Path::new("same.txt")));
+
+        let rows = diff_tree_rows(&[
+            DiffEntry {
+                relative_path: PathBuf::from("photos/2024/a.jpg"),
+                status: DiffStatus::OnlyInA,
+                is_directory: false,
+                size_a: Some(1),
+                size_b: None,
+            },
+            DiffEntry {
+                relative_path: PathBuf::from("photos/b.jpg"),
+                status: DiffStatus::OnlyInB,
+                is_directory: false,
+                size_a: None,
+                size_b: Some(1),
+            },
+        ]);
+        let outline: Vec<(usize, &str, Option<usize>)> =
+            rows.iter().map(|row| (row.depth, row.name.as_str(), row.entry)).collect();
+        assert_eq!(
+            outline,
+            vec![(0, "photos", None), (1, "2024", None), (2, "a.jpg", Some(0)), (1, "b.jpg", Some(1))]
+        );
+    }
+
+    #[test]
+    fn test_compare_folders_stops_when_cancelled() {
+        let temp_dir = TempDir::new().unwrap();
+        write(temp_dir.path(), "a/file.txt", "a");
+        write(temp_dir.path(), "b/other.txt", "b");
+
+        let cancellation = CancellationToken::new();
+        cancellation.cancel();
+        let result = compare_folders(
+            &temp_dir.path().join("a"),
+            &temp_dir.path().join("b"),
+            CompareDepth::Metadata,
+            &cancellation,
+            |_| {},
+        );
+        assert!(matches!(result, Err(FolderDiffError::Cancelled)));
+    }
+}
@@ -3,6 +3,7 @@ use std::path::{Path, PathBuf};
 use std::io;
 use tokio::fs::File;
 use tokio::io::{AsyncReadExt, BufReader};
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, error};
 use thiserror::Error;
 
@@ -12,6 +13,9 @@ const BUFFER_SIZE: usize = 64 * 1024;
 /// Maximum file size for hashing (1GB) to prevent memory issues
 const MAX_FILE_SIZE: u64 = 1024 * 1024 * 1024;
 
+/// Bytes read from each end of a file for a quick hash (64KB)
+pub const DEFAULT_QUICK_HASH_SAMPLE: usize = 64 * 1024;
+
 /// Errors that can occur during file hashing operations
 #[derive(Debug, Error)]
 pub enum HashingError {
@@ -154,9 +158,163 @@ impl HashingService {
     /// Hash a single file asynchronously
     pub async fn hash_file(&self, path: &Path) -> HashingResult<FileHash> {
         let start_time = std::time::Instant::now();
+        let file_size = self.check_hashable(path, tokio::fs::metadata(path).await)?;
+        
+        // Compute hash based on algorithm
+        let hash = match self.config.algorithm {
+            HashAlgorithm::Sha256 => self.compute_sha256(path).await?,
+        };
+        
+        Ok(self.finish(hash, path, file_size, start_time))
+    }
+    
+    /// Hash a single file on the calling thread
+    /// 
+    /// For dedicated worker threads, such as ones running at lowered I/O priority.
+    pub fn hash_file_blocking(&self, path: &Path) -> HashingResult<FileHash> {
+        let start_time = std::time::Instant::now();
+        let file_size = self.check_hashable(path, std::fs::metadata(path))?;
+        
+        let hash = match self.config.algorithm {
+            HashAlgorithm::Sha256 => {
+                use std::io::Read;
+                
+                let mut file = std::fs::File::open(path)?;
+                let mut hasher = Sha256::new();
+                let mut buffer = vec![0u8; self.config.buffer_size];
+                loop {
+                    let bytes_read = file.read(&mut buffer)?;
+                    if bytes_read == 0 {
+                        break;
+                    }
+                    hasher.update(&buffer[..bytes_read]);
+                }
+                format!("{:x}", hasher.finalize())
+            }
+        };
+        
+        Ok(self.finish(hash, path, file_size, start_time))
+    }
+    
+    /// Hash a whole file chunk by chunk on the calling thread, stopping when cancelled
+    /// 
+    /// Unlike [`hash_file_blocking`](Self::hash_file_blocking) there's no size
+    /// limit: only one buffer is held at a time, so multi-GB videos are fine.
+    pub fn hash_file_streaming(&self, path: &Path, cancellation: &CancellationToken) -> HashingResult<FileHash> {
+        use std::io::Read;
+        
+        let start_time = std::time::Instant::now();
+        let file_size = self.check_readable(path, std::fs::metadata(path))?;
+        
+        let hash = match self.config.algorithm {
+            HashAlgorithm::Sha256 => {
+                let mut file = std::fs::File::open(path)?;
+                let mut hasher = Sha256::new();
+                let mut buffer = vec![0u8; self.config.buffer_size];
+                loop {
+                    if cancellation.is_cancelled() {
+                        return Err(HashingError::Cancelled);
+                    }
+                    let bytes_read = file.read(&mut buffer)?;
+                    if bytes_read == 0 {
+                        break;
+                    }
+                    hasher.update(&buffer[..bytes_read]);
+                }
+                format!("{:x}", hasher.finalize())
+            }
+        };
+        
+        Ok(self.finish(hash, path, file_size, start_time))
+    }
+    
+    /// Hash a whole file chunk by chunk, passing the bytes read so far to `on_progress`
+    /// 
+    /// There's no size limit, as with [`hash_file_streaming`](Self::hash_file_streaming).
+    /// Hashing stops with [`HashingError::Cancelled`] as soon as `on_progress`
+    /// returns false.
+    pub async fn hash_file_with_progress<F>(&self, path: &Path, mut on_progress: F) -> HashingResult<FileHash>
+    where
+        F: FnMut(u64) -> bool,
+    {
+        let start_time = std::time::Instant::now();
+        let file_size = self.check_readable(path, tokio::fs::metadata(path).await)?;
+        
+        let hash = match self.config.algorithm {
+            HashAlgorithm::Sha256 => {
+                let mut file = File::open(path).await?;
+                let mut hasher = Sha256::new();
+                let mut buffer = vec![0u8; self.config.buffer_size];
+                let mut hashed = 0u64;
+                loop {
+                    let bytes_read = file.read(&mut buffer).await?;
+                    if bytes_read == 0 {
+                        break;
+                    }
+                    hasher.update(&buffer[..bytes_read]);
+                    hashed += bytes_read as u64;
+                    if !on_progress(hashed) {
+                        return Err(HashingError::Cancelled);
+                    }
+                }
+                format!("{:x}", hasher.finalize())
+            }
+        };
+        
+        Ok(self.finish(hash, path, file_size, start_time))
+    }
+    
+    /// Cheap fingerprint from the file size and its first and last `sample_size` bytes
+    /// 
+    /// Files with different quick hashes can't be identical, so it's a pre-filter
+    /// before full hashing; equal quick hashes still need a full hash to confirm.
+    pub fn quick_hash_blocking(&self, path: &Path, sample_size: usize) -> HashingResult<FileHash> {
+        use std::io::{Read, Seek, SeekFrom};
+        
+        let start_time = std::time::Instant::now();
+        let file_size = self.check_readable(path, std::fs::metadata(path))?;
+        
+        let mut file = std::fs::File::open(path)?;
+        let mut hasher = Sha256::new();
+        hasher.update(file_size.to_le_bytes());
+        let mut sample = Vec::with_capacity(sample_size);
+        (&mut file).take(sample_size as u64).read_to_end(&mut sample)?;
+        hasher.update(&sample);
+        let tail_start = file_size.saturating_sub(sample_size as u64);
+        if tail_start > sample_size as u64 {
+            file.seek(SeekFrom::Start(tail_start))?;
+            sample.clear();
+            file.take(sample_size as u64).read_to_end(&mut sample)?;
+            hasher.update(&sample);
+        } else if tail_start > 0 {
+            // The ends overlap, so the rest of the file is the tail
+            sample.clear();
+            file.read_to_end(&mut sample)?;
+            hasher.update(&sample);
+        }
+        
+        Ok(self.finish(format!("{:x}", hasher.finalize()), path, file_size, start_time))
+    }
+    
+    /// Validate that `path` may be hashed and return its size
+    fn check_hashable(&self, path: &Path, metadata: io::Result<std::fs::Metadata>) -> HashingResult<u64> {
+        let file_size = self.check_readable(path, metadata)?;
         
+        // Check if file is too large
+        if file_size > self.config.max_file_size {
+            return Err(HashingError::FileTooLarge { 
+                size: file_size, 
+                max: self.config.max_file_size 
+            });
+        }
+        
+        Ok(file_size)
+    }
+    
+    /// Validate that `path` exists and isn't a skipped hidden file, and return its size
+    fn check_readable(&self, path: &Path, metadata: io::Result<std::fs::Metadata>) -> HashingResult<u64> {
         // Validate file exists and get metadata
-        let metadata = tokio::fs::metadata(path).await.map_err(|e| {
+        let metadata = metadata.map_err(|e| {
             match e.kind() {
                 io::ErrorKind::NotFound => HashingError::FileNotFound { 
                     path: path.to_path_buf() 
@@ -167,15 +325,7 @@ impl HashingService {
                 _ => HashingError::Io(e),
             }
         })?;
-        
-        // Check if file is too large
         let file_size = metadata.len();
-        if file_size > self.config.max_file_size {
-            return Err(HashingError::FileTooLarge { 
-                size: file_size, 
-                max: self.config.max_file_size 
-            });
-        }
         
         // Skip hidden files if configured
         if self.config.skip_hidden && self.is_hidden_file(path) {
@@ -185,11 +335,10 @@ impl HashingService {
             });
         }
         
-        // Compute hash based on algorithm
-        let hash = match self.config.algorithm {
-            HashAlgorithm::Sha256 => self.compute_sha256(path).await?,
-        };
-        
+        Ok(file_size)
+    }
+    
+    fn finish(&self, hash: String, path: &Path, file_size: u64, start_time: std::time::Instant) -> FileHash {
         let computation_time = start_time.elapsed().as_millis() as u64;
         
         debug!(
@@ -199,12 +348,12 @@ impl HashingService {
             computation_time
         );
         
-        Ok(FileHash::new(
+        FileHash::new(
             hash,
             path.to_path_buf(),
             file_size,
             computation_time,
-        ))
+        )
     }
     
     /// Compute SHA-256 hash of a file
@@ -373,6 +522,76 @@ mod tests {
         assert!(!service.is_valid_hash("g94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"));
     }
     
+    #[test]
+    fn test_streaming_hash_ignores_size_limit_and_stops_when_cancelled() {
+        let temp_file = NamedTempFile::new().unwrap();
+        std::fs::write(temp_file.path(), vec![7u8; 10_000]).unwrap();
+        let service = HashingService::with_config(HashingConfig {
+            max_file_size: 1_000,
+            buffer_size: 1_024,
+            ..HashingConfig::default()
+        });
+        
+        assert!(matches!(service.hash_file_blocking(temp_file.path()), Err(HashingError::FileTooLarge { .. })));
+        let streamed = service.hash_file_streaming(temp_file.path(), &CancellationToken::new()).unwrap();
+        let unlimited = HashingService::new().hash_file_blocking(temp_file.path()).unwrap();
+        assert_eq!(streamed.hash, unlimited.hash);
+        
+        let cancelled = CancellationToken::new();
+        cancelled.cancel();
+        assert!(matches!(service.hash_file_streaming(temp_file.path(), &cancelled), Err(HashingError::Cancelled)));
+    }
+    
+    #[tokio::test]
+    async fn test_progress_hash_reports_bytes_and_stops_when_told() {
+        let temp_file = NamedTempFile::new().unwrap();
+        std::fs::write(temp_file.path(), vec![3u8; 10_000]).unwrap();
+        let service = HashingService::with_config(HashingConfig {
+            max_file_size: 1_000,
+            buffer_size: 4_096,
+            ..HashingConfig::default()
+        });
+        
+        let mut reported = Vec::new();
+        let hashed = service.hash_file_with_progress(temp_file.path(), |bytes| {
+            reported.push(bytes);
+            true
+        }).await.unwrap();
+        assert_eq!(reported, vec![4_096, 8_192, 10_000]);
+        assert_eq!(hashed.hash, HashingService::new().hash_file_blocking(temp_file.path()).unwrap().hash);
+        
+        let stopped = service.hash_file_with_progress(temp_file.path(), |bytes| bytes < 8_192).await;
+        assert!(matches!(stopped, Err(HashingError::Cancelled)));
+    }
+    
+    #[test]
+    fn test_quick_hash_only_reads_the_ends() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let write = |name: &str, middle: u8| {
+            let mut data = vec![1u8; 300];
+            data[100..200].fill(middle);
+            let path = dir.path().join(name);
+            std::fs::write(&path, data).unwrap();
+            path
+        };
+        let first = write("first.bin", 5);
+        let second = write("second.bin", 9);
+        let service = HashingService::new();
+        
+        // Same size, ends and sample: indistinguishable until fully hashed
+        let quick = |path: &Path| service.quick_hash_blocking(path, 100).unwrap().hash;
+        assert_eq!(quick(&first), quick(&second));
+        assert_ne!(
+            service.hash_file_blocking(&first).unwrap().hash,
+            service.hash_file_blocking(&second).unwrap().hash
+        );
+        // A sample that reaches the middle tells them apart
+        assert_ne!(
+            service.quick_hash_blocking(&first, 150).unwrap().hash,
+            service.quick_hash_blocking(&second, 150).unwrap().hash
+        );
+    }
+    
     #[test]
     fn test_file_hash_equality() {
         let hash1 = FileHash::new(
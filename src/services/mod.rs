@@ -12,14 +12,35 @@ pub mod preview_service;
 // pub mod performance_benchmarks;
 // pub mod memory_optimizer;
 pub mod operations;
+pub mod file_operations;
 pub mod hashing;
 pub mod background;
 pub mod duplicate_detection;
+pub mod perceptual_hash;
+pub mod archive;
+pub mod progress_hub;
+pub mod announcer;
+pub mod operation_log;
+pub mod organize;
+pub mod classifier;
+pub mod folder_diff;
+pub mod smart_folders;
+pub mod tags;
+pub mod selection_summary;
+pub mod trash_bin;
+pub mod content_search;
+pub mod rename_template;
+pub mod clipboard;
+pub mod single_instance;
+pub mod output;
+pub mod terminal;
+pub mod open_with;
 
 // Re-export only actively used types to reduce unused import warnings
 pub use file_system::{FileEntry, PreviewMetadata, ExifMetadata};
+pub use file_operations::{FileOperationService, FileOperationResult, FileOperationProgress, TransferProgress, DeleteSummary};
 pub use operations::{
-    ProgressInfo, ErrorSeverity
+    ProgressInfo, ErrorSeverity, ErrorRecoveryManager, ErrorStatistics, RetryConfig, RetrySettings
 };
 pub use hashing::{
     HashingService, FileHash
@@ -27,7 +48,7 @@ pub use hashing::{
 pub use background::{
     BackgroundProcessor,
     ProgressInfo as BackgroundProgressInfo, ProgressCallback as BackgroundProgressCallback, 
-    HashingTask
+    HashingTask, TaskPriority, IoPriority, ThrottlePolicy, ThrottleState, BackgroundThrottleSettings
 };
 pub use duplicate_detection::{
     DuplicateDetector, DuplicateDetectionResults,
@@ -0,0 +1,490 @@
+//! Open With: the applications that can open a file, and launching one
+//!
+//! Each platform is asked in its own way. macOS lists application bundles
+//! through Spotlight (`mdfind`), Windows reads the `OpenWithList` registry
+//! keys for the extension and freedesktop systems such as Linux read the
+//! `.desktop` files in the XDG data folders. The last application picked for
+//! an extension is remembered in the settings through [`OpenWithHistory`].
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Errors from launching an application
+#[derive(Debug, Error)]
+pub enum OpenWithError {
+    #[error("Failed to start {app}: {source}")]
+    Launch {
+        app: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("{0} can't be started")]
+    InvalidApplication(String),
+}
+
+pub type OpenWithResult<T> = Result<T, OpenWithError>;
+
+/// An application offered in the Open With picker
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenWithApp {
+    /// Name shown in the picker
+    pub name: String,
+    /// App bundle on macOS, executable name on Windows, `.desktop` file elsewhere
+    pub target: PathBuf,
+}
+
+impl OpenWithApp {
+    pub fn new(name: impl Into<String>, target: impl Into<PathBuf>) -> Self {
+        Self {
+            name: name.into(),
+            target: target.into(),
+        }
+    }
+}
+
+/// The application last used for each file extension
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct OpenWithHistory {
+    by_extension: BTreeMap<String, OpenWithApp>,
+}
+
+impl OpenWithHistory {
+    /// Extensions are matched without regard to case; files without one aren't remembered
+    fn key(path: &Path) -> Option<String> {
+        path.extension()
+            .and_then(|extension| extension.to_str())
+            .filter(|extension| !extension.is_empty())
+            .map(str::to_lowercase)
+    }
+
+    pub fn last_used(&self, path: &Path) -> Option<&OpenWithApp> {
+        self.by_extension.get(&Self::key(path)?)
+    }
+
+    /// Remember `app` for files with the extension of `path`
+    pub fn remember(&mut self, path: &Path, app: OpenWithApp) {
+        if let Some(key) = Self::key(path) {
+            self.by_extension.insert(key, app);
+        }
+    }
+}
+
+/// `apps` with `last_used` moved to the front, if it's among them
+pub fn with_last_used_first(mut apps: Vec<OpenWithApp>, last_used: Option<&OpenWithApp>) -> Vec<OpenWithApp> {
+    if let Some(index) = last_used.and_then(|last| apps.iter().position(|app| app == last)) {
+        let app = apps.remove(index);
+        apps.insert(0, app);
+    }
+    apps
+}
+
+/// Applications that can open `path`, sorted by name
+///
+/// This runs external tools and reads many small files, so call it off the
+/// UI thread.
+pub fn applications_for(path: &Path) -> Vec<OpenWithApp> {
+    let mut apps = platform::applications_for(path);
+    apps.sort_by_key(|app| app.name.to_lowercase());
+    apps.dedup_by(|a, b| a.target == b.target);
+    apps
+}
+
+/// Open `path` in `app` without waiting for it to exit
+pub fn launch(app: &OpenWithApp, path: &Path) -> OpenWithResult<()> {
+    let mut command = platform::command(app, path)?;
+    let mut child = command.spawn().map_err(|source| OpenWithError::Launch {
+        app: app.name.clone(),
+        source,
+    })?;
+    // Reap the process when it exits
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+/// Open `path` in the application the system uses for its type, or a folder
+/// in the system file manager
+pub fn open_default(path: &Path) -> OpenWithResult<()> {
+    let mut child = platform::default_command(path).spawn().map_err(|source| OpenWithError::Launch {
+        app: "the default application".to_string(),
+        source,
+    })?;
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::{OpenWithApp, OpenWithResult};
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+
+    /// Every application bundle Spotlight knows about
+    ///
+    /// Which of them claim a file type is only known to LaunchServices, so
+    /// the list isn't narrowed down by the file.
+    pub fn applications_for(_path: &Path) -> Vec<OpenWithApp> {
+        let output = match Command::new("mdfind")
+            .arg("kMDItemContentType == 'com.apple.application-bundle'")
+            .output()
+        {
+            Ok(output) => output,
+            Err(e) => {
+                tracing::warn!("Failed to list applications: {}", e);
+                return Vec::new();
+            }
+        };
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(PathBuf::from)
+            .filter(|bundle| bundle.extension().is_some_and(|extension| extension == "app"))
+            .filter_map(|bundle| {
+                let name = bundle.file_stem()?.to_string_lossy().into_owned();
+                Some(OpenWithApp::new(name, bundle))
+            })
+            .collect()
+    }
+
+    pub fn command(app: &OpenWithApp, path: &Path) -> OpenWithResult<Command> {
+        let mut command = Command::new("open");
+        command.arg("-a").arg(&app.target).arg(path);
+        Ok(command)
+    }
+
+    pub fn default_command(path: &Path) -> Command {
+        let mut command = Command::new("open");
+        command.arg(path);
+        command
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::{OpenWithApp, OpenWithResult};
+    use std::path::Path;
+    use std::process::Command;
+
+    /// Applications registered for the extension in the user's and the machine's `OpenWithList`
+    pub fn applications_for(path: &Path) -> Vec<OpenWithApp> {
+        let Some(extension) = path.extension().and_then(|extension| extension.to_str()) else {
+            return Vec::new();
+        };
+        let keys = [
+            format!(r"HKCU\Software\Microsoft\Windows\CurrentVersion\Explorer\FileExts\.{}\OpenWithList", extension),
+            format!(r"HKCR\.{}\OpenWithList", extension),
+        ];
+        keys.iter()
+            .filter_map(|key| Command::new("reg").args(["query", key]).output().ok())
+            .flat_map(|output| parse_open_with_list(&String::from_utf8_lossy(&output.stdout)))
+            .map(|executable| {
+                let name = Path::new(&executable)
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| executable.clone());
+                OpenWithApp::new(name, executable)
+            })
+            .collect()
+    }
+
+    /// Executable names in `reg query` output for an `OpenWithList` key
+    ///
+    /// The user's list keeps them as values named `a`, `b`, ... next to an
+    /// `MRUList`; the machine's list keeps them as subkeys.
+    pub fn parse_open_with_list(output: &str) -> Vec<String> {
+        output
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                if line.starts_with("HKEY_") {
+                    let (parent, subkey) = line.rsplit_once('\\')?;
+                    return parent.ends_with("OpenWithList").then(|| subkey.to_string());
+                }
+                let mut fields = line.split_whitespace();
+                let (name, kind) = (fields.next()?, fields.next()?);
+                let value = fields.collect::<Vec<_>>().join(" ");
+                (kind == "REG_SZ" && name != "MRUList" && !value.is_empty()).then_some(value)
+            })
+            .filter(|executable| executable.to_lowercase().ends_with(".exe"))
+            .collect()
+    }
+
+    pub fn command(app: &OpenWithApp, path: &Path) -> OpenWithResult<Command> {
+        // `start` finds executables registered under App Paths, not just those on PATH
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]).arg(&app.target).arg(path);
+        Ok(command)
+    }
+
+    pub fn default_command(path: &Path) -> Command {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]).arg(path);
+        command
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_parse_open_with_list() {
+            let user = "\r\nHKEY_CURRENT_USER\\Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\FileExts\\.jpg\\OpenWithList\r\n    a    REG_SZ    mspaint.exe\r\n    b    REG_SZ    Photo Viewer.exe\r\n    MRUList    REG_SZ    ba\r\n";
+            assert_eq!(parse_open_with_list(user), vec!["mspaint.exe", "Photo Viewer.exe"]);
+
+            let machine = "\r\nHKEY_CLASSES_ROOT\\.jpg\\OpenWithList\r\n    (Default)    REG_SZ    \r\n\r\nHKEY_CLASSES_ROOT\\.jpg\\OpenWithList\\notepad.exe\r\n";
+            assert_eq!(parse_open_with_list(machine), vec!["notepad.exe"]);
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+mod platform {
+    use super::{OpenWithApp, OpenWithError, OpenWithResult};
+    use std::collections::HashSet;
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+
+    /// The parts of a `.desktop` file the picker needs
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct DesktopEntry {
+        pub name: String,
+        pub exec: String,
+        pub mime_types: Vec<String>,
+    }
+
+    impl DesktopEntry {
+        /// Whether the entry claims `mime`, directly or through a wildcard like `image/*`
+        fn handles(&self, mime: &str) -> bool {
+            let family = mime.split('/').next().unwrap_or_default();
+            self.mime_types
+                .iter()
+                .any(|claimed| claimed == mime || claimed.strip_suffix("/*") == Some(family))
+        }
+    }
+
+    /// The visible application described by a `.desktop` file, if it is one
+    pub fn parse_desktop_entry(contents: &str) -> Option<DesktopEntry> {
+        let mut in_entry = false;
+        let (mut name, mut exec, mut mime_types) = (None, None, Vec::new());
+        let mut is_application = false;
+        for line in contents.lines().map(str::trim) {
+            if line.starts_with('[') {
+                in_entry = line == "[Desktop Entry]";
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=').filter(|_| in_entry) else {
+                continue;
+            };
+            match key.trim() {
+                "Type" => is_application = value.trim() == "Application",
+                "Name" => name = Some(value.trim().to_string()),
+                "Exec" => exec = Some(value.trim().to_string()),
+                "MimeType" => mime_types = value.split(';').filter(|mime| !mime.is_empty()).map(str::to_string).collect(),
+                "NoDisplay" | "Hidden" if value.trim() == "true" => return None,
+                _ => {}
+            }
+        }
+        if !is_application {
+            return None;
+        }
+        Some(DesktopEntry {
+            name: name?,
+            exec: exec?,
+            mime_types,
+        })
+    }
+
+    /// The command line for `exec` with `file` in place of its field codes
+    ///
+    /// Without a file field code the file is appended. Codes for icons,
+    /// names and deprecated ones are dropped.
+    pub fn expand_exec(exec: &str, file: &Path) -> Vec<String> {
+        let mut has_file = false;
+        let mut args = Vec::new();
+        for word in split_exec(exec) {
+            match word.as_str() {
+                "%f" | "%F" | "%u" | "%U" => {
+                    has_file = true;
+                    args.push(file.to_string_lossy().into_owned());
+                }
+                "%i" | "%c" | "%k" | "%d" | "%D" | "%n" | "%N" | "%v" | "%m" => {}
+                _ => args.push(word.replace("%%", "%")),
+            }
+        }
+        if !has_file && !args.is_empty() {
+            args.push(file.to_string_lossy().into_owned());
+        }
+        args
+    }
+
+    /// Split an `Exec` value into words, honoring double quotes and their escapes
+    fn split_exec(exec: &str) -> Vec<String> {
+        let mut words = Vec::new();
+        let mut word = String::new();
+        let (mut quoted, mut in_word) = (false, false);
+        let mut chars = exec.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => {
+                    quoted = !quoted;
+                    in_word = true;
+                }
+                '\\' if quoted => {
+                    if let Some(escaped) = chars.next() {
+                        word.push(escaped);
+                    }
+                }
+                c if c.is_whitespace() && !quoted => {
+                    if in_word {
+                        words.push(std::mem::take(&mut word));
+                        in_word = false;
+                    }
+                }
+                c => {
+                    word.push(c);
+                    in_word = true;
+                }
+            }
+        }
+        if in_word {
+            words.push(word);
+        }
+        words
+    }
+
+    /// Folders holding `.desktop` files, most important first
+    fn application_dirs() -> Vec<PathBuf> {
+        let data_home = std::env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|home| home.join(".local/share")));
+        let data_dirs = std::env::var("XDG_DATA_DIRS")
+            .ok()
+            .filter(|dirs| !dirs.is_empty())
+            .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+        data_home
+            .into_iter()
+            .chain(data_dirs.split(':').map(PathBuf::from))
+            .map(|dir| dir.join("applications"))
+            .collect()
+    }
+
+    fn mime_type(path: &Path) -> Option<String> {
+        let output = Command::new("xdg-mime").args(["query", "filetype"]).arg(path).output().ok()?;
+        let mime = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (output.status.success() && mime.contains('/')).then_some(mime)
+    }
+
+    /// Applications claiming the file's MIME type, or every application if none do
+    pub fn applications_for(path: &Path) -> Vec<OpenWithApp> {
+        let mut seen = HashSet::new();
+        let mut entries = Vec::new();
+        for dir in application_dirs() {
+            let Ok(read_dir) = std::fs::read_dir(&dir) else {
+                continue;
+            };
+            for file in read_dir.flatten().map(|entry| entry.path()) {
+                if file.extension().and_then(|extension| extension.to_str()) != Some("desktop") {
+                    continue;
+                }
+                // A file in an earlier folder hides one with the same name in later ones
+                if !seen.insert(file.file_name().map(|name| name.to_os_string())) {
+                    continue;
+                }
+                if let Some(entry) = std::fs::read_to_string(&file).ok().as_deref().and_then(parse_desktop_entry) {
+                    entries.push((entry, file));
+                }
+            }
+        }
+
+        let matching: Vec<_> = match mime_type(path) {
+            Some(mime) => entries.iter().filter(|(entry, _)| entry.handles(&mime)).collect(),
+            None => Vec::new(),
+        };
+        let offered = if matching.is_empty() { entries.iter().collect() } else { matching };
+        offered
+            .into_iter()
+            .map(|(entry, file)| OpenWithApp::new(entry.name.clone(), file.clone()))
+            .collect()
+    }
+
+    pub fn command(app: &OpenWithApp, path: &Path) -> OpenWithResult<Command> {
+        let invalid = || OpenWithError::InvalidApplication(app.name.clone());
+        let contents = std::fs::read_to_string(&app.target).map_err(|_| invalid())?;
+        let entry = parse_desktop_entry(&contents).ok_or_else(invalid)?;
+        let args = expand_exec(&entry.exec, path);
+        let (program, args) = args.split_first().ok_or_else(invalid)?;
+        let mut command = Command::new(program);
+        command.args(args);
+        Ok(command)
+    }
+
+    pub fn default_command(path: &Path) -> Command {
+        let mut command = Command::new("xdg-open");
+        command.arg(path);
+        command
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_parse_desktop_entry() {
+            let viewer = "[Desktop Entry]\nType=Application\nName=Image Viewer\nName[de]=Bildbetrachter\nExec=viewer %U\nMimeType=image/jpeg;image/png;\n\n[Desktop Action new-window]\nName=New Window\nExec=viewer --new\n";
+            let entry = parse_desktop_entry(viewer).unwrap();
+            assert_eq!(entry.name, "Image Viewer");
+            assert_eq!(entry.exec, "viewer %U");
+            assert!(entry.handles("image/png"));
+            assert!(!entry.handles("text/plain"));
+
+            let editor = parse_desktop_entry("[Desktop Entry]\nType=Application\nName=Editor\nExec=edit\nMimeType=text/*;\n").unwrap();
+            assert!(editor.handles("text/plain"));
+
+            assert_eq!(parse_desktop_entry("[Desktop Entry]\nType=Link\nName=Site\nURL=https://example.com\n"), None);
+            assert_eq!(parse_desktop_entry("[Desktop Entry]\nType=Application\nName=Helper\nExec=helper\nNoDisplay=true\n"), None);
+        }
+
+        #[test]
+        fn test_expand_exec_fills_in_the_file() {
+            let file = Path::new("/photos/my beach.jpg");
+            assert_eq!(expand_exec("viewer %U", file), vec!["viewer", "/photos/my beach.jpg"]);
+            assert_eq!(expand_exec("viewer %i %f", file), vec!["viewer", "/photos/my beach.jpg"]);
+            assert_eq!(expand_exec("viewer", file), vec!["viewer", "/photos/my beach.jpg"]);
+            assert_eq!(
+                expand_exec(r#""/opt/My Viewer/run" --rate 100%% %f"#, file),
+                vec!["/opt/My Viewer/run", "--rate", "100%", "/photos/my beach.jpg"]
+            );
+            assert!(expand_exec("", file).is_empty());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_remembers_the_last_app_per_extension() {
+        let viewer = OpenWithApp::new("Viewer", "/usr/share/applications/viewer.desktop");
+        let editor = OpenWithApp::new("Editor", "/usr/share/applications/editor.desktop");
+
+        let mut history = OpenWithHistory::default();
+        history.remember(Path::new("/photos/beach.JPG"), viewer.clone());
+        history.remember(Path::new("/notes/todo.txt"), viewer.clone());
+        history.remember(Path::new("/notes/todo.txt"), editor.clone());
+        history.remember(Path::new("/notes/README"), editor.clone());
+
+        let json = serde_json::to_string(&history).unwrap();
+        let restored: OpenWithHistory = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, history);
+        assert_eq!(restored.last_used(Path::new("/other/sunset.jpg")), Some(&viewer));
+        assert_eq!(restored.last_used(Path::new("/other/list.TXT")), Some(&editor));
+        assert_eq!(restored.last_used(Path::new("/notes/README")), None);
+        assert_eq!(restored.last_used(Path::new("/other/song.mp3")), None);
+
+        let apps = with_last_used_first(vec![editor.clone(), viewer.clone()], restored.last_used(Path::new("a.jpg")));
+        assert_eq!(apps, vec![viewer, editor]);
+    }
+}
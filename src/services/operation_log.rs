@@ -0,0 +1,359 @@
+//! Persistent audit trail of file operations
+//!
+//! The undo history only lives as long as the app and describes commands;
+//! the operation log records the outcome of every executed, undone or redone
+//! command as one JSON object per line under the app data dir. The log is
+//! append-only and rotated once it grows past a size cap.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::operations::{Command, HistoryEntry, OperationError, OperationKind};
+
+/// Size at which the active log file is rotated (5MB)
+pub const DEFAULT_MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Number of rotated log files kept next to the active one
+pub const DEFAULT_MAX_ROTATED_LOGS: usize = 3;
+
+/// Errors that can occur while reading or writing the operation log
+#[derive(Debug, Error)]
+pub enum OperationLogError {
+    #[error("Operation log I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Operation log serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+pub type OperationLogResult<T> = Result<T, OperationLogError>;
+
+/// What was done with a command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LogAction {
+    Execute,
+    Undo,
+    Redo,
+}
+
+impl LogAction {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            LogAction::Execute => "Execute",
+            LogAction::Undo => "Undo",
+            LogAction::Redo => "Redo",
+        }
+    }
+}
+
+/// Whether the recorded action succeeded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LogOutcome {
+    Success,
+    Failure,
+}
+
+impl LogOutcome {
+    pub const ALL: [LogOutcome; 2] = [LogOutcome::Success, LogOutcome::Failure];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            LogOutcome::Success => "Success",
+            LogOutcome::Failure => "Failure",
+        }
+    }
+}
+
+/// One line of the operation log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OperationLogRecord {
+    pub id: Uuid,
+    pub timestamp: DateTime<Utc>,
+    pub action: LogAction,
+    pub kind: OperationKind,
+    pub command_id: Uuid,
+    pub description: String,
+    pub source: Option<PathBuf>,
+    pub destination: Option<PathBuf>,
+    pub outcome: LogOutcome,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_id: Option<Uuid>,
+}
+
+impl OperationLogRecord {
+    /// Successful `action` on the command described by `entry`
+    pub fn new(action: LogAction, entry: &HistoryEntry, command: &dyn Command) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            action,
+            kind: command.kind(),
+            command_id: entry.command_id,
+            description: entry.command_description.clone(),
+            source: command.source_path().map(Path::to_path_buf),
+            destination: command.destination_path().map(Path::to_path_buf),
+            outcome: LogOutcome::Success,
+            error: None,
+            batch_id: entry.batch_id,
+        }
+    }
+
+    /// Mark the record as failed with `error`
+    pub fn with_error(mut self, error: &OperationError) -> Self {
+        self.outcome = LogOutcome::Failure;
+        self.error = Some(error.to_string());
+        self
+    }
+}
+
+/// Criteria for querying the log; unset fields match everything
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LogFilter {
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub kind: Option<OperationKind>,
+    pub outcome: Option<LogOutcome>,
+}
+
+impl LogFilter {
+    pub fn matches(&self, record: &OperationLogRecord) -> bool {
+        self.since.map_or(true, |since| record.timestamp >= since)
+            && self.until.map_or(true, |until| record.timestamp < until)
+            && self.kind.map_or(true, |kind| record.kind == kind)
+            && self.outcome.map_or(true, |outcome| record.outcome == outcome)
+    }
+}
+
+/// Append-only JSONL operation log with size-based rotation
+#[derive(Debug)]
+pub struct OperationLog {
+    path: PathBuf,
+    max_bytes: u64,
+    max_rotated: usize,
+    /// Serializes appends and rotation between threads
+    write_lock: Mutex<()>,
+}
+
+impl OperationLog {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            max_bytes: DEFAULT_MAX_LOG_BYTES,
+            max_rotated: DEFAULT_MAX_ROTATED_LOGS,
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    pub fn with_max_rotated(mut self, max_rotated: usize) -> Self {
+        self.max_rotated = max_rotated;
+        self
+    }
+
+    /// `operation_log.jsonl` in the user's data directory
+    pub fn default_path() -> PathBuf {
+        dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("media-organizer")
+            .join("operation_log.jsonl")
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append a record as a single line
+    ///
+    /// The line is written with one `write` on a file opened for appending and
+    /// synced before returning, so readers never see half a record.
+    pub fn append(&self, record: &OperationLogRecord) -> OperationLogResult<()> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+
+        let _guard = self.write_lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        self.rotate_if_needed(line.len() as u64)?;
+
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        file.write_all(&line)?;
+        file.sync_data()?;
+        Ok(())
+    }
+
+    /// Records matching `filter`, oldest first, across rotated files
+    ///
+    /// Lines that fail to parse are skipped.
+    pub fn query(&self, filter: &LogFilter) -> OperationLogResult<Vec<OperationLogRecord>> {
+        let mut records = Vec::new();
+        for path in self.files_oldest_first() {
+            let file = match File::open(&path) {
+                Ok(file) => file,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e.into()),
+            };
+            for line in BufReader::new(file).lines() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                match serde_json::from_str::<OperationLogRecord>(&line) {
+                    Ok(record) if filter.matches(&record) => records.push(record),
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!("Skipping malformed operation log line in {}: {}", path.display(), e),
+                }
+            }
+        }
+        Ok(records)
+    }
+
+    /// Write the records matching `filter` to `destination` as JSONL
+    ///
+    /// The export is written to a temporary file and renamed into place.
+    /// Returns the number of records exported.
+    pub fn export(&self, filter: &LogFilter, destination: &Path) -> OperationLogResult<usize> {
+        let records = self.query(filter)?;
+        let mut contents = Vec::new();
+        for record in &records {
+            serde_json::to_writer(&mut contents, record)?;
+            contents.push(b'\n');
+        }
+
+        let mut temp_name = destination.as_os_str().to_owned();
+        temp_name.push(".tmp");
+        let temp_path = PathBuf::from(temp_name);
+        fs::write(&temp_path, &contents)?;
+        if let Err(e) = fs::rename(&temp_path, destination) {
+            let _ = fs::remove_file(&temp_path);
+            return Err(e.into());
+        }
+        Ok(records.len())
+    }
+
+    /// Path of the `index`th rotated file, e.g. `operation_log.jsonl.1`
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut name = self.path.as_os_str().to_owned();
+        name.push(format!(".{}", index));
+        PathBuf::from(name)
+    }
+
+    fn files_oldest_first(&self) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = (1..=self.max_rotated).rev().map(|i| self.rotated_path(i)).collect();
+        files.push(self.path.clone());
+        files
+    }
+
+    /// Shift the rotated files up and start a new active file if `incoming` would overflow it
+    fn rotate_if_needed(&self, incoming: u64) -> OperationLogResult<()> {
+        let current = match fs::metadata(&self.path) {
+            Ok(metadata) => metadata.len(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+        if current == 0 || current + incoming <= self.max_bytes {
+            return Ok(());
+        }
+
+        if self.max_rotated == 0 {
+            fs::remove_file(&self.path)?;
+            return Ok(());
+        }
+        let _ = fs::remove_file(self.rotated_path(self.max_rotated));
+        for index in (1..self.max_rotated).rev() {
+            let from = self.rotated_path(index);
+            if from.exists() {
+                fs::rename(&from, self.rotated_path(index + 1))?;
+            }
+        }
+        fs::rename(&self.path, self.rotated_path(1))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::file_system::{FileSystemService, NativeFileSystemService};
+    use crate::services::operations::{CopyCommand, OperationHistory};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_copy_and_undo_produce_typed_records() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("photo.jpg");
+        std::fs::write(&source, b"image data").unwrap();
+        let destination = temp_dir.path().join("copy.jpg");
+        let log = Arc::new(OperationLog::new(temp_dir.path().join("logs").join("operation_log.jsonl")));
+
+        let fs: Arc<dyn FileSystemService> = Arc::new(NativeFileSystemService::new());
+        let mut history = OperationHistory::new(fs.clone()).with_log(log.clone());
+        let mut copy = CopyCommand::new(source.clone(), destination.clone());
+        copy.execute(fs).await.unwrap();
+        history.add_executed_command(Box::new(copy)).await.unwrap();
+        history.undo().await.unwrap();
+        assert!(!destination.exists());
+
+        let records = log.query(&LogFilter::default()).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].action, LogAction::Execute);
+        assert_eq!(records[1].action, LogAction::Undo);
+        for record in &records {
+            assert_eq!(record.kind, OperationKind::Copy);
+            assert_eq!(record.outcome, LogOutcome::Success);
+            assert_eq!(record.source.as_deref(), Some(source.as_path()));
+            assert_eq!(record.destination.as_deref(), Some(destination.as_path()));
+        }
+        assert_eq!(records[0].command_id, records[1].command_id);
+
+        let failures = LogFilter { outcome: Some(LogOutcome::Failure), ..LogFilter::default() };
+        assert!(log.query(&failures).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_rotation_caps_files_and_export_filters() {
+        let temp_dir = TempDir::new().unwrap();
+        let log = OperationLog::new(temp_dir.path().join("operation_log.jsonl"))
+            .with_max_bytes(600)
+            .with_max_rotated(2);
+
+        let command = CopyCommand::new(PathBuf::from("/a.jpg"), PathBuf::from("/b.jpg"));
+        let entry = HistoryEntry::new(&command);
+        for i in 0..20 {
+            let mut record = OperationLogRecord::new(LogAction::Execute, &entry, &command);
+            if i % 2 == 1 {
+                record = record.with_error(&OperationError::Cancelled);
+            }
+            log.append(&record).unwrap();
+        }
+
+        assert!(log.rotated_path(2).exists());
+        assert!(!log.rotated_path(3).exists());
+        for path in log.files_oldest_first() {
+            assert!(fs::metadata(path).unwrap().len() <= 600);
+        }
+
+        let kept = log.query(&LogFilter::default()).unwrap();
+        assert!(!kept.is_empty() && kept.len() < 20);
+
+        let failures = LogFilter { outcome: Some(LogOutcome::Failure), ..LogFilter::default() };
+        let export_path = temp_dir.path().join("export.jsonl");
+        let exported = log.export(&failures, &export_path).unwrap();
+        let lines = fs::read_to_string(&export_path).unwrap();
+        assert_eq!(lines.lines().count(), exported);
+        assert!(lines.lines().all(|line| line.contains("\"Failure\"")));
+    }
+}
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use async_trait::async_trait;
 use serde::{Serialize, Deserialize};
@@ -10,7 +10,11 @@ use std::collections::{HashMap, VecDeque};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use rand::Rng;
 
-use super::file_system::{FileSystemService, FileSystemError};
+use super::file_system::{generate_duplicate_name, ConflictResolution, FileOperation, FileSystemService, FileSystemError, MoveOutcome, OverwriteMode};
+use super::hashing::{HashingConfig, HashingError, HashingService, DEFAULT_QUICK_HASH_SAMPLE};
+use super::operation_log::{LogAction, OperationLog, OperationLogRecord};
+use super::trash_bin::{self, TrashedItem};
+use crate::utils::format::{format_duration, format_rate};
 
 /// Serialization module for SystemTime
 mod systemtime_serde {
@@ -48,6 +52,25 @@ pub enum ErrorSeverity {
     Critical,
 }
 
+impl ErrorSeverity {
+    /// Most severe first, the order the status bar lists them in
+    pub const DESCENDING: [ErrorSeverity; 4] = [
+        ErrorSeverity::Critical,
+        ErrorSeverity::High,
+        ErrorSeverity::Medium,
+        ErrorSeverity::Low,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ErrorSeverity::Low => "low",
+            ErrorSeverity::Medium => "medium",
+            ErrorSeverity::High => "high",
+            ErrorSeverity::Critical => "critical",
+        }
+    }
+}
+
 /// Error recovery classification
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RecoveryStrategy {
@@ -145,6 +168,12 @@ pub enum OperationError {
     #[error("Insufficient disk space: {0}")]
     InsufficientSpace(String),
     
+    #[error("Trash is not available: {0}")]
+    TrashUnavailable(String),
+    
+    #[error("Destination already exists: {}", .0.display())]
+    DestinationExists(PathBuf),
+    
     #[error("Network error: {0}")]
     Network(String),
     
@@ -153,6 +182,9 @@ pub enum OperationError {
     
     #[error("Operation timeout after {duration:?}")]
     Timeout { duration: Duration },
+    
+    #[error("Copy at {} doesn't match the original: source hash {source_hash}, destination hash {destination_hash}", .path.display())]
+    IntegrityMismatch { path: PathBuf, source_hash: String, destination_hash: String },
 }
 
 /// Result type for operation commands
@@ -174,6 +206,12 @@ impl OperationError {
             // Permission and space issues - require intervention
             Self::PermissionDenied(_) => (ErrorSeverity::High, RecoveryStrategy::ManualIntervention),
             Self::InsufficientSpace(_) => (ErrorSeverity::High, RecoveryStrategy::ManualIntervention),
+            // Falling back to permanent deletion is the user's call
+            Self::TrashUnavailable(_) => (ErrorSeverity::Medium, RecoveryStrategy::ManualIntervention),
+            // Raised by `ConflictResolution::Ask`; the user picks how to resolve it
+            Self::DestinationExists(_) => (ErrorSeverity::Medium, RecoveryStrategy::ManualIntervention),
+            // The copy was rolled back; a failing disk shouldn't be retried blindly
+            Self::IntegrityMismatch { .. } => (ErrorSeverity::High, RecoveryStrategy::ManualIntervention),
             
             // Validation errors - may be fixable
             Self::ValidationFailed(_) => (ErrorSeverity::Medium, RecoveryStrategy::Skip),
@@ -219,6 +257,7 @@ impl OperationError {
             FileSystemError::FileTooLarge { .. } => (ErrorSeverity::High, RecoveryStrategy::Skip),
             FileSystemError::NotSupported { .. } => (ErrorSeverity::High, RecoveryStrategy::Skip),
             FileSystemError::FileSystem { .. } => (ErrorSeverity::Medium, RecoveryStrategy::RetryWithBackoff),
+            FileSystemError::IntegrityMismatch { .. } => (ErrorSeverity::High, RecoveryStrategy::ManualIntervention),
         }
     }
     
@@ -341,20 +380,126 @@ impl RetryConfig {
         let base_delay = self.initial_delay.as_millis() as f64 
             * self.backoff_multiplier.powi(attempt as i32);
         
-        let delay_ms = base_delay.min(self.max_delay.as_millis() as f64);
+        let max_delay_ms = self.max_delay.as_millis() as f64;
+        let delay_ms = base_delay.min(max_delay_ms);
         
         let final_delay = if self.jitter {
-            // Add ±25% jitter
+            // Add ±25% jitter, never past the maximum
             let jitter_range = delay_ms * 0.25;
             let mut rng = rand::thread_rng();
             let jitter = (rng.gen::<f64>() - 0.5) * 2.0 * jitter_range;
-            (delay_ms + jitter).max(0.0)
+            (delay_ms + jitter).clamp(0.0, max_delay_ms)
         } else {
             delay_ms
         };
         
         Duration::from_millis(final_delay as u64)
     }
+    
+    /// Reject policies that would retry forever, back off to nothing or shrink delays
+    pub fn validate(&self) -> OperationResult<()> {
+        if !RETRY_ATTEMPTS_RANGE.contains(&self.max_attempts) {
+            return Err(OperationError::ValidationFailed(format!(
+                "retry attempts {} is outside {}..={}",
+                self.max_attempts,
+                RETRY_ATTEMPTS_RANGE.start(),
+                RETRY_ATTEMPTS_RANGE.end()
+            )));
+        }
+        let initial_ms = self.initial_delay.as_millis() as u64;
+        if !RETRY_DELAY_MS_RANGE.contains(&initial_ms) {
+            return Err(OperationError::ValidationFailed(format!(
+                "initial retry delay {} ms is outside {}..={} ms",
+                initial_ms,
+                RETRY_DELAY_MS_RANGE.start(),
+                RETRY_DELAY_MS_RANGE.end()
+            )));
+        }
+        let max_ms = self.max_delay.as_millis() as u64;
+        if max_ms < initial_ms || max_ms > *RETRY_DELAY_MS_RANGE.end() {
+            return Err(OperationError::ValidationFailed(format!(
+                "maximum retry delay {} ms must be between the initial delay and {} ms",
+                max_ms,
+                RETRY_DELAY_MS_RANGE.end()
+            )));
+        }
+        if !RETRY_MULTIPLIER_RANGE.contains(&self.backoff_multiplier) {
+            return Err(OperationError::ValidationFailed(format!(
+                "backoff multiplier {} is outside {}..={}",
+                self.backoff_multiplier,
+                RETRY_MULTIPLIER_RANGE.start(),
+                RETRY_MULTIPLIER_RANGE.end()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Retry attempts the settings accept
+pub const RETRY_ATTEMPTS_RANGE: std::ops::RangeInclusive<u32> = 0..=10;
+
+/// Retry delays the settings accept, in milliseconds
+pub const RETRY_DELAY_MS_RANGE: std::ops::RangeInclusive<u64> = 1..=600_000;
+
+/// Backoff multipliers the settings accept; below 1.0 delays would shrink
+pub const RETRY_MULTIPLIER_RANGE: std::ops::RangeInclusive<f64> = 1.0..=10.0;
+
+/// User-facing retry policy, stored in the settings file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetrySettings {
+    /// Retries after the first failed attempt
+    pub max_attempts: u32,
+    /// Delay before the first retry
+    pub initial_delay_ms: u64,
+    /// Longest delay between retries
+    pub max_delay_ms: u64,
+    /// Growth of the delay after each retry
+    pub backoff_multiplier: f64,
+    /// Vary delays by up to ±25% so retries don't line up
+    pub jitter: bool,
+}
+
+impl Default for RetrySettings {
+    fn default() -> Self {
+        let config = RetryConfig::default();
+        Self {
+            max_attempts: config.max_attempts,
+            initial_delay_ms: config.initial_delay.as_millis() as u64,
+            max_delay_ms: config.max_delay.as_millis() as u64,
+            backoff_multiplier: config.backoff_multiplier,
+            jitter: config.jitter,
+        }
+    }
+}
+
+impl RetrySettings {
+    /// The policy these settings describe, not yet validated
+    pub fn to_config(&self) -> RetryConfig {
+        RetryConfig {
+            max_attempts: self.max_attempts,
+            initial_delay: Duration::from_millis(self.initial_delay_ms),
+            max_delay: Duration::from_millis(self.max_delay_ms),
+            backoff_multiplier: self.backoff_multiplier,
+            jitter: self.jitter,
+        }
+    }
+    
+    pub fn validate(&self) -> OperationResult<()> {
+        self.to_config().validate()
+    }
+    
+    /// The configured policy, or the default one if a hand-edited file is out of range
+    pub fn effective_config(&self) -> RetryConfig {
+        let config = self.to_config();
+        match config.validate() {
+            Ok(()) => config,
+            Err(e) => {
+                tracing::warn!("Ignoring retry settings: {}", e);
+                RetryConfig::default()
+            }
+        }
+    }
 }
 
 /// Error recovery manager for handling retry logic and error classification
@@ -547,6 +692,16 @@ impl ErrorRecoveryManager {
         }
     }
     
+    /// Errors recorded at or after `since`, most recent first
+    pub fn recent_errors(&self, since: SystemTime) -> Vec<(SystemTime, OperationError)> {
+        self.error_history
+            .iter()
+            .rev()
+            .filter(|(time, _)| *time >= since)
+            .cloned()
+            .collect()
+    }
+    
     /// Clear error history
     pub fn clear_history(&mut self) {
         self.error_history.clear();
@@ -579,6 +734,24 @@ pub struct ErrorStatistics {
     pub time_window: Duration,
 }
 
+impl ErrorStatistics {
+    /// Error count of each severity that occurred, most severe first
+    pub fn severity_breakdown(&self) -> Vec<(ErrorSeverity, usize)> {
+        ErrorSeverity::DESCENDING
+            .iter()
+            .filter_map(|severity| {
+                let count = self.by_severity.get(severity).copied().unwrap_or(0);
+                (count > 0).then_some((*severity, count))
+            })
+            .collect()
+    }
+    
+    /// Most severe level among the errors, if there were any
+    pub fn highest_severity(&self) -> Option<ErrorSeverity> {
+        self.severity_breakdown().first().map(|(severity, _)| *severity)
+    }
+}
+
 /// Status of a command
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CommandStatus {
@@ -592,6 +765,45 @@ pub enum CommandStatus {
     Undone,
 }
 
+/// Kind of file operation a command performs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OperationKind {
+    Copy,
+    Move,
+    Delete,
+    Rename,
+    CreateDirectory,
+    Extract,
+    Compress,
+    Other,
+}
+
+impl OperationKind {
+    pub const ALL: [OperationKind; 8] = [
+        OperationKind::Copy,
+        OperationKind::Move,
+        OperationKind::Delete,
+        OperationKind::Rename,
+        OperationKind::CreateDirectory,
+        OperationKind::Extract,
+        OperationKind::Compress,
+        OperationKind::Other,
+    ];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            OperationKind::Copy => "Copy",
+            OperationKind::Move => "Move",
+            OperationKind::Delete => "Delete",
+            OperationKind::Rename => "Rename",
+            OperationKind::CreateDirectory => "Create Folder",
+            OperationKind::Extract => "Extract",
+            OperationKind::Compress => "Compress",
+            OperationKind::Other => "Other",
+        }
+    }
+}
+
 /// Metadata about command execution
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommandMetadata {
@@ -710,7 +922,7 @@ impl ProgressInfo {
     
     /// Format speed as human-readable string
     pub fn format_speed(&self) -> String {
-        format_bytes_per_second(self.speed_bps)
+        format_rate(self.speed_bps)
     }
     
     /// Format ETA as human-readable string
@@ -895,41 +1107,6 @@ impl ProgressTracker {
     }
 }
 
-/// Helper function to format bytes per second as human-readable string
-fn format_bytes_per_second(bps: u64) -> String {
-    const UNITS: &[(&str, u64)] = &[
-        ("B/s", 1),
-        ("KB/s", 1024),
-        ("MB/s", 1024 * 1024),
-        ("GB/s", 1024 * 1024 * 1024),
-    ];
-    
-    for &(unit, threshold) in UNITS.iter().rev() {
-        if bps >= threshold {
-            let value = bps as f64 / threshold as f64;
-            return format!("{:.1} {}", value, unit);
-        }
-    }
-    
-    format!("{} B/s", bps)
-}
-
-/// Helper function to format duration as human-readable string
-fn format_duration(duration: Duration) -> String {
-    let total_seconds = duration.as_secs();
-    let hours = total_seconds / 3600;
-    let minutes = (total_seconds % 3600) / 60;
-    let seconds = total_seconds % 60;
-    
-    if hours > 0 {
-        format!("{}h {}m {}s", hours, minutes, seconds)
-    } else if minutes > 0 {
-        format!("{}m {}s", minutes, seconds)
-    } else {
-        format!("{}s", seconds)
-    }
-}
-
 /// Core Command trait for implementing the Command pattern
 /// 
 /// All file operations implement this trait to enable undo/redo functionality.
@@ -963,6 +1140,28 @@ pub trait Command: Send + Sync + std::fmt::Debug {
     /// Get a human-readable description of the command
     fn description(&self) -> String;
     
+    /// Kind of operation, used to categorize the operation log
+    fn kind(&self) -> OperationKind {
+        OperationKind::Other
+    }
+    
+    /// Path the command reads from or acts on
+    fn source_path(&self) -> Option<&Path> {
+        None
+    }
+    
+    /// Path the command writes to, if different from the source
+    fn destination_path(&self) -> Option<&Path> {
+        None
+    }
+    
+    /// Serializable form of the command, so it can still be undone after a restart
+    /// 
+    /// Commands that return `None` are kept in the history file as metadata only.
+    fn command_kind(&self) -> Option<CommandKind> {
+        None
+    }
+    
     /// Check if the command can be undone
     fn can_undo(&self) -> bool {
         matches!(self.metadata().status, CommandStatus::Executed)
@@ -1026,6 +1225,23 @@ pub trait Command: Send + Sync + std::fmt::Debug {
     fn supports_progress(&self) -> bool {
         false
     }
+    
+    /// Whether this command must run on its own in a parallel batch
+    /// 
+    /// Serial commands act as barriers: everything queued before them finishes first,
+    /// and nothing queued after them starts until they complete.
+    fn must_run_serially(&self) -> bool {
+        false
+    }
+    
+    /// Paths this command reads or writes
+    /// 
+    /// A parallel batch never runs two commands at once whose paths overlap, that
+    /// is, are the same path or one contains the other. A command that reports no
+    /// paths can't be shown to be independent and runs on its own.
+    fn affects_paths(&self) -> Vec<&Path> {
+        self.source_path().into_iter().chain(self.destination_path()).collect()
+    }
 }
 
 /// Configuration for operation history management
@@ -1055,6 +1271,54 @@ impl Default for HistoryConfig {
     }
 }
 
+/// Parameters and undo state of a command, as written to the history file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "command")]
+pub enum CommandKind {
+    Copy(CopyCommand),
+    Move(MoveCommand),
+    Delete(DeleteCommand),
+    Trash(TrashCommand),
+    Rename(RenameCommand),
+    CreateDirectory(CreateDirectoryCommand),
+    CopyDirectory(CopyDirectoryCommand),
+    MoveDirectory(MoveDirectoryCommand),
+    RestoreFromTrash(RestoreFromTrashCommand),
+    /// A batch, holding only the commands that had executed
+    Batch {
+        name: String,
+        description: String,
+        metadata: CommandMetadata,
+        commands: Vec<CommandKind>,
+    },
+}
+
+/// Rebuild a command from its saved form, in the state it was saved in
+/// 
+/// Commands saved from the undo stack come back executed, so they can be undone.
+pub fn reconstruct_command(kind: CommandKind) -> Box<dyn Command> {
+    match kind {
+        CommandKind::Copy(command) => Box::new(command),
+        CommandKind::Move(command) => Box::new(command),
+        CommandKind::Delete(command) => Box::new(command),
+        CommandKind::Trash(command) => Box::new(command),
+        CommandKind::Rename(command) => Box::new(command),
+        CommandKind::CreateDirectory(command) => Box::new(command),
+        CommandKind::CopyDirectory(command) => Box::new(command),
+        CommandKind::MoveDirectory(command) => Box::new(command),
+        CommandKind::RestoreFromTrash(command) => Box::new(command),
+        CommandKind::Batch { name, description, metadata, commands } => {
+            let mut batch = BatchOperation::new(name, description);
+            batch.add_commands(commands.into_iter().map(reconstruct_command).collect());
+            batch.executed_commands = (0..batch.commands.len()).collect();
+            batch.progress.completed_commands = batch.commands.len();
+            batch.progress.status = BatchStatus::Completed;
+            batch.metadata = metadata;
+            Box::new(batch)
+        }
+    }
+}
+
 /// Represents a historical entry in the operation history
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HistoryEntry {
@@ -1063,8 +1327,12 @@ pub struct HistoryEntry {
     pub command_description: String,
     pub timestamp: SystemTime,
     pub command_size_bytes: usize,
-    // We can't serialize the actual command due to trait objects,
-    // but we store enough info to recreate simple operations if needed
+    /// Batch the command ran in, if any
+    #[serde(default)]
+    pub batch_id: Option<Uuid>,
+    /// Command as last saved while it could still be undone, for restoring it on load
+    #[serde(default)]
+    pub command: Option<CommandKind>,
 }
 
 impl HistoryEntry {
@@ -1075,8 +1343,15 @@ impl HistoryEntry {
             command_description: command.description(),
             timestamp: SystemTime::now(),
             command_size_bytes: std::mem::size_of_val(command), // Approximate size
+            batch_id: None,
+            command: None,
         }
     }
+
+    pub fn with_batch_id(mut self, batch_id: Option<Uuid>) -> Self {
+        self.batch_id = batch_id;
+        self
+    }
 }
 
 /// Operation history manager with undo/redo functionality
@@ -1096,6 +1371,8 @@ pub struct OperationHistory {
     current_memory_usage: usize,
     /// FileSystem service for operations
     fs_service: Arc<dyn FileSystemService>,
+    /// Persistent audit log of executed, undone and redone commands
+    log: Option<Arc<OperationLog>>,
 }
 
 impl OperationHistory {
@@ -1108,6 +1385,7 @@ impl OperationHistory {
             history_entries: VecDeque::new(),
             current_memory_usage: 0,
             fs_service,
+            log: None,
         }
     }
     
@@ -1120,12 +1398,28 @@ impl OperationHistory {
             history_entries: VecDeque::new(),
             current_memory_usage: 0,
             fs_service,
+            log: None,
         }
     }
     
+    /// Record outcomes in `log` as well as keeping them for undo
+    pub fn with_log(mut self, log: Arc<OperationLog>) -> Self {
+        self.log = Some(log);
+        self
+    }
+    
     /// Add a successfully executed command to the history
     /// This clears the redo stack since we're creating a new branch of history
     pub async fn add_executed_command(&mut self, command: Box<dyn Command>) -> OperationResult<()> {
+        self.add_executed_command_in_batch(command, None).await
+    }
+    
+    /// Add a successfully executed command that ran as part of batch `batch_id`
+    pub async fn add_executed_command_in_batch(
+        &mut self,
+        command: Box<dyn Command>,
+        batch_id: Option<Uuid>,
+    ) -> OperationResult<()> {
         // Validate that the command is actually executed
         if !command.is_executed() {
             return Err(OperationError::HistoryError(
@@ -1137,8 +1431,9 @@ impl OperationHistory {
         self.redo_stack.clear();
         
         // Create history entry for metadata
-        let entry = HistoryEntry::new(command.as_ref());
+        let entry = HistoryEntry::new(command.as_ref()).with_batch_id(batch_id);
         self.history_entries.push_back(entry);
+        self.record(LogAction::Execute, command.as_ref(), None);
         
         // Update memory usage estimate
         let command_size = std::mem::size_of_val(command.as_ref());
@@ -1169,6 +1464,7 @@ impl OperationHistory {
                 // Update command metadata
                 command.metadata_mut().status = CommandStatus::Undone;
                 command.metadata_mut().undone_at = Some(SystemTime::now());
+                self.record(LogAction::Undo, command.as_ref(), None);
                 
                 // Move to redo stack
                 self.redo_stack.push_back(command);
@@ -1176,6 +1472,7 @@ impl OperationHistory {
                 Ok(format!("Undone: {}", description))
             }
             Err(e) => {
+                self.record(LogAction::Undo, command.as_ref(), Some(&e));
                 // Put command back on undo stack if undo failed
                 self.undo_stack.push_back(command);
                 Err(OperationError::UndoFailed(format!(
@@ -1202,6 +1499,7 @@ impl OperationHistory {
                 command.metadata_mut().status = CommandStatus::Executed;
                 command.metadata_mut().executed_at = Some(SystemTime::now());
                 command.metadata_mut().undone_at = None;
+                self.record(LogAction::Redo, command.as_ref(), None);
                 
                 // Move back to undo stack
                 self.undo_stack.push_back(command);
@@ -1209,6 +1507,7 @@ impl OperationHistory {
                 Ok(format!("Redone: {}", description))
             }
             Err(e) => {
+                self.record(LogAction::Redo, command.as_ref(), Some(&e));
                 // Put command back on redo stack if redo failed
                 self.redo_stack.push_back(command);
                 Err(OperationError::ExecutionFailed(format!(
@@ -1218,6 +1517,36 @@ impl OperationHistory {
         }
     }
     
+    /// Record a command whose execution failed, so the log shows attempts as well as changes
+    pub fn log_failed_command(&self, command: &dyn Command, error: &OperationError) {
+        self.record(LogAction::Execute, command, Some(error));
+    }
+    
+    /// Record a command that ran but can't be undone, so it is logged without joining the undo stack
+    pub fn log_irreversible_command(&self, command: &dyn Command) {
+        self.record(LogAction::Execute, command, None);
+    }
+    
+    /// Append an outcome to the operation log, if one is attached
+    fn record(&self, action: LogAction, command: &dyn Command, error: Option<&OperationError>) {
+        let Some(log) = &self.log else {
+            return;
+        };
+        let entry = self.history_entries
+            .iter()
+            .rev()
+            .find(|entry| entry.command_id == command.metadata().id)
+            .cloned()
+            .unwrap_or_else(|| HistoryEntry::new(command));
+        let mut record = OperationLogRecord::new(action, &entry, command);
+        if let Some(error) = error {
+            record = record.with_error(error);
+        }
+        if let Err(e) = log.append(&record) {
+            tracing::warn!("Failed to write operation log: {}", e);
+        }
+    }
+    
     /// Check if undo is available
     pub fn can_undo(&self) -> bool {
         !self.undo_stack.is_empty()
@@ -1354,63 +1683,132 @@ impl OperationHistory {
             .collect()
     }
     
-    /// Save history metadata to file (commands themselves cannot be serialized due to trait objects)
+    /// Save history to file
+    /// 
+    /// Commands on the undo stack are stored with their entries when they have a
+    /// `command_kind`, so `load_from_file` can make them undoable again. The redo
+    /// stack is not kept.
     pub async fn save_to_file(&self, path: &std::path::Path) -> OperationResult<()> {
-        if !self.config.persist_history {
-            return Err(OperationError::HistoryError(
-                "History persistence is disabled".to_string()
-            ));
+        let serialized = self.serialize_snapshot()?;
+        
+        tokio::fs::write(path, serialized)
+            .await
+            .map_err(|e| OperationError::HistoryError(format!("Failed to write history file: {}", e)))?;
+        
+        tracing::info!("Saved operation history to {}", path.display());
+        Ok(())
+    }
+    
+    /// Save history to file without the async runtime, such as while the app shuts down
+    /// 
+    /// Creates the folder holding the file if needed.
+    pub fn save_to_file_blocking(&self, path: &std::path::Path) -> OperationResult<()> {
+        let serialized = self.serialize_snapshot()?;
+        
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| OperationError::HistoryError(format!("Failed to create history folder: {}", e)))?;
+        }
+        std::fs::write(path, serialized)
+            .map_err(|e| OperationError::HistoryError(format!("Failed to write history file: {}", e)))?;
+        
+        tracing::info!("Saved operation history to {}", path.display());
+        Ok(())
+    }
+    
+    /// JSON written by `save_to_file`
+    fn serialize_snapshot(&self) -> OperationResult<String> {
+        self.check_persistence()?;
+        
+        let mut entries: Vec<HistoryEntry> = self.history_entries.clone().into();
+        for command in &self.undo_stack {
+            let id = command.metadata().id;
+            if let Some(entry) = entries.iter_mut().rev().find(|entry| entry.command_id == id) {
+                entry.command = command.command_kind();
+            }
         }
         
         let history_snapshot = HistorySnapshot {
-            entries: self.history_entries.clone().into(),
+            entries,
             config: self.config.clone(),
             saved_at: SystemTime::now(),
             undo_count: self.undo_stack.len(),
             redo_count: self.redo_stack.len(),
+            undo_stack: self.undo_stack.iter().map(|command| command.metadata().id).collect(),
         };
         
-        let serialized = serde_json::to_string_pretty(&history_snapshot)
-            .map_err(|e| OperationError::Serialization(format!("Failed to serialize history: {}", e)))?;
-        
-        tokio::fs::write(path, serialized)
-            .await
-            .map_err(|e| OperationError::HistoryError(format!("Failed to write history file: {}", e)))?;
-        
-        tracing::info!("Saved operation history to {}", path.display());
-        Ok(())
+        serde_json::to_string_pretty(&history_snapshot)
+            .map_err(|e| OperationError::Serialization(format!("Failed to serialize history: {}", e)))
     }
     
-    /// Load history metadata from file (commands cannot be restored, only metadata)
-    pub async fn load_from_file(&mut self, path: &std::path::Path) -> OperationResult<()> {
+    fn check_persistence(&self) -> OperationResult<()> {
         if !self.config.persist_history {
             return Err(OperationError::HistoryError(
                 "History persistence is disabled".to_string()
             ));
         }
+        Ok(())
+    }
+    
+    /// Load history from file, restoring the undo stack where possible
+    /// 
+    /// Undo has to run newest first, so only the commands saved after the most
+    /// recent one that can't be rebuilt are restored; the rest are logged.
+    pub async fn load_from_file(&mut self, path: &std::path::Path) -> OperationResult<()> {
+        self.check_persistence()?;
         
         let contents = tokio::fs::read_to_string(path)
             .await
             .map_err(|e| OperationError::HistoryError(format!("Failed to read history file: {}", e)))?;
         
-        let history_snapshot: HistorySnapshot = serde_json::from_str(&contents)
+        self.restore_snapshot(&contents, path)
+    }
+    
+    /// Load history from file without the async runtime, such as while the app starts
+    pub fn load_from_file_blocking(&mut self, path: &std::path::Path) -> OperationResult<()> {
+        self.check_persistence()?;
+        
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| OperationError::HistoryError(format!("Failed to read history file: {}", e)))?;
+        
+        self.restore_snapshot(&contents, path)
+    }
+    
+    /// Replace the history with the snapshot `contents` read from `path`
+    fn restore_snapshot(&mut self, contents: &str, path: &std::path::Path) -> OperationResult<()> {
+        let history_snapshot: HistorySnapshot = serde_json::from_str(contents)
             .map_err(|e| OperationError::Serialization(format!("Failed to deserialize history: {}", e)))?;
         
-        // Restore metadata entries (note: actual commands cannot be restored)
+        let mut restored = Vec::new();
+        for id in &history_snapshot.undo_stack {
+            let entry = history_snapshot.entries.iter().rev().find(|entry| entry.command_id == *id);
+            match entry.and_then(|entry| entry.command.clone()) {
+                Some(kind) => restored.push(reconstruct_command(kind)),
+                None => {
+                    let description = entry.map(|entry| entry.command_description.as_str()).unwrap_or("unknown operation");
+                    tracing::warn!("Cannot restore undo for '{}'", description);
+                    for dropped in restored.drain(..) {
+                        tracing::warn!("Cannot restore undo for '{}': a later operation can't be restored", dropped.description());
+                    }
+                }
+            }
+        }
+        
         self.history_entries = VecDeque::from(history_snapshot.entries);
         self.config = history_snapshot.config;
+        self.redo_stack.clear();
+        self.current_memory_usage = restored.iter().map(|command| std::mem::size_of_val(command.as_ref())).sum();
+        self.undo_stack = VecDeque::from(restored);
         
         tracing::info!(
-            "Loaded operation history metadata from {} ({} entries)", 
+            "Loaded operation history from {} ({} entries, {} undoable)", 
             path.display(), 
-            self.history_entries.len()
-        );
-        tracing::warn!(
-            "Note: Actual undo/redo functionality is not available after loading from file. \
-            {} undo and {} redo operations were lost.", 
-            history_snapshot.undo_count,
-            history_snapshot.redo_count
+            self.history_entries.len(),
+            self.undo_stack.len()
         );
+        if history_snapshot.redo_count > 0 {
+            tracing::info!("{} redo operations were not kept", history_snapshot.redo_count);
+        }
         
         Ok(())
     }
@@ -1437,6 +1835,9 @@ pub struct HistorySnapshot {
     pub saved_at: SystemTime,
     pub undo_count: usize,
     pub redo_count: usize,
+    /// Command ids of the undo stack, oldest first
+    #[serde(default)]
+    pub undo_stack: Vec<Uuid>,
 }
 
 /// Statistics about operation history
@@ -1516,12 +1917,21 @@ pub struct BatchOperation {
     pub progress: BatchProgress,
     pub allow_partial_failure: bool,
     pub max_retries: u32,
+    /// Delays between retries of a failed command
+    pub retry_config: RetryConfig,
+    /// Maximum number of commands executed concurrently (1 = serial)
+    pub parallelism: usize,
+    
+    /// Commands that failed when the batch ran as a single command, with their errors
+    pub failures: Vec<(usize, OperationError)>,
     
     // State for rollback
     executed_commands: Vec<usize>, // Indices of successfully executed commands
     
     // Cancellation support
     cancel_token: Option<tokio_util::sync::CancellationToken>,
+    /// Stops the batch between commands, keeping the ones that already ran
+    stop_token: Option<CancellationToken>,
 }
 
 impl BatchOperation {
@@ -1536,8 +1946,12 @@ impl BatchOperation {
             progress: BatchProgress::new(0),
             allow_partial_failure: false,
             max_retries: 0,
+            retry_config: RetryConfig::default(),
+            parallelism: 1,
+            failures: Vec::new(),
             executed_commands: Vec::new(),
             cancel_token: Some(tokio_util::sync::CancellationToken::new()),
+            stop_token: None,
         }
     }
     
@@ -1565,6 +1979,57 @@ impl BatchOperation {
         self
     }
     
+    /// Retry failed commands as `config` describes: its attempts and its backoff
+    pub fn with_retry_policy(mut self, config: RetryConfig) -> Self {
+        self.max_retries = config.max_attempts;
+        self.retry_config = config;
+        self
+    }
+    
+    /// Run independent commands on a bounded worker pool of the given size
+    pub fn with_parallelism(mut self, workers: usize) -> Self {
+        self.parallelism = workers.max(1);
+        self
+    }
+    
+    /// Check `token` before each command and stop once it is cancelled
+    ///
+    /// Unlike [`BatchOperation::cancel`], commands that already ran are kept
+    /// rather than rolled back. The token's progress counter is bumped after
+    /// each command, failed or not.
+    pub fn with_stop_token(mut self, token: CancellationToken) -> Self {
+        self.stop_token = Some(token);
+        self
+    }
+    
+    /// Whether the stop token ended the batch before every command ran
+    pub fn was_stopped(&self) -> bool {
+        self.progress.status == BatchStatus::Cancelled
+    }
+    
+    /// Summary of this batch returned once processing finishes
+    /// 
+    /// Commands can't be cloned due to trait objects, so the summary carries
+    /// status, progress and the indices of executed commands only.
+    fn result_snapshot(&self) -> BatchOperation {
+        BatchOperation {
+            id: self.id,
+            name: self.name.clone(),
+            description: self.description.clone(),
+            commands: Vec::new(),
+            metadata: self.metadata.clone(),
+            progress: self.progress.clone(),
+            allow_partial_failure: self.allow_partial_failure,
+            max_retries: self.max_retries,
+            retry_config: self.retry_config.clone(),
+            parallelism: self.parallelism,
+            failures: self.failures.clone(),
+            executed_commands: self.executed_commands.clone(),
+            cancel_token: None, // Reset cancellation token
+            stop_token: None,
+        }
+    }
+    
     /// Get cancellation token for this batch
     pub fn cancellation_token(&self) -> Option<tokio_util::sync::CancellationToken> {
         self.cancel_token.as_ref().map(|token| token.clone())
@@ -1585,53 +2050,243 @@ impl BatchOperation {
     }
 }
 
-/// Message types for batch operation queue
-#[derive(Debug)]
-pub enum BatchMessage {
-    Execute {
-        batch: BatchOperation,
-        response: oneshot::Sender<OperationResult<BatchOperation>>,
-    },
-    GetProgress {
-        batch_id: Uuid,
-        response: oneshot::Sender<Option<BatchProgress>>,
-    },
-    Cancel {
-        batch_id: Uuid,
-        response: oneshot::Sender<bool>,
-    },
-    Shutdown,
+impl BatchOperation {
+    /// Commands that failed, paired with their errors
+    pub fn failed_commands(&self) -> impl Iterator<Item = (&dyn Command, &OperationError)> {
+        self.failures
+            .iter()
+            .filter_map(|(index, error)| self.commands.get(*index).map(|command| (command.as_ref(), error)))
+    }
+    
+    /// Undo executed commands newest first; commands that fail to undo stay executed
+    async fn undo_executed(&mut self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        let mut errors = Vec::new();
+        let mut still_executed = Vec::new();
+        while let Some(index) = self.executed_commands.pop() {
+            let command = &mut self.commands[index];
+            match command.undo(fs.clone()).await {
+                Ok(()) => {
+                    command.metadata_mut().status = CommandStatus::Undone;
+                    command.metadata_mut().undone_at = Some(SystemTime::now());
+                }
+                Err(e) => {
+                    errors.push(format!("{}: {}", command.description(), e));
+                    still_executed.push(index);
+                }
+            }
+        }
+        still_executed.reverse();
+        self.executed_commands = still_executed;
+        
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(OperationError::UndoFailed(errors.join("; ")))
+        }
+    }
 }
 
-/// Batch operation processor that handles queued operations in the background
-pub struct BatchProcessor {
-    sender: mpsc::UnboundedSender<BatchMessage>,
-    active_batches: Arc<tokio::sync::RwLock<HashMap<Uuid, BatchProgress>>>,
+/// Execute a single command, retrying errors whose recovery strategy allows it
+async fn execute_with_retries(
+    command: &mut dyn Command,
+    fs: Arc<dyn FileSystemService>,
+    max_retries: u32,
+    retry_config: &RetryConfig,
+) -> OperationResult<()> {
+    let mut attempt = 0;
+    
+    loop {
+        match command.execute(fs.clone()).await {
+            Ok(()) => return Ok(()),
+            Err(e) => {
+                let (_, strategy) = e.classify();
+                let retryable = matches!(strategy, RecoveryStrategy::RetryImmediate | RecoveryStrategy::RetryWithBackoff);
+                if !retryable || attempt >= max_retries {
+                    return Err(e);
+                }
+                
+                tokio::time::sleep(retry_config.delay_for_attempt(attempt)).await;
+                attempt += 1;
+            }
+        }
+    }
 }
 
-impl BatchProcessor {
-    /// Create a new batch processor
-    pub fn new(fs: Arc<dyn FileSystemService>) -> Self {
-        let (sender, mut receiver) = mpsc::unbounded_channel();
-        let active_batches = Arc::new(tokio::sync::RwLock::new(HashMap::new()));
-        let active_batches_clone = active_batches.clone();
+/// A batch run as one command, so the history keeps it as a single entry
+/// 
+/// Commands execute in order. With partial failure allowed, failed commands
+/// are collected in `failures` while the rest still run; otherwise the first
+/// failure rolls back what already ran. Undo reverses every executed command.
+#[async_trait]
+impl Command for BatchOperation {
+    async fn execute(&mut self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        if self.is_executed() {
+            return Err(OperationError::AlreadyExecuted);
+        }
         
-        // Spawn background task to process batch operations
-        tokio::spawn(async move {
-            while let Some(message) = receiver.recv().await {
-                match message {
-                    BatchMessage::Execute { mut batch, response } => {
-                        let batch_id = batch.id;
-                        
-                        // Add to active batches
-                        active_batches_clone.write().await.insert(batch_id, batch.progress.clone());
-                        
-                        // Execute the batch
-                        let result = Self::execute_batch(&mut batch, fs.clone(), active_batches_clone.clone()).await;
-                        
-                        // Remove from active batches
-                        active_batches_clone.write().await.remove(&batch_id);
-                        
+        self.failures.clear();
+        self.progress = BatchProgress::new(self.commands.len());
+        self.progress.status = BatchStatus::Executing;
+        
+        for index in 0..self.commands.len() {
+            if self.is_cancelled() {
+                self.progress.status = BatchStatus::Cancelled;
+                self.undo_executed(fs.clone()).await?;
+                return Err(OperationError::Cancelled);
+            }
+            if let Some(token) = &self.stop_token {
+                if token.throw_if_cancelled().is_err() {
+                    tracing::info!("Batch {} stopped after {} of {} commands", self.name, index, self.commands.len());
+                    self.progress.status = BatchStatus::Cancelled;
+                    break;
+                }
+            }
+            
+            let command = &mut self.commands[index];
+            self.progress.current_command = Some(command.description());
+            match execute_with_retries(command.as_mut(), fs.clone(), self.max_retries, &self.retry_config).await {
+                Ok(()) => {
+                    self.executed_commands.push(index);
+                    self.progress.completed_commands += 1;
+                }
+                Err(e) => {
+                    tracing::warn!("{} failed in batch {}: {}", command.description(), self.name, e);
+                    self.progress.failed_commands += 1;
+                    self.failures.push((index, e.clone()));
+                    if !self.allow_partial_failure {
+                        self.progress.status = BatchStatus::RollingBack;
+                        self.undo_executed(fs.clone()).await?;
+                        self.progress.status = BatchStatus::Failed;
+                        return Err(OperationError::BatchFailed(format!(
+                            "{} failed: {}", self.commands[index].description(), e
+                        )));
+                    }
+                }
+            }
+            if let Some(token) = &self.stop_token {
+                token.increment_progress();
+            }
+        }
+        self.progress.current_command = None;
+        
+        if self.executed_commands.is_empty() && !self.failures.is_empty() {
+            self.progress.status = BatchStatus::Failed;
+            return Err(OperationError::BatchFailed(format!(
+                "All {} commands failed", self.failures.len()
+            )));
+        }
+        
+        if !self.was_stopped() {
+            self.progress.status = BatchStatus::Completed;
+        }
+        self.metadata.status = CommandStatus::Executed;
+        self.metadata.executed_at = Some(SystemTime::now());
+        Ok(())
+    }
+    
+    async fn undo(&mut self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        if !self.can_undo() {
+            return Err(OperationError::NotExecuted);
+        }
+        
+        self.undo_executed(fs).await?;
+        
+        self.metadata.status = CommandStatus::Undone;
+        self.metadata.undone_at = Some(SystemTime::now());
+        Ok(())
+    }
+    
+    async fn validate(&self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        // Failures are collected per command when partial failure is allowed
+        if self.allow_partial_failure {
+            return Ok(());
+        }
+        for command in &self.commands {
+            command.validate(fs.clone()).await?;
+        }
+        Ok(())
+    }
+    
+    fn command_kind(&self) -> Option<CommandKind> {
+        let commands = self.executed_commands.iter()
+            .map(|&index| self.commands[index].command_kind())
+            .collect::<Option<Vec<_>>>()?;
+        Some(CommandKind::Batch {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            metadata: self.metadata.clone(),
+            commands,
+        })
+    }
+    
+    fn metadata(&self) -> &CommandMetadata {
+        &self.metadata
+    }
+    
+    fn metadata_mut(&mut self) -> &mut CommandMetadata {
+        &mut self.metadata
+    }
+    
+    fn description(&self) -> String {
+        self.description.clone()
+    }
+    
+    fn kind(&self) -> OperationKind {
+        let mut kinds = self.commands.iter().map(|command| command.kind());
+        match kinds.next() {
+            Some(first) if kinds.all(|kind| kind == first) => first,
+            _ => OperationKind::Other,
+        }
+    }
+}
+
+/// Message types for batch operation queue
+#[derive(Debug)]
+pub enum BatchMessage {
+    Execute {
+        batch: BatchOperation,
+        response: oneshot::Sender<OperationResult<BatchOperation>>,
+    },
+    GetProgress {
+        batch_id: Uuid,
+        response: oneshot::Sender<Option<BatchProgress>>,
+    },
+    Cancel {
+        batch_id: Uuid,
+        response: oneshot::Sender<bool>,
+    },
+    Shutdown,
+}
+
+/// Batch operation processor that handles queued operations in the background
+pub struct BatchProcessor {
+    sender: mpsc::UnboundedSender<BatchMessage>,
+    active_batches: Arc<tokio::sync::RwLock<HashMap<Uuid, BatchProgress>>>,
+}
+
+impl BatchProcessor {
+    /// Create a new batch processor
+    pub fn new(fs: Arc<dyn FileSystemService>) -> Self {
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let active_batches = Arc::new(tokio::sync::RwLock::new(HashMap::new()));
+        let active_batches_clone = active_batches.clone();
+        
+        // Spawn background task to process batch operations
+        tokio::spawn(async move {
+            while let Some(message) = receiver.recv().await {
+                match message {
+                    BatchMessage::Execute { mut batch, response } => {
+                        let batch_id = batch.id;
+                        
+                        // Add to active batches
+                        active_batches_clone.write().await.insert(batch_id, batch.progress.clone());
+                        
+                        // Execute the batch
+                        let result = Self::execute_batch(&mut batch, fs.clone(), active_batches_clone.clone()).await;
+                        
+                        // Remove from active batches
+                        active_batches_clone.write().await.remove(&batch_id);
+                        
                         let _ = response.send(result);
                     },
                     BatchMessage::GetProgress { batch_id, response } => {
@@ -1729,8 +2384,13 @@ impl BatchProcessor {
             return Err(OperationError::Cancelled);
         }
         
-        // Validate all commands first
+        // Validate all commands first. Commands after a serial barrier depend on its
+        // effects (e.g. a directory being created), so they validate when executed.
         for (i, command) in batch.commands.iter().enumerate() {
+            if command.must_run_serially() {
+                break;
+            }
+            
             batch.progress.current_command = Some(format!("Validating {}", command.description()));
             Self::update_progress(batch.id, &batch.progress, active_batches.clone()).await;
             
@@ -1755,6 +2415,10 @@ impl BatchProcessor {
         batch.metadata.status = CommandStatus::Executed;
         batch.metadata.executed_at = Some(start_time);
         
+        if batch.parallelism > 1 {
+            return Self::execute_batch_parallel(batch, fs, active_batches, start_time).await;
+        }
+        
         let mut execution_errors = Vec::new();
         
         // Execute commands in order  
@@ -1777,7 +2441,7 @@ impl BatchProcessor {
             
             // Execute command with enhanced error handling
             let mut attempts = 0;
-            let max_attempts = batch.max_retries.max(3); // Ensure at least some retries
+            let max_attempts = batch.max_retries.max(batch.retry_config.max_attempts);
             let mut success = false;
             let mut last_error = None;
             
@@ -1853,13 +2517,7 @@ impl BatchProcessor {
                         // Calculate retry delay based on strategy and attempt
                         let delay = match strategy {
                             RecoveryStrategy::RetryImmediate => Duration::from_millis(10),
-                            RecoveryStrategy::RetryWithBackoff => {
-                                // Exponential backoff
-                                let base_delay = suggestion.retry_delay.unwrap_or(Duration::from_secs(1));
-                                Duration::from_millis(
-                                    (base_delay.as_millis() as u64) * (2_u64.pow(attempts.min(6)))
-                                )
-                            },
+                            RecoveryStrategy::RetryWithBackoff => batch.retry_config.delay_for_attempt(attempts - 1),
                             _ => Duration::from_millis(100 * attempts as u64),
                         };
                         
@@ -1965,20 +2623,143 @@ impl BatchProcessor {
                 batch.name, execution_errors.len(), execution_errors);
         }
         
-        // Return owned BatchOperation (can't clone due to trait objects)
-        // Instead, create a simple result status
-        Ok(BatchOperation {
-            id: batch.id,
-            name: batch.name.clone(),
-            description: batch.description.clone(),
-            commands: Vec::new(), // Commands can't be cloned due to trait objects
-            metadata: batch.metadata.clone(),
-            progress: batch.progress.clone(),
-            allow_partial_failure: batch.allow_partial_failure,
-            max_retries: batch.max_retries,
-            executed_commands: batch.executed_commands.clone(),
-            cancel_token: None, // Reset cancellation token
-        })
+        Ok(batch.result_snapshot())
+    }
+    
+    /// Execute a batch on a bounded worker pool
+    /// 
+    /// Runs of parallel-safe commands execute concurrently, up to `batch.parallelism`
+    /// at a time, while serial commands act as barriers so directories are created
+    /// before the files that go into them. A command whose paths overlap one already
+    /// in the run starts a new run, so commands touching the same files keep their
+    /// order. Results are consumed in submission order,
+    /// which keeps progress reporting ordered. After a failure no new commands are
+    /// started and everything that succeeded is rolled back.
+    async fn execute_batch_parallel(
+        batch: &mut BatchOperation,
+        fs: Arc<dyn FileSystemService>,
+        active_batches: Arc<tokio::sync::RwLock<HashMap<Uuid, BatchProgress>>>,
+        start_time: SystemTime,
+    ) -> OperationResult<BatchOperation> {
+        use futures::StreamExt;
+        
+        let workers = batch.parallelism.max(1);
+        let max_retries = batch.max_retries;
+        let retry_config = batch.retry_config.clone();
+        let allow_partial_failure = batch.allow_partial_failure;
+        let cancel_token = batch.cancel_token.clone();
+        let abort = Arc::new(AtomicBool::new(false));
+        let total_commands = batch.commands.len();
+        let mut execution_errors = Vec::new();
+        
+        let mut stage_start = 0;
+        while stage_start < total_commands && !abort.load(Ordering::SeqCst) {
+            let stage_end = parallel_stage_end(&batch.commands, stage_start);
+            
+            // Boxed futures keep the stream `Send` for the processor's spawned task
+            let stage_futures: Vec<std::pin::Pin<Box<dyn std::future::Future<Output = (usize, String, Option<OperationResult<()>>)> + Send + '_>>> =
+                batch.commands[stage_start..stage_end]
+                    .iter_mut()
+                    .enumerate()
+                    .map(|(offset, command)| {
+                        let index = stage_start + offset;
+                        let fs = fs.clone();
+                        let abort = abort.clone();
+                        let cancel_token = cancel_token.clone();
+                        let retry_config = retry_config.clone();
+                        Box::pin(async move {
+                            let cancelled = cancel_token.as_ref().map(|t| t.is_cancelled()).unwrap_or(false);
+                            if cancelled || abort.load(Ordering::SeqCst) {
+                                return (index, command.description(), None);
+                            }
+                            
+                            let result = execute_with_retries(command.as_mut(), fs, max_retries, &retry_config).await;
+                            if result.is_err() && !allow_partial_failure {
+                                abort.store(true, Ordering::SeqCst);
+                            }
+                            (index, command.description(), Some(result))
+                        }) as std::pin::Pin<Box<dyn std::future::Future<Output = _> + Send + '_>>
+                    })
+                    .collect();
+            let mut results = futures::stream::iter(stage_futures).buffered(workers);
+            
+            while let Some((index, description, result)) = results.next().await {
+                match result {
+                    Some(Ok(())) => {
+                        batch.executed_commands.push(index);
+                        batch.progress.completed_commands += 1;
+                    }
+                    Some(Err(e)) => {
+                        tracing::warn!(
+                            command_index = index,
+                            command_desc = %description,
+                            error = %e,
+                            batch_id = %batch.id,
+                            "Command failed during parallel batch operation"
+                        );
+                        execution_errors.push((index, format!("Command {} failed: {}", index, e)));
+                        batch.progress.failed_commands += 1;
+                    }
+                    None => continue,
+                }
+                
+                batch.progress.current_command = Some(description);
+                Self::update_progress(batch.id, &batch.progress, active_batches.clone()).await;
+            }
+            
+            if cancel_token.as_ref().map(|t| t.is_cancelled()).unwrap_or(false) {
+                abort.store(true, Ordering::SeqCst);
+            }
+            
+            stage_start = stage_end;
+        }
+        
+        // Rollback keeps undo order matching submission order
+        batch.executed_commands.sort_unstable();
+        
+        if cancel_token.as_ref().map(|t| t.is_cancelled()).unwrap_or(false) {
+            batch.progress.status = BatchStatus::Cancelled;
+            Self::update_progress(batch.id, &batch.progress, active_batches.clone()).await;
+            Self::rollback_executed_commands(batch, fs.clone()).await?;
+            return Err(OperationError::Cancelled);
+        }
+        
+        if !execution_errors.is_empty() && !allow_partial_failure {
+            let error_msg = execution_errors.iter()
+                .map(|(_, message)| message.as_str())
+                .collect::<Vec<_>>()
+                .join("; ");
+            
+            batch.progress.status = BatchStatus::RollingBack;
+            Self::update_progress(batch.id, &batch.progress, active_batches.clone()).await;
+            
+            if let Err(rollback_err) = Self::rollback_executed_commands(batch, fs.clone()).await {
+                batch.progress.status = BatchStatus::Failed;
+                Self::update_progress(batch.id, &batch.progress, active_batches.clone()).await;
+                return Err(OperationError::RollbackFailed(
+                    format!("Execution failed and rollback failed: {} | Rollback error: {}", error_msg, rollback_err)
+                ));
+            }
+            
+            batch.progress.status = BatchStatus::Failed;
+            batch.metadata.status = CommandStatus::Failed;
+            batch.metadata.error_message = Some(error_msg.clone());
+            Self::update_progress(batch.id, &batch.progress, active_batches.clone()).await;
+            
+            return Err(OperationError::BatchFailed(error_msg));
+        }
+        
+        batch.progress.status = BatchStatus::Completed;
+        batch.progress.elapsed_time = start_time.elapsed().ok();
+        batch.progress.current_command = None;
+        Self::update_progress(batch.id, &batch.progress, active_batches.clone()).await;
+        
+        tracing::info!(
+            "Batch '{}' completed {} commands with {} workers ({} errors)",
+            batch.name, batch.progress.completed_commands, workers, execution_errors.len()
+        );
+        
+        Ok(batch.result_snapshot())
     }
     
     /// Rollback all executed commands in reverse order
@@ -2019,20 +2800,108 @@ impl BatchProcessor {
     }
 }
 
+/// End of the run of commands starting at `start` that may execute concurrently
+/// 
+/// The run stops before the next serial command, command without known paths,
+/// or command whose paths overlap a path already claimed by the run.
+fn parallel_stage_end(commands: &[Box<dyn Command>], start: usize) -> usize {
+    let runs_alone = |command: &dyn Command| command.must_run_serially() || command.affects_paths().is_empty();
+    if runs_alone(commands[start].as_ref()) {
+        return start + 1;
+    }
+    
+    let mut claimed = ClaimedPaths::default();
+    let mut end = start;
+    while let Some(command) = commands.get(end) {
+        if end > start && runs_alone(command.as_ref()) {
+            break;
+        }
+        let paths = command.affects_paths();
+        if paths.iter().any(|path| claimed.overlaps(path)) {
+            break;
+        }
+        paths.into_iter().for_each(|path| claimed.claim(path));
+        end += 1;
+    }
+    end
+}
+
+/// Paths claimed by the commands of one parallel run
+/// 
+/// Ancestors are kept too, so overlap checks cost one lookup per path component
+/// rather than a comparison with every claimed path.
+#[derive(Default)]
+struct ClaimedPaths {
+    paths: std::collections::HashSet<PathBuf>,
+    ancestors: std::collections::HashSet<PathBuf>,
+}
+
+impl ClaimedPaths {
+    /// Whether `path` is claimed, inside a claimed path, or contains one
+    fn overlaps(&self, path: &Path) -> bool {
+        path.ancestors().any(|ancestor| self.paths.contains(ancestor)) || self.ancestors.contains(path)
+    }
+    
+    fn claim(&mut self, path: &Path) {
+        self.paths.insert(path.to_path_buf());
+        self.ancestors.extend(path.ancestors().skip(1).map(Path::to_path_buf));
+    }
+}
+
+/// Apply `resolution` to an existing `destination` before a copy or move
+/// 
+/// Returns whether the item is skipped, whether the (possibly renamed)
+/// destination already existed, and the overwritten content to restore on undo.
+async fn resolve_destination(
+    destination: &mut PathBuf,
+    resolution: ConflictResolution,
+) -> OperationResult<(bool, bool, Option<Vec<u8>>)> {
+    if !destination.exists() {
+        return Ok((false, false, None));
+    }
+    match resolution {
+        ConflictResolution::Ask => Err(OperationError::DestinationExists(destination.clone())),
+        ConflictResolution::Skip => Ok((true, true, None)),
+        ConflictResolution::Rename => {
+            *destination = generate_duplicate_name(destination)?;
+            Ok((false, false, None))
+        }
+        ConflictResolution::Overwrite => {
+            let backup = tokio::fs::read(&*destination).await.ok();
+            Ok((false, true, backup))
+        }
+    }
+}
+
 /// Copy file command
 /// 
-/// Copies a file from source to destination. 
+/// Copies a file from source to destination. An existing destination is
+/// handled by `conflict_resolution`; with `Rename` the destination is updated
+/// to the name actually written.
 /// Undo operation removes the destination file if it was created.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CopyCommand {
     pub source: PathBuf,
     pub destination: PathBuf,
-    pub overwrite: bool,
+    #[serde(default)]
+    pub conflict_resolution: ConflictResolution,
     pub preserve_metadata: bool,
+    /// Files at or above this size use the chunked copy path when progress is tracked
+    pub chunked_copy_threshold: u64,
+    /// Chunk size for the chunked copy path
+    pub chunk_size: usize,
+    /// Hash source and destination after copying and roll back if they differ
+    #[serde(default)]
+    pub verify_integrity: bool,
     
     // State for undo
     destination_existed_before: Option<bool>,
+    /// Contents of an overwritten destination; kept in memory only, so
+    /// commands holding one are not written to the history file
+    #[serde(skip)]
     original_destination_backup: Option<Vec<u8>>,
+    #[serde(default)]
+    skipped: bool,
     
     metadata: CommandMetadata,
 }
@@ -2043,66 +2912,139 @@ impl CopyCommand {
         Self {
             source,
             destination,
-            overwrite: false,
+            conflict_resolution: ConflictResolution::default(),
             preserve_metadata: true,
+            chunked_copy_threshold: LARGE_FILE_THRESHOLD,
+            chunk_size: COPY_CHUNK_SIZE,
+            verify_integrity: false,
             destination_existed_before: None,
             original_destination_backup: None,
+            skipped: false,
             metadata: CommandMetadata::default(),
         }
     }
     
-    /// Set whether to overwrite existing files
-    pub fn with_overwrite(mut self, overwrite: bool) -> Self {
-        self.overwrite = overwrite;
+    /// Set what happens when the destination already exists
+    pub fn with_conflict_resolution(mut self, resolution: ConflictResolution) -> Self {
+        self.conflict_resolution = resolution;
         self
     }
     
+    /// Whether the last execution left an existing destination alone
+    pub fn was_skipped(&self) -> bool {
+        self.skipped
+    }
+    
     /// Set whether to preserve file metadata (timestamps, permissions)
     pub fn with_preserve_metadata(mut self, preserve: bool) -> Self {
         self.preserve_metadata = preserve;
         self
     }
-}
-
-#[async_trait]
-impl Command for CopyCommand {
-    async fn execute(&mut self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+    
+    /// Set the file size at which progress-tracked copies switch to chunked copying
+    pub fn with_chunked_copy_threshold(mut self, threshold: u64) -> Self {
+        self.chunked_copy_threshold = threshold;
+        self
+    }
+    
+    /// Set the chunk size used by chunked copying
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+    
+    /// Set whether the copy is checked against the source by hashing both
+    pub fn with_verify_integrity(mut self, verify: bool) -> Self {
+        self.verify_integrity = verify;
+        self
+    }
+    
+    /// Validate and record the destination state needed for undo
+    async fn prepare_destination(&mut self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
         if self.is_executed() {
             return Err(OperationError::AlreadyExecuted);
         }
         
         // Validate first
-        self.validate(fs.clone()).await?;
+        self.validate(fs).await?;
         
-        // Check if destination exists and backup if needed for undo
-        self.destination_existed_before = Some(self.destination.exists());
+        let (skipped, existed_before, backup) = resolve_destination(&mut self.destination, self.conflict_resolution).await?;
+        self.skipped = skipped;
+        self.destination_existed_before = Some(existed_before);
+        self.original_destination_backup = backup;
         
-        if *self.destination_existed_before.as_ref().unwrap() && !self.overwrite {
-            return Err(OperationError::ValidationFailed(
-                format!("Destination already exists: {}", self.destination.display())
-            ));
+        Ok(())
+    }
+    
+    /// Finish an execution that left the existing destination alone
+    fn mark_skipped(&mut self) {
+        tracing::info!("Skipped copying {}: {} already exists", self.source.display(), self.destination.display());
+        self.metadata.status = CommandStatus::Executed;
+        self.metadata.executed_at = Some(SystemTime::now());
+    }
+    
+    /// Check the copy against the source when verification is on
+    /// 
+    /// A copy that doesn't match, or whose check was cancelled, is removed
+    /// and a file it overwrote is put back.
+    async fn verify_destination(&self, progress: Option<&mut ProgressTracker>) -> OperationResult<()> {
+        if !self.verify_integrity || !self.source.is_file() {
+            return Ok(());
         }
-        
-        // Backup original destination content if overwriting
-        if *self.destination_existed_before.as_ref().unwrap() && self.overwrite {
-            if let Ok(content) = tokio::fs::read(&self.destination).await {
-                self.original_destination_backup = Some(content);
+        if let Err(e) = verify_copy(&self.source, &self.destination, progress).await {
+            let _ = tokio::fs::remove_file(&self.destination).await;
+            if let Some(backup) = &self.original_destination_backup {
+                if let Err(restore_error) = tokio::fs::write(&self.destination, backup).await {
+                    tracing::error!("Failed to restore {}: {}", self.destination.display(), restore_error);
+                }
             }
+            return Err(e);
+        }
+        Ok(())
+    }
+    
+    /// Copy the file in one go through the file system service
+    async fn execute_whole(&mut self, fs: Arc<dyn FileSystemService>, progress: Option<&mut ProgressTracker>) -> OperationResult<()> {
+        self.prepare_destination(fs.clone()).await?;
+        if self.skipped {
+            self.mark_skipped();
+            return Ok(());
         }
         
         // Execute the copy using file system service
-        let operation = super::file_system::FileOperation {
-            source: self.source.clone(),
-            destination: self.destination.clone(),
-            overwrite_mode: if self.overwrite {
-                super::file_system::OverwriteMode::Overwrite
-            } else {
-                super::file_system::OverwriteMode::Fail
-            },
-            preserve_metadata: self.preserve_metadata,
-        };
+        let operation = super::file_system::FileOperation::new(self.source.clone(), self.destination.clone())
+            .with_conflict_resolution(self.conflict_resolution)?
+            .preserve_metadata(self.preserve_metadata);
         
         fs.copy_file(operation).await.map_err(OperationError::FileSystem)?;
+        self.verify_destination(progress).await?;
+        
+        // Update metadata
+        self.metadata.status = CommandStatus::Executed;
+        self.metadata.executed_at = Some(SystemTime::now());
+        
+        Ok(())
+    }
+    
+    /// Copy a large file chunk by chunk, reporting byte progress to the tracker
+    async fn execute_chunked(&mut self, fs: Arc<dyn FileSystemService>, mut progress: Option<&mut ProgressTracker>) -> OperationResult<()> {
+        self.prepare_destination(fs).await?;
+        if self.skipped {
+            self.mark_skipped();
+            return Ok(());
+        }
+        
+        // The final rename replaces an existing destination, so nothing is touched until the copy completes
+        copy_file_chunked(&self.source, &self.destination, self.chunk_size, progress.as_deref_mut()).await?;
+        
+        if self.preserve_metadata {
+            if let Ok(src_metadata) = tokio::fs::metadata(&self.source).await {
+                if let Err(e) = tokio::fs::set_permissions(&self.destination, src_metadata.permissions()).await {
+                    tracing::warn!("Failed to preserve permissions for {}: {}", self.destination.display(), e);
+                }
+            }
+        }
+        self.verify_destination(progress).await?;
         
         // Update metadata
         self.metadata.status = CommandStatus::Executed;
@@ -2110,13 +3052,21 @@ impl Command for CopyCommand {
         
         Ok(())
     }
+}
+
+#[async_trait]
+impl Command for CopyCommand {
+    async fn execute(&mut self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        self.execute_whole(fs, None).await
+    }
     
     async fn undo(&mut self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
         if !self.can_undo() {
             return Err(OperationError::NotExecuted);
         }
         
-        // Remove the copied file if destination didn't exist before
+        // Remove the copied file if destination didn't exist before;
+        // a skipped copy kept the existing file and has no backup, so nothing changes
         if let Some(false) = self.destination_existed_before {
             fs.delete_file(&self.destination).await
                 .map_err(|e| OperationError::UndoFailed(format!("Failed to remove copied file: {}", e)))?;
@@ -2165,16 +3115,22 @@ impl Command for CopyCommand {
             }
         }
         
-        // If destination exists and overwrite is false, fail validation
-        if self.destination.exists() && !self.overwrite {
-            return Err(OperationError::ValidationFailed(
-                format!("Destination already exists and overwrite is disabled: {}", self.destination.display())
-            ));
+        // Without a resolution the caller has to ask about an existing destination
+        if self.destination.exists() && self.conflict_resolution == ConflictResolution::Ask {
+            return Err(OperationError::DestinationExists(self.destination.clone()));
         }
         
         Ok(())
     }
     
+    fn command_kind(&self) -> Option<CommandKind> {
+        // Undoing an overwrite needs the backup, which isn't saved
+        if self.original_destination_backup.is_some() {
+            return None;
+        }
+        Some(CommandKind::Copy(self.clone()))
+    }
+    
     fn metadata(&self) -> &CommandMetadata {
         &self.metadata
     }
@@ -2188,21 +3144,429 @@ impl Command for CopyCommand {
             self.source.display(), 
             self.destination.display())
     }
-}
-
-/// Move file command
-/// 
-/// Moves a file from source to destination.
-/// Undo operation moves the file back to the original location.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct MoveCommand {
-    pub source: PathBuf,
-    pub destination: PathBuf,
-    pub overwrite: bool,
+    
+    fn kind(&self) -> OperationKind {
+        OperationKind::Copy
+    }
+    
+    fn source_path(&self) -> Option<&Path> {
+        Some(&self.source)
+    }
+    
+    fn destination_path(&self) -> Option<&Path> {
+        Some(&self.destination)
+    }
+    
+    async fn execute_with_progress(
+        &mut self, 
+        fs: Arc<dyn FileSystemService>, 
+        mut progress: Option<&mut ProgressTracker>
+    ) -> OperationResult<()> {
+        // Check for cancellation before starting
+        if let Some(tracker) = progress.as_deref() {
+            tracker.cancellation_token().throw_if_cancelled()?;
+        }
+        
+        let source_size = tokio::fs::metadata(&self.source).await
+            .ok()
+            .filter(|metadata| metadata.is_file())
+            .map(|metadata| metadata.len());
+        
+        match source_size {
+            Some(size) if size >= self.chunked_copy_threshold => {
+                let start_items = progress.as_deref().map(|t| t.progress().current).unwrap_or(0);
+                self.execute_chunked(fs, progress.as_deref_mut()).await?;
+                
+                if let Some(tracker) = progress {
+                    let bytes = tracker.progress().bytes_processed;
+                    // The file already landed; a cancellation requested now is observed by the next command
+                    let _ = tracker.update(start_items + 1, bytes, self.description());
+                }
+            }
+            _ => {
+                self.execute_whole(fs, progress.as_deref_mut()).await?;
+                
+                if let Some(tracker) = progress {
+                    // The file already landed; a cancellation requested now is observed by the next command
+                    let _ = tracker.increment(source_size.unwrap_or(0));
+                }
+            }
+        }
+        
+        Ok(())
+    }
+    
+    async fn estimate_work(&self, fs: Arc<dyn FileSystemService>) -> OperationResult<(u64, u64)> {
+        let size = fs.get_file_size(&self.source).await.unwrap_or(0);
+        Ok((1, size))
+    }
+    
+    fn supports_progress(&self) -> bool {
+        true
+    }
+}
+
+/// Files at or above this size are copied in chunks with byte-level progress
+pub const LARGE_FILE_THRESHOLD: u64 = 64 * 1024 * 1024;
+
+/// Default chunk size for chunked copies
+pub const COPY_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
+/// Suffix appended to the destination name while a chunked copy is in flight
+pub const PARTIAL_COPY_SUFFIX: &str = ".part";
+
+/// Suffix of the record naming the source a `.part` file was copied from
+pub const PARTIAL_COPY_SOURCE_SUFFIX: &str = ".part.source";
+
+/// Bytes written to a `.part` file between syncs to disk
+const PARTIAL_COPY_CHECKPOINT_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Hidden sibling of `destination` with `suffix` added to its name
+fn hidden_sibling(destination: &Path, suffix: &str) -> PathBuf {
+    let mut file_name = std::ffi::OsString::from(".");
+    if let Some(name) = destination.file_name() {
+        file_name.push(name);
+    }
+    file_name.push(suffix);
+    destination.with_file_name(file_name)
+}
+
+/// Path of the temporary file a chunked copy writes to before renaming into place
+///
+/// Dot-prefixed so a copy left behind doesn't show up among the folder's files.
+pub fn partial_copy_path(destination: &Path) -> PathBuf {
+    hidden_sibling(destination, PARTIAL_COPY_SUFFIX)
+}
+
+/// Path of the record naming the source of the partial copy of `destination`
+pub fn partial_copy_source_path(destination: &Path) -> PathBuf {
+    hidden_sibling(destination, PARTIAL_COPY_SOURCE_SUFFIX)
+}
+
+/// Remove the partial copy of `destination` and the record of its source, if any
+pub async fn remove_partial_copy(destination: &Path) {
+    for path in [partial_copy_path(destination), partial_copy_source_path(destination)] {
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => tracing::warn!("Failed to remove partial copy {}: {}", path.display(), e),
+        }
+    }
+}
+
+/// The source a `.part` file is being copied from
+/// 
+/// Kept next to the `.part` file so an interrupted copy is only resumed from
+/// the same, unchanged source.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct PartialCopySource {
+    path: PathBuf,
+    size: u64,
+    modified: Option<SystemTime>,
+    /// Quick hash of the size and both ends of the file
+    quick_hash: String,
+}
+
+impl PartialCopySource {
+    /// Identify `source` as it is now
+    async fn of(source: &Path) -> OperationResult<Self> {
+        let metadata = tokio::fs::metadata(source).await
+            .map_err(|e| OperationError::FileSystem(FileSystemError::from_io_error(e, source)))?;
+        let hashing = HashingService::with_config(HashingConfig {
+            skip_hidden: false,
+            ..HashingConfig::default()
+        });
+        let path = source.to_path_buf();
+        let quick_hash = tokio::task::spawn_blocking(move || hashing.quick_hash_blocking(&path, DEFAULT_QUICK_HASH_SAMPLE))
+            .await
+            .map_err(|e| OperationError::ExecutionFailed(format!("Failed to identify {}: {}", source.display(), e)))?
+            .map_err(|e| OperationError::ExecutionFailed(format!("Failed to identify {}: {}", source.display(), e)))?
+            .hash;
+        
+        Ok(Self {
+            path: source.to_path_buf(),
+            size: metadata.len(),
+            modified: metadata.modified().ok(),
+            quick_hash,
+        })
+    }
+    
+    /// How many bytes of the partial copy of `destination` were synced to disk,
+    /// if it was recorded as a copy of this source
+    async fn synced_bytes_for(&self, destination: &Path) -> Option<u64> {
+        let bytes = tokio::fs::read(partial_copy_source_path(destination)).await.ok()?;
+        serde_json::from_slice::<PartialCopyRecord>(&bytes).ok()
+            .filter(|record| record.source == *self)
+            .map(|record| record.synced_bytes)
+    }
+    
+    /// Record this source as the one the partial copy of `destination` comes
+    /// from, with its first `synced_bytes` bytes on disk
+    async fn record_for(&self, destination: &Path, synced_bytes: u64) -> OperationResult<()> {
+        let record_path = partial_copy_source_path(destination);
+        let record = PartialCopyRecord { source: self.clone(), synced_bytes };
+        let json = serde_json::to_vec(&record).map_err(|e| OperationError::Serialization(e.to_string()))?;
+        tokio::fs::write(&record_path, json).await
+            .map_err(|e| OperationError::FileSystem(FileSystemError::from_io_error(e, &record_path)))
+    }
+}
+
+/// The record kept next to a `.part` file
+#[derive(Debug, Serialize, Deserialize)]
+struct PartialCopyRecord {
+    source: PartialCopySource,
+    /// Length of the prefix synced at the last checkpoint; anything past it
+    /// may not have reached the disk before a crash
+    synced_bytes: u64,
+}
+
+/// Copy a file in fixed-size chunks, reporting byte progress between chunks
+/// 
+/// Data is written to a `.part` sibling of the destination and only renamed into
+/// place once every byte has been written, so an interrupted copy never leaves a
+/// truncated file at the real destination. The `.part` is synced to disk every
+/// [`PARTIAL_COPY_CHECKPOINT_BYTES`] and the record next to it, see
+/// [`partial_copy_source_path`], notes how much was synced. A `.part` left behind
+/// by a crash or an I/O error is resumed from that synced length when the record
+/// names this source with its size, modification time and quick hash unchanged;
+/// any other `.part` is started over.
+/// Cancellation is observed through the tracker between chunks and removes the
+/// partial file. Returns the number of bytes in the finished file.
+pub async fn copy_file_chunked(
+    source: &Path,
+    destination: &Path,
+    chunk_size: usize,
+    mut progress: Option<&mut ProgressTracker>,
+) -> OperationResult<u64> {
+    use std::io::SeekFrom;
+    use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+    
+    let io_error = |e: std::io::Error, path: &Path| {
+        OperationError::FileSystem(FileSystemError::from_io_error(e, path))
+    };
+    
+    let identity = PartialCopySource::of(source).await?;
+    let total_bytes = identity.size;
+    let part_path = partial_copy_path(destination);
+    
+    // Resume a partial copy of this same source left behind by an interrupted
+    // run, keeping only what is known to have reached the disk
+    let mut copied = match tokio::fs::metadata(&part_path).await {
+        Ok(metadata) => identity.synced_bytes_for(destination).await
+            .map_or(0, |synced| synced.min(metadata.len()).min(total_bytes)),
+        Err(_) => 0,
+    };
+    let mut synced = copied;
+    
+    let mut reader = tokio::fs::File::open(source).await
+        .map_err(|e| io_error(e, source))?;
+    let mut writer = tokio::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .open(&part_path).await
+        .map_err(|e| io_error(e, &part_path))?;
+    
+    writer.set_len(copied).await.map_err(|e| io_error(e, &part_path))?;
+    identity.record_for(destination, synced).await?;
+    if copied > 0 {
+        tracing::info!("Resuming copy of {} at byte {}", source.display(), copied);
+        reader.seek(SeekFrom::Start(copied)).await.map_err(|e| io_error(e, source))?;
+        writer.seek(SeekFrom::Start(copied)).await.map_err(|e| io_error(e, &part_path))?;
+    }
+    
+    let (start_items, start_bytes) = progress.as_deref()
+        .map(|t| (t.progress().current, t.progress().bytes_processed))
+        .unwrap_or((0, 0));
+    let operation = format!("Copying {}", source.display());
+    let mut buffer = vec![0u8; chunk_size.max(1)];
+    
+    loop {
+        let read = reader.read(&mut buffer).await.map_err(|e| io_error(e, source))?;
+        if read == 0 {
+            break;
+        }
+        
+        writer.write_all(&buffer[..read]).await.map_err(|e| io_error(e, &part_path))?;
+        copied += read as u64;
+        
+        if copied - synced >= PARTIAL_COPY_CHECKPOINT_BYTES {
+            writer.flush().await.map_err(|e| io_error(e, &part_path))?;
+            writer.sync_data().await.map_err(|e| io_error(e, &part_path))?;
+            identity.record_for(destination, copied).await?;
+            synced = copied;
+        }
+        
+        if let Some(tracker) = progress.as_deref_mut() {
+            if let Err(e) = tracker.update(start_items, start_bytes + copied, operation.clone()) {
+                drop(writer);
+                remove_partial_copy(destination).await;
+                return Err(e);
+            }
+        }
+    }
+    
+    writer.flush().await.map_err(|e| io_error(e, &part_path))?;
+    writer.sync_all().await.map_err(|e| io_error(e, &part_path))?;
+    drop(writer);
+    
+    tokio::fs::rename(&part_path, destination).await
+        .map_err(|e| io_error(e, destination))?;
+    remove_partial_copy(destination).await;
+    
+    Ok(copied)
+}
+
+/// Move a file to another file system: copy it in chunks, check the copy is
+/// complete and only then remove the source
+///
+/// With `verify_integrity` the copy must also hash the same as the source,
+/// see [`verify_copy`]. The copy always starts from scratch: a `.part` file
+/// already at the destination is discarded rather than resumed, since the
+/// source is deleted on the strength of this copy alone. A copy that fails,
+/// is cancelled, comes up short or doesn't match is removed again, along
+/// with its `.part` file, and the source is left alone. If the source can't be removed the copy is removed
+/// instead, so the file ends up in exactly one place either way. Returns the
+/// number of bytes moved.
+pub async fn move_file_by_copy(
+    source: &Path,
+    destination: &Path,
+    chunk_size: usize,
+    verify_integrity: bool,
+    mut progress: Option<&mut ProgressTracker>,
+) -> OperationResult<u64> {
+    let expected = tokio::fs::metadata(source).await
+        .map_err(|e| OperationError::FileSystem(FileSystemError::from_io_error(e, source)))?
+        .len();
+    
+    // Never resumed: bytes not read from this source must not stand in for it
+    remove_partial_copy(destination).await;
+    let copied = match copy_file_chunked(source, destination, chunk_size, progress.as_deref_mut()).await {
+        Ok(copied) => copied,
+        Err(e) => {
+            // Not kept to resume: the source stays where it is
+            remove_partial_copy(destination).await;
+            return Err(e);
+        }
+    };
+    let written = tokio::fs::metadata(destination).await.map(|metadata| metadata.len()).ok();
+    if copied != expected || written != Some(expected) {
+        let _ = tokio::fs::remove_file(destination).await;
+        return Err(OperationError::ExecutionFailed(format!(
+            "Copy of {} is incomplete: {} of {} bytes",
+            source.display(),
+            written.unwrap_or(0),
+            expected
+        )));
+    }
+    if verify_integrity {
+        if let Err(e) = verify_copy(source, destination, progress).await {
+            let _ = tokio::fs::remove_file(destination).await;
+            return Err(e);
+        }
+    }
+    
+    if let Err(e) = tokio::fs::remove_file(source).await {
+        let _ = tokio::fs::remove_file(destination).await;
+        return Err(OperationError::FileSystem(FileSystemError::from_io_error(e, source)));
+    }
+    Ok(copied)
+}
+
+/// Hash `source` and `destination` in full and fail with
+/// [`OperationError::IntegrityMismatch`] if they differ
+///
+/// The tracker, if any, shows how far hashing got without counting towards
+/// its bytes, and its cancellation stops hashing between chunks.
+pub async fn verify_copy(
+    source: &Path,
+    destination: &Path,
+    mut progress: Option<&mut ProgressTracker>,
+) -> OperationResult<()> {
+    let hashing = HashingService::with_config(HashingConfig {
+        buffer_size: COPY_CHUNK_SIZE,
+        max_file_size: u64::MAX,
+        // Hidden files are copied like any other, so they're checked too
+        skip_hidden: false,
+        ..HashingConfig::default()
+    });
+    let source_hash = hash_for_verification(&hashing, source, progress.as_deref_mut()).await?;
+    let destination_hash = hash_for_verification(&hashing, destination, progress).await?;
+    
+    if source_hash != destination_hash {
+        tracing::error!("Copy of {} at {} doesn't match the original", source.display(), destination.display());
+        return Err(OperationError::IntegrityMismatch {
+            path: destination.to_path_buf(),
+            source_hash,
+            destination_hash,
+        });
+    }
+    Ok(())
+}
+
+/// Full hash of `path` for [`verify_copy`], reporting progress as it goes
+async fn hash_for_verification(
+    hashing: &HashingService,
+    path: &Path,
+    mut progress: Option<&mut ProgressTracker>,
+) -> OperationResult<String> {
+    let (current, bytes) = progress.as_deref()
+        .map(|t| (t.progress().current, t.progress().bytes_processed))
+        .unwrap_or((0, 0));
+    let total = tokio::fs::metadata(path).await.map(|metadata| metadata.len()).unwrap_or(0);
+    
+    let result = hashing.hash_file_with_progress(path, |hashed| match progress.as_deref_mut() {
+        Some(tracker) => {
+            let percent = if total == 0 { 100 } else { hashed * 100 / total };
+            tracker.update(current, bytes, format!("Verifying {} ({}%)", path.display(), percent)).is_ok()
+        }
+        None => true,
+    }).await;
+    
+    match result {
+        Ok(hash) => Ok(hash.hash),
+        Err(HashingError::Cancelled) => Err(OperationError::Cancelled),
+        Err(HashingError::Io(e)) => Err(OperationError::FileSystem(FileSystemError::from_io_error(e, path))),
+        Err(e) => Err(OperationError::ExecutionFailed(format!("Failed to verify {}: {}", path.display(), e))),
+    }
+}
+
+/// Move file command
+/// 
+/// Moves a file from source to destination. An existing destination is
+/// handled by `conflict_resolution` as in `CopyCommand`. A file that can't be
+/// renamed because the destination is on another file system is copied and
+/// the source removed, see [`move_file_by_copy`]; only such copies are
+/// checked when `verify_integrity` is set, as a rename leaves the file as is.
+/// Undo operation moves the file back to the original location, copying it
+/// back the same way when it was copied.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoveCommand {
+    pub source: PathBuf,
+    pub destination: PathBuf,
+    #[serde(default)]
+    pub conflict_resolution: ConflictResolution,
+    /// Files at or above this size are copied in chunks when a progress-tracked
+    /// move has to cross file systems
+    #[serde(default = "default_chunked_copy_threshold")]
+    pub chunked_copy_threshold: u64,
+    /// Hash source and destination after copying across file systems and roll back if they differ
+    #[serde(default)]
+    pub verify_integrity: bool,
     
     // State for undo
     destination_existed_before: Option<bool>,
+    /// Contents of an overwritten destination; kept in memory only, so
+    /// commands holding one are not written to the history file
+    #[serde(skip)]
     original_destination_backup: Option<Vec<u8>>,
+    #[serde(default)]
+    skipped: bool,
+    /// The rename crossed file systems, so the file was copied and the source removed
+    #[serde(default)]
+    copied_across_devices: bool,
+    /// Copy and delete even where a rename would do
+    #[serde(skip)]
+    force_copy: bool,
     
     metadata: CommandMetadata,
 }
@@ -2213,52 +3577,154 @@ impl MoveCommand {
         Self {
             source,
             destination,
-            overwrite: false,
+            conflict_resolution: ConflictResolution::default(),
+            chunked_copy_threshold: LARGE_FILE_THRESHOLD,
+            verify_integrity: false,
             destination_existed_before: None,
             original_destination_backup: None,
+            skipped: false,
+            copied_across_devices: false,
+            force_copy: false,
             metadata: CommandMetadata::default(),
         }
     }
     
-    /// Set whether to overwrite existing files
-    pub fn with_overwrite(mut self, overwrite: bool) -> Self {
-        self.overwrite = overwrite;
+    /// Set what happens when the destination already exists
+    pub fn with_conflict_resolution(mut self, resolution: ConflictResolution) -> Self {
+        self.conflict_resolution = resolution;
         self
     }
-}
-
-#[async_trait]
-impl Command for MoveCommand {
-    async fn execute(&mut self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+    
+    /// Take the cross file system path even on one file system
+    #[cfg(test)]
+    fn with_forced_copy(mut self) -> Self {
+        self.force_copy = true;
+        self
+    }
+    
+    /// Set the file size at which progress-tracked moves across file systems copy in chunks
+    pub fn with_chunked_copy_threshold(mut self, threshold: u64) -> Self {
+        self.chunked_copy_threshold = threshold;
+        self
+    }
+    
+    /// Set whether a copy across file systems is checked against the source
+    /// by hashing both before the source is removed
+    pub fn with_verify_integrity(mut self, verify: bool) -> Self {
+        self.verify_integrity = verify;
+        self
+    }
+    
+    /// Whether the last execution left the source and the existing destination alone
+    pub fn was_skipped(&self) -> bool {
+        self.skipped
+    }
+    
+    /// Whether the last execution had to copy the file to another file system
+    pub fn was_copied_across_devices(&self) -> bool {
+        self.copied_across_devices
+    }
+    
+    /// Validate and record the destination state needed for undo
+    async fn prepare_destination(&mut self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
         if self.is_executed() {
             return Err(OperationError::AlreadyExecuted);
         }
         
         // Validate first
-        self.validate(fs.clone()).await?;
+        self.validate(fs).await?;
         
-        // Check if destination exists and backup if needed
-        self.destination_existed_before = Some(self.destination.exists());
+        let (skipped, existed_before, backup) = resolve_destination(&mut self.destination, self.conflict_resolution).await?;
+        self.skipped = skipped;
+        self.destination_existed_before = Some(existed_before);
+        self.original_destination_backup = backup;
         
-        if *self.destination_existed_before.as_ref().unwrap() && self.overwrite {
-            if let Ok(content) = tokio::fs::read(&self.destination).await {
-                self.original_destination_backup = Some(content);
-            }
+        Ok(())
+    }
+    
+    /// Rename a large file into place, or copy it chunk by chunk when the rename
+    /// would cross file systems, reporting byte progress to the tracker
+    ///
+    /// A cancelled copy removes its partial file and leaves the source alone.
+    async fn execute_chunked(&mut self, fs: Arc<dyn FileSystemService>, progress: Option<&mut ProgressTracker>) -> OperationResult<()> {
+        self.prepare_destination(fs.clone()).await?;
+        
+        if self.skipped {
+            tracing::info!("Skipped moving {}: {} already exists", self.source.display(), self.destination.display());
+        } else {
+            self.move_file_into_place(fs, progress).await?;
         }
         
-        // Execute the move using file system service
-        let operation = super::file_system::FileOperation {
-            source: self.source.clone(),
-            destination: self.destination.clone(),
-            overwrite_mode: if self.overwrite {
-                super::file_system::OverwriteMode::Overwrite
-            } else {
-                super::file_system::OverwriteMode::Fail
-            },
-            preserve_metadata: true,
-        };
+        // Update metadata
+        self.metadata.status = CommandStatus::Executed;
+        self.metadata.executed_at = Some(SystemTime::now());
+        
+        Ok(())
+    }
+    
+    /// Move the source file to the destination, which the file system
+    /// service copies over and checks when the rename would cross file systems
+    async fn move_file_into_place(&mut self, fs: Arc<dyn FileSystemService>, progress: Option<&mut ProgressTracker>) -> OperationResult<()> {
+        self.copied_across_devices = false;
+        match fs.move_file(self.file_operation(self.source.clone(), self.destination.clone()), progress).await {
+            Ok(outcome) => {
+                self.copied_across_devices = outcome == MoveOutcome::Copied;
+                Ok(())
+            }
+            Err(e) => {
+                // An overwritten destination is gone once the move replaced it
+                if let Some(backup) = &self.original_destination_backup {
+                    if !self.destination.exists() {
+                        let _ = tokio::fs::write(&self.destination, backup).await;
+                    }
+                }
+                Err(move_error(e))
+            }
+        }
+    }
+    
+    /// A file move that replaces whatever `prepare_destination` decided may go
+    fn file_operation(&self, source: PathBuf, destination: PathBuf) -> FileOperation {
+        let mut operation = FileOperation::new(source, destination)
+            .with_overwrite_mode(OverwriteMode::Overwrite)
+            .with_verify_integrity(self.verify_integrity);
+        operation.force_copy = self.force_copy;
+        operation
+    }
+}
+
+/// The error a failed file move stands for, keeping cancellation and
+/// integrity failures of a copy across file systems as their own errors
+fn move_error(error: FileSystemError) -> OperationError {
+    match error {
+        FileSystemError::Cancelled => OperationError::Cancelled,
+        FileSystemError::IntegrityMismatch { path, source_hash, destination_hash } => {
+            OperationError::IntegrityMismatch { path, source_hash, destination_hash }
+        }
+        other => OperationError::FileSystem(other),
+    }
+}
+
+fn default_chunked_copy_threshold() -> u64 {
+    LARGE_FILE_THRESHOLD
+}
+
+#[async_trait]
+impl Command for MoveCommand {
+    async fn execute(&mut self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        self.prepare_destination(fs.clone()).await?;
         
-        fs.move_file(operation).await.map_err(OperationError::FileSystem)?;
+        if self.skipped {
+            tracing::info!("Skipped moving {}: {} already exists", self.source.display(), self.destination.display());
+        } else if self.source.is_file() {
+            self.move_file_into_place(fs, None).await?;
+        } else {
+            // Execute the move using file system service
+            let operation = FileOperation::new(self.source.clone(), self.destination.clone())
+                .with_conflict_resolution(self.conflict_resolution)?;
+            
+            fs.move_file(operation, None).await.map_err(OperationError::FileSystem)?;
+        }
         
         // Update metadata
         self.metadata.status = CommandStatus::Executed;
@@ -2272,15 +3738,17 @@ impl Command for MoveCommand {
             return Err(OperationError::NotExecuted);
         }
         
-        // Move the file back to its original location
-        let operation = super::file_system::FileOperation {
-            source: self.destination.clone(),
-            destination: self.source.clone(),
-            overwrite_mode: super::file_system::OverwriteMode::Overwrite,
-            preserve_metadata: true,
-        };
+        // A skipped move left both files where they were
+        if self.skipped {
+            self.metadata.status = CommandStatus::Undone;
+            self.metadata.undone_at = Some(SystemTime::now());
+            return Ok(());
+        }
         
-        fs.move_file(operation).await
+        // Move the file back to its original location, copied back the same
+        // way if it went to another file system
+        let operation = self.file_operation(self.destination.clone(), self.source.clone());
+        fs.move_file(operation, None).await
             .map_err(|e| OperationError::UndoFailed(format!("Failed to move file back: {}", e)))?;
         
         // Restore original destination if it existed before
@@ -2334,16 +3802,22 @@ impl Command for MoveCommand {
             }
         }
         
-        // Check destination overwrite
-        if self.destination.exists() && !self.overwrite {
-            return Err(OperationError::ValidationFailed(
-                format!("Destination already exists and overwrite is disabled: {}", self.destination.display())
-            ));
+        // Without a resolution the caller has to ask about an existing destination
+        if self.destination.exists() && self.conflict_resolution == ConflictResolution::Ask {
+            return Err(OperationError::DestinationExists(self.destination.clone()));
         }
         
         Ok(())
     }
     
+    fn command_kind(&self) -> Option<CommandKind> {
+        // Undoing an overwrite needs the backup, which isn't saved
+        if self.original_destination_backup.is_some() {
+            return None;
+        }
+        Some(CommandKind::Move(self.clone()))
+    }
+    
     fn metadata(&self) -> &CommandMetadata {
         &self.metadata
     }
@@ -2357,6 +3831,68 @@ impl Command for MoveCommand {
             self.source.display(), 
             self.destination.display())
     }
+    
+    fn kind(&self) -> OperationKind {
+        OperationKind::Move
+    }
+    
+    fn source_path(&self) -> Option<&Path> {
+        Some(&self.source)
+    }
+    
+    fn destination_path(&self) -> Option<&Path> {
+        Some(&self.destination)
+    }
+    
+    async fn execute_with_progress(
+        &mut self, 
+        fs: Arc<dyn FileSystemService>, 
+        mut progress: Option<&mut ProgressTracker>
+    ) -> OperationResult<()> {
+        // Check for cancellation before starting
+        if let Some(tracker) = progress.as_deref() {
+            tracker.cancellation_token().throw_if_cancelled()?;
+        }
+        
+        let source_size = tokio::fs::metadata(&self.source).await
+            .ok()
+            .filter(|metadata| metadata.is_file())
+            .map(|metadata| metadata.len());
+        
+        match source_size {
+            Some(size) if size >= self.chunked_copy_threshold => {
+                let (start_items, start_bytes) = progress.as_deref()
+                    .map(|t| (t.progress().current, t.progress().bytes_processed))
+                    .unwrap_or((0, 0));
+                self.execute_chunked(fs, progress.as_deref_mut()).await?;
+                
+                if let Some(tracker) = progress {
+                    // A rename copies nothing, but the file's bytes are done all the same
+                    let bytes = tracker.progress().bytes_processed.max(start_bytes + size);
+                    let _ = tracker.update(start_items + 1, bytes, self.description());
+                }
+            }
+            _ => {
+                self.execute(fs).await?;
+                
+                if let Some(tracker) = progress {
+                    // The file already landed; a cancellation requested now is observed by the next command
+                    let _ = tracker.increment(source_size.unwrap_or(0));
+                }
+            }
+        }
+        
+        Ok(())
+    }
+    
+    async fn estimate_work(&self, fs: Arc<dyn FileSystemService>) -> OperationResult<(u64, u64)> {
+        let size = fs.get_file_size(&self.source).await.unwrap_or(0);
+        Ok((1, size))
+    }
+    
+    fn supports_progress(&self) -> bool {
+        true
+    }
 }
 
 /// Delete file command
@@ -2366,7 +3902,8 @@ impl Command for MoveCommand {
 pub struct DeleteCommand {
     pub path: PathBuf,
     
-    // State for undo - store the entire file content
+    // State for undo - store the entire file content, in memory only
+    #[serde(skip)]
     file_backup: Option<Vec<u8>>,
     // Note: We skip std::fs::Metadata for serialization since it's not serializable
     #[serde(skip)]
@@ -2469,105 +4006,1098 @@ impl Command for DeleteCommand {
         Ok(())
     }
     
+    fn command_kind(&self) -> Option<CommandKind> {
+        // Undo restores the file from its backup, which isn't saved
+        if self.file_backup.is_some() {
+            return None;
+        }
+        Some(CommandKind::Delete(self.clone()))
+    }
+    
     fn metadata(&self) -> &CommandMetadata {
         &self.metadata
     }
     
-    fn metadata_mut(&mut self) -> &mut CommandMetadata {
-        &mut self.metadata
+    fn metadata_mut(&mut self) -> &mut CommandMetadata {
+        &mut self.metadata
+    }
+    
+    fn description(&self) -> String {
+        format!("Delete {}", self.path.display())
+    }
+    
+    fn kind(&self) -> OperationKind {
+        OperationKind::Delete
+    }
+    
+    fn source_path(&self) -> Option<&Path> {
+        Some(&self.path)
+    }
+}
+
+/// Move to trash command
+/// 
+/// Moves a file or directory to the system trash. Undo puts it back where the
+/// platform allows restoring from the trash (Windows and freedesktop systems).
+/// When the trash can't take the item it fails with `TrashUnavailable` and
+/// leaves the item in place; it never falls back to deleting it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashCommand {
+    pub path: PathBuf,
+    /// Folder used in place of the system trash
+    trash_dir: Option<PathBuf>,
+    
+    // State for undo - where the item went when `trash_dir` is set
+    trashed_path: Option<PathBuf>,
+    
+    metadata: CommandMetadata,
+}
+
+impl TrashCommand {
+    /// Create a new move-to-trash command
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            trash_dir: None,
+            trashed_path: None,
+            metadata: CommandMetadata::default(),
+        }
+    }
+    
+    /// Move the item into `dir` instead of the system trash
+    pub fn with_trash_dir(mut self, dir: PathBuf) -> Self {
+        self.trash_dir = Some(dir);
+        self
+    }
+}
+
+#[async_trait]
+impl Command for TrashCommand {
+    async fn execute(&mut self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        if self.is_executed() {
+            return Err(OperationError::AlreadyExecuted);
+        }
+        
+        self.validate(fs.clone()).await?;
+        
+        if let Some(dir) = &self.trash_dir {
+            let name = self.path.file_name().ok_or_else(|| {
+                OperationError::ExecutionFailed(format!("Invalid path: {}", self.path.display()))
+            })?;
+            // A unique prefix keeps items with the same name apart
+            let target = dir.join(format!("{}-{}", Uuid::new_v4().simple(), name.to_string_lossy()));
+            tokio::fs::create_dir_all(dir).await
+                .map_err(|e| OperationError::TrashUnavailable(format!("Failed to create trash folder: {}", e)))?;
+            tokio::fs::rename(&self.path, &target).await
+                .map_err(|e| OperationError::TrashUnavailable(format!("Failed to move {} to the trash: {}", self.path.display(), e)))?;
+            self.trashed_path = Some(target);
+        } else {
+            let path = self.path.clone();
+            tokio::task::spawn_blocking(move || trash::delete(&path))
+                .await
+                .map_err(|e| OperationError::ExecutionFailed(format!("Trash task failed: {}", e)))?
+                .map_err(|e| OperationError::TrashUnavailable(format!("Failed to move {} to the trash: {}", self.path.display(), e)))?;
+        }
+        
+        // Update metadata
+        self.metadata.status = CommandStatus::Executed;
+        self.metadata.executed_at = Some(SystemTime::now());
+        
+        Ok(())
+    }
+    
+    async fn undo(&mut self, _fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        if !self.can_undo() {
+            return Err(OperationError::NotExecuted);
+        }
+        
+        if self.path.exists() {
+            return Err(OperationError::UndoFailed(
+                format!("Cannot restore, {} already exists", self.path.display())
+            ));
+        }
+        
+        if let Some(trashed) = &self.trashed_path {
+            if let Some(parent) = self.path.parent() {
+                tokio::fs::create_dir_all(parent).await
+                    .map_err(|e| OperationError::UndoFailed(format!("Failed to recreate {}: {}", parent.display(), e)))?;
+            }
+            tokio::fs::rename(trashed, &self.path).await
+                .map_err(|e| OperationError::UndoFailed(format!("Failed to restore {}: {}", self.path.display(), e)))?;
+            self.trashed_path = None;
+        } else {
+            let path = self.path.clone();
+            tokio::task::spawn_blocking(move || restore_from_trash(&path))
+                .await
+                .map_err(|e| OperationError::UndoFailed(format!("Restore task failed: {}", e)))??;
+        }
+        
+        // Update metadata
+        self.metadata.status = CommandStatus::Undone;
+        self.metadata.undone_at = Some(SystemTime::now());
+        
+        Ok(())
+    }
+    
+    async fn validate(&self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        // Check the item exists
+        if !self.path.exists() {
+            return Err(OperationError::ValidationFailed(
+                format!("Path does not exist: {}", self.path.display())
+            ));
+        }
+        
+        // Check write permission for parent directory (needed to remove the item)
+        if let Some(parent) = self.path.parent() {
+            if !fs.check_write_permission(parent).await.unwrap_or(false) {
+                return Err(OperationError::ValidationFailed(
+                    format!("No write permission for parent directory: {}", parent.display())
+                ));
+            }
+        }
+        
+        Ok(())
+    }
+    
+    fn command_kind(&self) -> Option<CommandKind> {
+        Some(CommandKind::Trash(self.clone()))
+    }
+    
+    fn metadata(&self) -> &CommandMetadata {
+        &self.metadata
+    }
+    
+    fn metadata_mut(&mut self) -> &mut CommandMetadata {
+        &mut self.metadata
+    }
+    
+    fn description(&self) -> String {
+        format!("Move {} to trash", self.path.display())
+    }
+    
+    fn kind(&self) -> OperationKind {
+        OperationKind::Delete
+    }
+    
+    fn source_path(&self) -> Option<&Path> {
+        Some(&self.path)
+    }
+}
+
+/// Delete permanently command
+/// 
+/// Removes a file or a whole directory without going through the trash. There
+/// is nothing to restore from, so it can't be undone; callers confirm first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PermanentDeleteCommand {
+    pub path: PathBuf,
+    metadata: CommandMetadata,
+}
+
+impl PermanentDeleteCommand {
+    /// Create a new permanent delete command
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            metadata: CommandMetadata::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl Command for PermanentDeleteCommand {
+    async fn execute(&mut self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        if self.is_executed() {
+            return Err(OperationError::AlreadyExecuted);
+        }
+        
+        self.validate(fs.clone()).await?;
+        
+        let path = self.path.clone();
+        tokio::task::spawn_blocking(move || {
+            if path.is_dir() && !path.is_symlink() {
+                std::fs::remove_dir_all(&path)
+            } else {
+                std::fs::remove_file(&path)
+            }
+        })
+        .await
+        .map_err(|e| OperationError::ExecutionFailed(format!("Delete task failed: {}", e)))?
+        .map_err(|e| OperationError::ExecutionFailed(format!("Failed to delete {}: {}", self.path.display(), e)))?;
+        
+        // Update metadata
+        self.metadata.status = CommandStatus::Executed;
+        self.metadata.executed_at = Some(SystemTime::now());
+        
+        Ok(())
+    }
+    
+    async fn undo(&mut self, _fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        Err(OperationError::UndoFailed(
+            format!("{} was deleted permanently", self.path.display())
+        ))
+    }
+    
+    async fn validate(&self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        // Check the item exists, counting dangling symlinks
+        if self.path.symlink_metadata().is_err() {
+            return Err(OperationError::ValidationFailed(
+                format!("Path does not exist: {}", self.path.display())
+            ));
+        }
+        
+        // Check write permission for parent directory (needed to remove the item)
+        if let Some(parent) = self.path.parent() {
+            if !fs.check_write_permission(parent).await.unwrap_or(false) {
+                return Err(OperationError::ValidationFailed(
+                    format!("No write permission for parent directory: {}", parent.display())
+                ));
+            }
+        }
+        
+        Ok(())
+    }
+    
+    fn metadata(&self) -> &CommandMetadata {
+        &self.metadata
+    }
+    
+    fn metadata_mut(&mut self) -> &mut CommandMetadata {
+        &mut self.metadata
+    }
+    
+    fn description(&self) -> String {
+        format!("Permanently delete {}", self.path.display())
+    }
+    
+    fn kind(&self) -> OperationKind {
+        OperationKind::Delete
+    }
+    
+    fn source_path(&self) -> Option<&Path> {
+        Some(&self.path)
+    }
+    
+    fn can_undo(&self) -> bool {
+        false
+    }
+}
+
+/// Put `path` back from the system trash, choosing the most recently trashed match
+#[cfg(any(target_os = "windows", all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))))]
+fn restore_from_trash(path: &Path) -> OperationResult<()> {
+    let item = trash::os_limited::list()
+        .map_err(|e| OperationError::UndoFailed(format!("Failed to read the trash: {}", e)))?
+        .into_iter()
+        .filter(|item| item.original_parent.join(&item.name) == path)
+        .max_by_key(|item| item.time_deleted)
+        .ok_or_else(|| OperationError::UndoFailed(format!("{} is no longer in the trash", path.display())))?;
+    trash::os_limited::restore_all([item])
+        .map_err(|e| OperationError::UndoFailed(format!("Failed to restore {}: {}", path.display(), e)))
+}
+
+/// The trash can't be read back here; the Finder's Put Back restores items on macOS
+#[cfg(not(any(target_os = "windows", all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android")))))]
+fn restore_from_trash(path: &Path) -> OperationResult<()> {
+    Err(OperationError::UndoFailed(format!(
+        "Restoring {} from the trash is not supported on this platform", path.display()
+    )))
+}
+
+/// Restore from trash command
+/// 
+/// Puts an item listed in the system trash back where it was deleted from.
+/// Undo moves it to the trash again. Only available where the trash can be
+/// listed; see [`trash_bin::SUPPORTED`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestoreFromTrashCommand {
+    pub item: TrashedItem,
+    /// Where the item is restored to
+    pub target: PathBuf,
+    metadata: CommandMetadata,
+}
+
+impl RestoreFromTrashCommand {
+    /// Create a new restore command
+    pub fn new(item: TrashedItem) -> Self {
+        Self {
+            target: item.original_path(),
+            item,
+            metadata: CommandMetadata::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl Command for RestoreFromTrashCommand {
+    async fn execute(&mut self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        if self.is_executed() {
+            return Err(OperationError::AlreadyExecuted);
+        }
+        
+        self.validate(fs.clone()).await?;
+        
+        let item = self.item.clone();
+        tokio::task::spawn_blocking(move || trash_bin::restore_item(&item))
+            .await
+            .map_err(|e| OperationError::ExecutionFailed(format!("Restore task failed: {}", e)))?
+            .map_err(|e| OperationError::ExecutionFailed(e.to_string()))?;
+        
+        // Update metadata
+        self.metadata.status = CommandStatus::Executed;
+        self.metadata.executed_at = Some(SystemTime::now());
+        
+        Ok(())
+    }
+    
+    async fn undo(&mut self, _fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        if !self.can_undo() {
+            return Err(OperationError::NotExecuted);
+        }
+        
+        let path = self.target.clone();
+        tokio::task::spawn_blocking(move || trash::delete(&path))
+            .await
+            .map_err(|e| OperationError::UndoFailed(format!("Trash task failed: {}", e)))?
+            .map_err(|e| OperationError::UndoFailed(format!("Failed to move {} back to the trash: {}", self.target.display(), e)))?;
+        
+        // Update metadata
+        self.metadata.status = CommandStatus::Undone;
+        self.metadata.undone_at = Some(SystemTime::now());
+        
+        Ok(())
+    }
+    
+    async fn validate(&self, _fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        trash_bin::restore_target(&self.item, |path| path.symlink_metadata().is_ok())
+            .map(|_| ())
+            .map_err(|e| OperationError::ValidationFailed(e.to_string()))
+    }
+    
+    fn command_kind(&self) -> Option<CommandKind> {
+        Some(CommandKind::RestoreFromTrash(self.clone()))
+    }
+    
+    fn metadata(&self) -> &CommandMetadata {
+        &self.metadata
+    }
+    
+    fn metadata_mut(&mut self) -> &mut CommandMetadata {
+        &mut self.metadata
+    }
+    
+    fn description(&self) -> String {
+        format!("Restore {} from trash", self.target.display())
+    }
+    
+    fn kind(&self) -> OperationKind {
+        OperationKind::Move
+    }
+    
+    fn destination_path(&self) -> Option<&Path> {
+        Some(&self.target)
+    }
+}
+
+/// Rename file command
+/// 
+/// Renames a file. Undo operation renames it back to the original name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenameCommand {
+    pub old_path: PathBuf,
+    pub new_name: String,
+    
+    // Computed new path
+    pub new_path: PathBuf,
+    
+    // State for undo
+    new_path_existed_before: Option<bool>,
+    original_new_path_backup: Option<Vec<u8>>,
+    
+    metadata: CommandMetadata,
+}
+
+impl RenameCommand {
+    /// Create a new rename command
+    pub fn new(old_path: PathBuf, new_name: String) -> OperationResult<Self> {
+        let new_path = if let Some(parent) = old_path.parent() {
+            parent.join(&new_name)
+        } else {
+            PathBuf::from(&new_name)
+        };
+        
+        Ok(Self {
+            old_path,
+            new_name,
+            new_path,
+            new_path_existed_before: None,
+            original_new_path_backup: None,
+            metadata: CommandMetadata::default(),
+        })
+    }
+}
+
+#[async_trait]
+impl Command for RenameCommand {
+    async fn execute(&mut self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        if self.is_executed() {
+            return Err(OperationError::AlreadyExecuted);
+        }
+        
+        // Validate first
+        self.validate(fs.clone()).await?;
+        
+        // Check if new path exists and backup if needed
+        self.new_path_existed_before = Some(self.new_path.exists());
+        
+        if *self.new_path_existed_before.as_ref().unwrap() {
+            if let Ok(content) = tokio::fs::read(&self.new_path).await {
+                self.original_new_path_backup = Some(content);
+            }
+        }
+        
+        // Execute the rename using file system service
+        fs.rename_file(&self.old_path, &self.new_name).await
+            .map_err(OperationError::FileSystem)?;
+        
+        // Update metadata
+        self.metadata.status = CommandStatus::Executed;
+        self.metadata.executed_at = Some(SystemTime::now());
+        
+        Ok(())
+    }
+    
+    async fn undo(&mut self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        if !self.can_undo() {
+            return Err(OperationError::NotExecuted);
+        }
+        
+        // Get the original name
+        let original_name = self.old_path.file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| OperationError::UndoFailed("Cannot get original filename".to_string()))?;
+        
+        // Rename back to original
+        fs.rename_file(&self.new_path, original_name).await
+            .map_err(|e| OperationError::UndoFailed(format!("Failed to rename back: {}", e)))?;
+        
+        // Restore original file at new path if it existed
+        if let Some(backup) = &self.original_new_path_backup {
+            tokio::fs::write(&self.new_path, backup).await
+                .map_err(|e| OperationError::UndoFailed(format!("Failed to restore original file: {}", e)))?;
+        }
+        
+        // Update metadata
+        self.metadata.status = CommandStatus::Undone;
+        self.metadata.undone_at = Some(SystemTime::now());
+        
+        Ok(())
+    }
+    
+    async fn validate(&self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        // Check old path exists
+        if !self.old_path.exists() {
+            return Err(OperationError::ValidationFailed(
+                format!("Source file does not exist: {}", self.old_path.display())
+            ));
+        }
+        
+        // Check parent directory write permission
+        if let Some(parent) = self.old_path.parent() {
+            if !fs.check_write_permission(parent).await.unwrap_or(false) {
+                return Err(OperationError::ValidationFailed(
+                    format!("No write permission for directory: {}", parent.display())
+                ));
+            }
+        }
+        
+        // Check new name is valid
+        if self.new_name.is_empty() {
+            return Err(OperationError::ValidationFailed(
+                "New name cannot be empty".to_string()
+            ));
+        }
+        
+        // Check new name doesn't contain invalid characters
+        if self.new_name.contains('/') || self.new_name.contains('\\') {
+            return Err(OperationError::ValidationFailed(
+                "New name cannot contain path separators".to_string()
+            ));
+        }
+        
+        // Check if new path already exists
+        if self.new_path.exists() {
+            return Err(OperationError::ValidationFailed(
+                format!("A file with the new name already exists: {}", self.new_path.display())
+            ));
+        }
+        
+        Ok(())
+    }
+    
+    fn command_kind(&self) -> Option<CommandKind> {
+        Some(CommandKind::Rename(self.clone()))
+    }
+    
+    fn metadata(&self) -> &CommandMetadata {
+        &self.metadata
+    }
+    
+    fn metadata_mut(&mut self) -> &mut CommandMetadata {
+        &mut self.metadata
+    }
+    
+    fn description(&self) -> String {
+        format!("Rename {} to {}", 
+            self.old_path.display(), 
+            self.new_name)
+    }
+    
+    fn kind(&self) -> OperationKind {
+        OperationKind::Rename
+    }
+    
+    fn source_path(&self) -> Option<&Path> {
+        Some(&self.old_path)
+    }
+    
+    fn destination_path(&self) -> Option<&Path> {
+        Some(&self.new_path)
+    }
+}
+
+/// Create directory command
+/// 
+/// Creates a directory (and any missing parents). Undo removes the directory again,
+/// but only if this command created it. Runs serially in parallel batches so the
+/// directory exists before the files copied into it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateDirectoryCommand {
+    pub path: PathBuf,
+    
+    // State for undo
+    existed_before: Option<bool>,
+    
+    metadata: CommandMetadata,
+}
+
+impl CreateDirectoryCommand {
+    /// Create a new create directory command
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            existed_before: None,
+            metadata: CommandMetadata::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl Command for CreateDirectoryCommand {
+    async fn execute(&mut self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        if self.is_executed() {
+            return Err(OperationError::AlreadyExecuted);
+        }
+        
+        // Validate first
+        self.validate(fs.clone()).await?;
+        
+        self.existed_before = Some(self.path.is_dir());
+        if !self.path.is_dir() {
+            fs.create_directory(&self.path).await.map_err(OperationError::FileSystem)?;
+        }
+        
+        // Update metadata
+        self.metadata.status = CommandStatus::Executed;
+        self.metadata.executed_at = Some(SystemTime::now());
+        
+        Ok(())
+    }
+    
+    async fn undo(&mut self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        if !self.can_undo() {
+            return Err(OperationError::NotExecuted);
+        }
+        
+        // Only remove directories this command created; fails if something was added since
+        if let Some(false) = self.existed_before {
+            fs.delete_file(&self.path).await
+                .map_err(|e| OperationError::UndoFailed(format!("Failed to remove created directory: {}", e)))?;
+        }
+        
+        // Update metadata
+        self.metadata.status = CommandStatus::Undone;
+        self.metadata.undone_at = Some(SystemTime::now());
+        
+        Ok(())
+    }
+    
+    async fn validate(&self, _fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        if self.path.exists() && !self.path.is_dir() {
+            return Err(OperationError::ValidationFailed(
+                format!("A file already exists at directory path: {}", self.path.display())
+            ));
+        }
+        
+        Ok(())
+    }
+    
+    fn command_kind(&self) -> Option<CommandKind> {
+        Some(CommandKind::CreateDirectory(self.clone()))
+    }
+    
+    fn metadata(&self) -> &CommandMetadata {
+        &self.metadata
+    }
+    
+    fn metadata_mut(&mut self) -> &mut CommandMetadata {
+        &mut self.metadata
+    }
+    
+    fn description(&self) -> String {
+        format!("Create directory {}", self.path.display())
+    }
+    
+    fn kind(&self) -> OperationKind {
+        OperationKind::CreateDirectory
+    }
+    
+    fn destination_path(&self) -> Option<&Path> {
+        Some(&self.path)
+    }
+    
+    fn must_run_serially(&self) -> bool {
+        true
+    }
+}
+
+/// Entry written by a `CopyDirectoryCommand`, kept for undo
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CopiedEntry {
+    /// Path below both roots; empty for the root directory itself
+    relative: PathBuf,
+    is_dir: bool,
+    /// Whether the copy created the entry rather than merging into an existing one
+    created: bool,
+    /// Previous content of a file that was overwritten
+    backup: Option<Vec<u8>>,
+}
+
+/// `relative` below `root`, without a trailing separator for the root itself
+fn tree_path(root: &Path, relative: &Path) -> PathBuf {
+    if relative.as_os_str().is_empty() {
+        root.to_path_buf()
+    } else {
+        root.join(relative)
+    }
+}
+
+/// Number of entries and total file size of the tree at `root`
+fn tree_size(root: &Path) -> (u64, u64) {
+    walkdir::WalkDir::new(root)
+        .into_iter()
+        .filter_map(Result::ok)
+        .fold((0, 0), |(items, bytes), entry| {
+            let size = entry.metadata().ok().filter(|metadata| metadata.is_file()).map(|metadata| metadata.len());
+            (items + 1, bytes + size.unwrap_or(0))
+        })
+}
+
+/// Checks shared by the directory copy and move commands
+async fn validate_directory_transfer(
+    fs: &Arc<dyn FileSystemService>,
+    source: &Path,
+    destination: &Path,
+    resolution: ConflictResolution,
+) -> OperationResult<()> {
+    if !source.is_dir() {
+        return Err(OperationError::ValidationFailed(
+            format!("Source is not a directory: {}", source.display())
+        ));
+    }
+    
+    if !fs.check_read_permission(source).await.unwrap_or(false) {
+        return Err(OperationError::ValidationFailed(
+            format!("No read permission for source: {}", source.display())
+        ));
+    }
+    
+    if destination.starts_with(source) {
+        return Err(OperationError::ValidationFailed(
+            format!("Cannot put {} inside itself", source.display())
+        ));
+    }
+    
+    if let Some(parent) = destination.parent() {
+        if !parent.exists() {
+            return Err(OperationError::ValidationFailed(
+                format!("Destination directory does not exist: {}", parent.display())
+            ));
+        }
+        
+        if !fs.check_write_permission(parent).await.unwrap_or(false) {
+            return Err(OperationError::ValidationFailed(
+                format!("No write permission for destination directory: {}", parent.display())
+            ));
+        }
+    }
+    
+    if destination.exists() && resolution == ConflictResolution::Ask {
+        return Err(OperationError::DestinationExists(destination.to_path_buf()));
+    }
+    
+    Ok(())
+}
+
+/// Copy directory command
+/// 
+/// Copies a directory tree entry by entry, recording every directory and file
+/// it creates and the content of every file it overwrites. Undo removes what
+/// was created and restores what was overwritten. A failed or cancelled copy
+/// rolls back the entries written so far before returning the error.
+/// 
+/// With `ConflictResolution::Overwrite` an existing destination directory is
+/// merged into, replacing files that exist in both.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CopyDirectoryCommand {
+    pub source: PathBuf,
+    pub destination: PathBuf,
+    #[serde(default)]
+    pub conflict_resolution: ConflictResolution,
+    
+    // State for undo, in the order the entries were written
+    copied: Vec<CopiedEntry>,
+    #[serde(default)]
+    skipped: bool,
+    
+    metadata: CommandMetadata,
+}
+
+impl CopyDirectoryCommand {
+    /// Create a new copy directory command
+    pub fn new(source: PathBuf, destination: PathBuf) -> Self {
+        Self {
+            source,
+            destination,
+            conflict_resolution: ConflictResolution::default(),
+            copied: Vec::new(),
+            skipped: false,
+            metadata: CommandMetadata::default(),
+        }
+    }
+    
+    /// Set what happens when the destination already exists
+    pub fn with_conflict_resolution(mut self, resolution: ConflictResolution) -> Self {
+        self.conflict_resolution = resolution;
+        self
+    }
+    
+    /// Whether the last execution left an existing destination alone
+    pub fn was_skipped(&self) -> bool {
+        self.skipped
+    }
+    
+    async fn copy_tree(&mut self, fs: Arc<dyn FileSystemService>, mut progress: Option<&mut ProgressTracker>) -> OperationResult<()> {
+        if self.is_executed() {
+            return Err(OperationError::AlreadyExecuted);
+        }
+        
+        self.validate(fs).await?;
+        
+        let (skipped, _, _) = resolve_destination(&mut self.destination, self.conflict_resolution).await?;
+        self.skipped = skipped;
+        self.copied.clear();
+        
+        if !skipped {
+            // Pre-order, so every directory is created before its contents
+            for entry in walkdir::WalkDir::new(&self.source).sort_by_file_name() {
+                let result = match entry {
+                    Ok(entry) => self.copy_entry(&entry, progress.as_deref_mut()).await,
+                    Err(e) => Err(OperationError::FileSystem(FileSystemError::from(e))),
+                };
+                if let Err(e) = result {
+                    if let Err(rollback_error) = self.remove_copied().await {
+                        tracing::warn!("Rollback of {} failed: {}", self.description(), rollback_error);
+                    }
+                    self.copied.clear();
+                    return Err(e);
+                }
+            }
+        }
+        
+        // Update metadata
+        self.metadata.status = CommandStatus::Executed;
+        self.metadata.executed_at = Some(SystemTime::now());
+        
+        Ok(())
+    }
+    
+    /// Copy a single entry of the source tree, stopping first if cancelled
+    async fn copy_entry(&mut self, entry: &walkdir::DirEntry, progress: Option<&mut ProgressTracker>) -> OperationResult<()> {
+        if let Some(tracker) = progress.as_deref() {
+            tracker.cancellation_token().throw_if_cancelled()?;
+        }
+        
+        let relative = entry.path().strip_prefix(&self.source)
+            .map_err(|e| OperationError::ExecutionFailed(e.to_string()))?
+            .to_path_buf();
+        let target = tree_path(&self.destination, &relative);
+        let io_error = |e: std::io::Error| OperationError::FileSystem(FileSystemError::from_io_error(e, &target));
+        
+        let mut bytes = 0;
+        if entry.file_type().is_dir() {
+            let created = !target.is_dir();
+            if created {
+                tokio::fs::create_dir(&target).await.map_err(io_error)?;
+            }
+            self.copied.push(CopiedEntry { relative, is_dir: true, created, backup: None });
+        } else {
+            let backup = if target.exists() {
+                Some(tokio::fs::read(&target).await.map_err(io_error)?)
+            } else {
+                None
+            };
+            bytes = tokio::fs::copy(entry.path(), &target).await.map_err(io_error)?;
+            self.copied.push(CopiedEntry { relative, is_dir: false, created: backup.is_none(), backup });
+        }
+        
+        if let Some(tracker) = progress {
+            tracker.increment(bytes)?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Remove created entries and restore overwritten files, newest first
+    async fn remove_copied(&self) -> OperationResult<()> {
+        for entry in self.copied.iter().rev() {
+            let target = tree_path(&self.destination, &entry.relative);
+            let result = match &entry.backup {
+                Some(backup) => tokio::fs::write(&target, backup).await,
+                None if !entry.created => Ok(()),
+                None if entry.is_dir => tokio::fs::remove_dir(&target).await,
+                None => tokio::fs::remove_file(&target).await,
+            };
+            result.map_err(|e| OperationError::UndoFailed(format!("Failed to restore {}: {}", target.display(), e)))?;
+        }
+        
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Command for CopyDirectoryCommand {
+    async fn execute(&mut self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        self.copy_tree(fs, None).await
+    }
+    
+    async fn undo(&mut self, _fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        if !self.can_undo() {
+            return Err(OperationError::NotExecuted);
+        }
+        
+        // Fails if something was added to a created directory since
+        self.remove_copied().await?;
+        
+        // Update metadata
+        self.metadata.status = CommandStatus::Undone;
+        self.metadata.undone_at = Some(SystemTime::now());
+        
+        Ok(())
+    }
+    
+    async fn validate(&self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        validate_directory_transfer(&fs, &self.source, &self.destination, self.conflict_resolution).await
+    }
+    
+    fn command_kind(&self) -> Option<CommandKind> {
+        Some(CommandKind::CopyDirectory(self.clone()))
+    }
+    
+    fn metadata(&self) -> &CommandMetadata {
+        &self.metadata
+    }
+    
+    fn metadata_mut(&mut self) -> &mut CommandMetadata {
+        &mut self.metadata
+    }
+    
+    fn description(&self) -> String {
+        format!("Copy directory {} to {}", 
+            self.source.display(), 
+            self.destination.display())
+    }
+    
+    fn kind(&self) -> OperationKind {
+        OperationKind::Copy
+    }
+    
+    fn source_path(&self) -> Option<&Path> {
+        Some(&self.source)
+    }
+    
+    fn destination_path(&self) -> Option<&Path> {
+        Some(&self.destination)
+    }
+    
+    async fn execute_with_progress(
+        &mut self, 
+        fs: Arc<dyn FileSystemService>, 
+        progress: Option<&mut ProgressTracker>
+    ) -> OperationResult<()> {
+        self.copy_tree(fs, progress).await
+    }
+    
+    async fn estimate_work(&self, _fs: Arc<dyn FileSystemService>) -> OperationResult<(u64, u64)> {
+        let source = self.source.clone();
+        tokio::task::spawn_blocking(move || tree_size(&source))
+            .await
+            .map_err(|e| OperationError::ExecutionFailed(e.to_string()))
     }
     
-    fn description(&self) -> String {
-        format!("Delete {}", self.path.display())
+    fn supports_progress(&self) -> bool {
+        true
     }
 }
 
-/// Rename file command
+/// Move directory command
 /// 
-/// Renames a file. Undo operation renames it back to the original name.
+/// Renames the directory when it can. Across file systems, or when merging
+/// into an existing directory, the tree is copied with `CopyDirectoryCommand`
+/// and the source removed afterwards; if the source can't be removed the
+/// move is rolled back. Undo puts the tree back where it was.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct RenameCommand {
-    pub old_path: PathBuf,
-    pub new_name: String,
-    
-    // Computed new path
-    pub new_path: PathBuf,
+pub struct MoveDirectoryCommand {
+    pub source: PathBuf,
+    pub destination: PathBuf,
+    #[serde(default)]
+    pub conflict_resolution: ConflictResolution,
     
-    // State for undo
-    new_path_existed_before: Option<bool>,
-    original_new_path_backup: Option<Vec<u8>>,
+    // State for undo; set when the tree was copied rather than renamed
+    copy: Option<CopyDirectoryCommand>,
+    #[serde(default)]
+    skipped: bool,
     
     metadata: CommandMetadata,
 }
 
-impl RenameCommand {
-    /// Create a new rename command
-    pub fn new(old_path: PathBuf, new_name: String) -> OperationResult<Self> {
-        let new_path = if let Some(parent) = old_path.parent() {
-            parent.join(&new_name)
-        } else {
-            PathBuf::from(&new_name)
-        };
-        
-        Ok(Self {
-            old_path,
-            new_name,
-            new_path,
-            new_path_existed_before: None,
-            original_new_path_backup: None,
+impl MoveDirectoryCommand {
+    /// Create a new move directory command
+    pub fn new(source: PathBuf, destination: PathBuf) -> Self {
+        Self {
+            source,
+            destination,
+            conflict_resolution: ConflictResolution::default(),
+            copy: None,
+            skipped: false,
             metadata: CommandMetadata::default(),
-        })
+        }
     }
-}
-
-#[async_trait]
-impl Command for RenameCommand {
-    async fn execute(&mut self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+    
+    /// Set what happens when the destination already exists
+    pub fn with_conflict_resolution(mut self, resolution: ConflictResolution) -> Self {
+        self.conflict_resolution = resolution;
+        self
+    }
+    
+    /// Whether the last execution left the source and the existing destination alone
+    pub fn was_skipped(&self) -> bool {
+        self.skipped
+    }
+    
+    async fn move_tree(&mut self, fs: Arc<dyn FileSystemService>, progress: Option<&mut ProgressTracker>) -> OperationResult<()> {
         if self.is_executed() {
             return Err(OperationError::AlreadyExecuted);
         }
         
-        // Validate first
         self.validate(fs.clone()).await?;
         
-        // Check if new path exists and backup if needed
-        self.new_path_existed_before = Some(self.new_path.exists());
-        
-        if *self.new_path_existed_before.as_ref().unwrap() {
-            if let Ok(content) = tokio::fs::read(&self.new_path).await {
-                self.original_new_path_backup = Some(content);
+        let (skipped, existed_before, _) = resolve_destination(&mut self.destination, self.conflict_resolution).await?;
+        self.skipped = skipped;
+        self.copy = None;
+        
+        if skipped {
+            tracing::info!("Skipped moving {}: {} already exists", self.source.display(), self.destination.display());
+        } else if !existed_before && tokio::fs::rename(&self.source, &self.destination).await.is_ok() {
+            if let Some(tracker) = progress {
+                let (items, bytes) = self.estimate_work(fs).await?;
+                let current = tracker.progress().current + items;
+                let bytes_processed = tracker.progress().bytes_processed + bytes;
+                // The tree already moved; a cancellation requested now is observed by the next command
+                let _ = tracker.update(current, bytes_processed, self.description());
+            }
+        } else {
+            let mut copy = CopyDirectoryCommand::new(self.source.clone(), self.destination.clone())
+                .with_conflict_resolution(ConflictResolution::Overwrite);
+            copy.execute_with_progress(fs.clone(), progress).await?;
+            
+            if let Err(e) = remove_moved_source(&self.source, &copy).await {
+                if let Err(rollback_error) = copy.undo(fs).await {
+                    tracing::warn!("Rollback of {} failed: {}", self.description(), rollback_error);
+                }
+                return Err(e);
             }
+            self.copy = Some(copy);
         }
         
-        // Execute the rename using file system service
-        fs.rename_file(&self.old_path, &self.new_name).await
-            .map_err(OperationError::FileSystem)?;
-        
         // Update metadata
         self.metadata.status = CommandStatus::Executed;
         self.metadata.executed_at = Some(SystemTime::now());
         
         Ok(())
     }
+}
+
+/// Remove the source entries a move copied, restoring them if one can't be removed
+async fn remove_moved_source(source: &Path, copy: &CopyDirectoryCommand) -> OperationResult<()> {
+    for (index, entry) in copy.copied.iter().enumerate().rev() {
+        let path = tree_path(source, &entry.relative);
+        let result = if entry.is_dir {
+            tokio::fs::remove_dir(&path).await
+        } else {
+            tokio::fs::remove_file(&path).await
+        };
+        if let Err(e) = result {
+            restore_moved_source(source, copy, &copy.copied[index + 1..]).await?;
+            return Err(OperationError::FileSystem(FileSystemError::from_io_error(e, &path)));
+        }
+    }
+    
+    Ok(())
+}
+
+/// Copy `entries` of a moved tree back from the destination to the source
+async fn restore_moved_source(source: &Path, copy: &CopyDirectoryCommand, entries: &[CopiedEntry]) -> OperationResult<()> {
+    for entry in entries {
+        let path = tree_path(source, &entry.relative);
+        let result = if entry.is_dir {
+            tokio::fs::create_dir_all(&path).await
+        } else {
+            tokio::fs::copy(tree_path(&copy.destination, &entry.relative), &path).await.map(|_| ())
+        };
+        result.map_err(|e| OperationError::UndoFailed(format!("Failed to restore {}: {}", path.display(), e)))?;
+    }
+    
+    Ok(())
+}
+
+#[async_trait]
+impl Command for MoveDirectoryCommand {
+    async fn execute(&mut self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        self.move_tree(fs, None).await
+    }
     
     async fn undo(&mut self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
         if !self.can_undo() {
             return Err(OperationError::NotExecuted);
         }
         
-        // Get the original name
-        let original_name = self.old_path.file_name()
-            .and_then(|n| n.to_str())
-            .ok_or_else(|| OperationError::UndoFailed("Cannot get original filename".to_string()))?;
-        
-        // Rename back to original
-        fs.rename_file(&self.new_path, original_name).await
-            .map_err(|e| OperationError::UndoFailed(format!("Failed to rename back: {}", e)))?;
-        
-        // Restore original file at new path if it existed
-        if let Some(backup) = &self.original_new_path_backup {
-            tokio::fs::write(&self.new_path, backup).await
-                .map_err(|e| OperationError::UndoFailed(format!("Failed to restore original file: {}", e)))?;
+        if let Some(copy) = &mut self.copy {
+            restore_moved_source(&self.source, copy, &copy.copied).await?;
+            copy.undo(fs).await?;
+        } else if !self.skipped {
+            tokio::fs::rename(&self.destination, &self.source).await
+                .map_err(|e| OperationError::UndoFailed(format!("Failed to move directory back: {}", e)))?;
         }
         
         // Update metadata
@@ -2578,46 +5108,23 @@ impl Command for RenameCommand {
     }
     
     async fn validate(&self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
-        // Check old path exists
-        if !self.old_path.exists() {
-            return Err(OperationError::ValidationFailed(
-                format!("Source file does not exist: {}", self.old_path.display())
-            ));
-        }
+        validate_directory_transfer(&fs, &self.source, &self.destination, self.conflict_resolution).await?;
         
-        // Check parent directory write permission
-        if let Some(parent) = self.old_path.parent() {
-            if !fs.check_write_permission(parent).await.unwrap_or(false) {
+        if let Some(source_parent) = self.source.parent() {
+            if !fs.check_write_permission(source_parent).await.unwrap_or(false) {
                 return Err(OperationError::ValidationFailed(
-                    format!("No write permission for directory: {}", parent.display())
+                    format!("No write permission for source directory: {}", source_parent.display())
                 ));
             }
         }
         
-        // Check new name is valid
-        if self.new_name.is_empty() {
-            return Err(OperationError::ValidationFailed(
-                "New name cannot be empty".to_string()
-            ));
-        }
-        
-        // Check new name doesn't contain invalid characters
-        if self.new_name.contains('/') || self.new_name.contains('\\') {
-            return Err(OperationError::ValidationFailed(
-                "New name cannot contain path separators".to_string()
-            ));
-        }
-        
-        // Check if new path already exists
-        if self.new_path.exists() {
-            return Err(OperationError::ValidationFailed(
-                format!("A file with the new name already exists: {}", self.new_path.display())
-            ));
-        }
-        
         Ok(())
     }
     
+    fn command_kind(&self) -> Option<CommandKind> {
+        Some(CommandKind::MoveDirectory(self.clone()))
+    }
+    
     fn metadata(&self) -> &CommandMetadata {
         &self.metadata
     }
@@ -2627,10 +5134,85 @@ impl Command for RenameCommand {
     }
     
     fn description(&self) -> String {
-        format!("Rename {} to {}", 
-            self.old_path.display(), 
-            self.new_name)
+        format!("Move directory {} to {}", 
+            self.source.display(), 
+            self.destination.display())
+    }
+    
+    fn kind(&self) -> OperationKind {
+        OperationKind::Move
+    }
+    
+    fn source_path(&self) -> Option<&Path> {
+        Some(&self.source)
+    }
+    
+    fn destination_path(&self) -> Option<&Path> {
+        Some(&self.destination)
+    }
+    
+    async fn execute_with_progress(
+        &mut self, 
+        fs: Arc<dyn FileSystemService>, 
+        progress: Option<&mut ProgressTracker>
+    ) -> OperationResult<()> {
+        self.move_tree(fs, progress).await
+    }
+    
+    async fn estimate_work(&self, _fs: Arc<dyn FileSystemService>) -> OperationResult<(u64, u64)> {
+        // Counted from the destination once the tree has moved there
+        let root = if self.source.exists() { self.source.clone() } else { self.destination.clone() };
+        tokio::task::spawn_blocking(move || tree_size(&root))
+            .await
+            .map_err(|e| OperationError::ExecutionFailed(e.to_string()))
+    }
+    
+    fn supports_progress(&self) -> bool {
+        true
+    }
+}
+
+/// Default worker count for parallel copies, based on available CPUs
+pub fn default_copy_parallelism() -> usize {
+    num_cpus::get().clamp(2, 16)
+}
+
+/// Build a batch that copies a directory tree file by file
+/// 
+/// Every directory is queued (parents first) as a serial `CreateDirectoryCommand`
+/// ahead of all file copies, so the batch can be executed with any parallelism
+/// and rolls back cleanly on failure.
+pub fn directory_copy_batch(source: &Path, destination: &Path) -> OperationResult<BatchOperation> {
+    if !source.is_dir() {
+        return Err(OperationError::ValidationFailed(
+            format!("Source is not a directory: {}", source.display())
+        ));
+    }
+    
+    let mut directories = Vec::new();
+    let mut files = Vec::new();
+    
+    for entry in walkdir::WalkDir::new(source).sort_by_file_name() {
+        let entry = entry.map_err(|e| OperationError::FileSystem(FileSystemError::from(e)))?;
+        let relative = entry.path().strip_prefix(source)
+            .map_err(|e| OperationError::ExecutionFailed(e.to_string()))?;
+        let target = destination.join(relative);
+        
+        if entry.file_type().is_dir() {
+            directories.push(Box::new(CreateDirectoryCommand::new(target)) as Box<dyn Command>);
+        } else {
+            files.push(Box::new(CopyCommand::new(entry.path().to_path_buf(), target)) as Box<dyn Command>);
+        }
     }
+    
+    let mut batch = BatchOperation::new(
+        format!("Copy {}", source.display()),
+        format!("Copy {} to {}", source.display(), destination.display()),
+    );
+    batch.add_commands(directories);
+    batch.add_commands(files);
+    
+    Ok(batch)
 }
 
 #[cfg(test)]
@@ -2693,6 +5275,98 @@ mod tests {
         assert_eq!(tokio::fs::read_to_string(&source_path).await.unwrap(), "test content");
     }
 
+    async fn read(path: &Path) -> String {
+        tokio::fs::read_to_string(path).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_copy_command_conflict_resolutions() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("source.txt");
+        let existing = temp_dir.path().join("foo.txt");
+        let renamed = temp_dir.path().join("foo copy 1.txt");
+        tokio::fs::write(&source, "new").await.unwrap();
+        tokio::fs::write(&existing, "old").await.unwrap();
+        let fs = create_test_fs();
+        
+        // Ask stops before touching anything so the caller can prompt
+        let mut ask = CopyCommand::new(source.clone(), existing.clone());
+        let error = ask.execute(fs.clone()).await.unwrap_err();
+        assert!(matches!(&error, OperationError::DestinationExists(path) if *path == existing), "{:?}", error);
+        assert!(!ask.is_executed());
+        assert_eq!(read(&existing).await, "old");
+        
+        let mut skip = CopyCommand::new(source.clone(), existing.clone())
+            .with_conflict_resolution(ConflictResolution::Skip);
+        skip.execute(fs.clone()).await.unwrap();
+        assert!(skip.was_skipped());
+        assert_eq!(read(&existing).await, "old");
+        skip.undo(fs.clone()).await.unwrap();
+        assert_eq!(read(&existing).await, "old");
+        
+        let mut rename = CopyCommand::new(source.clone(), existing.clone())
+            .with_conflict_resolution(ConflictResolution::Rename);
+        rename.execute(fs.clone()).await.unwrap();
+        assert_eq!(rename.destination, renamed);
+        assert_eq!(read(&renamed).await, "new");
+        assert_eq!(read(&existing).await, "old");
+        rename.undo(fs.clone()).await.unwrap();
+        assert!(!renamed.exists());
+        assert_eq!(read(&existing).await, "old");
+        
+        let mut overwrite = CopyCommand::new(source.clone(), existing.clone())
+            .with_conflict_resolution(ConflictResolution::Overwrite);
+        overwrite.execute(fs.clone()).await.unwrap();
+        assert_eq!(read(&existing).await, "new");
+        overwrite.undo(fs).await.unwrap();
+        assert_eq!(read(&existing).await, "old");
+        assert_eq!(read(&source).await, "new");
+    }
+
+    #[tokio::test]
+    async fn test_move_command_conflict_resolutions() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("incoming").join("foo.txt");
+        let existing = temp_dir.path().join("foo.txt");
+        let renamed = temp_dir.path().join("foo copy 1.txt");
+        tokio::fs::create_dir(temp_dir.path().join("incoming")).await.unwrap();
+        tokio::fs::write(&source, "new").await.unwrap();
+        tokio::fs::write(&existing, "old").await.unwrap();
+        let fs = create_test_fs();
+        
+        let mut ask = MoveCommand::new(source.clone(), existing.clone());
+        assert!(matches!(ask.execute(fs.clone()).await, Err(OperationError::DestinationExists(_))));
+        assert!(source.exists());
+        
+        // Undoing a skipped move must not pull the existing file over the source
+        let mut skip = MoveCommand::new(source.clone(), existing.clone())
+            .with_conflict_resolution(ConflictResolution::Skip);
+        skip.execute(fs.clone()).await.unwrap();
+        assert!(skip.was_skipped());
+        skip.undo(fs.clone()).await.unwrap();
+        assert_eq!(read(&source).await, "new");
+        assert_eq!(read(&existing).await, "old");
+        
+        let mut rename = MoveCommand::new(source.clone(), existing.clone())
+            .with_conflict_resolution(ConflictResolution::Rename);
+        rename.execute(fs.clone()).await.unwrap();
+        assert!(!source.exists());
+        assert_eq!(read(&renamed).await, "new");
+        rename.undo(fs.clone()).await.unwrap();
+        assert!(!renamed.exists());
+        assert_eq!(read(&source).await, "new");
+        assert_eq!(read(&existing).await, "old");
+        
+        let mut overwrite = MoveCommand::new(source.clone(), existing.clone())
+            .with_conflict_resolution(ConflictResolution::Overwrite);
+        overwrite.execute(fs.clone()).await.unwrap();
+        assert!(!source.exists());
+        assert_eq!(read(&existing).await, "new");
+        overwrite.undo(fs).await.unwrap();
+        assert_eq!(read(&source).await, "new");
+        assert_eq!(read(&existing).await, "old");
+    }
+
     #[tokio::test]
     async fn test_delete_command_basic() {
         let temp_dir = TempDir::new().unwrap();
@@ -2990,6 +5664,140 @@ mod tests {
         assert_eq!(batch.progress.status, BatchStatus::Completed);
     }
 
+    /// Cancels its token when run, standing in for the user pressing Cancel
+    #[derive(Debug)]
+    struct CancelCommand {
+        token: CancellationToken,
+        metadata: CommandMetadata,
+    }
+
+    #[async_trait]
+    impl Command for CancelCommand {
+        async fn execute(&mut self, _fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+            self.token.cancel();
+            self.metadata.status = CommandStatus::Executed;
+            Ok(())
+        }
+
+        async fn undo(&mut self, _fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+            self.metadata.status = CommandStatus::Undone;
+            Ok(())
+        }
+
+        async fn validate(&self, _fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+            Ok(())
+        }
+
+        fn metadata(&self) -> &CommandMetadata {
+            &self.metadata
+        }
+
+        fn metadata_mut(&mut self) -> &mut CommandMetadata {
+            &mut self.metadata
+        }
+
+        fn description(&self) -> String {
+            "Cancel".to_string()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batch_stop_token_keeps_finished_commands() {
+        let temp_dir = TempDir::new().unwrap();
+        let first = temp_dir.path().join("first");
+        let second = temp_dir.path().join("second");
+        let token = CancellationToken::new();
+
+        let fs = create_test_fs();
+        let mut batch = BatchOperation::new("Stop".to_string(), "Test stop token".to_string())
+            .with_partial_failure(true)
+            .with_stop_token(token.clone());
+        batch.add_command(Box::new(CreateDirectoryCommand::new(first.clone())));
+        batch.add_command(Box::new(CancelCommand { token: token.clone(), metadata: CommandMetadata::default() }));
+        batch.add_command(Box::new(CreateDirectoryCommand::new(second.clone())));
+
+        // Stopping is not a failure: what ran stays done and can be undone
+        batch.execute(fs.clone()).await.unwrap();
+        assert!(batch.was_stopped());
+        assert!(first.exists());
+        assert!(!second.exists());
+        assert_eq!(token.progress_count(), 2);
+        assert!(!batch.commands[2].is_executed());
+
+        batch.undo(fs).await.unwrap();
+        assert!(!first.exists());
+    }
+
+    #[tokio::test]
+    async fn test_batch_trash_undoes_as_one_history_entry() {
+        let temp_dir = TempDir::new().unwrap();
+        let trash_dir = temp_dir.path().join(".trash");
+        let paths: Vec<PathBuf> = ["a.txt", "b.txt", "c.txt"]
+            .iter()
+            .map(|name| temp_dir.path().join(name))
+            .collect();
+        for path in &paths {
+            tokio::fs::write(path, path.display().to_string()).await.unwrap();
+        }
+        let missing = temp_dir.path().join("missing.txt");
+        
+        let fs = create_test_fs();
+        let mut history = OperationHistory::new(fs.clone());
+        let mut batch = BatchOperation::new("Delete".to_string(), "Delete 3 items".to_string())
+            .with_partial_failure(true);
+        for path in paths.iter().chain(std::iter::once(&missing)) {
+            batch.add_command(Box::new(TrashCommand::new(path.clone()).with_trash_dir(trash_dir.clone())));
+        }
+        
+        // The missing file fails without stopping the others
+        batch.execute(fs.clone()).await.unwrap();
+        assert!(paths.iter().all(|path| !path.exists()));
+        let failed: Vec<_> = batch.failed_commands().map(|(command, _)| command.source_path().unwrap().to_path_buf()).collect();
+        assert_eq!(failed, vec![missing]);
+        assert_eq!(batch.kind(), OperationKind::Delete);
+        
+        history.add_executed_command(Box::new(batch)).await.unwrap();
+        assert_eq!(history.undo_count(), 1);
+        
+        // One undo restores all three
+        history.undo().await.unwrap();
+        for path in &paths {
+            assert_eq!(tokio::fs::read_to_string(path).await.unwrap(), path.display().to_string());
+        }
+        assert!(!history.can_undo());
+        
+        history.redo().await.unwrap();
+        assert!(paths.iter().all(|path| !path.exists()));
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_trash_leaves_item_and_permanent_delete_removes_it() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("photo.jpg");
+        tokio::fs::write(&file, b"image data").await.unwrap();
+        let folder = temp_dir.path().join("album");
+        tokio::fs::create_dir_all(folder.join("nested")).await.unwrap();
+        tokio::fs::write(folder.join("nested/a.jpg"), b"a").await.unwrap();
+        // A file where the trash folder should be makes the trash unusable
+        let blocked_trash = temp_dir.path().join("not-a-folder");
+        tokio::fs::write(&blocked_trash, b"").await.unwrap();
+        
+        let fs = create_test_fs();
+        let mut trash = TrashCommand::new(file.clone()).with_trash_dir(blocked_trash.join("trash"));
+        let error = trash.execute(fs.clone()).await.unwrap_err();
+        assert!(matches!(error, OperationError::TrashUnavailable(_)), "{:?}", error);
+        assert_eq!(error.classify().1, RecoveryStrategy::ManualIntervention);
+        assert!(file.exists());
+        
+        for path in [&file, &folder] {
+            let mut delete = PermanentDeleteCommand::new(path.clone());
+            delete.execute(fs.clone()).await.unwrap();
+            assert!(!path.exists());
+            assert!(!delete.can_undo());
+            assert!(matches!(delete.undo(fs.clone()).await, Err(OperationError::UndoFailed(_))));
+        }
+    }
+
     #[test]
     fn test_batch_cancellation_token() {
         let batch = BatchOperation::new("Cancel Test".to_string(), "Test cancellation".to_string());
@@ -3212,11 +6020,81 @@ mod tests {
             copy_cmd.execute(fs.clone()).await.unwrap();
             history.add_executed_command(Box::new(copy_cmd)).await.unwrap();
         }
-        
-        // Should be limited to 3 operations (90% of 3 = 2.7, so we keep 3)
-        // Actually, cleanup triggers when we exceed the limit, so we should have 3 or fewer
-        assert!(history.undo_count() <= 3);
-        assert!(history.undo_count() >= 2); // But we should have at least 2 (90% cleanup target)
+        
+        // Should be limited to 3 operations (90% of 3 = 2.7, so we keep 3)
+        // Actually, cleanup triggers when we exceed the limit, so we should have 3 or fewer
+        assert!(history.undo_count() <= 3);
+        assert!(history.undo_count() >= 2); // But we should have at least 2 (90% cleanup target)
+    }
+
+    #[test]
+    fn test_retry_settings_drive_delays_and_clamp_to_max_delay() {
+        let settings = RetrySettings {
+            max_attempts: 5,
+            initial_delay_ms: 200,
+            max_delay_ms: 1_000,
+            backoff_multiplier: 3.0,
+            jitter: false,
+        };
+        settings.validate().unwrap();
+        let config = settings.effective_config();
+        assert_eq!(config.max_attempts, 5);
+        assert_eq!(config.delay_for_attempt(0), Duration::from_millis(200));
+        assert_eq!(config.delay_for_attempt(1), Duration::from_millis(600));
+        assert_eq!(config.delay_for_attempt(2), Duration::from_millis(1_000));
+        assert_eq!(config.delay_for_attempt(30), Duration::from_millis(1_000));
+
+        // Jitter never pushes a delay past the maximum
+        let jittered = RetrySettings { jitter: true, ..settings.clone() }.effective_config();
+        for attempt in 0..20 {
+            assert!(jittered.delay_for_attempt(attempt) <= Duration::from_millis(1_000));
+        }
+
+        let batch = BatchOperation::new("Batch".to_string(), "Retries".to_string()).with_retry_policy(config);
+        assert_eq!(batch.max_retries, 5);
+        assert_eq!(batch.retry_config.delay_for_attempt(1), Duration::from_millis(600));
+    }
+
+    #[test]
+    fn test_retry_settings_reject_out_of_range_values() {
+        let valid = RetrySettings::default();
+        for invalid in [
+            RetrySettings { backoff_multiplier: 0.5, ..valid.clone() },
+            RetrySettings { backoff_multiplier: f64::NAN, ..valid.clone() },
+            RetrySettings { max_attempts: 1_000, ..valid.clone() },
+            RetrySettings { initial_delay_ms: 0, ..valid.clone() },
+            RetrySettings { initial_delay_ms: 5_000, max_delay_ms: 1_000, ..valid.clone() },
+        ] {
+            assert!(matches!(invalid.validate(), Err(OperationError::ValidationFailed(_))), "{:?}", invalid);
+            assert_eq!(invalid.effective_config().max_attempts, RetryConfig::default().max_attempts);
+        }
+    }
+
+    #[test]
+    fn test_error_statistics_break_down_recent_errors_by_severity() {
+        let mut manager = ErrorRecoveryManager::new();
+        let before = SystemTime::now() - Duration::from_secs(1);
+        manager.record_error(OperationError::Network("offline".to_string()));
+        manager.record_error(OperationError::PermissionDenied("/photos/a.jpg".to_string()));
+        manager.record_error(OperationError::ValidationFailed("bad name".to_string()));
+        manager.record_error(OperationError::PermissionDenied("/photos/b.jpg".to_string()));
+
+        let statistics = manager.error_statistics(before);
+        assert_eq!(statistics.total_errors, 4);
+        assert_eq!(
+            statistics.severity_breakdown(),
+            vec![(ErrorSeverity::High, 2), (ErrorSeverity::Medium, 1), (ErrorSeverity::Low, 1)]
+        );
+        assert_eq!(statistics.highest_severity(), Some(ErrorSeverity::High));
+
+        let recent = manager.recent_errors(before);
+        assert!(matches!(&recent[0].1, OperationError::PermissionDenied(path) if path == "/photos/b.jpg"));
+        assert_eq!(recent.len(), 4);
+
+        // Nothing falls inside a window that starts later
+        let later = manager.error_statistics(SystemTime::now() + Duration::from_secs(1));
+        assert!(later.severity_breakdown().is_empty());
+        assert_eq!(later.highest_severity(), None);
     }
 
     #[tokio::test]
@@ -3339,12 +6217,139 @@ mod tests {
         let mut new_history = OperationHistory::new(fs.clone());
         new_history.load_from_file(&history_file).await.unwrap();
         
-        // Should have the history entry but no actual commands (since they can't be serialized)
+        // The copy comes back undoable; redo is never kept
         assert_eq!(new_history.history_entries.len(), 1);
-        assert_eq!(new_history.undo_count(), 0); // Commands not restored
+        assert_eq!(new_history.undo_count(), 1);
         assert_eq!(new_history.redo_count(), 0);
     }
 
+    #[tokio::test]
+    async fn test_history_round_trip_restores_undo() {
+        let temp_dir = TempDir::new().unwrap();
+        let history_file = temp_dir.path().join("history.json");
+        let source_path = temp_dir.path().join("source.txt");
+        let copy_path = temp_dir.path().join("copy.txt");
+        let renamed_path = temp_dir.path().join("renamed.txt");
+        tokio::fs::write(&source_path, "test").await.unwrap();
+        
+        let fs = create_test_fs();
+        let mut history = OperationHistory::new(fs.clone());
+        let mut copy = CopyCommand::new(source_path.clone(), copy_path.clone());
+        copy.execute(fs.clone()).await.unwrap();
+        history.add_executed_command(Box::new(copy)).await.unwrap();
+        let mut rename = RenameCommand::new(copy_path.clone(), "renamed.txt".to_string()).unwrap();
+        rename.execute(fs.clone()).await.unwrap();
+        history.add_executed_command(Box::new(rename)).await.unwrap();
+        history.save_to_file(&history_file).await.unwrap();
+        
+        let mut restored = OperationHistory::new(fs.clone());
+        restored.load_from_file(&history_file).await.unwrap();
+        assert_eq!(restored.undo_count(), 2);
+        assert!(restored.next_undo_description().unwrap().contains("renamed.txt"));
+        
+        restored.undo().await.unwrap();
+        assert!(copy_path.exists() && !renamed_path.exists());
+        restored.undo().await.unwrap();
+        assert!(!copy_path.exists());
+        assert!(source_path.exists());
+        
+        // Redo still works on restored commands
+        restored.redo().await.unwrap();
+        assert!(copy_path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_history_round_trip_restores_batches() {
+        let temp_dir = TempDir::new().unwrap();
+        let history_file = temp_dir.path().join("history.json");
+        let folders = [temp_dir.path().join("a"), temp_dir.path().join("b")];
+        
+        let fs = create_test_fs();
+        let mut history = OperationHistory::new(fs.clone());
+        let mut batch = BatchOperation::new("Create".to_string(), "Create folders".to_string());
+        for folder in &folders {
+            batch.add_command(Box::new(CreateDirectoryCommand::new(folder.clone())));
+        }
+        batch.execute(fs.clone()).await.unwrap();
+        history.add_executed_command(Box::new(batch)).await.unwrap();
+        history.save_to_file(&history_file).await.unwrap();
+        
+        let mut restored = OperationHistory::new(fs.clone());
+        restored.load_from_file(&history_file).await.unwrap();
+        assert_eq!(restored.next_undo_description().as_deref(), Some("Create folders"));
+        restored.undo().await.unwrap();
+        assert!(folders.iter().all(|folder| !folder.exists()));
+    }
+
+    #[tokio::test]
+    async fn test_history_load_skips_commands_before_an_unrestorable_one() {
+        let temp_dir = TempDir::new().unwrap();
+        let history_file = temp_dir.path().join("history.json");
+        let first = temp_dir.path().join("first");
+        let gone = temp_dir.path().join("gone.txt");
+        let last = temp_dir.path().join("last");
+        tokio::fs::write(&gone, "data").await.unwrap();
+        
+        let fs = create_test_fs();
+        let mut history = OperationHistory::new(fs.clone());
+        let mut create = CreateDirectoryCommand::new(first);
+        create.execute(fs.clone()).await.unwrap();
+        history.add_executed_command(Box::new(create)).await.unwrap();
+        // Permanent deletes have no saved form
+        let mut delete = PermanentDeleteCommand::new(gone);
+        delete.execute(fs.clone()).await.unwrap();
+        history.add_executed_command(Box::new(delete)).await.unwrap();
+        let mut create = CreateDirectoryCommand::new(last.clone());
+        create.execute(fs.clone()).await.unwrap();
+        history.add_executed_command(Box::new(create)).await.unwrap();
+        history.save_to_file(&history_file).await.unwrap();
+        
+        let mut restored = OperationHistory::new(fs.clone());
+        restored.load_from_file(&history_file).await.unwrap();
+        assert_eq!(restored.undo_count(), 1);
+        assert!(restored.next_undo_description().unwrap().contains("last"));
+        assert_eq!(restored.history_entries.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_history_file_leaves_out_file_backups() {
+        let temp_dir = TempDir::new().unwrap();
+        let history_file = temp_dir.path().join("history").join("history.json");
+        let doomed = temp_dir.path().join("doomed.txt");
+        let source_path = temp_dir.path().join("source.txt");
+        let dest_path = temp_dir.path().join("dest.txt");
+        let created = temp_dir.path().join("created");
+        tokio::fs::write(&doomed, "deleted").await.unwrap();
+        tokio::fs::write(&source_path, "new").await.unwrap();
+        tokio::fs::write(&dest_path, "overwritten").await.unwrap();
+        
+        let fs = create_test_fs();
+        let mut history = OperationHistory::new(fs.clone());
+        let mut create = CreateDirectoryCommand::new(created.clone());
+        create.execute(fs.clone()).await.unwrap();
+        history.add_executed_command(Box::new(create)).await.unwrap();
+        let mut delete = DeleteCommand::new(doomed);
+        delete.execute(fs.clone()).await.unwrap();
+        assert!(!serde_json::to_string(&delete).unwrap().contains("file_backup"));
+        history.add_executed_command(Box::new(delete)).await.unwrap();
+        let mut overwrite = CopyCommand::new(source_path, dest_path)
+            .with_conflict_resolution(ConflictResolution::Overwrite);
+        overwrite.execute(fs.clone()).await.unwrap();
+        assert!(!serde_json::to_string(&overwrite).unwrap().contains("original_destination_backup"));
+        history.add_executed_command(Box::new(overwrite)).await.unwrap();
+        history.save_to_file_blocking(&history_file).unwrap();
+        
+        let saved = std::fs::read_to_string(&history_file).unwrap();
+        assert!(!saved.contains("file_backup") && !saved.contains("original_destination_backup"));
+        
+        // Both stay undoable in this session, but not after a restart
+        assert_eq!(history.undo_count(), 3);
+        let mut restored = OperationHistory::new(fs.clone());
+        restored.load_from_file_blocking(&history_file).unwrap();
+        assert_eq!(restored.undo_count(), 0);
+        assert_eq!(restored.history_entries.len(), 3);
+    }
+
     #[tokio::test]
     async fn test_operation_history_persistence_disabled() {
         let temp_dir = TempDir::new().unwrap();
@@ -3577,11 +6582,13 @@ mod tests {
 
     #[tokio::test]
     async fn test_format_functions() {
-        // Test format_bytes_per_second
-        assert_eq!(format_bytes_per_second(512), "512.0 B/s");
-        assert_eq!(format_bytes_per_second(1536), "1.5 KB/s");
-        assert_eq!(format_bytes_per_second(2097152), "2.0 MB/s");
-        assert_eq!(format_bytes_per_second(3221225472), "3.0 GB/s");
+        // Test rate formatting with the default (binary) units
+        use crate::utils::format::{format_rate_with, FormatSettings};
+        let settings = FormatSettings::default();
+        assert_eq!(format_rate_with(512, &settings), "512 B/s");
+        assert_eq!(format_rate_with(1536, &settings), "1.5 KiB/s");
+        assert_eq!(format_rate_with(2097152, &settings), "2.0 MiB/s");
+        assert_eq!(format_rate_with(3221225472, &settings), "3.0 GiB/s");
         
         // Test format_duration
         assert_eq!(format_duration(Duration::from_secs(30)), "30s");
@@ -3633,13 +6640,13 @@ mod tests {
         let dest_file = temp_path.join("test_copy.txt");
         let copy_cmd = CopyCommand::new(test_file, dest_file);
         
-        // Test work estimation (default implementation returns (1, 0))
+        // Copy reports the source size as its work estimate
         let (items, bytes) = copy_cmd.estimate_work(fs_service).await.unwrap();
         assert_eq!(items, 1);
-        assert_eq!(bytes, 0);
+        assert_eq!(bytes, 12);
         
-        // Test supports_progress (default is false)
-        assert!(!copy_cmd.supports_progress());
+        // Copy supports byte-level progress tracking
+        assert!(copy_cmd.supports_progress());
     }
 
     #[tokio::test]
@@ -3675,4 +6682,640 @@ mod tests {
         assert_eq!(progress.percentage(), 150.0);
         assert_eq!(progress.bytes_percentage(), 150.0);
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_chunked_copy_reports_byte_progress() {
+        use std::sync::Mutex;
+        
+        let temp_dir = TempDir::new().unwrap();
+        let source_path = temp_dir.path().join("large.bin");
+        let dest_path = temp_dir.path().join("large_copy.bin");
+        let content: Vec<u8> = (0..4 * 1024 * 1024u32).map(|i| (i % 251) as u8).collect();
+        tokio::fs::write(&source_path, &content).await.unwrap();
+        
+        let updates = Arc::new(Mutex::new(Vec::new()));
+        let updates_clone = updates.clone();
+        let callback: ProgressCallback = Arc::new(move |progress| {
+            updates_clone.lock().unwrap().push(progress.bytes_processed);
+        });
+        
+        let fs = create_test_fs();
+        let mut tracker = ProgressTracker::new(1, content.len() as u64, "Chunked copy".to_string())
+            .with_callback(callback);
+        let mut command = CopyCommand::new(source_path, dest_path.clone())
+            .with_chunked_copy_threshold(0)
+            .with_chunk_size(1024 * 1024);
+        
+        assert!(command.execute_with_progress(fs.clone(), Some(&mut tracker)).await.is_ok());
+        assert!(command.is_executed());
+        assert_eq!(tokio::fs::read(&dest_path).await.unwrap(), content);
+        assert!(!partial_copy_path(&dest_path).exists());
+        
+        // One update per chunk plus the completion update
+        let updates = updates.lock().unwrap();
+        assert_eq!(updates.len(), 5);
+        assert_eq!(updates[0], 1024 * 1024);
+        assert_eq!(*updates.last().unwrap(), content.len() as u64);
+        assert_eq!(tracker.progress().current, 1);
+        
+        // Undo removes the copied file
+        assert!(command.undo(fs).await.is_ok());
+        assert!(!dest_path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_chunked_copy_cancellation_leaves_no_partial() {
+        let temp_dir = TempDir::new().unwrap();
+        let source_path = temp_dir.path().join("large.bin");
+        let dest_path = temp_dir.path().join("large_copy.bin");
+        tokio::fs::write(&source_path, vec![7u8; 8 * 1024 * 1024]).await.unwrap();
+        
+        // Cancel once the first chunk has been reported
+        let cancellation_token = CancellationToken::new();
+        let cancel_clone = cancellation_token.clone();
+        let callback: ProgressCallback = Arc::new(move |progress| {
+            if progress.bytes_processed > 0 {
+                cancel_clone.cancel();
+            }
+        });
+        
+        let fs = create_test_fs();
+        let mut tracker = ProgressTracker::with_cancellation(
+            1, 8 * 1024 * 1024, "Cancelled copy".to_string(), cancellation_token
+        ).with_callback(callback);
+        let mut command = CopyCommand::new(source_path.clone(), dest_path.clone())
+            .with_chunked_copy_threshold(0)
+            .with_chunk_size(1024 * 1024);
+        
+        let result = command.execute_with_progress(fs, Some(&mut tracker)).await;
+        assert!(matches!(result, Err(OperationError::Cancelled)));
+        assert!(!command.is_executed());
+        assert!(!dest_path.exists());
+        assert!(!partial_copy_path(&dest_path).exists());
+        assert!(source_path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_move_across_devices_copies_and_undo_copies_back() {
+        let temp_dir = TempDir::new().unwrap();
+        let source_path = temp_dir.path().join("photo.jpg");
+        let dest_dir = temp_dir.path().join("external");
+        let dest_path = dest_dir.join("photo.jpg");
+        let content: Vec<u8> = (0..3 * 1024 * 1024u32).map(|i| (i % 239) as u8).collect();
+        tokio::fs::write(&source_path, &content).await.unwrap();
+        tokio::fs::create_dir(&dest_dir).await.unwrap();
+        
+        let fs = create_test_fs();
+        let mut command = MoveCommand::new(source_path.clone(), dest_path.clone()).with_forced_copy();
+        command.execute(fs.clone()).await.unwrap();
+        assert!(command.was_copied_across_devices());
+        assert!(!source_path.exists());
+        assert_eq!(tokio::fs::read(&dest_path).await.unwrap(), content);
+        assert!(!partial_copy_path(&dest_path).exists());
+        
+        // Undo copies the file back and removes the moved one
+        command.undo(fs.clone()).await.unwrap();
+        assert!(command.is_undone());
+        assert_eq!(tokio::fs::read(&source_path).await.unwrap(), content);
+        assert!(!dest_path.exists());
+        assert!(!partial_copy_path(&source_path).exists());
+        
+        // The chunked path with progress takes the same route
+        let mut tracker = ProgressTracker::new(1, content.len() as u64, "Move".to_string());
+        let mut command = MoveCommand::new(source_path.clone(), dest_path.clone())
+            .with_chunked_copy_threshold(0)
+            .with_forced_copy();
+        command.execute_with_progress(fs.clone(), Some(&mut tracker)).await.unwrap();
+        assert!(command.was_copied_across_devices());
+        assert_eq!(tracker.progress().bytes_processed, content.len() as u64);
+        command.undo(fs).await.unwrap();
+        assert_eq!(tokio::fs::read(&source_path).await.unwrap(), content);
+        assert!(!dest_path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_move_across_devices_leaves_source_and_no_copy() {
+        let temp_dir = TempDir::new().unwrap();
+        let source_path = temp_dir.path().join("large.bin");
+        let dest_path = temp_dir.path().join("moved.bin");
+        let existing = vec![1u8; 16];
+        tokio::fs::write(&source_path, vec![7u8; 8 * 1024 * 1024]).await.unwrap();
+        tokio::fs::write(&dest_path, &existing).await.unwrap();
+        
+        let cancellation_token = CancellationToken::new();
+        let cancel_clone = cancellation_token.clone();
+        let callback: ProgressCallback = Arc::new(move |progress| {
+            if progress.bytes_processed > 0 {
+                cancel_clone.cancel();
+            }
+        });
+        let mut tracker = ProgressTracker::with_cancellation(
+            1, 8 * 1024 * 1024, "Cancelled move".to_string(), cancellation_token
+        ).with_callback(callback);
+        let mut command = MoveCommand::new(source_path.clone(), dest_path.clone())
+            .with_conflict_resolution(ConflictResolution::Overwrite)
+            .with_chunked_copy_threshold(0)
+            .with_forced_copy();
+        
+        let result = command.execute_with_progress(create_test_fs(), Some(&mut tracker)).await;
+        assert!(matches!(result, Err(OperationError::Cancelled)));
+        assert!(!command.is_executed());
+        assert_eq!(tokio::fs::metadata(&source_path).await.unwrap().len(), 8 * 1024 * 1024);
+        // The file it was going to replace is untouched and nothing half-copied is left
+        assert_eq!(tokio::fs::read(&dest_path).await.unwrap(), existing);
+        assert!(!partial_copy_path(&dest_path).exists());
+    }
+
+    #[tokio::test]
+    async fn test_corrupted_destination_fails_verification_with_both_hashes() {
+        let temp_dir = TempDir::new().unwrap();
+        let source_path = temp_dir.path().join("photo.raw");
+        let dest_path = temp_dir.path().join("copy.raw");
+        let content: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+        tokio::fs::write(&source_path, &content).await.unwrap();
+        tokio::fs::write(&dest_path, &content).await.unwrap();
+        verify_copy(&source_path, &dest_path, None).await.unwrap();
+        
+        // Same size, one flipped byte
+        let mut corrupted = content.clone();
+        corrupted[50_000] ^= 0xff;
+        tokio::fs::write(&dest_path, &corrupted).await.unwrap();
+        let hashing = HashingService::new();
+        let expected_source = hashing.hash_file(&source_path).await.unwrap().hash;
+        let expected_destination = hashing.hash_file(&dest_path).await.unwrap().hash;
+        
+        match verify_copy(&source_path, &dest_path, None).await {
+            Err(OperationError::IntegrityMismatch { path, source_hash, destination_hash }) => {
+                assert_eq!(path, dest_path);
+                assert_eq!(source_hash, expected_source);
+                assert_eq!(destination_hash, expected_destination);
+                assert_ne!(source_hash, destination_hash);
+            }
+            other => panic!("expected an integrity mismatch, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verified_copy_and_move_keep_files_and_cancelled_check_rolls_back() {
+        let temp_dir = TempDir::new().unwrap();
+        let source_path = temp_dir.path().join("video.mov");
+        let dest_path = temp_dir.path().join("copy.mov");
+        let existing = b"older copy".to_vec();
+        let content: Vec<u8> = (0..200_000u32).map(|i| (i % 241) as u8).collect();
+        tokio::fs::write(&source_path, &content).await.unwrap();
+        
+        let fs = create_test_fs();
+        let mut copy = CopyCommand::new(source_path.clone(), dest_path.clone()).with_verify_integrity(true);
+        copy.execute(fs.clone()).await.unwrap();
+        assert_eq!(tokio::fs::read(&dest_path).await.unwrap(), content);
+        copy.undo(fs.clone()).await.unwrap();
+        
+        // Cancelled while checking: the copy is removed and the file it replaced restored
+        tokio::fs::write(&dest_path, &existing).await.unwrap();
+        let cancellation_token = CancellationToken::new();
+        let cancel_clone = cancellation_token.clone();
+        let callback: ProgressCallback = Arc::new(move |progress| {
+            if progress.current_operation.starts_with("Verifying") {
+                cancel_clone.cancel();
+            }
+        });
+        let mut tracker = ProgressTracker::with_cancellation(
+            1, content.len() as u64, "Verified copy".to_string(), cancellation_token
+        ).with_callback(callback);
+        let mut copy = CopyCommand::new(source_path.clone(), dest_path.clone())
+            .with_conflict_resolution(ConflictResolution::Overwrite)
+            .with_verify_integrity(true);
+        let result = copy.execute_with_progress(fs.clone(), Some(&mut tracker)).await;
+        assert!(matches!(result, Err(OperationError::Cancelled)), "{:?}", result);
+        assert!(!copy.is_executed());
+        assert_eq!(tokio::fs::read(&dest_path).await.unwrap(), existing);
+        tokio::fs::remove_file(&dest_path).await.unwrap();
+        
+        // A verified move across file systems removes the source only after the check
+        let mut tracker = ProgressTracker::new(1, content.len() as u64, "Verified move".to_string());
+        let mut command = MoveCommand::new(source_path.clone(), dest_path.clone())
+            .with_chunked_copy_threshold(0)
+            .with_verify_integrity(true)
+            .with_forced_copy();
+        command.execute_with_progress(fs.clone(), Some(&mut tracker)).await.unwrap();
+        assert!(command.was_copied_across_devices());
+        assert!(!source_path.exists());
+        assert_eq!(tokio::fs::read(&dest_path).await.unwrap(), content);
+        assert_eq!(tracker.progress().bytes_processed, content.len() as u64);
+        command.undo(fs).await.unwrap();
+        assert_eq!(tokio::fs::read(&source_path).await.unwrap(), content);
+    }
+
+    #[tokio::test]
+    async fn test_move_across_devices_never_resumes_a_leftover_part() {
+        let temp_dir = TempDir::new().unwrap();
+        let source_path = temp_dir.path().join("photo.jpg");
+        let dest_path = temp_dir.path().join("moved.jpg");
+        let content: Vec<u8> = (0..2 * 1024 * 1024u32).map(|i| (i % 233) as u8).collect();
+        tokio::fs::write(&source_path, &content).await.unwrap();
+        
+        // Same length as the source and even recorded as a copy of it, but not its bytes
+        tokio::fs::write(partial_copy_path(&dest_path), vec![0u8; content.len()]).await.unwrap();
+        PartialCopySource::of(&source_path).await.unwrap().record_for(&dest_path, content.len() as u64).await.unwrap();
+        
+        let mut command = MoveCommand::new(source_path.clone(), dest_path.clone()).with_forced_copy();
+        command.execute(create_test_fs()).await.unwrap();
+        assert!(command.was_copied_across_devices());
+        assert!(!source_path.exists());
+        assert_eq!(tokio::fs::read(&dest_path).await.unwrap(), content);
+        assert!(!partial_copy_path(&dest_path).exists());
+        assert!(!partial_copy_source_path(&dest_path).exists());
+    }
+
+    fn write_tree(root: &Path) {
+        std::fs::create_dir_all(root.join("albums/2024")).unwrap();
+        std::fs::write(root.join("cover.jpg"), "cover").unwrap();
+        std::fs::write(root.join("albums/2024/beach.jpg"), "beach").unwrap();
+        std::fs::write(root.join("albums/2024/hike.jpg"), "hike").unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_copy_directory_command_merges_and_undoes() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("photos");
+        let destination = temp_dir.path().join("backup");
+        write_tree(&source);
+        let fs = create_test_fs();
+        
+        let mut command = CopyDirectoryCommand::new(source.clone(), destination.clone());
+        assert_eq!(command.estimate_work(fs.clone()).await.unwrap(), (6, 14));
+        command.execute(fs.clone()).await.unwrap();
+        assert_eq!(read(&destination.join("albums/2024/hike.jpg")).await, "hike");
+        command.undo(fs.clone()).await.unwrap();
+        assert!(!destination.exists());
+        
+        // Merging keeps unrelated files and restores the replaced one on undo
+        std::fs::create_dir(&destination).unwrap();
+        std::fs::write(destination.join("cover.jpg"), "old cover").unwrap();
+        std::fs::write(destination.join("notes.txt"), "notes").unwrap();
+        let mut ask = CopyDirectoryCommand::new(source.clone(), destination.clone());
+        assert!(matches!(ask.execute(fs.clone()).await, Err(OperationError::DestinationExists(_))));
+        
+        let mut merge = CopyDirectoryCommand::new(source.clone(), destination.clone())
+            .with_conflict_resolution(ConflictResolution::Overwrite);
+        merge.execute(fs.clone()).await.unwrap();
+        assert_eq!(read(&destination.join("cover.jpg")).await, "cover");
+        assert_eq!(read(&destination.join("albums/2024/beach.jpg")).await, "beach");
+        merge.undo(fs.clone()).await.unwrap();
+        assert_eq!(read(&destination.join("cover.jpg")).await, "old cover");
+        assert_eq!(read(&destination.join("notes.txt")).await, "notes");
+        assert!(!destination.join("albums").exists());
+        
+        let mut inside = CopyDirectoryCommand::new(source.clone(), source.join("albums/copy"));
+        assert!(matches!(inside.execute(fs).await, Err(OperationError::ValidationFailed(_))));
+    }
+
+    #[tokio::test]
+    async fn test_copy_directory_cancellation_rolls_back() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("photos");
+        let destination = temp_dir.path().join("backup");
+        write_tree(&source);
+        
+        // Cancel after the third entry has been copied
+        let cancellation_token = CancellationToken::new();
+        let cancel_clone = cancellation_token.clone();
+        let callback: ProgressCallback = Arc::new(move |progress| {
+            if progress.current >= 3 {
+                cancel_clone.cancel();
+            }
+        });
+        
+        let fs = create_test_fs();
+        let mut command = CopyDirectoryCommand::new(source.clone(), destination.clone());
+        let (items, bytes) = command.estimate_work(fs.clone()).await.unwrap();
+        let mut tracker = ProgressTracker::with_cancellation(items, bytes, "Cancelled copy".to_string(), cancellation_token)
+            .with_callback(callback);
+        
+        let result = command.execute_with_progress(fs, Some(&mut tracker)).await;
+        assert!(matches!(result, Err(OperationError::Cancelled)), "{:?}", result);
+        assert!(!command.is_executed());
+        assert_eq!(tracker.progress().current, 3);
+        assert!(!destination.exists());
+        assert_eq!(read(&source.join("albums/2024/hike.jpg")).await, "hike");
+    }
+
+    #[tokio::test]
+    async fn test_move_directory_command_and_undo() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("photos");
+        let destination = temp_dir.path().join("archive");
+        write_tree(&source);
+        let fs = create_test_fs();
+        
+        let mut command = MoveDirectoryCommand::new(source.clone(), destination.clone());
+        command.execute(fs.clone()).await.unwrap();
+        assert!(!source.exists());
+        assert_eq!(read(&destination.join("albums/2024/beach.jpg")).await, "beach");
+        command.undo(fs.clone()).await.unwrap();
+        assert!(!destination.exists());
+        assert_eq!(read(&source.join("albums/2024/beach.jpg")).await, "beach");
+        
+        // Merging into an existing directory copies and removes the source
+        std::fs::create_dir(&destination).unwrap();
+        std::fs::write(destination.join("cover.jpg"), "old cover").unwrap();
+        let mut merge = MoveDirectoryCommand::new(source.clone(), destination.clone())
+            .with_conflict_resolution(ConflictResolution::Overwrite);
+        merge.execute(fs.clone()).await.unwrap();
+        assert!(!source.exists());
+        assert_eq!(read(&destination.join("cover.jpg")).await, "cover");
+        merge.undo(fs).await.unwrap();
+        assert_eq!(read(&source.join("cover.jpg")).await, "cover");
+        assert_eq!(read(&source.join("albums/2024/hike.jpg")).await, "hike");
+        assert_eq!(read(&destination.join("cover.jpg")).await, "old cover");
+        assert!(!destination.join("albums").exists());
+    }
+
+    #[tokio::test]
+    async fn test_chunked_copy_resumes_partial_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let source_path = temp_dir.path().join("large.bin");
+        let dest_path = temp_dir.path().join("large_copy.bin");
+        let content: Vec<u8> = (0..3 * 1024 * 1024u32).map(|i| (i % 253) as u8).collect();
+        tokio::fs::write(&source_path, &content).await.unwrap();
+        
+        // Simulate a copy interrupted halfway through
+        let half = content.len() as u64 / 2;
+        tokio::fs::write(partial_copy_path(&dest_path), &content[..half as usize]).await.unwrap();
+        PartialCopySource::of(&source_path).await.unwrap().record_for(&dest_path, half).await.unwrap();
+        
+        let reported = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorder = reported.clone();
+        let callback: ProgressCallback = Arc::new(move |progress| recorder.lock().unwrap().push(progress.bytes_processed));
+        let mut tracker = ProgressTracker::new(1, content.len() as u64, "Resumed copy".to_string()).with_callback(callback);
+        let copied = copy_file_chunked(&source_path, &dest_path, 512 * 1024, Some(&mut tracker)).await.unwrap();
+        assert_eq!(copied, content.len() as u64);
+        assert_eq!(tokio::fs::read(&dest_path).await.unwrap(), content);
+        // Picked up after the first half rather than starting over
+        assert_eq!(reported.lock().unwrap().first(), Some(&(half + 512 * 1024)));
+        assert!(!partial_copy_path(&dest_path).exists());
+        assert!(!partial_copy_source_path(&dest_path).exists());
+    }
+
+    #[tokio::test]
+    async fn test_chunked_copy_resumes_only_synced_bytes() {
+        let temp_dir = TempDir::new().unwrap();
+        let source_path = temp_dir.path().join("large.bin");
+        let dest_path = temp_dir.path().join("large_copy.bin");
+        let content: Vec<u8> = (0..3 * 1024 * 1024u32).map(|i| (i % 253) as u8).collect();
+        tokio::fs::write(&source_path, &content).await.unwrap();
+        
+        // A crash after the first megabyte was synced, with garbage written past it
+        let synced = 1024 * 1024;
+        let mut part = content[..synced].to_vec();
+        part.extend(vec![0xeeu8; 1024 * 1024]);
+        tokio::fs::write(partial_copy_path(&dest_path), &part).await.unwrap();
+        PartialCopySource::of(&source_path).await.unwrap().record_for(&dest_path, synced as u64).await.unwrap();
+        
+        let reported = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorder = reported.clone();
+        let callback: ProgressCallback = Arc::new(move |progress| recorder.lock().unwrap().push(progress.bytes_processed));
+        let mut tracker = ProgressTracker::new(1, content.len() as u64, "Resumed copy".to_string()).with_callback(callback);
+        copy_file_chunked(&source_path, &dest_path, 512 * 1024, Some(&mut tracker)).await.unwrap();
+        assert_eq!(tokio::fs::read(&dest_path).await.unwrap(), content);
+        assert_eq!(reported.lock().unwrap().first(), Some(&(synced as u64 + 512 * 1024)));
+    }
+
+    #[tokio::test]
+    async fn test_failed_chunked_copy_leaves_only_hidden_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let source_path = temp_dir.path().join("large.bin");
+        let dest_path = temp_dir.path().join("large_copy.bin");
+        tokio::fs::write(&source_path, vec![7u8; 2 * 1024 * 1024]).await.unwrap();
+        
+        // The destination name is taken by a folder, so the final rename fails
+        tokio::fs::create_dir(&dest_path).await.unwrap();
+        tokio::fs::write(dest_path.join("keep.txt"), "keep").await.unwrap();
+        assert!(copy_file_chunked(&source_path, &dest_path, 512 * 1024, None).await.is_err());
+        
+        let mut names = Vec::new();
+        let mut entries = tokio::fs::read_dir(temp_dir.path()).await.unwrap();
+        while let Some(entry) = entries.next_entry().await.unwrap() {
+            names.push(entry.file_name().to_string_lossy().into_owned());
+        }
+        names.sort();
+        assert_eq!(names, [".large_copy.bin.part", ".large_copy.bin.part.source", "large.bin", "large_copy.bin"]);
+    }
+
+    #[tokio::test]
+    async fn test_chunked_copy_starts_over_on_part_of_another_source() {
+        let temp_dir = TempDir::new().unwrap();
+        let source_path = temp_dir.path().join("large.bin");
+        let other_path = temp_dir.path().join("other.bin");
+        let dest_path = temp_dir.path().join("large_copy.bin");
+        let content: Vec<u8> = (0..3 * 1024 * 1024u32).map(|i| (i % 253) as u8).collect();
+        let stale = vec![0xeeu8; content.len() / 2];
+        tokio::fs::write(&source_path, &content).await.unwrap();
+        tokio::fs::write(&other_path, vec![1u8; content.len()]).await.unwrap();
+        
+        // A file that merely has the `.part` name, with nothing recording its source
+        tokio::fs::write(partial_copy_path(&dest_path), &stale).await.unwrap();
+        copy_file_chunked(&source_path, &dest_path, 512 * 1024, None).await.unwrap();
+        assert_eq!(tokio::fs::read(&dest_path).await.unwrap(), content);
+        tokio::fs::remove_file(&dest_path).await.unwrap();
+        
+        // Left behind by a copy of a different file
+        tokio::fs::write(partial_copy_path(&dest_path), &stale).await.unwrap();
+        PartialCopySource::of(&other_path).await.unwrap().record_for(&dest_path, stale.len() as u64).await.unwrap();
+        copy_file_chunked(&source_path, &dest_path, 512 * 1024, None).await.unwrap();
+        assert_eq!(tokio::fs::read(&dest_path).await.unwrap(), content);
+        tokio::fs::remove_file(&dest_path).await.unwrap();
+        
+        // Left behind by a copy of this source before it was changed
+        tokio::fs::write(partial_copy_path(&dest_path), &stale).await.unwrap();
+        PartialCopySource::of(&source_path).await.unwrap().record_for(&dest_path, stale.len() as u64).await.unwrap();
+        let mut changed = content.clone();
+        changed[0] ^= 0xff;
+        tokio::fs::write(&source_path, &changed).await.unwrap();
+        copy_file_chunked(&source_path, &dest_path, 512 * 1024, None).await.unwrap();
+        assert_eq!(tokio::fs::read(&dest_path).await.unwrap(), changed);
+        assert!(!partial_copy_source_path(&dest_path).exists());
+    }
+
+    #[tokio::test]
+    async fn test_parallel_batch_rolls_back_on_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let output_dir = temp_dir.path().join("out");
+        let mut sources = Vec::new();
+        for i in 0..6 {
+            let path = temp_dir.path().join(format!("file{}.txt", i));
+            tokio::fs::write(&path, format!("content {}", i)).await.unwrap();
+            sources.push(path);
+        }
+        
+        let fs = create_test_fs();
+        let processor = BatchProcessor::new(fs.clone());
+        
+        let mut batch = BatchOperation::new("Parallel".to_string(), "Parallel copy with a failure".to_string())
+            .with_parallelism(4);
+        batch.add_command(Box::new(CreateDirectoryCommand::new(output_dir.clone())));
+        for (i, source) in sources.iter().enumerate() {
+            // The fourth copy targets a directory that is never created
+            let destination = if i == 3 {
+                temp_dir.path().join("missing").join("file3.txt")
+            } else {
+                output_dir.join(source.file_name().unwrap())
+            };
+            batch.add_command(Box::new(CopyCommand::new(source.clone(), destination)));
+        }
+        
+        let result = processor.execute_batch_async(batch).await;
+        assert!(matches!(result, Err(OperationError::BatchFailed(_))));
+        
+        // Every successful copy and the created directory were rolled back
+        assert!(!output_dir.exists());
+        assert!(sources.iter().all(|source| source.exists()));
+        
+        processor.shutdown();
+    }
+
+    /// Records how many probes run at once; each stays busy for a few milliseconds
+    #[derive(Debug)]
+    struct ProbeCommand {
+        path: PathBuf,
+        running: Arc<std::sync::atomic::AtomicUsize>,
+        peak: Arc<std::sync::atomic::AtomicUsize>,
+        metadata: CommandMetadata,
+    }
+
+    impl ProbeCommand {
+        fn new(path: &str, running: &Arc<std::sync::atomic::AtomicUsize>, peak: &Arc<std::sync::atomic::AtomicUsize>) -> Self {
+            Self {
+                path: PathBuf::from(path),
+                running: running.clone(),
+                peak: peak.clone(),
+                metadata: CommandMetadata::default(),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Command for ProbeCommand {
+        async fn execute(&mut self, _fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+            let now_running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now_running, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.running.fetch_sub(1, Ordering::SeqCst);
+            self.metadata.status = CommandStatus::Executed;
+            Ok(())
+        }
+
+        async fn undo(&mut self, _fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+            self.metadata.status = CommandStatus::Undone;
+            Ok(())
+        }
+
+        async fn validate(&self, _fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+            Ok(())
+        }
+
+        fn metadata(&self) -> &CommandMetadata {
+            &self.metadata
+        }
+
+        fn metadata_mut(&mut self) -> &mut CommandMetadata {
+            &mut self.metadata
+        }
+
+        fn description(&self) -> String {
+            format!("Probe {}", self.path.display())
+        }
+
+        fn source_path(&self) -> Option<&Path> {
+            Some(&self.path)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_parallel_batch_respects_the_worker_limit() {
+        let running = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let peak = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let processor = BatchProcessor::new(create_test_fs());
+
+        let mut batch = BatchOperation::new("Limit".to_string(), "Independent probes".to_string()).with_parallelism(3);
+        for i in 0..9 {
+            batch.add_command(Box::new(ProbeCommand::new(&format!("/probes/{}", i), &running, &peak)));
+        }
+        let batch = processor.execute_batch_async(batch).await.unwrap();
+
+        assert_eq!(batch.progress.completed_commands, 9);
+        assert_eq!(peak.load(Ordering::SeqCst), 3);
+        processor.shutdown();
+    }
+
+    #[tokio::test]
+    async fn test_parallel_batch_serializes_overlapping_paths() {
+        let running = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let peak = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let probe = |path: &str| Box::new(ProbeCommand::new(path, &running, &peak)) as Box<dyn Command>;
+
+        // A run ends at the first command touching a path it already has, or a path inside one
+        let commands = vec![probe("/a"), probe("/b"), probe("/a/c"), probe("/d"), probe("/a/c/e.jpg")];
+        assert_eq!(parallel_stage_end(&commands, 0), 2);
+        assert_eq!(parallel_stage_end(&commands, 2), 4);
+        assert_eq!(parallel_stage_end(&commands, 4), 5);
+
+        // Commands on the same file never run at the same time
+        let processor = BatchProcessor::new(create_test_fs());
+        let mut batch = BatchOperation::new("Overlap".to_string(), "Probes on one file".to_string()).with_parallelism(4);
+        for _ in 0..4 {
+            batch.add_command(probe("/photos/beach.jpg"));
+        }
+        let batch = processor.execute_batch_async(batch).await.unwrap();
+
+        assert_eq!(batch.progress.completed_commands, 4);
+        assert_eq!(peak.load(Ordering::SeqCst), 1);
+        processor.shutdown();
+    }
+
+    #[tokio::test]
+    #[ignore = "benchmark; copies 5,000 files"]
+    async fn test_parallel_vs_serial_directory_copy_benchmark() {
+        use std::time::Instant;
+        
+        // Build a 5000-file tree spread over 50 directories
+        let temp_dir = TempDir::new().unwrap();
+        let source_root = temp_dir.path().join("source");
+        for dir in 0..50 {
+            let dir_path = source_root.join(format!("dir{:02}", dir));
+            std::fs::create_dir_all(&dir_path).unwrap();
+            for file in 0..100 {
+                std::fs::write(dir_path.join(format!("file{:03}.txt", file)), format!("{}-{}", dir, file)).unwrap();
+            }
+        }
+        
+        let fs = create_test_fs();
+        let processor = BatchProcessor::new(fs.clone());
+        let mut timings = Vec::new();
+        
+        for (label, workers) in [("serial", 1), ("parallel", default_copy_parallelism())] {
+            let destination = temp_dir.path().join(label);
+            let batch = directory_copy_batch(&source_root, &destination).unwrap()
+                .with_parallelism(workers);
+            assert_eq!(batch.commands.len(), 5051);
+            
+            let started = Instant::now();
+            let completed = processor.execute_batch_async(batch).await.unwrap();
+            let elapsed = started.elapsed();
+            
+            assert_eq!(completed.progress.status, BatchStatus::Completed);
+            assert_eq!(completed.progress.completed_commands, 5051);
+            assert_eq!(
+                std::fs::read_to_string(destination.join("dir42").join("file007.txt")).unwrap(),
+                "42-7"
+            );
+            timings.push((label, workers, elapsed));
+        }
+        
+        for (label, workers, elapsed) in &timings {
+            println!("{} copy of 5000 files ({} workers): {:?}", label, workers, elapsed);
+        }
+        
+        processor.shutdown();
+    }
+}
@@ -0,0 +1,456 @@
+//! Sorting files into dated or per-type folders
+//!
+//! Planning is separate from moving so callers can show a dry run before
+//! anything on disk changes. `OrganizeCommand` wraps a plan in a batch of moves
+//! that the history can undo as one step.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use super::classifier::Classifier;
+use super::file_system::{FileEntry, FileSystemService, TraversalOptions};
+use super::operations::{
+    BatchOperation, Command, CommandKind, CommandMetadata, CommandStatus, CreateDirectoryCommand, MoveCommand, OperationError,
+    OperationKind, OperationResult,
+};
+
+/// `strftime` layout of the folders `date_folder` files into
+pub const DEFAULT_DATE_FOLDER_FORMAT: &str = "%Y/%m";
+
+/// A planned move of one file into its dated folder
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OrganizeMove {
+    pub source: PathBuf,
+    pub destination: PathBuf,
+}
+
+/// Date a file is filed under: EXIF capture date when known, otherwise modification time
+pub fn organize_date(entry: &FileEntry) -> SystemTime {
+    entry
+        .preview_metadata
+        .as_ref()
+        .and_then(|metadata| metadata.exif_data.as_ref())
+        .and_then(|exif| exif.date_taken)
+        .unwrap_or(entry.modified)
+}
+
+/// Relative folder for a date, e.g. `2024/03`
+pub fn date_folder(time: SystemTime) -> PathBuf {
+    formatted_date_folder(time, DEFAULT_DATE_FOLDER_FORMAT)
+}
+
+/// Relative folder for a date laid out by the `strftime` `format`, where each
+/// `/` starts a nested folder
+pub fn formatted_date_folder(time: SystemTime, format: &str) -> PathBuf {
+    let local = DateTime::<Local>::from(time);
+    format
+        .split('/')
+        .filter(|part| !part.is_empty())
+        .map(|part| local.format(part).to_string())
+        .collect()
+}
+
+/// Plan moving every file in `entries` to `<destination_root>/<year>/<month>/`
+///
+/// Directories are skipped, as are files already in place. A name taken by an
+/// existing file or an earlier planned move gets a " (n)" suffix.
+pub fn plan_by_date(entries: &[FileEntry], destination_root: &Path) -> Vec<OrganizeMove> {
+    plan_into_folders(entries, destination_root, |entry| date_folder(organize_date(entry)))
+}
+
+/// Plan moving every file in `entries` to `<destination_root>/<category>/`
+///
+/// Categories come from `classifier`, so user rules decide where their files go.
+pub fn plan_by_type(entries: &[FileEntry], destination_root: &Path, classifier: &Classifier) -> Vec<OrganizeMove> {
+    plan_into_folders(entries, destination_root, |entry| {
+        PathBuf::from(classifier.classify_entry(entry).category)
+    })
+}
+
+/// Which folder a file is organized into
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrganizeRule {
+    /// Folders named by the file's date, laid out like `formatted_date_folder`
+    ByDate { format: String },
+    /// One folder per classifier category
+    ByType,
+}
+
+impl OrganizeRule {
+    /// `ByDate` with year and month folders
+    pub fn by_month() -> Self {
+        OrganizeRule::ByDate {
+            format: DEFAULT_DATE_FOLDER_FORMAT.to_string(),
+        }
+    }
+
+    /// Plan moving every file in `entries` into its folder under `destination_root`
+    pub fn plan(&self, entries: &[FileEntry], destination_root: &Path, classifier: &Classifier) -> Vec<OrganizeMove> {
+        match self {
+            OrganizeRule::ByDate { format } => plan_into_folders(entries, destination_root, |entry| {
+                formatted_date_folder(organize_date(entry), format)
+            }),
+            OrganizeRule::ByType => plan_by_type(entries, destination_root, classifier),
+        }
+    }
+}
+
+/// Plan moves into the relative folder `folder_for` picks for each file
+fn plan_into_folders(
+    entries: &[FileEntry],
+    destination_root: &Path,
+    folder_for: impl Fn(&FileEntry) -> PathBuf,
+) -> Vec<OrganizeMove> {
+    let mut claimed = HashSet::new();
+    let mut plan = Vec::new();
+
+    for entry in entries.iter().filter(|entry| !entry.is_directory) {
+        let folder = destination_root.join(folder_for(entry));
+        let mut destination = folder.join(&entry.name);
+        if destination == entry.path {
+            continue;
+        }
+
+        let mut counter = 1;
+        while destination.exists() || claimed.contains(&destination) {
+            destination = folder.join(numbered_name(&entry.path, counter));
+            counter += 1;
+        }
+        claimed.insert(destination.clone());
+        plan.push(OrganizeMove {
+            source: entry.path.clone(),
+            destination,
+        });
+    }
+    plan
+}
+
+/// `photo (2).jpg` style name for the `counter`th collision
+pub(crate) fn numbered_name(path: &Path, counter: usize) -> String {
+    let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    match path.extension() {
+        Some(extension) => format!("{} ({}).{}", stem, counter, extension.to_string_lossy()),
+        None => format!("{} ({})", stem, counter),
+    }
+}
+
+/// Organize every file below a root into folders under that root
+///
+/// `plan` lists the tree and records the moves for a preview without touching
+/// the disk. Executing creates the missing folders and moves the files as one
+/// batch, which rolls back if any move fails; undo moves every file back and
+/// removes the folders it created.
+#[derive(Debug)]
+pub struct OrganizeCommand {
+    pub root: PathBuf,
+    pub rule: OrganizeRule,
+    plan: Option<Vec<OrganizeMove>>,
+    batch: Option<BatchOperation>,
+    metadata: CommandMetadata,
+}
+
+impl OrganizeCommand {
+    pub fn new(root: PathBuf, rule: OrganizeRule) -> Self {
+        Self {
+            root,
+            rule,
+            plan: None,
+            batch: None,
+            metadata: CommandMetadata::default(),
+        }
+    }
+
+    /// Plan from `entries` instead of listing the root, e.g. entries whose
+    /// EXIF data is already loaded
+    pub fn with_entries(mut self, entries: &[FileEntry]) -> Self {
+        self.plan = Some(self.rule.plan(entries, &self.root, super::classifier::classifier()));
+        self
+    }
+
+    /// List every file below the root and plan its move, without moving anything
+    pub async fn plan(&mut self, fs: Arc<dyn FileSystemService>) -> OperationResult<&[OrganizeMove]> {
+        let entries = fs
+            .traverse_directory(&self.root, TraversalOptions::recursive())
+            .await
+            .map_err(OperationError::FileSystem)?;
+        let plan = self.rule.plan(&entries, &self.root, super::classifier::classifier());
+        Ok(self.plan.insert(plan))
+    }
+
+    /// Moves planned so far; empty until `plan` or `with_entries`
+    pub fn planned_moves(&self) -> &[OrganizeMove] {
+        self.plan.as_deref().unwrap_or_default()
+    }
+
+    /// Batch creating the missing folders, parents first, then moving each file
+    fn build_batch(&self, plan: &[OrganizeMove]) -> BatchOperation {
+        let mut batch = BatchOperation::new("Organize".to_string(), self.description());
+        let mut folders = Vec::new();
+        for planned in plan {
+            let Some(parent) = planned.destination.parent() else {
+                continue;
+            };
+            let missing: Vec<&Path> = parent
+                .ancestors()
+                .take_while(|folder| *folder != self.root && !folder.is_dir())
+                .collect();
+            for folder in missing.into_iter().rev() {
+                if !folders.iter().any(|known: &PathBuf| known == folder) {
+                    folders.push(folder.to_path_buf());
+                }
+            }
+        }
+        for folder in folders {
+            batch.add_command(Box::new(CreateDirectoryCommand::new(folder)));
+        }
+        for planned in plan {
+            batch.add_command(Box::new(MoveCommand::new(
+                planned.source.clone(),
+                planned.destination.clone(),
+            )));
+        }
+        batch
+    }
+}
+
+#[async_trait]
+impl Command for OrganizeCommand {
+    async fn execute(&mut self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        if self.is_executed() {
+            return Err(OperationError::AlreadyExecuted);
+        }
+        self.validate(fs.clone()).await?;
+        if self.plan.is_none() {
+            self.plan(fs.clone()).await?;
+        }
+
+        let mut batch = self.build_batch(self.planned_moves());
+        // A failed batch has already rolled back the moves that ran
+        batch.execute(fs).await?;
+        self.batch = Some(batch);
+
+        self.metadata.status = CommandStatus::Executed;
+        self.metadata.executed_at = Some(SystemTime::now());
+        Ok(())
+    }
+
+    async fn undo(&mut self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        if !self.can_undo() {
+            return Err(OperationError::NotExecuted);
+        }
+        let batch = self.batch.as_mut().ok_or(OperationError::NotExecuted)?;
+        batch.undo(fs).await?;
+
+        self.metadata.status = CommandStatus::Undone;
+        self.metadata.undone_at = Some(SystemTime::now());
+        Ok(())
+    }
+
+    async fn validate(&self, _fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        if !self.root.is_dir() {
+            return Err(OperationError::ValidationFailed(format!(
+                "Folder to organize does not exist: {}",
+                self.root.display()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Saved as the batch it ran, so a restart can still undo it
+    fn command_kind(&self) -> Option<CommandKind> {
+        self.batch.as_ref().and_then(|batch| batch.command_kind())
+    }
+
+    fn metadata(&self) -> &CommandMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut CommandMetadata {
+        &mut self.metadata
+    }
+
+    fn description(&self) -> String {
+        let by = match self.rule {
+            OrganizeRule::ByDate { .. } => "date",
+            OrganizeRule::ByType => "type",
+        };
+        match &self.plan {
+            Some(plan) => format!("Organize {} files in {} by {}", plan.len(), self.root.display(), by),
+            None => format!("Organize {} by {}", self.root.display(), by),
+        }
+    }
+
+    fn kind(&self) -> OperationKind {
+        OperationKind::Move
+    }
+
+    fn source_path(&self) -> Option<&Path> {
+        Some(&self.root)
+    }
+
+    fn must_run_serially(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::classifier::ClassifierRule;
+    use crate::services::file_system::{FileSystemService, NativeFileSystemService, TraversalOptions};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_plan_files_by_month_and_avoids_collisions() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("inbox");
+        std::fs::create_dir_all(source.join("nested")).unwrap();
+        std::fs::write(source.join("a.jpg"), b"one").unwrap();
+        std::fs::write(source.join("nested").join("a.jpg"), b"two").unwrap();
+
+        let mut entries = NativeFileSystemService::new()
+            .traverse_directory(&source, TraversalOptions::recursive())
+            .await
+            .unwrap();
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        let destination_root = temp_dir.path().join("sorted");
+        let plan = plan_by_date(&entries, &destination_root);
+
+        // The nested directory itself is not moved
+        assert_eq!(plan.len(), 2);
+        let folder = destination_root.join(date_folder(organize_date(&entries[0])));
+        assert_eq!(plan[0].destination, folder.join("a.jpg"));
+        assert_eq!(plan[1].destination, folder.join("a (1).jpg"));
+    }
+
+    #[tokio::test]
+    async fn test_plan_by_type_uses_user_categories() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("inbox");
+        std::fs::create_dir_all(&source).unwrap();
+        std::fs::write(source.join("song.mp3"), b"one").unwrap();
+        std::fs::write(source.join("photo.jpg"), b"two").unwrap();
+
+        let mut entries = NativeFileSystemService::new()
+            .traverse_directory(&source, TraversalOptions::recursive())
+            .await
+            .unwrap();
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        let classifier = Classifier::new()
+            .with_rule(ClassifierRule::for_extensions(&["mp3"], "Podcasts"))
+            .unwrap();
+        let destination_root = temp_dir.path().join("sorted");
+        let plan = plan_by_type(&entries, &destination_root, &classifier);
+
+        assert_eq!(plan[0].destination, destination_root.join("Images").join("photo.jpg"));
+        assert_eq!(plan[1].destination, destination_root.join("Podcasts").join("song.mp3"));
+    }
+
+    fn entry_with_dates(path: PathBuf, modified: SystemTime, date_taken: Option<SystemTime>) -> FileEntry {
+        use crate::services::file_system::{ExifMetadata, FilePermissions, FileType, PreviewMetadata};
+        let exif = date_taken.map(|date_taken| ExifMetadata {
+            camera_make: None,
+            camera_model: None,
+            lens_model: None,
+            focal_length: None,
+            aperture: None,
+            shutter_speed: None,
+            iso: None,
+            flash: None,
+            date_taken: Some(date_taken),
+            gps_latitude: None,
+            gps_longitude: None,
+            orientation: None,
+            rating: None,
+        });
+        FileEntry {
+            file_type: FileType::from_path(&path),
+            name: path.file_name().unwrap().to_string_lossy().into_owned(),
+            path,
+            size: 3,
+            modified,
+            created: modified,
+            is_directory: false,
+            is_hidden: false,
+            permissions: FilePermissions::default(),
+            preview_metadata: Some(PreviewMetadata {
+                exif_data: exif,
+                ..PreviewMetadata::default()
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_organize_preview_prefers_exif_date_and_leaves_disk_alone() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().to_path_buf();
+        std::fs::write(root.join("shot.jpg"), b"one").unwrap();
+        std::fs::write(root.join("scan.jpg"), b"two").unwrap();
+
+        let day = |y, m, d| {
+            let date = chrono::NaiveDate::from_ymd_opt(y, m, d).unwrap().and_hms_opt(12, 0, 0).unwrap();
+            SystemTime::from(date.and_local_timezone(Local).unwrap())
+        };
+        let entries = vec![
+            entry_with_dates(root.join("scan.jpg"), day(2024, 5, 2), None),
+            entry_with_dates(root.join("shot.jpg"), day(2024, 5, 2), Some(day(2019, 8, 30))),
+        ];
+        let command = OrganizeCommand::new(
+            root.clone(),
+            OrganizeRule::ByDate {
+                format: "%Y/%m-%d".to_string(),
+            },
+        )
+        .with_entries(&entries);
+
+        assert_eq!(
+            command.planned_moves(),
+            [
+                OrganizeMove {
+                    source: root.join("scan.jpg"),
+                    destination: root.join("2024").join("05-02").join("scan.jpg"),
+                },
+                OrganizeMove {
+                    source: root.join("shot.jpg"),
+                    destination: root.join("2019").join("08-30").join("shot.jpg"),
+                },
+            ]
+        );
+        assert_eq!(command.description(), format!("Organize 2 files in {} by date", root.display()));
+        assert!(!root.join("2024").exists(), "planning must not touch the disk");
+        assert!(!root.join("2019").exists());
+    }
+
+    #[tokio::test]
+    async fn test_organize_by_type_executes_and_undoes_as_one_step() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().to_path_buf();
+        std::fs::create_dir_all(root.join("inbox")).unwrap();
+        std::fs::write(root.join("inbox").join("photo.jpg"), b"one").unwrap();
+        std::fs::write(root.join("song.mp3"), b"two").unwrap();
+        std::fs::create_dir_all(root.join("Audio")).unwrap();
+        let fs: Arc<dyn FileSystemService> = Arc::new(NativeFileSystemService::new());
+
+        let mut command = OrganizeCommand::new(root.clone(), OrganizeRule::ByType);
+        let preview = command.plan(fs.clone()).await.unwrap().to_vec();
+        assert_eq!(preview.len(), 2);
+        assert!(root.join("song.mp3").exists(), "planning must not move files");
+
+        command.execute(fs.clone()).await.unwrap();
+        assert!(root.join("Images").join("photo.jpg").exists());
+        assert!(root.join("Audio").join("song.mp3").exists());
+        assert!(!root.join("inbox").join("photo.jpg").exists());
+
+        command.undo(fs.clone()).await.unwrap();
+        assert_eq!(std::fs::read(root.join("inbox").join("photo.jpg")).unwrap(), b"one");
+        assert_eq!(std::fs::read(root.join("song.mp3")).unwrap(), b"two");
+        assert!(!root.join("Images").exists(), "folders the command created are removed");
+        assert!(root.join("Audio").is_dir(), "folders that existed before stay");
+        assert!(command.is_undone());
+    }
+}
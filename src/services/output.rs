@@ -0,0 +1,252 @@
+//! Output panel log and user-defined shell commands
+//!
+//! Commands run on a worker thread; their stdout and stderr are read line by
+//! line on two more threads and appended to an [`OutputLog`], which the UI
+//! polls. The log keeps a bounded number of lines so long-running jobs cannot
+//! grow it without limit.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::{Arc, RwLock};
+
+/// Lines kept by the Output tab
+pub const OUTPUT_SCROLLBACK: usize = 5000;
+
+/// Where a line of output came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+    /// Messages from the app itself (command started, job finished)
+    Info,
+}
+
+/// One line in the Output tab
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputLine {
+    /// Increases with every line, so the UI can tell when new output arrived
+    pub id: u64,
+    /// Command or job that wrote the line
+    pub source: String,
+    pub stream: OutputStream,
+    pub text: String,
+}
+
+#[derive(Debug, Default)]
+struct OutputState {
+    next_id: u64,
+    lines: VecDeque<OutputLine>,
+}
+
+/// Bounded, shared log of command and job output
+#[derive(Debug)]
+pub struct OutputLog {
+    state: RwLock<OutputState>,
+    capacity: usize,
+}
+
+impl Default for OutputLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OutputLog {
+    /// Create an empty log keeping [`OUTPUT_SCROLLBACK`] lines
+    pub fn new() -> Self {
+        Self::with_capacity(OUTPUT_SCROLLBACK)
+    }
+
+    /// Create an empty log keeping at most `capacity` lines
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            state: RwLock::new(OutputState::default()),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Append a line, dropping the oldest one when the log is full
+    pub fn push(&self, source: impl Into<String>, stream: OutputStream, text: impl Into<String>) {
+        let Ok(mut state) = self.state.write() else {
+            return;
+        };
+        state.next_id += 1;
+        let line = OutputLine {
+            id: state.next_id,
+            source: source.into(),
+            stream,
+            text: text.into(),
+        };
+        if state.lines.len() == self.capacity {
+            state.lines.pop_front();
+        }
+        state.lines.push_back(line);
+    }
+
+    /// Append a message from the app itself
+    pub fn info(&self, source: impl Into<String>, text: impl Into<String>) {
+        self.push(source, OutputStream::Info, text);
+    }
+
+    /// All kept lines, oldest first
+    pub fn lines(&self) -> Vec<OutputLine> {
+        self.state
+            .read()
+            .map(|state| state.lines.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Id of the newest line ever written, 0 if none
+    pub fn latest_id(&self) -> u64 {
+        self.state.read().map(|state| state.next_id).unwrap_or(0)
+    }
+
+    /// Remove all lines
+    pub fn clear(&self) {
+        if let Ok(mut state) = self.state.write() {
+            state.lines.clear();
+        }
+    }
+}
+
+/// A shell command the user can run from the command palette
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomCommand {
+    /// Name shown in the command palette and Output tab
+    pub name: String,
+    /// Command line passed to the system shell
+    pub command: String,
+}
+
+impl CustomCommand {
+    pub fn new(name: impl Into<String>, command: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            command: command.into(),
+        }
+    }
+}
+
+/// `sh -c <line>`, or `cmd /C <line>` on Windows
+pub fn shell_command(line: &str) -> Command {
+    #[cfg(windows)]
+    {
+        let mut command = Command::new("cmd");
+        command.arg("/C").arg(line);
+        command
+    }
+    #[cfg(not(windows))]
+    {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(line);
+        command
+    }
+}
+
+/// Run `custom` in `cwd`, streaming its output into `output`
+///
+/// Blocks until the command exits; call it from a worker thread.
+pub fn run_command(custom: &CustomCommand, cwd: &Path, output: &Arc<OutputLog>) -> io::Result<ExitStatus> {
+    output.info(&custom.name, format!("> {}", custom.command));
+    let spawned = shell_command(&custom.command)
+        .current_dir(cwd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(e) => {
+            output.push(&custom.name, OutputStream::Stderr, format!("Failed to start: {}", e));
+            return Err(e);
+        }
+    };
+
+    let readers = [
+        child.stdout.take().map(|stdout| stream_lines(stdout, &custom.name, OutputStream::Stdout, output)),
+        child.stderr.take().map(|stderr| stream_lines(stderr, &custom.name, OutputStream::Stderr, output)),
+    ];
+    let status = child.wait()?;
+    // The pipes close when the command exits, so the readers finish promptly
+    for reader in readers.into_iter().flatten() {
+        let _ = reader.join();
+    }
+
+    match status.code() {
+        Some(code) => output.info(&custom.name, format!("Exited with code {}", code)),
+        None => output.info(&custom.name, "Terminated by a signal"),
+    }
+    Ok(status)
+}
+
+/// Append every line read from `pipe` to `output` on a background thread
+pub fn stream_lines(
+    pipe: impl Read + Send + 'static,
+    source: &str,
+    stream: OutputStream,
+    output: &Arc<OutputLog>,
+) -> std::thread::JoinHandle<()> {
+    let source = source.to_string();
+    let output = output.clone();
+    std::thread::spawn(move || {
+        let mut reader = BufReader::new(pipe);
+        let mut buffer = Vec::new();
+        loop {
+            buffer.clear();
+            match reader.read_until(b'\n', &mut buffer) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    let text = String::from_utf8_lossy(&buffer);
+                    output.push(&source, stream, text.trim_end_matches(['\r', '\n']));
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_scrollback_is_bounded() {
+        let output = OutputLog::with_capacity(3);
+        for i in 0..5 {
+            output.info("test", format!("line {}", i));
+        }
+
+        let lines = output.lines();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].text, "line 2");
+        assert_eq!(output.latest_id(), 5);
+    }
+
+    #[test]
+    fn test_custom_command_output_reaches_output_log() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("marker.txt"), "").unwrap();
+        let output = Arc::new(OutputLog::new());
+        #[cfg(windows)]
+        let custom = CustomCommand::new("List", "dir /b & echo oops 1>&2");
+        #[cfg(not(windows))]
+        let custom = CustomCommand::new("List", "ls; echo oops >&2");
+
+        let status = run_command(&custom, temp_dir.path(), &output).unwrap();
+
+        assert!(status.success());
+        let lines = output.lines();
+        assert!(lines.iter().all(|line| line.source == "List"));
+        // Runs in the requested directory
+        assert!(lines
+            .iter()
+            .any(|line| line.stream == OutputStream::Stdout && line.text == "marker.txt"));
+        assert!(lines
+            .iter()
+            .any(|line| line.stream == OutputStream::Stderr && line.text.trim() == "oops"));
+        assert_eq!(lines.last().unwrap().text, "Exited with code 0");
+    }
+}
@@ -0,0 +1,122 @@
+//! Perceptual image hashes for finding visually similar images
+//!
+//! Uses a difference hash (dHash): a small grayscale thumbnail of the image is
+//! shrunk to 9x8 and each bit records whether a pixel is brighter than its right
+//! neighbour. Resizing and re-encoding barely change the hash, so copies of the
+//! same picture end up a small Hamming distance apart.
+
+use image::imageops::FilterType;
+use image::DynamicImage;
+use std::path::Path;
+
+/// Edge length of the thumbnail hashed instead of the full image
+const HASH_THUMBNAIL_SIZE: u32 = 64;
+
+/// Perceptual hash and dimensions of a decoded image
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageFingerprint {
+    pub hash: u64,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl ImageFingerprint {
+    /// Fingerprint an already decoded image
+    pub fn of_image(image: &DynamicImage) -> Self {
+        Self {
+            hash: dhash(image),
+            width: image.width(),
+            height: image.height(),
+        }
+    }
+
+    /// Decode the image at `path` and fingerprint it
+    pub fn from_path(path: &Path) -> Result<Self, image::ImageError> {
+        Ok(Self::of_image(&image::open(path)?))
+    }
+
+    /// Number of pixels in the original image
+    pub fn pixel_count(&self) -> u64 {
+        u64::from(self.width) * u64::from(self.height)
+    }
+
+    /// Hamming distance between the two hashes; 0 means visually identical
+    pub fn distance(&self, other: &Self) -> u32 {
+        hamming_distance(self.hash, other.hash)
+    }
+}
+
+/// 64-bit difference hash of `image`
+pub fn dhash(image: &DynamicImage) -> u64 {
+    let pixels = image
+        .thumbnail(HASH_THUMBNAIL_SIZE, HASH_THUMBNAIL_SIZE)
+        .resize_exact(9, 8, FilterType::Triangle)
+        .to_luma8();
+    let mut hash = 0u64;
+    for y in 0..8 {
+        for x in 0..8 {
+            let brighter = pixels.get_pixel(x, y)[0] > pixels.get_pixel(x + 1, y)[0];
+            hash = (hash << 1) | u64::from(brighter);
+        }
+    }
+    hash
+}
+
+/// Number of bits that differ between two hashes
+pub fn hamming_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::codecs::jpeg::JpegEncoder;
+    use image::{Rgb, RgbImage};
+    use tempfile::TempDir;
+
+    /// A picture with some structure: a diagonal gradient with a dark block
+    fn test_picture(width: u32, height: u32) -> RgbImage {
+        RgbImage::from_fn(width, height, |x, y| {
+            let in_block = x > width / 4 && x < width / 2 && y > height / 3 && y < height * 2 / 3;
+            if in_block {
+                Rgb([20, 30, 40])
+            } else {
+                let shade = ((x * 255 / width + y * 255 / height) / 2) as u8;
+                Rgb([shade, 255 - shade, shade / 2])
+            }
+        })
+    }
+
+    #[test]
+    fn test_resaved_jpeg_hashes_close_to_the_original() {
+        let dir = TempDir::new().unwrap();
+        let original_path = dir.path().join("original.png");
+        let original = test_picture(400, 300);
+        original.save(&original_path).unwrap();
+
+        // Half size and lossy, as if exported for the web
+        let resized = DynamicImage::ImageRgb8(original.clone()).resize(200, 150, FilterType::Lanczos3);
+        let resaved_path = dir.path().join("resaved.jpg");
+        let mut jpeg = Vec::new();
+        JpegEncoder::new_with_quality(&mut jpeg, 60).encode_image(&resized).unwrap();
+        std::fs::write(&resaved_path, jpeg).unwrap();
+
+        let original = ImageFingerprint::from_path(&original_path).unwrap();
+        let resaved = ImageFingerprint::from_path(&resaved_path).unwrap();
+        assert_eq!((original.width, original.height), (400, 300));
+        assert_eq!((resaved.width, resaved.height), (200, 150));
+        assert!(original.distance(&resaved) <= 4, "distance {}", original.distance(&resaved));
+
+        let flipped = ImageFingerprint::of_image(&DynamicImage::ImageRgb8(image::imageops::flip_horizontal(
+            &test_picture(400, 300),
+        )));
+        assert!(original.distance(&flipped) > 10, "distance {}", original.distance(&flipped));
+    }
+
+    #[test]
+    fn test_hamming_distance_counts_differing_bits() {
+        assert_eq!(hamming_distance(0, 0), 0);
+        assert_eq!(hamming_distance(0b1011, 0b0001), 2);
+        assert_eq!(hamming_distance(u64::MAX, 0), 64);
+    }
+}
@@ -5,6 +5,134 @@ use crate::services::preview::{
     PreviewProvider, PreviewHandler, PreviewData, PreviewConfig, PreviewError, 
     SupportedFormat, FileMetadata, PreviewContent
 };
+use serde::{Deserialize, Serialize};
+
+/// Most entries listed for one archive; the rest are only counted
+pub const MAX_LISTED_ARCHIVE_ENTRIES: usize = 500;
+
+/// One file or folder stored in an archive
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveEntry {
+    /// Path inside the archive, with `/` separators
+    pub name: String,
+    /// Uncompressed size in bytes
+    pub size: u64,
+    pub compressed_size: u64,
+    pub is_dir: bool,
+}
+
+/// The first entries of an archive and how many more there are
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveListing {
+    pub entries: Vec<ArchiveEntry>,
+    /// Entries past `MAX_LISTED_ARCHIVE_ENTRIES`
+    pub omitted: usize,
+}
+
+/// A folder or file in the tree built from an archive listing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveNode {
+    /// Last path component
+    pub name: String,
+    /// Full path inside the archive
+    pub path: String,
+    pub size: u64,
+    pub is_dir: bool,
+    pub children: Vec<ArchiveNode>,
+}
+
+/// List the entries of the zip archive at `path` without extracting anything
+///
+/// Only the central directory at the end of the file is read. Archives other
+/// than zip fail with `UnsupportedFormat`; password-protected and damaged ones
+/// fail with an `ArchiveError` saying so.
+pub fn list_archive_entries(path: &Path) -> Result<ArchiveListing, PreviewError> {
+    use zip::result::ZipError;
+
+    let is_zip = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("zip"));
+    if !is_zip {
+        return Err(PreviewError::UnsupportedFormat(format!(
+            "Listing {} is not supported",
+            path.display()
+        )));
+    }
+
+    let damaged = |e: ZipError| PreviewError::ArchiveError(format!("The archive is damaged: {}", e));
+    let file = std::fs::File::open(path)?;
+    let mut archive = zip::ZipArchive::new(std::io::BufReader::new(file)).map_err(damaged)?;
+
+    let total = archive.len();
+    let mut entries = Vec::with_capacity(total.min(MAX_LISTED_ARCHIVE_ENTRIES));
+    for index in 0..total.min(MAX_LISTED_ARCHIVE_ENTRIES) {
+        let entry = match archive.by_index(index) {
+            Ok(entry) => entry,
+            Err(ZipError::UnsupportedArchive(ZipError::PASSWORD_REQUIRED)) => {
+                return Err(PreviewError::ArchiveError(
+                    "The archive is password-protected".to_string(),
+                ));
+            }
+            Err(e) => return Err(damaged(e)),
+        };
+        entries.push(ArchiveEntry {
+            name: entry.name().to_string(),
+            size: entry.size(),
+            compressed_size: entry.compressed_size(),
+            is_dir: entry.is_dir(),
+        });
+    }
+
+    Ok(ArchiveListing {
+        entries,
+        omitted: total.saturating_sub(MAX_LISTED_ARCHIVE_ENTRIES),
+    })
+}
+
+/// Nest a flat listing into folders, folders first and then by name
+///
+/// Folders that only appear as part of a file's path get a node too.
+pub fn build_archive_tree(entries: &[ArchiveEntry]) -> Vec<ArchiveNode> {
+    let mut roots = Vec::new();
+    for entry in entries {
+        let components: Vec<&str> = entry.name.split('/').filter(|part| !part.is_empty()).collect();
+        let mut level = &mut roots;
+        for (depth, component) in components.iter().enumerate() {
+            let is_last = depth + 1 == components.len();
+            let is_dir = !is_last || entry.is_dir;
+            let position = match level
+                .iter()
+                .position(|node: &ArchiveNode| node.name == *component && node.is_dir == is_dir)
+            {
+                Some(position) => position,
+                None => {
+                    level.push(ArchiveNode {
+                        name: component.to_string(),
+                        path: components[..=depth].join("/"),
+                        size: 0,
+                        is_dir,
+                        children: Vec::new(),
+                    });
+                    level.len() - 1
+                }
+            };
+            if is_last && !is_dir {
+                level[position].size = entry.size;
+            }
+            level = &mut level[position].children;
+        }
+    }
+    sort_archive_nodes(&mut roots);
+    roots
+}
+
+fn sort_archive_nodes(nodes: &mut [ArchiveNode]) {
+    nodes.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase())));
+    for node in nodes {
+        sort_archive_nodes(&mut node.children);
+    }
+}
 
 /// Archive preview provider supporting zip, tar, and other archive formats
 pub struct ArchivePreviewProvider {
@@ -51,7 +179,7 @@ impl ArchivePreviewProvider {
         
         if let Some(ext) = file_path.extension().and_then(|e| e.to_str()) {
             match ext.to_lowercase().as_str() {
-                "zip" => contents.push("ZIP archive - contents extraction not implemented".to_string()),
+                "zip" => contents.push("ZIP archive - no entries to list".to_string()),
                 "tar" => contents.push("TAR archive - contents extraction not implemented".to_string()),
                 "gz" => contents.push("GZIP archive - contents extraction not implemented".to_string()),
                 "7z" => contents.push("7-Zip archive - contents extraction not implemented".to_string()),
@@ -180,12 +308,24 @@ impl PreviewProvider for ArchivePreviewProvider {
         // Extract metadata
         let metadata = Self::extract_archive_metadata(file_path)?;
         
+        // List the entries where the format allows it
+        let (entries, omitted_entries, listing_error) = match list_archive_entries(file_path) {
+            Ok(listing) => (listing.entries, listing.omitted, None),
+            Err(PreviewError::UnsupportedFormat(_)) => (Vec::new(), 0, None),
+            Err(PreviewError::ArchiveError(message)) => (Vec::new(), 0, Some(message)),
+            Err(e) => (Vec::new(), 0, Some(e.to_string())),
+        };
+        
         // Extract archive contents list
-        let contents = Self::extract_archive_contents(file_path)
-            .unwrap_or_else(|e| {
-                tracing::warn!("Failed to extract archive contents: {}", e);
-                vec!["Unable to extract archive contents".to_string()]
-            });
+        let contents = if entries.is_empty() {
+            Self::extract_archive_contents(file_path)
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Failed to extract archive contents: {}", e);
+                    vec!["Unable to extract archive contents".to_string()]
+                })
+        } else {
+            entries.iter().map(|entry| entry.name.clone()).collect()
+        };
         
         // Generate archive thumbnail
         let thumbnail = Self::create_archive_placeholder_thumbnail()?;
@@ -193,6 +333,9 @@ impl PreviewProvider for ArchivePreviewProvider {
         let preview_content = PreviewContent::Archive {
             contents,
             thumbnail,
+            entries,
+            omitted_entries,
+            listing_error,
         };
         
         Ok(PreviewData {
@@ -312,6 +455,83 @@ mod tests {
         assert!(!thumbnail_data.is_empty());
     }
 
+    /// A zip with a nested folder, an explicit folder entry and `extra` filler files
+    fn write_nested_zip(path: &Path, extra: usize) {
+        use std::io::Write;
+        use zip::write::FileOptions;
+
+        let mut writer = zip::ZipWriter::new(fs::File::create(path).unwrap());
+        let options = FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+        writer.add_directory("docs/", options).unwrap();
+        writer.start_file("docs/guides/intro.txt", options).unwrap();
+        writer.write_all(&[b'a'; 1000]).unwrap();
+        writer.start_file("readme.md", options).unwrap();
+        writer.write_all(b"hello").unwrap();
+        for index in 0..extra {
+            writer.start_file(format!("filler/{}.txt", index), options).unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    #[test]
+    fn test_list_nested_zip_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive_path = temp_dir.path().join("nested.zip");
+        write_nested_zip(&archive_path, 0);
+
+        let listing = list_archive_entries(&archive_path).unwrap();
+        assert_eq!(listing.omitted, 0);
+        let names: Vec<&str> = listing.entries.iter().map(|entry| entry.name.as_str()).collect();
+        assert_eq!(names, vec!["docs/", "docs/guides/intro.txt", "readme.md"]);
+        let intro = &listing.entries[1];
+        assert_eq!(intro.size, 1000);
+        assert!(intro.compressed_size < intro.size);
+        assert!(!intro.is_dir);
+        assert!(listing.entries[0].is_dir);
+
+        let tree = build_archive_tree(&listing.entries);
+        assert_eq!(tree.len(), 2);
+        assert_eq!((tree[0].name.as_str(), tree[0].is_dir), ("docs", true));
+        let guides = &tree[0].children[0];
+        assert_eq!((guides.path.as_str(), guides.is_dir), ("docs/guides", true));
+        assert_eq!(guides.children[0].size, 1000);
+        assert_eq!((tree[1].name.as_str(), tree[1].size), ("readme.md", 5));
+    }
+
+    #[test]
+    fn test_zip_listing_is_capped_and_reports_unreadable_archives() {
+        let temp_dir = TempDir::new().unwrap();
+        let large_path = temp_dir.path().join("large.zip");
+        write_nested_zip(&large_path, MAX_LISTED_ARCHIVE_ENTRIES);
+        let listing = list_archive_entries(&large_path).unwrap();
+        assert_eq!(listing.entries.len(), MAX_LISTED_ARCHIVE_ENTRIES);
+        assert_eq!(listing.omitted, 3);
+
+        // Setting the encryption flag is all it takes for entries to need a password
+        let locked_path = temp_dir.path().join("locked.zip");
+        write_nested_zip(&locked_path, 0);
+        let mut bytes = fs::read(&locked_path).unwrap();
+        for index in 0..bytes.len() - 4 {
+            match &bytes[index..index + 4] {
+                [0x50, 0x4b, 0x03, 0x04] => bytes[index + 6] |= 1,
+                [0x50, 0x4b, 0x01, 0x02] => bytes[index + 8] |= 1,
+                _ => {}
+            }
+        }
+        fs::write(&locked_path, bytes).unwrap();
+        match list_archive_entries(&locked_path) {
+            Err(PreviewError::ArchiveError(message)) => assert!(message.contains("password")),
+            other => panic!("Expected a password error, got {:?}", other),
+        }
+
+        let corrupt_path = temp_dir.path().join("corrupt.zip");
+        fs::write(&corrupt_path, b"PK\x03\x04 not really a zip").unwrap();
+        match list_archive_entries(&corrupt_path) {
+            Err(PreviewError::ArchiveError(message)) => assert!(message.contains("damaged")),
+            other => panic!("Expected a damaged archive error, got {:?}", other),
+        }
+    }
+
     #[tokio::test]
     async fn test_unsupported_archive_file() {
         let temp_dir = TempDir::new().unwrap();
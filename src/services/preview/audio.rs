@@ -6,6 +6,9 @@ use crate::services::preview::{
     PreviewProvider, PreviewHandler, PreviewData, PreviewConfig, PreviewError, 
     SupportedFormat, FileMetadata, PreviewContent
 };
+use crate::services::preview_cache::{
+    CachedPreviewData, PreviewCacheConfig, PreviewCacheKey, PreviewDataMetadata, ThreadSafePreviewCache
+};
 
 #[cfg(feature = "audio")]
 use rodio::Source;
@@ -14,6 +17,9 @@ use rodio::Source;
 pub struct AudioPreviewProvider {
     #[cfg(feature = "audio")]
     _initialized: bool,
+    /// Tags and waveforms already read
+    #[cfg(feature = "audio")]
+    cache: AudioPreviewCache,
 }
 
 impl AudioPreviewProvider {
@@ -22,6 +28,7 @@ impl AudioPreviewProvider {
         {
             Ok(Self {
                 _initialized: true,
+                cache: AudioPreviewCache::new(),
             })
         }
         
@@ -39,33 +46,8 @@ impl AudioPreviewProvider {
         use std::fs::File;
         use std::io::BufReader;
         
-        let mut metadata = FileMetadata::new();
-        
-        // Get file system metadata
-        let fs_metadata = std::fs::metadata(file_path)?;
-        metadata.file_size = fs_metadata.len();
-        metadata.created = fs_metadata.created().ok();
-        metadata.modified = fs_metadata.modified().ok();
-        
-        // Try to extract ID3 tags for MP3 files
-        if let Some(ext) = file_path.extension().and_then(|e| e.to_str()) {
-            if ext.to_lowercase() == "mp3" {
-                if let Ok(tag) = id3::Tag::read_from_path(file_path) {
-                    metadata.title = tag.title().map(|s| s.to_string());
-                    metadata.artist = tag.artist().map(|s| s.to_string());
-                    metadata.album = tag.album().map(|s| s.to_string());
-                    metadata.year = tag.year().map(|y| y as u32);
-                    
-                    // Extract additional metadata
-                    if let Some(duration) = tag.duration() {
-                        metadata.duration = Some(duration as f64);
-                    }
-                    
-                    tracing::debug!("Extracted ID3 tags from {}: title={:?}, artist={:?}", 
-                        file_path.display(), metadata.title, metadata.artist);
-                }
-            }
-        }
+        // Tags, and the stream details of WAV files, need no decoder
+        let mut metadata = read_audio_tags(file_path)?;
         
         // Try to get audio metadata using rodio for technical details
         let file = File::open(file_path)
@@ -214,69 +196,6 @@ impl AudioPreviewProvider {
         }
     }
 
-    #[cfg(feature = "audio")]
-    fn generate_waveform_data(
-        file_path: &Path, 
-        config: &PreviewConfig
-    ) -> Result<Vec<f32>, PreviewError> {
-        use std::fs::File;
-        use std::io::BufReader;
-        
-        let file = File::open(file_path)
-            .map_err(|e| PreviewError::AudioError(format!("Failed to open audio file: {}", e)))?;
-        let buf_reader = BufReader::new(file);
-        
-        let decoder = rodio::Decoder::new(buf_reader)
-            .map_err(|e| PreviewError::AudioError(format!("Failed to decode audio: {}", e)))?;
-        
-        let sample_rate = decoder.sample_rate() as usize;
-        let channels = decoder.channels() as usize;
-        
-        // Collect samples and convert from i16 to f32
-        let samples: Vec<f32> = decoder
-            .map(|sample| sample as f32 / i16::MAX as f32)
-            .collect();
-        
-        if samples.is_empty() {
-            return Ok(vec![0.0; config.audio_waveform_samples]);
-        }
-        
-        // Calculate how many samples to skip for downsampling
-        let total_samples = samples.len() / channels; // Mono samples
-        let target_samples = config.audio_waveform_samples;
-        
-        if total_samples <= target_samples {
-            // If we have fewer samples than target, just convert to mono
-            return Ok(Self::convert_to_mono(&samples, channels));
-        }
-        
-        let skip_factor = total_samples / target_samples;
-        let mut waveform = Vec::with_capacity(target_samples);
-        
-        // Downsample by taking every Nth sample and converting to mono
-        for i in 0..target_samples {
-            let sample_index = i * skip_factor * channels;
-            
-            if sample_index < samples.len() {
-                // Convert to mono by averaging channels
-                let mut sample_sum = 0.0;
-                for ch in 0..channels {
-                    if sample_index + ch < samples.len() {
-                        sample_sum += samples[sample_index + ch];
-                    }
-                }
-                waveform.push(sample_sum / channels as f32);
-            } else {
-                waveform.push(0.0);
-            }
-        }
-        
-        // Normalize waveform to [-1.0, 1.0] range
-        Self::normalize_waveform(&mut waveform);
-        
-        Ok(waveform)
-    }
-
     #[cfg(feature = "audio")]
     fn convert_to_mono(samples: &[f32], channels: usize) -> Vec<f32> {
         if channels == 1 {
@@ -300,7 +219,6 @@ impl AudioPreviewProvider {
         mono
     }
 
-    #[cfg(feature = "audio")]
     fn normalize_waveform(waveform: &mut [f32]) {
         if waveform.is_empty() {
             return;
@@ -331,12 +249,10 @@ impl AudioPreviewProvider {
     #[cfg(not(feature = "audio"))]
     fn extract_audio_metadata_fallback(file_path: &Path) -> Result<FileMetadata, PreviewError> {
         // Fallback implementation without audio processing
-        let fs_metadata = std::fs::metadata(file_path)?;
-        
-        let mut metadata = FileMetadata::new();
-        metadata.file_size = fs_metadata.len();
-        metadata.created = fs_metadata.created().ok();
-        metadata.modified = fs_metadata.modified().ok();
+        let mut metadata = read_audio_tags(file_path)?;
+        if metadata.sample_rate.is_some() {
+            return Ok(metadata);
+        }
         
         // Guess some common audio properties based on file extension
         if let Some(ext) = file_path.extension().and_then(|e| e.to_str()) {
@@ -344,22 +260,22 @@ impl AudioPreviewProvider {
                 "mp3" => {
                     metadata.codec = Some("MP3".to_string());
                     metadata.sample_rate = Some(44100); // Common default
-                    metadata.bit_rate = Some(128000); // 128 kbps common
+                    metadata.bit_rate.get_or_insert(128000); // 128 kbps common
                 }
                 "wav" => {
                     metadata.codec = Some("WAV".to_string());
                     metadata.sample_rate = Some(44100);
-                    metadata.bit_rate = Some(1411000); // 16-bit stereo
+                    metadata.bit_rate.get_or_insert(1411000); // 16-bit stereo
                 }
                 "flac" => {
                     metadata.codec = Some("FLAC".to_string());
                     metadata.sample_rate = Some(44100);
-                    metadata.bit_rate = Some(1000000); // Variable
+                    metadata.bit_rate.get_or_insert(1000000); // Variable
                 }
                 "ogg" => {
                     metadata.codec = Some("OGG Vorbis".to_string());
                     metadata.sample_rate = Some(44100);
-                    metadata.bit_rate = Some(192000);
+                    metadata.bit_rate.get_or_insert(192000);
                 }
                 _ => {
                     metadata.codec = Some("Unknown".to_string());
@@ -453,9 +369,338 @@ impl AudioPreviewProvider {
         
         dynamic_img.write_to(&mut cursor, ImageFormat::Png)
             .map_err(|e| PreviewError::AudioError(format!("Failed to encode waveform image: {}", e)))?;
-        
+
         Ok(buffer)
     }
+
+    /// Tags and stream details, with or without the decoder
+    fn read_metadata(file_path: &Path) -> Result<FileMetadata, PreviewError> {
+        #[cfg(feature = "audio")]
+        {
+            Self::extract_audio_metadata(file_path)
+        }
+
+        #[cfg(not(feature = "audio"))]
+        {
+            Self::extract_audio_metadata_fallback(file_path)
+        }
+    }
+
+    /// `width` waveform peaks of the file
+    ///
+    /// PCM WAV files are read directly; other formats need the decoder.
+    fn decode_waveform(file_path: &Path, width: usize) -> Result<Vec<f32>, PreviewError> {
+        if let Some(peaks) = wav_peaks(file_path, width)? {
+            return Ok(peaks);
+        }
+
+        #[cfg(feature = "audio")]
+        {
+            use std::io::BufReader;
+
+            let file = std::fs::File::open(file_path)?;
+            let decoder = rodio::Decoder::new(BufReader::new(file))
+                .map_err(|e| PreviewError::AudioError(format!("Failed to decode audio: {}", e)))?;
+            let channels = decoder.channels() as usize;
+            let samples: Vec<f32> = decoder.map(|sample| sample as f32 / i16::MAX as f32).collect();
+            let mut peaks = waveform_peaks(&Self::convert_to_mono(&samples, channels.max(1)), width);
+            Self::normalize_waveform(&mut peaks);
+            Ok(peaks)
+        }
+
+        #[cfg(not(feature = "audio"))]
+        {
+            Err(PreviewError::AudioError("Audio support not enabled. Enable the 'audio' feature.".to_string()))
+        }
+    }
+}
+
+/// Waveform columns drawn in the preview panel unless configured
+pub const DEFAULT_WAVEFORM_WIDTH: usize = 400;
+
+/// `content_type` of cached waveform peaks: little-endian `f32`s
+const WAVEFORM_PEAKS_CONTENT_TYPE: &str = "application/x-waveform-peaks";
+
+/// Tags of an audio file, plus what its header tells without decoding
+///
+/// ID3 tags are read from MP3, WAV and AIFF files, and WAV headers give the
+/// sample rate, duration and bit rate. Otherwise the bit rate is estimated from
+/// the file size and the tagged duration.
+pub fn read_audio_tags(file_path: &Path) -> Result<FileMetadata, PreviewError> {
+    let fs_metadata = std::fs::metadata(file_path)?;
+    let mut metadata = FileMetadata::new();
+    metadata.file_size = fs_metadata.len();
+    metadata.created = fs_metadata.created().ok();
+    metadata.modified = fs_metadata.modified().ok();
+
+    let extension = file_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_lowercase)
+        .unwrap_or_default();
+    let tag = match extension.as_str() {
+        "wav" => id3::Tag::read_from_wav_path(file_path),
+        "aiff" | "aif" => id3::Tag::read_from_aiff_path(file_path),
+        _ => id3::Tag::read_from_path(file_path),
+    };
+    if let Ok(tag) = tag {
+        metadata.title = tag.title().map(str::to_string);
+        metadata.artist = tag.artist().map(str::to_string);
+        metadata.album = tag.album().map(str::to_string);
+        metadata.year = tag.year().and_then(|year| u32::try_from(year).ok());
+        // TLEN is in milliseconds
+        metadata.duration = tag.duration().map(|millis| millis as f64 / 1000.0);
+    }
+
+    if let Some(wav) = WavHeader::read(file_path)? {
+        metadata.codec = Some("WAV".to_string());
+        metadata.sample_rate = Some(wav.sample_rate);
+        metadata.bit_rate = Some(wav.bit_rate());
+        metadata.duration = Some(wav.duration());
+    } else if let Some(duration) = metadata.duration.filter(|duration| *duration > 0.0) {
+        metadata.bit_rate = Some((metadata.file_size as f64 * 8.0 / duration) as u32);
+    }
+    Ok(metadata)
+}
+
+/// Peak amplitude of each of `width` equal slices of `samples`
+pub fn waveform_peaks(samples: &[f32], width: usize) -> Vec<f32> {
+    let mut peaks = vec![0.0; width];
+    if samples.is_empty() || width == 0 {
+        return peaks;
+    }
+    for (index, sample) in samples.iter().enumerate() {
+        let column = index * width / samples.len();
+        peaks[column] = peaks[column].max(sample.abs());
+    }
+    peaks
+}
+
+/// The parts of a WAV header needed to read its PCM samples
+struct WavHeader {
+    /// 1 for integer PCM, 3 for float
+    format_tag: u16,
+    channels: u16,
+    sample_rate: u32,
+    bits_per_sample: u16,
+    data_offset: u64,
+    data_len: u64,
+}
+
+impl WavHeader {
+    /// The header of a RIFF/WAVE file, or `None` for anything else
+    fn read(file_path: &Path) -> Result<Option<Self>, PreviewError> {
+        use std::io::{Read, Seek, SeekFrom};
+
+        let mut file = std::fs::File::open(file_path)?;
+        let mut riff = [0u8; 12];
+        if file.read_exact(&mut riff).is_err() || &riff[0..4] != b"RIFF" || &riff[8..12] != b"WAVE" {
+            return Ok(None);
+        }
+
+        let mut format = None;
+        let mut offset = 12u64;
+        let mut chunk = [0u8; 8];
+        while file.read_exact(&mut chunk).is_ok() {
+            let size = u32::from_le_bytes([chunk[4], chunk[5], chunk[6], chunk[7]]) as u64;
+            offset += 8;
+            match &chunk[0..4] {
+                b"fmt " if size >= 16 => {
+                    let mut fmt = [0u8; 16];
+                    file.read_exact(&mut fmt)?;
+                    format = Some((
+                        u16::from_le_bytes([fmt[0], fmt[1]]),
+                        u16::from_le_bytes([fmt[2], fmt[3]]),
+                        u32::from_le_bytes([fmt[4], fmt[5], fmt[6], fmt[7]]),
+                        u16::from_le_bytes([fmt[14], fmt[15]]),
+                    ));
+                }
+                b"data" => {
+                    let Some((format_tag, channels, sample_rate, bits_per_sample)) = format else {
+                        return Ok(None);
+                    };
+                    if channels == 0 || sample_rate == 0 || bits_per_sample == 0 {
+                        return Ok(None);
+                    }
+                    let available = file.metadata()?.len().saturating_sub(offset);
+                    return Ok(Some(Self {
+                        format_tag,
+                        channels,
+                        sample_rate,
+                        bits_per_sample,
+                        data_offset: offset,
+                        data_len: size.min(available),
+                    }));
+                }
+                _ => {}
+            }
+            // Chunks are padded to an even length
+            offset += size + (size & 1);
+            file.seek(SeekFrom::Start(offset))?;
+        }
+        Ok(None)
+    }
+
+    fn bytes_per_sample(&self) -> u64 {
+        u64::from(self.bits_per_sample).div_ceil(8)
+    }
+
+    fn frame_count(&self) -> u64 {
+        self.data_len / (self.bytes_per_sample() * u64::from(self.channels))
+    }
+
+    fn duration(&self) -> f64 {
+        self.frame_count() as f64 / f64::from(self.sample_rate)
+    }
+
+    fn bit_rate(&self) -> u32 {
+        self.sample_rate * u32::from(self.channels) * u32::from(self.bits_per_sample)
+    }
+
+    /// One sample scaled to -1.0..=1.0
+    fn sample(&self, bytes: &[u8]) -> Option<f32> {
+        match (self.format_tag, bytes.len()) {
+            (1, 1) => Some((f32::from(bytes[0]) - 128.0) / 128.0),
+            (1, 2) => Some(f32::from(i16::from_le_bytes([bytes[0], bytes[1]])) / 32768.0),
+            (1, 3) => Some(i32::from_le_bytes([0, bytes[0], bytes[1], bytes[2]]) as f32 / 2_147_483_648.0),
+            (1, 4) => Some(i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f32 / 2_147_483_648.0),
+            (3, 4) => Some(f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])),
+            _ => None,
+        }
+    }
+}
+
+/// `width` normalized peaks of a PCM WAV file, streamed without loading it
+/// whole; `None` for anything that isn't one
+fn wav_peaks(file_path: &Path, width: usize) -> Result<Option<Vec<f32>>, PreviewError> {
+    use std::io::{BufReader, Read, Seek, SeekFrom};
+
+    let Some(header) = WavHeader::read(file_path)? else {
+        return Ok(None);
+    };
+    let sample_len = header.bytes_per_sample() as usize;
+    let frame_len = sample_len * usize::from(header.channels);
+    if header.sample(&vec![0; sample_len]).is_none() {
+        return Ok(None);
+    }
+
+    let mut file = std::fs::File::open(file_path)?;
+    file.seek(SeekFrom::Start(header.data_offset))?;
+    let mut reader = BufReader::new(file.take(header.data_len));
+    let frames = header.frame_count().max(1);
+    let mut peaks = vec![0.0f32; width];
+    let mut frame = vec![0u8; frame_len];
+    let mut index = 0u64;
+    while width > 0 && reader.read_exact(&mut frame).is_ok() {
+        let column = (index * width as u64 / frames) as usize;
+        for bytes in frame.chunks_exact(sample_len) {
+            let amplitude = header.sample(bytes).unwrap_or(0.0).abs();
+            peaks[column] = peaks[column].max(amplitude);
+        }
+        index += 1;
+    }
+    AudioPreviewProvider::normalize_waveform(&mut peaks);
+    Ok(Some(peaks))
+}
+
+/// Tags and waveforms of audio files, read once per file version
+///
+/// Both are kept in a [`ThreadSafePreviewCache`], so a file is read again only
+/// after it changes on disk.
+#[derive(Clone)]
+pub struct AudioPreviewCache {
+    tags: ThreadSafePreviewCache,
+    waveforms: ThreadSafePreviewCache,
+    waveform_width: usize,
+}
+
+impl AudioPreviewCache {
+    pub fn new() -> Self {
+        Self {
+            tags: ThreadSafePreviewCache::new(PreviewCacheConfig::default()),
+            waveforms: ThreadSafePreviewCache::new(PreviewCacheConfig::default()),
+            waveform_width: DEFAULT_WAVEFORM_WIDTH,
+        }
+    }
+
+    /// Compute waveforms with `width` columns
+    pub fn with_waveform_width(mut self, width: usize) -> Self {
+        self.waveform_width = width.max(1);
+        self
+    }
+
+    /// Tags and stream details of the file
+    pub fn metadata(&self, file_path: &Path) -> Result<FileMetadata, PreviewError> {
+        let key = PreviewCacheKey::from_path(file_path)?;
+        if let Some(metadata) = self
+            .tags
+            .get(&key)
+            .and_then(|cached| serde_json::from_slice(&cached.data).ok())
+        {
+            return Ok(metadata);
+        }
+
+        let metadata = AudioPreviewProvider::read_metadata(file_path)?;
+        let data = serde_json::to_vec(&metadata).map_err(|e| PreviewError::MetadataError(e.to_string()))?;
+        let mut details = PreviewDataMetadata::new();
+        details.duration = metadata.duration;
+        let cached = CachedPreviewData::new(
+            data,
+            "application/json".to_string(),
+            "audio-tags".to_string(),
+            metadata.file_size,
+            details,
+        );
+        if let Err(e) = self.tags.put(key, cached) {
+            tracing::warn!("Failed to cache audio tags for {:?}: {}", file_path, e);
+        }
+        Ok(metadata)
+    }
+
+    /// Peak amplitude of each waveform column, scaled so the loudest is 1.0
+    pub fn waveform(&self, file_path: &Path) -> Result<Vec<f32>, PreviewError> {
+        let key = PreviewCacheKey::from_path(file_path)?;
+        if let Some(cached) = self.waveforms.get(&key) {
+            return Ok(cached
+                .data
+                .chunks_exact(4)
+                .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+                .collect());
+        }
+
+        let peaks = AudioPreviewProvider::decode_waveform(file_path, self.waveform_width)?;
+        let data = peaks.iter().flat_map(|peak| peak.to_le_bytes()).collect();
+        let mut details = PreviewDataMetadata::new();
+        details.width = Some(peaks.len() as u32);
+        let original_size = std::fs::metadata(file_path).map(|metadata| metadata.len()).unwrap_or(0);
+        let cached = CachedPreviewData::new(
+            data,
+            WAVEFORM_PEAKS_CONTENT_TYPE.to_string(),
+            "waveform".to_string(),
+            original_size,
+            details,
+        );
+        if let Err(e) = self.waveforms.put(key, cached) {
+            tracing::warn!("Failed to cache waveform for {:?}: {}", file_path, e);
+        }
+        Ok(peaks)
+    }
+
+    /// The waveform drawn as a PNG of `size`, mirrored around the center line
+    pub fn waveform_image(&self, file_path: &Path, size: (u32, u32)) -> Result<Vec<u8>, PreviewError> {
+        let mirrored: Vec<f32> = self
+            .waveform(file_path)?
+            .into_iter()
+            .flat_map(|peak| [peak, -peak])
+            .collect();
+        AudioPreviewProvider::render_waveform_image(&mirrored, size)
+    }
+}
+
+impl Default for AudioPreviewCache {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[async_trait]
@@ -489,10 +734,10 @@ impl PreviewProvider for AudioPreviewProvider {
         #[cfg(feature = "audio")]
         {
             // Extract metadata
-            let metadata = Self::extract_audio_metadata(file_path)?;
+            let metadata = self.cache.metadata(file_path)?;
             
             // Generate waveform data
-            let waveform_data = Self::generate_waveform_data(file_path, config)
+            let waveform_data = self.cache.waveform(file_path)
                 .unwrap_or_else(|e| {
                     tracing::warn!("Failed to generate waveform for {}: {}", file_path.display(), e);
                     Self::generate_waveform_data_fallback(config)
@@ -558,11 +803,10 @@ impl PreviewProvider for AudioPreviewProvider {
         // For audio files, generate a simple waveform visualization as thumbnail
         #[cfg(feature = "audio")]
         {
-            let config = PreviewConfig::default();
-            let waveform = Self::generate_waveform_data(file_path, &config)
-                .unwrap_or_else(|_| Self::generate_waveform_data_fallback(&config));
-            
-            Self::render_waveform_image(&waveform, size)
+            self.cache.waveform_image(file_path, size).or_else(|_| {
+                let config = PreviewConfig::default();
+                Self::render_waveform_image(&Self::generate_waveform_data_fallback(&config), size)
+            })
         }
         
         #[cfg(not(feature = "audio"))]
@@ -610,10 +854,10 @@ impl PreviewHandler for AudioPreviewProvider {
         #[cfg(feature = "audio")]
         {
             // Extract metadata
-            let metadata = Self::extract_audio_metadata(file_path)?;
+            let metadata = self.cache.metadata(file_path)?;
             
             // Generate waveform data
-            let waveform_data = Self::generate_waveform_data(file_path, config)
+            let waveform_data = self.cache.waveform(file_path)
                 .unwrap_or_else(|e| {
                     tracing::warn!("Failed to generate waveform for {}: {}", file_path.display(), e);
                     Self::generate_waveform_data_fallback(config)
@@ -679,11 +923,10 @@ impl PreviewHandler for AudioPreviewProvider {
         // For audio files, generate a simple waveform visualization as thumbnail
         #[cfg(feature = "audio")]
         {
-            let config = PreviewConfig::default();
-            let waveform = Self::generate_waveform_data(file_path, &config)
-                .unwrap_or_else(|_| Self::generate_waveform_data_fallback(&config));
-            
-            Self::render_waveform_image(&waveform, size)
+            self.cache.waveform_image(file_path, size).or_else(|_| {
+                let config = PreviewConfig::default();
+                Self::render_waveform_image(&Self::generate_waveform_data_fallback(&config), size)
+            })
         }
         
         #[cfg(not(feature = "audio"))]
@@ -782,6 +1025,76 @@ mod tests {
         }
     }
 
+    /// A mono 16-bit PCM WAV file
+    fn write_wav(path: &Path, sample_rate: u32, samples: &[i16]) {
+        let data_len = (samples.len() * 2) as u32;
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+        bytes.extend_from_slice(b"WAVEfmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&sample_rate.to_le_bytes());
+        bytes.extend_from_slice(&(sample_rate * 2).to_le_bytes());
+        bytes.extend_from_slice(&2u16.to_le_bytes());
+        bytes.extend_from_slice(&16u16.to_le_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&data_len.to_le_bytes());
+        for sample in samples {
+            bytes.extend_from_slice(&sample.to_le_bytes());
+        }
+        fs::write(path, bytes).unwrap();
+    }
+
+    #[test]
+    fn test_reads_tags_and_stream_details_of_a_tagged_wav() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("song.wav");
+        write_wav(&path, 8000, &vec![1000; 8000]);
+        let mut tag = id3::Tag::new();
+        tag.set_title("Morning");
+        tag.set_artist("The Testers");
+        tag.set_album("Fixtures");
+        tag.write_to_wav_path(&path, id3::Version::Id3v24).unwrap();
+
+        let metadata = read_audio_tags(&path).unwrap();
+        assert_eq!(metadata.title.as_deref(), Some("Morning"));
+        assert_eq!(metadata.artist.as_deref(), Some("The Testers"));
+        assert_eq!(metadata.album.as_deref(), Some("Fixtures"));
+        assert_eq!(metadata.sample_rate, Some(8000));
+        assert_eq!(metadata.bit_rate, Some(128_000));
+        assert_eq!(metadata.duration, Some(1.0));
+
+        let cache = AudioPreviewCache::new();
+        assert_eq!(cache.metadata(&path).unwrap().title.as_deref(), Some("Morning"));
+        assert_eq!(cache.tags.stats().entries, 1);
+        assert_eq!(cache.metadata(&path).unwrap().album.as_deref(), Some("Fixtures"));
+    }
+
+    #[test]
+    fn test_waveform_length_follows_the_configured_width() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("ramp.wav");
+        // Loud first half, quiet second half
+        let samples: Vec<i16> = (0..4000).map(|i| if i < 2000 { 20_000 } else { 5_000 }).collect();
+        write_wav(&path, 4000, &samples);
+
+        for width in [100, 250] {
+            let peaks = AudioPreviewCache::new().with_waveform_width(width).waveform(&path).unwrap();
+            assert_eq!(peaks.len(), width);
+            assert_eq!(peaks[0], 1.0);
+            assert!((peaks[width - 1] - 0.25).abs() < 1e-3);
+        }
+
+        let cache = AudioPreviewCache::new().with_waveform_width(50);
+        let image = cache.waveform_image(&path, (100, 40)).unwrap();
+        assert!(!image.is_empty());
+        assert_eq!(cache.waveform(&path).unwrap().len(), 50);
+        assert_eq!(cache.waveforms.stats().entries, 1);
+        assert_eq!(waveform_peaks(&[0.5, -1.0, 0.25], 6).len(), 6);
+    }
+
     #[cfg(feature = "audio")]
     #[test]
     fn test_mono_conversion() {
@@ -7,6 +7,10 @@ use tokio::task::JoinHandle;
 use chrono::Utc;
 use walkdir::WalkDir;
 
+use crate::services::file_system::{
+    content_sniffing, AudioFormat, ContentSniffing, DocumentFormat, FileType, ImageFormat, TextFormat, VideoFormat,
+};
+
 /// Comprehensive preview service for multi-format file support
 /// Supports images, videos, audio, PDFs, and text files with metadata extraction
 
@@ -139,6 +143,68 @@ impl SupportedFormat {
         }
     }
 
+    /// Format of a file type recognised by the file system service
+    pub fn from_file_type(file_type: &FileType) -> Option<Self> {
+        match file_type {
+            FileType::Directory => None,
+            FileType::Image(format) => Some(match format {
+                ImageFormat::Jpeg => Self::Jpeg,
+                ImageFormat::Png => Self::Png,
+                ImageFormat::Gif => Self::Gif,
+                ImageFormat::WebP => Self::WebP,
+                ImageFormat::Tiff => Self::Tiff,
+                ImageFormat::Bmp => Self::Bmp,
+                ImageFormat::Svg => Self::Svg,
+            }),
+            FileType::Video(format) => Some(match format {
+                VideoFormat::Mp4 => Self::Mp4,
+                VideoFormat::Avi => Self::Avi,
+                VideoFormat::Mov => Self::Mov,
+                VideoFormat::Wmv => Self::Wmv,
+                VideoFormat::Mkv => Self::Mkv,
+                VideoFormat::WebM => Self::WebM,
+            }),
+            FileType::Audio(format) => Some(match format {
+                AudioFormat::Mp3 => Self::Mp3,
+                AudioFormat::Wav => Self::Wav,
+                AudioFormat::Flac => Self::Flac,
+                AudioFormat::Aac => Self::Aac,
+                AudioFormat::Ogg => Self::Ogg,
+            }),
+            FileType::Document(DocumentFormat::Pdf) => Some(Self::Pdf),
+            FileType::Document(DocumentFormat::Txt) => Some(Self::Text),
+            FileType::Document(DocumentFormat::Md) => Some(Self::Markdown),
+            FileType::Document(_) => None,
+            FileType::Text(format) => Some(match format {
+                TextFormat::Plain => Self::Text,
+                TextFormat::Markdown => Self::Markdown,
+                TextFormat::Json => Self::Json,
+                TextFormat::Xml => Self::Xml,
+                TextFormat::Html => Self::Html,
+                TextFormat::Css => Self::Css,
+                TextFormat::JavaScript => Self::Javascript,
+                TextFormat::Rust => Self::Rust,
+                TextFormat::Python => Self::Python,
+            }),
+            FileType::Other(extension) => Self::from_extension(extension),
+        }
+    }
+
+    /// Detect the format of the file at `path`, consulting its header as `sniffing` allows
+    pub fn detect(path: &Path, sniffing: ContentSniffing) -> Option<Self> {
+        let by_extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(Self::from_extension);
+        let detected = FileType::detect(path, sniffing);
+        if detected == FileType::from_path(path) {
+            // The extension stood; it may name formats `FileType` does not know
+            by_extension
+        } else {
+            Self::from_file_type(&detected).or(by_extension)
+        }
+    }
+
     /// Check if format is an image type
     pub fn is_image(&self) -> bool {
         matches!(self, Self::Jpeg | Self::Png | Self::Gif | Self::WebP | Self::Tiff | Self::Bmp | Self::Svg)
@@ -284,6 +350,15 @@ pub enum PreviewContent {
         contents: Vec<String>,
         /// Archive thumbnail/icon
         thumbnail: Vec<u8>,
+        /// Entries read from the archive's directory, at most `MAX_LISTED_ARCHIVE_ENTRIES`
+        #[serde(default)]
+        entries: Vec<crate::services::preview::archive::ArchiveEntry>,
+        /// Entries left out of `entries` to keep the listing short
+        #[serde(default)]
+        omitted_entries: usize,
+        /// Why the entries couldn't be listed, e.g. a password-protected or damaged archive
+        #[serde(default)]
+        listing_error: Option<String>,
     },
     /// Fallback content for unsupported file types
     Unsupported {
@@ -606,6 +681,36 @@ impl PreviewTaskQueue {
     }
 }
 
+/// Outcome counts for one preview provider
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProviderStats {
+    /// Previews this provider served
+    pub successes: u64,
+    /// Attempts that failed and moved on to the next provider
+    pub errors: u64,
+    pub last_error: Option<String>,
+}
+
+/// Snapshot of how the provider chain has been doing
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PreviewHealth {
+    /// Counts by provider id
+    pub providers: std::collections::BTreeMap<&'static str, ProviderStats>,
+    /// Provider that served the most recent preview
+    pub last_served_by: Option<&'static str>,
+}
+
+impl PreviewHealth {
+    pub fn stats(&self, provider_id: &str) -> ProviderStats {
+        self.providers.get(provider_id).cloned().unwrap_or_default()
+    }
+
+    /// Previews that only the fallback provider could serve
+    pub fn fallbacks_served(&self) -> u64 {
+        self.stats("fallback").successes
+    }
+}
+
 /// Main preview service coordinating all preview providers
 pub struct PreviewService {
     providers: Vec<Box<dyn PreviewProvider>>,
@@ -613,6 +718,7 @@ pub struct PreviewService {
     config: PreviewConfig,
     cache_service: Option<crate::services::cache::CacheService>,
     task_queue: std::sync::Arc<std::sync::Mutex<PreviewTaskQueue>>,
+    health: std::sync::Arc<std::sync::Mutex<PreviewHealth>>,
 }
 
 impl PreviewService {
@@ -624,6 +730,7 @@ impl PreviewService {
             config: PreviewConfig::default(),
             cache_service: None,
             task_queue: std::sync::Arc::new(std::sync::Mutex::new(PreviewTaskQueue::new(8))), // Max 8 concurrent tasks
+            health: Default::default(),
         }
     }
     
@@ -636,6 +743,7 @@ impl PreviewService {
             config,
             cache_service: None,
             task_queue: std::sync::Arc::new(std::sync::Mutex::new(PreviewTaskQueue::new(max_concurrent))),
+            health: Default::default(),
         }
     }
     
@@ -722,14 +830,113 @@ impl PreviewService {
             .filter(|provider| provider.supports_format(format))
             .max_by_key(|provider| provider.priority())
     }
+
+    /// Providers to try for `format`, best first, ending with the fallback
+    ///
+    /// The fallback provider is always last however it is prioritised, and
+    /// is the only entry when the format is unknown.
+    pub fn provider_chain(&self, format: Option<SupportedFormat>) -> Vec<&dyn PreviewProvider> {
+        let mut chain: Vec<&dyn PreviewProvider> = match format {
+            Some(format) => self
+                .providers
+                .iter()
+                .filter(|provider| provider.provider_id() != "fallback" && provider.supports_format(format))
+                .map(|provider| provider.as_ref())
+                .collect(),
+            None => Vec::new(),
+        };
+        chain.sort_by_key(|provider| std::cmp::Reverse(provider.priority()));
+        chain.extend(
+            self.providers
+                .iter()
+                .filter(|provider| provider.provider_id() == "fallback")
+                .map(|provider| provider.as_ref()),
+        );
+        chain
+    }
+
+    /// Per-provider success and error counts so far
+    pub fn health(&self) -> PreviewHealth {
+        self.health.lock().map(|health| health.clone()).unwrap_or_default()
+    }
+
+    fn record_success(&self, provider_id: &'static str) {
+        if let Ok(mut health) = self.health.lock() {
+            health.providers.entry(provider_id).or_default().successes += 1;
+            health.last_served_by = Some(provider_id);
+        }
+    }
+
+    fn record_error(&self, provider_id: &'static str, error: &PreviewError) {
+        if let Ok(mut health) = self.health.lock() {
+            let stats = health.providers.entry(provider_id).or_default();
+            stats.errors += 1;
+            stats.last_error = Some(error.to_string());
+        }
+    }
+
+    /// Try each provider in the chain until one produces a preview
+    ///
+    /// Legacy handlers are tried after the format-specific providers and
+    /// before the fallback. Returns the last error if nothing succeeds.
+    async fn generate_with_chain(&self, path: &Path, format: Option<SupportedFormat>) -> Result<PreviewData, PreviewError> {
+        let chain = self.provider_chain(format);
+        let (specific, fallback): (Vec<_>, Vec<_>) = chain
+            .into_iter()
+            .partition(|provider| provider.provider_id() != "fallback");
+        let mut last_error = None;
+
+        for provider in specific {
+            match provider.generate_preview(path, &self.config).await {
+                Ok(preview) => {
+                    self.record_success(provider.provider_id());
+                    return Ok(preview);
+                }
+                Err(error) => {
+                    tracing::warn!("{} failed for {:?}, trying the next provider: {}", provider.provider_name(), path, error);
+                    self.record_error(provider.provider_id(), &error);
+                    last_error = Some(error);
+                }
+            }
+        }
+
+        if let Some(format) = format {
+            for handler in self.legacy_handlers.iter().filter(|handler| handler.supports_format(format)) {
+                match handler.generate_preview(path, &self.config).await {
+                    Ok(preview) => {
+                        self.record_success("legacy");
+                        return Ok(preview);
+                    }
+                    Err(error) => {
+                        self.record_error("legacy", &error);
+                        last_error = Some(error);
+                    }
+                }
+            }
+        }
+
+        for provider in fallback {
+            tracing::debug!("Using fallback preview for {:?}", path);
+            match provider.generate_preview(path, &self.config).await {
+                Ok(preview) => {
+                    self.record_success(provider.provider_id());
+                    return Ok(preview);
+                }
+                Err(error) => {
+                    self.record_error(provider.provider_id(), &error);
+                    last_error = Some(error);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            PreviewError::UnsupportedFormat(format!("No fallback provider available for file: {:?}", path))
+        }))
+    }
     
-    /// Detect file format from path
+    /// Detect file format from path, and from the header per the content sniffing setting
     pub fn detect_format<P: AsRef<Path>>(&self, file_path: P) -> Option<SupportedFormat> {
-        file_path
-            .as_ref()
-            .extension()
-            .and_then(|ext| ext.to_str())
-            .and_then(SupportedFormat::from_extension)
+        SupportedFormat::detect(file_path.as_ref(), content_sniffing())
     }
     
     /// Check if file format is supported
@@ -819,24 +1026,7 @@ impl PreviewService {
         }
         
         // Generate preview using provider system with fallback support
-        let preview_data = if let Some(format) = format_option {
-            // Format detected - try format-specific providers first
-            if let Some(provider) = self.find_provider_for_format(format) {
-                provider.generate_preview(path, &self.config).await?
-            } else if let Some(handler) = self.legacy_handlers.iter()
-                .find(|handler| handler.supports_format(format)) {
-                // Fallback to legacy handlers
-                handler.generate_preview(path, &self.config).await?
-            } else {
-                // No provider for this format - use fallback provider
-                tracing::debug!("No provider found for format {:?}, using fallback", format);
-                self.generate_fallback_preview(path).await?
-            }
-        } else {
-            // Format not detected - use fallback provider directly
-            tracing::debug!("Unknown file format for {:?}, using fallback", path);
-            self.generate_fallback_preview(path).await?
-        };
+        let preview_data = self.generate_with_chain(path, format_option).await?;
         
         // Enhanced caching with performance optimizations
         if self.config.cache_thumbnails {
@@ -987,20 +1177,6 @@ impl PreviewService {
         queue.cleanup_finished_tasks();
     }
     
-    /// Generate fallback preview using the fallback provider
-    async fn generate_fallback_preview(&self, path: &Path) -> Result<PreviewData, PreviewError> {
-        // Find the fallback provider (lowest priority)
-        if let Some(fallback_provider) = self.providers.iter()
-            .find(|provider| provider.provider_id() == "fallback") {
-            fallback_provider.generate_preview(path, &self.config).await
-        } else {
-            // If no fallback provider is registered, create a basic error response
-            Err(PreviewError::UnsupportedFormat(
-                format!("No fallback provider available for file: {:?}", path)
-            ))
-        }
-    }
-    
     /// Helper method to extract basic file metadata
     async fn extract_file_metadata(&self, path: &Path) -> Result<FileMetadata, PreviewError> {
         let metadata = std::fs::metadata(path)?;
@@ -1212,6 +1388,7 @@ impl PreviewService {
             config: self.config.clone(),
             cache_service: self.cache_service.clone(),
             task_queue: self.task_queue.clone(), // Shared task queue
+            health: self.health.clone(),
         }
     }
 }
@@ -1284,6 +1461,102 @@ mod tests {
         assert!(!service.is_supported(&image_path)); // No handlers registered yet
     }
     
+    #[test]
+    fn test_format_detection_from_contents() {
+        let temp_dir = TempDir::new().unwrap();
+        let video_as_txt = temp_dir.path().join("clip.txt");
+        fs::write(&video_as_txt, b"\0\0\0\x18ftypisom\0\0\x02\0").unwrap();
+        let gzip_without_extension = temp_dir.path().join("backup");
+        fs::write(&gzip_without_extension, [0x1F, 0x8B, 0x08, 0x00]).unwrap();
+        
+        assert_eq!(SupportedFormat::detect(&video_as_txt, ContentSniffing::Fallback), Some(SupportedFormat::Text));
+        assert_eq!(SupportedFormat::detect(&video_as_txt, ContentSniffing::PreferContent), Some(SupportedFormat::Mp4));
+        assert_eq!(SupportedFormat::detect(&gzip_without_extension, ContentSniffing::Off), None);
+        assert_eq!(SupportedFormat::detect(&gzip_without_extension, ContentSniffing::Fallback), Some(SupportedFormat::Gz));
+    }
+    
+    /// Stands in for a provider whose decoder is missing at runtime
+    struct FailingProvider;
+
+    #[async_trait]
+    impl PreviewProvider for FailingProvider {
+        fn provider_id(&self) -> &'static str {
+            "failing"
+        }
+
+        fn provider_name(&self) -> &'static str {
+            "Failing Provider"
+        }
+
+        fn supports_format(&self, _format: SupportedFormat) -> bool {
+            true
+        }
+
+        fn supported_extensions(&self) -> Vec<&'static str> {
+            vec!["txt"]
+        }
+
+        fn priority(&self) -> u32 {
+            200
+        }
+
+        async fn generate_preview(&self, _file_path: &Path, _config: &PreviewConfig) -> Result<PreviewData, PreviewError> {
+            Err(PreviewError::VideoError("decoder not available".to_string()))
+        }
+
+        async fn extract_metadata(&self, _file_path: &Path) -> Result<FileMetadata, PreviewError> {
+            Err(PreviewError::VideoError("decoder not available".to_string()))
+        }
+
+        async fn generate_thumbnail(&self, _file_path: &Path, _size: (u32, u32)) -> Result<Vec<u8>, PreviewError> {
+            Err(PreviewError::VideoError("decoder not available".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failing_provider_falls_back() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("notes.txt");
+        fs::write(&path, b"hello").unwrap();
+
+        let mut service = PreviewService::new().with_fallback_provider();
+        service.register_provider(Box::new(FailingProvider));
+        let chain: Vec<_> = service
+            .provider_chain(Some(SupportedFormat::Text))
+            .iter()
+            .map(|provider| provider.provider_id())
+            .collect();
+        assert_eq!(chain, vec!["failing", "fallback"]);
+
+        let preview = service.generate_preview(&path).await.unwrap();
+        assert_eq!(preview.file_path, path);
+
+        let health = service.health();
+        assert_eq!(health.stats("failing"), ProviderStats {
+            successes: 0,
+            errors: 1,
+            last_error: Some("Video processing error: decoder not available".to_string()),
+        });
+        assert_eq!(health.stats("fallback").successes, 1);
+        assert_eq!(health.fallbacks_served(), 1);
+        assert_eq!(health.last_served_by, Some("fallback"));
+    }
+
+    #[tokio::test]
+    async fn test_chain_without_fallback_returns_the_last_error() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("notes.txt");
+        fs::write(&path, b"hello").unwrap();
+
+        let mut service = PreviewService::new();
+        service.register_provider(Box::new(FailingProvider));
+        assert!(matches!(service.generate_preview(&path).await, Err(PreviewError::VideoError(_))));
+        assert_eq!(service.health().stats("failing").errors, 1);
+        assert_eq!(service.health().last_served_by, None);
+        // Unknown formats go straight to the (missing) fallback
+        assert!(service.provider_chain(None).is_empty());
+    }
+
     #[test]
     fn test_metadata_creation() {
         let metadata = FileMetadata::new();
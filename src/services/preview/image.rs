@@ -1,11 +1,22 @@
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::SystemTime;
 use async_trait::async_trait;
 use image::{ImageFormat, DynamicImage, GenericImageView};
+use tokio::sync::{mpsc, oneshot};
 use crate::services::preview::{
     PreviewProvider, PreviewHandler, PreviewData, PreviewConfig, PreviewError, 
     SupportedFormat, FileMetadata, PreviewContent, ExifData
 };
+use crate::services::preview_cache::{CachedPreviewData, PreviewDataMetadata};
+use crate::services::progressive_loader::{LoadingProgress, LoadingStage, ProgressiveLoadHandle, ProgressiveLoaderError};
+
+/// Longest edge of the placeholder shown while the full image loads
+pub const PROGRESSIVE_PREVIEW_EDGE: u32 = 64;
+
+/// Longest edge of the full-quality stage
+pub const PROGRESSIVE_FULL_EDGE: u32 = 2048;
 
 /// Image preview provider supporting multiple formats using the image crate v0.24
 pub struct ImagePreviewProvider;
@@ -60,6 +71,139 @@ impl ImagePreviewProvider {
         Ok(buffer)
     }
 
+    /// Load `file_path` in two stages: a blurry placeholder, then the full image
+    ///
+    /// Both stages arrive as progress updates with the image in
+    /// `intermediate_preview`; the full image is also the handle's result.
+    /// Cancelling the handle stops the load before the full stage.
+    pub fn load_progressive(&self, file_path: &Path) -> ProgressiveLoadHandle {
+        let (progress_sender, progress_receiver) = mpsc::channel(8);
+        let (result_sender, result_receiver) = oneshot::channel();
+        let cancellation_token = Arc::new(AtomicBool::new(false));
+        let file_size = std::fs::metadata(file_path).map(|metadata| metadata.len()).unwrap_or(0);
+
+        let path = file_path.to_path_buf();
+        let token = cancellation_token.clone();
+        tokio::task::spawn_blocking(move || {
+            let result = Self::load_in_stages(&path, &token, |progress| {
+                let _ = progress_sender.blocking_send(progress);
+            });
+            let _ = result_sender.send(result);
+        });
+
+        ProgressiveLoadHandle {
+            progress_receiver,
+            result_receiver,
+            cancellation_token,
+            file_path: file_path.to_path_buf(),
+            file_size,
+            started_at: SystemTime::now(),
+        }
+    }
+
+    /// The work behind [`Self::load_progressive`], reporting each stage to `report`
+    pub(crate) fn load_in_stages(
+        file_path: &Path,
+        cancellation_token: &AtomicBool,
+        mut report: impl FnMut(LoadingProgress),
+    ) -> Result<CachedPreviewData, ProgressiveLoaderError> {
+        let cancelled = || cancellation_token.load(Ordering::SeqCst);
+        let file_size = std::fs::metadata(file_path)?.len();
+        let mut progress = LoadingProgress::new(file_size, 1);
+        report(progress.clone());
+
+        // JPEGs can be decoded straight at a fraction of their size; anything
+        // else is decoded once and the full image kept for the second stage
+        let (small, full) = match Self::decode_scaled_jpeg(file_path, PROGRESSIVE_PREVIEW_EDGE) {
+            Some(small) => (small.thumbnail(PROGRESSIVE_PREVIEW_EDGE, PROGRESSIVE_PREVIEW_EDGE), None),
+            None => {
+                let full = Self::open_image(file_path)?;
+                (full.thumbnail(PROGRESSIVE_PREVIEW_EDGE, PROGRESSIVE_PREVIEW_EDGE), Some(full))
+            }
+        };
+        progress.stage = LoadingStage::Preview;
+        progress.percentage = 50.0;
+        progress.intermediate_preview = Some(Self::encode_stage(&small, file_size, 30)?);
+        report(progress.clone());
+
+        if cancelled() {
+            return Err(ProgressiveLoaderError::Cancelled);
+        }
+        let full = match full {
+            Some(full) => full,
+            None => Self::open_image(file_path)?,
+        };
+        let full = if full.width().max(full.height()) > PROGRESSIVE_FULL_EDGE {
+            full.resize(PROGRESSIVE_FULL_EDGE, PROGRESSIVE_FULL_EDGE, image::imageops::FilterType::Lanczos3)
+        } else {
+            full
+        };
+        let full = Self::encode_stage(&full, file_size, 90)?;
+        if cancelled() {
+            return Err(ProgressiveLoaderError::Cancelled);
+        }
+
+        progress.stage = LoadingStage::Full;
+        progress.percentage = 100.0;
+        progress.processed_bytes = file_size;
+        progress.current_chunk = 1;
+        progress.intermediate_preview = Some(full.clone());
+        report(progress.clone());
+
+        progress.stage = LoadingStage::Complete;
+        progress.intermediate_preview = None;
+        report(progress);
+        Ok(full)
+    }
+
+    fn open_image(file_path: &Path) -> Result<DynamicImage, ProgressiveLoaderError> {
+        image::open(file_path).map_err(|e| ProgressiveLoaderError::ChunkProcessingFailed {
+            reason: format!("Failed to load image: {}", e),
+        })
+    }
+
+    /// Decode a JPEG at the smallest DCT scale covering `edge`, if it is one
+    fn decode_scaled_jpeg(file_path: &Path, edge: u32) -> Option<DynamicImage> {
+        if ImageFormat::from_path(file_path).ok()? != ImageFormat::Jpeg {
+            return None;
+        }
+        let reader = std::io::BufReader::new(std::fs::File::open(file_path).ok()?);
+        let mut decoder = image::codecs::jpeg::JpegDecoder::new(reader).ok()?;
+        let edge = edge.min(u16::MAX as u32) as u16;
+        decoder.scale(edge, edge).ok()?;
+        DynamicImage::from_decoder(decoder).ok()
+    }
+
+    /// Encode one stage as JPEG at `quality`, or PNG if it has transparency
+    fn encode_stage(img: &DynamicImage, original_size: u64, quality: u8) -> Result<CachedPreviewData, ProgressiveLoaderError> {
+        let mut data = Vec::new();
+        let (content_type, format) = if img.color().has_alpha() {
+            img.write_to(&mut std::io::Cursor::new(&mut data), ImageFormat::Png)
+                .map(|_| ("image/png", "png"))
+        } else {
+            image::codecs::jpeg::JpegEncoder::new_with_quality(&mut data, quality)
+                .encode_image(&img.to_rgb8())
+                .map(|_| ("image/jpeg", "jpeg"))
+        }
+        .map_err(|e| ProgressiveLoaderError::ChunkProcessingFailed {
+            reason: format!("Failed to encode image: {}", e),
+        })?;
+
+        let (width, height) = img.dimensions();
+        Ok(CachedPreviewData::new(
+            data,
+            content_type.to_string(),
+            format.to_string(),
+            original_size,
+            PreviewDataMetadata {
+                width: Some(width),
+                height: Some(height),
+                duration: None,
+                quality_level: quality,
+            },
+        ))
+    }
+
     /// Extract basic image metadata
     fn extract_image_metadata(img: &DynamicImage, file_path: &Path) -> Result<FileMetadata, PreviewError> {
         let (width, height) = img.dimensions();
@@ -394,6 +538,68 @@ mod tests {
         assert!(!thumbnail_data.is_empty());
     }
 
+    #[test]
+    fn test_progressive_load_emits_preview_then_full() {
+        let temp_dir = TempDir::new().unwrap();
+        let image_path = temp_dir.path().join("large.jpg");
+        image::DynamicImage::ImageRgb8(image::RgbImage::new(800, 600)).save(&image_path).unwrap();
+
+        let token = AtomicBool::new(false);
+        let mut stages = Vec::new();
+        let full = ImagePreviewProvider::load_in_stages(&image_path, &token, |progress| {
+            if let Some(image) = &progress.intermediate_preview {
+                stages.push((progress.stage.clone(), image.metadata.width));
+            }
+        })
+        .unwrap();
+
+        assert_eq!(stages, vec![
+            (LoadingStage::Preview, Some(PROGRESSIVE_PREVIEW_EDGE)),
+            (LoadingStage::Full, Some(800)),
+        ]);
+        assert_eq!(full.content_type, "image/jpeg");
+        assert_eq!(full.metadata.height, Some(600));
+    }
+
+    #[test]
+    fn test_cancelling_after_the_preview_skips_the_full_stage() {
+        let temp_dir = TempDir::new().unwrap();
+        let image_path = temp_dir.path().join("large.png");
+        image::DynamicImage::ImageRgba8(image::RgbaImage::new(400, 300)).save(&image_path).unwrap();
+
+        let token = AtomicBool::new(false);
+        let mut stages = Vec::new();
+        let result = ImagePreviewProvider::load_in_stages(&image_path, &token, |progress| {
+            if progress.stage == LoadingStage::Preview {
+                token.store(true, Ordering::SeqCst);
+            }
+            stages.push(progress.stage);
+        });
+
+        assert!(matches!(result, Err(ProgressiveLoaderError::Cancelled)));
+        assert_eq!(stages, vec![LoadingStage::Initializing, LoadingStage::Preview]);
+    }
+
+    #[tokio::test]
+    async fn test_progressive_load_handle_delivers_both_stages() {
+        let temp_dir = TempDir::new().unwrap();
+        let image_path = temp_dir.path().join("large.png");
+        image::DynamicImage::ImageRgb8(image::RgbImage::new(300, 200)).save(&image_path).unwrap();
+
+        let mut handle = ImagePreviewProvider::new().load_progressive(&image_path);
+        let mut stages = Vec::new();
+        while let Some(progress) = handle.next_progress().await {
+            stages.push(progress.stage);
+        }
+        assert_eq!(stages, vec![
+            LoadingStage::Initializing,
+            LoadingStage::Preview,
+            LoadingStage::Full,
+            LoadingStage::Complete,
+        ]);
+        assert!(handle.await_result().await.is_ok());
+    }
+
     #[tokio::test]
     async fn test_unsupported_file() {
         let temp_dir = TempDir::new().unwrap();
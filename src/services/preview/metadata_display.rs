@@ -42,6 +42,9 @@ pub struct TechnicalInfoSection {
     pub color_space: Option<String>,
     pub compression: Option<String>,
     pub page_count: Option<String>, // "5 pages" or "150 lines"
+    /// Title, artist and album of audio files, e.g. [("Artist", "Nina Simone")]
+    #[serde(default)]
+    pub tags: Vec<(String, String)>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -104,6 +107,7 @@ impl MetadataDisplay {
                 color_space: metadata.color_space.clone(),
                 compression: metadata.compression.clone(),
                 page_count: Self::format_page_count(metadata.page_count, format),
+                tags: Self::format_audio_tags(metadata, format),
             },
             content_info: ContentInfoSection {
                 title: metadata.title.clone(),
@@ -159,16 +163,19 @@ impl MetadataDisplay {
         if let Some(page_count) = &self.technical_info.page_count {
             items.push(("Pages/Lines".to_string(), page_count.clone()));
         }
+        items.extend(self.technical_info.tags.iter().cloned());
 
-        // Content Info
-        if let Some(title) = &self.content_info.title {
-            items.push(("Title".to_string(), title.clone()));
-        }
-        if let Some(artist) = &self.content_info.artist {
-            items.push(("Artist/Author".to_string(), artist.clone()));
-        }
-        if let Some(album) = &self.content_info.album {
-            items.push(("Album/Collection".to_string(), album.clone()));
+        // Content Info; audio tags are already listed above
+        if self.technical_info.tags.is_empty() {
+            if let Some(title) = &self.content_info.title {
+                items.push(("Title".to_string(), title.clone()));
+            }
+            if let Some(artist) = &self.content_info.artist {
+                items.push(("Artist/Author".to_string(), artist.clone()));
+            }
+            if let Some(album) = &self.content_info.album {
+                items.push(("Album/Collection".to_string(), album.clone()));
+            }
         }
         if let Some(year) = &self.content_info.year {
             items.push(("Year".to_string(), year.clone()));
@@ -215,22 +222,20 @@ impl MetadataDisplay {
         items
     }
 
-    /// Format file size in human-readable format
-    fn format_file_size(size: u64) -> String {
-        const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
-        let mut size_f = size as f64;
-        let mut unit_index = 0;
-
-        while size_f >= 1024.0 && unit_index < UNITS.len() - 1 {
-            size_f /= 1024.0;
-            unit_index += 1;
+    /// Title, artist and album of an audio file, for the technical section
+    fn format_audio_tags(metadata: &FileMetadata, format: SupportedFormat) -> Vec<(String, String)> {
+        if !format.is_audio() {
+            return Vec::new();
         }
+        [("Title", &metadata.title), ("Artist", &metadata.artist), ("Album", &metadata.album)]
+            .into_iter()
+            .filter_map(|(label, value)| Some((label.to_string(), value.clone()?)))
+            .collect()
+    }
 
-        if unit_index == 0 {
-            format!("{} {}", size, UNITS[unit_index])
-        } else {
-            format!("{:.1} {}", size_f, UNITS[unit_index])
-        }
+    /// Format file size in human-readable format
+    fn format_file_size(size: u64) -> String {
+        crate::utils::format::format_size(size)
     }
 
     /// Format file type description
@@ -354,12 +359,7 @@ impl MetadataDisplay {
 
     /// Format timestamp as a readable string
     fn format_timestamp(timestamp: Option<SystemTime>) -> Option<String> {
-        timestamp.map(|time| {
-            use chrono::{DateTime, Utc};
-            
-            let datetime: DateTime<Utc> = time.into();
-            datetime.format("%B %d, %Y at %I:%M %p").to_string()
-        })
+        timestamp.map(crate::utils::format::format_date_time)
     }
 
     /// Extract language information from preview content
@@ -453,11 +453,12 @@ mod tests {
 
     #[test]
     fn test_file_size_formatting() {
+        // Follows the global size unit preference, binary by default
         assert_eq!(MetadataDisplay::format_file_size(512), "512 B");
-        assert_eq!(MetadataDisplay::format_file_size(1024), "1.0 KB");
-        assert_eq!(MetadataDisplay::format_file_size(1536), "1.5 KB");
-        assert_eq!(MetadataDisplay::format_file_size(1048576), "1.0 MB");
-        assert_eq!(MetadataDisplay::format_file_size(1073741824), "1.0 GB");
+        assert_eq!(MetadataDisplay::format_file_size(1024), "1.0 KiB");
+        assert_eq!(MetadataDisplay::format_file_size(1536), "1.5 KiB");
+        assert_eq!(MetadataDisplay::format_file_size(1048576), "1.0 MiB");
+        assert_eq!(MetadataDisplay::format_file_size(1073741824), "1.0 GiB");
     }
 
     #[test]
@@ -554,6 +555,35 @@ mod tests {
         assert_eq!(display.content_info.title, Some("Test Image".to_string()));
     }
 
+    #[test]
+    fn test_audio_tags_are_listed_with_the_technical_info() {
+        let mut metadata = FileMetadata::new();
+        metadata.title = Some("Morning".to_string());
+        metadata.artist = Some("The Testers".to_string());
+        metadata.duration = Some(90.0);
+        metadata.bit_rate = Some(128000);
+
+        let display = MetadataDisplay::from_metadata(
+            &PathBuf::from("/music/morning.mp3"),
+            &metadata,
+            SupportedFormat::Mp3,
+            None,
+        );
+        assert_eq!(
+            display.technical_info.tags,
+            vec![
+                ("Title".to_string(), "Morning".to_string()),
+                ("Artist".to_string(), "The Testers".to_string()),
+            ]
+        );
+
+        let flat_list = display.to_flat_list();
+        let labels: Vec<&str> = flat_list.iter().map(|(label, _)| label.as_str()).collect();
+        let position = |label| labels.iter().position(|&l| l == label).unwrap();
+        assert!(position("Bit Rate") < position("Title"));
+        assert!(!labels.contains(&"Artist/Author"));
+    }
+
     #[test]
     fn test_flat_list_generation() {
         let mut metadata = FileMetadata::new();
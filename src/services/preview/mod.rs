@@ -7,6 +7,7 @@ pub mod text;
 pub mod archive;
 pub mod fallback;
 pub mod thumbnail_service;
+pub mod thumbnail_encoding;
 pub mod metadata_display;
 pub mod integration_tests;
 
@@ -15,9 +16,10 @@ pub use image::{ImagePreviewProvider, ImagePreviewHandler};
 pub use video::{VideoPreviewProvider, VideoPreviewHandler};
 pub use audio::{AudioPreviewProvider, AudioPreviewHandler};
 pub use pdf::{PdfPreviewProvider, PdfPreviewHandler};
-pub use text::{TextPreviewProvider, TextPreviewHandler};
+pub use text::{is_text_file, TextPreviewProvider, TextPreviewHandler};
 pub use archive::{ArchivePreviewProvider, ArchivePreviewHandler};
 pub use fallback::{FallbackPreviewProvider, FallbackPreviewHandler};
+pub use thumbnail_encoding::{ThumbnailCacheFormat, ThumbnailEncoding};
 // pub use thumbnail_service::{
 //     ThumbnailService, ThumbnailPriority, ThumbnailJobStatus, ThumbnailJobConfig, 
 //     ThumbnailJob, ThumbnailServiceStats
@@ -6,6 +6,27 @@ use crate::services::preview::{
     PreviewProvider, PreviewHandler, PreviewData, PreviewConfig, PreviewError, 
     SupportedFormat, FileMetadata, PreviewContent
 };
+use crate::services::preview_cache::{
+    CachedPreviewData, PreviewCacheConfig, PreviewCacheKey, PreviewDataMetadata, ThreadSafePreviewCache
+};
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+
+/// Pages render at `scale` × 72 dpi, so 1.0 draws a page at its size in points
+pub const DEFAULT_PAGE_SCALE: f32 = 1.0;
+
+/// Longest edge of a rendered page in pixels, whatever the scale
+pub const MAX_PAGE_DIMENSION: u32 = 2048;
+
+/// US Letter in points, for documents without any `/MediaBox`
+const DEFAULT_PAGE_SIZE: (f32, f32) = (612.0, 792.0);
+
+/// A page object; `\b` keeps the `/Pages` tree nodes out
+static PAGE_OBJECT: Lazy<Regex> = Lazy::new(|| Regex::new(r"/Type\s*/Page\b").unwrap());
+
+static MEDIA_BOX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"/MediaBox\s*\[\s*(-?[\d.]+)\s+(-?[\d.]+)\s+(-?[\d.]+)\s+(-?[\d.]+)\s*\]").unwrap()
+});
 
 /// PDF preview provider supporting basic document metadata extraction
 pub struct PdfPreviewProvider {
@@ -60,10 +81,10 @@ impl PdfPreviewProvider {
             metadata.codec = Some("PDF".to_string());
         }
         
-        // Basic page count estimation by counting page objects
+        // Page count from the page objects, as used for page navigation
         let content_str = String::from_utf8_lossy(&file_content);
-        let page_objects = content_str.matches("/Type/Page").count() + content_str.matches("/Type /Page").count();
-        metadata.page_count = Some(if page_objects > 0 { page_objects as u32 } else { 1 });
+        let page_objects = scan_page_sizes(&file_content).map(|sizes| sizes.len()).unwrap_or(0);
+        metadata.page_count = Some(page_objects.max(1) as u32);
         
         // Try to extract document metadata from PDF metadata dictionary
         if let Some(title_match) = Self::extract_pdf_string(&content_str, "/Title") {
@@ -208,8 +229,7 @@ impl PdfPreviewProvider {
         let mut outline = Vec::new();
         
         // Count pages for basic information
-        let page_objects = content_str.matches("/Type/Page").count() + content_str.matches("/Type /Page").count();
-        let page_count = if page_objects > 0 { page_objects } else { 1 };
+        let page_count = scan_page_sizes(&file_content).map(|sizes| sizes.len()).unwrap_or(0).max(1);
         
         // Look for outline/bookmarks structure
         if content_str.contains("/Outlines") || content_str.contains("/Outline") {
@@ -323,7 +343,12 @@ impl PdfPreviewProvider {
         
         // Basic PDF assumptions for fallback
         metadata.codec = Some("PDF".to_string());
-        metadata.page_count = Some(1); // Assume at least 1 page
+        let page_objects = std::fs::read(file_path)
+            .ok()
+            .and_then(|bytes| scan_page_sizes(&bytes).ok())
+            .map(|sizes| sizes.len())
+            .unwrap_or(0);
+        metadata.page_count = Some(page_objects.max(1) as u32); // Assume at least 1 page
         
         Ok(metadata)
     }
@@ -545,6 +570,186 @@ impl PreviewHandler for PdfPreviewProvider {
 }
 
 
+/// Page sizes in points of the PDF at `path`, in document order
+///
+/// Fails with a `PdfError` for files that are not PDFs, are encrypted or have
+/// no pages we can find, so the preview can say why instead of staying blank.
+pub fn page_sizes(path: &Path) -> Result<Vec<(f32, f32)>, PreviewError> {
+    let bytes = std::fs::read(path)?;
+    #[allow(unused_mut)]
+    let mut sizes = scan_page_sizes(&bytes)?;
+
+    #[cfg(feature = "pdf")]
+    {
+        // Pages stored in compressed object streams are invisible to the scan
+        if let Ok(file) = pdf::file::FileOptions::cached().open(path) {
+            let parsed = file.num_pages() as usize;
+            if parsed > sizes.len() {
+                let fill = sizes.last().copied().unwrap_or(DEFAULT_PAGE_SIZE);
+                sizes.resize(parsed, fill);
+            }
+        }
+    }
+
+    if sizes.is_empty() {
+        return Err(PreviewError::PdfError("No pages found; the PDF may be damaged".to_string()));
+    }
+    Ok(sizes)
+}
+
+/// Number of pages of the PDF at `path`
+pub fn page_count(path: &Path) -> Result<u32, PreviewError> {
+    Ok(page_sizes(path)?.len() as u32)
+}
+
+/// Page sizes read from the raw page objects
+///
+/// Pages without their own `/MediaBox` use the first one in the file, which
+/// is normally the one they inherit from the page tree.
+fn scan_page_sizes(bytes: &[u8]) -> Result<Vec<(f32, f32)>, PreviewError> {
+    if !bytes.starts_with(b"%PDF") {
+        return Err(PreviewError::PdfError("Not a valid PDF file".to_string()));
+    }
+    let content = String::from_utf8_lossy(bytes);
+    if content.contains("/Encrypt") {
+        return Err(PreviewError::PdfError("The PDF is encrypted".to_string()));
+    }
+
+    let inherited = MEDIA_BOX
+        .captures(&content)
+        .and_then(|caps| media_box_size(&caps))
+        .unwrap_or(DEFAULT_PAGE_SIZE);
+    Ok(PAGE_OBJECT
+        .find_iter(&content)
+        .map(|page| {
+            let start = content[..page.start()].rfind(" obj").unwrap_or(0);
+            let end = content[page.end()..]
+                .find("endobj")
+                .map_or(content.len(), |offset| page.end() + offset);
+            MEDIA_BOX
+                .captures(&content[start..end])
+                .and_then(|caps| media_box_size(&caps))
+                .unwrap_or(inherited)
+        })
+        .collect())
+}
+
+fn media_box_size(caps: &Captures) -> Option<(f32, f32)> {
+    let corner: Vec<f32> = (1..=4)
+        .map(|i| caps[i].parse().ok())
+        .collect::<Option<_>>()?;
+    let (width, height) = ((corner[2] - corner[0]).abs(), (corner[3] - corner[1]).abs());
+    (width > 0.0 && height > 0.0).then_some((width, height))
+}
+
+/// Pixel size of a page of `size` points at `scale`, capped at `MAX_PAGE_DIMENSION`
+fn page_pixels(size: (f32, f32), scale: f32) -> (u32, u32) {
+    let (width, height) = (size.0 * scale, size.1 * scale);
+    let fit = (MAX_PAGE_DIMENSION as f32 / width.max(height)).min(1.0);
+    (((width * fit).round() as u32).max(1), ((height * fit).round() as u32).max(1))
+}
+
+/// Render page `page_index` (zero-based) of the PDF at `path` as a PNG
+///
+/// The `pdf` crate parses documents but cannot rasterise them, so the page is
+/// drawn as a sheet of its real size and proportions with ruled text lines.
+pub async fn render_page(path: &Path, page_index: u32, scale: f32) -> Result<CachedPreviewData, PreviewError> {
+    if !scale.is_finite() || scale <= 0.0 {
+        return Err(PreviewError::PdfError(format!("Invalid page scale {}", scale)));
+    }
+    let job_path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || render_page_blocking(&job_path, page_index, scale))
+        .await
+        .map_err(|e| PreviewError::TaskError(e.to_string()))?
+}
+
+fn render_page_blocking(path: &Path, page_index: u32, scale: f32) -> Result<CachedPreviewData, PreviewError> {
+    let sizes = page_sizes(path)?;
+    let size = *sizes.get(page_index as usize).ok_or_else(|| {
+        PreviewError::PdfError(format!(
+            "Page {} is out of range; the document has {} page{}",
+            page_index + 1,
+            sizes.len(),
+            if sizes.len() == 1 { "" } else { "s" }
+        ))
+    })?;
+
+    let (width, height) = page_pixels(size, scale);
+    let data = draw_page(width, height)?;
+    let mut metadata = PreviewDataMetadata::new();
+    metadata.width = Some(width);
+    metadata.height = Some(height);
+    let original_size = std::fs::metadata(path).map(|metadata| metadata.len()).unwrap_or(0);
+    Ok(CachedPreviewData::new(
+        data,
+        "image/png".to_string(),
+        "pdf-page".to_string(),
+        original_size,
+        metadata,
+    ))
+}
+
+fn draw_page(width: u32, height: u32) -> Result<Vec<u8>, PreviewError> {
+    use image::{DynamicImage, ImageFormat, Rgb, RgbImage};
+
+    let mut img = RgbImage::from_pixel(width, height, Rgb([255, 255, 255]));
+    let margin = width.min(height) / 10;
+    let line_height = (height / 40).max(2);
+    let line_color = Rgb([210, 210, 210]);
+    let mut y = margin;
+    let mut line = 0;
+    while y + line_height / 2 < height.saturating_sub(margin) {
+        // Every sixth line ends a paragraph early
+        let right = if line % 6 == 5 { width / 2 } else { width.saturating_sub(margin) };
+        for x in margin..right {
+            for dy in 0..(line_height / 3).max(1) {
+                img.put_pixel(x, y + dy, line_color);
+            }
+        }
+        y += line_height;
+        line += 1;
+    }
+
+    let mut buffer = Vec::new();
+    DynamicImage::ImageRgb8(img)
+        .write_to(&mut std::io::Cursor::new(&mut buffer), ImageFormat::Png)
+        .map_err(|e| PreviewError::PdfError(format!("Failed to encode PDF page: {}", e)))?;
+    Ok(buffer)
+}
+
+/// Renders PDF pages, keeping each page at each scale once per file version
+/// so flipping back to a page is instant
+#[derive(Clone)]
+pub struct PdfPageRenderer {
+    cache: ThreadSafePreviewCache,
+}
+
+impl PdfPageRenderer {
+    pub fn new(cache: ThreadSafePreviewCache) -> Self {
+        Self { cache }
+    }
+
+    /// Page `page_index` (zero-based) of the PDF at `path`, from the cache if rendered before
+    pub async fn render_page(&self, path: &Path, page_index: u32, scale: f32) -> Result<CachedPreviewData, PreviewError> {
+        let key = PreviewCacheKey::from_path(path)?.with_variant(format!("page={};scale={:.3}", page_index, scale));
+        if let Some(cached) = self.cache.get(&key) {
+            return Ok(cached);
+        }
+
+        let page = render_page(path, page_index, scale).await?;
+        if let Err(e) = self.cache.put(key, page.clone()) {
+            tracing::warn!("Failed to cache page {} of {:?}: {}", page_index + 1, path, e);
+        }
+        Ok(page)
+    }
+}
+
+impl Default for PdfPageRenderer {
+    fn default() -> Self {
+        Self::new(ThreadSafePreviewCache::new(PreviewCacheConfig::default()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -623,6 +828,37 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_page_count_and_out_of_range_pages() {
+        let temp_dir = TempDir::new().unwrap();
+        let pdf_path = temp_dir.path().join("pages.pdf");
+        let pdf_content = b"%PDF-1.4\n1 0 obj<</Type/Catalog/Pages 2 0 R>>endobj\n2 0 obj<</Type /Pages/Kids[3 0 R 4 0 R]/Count 2/MediaBox[0 0 612 792]>>endobj\n3 0 obj<</Type/Page/Parent 2 0 R>>endobj\n4 0 obj<</Type /Page/Parent 2 0 R/MediaBox [0 0 842 595]>>endobj\ntrailer<</Size 5/Root 1 0 R>>\n%%EOF";
+        fs::write(&pdf_path, pdf_content).unwrap();
+
+        assert_eq!(page_count(&pdf_path).unwrap(), 2);
+        assert_eq!(page_sizes(&pdf_path).unwrap(), vec![(612.0, 792.0), (842.0, 595.0)]);
+
+        let cache = ThreadSafePreviewCache::new(PreviewCacheConfig::default());
+        let renderer = PdfPageRenderer::new(cache.clone());
+        let landscape = renderer.render_page(&pdf_path, 1, 0.5).await.unwrap();
+        assert_eq!((landscape.metadata.width, landscape.metadata.height), (Some(421), Some(298)));
+        renderer.render_page(&pdf_path, 1, 0.5).await.unwrap();
+        renderer.render_page(&pdf_path, 0, 0.5).await.unwrap();
+        assert_eq!(cache.stats().entries, 2);
+
+        match renderer.render_page(&pdf_path, 2, 1.0).await {
+            Err(PreviewError::PdfError(message)) => assert!(message.contains("out of range")),
+            other => panic!("Expected an out of range error, got {:?}", other.map(|page| page.format)),
+        }
+
+        let encrypted_path = temp_dir.path().join("encrypted.pdf");
+        fs::write(&encrypted_path, b"%PDF-1.4\n1 0 obj<</Type/Page>>endobj\ntrailer<</Encrypt 5 0 R>>").unwrap();
+        assert!(matches!(page_count(&encrypted_path), Err(PreviewError::PdfError(_))));
+        let corrupt_path = temp_dir.path().join("corrupt.pdf");
+        fs::write(&corrupt_path, b"%PDF-1.4\ngarbage").unwrap();
+        assert!(render_page(&corrupt_path, 0, 1.0).await.is_err());
+    }
+
     #[cfg(feature = "pdf")]
     #[tokio::test]
     async fn test_pdf_metadata_extraction() {
@@ -1,12 +1,47 @@
 use std::path::Path;
 use std::time::SystemTime;
 use std::fs;
+use std::io::Read;
 use async_trait::async_trait;
 use crate::services::preview::{
     PreviewProvider, PreviewHandler, PreviewData, PreviewConfig, PreviewError, 
     SupportedFormat, FileMetadata, PreviewContent
 };
 
+/// Extensions, or whole lowercase file names, the text provider shows
+const TEXT_EXTENSIONS: &[&str] = &[
+    "txt", "md", "rst", "log", "cfg", "conf", "ini", "toml", "yaml", "yml", "json", "xml",
+    "html", "htm", "css", "js", "ts", "jsx", "tsx", "py", "rs", "go", "java", "c", "cpp",
+    "h", "hpp", "cs", "php", "rb", "swift", "kt", "scala", "sh", "bash", "ps1", "bat",
+    "dockerfile", "makefile", "cmake", "sql", "r", "m", "pl", "pm", "lua", "vim", "vimrc"
+];
+
+/// Bytes read from the start of a file when checking it for binary content
+const BINARY_SNIFF_LEN: usize = 8192;
+
+/// Whether `path` is a text file: a known text extension and no NUL byte
+/// in its first block
+///
+/// Unreadable files count as binary.
+pub fn is_text_file(path: &Path) -> bool {
+    let known = path
+        .extension()
+        .or_else(|| path.file_name())
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .is_some_and(|ext| TEXT_EXTENSIONS.contains(&ext.as_str()));
+    if !known {
+        return false;
+    }
+    let Ok(file) = fs::File::open(path) else {
+        return false;
+    };
+    let mut head = Vec::with_capacity(BINARY_SNIFF_LEN);
+    match file.take(BINARY_SNIFF_LEN as u64).read_to_end(&mut head) {
+        Ok(_) => !head.contains(&0),
+        Err(_) => false,
+    }
+}
+
 /// Text/Code preview provider supporting various formats with syntax highlighting using syntect 5.0
 pub struct TextPreviewProvider {
     _initialized: bool,
@@ -300,12 +335,7 @@ impl PreviewProvider for TextPreviewProvider {
     }
     
     fn supported_extensions(&self) -> Vec<&'static str> {
-        vec![
-            "txt", "md", "rst", "log", "cfg", "conf", "ini", "toml", "yaml", "yml", "json", "xml",
-            "html", "htm", "css", "js", "ts", "jsx", "tsx", "py", "rs", "go", "java", "c", "cpp",
-            "h", "hpp", "cs", "php", "rb", "swift", "kt", "scala", "sh", "bash", "ps1", "bat",
-            "dockerfile", "makefile", "cmake", "sql", "r", "m", "pl", "pm", "lua", "vim", "vimrc"
-        ]
+        TEXT_EXTENSIONS.to_vec()
     }
     
     async fn generate_preview(&self, file_path: &Path, config: &PreviewConfig) -> Result<PreviewData, PreviewError> {
@@ -0,0 +1,239 @@
+//! Encoding of thumbnails written to the disk cache
+//!
+//! Thumbnails are generated and handed out as PNG; only the copy written to
+//! disk is re-encoded. Cache entries are looked up by source path and decoded
+//! by content, so changing the encoding keeps existing entries usable and
+//! only applies to thumbnails generated afterwards.
+
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::{ColorType, DynamicImage, ImageEncoder};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use super::PreviewError;
+
+/// Image format of thumbnails written to the disk cache
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ThumbnailEncoding {
+    Png,
+    /// Smallest files; thumbnails with transparency are written as PNG instead
+    #[default]
+    Jpeg,
+    /// Lossless WebP, smaller than PNG and keeps transparency
+    WebP,
+}
+
+impl ThumbnailEncoding {
+    pub const ALL: [ThumbnailEncoding; 3] = [ThumbnailEncoding::Png, ThumbnailEncoding::Jpeg, ThumbnailEncoding::WebP];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ThumbnailEncoding::Png => "png",
+            ThumbnailEncoding::Jpeg => "jpeg",
+            ThumbnailEncoding::WebP => "webp",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "png" => ThumbnailEncoding::Png,
+            "webp" => ThumbnailEncoding::WebP,
+            _ => ThumbnailEncoding::Jpeg,
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ThumbnailEncoding::Png => "PNG",
+            ThumbnailEncoding::Jpeg => "JPEG",
+            ThumbnailEncoding::WebP => "WebP (lossless)",
+        }
+    }
+
+    /// File extension of cache entries in this encoding
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ThumbnailEncoding::Png => "png",
+            ThumbnailEncoding::Jpeg => "jpg",
+            ThumbnailEncoding::WebP => "webp",
+        }
+    }
+}
+
+/// Encoding and quality of thumbnails written to the disk cache
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThumbnailCacheFormat {
+    pub encoding: ThumbnailEncoding,
+    /// JPEG quality from 1 to 100; PNG and WebP are lossless
+    pub quality: u8,
+}
+
+impl Default for ThumbnailCacheFormat {
+    fn default() -> Self {
+        Self {
+            encoding: ThumbnailEncoding::default(),
+            quality: 85,
+        }
+    }
+}
+
+impl ThumbnailCacheFormat {
+    pub fn new(encoding: ThumbnailEncoding, quality: u8) -> Self {
+        Self {
+            encoding,
+            quality: quality.clamp(1, 100),
+        }
+    }
+}
+
+static THUMBNAIL_CACHE_FORMAT: Lazy<RwLock<ThumbnailCacheFormat>> =
+    Lazy::new(|| RwLock::new(ThumbnailCacheFormat::default()));
+
+/// Replace the format used for newly cached thumbnails
+pub fn set_thumbnail_cache_format(format: ThumbnailCacheFormat) {
+    if let Ok(mut current) = THUMBNAIL_CACHE_FORMAT.write() {
+        *current = format;
+    }
+}
+
+pub fn thumbnail_cache_format() -> ThumbnailCacheFormat {
+    THUMBNAIL_CACHE_FORMAT.read().map(|format| *format).unwrap_or_default()
+}
+
+/// Whether any pixel of `image` is not fully opaque
+fn has_transparency(image: &DynamicImage) -> bool {
+    image.color().has_alpha() && image.to_rgba8().pixels().any(|pixel| pixel[3] < u8::MAX)
+}
+
+/// Encode `image` for the disk cache, returning the bytes and the encoding used
+///
+/// Transparent images are never flattened: JPEG falls back to PNG for them.
+pub fn encode_thumbnail(
+    image: &DynamicImage,
+    format: ThumbnailCacheFormat,
+) -> Result<(Vec<u8>, ThumbnailEncoding), PreviewError> {
+    let encoding = match format.encoding {
+        ThumbnailEncoding::Jpeg if has_transparency(image) => ThumbnailEncoding::Png,
+        encoding => encoding,
+    };
+    let (width, height) = (image.width(), image.height());
+    let (pixels, color) = if image.color().has_alpha() && encoding != ThumbnailEncoding::Jpeg {
+        (image.to_rgba8().into_raw(), ColorType::Rgba8)
+    } else {
+        (image.to_rgb8().into_raw(), ColorType::Rgb8)
+    };
+
+    let mut bytes = Vec::new();
+    let result = match encoding {
+        ThumbnailEncoding::Png => PngEncoder::new(&mut bytes).write_image(&pixels, width, height, color),
+        ThumbnailEncoding::Jpeg => {
+            JpegEncoder::new_with_quality(&mut bytes, format.quality).write_image(&pixels, width, height, color)
+        }
+        ThumbnailEncoding::WebP => WebPEncoder::new_lossless(&mut bytes).write_image(&pixels, width, height, color),
+    };
+    result.map_err(|e| PreviewError::ImageError(format!("Failed to encode thumbnail: {}", e)))?;
+    Ok((bytes, encoding))
+}
+
+/// Write the generated `thumbnail` of `source` into `dir`, returning the file written
+///
+/// An earlier entry for the same source in another encoding is replaced.
+pub fn write_cached_thumbnail(
+    dir: &Path,
+    source: &Path,
+    thumbnail: &[u8],
+    format: ThumbnailCacheFormat,
+) -> Result<PathBuf, PreviewError> {
+    let image = image::load_from_memory(thumbnail)
+        .map_err(|e| PreviewError::ImageError(format!("Failed to decode thumbnail: {}", e)))?;
+    let (bytes, encoding) = encode_thumbnail(&image, format)?;
+
+    let digest = Sha256::digest(source.to_string_lossy().as_bytes());
+    let stem = format!("{}_{}x{}", hex::encode(&digest[..16]), image.width(), image.height());
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(format!("{}.{}", stem, encoding.extension()));
+    std::fs::write(&path, bytes)?;
+    for other in ThumbnailEncoding::ALL.iter().filter(|other| **other != encoding) {
+        let _ = std::fs::remove_file(dir.join(format!("{}.{}", stem, other.extension())));
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageFormat, Rgb, RgbImage, Rgba, RgbaImage};
+    use tempfile::TempDir;
+
+    fn opaque() -> DynamicImage {
+        DynamicImage::ImageRgb8(RgbImage::from_fn(32, 24, |x, y| Rgb([x as u8 * 8, y as u8 * 10, 128])))
+    }
+
+    fn translucent() -> DynamicImage {
+        DynamicImage::ImageRgba8(RgbaImage::from_fn(32, 24, |x, _| Rgba([200, 40, 40, if x < 16 { 0 } else { 255 }])))
+    }
+
+    #[test]
+    fn test_encoder_choice_produces_expected_format() {
+        for (encoding, expected) in [
+            (ThumbnailEncoding::Png, ImageFormat::Png),
+            (ThumbnailEncoding::Jpeg, ImageFormat::Jpeg),
+            (ThumbnailEncoding::WebP, ImageFormat::WebP),
+        ] {
+            let (bytes, used) = encode_thumbnail(&opaque(), ThumbnailCacheFormat::new(encoding, 80)).unwrap();
+            assert_eq!(used, encoding);
+            assert_eq!(image::guess_format(&bytes).unwrap(), expected);
+            let decoded = image::load_from_memory(&bytes).unwrap();
+            assert_eq!((decoded.width(), decoded.height()), (32, 24));
+        }
+
+        let (high, _) = encode_thumbnail(&opaque(), ThumbnailCacheFormat::new(ThumbnailEncoding::Jpeg, 95)).unwrap();
+        let (low, _) = encode_thumbnail(&opaque(), ThumbnailCacheFormat::new(ThumbnailEncoding::Jpeg, 10)).unwrap();
+        assert!(low.len() < high.len());
+    }
+
+    #[test]
+    fn test_transparent_thumbnails_are_not_flattened_to_jpeg() {
+        let (bytes, used) = encode_thumbnail(&translucent(), ThumbnailCacheFormat::default()).unwrap();
+        assert_eq!(used, ThumbnailEncoding::Png);
+        assert_eq!(image::load_from_memory(&bytes).unwrap().to_rgba8().get_pixel(0, 0)[3], 0);
+
+        let (bytes, used) =
+            encode_thumbnail(&translucent(), ThumbnailCacheFormat::new(ThumbnailEncoding::WebP, 80)).unwrap();
+        assert_eq!(used, ThumbnailEncoding::WebP);
+        assert_eq!(image::load_from_memory(&bytes).unwrap().to_rgba8().get_pixel(0, 0)[3], 0);
+
+        // An alpha channel that is fully opaque may still become JPEG
+        let opaque_rgba = DynamicImage::ImageRgba8(opaque().to_rgba8());
+        let (_, used) = encode_thumbnail(&opaque_rgba, ThumbnailCacheFormat::default()).unwrap();
+        assert_eq!(used, ThumbnailEncoding::Jpeg);
+    }
+
+    #[test]
+    fn test_new_encoding_replaces_entry_for_same_source() {
+        let dir = TempDir::new().unwrap();
+        let mut png = Vec::new();
+        opaque()
+            .write_to(&mut std::io::Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+        let source = Path::new("/photos/beach.jpg");
+
+        let first = write_cached_thumbnail(dir.path(), source, &png, ThumbnailCacheFormat::default()).unwrap();
+        assert_eq!(first.extension().unwrap(), "jpg");
+        let second = write_cached_thumbnail(
+            dir.path(),
+            source,
+            &png,
+            ThumbnailCacheFormat::new(ThumbnailEncoding::WebP, 80),
+        )
+        .unwrap();
+        assert_eq!(second.extension().unwrap(), "webp");
+        assert!(!first.exists());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
@@ -1,26 +1,44 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{SystemTime, Duration};
 use tokio::sync::{RwLock, mpsc, oneshot, Semaphore};
 use tokio::time::{sleep, Instant};
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 use tracing::{info, warn, debug};
 
 use crate::services::preview::{
-    PreviewHandler, PreviewError, PreviewConfig, SupportedFormat,
+    PreviewHandler, PreviewProvider, PreviewError, PreviewConfig, SupportedFormat,
     ImagePreviewHandler, VideoPreviewHandler, AudioPreviewHandler, 
-    PdfPreviewHandler, TextPreviewHandler
+    PdfPreviewHandler, TextPreviewHandler, ImagePreviewProvider, FallbackPreviewProvider
 };
-use crate::services::cache::CacheService;
+use crate::services::preview::video::PosterFrameExtractor;
+use crate::services::background::{ThrottlePolicy, ThrottleState};
+use crate::services::cache::{CacheService, CachedThumbnail};
+use crate::services::preview::thumbnail_encoding::{thumbnail_cache_format, write_cached_thumbnail};
+use crate::services::preview_cache::{
+    CachedPreviewData, PreviewCacheConfig, PreviewCacheKey, PreviewDataMetadata, ThreadSafePreviewCache
+};
+
+/// Longest edge of thumbnails from [`ThumbnailService::get_thumbnail`] unless configured
+pub const DEFAULT_THUMBNAIL_MAX_DIMENSION: u32 = 128;
+
+/// Thumbnails kept in memory by default; small enough to hold a few large folders
+const THUMBNAIL_MEMORY_CACHE_ENTRIES: usize = 1000;
 
-/// Priority levels for thumbnail generation jobs
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+/// `format` of thumbnails that came from the fallback provider instead of the image
+pub const FALLBACK_THUMBNAIL_FORMAT: &str = "fallback";
+
+/// How urgently a thumbnail is needed, from where its row is relative to the viewport
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum ThumbnailPriority {
-    Low = 0,
-    Normal = 1,
-    High = 2,
-    Urgent = 3,
+    /// Far from the viewport; generated when nothing else is waiting
+    Background = 0,
+    /// Just outside the viewport, likely to scroll into view next
+    NearViewport = 1,
+    /// On screen now
+    Visible = 2,
 }
 
 /// Status of a thumbnail generation job
@@ -47,7 +65,7 @@ impl Default for ThumbnailJobConfig {
     fn default() -> Self {
         Self {
             size: (256, 256),
-            priority: ThumbnailPriority::Normal,
+            priority: ThumbnailPriority::Background,
             timeout: Duration::from_secs(30),
             max_retries: 3,
             cache_result: true,
@@ -97,6 +115,14 @@ pub struct ThumbnailServiceStats {
     pub cache_misses: u64,
     pub active_jobs: usize,
     pub queued_jobs: usize,
+    /// Jobs waiting in the queue, by their current priority
+    pub queued_by_priority: HashMap<ThumbnailPriority, usize>,
+    /// Finished jobs, by the priority they had when they started
+    pub processed_by_priority: HashMap<ThumbnailPriority, u64>,
+    /// Jobs taken off the queue so far
+    pub jobs_started: u64,
+    /// Average time jobs spent queued before processing started
+    pub average_wait_time: Duration,
 }
 
 /// Main thumbnail generation service with background processing
@@ -111,10 +137,18 @@ pub struct ThumbnailService {
     stats: Arc<RwLock<ThumbnailServiceStats>>,
     /// Cache service for thumbnail persistence
     cache_service: Option<Arc<CacheService>>,
+    /// Directory generated thumbnails are written to
+    disk_cache_dir: Option<PathBuf>,
+    /// In-memory thumbnails served by `get_thumbnail`
+    memory_cache: ThreadSafePreviewCache,
+    /// Longest edge of thumbnails served by `get_thumbnail`
+    max_dimension: u32,
     /// Preview configuration
     config: PreviewConfig,
     /// Semaphore to limit concurrent thumbnail generation
     processing_semaphore: Arc<Semaphore>,
+    /// Policy holding back background-priority jobs and prefetching
+    throttle_policy: Option<Arc<ThrottlePolicy>>,
     /// Channel for shutdown signaling
     shutdown_tx: Option<mpsc::UnboundedSender<()>>,
     /// Service configuration
@@ -125,13 +159,14 @@ pub struct ThumbnailService {
 impl ThumbnailService {
     /// Create a new thumbnail service
     pub fn new(config: PreviewConfig) -> Self {
-        let handlers: Vec<Box<dyn PreviewHandler + Send + Sync>> = vec![
+        let mut handlers: Vec<Box<dyn PreviewHandler + Send + Sync>> = vec![
             Box::new(ImagePreviewHandler::default()),
-            Box::new(VideoPreviewHandler::new().expect("Failed to create VideoPreviewHandler")),
-            Box::new(AudioPreviewHandler::new().expect("Failed to create AudioPreviewHandler")),
-            Box::new(PdfPreviewHandler::new().expect("Failed to create PdfPreviewHandler")),
-            Box::new(TextPreviewHandler::new().expect("Failed to create TextPreviewHandler")),
         ];
+        // Handlers whose cargo feature is off fail to build; the rest still work
+        add_handler(&mut handlers, VideoPreviewHandler::new());
+        add_handler(&mut handlers, AudioPreviewHandler::new());
+        add_handler(&mut handlers, PdfPreviewHandler::new());
+        add_handler(&mut handlers, TextPreviewHandler::new());
 
         let max_concurrent_jobs = num_cpus::get().max(2).min(8);
 
@@ -149,10 +184,18 @@ impl ThumbnailService {
                 cache_misses: 0,
                 active_jobs: 0,
                 queued_jobs: 0,
+                queued_by_priority: HashMap::new(),
+                processed_by_priority: HashMap::new(),
+                jobs_started: 0,
+                average_wait_time: Duration::ZERO,
             })),
             cache_service: None,
+            disk_cache_dir: None,
+            memory_cache: ThreadSafePreviewCache::new(thumbnail_memory_cache_config()),
+            max_dimension: DEFAULT_THUMBNAIL_MAX_DIMENSION,
             config,
             processing_semaphore: Arc::new(Semaphore::new(max_concurrent_jobs)),
+            throttle_policy: None,
             shutdown_tx: None,
             max_concurrent_jobs,
             max_queue_size: 1000,
@@ -165,6 +208,133 @@ impl ThumbnailService {
         self
     }
 
+    /// Write generated thumbnails to `dir` in the configured cache format
+    pub fn with_disk_cache(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.disk_cache_dir = Some(dir.into());
+        self
+    }
+
+    /// Generate at most `max_concurrent_jobs` queued thumbnails at a time
+    pub fn with_max_concurrent_jobs(mut self, max_concurrent_jobs: usize) -> Self {
+        self.max_concurrent_jobs = max_concurrent_jobs.max(1);
+        self.processing_semaphore = Arc::new(Semaphore::new(self.max_concurrent_jobs));
+        self
+    }
+
+    /// Keep `get_thumbnail` results in `cache` instead of the default one
+    pub fn with_memory_cache(mut self, cache: ThreadSafePreviewCache) -> Self {
+        self.memory_cache = cache;
+        self
+    }
+
+    /// Also keep `get_thumbnail` results in `dir`, so they survive restarts
+    pub fn with_persistent_thumbnails(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.memory_cache = ThreadSafePreviewCache::new(thumbnail_memory_cache_config().with_disk_cache(dir));
+        self
+    }
+
+    /// Scale `get_thumbnail` results so their longest edge is `max_dimension` pixels
+    pub fn with_max_dimension(mut self, max_dimension: u32) -> Self {
+        self.max_dimension = max_dimension.max(1);
+        self
+    }
+
+    /// Hold back background-priority jobs and prefetching while `throttle_policy` says so
+    pub fn with_throttle_policy(mut self, throttle_policy: Arc<ThrottlePolicy>) -> Self {
+        self.throttle_policy = Some(throttle_policy);
+        self
+    }
+
+    /// A downscaled thumbnail of the file, from the memory cache when it's current
+    ///
+    /// Images are decoded and scaled to fit the configured max dimension as PNG,
+    /// and videos get their poster frame from [`PosterFrameExtractor`]. Anything
+    /// else, or anything unreadable, gets the fallback provider's generic
+    /// thumbnail, marked with [`FALLBACK_THUMBNAIL_FORMAT`]. Decoding is CPU-bound,
+    /// so call this off the UI thread.
+    pub async fn get_thumbnail(&self, path: &Path) -> Result<CachedPreviewData, PreviewError> {
+        let key = PreviewCacheKey::from_path(path)?;
+        if let Some(cached) = self.memory_cache.get(&key) {
+            self.stats.write().await.cache_hits += 1;
+            return Ok(cached);
+        }
+        self.stats.write().await.cache_misses += 1;
+
+        let original_size = std::fs::metadata(path)?.len();
+        let size = (self.max_dimension, self.max_dimension);
+        let format = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(SupportedFormat::from_extension);
+        if format.is_some_and(|format| format.is_video()) {
+            let poster_frames = PosterFrameExtractor::new(self.memory_cache.clone()).with_max_dimension(self.max_dimension);
+            return Ok(poster_frames.get_poster_frame(path).await);
+        }
+        let generated = if format.is_some_and(|format| format.is_image()) {
+            PreviewProvider::generate_thumbnail(&ImagePreviewProvider::new(), path, size).await
+        } else {
+            Err(PreviewError::UnsupportedFormat(path.display().to_string()))
+        };
+
+        let thumbnail = match generated {
+            Ok(data) => {
+                let mut metadata = PreviewDataMetadata::new();
+                if let Ok((width, height)) = image::io::Reader::new(std::io::Cursor::new(&data))
+                    .with_guessed_format()
+                    .map_err(|e| e.to_string())
+                    .and_then(|reader| reader.into_dimensions().map_err(|e| e.to_string()))
+                {
+                    metadata.width = Some(width);
+                    metadata.height = Some(height);
+                }
+                CachedPreviewData::new(data, "image/png".to_string(), "png".to_string(), original_size, metadata)
+            }
+            Err(e) => {
+                debug!("Using a fallback thumbnail for {:?}: {}", path, e);
+                let data = PreviewProvider::generate_thumbnail(&FallbackPreviewProvider::new(), path, size).await?;
+                CachedPreviewData::new(
+                    data,
+                    "image/x-portable-pixmap".to_string(),
+                    FALLBACK_THUMBNAIL_FORMAT.to_string(),
+                    original_size,
+                    PreviewDataMetadata::new(),
+                )
+            }
+        };
+
+        if let Err(e) = self.memory_cache.put(key, thumbnail.clone()) {
+            warn!("Failed to cache thumbnail for {:?}: {}", path, e);
+        }
+        Ok(thumbnail)
+    }
+
+    /// Generate thumbnails for `paths` ahead of time, one at a time, as low-priority work
+    ///
+    /// Each file waits on the throttle policy first, so prefetching pauses or
+    /// slows down with the other background tasks. Returns how many paths were
+    /// handled before `cancellation_token` fired.
+    pub async fn prefetch_thumbnails(&self, paths: &[PathBuf], cancellation_token: &CancellationToken) -> usize {
+        for (done, path) in paths.iter().enumerate() {
+            if let Some(throttle_policy) = &self.throttle_policy {
+                if !throttle_policy.wait_until_allowed(cancellation_token).await {
+                    return done;
+                }
+            }
+            if cancellation_token.is_cancelled() {
+                return done;
+            }
+            if let Err(e) = self.get_thumbnail(path).await {
+                debug!("Could not prefetch thumbnail for {:?}: {}", path, e);
+            }
+        }
+        paths.len()
+    }
+
+    /// The cache `get_thumbnail` serves from
+    pub fn memory_cache(&self) -> &ThreadSafePreviewCache {
+        &self.memory_cache
+    }
+
     /// Start the background processing loop
     pub async fn start(&mut self) -> Result<(), PreviewError> {
         let (shutdown_tx, mut shutdown_rx) = mpsc::unbounded_channel();
@@ -175,13 +345,16 @@ impl ThumbnailService {
         let stats = Arc::clone(&self.stats);
         let handlers = Arc::clone(&self.handlers);
         let cache_service = self.cache_service.clone();
+        let disk_cache_dir = self.disk_cache_dir.clone();
         let config = self.config.clone();
         let processing_semaphore = Arc::clone(&self.processing_semaphore);
+        let throttle_policy = self.throttle_policy.clone();
 
         tokio::spawn(async move {
             info!("Starting thumbnail service background processor");
             
             let mut processing_interval = tokio::time::interval(Duration::from_millis(100));
+            let mut last_background_dispatch = None;
             
             loop {
                 tokio::select! {
@@ -192,8 +365,11 @@ impl ThumbnailService {
                             &stats,
                             &handlers,
                             &cache_service,
+                            &disk_cache_dir,
                             &config,
                             &processing_semaphore,
+                            &throttle_policy,
+                            &mut last_background_dispatch,
                         ).await;
                     }
                     _ = shutdown_rx.recv() => {
@@ -276,13 +452,40 @@ impl ThumbnailService {
         // Update stats
         {
             let mut stats = self.stats.write().await;
-            stats.queued_jobs = self.job_queue.read().await.len();
+            record_queue(&mut stats, &*self.job_queue.read().await);
         }
 
         debug!("Queued thumbnail job {} for {:?}", job_id, file_path);
         Ok(receiver)
     }
 
+    /// Re-rank queued jobs after the viewport moved
+    ///
+    /// Jobs for `visible` paths jump the queue, `near_viewport` ones follow them,
+    /// and everything else is demoted to background. Queue order is kept within
+    /// each priority.
+    pub async fn update_viewport(&self, visible: &[PathBuf], near_viewport: &[PathBuf]) {
+        let visible: HashSet<&Path> = visible.iter().map(PathBuf::as_path).collect();
+        let near_viewport: HashSet<&Path> = near_viewport.iter().map(PathBuf::as_path).collect();
+
+        let mut queue = self.job_queue.write().await;
+        for job in queue.iter_mut() {
+            job.config.priority = if visible.contains(job.file_path.as_path()) {
+                ThumbnailPriority::Visible
+            } else if near_viewport.contains(job.file_path.as_path()) {
+                ThumbnailPriority::NearViewport
+            } else {
+                ThumbnailPriority::Background
+            };
+        }
+        // Stable, so jobs of equal priority stay first-come first-served
+        queue.make_contiguous().sort_by(|a, b| b.config.priority.cmp(&a.config.priority));
+        drop(queue);
+
+        let mut stats = self.stats.write().await;
+        record_queue(&mut stats, &*self.job_queue.read().await);
+    }
+
     /// Process the job queue
     async fn process_queue(
         job_queue: &Arc<RwLock<VecDeque<ThumbnailJob>>>,
@@ -290,28 +493,42 @@ impl ThumbnailService {
         stats: &Arc<RwLock<ThumbnailServiceStats>>,
         handlers: &Arc<Vec<Box<dyn PreviewHandler + Send + Sync>>>,
         cache_service: &Option<Arc<CacheService>>,
+        disk_cache_dir: &Option<PathBuf>,
         config: &PreviewConfig,
         processing_semaphore: &Arc<Semaphore>,
+        throttle_policy: &Option<Arc<ThrottlePolicy>>,
+        last_background_dispatch: &mut Option<Instant>,
     ) {
-        // Try to acquire semaphore permit for concurrent job limiting  
-        let permit = match Arc::clone(processing_semaphore).try_acquire_owned() {
-            Ok(permit) => permit,
-            Err(_) => return, // All processing slots are busy
-        };
-
-        // Get next job from queue
-        let job = {
+        // Background jobs wait here, in the queue and without a processing slot,
+        // while the throttle policy holds them back
+        let (mut job, permit) = {
             let mut queue = job_queue.write().await;
-            queue.pop_front()
-        };
-
-        let Some(mut job) = job else {
-            return; // No jobs in queue
+            let Some(next) = queue.front() else {
+                return; // No jobs in queue
+            };
+            if next.config.priority == ThumbnailPriority::Background
+                && !Self::background_dispatch_allowed(throttle_policy, *last_background_dispatch)
+            {
+                return;
+            }
+            // Try to acquire semaphore permit for concurrent job limiting
+            let permit = match Arc::clone(processing_semaphore).try_acquire_owned() {
+                Ok(permit) => permit,
+                Err(_) => return, // All processing slots are busy
+            };
+            let Some(job) = queue.pop_front() else {
+                return;
+            };
+            (job, permit)
         };
+        if job.config.priority == ThumbnailPriority::Background {
+            *last_background_dispatch = Some(Instant::now());
+        }
 
         let job_id = job.id;
         let file_path = job.file_path.clone();
         let job_config = job.config.clone();
+        let wait_time = job.created_at.elapsed().unwrap_or(Duration::ZERO);
 
         // Move job to active jobs
         job.status = ThumbnailJobStatus::Processing;
@@ -324,7 +541,10 @@ impl ThumbnailService {
         {
             let mut stats = stats.write().await;
             stats.active_jobs = active_jobs.read().await.len();
-            stats.queued_jobs = job_queue.read().await.len();
+            record_queue(&mut stats, &*job_queue.read().await);
+            stats.jobs_started += 1;
+            let total_wait = stats.average_wait_time * (stats.jobs_started - 1) as u32 + wait_time;
+            stats.average_wait_time = total_wait / stats.jobs_started as u32;
         }
 
         // Spawn processing task
@@ -332,6 +552,7 @@ impl ThumbnailService {
         let stats_clone = Arc::clone(stats);
         let handlers_clone = Arc::clone(handlers);
         let cache_service_clone = cache_service.clone();
+        let disk_cache_dir_clone = disk_cache_dir.clone();
         let config_clone = config.clone();
 
         tokio::spawn(async move {
@@ -345,6 +566,7 @@ impl ThumbnailService {
                 &job_config,
                 &handlers_clone,
                 &cache_service_clone,
+                &disk_cache_dir_clone,
                 &config_clone,
             ).await;
 
@@ -377,6 +599,7 @@ impl ThumbnailService {
             {
                 let mut stats = stats_clone.write().await;
                 stats.total_jobs_processed += 1;
+                *stats.processed_by_priority.entry(job_config.priority).or_insert(0) += 1;
                 if result_success {
                     stats.successful_generations += 1;
                 } else {
@@ -392,12 +615,29 @@ impl ThumbnailService {
         });
     }
 
+    /// Whether the next background job may start: not while paused, and in
+    /// throttle mode only once the delay since the last one has passed
+    fn background_dispatch_allowed(throttle_policy: &Option<Arc<ThrottlePolicy>>, last_dispatch: Option<Instant>) -> bool {
+        let Some(throttle_policy) = throttle_policy else {
+            return true;
+        };
+        match throttle_policy.refreshed_state() {
+            ThrottleState::Running => true,
+            ThrottleState::Paused(_) => false,
+            ThrottleState::Throttled(_) => {
+                let delay = Duration::from_millis(throttle_policy.settings().throttle_delay_ms);
+                last_dispatch.map_or(true, |dispatched| dispatched.elapsed() >= delay)
+            }
+        }
+    }
+
     /// Internal thumbnail generation logic
     async fn generate_thumbnail_internal(
         file_path: &Path,
         config: &ThumbnailJobConfig,
         handlers: &Vec<Box<dyn PreviewHandler + Send + Sync>>,
         cache_service: &Option<Arc<CacheService>>,
+        disk_cache_dir: &Option<PathBuf>,
         _preview_config: &PreviewConfig,
     ) -> Result<Vec<u8>, PreviewError> {
         // Detect file format
@@ -420,11 +660,10 @@ impl ThumbnailService {
 
         match thumbnail_result {
             Ok(Ok(thumbnail_data)) => {
-                // Cache the result if enabled
+                // Cache the result if enabled; the returned data stays as generated
                 if config.cache_result {
-                    if let Some(_cache) = cache_service {
-                        // TODO: Store thumbnail in cache when API is available
-                        debug!("Thumbnail caching not yet fully implemented");
+                    if let Some(dir) = disk_cache_dir {
+                        Self::store_on_disk(file_path, &thumbnail_data, dir, cache_service).await;
                     }
                 }
                 Ok(thumbnail_data)
@@ -434,6 +673,31 @@ impl ThumbnailService {
         }
     }
 
+    /// Write a thumbnail to the disk cache and record it; failures only cost the cache entry
+    async fn store_on_disk(
+        file_path: &Path,
+        thumbnail_data: &[u8],
+        dir: &Path,
+        cache_service: &Option<Arc<CacheService>>,
+    ) {
+        let format = thumbnail_cache_format();
+        let (dir, source, data) = (dir.to_path_buf(), file_path.to_path_buf(), thumbnail_data.to_vec());
+        let written = tokio::task::spawn_blocking(move || write_cached_thumbnail(&dir, &source, &data, format)).await;
+        match written {
+            Ok(Ok(thumbnail_path)) => {
+                debug!("Cached thumbnail for {:?} at {:?}", file_path, thumbnail_path);
+                if let Some(cache) = cache_service {
+                    let cached = CachedThumbnail::new(file_path.to_path_buf(), thumbnail_path);
+                    if let Err(e) = cache.store_thumbnail_path(&cached).await {
+                        warn!("Failed to record cached thumbnail for {:?}: {}", file_path, e);
+                    }
+                }
+            }
+            Ok(Err(e)) => warn!("Failed to write cached thumbnail for {:?}: {}", file_path, e),
+            Err(e) => warn!("Thumbnail cache task failed for {:?}: {}", file_path, e),
+        }
+    }
+
     /// Get current service statistics
     pub async fn get_stats(&self) -> ThumbnailServiceStats {
         self.stats.read().await.clone()
@@ -496,12 +760,13 @@ impl ThumbnailService {
                 let _ = sender.send(Err(PreviewError::TaskError("Queue cleared".to_string())));
             }
         }
+        drop(queue);
 
         // Update stats
         {
             let mut stats = self.stats.write().await;
             stats.cancelled_jobs += cleared_count as u64;
-            stats.queued_jobs = 0;
+            record_queue(&mut stats, &*self.job_queue.read().await);
         }
 
         cleared_count
@@ -531,9 +796,39 @@ impl Drop for ThumbnailService {
     }
 }
 
+/// Refresh the queue counts in `stats` from `queue`
+fn record_queue(stats: &mut ThumbnailServiceStats, queue: &VecDeque<ThumbnailJob>) {
+    stats.queued_jobs = queue.len();
+    stats.queued_by_priority.clear();
+    for job in queue {
+        *stats.queued_by_priority.entry(job.config.priority).or_insert(0) += 1;
+    }
+}
+
+/// Memory tier sizing for `get_thumbnail`
+fn thumbnail_memory_cache_config() -> PreviewCacheConfig {
+    PreviewCacheConfig {
+        max_entries: THUMBNAIL_MEMORY_CACHE_ENTRIES,
+        max_memory_bytes: 64 * 1024 * 1024,
+        ..PreviewCacheConfig::default()
+    }
+}
+
+/// Register `handler`, or log why it's unavailable
+fn add_handler<H: PreviewHandler + Send + Sync + 'static>(
+    handlers: &mut Vec<Box<dyn PreviewHandler + Send + Sync>>,
+    handler: Result<H, PreviewError>,
+) {
+    match handler {
+        Ok(handler) => handlers.push(Box::new(handler)),
+        Err(e) => warn!("Thumbnail handler unavailable: {}", e),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::services::background::{BackgroundThrottleSettings, ThrottleMode};
     use tempfile::TempDir;
     use std::fs;
 
@@ -556,16 +851,240 @@ mod tests {
         
         assert_eq!(job.file_path, file_path);
         assert_eq!(job.config.size, (256, 256));
-        assert_eq!(job.config.priority, ThumbnailPriority::Normal);
+        assert_eq!(job.config.priority, ThumbnailPriority::Background);
         assert_eq!(job.status, ThumbnailJobStatus::Queued);
         assert_eq!(job.retry_count, 0);
     }
 
     #[tokio::test]
     async fn test_priority_ordering() {
-        assert!(ThumbnailPriority::Urgent > ThumbnailPriority::High);
-        assert!(ThumbnailPriority::High > ThumbnailPriority::Normal);
-        assert!(ThumbnailPriority::Normal > ThumbnailPriority::Low);
+        assert!(ThumbnailPriority::Visible > ThumbnailPriority::NearViewport);
+        assert!(ThumbnailPriority::NearViewport > ThumbnailPriority::Background);
+    }
+
+    fn write_test_images(dir: &Path, names: &[&str]) -> Vec<PathBuf> {
+        names
+            .iter()
+            .map(|name| {
+                let path = dir.join(name);
+                image::RgbImage::new(32, 32).save(&path).unwrap();
+                path
+            })
+            .collect()
+    }
+
+    fn job_config(priority: ThumbnailPriority) -> ThumbnailJobConfig {
+        ThumbnailJobConfig {
+            priority,
+            cache_result: false,
+            ..ThumbnailJobConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_visible_job_completes_before_queued_background_jobs() {
+        let temp_dir = TempDir::new().unwrap();
+        let paths = write_test_images(temp_dir.path(), &["a.png", "b.png", "c.png", "visible.png"]);
+        let mut service = ThumbnailService::new(PreviewConfig::default()).with_max_concurrent_jobs(1);
+
+        let mut receivers = Vec::new();
+        for path in &paths[..3] {
+            let receiver = service
+                .generate_thumbnail_async(path, job_config(ThumbnailPriority::Background))
+                .await
+                .unwrap();
+            receivers.push((path.clone(), receiver));
+        }
+        let receiver = service
+            .generate_thumbnail_async(&paths[3], job_config(ThumbnailPriority::Visible))
+            .await
+            .unwrap();
+        receivers.push((paths[3].clone(), receiver));
+
+        let stats = service.get_stats().await;
+        assert_eq!(stats.queued_by_priority[&ThumbnailPriority::Background], 3);
+        assert_eq!(stats.queued_by_priority[&ThumbnailPriority::Visible], 1);
+
+        let completed = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let waiters: Vec<_> = receivers
+            .into_iter()
+            .map(|(path, receiver)| {
+                let completed = Arc::clone(&completed);
+                tokio::spawn(async move {
+                    receiver.await.unwrap().unwrap();
+                    completed.lock().unwrap().push(path);
+                })
+            })
+            .collect();
+        service.start().await.unwrap();
+        for waiter in waiters {
+            waiter.await.unwrap();
+        }
+
+        assert_eq!(completed.lock().unwrap()[0], paths[3]);
+        let stats = service.get_stats().await;
+        assert_eq!(stats.processed_by_priority[&ThumbnailPriority::Background], 3);
+        assert_eq!(stats.processed_by_priority[&ThumbnailPriority::Visible], 1);
+        assert_eq!(stats.jobs_started, 4);
+        assert!(stats.average_wait_time > Duration::ZERO);
+        service.stop().await;
+    }
+
+    fn paused_policy() -> Arc<ThrottlePolicy> {
+        let policy = ThrottlePolicy::new(BackgroundThrottleSettings {
+            pause_on_battery: false,
+            pause_when_unfocused: true,
+            ..BackgroundThrottleSettings::default()
+        });
+        policy.set_app_focused(false);
+        Arc::new(policy)
+    }
+
+    #[tokio::test]
+    async fn test_paused_policy_holds_back_background_jobs_only() {
+        let temp_dir = TempDir::new().unwrap();
+        let paths = write_test_images(temp_dir.path(), &["background.png", "visible.png"]);
+        let policy = paused_policy();
+        let mut service = ThumbnailService::new(PreviewConfig::default()).with_throttle_policy(policy.clone());
+
+        let background = service
+            .generate_thumbnail_async(&paths[0], job_config(ThumbnailPriority::Background))
+            .await
+            .unwrap();
+        let visible = service
+            .generate_thumbnail_async(&paths[1], job_config(ThumbnailPriority::Visible))
+            .await
+            .unwrap();
+        service.start().await.unwrap();
+
+        tokio::time::timeout(Duration::from_secs(5), visible).await.unwrap().unwrap().unwrap();
+        sleep(Duration::from_millis(300)).await;
+        assert_eq!(service.queue_size().await, 1);
+
+        policy.set_app_focused(true);
+        tokio::time::timeout(Duration::from_secs(5), background).await.unwrap().unwrap().unwrap();
+        service.stop().await;
+    }
+
+    #[tokio::test]
+    async fn test_pausing_with_background_jobs_in_flight_keeps_visible_jobs_running() {
+        let temp_dir = TempDir::new().unwrap();
+        let names: Vec<String> = (0..6).map(|i| format!("background{}.png", i)).collect();
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        let mut paths = write_test_images(temp_dir.path(), &names);
+        let visible_path = paths.pop().unwrap();
+        let policy = paused_policy();
+        policy.set_app_focused(true);
+        let mut service = ThumbnailService::new(PreviewConfig::default())
+            .with_max_concurrent_jobs(1)
+            .with_throttle_policy(policy.clone());
+
+        let mut background = Vec::new();
+        for path in &paths {
+            background.push(
+                service
+                    .generate_thumbnail_async(path, job_config(ThumbnailPriority::Background))
+                    .await
+                    .unwrap(),
+            );
+        }
+        service.start().await.unwrap();
+        while service.get_stats().await.jobs_started == 0 {
+            sleep(Duration::from_millis(10)).await;
+        }
+
+        // Quiet hours, battery or focus loss begin while a background job is running
+        policy.set_app_focused(false);
+        let visible = service
+            .generate_thumbnail_async(&visible_path, job_config(ThumbnailPriority::Visible))
+            .await
+            .unwrap();
+        tokio::time::timeout(Duration::from_secs(5), visible).await.unwrap().unwrap().unwrap();
+
+        sleep(Duration::from_millis(300)).await;
+        assert!(service.queue_size().await > 0);
+        assert_eq!(service.active_jobs_count().await, 0);
+
+        policy.set_app_focused(true);
+        for receiver in background {
+            tokio::time::timeout(Duration::from_secs(5), receiver).await.unwrap().unwrap().unwrap();
+        }
+        service.stop().await;
+    }
+
+    #[tokio::test]
+    async fn test_throttle_mode_spaces_out_background_jobs() {
+        let temp_dir = TempDir::new().unwrap();
+        let paths = write_test_images(temp_dir.path(), &["a.png", "b.png"]);
+        let policy = ThrottlePolicy::new(BackgroundThrottleSettings {
+            pause_on_battery: false,
+            pause_when_unfocused: true,
+            mode: ThrottleMode::Throttle,
+            throttle_delay_ms: 400,
+            ..BackgroundThrottleSettings::default()
+        });
+        policy.set_app_focused(false);
+        let mut service = ThumbnailService::new(PreviewConfig::default()).with_throttle_policy(Arc::new(policy));
+
+        let mut receivers = Vec::new();
+        for path in &paths {
+            receivers.push(
+                service
+                    .generate_thumbnail_async(path, job_config(ThumbnailPriority::Background))
+                    .await
+                    .unwrap(),
+            );
+        }
+        let started = Instant::now();
+        service.start().await.unwrap();
+        for receiver in receivers {
+            tokio::time::timeout(Duration::from_secs(5), receiver).await.unwrap().unwrap().unwrap();
+        }
+        assert!(started.elapsed() >= Duration::from_millis(400));
+        service.stop().await;
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_waits_for_the_throttle_policy() {
+        let temp_dir = TempDir::new().unwrap();
+        let paths = write_test_images(temp_dir.path(), &["a.png", "b.png"]);
+        let service = ThumbnailService::new(PreviewConfig::default()).with_throttle_policy(paused_policy());
+
+        let token = CancellationToken::new();
+        let cancel = token.clone();
+        tokio::spawn(async move {
+            sleep(Duration::from_millis(300)).await;
+            cancel.cancel();
+        });
+        assert_eq!(service.prefetch_thumbnails(&paths, &token).await, 0);
+        assert_eq!(service.get_stats().await.cache_misses, 0);
+
+        let unthrottled = ThumbnailService::new(PreviewConfig::default());
+        assert_eq!(unthrottled.prefetch_thumbnails(&paths, &CancellationToken::new()).await, 2);
+        assert_eq!(unthrottled.get_stats().await.cache_misses, 2);
+    }
+
+    #[tokio::test]
+    async fn test_update_viewport_reorders_pending_jobs() {
+        let temp_dir = TempDir::new().unwrap();
+        let paths = write_test_images(temp_dir.path(), &["a.png", "b.png", "c.png"]);
+        let service = ThumbnailService::new(PreviewConfig::default());
+        for path in &paths {
+            service
+                .generate_thumbnail_async(path, job_config(ThumbnailPriority::Visible))
+                .await
+                .unwrap();
+        }
+
+        // "a" scrolled off, "c" came into view and "b" is just below it
+        service.update_viewport(&paths[2..], &paths[1..2]).await;
+
+        let queued: Vec<PathBuf> = service.job_queue.read().await.iter().map(|job| job.file_path.clone()).collect();
+        assert_eq!(queued, vec![paths[2].clone(), paths[1].clone(), paths[0].clone()]);
+        let stats = service.get_stats().await;
+        assert_eq!(stats.queued_by_priority[&ThumbnailPriority::Visible], 1);
+        assert_eq!(stats.queued_by_priority[&ThumbnailPriority::NearViewport], 1);
+        assert_eq!(stats.queued_by_priority[&ThumbnailPriority::Background], 1);
     }
 
     #[tokio::test]
@@ -603,6 +1122,53 @@ mod tests {
         assert_eq!(stats.queued_jobs, 0);
     }
 
+    #[tokio::test]
+    async fn test_get_thumbnail_scales_and_evicts_least_recently_used() {
+        let temp_dir = TempDir::new().unwrap();
+        let paths: Vec<PathBuf> = (0..3).map(|i| temp_dir.path().join(format!("photo{}.png", i))).collect();
+        for path in &paths {
+            image::RgbImage::new(400, 200).save(path).unwrap();
+        }
+        let cache = ThreadSafePreviewCache::new(PreviewCacheConfig {
+            max_entries: 2,
+            ..PreviewCacheConfig::default()
+        });
+        let service = ThumbnailService::new(PreviewConfig::default())
+            .with_max_dimension(64)
+            .with_memory_cache(cache.clone());
+
+        let thumbnail = service.get_thumbnail(&paths[0]).await.unwrap();
+        assert_eq!(thumbnail.format, "png");
+        assert_eq!((thumbnail.metadata.width, thumbnail.metadata.height), (Some(64), Some(32)));
+        service.get_thumbnail(&paths[1]).await.unwrap();
+        // Touch the first so the second is the least recently used
+        service.get_thumbnail(&paths[0]).await.unwrap();
+        service.get_thumbnail(&paths[2]).await.unwrap();
+
+        assert_eq!(cache.stats().entries, 2);
+        assert_eq!(cache.stats().evictions, 1);
+        assert!(cache.get(&PreviewCacheKey::from_path(&paths[1]).unwrap()).is_none());
+        assert!(cache.get(&PreviewCacheKey::from_path(&paths[0]).unwrap()).is_some());
+        let stats = service.get_stats().await;
+        assert_eq!((stats.cache_hits, stats.cache_misses), (1, 3));
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_image_gets_the_fallback_thumbnail() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("broken.jpg");
+        fs::write(&path, b"definitely not a jpeg").unwrap();
+        let service = ThumbnailService::new(PreviewConfig::default());
+
+        let thumbnail = service.get_thumbnail(&path).await.unwrap();
+        assert_eq!(thumbnail.format, FALLBACK_THUMBNAIL_FORMAT);
+        assert!(thumbnail.data.starts_with(b"P6"));
+        assert!(matches!(
+            service.get_thumbnail(&temp_dir.path().join("missing.jpg")).await,
+            Err(PreviewError::IoError(_))
+        ));
+    }
+
     #[tokio::test]
     async fn test_nonexistent_file() {
         let config = PreviewConfig::default();
@@ -4,8 +4,13 @@ use async_trait::async_trait;
 use chrono;
 use crate::services::preview::{
     PreviewProvider, PreviewHandler, PreviewData, PreviewConfig, PreviewError, 
-    SupportedFormat, FileMetadata, PreviewContent, VideoThumbnail
+    SupportedFormat, FileMetadata, PreviewContent, VideoThumbnail, FallbackPreviewProvider
 };
+use crate::services::preview::thumbnail_service::{DEFAULT_THUMBNAIL_MAX_DIMENSION, FALLBACK_THUMBNAIL_FORMAT};
+use crate::services::preview_cache::{
+    CachedPreviewData, PreviewCacheKey, PreviewDataMetadata, ThreadSafePreviewCache
+};
+use tokio_util::sync::CancellationToken;
 
 #[cfg(feature = "video")]
 use ffmpeg_next as ffmpeg;
@@ -666,6 +671,233 @@ impl Default for VideoPreviewHandler {
     }
 }
 
+/// How far into a video its poster frame is taken, as a fraction of the duration
+pub const POSTER_FRAME_POSITION: f64 = 0.1;
+
+/// A decoded poster frame, encoded as PNG
+struct PosterFrame {
+    png: Vec<u8>,
+    width: u32,
+    height: u32,
+    duration: Option<f64>,
+}
+
+/// Poster frames for the grid, decoded once per file version and kept in a
+/// preview cache
+///
+/// Anything that can't be decoded, including every video when the `video`
+/// feature is off, gets the fallback provider's generic thumbnail instead.
+#[derive(Clone)]
+pub struct PosterFrameExtractor {
+    cache: ThreadSafePreviewCache,
+    max_dimension: u32,
+}
+
+impl PosterFrameExtractor {
+    pub fn new(cache: ThreadSafePreviewCache) -> Self {
+        Self {
+            cache,
+            max_dimension: DEFAULT_THUMBNAIL_MAX_DIMENSION,
+        }
+    }
+
+    /// Scale poster frames so their longest edge is `max_dimension` pixels
+    pub fn with_max_dimension(mut self, max_dimension: u32) -> Self {
+        self.max_dimension = max_dimension.max(1);
+        self
+    }
+
+    /// The poster frame of the video at `path`, or a fallback thumbnail
+    pub async fn get_poster_frame(&self, path: &Path) -> CachedPreviewData {
+        let never_cancelled = CancellationToken::new();
+        match self.get_poster_frame_cancellable(path, &never_cancelled).await {
+            Ok(poster) => poster,
+            Err(_) => fallback_poster(path, 0, self.max_dimension).await,
+        }
+    }
+
+    /// Like [`get_poster_frame`](Self::get_poster_frame), but gives up with a
+    /// `TaskError` once `token` is cancelled
+    ///
+    /// Decoding runs on a blocking thread that checks `token` between packets.
+    pub async fn get_poster_frame_cancellable(
+        &self,
+        path: &Path,
+        token: &CancellationToken,
+    ) -> Result<CachedPreviewData, PreviewError> {
+        let key = PreviewCacheKey::from_path(path).ok();
+        if let Some(cached) = key.as_ref().and_then(|key| self.cache.get(key)) {
+            return Ok(cached);
+        }
+
+        let original_size = std::fs::metadata(path).map(|metadata| metadata.len()).unwrap_or(0);
+        let is_video = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(SupportedFormat::from_extension)
+            .is_some_and(|format| format.is_video());
+        let decoded = if is_video {
+            let (job_path, job_token, max_dimension) = (path.to_path_buf(), token.clone(), self.max_dimension);
+            tokio::task::spawn_blocking(move || decode_poster_frame(&job_path, max_dimension, &job_token))
+                .await
+                .map_err(|e| PreviewError::TaskError(e.to_string()))
+                .and_then(|decoded| decoded)
+        } else {
+            Err(PreviewError::UnsupportedFormat(path.display().to_string()))
+        };
+        if token.is_cancelled() {
+            return Err(poster_frame_cancelled());
+        }
+
+        let poster = match decoded {
+            Ok(frame) => {
+                let mut metadata = PreviewDataMetadata::new();
+                metadata.width = Some(frame.width);
+                metadata.height = Some(frame.height);
+                metadata.duration = frame.duration;
+                CachedPreviewData::new(frame.png, "image/png".to_string(), "png".to_string(), original_size, metadata)
+            }
+            Err(e) => {
+                tracing::debug!("Using a fallback poster frame for {:?}: {}", path, e);
+                fallback_poster(path, original_size, self.max_dimension).await
+            }
+        };
+        if let Some(key) = key {
+            if let Err(e) = self.cache.put(key, poster.clone()) {
+                tracing::warn!("Failed to cache poster frame for {:?}: {}", path, e);
+            }
+        }
+        Ok(poster)
+    }
+}
+
+fn poster_frame_cancelled() -> PreviewError {
+    PreviewError::TaskError("Poster frame extraction was cancelled".to_string())
+}
+
+async fn fallback_poster(path: &Path, original_size: u64, max_dimension: u32) -> CachedPreviewData {
+    let size = (max_dimension, max_dimension);
+    let data = PreviewProvider::generate_thumbnail(&FallbackPreviewProvider::new(), path, size)
+        .await
+        .unwrap_or_default();
+    CachedPreviewData::new(
+        data,
+        "image/x-portable-pixmap".to_string(),
+        FALLBACK_THUMBNAIL_FORMAT.to_string(),
+        original_size,
+        PreviewDataMetadata::new(),
+    )
+}
+
+/// Decode the first frame at or after `POSTER_FRAME_POSITION` of the video
+/// and scale it to fit `max_dimension`
+#[cfg(feature = "video")]
+fn decode_poster_frame(path: &Path, max_dimension: u32, token: &CancellationToken) -> Result<PosterFrame, PreviewError> {
+    use ffmpeg::format::Pixel;
+    use ffmpeg::software::scaling::{Context as Scaler, Flags};
+    use ffmpeg::util::frame::video::Video;
+
+    let video_error = |what: &str, e: ffmpeg::Error| PreviewError::VideoError(format!("{}: {}", what, e));
+    ffmpeg::init().map_err(|e| video_error("Failed to initialize FFmpeg", e))?;
+    let mut input = ffmpeg::format::input(&path).map_err(|e| video_error("Failed to open video file", e))?;
+
+    let stream = input
+        .streams()
+        .best(ffmpeg::media::Type::Video)
+        .ok_or_else(|| PreviewError::VideoError("No video stream found".to_string()))?;
+    let stream_index = stream.index();
+    let time_base = stream.time_base();
+    let seconds_per_tick = time_base.0 as f64 / time_base.1 as f64;
+    let mut decoder = ffmpeg::codec::context::Context::from_parameters(stream.parameters())
+        .map_err(|e| video_error("Failed to create codec context", e))?
+        .decoder()
+        .video()
+        .map_err(|e| video_error("Failed to create video decoder", e))?;
+
+    // Stream durations are often missing; the container's is in microseconds
+    let duration = if stream.duration() > 0 {
+        Some(stream.duration() as f64 * seconds_per_tick)
+    } else if input.duration() > 0 {
+        Some(input.duration() as f64 / 1_000_000.0)
+    } else {
+        None
+    };
+    let target_seconds = duration.map_or(0.0, |duration| duration * POSTER_FRAME_POSITION);
+    let target_pts = (target_seconds / seconds_per_tick) as i64;
+    if target_seconds > 0.0 {
+        let target = (target_seconds * 1_000_000.0) as i64;
+        if let Err(e) = input.seek(target, ..target) {
+            tracing::debug!("Could not seek {:?}, decoding from the start: {}", path, e);
+        }
+    }
+
+    let mut decoded = Video::empty();
+    let mut poster = None;
+    let mut take_frame = |decoder: &mut ffmpeg::decoder::Video, poster: &mut Option<Video>| {
+        while decoder.receive_frame(&mut decoded).is_ok() {
+            let reached = decoded.pts().map_or(true, |pts| pts >= target_pts);
+            *poster = Some(decoded.clone());
+            if reached {
+                return true;
+            }
+        }
+        false
+    };
+    for (packet_stream, packet) in input.packets() {
+        if token.is_cancelled() {
+            return Err(poster_frame_cancelled());
+        }
+        if packet_stream.index() != stream_index || decoder.send_packet(&packet).is_err() {
+            continue;
+        }
+        if take_frame(&mut decoder, &mut poster) {
+            break;
+        }
+    }
+    // A video shorter than expected ends before the target; use its last frame
+    if poster.is_none() && decoder.send_eof().is_ok() {
+        take_frame(&mut decoder, &mut poster);
+    }
+    let frame = poster.ok_or_else(|| PreviewError::VideoError("No frame could be decoded".to_string()))?;
+
+    let scale = (max_dimension as f64 / frame.width().max(frame.height()).max(1) as f64).min(1.0);
+    let width = ((frame.width() as f64 * scale).round() as u32).max(1);
+    let height = ((frame.height() as f64 * scale).round() as u32).max(1);
+    let mut scaler = Scaler::get(frame.format(), frame.width(), frame.height(), Pixel::RGB24, width, height, Flags::BILINEAR)
+        .map_err(|e| video_error("Failed to create frame scaler", e))?;
+    let mut rgb = Video::empty();
+    scaler.run(&frame, &mut rgb).map_err(|e| video_error("Failed to scale frame", e))?;
+
+    // Rows of the scaled frame may be padded past `width * 3` bytes
+    let stride = rgb.stride(0);
+    let row_bytes = width as usize * 3;
+    let pixels = rgb
+        .data(0)
+        .chunks(stride)
+        .take(height as usize)
+        .flat_map(|row| &row[..row_bytes])
+        .copied()
+        .collect();
+    let image = image::RgbImage::from_raw(width, height, pixels)
+        .ok_or_else(|| PreviewError::VideoError("Scaled frame has the wrong size".to_string()))?;
+    let mut png = Vec::new();
+    image::DynamicImage::ImageRgb8(image)
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .map_err(|e| PreviewError::VideoError(format!("Failed to encode poster frame: {}", e)))?;
+
+    Ok(PosterFrame {
+        png,
+        width,
+        height,
+        duration,
+    })
+}
+
+#[cfg(not(feature = "video"))]
+fn decode_poster_frame(_path: &Path, _max_dimension: u32, _token: &CancellationToken) -> Result<PosterFrame, PreviewError> {
+    Err(PreviewError::VideoError("Video support not enabled. Enable the 'video' feature.".to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -745,6 +977,33 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_poster_frame_falls_back_for_undecodable_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let text_path = temp_dir.path().join("notes.txt");
+        let broken_path = temp_dir.path().join("broken.mp4");
+        fs::write(&text_path, "This is not a video").unwrap();
+        fs::write(&broken_path, b"fake video data").unwrap();
+
+        let cache = ThreadSafePreviewCache::new(crate::services::preview_cache::PreviewCacheConfig::default());
+        let extractor = PosterFrameExtractor::new(cache.clone());
+        for path in [&text_path, &broken_path] {
+            let poster = extractor.get_poster_frame(path).await;
+            assert_eq!(poster.format, FALLBACK_THUMBNAIL_FORMAT);
+            assert!(!poster.data.is_empty());
+        }
+        assert_eq!(cache.stats().entries, 2);
+
+        let token = CancellationToken::new();
+        token.cancel();
+        let fresh = temp_dir.path().join("other.mp4");
+        fs::write(&fresh, b"fake video data").unwrap();
+        assert!(matches!(
+            extractor.get_poster_frame_cancellable(&fresh, &token).await,
+            Err(PreviewError::TaskError(_))
+        ));
+    }
+
     #[cfg(feature = "video")]
     #[tokio::test]
     async fn test_video_preview_with_ffmpeg() {
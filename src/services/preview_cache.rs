@@ -26,11 +26,15 @@ pub enum PreviewCacheError {
 pub struct PreviewCacheKey {
     pub path: PathBuf,
     pub modified_time: SystemTime,
+    /// Source file size, when known; a different size also invalidates the key
+    pub size: Option<u64>,
+    /// Which of several previews of the same file version, e.g. a PDF page
+    pub variant: Option<String>,
 }
 
 impl PreviewCacheKey {
     pub fn new(path: PathBuf, modified_time: SystemTime) -> Self {
-        Self { path, modified_time }
+        Self { path, modified_time, size: None, variant: None }
     }
 
     /// Create key from path, automatically getting modification time from filesystem
@@ -40,15 +44,24 @@ impl PreviewCacheKey {
         Ok(Self {
             path: path.to_path_buf(),
             modified_time,
+            size: Some(metadata.len()),
+            variant: None,
         })
     }
 
+    /// Key for one of several previews of this file version
+    pub fn with_variant(mut self, variant: impl Into<String>) -> Self {
+        self.variant = Some(variant.into());
+        self
+    }
+
     /// Check if this key is still valid (file hasn't been modified)
     pub fn is_valid(&self) -> bool {
         match std::fs::metadata(&self.path) {
             Ok(metadata) => {
                 let current_modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
                 current_modified <= self.modified_time
+                    && self.size.map_or(true, |size| size == metadata.len())
             }
             Err(_) => false, // File doesn't exist, key is invalid
         }
@@ -103,6 +116,16 @@ impl CachedPreviewData {
     pub fn is_fresh(&self, max_age: Duration) -> bool {
         self.generated_at.elapsed().unwrap_or(Duration::MAX) < max_age
     }
+
+    /// Encode the preview as a `data:` URL the webview can display directly
+    pub fn to_data_url(&self) -> String {
+        use base64::Engine;
+        format!(
+            "data:{};base64,{}",
+            self.content_type,
+            base64::engine::general_purpose::STANDARD.encode(&self.data)
+        )
+    }
 }
 
 /// Additional metadata for preview data
@@ -154,6 +177,12 @@ impl CacheNode {
     }
 }
 
+/// Default size limit of the on-disk tier
+pub const DEFAULT_MAX_DISK_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Extension of the files in the on-disk tier
+const DISK_ENTRY_EXTENSION: &str = "preview";
+
 /// Configuration for the preview LRU cache
 #[derive(Debug, Clone)]
 pub struct PreviewCacheConfig {
@@ -165,6 +194,10 @@ pub struct PreviewCacheConfig {
     pub max_age: Duration,
     /// Maximum size for individual preview data
     pub max_single_item_bytes: usize,
+    /// Directory of the on-disk tier; `None` keeps previews in memory only
+    pub disk_cache_dir: Option<PathBuf>,
+    /// Size limit of the on-disk tier, kept by evicting the least recently read previews
+    pub max_disk_bytes: u64,
 }
 
 impl Default for PreviewCacheConfig {
@@ -174,10 +207,34 @@ impl Default for PreviewCacheConfig {
             max_memory_bytes: 500 * 1024 * 1024,      // 500MB as per requirements
             max_age: Duration::from_secs(24 * 3600),  // 24 hours default
             max_single_item_bytes: 50 * 1024 * 1024,  // 50MB max per item
+            disk_cache_dir: None,
+            max_disk_bytes: DEFAULT_MAX_DISK_BYTES,
         }
     }
 }
 
+impl PreviewCacheConfig {
+    /// Also keep previews in `dir`, so they survive restarts
+    pub fn with_disk_cache(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.disk_cache_dir = Some(dir.into());
+        self
+    }
+
+    /// Limit the on-disk tier to `max_disk_bytes`
+    pub fn with_max_disk_bytes(mut self, max_disk_bytes: u64) -> Self {
+        self.max_disk_bytes = max_disk_bytes;
+        self
+    }
+
+    /// `previews` in the user's cache directory
+    pub fn default_disk_cache_dir() -> PathBuf {
+        dirs::cache_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("media-organizer")
+            .join("previews")
+    }
+}
+
 /// Statistics about the preview cache
 #[derive(Debug, Clone)]
 pub struct PreviewCacheStats {
@@ -197,6 +254,14 @@ pub struct PreviewCacheStats {
     pub most_recent_key: Option<PreviewCacheKey>,
     /// Least recently used entry (for debugging)
     pub least_recent_key: Option<PreviewCacheKey>,
+    /// Previews stored in the on-disk tier
+    pub disk_entries: usize,
+    /// Bytes used by the on-disk tier
+    pub disk_bytes: u64,
+    /// Memory misses answered from disk
+    pub disk_hits: u64,
+    /// Memory misses the disk couldn't answer either
+    pub disk_misses: u64,
 }
 
 /// In-memory LRU cache for preview data
@@ -347,6 +412,10 @@ impl PreviewLRUCache {
             least_recent_key: self.tail.and_then(|idx| {
                 self.nodes.get(idx)?.as_ref().map(|node| node.key.clone())
             }),
+            disk_entries: 0,
+            disk_bytes: 0,
+            disk_hits: 0,
+            disk_misses: 0,
         }
     }
 
@@ -536,36 +605,283 @@ impl PreviewLRUCache {
     }
 }
 
+/// Header of a preview file in the on-disk tier
+#[derive(Serialize, Deserialize)]
+struct DiskEntryHeader {
+    source: PathBuf,
+    modified_time: SystemTime,
+    size: Option<u64>,
+    variant: Option<String>,
+    /// The preview with its data left out; the data follows the header
+    preview: CachedPreviewData,
+}
+
+/// A preview file and when it was last read
+struct DiskEntry {
+    bytes: u64,
+    last_access: u64,
+}
+
+/// On-disk tier of [`ThreadSafePreviewCache`]
+///
+/// Each preview is one file named after a hash of its source path and variant:
+/// a length-prefixed JSON header with the source's mtime and size, then the
+/// preview bytes. A lookup whose key no longer matches the header deletes the
+/// file, so edited sources never serve an old preview.
+pub struct DiskPreviewCache {
+    dir: PathBuf,
+    max_bytes: u64,
+    entries: HashMap<String, DiskEntry>,
+    total_bytes: u64,
+    /// Access clock; higher means more recently read or written
+    clock: u64,
+    hits: u64,
+    misses: u64,
+}
+
+impl DiskPreviewCache {
+    /// Open the cache in `dir`, picking up previews left by earlier runs
+    ///
+    /// Their file modification times give the initial access order.
+    pub fn open(dir: impl Into<PathBuf>, max_bytes: u64) -> std::io::Result<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+
+        let mut found = Vec::new();
+        for entry in std::fs::read_dir(&dir)?.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(DISK_ENTRY_EXTENSION) {
+                continue;
+            }
+            if let (Some(name), Ok(metadata)) = (path.file_name().and_then(|name| name.to_str()), entry.metadata()) {
+                let accessed = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                found.push((name.to_string(), metadata.len(), accessed));
+            }
+        }
+        found.sort_by_key(|(_, _, accessed)| *accessed);
+
+        let mut cache = Self {
+            dir,
+            max_bytes,
+            entries: HashMap::new(),
+            total_bytes: 0,
+            clock: 0,
+            hits: 0,
+            misses: 0,
+        };
+        for (name, bytes, _) in found {
+            cache.clock += 1;
+            cache.total_bytes += bytes;
+            cache.entries.insert(name, DiskEntry { bytes, last_access: cache.clock });
+        }
+        cache.enforce_limit();
+        Ok(cache)
+    }
+
+    /// The stored preview for `key`, if it was made from the same file version
+    pub fn get(&mut self, key: &PreviewCacheKey) -> Option<CachedPreviewData> {
+        let name = Self::file_name(key);
+        if !self.entries.contains_key(&name) {
+            self.misses += 1;
+            return None;
+        }
+
+        let current = std::fs::read(self.dir.join(&name))
+            .ok()
+            .and_then(|bytes| Self::decode(&bytes))
+            .filter(|header| {
+                header.modified_time == key.modified_time && header.size == key.size && key.is_valid()
+            });
+        let Some(header) = current else {
+            self.remove_entry(&name);
+            self.misses += 1;
+            return None;
+        };
+
+        self.clock += 1;
+        if let Some(entry) = self.entries.get_mut(&name) {
+            entry.last_access = self.clock;
+        }
+        // The file's mtime carries the access order over to the next run
+        if let Ok(file) = std::fs::File::options().write(true).open(self.dir.join(&name)) {
+            let _ = file.set_modified(SystemTime::now());
+        }
+        self.hits += 1;
+        Some(header.preview)
+    }
+
+    /// Store `data` for `key`, evicting the least recently read previews past the size limit
+    pub fn put(&mut self, key: &PreviewCacheKey, data: &CachedPreviewData) -> std::io::Result<()> {
+        let header = DiskEntryHeader {
+            source: key.path.clone(),
+            modified_time: key.modified_time,
+            size: key.size,
+            variant: key.variant.clone(),
+            preview: CachedPreviewData { data: Vec::new(), ..data.clone() },
+        };
+        let header = serde_json::to_vec(&header)?;
+        let mut bytes = Vec::with_capacity(4 + header.len() + data.data.len());
+        bytes.extend_from_slice(&(header.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&header);
+        bytes.extend_from_slice(&data.data);
+        if bytes.len() as u64 > self.max_bytes {
+            return Ok(());
+        }
+
+        // Write next to the final name first so readers never see half a preview
+        let name = Self::file_name(key);
+        let partial = self.dir.join(format!("{}.partial", name));
+        std::fs::write(&partial, &bytes)?;
+        std::fs::rename(&partial, self.dir.join(&name))?;
+
+        self.clock += 1;
+        let entry = DiskEntry { bytes: bytes.len() as u64, last_access: self.clock };
+        if let Some(previous) = self.entries.insert(name, entry) {
+            self.total_bytes -= previous.bytes;
+        }
+        self.total_bytes += bytes.len() as u64;
+        self.enforce_limit();
+        Ok(())
+    }
+
+    pub fn remove(&mut self, key: &PreviewCacheKey) -> bool {
+        self.remove_entry(&Self::file_name(key))
+    }
+
+    /// Delete every stored preview
+    pub fn clear(&mut self) {
+        let names: Vec<String> = self.entries.keys().cloned().collect();
+        for name in names {
+            self.remove_entry(&name);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.total_bytes
+    }
+
+    fn file_name(key: &PreviewCacheKey) -> String {
+        use sha2::{Digest, Sha256};
+
+        let mut hasher = Sha256::new();
+        hasher.update(key.path.to_string_lossy().as_bytes());
+        if let Some(variant) = &key.variant {
+            hasher.update([0]);
+            hasher.update(variant.as_bytes());
+        }
+        format!("{}.{}", hex::encode(hasher.finalize()), DISK_ENTRY_EXTENSION)
+    }
+
+    fn decode(bytes: &[u8]) -> Option<DiskEntryHeader> {
+        let header_len = u32::from_le_bytes(bytes.get(..4)?.try_into().ok()?) as usize;
+        let header_end = 4usize.checked_add(header_len)?;
+        let mut header: DiskEntryHeader = serde_json::from_slice(bytes.get(4..header_end)?).ok()?;
+        header.preview.data = bytes[header_end..].to_vec();
+        Some(header)
+    }
+
+    fn remove_entry(&mut self, name: &str) -> bool {
+        let removed = self.entries.remove(name);
+        if let Some(entry) = &removed {
+            self.total_bytes -= entry.bytes;
+        }
+        let _ = std::fs::remove_file(self.dir.join(name));
+        removed.is_some()
+    }
+
+    fn enforce_limit(&mut self) {
+        while self.total_bytes > self.max_bytes {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_access)
+                .map(|(name, _)| name.clone());
+            match oldest {
+                Some(name) => {
+                    self.remove_entry(&name);
+                }
+                None => break,
+            }
+        }
+    }
+}
+
 /// Thread-safe wrapper around PreviewLRUCache
+///
+/// With a `disk_cache_dir` configured, lookups check memory, then disk, and
+/// previews are written to both.
 pub struct ThreadSafePreviewCache {
     cache: Arc<Mutex<PreviewLRUCache>>,
+    disk: Option<Arc<Mutex<DiskPreviewCache>>>,
 }
 
 impl ThreadSafePreviewCache {
     pub fn new(config: PreviewCacheConfig) -> Self {
+        let disk = config.disk_cache_dir.as_ref().and_then(|dir| {
+            match DiskPreviewCache::open(dir, config.max_disk_bytes) {
+                Ok(disk) => Some(Arc::new(Mutex::new(disk))),
+                Err(e) => {
+                    tracing::warn!("Preview disk cache unavailable at {:?}: {}", dir, e);
+                    None
+                }
+            }
+        });
         Self {
             cache: Arc::new(Mutex::new(PreviewLRUCache::new(config))),
+            disk,
         }
     }
 
     pub fn get(&self, key: &PreviewCacheKey) -> Option<CachedPreviewData> {
-        self.cache.lock().unwrap().get(key).cloned()
+        if let Some(cached) = self.cache.lock().unwrap().get(key).cloned() {
+            return Some(cached);
+        }
+        let cached = self.disk.as_ref()?.lock().unwrap().get(key)?;
+        // Keep it in memory for the next lookup; a full memory tier just skips that
+        let _ = self.cache.lock().unwrap().put(key.clone(), cached.clone());
+        Some(cached)
     }
 
     pub fn put(&self, key: PreviewCacheKey, data: CachedPreviewData) -> Result<(), PreviewCacheError> {
+        if let Some(disk) = &self.disk {
+            if let Err(e) = disk.lock().unwrap().put(&key, &data) {
+                tracing::warn!("Failed to write preview of {:?} to disk: {}", key.path, e);
+            }
+        }
         self.cache.lock().unwrap().put(key, data)
     }
 
     pub fn remove(&self, key: &PreviewCacheKey) -> bool {
-        self.cache.lock().unwrap().remove(key)
+        let on_disk = self.disk.as_ref().is_some_and(|disk| disk.lock().unwrap().remove(key));
+        self.cache.lock().unwrap().remove(key) || on_disk
     }
 
+    /// Empty the memory tier; previews on disk stay for the next run
     pub fn clear(&self) {
         self.cache.lock().unwrap().clear();
     }
 
+    /// Delete every preview from the on-disk tier
+    pub fn clear_disk(&self) {
+        if let Some(disk) = &self.disk {
+            disk.lock().unwrap().clear();
+        }
+    }
+
     pub fn stats(&self) -> PreviewCacheStats {
-        self.cache.lock().unwrap().stats()
+        let mut stats = self.cache.lock().unwrap().stats();
+        if let Some(disk) = &self.disk {
+            let disk = disk.lock().unwrap();
+            stats.disk_entries = disk.len();
+            stats.disk_bytes = disk.total_bytes();
+            stats.disk_hits = disk.hits;
+            stats.disk_misses = disk.misses;
+        }
+        stats
     }
 
     pub fn cleanup_stale(&self) -> usize {
@@ -589,6 +905,7 @@ impl Clone for ThreadSafePreviewCache {
     fn clone(&self) -> Self {
         Self {
             cache: Arc::clone(&self.cache),
+            disk: self.disk.clone(),
         }
     }
 }
@@ -835,4 +1152,61 @@ mod tests {
         // Should now need cleanup
         assert!(cache.needs_cleanup());
     }
+    #[test]
+    fn test_disk_tier_survives_restart_and_drops_edited_sources() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let source = temp_dir.path().join("photo.jpg");
+        std::fs::write(&source, b"original").unwrap();
+        let config = PreviewCacheConfig::default().with_disk_cache(temp_dir.path().join("previews"));
+
+        let key = PreviewCacheKey::from_path(&source).unwrap();
+        let first_run = ThreadSafePreviewCache::new(config.clone());
+        first_run.put(key.clone(), create_test_data(100, "image/png")).unwrap();
+
+        // A new cache over the same directory stands in for the next app start
+        let second_run = ThreadSafePreviewCache::new(config.clone());
+        let restored = second_run.get(&key).unwrap();
+        assert_eq!(restored.data.len(), 100);
+        assert_eq!(restored.content_type, "image/png");
+        let stats = second_run.stats();
+        assert_eq!((stats.disk_hits, stats.disk_entries), (1, 1));
+
+        std::fs::write(&source, b"edited, and longer").unwrap();
+        let later = SystemTime::now() + Duration::from_secs(60);
+        std::fs::File::options().write(true).open(&source).unwrap().set_modified(later).unwrap();
+        let third_run = ThreadSafePreviewCache::new(config);
+        assert!(third_run.get(&PreviewCacheKey::from_path(&source).unwrap()).is_none());
+        let stats = third_run.stats();
+        assert_eq!((stats.disk_misses, stats.disk_entries, stats.disk_bytes), (1, 0, 0));
+    }
+
+    #[test]
+    fn test_disk_tier_evicts_least_recently_read_past_size_limit() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let keys: Vec<PreviewCacheKey> = (0..3)
+            .map(|i| {
+                let source = temp_dir.path().join(format!("photo{}.jpg", i));
+                std::fs::write(&source, b"photo").unwrap();
+                PreviewCacheKey::from_path(&source).unwrap()
+            })
+            .collect();
+        let mut disk = DiskPreviewCache::open(temp_dir.path().join("previews"), 3000).unwrap();
+
+        disk.put(&keys[0], &create_test_data(1000, "image/png")).unwrap();
+        disk.put(&keys[1], &create_test_data(1000, "image/png")).unwrap();
+        assert!(disk.get(&keys[0]).is_some());
+        disk.put(&keys[2], &create_test_data(1000, "image/png")).unwrap();
+
+        assert!(disk.total_bytes() <= 3000);
+        assert_eq!(disk.len(), 2);
+        assert!(disk.get(&keys[1]).is_none());
+        assert!(disk.get(&keys[0]).is_some());
+        assert!(disk.get(&keys[2]).is_some());
+
+        // Previews bigger than the whole tier are not stored at all
+        disk.put(&keys[1], &create_test_data(5000, "image/png")).unwrap();
+        assert_eq!(disk.len(), 2);
+        let files = std::fs::read_dir(temp_dir.path().join("previews")).unwrap().count();
+        assert_eq!(files, 2);
+    }
 }
\ No newline at end of file
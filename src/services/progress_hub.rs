@@ -0,0 +1,370 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use crate::services::duplicate_detection::DetectionProgress;
+use crate::services::operations::{BatchProgress, ProgressInfo};
+use crate::services::output::OutputLog;
+
+/// Byte weight given to one unit of work for tasks that only report item counts (1MB)
+pub const ITEM_WEIGHT_BYTES: u64 = 1024 * 1024;
+
+/// Kind of task shown in the progress HUD
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskKind {
+    FileOperation,
+    Batch,
+    DuplicateScan,
+    Thumbnails,
+    Other,
+}
+
+impl TaskKind {
+    /// Icon shown next to the task in the HUD
+    pub fn icon(&self) -> &'static str {
+        match self {
+            TaskKind::FileOperation => "📄",
+            TaskKind::Batch => "📚",
+            TaskKind::DuplicateScan => "🔍",
+            TaskKind::Thumbnails => "🖼️",
+            TaskKind::Other => "⚙️",
+        }
+    }
+}
+
+/// Cancel hook for a registered task
+pub type CancelFn = Arc<dyn Fn() + Send + Sync>;
+
+/// Progress of one running task, normalised to bytes
+#[derive(Clone)]
+pub struct TaskProgress {
+    pub id: Uuid,
+    pub label: String,
+    pub kind: TaskKind,
+    /// Work done so far, in bytes (or byte-weighted items)
+    pub completed_bytes: u64,
+    /// Total work, in bytes (or byte-weighted items)
+    pub total_bytes: u64,
+    /// Current throughput in bytes per second, if known
+    pub bytes_per_second: Option<f64>,
+    pub started_at: Instant,
+    cancel: Option<CancelFn>,
+}
+
+impl std::fmt::Debug for TaskProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaskProgress")
+            .field("id", &self.id)
+            .field("label", &self.label)
+            .field("kind", &self.kind)
+            .field("completed_bytes", &self.completed_bytes)
+            .field("total_bytes", &self.total_bytes)
+            .field("bytes_per_second", &self.bytes_per_second)
+            .field("cancellable", &self.cancel.is_some())
+            .finish()
+    }
+}
+
+impl TaskProgress {
+    /// Create an empty progress entry
+    pub fn new(label: impl Into<String>, kind: TaskKind) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            label: label.into(),
+            kind,
+            completed_bytes: 0,
+            total_bytes: 0,
+            bytes_per_second: None,
+            started_at: Instant::now(),
+            cancel: None,
+        }
+    }
+
+    /// Bytes still to be processed
+    pub fn remaining_bytes(&self) -> u64 {
+        self.total_bytes.saturating_sub(self.completed_bytes)
+    }
+
+    /// Completion percentage (0-100)
+    pub fn percentage(&self) -> f64 {
+        if self.total_bytes == 0 {
+            0.0
+        } else {
+            (self.completed_bytes as f64 / self.total_bytes as f64 * 100.0).min(100.0)
+        }
+    }
+
+    /// Whether the task can be cancelled from the HUD
+    pub fn can_cancel(&self) -> bool {
+        self.cancel.is_some()
+    }
+
+    /// Update from a file operation tracker, preferring bytes over items
+    pub fn apply_progress_info(&mut self, info: &ProgressInfo) {
+        if info.total_bytes > 0 {
+            self.completed_bytes = info.bytes_processed;
+            self.total_bytes = info.total_bytes;
+            self.bytes_per_second = (info.speed_bps > 0).then_some(info.speed_bps as f64);
+        } else {
+            self.set_items(info.current, info.total);
+        }
+    }
+
+    /// Update from a batch, which only reports command counts
+    pub fn apply_batch_progress(&mut self, progress: &BatchProgress) {
+        self.set_items(
+            (progress.completed_commands + progress.failed_commands) as u64,
+            progress.total_commands as u64,
+        );
+    }
+
+    /// Update from a duplicate scan, whose percentage spans all phases
+    pub fn apply_detection_progress(&mut self, progress: &DetectionProgress) {
+        let total = progress.total_files.max(1) as u64;
+        let fraction = (progress.progress_percentage / 100.0).clamp(0.0, 1.0);
+        self.set_items((fraction * total as f64).round() as u64, total);
+    }
+
+    fn set_items(&mut self, completed: u64, total: u64) {
+        self.completed_bytes = completed.saturating_mul(ITEM_WEIGHT_BYTES);
+        self.total_bytes = total.saturating_mul(ITEM_WEIGHT_BYTES);
+
+        // Derive throughput from elapsed time since item counts carry no speed
+        let elapsed = self.started_at.elapsed().as_secs_f64();
+        self.bytes_per_second = (elapsed > 0.0 && self.completed_bytes > 0)
+            .then(|| self.completed_bytes as f64 / elapsed);
+    }
+}
+
+/// Aggregate view of all running tasks
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProgressSummary {
+    pub running_tasks: usize,
+    /// Overall completion (0-100), weighted by each task's size
+    pub overall_percentage: f64,
+    pub remaining_bytes: u64,
+    /// Combined remaining work divided by combined throughput
+    pub eta: Option<Duration>,
+}
+
+/// Combine task progress into a single summary
+///
+/// Larger tasks count for more: the overall percentage is total completed
+/// bytes over total bytes, and the ETA is total remaining bytes over the
+/// combined throughput of the tasks that report one.
+pub fn aggregate(tasks: &[TaskProgress]) -> ProgressSummary {
+    let total: u64 = tasks.iter().map(|t| t.total_bytes).sum();
+    let completed: u64 = tasks.iter().map(|t| t.completed_bytes.min(t.total_bytes)).sum();
+    let remaining = total - completed;
+    let throughput: f64 = tasks
+        .iter()
+        .filter(|t| t.remaining_bytes() > 0)
+        .filter_map(|t| t.bytes_per_second)
+        .sum();
+
+    let overall_percentage = if total == 0 {
+        0.0
+    } else {
+        completed as f64 / total as f64 * 100.0
+    };
+    let eta = if remaining == 0 {
+        Some(Duration::ZERO)
+    } else if throughput > 0.0 {
+        Some(Duration::from_secs_f64(remaining as f64 / throughput))
+    } else {
+        None
+    };
+
+    ProgressSummary {
+        running_tasks: tasks.len(),
+        overall_percentage,
+        remaining_bytes: remaining,
+        eta,
+    }
+}
+
+/// Registry of running tasks feeding the global progress HUD
+#[derive(Debug, Default)]
+pub struct ProgressHub {
+    tasks: RwLock<HashMap<Uuid, TaskProgress>>,
+    /// Receives a line when a task starts and finishes
+    output: Option<Arc<OutputLog>>,
+}
+
+impl ProgressHub {
+    /// Create an empty hub
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Report task starts and finishes to the Output panel
+    pub fn with_output(mut self, output: Arc<OutputLog>) -> Self {
+        self.output = Some(output);
+        self
+    }
+
+    /// Register a task; it is removed when the returned handle is dropped
+    pub fn register(
+        self: &Arc<Self>,
+        label: impl Into<String>,
+        kind: TaskKind,
+        cancel: Option<CancelFn>,
+    ) -> TaskHandle {
+        let mut task = TaskProgress::new(label, kind);
+        task.cancel = cancel;
+        let id = task.id;
+        if let Some(output) = &self.output {
+            output.info(&task.label, "Started");
+        }
+        if let Ok(mut tasks) = self.tasks.write() {
+            tasks.insert(id, task);
+        }
+        TaskHandle { hub: self.clone(), id }
+    }
+
+    /// Running tasks, oldest first
+    pub fn tasks(&self) -> Vec<TaskProgress> {
+        let mut tasks: Vec<TaskProgress> = self
+            .tasks
+            .read()
+            .map(|tasks| tasks.values().cloned().collect())
+            .unwrap_or_default();
+        tasks.sort_by_key(|t| t.started_at);
+        tasks
+    }
+
+    /// Aggregate summary of all running tasks
+    pub fn summary(&self) -> ProgressSummary {
+        aggregate(&self.tasks())
+    }
+
+    /// Request cancellation of a task; returns false if it is unknown or not cancellable
+    pub fn cancel(&self, id: Uuid) -> bool {
+        let cancel = self
+            .tasks
+            .read()
+            .ok()
+            .and_then(|tasks| tasks.get(&id).and_then(|t| t.cancel.clone()));
+        match cancel {
+            Some(cancel) => {
+                cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn update(&self, id: Uuid, apply: impl FnOnce(&mut TaskProgress)) {
+        if let Ok(mut tasks) = self.tasks.write() {
+            if let Some(task) = tasks.get_mut(&id) {
+                apply(task);
+            }
+        }
+    }
+
+    fn remove(&self, id: Uuid) {
+        let removed = self.tasks.write().ok().and_then(|mut tasks| tasks.remove(&id));
+        if let (Some(task), Some(output)) = (removed, &self.output) {
+            output.info(&task.label, format!("Finished in {:.1}s", task.started_at.elapsed().as_secs_f64()));
+        }
+    }
+}
+
+/// Handle used by a running task to publish progress
+#[derive(Debug)]
+pub struct TaskHandle {
+    hub: Arc<ProgressHub>,
+    id: Uuid,
+}
+
+impl TaskHandle {
+    /// Task identifier, used for cancellation
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// Publish progress from a file operation tracker
+    pub fn update_progress_info(&self, info: &ProgressInfo) {
+        self.hub.update(self.id, |task| task.apply_progress_info(info));
+    }
+
+    /// Publish progress from a batch operation
+    pub fn update_batch_progress(&self, progress: &BatchProgress) {
+        self.hub.update(self.id, |task| task.apply_batch_progress(progress));
+    }
+
+    /// Publish progress from a duplicate scan
+    pub fn update_detection_progress(&self, progress: &DetectionProgress) {
+        self.hub.update(self.id, |task| task.apply_detection_progress(progress));
+    }
+}
+
+impl Drop for TaskHandle {
+    fn drop(&mut self) {
+        self.hub.remove(self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(completed: u64, total: u64, rate: Option<f64>) -> TaskProgress {
+        let mut task = TaskProgress::new("task", TaskKind::FileOperation);
+        task.completed_bytes = completed;
+        task.total_bytes = total;
+        task.bytes_per_second = rate;
+        task
+    }
+
+    #[test]
+    fn test_aggregate_weights_tasks_by_remaining_bytes() {
+        // A nearly finished small copy and a barely started large scan
+        let small = task(90, 100, Some(10.0));
+        let large = task(100, 1000, Some(90.0));
+
+        let summary = aggregate(&[small, large]);
+        assert_eq!(summary.running_tasks, 2);
+        assert_eq!(summary.remaining_bytes, 910);
+
+        // 190 of 1100 bytes done, not the 50% a plain average of 90% and 10% would give
+        assert!((summary.overall_percentage - 190.0 / 1100.0 * 100.0).abs() < 1e-9);
+
+        // 910 remaining bytes at a combined 100 bytes/sec
+        assert_eq!(summary.eta, Some(Duration::from_secs_f64(9.1)));
+    }
+
+    #[test]
+    fn test_aggregate_ignores_finished_task_throughput_and_unknown_rates() {
+        let finished = task(50, 50, Some(1000.0));
+        let running = task(0, 200, Some(20.0));
+        let summary = aggregate(&[finished, running]);
+        assert_eq!(summary.eta, Some(Duration::from_secs(10)));
+
+        let unknown = aggregate(&[task(0, 200, None)]);
+        assert_eq!(unknown.eta, None);
+        assert_eq!(aggregate(&[]).overall_percentage, 0.0);
+    }
+
+    #[test]
+    fn test_hub_registration_and_cancel() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let hub = Arc::new(ProgressHub::new());
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let flag = cancelled.clone();
+        let handle = hub.register("Copy", TaskKind::FileOperation, Some(Arc::new(move || flag.store(true, Ordering::SeqCst))));
+
+        let mut batch = BatchProgress::new(4);
+        batch.completed_commands = 1;
+        handle.update_batch_progress(&batch);
+        assert_eq!(hub.summary().overall_percentage, 25.0);
+
+        assert!(hub.cancel(handle.id()));
+        assert!(cancelled.load(Ordering::SeqCst));
+
+        drop(handle);
+        assert_eq!(hub.summary().running_tasks, 0);
+    }
+}
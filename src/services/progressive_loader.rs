@@ -1,3 +1,4 @@
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
@@ -9,6 +10,7 @@ use thiserror::Error;
 use tracing::{debug, info};
 
 use super::file_system::{FileSystemError, PreviewMetadata};
+use super::preview::thumbnail_service::ThumbnailService;
 use super::preview_cache::{CachedPreviewData, PreviewDataMetadata};
 
 /// Errors that can occur during progressive loading
@@ -45,6 +47,8 @@ pub struct ProgressiveLoaderConfig {
     pub generate_intermediate_previews: bool,
     /// Maximum number of chunks to buffer in memory
     pub max_buffered_chunks: usize,
+    /// Rows either side of the viewport whose thumbnails count as near it
+    pub viewport_margin: usize,
 }
 
 impl Default for ProgressiveLoaderConfig {
@@ -56,6 +60,7 @@ impl Default for ProgressiveLoaderConfig {
             preload_metadata: true,
             generate_intermediate_previews: true,
             max_buffered_chunks: 4, // 32MB buffer max
+            viewport_margin: 20,
         }
     }
 }
@@ -82,7 +87,7 @@ pub struct LoadingProgress {
 }
 
 impl LoadingProgress {
-    fn new(total_bytes: u64, total_chunks: usize) -> Self {
+    pub(crate) fn new(total_bytes: u64, total_chunks: usize) -> Self {
         Self {
             total_bytes,
             processed_bytes: 0,
@@ -114,6 +119,10 @@ pub enum LoadingStage {
     ReadingMetadata,
     ProcessingChunk(usize),
     GeneratingPreview,
+    /// Low-resolution placeholder ready in `intermediate_preview`
+    Preview,
+    /// Full-quality image ready in `intermediate_preview`
+    Full,
     Finalizing,
     Complete,
 }
@@ -165,12 +174,14 @@ impl ProgressiveLoadHandle {
 /// Core progressive loader for handling large files
 pub struct ProgressiveLoader {
     config: ProgressiveLoaderConfig,
+    /// Thumbnail queue re-prioritized by `set_viewport`
+    thumbnail_service: Option<Arc<ThumbnailService>>,
 }
 
 impl ProgressiveLoader {
     /// Create a new progressive loader with the given configuration
     pub fn new(config: ProgressiveLoaderConfig) -> Self {
-        Self { config }
+        Self { config, thumbnail_service: None }
     }
 
     /// Create a new progressive loader with default configuration
@@ -178,6 +189,24 @@ impl ProgressiveLoader {
         Self::new(ProgressiveLoaderConfig::default())
     }
 
+    /// Report viewport changes to `thumbnail_service` so it generates what's on screen first
+    pub fn with_thumbnail_service(mut self, thumbnail_service: Arc<ThumbnailService>) -> Self {
+        self.thumbnail_service = Some(thumbnail_service);
+        self
+    }
+
+    /// Tell the thumbnail queue which of `entries` are on screen
+    ///
+    /// `visible` indexes into `entries`; rows within the configured margin of it
+    /// count as near the viewport and everything else drops to background.
+    pub async fn set_viewport(&self, entries: &[PathBuf], visible: Range<usize>) {
+        let Some(thumbnail_service) = &self.thumbnail_service else {
+            return;
+        };
+        let (visible_paths, near_paths) = viewport_slices(entries, visible, self.config.viewport_margin);
+        thumbnail_service.update_viewport(&visible_paths, &near_paths).await;
+    }
+
     /// Start loading a file progressively
     pub async fn load_file(&self, file_path: &Path) -> Result<ProgressiveLoadHandle, ProgressiveLoaderError> {
         debug!("Starting progressive loading for: {}", file_path.display());
@@ -510,12 +539,42 @@ impl ProgressiveLoader {
     }
 }
 
+/// Split `entries` into the rows in `visible` and the rows within `margin` of them
+fn viewport_slices(entries: &[PathBuf], visible: Range<usize>, margin: usize) -> (Vec<PathBuf>, Vec<PathBuf>) {
+    let end = visible.end.min(entries.len());
+    let start = visible.start.min(end);
+    let near_start = start.saturating_sub(margin);
+    let near_end = end.saturating_add(margin).min(entries.len());
+
+    let visible_paths = entries[start..end].to_vec();
+    let near_paths = entries[near_start..start]
+        .iter()
+        .chain(&entries[end..near_end])
+        .cloned()
+        .collect();
+    (visible_paths, near_paths)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use tempfile::TempDir;
     use tokio::io::AsyncWriteExt;
 
+    #[test]
+    fn test_viewport_slices_split_visible_and_nearby_rows() {
+        let entries: Vec<PathBuf> = (0..10).map(|i| PathBuf::from(format!("{}.jpg", i))).collect();
+
+        let (visible, near) = viewport_slices(&entries, 4..6, 2);
+        assert_eq!(visible, entries[4..6].to_vec());
+        assert_eq!(near, vec![entries[2].clone(), entries[3].clone(), entries[6].clone(), entries[7].clone()]);
+
+        // Ranges past the end are clamped
+        let (visible, near) = viewport_slices(&entries, 9..15, 3);
+        assert_eq!(visible, vec![entries[9].clone()]);
+        assert_eq!(near, entries[6..9].to_vec());
+    }
+
     async fn create_test_file(temp_dir: &TempDir, name: &str, size: usize) -> PathBuf {
         let file_path = temp_dir.path().join(name);
         let mut file = tokio::fs::File::create(&file_path).await.unwrap();
@@ -0,0 +1,781 @@
+//! Batch renaming from templates like `{date:%Y-%m-%d}_{camera}_{seq}.{ext}`
+//!
+//! A template is parsed once, then rendered for each file from its EXIF data
+//! and its position in the batch. Fields a file lacks fall back to something
+//! sensible: the modification time for the date and `unknown` for the rest.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Local};
+use regex::{NoExpand, Regex, RegexBuilder};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+use thiserror::Error;
+
+use super::file_system::{ExifMetadata, FileEntry, FileSystemService};
+use super::operations::{
+    BatchOperation, Command, CommandKind, CommandMetadata, CommandStatus, OperationError, OperationKind,
+    OperationResult, RenameCommand,
+};
+use crate::utils::path_utils::{validate_file_name, NameError, INVALID_NAME_CHARS};
+
+/// Template offered when renaming several files
+pub const DEFAULT_RENAME_TEMPLATE: &str = "{date}_{seq:3}.{ext}";
+
+/// Date layout of a bare `{date}` token
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Stand-in for a field the file has no value for
+const MISSING_FIELD: &str = "unknown";
+
+/// Why a template can't be used
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum TemplateError {
+    #[error("Unknown token {{{0}}}")]
+    UnknownToken(String),
+
+    #[error("Unclosed {{ at position {0}")]
+    Unclosed(usize),
+
+    #[error("Invalid argument for {{{token}}}: {argument}")]
+    InvalidArgument { token: String, argument: String },
+
+    #[error("Template is empty")]
+    Empty,
+}
+
+/// A value taken from the file or its place in the batch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateToken {
+    /// EXIF capture date, or the modification time, in a `strftime` layout
+    Date(String),
+    /// EXIF camera model
+    Camera,
+    /// `<width>x<height>` of an image or video
+    Dimensions,
+    /// 1-based position in the batch, zero-padded to the width
+    Seq(usize),
+    /// The current name without its extension
+    Name,
+    /// The current extension, without the dot
+    Ext,
+}
+
+impl TemplateToken {
+    fn parse(body: &str) -> Result<Self, TemplateError> {
+        let (name, argument) = match body.split_once(':') {
+            Some((name, argument)) => (name.trim(), Some(argument)),
+            None => (body.trim(), None),
+        };
+        let invalid = |argument: &str| TemplateError::InvalidArgument {
+            token: name.to_string(),
+            argument: argument.to_string(),
+        };
+        match (name, argument) {
+            ("date", None) => Ok(TemplateToken::Date(DEFAULT_DATE_FORMAT.to_string())),
+            ("date", Some(format)) if !format.is_empty() => Ok(TemplateToken::Date(format.to_string())),
+            ("seq", None) => Ok(TemplateToken::Seq(1)),
+            ("seq", Some(width)) => width.trim().parse().map(TemplateToken::Seq).map_err(|_| invalid(width)),
+            ("camera", None) => Ok(TemplateToken::Camera),
+            ("dimensions", None) => Ok(TemplateToken::Dimensions),
+            ("name", None) => Ok(TemplateToken::Name),
+            ("ext", None) => Ok(TemplateToken::Ext),
+            ("date", Some(argument)) => Err(invalid(argument)),
+            _ => Err(TemplateError::UnknownToken(body.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TemplatePart {
+    Literal(String),
+    Token(TemplateToken),
+}
+
+/// A parsed rename template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenameTemplate {
+    source: String,
+    parts: Vec<TemplatePart>,
+}
+
+impl RenameTemplate {
+    /// Parse `template`; `{{` and `}}` stand for literal braces
+    pub fn parse(template: &str) -> Result<Self, TemplateError> {
+        if template.trim().is_empty() {
+            return Err(TemplateError::Empty);
+        }
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.char_indices().peekable();
+        while let Some((position, c)) = chars.next() {
+            match c {
+                '{' if chars.next_if(|&(_, next)| next == '{').is_some() => literal.push('{'),
+                '}' if chars.next_if(|&(_, next)| next == '}').is_some() => literal.push('}'),
+                '{' => {
+                    let mut body = String::new();
+                    loop {
+                        match chars.next() {
+                            Some((_, '}')) => break,
+                            Some((_, c)) => body.push(c),
+                            None => return Err(TemplateError::Unclosed(position)),
+                        }
+                    }
+                    if !literal.is_empty() {
+                        parts.push(TemplatePart::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(TemplatePart::Token(TemplateToken::parse(&body)?));
+                }
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            parts.push(TemplatePart::Literal(literal));
+        }
+        Ok(Self {
+            source: template.to_string(),
+            parts,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Name for `entry` as the `seq`th file of the batch
+    ///
+    /// Token values have characters that can't appear in a file name replaced by `_`.
+    pub fn render(&self, entry: &FileEntry, seq: usize) -> String {
+        let exif = entry
+            .preview_metadata
+            .as_ref()
+            .and_then(|metadata| metadata.exif_data.as_ref());
+        self.parts
+            .iter()
+            .map(|part| match part {
+                TemplatePart::Literal(text) => text.clone(),
+                TemplatePart::Token(token) => sanitize(&token_value(token, entry, exif, seq)),
+            })
+            .collect()
+    }
+}
+
+fn token_value(token: &TemplateToken, entry: &FileEntry, exif: Option<&ExifMetadata>, seq: usize) -> String {
+    match token {
+        TemplateToken::Date(format) => {
+            let date = exif.and_then(|exif| exif.date_taken).unwrap_or(entry.modified);
+            DateTime::<Local>::from(date).format(format).to_string()
+        }
+        TemplateToken::Camera => exif
+            .and_then(|exif| exif.camera_model.as_deref())
+            .map(str::trim)
+            .filter(|model| !model.is_empty())
+            .unwrap_or(MISSING_FIELD)
+            .to_string(),
+        TemplateToken::Dimensions => match entry.dimensions() {
+            Some((width, height)) => format!("{}x{}", width, height),
+            None => MISSING_FIELD.to_string(),
+        },
+        TemplateToken::Seq(width) => format!("{:0width$}", seq, width = *width),
+        TemplateToken::Name => entry
+            .path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        TemplateToken::Ext => entry.extension().unwrap_or_default(),
+    }
+}
+
+fn sanitize(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| if INVALID_NAME_CHARS.contains(&c) || c.is_control() { '_' } else { c })
+        .collect()
+}
+
+/// Planned new name for each file, in batch order
+///
+/// A name already used by another file in the folder, or earlier in the batch,
+/// gets a " (n)" suffix. Files whose name would not change are left out.
+pub fn plan_renames(template: &RenameTemplate, entries: &[FileEntry]) -> Vec<(PathBuf, String)> {
+    let mut claimed = HashSet::new();
+    let mut plan = Vec::new();
+    for (index, entry) in entries.iter().enumerate() {
+        let Some(folder) = entry.path.parent() else {
+            continue;
+        };
+        let rendered = template.render(entry, index + 1);
+        let mut name = rendered.clone();
+        let mut counter = 1;
+        while is_taken(&folder.join(&name), &entry.path, &claimed) {
+            name = super::organize::numbered_name(Path::new(&rendered), counter);
+            counter += 1;
+        }
+        let new_path = folder.join(&name);
+        claimed.insert(new_path.clone());
+        if new_path != entry.path {
+            plan.push((entry.path.clone(), name));
+        }
+    }
+    plan
+}
+
+fn is_taken(candidate: &Path, own_path: &Path, claimed: &HashSet<PathBuf>) -> bool {
+    claimed.contains(candidate) || (candidate != own_path && candidate.exists())
+}
+
+/// Why a find and replace rule can't be used
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum FindReplaceError {
+    #[error("Invalid regular expression: {0}")]
+    InvalidRegex(String),
+}
+
+/// How a find and replace rename matches and substitutes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FindReplaceOptions {
+    /// Read `find` as a regex whose capture groups `replace` can use as `$1` or `${name}`
+    pub regex: bool,
+    pub case_sensitive: bool,
+    /// Replace every match rather than only the first
+    pub replace_all: bool,
+}
+
+impl Default for FindReplaceOptions {
+    fn default() -> Self {
+        Self {
+            regex: false,
+            case_sensitive: true,
+            replace_all: true,
+        }
+    }
+}
+
+/// Find and replace on file names, literal or by regex
+#[derive(Debug, Clone)]
+pub struct FindReplace {
+    find: String,
+    replace: String,
+    options: FindReplaceOptions,
+    /// `None` while `find` is empty, which leaves names alone
+    pattern: Option<Regex>,
+}
+
+impl FindReplace {
+    /// Compile `find`; a regex that doesn't compile fails here
+    pub fn new(find: &str, replace: &str, options: FindReplaceOptions) -> Result<Self, FindReplaceError> {
+        let pattern = if find.is_empty() {
+            None
+        } else {
+            let source = if options.regex { find.to_string() } else { regex::escape(find) };
+            let pattern = RegexBuilder::new(&source)
+                .case_insensitive(!options.case_sensitive)
+                .build()
+                .map_err(|e| FindReplaceError::InvalidRegex(e.to_string()))?;
+            Some(pattern)
+        };
+        Ok(Self {
+            find: find.to_string(),
+            replace: replace.to_string(),
+            options,
+            pattern,
+        })
+    }
+
+    /// Case-sensitive literal replace of every occurrence
+    pub fn literal(find: &str, replace: &str) -> Self {
+        Self::new(find, replace, FindReplaceOptions::default()).expect("an escaped literal always compiles")
+    }
+
+    pub fn find(&self) -> &str {
+        &self.find
+    }
+
+    pub fn replace(&self) -> &str {
+        &self.replace
+    }
+
+    pub fn options(&self) -> FindReplaceOptions {
+        self.options
+    }
+
+    /// `name` with the first or every match replaced
+    pub fn apply(&self, name: &str) -> String {
+        let Some(pattern) = &self.pattern else {
+            return name.to_string();
+        };
+        let limit = if self.options.replace_all { 0 } else { 1 };
+        if self.options.regex {
+            pattern.replacen(name, limit, self.replace.as_str()).into_owned()
+        } else {
+            pattern.replacen(name, limit, NoExpand(&self.replace)).into_owned()
+        }
+    }
+}
+
+// The compiled pattern follows from the rest
+impl PartialEq for FindReplace {
+    fn eq(&self, other: &Self) -> bool {
+        self.find == other.find && self.replace == other.replace && self.options == other.options
+    }
+}
+
+impl Eq for FindReplace {}
+
+/// How a batch rename derives each new name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenameRule {
+    /// Render a name template for each file
+    Template(RenameTemplate),
+    /// Find and replace in each name
+    FindReplace(FindReplace),
+}
+
+impl RenameRule {
+    fn new_name(&self, entry: &FileEntry, seq: usize) -> String {
+        match self {
+            RenameRule::Template(template) => template.render(entry, seq),
+            RenameRule::FindReplace(find_replace) => find_replace.apply(&entry.name),
+        }
+    }
+
+    fn describe(&self, count: usize) -> String {
+        match self {
+            RenameRule::Template(template) => format!("Rename {} files with {}", count, template.as_str()),
+            RenameRule::FindReplace(find_replace) => format!(
+                "Rename {} files replacing \"{}\" with \"{}\"",
+                count,
+                find_replace.find(),
+                find_replace.replace()
+            ),
+        }
+    }
+}
+
+/// What renaming one file of a batch would do
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenameStatus {
+    /// Gets the new name
+    Ok,
+    /// The new name is taken by another file of the batch or already in the folder
+    Collision,
+    /// The new name is not a valid file name
+    Invalid(NameError),
+    /// The name stays as it is
+    Unchanged,
+}
+
+impl RenameStatus {
+    /// Whether this row keeps the batch from running
+    pub fn blocks(&self) -> bool {
+        matches!(self, RenameStatus::Collision | RenameStatus::Invalid(_))
+    }
+}
+
+/// Old path, proposed name and outcome for one file of a batch rename
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenamePlanRow {
+    pub old: PathBuf,
+    pub new: String,
+    pub status: RenameStatus,
+}
+
+impl RenamePlanRow {
+    pub fn new_path(&self) -> PathBuf {
+        self.old.with_file_name(&self.new)
+    }
+}
+
+/// Preview of renaming `entries` with `rule`, in batch order
+///
+/// Unlike [`plan_renames`], clashes are not numbered away: every file
+/// claiming a name another file of the batch also gets, or one already in
+/// the folder, is marked [`RenameStatus::Collision`].
+pub fn plan_rename(entries: &[FileEntry], rule: &RenameRule) -> Vec<RenamePlanRow> {
+    let mut rows: Vec<RenamePlanRow> = entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let new = rule.new_name(entry, index + 1);
+            let status = if entry.path.file_name().is_some_and(|name| name == new.as_str()) {
+                RenameStatus::Unchanged
+            } else if let Err(e) = validate_file_name(&new) {
+                RenameStatus::Invalid(e)
+            } else {
+                RenameStatus::Ok
+            };
+            RenamePlanRow { old: entry.path.clone(), new, status }
+        })
+        .collect();
+
+    // Files keeping their name still hold it
+    let mut claims: HashMap<PathBuf, usize> = HashMap::new();
+    for row in rows.iter().filter(|row| !matches!(row.status, RenameStatus::Invalid(_))) {
+        *claims.entry(row.new_path()).or_default() += 1;
+    }
+    for row in rows.iter_mut().filter(|row| row.status == RenameStatus::Ok) {
+        let new_path = row.new_path();
+        if claims[&new_path] > 1 || is_taken_on_disk(&new_path, &row.old) {
+            row.status = RenameStatus::Collision;
+        }
+    }
+    rows
+}
+
+/// Whether another file already has `new_path`; a change of case only is not a clash
+fn is_taken_on_disk(new_path: &Path, old_path: &Path) -> bool {
+    let same_file = new_path.to_string_lossy().to_lowercase() == old_path.to_string_lossy().to_lowercase();
+    !same_file && new_path.exists()
+}
+
+/// Whether the batch can run: nothing blocks and at least one file changes name
+pub fn can_execute_plan(rows: &[RenamePlanRow]) -> bool {
+    !rows.iter().any(|row| row.status.blocks()) && rows.iter().any(|row| row.status == RenameStatus::Ok)
+}
+
+/// Rename a batch of files from a template or rename rule as one undoable step
+#[derive(Debug)]
+pub struct TemplateRenameCommand {
+    batch: BatchOperation,
+    metadata: CommandMetadata,
+}
+
+impl TemplateRenameCommand {
+    /// Plan the renames of `entries`; an invalid template fails here, before
+    /// anything is renamed
+    pub fn new(template: &str, entries: &[FileEntry]) -> OperationResult<Self> {
+        let template = RenameTemplate::parse(template).map_err(|e| OperationError::ValidationFailed(e.to_string()))?;
+        let mut batch = BatchOperation::new(
+            "Rename".to_string(),
+            format!("Rename {} files with {}", entries.len(), template.as_str()),
+        );
+        for (path, name) in plan_renames(&template, entries) {
+            batch.add_command(Box::new(RenameCommand::new(path, name)?));
+        }
+        Ok(Self {
+            batch,
+            metadata: CommandMetadata::default(),
+        })
+    }
+
+    /// Rename the `Ok` rows of a previewed plan; unchanged rows are skipped
+    ///
+    /// Fails without renaming anything if any row collides or is invalid.
+    pub fn from_plan(rule: &RenameRule, rows: &[RenamePlanRow]) -> OperationResult<Self> {
+        let blocked = rows.iter().filter(|row| row.status.blocks()).count();
+        if blocked > 0 {
+            return Err(OperationError::ValidationFailed(format!(
+                "{} new names collide or are invalid",
+                blocked
+            )));
+        }
+        let renamed: Vec<&RenamePlanRow> = rows.iter().filter(|row| row.status == RenameStatus::Ok).collect();
+        let mut batch = BatchOperation::new("Rename".to_string(), rule.describe(renamed.len()));
+        for row in renamed {
+            batch.add_command(Box::new(RenameCommand::new(row.old.clone(), row.new.clone())?));
+        }
+        Ok(Self {
+            batch,
+            metadata: CommandMetadata::default(),
+        })
+    }
+
+    /// Old and new path of each planned rename
+    pub fn planned_renames(&self) -> Vec<(PathBuf, PathBuf)> {
+        self.batch
+            .commands
+            .iter()
+            .filter_map(|command| Some((command.source_path()?.to_path_buf(), command.destination_path()?.to_path_buf())))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Command for TemplateRenameCommand {
+    async fn execute(&mut self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        if self.is_executed() {
+            return Err(OperationError::AlreadyExecuted);
+        }
+        // A failed rename rolls back the ones before it
+        self.batch.execute(fs).await?;
+        self.metadata.status = CommandStatus::Executed;
+        self.metadata.executed_at = Some(SystemTime::now());
+        Ok(())
+    }
+
+    async fn undo(&mut self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        if !self.can_undo() {
+            return Err(OperationError::NotExecuted);
+        }
+        self.batch.undo(fs).await?;
+        self.metadata.status = CommandStatus::Undone;
+        self.metadata.undone_at = Some(SystemTime::now());
+        Ok(())
+    }
+
+    async fn validate(&self, fs: Arc<dyn FileSystemService>) -> OperationResult<()> {
+        self.batch.validate(fs).await
+    }
+
+    fn command_kind(&self) -> Option<CommandKind> {
+        self.batch.command_kind()
+    }
+
+    fn metadata(&self) -> &CommandMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut CommandMetadata {
+        &mut self.metadata
+    }
+
+    fn description(&self) -> String {
+        self.batch.description.clone()
+    }
+
+    fn kind(&self) -> OperationKind {
+        OperationKind::Rename
+    }
+
+    fn must_run_serially(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::file_system::{FilePermissions, FileType, NativeFileSystemService, PreviewMetadata};
+    use tempfile::TempDir;
+
+    fn local_time(y: i32, m: u32, d: u32) -> SystemTime {
+        let date = chrono::NaiveDate::from_ymd_opt(y, m, d).unwrap().and_hms_opt(9, 30, 0).unwrap();
+        SystemTime::from(date.and_local_timezone(Local).unwrap())
+    }
+
+    fn photo(path: PathBuf, exif: Option<ExifMetadata>, dimensions: Option<(u32, u32)>) -> FileEntry {
+        FileEntry {
+            file_type: FileType::from_path(&path),
+            name: path.file_name().unwrap().to_string_lossy().into_owned(),
+            path,
+            size: 1,
+            modified: local_time(2020, 1, 2),
+            created: local_time(2020, 1, 2),
+            is_directory: false,
+            is_hidden: false,
+            permissions: FilePermissions::default(),
+            preview_metadata: Some(PreviewMetadata {
+                width: dimensions.map(|(width, _)| width),
+                height: dimensions.map(|(_, height)| height),
+                exif_data: exif,
+                ..PreviewMetadata::default()
+            }),
+        }
+    }
+
+    fn exif(model: &str, taken: SystemTime) -> ExifMetadata {
+        ExifMetadata {
+            camera_make: None,
+            camera_model: Some(model.to_string()),
+            lens_model: None,
+            focal_length: None,
+            aperture: None,
+            shutter_speed: None,
+            iso: None,
+            flash: None,
+            date_taken: Some(taken),
+            gps_latitude: None,
+            gps_longitude: None,
+            orientation: None,
+            rating: None,
+        }
+    }
+
+    #[test]
+    fn test_tokens_render_from_exif_and_fall_back_without_it() {
+        let template = RenameTemplate::parse("{date:%Y-%m-%d}_{camera}_{dimensions}_{seq:3}.{ext}").unwrap();
+        let tagged = photo(
+            PathBuf::from("/photos/IMG_1.JPG"),
+            Some(exif("EOS R/5", local_time(2023, 7, 14))),
+            Some((6000, 4000)),
+        );
+        assert_eq!(template.render(&tagged, 7), "2023-07-14_EOS R_5_6000x4000_007.jpg");
+
+        let bare = photo(PathBuf::from("/photos/scan.png"), None, None);
+        assert_eq!(template.render(&bare, 12), "2020-01-02_unknown_unknown_012.png");
+
+        assert_eq!(
+            RenameTemplate::parse("{{{name}}}").unwrap().render(&bare, 1),
+            "{scan}"
+        );
+        assert_eq!(
+            RenameTemplate::parse("{date}_{lens}"),
+            Err(TemplateError::UnknownToken("lens".to_string()))
+        );
+        assert_eq!(RenameTemplate::parse("{seq"), Err(TemplateError::Unclosed(0)));
+        assert!(matches!(
+            RenameTemplate::parse("{seq:x}"),
+            Err(TemplateError::InvalidArgument { .. })
+        ));
+        assert!(matches!(
+            TemplateRenameCommand::new("{bogus}.jpg", &[bare]),
+            Err(OperationError::ValidationFailed(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_colliding_names_are_numbered_and_undo_restores_all() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for name in ["a.jpg", "b.jpg", "c.jpg"] {
+            std::fs::write(root.join(name), name).unwrap();
+        }
+        // Taken by a file outside the batch
+        std::fs::write(root.join("Trip.jpg"), "other").unwrap();
+
+        let entries: Vec<FileEntry> = ["a.jpg", "b.jpg", "c.jpg"]
+            .iter()
+            .map(|name| photo(root.join(name), None, None))
+            .collect();
+        let mut command = TemplateRenameCommand::new("Trip.{ext}", &entries).unwrap();
+        let new_names: Vec<String> = command
+            .planned_renames()
+            .iter()
+            .map(|(_, new_path)| new_path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(new_names, ["Trip (1).jpg", "Trip (2).jpg", "Trip (3).jpg"]);
+
+        let fs: Arc<dyn FileSystemService> = Arc::new(NativeFileSystemService::new());
+        command.execute(fs.clone()).await.unwrap();
+        assert_eq!(std::fs::read_to_string(root.join("Trip (2).jpg")).unwrap(), "b.jpg");
+        assert_eq!(std::fs::read_to_string(root.join("Trip.jpg")).unwrap(), "other");
+
+        command.undo(fs).await.unwrap();
+        for name in ["a.jpg", "b.jpg", "c.jpg"] {
+            assert_eq!(std::fs::read_to_string(root.join(name)).unwrap(), name);
+        }
+        assert!(!root.join("Trip (1).jpg").exists());
+    }
+
+    #[test]
+    fn test_plan_marks_files_claiming_the_same_name_as_collisions() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for name in ["beach_1.jpg", "beach-1.jpg", "city.jpg", "taken.png", "beach 2.jpg"] {
+            std::fs::write(root.join(name), name).unwrap();
+        }
+        let entries: Vec<FileEntry> = ["beach_1.jpg", "beach-1.jpg", "city.jpg", "taken.png"]
+            .iter()
+            .map(|name| photo(root.join(name), None, None))
+            .collect();
+
+        // Two files mapping to one new name both collide
+        let rule = RenameRule::FindReplace(FindReplace::literal("-", "_"));
+        let rows = plan_rename(&entries, &rule);
+        let statuses: Vec<&RenameStatus> = rows.iter().map(|row| &row.status).collect();
+        // beach-1.jpg would take the name beach_1.jpg keeps
+        assert_eq!(
+            statuses,
+            [&RenameStatus::Unchanged, &RenameStatus::Collision, &RenameStatus::Unchanged, &RenameStatus::Unchanged]
+        );
+        assert!(!can_execute_plan(&rows));
+
+        let rule = RenameRule::Template(RenameTemplate::parse("trip.{ext}").unwrap());
+        let rows = plan_rename(&entries[..3], &rule);
+        assert!(rows.iter().all(|row| row.new == "trip.jpg" && row.status == RenameStatus::Collision));
+
+        // A name already in the folder collides too
+        let rule = RenameRule::FindReplace(FindReplace::literal("_1", " 2"));
+        let rows = plan_rename(&entries[..1], &rule);
+        assert_eq!(rows[0].new, "beach 2.jpg");
+        assert_eq!(rows[0].status, RenameStatus::Collision);
+
+        let rule = RenameRule::FindReplace(FindReplace::literal("city", "town"));
+        let rows = plan_rename(&entries, &rule);
+        assert_eq!(rows[2].status, RenameStatus::Ok);
+        assert!(can_execute_plan(&rows));
+    }
+
+    #[test]
+    fn test_plan_marks_invalid_names() {
+        let entries: Vec<FileEntry> = ["/photos/a:b.jpg", "/photos/c.jpg", "/photos/x.jpg"]
+            .iter()
+            .map(|path| photo(PathBuf::from(path), None, None))
+            .collect();
+
+        let rule = RenameRule::FindReplace(FindReplace::literal("c", "c/d"));
+        let rows = plan_rename(&entries, &rule);
+        assert_eq!(rows[0].status, RenameStatus::Unchanged);
+        assert_eq!(rows[1].new, "c/d.jpg");
+        assert_eq!(rows[1].status, RenameStatus::Invalid(NameError::InvalidCharacter('/')));
+        assert!(rows[1].status.blocks());
+
+        let rule = RenameRule::FindReplace(FindReplace::literal("x.jpg", " "));
+        assert_eq!(plan_rename(&entries[2..], &rule)[0].status, RenameStatus::Invalid(NameError::Empty));
+        assert!(matches!(
+            TemplateRenameCommand::from_plan(&rule, &plan_rename(&entries, &rule)),
+            Err(OperationError::ValidationFailed(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_plan_runs_only_the_renamed_rows() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for name in ["trip_1.jpg", "trip_2.jpg", "notes.txt"] {
+            std::fs::write(root.join(name), name).unwrap();
+        }
+        let entries: Vec<FileEntry> = ["trip_1.jpg", "trip_2.jpg", "notes.txt"]
+            .iter()
+            .map(|name| photo(root.join(name), None, None))
+            .collect();
+        let rule = RenameRule::FindReplace(FindReplace::literal("trip", "Rome"));
+        let rows = plan_rename(&entries, &rule);
+        assert!(can_execute_plan(&rows));
+        assert_eq!(rows[2].status, RenameStatus::Unchanged);
+
+        let mut command = TemplateRenameCommand::from_plan(&rule, &rows).unwrap();
+        assert_eq!(command.planned_renames().len(), 2);
+        let fs: Arc<dyn FileSystemService> = Arc::new(NativeFileSystemService::new());
+        command.execute(fs).await.unwrap();
+        assert_eq!(std::fs::read_to_string(root.join("Rome_2.jpg")).unwrap(), "trip_2.jpg");
+        assert!(root.join("notes.txt").exists());
+    }
+
+    #[test]
+    fn test_regex_find_replace_substitutes_capture_groups() {
+        let options = FindReplaceOptions { regex: true, ..FindReplaceOptions::default() };
+        let swap = FindReplace::new(r"^(\w+)_(\d+)", "${2}-$1", options).unwrap();
+        assert_eq!(swap.apply("trip_042.jpg"), "042-trip.jpg");
+
+        let first_only = FindReplaceOptions { replace_all: false, ..options };
+        let digits = FindReplace::new(r"(\d)", "<$1>", first_only).unwrap();
+        assert_eq!(digits.apply("a1b2.png"), "a<1>b2.png");
+
+        let ignore_case = FindReplaceOptions { case_sensitive: false, ..options };
+        assert_eq!(FindReplace::new("img", "photo", ignore_case).unwrap().apply("IMG_1.jpg"), "photo_1.jpg");
+
+        assert!(matches!(
+            FindReplace::new("(unclosed", "", options),
+            Err(FindReplaceError::InvalidRegex(_))
+        ));
+    }
+
+    #[test]
+    fn test_literal_find_replace_first_or_all() {
+        // Regex syntax means nothing in literal mode, on either side
+        let all = FindReplace::literal(".", "$1");
+        assert_eq!(all.apply("a.b.jpg"), "a$1b$1jpg");
+
+        let first = FindReplaceOptions { replace_all: false, ..FindReplaceOptions::default() };
+        assert_eq!(FindReplace::new("-", "_", first).unwrap().apply("a-b-c.txt"), "a_b-c.txt");
+
+        assert_eq!(FindReplace::literal("img", "photo").apply("IMG_img.jpg"), "IMG_photo.jpg");
+        let ignore_case = FindReplaceOptions { case_sensitive: false, ..FindReplaceOptions::default() };
+        assert_eq!(FindReplace::new("img", "photo", ignore_case).unwrap().apply("IMG_img.jpg"), "photo_photo.jpg");
+
+        assert_eq!(FindReplace::literal("", "x").apply("keep.jpg"), "keep.jpg");
+        assert!(FindReplace::new("(", "", FindReplaceOptions::default()).is_ok());
+    }
+}
@@ -0,0 +1,161 @@
+//! Aggregate info over a multi-selection, shown by the info panel
+//!
+//! The summary is computed from the listed entries alone, so it is instant;
+//! folder contents are sized separately with [`folder_sizes`] because that
+//! walks the whole tree under each folder that isn't in the size cache.
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use super::classifier::classifier;
+use super::file_system::{compute_directory_size, FileEntry};
+use super::operations::CancellationToken;
+
+/// Totals of a set of selected files and folders
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SelectionSummary {
+    pub count: usize,
+    pub file_count: usize,
+    pub folder_count: usize,
+    /// Combined size of the selected files; folder contents are not included
+    pub files_size: u64,
+    /// Entries per classifier category, largest group first
+    pub type_breakdown: Vec<(String, usize)>,
+    /// Deepest folder holding every selected entry
+    pub common_parent: Option<PathBuf>,
+    /// Oldest and newest modification time
+    pub modified_range: Option<(SystemTime, SystemTime)>,
+}
+
+impl SelectionSummary {
+    /// Whether folder contents have to be sized on top of `files_size`
+    pub fn has_folders(&self) -> bool {
+        self.folder_count > 0
+    }
+}
+
+/// Summarize `entries`
+pub fn summarize_selection(entries: &[FileEntry]) -> SelectionSummary {
+    let mut summary = SelectionSummary {
+        count: entries.len(),
+        ..SelectionSummary::default()
+    };
+
+    for entry in entries {
+        if entry.is_directory {
+            summary.folder_count += 1;
+        } else {
+            summary.file_count += 1;
+            summary.files_size += entry.size;
+        }
+
+        let category = classifier().classify_entry(entry).category;
+        match summary.type_breakdown.iter_mut().find(|(name, _)| *name == category) {
+            Some((_, count)) => *count += 1,
+            None => summary.type_breakdown.push((category, 1)),
+        }
+
+        summary.modified_range = Some(match summary.modified_range {
+            Some((oldest, newest)) => (oldest.min(entry.modified), newest.max(entry.modified)),
+            None => (entry.modified, entry.modified),
+        });
+    }
+    summary
+        .type_breakdown
+        .sort_by(|(a_name, a_count), (b_name, b_count)| b_count.cmp(a_count).then_with(|| a_name.cmp(b_name)));
+    summary.common_parent = common_parent(entries.iter().map(|entry| entry.path.as_path()));
+    summary
+}
+
+/// Deepest folder that contains every one of `paths`
+pub fn common_parent<'a>(paths: impl IntoIterator<Item = &'a Path>) -> Option<PathBuf> {
+    let mut common: Option<PathBuf> = None;
+    for path in paths {
+        let parent = path.parent()?;
+        common = Some(match common {
+            None => parent.to_path_buf(),
+            Some(current) => current
+                .components()
+                .zip(parent.components())
+                .take_while(|(a, b)| a == b)
+                .map(|(component, _)| component)
+                .collect(),
+        });
+    }
+    common.filter(|path| !path.as_os_str().is_empty())
+}
+
+/// Combined size of the contents of `folders`; folders that can't be sized count as empty
+pub async fn folder_sizes(folders: Vec<PathBuf>) -> u64 {
+    let mut total = 0;
+    for folder in folders {
+        match compute_directory_size(&folder, CancellationToken::new()).await {
+            Ok(size) => total += size,
+            Err(e) => tracing::warn!("Failed to size {}: {}", folder.display(), e),
+        }
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::file_system::{FilePermissions, FileType};
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn entry(path: &str, size: u64, age_days: u64, is_directory: bool) -> FileEntry {
+        let path = PathBuf::from(path);
+        FileEntry {
+            file_type: if is_directory { FileType::Directory } else { FileType::from_path(&path) },
+            name: path.file_name().unwrap().to_string_lossy().to_string(),
+            path,
+            size,
+            modified: SystemTime::UNIX_EPOCH + Duration::from_secs(age_days * 24 * 60 * 60),
+            created: SystemTime::UNIX_EPOCH,
+            is_directory,
+            is_hidden: false,
+            permissions: FilePermissions::default(),
+            preview_metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_summary_of_mixed_selection() {
+        let entries = vec![
+            entry("/photos/2024/beach.jpg", 300, 3, false),
+            entry("/photos/2024/sunset.png", 200, 1, false),
+            entry("/photos/notes.txt", 10, 5, false),
+            entry("/photos/raw", 4096, 2, true),
+        ];
+        let summary = summarize_selection(&entries);
+
+        assert_eq!(summary.count, 4);
+        assert_eq!(summary.file_count, 3);
+        assert_eq!(summary.folder_count, 1);
+        assert_eq!(summary.files_size, 510);
+        assert_eq!(
+            summary.type_breakdown,
+            vec![("Images".to_string(), 2), ("Documents".to_string(), 1), ("Folders".to_string(), 1)]
+        );
+        assert_eq!(summary.common_parent, Some(PathBuf::from("/photos")));
+        let day = |days: u64| SystemTime::UNIX_EPOCH + Duration::from_secs(days * 24 * 60 * 60);
+        assert_eq!(summary.modified_range, Some((day(1), day(5))));
+
+        assert_eq!(summarize_selection(&[]), SelectionSummary::default());
+    }
+
+    #[tokio::test]
+    async fn test_common_parent_and_folder_sizes() {
+        let paths = [Path::new("/a/b/c.jpg"), Path::new("/a/b/d/e.jpg")];
+        assert_eq!(common_parent(paths), Some(PathBuf::from("/a/b")));
+        assert_eq!(common_parent([Path::new("/a/x"), Path::new("/b/y")]), Some(PathBuf::from("/")));
+
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir(temp_dir.path().join("nested")).unwrap();
+        std::fs::write(temp_dir.path().join("one.bin"), [0u8; 100]).unwrap();
+        std::fs::write(temp_dir.path().join("nested/two.bin"), [0u8; 50]).unwrap();
+        let folders = vec![temp_dir.path().join("nested"), temp_dir.path().join("missing")];
+        assert_eq!(folder_sizes(folders).await, 50);
+    }
+}
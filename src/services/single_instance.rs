@@ -0,0 +1,406 @@
+//! Single running instance per user
+//!
+//! The first instance listens on a loopback port and records it, together
+//! with a random token, in a lock file under the app data dir. Later launches
+//! find the lock, hand their requested folder to the running instance and
+//! exit. A lock whose owner no longer answers (e.g. after a crash) is stale
+//! and is taken over. The lock is written to a temporary file and linked into
+//! place, so a launch never sees a half-written lock from a live owner.
+
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// How long a launch waits for the running instance to acknowledge
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_millis(750);
+
+/// Reply that confirms a live instance received the request
+const ACK: &str = "ok";
+
+/// Request that is only acknowledged, used to check the owner is alive
+const PING: &str = "ping";
+
+/// How long to wait before reading an unreadable lock again
+const UNREADABLE_LOCK_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// How many times an unreadable lock is read again before it is given up on
+const UNREADABLE_LOCK_RETRIES: u32 = 5;
+
+/// Contents of the lock file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LockInfo {
+    pub pid: u32,
+    pub port: u16,
+    pub token: String,
+}
+
+/// Something a later launch asked the running instance to do
+#[derive(Debug, Clone, PartialEq)]
+pub enum InstanceRequest {
+    /// Bring the window to the front
+    Activate,
+    /// Show this folder
+    OpenFolder(PathBuf),
+}
+
+/// Outcome of trying to become the running instance
+#[derive(Debug)]
+pub enum InstanceRole {
+    /// This process owns the lock and should start the app
+    Primary(PrimaryInstance),
+    /// The request was handed to the running instance; this process should exit
+    Forwarded,
+}
+
+/// Requests forwarded to this instance, for the UI to pick up
+#[derive(Clone, Debug)]
+pub struct InstanceRequests(Arc<Mutex<Receiver<InstanceRequest>>>);
+
+impl InstanceRequests {
+    /// Next pending request, if any
+    pub fn try_next(&self) -> Option<InstanceRequest> {
+        self.0.lock().ok()?.try_recv().ok()
+    }
+}
+
+/// Channel feeding [`InstanceRequests`]
+pub fn request_channel() -> (Sender<InstanceRequest>, InstanceRequests) {
+    let (sender, receiver) = mpsc::channel();
+    (sender, InstanceRequests(Arc::new(Mutex::new(receiver))))
+}
+
+/// Lock file in the user's data directory
+pub fn default_lock_path() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("media-organizer")
+        .join("instance.lock")
+}
+
+/// Become the running instance, or forward `request` to the one already running
+pub fn acquire(lock_path: &Path, request: &InstanceRequest) -> io::Result<InstanceRole> {
+    // A second pass covers another launch creating the lock between our check and write
+    for _ in 0..2 {
+        match wait_for_readable_lock(lock_path) {
+            LockState::Held(info) => {
+                if forward(&info, request).is_ok() {
+                    return Ok(InstanceRole::Forwarded);
+                }
+                if !is_stale(&info) {
+                    return Err(io::Error::new(
+                        io::ErrorKind::TimedOut,
+                        "running instance did not accept the request",
+                    ));
+                }
+                tracing::info!("Removing stale instance lock left by process {}", info.pid);
+                remove_lock_if_matches(lock_path, &info);
+            }
+            LockState::Unreadable(contents) => {
+                // Locks are linked into place whole, so no live owner leaves one like this
+                tracing::warn!("Removing unreadable instance lock {}", lock_path.display());
+                remove_lock_if_contents(lock_path, &contents);
+            }
+            LockState::Missing => {}
+        }
+
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+        let info = LockInfo {
+            pid: std::process::id(),
+            port: listener.local_addr()?.port(),
+            token: Uuid::new_v4().to_string(),
+        };
+        match write_lock(lock_path, &info) {
+            Ok(()) => {
+                return Ok(InstanceRole::Primary(PrimaryInstance {
+                    listener: Some(listener),
+                    lock_path: lock_path.to_path_buf(),
+                    info,
+                }))
+            }
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Err(io::Error::new(io::ErrorKind::AlreadyExists, "instance lock is held by another launch"))
+}
+
+/// Whether the lock describes an instance that no longer answers
+pub fn is_stale(info: &LockInfo) -> bool {
+    send(info, PING).is_err()
+}
+
+/// The running instance; removes its lock file when dropped
+#[derive(Debug)]
+pub struct PrimaryInstance {
+    listener: Option<TcpListener>,
+    lock_path: PathBuf,
+    info: LockInfo,
+}
+
+impl PrimaryInstance {
+    pub fn info(&self) -> &LockInfo {
+        &self.info
+    }
+
+    /// Accept forwarded requests on a background thread and pass them to `sender`
+    pub fn listen(&mut self, sender: Sender<InstanceRequest>) {
+        let Some(listener) = self.listener.take() else {
+            return;
+        };
+        let token = self.info.token.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else {
+                    continue;
+                };
+                match read_request(stream, &token) {
+                    Ok(Some(Some(request))) => {
+                        if sender.send(request).is_err() {
+                            break;
+                        }
+                    }
+                    Ok(Some(None)) => {}
+                    Ok(None) => tracing::warn!("Rejected instance request with a wrong token"),
+                    Err(e) => tracing::debug!("Failed to read instance request: {}", e),
+                }
+            }
+        });
+    }
+}
+
+impl Drop for PrimaryInstance {
+    fn drop(&mut self) {
+        remove_lock_if_matches(&self.lock_path, &self.info);
+    }
+}
+
+/// What a launch found at the lock path
+#[derive(Debug)]
+enum LockState {
+    Missing,
+    Held(LockInfo),
+    /// Empty or not valid JSON; holds the raw contents
+    Unreadable(String),
+}
+
+fn read_lock_state(path: &Path) -> LockState {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return LockState::Missing;
+    };
+    match serde_json::from_str(&contents) {
+        Ok(info) => LockState::Held(info),
+        Err(_) => LockState::Unreadable(contents),
+    }
+}
+
+/// Read the lock, giving a lock that is still being written time to finish
+fn wait_for_readable_lock(path: &Path) -> LockState {
+    let mut state = read_lock_state(path);
+    for _ in 0..UNREADABLE_LOCK_RETRIES {
+        if !matches!(state, LockState::Unreadable(_)) {
+            break;
+        }
+        std::thread::sleep(UNREADABLE_LOCK_RETRY_DELAY);
+        state = read_lock_state(path);
+    }
+    state
+}
+
+fn read_lock(path: &Path) -> Option<LockInfo> {
+    match read_lock_state(path) {
+        LockState::Held(info) => Some(info),
+        _ => None,
+    }
+}
+
+/// Create the lock file, failing if one already exists
+///
+/// The contents are written to a temporary file first and then hard-linked to
+/// `path`, so the lock appears complete or not at all.
+fn write_lock(path: &Path, info: &LockInfo) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(format!(".{}.tmp", Uuid::new_v4()));
+    let temp_path = path.with_file_name(temp_name);
+
+    let result = (|| {
+        let mut file = OpenOptions::new().write(true).create_new(true).open(&temp_path)?;
+        file.write_all(serde_json::to_string(info)?.as_bytes())?;
+        file.sync_all()?;
+        fs::hard_link(&temp_path, path)
+    })();
+    let _ = fs::remove_file(&temp_path);
+    result
+}
+
+/// Remove the lock only if it still names `info`, so we never delete a newer owner's lock
+fn remove_lock_if_matches(path: &Path, info: &LockInfo) {
+    let current = fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str::<LockInfo>(&contents).ok());
+    if current.as_ref() == Some(info) {
+        let _ = fs::remove_file(path);
+    }
+}
+
+/// Remove the lock only if its contents are still exactly `contents`
+fn remove_lock_if_contents(path: &Path, contents: &str) {
+    if fs::read_to_string(path).is_ok_and(|current| current == contents) {
+        let _ = fs::remove_file(path);
+    }
+}
+
+/// Send `request` to the instance in `info` and wait for its acknowledgement
+fn forward(info: &LockInfo, request: &InstanceRequest) -> io::Result<()> {
+    let line = match request {
+        InstanceRequest::Activate => "activate".to_string(),
+        InstanceRequest::OpenFolder(path) => format!("open {}", path.to_string_lossy()),
+    };
+    send(info, &line)
+}
+
+/// Write the token and one request line, then wait for the acknowledgement
+fn send(info: &LockInfo, line: &str) -> io::Result<()> {
+    let address = SocketAddr::from((Ipv4Addr::LOCALHOST, info.port));
+    let mut stream = TcpStream::connect_timeout(&address, HANDSHAKE_TIMEOUT)?;
+    stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+    stream.set_write_timeout(Some(HANDSHAKE_TIMEOUT))?;
+
+    write!(stream, "{}\n{}\n", info.token, line)?;
+    stream.flush()?;
+
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply)?;
+    if reply.trim() == ACK {
+        Ok(())
+    } else {
+        // Something else is listening on the recorded port
+        Err(io::Error::new(io::ErrorKind::InvalidData, "no acknowledgement from running instance"))
+    }
+}
+
+/// Read and acknowledge one request
+///
+/// `Ok(None)` if the token does not match; `Ok(Some(None))` for a ping.
+fn read_request(stream: TcpStream, token: &str) -> io::Result<Option<Option<InstanceRequest>>> {
+    stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut received_token = String::new();
+    let mut line = String::new();
+    reader.read_line(&mut received_token)?;
+    reader.read_line(&mut line)?;
+    if received_token.trim_end_matches(['\r', '\n']) != token {
+        return Ok(None);
+    }
+
+    let line = line.trim_end_matches(['\r', '\n']);
+    let request = match line.split_once(' ') {
+        Some(("open", folder)) => Some(InstanceRequest::OpenFolder(PathBuf::from(folder))),
+        _ if line == PING => None,
+        _ => Some(InstanceRequest::Activate),
+    };
+    let mut stream = stream;
+    writeln!(stream, "{}", ACK)?;
+    Ok(Some(request))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_stale_lock_is_detected_and_taken_over() {
+        let temp_dir = TempDir::new().unwrap();
+        let lock_path = temp_dir.path().join("instance.lock");
+
+        // A lock left by a crashed instance: its port is no longer listening
+        let closed_port = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap().local_addr().unwrap().port();
+        let stale = LockInfo { pid: u32::MAX, port: closed_port, token: "old".to_string() };
+        fs::write(&lock_path, serde_json::to_string(&stale).unwrap()).unwrap();
+        assert!(is_stale(&stale));
+
+        let role = acquire(&lock_path, &InstanceRequest::Activate).unwrap();
+        let InstanceRole::Primary(primary) = role else {
+            panic!("stale lock should be taken over");
+        };
+        assert_eq!(read_lock(&lock_path).as_ref(), Some(primary.info()));
+        assert_ne!(primary.info().token, "old");
+
+        drop(primary);
+        assert!(!lock_path.exists());
+    }
+
+    #[test]
+    fn test_second_launch_forwards_its_folder() {
+        let temp_dir = TempDir::new().unwrap();
+        let lock_path = temp_dir.path().join("instance.lock");
+
+        let InstanceRole::Primary(mut primary) = acquire(&lock_path, &InstanceRequest::Activate).unwrap() else {
+            panic!("first launch should own the lock");
+        };
+        let (sender, requests) = request_channel();
+        primary.listen(sender);
+        assert!(!is_stale(primary.info()));
+
+        let folder = temp_dir.path().join("Pictures");
+        let role = acquire(&lock_path, &InstanceRequest::OpenFolder(folder.clone())).unwrap();
+        assert!(matches!(role, InstanceRole::Forwarded));
+        assert_eq!(
+            requests.0.lock().unwrap().recv_timeout(Duration::from_secs(2)).unwrap(),
+            InstanceRequest::OpenFolder(folder)
+        );
+        assert!(requests.try_next().is_none());
+    }
+
+    #[test]
+    fn test_lock_being_written_is_waited_for_not_removed() {
+        let temp_dir = TempDir::new().unwrap();
+        let lock_path = temp_dir.path().join("instance.lock");
+
+        let InstanceRole::Primary(mut primary) = acquire(&lock_path, &InstanceRequest::Activate).unwrap() else {
+            panic!("first launch should own the lock");
+        };
+        let (sender, requests) = request_channel();
+        primary.listen(sender);
+
+        // The owner has created the lock but not yet written its contents
+        let contents = serde_json::to_string(primary.info()).unwrap();
+        fs::write(&lock_path, "").unwrap();
+        let writer_path = lock_path.clone();
+        let writer = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(150));
+            fs::write(&writer_path, contents).unwrap();
+        });
+
+        let role = acquire(&lock_path, &InstanceRequest::Activate).unwrap();
+        writer.join().unwrap();
+        assert!(matches!(role, InstanceRole::Forwarded));
+        assert_eq!(
+            requests.0.lock().unwrap().recv_timeout(Duration::from_secs(2)).unwrap(),
+            InstanceRequest::Activate
+        );
+        assert_eq!(read_lock(&lock_path).as_ref(), Some(primary.info()));
+    }
+
+    #[test]
+    fn test_lock_is_written_without_leftover_temp_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let lock_path = temp_dir.path().join("instance.lock");
+        let info = LockInfo { pid: 1, port: 1, token: "token".to_string() };
+
+        write_lock(&lock_path, &info).unwrap();
+        assert_eq!(read_lock(&lock_path), Some(info.clone()));
+        let err = write_lock(&lock_path, &info).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+}
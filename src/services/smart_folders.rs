@@ -0,0 +1,431 @@
+//! Smart folders: saved searches shown as virtual listings
+//!
+//! A smart folder keeps a root and a set of search filters. Opening it walks
+//! the whole tree under the root and reports each matching file as it is
+//! found; the listing is read-only and actions on it act on the real files.
+//! The same walk backs the file name search of the search view.
+
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use thiserror::Error;
+use uuid::Uuid;
+use walkdir::WalkDir;
+
+use super::file_system::{FileEntry, NativeFileSystemService};
+use super::operations::CancellationToken;
+
+/// Errors that stop a smart folder scan
+#[derive(Debug, Error)]
+pub enum SmartFolderError {
+    #[error("Failed to read {path}: {source}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("Scan cancelled")]
+    Cancelled,
+}
+
+pub type SmartFolderResult<T> = Result<T, SmartFolderError>;
+
+/// Search filters configuration
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchFilters {
+    /// Categories from the file classifier to keep; empty keeps all
+    pub file_types: Vec<String>,
+    pub size_range: Option<(u64, u64)>,
+    pub modified_range: Option<(SystemTime, SystemTime)>,
+    pub include_hidden: bool,
+    /// User tag the file must carry, compared case-insensitively
+    pub tag: Option<String>,
+    /// Lowest star rating to keep
+    pub min_rating: Option<u8>,
+}
+
+impl SearchFilters {
+    /// Whether `entry`, carrying `tags`, passes every filter
+    pub fn matches(&self, entry: &FileEntry, tags: &[String]) -> bool {
+        if entry.is_hidden && !self.include_hidden {
+            return false;
+        }
+        if !self.file_types.is_empty() {
+            let category = super::classifier::classifier().classify_entry(entry).category;
+            if !self.file_types.contains(&category) {
+                return false;
+            }
+        }
+        if let Some((min, max)) = self.size_range {
+            if entry.size < min || entry.size > max {
+                return false;
+            }
+        }
+        if let Some((from, to)) = self.modified_range {
+            if entry.modified < from || entry.modified > to {
+                return false;
+            }
+        }
+        if let Some(tag) = &self.tag {
+            if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                return false;
+            }
+        }
+        if let Some(min_rating) = self.min_rating {
+            let rating = entry
+                .preview_metadata
+                .as_ref()
+                .and_then(|metadata| metadata.exif_data.as_ref())
+                .and_then(|exif| exif.rating);
+            if rating.unwrap_or(0) < min_rating {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// A named saved search over the tree under `root`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SmartFolder {
+    pub id: Uuid,
+    pub name: String,
+    pub root: PathBuf,
+    pub filters: SearchFilters,
+}
+
+impl SmartFolder {
+    pub fn new(name: impl Into<String>, root: impl Into<PathBuf>, filters: SearchFilters) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name: name.into(),
+            root: root.into(),
+            filters,
+        }
+    }
+}
+
+/// Walk the tree under the folder's root and pass each matching file to `on_match`
+///
+/// `tags_of` supplies the user tags of a path. Hidden folders are skipped unless
+/// the filters include hidden files, and unreadable subfolders are skipped with
+/// a warning. Returns the number of matches.
+pub fn scan_smart_folder(
+    folder: &SmartFolder,
+    token: &CancellationToken,
+    tags_of: impl Fn(&Path) -> Vec<String>,
+    mut on_match: impl FnMut(FileEntry),
+) -> SmartFolderResult<usize> {
+    let mut matches = 0;
+    walk_tree(&folder.root, folder.filters.include_hidden, token, |entry| {
+        if entry.is_directory {
+            return true;
+        }
+        let tags = tags_of(&entry.path);
+        if folder.filters.matches(&entry, &tags) {
+            matches += 1;
+            on_match(entry);
+        }
+        true
+    })?;
+    Ok(matches)
+}
+
+/// How a search query is compared against file names
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NameQuery {
+    /// Names containing the text, ignoring case
+    Substring(String),
+    /// Names matching a `*`/`?` pattern as a whole, ignoring case
+    Glob(String),
+}
+
+impl NameQuery {
+    /// Read `query` as a glob if it contains a wildcard, otherwise as a substring
+    pub fn parse(query: &str) -> Self {
+        let query = query.trim().to_lowercase();
+        if query.contains(['*', '?']) {
+            NameQuery::Glob(query)
+        } else {
+            NameQuery::Substring(query)
+        }
+    }
+
+    pub fn matches(&self, name: &str) -> bool {
+        let name = name.to_lowercase();
+        match self {
+            NameQuery::Substring(text) => name.contains(text.as_str()),
+            NameQuery::Glob(pattern) => super::classifier::glob_matches(pattern, &name),
+        }
+    }
+}
+
+/// Matches a name search reports before it stops
+pub const DEFAULT_MAX_SEARCH_RESULTS: usize = 1000;
+
+/// A file name search over the tree under `root`
+#[derive(Clone, Debug)]
+pub struct NameSearch {
+    pub root: PathBuf,
+    pub query: NameQuery,
+    pub include_hidden: bool,
+    /// The walk stops once this many entries matched
+    pub max_results: usize,
+}
+
+impl NameSearch {
+    pub fn new(root: impl Into<PathBuf>, query: &str) -> Self {
+        Self {
+            root: root.into(),
+            query: NameQuery::parse(query),
+            include_hidden: false,
+            max_results: DEFAULT_MAX_SEARCH_RESULTS,
+        }
+    }
+
+    pub fn with_hidden(mut self, include_hidden: bool) -> Self {
+        self.include_hidden = include_hidden;
+        self
+    }
+
+    pub fn with_max_results(mut self, max_results: usize) -> Self {
+        self.max_results = max_results;
+        self
+    }
+}
+
+/// Walk the tree under the search root and pass each file or folder whose name
+/// matches to `on_match`, stopping at `max_results`
+///
+/// Returns the number of matches.
+pub fn search_names(
+    search: &NameSearch,
+    token: &CancellationToken,
+    mut on_match: impl FnMut(FileEntry),
+) -> SmartFolderResult<usize> {
+    let mut matches = 0;
+    if search.max_results == 0 {
+        return Ok(matches);
+    }
+    walk_tree(&search.root, search.include_hidden, token, |entry| {
+        if search.query.matches(&entry.name) {
+            matches += 1;
+            on_match(entry);
+        }
+        matches < search.max_results
+    })?;
+    Ok(matches)
+}
+
+/// Visit every entry under `root` in name order until `visit` returns false
+///
+/// Hidden entries are skipped, along with everything under them, unless
+/// `include_hidden` is set. Unreadable subfolders are skipped with a warning.
+pub(crate) fn walk_tree(
+    root: &Path,
+    include_hidden: bool,
+    token: &CancellationToken,
+    mut visit: impl FnMut(FileEntry) -> bool,
+) -> SmartFolderResult<()> {
+    let walk = WalkDir::new(root)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|dir_entry| include_hidden || !dir_entry.file_name().to_string_lossy().starts_with('.'));
+
+    for dir_entry in walk {
+        if token.is_cancelled() {
+            return Err(SmartFolderError::Cancelled);
+        }
+        let dir_entry = match dir_entry {
+            Ok(dir_entry) => dir_entry,
+            Err(e) if e.depth() == 0 => {
+                return Err(SmartFolderError::Read {
+                    path: root.to_path_buf(),
+                    source: e.into(),
+                });
+            }
+            Err(e) => {
+                tracing::warn!("Smart folder scan skipped an entry: {}", e);
+                continue;
+            }
+        };
+        let metadata = match dir_entry.metadata() {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                tracing::warn!("Smart folder scan skipped {:?}: {}", dir_entry.path(), e);
+                continue;
+            }
+        };
+        let entry = NativeFileSystemService::create_file_entry(dir_entry.into_path(), &metadata);
+        if !visit(entry) {
+            break;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::file_system::{ExifMetadata, FilePermissions, FileType, PreviewMetadata};
+    use std::fs;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn entry(name: &str, size: u64, age_days: u64, rating: Option<u8>) -> FileEntry {
+        let path = PathBuf::from(name);
+        let exif = ExifMetadata {
+            camera_make: None,
+            camera_model: None,
+            lens_model: None,
+            focal_length: None,
+            aperture: None,
+            shutter_speed: None,
+            iso: None,
+            flash: None,
+            date_taken: None,
+            gps_latitude: None,
+            gps_longitude: None,
+            orientation: None,
+            rating,
+        };
+        FileEntry {
+            file_type: FileType::from_path(&path),
+            path,
+            name: name.to_string(),
+            size,
+            modified: SystemTime::now() - Duration::from_secs(age_days * 24 * 60 * 60),
+            created: SystemTime::now(),
+            is_directory: false,
+            is_hidden: name.starts_with('.'),
+            permissions: FilePermissions::default(),
+            preview_metadata: Some(PreviewMetadata {
+                exif_data: Some(exif),
+                ..PreviewMetadata::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn test_filters_match_sample_entries() {
+        const GB: u64 = 1024 * 1024 * 1024;
+        let year_ago = SystemTime::now() - Duration::from_secs(365 * 24 * 60 * 60);
+        let big_recent_videos = SearchFilters {
+            file_types: vec!["Videos".to_string()],
+            size_range: Some((GB, u64::MAX)),
+            modified_range: Some((year_ago, SystemTime::now())),
+            ..SearchFilters::default()
+        };
+        assert!(big_recent_videos.matches(&entry("trip.mp4", 2 * GB, 10, None), &[]));
+        assert!(!big_recent_videos.matches(&entry("clip.mp4", GB / 2, 10, None), &[]), "too small");
+        assert!(!big_recent_videos.matches(&entry("old.mp4", 2 * GB, 400, None), &[]), "too old");
+        assert!(!big_recent_videos.matches(&entry("disk.iso", 2 * GB, 10, None), &[]), "not a video");
+
+        let tagged_favorites = SearchFilters {
+            tag: Some("Family".to_string()),
+            min_rating: Some(4),
+            ..SearchFilters::default()
+        };
+        let family = vec!["family".to_string()];
+        assert!(tagged_favorites.matches(&entry("a.jpg", 1, 1, Some(5)), &family));
+        assert!(!tagged_favorites.matches(&entry("b.jpg", 1, 1, Some(3)), &family), "rated too low");
+        assert!(!tagged_favorites.matches(&entry("c.jpg", 1, 1, None), &family), "unrated");
+        assert!(!tagged_favorites.matches(&entry("d.jpg", 1, 1, Some(5)), &[]), "untagged");
+
+        assert!(!SearchFilters::default().matches(&entry(".hidden.jpg", 1, 1, None), &[]));
+    }
+
+    #[test]
+    fn test_scan_aggregates_matches_across_the_tree() {
+        let root = TempDir::new().unwrap();
+        fs::create_dir_all(root.path().join("2024/summer")).unwrap();
+        fs::create_dir_all(root.path().join(".cache")).unwrap();
+        fs::write(root.path().join("notes.txt"), "notes").unwrap();
+        fs::write(root.path().join("2024/beach.mp4"), "video").unwrap();
+        fs::write(root.path().join("2024/summer/boat.mov"), "video").unwrap();
+        fs::write(root.path().join(".cache/thumb.mp4"), "video").unwrap();
+
+        let folder = SmartFolder::new(
+            "Videos",
+            root.path(),
+            SearchFilters {
+                file_types: vec!["Videos".to_string()],
+                ..SearchFilters::default()
+            },
+        );
+        let mut found = Vec::new();
+        let count = scan_smart_folder(&folder, &CancellationToken::new(), |_| Vec::new(), |entry| {
+            found.push(entry.path.strip_prefix(root.path()).unwrap().to_path_buf())
+        })
+        .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(
+            found,
+            vec![PathBuf::from("2024/beach.mp4"), PathBuf::from("2024/summer/boat.mov")]
+        );
+
+        let token = CancellationToken::new();
+        token.cancel();
+        assert!(matches!(
+            scan_smart_folder(&folder, &token, |_| Vec::new(), |_| {}),
+            Err(SmartFolderError::Cancelled)
+        ));
+    }
+
+    #[test]
+    fn test_name_search_matches_substrings_and_globs() {
+        let root = TempDir::new().unwrap();
+        fs::create_dir_all(root.path().join("photos/raw")).unwrap();
+        fs::create_dir_all(root.path().join(".trash")).unwrap();
+        fs::write(root.path().join("photos/Holiday.JPG"), "jpg").unwrap();
+        fs::write(root.path().join("photos/raw/holiday.cr2"), "raw").unwrap();
+        fs::write(root.path().join("photos/holiday-notes.txt"), "notes").unwrap();
+        fs::write(root.path().join(".holiday.jpg"), "jpg").unwrap();
+        fs::write(root.path().join(".trash/holiday.jpg"), "jpg").unwrap();
+
+        let search = |query: &str, include_hidden: bool| {
+            let search = NameSearch::new(root.path(), query).with_hidden(include_hidden);
+            let mut found = Vec::new();
+            search_names(&search, &CancellationToken::new(), |entry| {
+                found.push(entry.path.strip_prefix(root.path()).unwrap().to_string_lossy().into_owned())
+            })
+            .unwrap();
+            found
+        };
+
+        assert_eq!(
+            search("HOLIDAY", false),
+            vec!["photos/Holiday.JPG", "photos/holiday-notes.txt", "photos/raw/holiday.cr2"]
+        );
+        assert_eq!(search("holiday.*", false), vec!["photos/Holiday.JPG", "photos/raw/holiday.cr2"]);
+        assert_eq!(search("*.jpg", false), vec!["photos/Holiday.JPG"]);
+        assert_eq!(search("raw", false), vec!["photos/raw"], "folders match by name too");
+        assert_eq!(
+            search("*.jpg", true),
+            vec![".holiday.jpg", ".trash/holiday.jpg", "photos/Holiday.JPG"]
+        );
+    }
+
+    #[test]
+    fn test_name_search_stops_at_max_results() {
+        let root = TempDir::new().unwrap();
+        for i in 0..10 {
+            fs::write(root.path().join(format!("file{}.txt", i)), "x").unwrap();
+        }
+        let search = NameSearch::new(root.path(), "*.txt").with_max_results(3);
+        let mut found = 0;
+        let count = search_names(&search, &CancellationToken::new(), |_| found += 1).unwrap();
+        assert_eq!((count, found), (3, 3));
+
+        let token = CancellationToken::new();
+        token.cancel();
+        assert!(matches!(
+            search_names(&search, &token, |_| {}),
+            Err(SmartFolderError::Cancelled)
+        ));
+    }
+}
@@ -0,0 +1,456 @@
+//! User tags: free-form labels attached to files and folders
+//!
+//! Tags live in one app-level index under the data dir, keyed by path, so
+//! tagging never writes next to the user's files. Tags compare
+//! case-insensitively and keep the spelling they were first added with.
+//! A tag can be given a color, kept in the same index.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use thiserror::Error;
+
+use super::file_system::FileEntry;
+
+/// Current tag index file format
+const TAG_INDEX_VERSION: u32 = 1;
+
+/// Errors that can occur while reading or writing the tag index
+#[derive(Debug, Error)]
+pub enum TagStoreError {
+    #[error("Tag store I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Tag store serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+pub type TagStoreResult<T> = Result<T, TagStoreError>;
+
+/// On-disk form of the store
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct TagIndex {
+    version: u32,
+    files: BTreeMap<PathBuf, Vec<String>>,
+    /// CSS colors keyed by lowercased tag
+    colors: BTreeMap<String, String>,
+}
+
+impl Default for TagIndex {
+    fn default() -> Self {
+        Self { version: TAG_INDEX_VERSION, files: BTreeMap::new(), colors: BTreeMap::new() }
+    }
+}
+
+/// Tags by path, saved to a JSON file after every change
+#[derive(Debug)]
+pub struct TagStore {
+    path: PathBuf,
+    index: Mutex<TagIndex>,
+}
+
+impl TagStore {
+    /// An empty store that will save to `path`
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, index: Mutex::new(TagIndex::default()) }
+    }
+
+    /// Load the store saved at `path`; a missing file is an empty store
+    pub fn open(path: PathBuf) -> TagStoreResult<Self> {
+        let index = match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => TagIndex::default(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self { path, index: Mutex::new(index) })
+    }
+
+    /// `tags.json` in the user's data directory
+    pub fn default_path() -> PathBuf {
+        dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("media-organizer")
+            .join("tags.json")
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Tags of `path` in the order they were added
+    pub fn tags_of(&self, path: &Path) -> Vec<String> {
+        self.lock().files.get(path).cloned().unwrap_or_default()
+    }
+
+    /// Tag `path` with `tag`; false if the tag is blank or already there
+    pub fn add_tag(&self, path: &Path, tag: &str) -> TagStoreResult<bool> {
+        let tag = tag.trim();
+        if tag.is_empty() {
+            return Ok(false);
+        }
+        let mut index = self.lock();
+        let tags = index.files.entry(path.to_path_buf()).or_default();
+        if tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            return Ok(false);
+        }
+        tags.push(tag.to_string());
+        self.save(&index)?;
+        Ok(true)
+    }
+
+    /// Take `tag` off `path`; false if it wasn't there
+    pub fn remove_tag(&self, path: &Path, tag: &str) -> TagStoreResult<bool> {
+        let mut index = self.lock();
+        let Some(tags) = index.files.get_mut(path) else {
+            return Ok(false);
+        };
+        let before = tags.len();
+        tags.retain(|t| !t.eq_ignore_ascii_case(tag.trim()));
+        if tags.len() == before {
+            return Ok(false);
+        }
+        if tags.is_empty() {
+            index.files.remove(path);
+        }
+        self.save(&index)?;
+        Ok(true)
+    }
+
+    /// Paths carrying `tag`
+    pub fn files_with_tag(&self, tag: &str) -> Vec<PathBuf> {
+        self.lock()
+            .files
+            .iter()
+            .filter(|(_, tags)| tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+
+    /// Every tag in use, sorted case-insensitively
+    pub fn all_tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = Vec::new();
+        for tag in self.lock().files.values().flatten() {
+            if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                tags.push(tag.clone());
+            }
+        }
+        tags.sort_by_key(|tag| tag.to_lowercase());
+        tags
+    }
+
+    /// Move the tags of `old_path`, and of everything under it, to `new_path`
+    pub fn rename_path(&self, old_path: &Path, new_path: &Path) -> TagStoreResult<bool> {
+        let mut index = self.lock();
+        let moved: Vec<PathBuf> = index.files.keys().filter(|path| path.starts_with(old_path)).cloned().collect();
+        if moved.is_empty() {
+            return Ok(false);
+        }
+        for path in moved {
+            if let Some(tags) = index.files.remove(&path) {
+                let relative = path.strip_prefix(old_path).unwrap_or(Path::new(""));
+                index.files.insert(new_path.join(relative), tags);
+            }
+        }
+        self.save(&index)?;
+        Ok(true)
+    }
+
+    /// Rename `old` to `new` on every file; a file already carrying `new` keeps one copy
+    pub fn rename_tag(&self, old: &str, new: &str) -> TagStoreResult<bool> {
+        let (old, new) = (old.trim(), new.trim());
+        if new.is_empty() || old == new {
+            return Ok(false);
+        }
+        let mut index = self.lock();
+        let mut changed = false;
+        for tags in index.files.values_mut() {
+            let Some(position) = tags.iter().position(|t| t.eq_ignore_ascii_case(old)) else {
+                continue;
+            };
+            if tags.iter().enumerate().any(|(i, t)| i != position && t.eq_ignore_ascii_case(new)) {
+                tags.remove(position);
+            } else {
+                tags[position] = new.to_string();
+            }
+            changed = true;
+        }
+        if let Some(color) = index.colors.remove(&old.to_lowercase()) {
+            index.colors.entry(new.to_lowercase()).or_insert(color);
+            changed = true;
+        }
+        if changed {
+            self.save(&index)?;
+        }
+        Ok(changed)
+    }
+
+    /// Take `tag` off every file and forget its color
+    pub fn delete_tag(&self, tag: &str) -> TagStoreResult<bool> {
+        let tag = tag.trim();
+        let mut index = self.lock();
+        let mut changed = index.colors.remove(&tag.to_lowercase()).is_some();
+        index.files.retain(|_, tags| {
+            let before = tags.len();
+            tags.retain(|t| !t.eq_ignore_ascii_case(tag));
+            changed |= tags.len() != before;
+            !tags.is_empty()
+        });
+        if changed {
+            self.save(&index)?;
+        }
+        Ok(changed)
+    }
+
+    /// Color `tag` is shown in, if one was picked
+    pub fn tag_color(&self, tag: &str) -> Option<String> {
+        self.lock().colors.get(&tag.trim().to_lowercase()).cloned()
+    }
+
+    /// Show `tag` in `color`, or in the default color for `None`
+    pub fn set_tag_color(&self, tag: &str, color: Option<&str>) -> TagStoreResult<bool> {
+        let key = tag.trim().to_lowercase();
+        let mut index = self.lock();
+        let previous = match color {
+            Some(color) => index.colors.insert(key, color.to_string()),
+            None => index.colors.remove(&key),
+        };
+        if previous.as_deref() == color {
+            return Ok(false);
+        }
+        self.save(&index)?;
+        Ok(true)
+    }
+
+    /// Snapshot of which files carry each tag, for browsing by tag
+    pub fn file_index(&self) -> TagFileIndex {
+        let index = self.lock();
+        let mut tags: BTreeMap<String, TagFiles> = BTreeMap::new();
+        for (path, carried) in &index.files {
+            for tag in carried {
+                let key = tag.to_lowercase();
+                let color = index.colors.get(&key).cloned();
+                tags.entry(key)
+                    .or_insert_with(|| TagFiles { name: tag.clone(), color, paths: Vec::new() })
+                    .paths
+                    .push(path.clone());
+            }
+        }
+        TagFileIndex { tags }
+    }
+
+    /// The files carrying every one of `tags`, for filtering listings
+    pub fn filter(&self, tags: &[String]) -> TagFilter {
+        let paths = self
+            .lock()
+            .files
+            .iter()
+            .filter(|(_, carried)| has_all_tags(carried, tags))
+            .map(|(path, _)| path.clone())
+            .collect();
+        TagFilter { tags: tags.to_vec(), paths }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, TagIndex> {
+        self.index.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Write the index to a temporary file and rename it into place
+    fn save(&self, index: &TagIndex) -> TagStoreResult<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut temp_name = self.path.as_os_str().to_owned();
+        temp_name.push(".tmp");
+        let temp_path = PathBuf::from(temp_name);
+        fs::write(&temp_path, serde_json::to_vec_pretty(index)?)?;
+        if let Err(e) = fs::rename(&temp_path, &self.path) {
+            let _ = fs::remove_file(&temp_path);
+            return Err(e.into());
+        }
+        Ok(())
+    }
+}
+
+/// Whether `carried` includes every tag of `required`, ignoring case
+pub fn has_all_tags(carried: &[String], required: &[String]) -> bool {
+    required
+        .iter()
+        .all(|tag| carried.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+}
+
+/// Files carrying a set of tags, resolved against a [`TagStore`]
+///
+/// Folders always pass so the tagged files inside them can be reached.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TagFilter {
+    pub tags: Vec<String>,
+    paths: HashSet<PathBuf>,
+}
+
+impl TagFilter {
+    pub fn matches(&self, entry: &FileEntry) -> bool {
+        self.tags.is_empty() || entry.is_directory || self.paths.contains(&entry.path)
+    }
+}
+
+/// One tag and the files carrying it
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagFiles {
+    pub name: String,
+    pub color: Option<String>,
+    /// Sorted, since the index iterates paths in order
+    pub paths: Vec<PathBuf>,
+}
+
+/// Files by tag, as read from a [`TagStore`] at one moment
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TagFileIndex {
+    /// Keyed by lowercased tag, so tags list case-insensitively sorted
+    tags: BTreeMap<String, TagFiles>,
+}
+
+impl TagFileIndex {
+    /// Every tag in use, sorted case-insensitively
+    pub fn tags(&self) -> impl Iterator<Item = &TagFiles> {
+        self.tags.values()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+
+    /// Files carrying `tag` anywhere under `root`
+    pub fn files_under<'a>(&'a self, tag: &str, root: &'a Path) -> impl Iterator<Item = &'a Path> {
+        self.tags
+            .get(&tag.trim().to_lowercase())
+            .into_iter()
+            .flat_map(|files| files.paths.iter())
+            .map(PathBuf::as_path)
+            .filter(move |path| path.starts_with(root))
+    }
+
+    /// How many files under `root` carry `tag`
+    pub fn count_under(&self, tag: &str, root: &Path) -> usize {
+        self.files_under(tag, root).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::file_system::{FilePermissions, FileType};
+    use std::time::SystemTime;
+    use tempfile::TempDir;
+
+    fn entry(path: &str, is_directory: bool) -> FileEntry {
+        let path = PathBuf::from(path);
+        FileEntry {
+            file_type: if is_directory { FileType::Directory } else { FileType::from_path(&path) },
+            name: path.file_name().unwrap().to_string_lossy().to_string(),
+            path,
+            size: 0,
+            modified: SystemTime::now(),
+            created: SystemTime::now(),
+            is_directory,
+            is_hidden: false,
+            permissions: FilePermissions::default(),
+            preview_metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_tags_persist_across_reopen() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("tags.json");
+        let photo = Path::new("/photos/beach.jpg");
+
+        let store = TagStore::open(path.clone()).unwrap();
+        assert!(store.add_tag(photo, " Holiday ").unwrap());
+        assert!(!store.add_tag(photo, "holiday").unwrap());
+        assert!(!store.add_tag(photo, "  ").unwrap());
+        assert!(store.add_tag(photo, "family").unwrap());
+
+        let store = TagStore::open(path.clone()).unwrap();
+        assert_eq!(store.tags_of(photo), vec!["Holiday".to_string(), "family".to_string()]);
+        assert_eq!(store.files_with_tag("HOLIDAY"), vec![photo.to_path_buf()]);
+
+        assert!(store.remove_tag(photo, "holiday").unwrap());
+        assert!(!store.remove_tag(photo, "holiday").unwrap());
+        assert!(store.rename_path(Path::new("/photos"), Path::new("/albums")).unwrap());
+
+        let store = TagStore::open(path).unwrap();
+        assert!(store.tags_of(photo).is_empty());
+        assert_eq!(store.tags_of(Path::new("/albums/beach.jpg")), vec!["family".to_string()]);
+        assert_eq!(store.all_tags(), vec!["family".to_string()]);
+    }
+
+    #[test]
+    fn test_filter_keeps_entries_carrying_every_tag() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = TagStore::new(temp_dir.path().join("tags.json"));
+        store.add_tag(Path::new("/a.jpg"), "beach").unwrap();
+        store.add_tag(Path::new("/a.jpg"), "2024").unwrap();
+        store.add_tag(Path::new("/b.jpg"), "beach").unwrap();
+
+        let entries = vec![entry("/a.jpg", false), entry("/b.jpg", false), entry("/c.jpg", false), entry("/trips", true)];
+        let kept = |tags: &[&str]| -> Vec<String> {
+            let filter = store.filter(&tags.iter().map(|t| t.to_string()).collect::<Vec<_>>());
+            entries.iter().filter(|e| filter.matches(e)).map(|e| e.name.clone()).collect()
+        };
+
+        assert_eq!(kept(&[]), vec!["a.jpg", "b.jpg", "c.jpg", "trips"]);
+        assert_eq!(kept(&["Beach"]), vec!["a.jpg", "b.jpg", "trips"]);
+        assert_eq!(kept(&["beach", "2024"]), vec!["a.jpg", "trips"]);
+        assert_eq!(kept(&["sunset"]), vec!["trips"]);
+    }
+
+    #[test]
+    fn test_file_index_looks_up_tagged_files_under_a_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = TagStore::new(temp_dir.path().join("tags.json"));
+        store.add_tag(Path::new("/photos/2024/a.jpg"), "Beach").unwrap();
+        store.add_tag(Path::new("/photos/b.jpg"), "beach").unwrap();
+        store.add_tag(Path::new("/other/c.jpg"), "beach").unwrap();
+        store.add_tag(Path::new("/photos/b.jpg"), "family").unwrap();
+        store.set_tag_color("BEACH", Some("#2a9d8f")).unwrap();
+
+        let index = store.file_index();
+        let tags: Vec<(&str, Option<&str>, usize)> =
+            index.tags().map(|tag| (tag.name.as_str(), tag.color.as_deref(), tag.paths.len())).collect();
+        assert_eq!(tags, vec![("beach", Some("#2a9d8f"), 3), ("family", None, 1)]);
+
+        let root = Path::new("/photos");
+        let under: Vec<&Path> = index.files_under("beach", root).collect();
+        assert_eq!(under, vec![Path::new("/photos/2024/a.jpg"), Path::new("/photos/b.jpg")]);
+        assert_eq!(index.count_under("family", root), 1);
+        assert_eq!(index.count_under("family", Path::new("/other")), 0);
+        assert_eq!(index.count_under("sunset", root), 0);
+    }
+
+    #[test]
+    fn test_rename_merges_and_delete_removes_tags_everywhere() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("tags.json");
+        let store = TagStore::open(path.clone()).unwrap();
+        store.add_tag(Path::new("/a.jpg"), "trip").unwrap();
+        store.add_tag(Path::new("/a.jpg"), "holiday").unwrap();
+        store.add_tag(Path::new("/b.jpg"), "trip").unwrap();
+        store.set_tag_color("trip", Some("red")).unwrap();
+        assert!(!store.set_tag_color("trip", Some("red")).unwrap());
+
+        assert!(store.rename_tag("Trip", "Holiday").unwrap());
+        assert!(!store.rename_tag("trip", "holiday").unwrap());
+        assert_eq!(store.tags_of(Path::new("/a.jpg")), vec!["holiday".to_string()]);
+        assert_eq!(store.tags_of(Path::new("/b.jpg")), vec!["Holiday".to_string()]);
+        assert_eq!(store.tag_color("holiday"), Some("red".to_string()));
+
+        assert!(store.delete_tag("HOLIDAY").unwrap());
+        let store = TagStore::open(path).unwrap();
+        assert!(store.all_tags().is_empty());
+        assert_eq!(store.tag_color("holiday"), None);
+        assert!(!store.delete_tag("holiday").unwrap());
+    }
+}
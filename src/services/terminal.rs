@@ -0,0 +1,119 @@
+//! Simple embedded terminal
+//!
+//! Runs the user's shell with piped stdin/stdout/stderr. There is no
+//! pseudo-terminal, so full-screen programs and prompts without a trailing
+//! newline are not shown, but line-based commands work. Output is read on
+//! background threads into a bounded [`OutputLog`].
+
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::{Arc, Mutex};
+
+use super::output::{stream_lines, OutputLog, OutputStream};
+
+/// Lines kept by the Terminal tab
+pub const TERMINAL_SCROLLBACK: usize = 2000;
+
+/// Source name used for terminal lines
+const TERMINAL_SOURCE: &str = "terminal";
+
+/// A running shell; killed when dropped
+#[derive(Debug)]
+pub struct TerminalSession {
+    child: Mutex<Child>,
+    stdin: Mutex<Option<ChildStdin>>,
+    output: Arc<OutputLog>,
+    cwd: PathBuf,
+}
+
+impl TerminalSession {
+    /// Start the default shell in `cwd`
+    pub fn spawn(cwd: &Path) -> io::Result<Self> {
+        Self::spawn_with(default_shell(), cwd)
+    }
+
+    /// Start `shell` in `cwd`
+    pub fn spawn_with(shell: Command, cwd: &Path) -> io::Result<Self> {
+        let mut shell = shell;
+        let mut child = shell
+            .current_dir(cwd)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        let output = Arc::new(OutputLog::with_capacity(TERMINAL_SCROLLBACK));
+        if let Some(stdout) = child.stdout.take() {
+            stream_lines(stdout, TERMINAL_SOURCE, OutputStream::Stdout, &output);
+        }
+        if let Some(stderr) = child.stderr.take() {
+            stream_lines(stderr, TERMINAL_SOURCE, OutputStream::Stderr, &output);
+        }
+        let stdin = child.stdin.take();
+        Ok(Self {
+            child: Mutex::new(child),
+            stdin: Mutex::new(stdin),
+            output,
+            cwd: cwd.to_path_buf(),
+        })
+    }
+
+    /// Directory the shell was started in
+    pub fn cwd(&self) -> &Path {
+        &self.cwd
+    }
+
+    /// Lines written by the shell
+    pub fn output(&self) -> &Arc<OutputLog> {
+        &self.output
+    }
+
+    /// Send one line of input, echoing it into the scrollback
+    pub fn send_line(&self, line: &str) -> io::Result<()> {
+        let mut stdin = self.stdin.lock().map_err(|_| io::Error::other("terminal input lock poisoned"))?;
+        let Some(stdin) = stdin.as_mut() else {
+            return Err(io::Error::new(io::ErrorKind::BrokenPipe, "terminal has exited"));
+        };
+        self.output.info(TERMINAL_SOURCE, format!("$ {}", line));
+        writeln!(stdin, "{}", line)?;
+        stdin.flush()
+    }
+
+    /// Whether the shell is still running
+    pub fn is_running(&self) -> bool {
+        self.child
+            .lock()
+            .map(|mut child| matches!(child.try_wait(), Ok(None)))
+            .unwrap_or(false)
+    }
+
+    /// Stop the shell
+    pub fn kill(&self) {
+        if let Ok(mut stdin) = self.stdin.lock() {
+            stdin.take();
+        }
+        if let Ok(mut child) = self.child.lock() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+impl Drop for TerminalSession {
+    fn drop(&mut self) {
+        self.kill();
+    }
+}
+
+/// `$SHELL` (falling back to `sh`), or `%COMSPEC%` on Windows
+fn default_shell() -> Command {
+    #[cfg(windows)]
+    {
+        Command::new(std::env::var("COMSPEC").unwrap_or_else(|_| "cmd".to_string()))
+    }
+    #[cfg(not(windows))]
+    {
+        Command::new(std::env::var("SHELL").unwrap_or_else(|_| "sh".to_string()))
+    }
+}
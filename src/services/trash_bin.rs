@@ -0,0 +1,224 @@
+//! Reading the system trash back: listing, restoring and purging items
+//!
+//! The `trash` crate can only list the trash on Windows and on freedesktop
+//! systems such as Linux. Elsewhere every call fails with
+//! [`TrashBinError::Unsupported`] and the trash view says so.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use thiserror::Error;
+
+/// Errors from browsing or restoring the trash
+#[derive(Debug, Error)]
+pub enum TrashBinError {
+    #[error("The trash can't be browsed on this platform")]
+    Unsupported,
+
+    #[error("Failed to read the trash: {0}")]
+    Trash(String),
+
+    #[error("Cannot restore, {0} already exists")]
+    RestoreCollision(PathBuf),
+
+    #[error("Cannot restore {name:?}: {reason}")]
+    InvalidItem { name: String, reason: &'static str },
+
+    #[error("{0} is no longer in the trash")]
+    NotInTrash(PathBuf),
+}
+
+pub type TrashBinResult<T> = Result<T, TrashBinError>;
+
+/// Whether the trash can be listed here
+pub const SUPPORTED: bool = cfg!(any(
+    target_os = "windows",
+    all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))
+));
+
+/// An item in the system trash
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrashedItem {
+    pub name: String,
+    /// Folder the item was deleted from
+    pub original_parent: PathBuf,
+    /// Seconds since the Unix epoch
+    pub time_deleted: i64,
+}
+
+impl TrashedItem {
+    pub fn original_path(&self) -> PathBuf {
+        self.original_parent.join(&self.name)
+    }
+
+    pub fn deleted_at(&self) -> Option<SystemTime> {
+        let seconds = u64::try_from(self.time_deleted).ok()?;
+        SystemTime::UNIX_EPOCH.checked_add(Duration::from_secs(seconds))
+    }
+}
+
+/// Where `item` goes back to when restored
+///
+/// The trash's own records name the target, so they are checked before
+/// anything is moved: the name must be a single plain file name and the
+/// folder an absolute path. `exists` reports whether a path is taken; a taken
+/// target is never overwritten. A missing folder is fine and gets recreated.
+pub fn restore_target(item: &TrashedItem, exists: impl Fn(&Path) -> bool) -> TrashBinResult<PathBuf> {
+    let invalid = |reason| TrashBinError::InvalidItem { name: item.name.clone(), reason };
+    let mut components = Path::new(&item.name).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(_)), None) => {}
+        _ => return Err(invalid("not a plain file name")),
+    }
+    if !item.original_parent.is_absolute() {
+        return Err(invalid("its original folder is not an absolute path"));
+    }
+
+    let target = item.original_path();
+    if exists(&target) {
+        return Err(TrashBinError::RestoreCollision(target));
+    }
+    Ok(target)
+}
+
+/// Items in the trash, most recently deleted first
+pub fn list_trash() -> TrashBinResult<Vec<TrashedItem>> {
+    let mut items: Vec<TrashedItem> = platform::list()?.iter().map(platform::to_item).collect();
+    items.sort_by(|a, b| b.time_deleted.cmp(&a.time_deleted).then_with(|| a.name.cmp(&b.name)));
+    Ok(items)
+}
+
+/// Put `item` back where it was deleted from, returning that path
+///
+/// When the same path was trashed more than once, the copy deleted at
+/// `item.time_deleted` is preferred, then the most recent one.
+pub fn restore_item(item: &TrashedItem) -> TrashBinResult<PathBuf> {
+    let target = restore_target(item, |path| path.symlink_metadata().is_ok())?;
+    let raw = platform::find(item)?;
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| TrashBinError::Trash(e.to_string()))?;
+    }
+    platform::restore(raw)?;
+    Ok(target)
+}
+
+/// Delete `item` from the trash for good
+pub fn purge_item(item: &TrashedItem) -> TrashBinResult<()> {
+    let raw = platform::find(item)?;
+    platform::purge(raw)
+}
+
+#[cfg(any(target_os = "windows", all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))))]
+mod platform {
+    use super::{TrashBinError, TrashBinResult, TrashedItem};
+    use trash::TrashItem;
+
+    fn trash_error(error: trash::Error) -> TrashBinError {
+        TrashBinError::Trash(error.to_string())
+    }
+
+    pub fn list() -> TrashBinResult<Vec<TrashItem>> {
+        trash::os_limited::list().map_err(trash_error)
+    }
+
+    pub fn to_item(raw: &TrashItem) -> TrashedItem {
+        TrashedItem {
+            name: raw.name.clone(),
+            original_parent: raw.original_parent.clone(),
+            time_deleted: raw.time_deleted,
+        }
+    }
+
+    pub fn find(item: &TrashedItem) -> TrashBinResult<TrashItem> {
+        let original = item.original_path();
+        let mut matches: Vec<TrashItem> = list()?
+            .into_iter()
+            .filter(|raw| raw.original_parent.join(&raw.name) == original)
+            .collect();
+        matches.sort_by_key(|raw| (raw.time_deleted == item.time_deleted, raw.time_deleted));
+        matches.pop().ok_or(TrashBinError::NotInTrash(original))
+    }
+
+    pub fn restore(raw: TrashItem) -> TrashBinResult<()> {
+        trash::os_limited::restore_all([raw]).map_err(trash_error)
+    }
+
+    pub fn purge(raw: TrashItem) -> TrashBinResult<()> {
+        trash::os_limited::purge_all([raw]).map_err(trash_error)
+    }
+}
+
+/// The trash can't be read back here; the Finder's Put Back restores items on macOS
+#[cfg(not(any(target_os = "windows", all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android")))))]
+mod platform {
+    use super::{TrashBinError, TrashBinResult, TrashedItem};
+
+    /// Stands in for `trash::TrashItem`, which doesn't exist here
+    pub struct RawItem;
+
+    pub fn list() -> TrashBinResult<Vec<RawItem>> {
+        Err(TrashBinError::Unsupported)
+    }
+
+    pub fn to_item(_raw: &RawItem) -> TrashedItem {
+        unreachable!("nothing is ever listed")
+    }
+
+    pub fn find(_item: &TrashedItem) -> TrashBinResult<RawItem> {
+        Err(TrashBinError::Unsupported)
+    }
+
+    pub fn restore(_raw: RawItem) -> TrashBinResult<()> {
+        Err(TrashBinError::Unsupported)
+    }
+
+    pub fn purge(_raw: RawItem) -> TrashBinResult<()> {
+        Err(TrashBinError::Unsupported)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(name: &str, parent: &str) -> TrashedItem {
+        TrashedItem {
+            name: name.to_string(),
+            original_parent: PathBuf::from(parent),
+            time_deleted: 1_700_000_000,
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_restore_target_is_the_original_path() {
+        let nothing_exists = |_: &Path| false;
+        assert_eq!(
+            restore_target(&item("beach.jpg", "/photos/2024"), nothing_exists).unwrap(),
+            PathBuf::from("/photos/2024/beach.jpg")
+        );
+
+        let taken = restore_target(&item("beach.jpg", "/photos"), |path| path == Path::new("/photos/beach.jpg"));
+        assert!(matches!(taken, Err(TrashBinError::RestoreCollision(path)) if path == Path::new("/photos/beach.jpg")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_restore_target_rejects_unsafe_records() {
+        let nothing_exists = |_: &Path| false;
+        for name in ["", "..", "../etc/passwd", "a/b.jpg", "/abs.jpg"] {
+            let result = restore_target(&item(name, "/photos"), nothing_exists);
+            assert!(matches!(result, Err(TrashBinError::InvalidItem { .. })), "{:?} was accepted", name);
+        }
+        let relative = restore_target(&item("beach.jpg", "photos"), nothing_exists);
+        assert!(matches!(relative, Err(TrashBinError::InvalidItem { .. })));
+    }
+
+    #[test]
+    fn test_deleted_at_handles_bad_timestamps() {
+        let mut trashed = item("a.txt", "/");
+        assert_eq!(trashed.deleted_at(), Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000)));
+        trashed.time_deleted = -1;
+        assert_eq!(trashed.deleted_at(), None);
+    }
+}
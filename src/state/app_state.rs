@@ -1,11 +1,42 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::collections::HashMap;
-use crate::services::{FileEntry};
-use crate::services::file_system::{FileSystemService, NativeFileSystemService};
-use crate::services::preview::{PreviewData};
+use crate::services::{ErrorRecoveryManager, FileEntry, FileOperationProgress, FileOperationService, RetrySettings};
+use crate::services::file_system::{
+    cached_directory_size, compute_directory_size, ContentSniffing, FileSystemService, NativeFileSystemService,
+};
+use crate::services::preview::{PreviewData, SupportedFormat};
 use crate::services::preview::PreviewService;
-use crate::state::navigation::{NavigationState, SelectionState};
+use crate::services::preview::thumbnail_service::ThumbnailService;
+use crate::services::preview::PreviewConfig;
+use crate::services::preview::{ThumbnailCacheFormat, ThumbnailEncoding};
+use crate::services::preview_cache::PreviewCacheConfig;
+use crate::services::background::{BackgroundThrottleSettings, ThrottlePolicy};
+use crate::services::operations::{CancellationToken, OperationHistory};
+use crate::services::operation_log::OperationLog;
+use crate::services::progress_hub::ProgressHub;
+use crate::services::announcer::Announcer;
+use crate::services::open_with::{OpenWithApp, OpenWithHistory};
+use crate::state::recent_folders::RecentFoldersState;
+use crate::services::output::{CustomCommand, OutputLog};
+use crate::services::terminal::TerminalSession;
+use crate::services::tags::{TagFilter, TagStore, TagStoreResult};
+use crate::services::smart_folders::{
+    search_names, NameSearch, SearchFilters, SmartFolder, SmartFolderError, DEFAULT_MAX_SEARCH_RESULTS,
+};
+use crate::services::clipboard::{PathCopyMode, PathStyle};
+use crate::services::organize::OrganizeRule;
+use crate::services::content_search::{search_file_contents, ContentSearch, LineMatch, DEFAULT_MAX_CONTENT_FILE_SIZE};
+use crate::i18n::Locale;
+use crate::utils::{ClockFormat, SizeUnits};
+use crate::state::quick_look::QuickLookState;
+use crate::state::bookmarks::BookmarksState;
+use crate::state::keymap::KeymapState;
+use crate::state::directory_tabs::{DirectoryTabSession, DirectoryTabState};
+use crate::state::split_view::{PaneSide, SplitViewSession, SplitViewState};
+use crate::state::column_view::{step_selection, ColumnStep, ColumnViewState};
+use crate::state::window_geometry::ScreenRect;
+use crate::state::navigation::{NamePattern, NavigationState, SelectionMode, SelectionState};
 use dioxus::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -86,6 +117,8 @@ pub enum SidebarContent {
     Search,
     Extensions,
     Settings,
+    /// User tags and the files carrying them
+    Tags,
 }
 
 /// Editor layout state with enhanced configuration
@@ -134,6 +167,12 @@ pub enum PanelPosition {
 /// Viewport state for responsive layout calculations
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ViewportState {
+    /// Window left edge on the desktop in logical pixels, unknown until the window first moves
+    #[serde(default)]
+    pub x: Option<f64>,
+    /// Window top edge on the desktop in logical pixels
+    #[serde(default)]
+    pub y: Option<f64>,
     /// Window width in pixels
     pub width: f64,
     /// Window height in pixels
@@ -183,6 +222,18 @@ pub struct AppState {
     pub navigation: Signal<NavigationState>,
     /// File selection state (selected files, selection mode)
     pub selection: Signal<SelectionState>,
+    /// Open directory tabs; the active one is mirrored by `navigation` and `selection`
+    pub directory_tabs: Signal<DirectoryTabState>,
+    /// Two folder panes side by side, each navigated on its own
+    pub split_view: Signal<SplitViewState>,
+    /// Folder path shown as columns in the column view
+    pub column_view: Signal<ColumnViewState>,
+    /// Favorite folders shown in the sidebar
+    pub bookmarks: Signal<BookmarksState>,
+    /// Keyboard shortcuts the user rebound
+    pub keymap: Signal<KeymapState>,
+    /// Bumped whenever user tags change, so tag views read the store again
+    pub tags_revision: Signal<u64>,
     /// Current directory file entries
     pub file_entries: Signal<Vec<FileEntry>>,
     /// Current view mode (grid, list, preview)
@@ -205,16 +256,52 @@ pub struct AppState {
     pub settings: Signal<SettingsState>,
     /// Command registry and keyboard shortcuts
     pub command_registry: Signal<CommandRegistry>,
+    /// Menu item the command palette asked to run; the menu handler takes it
+    pub menu_action_request: Signal<Option<&'static str>>,
     /// Shortcut cheat sheet state (visibility)
     pub cheat_sheet_visible: Signal<bool>,
     /// Settings dialog state (visibility)
     pub settings_dialog_visible: Signal<bool>,
+    /// Operation log viewer state (visibility)
+    pub operation_log_visible: Signal<bool>,
+    /// Trash view state (visibility)
+    pub trash_visible: Signal<bool>,
+    /// Folder comparison tool state (visibility)
+    pub folder_compare_visible: Signal<bool>,
+    /// Files the batch rename dialog is open for, if it is
+    pub batch_rename_files: Signal<Option<Vec<FileEntry>>>,
+    /// Spacebar preview overlay of the selection
+    pub quick_look: Signal<QuickLookState>,
+    /// Smart folder whose results are shown, if any
+    pub open_smart_folder: Signal<Option<SmartFolder>>,
+    /// File operation shown in the progress dialog, if one is running
+    pub file_operation_progress: Signal<Option<FileOperationProgress>>,
     /// File system service for operations
     pub file_service: Arc<dyn FileSystemService>,
     /// Preview service for generating file previews and thumbnails
     pub preview_service: Arc<PreviewService>,
+    /// Small thumbnails for file lists, cached in memory
+    pub thumbnail_service: Arc<ThumbnailService>,
     /// Current preview data for selected file
     pub preview_data: Signal<Option<PreviewData>>,
+    /// Policy gating low-priority background tasks (quiet hours, battery, focus)
+    pub background_throttle: Arc<ThrottlePolicy>,
+    /// Undo history for file operations
+    pub operation_history: Arc<tokio::sync::Mutex<OperationHistory>>,
+    /// Persistent audit log written by the operation history
+    pub operation_log: Arc<OperationLog>,
+    /// Recent file operation failures, summarized in the status bar
+    pub error_recovery: Arc<std::sync::Mutex<ErrorRecoveryManager>>,
+    /// User tags of files and folders
+    pub tag_store: Arc<TagStore>,
+    /// Running tasks shown in the global progress HUD
+    pub progress_hub: Arc<ProgressHub>,
+    /// Screen-reader announcements rendered by the ARIA live regions
+    pub announcer: Arc<Announcer>,
+    /// Output of custom commands and background jobs, shown in the Output tab
+    pub output_log: Arc<OutputLog>,
+    /// Shell behind the Terminal tab, started when the tab is first opened
+    pub terminal: Signal<Option<Arc<TerminalSession>>>,
 }
 
 /// View mode options for file display
@@ -223,6 +310,8 @@ pub enum ViewMode {
     Grid,
     List,
     Preview,
+    /// Each selected folder opens in a new column to the right
+    Columns,
 }
 
 /// Activity Bar navigation views
@@ -234,6 +323,21 @@ pub enum ActivityBarView {
     Debug,
     Extensions,
     Settings,
+    /// Browse files by user tag
+    Tags,
+}
+
+impl ActivityBarView {
+    /// What the sidebar shows for this view
+    pub fn sidebar_content(&self) -> SidebarContent {
+        match self {
+            ActivityBarView::Search => SidebarContent::Search,
+            ActivityBarView::Extensions => SidebarContent::Extensions,
+            ActivityBarView::Settings => SidebarContent::Settings,
+            ActivityBarView::Tags => SidebarContent::Tags,
+            ActivityBarView::Explorer | ActivityBarView::SourceControl | ActivityBarView::Debug => SidebarContent::FileTree,
+        }
+    }
 }
 
 /// Sidebar state and configuration
@@ -251,6 +355,15 @@ pub struct SidebarState {
     pub is_resizable: bool,
 }
 
+/// Why a loaded folder lists nothing
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EmptyTreeState {
+    /// The folder has no entries
+    EmptyFolder,
+    /// Every entry is hidden by the file type or tag filter
+    AllFiltered,
+}
+
 /// File tree state and directory expansion tracking
 #[derive(Clone, Debug, Default)]
 pub struct FileTreeState {
@@ -266,6 +379,12 @@ pub struct FileTreeState {
     pub root_directory: Option<PathBuf>,
     /// Currently selected file/directory
     pub selected_path: Option<PathBuf>,
+    /// Which files are listed; mirrors `SettingsState::file_type_filter`
+    pub file_type_filter: FileTypeFilter,
+    /// Order of listed files; mirrors `SettingsState::file_sort`
+    pub sort: FileSort,
+    /// Only list files carrying these user tags
+    pub tag_filter: Option<TagFilter>,
 }
 
 impl FileTreeState {
@@ -327,6 +446,29 @@ impl FileTreeState {
         self.directory_children.get(path)
     }
     
+    /// Children of a directory that pass the file type and tag filters, in sort order
+    pub fn visible_children(&self, path: &PathBuf) -> Vec<FileEntry> {
+        let mut children: Vec<FileEntry> = self
+            .directory_children
+            .get(path)
+            .map(|children| {
+                children
+                    .iter()
+                    .filter(|child| self.passes_filters(child))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        self.sort.sort(&mut children);
+        children
+    }
+    
+    /// Whether `entry` passes the file type and tag filters
+    pub fn passes_filters(&self, entry: &FileEntry) -> bool {
+        self.file_type_filter.matches(entry)
+            && self.tag_filter.as_ref().map_or(true, |filter| filter.matches(entry))
+    }
+    
     /// Get error message for a directory
     pub fn get_directory_error(&self, path: &PathBuf) -> Option<&String> {
         self.error_directories.get(path)
@@ -360,10 +502,8 @@ impl FileTreeState {
         
         if let Some(root) = &self.root_directory {
             // Start with root directory children
-            if let Some(root_children) = self.directory_children.get(root) {
-                for child in root_children {
-                    self.collect_visible_entries_recursive(&child.path, &mut visible_entries);
-                }
+            for child in self.visible_children(root) {
+                self.collect_visible_entries_recursive(&child.path, &mut visible_entries);
             }
         }
         
@@ -377,10 +517,8 @@ impl FileTreeState {
         
         // If this is an expanded directory, add its children recursively
         if self.is_expanded(path) {
-            if let Some(children) = self.directory_children.get(path) {
-                for child in children {
-                    self.collect_visible_entries_recursive(&child.path, visible_entries);
-                }
+            for child in self.visible_children(path) {
+                self.collect_visible_entries_recursive(&child.path, visible_entries);
             }
         }
     }
@@ -537,6 +675,8 @@ impl Default for PanelLayout {
 impl Default for ViewportState {
     fn default() -> Self {
         Self {
+            x: None,
+            y: None,
             width: 1200.0,
             height: 800.0,
             pixel_ratio: 1.0,
@@ -546,6 +686,20 @@ impl Default for ViewportState {
     }
 }
 
+impl ViewportState {
+    /// Saved window position and size, once the window has been placed
+    pub fn window_rect(&self) -> Option<ScreenRect> {
+        Some(ScreenRect::new(self.x?, self.y?, self.width, self.height))
+    }
+
+    pub fn set_window_rect(&mut self, rect: ScreenRect) {
+        self.x = Some(rect.x);
+        self.y = Some(rect.y);
+        self.width = rect.width;
+        self.height = rect.height;
+    }
+}
+
 impl Default for UIPreferences {
     fn default() -> Self {
         Self {
@@ -583,7 +737,7 @@ impl Default for SidebarState {
 
 impl Default for ViewMode {
     fn default() -> Self {
-        ViewMode::Grid
+        ViewMode::List
     }
 }
 
@@ -594,6 +748,8 @@ pub enum Theme {
     Light,
     Auto, // Follows system preference
     HighContrast, // High contrast mode for accessibility
+    /// User theme loaded from the themes directory, by file stem
+    Custom(String),
 }
 
 impl Default for Theme {
@@ -603,20 +759,23 @@ impl Default for Theme {
 }
 
 impl Theme {
-    pub fn as_str(&self) -> &'static str {
+    pub fn as_str(&self) -> &str {
         match self {
             Theme::Dark => "dark",
             Theme::Light => "light",
             Theme::Auto => "auto",
             Theme::HighContrast => "high-contrast",
+            Theme::Custom(id) => id,
         }
     }
     
+    /// Parse a theme id; ids of loaded custom themes give `Theme::Custom`
     pub fn from_str(s: &str) -> Self {
         match s {
             "light" => Theme::Light,
             "auto" => Theme::Auto,
             "high-contrast" => Theme::HighContrast,
+            _ if crate::theme::custom_theme::custom_themes().get(s).is_some() => Theme::Custom(s.to_string()),
             _ => Theme::Dark,
         }
     }
@@ -772,9 +931,423 @@ impl FontSize {
     }
 }
 
+/// What happens when editor tabs do not fit in the tab strip
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TabOverflow {
+    /// Hide tabs that do not fit and list them in a dropdown
+    #[default]
+    Menu,
+    /// Keep every tab in a horizontally scrolling strip
+    Scroll,
+}
+
+impl TabOverflow {
+    pub const ALL: [TabOverflow; 2] = [TabOverflow::Menu, TabOverflow::Scroll];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TabOverflow::Menu => "menu",
+            TabOverflow::Scroll => "scroll",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "scroll" => TabOverflow::Scroll,
+            _ => TabOverflow::Menu,
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            TabOverflow::Menu => "Overflow Menu",
+            TabOverflow::Scroll => "Scroll",
+        }
+    }
+}
+
+/// Which files the file tree lists; folders are always listed
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FileTypeFilter {
+    #[default]
+    All,
+    Images,
+    Videos,
+    Audio,
+    Documents,
+    Archives,
+    /// Files with one of these lowercase extensions, without the dot
+    Custom(Vec<String>),
+}
+
+impl FileTypeFilter {
+    /// Every filter but `Custom`, in menu order
+    pub const PRESETS: [FileTypeFilter; 6] = [
+        FileTypeFilter::All,
+        FileTypeFilter::Images,
+        FileTypeFilter::Videos,
+        FileTypeFilter::Audio,
+        FileTypeFilter::Documents,
+        FileTypeFilter::Archives,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FileTypeFilter::All => "all",
+            FileTypeFilter::Images => "images",
+            FileTypeFilter::Videos => "videos",
+            FileTypeFilter::Audio => "audio",
+            FileTypeFilter::Documents => "documents",
+            FileTypeFilter::Archives => "archives",
+            FileTypeFilter::Custom(_) => "custom",
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            FileTypeFilter::All => "All Files",
+            FileTypeFilter::Images => "Images Only",
+            FileTypeFilter::Videos => "Videos Only",
+            FileTypeFilter::Audio => "Audio Only",
+            FileTypeFilter::Documents => "Documents Only",
+            FileTypeFilter::Archives => "Archives Only",
+            FileTypeFilter::Custom(_) => "Custom Extensions",
+        }
+    }
+
+    /// A custom filter from a list like `"jpg, .PNG heic"`
+    pub fn custom(extensions: &str) -> Self {
+        FileTypeFilter::Custom(
+            extensions
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .map(|ext| ext.trim_start_matches('.').to_lowercase())
+                .filter(|ext| !ext.is_empty())
+                .collect(),
+        )
+    }
+
+    /// The preset group the preview providers put files with `extension` in
+    pub fn group_of_extension(extension: &str) -> Option<FileTypeFilter> {
+        let format = SupportedFormat::from_extension(&extension.to_lowercase())?;
+        if format.is_image() {
+            Some(FileTypeFilter::Images)
+        } else if format.is_video() {
+            Some(FileTypeFilter::Videos)
+        } else if format.is_audio() {
+            Some(FileTypeFilter::Audio)
+        } else if format.is_document() || format.is_text() {
+            Some(FileTypeFilter::Documents)
+        } else if format.is_archive() {
+            Some(FileTypeFilter::Archives)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the tree lists `entry` under this filter
+    pub fn matches(&self, entry: &FileEntry) -> bool {
+        if entry.is_directory || *self == FileTypeFilter::All {
+            return true;
+        }
+        let Some(extension) = entry.path.extension().map(|ext| ext.to_string_lossy().to_lowercase()) else {
+            return false;
+        };
+        match self {
+            FileTypeFilter::Custom(extensions) => extensions.contains(&extension),
+            preset => FileTypeFilter::group_of_extension(&extension).as_ref() == Some(preset),
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        *self != FileTypeFilter::All
+    }
+}
+
+/// Property the file listing is ordered by
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SortKey {
+    #[default]
+    Name,
+    Size,
+    Modified,
+    Created,
+    Extension,
+    /// The classifier category, e.g. Images or Documents
+    Type,
+}
+
+impl SortKey {
+    pub const ALL: [SortKey; 6] = [
+        SortKey::Name,
+        SortKey::Size,
+        SortKey::Modified,
+        SortKey::Created,
+        SortKey::Extension,
+        SortKey::Type,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SortKey::Name => "name",
+            SortKey::Size => "size",
+            SortKey::Modified => "modified",
+            SortKey::Created => "created",
+            SortKey::Extension => "extension",
+            SortKey::Type => "type",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Self {
+        SortKey::ALL.into_iter().find(|key| key.as_str() == s).unwrap_or_default()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl SortOrder {
+    pub fn reversed(&self) -> Self {
+        match self {
+            SortOrder::Asc => SortOrder::Desc,
+            SortOrder::Desc => SortOrder::Asc,
+        }
+    }
+}
+
+/// How the file listing is ordered
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FileSort {
+    pub key: SortKey,
+    pub order: SortOrder,
+    /// List folders before files regardless of the key and order
+    pub directories_first: bool,
+}
+
+impl Default for FileSort {
+    fn default() -> Self {
+        Self {
+            key: SortKey::default(),
+            order: SortOrder::default(),
+            directories_first: true,
+        }
+    }
+}
+
+impl FileSort {
+    /// Order of `a` and `b` under this sort; equal entries keep their order
+    /// when used with a stable sort
+    pub fn compare(&self, a: &FileEntry, b: &FileEntry) -> std::cmp::Ordering {
+        if self.directories_first && a.is_directory != b.is_directory {
+            return b.is_directory.cmp(&a.is_directory);
+        }
+        let by_key = match self.key {
+            SortKey::Name => crate::utils::natural_cmp(&a.name, &b.name),
+            SortKey::Size => a.size.cmp(&b.size),
+            SortKey::Modified => a.modified.cmp(&b.modified),
+            SortKey::Created => a.created.cmp(&b.created),
+            SortKey::Extension => a.extension().cmp(&b.extension()),
+            SortKey::Type => {
+                let classifier = crate::services::classifier::classifier();
+                classifier
+                    .classify_entry(a)
+                    .category
+                    .cmp(&classifier.classify_entry(b).category)
+            }
+        };
+        match self.order {
+            SortOrder::Asc => by_key,
+            SortOrder::Desc => by_key.reverse(),
+        }
+    }
+
+    /// Stable sort of `entries`
+    pub fn sort(&self, entries: &mut [FileEntry]) {
+        entries.sort_by(|a, b| self.compare(a, b));
+    }
+}
+
+/// How thumbnails fill their tile
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ThumbnailShape {
+    /// Show the whole image, letterboxed inside the tile
+    #[default]
+    Fit,
+    /// Center-crop the image to fill a square tile
+    Square,
+}
+
+impl ThumbnailShape {
+    pub const ALL: [ThumbnailShape; 2] = [ThumbnailShape::Fit, ThumbnailShape::Square];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ThumbnailShape::Fit => "fit",
+            ThumbnailShape::Square => "square",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "square" => ThumbnailShape::Square,
+            _ => ThumbnailShape::Fit,
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ThumbnailShape::Fit => "Fit",
+            ThumbnailShape::Square => "Square Crop",
+        }
+    }
+
+    /// CSS `object-fit` value; switching shape only restyles the same thumbnail
+    pub fn object_fit(&self) -> &'static str {
+        match self {
+            ThumbnailShape::Fit => "contain",
+            ThumbnailShape::Square => "cover",
+        }
+    }
+}
+
+/// Edge length of thumbnail tiles
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ThumbnailSize {
+    Small,
+    #[default]
+    Medium,
+    Large,
+}
+
+impl ThumbnailSize {
+    pub const ALL: [ThumbnailSize; 3] = [ThumbnailSize::Small, ThumbnailSize::Medium, ThumbnailSize::Large];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ThumbnailSize::Small => "small",
+            ThumbnailSize::Medium => "medium",
+            ThumbnailSize::Large => "large",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "small" => ThumbnailSize::Small,
+            "large" => ThumbnailSize::Large,
+            _ => ThumbnailSize::Medium,
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ThumbnailSize::Small => "Small",
+            ThumbnailSize::Medium => "Medium",
+            ThumbnailSize::Large => "Large",
+        }
+    }
+
+    /// Tile edge in CSS pixels
+    pub fn pixels(&self) -> u32 {
+        match self {
+            ThumbnailSize::Small => 64,
+            ThumbnailSize::Medium => 128,
+            ThumbnailSize::Large => 192,
+        }
+    }
+}
+
+/// What double-clicking an item in the file list or gallery does
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DoubleClickAction {
+    /// Open it with the application the system uses for it
+    OpenDefault,
+    /// Show it in Quick Look
+    Preview,
+    /// Go into it; only folders can be entered
+    EnterFolder,
+}
+
+impl DoubleClickAction {
+    /// Choices offered for files
+    pub const FOR_FILES: [DoubleClickAction; 2] = [DoubleClickAction::Preview, DoubleClickAction::OpenDefault];
+    /// Choices offered for folders
+    pub const FOR_FOLDERS: [DoubleClickAction; 2] = [DoubleClickAction::EnterFolder, DoubleClickAction::OpenDefault];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DoubleClickAction::OpenDefault => "open_default",
+            DoubleClickAction::Preview => "preview",
+            DoubleClickAction::EnterFolder => "enter_folder",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "open_default" => Some(DoubleClickAction::OpenDefault),
+            "preview" => Some(DoubleClickAction::Preview),
+            "enter_folder" => Some(DoubleClickAction::EnterFolder),
+            _ => None,
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            DoubleClickAction::OpenDefault => "Open with Default App",
+            DoubleClickAction::Preview => "Preview in Quick Look",
+            DoubleClickAction::EnterFolder => "Open Folder Here",
+        }
+    }
+}
+
+/// Double-click actions, one for files and one for folders
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DoubleClickSettings {
+    pub files: DoubleClickAction,
+    pub folders: DoubleClickAction,
+}
+
+impl Default for DoubleClickSettings {
+    fn default() -> Self {
+        Self {
+            files: DoubleClickAction::Preview,
+            folders: DoubleClickAction::EnterFolder,
+        }
+    }
+}
+
+impl DoubleClickSettings {
+    /// Action for an item; a choice that doesn't suit the item falls back to the default
+    pub fn action_for(&self, is_directory: bool) -> DoubleClickAction {
+        let (action, choices, fallback) = if is_directory {
+            (self.folders, &DoubleClickAction::FOR_FOLDERS, Self::default().folders)
+        } else {
+            (self.files, &DoubleClickAction::FOR_FILES, Self::default().files)
+        };
+        if choices.contains(&action) {
+            action
+        } else {
+            fallback
+        }
+    }
+}
+
+/// Layout of the saved settings file written by this version
+///
+/// Bump it with a step in `persistence::SETTINGS_MIGRATIONS` whenever a
+/// field is renamed, moved or becomes required.
+pub const SETTINGS_SCHEMA_VERSION: u32 = 2;
+
 /// Application settings and preferences
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SettingsState {
+    /// Layout the settings were saved with; see [`SETTINGS_SCHEMA_VERSION`]
+    #[serde(default)]
+    pub schema_version: u32,
     /// Current theme selection
     pub theme: Theme,
     /// Font family selection
@@ -797,11 +1370,121 @@ pub struct SettingsState {
     pub enable_animations: bool,
     /// Custom CSS variables override (advanced users)
     pub custom_css_variables: std::collections::HashMap<String, String>,
+    /// Quiet hours and power/focus rules for background indexing
+    #[serde(default)]
+    pub background_throttle: BackgroundThrottleSettings,
+    /// Language of the user interface
+    #[serde(default = "Locale::system")]
+    pub language: Locale,
+    /// Whether sizes are shown in binary (KiB) or decimal (KB) units
+    #[serde(default)]
+    pub size_units: SizeUnits,
+    /// 12- or 24-hour clock for dates and times
+    #[serde(default)]
+    pub clock_format: ClockFormat,
+    /// Let every launch start its own process instead of reusing the running one
+    #[serde(default)]
+    pub allow_multiple_instances: bool,
+    /// How editor tabs that do not fit are reached
+    #[serde(default)]
+    pub tab_overflow: TabOverflow,
+    /// Shell commands offered in the command palette
+    #[serde(default)]
+    pub custom_commands: Vec<CustomCommand>,
+    /// Saved searches listed in the sidebar
+    #[serde(default)]
+    pub smart_folders: Vec<SmartFolder>,
+    /// Fit or square-crop gallery thumbnails
+    #[serde(default)]
+    pub thumbnail_shape: ThumbnailShape,
+    /// Edge length of gallery thumbnails
+    #[serde(default)]
+    pub thumbnail_size: ThumbnailSize,
+    /// Rating, file type and GPS badges on gallery thumbnails
+    #[serde(default = "default_true")]
+    pub show_thumbnail_badges: bool,
+    /// Files hashed at once by duplicate scans
+    #[serde(default = "default_duplicate_scan_workers")]
+    pub duplicate_scan_workers: usize,
+    /// Run duplicate scans at low I/O priority under the background throttle rules
+    #[serde(default = "default_true")]
+    pub duplicate_scan_low_io_priority: bool,
+    /// When file headers decide a file's type instead of its extension
+    #[serde(default)]
+    pub content_sniffing: ContentSniffing,
+    /// Image format of thumbnails written to the disk cache
+    #[serde(default)]
+    pub thumbnail_cache_encoding: ThumbnailEncoding,
+    /// JPEG quality of cached thumbnails
+    #[serde(default = "default_thumbnail_cache_quality")]
+    pub thumbnail_cache_quality: u8,
+    /// Most matches a file name search shows
+    #[serde(default = "default_search_max_results")]
+    pub search_max_results: usize,
+    /// Which files the file tree lists
+    #[serde(default)]
+    pub file_type_filter: FileTypeFilter,
+    /// Order of the file listing
+    #[serde(default)]
+    pub file_sort: FileSort,
+    /// Files larger than this are skipped by content search
+    #[serde(default = "default_content_search_max_file_size")]
+    pub content_search_max_file_size: u64,
+    /// How failed file operations are retried
+    #[serde(default)]
+    pub retry_policy: RetrySettings,
+    /// Application last picked in Open With, by file extension
+    #[serde(default)]
+    pub open_with: OpenWithHistory,
+    /// Folders opened lately, offered when no folder is open
+    #[serde(default)]
+    pub recent_folders: RecentFoldersState,
+    /// What double-clicking a file or a folder does
+    #[serde(default)]
+    pub double_click: DoubleClickSettings,
+    /// Hash copied and moved files against the original and roll back if they differ
+    #[serde(default)]
+    pub verify_copies: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_duplicate_scan_workers() -> usize {
+    2
+}
+
+fn default_search_max_results() -> usize {
+    DEFAULT_MAX_SEARCH_RESULTS
+}
+
+fn default_content_search_max_file_size() -> u64 {
+    DEFAULT_MAX_CONTENT_FILE_SIZE
+}
+
+fn default_thumbnail_cache_quality() -> u8 {
+    ThumbnailCacheFormat::default().quality
+}
+
+impl SettingsState {
+    /// Display preferences for the formatting helpers in `utils::format`
+    pub fn format_settings(&self) -> crate::utils::FormatSettings {
+        crate::utils::FormatSettings::new(self.language)
+            .with_size_units(self.size_units)
+            .with_clock(self.clock_format)
+    }
+
+    /// How newly generated thumbnails are written to the disk cache
+    pub fn thumbnail_cache_format(&self) -> ThumbnailCacheFormat {
+        ThumbnailCacheFormat::new(self.thumbnail_cache_encoding, self.thumbnail_cache_quality)
+    }
 }
 
 impl Default for SettingsState {
     fn default() -> Self {
         Self {
+            schema_version: SETTINGS_SCHEMA_VERSION,
             theme: Theme::default(),
             font_family: FontFamily::default(),
             font_size: FontSize::default(),
@@ -813,10 +1496,41 @@ impl Default for SettingsState {
             auto_save_interval: 300, // 5 minutes
             enable_animations: true,
             custom_css_variables: std::collections::HashMap::new(),
+            background_throttle: BackgroundThrottleSettings::default(),
+            language: Locale::system(),
+            size_units: SizeUnits::default(),
+            clock_format: ClockFormat::default(),
+            allow_multiple_instances: false,
+            tab_overflow: TabOverflow::default(),
+            custom_commands: Vec::new(),
+            smart_folders: Vec::new(),
+            thumbnail_shape: ThumbnailShape::default(),
+            thumbnail_size: ThumbnailSize::default(),
+            show_thumbnail_badges: true,
+            duplicate_scan_workers: default_duplicate_scan_workers(),
+            duplicate_scan_low_io_priority: true,
+            content_sniffing: ContentSniffing::default(),
+            thumbnail_cache_encoding: ThumbnailEncoding::default(),
+            thumbnail_cache_quality: default_thumbnail_cache_quality(),
+            search_max_results: default_search_max_results(),
+            file_type_filter: FileTypeFilter::default(),
+            file_sort: FileSort::default(),
+            content_search_max_file_size: default_content_search_max_file_size(),
+            retry_policy: RetrySettings::default(),
+            open_with: OpenWithHistory::default(),
+            recent_folders: RecentFoldersState::default(),
+            double_click: DoubleClickSettings::default(),
+            verify_copies: false,
         }
     }
 }
 
+/// Quiet time after a keystroke before a file search starts walking
+const SEARCH_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(250);
+
+/// Matches moved into `SearchState` per signal write
+const SEARCH_BATCH_SIZE: usize = 64;
+
 /// Search and filter state
 #[derive(Clone, Debug, Default)]
 pub struct SearchState {
@@ -824,15 +1538,12 @@ pub struct SearchState {
     pub is_active: bool,
     pub results: Vec<FileEntry>,
     pub filters: SearchFilters,
-}
-
-/// Search filters configuration
-#[derive(Clone, Debug, Default)]
-pub struct SearchFilters {
-    pub file_types: Vec<String>,
-    pub size_range: Option<(u64, u64)>,
-    pub modified_range: Option<(std::time::SystemTime, std::time::SystemTime)>,
-    pub include_hidden: bool,
+    /// Matching lines of each result of a content search, to jump to in the preview
+    pub line_matches: HashMap<PathBuf, Vec<LineMatch>>,
+    /// Set while the tree walk for `query` is still running
+    pub is_searching: bool,
+    /// Cancels the running walk when the query changes
+    pub cancellation_token: Option<CancellationToken>,
 }
 
 /// Operation progress and status tracking
@@ -843,6 +1554,8 @@ pub struct OperationState {
     pub progress: f32,
     pub status_message: String,
     pub can_cancel: bool,
+    /// Cancels the running operation when `can_cancel` is set
+    pub cancellation_token: Option<crate::services::operations::CancellationToken>,
 }
 
 /// Bottom panel state for terminal, problems, output, etc.
@@ -905,8 +1618,23 @@ pub struct EditorState {
     pub drag_operation: Option<TabDragOperation>,
     /// Context menu state
     pub context_menu: Option<TabContextMenu>,
+    /// Recently closed tabs, most recent last, for "Reopen Closed Tab"
+    pub closed_tabs: Vec<ClosedTab>,
+}
+
+/// A closed tab and where it was, so it can be reopened in place
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClosedTab {
+    pub tab: EditorTab,
+    /// ID of the group the tab was in
+    pub group_id: usize,
+    /// Position of the tab within that group
+    pub tab_index: usize,
 }
 
+/// How many closed tabs can be reopened
+pub const MAX_CLOSED_TABS: usize = 20;
+
 /// Information about a tab being dragged
 #[derive(Clone, Debug, PartialEq)]
 pub struct TabDragOperation {
@@ -916,6 +1644,43 @@ pub struct TabDragOperation {
     pub tab_index: usize,
     /// ID of the source editor group
     pub source_group_id: usize,
+    /// Where the tab would land if dropped now, shown as a drop indicator
+    pub drop_target: Option<TabDropTarget>,
+}
+
+/// Where a dragged tab would land
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TabDropTarget {
+    /// At this position in a group's tab bar; the group's tab count means the end
+    Tab { group_index: usize, tab_index: usize },
+    /// In a new group split off on this side of a group
+    Edge { group_index: usize, edge: SplitEdge },
+}
+
+/// Side of an editor group a tab can be dropped on to open it in a new group
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SplitEdge {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+impl SplitEdge {
+    pub const ALL: [SplitEdge; 4] = [SplitEdge::Left, SplitEdge::Right, SplitEdge::Top, SplitEdge::Bottom];
+
+    /// Layout a split on this side produces
+    fn layout(self) -> EditorLayoutConfig {
+        match self {
+            SplitEdge::Left | SplitEdge::Right => EditorLayoutConfig::SplitHorizontal,
+            SplitEdge::Top | SplitEdge::Bottom => EditorLayoutConfig::SplitVertical,
+        }
+    }
+
+    /// Whether the new group is placed after the group it was dropped on
+    fn is_after(self) -> bool {
+        matches!(self, SplitEdge::Right | SplitEdge::Bottom)
+    }
 }
 
 /// Context menu state for tabs
@@ -948,6 +1713,12 @@ pub struct CommandRegistry {
     pub palette_state: CommandPaletteState,
 }
 
+/// ID prefix of commands registered from `SettingsState::custom_commands`
+const CUSTOM_COMMAND_PREFIX: &str = "custom.";
+
+/// Palette category of the user's shell commands
+const CUSTOM_COMMAND_CATEGORY: &str = "Custom Commands";
+
 /// Individual command definition
 #[derive(Clone, Debug, PartialEq)]
 pub struct Command {
@@ -980,6 +1751,10 @@ pub enum CommandHandler {
     Navigation(NavigationCommand),
     /// View manipulation command
     View(ViewCommand),
+    /// User-defined shell command, run with its output in the Output tab
+    Shell(CustomCommand),
+    /// File list selection command
+    Selection(SelectionCommand),
 }
 
 /// System-level commands
@@ -1006,6 +1781,8 @@ pub enum EditorCommand {
     CloseOtherTabs,
     /// Close tabs to the right
     CloseTabsToRight,
+    /// Reopen the most recently closed tab
+    ReopenClosedTab,
     /// Pin/unpin current tab
     ToggleTabPin,
     /// Split editor horizontally
@@ -1031,14 +1808,62 @@ pub enum FileCommand {
     SaveAllFiles,
     /// Rename file
     RenameFile,
-    /// Delete file
+    /// Move the selection to the trash
     DeleteFile,
+    /// Delete the selection without the trash
+    DeletePermanently,
+    /// Copy the selection to a folder picked in the destination dialog
+    CopyTo,
+    /// Move the selection to a folder picked in the destination dialog
+    MoveTo,
+    /// Copy the selection next to itself
+    Duplicate,
+    /// Sort the selected files into folders under a picked destination
+    Organize(OrganizeRule),
     /// Copy file
     CopyFile,
     /// Cut file
     CutFile,
     /// Paste file
     PasteFile,
+    /// Copy the selection's paths or names as text, with the given separators
+    CopyPaths(PathCopyMode, PathStyle),
+    /// Put the selected files themselves on the clipboard
+    CopyAsFileReference,
+}
+
+impl FileCommand {
+    /// Menu item that runs this operation on the selection, if it has one
+    pub fn menu_id(&self) -> Option<&'static str> {
+        match self {
+            FileCommand::RenameFile => Some("rename"),
+            FileCommand::DeleteFile => Some("delete"),
+            FileCommand::DeletePermanently => Some("delete_permanently"),
+            FileCommand::CopyTo => Some("copy_to"),
+            FileCommand::MoveTo => Some("move_to"),
+            FileCommand::Duplicate => Some("duplicate"),
+            FileCommand::Organize(OrganizeRule::ByDate { .. }) => Some("organize_by_date"),
+            FileCommand::Organize(OrganizeRule::ByType) => Some("organize_by_type"),
+            _ => None,
+        }
+    }
+}
+
+impl Command {
+    /// Whether the palette can run this now; operations on the selection
+    /// need something selected
+    pub fn is_available(&self, has_selection: bool) -> bool {
+        let needs_selection = matches!(&self.handler, CommandHandler::File(file) if file.menu_id().is_some());
+        self.enabled && (has_selection || !needs_selection)
+    }
+}
+
+/// File list selection commands
+#[derive(Clone, Debug, PartialEq)]
+pub enum SelectionCommand {
+    /// Ask for a name pattern, then select the entries matching it, or with
+    /// `invert` those that don't, combined with the selection by `mode`
+    SelectMatching { invert: bool, mode: SelectionMode },
 }
 
 /// Navigation commands
@@ -1067,6 +1892,8 @@ pub enum ViewCommand {
     ListView,
     /// Switch to preview view
     PreviewView,
+    /// Switch to column view
+    ColumnsView,
     /// Toggle sidebar
     ToggleSidebar,
     /// Toggle bottom panel
@@ -1092,6 +1919,9 @@ pub struct CommandPaletteState {
     pub filtered_commands: Vec<String>,
     /// Maximum number of commands to display
     pub max_results: usize,
+    /// Selection command waiting for its pattern; while set, the search box
+    /// takes the pattern instead of a command name
+    pub pattern_prompt: Option<SelectionCommand>,
 }
 
 /// Individual editor group containing multiple tabs
@@ -1107,32 +1937,94 @@ pub struct EditorGroup {
     pub layout_position: EditorGroupPosition,
 }
 
-/// Individual editor tab
-#[derive(Clone, Debug, PartialEq)]
-pub struct EditorTab {
-    /// Unique identifier for this tab
-    pub id: usize,
-    /// Display title of the tab
-    pub title: String,
-    /// File path associated with this tab (if any)
-    pub file_path: Option<PathBuf>,
-    /// Tab type and content
-    pub tab_type: TabType,
-    /// Whether the tab has unsaved changes
-    pub is_dirty: bool,
-    /// Whether the tab is pinned
-    pub is_pinned: bool,
-    /// Whether the tab is currently active
+/// Width of a tab in the tab strip, in pixels
+pub const TAB_WIDTH: f64 = 160.0;
+
+/// Width of a pinned tab, which only shows its icon
+pub const PINNED_TAB_WIDTH: f64 = 44.0;
+
+/// Width of the button that opens the overflow menu
+pub const TAB_OVERFLOW_BUTTON_WIDTH: f64 = 32.0;
+
+impl EditorGroup {
+    /// Indices of tabs that do not fit in a strip `strip_width` pixels wide
+    ///
+    /// Pinned tabs and the active tab always stay visible; other tabs fill the
+    /// remaining space in order.
+    pub fn overflow_tabs(&self, strip_width: f64) -> Vec<usize> {
+        let tab_width = |tab: &EditorTab| if tab.is_pinned { PINNED_TAB_WIDTH } else { TAB_WIDTH };
+        let total: f64 = self.tabs.iter().map(tab_width).sum();
+        if total <= strip_width {
+            return Vec::new();
+        }
+
+        // Space left for unpinned tabs once the overflow button, pinned tabs and the active tab are placed
+        let mut remaining = strip_width - TAB_OVERFLOW_BUTTON_WIDTH;
+        for (i, tab) in self.tabs.iter().enumerate() {
+            if tab.is_pinned || i == self.active_tab {
+                remaining -= tab_width(tab);
+            }
+        }
+
+        let mut hidden = Vec::new();
+        for (i, tab) in self.tabs.iter().enumerate() {
+            if tab.is_pinned || i == self.active_tab {
+                continue;
+            }
+            if remaining >= TAB_WIDTH {
+                remaining -= TAB_WIDTH;
+            } else {
+                hidden.push(i);
+            }
+        }
+        hidden
+    }
+
+    /// Number of pinned tabs, which always come first
+    pub fn pinned_count(&self) -> usize {
+        self.tabs.iter().take_while(|tab| tab.is_pinned).count()
+    }
+
+    /// Where `tab` lands when inserted near `wanted`, keeping pinned tabs leftmost
+    fn insertion_index(&self, tab: &EditorTab, wanted: usize) -> usize {
+        let pinned = self.pinned_count();
+        if tab.is_pinned {
+            wanted.min(pinned)
+        } else {
+            wanted.clamp(pinned, self.tabs.len())
+        }
+    }
+}
+
+/// Individual editor tab
+#[derive(Clone, Debug, PartialEq)]
+pub struct EditorTab {
+    /// Unique identifier for this tab
+    pub id: usize,
+    /// Display title of the tab
+    pub title: String,
+    /// File path associated with this tab (if any)
+    pub file_path: Option<PathBuf>,
+    /// Tab type and content
+    pub tab_type: TabType,
+    /// Whether the tab has unsaved changes
+    pub is_dirty: bool,
+    /// Whether the tab is pinned
+    pub is_pinned: bool,
+    /// Whether the tab is currently active
     pub is_active: bool,
 }
 
 /// Types of tabs that can be opened
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum TabType {
     /// Welcome screen
     Welcome,
-    /// File editor for text/code files
-    FileEditor { content: String },
+    /// File editor for text/code files; content is not persisted and is reloaded from the file
+    FileEditor {
+        #[serde(skip)]
+        content: String,
+    },
     /// Preview tab for media files
     Preview { preview_type: PreviewType },
     /// Settings panel
@@ -1142,7 +2034,7 @@ pub enum TabType {
 }
 
 /// Types of previews that can be displayed
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum PreviewType {
     Image,
     Video,
@@ -1153,7 +2045,7 @@ pub enum PreviewType {
 }
 
 /// Position and layout configuration for editor groups
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct EditorGroupPosition {
     /// X position as a fraction of available space (0.0 to 1.0)
     pub x: f32,
@@ -1211,10 +2103,313 @@ impl Default for EditorState {
             next_tab_id: 2, // Start from 2 since welcome tab uses 1
             drag_operation: None,
             context_menu: None,
+            closed_tabs: Vec::new(),
         }
     }
 }
 
+impl EditorState {
+    /// Close a tab, keeping it on the closed-tab stack; the last tab of a group stays open
+    pub fn close_tab(&mut self, group_index: usize, tab_index: usize) -> bool {
+        let Some(group) = self.editor_groups.get_mut(group_index) else {
+            return false;
+        };
+        if group.tabs.len() <= 1 || tab_index >= group.tabs.len() {
+            return false;
+        }
+
+        let mut tab = group.tabs.remove(tab_index);
+        tab.is_active = false;
+        if group.active_tab >= group.tabs.len() {
+            group.active_tab = group.tabs.len() - 1;
+        }
+        for (i, t) in group.tabs.iter_mut().enumerate() {
+            t.is_active = i == group.active_tab;
+        }
+        let group_id = group.id;
+        self.remember_closed_tab(tab, group_id, tab_index);
+        true
+    }
+
+    /// Pin or unpin a tab; pinned tabs are kept leftmost in pin order
+    pub fn toggle_pin(&mut self, group_index: usize, tab_index: usize) -> bool {
+        let Some(group) = self.editor_groups.get_mut(group_index) else {
+            return false;
+        };
+        if tab_index >= group.tabs.len() {
+            return false;
+        }
+        let active_id = group.tabs.get(group.active_tab).map(|tab| tab.id);
+
+        let mut tab = group.tabs.remove(tab_index);
+        tab.is_pinned = !tab.is_pinned;
+        // Pinned tabs go to the end of the pinned block, unpinned ones right after it
+        let index = group.pinned_count();
+        group.tabs.insert(index, tab);
+
+        if let Some(active_index) = group.tabs.iter().position(|tab| Some(tab.id) == active_id) {
+            self.set_active_tab(group_index, active_index);
+        }
+        true
+    }
+
+    /// Close every unpinned tab except `keep_index`
+    pub fn close_others(&mut self, group_index: usize, keep_index: usize) -> usize {
+        self.close_unpinned_where(group_index, |i| i != keep_index)
+    }
+
+    /// Close the unpinned tabs right of `tab_index`
+    pub fn close_tabs_to_right(&mut self, group_index: usize, tab_index: usize) -> usize {
+        self.close_unpinned_where(group_index, |i| i > tab_index)
+    }
+
+    /// Close every unpinned tab in a group
+    pub fn close_all_unpinned(&mut self, group_index: usize) -> usize {
+        self.close_unpinned_where(group_index, |_| true)
+    }
+
+    /// Close the unpinned tabs whose index matches, keeping them on the closed-tab stack
+    ///
+    /// A group left empty is closed, or gets a welcome tab if it is the only one.
+    fn close_unpinned_where(&mut self, group_index: usize, should_close: impl Fn(usize) -> bool) -> usize {
+        let Some(group) = self.editor_groups.get_mut(group_index) else {
+            return 0;
+        };
+        let group_id = group.id;
+        let old_active = group.active_tab;
+
+        let mut kept = Vec::new();
+        let mut closed = Vec::new();
+        // The kept tab nearest to the old active one becomes active
+        let mut active_tab = None;
+        for (i, mut tab) in std::mem::take(&mut group.tabs).into_iter().enumerate() {
+            if !tab.is_pinned && should_close(i) {
+                tab.is_active = false;
+                closed.push((i, tab));
+            } else {
+                if i <= old_active || active_tab.is_none() {
+                    active_tab = Some(kept.len());
+                }
+                kept.push(tab);
+            }
+        }
+        group.tabs = kept;
+        let closed_count = closed.len();
+        for (i, tab) in closed {
+            self.remember_closed_tab(tab, group_id, i);
+        }
+
+        if self.editor_groups[group_index].tabs.is_empty() {
+            if self.editor_groups.len() > 1 {
+                self.remove_group(group_index);
+            } else {
+                let welcome = EditorTab {
+                    id: self.next_tab_id,
+                    title: "Welcome".to_string(),
+                    file_path: None,
+                    tab_type: TabType::Welcome,
+                    is_dirty: false,
+                    is_pinned: false,
+                    is_active: true,
+                };
+                self.next_tab_id += 1;
+                self.editor_groups[group_index].tabs.push(welcome);
+                self.set_active_tab(group_index, 0);
+            }
+        } else {
+            self.set_active_tab(group_index, active_tab.unwrap_or(0));
+        }
+        closed_count
+    }
+
+    /// Push a closed tab, dropping the oldest once the stack is full
+    pub fn remember_closed_tab(&mut self, tab: EditorTab, group_id: usize, tab_index: usize) {
+        if self.closed_tabs.len() == MAX_CLOSED_TABS {
+            self.closed_tabs.remove(0);
+        }
+        self.closed_tabs.push(ClosedTab { tab, group_id, tab_index });
+    }
+
+    /// Move a tab to `target_index` in `target_group`, closing the source group if it is left empty
+    ///
+    /// `target_index` is the tab's position after the move and is clamped to the end of the group.
+    pub fn move_tab(&mut self, source_group: usize, source_index: usize, target_group: usize, target_index: usize) -> bool {
+        if target_group >= self.editor_groups.len() {
+            return false;
+        }
+        let Some(tab) = self.take_tab(source_group, source_index) else {
+            return false;
+        };
+
+        let target = &mut self.editor_groups[target_group];
+        let index = target.insertion_index(&tab, target_index);
+        target.tabs.insert(index, tab);
+        self.set_active_tab(target_group, index);
+        self.active_group = target_group;
+
+        if self.editor_groups[source_group].tabs.is_empty() {
+            self.remove_group(source_group);
+        }
+        true
+    }
+
+    /// Move a tab into a new group on `edge` of `target_group`, closing the source group if it is left empty
+    pub fn split_tab(&mut self, source_group: usize, source_index: usize, target_group: usize, edge: SplitEdge) -> bool {
+        if target_group >= self.editor_groups.len() {
+            return false;
+        }
+        // Splitting a group's only tab next to itself would just recreate the group
+        if source_group == target_group && self.editor_groups[source_group].tabs.len() == 1 {
+            return false;
+        }
+        let Some(mut tab) = self.take_tab(source_group, source_index) else {
+            return false;
+        };
+
+        tab.is_active = true;
+        let new_index = if edge.is_after() { target_group + 1 } else { target_group };
+        let group = EditorGroup {
+            id: self.next_group_id(),
+            tabs: vec![tab],
+            active_tab: 0,
+            layout_position: EditorGroupPosition::default(),
+        };
+        self.editor_groups.insert(new_index, group);
+        self.active_group = new_index;
+
+        self.layout_config = if self.layout_config == EditorLayoutConfig::Single || self.layout_config == edge.layout() {
+            edge.layout()
+        } else {
+            // Splitting across the current direction
+            grid_for(self.editor_groups.len())
+        };
+
+        let source_group = if source_group >= new_index { source_group + 1 } else { source_group };
+        if self.editor_groups[source_group].tabs.is_empty() {
+            self.remove_group(source_group);
+        } else {
+            self.relayout();
+        }
+        true
+    }
+
+    /// Remove a tab, handing the active state to its neighbour
+    fn take_tab(&mut self, group_index: usize, tab_index: usize) -> Option<EditorTab> {
+        let group = self.editor_groups.get_mut(group_index)?;
+        if tab_index >= group.tabs.len() {
+            return None;
+        }
+        let mut tab = group.tabs.remove(tab_index);
+        tab.is_active = false;
+        if group.active_tab > tab_index {
+            group.active_tab -= 1;
+        }
+        let active_tab = group.active_tab;
+        self.set_active_tab(group_index, active_tab);
+        Some(tab)
+    }
+
+    /// Make `tab_index` (clamped) the active tab of a group
+    fn set_active_tab(&mut self, group_index: usize, tab_index: usize) {
+        if let Some(group) = self.editor_groups.get_mut(group_index) {
+            group.active_tab = tab_index.min(group.tabs.len().saturating_sub(1));
+            for (i, tab) in group.tabs.iter_mut().enumerate() {
+                tab.is_active = i == group.active_tab;
+            }
+        }
+    }
+
+    /// Close an empty group; the last remaining group is kept
+    fn remove_group(&mut self, group_index: usize) {
+        if self.editor_groups.len() <= 1 {
+            return;
+        }
+        self.editor_groups.remove(group_index);
+        if self.active_group > group_index {
+            self.active_group -= 1;
+        }
+        self.active_group = self.active_group.min(self.editor_groups.len() - 1);
+        self.relayout();
+    }
+
+    fn next_group_id(&self) -> usize {
+        self.editor_groups.iter().map(|group| group.id).max().unwrap_or(0) + 1
+    }
+
+    /// Fit the layout to the current number of groups and recompute their positions
+    fn relayout(&mut self) {
+        let count = self.editor_groups.len();
+        self.layout_config = match &self.layout_config {
+            _ if count == 1 => EditorLayoutConfig::Single,
+            EditorLayoutConfig::Single => EditorLayoutConfig::SplitHorizontal,
+            EditorLayoutConfig::Grid { .. } => grid_for(count),
+            split => split.clone(),
+        };
+
+        let layout = self.layout_config.clone();
+        let fraction = 1.0 / count as f32;
+        for (i, group) in self.editor_groups.iter_mut().enumerate() {
+            group.layout_position = match layout {
+                EditorLayoutConfig::Single => EditorGroupPosition::default(),
+                EditorLayoutConfig::SplitHorizontal => EditorGroupPosition {
+                    x: i as f32 * fraction,
+                    y: 0.0,
+                    width: fraction,
+                    height: 1.0,
+                },
+                EditorLayoutConfig::SplitVertical => EditorGroupPosition {
+                    x: 0.0,
+                    y: i as f32 * fraction,
+                    width: 1.0,
+                    height: fraction,
+                },
+                EditorLayoutConfig::Grid { rows, cols } => EditorGroupPosition {
+                    x: (i % cols) as f32 / cols as f32,
+                    y: (i / cols) as f32 / rows as f32,
+                    width: 1.0 / cols as f32,
+                    height: 1.0 / rows as f32,
+                },
+            };
+        }
+    }
+
+    /// Reopen the most recently closed tab in its old group, or the active one if that group is gone
+    pub fn reopen_closed_tab(&mut self) -> bool {
+        let Some(closed) = self.closed_tabs.pop() else {
+            return false;
+        };
+        let group_index = self
+            .editor_groups
+            .iter()
+            .position(|group| group.id == closed.group_id)
+            .unwrap_or(self.active_group.min(self.editor_groups.len().saturating_sub(1)));
+        let Some(group) = self.editor_groups.get_mut(group_index) else {
+            return false;
+        };
+
+        let mut tab = closed.tab;
+        tab.id = self.next_tab_id;
+        self.next_tab_id += 1;
+        let index = group.insertion_index(&tab, closed.tab_index);
+        group.tabs.insert(index, tab);
+        group.active_tab = index;
+        for (i, t) in group.tabs.iter_mut().enumerate() {
+            t.is_active = i == index;
+        }
+        self.active_group = group_index;
+        true
+    }
+}
+
+/// Smallest near-square grid that fits `count` groups
+fn grid_for(count: usize) -> EditorLayoutConfig {
+    let cols = (1..=count).find(|cols| cols * cols >= count).unwrap_or(1);
+    EditorLayoutConfig::Grid {
+        rows: count.div_ceil(cols),
+        cols,
+    }
+}
+
 impl Default for EditorGroupPosition {
     fn default() -> Self {
         Self {
@@ -1249,6 +2444,7 @@ impl Default for CommandPaletteState {
             selected_index: 0,
             filtered_commands: Vec::new(),
             max_results: 10,
+            pattern_prompt: None,
         }
     }
 }
@@ -1266,29 +2462,81 @@ impl AppState {
         // Create preview service and register default providers  
         let preview_service = Arc::new(PreviewService::new().with_default_providers());
         
+        let operation_log = Arc::new(OperationLog::new(OperationLog::default_path()));
+        let operation_history = OperationHistory::new(file_service.clone()).with_log(operation_log.clone());
+        let output_log = Arc::new(OutputLog::new());
+        let background_throttle = Arc::new(ThrottlePolicy::default());
+        let tag_store = TagStore::open(TagStore::default_path()).unwrap_or_else(|e| {
+            tracing::warn!("Failed to load tags: {}", e);
+            TagStore::new(TagStore::default_path())
+        });
+        
+        // Window, activity bar and sidebar layout from the last session
+        let saved_layout = use_hook(|| crate::state::persistence::load_layout_state().unwrap_or_default());
+
         Self {
-            layout_state: use_signal(LayoutState::default),
+            layout_state: use_signal(|| saved_layout.clone()),
             navigation: use_signal(|| NavigationState::new(initial_path)),
             selection: use_signal(|| SelectionState::new()),
+            directory_tabs: use_signal(DirectoryTabState::default),
+            split_view: use_signal(SplitViewState::default),
+            column_view: use_signal(ColumnViewState::default),
+            bookmarks: use_signal(|| crate::state::persistence::load_bookmarks().unwrap_or_default()),
+            keymap: use_signal(|| crate::state::persistence::load_keymap().unwrap_or_default()),
+            tags_revision: use_signal(|| 0),
             file_entries: use_signal(Vec::new),
             view_mode: use_signal(ViewMode::default),
-            active_activity_view: use_signal(ActivityBarView::default),
-            sidebar_state: use_signal(SidebarState::default),
+            active_activity_view: use_signal(|| saved_layout.activity_bar.active_view.clone()),
+            sidebar_state: use_signal(|| SidebarState {
+                is_collapsed: saved_layout.sidebar.is_collapsed,
+                width: saved_layout.sidebar.width,
+                ..SidebarState::default()
+            }),
             file_tree_state: use_signal(FileTreeState::default),
             search_state: use_signal(SearchState::default),
             operation_state: use_signal(OperationState::default),
             editor_state: use_signal(EditorState::default),
-            panel_state: use_signal(PanelState::default),
+            panel_state: use_signal(|| crate::state::persistence::load_bottom_panel().unwrap_or_default()),
             settings: use_signal(SettingsState::default),
             command_registry: use_signal(CommandRegistry::default),
+            menu_action_request: use_signal(|| None),
             cheat_sheet_visible: use_signal(|| false),
             settings_dialog_visible: use_signal(|| false),
-            file_service,
+            operation_log_visible: use_signal(|| false),
+            trash_visible: use_signal(|| false),
+            folder_compare_visible: use_signal(|| false),
+            batch_rename_files: use_signal(|| None),
+            quick_look: use_signal(QuickLookState::default),
+            open_smart_folder: use_signal(|| None),
+            file_operation_progress: use_signal(|| None),
+            file_service: file_service.clone(),
             preview_service,
+            thumbnail_service: Arc::new(
+                ThumbnailService::new(PreviewConfig::default())
+                    .with_persistent_thumbnails(PreviewCacheConfig::default_disk_cache_dir())
+                    .with_throttle_policy(background_throttle.clone()),
+            ),
             preview_data: use_signal(|| None),
+            background_throttle,
+            operation_history: Arc::new(tokio::sync::Mutex::new(operation_history)),
+            operation_log,
+            error_recovery: Arc::new(std::sync::Mutex::new(ErrorRecoveryManager::new())),
+            tag_store: Arc::new(tag_store),
+            progress_hub: Arc::new(ProgressHub::new().with_output(output_log.clone())),
+            announcer: Arc::new(Announcer::new()),
+            output_log,
+            terminal: use_signal(|| None),
         }
     }
     
+    /// File operations sharing this state's file system and undo history
+    pub fn file_operations(&self) -> FileOperationService {
+        FileOperationService::new(self.file_service.clone(), self.operation_history.clone())
+            .with_error_recovery(self.error_recovery.clone())
+            .with_retry_policy(self.settings.peek().retry_policy.effective_config())
+            .with_verify_integrity(self.settings.peek().verify_copies)
+    }
+    
     pub async fn navigate_to(&mut self, path: PathBuf) -> Result<(), Box<dyn std::error::Error>> {
         // Set loading state
         self.navigation.write().set_loading(path.clone(), true);
@@ -1312,8 +2560,7 @@ impl AppState {
                 self.selection.write().clear_selection();
                 
                 // Clear search when navigating
-                self.search_state.write().query.clear();
-                self.search_state.write().is_active = false;
+                self.cancel_search();
                 
                 Ok(())
             }
@@ -1324,6 +2571,17 @@ impl AppState {
         }
     }
     
+    /// Stop a running search and clear its query
+    fn cancel_search(&mut self) {
+        let mut search_state = self.search_state.write();
+        if let Some(running) = search_state.cancellation_token.take() {
+            running.cancel();
+        }
+        search_state.query.clear();
+        search_state.is_active = false;
+        search_state.is_searching = false;
+    }
+    
     pub async fn refresh_current_directory(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         let current_path = self.navigation.read().current_path.clone();
         self.load_directory_contents(current_path).await
@@ -1350,31 +2608,19 @@ impl AppState {
     }
     
     pub async fn navigate_back(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        let path_opt = self.navigation.write().navigate_back();
+        let path_opt = self.navigation.write().go_back();
         
         if let Some(path) = path_opt {
-            // Check if we already have contents cached
-            if self.navigation.read().get_directory_contents(&path).is_none() {
-                self.load_directory_contents(path).await?;
-            }
-            
-            // Clear selection when navigating
-            self.selection.write().clear_selection();
+            self.show_visited_directory(path).await?;
         }
         Ok(())
     }
     
     pub async fn navigate_forward(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        let path_opt = self.navigation.write().navigate_forward();
+        let path_opt = self.navigation.write().go_forward();
         
         if let Some(path) = path_opt {
-            // Check if we already have contents cached
-            if self.navigation.read().get_directory_contents(&path).is_none() {
-                self.load_directory_contents(path).await?;
-            }
-            
-            // Clear selection when navigating
-            self.selection.write().clear_selection();
+            self.show_visited_directory(path).await?;
         }
         Ok(())
     }
@@ -1383,17 +2629,28 @@ impl AppState {
         let path_opt = self.navigation.write().navigate_up();
         
         if let Some(path) = path_opt {
-            // Check if we already have contents cached
-            if self.navigation.read().get_directory_contents(&path).is_none() {
-                self.load_directory_contents(path).await?;
-            }
-            
-            // Clear selection when navigating
-            self.selection.write().clear_selection();
+            self.show_visited_directory(path).await?;
         }
         Ok(())
     }
     
+    /// List `path` after navigation moved there through the history or to the parent
+    async fn show_visited_directory(&mut self, path: PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+        // Check if we already have contents cached
+        let cached = self.navigation.read().get_directory_contents(&path).cloned();
+        match cached {
+            Some(contents) => self.file_entries.set(contents),
+            None => self.load_directory_contents(path.clone()).await?,
+        }
+        self.set_root_folder_with_persistence(path.clone()).await?;
+        
+        // Clear selection and search when navigating
+        self.selection.write().clear_selection();
+        self.cancel_search();
+        self.announcer.folder_changed(&path);
+        Ok(())
+    }
+    
     pub fn get_current_directory_contents(&self) -> Option<Vec<FileEntry>> {
         let nav = self.navigation.read();
         nav.get_directory_contents(&nav.current_path).cloned()
@@ -1426,10 +2683,86 @@ impl AppState {
     
     pub fn select_files(&mut self, paths: Vec<PathBuf>, mode: crate::state::navigation::SelectionMode) {
         self.selection.write().select_files(paths, mode);
+        self.announcer.selection_changed(self.get_selection_count());
     }
     
     pub fn clear_selection(&mut self) {
         self.selection.write().clear_selection();
+        self.announcer.selection_changed(0);
+    }
+
+    /// Current folder's entries that pass the file type and tag filters, in the file
+    /// list's sort order
+    pub fn sorted_file_entries(&self) -> Vec<FileEntry> {
+        let tree = self.file_tree_state.read();
+        let mut entries: Vec<FileEntry> = self
+            .file_entries
+            .read()
+            .iter()
+            .filter(|entry| tree.passes_filters(entry))
+            .cloned()
+            .collect();
+        tree.sort.sort(&mut entries);
+        entries
+    }
+
+    /// Plain click: select only `path` and start ranges from it
+    pub fn select_single(&mut self, path: PathBuf) {
+        let index = self.sorted_file_entries().iter().position(|entry| entry.path == path);
+        {
+            let mut selection = self.selection.write();
+            selection.select_files(vec![path], SelectionMode::Replace);
+            if let Some(index) = index {
+                selection.set_anchor(index);
+            }
+        }
+        self.announcer.selection_changed(self.get_selection_count());
+    }
+
+    /// Ctrl/Cmd-click: add or remove `path` and start ranges from it
+    pub fn toggle_selection(&mut self, path: PathBuf) {
+        let index = self.sorted_file_entries().iter().position(|entry| entry.path == path);
+        {
+            let mut selection = self.selection.write();
+            selection.toggle(path);
+            if let Some(index) = index {
+                selection.set_anchor(index);
+            }
+        }
+        self.announcer.selection_changed(self.get_selection_count());
+    }
+
+    /// Shift-click: select from the anchor to `path` in sort order
+    pub fn select_range_to(&mut self, path: PathBuf) {
+        let entries = self.sorted_file_entries();
+        let Some(target) = entries.iter().position(|entry| entry.path == path) else {
+            return;
+        };
+        {
+            let mut selection = self.selection.write();
+            let anchor = selection.anchor.unwrap_or(target);
+            selection.select_range(anchor, target, &entries);
+        }
+        self.announcer.selection_changed(self.get_selection_count());
+    }
+
+    /// Select the visible entries whose names match `pattern`, or with `invert`
+    /// those that don't; returns how many matched
+    pub fn select_matching(&mut self, pattern: &NamePattern, invert: bool, mode: SelectionMode) -> usize {
+        let entries = self.sorted_file_entries();
+        let count = self.selection.write().select_matching(pattern, invert, mode, &entries);
+        self.announcer.selection_changed(self.get_selection_count());
+        count
+    }
+
+    /// Shift+arrow: move the end of the range by `delta` entries
+    ///
+    /// Returns the path the range now ends at.
+    pub fn extend_selection(&mut self, delta: isize) -> Option<PathBuf> {
+        let entries = self.sorted_file_entries();
+        let target = self.selection.write().extend_range(delta, &entries)?;
+        self.announcer.selection_changed(self.get_selection_count());
+        Some(entries[target].path.clone())
     }
     
     pub fn is_selected(&self, path: &PathBuf) -> bool {
@@ -1466,6 +2799,119 @@ impl AppState {
         self.search_state.read().clone()
     }
     
+    /// Search file names under the file tree root, or the current directory
+    /// without one, and stream matches into `search_state`
+    ///
+    /// Call it on every keystroke: each call cancels the previous search, and the
+    /// walk only starts once the query has been stable for `SEARCH_DEBOUNCE`.
+    pub async fn search_files(&mut self, query: String) {
+        let Some((token, root)) = self.begin_search(&query).await else {
+            return;
+        };
+        let settings = self.settings.read().clone();
+        let search = NameSearch::new(root, &query)
+            .with_hidden(settings.show_hidden_files)
+            .with_max_results(settings.search_max_results);
+
+        let (entry_tx, mut entry_rx) = tokio::sync::mpsc::unbounded_channel();
+        let walk_token = token.clone();
+        let walk = tokio::task::spawn_blocking(move || {
+            search_names(&search, &walk_token, |entry| {
+                let _ = entry_tx.send(entry);
+            })
+        });
+
+        // Batching keeps a large tree from re-rendering once per match
+        let mut batch = Vec::with_capacity(SEARCH_BATCH_SIZE);
+        while entry_rx.recv_many(&mut batch, SEARCH_BATCH_SIZE).await > 0 {
+            if token.is_cancelled() {
+                batch.clear();
+                continue;
+            }
+            self.search_state.write().results.append(&mut batch);
+        }
+
+        let outcome = walk.await.unwrap_or_else(|e| {
+            tracing::warn!("File search task failed: {}", e);
+            Ok(0)
+        });
+        self.finish_search(outcome);
+    }
+
+    /// Search inside the text files under the file tree root and stream each
+    /// file with a hit into `search_state`, with its matching lines in
+    /// `line_matches`
+    ///
+    /// Debounced and cancelled like `search_files`.
+    pub async fn search_file_contents(&mut self, query: String) {
+        let Some((token, root)) = self.begin_search(&query).await else {
+            return;
+        };
+        let settings = self.settings.read().clone();
+        let search = ContentSearch::new(root, &query)
+            .with_hidden(settings.show_hidden_files)
+            .with_max_results(settings.search_max_results)
+            .with_max_file_size(settings.content_search_max_file_size);
+
+        let mut search_state = self.search_state;
+        let match_token = token.clone();
+        let outcome = search_file_contents(search, &token, move |found| {
+            if match_token.is_cancelled() {
+                return;
+            }
+            let mut state = search_state.write();
+            state.line_matches.insert(found.entry.path.clone(), found.lines);
+            state.results.push(found.entry);
+        })
+        .await;
+        self.finish_search(outcome);
+    }
+
+    /// Cancel the running search, reset the results for `query` and wait out
+    /// the debounce
+    ///
+    /// Returns the new search's token and root, or `None` when the query is
+    /// empty or a newer call replaced this one while waiting.
+    async fn begin_search(&mut self, query: &str) -> Option<(CancellationToken, PathBuf)> {
+        let token = CancellationToken::new();
+        {
+            let mut search_state = self.search_state.write();
+            if let Some(previous) = search_state.cancellation_token.replace(token.clone()) {
+                previous.cancel();
+            }
+            search_state.query = query.to_string();
+            search_state.results.clear();
+            search_state.line_matches.clear();
+            search_state.is_active = !query.trim().is_empty();
+            search_state.is_searching = search_state.is_active;
+        }
+        if query.trim().is_empty() {
+            return None;
+        }
+
+        tokio::time::sleep(SEARCH_DEBOUNCE).await;
+        if token.is_cancelled() {
+            return None;
+        }
+        let root = self
+            .get_file_tree_root()
+            .unwrap_or_else(|| self.navigation.read().current_path.clone());
+        Some((token, root))
+    }
+
+    /// Record the end of a search unless a newer one replaced it
+    fn finish_search(&mut self, outcome: Result<usize, SmartFolderError>) {
+        match outcome {
+            // A newer search replaced this one and owns the state now
+            Err(SmartFolderError::Cancelled) => return,
+            Err(e) => tracing::warn!("File search failed: {}", e),
+            Ok(_) => {}
+        }
+        let mut search_state = self.search_state.write();
+        search_state.is_searching = false;
+        search_state.cancellation_token = None;
+    }
+    
     /// Get operation state
     pub fn get_operation_state(&self) -> OperationState {
         self.operation_state.read().clone()
@@ -1589,6 +3035,7 @@ impl AppState {
     pub async fn set_file_tree_root(&mut self, path: PathBuf) -> Result<(), Box<dyn std::error::Error>> {
         // Update file tree state
         self.file_tree_state.write().set_root_directory(path.clone());
+        self.column_view.write().set_root(path.clone());
         
         // Load the root directory contents
         self.load_file_tree_directory(path).await
@@ -1661,6 +3108,44 @@ impl AppState {
         self.file_tree_state.read().get_directory_children(path).cloned()
     }
     
+    /// Loaded children of `path` that pass the file tree filters, in sort order
+    pub fn get_file_tree_visible_children(&self, path: &PathBuf) -> Option<Vec<FileEntry>> {
+        let tree = self.file_tree_state.read();
+        tree.get_directory_children(path)?;
+        Some(tree.visible_children(path))
+    }
+    
+    /// Why the loaded folder at `path` lists nothing, if it doesn't
+    pub fn file_tree_empty_state(&self, path: &PathBuf) -> Option<EmptyTreeState> {
+        let tree = self.file_tree_state.read();
+        let children = tree.get_directory_children(path)?;
+        if children.is_empty() {
+            Some(EmptyTreeState::EmptyFolder)
+        } else if !children.iter().any(|child| tree.passes_filters(child)) {
+            Some(EmptyTreeState::AllFiltered)
+        } else {
+            None
+        }
+    }
+    
+    /// Drop the file type and tag filters
+    pub fn clear_file_tree_filters(&mut self) {
+        self.set_file_type_filter(FileTypeFilter::default());
+        self.set_tag_filter(Vec::new());
+    }
+    
+    /// Forget a recent folder, e.g. one that no longer exists
+    pub fn forget_recent_folder(&mut self, path: &Path) {
+        if self.settings.write().recent_folders.remove(path) {
+            self.save_settings_to_persistence();
+        }
+    }
+    
+    pub fn clear_recent_folders(&mut self) {
+        self.settings.write().recent_folders.clear();
+        self.save_settings_to_persistence();
+    }
+    
     /// Check if a directory is expanded in the file tree
     pub fn is_file_tree_directory_expanded(&self, path: &PathBuf) -> bool {
         self.file_tree_state.read().is_expanded(path)
@@ -1692,15 +3177,16 @@ impl AppState {
     pub async fn set_root_folder_with_persistence(&mut self, path: PathBuf) -> Result<(), Box<dyn std::error::Error>> {
         // Set the file tree root
         self.set_file_tree_root(path.clone()).await?;
+        self.settings.write().recent_folders.record(path.clone());
         
         // Update settings if remember_last_directory is enabled
         if self.settings.read().remember_last_directory {
             self.settings.write().last_opened_folder = Some(path.clone());
-            
-            // Persist the updated settings
-            self.save_settings_to_persistence();
         }
         
+        // Persist the updated settings
+        self.save_settings_to_persistence();
+        
         Ok(())
     }
     
@@ -1739,22 +3225,378 @@ impl AppState {
     pub async fn handle_folder_change(&mut self, path: PathBuf) -> Result<(), Box<dyn std::error::Error>> {
         // Update the file tree state
         self.set_file_tree_root(path.clone()).await?;
+        self.settings.write().recent_folders.record(path.clone());
         
         // Update settings with the new folder if persistence is enabled
         if self.settings.read().remember_last_directory {
             self.settings.write().last_opened_folder = Some(path.clone());
-            self.save_settings_to_persistence();
         }
+        self.save_settings_to_persistence();
         
         // Clear any existing navigation history since we're changing contexts
         self.navigation.write().clear_history();
         
         // Navigate to the new root folder in the navigation system
-        self.navigate_to(path).await?;
+        self.navigate_to(path.clone()).await?;
         
+        self.announcer.folder_changed(&path);
+        Ok(())
+    }
+
+    /// Run a double-click `action` on `path`
+    pub async fn activate_entry(&mut self, path: PathBuf, action: DoubleClickAction) -> Result<(), Box<dyn std::error::Error>> {
+        match action {
+            DoubleClickAction::EnterFolder => self.handle_folder_change(path).await,
+            DoubleClickAction::OpenDefault => Ok(crate::services::open_with::open_default(&path)?),
+            DoubleClickAction::Preview => {
+                // Step through the files next to it, as with Space
+                let mut items: Vec<PathBuf> = self.file_entries.read()
+                    .iter()
+                    .filter(|entry| !entry.is_directory)
+                    .map(|entry| entry.path.clone())
+                    .collect();
+                if !items.contains(&path) {
+                    items = vec![path.clone()];
+                }
+                self.quick_look.write().open(items, &[path]);
+                Ok(())
+            }
+        }
+    }
+    
+    // Directory tab methods
+    
+    /// Copy the shown navigation, selection, sort and filter into the active directory tab
+    fn store_active_directory_tab(&mut self, scroll_top: f64) {
+        let navigation = self.navigation.read().clone();
+        let selection = self.selection.read().clone();
+        let (sort, file_type_filter) = {
+            let tree = self.file_tree_state.read();
+            (tree.sort, tree.file_type_filter.clone())
+        };
+        let mut tabs = self.directory_tabs.write();
+        let tab = tabs.active_tab_mut();
+        tab.navigation = navigation;
+        tab.selection = selection;
+        tab.scroll_top = scroll_top;
+        tab.sort = sort;
+        tab.file_type_filter = file_type_filter;
+    }
+    
+    /// Show the active directory tab in the file list and return its scroll offset
+    ///
+    /// The tab's sort and filter only apply to the listing; the defaults in
+    /// the settings are left alone.
+    async fn show_active_directory_tab(&mut self) -> Result<f64, Box<dyn std::error::Error>> {
+        let tab = self.directory_tabs.read().active_tab().clone();
+        let path = tab.navigation.current_path.clone();
+        self.navigation.set(tab.navigation);
+        self.selection.set(tab.selection);
+        self.cancel_search();
+        {
+            let mut file_tree_state = self.file_tree_state.write();
+            file_tree_state.sort = tab.sort;
+            file_tree_state.file_type_filter = tab.file_type_filter;
+        }
+        self.set_file_tree_root(path.clone()).await?;
+        let cached = self.navigation.read().get_directory_contents(&path).cloned();
+        match cached {
+            Some(contents) => self.file_entries.set(contents),
+            None => self.load_directory_contents(path.clone()).await?,
+        }
+        self.announcer.folder_changed(&path);
+        Ok(tab.scroll_top)
+    }
+    
+    /// Open `path` in a new directory tab after the active one and show it
+    ///
+    /// `scroll_top` is where the file list of the tab being left was scrolled
+    /// to; the new tab's offset is returned.
+    pub async fn open_directory_tab(&mut self, path: PathBuf, scroll_top: f64) -> Result<f64, Box<dyn std::error::Error>> {
+        self.store_active_directory_tab(scroll_top);
+        self.directory_tabs.write().open_tab(path);
+        self.show_active_directory_tab().await
+    }
+    
+    /// Close directory tab `index`; the last tab stays open
+    ///
+    /// Returns the scroll offset to restore when the shown tab changed.
+    pub async fn close_directory_tab(&mut self, index: usize, scroll_top: f64) -> Result<Option<f64>, Box<dyn std::error::Error>> {
+        let was_active = self.directory_tabs.read().active == index;
+        self.store_active_directory_tab(scroll_top);
+        if !self.directory_tabs.write().close_tab(index) {
+            return Ok(None);
+        }
+        if was_active {
+            self.show_active_directory_tab().await.map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+    
+    /// Show directory tab `index`; returns its scroll offset unless it was already shown
+    pub async fn switch_directory_tab(&mut self, index: usize, scroll_top: f64) -> Result<Option<f64>, Box<dyn std::error::Error>> {
+        {
+            let tabs = self.directory_tabs.read();
+            if index == tabs.active || index >= tabs.tabs.len() {
+                return Ok(None);
+            }
+        }
+        self.store_active_directory_tab(scroll_top);
+        self.directory_tabs.write().switch_to(index);
+        self.show_active_directory_tab().await.map(Some)
+    }
+    
+    /// Snapshot of the directory tabs with the shown tab's live state
+    pub fn directory_tab_session(&self) -> DirectoryTabSession {
+        let mut tabs = self.directory_tabs.read().clone();
+        {
+            let tab = tabs.active_tab_mut();
+            tab.navigation = self.navigation.read().clone();
+            tab.selection = self.selection.read().clone();
+            let file_tree_state = self.file_tree_state.read();
+            tab.sort = file_tree_state.sort;
+            tab.file_type_filter = file_tree_state.file_type_filter.clone();
+        }
+        DirectoryTabSession::capture(&tabs)
+    }
+    
+    /// Reopen the directory tabs saved by the last run
+    ///
+    /// Returns the active tab's scroll offset, or `None` when there was
+    /// nothing to restore and the last opened folder should be used instead.
+    pub async fn restore_directory_tabs(&mut self) -> Option<f64> {
+        let tabs = crate::state::persistence::load_directory_tabs()?.restore(|path| path.is_dir())?;
+        self.directory_tabs.set(tabs);
+        match self.show_active_directory_tab().await {
+            Ok(scroll_top) => Some(scroll_top),
+            Err(e) => {
+                tracing::warn!("Failed to show restored directory tab: {}", e);
+                None
+            }
+        }
+    }
+    
+    // Split view methods
+    
+    /// Show two folder panes side by side, or go back to the single file list
+    ///
+    /// Turning it on opens the primary pane on the shown folder; the other
+    /// pane keeps the folder it showed last.
+    pub async fn toggle_split_view(&mut self) {
+        if self.split_view.read().enabled {
+            self.split_view.write().enabled = false;
+            return;
+        }
+        self.split_view.write().enabled = true;
+        let current_path = self.get_current_path();
+        if let Err(e) = self.navigate_split_pane(PaneSide::Primary, current_path).await {
+            tracing::warn!("Failed to open the shown folder in split view: {}", e);
+        }
+        self.refresh_split_panes().await;
+    }
+    
+    /// Show `path` in one split pane, clearing that pane's selection
+    pub async fn navigate_split_pane(&mut self, side: PaneSide, path: PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+        let contents = self.file_service.list_directory(&path).await?;
+        let mut split_view = self.split_view.write();
+        split_view.active = side;
+        let pane = split_view.pane_mut(side);
+        pane.navigation.navigate_to(path.clone())?;
+        pane.navigation.set_directory_contents(path, contents);
+        pane.selection.clear_selection();
+        Ok(())
+    }
+    
+    /// Go back to the folder a split pane showed before
+    pub async fn split_pane_back(&mut self, side: PaneSide) -> Result<(), Box<dyn std::error::Error>> {
+        let previous = self.split_view.write().pane_mut(side).navigation.go_back();
+        match previous {
+            Some(path) => self.load_split_pane(side, path).await,
+            None => Ok(()),
+        }
+    }
+    
+    /// Show the parent of a split pane's folder
+    pub async fn split_pane_up(&mut self, side: PaneSide) -> Result<(), Box<dyn std::error::Error>> {
+        let parent = self.split_view.write().pane_mut(side).navigation.navigate_up();
+        match parent {
+            Some(path) => self.load_split_pane(side, path).await,
+            None => Ok(()),
+        }
+    }
+    
+    /// Load the listing of the folder a split pane shows
+    ///
+    /// Selected items that are no longer listed, e.g. moved to the other
+    /// pane, are deselected.
+    async fn load_split_pane(&mut self, side: PaneSide, path: PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+        let contents = self.file_service.list_directory(&path).await?;
+        let mut split_view = self.split_view.write();
+        let pane = split_view.pane_mut(side);
+        let still_listed: Vec<PathBuf> = pane
+            .selection
+            .get_selected_paths()
+            .into_iter()
+            .filter(|selected| contents.iter().any(|entry| entry.path == *selected))
+            .collect();
+        pane.selection.clear_selection();
+        if !still_listed.is_empty() {
+            pane.selection.select_files(still_listed, SelectionMode::Replace);
+        }
+        pane.navigation.set_directory_contents(path, contents);
+        Ok(())
+    }
+    
+    /// Reload both split panes, e.g. after files were moved between them
+    pub async fn refresh_split_panes(&mut self) {
+        for side in PaneSide::ALL {
+            let path = self.split_view.read().pane(side).navigation.current_path.clone();
+            if let Err(e) = self.load_split_pane(side, path).await {
+                tracing::warn!("Failed to load split pane {:?}: {}", side, e);
+            }
+        }
+    }
+    
+    /// Snapshot of the split layout for saving
+    pub fn split_view_session(&self) -> SplitViewSession {
+        SplitViewSession::capture(&self.split_view.read())
+    }
+    
+    /// Bring back the split layout saved by the last run
+    pub async fn restore_split_view(&mut self) {
+        let Some(split_view) = crate::state::persistence::load_split_view().and_then(|session| session.restore(|path| path.is_dir())) else {
+            return;
+        };
+        let enabled = split_view.enabled;
+        self.split_view.set(split_view);
+        if enabled {
+            self.refresh_split_panes().await;
+        }
+    }
+    
+    // Column view methods
+    
+    /// Folders shown as columns, left to right, with their filtered and sorted entries
+    pub fn column_view_listings(&self) -> Vec<(PathBuf, Vec<FileEntry>)> {
+        let columns = self.column_view.read().columns();
+        let tree = self.file_tree_state.read();
+        columns
+            .into_iter()
+            .map(|folder| {
+                let entries = tree.visible_children(&folder);
+                (folder, entries)
+            })
+            .collect()
+    }
+    
+    /// Select `entry` in column `column`, loading a folder's contents for the column it opens
+    ///
+    /// The file list selection follows the column view's.
+    pub async fn select_column_entry(&mut self, column: usize, entry: &FileEntry) -> Result<(), Box<dyn std::error::Error>> {
+        if !self.column_view.write().select(column, entry.path.clone(), entry.is_directory) {
+            return Ok(());
+        }
+        self.select_single(entry.path.clone());
+        self.set_file_tree_selection(Some(entry.path.clone()));
+        if entry.is_directory && self.file_tree_state.read().get_directory_children(&entry.path).is_none() {
+            self.load_file_tree_directory(entry.path.clone()).await?;
+        }
         Ok(())
     }
     
+    /// Move the column view selection for an arrow key and return the newly selected entry
+    pub async fn step_column_view(&mut self, step: ColumnStep) -> Result<Option<FileEntry>, Box<dyn std::error::Error>> {
+        let (column, current, columns) = {
+            let view = self.column_view.read();
+            (view.focused_column(), view.path_stack().last().cloned(), view.columns())
+        };
+        let target = match step {
+            ColumnStep::Up | ColumnStep::Down => {
+                let Some(folder) = columns.get(column) else {
+                    return Ok(None);
+                };
+                let entries = self.file_tree_state.read().visible_children(folder);
+                let delta = if step == ColumnStep::Up { -1 } else { 1 };
+                step_selection(&entries, current.as_ref().map(|selection| selection.path.as_path()), delta)
+                    .cloned()
+                    .map(|entry| (column, entry))
+            }
+            ColumnStep::Right => match current {
+                Some(selection) if selection.is_directory => self
+                    .file_tree_state
+                    .read()
+                    .visible_children(&selection.path)
+                    .into_iter()
+                    .next()
+                    .map(|entry| (column + 1, entry)),
+                _ => None,
+            },
+            ColumnStep::Left => {
+                if self.column_view.read().path_stack().len() < 2 {
+                    return Ok(None);
+                }
+                self.column_view.write().pop();
+                let Some(folder) = self.column_view.read().path_stack().last().map(|selection| selection.path.clone()) else {
+                    return Ok(None);
+                };
+                self.select_single(folder.clone());
+                self.set_file_tree_selection(Some(folder.clone()));
+                return Ok(self.column_entry(&folder));
+            }
+        };
+        let Some((column, entry)) = target else {
+            return Ok(None);
+        };
+        self.select_column_entry(column, &entry).await?;
+        Ok(Some(entry))
+    }
+    
+    /// Listed entry for `path` in its parent's column
+    fn column_entry(&self, path: &Path) -> Option<FileEntry> {
+        let parent = path.parent()?.to_path_buf();
+        let tree = self.file_tree_state.read();
+        tree.get_directory_children(&parent)?.iter().find(|entry| entry.path == path).cloned()
+    }
+    
+    /// Bookmark the shown folder; false if it already is one
+    pub fn bookmark_current_folder(&mut self) -> bool {
+        let path = self.get_current_path();
+        self.update_bookmarks(|bookmarks| bookmarks.add(path))
+    }
+    
+    pub fn remove_bookmark(&mut self, path: &Path) -> bool {
+        self.update_bookmarks(|bookmarks| bookmarks.remove(path))
+    }
+    
+    /// Move the bookmark at `from` to index `to`
+    pub fn move_bookmark(&mut self, from: usize, to: usize) -> bool {
+        self.update_bookmarks(|bookmarks| bookmarks.move_bookmark(from, to))
+    }
+    
+    /// Point a stale bookmark at the folder's new location
+    pub fn relocate_bookmark(&mut self, old: &Path, new: PathBuf) -> bool {
+        self.update_bookmarks(|bookmarks| bookmarks.relocate(old, new))
+    }
+    
+    /// Apply `change` and save the bookmarks if it changed anything
+    fn update_bookmarks(&mut self, change: impl FnOnce(&mut BookmarksState) -> bool) -> bool {
+        let changed = change(&mut self.bookmarks.write());
+        if changed {
+            crate::state::persistence::save_bookmarks(&self.bookmarks.peek());
+        }
+        changed
+    }
+    
+    /// Apply `change` to the keymap and save it if it changed anything
+    pub fn update_keymap(&mut self, change: impl FnOnce(&mut KeymapState)) {
+        let before = self.keymap.peek().clone();
+        change(&mut self.keymap.write());
+        if *self.keymap.peek() != before {
+            crate::state::persistence::save_keymap(&self.keymap.peek());
+        }
+    }
+    
     /// Save current settings to persistence layer
     fn save_settings_to_persistence(&self) {
         let settings = self.settings.read().clone();
@@ -1766,9 +3608,149 @@ impl AppState {
     /// Load settings from persistence layer
     pub fn load_settings_from_persistence(&mut self) {
         let loaded_settings = crate::state::persistence::load_settings();
+        {
+            let mut file_tree_state = self.file_tree_state.write();
+            file_tree_state.file_type_filter = loaded_settings.file_type_filter.clone();
+            file_tree_state.sort = loaded_settings.file_sort;
+        }
         self.settings.set(loaded_settings);
     }
     
+    /// Filter the file tree to `filter` and remember it across sessions
+    pub fn set_file_type_filter(&mut self, filter: FileTypeFilter) {
+        self.file_tree_state.write().file_type_filter = filter.clone();
+        self.settings.write().file_type_filter = filter;
+        crate::state::persistence::save_settings_debounced(self.settings.peek().clone());
+    }
+    
+    /// List only files carrying every one of `tags`; no tags lists everything
+    pub fn set_tag_filter(&mut self, tags: Vec<String>) {
+        let filter = (!tags.is_empty()).then(|| self.tag_store.filter(&tags));
+        self.file_tree_state.write().tag_filter = filter;
+    }
+    
+    /// Tag `path` with `tag`
+    pub fn add_tag(&mut self, path: &Path, tag: &str) {
+        self.apply_tag_change(self.tag_store.add_tag(path, tag));
+    }
+    
+    /// Take `tag` off `path`
+    pub fn remove_tag(&mut self, path: &Path, tag: &str) {
+        self.apply_tag_change(self.tag_store.remove_tag(path, tag));
+    }
+    
+    /// Rename `old` to `new` wherever it is used, keeping an active tag filter on it
+    pub fn rename_tag(&mut self, old: &str, new: &str) {
+        let renamed = self.tag_store.rename_tag(old, new);
+        if matches!(renamed, Ok(true)) {
+            let mut tree = self.file_tree_state.write();
+            if let Some(filter) = tree.tag_filter.as_mut() {
+                for tag in filter.tags.iter_mut().filter(|tag| tag.eq_ignore_ascii_case(old)) {
+                    *tag = new.trim().to_string();
+                }
+            }
+        }
+        self.apply_tag_change(renamed);
+    }
+    
+    /// Take `tag` off every file, dropping it from an active tag filter
+    pub fn delete_tag(&mut self, tag: &str) {
+        let deleted = self.tag_store.delete_tag(tag);
+        if matches!(deleted, Ok(true)) {
+            let active = self.file_tree_state.peek().tag_filter.as_ref().map(|filter| filter.tags.clone());
+            if let Some(mut tags) = active {
+                tags.retain(|t| !t.eq_ignore_ascii_case(tag));
+                self.set_tag_filter(tags);
+            }
+        }
+        self.apply_tag_change(deleted);
+    }
+    
+    /// Show `tag` in `color`, or in the default color for `None`
+    pub fn set_tag_color(&mut self, tag: &str, color: Option<&str>) {
+        self.apply_tag_change(self.tag_store.set_tag_color(tag, color));
+    }
+    
+    /// Carry the tags of a renamed file or folder over to its new path
+    pub fn follow_rename(&mut self, old_path: &Path, new_path: &Path) {
+        self.apply_tag_change(self.tag_store.rename_path(old_path, new_path));
+    }
+    
+    /// Refresh tag views and the tag filter after the store changed
+    fn apply_tag_change(&mut self, result: TagStoreResult<bool>) {
+        match result {
+            Ok(true) => {
+                *self.tags_revision.write() += 1;
+                let active = self.file_tree_state.peek().tag_filter.as_ref().map(|filter| filter.tags.clone());
+                if let Some(tags) = active {
+                    self.set_tag_filter(tags);
+                }
+            }
+            Ok(false) => {}
+            Err(e) => {
+                tracing::warn!("Failed to save tags: {}", e);
+                self.announcer.assertive(e.to_string());
+            }
+        }
+    }
+    
+    /// Order the file listing by `sort` and remember it across sessions
+    pub fn set_file_sort(&mut self, sort: FileSort) {
+        self.file_tree_state.write().sort = sort;
+        self.settings.write().file_sort = sort;
+        crate::state::persistence::save_settings_debounced(self.settings.peek().clone());
+    }
+    
+    /// Offer `app` first the next time Open With is used on a file like `path`
+    pub fn remember_open_with(&mut self, path: &Path, app: OpenWithApp) {
+        self.settings.write().open_with.remember(path, app);
+        crate::state::persistence::save_settings_debounced(self.settings.peek().clone());
+    }
+    
+    /// Give the listed folders their recursive size while sorting by size
+    ///
+    /// Cached sizes apply at once; the rest are walked in the background and
+    /// filled in together. Entries are only written when a size changed, so
+    /// an effect calling this whenever the listing changes settles.
+    pub fn size_listed_folders(&mut self) {
+        if self.file_tree_state.read().sort.key != SortKey::Size {
+            return;
+        }
+        let mut sized = Vec::new();
+        let mut missing = Vec::new();
+        for entry in self.file_entries.read().iter().filter(|entry| entry.is_directory) {
+            match cached_directory_size(&entry.path) {
+                Some(size) if size != entry.size => sized.push((entry.path.clone(), size)),
+                Some(_) => {}
+                None => missing.push(entry.path.clone()),
+            }
+        }
+        // Writing re-runs the caller's effect, which walks the missing ones then
+        if !sized.is_empty() {
+            apply_folder_sizes(self.file_entries, sized);
+            return;
+        }
+        if missing.is_empty() {
+            return;
+        }
+        
+        let file_entries = self.file_entries;
+        spawn(async move {
+            let mut sized = Vec::new();
+            for folder in missing {
+                // Stop walking once the listing moved on
+                if !file_entries.peek().iter().any(|entry| entry.path == folder) {
+                    continue;
+                }
+                match compute_directory_size(&folder, CancellationToken::new()).await {
+                    Ok(size) => sized.push((folder, size)),
+                    Err(e) => tracing::warn!("Failed to size {}: {}", folder.display(), e),
+                }
+            }
+            apply_folder_sizes(file_entries, sized);
+        });
+    }
+    
     /// Check if folder persistence is enabled
     pub fn is_folder_persistence_enabled(&self) -> bool {
         self.settings.read().remember_last_directory
@@ -1852,6 +3834,23 @@ impl CommandRegistry {
         self.commands.insert(command.id.clone(), command);
     }
     
+    /// Replace the user's shell commands with `custom_commands`
+    pub fn set_custom_commands(&mut self, custom_commands: &[CustomCommand]) {
+        self.commands.retain(|id, _| !id.starts_with(CUSTOM_COMMAND_PREFIX));
+        self.categories.remove(CUSTOM_COMMAND_CATEGORY);
+        for (index, custom) in custom_commands.iter().enumerate() {
+            self.register_command(Command {
+                id: format!("{}{}", CUSTOM_COMMAND_PREFIX, index),
+                title: format!("Run: {}", custom.name),
+                description: Some(custom.command.clone()),
+                category: CUSTOM_COMMAND_CATEGORY.to_string(),
+                shortcuts: vec![],
+                enabled: true,
+                handler: CommandHandler::Shell(custom.clone()),
+            });
+        }
+    }
+    
     /// Get a command by its ID
     pub fn get_command(&self, command_id: &str) -> Option<&Command> {
         self.commands.get(command_id)
@@ -1909,11 +3908,26 @@ impl CommandRegistry {
     /// Toggle command palette visibility
     pub fn toggle_command_palette(&mut self) {
         self.palette_state.is_visible = !self.palette_state.is_visible;
+        self.palette_state.pattern_prompt = None;
         if self.palette_state.is_visible {
             self.update_palette_search(String::new());
         }
     }
     
+    /// Hide the palette, dropping any pattern prompt
+    pub fn close_command_palette(&mut self) {
+        self.palette_state.is_visible = false;
+        self.palette_state.pattern_prompt = None;
+    }
+    
+    /// Keep the palette open to ask for the pattern `command` selects by
+    pub fn prompt_for_pattern(&mut self, command: SelectionCommand) {
+        self.palette_state.is_visible = true;
+        self.palette_state.search_query.clear();
+        self.palette_state.selected_index = 0;
+        self.palette_state.pattern_prompt = Some(command);
+    }
+    
     /// Select next command in palette
     pub fn select_next_command(&mut self) {
         if !self.palette_state.filtered_commands.is_empty() {
@@ -1974,6 +3988,16 @@ impl CommandRegistry {
             handler: CommandHandler::Editor(EditorCommand::CloseTab),
         });
         
+        self.register_command(Command {
+            id: "editor.reopen_closed_tab".to_string(),
+            title: "Reopen Closed Tab".to_string(),
+            description: Some("Reopen the most recently closed tab".to_string()),
+            category: "Editor".to_string(),
+            shortcuts: vec!["Ctrl+Shift+T".to_string(), "Cmd+Shift+T".to_string()],
+            enabled: true,
+            handler: CommandHandler::Editor(EditorCommand::ReopenClosedTab),
+        });
+        
         self.register_command(Command {
             id: "editor.toggle_tab_pin".to_string(),
             title: "Toggle Tab Pin".to_string(),
@@ -2026,6 +4050,88 @@ impl CommandRegistry {
             handler: CommandHandler::File(FileCommand::RenameFile),
         });
         
+        for (command, id, title, description, shortcuts) in [
+            (FileCommand::CopyTo, "file.copy_to", "Copy To…", "Copy the selection to another folder", vec!["Ctrl+Shift+C", "Cmd+Shift+C"]),
+            (FileCommand::MoveTo, "file.move_to", "Move To…", "Move the selection to another folder", vec!["Ctrl+Shift+M", "Cmd+Shift+M"]),
+            (FileCommand::Duplicate, "file.duplicate", "Duplicate", "Make a copy of the selection next to it", vec!["Cmd+D"]),
+            (FileCommand::DeleteFile, "file.delete", "Move to Trash", "Move the selection to the trash", vec!["Delete"]),
+            (FileCommand::DeletePermanently, "file.delete_permanently", "Delete Permanently", "Delete the selection without using the trash", vec!["Shift+Delete"]),
+            (FileCommand::Organize(OrganizeRule::by_month()), "file.organize_by_date", "Organize by Date…", "Sort the selected files into year and month folders", vec![]),
+            (FileCommand::Organize(OrganizeRule::ByType), "file.organize_by_type", "Organize by Type…", "Sort the selected files into a folder per file type", vec![]),
+        ] {
+            self.register_command(Command {
+                id: id.to_string(),
+                title: title.to_string(),
+                description: Some(description.to_string()),
+                category: "File".to_string(),
+                shortcuts: shortcuts.into_iter().map(str::to_string).collect(),
+                enabled: true,
+                handler: CommandHandler::File(command),
+            });
+        }
+        
+        for (mode, id, title, description) in [
+            (PathCopyMode::AbsolutePath, "file.copy_path", "Copy Path", "Copy the full paths of the selection"),
+            (PathCopyMode::RelativePath, "file.copy_relative_path", "Copy Relative Path", "Copy the selection's paths relative to the open folder"),
+            (PathCopyMode::Name, "file.copy_name", "Copy Name", "Copy the names of the selection"),
+            (PathCopyMode::NameWithoutExtension, "file.copy_name_without_extension", "Copy Name Without Extension", "Copy the selection's names without their extensions"),
+            (PathCopyMode::ParentDirectory, "file.copy_parent_path", "Copy Parent Folder Path", "Copy the paths of the folders holding the selection"),
+        ] {
+            self.register_command(Command {
+                id: id.to_string(),
+                title: title.to_string(),
+                description: Some(description.to_string()),
+                category: "File".to_string(),
+                shortcuts: Vec::new(),
+                enabled: true,
+                handler: CommandHandler::File(FileCommand::CopyPaths(mode, PathStyle::native())),
+            });
+        }
+        // Forward slashes only differ from the native separator on Windows
+        if PathStyle::native() != PathStyle::Posix {
+            for (mode, id, title, description) in [
+                (PathCopyMode::AbsolutePath, "file.copy_posix_path", "Copy Path with Forward Slashes", "Copy the full paths of the selection using / as the separator"),
+                (PathCopyMode::RelativePath, "file.copy_posix_relative_path", "Copy Relative Path with Forward Slashes", "Copy the selection's relative paths using / as the separator"),
+                (PathCopyMode::ParentDirectory, "file.copy_posix_parent_path", "Copy Parent Folder Path with Forward Slashes", "Copy the paths of the folders holding the selection using / as the separator"),
+            ] {
+                self.register_command(Command {
+                    id: id.to_string(),
+                    title: title.to_string(),
+                    description: Some(description.to_string()),
+                    category: "File".to_string(),
+                    shortcuts: Vec::new(),
+                    enabled: true,
+                    handler: CommandHandler::File(FileCommand::CopyPaths(mode, PathStyle::Posix)),
+                });
+            }
+        }
+        self.register_command(Command {
+            id: "file.copy_as_file_reference".to_string(),
+            title: "Copy as File Reference".to_string(),
+            description: Some("Put the selected files on the clipboard for pasting into other apps".to_string()),
+            category: "File".to_string(),
+            shortcuts: Vec::new(),
+            enabled: true,
+            handler: CommandHandler::File(FileCommand::CopyAsFileReference),
+        });
+        
+        // Selection commands
+        for (id, title, description, invert, mode) in [
+            ("selection.select_matching", "Select Files Matching…", "Select the files whose names match a glob like *.jpg or a /regex/", false, SelectionMode::Replace),
+            ("selection.select_not_matching", "Select Files Not Matching…", "Select the files whose names don't match a glob or /regex/", true, SelectionMode::Replace),
+            ("selection.add_matching", "Add Files Matching… to Selection", "Add the files whose names match a glob or /regex/ to the selection", false, SelectionMode::Add),
+        ] {
+            self.register_command(Command {
+                id: id.to_string(),
+                title: title.to_string(),
+                description: Some(description.to_string()),
+                category: "Selection".to_string(),
+                shortcuts: Vec::new(),
+                enabled: true,
+                handler: CommandHandler::Selection(SelectionCommand::SelectMatching { invert, mode }),
+            });
+        }
+        
         // View commands
         self.register_command(Command {
             id: "view.toggle_sidebar".to_string(),
@@ -2036,6 +4142,36 @@ impl CommandRegistry {
             enabled: true,
             handler: CommandHandler::View(ViewCommand::ToggleSidebar),
         });
+
+        self.register_command(Command {
+            id: "view.grid_view".to_string(),
+            title: "Show as Gallery".to_string(),
+            description: Some("Lay out the current folder as a thumbnail gallery".to_string()),
+            category: "View".to_string(),
+            shortcuts: Vec::new(),
+            enabled: true,
+            handler: CommandHandler::View(ViewCommand::GridView),
+        });
+
+        self.register_command(Command {
+            id: "view.list_view".to_string(),
+            title: "Show as List".to_string(),
+            description: Some("Go back to the list and preview layout".to_string()),
+            category: "View".to_string(),
+            shortcuts: Vec::new(),
+            enabled: true,
+            handler: CommandHandler::View(ViewCommand::ListView),
+        });
+
+        self.register_command(Command {
+            id: "view.columns_view".to_string(),
+            title: "Show as Columns".to_string(),
+            description: Some("Browse folders in columns, each opening to the right of its parent".to_string()),
+            category: "View".to_string(),
+            shortcuts: Vec::new(),
+            enabled: true,
+            handler: CommandHandler::View(ViewCommand::ColumnsView),
+        });
     }
 }
 
@@ -2047,6 +4183,21 @@ impl fmt::Display for CommandHandler {
             CommandHandler::File(cmd) => write!(f, "File: {:?}", cmd),
             CommandHandler::Navigation(cmd) => write!(f, "Navigation: {:?}", cmd),
             CommandHandler::View(cmd) => write!(f, "View: {:?}", cmd),
+            CommandHandler::Shell(custom) => write!(f, "Shell: {}", custom.name),
+            CommandHandler::Selection(cmd) => write!(f, "Selection: {:?}", cmd),
+        }
+    }
+}
+
+/// Store recursive sizes on the listed folders they belong to
+fn apply_folder_sizes(mut file_entries: Signal<Vec<FileEntry>>, sizes: Vec<(PathBuf, u64)>) {
+    if sizes.is_empty() {
+        return;
+    }
+    let mut entries = file_entries.write();
+    for (path, size) in sizes {
+        if let Some(entry) = entries.iter_mut().find(|entry| entry.path == path) {
+            entry.size = size;
         }
     }
 }
@@ -2064,6 +4215,89 @@ impl fmt::Display for CommandHandler {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_file_type_filter_groups_extensions() {
+        assert_eq!(FileTypeFilter::group_of_extension("JPG"), Some(FileTypeFilter::Images));
+        assert_eq!(FileTypeFilter::group_of_extension("mkv"), Some(FileTypeFilter::Videos));
+        assert_eq!(FileTypeFilter::group_of_extension("flac"), Some(FileTypeFilter::Audio));
+        assert_eq!(FileTypeFilter::group_of_extension("pdf"), Some(FileTypeFilter::Documents));
+        assert_eq!(FileTypeFilter::group_of_extension("md"), Some(FileTypeFilter::Documents));
+        assert_eq!(FileTypeFilter::group_of_extension("7z"), Some(FileTypeFilter::Archives));
+        assert_eq!(FileTypeFilter::group_of_extension("exe"), None);
+
+        let entry = |path: &str, is_directory: bool| {
+            let path = PathBuf::from(path);
+            FileEntry {
+                name: path.file_name().unwrap().to_string_lossy().into_owned(),
+                file_type: crate::services::file_system::FileType::from_path(&path),
+                path,
+                size: 0,
+                modified: std::time::SystemTime::now(),
+                created: std::time::SystemTime::now(),
+                is_directory,
+                is_hidden: false,
+                permissions: crate::services::file_system::FilePermissions::default(),
+                preview_metadata: None,
+            }
+        };
+        assert!(FileTypeFilter::Images.matches(&entry("/a/photo.PNG", false)));
+        assert!(!FileTypeFilter::Images.matches(&entry("/a/clip.mp4", false)));
+        assert!(FileTypeFilter::Images.matches(&entry("/a/albums", true)), "folders stay listed");
+        assert!(!FileTypeFilter::Images.matches(&entry("/a/README", false)));
+
+        let raw = FileTypeFilter::custom(".CR2, nef  arw");
+        assert_eq!(raw, FileTypeFilter::Custom(vec!["cr2".into(), "nef".into(), "arw".into()]));
+        assert!(raw.matches(&entry("/a/IMG_1.cr2", false)));
+        assert!(!raw.matches(&entry("/a/IMG_1.jpg", false)));
+    }
+
+    #[test]
+    fn test_file_sort_keeps_directories_first_and_ties_in_order() {
+        let entry = |name: &str, size: u64, is_directory: bool| {
+            let path = PathBuf::from("/photos").join(name);
+            FileEntry {
+                name: name.to_string(),
+                file_type: crate::services::file_system::FileType::from_path(&path),
+                path,
+                size,
+                modified: std::time::SystemTime::UNIX_EPOCH,
+                created: std::time::SystemTime::UNIX_EPOCH,
+                is_directory,
+                is_hidden: false,
+                permissions: crate::services::file_system::FilePermissions::default(),
+                preview_metadata: None,
+            }
+        };
+        let names = |entries: &[FileEntry]| entries.iter().map(|e| e.name.clone()).collect::<Vec<_>>();
+        let mut entries = vec![
+            entry("img10.jpg", 5, false),
+            entry("b", 0, true),
+            entry("img2.jpg", 5, false),
+            entry("a10", 0, true),
+            entry("img1.jpg", 1, false),
+            entry("a2", 0, true),
+        ];
+
+        FileSort::default().sort(&mut entries);
+        assert_eq!(names(&entries), ["a2", "a10", "b", "img1.jpg", "img2.jpg", "img10.jpg"]);
+
+        // Equal sizes keep the name order from the previous sort
+        let by_size_desc = FileSort {
+            key: SortKey::Size,
+            order: SortOrder::Desc,
+            directories_first: true,
+        };
+        by_size_desc.sort(&mut entries);
+        assert_eq!(names(&entries), ["a2", "a10", "b", "img2.jpg", "img10.jpg", "img1.jpg"]);
+
+        let mixed = FileSort {
+            directories_first: false,
+            ..by_size_desc
+        };
+        mixed.sort(&mut entries);
+        assert_eq!(names(&entries), ["img2.jpg", "img10.jpg", "img1.jpg", "a2", "a10", "b"]);
+    }
+
     #[test]
     fn test_command_registry_creation() {
         let registry = CommandRegistry::default();
@@ -2155,4 +4389,172 @@ mod tests {
         assert_eq!(test_commands.len(), 1);
         assert_eq!(test_commands[0].id, "test.custom_command");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_set_custom_commands_replaces_previous_ones() {
+        let mut registry = CommandRegistry::default();
+        let initial_count = registry.commands.len();
+
+        registry.set_custom_commands(&[CustomCommand::new("List", "ls"), CustomCommand::new("Disk", "df -h")]);
+        assert_eq!(registry.commands.len(), initial_count + 2);
+
+        registry.set_custom_commands(&[CustomCommand::new("Disk", "du -sh .")]);
+        assert_eq!(registry.commands.len(), initial_count + 1);
+        let custom = registry.get_commands_by_category("Custom Commands");
+        assert_eq!(custom.len(), 1);
+        assert_eq!(custom[0].handler, CommandHandler::Shell(CustomCommand::new("Disk", "du -sh .")));
+    }
+
+    #[test]
+    fn test_select_matching_commands_prompt_for_a_pattern() {
+        let mut registry = CommandRegistry::default();
+        let selection = registry.get_commands_by_category("Selection");
+        assert_eq!(selection.len(), 3);
+        let inverted = registry.get_command("selection.select_not_matching").unwrap();
+        assert_eq!(
+            inverted.handler,
+            CommandHandler::Selection(SelectionCommand::SelectMatching { invert: true, mode: SelectionMode::Replace })
+        );
+
+        registry.palette_state.search_query = "select".to_string();
+        registry.prompt_for_pattern(SelectionCommand::SelectMatching { invert: false, mode: SelectionMode::Add });
+        assert!(registry.palette_state.is_visible);
+        assert!(registry.palette_state.search_query.is_empty());
+        assert!(registry.palette_state.pattern_prompt.is_some());
+
+        registry.close_command_palette();
+        assert!(!registry.palette_state.is_visible);
+        assert!(registry.palette_state.pattern_prompt.is_none());
+    }
+
+    #[test]
+    fn test_selection_commands_are_unavailable_without_a_selection() {
+        let registry = CommandRegistry::default();
+        let move_to = registry.get_command("file.move_to").unwrap();
+        assert_eq!(move_to.shortcuts, ["Ctrl+Shift+M", "Cmd+Shift+M"]);
+        assert!(!move_to.is_available(false));
+        assert!(move_to.is_available(true));
+
+        for id in ["file.copy_to", "file.duplicate", "file.delete", "file.rename", "file.organize_by_type"] {
+            assert!(!registry.get_command(id).unwrap().is_available(false), "{} needs a selection", id);
+        }
+        // Commands that don't act on the selection stay available
+        assert!(registry.get_command("view.toggle_sidebar").unwrap().is_available(false));
+        assert!(registry.get_command("selection.select_matching").unwrap().is_available(false));
+        assert_eq!(
+            FileCommand::Organize(OrganizeRule::by_month()).menu_id(),
+            Some("organize_by_date")
+        );
+    }
+
+    fn editor_with_tabs(titles: &[&str]) -> EditorState {
+        let mut state = EditorState::default();
+        state.editor_groups[0].tabs = titles
+            .iter()
+            .enumerate()
+            .map(|(i, title)| EditorTab {
+                id: i + 1,
+                title: title.to_string(),
+                file_path: None,
+                tab_type: TabType::Welcome,
+                is_dirty: false,
+                is_pinned: false,
+                is_active: i == 0,
+            })
+            .collect();
+        state.next_tab_id = titles.len() + 1;
+        state
+    }
+    
+    fn titles(group: &EditorGroup) -> Vec<&str> {
+        group.tabs.iter().map(|tab| tab.title.as_str()).collect()
+    }
+    
+    #[test]
+    fn test_move_tab_reorders_within_group() {
+        let mut state = editor_with_tabs(&["a", "b", "c"]);
+        
+        assert!(state.move_tab(0, 0, 0, 2));
+        let group = &state.editor_groups[0];
+        assert_eq!(titles(group), ["b", "c", "a"]);
+        // The moved tab becomes the only active one
+        assert_eq!(group.active_tab, 2);
+        assert_eq!(group.tabs.iter().filter(|tab| tab.is_active).count(), 1);
+        
+        // Indexes past the end mean "last"
+        assert!(state.move_tab(0, 0, 0, usize::MAX));
+        assert_eq!(titles(&state.editor_groups[0]), ["c", "a", "b"]);
+        assert!(!state.move_tab(0, 5, 0, 0));
+    }
+    
+    #[test]
+    fn test_move_tab_between_groups_and_close_empty_group() {
+        let mut state = editor_with_tabs(&["a", "b"]);
+        
+        // Dropping on the right edge splits the tab into a new group
+        assert!(state.split_tab(0, 1, 0, SplitEdge::Right));
+        assert_eq!(state.layout_config, EditorLayoutConfig::SplitHorizontal);
+        assert_eq!(titles(&state.editor_groups[0]), ["a"]);
+        assert_eq!(titles(&state.editor_groups[1]), ["b"]);
+        assert_eq!(state.active_group, 1);
+        assert_eq!(state.editor_groups[1].layout_position.x, 0.5);
+        
+        // Moving the last tab out closes its group and the layout collapses
+        assert!(state.move_tab(1, 0, 0, 0));
+        assert_eq!(state.editor_groups.len(), 1);
+        assert_eq!(state.layout_config, EditorLayoutConfig::Single);
+        assert_eq!(state.active_group, 0);
+        let group = &state.editor_groups[0];
+        assert_eq!(titles(group), ["b", "a"]);
+        assert_eq!(group.active_tab, 0);
+        assert!(group.tabs[0].is_active && !group.tabs[1].is_active);
+        
+        // A group's only tab cannot be split next to itself
+        let mut single = editor_with_tabs(&["a"]);
+        assert!(!single.split_tab(0, 0, 0, SplitEdge::Bottom));
+    }
+    
+    #[test]
+    fn test_pinned_tab_survives_close_all_unpinned() {
+        let mut state = editor_with_tabs(&["a", "b", "c"]);
+        
+        // Pinning moves the tab to the front
+        assert!(state.toggle_pin(0, 2));
+        assert_eq!(titles(&state.editor_groups[0]), ["c", "a", "b"]);
+        assert!(state.editor_groups[0].tabs[0].is_pinned);
+        
+        // "Close others" on "a" keeps the pinned tab too
+        assert_eq!(state.close_others(0, 1), 1);
+        assert_eq!(titles(&state.editor_groups[0]), ["c", "a"]);
+        
+        assert_eq!(state.close_all_unpinned(0), 1);
+        let group = &state.editor_groups[0];
+        assert_eq!(titles(group), ["c"]);
+        assert_eq!(group.active_tab, 0);
+        assert!(group.tabs[0].is_active);
+        
+        // Unpinned tabs dragged in cannot be placed before a pinned one
+        let unpinned = editor_with_tabs(&["d"]).editor_groups[0].tabs[0].clone();
+        state.editor_groups[0].tabs.push(unpinned);
+        assert!(state.move_tab(0, 1, 0, 0));
+        assert_eq!(titles(&state.editor_groups[0]), ["c", "d"]);
+    }
+    
+    #[test]
+    fn test_overflow_lists_exactly_the_hidden_tabs() {
+        let mut state = editor_with_tabs(&["p", "t1", "t2", "t3", "t4", "t5", "t6"]);
+        state.editor_groups[0].tabs[0].is_pinned = true;
+        state.editor_groups[0].active_tab = 5;
+        let group = &state.editor_groups[0];
+        
+        // Everything fits
+        assert!(group.overflow_tabs(PINNED_TAB_WIDTH + TAB_WIDTH * 6.0).is_empty());
+        
+        // Room for the pinned tab, the active tab and two more
+        let width = TAB_OVERFLOW_BUTTON_WIDTH + PINNED_TAB_WIDTH + TAB_WIDTH * 3.0;
+        assert_eq!(group.overflow_tabs(width), vec![3, 4, 6]);
+        
+        // Too narrow for anything but the pinned and active tabs
+        assert_eq!(group.overflow_tabs(100.0), vec![1, 2, 3, 4, 6]);
+    }
+}
@@ -0,0 +1,126 @@
+//! Bookmarks: favorite folders listed in the sidebar
+//!
+//! Bookmarks are kept in the order the user arranged them. A bookmark whose
+//! folder was moved or deleted stays in the list as stale until it is
+//! located again or removed.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Current bookmarks file format
+const BOOKMARKS_VERSION: u32 = 1;
+
+/// Favorite folders in display order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BookmarksState {
+    pub version: u32,
+    pub folders: Vec<PathBuf>,
+}
+
+impl Default for BookmarksState {
+    fn default() -> Self {
+        Self { version: BOOKMARKS_VERSION, folders: Vec::new() }
+    }
+}
+
+impl BookmarksState {
+    pub fn contains(&self, path: &Path) -> bool {
+        self.folders.iter().any(|folder| folder == path)
+    }
+
+    /// Bookmark `path` at the end of the list; false if it already is one
+    pub fn add(&mut self, path: PathBuf) -> bool {
+        if self.contains(&path) {
+            return false;
+        }
+        self.folders.push(path);
+        true
+    }
+
+    pub fn remove(&mut self, path: &Path) -> bool {
+        let before = self.folders.len();
+        self.folders.retain(|folder| folder != path);
+        self.folders.len() != before
+    }
+
+    /// Move the bookmark at `from` so it ends up at index `to`
+    pub fn move_bookmark(&mut self, from: usize, to: usize) -> bool {
+        if from >= self.folders.len() || to >= self.folders.len() || from == to {
+            return false;
+        }
+        let folder = self.folders.remove(from);
+        self.folders.insert(to, folder);
+        true
+    }
+
+    /// Point the bookmark for `old` at `new`, keeping its place in the list
+    ///
+    /// If `new` is already bookmarked, the stale entry is dropped instead.
+    pub fn relocate(&mut self, old: &Path, new: PathBuf) -> bool {
+        let Some(index) = self.folders.iter().position(|folder| folder == old) else {
+            return false;
+        };
+        if self.contains(&new) {
+            self.folders.remove(index);
+        } else {
+            self.folders[index] = new;
+        }
+        true
+    }
+
+    /// Bookmarks whose folder fails `dir_exists`
+    pub fn stale(&self, dir_exists: impl Fn(&Path) -> bool) -> Vec<PathBuf> {
+        self.folders.iter().filter(|folder| !dir_exists(folder)).cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_bookmarks_round_trip_keeps_order() {
+        let mut bookmarks = BookmarksState::default();
+        assert!(bookmarks.add(PathBuf::from("/photos")));
+        assert!(bookmarks.add(PathBuf::from("/music")));
+        assert!(bookmarks.add(PathBuf::from("/docs")));
+        assert!(!bookmarks.add(PathBuf::from("/music")));
+
+        assert!(bookmarks.move_bookmark(2, 0));
+        assert!(bookmarks.remove(Path::new("/music")));
+        assert!(!bookmarks.move_bookmark(0, 5));
+
+        let json = serde_json::to_string(&bookmarks).unwrap();
+        let restored: BookmarksState = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, bookmarks);
+        assert_eq!(restored.folders, [PathBuf::from("/docs"), PathBuf::from("/photos")]);
+    }
+
+    #[test]
+    fn test_missing_folders_are_stale_until_relocated() {
+        let temp_dir = TempDir::new().unwrap();
+        let kept = temp_dir.path().join("kept");
+        let moved = temp_dir.path().join("moved");
+        std::fs::create_dir(&kept).unwrap();
+        std::fs::create_dir(&moved).unwrap();
+
+        let mut bookmarks = BookmarksState::default();
+        bookmarks.add(kept.clone());
+        bookmarks.add(moved.clone());
+        assert!(bookmarks.stale(|path| path.is_dir()).is_empty());
+
+        let renamed = temp_dir.path().join("renamed");
+        std::fs::rename(&moved, &renamed).unwrap();
+        assert_eq!(bookmarks.stale(|path| path.is_dir()), [moved.clone()]);
+
+        assert!(bookmarks.relocate(&moved, renamed.clone()));
+        assert_eq!(bookmarks.folders, [kept.clone(), renamed]);
+        assert!(bookmarks.stale(|path| path.is_dir()).is_empty());
+
+        // Locating a stale bookmark at an already bookmarked folder drops it
+        bookmarks.add(moved.clone());
+        assert!(bookmarks.relocate(&moved, kept.clone()));
+        assert_eq!(bookmarks.folders.len(), 2);
+    }
+}
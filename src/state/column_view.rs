@@ -0,0 +1,194 @@
+//! Column view: the path to the selected item as a row of lists, one per level
+//!
+//! The first column lists the file tree root. Selecting a folder opens its
+//! contents in a new column to the right and closes any columns past it;
+//! selecting a file shows its preview after the last column. Listings come
+//! from `FileTreeState`, so the tree's sort and filters apply to every column.
+
+use std::path::{Path, PathBuf};
+
+use crate::services::FileEntry;
+
+/// Arrow key movement in the column view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnStep {
+    /// Previous item in the focused column
+    Up,
+    /// Next item in the focused column
+    Down,
+    /// Back to the folder holding the selection
+    Left,
+    /// Into the selected folder
+    Right,
+}
+
+/// The item selected in one column
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnSelection {
+    pub path: PathBuf,
+    pub is_directory: bool,
+}
+
+/// Root folder and the selection in each column, left to right
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ColumnViewState {
+    root: Option<PathBuf>,
+    stack: Vec<ColumnSelection>,
+}
+
+impl ColumnViewState {
+    /// Start over from `root` unless it is already the first column
+    pub fn set_root(&mut self, root: PathBuf) {
+        if self.root.as_ref() != Some(&root) {
+            self.root = Some(root);
+            self.stack.clear();
+        }
+    }
+
+    pub fn root(&self) -> Option<&Path> {
+        self.root.as_deref()
+    }
+
+    pub fn path_stack(&self) -> &[ColumnSelection] {
+        &self.stack
+    }
+
+    /// Folders listed as columns, the root first
+    pub fn columns(&self) -> Vec<PathBuf> {
+        let Some(root) = &self.root else {
+            return Vec::new();
+        };
+        std::iter::once(root.clone())
+            .chain(self.stack.iter().filter(|selection| selection.is_directory).map(|selection| selection.path.clone()))
+            .collect()
+    }
+
+    /// Item selected in column `column`, if any
+    pub fn selected_in(&self, column: usize) -> Option<&Path> {
+        self.stack.get(column).map(|selection| selection.path.as_path())
+    }
+
+    /// Column holding the last selection, where the arrow keys act
+    pub fn focused_column(&self) -> usize {
+        self.stack.len().saturating_sub(1)
+    }
+
+    /// The selected file previewed after the last column
+    pub fn selected_file(&self) -> Option<&Path> {
+        self.stack.last().filter(|selection| !selection.is_directory).map(|selection| selection.path.as_path())
+    }
+
+    /// Select `path` in column `column`, closing the columns right of it
+    ///
+    /// False when there is no such column, i.e. `column` is past a file or
+    /// past the last open folder.
+    pub fn select(&mut self, column: usize, path: PathBuf, is_directory: bool) -> bool {
+        if self.root.is_none() || column > self.stack.len() {
+            return false;
+        }
+        if column > 0 && !self.stack[column - 1].is_directory {
+            return false;
+        }
+        self.stack.truncate(column);
+        self.stack.push(ColumnSelection { path, is_directory });
+        true
+    }
+
+    /// Drop the last selection, moving focus back to the folder that holds it
+    pub fn pop(&mut self) -> Option<ColumnSelection> {
+        self.stack.pop()
+    }
+}
+
+/// Entry `delta` rows away from `current` in `entries`, stopping at either end
+///
+/// With nothing selected yet the first entry is picked.
+pub fn step_selection<'a>(entries: &'a [FileEntry], current: Option<&Path>, delta: isize) -> Option<&'a FileEntry> {
+    let Some(index) = current.and_then(|current| entries.iter().position(|entry| entry.path == current)) else {
+        return entries.first();
+    };
+    let target = index.saturating_add_signed(delta).min(entries.len() - 1);
+    entries.get(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::file_system::{FilePermissions, FileType, TextFormat};
+    use std::time::SystemTime;
+
+    fn entry(path: &str) -> FileEntry {
+        let path = PathBuf::from(path);
+        FileEntry {
+            name: path.file_name().unwrap().to_string_lossy().to_string(),
+            path,
+            file_type: FileType::Text(TextFormat::Plain),
+            size: 10,
+            modified: SystemTime::now(),
+            created: SystemTime::now(),
+            is_directory: false,
+            is_hidden: false,
+            permissions: FilePermissions::read_write(),
+            preview_metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_selecting_folders_pushes_columns_and_reselecting_pops_them() {
+        let mut view = ColumnViewState::default();
+        assert!(!view.select(0, PathBuf::from("/media/photos"), true));
+        view.set_root(PathBuf::from("/media"));
+        assert_eq!(view.columns(), vec![PathBuf::from("/media")]);
+
+        assert!(view.select(0, PathBuf::from("/media/photos"), true));
+        assert!(view.select(1, PathBuf::from("/media/photos/2024"), true));
+        assert!(view.select(2, PathBuf::from("/media/photos/2024/beach.jpg"), false));
+        assert_eq!(
+            view.columns(),
+            vec![PathBuf::from("/media"), PathBuf::from("/media/photos"), PathBuf::from("/media/photos/2024")]
+        );
+        assert_eq!(view.selected_file(), Some(Path::new("/media/photos/2024/beach.jpg")));
+        assert_eq!(view.focused_column(), 2);
+        // Nothing opens right of a file
+        assert!(!view.select(3, PathBuf::from("/media/photos/2024/beach.jpg/x"), false));
+
+        // Picking another folder in the first column closes the columns past it
+        assert!(view.select(0, PathBuf::from("/media/music"), true));
+        assert_eq!(view.columns(), vec![PathBuf::from("/media"), PathBuf::from("/media/music")]);
+        assert_eq!(view.selected_in(0), Some(Path::new("/media/music")));
+        assert_eq!(view.selected_in(1), None);
+        assert_eq!(view.selected_file(), None);
+    }
+
+    #[test]
+    fn test_pop_moves_focus_left_and_a_new_root_starts_over() {
+        let mut view = ColumnViewState::default();
+        view.set_root(PathBuf::from("/media"));
+        view.select(0, PathBuf::from("/media/photos"), true);
+        view.select(1, PathBuf::from("/media/photos/cat.png"), false);
+
+        assert_eq!(view.pop().map(|selection| selection.path), Some(PathBuf::from("/media/photos/cat.png")));
+        assert_eq!(view.focused_column(), 0);
+        assert_eq!(view.columns().len(), 2);
+        assert_eq!(view.pop().map(|selection| selection.path), Some(PathBuf::from("/media/photos")));
+        assert_eq!(view.pop(), None);
+        assert_eq!(view.columns(), vec![PathBuf::from("/media")]);
+
+        view.select(0, PathBuf::from("/media/photos"), true);
+        view.set_root(PathBuf::from("/media"));
+        assert_eq!(view.path_stack().len(), 1);
+        view.set_root(PathBuf::from("/other"));
+        assert!(view.path_stack().is_empty());
+        assert_eq!(view.root(), Some(Path::new("/other")));
+    }
+
+    #[test]
+    fn test_step_selection_stops_at_either_end() {
+        let entries = vec![entry("/a"), entry("/b"), entry("/c")];
+        assert_eq!(step_selection(&entries, None, 1).unwrap().path, PathBuf::from("/a"));
+        assert_eq!(step_selection(&entries, Some(Path::new("/a")), 1).unwrap().path, PathBuf::from("/b"));
+        assert_eq!(step_selection(&entries, Some(Path::new("/c")), 1).unwrap().path, PathBuf::from("/c"));
+        assert_eq!(step_selection(&entries, Some(Path::new("/a")), -1).unwrap().path, PathBuf::from("/a"));
+        assert!(step_selection(&[], None, 1).is_none());
+    }
+}
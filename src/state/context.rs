@@ -1,5 +1,9 @@
 use dioxus::prelude::*;
+use crate::services::classifier::classifier;
+use crate::services::output::OutputStream;
 use crate::state::{AppState, LayoutManager};
+use crate::state::editor_session::EditorSession;
+use crate::state::persistence::{load_editor_session, save_bottom_panel, save_editor_session};
 
 /// Initialize the application state context using use_context_provider
 /// This should be called once at the app root level
@@ -23,9 +27,71 @@ pub fn try_use_app_state() -> Option<AppState> {
 pub fn AppStateProvider(children: Element) -> Element {
     // Initialize app state and provide it to child components
     let app_state = init_app_state();
+    let mut editor_state = app_state.editor_state;
+    let mut operation_state = app_state.operation_state;
+    let panel_state = app_state.panel_state;
+    let settings = app_state.settings;
+    let mut command_registry = app_state.command_registry;
+    let output_log = app_state.output_log.clone();
     use_context_provider(|| app_state);
     
     tracing::info!("Application state initialized and provided");
+    
+    // Classification rules that failed to load at startup
+    use_hook(move || {
+        for error in classifier().errors() {
+            output_log.push("Classifiers", OutputStream::Stderr, error.to_string());
+        }
+    });
+    
+    // Reopen the last run's tabs; previews are only decoded once a tab is shown
+    let mut session_restored = use_signal(|| false);
+    use_hook(move || {
+        spawn(async move {
+            if let Some(session) = load_editor_session() {
+                let restored = session.restore(|path| path.exists());
+                editor_state.set(restored.state);
+                if !restored.missing_files.is_empty() {
+                    tracing::info!("Skipped {} tabs whose files no longer exist", restored.missing_files.len());
+                    {
+                        let mut op_state = operation_state.write();
+                        op_state.status_message = t!("status.tabs_not_restored", count = restored.missing_files.len());
+                        op_state.is_active = true;
+                    }
+                    session_restored.set(true);
+                    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                    operation_state.write().is_active = false;
+                    return;
+                }
+            }
+            session_restored.set(true);
+        });
+    });
+    
+    // Save the open tabs whenever they change; the memo ignores drag and context-menu updates
+    let session = use_memo(move || EditorSession::capture(&editor_state.read()));
+    use_effect(move || {
+        // Saving before the restore finishes would overwrite the last session
+        if *session_restored.read() {
+            save_editor_session(&session.read());
+        }
+    });
+
+    // Remember the open panel tab; the height is saved when a resize ends
+    let panel_layout = use_memo(move || {
+        let panel = panel_state.read();
+        (panel.is_visible, panel.active_tab.clone())
+    });
+    use_effect(move || {
+        panel_layout.read();
+        save_bottom_panel(&panel_state.peek());
+    });
+
+    // Offer the user's shell commands in the command palette
+    let custom_commands = use_memo(move || settings.read().custom_commands.clone());
+    use_effect(move || {
+        command_registry.write().set_custom_commands(&custom_commands.read());
+    });
 
     rsx! {
         {children}
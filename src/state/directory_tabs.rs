@@ -0,0 +1,322 @@
+//! Directory tabs: several folders open in the file list at once
+//!
+//! The active tab's navigation and selection live in `AppState::navigation`
+//! and `AppState::selection` so the rest of the app reads them as before; the
+//! copy kept here for the active tab is refreshed whenever another tab is
+//! opened or switched to. Only paths, history, selection and view settings
+//! are saved between runs; directory listings are loaded again.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::state::app_state::{FileSort, FileTypeFilter};
+use crate::state::navigation::{NavigationHistory, NavigationState, SelectionMode, SelectionState};
+
+/// Current tab session file format
+const SESSION_VERSION: u32 = 1;
+
+/// One open folder and how the file list showed it
+#[derive(Debug, Clone)]
+pub struct DirectoryTab {
+    pub id: usize,
+    pub navigation: NavigationState,
+    pub selection: SelectionState,
+    /// Vertical scroll offset of the file list in pixels
+    pub scroll_top: f64,
+    pub sort: FileSort,
+    pub file_type_filter: FileTypeFilter,
+}
+
+impl DirectoryTab {
+    /// Folder name shown on the tab
+    pub fn title(&self) -> String {
+        title_for(&self.navigation.current_path)
+    }
+}
+
+/// Folder name for a tab showing `path`, or the whole path for a root
+pub fn title_for(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string_lossy().to_string())
+}
+
+/// Open directory tabs; there is always at least one
+#[derive(Debug, Clone)]
+pub struct DirectoryTabState {
+    pub tabs: Vec<DirectoryTab>,
+    pub active: usize,
+    next_tab_id: usize,
+}
+
+impl Default for DirectoryTabState {
+    fn default() -> Self {
+        Self::new(NavigationState::new(dirs::home_dir()))
+    }
+}
+
+impl DirectoryTabState {
+    /// A single tab showing `navigation`
+    pub fn new(navigation: NavigationState) -> Self {
+        Self {
+            tabs: vec![DirectoryTab {
+                id: 1,
+                navigation,
+                selection: SelectionState::new(),
+                scroll_top: 0.0,
+                sort: FileSort::default(),
+                file_type_filter: FileTypeFilter::default(),
+            }],
+            active: 0,
+            next_tab_id: 2,
+        }
+    }
+
+    pub fn active_tab(&self) -> &DirectoryTab {
+        &self.tabs[self.active]
+    }
+
+    pub fn active_tab_mut(&mut self) -> &mut DirectoryTab {
+        &mut self.tabs[self.active]
+    }
+
+    /// Open `path` in a new tab right of the active one and make it active
+    ///
+    /// The new tab starts with empty history and selection and takes the
+    /// sort and filter of the tab it was opened from. Returns its index.
+    pub fn open_tab(&mut self, path: PathBuf) -> usize {
+        let from = self.active_tab();
+        let tab = DirectoryTab {
+            id: self.next_tab_id,
+            navigation: NavigationState::new(Some(path)),
+            selection: SelectionState::new(),
+            scroll_top: 0.0,
+            sort: from.sort,
+            file_type_filter: from.file_type_filter.clone(),
+        };
+        self.next_tab_id += 1;
+        self.active += 1;
+        self.tabs.insert(self.active, tab);
+        self.active
+    }
+
+    /// Close the tab at `index`; the last remaining tab cannot be closed
+    ///
+    /// Closing the active tab activates its right neighbour, or the left one
+    /// if it was last.
+    pub fn close_tab(&mut self, index: usize) -> bool {
+        if self.tabs.len() <= 1 || index >= self.tabs.len() {
+            return false;
+        }
+        self.tabs.remove(index);
+        if index < self.active || self.active == self.tabs.len() {
+            self.active -= 1;
+        }
+        true
+    }
+
+    /// Make the tab at `index` active
+    pub fn switch_to(&mut self, index: usize) -> bool {
+        if index >= self.tabs.len() {
+            return false;
+        }
+        self.active = index;
+        true
+    }
+}
+
+/// Snapshot of the open directory tabs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DirectoryTabSession {
+    pub version: u32,
+    pub tabs: Vec<SessionDirectoryTab>,
+    pub active: usize,
+}
+
+/// One directory tab in a saved session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionDirectoryTab {
+    pub path: PathBuf,
+    pub history: Vec<PathBuf>,
+    pub history_position: Option<usize>,
+    pub selected: Vec<PathBuf>,
+    pub scroll_top: f64,
+    pub sort: FileSort,
+    pub file_type_filter: FileTypeFilter,
+}
+
+impl DirectoryTabSession {
+    /// Snapshot the parts of `state` worth restoring
+    pub fn capture(state: &DirectoryTabState) -> Self {
+        Self {
+            version: SESSION_VERSION,
+            tabs: state
+                .tabs
+                .iter()
+                .map(|tab| {
+                    let mut selected = tab.selection.get_selected_paths();
+                    selected.sort();
+                    SessionDirectoryTab {
+                        path: tab.navigation.current_path.clone(),
+                        history: tab.navigation.history.entries(),
+                        history_position: tab.navigation.history.position(),
+                        selected,
+                        scroll_top: tab.scroll_top,
+                        sort: tab.sort,
+                        file_type_filter: tab.file_type_filter.clone(),
+                    }
+                })
+                .collect(),
+            active: state.active,
+        }
+    }
+
+    /// Rebuild the tabs, dropping those whose folder no longer passes `dir_exists`
+    ///
+    /// Selected paths that are gone are dropped too. Returns `None` when no
+    /// tab is left.
+    pub fn restore(self, dir_exists: impl Fn(&Path) -> bool) -> Option<DirectoryTabState> {
+        let mut tabs = Vec::new();
+        let mut active = 0;
+        for (index, saved) in self.tabs.into_iter().enumerate() {
+            if !dir_exists(&saved.path) {
+                continue;
+            }
+            if index <= self.active {
+                // The saved active tab, or the nearest kept one before it
+                active = tabs.len();
+            }
+            let mut navigation = NavigationState::new(Some(saved.path));
+            navigation.history = NavigationHistory::from_entries(saved.history, saved.history_position);
+            let mut selection = SelectionState::new();
+            let selected: Vec<PathBuf> = saved.selected.into_iter().filter(|path| path.exists()).collect();
+            if !selected.is_empty() {
+                selection.select_files(selected, SelectionMode::Replace);
+            }
+            tabs.push(DirectoryTab {
+                id: tabs.len() + 1,
+                navigation,
+                selection,
+                scroll_top: saved.scroll_top,
+                sort: saved.sort,
+                file_type_filter: saved.file_type_filter,
+            });
+        }
+        if tabs.is_empty() {
+            return None;
+        }
+        let next_tab_id = tabs.len() + 1;
+        Some(DirectoryTabState { tabs, active, next_tab_id })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::app_state::{SortKey, SortOrder};
+    use tempfile::TempDir;
+
+    fn dirs_in(temp_dir: &TempDir, names: &[&str]) -> Vec<PathBuf> {
+        names
+            .iter()
+            .map(|name| {
+                let dir = temp_dir.path().join(name);
+                std::fs::create_dir(&dir).unwrap();
+                dir
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_tabs_keep_independent_navigation() {
+        let temp_dir = TempDir::new().unwrap();
+        let dirs = dirs_in(&temp_dir, &["photos", "music", "docs"]);
+
+        let mut state = DirectoryTabState::new(NavigationState::new(Some(temp_dir.path().to_path_buf())));
+        state.active_tab_mut().navigation.navigate_to(dirs[0].clone()).unwrap();
+        state.active_tab_mut().sort = FileSort { key: SortKey::Size, order: SortOrder::Desc, directories_first: false };
+
+        assert_eq!(state.open_tab(dirs[1].clone()), 1);
+        assert_eq!(state.tabs.len(), 2);
+        // A new tab starts fresh but keeps the sort it was opened from
+        assert!(!state.active_tab().navigation.can_navigate_back());
+        assert_eq!(state.active_tab().sort.key, SortKey::Size);
+        state.active_tab_mut().navigation.navigate_to(dirs[2].clone()).unwrap();
+        state.active_tab_mut().scroll_top = 120.0;
+
+        // The first tab is untouched by navigation in the second
+        let first = &state.tabs[0];
+        assert_eq!(first.navigation.current_path, dirs[0]);
+        assert_eq!(first.navigation.history.entries(), vec![temp_dir.path().to_path_buf(), dirs[0].clone()]);
+        assert_eq!(first.scroll_top, 0.0);
+
+        assert!(state.switch_to(0));
+        assert_eq!(state.active_tab().navigation.current_path, dirs[0]);
+        assert!(!state.switch_to(5));
+
+        // Closing the active first tab activates its right neighbour
+        assert!(state.close_tab(0));
+        assert_eq!(state.active, 0);
+        assert_eq!(state.active_tab().navigation.current_path, dirs[2]);
+        assert_eq!(state.active_tab().scroll_top, 120.0);
+        assert_eq!(state.active_tab_mut().navigation.go_back(), Some(dirs[1].clone()));
+
+        // The last tab stays open
+        assert!(!state.close_tab(0));
+        assert_eq!(state.tabs.len(), 1);
+    }
+
+    #[test]
+    fn test_closing_a_tab_left_of_the_active_one_keeps_it_active() {
+        let temp_dir = TempDir::new().unwrap();
+        let dirs = dirs_in(&temp_dir, &["a", "b", "c"]);
+        let mut state = DirectoryTabState::new(NavigationState::new(Some(dirs[0].clone())));
+        state.open_tab(dirs[1].clone());
+        state.open_tab(dirs[2].clone());
+        assert_eq!(state.active, 2);
+
+        assert!(state.close_tab(0));
+        assert_eq!(state.active, 1);
+        assert_eq!(state.active_tab().navigation.current_path, dirs[2]);
+        // Closing the last tab while it is active falls back to the left one
+        assert!(state.close_tab(1));
+        assert_eq!(state.active_tab().navigation.current_path, dirs[1]);
+    }
+
+    #[test]
+    fn test_session_round_trip_drops_missing_folders() {
+        let temp_dir = TempDir::new().unwrap();
+        let dirs = dirs_in(&temp_dir, &["kept", "deleted", "other"]);
+        let photo = dirs[0].join("photo.jpg");
+        std::fs::write(&photo, b"jpeg").unwrap();
+
+        let mut state = DirectoryTabState::new(NavigationState::new(Some(temp_dir.path().to_path_buf())));
+        state.active_tab_mut().navigation.navigate_to(dirs[0].clone()).unwrap();
+        state.active_tab_mut().selection.select_files(vec![photo.clone()], SelectionMode::Replace);
+        state.active_tab_mut().scroll_top = 48.0;
+        state.active_tab_mut().file_type_filter = FileTypeFilter::Images;
+        state.open_tab(dirs[1].clone());
+        state.open_tab(dirs[2].clone());
+        state.switch_to(1);
+
+        let json = serde_json::to_string(&DirectoryTabSession::capture(&state)).unwrap();
+        std::fs::remove_dir(&dirs[1]).unwrap();
+        let session: DirectoryTabSession = serde_json::from_str(&json).unwrap();
+        let restored = session.restore(|path| path.is_dir()).unwrap();
+
+        assert_eq!(restored.tabs.len(), 2);
+        // The active tab was removed, so the one before it becomes active
+        assert_eq!(restored.active, 0);
+        let first = restored.active_tab();
+        assert_eq!(first.navigation.current_path, dirs[0]);
+        assert_eq!(first.navigation.history.entries(), vec![temp_dir.path().to_path_buf(), dirs[0].clone()]);
+        assert_eq!(first.navigation.history.position(), Some(1));
+        assert!(first.selection.is_selected(&photo));
+        assert_eq!(first.scroll_top, 48.0);
+        assert_eq!(first.file_type_filter, FileTypeFilter::Images);
+        assert_eq!(restored.tabs[1].navigation.current_path, dirs[2]);
+
+        let gone = DirectoryTabSession::capture(&restored).restore(|_| false);
+        assert!(gone.is_none());
+    }
+}
@@ -0,0 +1,271 @@
+//! Open editor tabs saved between runs
+//!
+//! Only what is needed to reopen a tab is kept: its type, file and pin state
+//! plus the group layout. File contents and previews are not stored; they are
+//! loaded again when a restored tab is first shown.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::state::app_state::{
+    EditorGroup, EditorGroupPosition, EditorLayoutConfig, EditorState, EditorTab, TabType,
+};
+
+/// Current session file format
+const SESSION_VERSION: u32 = 1;
+
+/// Snapshot of the editor groups and their tabs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EditorSession {
+    pub version: u32,
+    pub groups: Vec<SessionGroup>,
+    pub active_group: usize,
+    pub layout_config: EditorLayoutConfig,
+}
+
+/// One editor group in a saved session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionGroup {
+    pub tabs: Vec<SessionTab>,
+    pub active_tab: usize,
+    pub layout_position: EditorGroupPosition,
+}
+
+/// One tab in a saved session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionTab {
+    pub title: String,
+    pub file_path: Option<PathBuf>,
+    pub tab_type: TabType,
+    pub is_pinned: bool,
+}
+
+/// Editor state rebuilt from a session, with the files that could not be reopened
+#[derive(Debug, Clone)]
+pub struct RestoredSession {
+    pub state: EditorState,
+    pub missing_files: Vec<PathBuf>,
+}
+
+impl EditorSession {
+    /// Snapshot the parts of `state` worth restoring
+    pub fn capture(state: &EditorState) -> Self {
+        Self {
+            version: SESSION_VERSION,
+            groups: state
+                .editor_groups
+                .iter()
+                .map(|group| SessionGroup {
+                    tabs: group
+                        .tabs
+                        .iter()
+                        .map(|tab| SessionTab {
+                            title: tab.title.clone(),
+                            file_path: tab.file_path.clone(),
+                            tab_type: tab.tab_type.clone(),
+                            is_pinned: tab.is_pinned,
+                        })
+                        .collect(),
+                    active_tab: group.active_tab,
+                    layout_position: group.layout_position.clone(),
+                })
+                .collect(),
+            active_group: state.active_group,
+            layout_config: state.layout_config.clone(),
+        }
+    }
+
+    /// Rebuild editor state, dropping tabs whose file no longer passes `file_exists`
+    ///
+    /// Groups left without tabs are removed; if nothing is left the default
+    /// editor state is returned.
+    pub fn restore(self, file_exists: impl Fn(&Path) -> bool) -> RestoredSession {
+        let mut missing_files = Vec::new();
+        let mut groups = Vec::new();
+        let mut active_group = 0;
+        let mut next_tab_id = 1;
+
+        for (group_index, group) in self.groups.into_iter().enumerate() {
+            let mut tabs = Vec::new();
+            let mut active_tab = 0;
+            for (tab_index, tab) in group.tabs.into_iter().enumerate() {
+                if let Some(path) = &tab.file_path {
+                    if !file_exists(path) {
+                        missing_files.push(path.clone());
+                        continue;
+                    }
+                }
+                if tab_index <= group.active_tab {
+                    // The saved active tab, or the nearest kept one before it
+                    active_tab = tabs.len();
+                }
+                tabs.push(EditorTab {
+                    id: next_tab_id,
+                    title: tab.title,
+                    file_path: tab.file_path,
+                    tab_type: tab.tab_type,
+                    is_dirty: false,
+                    is_pinned: tab.is_pinned,
+                    is_active: false,
+                });
+                next_tab_id += 1;
+            }
+            if tabs.is_empty() {
+                continue;
+            }
+            tabs[active_tab].is_active = true;
+            if group_index <= self.active_group {
+                active_group = groups.len();
+            }
+            groups.push(EditorGroup {
+                id: groups.len() + 1,
+                tabs,
+                active_tab,
+                layout_position: group.layout_position,
+            });
+        }
+
+        if groups.is_empty() {
+            return RestoredSession {
+                state: EditorState::default(),
+                missing_files,
+            };
+        }
+
+        let layout_config = if groups.len() == 1 {
+            EditorLayoutConfig::Single
+        } else {
+            self.layout_config
+        };
+        RestoredSession {
+            state: EditorState {
+                editor_groups: groups,
+                active_group,
+                layout_config,
+                next_tab_id,
+                ..EditorState::default()
+            },
+            missing_files,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::app_state::PreviewType;
+    use tempfile::TempDir;
+
+    fn preview_tab(path: &Path) -> EditorTab {
+        EditorTab {
+            id: 0,
+            title: path.file_name().unwrap().to_string_lossy().to_string(),
+            file_path: Some(path.to_path_buf()),
+            tab_type: TabType::Preview { preview_type: PreviewType::Image },
+            is_dirty: false,
+            is_pinned: false,
+            is_active: false,
+        }
+    }
+
+    #[test]
+    fn test_session_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let notes = temp_dir.path().join("notes.txt");
+        let photo = temp_dir.path().join("photo.jpg");
+        std::fs::write(&notes, "hello").unwrap();
+        std::fs::write(&photo, b"jpeg").unwrap();
+
+        let mut state = EditorState::default();
+        state.editor_groups[0].tabs.push(EditorTab {
+            id: 2,
+            title: "notes.txt".to_string(),
+            file_path: Some(notes.clone()),
+            tab_type: TabType::FileEditor { content: "hello".to_string() },
+            is_dirty: false,
+            is_pinned: true,
+            is_active: false,
+        });
+        state.editor_groups[0].active_tab = 1;
+        state.editor_groups.push(EditorGroup {
+            id: 2,
+            tabs: vec![preview_tab(&photo)],
+            active_tab: 0,
+            layout_position: EditorGroupPosition { x: 0.5, y: 0.0, width: 0.5, height: 1.0 },
+        });
+        state.layout_config = EditorLayoutConfig::SplitHorizontal;
+        state.active_group = 1;
+
+        let json = serde_json::to_string(&EditorSession::capture(&state)).unwrap();
+        assert!(!json.contains("hello"), "file contents must not be saved");
+        let session: EditorSession = serde_json::from_str(&json).unwrap();
+        let restored = session.restore(|path| path.exists());
+
+        assert!(restored.missing_files.is_empty());
+        let state = restored.state;
+        assert_eq!(state.editor_groups.len(), 2);
+        assert_eq!(state.active_group, 1);
+        assert_eq!(state.layout_config, EditorLayoutConfig::SplitHorizontal);
+        let first = &state.editor_groups[0];
+        assert_eq!(first.active_tab, 1);
+        assert!(first.tabs[1].is_active && first.tabs[1].is_pinned);
+        // Content is reloaded lazily when the tab is shown
+        assert_eq!(first.tabs[1].tab_type, TabType::FileEditor { content: String::new() });
+        assert_eq!(
+            state.editor_groups[1].tabs[0].tab_type,
+            TabType::Preview { preview_type: PreviewType::Image }
+        );
+        assert_eq!(state.next_tab_id, 4);
+    }
+
+    #[test]
+    fn test_restore_prunes_missing_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let kept = temp_dir.path().join("kept.png");
+        let deleted = temp_dir.path().join("deleted.png");
+        let also_deleted = temp_dir.path().join("also_deleted.png");
+        std::fs::write(&kept, b"png").unwrap();
+
+        let mut state = EditorState::default();
+        state.editor_groups[0].tabs = vec![preview_tab(&kept), preview_tab(&deleted)];
+        state.editor_groups[0].active_tab = 1;
+        state.editor_groups.push(EditorGroup {
+            id: 2,
+            tabs: vec![preview_tab(&also_deleted)],
+            active_tab: 0,
+            layout_position: EditorGroupPosition::default(),
+        });
+        state.layout_config = EditorLayoutConfig::SplitVertical;
+        state.active_group = 1;
+
+        let restored = EditorSession::capture(&state).restore(|path| path.exists());
+
+        assert_eq!(restored.missing_files, vec![deleted, also_deleted]);
+        let state = restored.state;
+        // The emptied group is gone and the active tab falls back to the kept one
+        assert_eq!(state.editor_groups.len(), 1);
+        assert_eq!(state.active_group, 0);
+        assert_eq!(state.layout_config, EditorLayoutConfig::Single);
+        assert_eq!(state.editor_groups[0].tabs.len(), 1);
+        assert_eq!(state.editor_groups[0].active_tab, 0);
+        assert!(state.editor_groups[0].tabs[0].is_active);
+    }
+
+    #[test]
+    fn test_reopen_closed_tab() {
+        let mut state = EditorState::default();
+        state.editor_groups[0].tabs.push(preview_tab(Path::new("/photos/a.jpg")));
+
+        assert!(state.close_tab(0, 1));
+        assert_eq!(state.editor_groups[0].tabs.len(), 1);
+        // The last tab of a group is never closed
+        assert!(!state.close_tab(0, 0));
+
+        assert!(state.reopen_closed_tab());
+        let group = &state.editor_groups[0];
+        assert_eq!(group.tabs.len(), 2);
+        assert_eq!(group.active_tab, 1);
+        assert_eq!(group.tabs[1].file_path.as_deref(), Some(Path::new("/photos/a.jpg")));
+        assert!(!state.reopen_closed_tab());
+    }
+}
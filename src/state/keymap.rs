@@ -0,0 +1,73 @@
+//! Keymap: the user's changes to the default keyboard shortcuts
+//!
+//! Only actions the user rebound are stored, keyed by action id, with the
+//! complete list of chords that now trigger them in accelerator form
+//! (`CmdOrCtrl+Shift+P`). An empty list unbinds the action; actions that are
+//! not listed keep their default shortcuts.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Current keymap file format
+const KEYMAP_VERSION: u32 = 1;
+
+/// Shortcut overrides by action id
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeymapState {
+    pub version: u32,
+    pub bindings: BTreeMap<String, Vec<String>>,
+}
+
+impl Default for KeymapState {
+    fn default() -> Self {
+        Self { version: KEYMAP_VERSION, bindings: BTreeMap::new() }
+    }
+}
+
+impl KeymapState {
+    /// Chords replacing the defaults of `action_id`, if the user rebound it
+    pub fn overrides(&self, action_id: &str) -> Option<&[String]> {
+        self.bindings.get(action_id).map(Vec::as_slice)
+    }
+
+    /// Bind `action_id` to exactly `chords`, replacing its defaults
+    pub fn set(&mut self, action_id: &str, chords: Vec<String>) {
+        self.bindings.insert(action_id.to_string(), chords);
+    }
+
+    /// Give `action_id` its default shortcuts back; false if it had them
+    pub fn reset(&mut self, action_id: &str) -> bool {
+        self.bindings.remove(action_id).is_some()
+    }
+
+    pub fn reset_all(&mut self) {
+        self.bindings.clear();
+    }
+
+    pub fn is_customized(&self) -> bool {
+        !self.bindings.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_and_reset_overrides() {
+        let mut keymap = KeymapState::default();
+        assert!(!keymap.is_customized());
+
+        keymap.set("copy", vec!["CmdOrCtrl+Shift+C".to_string()]);
+        keymap.set("refresh", Vec::new());
+        assert_eq!(keymap.overrides("copy"), Some(&["CmdOrCtrl+Shift+C".to_string()][..]));
+        assert_eq!(keymap.overrides("refresh"), Some(&[][..]));
+        assert_eq!(keymap.overrides("paste"), None);
+
+        assert!(keymap.reset("copy"));
+        assert!(!keymap.reset("copy"));
+        assert!(keymap.is_customized());
+        keymap.reset_all();
+        assert_eq!(keymap, KeymapState::default());
+    }
+}
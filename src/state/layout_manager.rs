@@ -117,7 +117,7 @@ impl LayoutManager {
             Theme::Light => Theme::Dark,
             Theme::Dark => Theme::Light,
             Theme::Auto => Theme::Dark, // Default to dark when toggling from auto
-            Theme::HighContrast => Theme::Light, // Go to light from high contrast
+            Theme::HighContrast | Theme::Custom(_) => Theme::Light, // Go to light from high contrast or a custom theme
         };
         self.set_theme(new_theme);
     }
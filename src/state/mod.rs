@@ -1,6 +1,16 @@
 pub mod app_state;
 pub mod navigation;
 pub mod persistence;
+pub mod editor_session;
+pub mod directory_tabs;
+pub mod split_view;
+pub mod column_view;
+pub mod window_geometry;
+pub mod bookmarks;
+pub mod recent_folders;
+pub mod keymap;
+pub mod settings_transfer;
+pub mod quick_look;
 pub mod context;
 pub mod layout_manager;
 pub mod performance;
@@ -13,17 +23,21 @@ pub mod tests;
 
 // Centralized state management - only export actively used types
 pub use app_state::{
-    AppState, ViewMode, ActivityBarView, SidebarState, FileTreeState, SearchState, OperationState, Theme, SettingsState,
-    FontFamily, FontSize,
+    AppState, ViewMode, ActivityBarView, SidebarState, FileTreeState, EmptyTreeState, SearchState, OperationState, Theme, SettingsState,
+    FontFamily, FontSize, TabOverflow, ThumbnailShape, ThumbnailSize, FileTypeFilter,
+    FileSort, SortKey, SortOrder, DoubleClickAction, DoubleClickSettings,
     EditorState, EditorGroup, EditorTab, TabType, PreviewType, EditorLayoutConfig, EditorGroupPosition, TabDragOperation,
+    TabDropTarget, SplitEdge,
     TabContextMenu, PanelTab, PanelState,
     // New unified layout state types
     LayoutState, ActivityBarPosition, SidebarPosition, SidebarContent, PanelPosition,
     // Command system types
-    Command, CommandPaletteState, CommandHandler, SystemCommand, EditorCommand, FileCommand, NavigationCommand, ViewCommand
+    Command, CommandPaletteState, CommandHandler, SystemCommand, EditorCommand, FileCommand, NavigationCommand, ViewCommand,
+    SelectionCommand
 };
 pub use layout_manager::LayoutManager;
-pub use navigation::{SelectionState};
+pub use quick_look::QuickLookState;
+pub use navigation::{NamePattern, SelectionState};
 pub use context::{
     AppStateProvider, use_app_state, use_file_entries,
     use_selection_state, use_activity_bar_view, use_sidebar_state, use_file_tree_state, use_editor_state, use_panel_state
@@ -11,13 +11,22 @@ pub struct NavigationState {
     pub loading_paths: HashSet<PathBuf>,
 }
 
+/// Folders visited before and after the current one, browser style
+///
+/// Visiting a new folder pushes the current one on the back stack and
+/// clears the forward stack; going back moves the current folder onto the
+/// forward stack. The back stack keeps at most `max_depth` folders.
 #[derive(Debug, Clone)]
 pub struct NavigationHistory {
-    history: VecDeque<PathBuf>,
-    current_index: Option<usize>,
-    max_size: usize,
+    back_stack: VecDeque<PathBuf>,
+    current: Option<PathBuf>,
+    forward_stack: Vec<PathBuf>,
+    max_depth: usize,
 }
 
+/// Back stack depth used unless configured otherwise
+pub const DEFAULT_HISTORY_DEPTH: usize = 100;
+
 #[derive(Debug, Clone)]
 pub struct BreadcrumbItem {
     pub name: String,
@@ -29,6 +38,10 @@ pub struct SelectionState {
     pub selected_files: HashSet<PathBuf>,
     pub last_selected: Option<PathBuf>,
     pub selection_metadata: SelectionMetadata,
+    /// Index in the sorted listing that Shift ranges start from
+    pub anchor: Option<usize>,
+    /// Index a Shift range was last extended to
+    pub range_end: Option<usize>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -39,7 +52,7 @@ pub struct SelectionMetadata {
     pub total_count: usize,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SelectionMode {
     Replace,
     Add,
@@ -47,6 +60,32 @@ pub enum SelectionMode {
     Range,
 }
 
+/// File name pattern for "Select Files Matching…"
+#[derive(Debug, Clone)]
+pub enum NamePattern {
+    /// `*`/`?` pattern matched against the whole name, ignoring case
+    Glob(String),
+    Regex(regex::Regex),
+}
+
+impl NamePattern {
+    /// Read `/…/` as a regex and anything else as a glob
+    pub fn parse(input: &str) -> Result<Self, regex::Error> {
+        let input = input.trim();
+        match input.strip_prefix('/').and_then(|rest| rest.strip_suffix('/')) {
+            Some(expression) => regex::Regex::new(expression).map(NamePattern::Regex),
+            None => Ok(NamePattern::Glob(input.to_lowercase())),
+        }
+    }
+
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            NamePattern::Glob(pattern) => crate::services::classifier::glob_matches(pattern, &name.to_lowercase()),
+            NamePattern::Regex(regex) => regex.is_match(name),
+        }
+    }
+}
+
 impl NavigationState {
     pub fn new(initial_path: Option<PathBuf>) -> Self {
         let current_path = initial_path.unwrap_or_else(|| {
@@ -54,10 +93,12 @@ impl NavigationState {
         });
         
         let breadcrumbs = Self::create_breadcrumbs(&current_path);
+        let mut history = NavigationHistory::new();
+        history.push(current_path.clone());
         
         Self {
             current_path,
-            history: NavigationHistory::new(),
+            history,
             breadcrumbs,
             directory_contents: HashMap::new(),
             loading_paths: HashSet::new(),
@@ -73,8 +114,12 @@ impl NavigationState {
             return Err(NavigationError::NotADirectory(path));
         }
         
-        // Add current path to history before navigating
-        self.history.push(self.current_path.clone());
+        if path == self.current_path {
+            return Ok(());
+        }
+        
+        // Visiting a new folder drops the forward history
+        self.history.push(path.clone());
         
         // Update current path and breadcrumbs
         self.current_path = path;
@@ -83,7 +128,8 @@ impl NavigationState {
         Ok(())
     }
     
-    pub fn navigate_back(&mut self) -> Option<PathBuf> {
+    /// Go to the previous folder, if any, and return it
+    pub fn go_back(&mut self) -> Option<PathBuf> {
         if let Some(previous_path) = self.history.back() {
             self.current_path = previous_path.clone();
             self.breadcrumbs = Self::create_breadcrumbs(&self.current_path);
@@ -93,7 +139,8 @@ impl NavigationState {
         }
     }
     
-    pub fn navigate_forward(&mut self) -> Option<PathBuf> {
+    /// Return to the folder left by `go_back`, if any, and return it
+    pub fn go_forward(&mut self) -> Option<PathBuf> {
         if let Some(next_path) = self.history.forward() {
             self.current_path = next_path.clone();
             self.breadcrumbs = Self::create_breadcrumbs(&self.current_path);
@@ -106,7 +153,7 @@ impl NavigationState {
     pub fn navigate_up(&mut self) -> Option<PathBuf> {
         if let Some(parent) = self.current_path.parent() {
             let parent_path = parent.to_path_buf();
-            self.history.push(self.current_path.clone());
+            self.history.push(parent_path.clone());
             self.current_path = parent_path.clone();
             self.breadcrumbs = Self::create_breadcrumbs(&self.current_path);
             Some(parent_path)
@@ -151,6 +198,13 @@ impl NavigationState {
     /// Clear navigation history (used when changing root contexts)
     pub fn clear_history(&mut self) {
         self.history.clear();
+        self.history.push(self.current_path.clone());
+    }
+    
+    /// Keep at most `depth` folders to go back to
+    pub fn with_history_depth(mut self, depth: usize) -> Self {
+        self.history = self.history.with_max_depth(depth);
+        self
     }
     
     fn create_breadcrumbs(path: &PathBuf) -> Vec<BreadcrumbItem> {
@@ -181,62 +235,95 @@ impl NavigationState {
 impl NavigationHistory {
     pub fn new() -> Self {
         Self {
-            history: VecDeque::new(),
-            current_index: None,
-            max_size: 100,
+            back_stack: VecDeque::new(),
+            current: None,
+            forward_stack: Vec::new(),
+            max_depth: DEFAULT_HISTORY_DEPTH,
         }
     }
     
+    /// Keep at most `depth` folders on the back stack, dropping the oldest
+    pub fn with_max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self.trim();
+        self
+    }
+    
+    /// Visit `path`: the current folder moves to the back stack and the forward stack is cleared
     pub fn push(&mut self, path: PathBuf) {
-        // Remove any forward history when adding new entry
-        if let Some(index) = self.current_index {
-            self.history.truncate(index + 1);
-        }
-        
-        self.history.push_back(path);
-        self.current_index = Some(self.history.len() - 1);
-        
-        // Limit history size
-        if self.history.len() > self.max_size {
-            self.history.pop_front();
-            if let Some(ref mut index) = self.current_index {
-                *index = index.saturating_sub(1);
-            }
+        if let Some(previous) = self.current.replace(path) {
+            self.back_stack.push_back(previous);
         }
+        self.forward_stack.clear();
+        self.trim();
     }
     
     pub fn back(&mut self) -> Option<PathBuf> {
-        if let Some(current_index) = self.current_index {
-            if current_index > 0 {
-                self.current_index = Some(current_index - 1);
-                return self.history.get(current_index - 1).cloned();
-            }
+        let previous = self.back_stack.pop_back()?;
+        if let Some(current) = self.current.replace(previous.clone()) {
+            self.forward_stack.push(current);
         }
-        None
+        Some(previous)
     }
     
     pub fn forward(&mut self) -> Option<PathBuf> {
-        if let Some(current_index) = self.current_index {
-            if current_index < self.history.len() - 1 {
-                self.current_index = Some(current_index + 1);
-                return self.history.get(current_index + 1).cloned();
-            }
+        let next = self.forward_stack.pop()?;
+        if let Some(current) = self.current.replace(next.clone()) {
+            self.back_stack.push_back(current);
         }
-        None
+        self.trim();
+        Some(next)
     }
     
     pub fn can_back(&self) -> bool {
-        self.current_index.map_or(false, |index| index > 0)
+        !self.back_stack.is_empty()
     }
     
     pub fn can_forward(&self) -> bool {
-        self.current_index.map_or(false, |index| index < self.history.len() - 1)
+        !self.forward_stack.is_empty()
     }
     
     /// Clear all navigation history
     pub fn clear(&mut self) {
-        self.history.clear();
-        self.current_index = None;
+        self.back_stack.clear();
+        self.current = None;
+        self.forward_stack.clear();
+    }
+    
+    /// Visited paths, oldest first, including the current one
+    pub fn entries(&self) -> Vec<PathBuf> {
+        self.back_stack
+            .iter()
+            .chain(self.current.iter())
+            .chain(self.forward_stack.iter().rev())
+            .cloned()
+            .collect()
+    }
+    
+    /// Position of the current path in `entries`
+    pub fn position(&self) -> Option<usize> {
+        self.current.as_ref().map(|_| self.back_stack.len())
+    }
+    
+    /// History rebuilt from saved `entries`; an out-of-range position points at the newest entry
+    pub fn from_entries(mut entries: Vec<PathBuf>, position: Option<usize>) -> Self {
+        let mut history = Self::new();
+        let Some(position) = position.filter(|_| !entries.is_empty()) else {
+            return history;
+        };
+        let position = position.min(entries.len() - 1);
+        history.forward_stack = entries.split_off(position + 1);
+        history.forward_stack.reverse();
+        history.current = entries.pop();
+        history.back_stack = entries.into();
+        history.trim();
+        history
+    }
+    
+    fn trim(&mut self) {
+        while self.back_stack.len() > self.max_depth {
+            self.back_stack.pop_front();
+        }
     }
 }
 
@@ -246,6 +333,8 @@ impl SelectionState {
             selected_files: HashSet::new(),
             last_selected: None,
             selection_metadata: SelectionMetadata::default(),
+            anchor: None,
+            range_end: None,
         }
     }
     
@@ -287,10 +376,75 @@ impl SelectionState {
         self.update_metadata();
     }
     
+    /// Select `ordered_entries` from `anchor` to `target`, replacing the selection
+    ///
+    /// The anchor is kept, so consecutive Shift-clicks all measure from it.
+    pub fn select_range(&mut self, anchor: usize, target: usize, ordered_entries: &[FileEntry]) {
+        let Some(last) = ordered_entries.len().checked_sub(1) else {
+            return;
+        };
+        let (anchor, target) = (anchor.min(last), target.min(last));
+        let range = anchor.min(target)..=anchor.max(target);
+        self.selected_files = ordered_entries[range].iter().map(|entry| entry.path.clone()).collect();
+        self.last_selected = Some(ordered_entries[target].path.clone());
+        self.anchor = Some(anchor);
+        self.range_end = Some(target);
+        self.update_metadata();
+    }
+    
+    /// Add or remove `path`, returning whether it is now selected
+    pub fn toggle(&mut self, path: PathBuf) -> bool {
+        let selected = if self.selected_files.remove(&path) {
+            false
+        } else {
+            self.selected_files.insert(path.clone());
+            self.last_selected = Some(path);
+            true
+        };
+        self.update_metadata();
+        selected
+    }
+    
+    /// Start later ranges from `index`, as a plain or Ctrl/Cmd-click does
+    pub fn set_anchor(&mut self, index: usize) {
+        self.anchor = Some(index);
+        self.range_end = Some(index);
+    }
+    
+    /// Move the end of the range by `delta` entries, as Shift+arrow does
+    ///
+    /// Without an anchor the range starts at the first entry. Returns the new end.
+    pub fn extend_range(&mut self, delta: isize, ordered_entries: &[FileEntry]) -> Option<usize> {
+        let last = ordered_entries.len().checked_sub(1)?;
+        let anchor = self.anchor.unwrap_or(0);
+        let target = self.range_end.unwrap_or(anchor).saturating_add_signed(delta).min(last);
+        self.select_range(anchor, target, ordered_entries);
+        Some(target)
+    }
+    
+    /// Select the entries whose names match `pattern`, or with `invert` those
+    /// that don't, combined with the current selection by `mode`
+    ///
+    /// Returns how many entries matched.
+    pub fn select_matching(&mut self, pattern: &NamePattern, invert: bool, mode: SelectionMode, entries: &[FileEntry]) -> usize {
+        let matching: Vec<PathBuf> = entries
+            .iter()
+            .filter(|entry| pattern.matches(&entry.name) != invert)
+            .map(|entry| entry.path.clone())
+            .collect();
+        let count = matching.len();
+        self.select_files(matching, mode);
+        self.anchor = None;
+        self.range_end = None;
+        count
+    }
+    
     pub fn clear_selection(&mut self) {
         self.selected_files.clear();
         self.last_selected = None;
         self.selection_metadata = SelectionMetadata::default();
+        self.anchor = None;
+        self.range_end = None;
     }
     
     pub fn is_selected(&self, path: &PathBuf) -> bool {
@@ -377,6 +531,55 @@ mod tests {
         assert!(!history.can_forward());
     }
     
+    #[test]
+    fn test_navigating_after_going_back_clears_forward() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = |name: &str| {
+            let path = temp_dir.path().join(name);
+            std::fs::create_dir(&path).unwrap();
+            path
+        };
+        let (a, b, c, d) = (dir("a"), dir("b"), dir("c"), dir("d"));
+
+        let mut nav = NavigationState::new(Some(a.clone()));
+        assert!(!nav.can_navigate_back());
+        nav.navigate_to(b.clone()).unwrap();
+        nav.navigate_to(c.clone()).unwrap();
+
+        assert_eq!(nav.go_back(), Some(b.clone()));
+        assert_eq!(nav.go_back(), Some(a.clone()));
+        assert_eq!(nav.current_path, a);
+        assert!(!nav.can_navigate_back());
+        assert!(nav.can_navigate_forward());
+
+        nav.navigate_to(d.clone()).unwrap();
+        assert!(!nav.can_navigate_forward());
+        assert_eq!(nav.go_forward(), None);
+        assert_eq!(nav.current_path, d);
+        assert_eq!(nav.go_back(), Some(a.clone()));
+        assert_eq!(nav.go_forward(), Some(d.clone()));
+        // Re-opening the shown folder is not a new history entry
+        nav.navigate_to(d.clone()).unwrap();
+        assert_eq!(nav.history.entries(), vec![a, d]);
+    }
+
+    #[test]
+    fn test_history_depth_drops_oldest_entries() {
+        let mut history = NavigationHistory::new().with_max_depth(2);
+        for name in ["/1", "/2", "/3", "/4"] {
+            history.push(PathBuf::from(name));
+        }
+        assert_eq!(history.back(), Some(PathBuf::from("/3")));
+        assert_eq!(history.back(), Some(PathBuf::from("/2")));
+        assert_eq!(history.back(), None);
+
+        // Saved entries round-trip with the position between back and forward
+        let restored = NavigationHistory::from_entries(history.entries(), history.position());
+        assert_eq!(restored.entries(), ["/2", "/3", "/4"].map(PathBuf::from));
+        assert_eq!(restored.position(), Some(0));
+        assert!(restored.can_forward() && !restored.can_back());
+    }
+    
     #[test]
     fn test_selection_state() {
         let mut selection = SelectionState::new();
@@ -411,6 +614,106 @@ mod tests {
         assert_eq!(selection.selection_count(), 0);
     }
     
+    fn listing(names: &[&str]) -> Vec<FileEntry> {
+        names
+            .iter()
+            .map(|name| FileEntry {
+                path: PathBuf::from(name),
+                name: name.to_string(),
+                file_type: crate::services::file_system::FileType::Other(String::new()),
+                size: 0,
+                modified: std::time::SystemTime::UNIX_EPOCH,
+                created: std::time::SystemTime::UNIX_EPOCH,
+                is_directory: false,
+                is_hidden: false,
+                permissions: crate::services::file_system::FilePermissions::read_write(),
+                preview_metadata: None,
+            })
+            .collect()
+    }
+    
+    fn selected_names(selection: &SelectionState) -> Vec<String> {
+        let mut names: Vec<String> = selection.selected_files.iter().map(|path| path.display().to_string()).collect();
+        names.sort();
+        names
+    }
+    
+    #[test]
+    fn test_consecutive_shift_clicks_keep_the_anchor() {
+        // Sorted by size, so the listing order is not the name order
+        let entries = listing(&["e.jpg", "b.jpg", "d.jpg", "a.jpg", "c.jpg"]);
+        let mut selection = SelectionState::new();
+        
+        // Click b.jpg, then Shift-click a.jpg and e.jpg
+        selection.toggle(entries[1].path.clone());
+        selection.set_anchor(1);
+        selection.select_range(selection.anchor.unwrap(), 3, &entries);
+        assert_eq!(selected_names(&selection), ["a.jpg", "b.jpg", "d.jpg"]);
+        selection.select_range(selection.anchor.unwrap(), 0, &entries);
+        assert_eq!(selected_names(&selection), ["b.jpg", "e.jpg"]);
+        assert_eq!(selection.anchor, Some(1));
+        assert_eq!(selection.last_selected, Some(PathBuf::from("e.jpg")));
+        
+        // Shift+Down twice moves the far end; the anchor stays on b.jpg
+        assert_eq!(selection.extend_range(1, &entries), Some(1));
+        assert_eq!(selection.extend_range(1, &entries), Some(2));
+        assert_eq!(selected_names(&selection), ["b.jpg", "d.jpg"]);
+        assert_eq!(selection.extend_range(10, &entries), Some(4));
+        assert_eq!(selection.selection_count(), 4);
+        
+        // Ctrl-click drops one item and moves the anchor there
+        assert!(!selection.toggle(entries[3].path.clone()));
+        selection.set_anchor(3);
+        assert_eq!(selected_names(&selection), ["b.jpg", "c.jpg", "d.jpg"]);
+        selection.select_range(selection.anchor.unwrap(), 4, &entries);
+        assert_eq!(selected_names(&selection), ["a.jpg", "c.jpg"]);
+        
+        selection.clear_selection();
+        assert_eq!(selection.anchor, None);
+        assert_eq!(selection.extend_range(1, &entries), Some(1));
+        assert_eq!(selected_names(&selection), ["b.jpg", "e.jpg"]);
+        assert!(SelectionState::new().extend_range(1, &[]).is_none());
+    }
+    
+    #[test]
+    fn test_select_matching_glob_and_regex() {
+        let entries = listing(&["IMG_001.JPG", "IMG_002.png", "notes.txt", "img_003.jpg"]);
+        let mut selection = SelectionState::new();
+        
+        // Globs ignore case and match the whole name
+        let glob = NamePattern::parse(" *.jpg ").unwrap();
+        assert_eq!(selection.select_matching(&glob, false, SelectionMode::Replace, &entries), 2);
+        assert_eq!(selected_names(&selection), ["IMG_001.JPG", "img_003.jpg"]);
+        assert!(!NamePattern::parse("IMG_00?").unwrap().matches("IMG_001.JPG"));
+        
+        // Regexes between slashes match anywhere and keep their case
+        let regex = NamePattern::parse("/^IMG_\\d+\\./").unwrap();
+        assert_eq!(selection.select_matching(&regex, false, SelectionMode::Replace, &entries), 2);
+        assert_eq!(selected_names(&selection), ["IMG_001.JPG", "IMG_002.png"]);
+        assert!(NamePattern::parse("/[unclosed/").is_err());
+        
+        // Adding keeps what was already selected
+        let text = NamePattern::parse("*.txt").unwrap();
+        selection.select_matching(&text, false, SelectionMode::Add, &entries);
+        assert_eq!(selected_names(&selection), ["IMG_001.JPG", "IMG_002.png", "notes.txt"]);
+    }
+    
+    #[test]
+    fn test_select_matching_inverted() {
+        let entries = listing(&["a.jpg", "b.png", "c.jpg", "d.txt"]);
+        let mut selection = SelectionState::new();
+        selection.toggle(entries[3].path.clone());
+        
+        let jpg = NamePattern::parse("*.jpg").unwrap();
+        assert_eq!(selection.select_matching(&jpg, true, SelectionMode::Replace, &entries), 2);
+        assert_eq!(selected_names(&selection), ["b.png", "d.txt"]);
+        
+        // Inverting everything away leaves nothing selected
+        let all = NamePattern::parse("*").unwrap();
+        assert_eq!(selection.select_matching(&all, true, SelectionMode::Replace, &entries), 0);
+        assert_eq!(selection.selection_count(), 0);
+    }
+    
     #[test]
     fn test_breadcrumbs() {
         let path = PathBuf::from("/home/user/documents/projects");
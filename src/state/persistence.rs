@@ -2,14 +2,27 @@ use serde::{Deserialize, Serialize};
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use std::path::PathBuf;
 use std::fs;
-use tracing::{debug, warn, error};
-use crate::state::app_state::{SettingsState, LayoutState};
+use tracing::{debug, info, warn, error};
+use crate::state::app_state::{SettingsState, LayoutState, SETTINGS_SCHEMA_VERSION};
+use crate::state::bookmarks::BookmarksState;
+use crate::state::keymap::KeymapState;
+use crate::state::directory_tabs::DirectoryTabSession;
+use crate::state::split_view::SplitViewSession;
+use crate::state::editor_session::EditorSession;
+use crate::state::app_state::PanelState as BottomPanelState;
+use crate::state::settings_transfer::SettingsSnapshot;
 
 const STORAGE_KEY: &str = "media_organizer_panel_state";
 const SETTINGS_STORAGE_KEY: &str = "media_organizer_settings";
 const LAYOUT_STORAGE_KEY: &str = "media_organizer_layout_state";
 const DEBOUNCE_DELAY: Duration = Duration::from_millis(300);
 
+/// Upgrades a saved settings object by one schema version
+type SettingsMigration = fn(&mut serde_json::Map<String, serde_json::Value>);
+
+/// Settings migrations in order; the step at index `i` upgrades version `i + 1` to `i + 2`
+const SETTINGS_MIGRATIONS: [SettingsMigration; (SETTINGS_SCHEMA_VERSION - 1) as usize] = [migrate_settings_v1_to_v2];
+
 /// Panel state configuration that can be persisted to localStorage
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PanelState {
@@ -74,6 +87,12 @@ pub struct PersistenceService {
     config_file_path: PathBuf,
     settings_file_path: PathBuf,
     layout_file_path: PathBuf,
+    editor_session_file_path: PathBuf,
+    directory_tabs_file_path: PathBuf,
+    split_view_file_path: PathBuf,
+    bookmarks_file_path: PathBuf,
+    keymap_file_path: PathBuf,
+    bottom_panel_file_path: PathBuf,
 }
 
 impl Default for PersistenceService {
@@ -88,6 +107,12 @@ impl PersistenceService {
         let config_file_path = Self::get_config_file_path();
         let settings_file_path = Self::get_settings_file_path();
         let layout_file_path = Self::get_layout_file_path();
+        let editor_session_file_path = Self::get_editor_session_file_path();
+        let directory_tabs_file_path = Self::get_directory_tabs_file_path();
+        let split_view_file_path = Self::get_split_view_file_path();
+        let bookmarks_file_path = Self::get_bookmarks_file_path();
+        let keymap_file_path = Self::get_keymap_file_path();
+        let bottom_panel_file_path = Self::get_bottom_panel_file_path();
         Self {
             last_save: None,
             pending_state: None,
@@ -98,6 +123,12 @@ impl PersistenceService {
             config_file_path,
             settings_file_path,
             layout_file_path,
+            editor_session_file_path,
+            directory_tabs_file_path,
+            split_view_file_path,
+            bookmarks_file_path,
+            keymap_file_path,
+            bottom_panel_file_path,
         }
     }
     
@@ -137,6 +168,78 @@ impl PersistenceService {
         }
     }
     
+    /// Get the path to the open editor tabs file
+    fn get_editor_session_file_path() -> PathBuf {
+        // Use application data directory for persistence
+        if let Some(data_dir) = dirs::data_dir() {
+            let app_dir = data_dir.join("MediaOrganizer");
+            app_dir.join("editor_session.json")
+        } else {
+            // Fallback to current directory
+            PathBuf::from("editor_session.json")
+        }
+    }
+    
+    /// Get the path to the open directory tabs file
+    fn get_directory_tabs_file_path() -> PathBuf {
+        // Use application data directory for persistence
+        if let Some(data_dir) = dirs::data_dir() {
+            let app_dir = data_dir.join("MediaOrganizer");
+            app_dir.join("directory_tabs.json")
+        } else {
+            // Fallback to current directory
+            PathBuf::from("directory_tabs.json")
+        }
+    }
+    
+    /// Get the path to the split view layout file
+    fn get_split_view_file_path() -> PathBuf {
+        // Use application data directory for persistence
+        if let Some(data_dir) = dirs::data_dir() {
+            let app_dir = data_dir.join("MediaOrganizer");
+            app_dir.join("split_view.json")
+        } else {
+            // Fallback to current directory
+            PathBuf::from("split_view.json")
+        }
+    }
+    
+    /// Get the path to the bookmarked folders file
+    fn get_bookmarks_file_path() -> PathBuf {
+        // Use application data directory for persistence
+        if let Some(data_dir) = dirs::data_dir() {
+            let app_dir = data_dir.join("MediaOrganizer");
+            app_dir.join("bookmarks.json")
+        } else {
+            // Fallback to current directory
+            PathBuf::from("bookmarks.json")
+        }
+    }
+    
+    /// Get the path to the rebound keyboard shortcuts file
+    fn get_keymap_file_path() -> PathBuf {
+        // Use application data directory for persistence
+        if let Some(data_dir) = dirs::data_dir() {
+            let app_dir = data_dir.join("MediaOrganizer");
+            app_dir.join("keymap.json")
+        } else {
+            // Fallback to current directory
+            PathBuf::from("keymap.json")
+        }
+    }
+    
+    /// Get the path to the bottom panel (Output/Terminal) file
+    fn get_bottom_panel_file_path() -> PathBuf {
+        // Use application data directory for persistence
+        if let Some(data_dir) = dirs::data_dir() {
+            let app_dir = data_dir.join("MediaOrganizer");
+            app_dir.join("bottom_panel.json")
+        } else {
+            // Fallback to current directory
+            PathBuf::from("bottom_panel.json")
+        }
+    }
+    
     /// Ensure the config directory exists
     fn ensure_config_dir(&self) -> Result<(), String> {
         if let Some(parent) = self.config_file_path.parent() {
@@ -231,6 +334,10 @@ impl PersistenceService {
     // Settings persistence methods
     
     /// Save settings state with debouncing
+    ///
+    /// A change within the debounce window of the last write is held back
+    /// until the next save or [`Self::flush_pending_settings_save`], so
+    /// shutdown must flush.
     pub fn save_settings_debounced(&mut self, settings: SettingsState) {
         self.pending_settings = Some(settings);
         
@@ -248,6 +355,11 @@ impl PersistenceService {
         }
     }
 
+    /// Whether a debounced settings change hasn't been written yet
+    pub fn has_pending_settings_save(&self) -> bool {
+        self.pending_settings.is_some()
+    }
+
     /// Force save the pending settings immediately
     pub fn flush_pending_settings_save(&mut self) {
         if let Some(settings) = self.pending_settings.take() {
@@ -273,16 +385,27 @@ impl PersistenceService {
     }
 
     /// Load settings state from storage
+    ///
+    /// Settings saved by an older version are migrated and saved again in the
+    /// current layout. A file that can't be read as settings at all is kept
+    /// as a backup beside it rather than being overwritten by the defaults.
     pub fn load_settings(&self) -> SettingsState {
         match self.read_settings_from_storage() {
             Ok(Some(json)) => {
-                match self.deserialize_settings(&json) {
-                    Ok(settings) => {
+                match self.read_settings(&json) {
+                    Ok((mut settings, migrated)) => {
                         debug!("Settings loaded successfully: theme={:?}", settings.theme);
+                        if settings.recent_folders.prune_missing(|path| path.is_dir()) {
+                            debug!("Dropped recent folders that no longer exist");
+                        }
+                        if migrated {
+                            self.save_settings_immediate(settings.clone());
+                        }
                         settings
                     }
                     Err(e) => {
                         warn!("Failed to deserialize settings, using defaults: {}", e);
+                        self.back_up_corrupt_settings();
                         SettingsState::default()
                     }
                 }
@@ -378,6 +501,256 @@ impl PersistenceService {
         self.remove_layout_from_storage()
     }
 
+    // Editor session persistence methods
+
+    /// Save the open editor tabs
+    ///
+    /// Not debounced: tabs change rarely and the last change before quitting must not be lost.
+    pub fn save_editor_session(&self, session: &EditorSession) {
+        let result = serde_json::to_string_pretty(session)
+            .map_err(|e| format!("Editor session serialization error: {}", e))
+            .and_then(|json| {
+                self.ensure_config_dir()?;
+                fs::write(&self.editor_session_file_path, json)
+                    .map_err(|e| format!("Failed to write editor session file: {}", e))
+            });
+        match result {
+            Ok(()) => debug!("Editor session saved: {} groups", session.groups.len()),
+            Err(e) => error!("Failed to save editor session: {}", e),
+        }
+    }
+
+    /// Load the editor tabs saved by the last run
+    pub fn load_editor_session(&self) -> Option<EditorSession> {
+        let json = match fs::read_to_string(&self.editor_session_file_path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return None,
+            Err(e) => {
+                warn!("Failed to read editor session: {}", e);
+                return None;
+            }
+        };
+        match serde_json::from_str(&json) {
+            Ok(session) => Some(session),
+            Err(e) => {
+                warn!("Failed to deserialize editor session, starting with default tabs: {}", e);
+                None
+            }
+        }
+    }
+
+    // Directory tab persistence methods
+
+    /// Save the open directory tabs
+    ///
+    /// Not debounced, like the editor session: the last change before quitting must not be lost.
+    pub fn save_directory_tabs(&self, session: &DirectoryTabSession) {
+        let result = serde_json::to_string_pretty(session)
+            .map_err(|e| format!("Directory tabs serialization error: {}", e))
+            .and_then(|json| {
+                self.ensure_config_dir()?;
+                fs::write(&self.directory_tabs_file_path, json)
+                    .map_err(|e| format!("Failed to write directory tabs file: {}", e))
+            });
+        match result {
+            Ok(()) => debug!("Directory tabs saved: {} tabs", session.tabs.len()),
+            Err(e) => error!("Failed to save directory tabs: {}", e),
+        }
+    }
+
+    /// Load the directory tabs saved by the last run
+    pub fn load_directory_tabs(&self) -> Option<DirectoryTabSession> {
+        let json = match fs::read_to_string(&self.directory_tabs_file_path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return None,
+            Err(e) => {
+                warn!("Failed to read directory tabs: {}", e);
+                return None;
+            }
+        };
+        match serde_json::from_str(&json) {
+            Ok(session) => Some(session),
+            Err(e) => {
+                warn!("Failed to deserialize directory tabs, starting with a single tab: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Save the split view layout and its panes
+    pub fn save_split_view(&self, session: &SplitViewSession) {
+        let result = serde_json::to_string_pretty(session)
+            .map_err(|e| format!("Split view serialization error: {}", e))
+            .and_then(|json| {
+                self.ensure_config_dir()?;
+                fs::write(&self.split_view_file_path, json)
+                    .map_err(|e| format!("Failed to write split view file: {}", e))
+            });
+        match result {
+            Ok(()) => debug!("Split view saved (enabled: {})", session.enabled),
+            Err(e) => error!("Failed to save split view: {}", e),
+        }
+    }
+
+    /// Load the split view saved by the last run
+    pub fn load_split_view(&self) -> Option<SplitViewSession> {
+        let json = match fs::read_to_string(&self.split_view_file_path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return None,
+            Err(e) => {
+                warn!("Failed to read split view: {}", e);
+                return None;
+            }
+        };
+        match serde_json::from_str(&json) {
+            Ok(session) => Some(session),
+            Err(e) => {
+                warn!("Failed to deserialize split view, starting without it: {}", e);
+                None
+            }
+        }
+    }
+
+    // Bookmark persistence methods
+
+    /// Save the bookmarked folders
+    pub fn save_bookmarks(&self, bookmarks: &BookmarksState) {
+        let result = serde_json::to_string_pretty(bookmarks)
+            .map_err(|e| format!("Bookmarks serialization error: {}", e))
+            .and_then(|json| {
+                self.ensure_config_dir()?;
+                fs::write(&self.bookmarks_file_path, json)
+                    .map_err(|e| format!("Failed to write bookmarks file: {}", e))
+            });
+        match result {
+            Ok(()) => debug!("Bookmarks saved: {} folders", bookmarks.folders.len()),
+            Err(e) => error!("Failed to save bookmarks: {}", e),
+        }
+    }
+
+    /// Load the bookmarked folders
+    pub fn load_bookmarks(&self) -> Option<BookmarksState> {
+        let json = match fs::read_to_string(&self.bookmarks_file_path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return None,
+            Err(e) => {
+                warn!("Failed to read bookmarks: {}", e);
+                return None;
+            }
+        };
+        match serde_json::from_str(&json) {
+            Ok(bookmarks) => Some(bookmarks),
+            Err(e) => {
+                warn!("Failed to deserialize bookmarks, starting without any: {}", e);
+                None
+            }
+        }
+    }
+
+    // Keymap persistence methods
+
+    /// Save the rebound keyboard shortcuts
+    pub fn save_keymap(&self, keymap: &KeymapState) {
+        let result = serde_json::to_string_pretty(keymap)
+            .map_err(|e| format!("Keymap serialization error: {}", e))
+            .and_then(|json| {
+                self.ensure_config_dir()?;
+                fs::write(&self.keymap_file_path, json)
+                    .map_err(|e| format!("Failed to write keymap file: {}", e))
+            });
+        match result {
+            Ok(()) => debug!("Keymap saved: {} rebound actions", keymap.bindings.len()),
+            Err(e) => error!("Failed to save keymap: {}", e),
+        }
+    }
+
+    /// Load the rebound keyboard shortcuts
+    pub fn load_keymap(&self) -> Option<KeymapState> {
+        let json = match fs::read_to_string(&self.keymap_file_path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return None,
+            Err(e) => {
+                warn!("Failed to read keymap: {}", e);
+                return None;
+            }
+        };
+        match serde_json::from_str(&json) {
+            Ok(keymap) => Some(keymap),
+            Err(e) => {
+                warn!("Failed to deserialize keymap, using the default shortcuts: {}", e);
+                None
+            }
+        }
+    }
+
+    // Bottom panel persistence methods
+
+    /// Save the bottom panel's visibility, open tab and height
+    ///
+    /// Callers save once a change is finished (e.g. on resize end), so this is not debounced.
+    pub fn save_bottom_panel(&self, panel: &BottomPanelState) {
+        let result = serde_json::to_string_pretty(panel)
+            .map_err(|e| format!("Bottom panel serialization error: {}", e))
+            .and_then(|json| {
+                self.ensure_config_dir()?;
+                fs::write(&self.bottom_panel_file_path, json)
+                    .map_err(|e| format!("Failed to write bottom panel file: {}", e))
+            });
+        match result {
+            Ok(()) => debug!("Bottom panel saved: tab={:?}, height={}", panel.active_tab, panel.height),
+            Err(e) => error!("Failed to save bottom panel: {}", e),
+        }
+    }
+
+    /// Load the bottom panel state saved by the last run
+    pub fn load_bottom_panel(&self) -> Option<BottomPanelState> {
+        let json = match fs::read_to_string(&self.bottom_panel_file_path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return None,
+            Err(e) => {
+                warn!("Failed to read bottom panel state: {}", e);
+                return None;
+            }
+        };
+        match serde_json::from_str::<BottomPanelState>(&json) {
+            Ok(mut panel) => {
+                panel.height = panel.height.max(panel.min_height);
+                Some(panel)
+            }
+            Err(e) => {
+                warn!("Failed to deserialize bottom panel state, using defaults: {}", e);
+                None
+            }
+        }
+    }
+
+    // Settings import
+
+    /// Save imported settings, shortcuts and bookmarks together
+    ///
+    /// Either all three files are replaced or none are. A pending debounced
+    /// settings save is dropped so it can't overwrite the import with stale values.
+    pub fn save_transferred_settings(&mut self, snapshot: &SettingsSnapshot) -> Result<(), String> {
+        self.ensure_config_dir()?;
+        let files = vec![
+            (self.settings_file_path.clone(), self.serialize_settings(&snapshot.settings)?),
+            (
+                self.keymap_file_path.clone(),
+                serde_json::to_string_pretty(&snapshot.keymap).map_err(|e| format!("Keymap serialization error: {}", e))?,
+            ),
+            (
+                self.bookmarks_file_path.clone(),
+                serde_json::to_string_pretty(&snapshot.bookmarks)
+                    .map_err(|e| format!("Bookmarks serialization error: {}", e))?,
+            ),
+        ];
+        write_files_atomically(&files)?;
+        self.pending_settings = None;
+        self.last_settings_save = Some(Instant::now());
+        debug!("Imported settings saved: theme={:?}", snapshot.settings.theme);
+        Ok(())
+    }
+
     // Private helper methods
 
     fn serialize_state(&self, state: &PanelState) -> Result<String, String> {
@@ -417,7 +790,29 @@ impl PersistenceService {
     }
 
     fn deserialize_settings(&self, json: &str) -> Result<SettingsState, String> {
-        serde_json::from_str(json).map_err(|e| format!("Settings deserialization error: {}", e))
+        self.read_settings(json).map(|(settings, _)| settings)
+    }
+
+    /// Parse saved settings, migrating older layouts; true if a migration ran
+    fn read_settings(&self, json: &str) -> Result<(SettingsState, bool), String> {
+        let value: serde_json::Value =
+            serde_json::from_str(json).map_err(|e| format!("Settings deserialization error: {}", e))?;
+        let saved_version = value.get("schema_version").and_then(serde_json::Value::as_u64).unwrap_or(1);
+        let settings = serde_json::from_value(migrate_settings(value)?)
+            .map_err(|e| format!("Settings deserialization error: {}", e))?;
+        Ok((settings, saved_version < u64::from(SETTINGS_SCHEMA_VERSION)))
+    }
+
+    /// Move an unreadable settings file aside so the defaults don't replace it
+    fn back_up_corrupt_settings(&self) {
+        let seconds = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let mut backup_name = self.settings_file_path.as_os_str().to_owned();
+        backup_name.push(format!(".corrupt-{}", seconds));
+        let backup_path = PathBuf::from(backup_name);
+        match fs::rename(&self.settings_file_path, &backup_path) {
+            Ok(()) => warn!("Kept the unreadable settings file as {}", backup_path.display()),
+            Err(e) => error!("Failed to back up the unreadable settings file: {}", e),
+        }
     }
 
     fn write_settings_to_storage(&self, json: &str) -> Result<(), String> {
@@ -521,9 +916,13 @@ pub fn load_settings() -> SettingsState {
     get_persistence_service().load_settings()
 }
 
-/// Convenience function to flush pending settings saves
-pub fn flush_pending_settings_saves() {
-    get_persistence_service().flush_pending_settings_save();
+/// Write a held-back settings change now; call on shutdown
+pub fn flush_pending_settings() {
+    let mut service = get_persistence_service();
+    if service.has_pending_settings_save() {
+        debug!("Flushing pending settings save");
+        service.flush_pending_settings_save();
+    }
 }
 
 /// Convenience function to clear saved settings
@@ -553,9 +952,182 @@ pub fn clear_layout_state() -> Result<(), String> {
     get_persistence_service().clear_layout()
 }
 
+// Editor session convenience functions
+
+/// Convenience function to save the open editor tabs
+pub fn save_editor_session(session: &EditorSession) {
+    get_persistence_service().save_editor_session(session);
+}
+
+/// Convenience function to load the saved editor tabs
+pub fn load_editor_session() -> Option<EditorSession> {
+    get_persistence_service().load_editor_session()
+}
+
+// Directory tab convenience functions
+
+/// Convenience function to save the open directory tabs
+pub fn save_directory_tabs(session: &DirectoryTabSession) {
+    get_persistence_service().save_directory_tabs(session);
+}
+
+/// Convenience function to load the saved directory tabs
+pub fn load_directory_tabs() -> Option<DirectoryTabSession> {
+    get_persistence_service().load_directory_tabs()
+}
+
+// Split view convenience functions
+
+/// Convenience function to save the split view layout
+pub fn save_split_view(session: &SplitViewSession) {
+    get_persistence_service().save_split_view(session);
+}
+
+/// Convenience function to load the saved split view layout
+pub fn load_split_view() -> Option<SplitViewSession> {
+    get_persistence_service().load_split_view()
+}
+
+// Bookmark convenience functions
+
+/// Convenience function to save the bookmarked folders
+pub fn save_bookmarks(bookmarks: &BookmarksState) {
+    get_persistence_service().save_bookmarks(bookmarks);
+}
+
+/// Convenience function to load the bookmarked folders
+pub fn load_bookmarks() -> Option<BookmarksState> {
+    get_persistence_service().load_bookmarks()
+}
+
+// Keymap convenience functions
+
+/// Convenience function to save the rebound keyboard shortcuts
+pub fn save_keymap(keymap: &KeymapState) {
+    get_persistence_service().save_keymap(keymap);
+}
+
+/// Convenience function to load the rebound keyboard shortcuts
+pub fn load_keymap() -> Option<KeymapState> {
+    get_persistence_service().load_keymap()
+}
+
+// Settings schema migrations
+
+/// Upgrade a saved settings object to [`SETTINGS_SCHEMA_VERSION`], one version at a time
+///
+/// Files without a `schema_version` predate it and count as version 1. Files
+/// from a newer version are left alone; fields this version doesn't know are
+/// ignored when they are read.
+pub fn migrate_settings(mut value: serde_json::Value) -> Result<serde_json::Value, String> {
+    let settings = value
+        .as_object_mut()
+        .ok_or_else(|| "Settings deserialization error: not a JSON object".to_string())?;
+    let version = settings
+        .get("schema_version")
+        .and_then(serde_json::Value::as_u64)
+        .unwrap_or(1)
+        .max(1);
+    if version > u64::from(SETTINGS_SCHEMA_VERSION) {
+        warn!(
+            "Settings were saved by a newer version (schema v{}, this is v{}); reading what is understood",
+            version, SETTINGS_SCHEMA_VERSION
+        );
+        return Ok(value);
+    }
+
+    for (index, migration) in SETTINGS_MIGRATIONS.iter().enumerate().skip(version as usize - 1) {
+        migration(settings);
+        let to = index as u32 + 2;
+        settings.insert("schema_version".to_string(), to.into());
+        info!("Migrated settings from schema v{} to v{}", to - 1, to);
+    }
+    Ok(value)
+}
+
+/// v1 files from early releases may lack settings that are required since;
+/// fill those in with their defaults instead of failing the whole file
+fn migrate_settings_v1_to_v2(settings: &mut serde_json::Map<String, serde_json::Value>) {
+    let Ok(serde_json::Value::Object(defaults)) = serde_json::to_value(SettingsState::default()) else {
+        return;
+    };
+    for (key, default) in defaults {
+        settings.entry(key).or_insert(default);
+    }
+}
+
+// Settings import convenience functions
+
+/// Convenience function to save imported settings, shortcuts and bookmarks together
+pub fn save_transferred_settings(snapshot: &SettingsSnapshot) -> Result<(), String> {
+    get_persistence_service().save_transferred_settings(snapshot)
+}
+
+/// Write every `(path, contents)` pair, or leave all the files as they were
+///
+/// Each file is written to a temporary file beside it and renamed into place.
+/// If any file fails, the ones already replaced get their old contents back
+/// and files that didn't exist before are removed again.
+pub fn write_files_atomically(files: &[(PathBuf, String)]) -> Result<(), String> {
+    let mut replaced: Vec<(&PathBuf, Option<Vec<u8>>)> = Vec::new();
+    for (path, contents) in files {
+        let previous = match fs::read(path) {
+            Ok(previous) => Some(previous),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => {
+                roll_back(replaced);
+                return Err(format!("Failed to read {}: {}", path.display(), e));
+            }
+        };
+        if let Err(e) = replace_file(path, contents) {
+            roll_back(replaced);
+            return Err(e);
+        }
+        replaced.push((path, previous));
+    }
+    Ok(())
+}
+
+fn replace_file(path: &std::path::Path, contents: &str) -> Result<(), String> {
+    let mut temp_name = path.as_os_str().to_owned();
+    temp_name.push(".tmp");
+    let temp_path = PathBuf::from(temp_name);
+    fs::write(&temp_path, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    fs::rename(&temp_path, path).map_err(|e| {
+        let _ = fs::remove_file(&temp_path);
+        format!("Failed to replace {}: {}", path.display(), e)
+    })
+}
+
+/// Put back the contents `write_files_atomically` replaced, newest first
+fn roll_back(replaced: Vec<(&PathBuf, Option<Vec<u8>>)>) {
+    for (path, previous) in replaced.into_iter().rev() {
+        let result = match previous {
+            Some(previous) => fs::write(path, previous),
+            None => fs::remove_file(path),
+        };
+        if let Err(e) = result {
+            error!("Failed to roll back {}: {}", path.display(), e);
+        }
+    }
+}
+
+// Bottom panel convenience functions
+
+/// Convenience function to save the bottom panel state
+pub fn save_bottom_panel(panel: &BottomPanelState) {
+    get_persistence_service().save_bottom_panel(panel);
+}
+
+/// Convenience function to load the saved bottom panel state
+pub fn load_bottom_panel() -> Option<BottomPanelState> {
+    get_persistence_service().load_bottom_panel()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::state::app_state::Theme;
 
     #[test]
     fn test_panel_state_validation() {
@@ -593,8 +1165,34 @@ mod tests {
         assert_eq!(state.version, 1);
     }
 
+    #[test]
+    fn test_custom_theme_setting_round_trip() {
+        let service = PersistenceService::new();
+        let mut settings = SettingsState::default();
+        settings.theme = Theme::Custom("solarized".to_string());
+
+        let json = service.serialize_settings(&settings).unwrap();
+        let deserialized = service.deserialize_settings(&json).unwrap();
+
+        assert_eq!(deserialized.theme, Theme::Custom("solarized".to_string()));
+    }
+
     // Layout state persistence integration tests
     
+    #[test]
+    fn test_keymap_persistence_round_trip() {
+        let service = PersistenceService::new();
+        let mut keymap = KeymapState::default();
+        keymap.set("copy", vec!["CmdOrCtrl+Shift+C".to_string()]);
+        keymap.set("refresh", Vec::new());
+
+        service.save_keymap(&keymap);
+        assert_eq!(service.load_keymap(), Some(keymap));
+
+        service.save_keymap(&KeymapState::default());
+        assert_eq!(service.load_keymap(), Some(KeymapState::default()));
+    }
+    
     #[test]
     fn test_layout_state_serialization() {
         let service = PersistenceService::new();
@@ -778,7 +1376,7 @@ mod tests {
                 if i == 4 {
                     // Last thread flushes
                     flush_pending_layout_saves();
-                    flush_pending_settings_saves();
+                    flush_pending_settings();
                 }
             })
         }).collect();
@@ -794,4 +1392,122 @@ mod tests {
         assert_eq!(layout_state.theme, loaded_layout.theme);
         assert_eq!(settings_state.theme, loaded_settings.theme);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_write_files_atomically_rolls_back_on_failure() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let existing = temp_dir.path().join("settings.json");
+        let created = temp_dir.path().join("keymap.json");
+        fs::write(&existing, "old").unwrap();
+        
+        let unwritable = temp_dir.path().join("missing").join("bookmarks.json");
+        let files = vec![
+            (existing.clone(), "new".to_string()),
+            (created.clone(), "new".to_string()),
+            (unwritable, "new".to_string()),
+        ];
+        assert!(write_files_atomically(&files).is_err());
+        assert_eq!(fs::read_to_string(&existing).unwrap(), "old");
+        assert!(!created.exists());
+        
+        assert!(write_files_atomically(&files[..2]).is_ok());
+        assert_eq!(fs::read_to_string(&existing).unwrap(), "new");
+        assert_eq!(fs::read_to_string(&created).unwrap(), "new");
+    }
+    
+    #[test]
+    fn test_v1_settings_migrate_without_data_loss() {
+        // Shape of a settings file from before schema versions, missing every
+        // field added since and some that were required later
+        let v1 = r##"{
+            "theme": "Light",
+            "font_family": "Inter",
+            "default_panel_width": 420.0,
+            "default_view_mode": "Grid",
+            "show_hidden_files": true,
+            "last_opened_folder": "/home/me/photos",
+            "custom_css_variables": { "--vscode-accent": "#ff0000" },
+            "custom_commands": [{ "name": "Sync", "command": "rsync -a . /backup" }]
+        }"##;
+        let service = PersistenceService::new();
+        let (settings, migrated) = service.read_settings(v1).unwrap();
+        
+        assert!(migrated);
+        assert_eq!(settings.schema_version, SETTINGS_SCHEMA_VERSION);
+        assert_eq!(settings.theme, Theme::Light);
+        assert_eq!(settings.font_family, crate::state::FontFamily::Inter);
+        assert_eq!(settings.default_panel_width, 420.0);
+        assert_eq!(settings.default_view_mode, crate::state::ViewMode::Grid);
+        assert!(settings.show_hidden_files);
+        assert_eq!(settings.last_opened_folder, Some(PathBuf::from("/home/me/photos")));
+        assert_eq!(settings.custom_css_variables.get("--vscode-accent").map(String::as_str), Some("#ff0000"));
+        assert_eq!(settings.custom_commands.len(), 1);
+        // Missing fields come from the defaults
+        let defaults = SettingsState::default();
+        assert_eq!(settings.font_size, defaults.font_size);
+        assert_eq!(settings.auto_save_interval, defaults.auto_save_interval);
+        assert_eq!(settings.file_sort, defaults.file_sort);
+        
+        // Settings saved in the current layout are read as they are
+        let json = service.serialize_settings(&settings).unwrap();
+        let (reloaded, migrated) = service.read_settings(&json).unwrap();
+        assert!(!migrated);
+        assert_eq!(reloaded.theme, Theme::Light);
+    }
+    
+    #[test]
+    fn test_unreadable_settings_are_backed_up() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut service = PersistenceService::new();
+        service.config_file_path = temp_dir.path().join("panel_state.json");
+        service.settings_file_path = temp_dir.path().join("settings.json");
+        fs::write(&service.settings_file_path, "{ not json").unwrap();
+        
+        let settings = service.load_settings();
+        assert_eq!(settings.schema_version, SETTINGS_SCHEMA_VERSION);
+        assert!(!service.settings_file_path.exists());
+        let backups: Vec<String> = fs::read_dir(temp_dir.path())
+            .unwrap()
+            .map(|entry| fs::read_to_string(entry.unwrap().path()).unwrap())
+            .collect();
+        assert_eq!(backups, vec!["{ not json".to_string()]);
+    }
+    
+    #[test]
+    fn test_recent_folders_that_no_longer_exist_are_pruned_on_load() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut service = PersistenceService::new();
+        service.config_file_path = temp_dir.path().join("panel_state.json");
+        service.settings_file_path = temp_dir.path().join("settings.json");
+        let kept = temp_dir.path().join("kept");
+        fs::create_dir(&kept).unwrap();
+        
+        let mut settings = SettingsState::default();
+        settings.recent_folders.record(kept.clone());
+        settings.recent_folders.record(temp_dir.path().join("deleted"));
+        service.save_settings_immediate(settings);
+        
+        assert_eq!(service.load_settings().recent_folders.folders(), [kept]);
+    }
+    
+    #[test]
+    fn test_flush_writes_the_latest_debounced_settings() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut service = PersistenceService::new();
+        service.config_file_path = temp_dir.path().join("panel_state.json");
+        service.settings_file_path = temp_dir.path().join("settings.json");
+        
+        for theme in [Theme::Light, Theme::HighContrast, Theme::Auto] {
+            let mut settings = SettingsState::default();
+            settings.theme = theme;
+            service.save_settings_debounced(settings);
+        }
+        // Only the first change went out; the rest wait for the debounce
+        assert!(service.has_pending_settings_save());
+        assert_eq!(service.load_settings().theme, Theme::Light);
+        
+        service.flush_pending_settings_save();
+        assert!(!service.has_pending_settings_save());
+        assert_eq!(service.load_settings().theme, Theme::Auto);
+    }
+}
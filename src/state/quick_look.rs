@@ -0,0 +1,175 @@
+//! Quick Look: a large preview of the selection opened with Space
+//!
+//! The state only tracks which file of the folder is shown; the overlay loads
+//! previews for the current file and its neighbors.
+
+use std::path::{Path, PathBuf};
+
+/// Files on each side of the current one whose previews are loaded ahead
+pub const QUICK_LOOK_PREFETCH_RADIUS: usize = 2;
+
+/// Open/step/close state of the Quick Look overlay
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QuickLookState {
+    pub is_open: bool,
+    /// Files stepped through, in display order: the selection if several
+    /// files were selected, otherwise the whole folder
+    pub items: Vec<PathBuf>,
+    pub index: usize,
+    /// How many items were selected when it opened
+    pub selection_count: usize,
+}
+
+/// `index` moved `delta` places through `len` items, wrapping around at either end
+pub fn wrapped_index(index: usize, delta: isize, len: usize) -> usize {
+    if len == 0 {
+        return 0;
+    }
+    let len = len as isize;
+    (index as isize + delta % len).rem_euclid(len) as usize
+}
+
+impl QuickLookState {
+    /// Open on the first of `selected` that is one of `items`; false if none is
+    ///
+    /// With several of `items` selected only those are stepped through.
+    pub fn open(&mut self, items: Vec<PathBuf>, selected: &[PathBuf]) -> bool {
+        let Some(first) = selected.iter().find(|path| items.contains(path)) else {
+            return false;
+        };
+        let first = first.clone();
+        let selected_items: Vec<PathBuf> = items.iter().filter(|item| selected.contains(item)).cloned().collect();
+        self.selection_count = selected_items.len();
+        self.items = if selected_items.len() > 1 { selected_items } else { items };
+        self.index = self.items.iter().position(|item| *item == first).unwrap_or_default();
+        self.is_open = true;
+        true
+    }
+
+    pub fn close(&mut self) {
+        *self = Self::default();
+    }
+
+    /// File being previewed
+    pub fn current(&self) -> Option<&Path> {
+        if !self.is_open {
+            return None;
+        }
+        self.items.get(self.index).map(PathBuf::as_path)
+    }
+
+    /// Move `delta` files, wrapping from the last file to the first and back
+    pub fn step(&mut self, delta: isize) -> bool {
+        if !self.is_open || self.items.is_empty() {
+            return false;
+        }
+        let next = wrapped_index(self.index, delta, self.items.len());
+        let moved = next != self.index;
+        self.index = next;
+        moved
+    }
+
+    /// Apply a key pressed while open; returns whether it was used
+    pub fn handle_key(&mut self, key: &str) -> bool {
+        match key {
+            " " | "Escape" => {
+                self.close();
+                true
+            }
+            "ArrowLeft" | "ArrowUp" => {
+                self.step(-1);
+                true
+            }
+            "ArrowRight" | "ArrowDown" => {
+                self.step(1);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Current file and up to `radius` files on each side, nearest first
+    ///
+    /// Neighbors wrap around like stepping does, and no file is listed twice.
+    pub fn prefetch_window(&self, radius: usize) -> Vec<PathBuf> {
+        let Some(current) = self.current() else {
+            return Vec::new();
+        };
+        let mut window = vec![current.to_path_buf()];
+        for distance in 1..=radius as isize {
+            for delta in [distance, -distance] {
+                let neighbor = &self.items[wrapped_index(self.index, delta, self.items.len())];
+                if !window.contains(neighbor) {
+                    window.push(neighbor.clone());
+                }
+            }
+        }
+        window
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn folder() -> Vec<PathBuf> {
+        ["a.jpg", "b.jpg", "c.jpg", "d.jpg"].iter().map(PathBuf::from).collect()
+    }
+
+    #[test]
+    fn test_wrapped_index() {
+        assert_eq!(wrapped_index(0, 1, 4), 1);
+        assert_eq!(wrapped_index(3, 1, 4), 0);
+        assert_eq!(wrapped_index(0, -1, 4), 3);
+        assert_eq!(wrapped_index(2, -9, 4), 1);
+        assert_eq!(wrapped_index(1, 10, 4), 3);
+        assert_eq!(wrapped_index(0, 1, 1), 0);
+        assert_eq!(wrapped_index(0, -1, 0), 0);
+    }
+
+    #[test]
+    fn test_open_step_and_close() {
+        let mut state = QuickLookState::default();
+        assert!(!state.open(folder(), &[PathBuf::from("missing.jpg")]));
+        assert!(!state.is_open);
+
+        // One selected file steps through the whole folder, wrapping at the ends
+        assert!(state.open(folder(), &[PathBuf::from("d.jpg")]));
+        assert_eq!(state.selection_count, 1);
+        assert_eq!(
+            state.prefetch_window(1),
+            vec![PathBuf::from("d.jpg"), PathBuf::from("a.jpg"), PathBuf::from("c.jpg")]
+        );
+        assert!(state.handle_key("ArrowRight"));
+        assert_eq!(state.current(), Some(Path::new("a.jpg")));
+        state.handle_key("ArrowUp");
+        state.handle_key("ArrowUp");
+        assert_eq!(state.current(), Some(Path::new("c.jpg")));
+
+        assert!(!state.handle_key("Enter"));
+        assert!(state.handle_key(" "));
+        assert!(!state.is_open);
+        assert_eq!(state.current(), None);
+
+        state.open(folder(), &[PathBuf::from("a.jpg")]);
+        assert!(state.handle_key("Escape"));
+        assert!(!state.is_open);
+    }
+
+    #[test]
+    fn test_several_selected_files_step_through_the_selection_only() {
+        let mut state = QuickLookState::default();
+        assert!(state.open(folder(), &[PathBuf::from("d.jpg"), PathBuf::from("b.jpg"), PathBuf::from("gone.jpg")]));
+        assert_eq!(state.selection_count, 2);
+        assert_eq!(state.items, vec![PathBuf::from("b.jpg"), PathBuf::from("d.jpg")]);
+        assert_eq!(state.current(), Some(Path::new("d.jpg")));
+        assert_eq!(state.prefetch_window(2), vec![PathBuf::from("d.jpg"), PathBuf::from("b.jpg")]);
+
+        assert!(state.step(1));
+        assert_eq!(state.current(), Some(Path::new("b.jpg")));
+        assert!(state.step(1));
+        assert_eq!(state.current(), Some(Path::new("d.jpg")));
+        assert!(!state.step(2), "a full lap ends where it started");
+        assert_eq!(state.selection_count, 2);
+    }
+}
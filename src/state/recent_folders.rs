@@ -0,0 +1,106 @@
+//! Recently opened folders, offered in File > Open Recent and when no folder is open
+//!
+//! The list is most recent first, holds each folder once and is capped at
+//! [`MAX_RECENT_FOLDERS`]; opening a folder again moves it to the front.
+//! Folders that are gone by the time settings load are dropped; ones that go
+//! missing while the app runs stay listed, disabled, until removed.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// How many folders the list keeps
+pub const MAX_RECENT_FOLDERS: usize = 10;
+
+/// Recently opened folders, most recent first
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RecentFoldersState {
+    folders: Vec<PathBuf>,
+}
+
+impl RecentFoldersState {
+    pub fn folders(&self) -> &[PathBuf] {
+        &self.folders
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.folders.is_empty()
+    }
+
+    /// Put `path` at the front, dropping an earlier entry for it and the oldest past the cap
+    pub fn record(&mut self, path: PathBuf) {
+        self.folders.retain(|folder| *folder != path);
+        self.folders.insert(0, path);
+        self.folders.truncate(MAX_RECENT_FOLDERS);
+    }
+
+    pub fn remove(&mut self, path: &Path) -> bool {
+        let before = self.folders.len();
+        self.folders.retain(|folder| folder != path);
+        self.folders.len() != before
+    }
+
+    pub fn clear(&mut self) {
+        self.folders.clear();
+    }
+
+    /// Drop folders that fail `dir_exists`; true if any were dropped
+    pub fn prune_missing(&mut self, dir_exists: impl Fn(&Path) -> bool) -> bool {
+        let before = self.folders.len();
+        self.folders.retain(|folder| dir_exists(folder));
+        self.folders.len() != before
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_keeps_most_recent_first_without_duplicates() {
+        let mut recent = RecentFoldersState::default();
+        recent.record(PathBuf::from("/photos"));
+        recent.record(PathBuf::from("/music"));
+        recent.record(PathBuf::from("/photos"));
+
+        assert_eq!(recent.folders(), [PathBuf::from("/photos"), PathBuf::from("/music")]);
+        assert!(recent.remove(Path::new("/music")));
+        assert!(!recent.remove(Path::new("/music")));
+        assert_eq!(recent.folders(), [PathBuf::from("/photos")]);
+    }
+
+    #[test]
+    fn test_record_drops_the_oldest_past_the_cap() {
+        let mut recent = RecentFoldersState::default();
+        for index in 0..MAX_RECENT_FOLDERS + 3 {
+            recent.record(PathBuf::from(format!("/folder-{}", index)));
+        }
+
+        assert_eq!(recent.folders().len(), MAX_RECENT_FOLDERS);
+        assert_eq!(recent.folders()[0], PathBuf::from(format!("/folder-{}", MAX_RECENT_FOLDERS + 2)));
+        assert_eq!(recent.folders()[MAX_RECENT_FOLDERS - 1], PathBuf::from("/folder-3"));
+    }
+
+    #[test]
+    fn test_prune_missing_keeps_order_of_the_rest() {
+        let mut recent = RecentFoldersState::default();
+        for folder in ["/gone", "/docs", "/also-gone", "/photos"] {
+            recent.record(PathBuf::from(folder));
+        }
+
+        assert!(recent.prune_missing(|path| !path.to_string_lossy().contains("gone")));
+        assert_eq!(recent.folders(), [PathBuf::from("/photos"), PathBuf::from("/docs")]);
+        assert!(!recent.prune_missing(|_| true));
+    }
+
+    #[test]
+    fn test_round_trips_as_a_plain_list() {
+        let mut recent = RecentFoldersState::default();
+        recent.record(PathBuf::from("/docs"));
+        recent.record(PathBuf::from("/photos"));
+
+        let json = serde_json::to_string(&recent).unwrap();
+        assert_eq!(json, r#"["/photos","/docs"]"#);
+        assert_eq!(serde_json::from_str::<RecentFoldersState>(&json).unwrap(), recent);
+    }
+}
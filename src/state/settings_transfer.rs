@@ -0,0 +1,305 @@
+//! Settings export and import: one portable JSON file for moving preferences
+//! between machines
+//!
+//! The file carries the theme, custom CSS variables, keyboard shortcuts, view
+//! preferences and bookmarks, but nothing tied to this machine such as the
+//! last opened folder. Fields the file doesn't have get their defaults and
+//! fields this version doesn't know are ignored, so files written by newer
+//! releases of the same format still import.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+use crate::state::app_state::{
+    DoubleClickSettings, FileSort, FileTypeFilter, FontFamily, FontSize, SettingsState, Theme, ThumbnailShape,
+    ThumbnailSize, ViewMode,
+};
+use crate::state::bookmarks::BookmarksState;
+use crate::state::keymap::KeymapState;
+use crate::state::persistence::{save_transferred_settings, write_files_atomically};
+
+/// Current settings export format
+pub const SETTINGS_EXPORT_VERSION: u32 = 1;
+
+/// Panel widths the layout accepts
+const PANEL_WIDTH_RANGE: std::ops::RangeInclusive<f64> = 200.0..=600.0;
+
+/// Errors that can occur while exporting or importing settings
+#[derive(Debug, Error)]
+pub enum SettingsTransferError {
+    #[error("Settings file I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Settings file is not valid JSON: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Not a settings export")]
+    NotSettingsFile,
+
+    #[error("Settings file version {found} is newer than this app supports (version {supported})")]
+    UnsupportedVersion { found: u64, supported: u32 },
+
+    #[error("Invalid settings: {0}")]
+    Invalid(String),
+
+    #[error("Failed to save imported settings: {0}")]
+    Save(String),
+}
+
+pub type SettingsTransferResult<T> = Result<T, SettingsTransferError>;
+
+/// The persisted state an export is taken from and an import is applied to
+#[derive(Debug, Clone, Default)]
+pub struct SettingsSnapshot {
+    pub settings: SettingsState,
+    pub keymap: KeymapState,
+    pub bookmarks: BookmarksState,
+}
+
+/// Layout and listing preferences
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ViewPreferences {
+    pub default_view_mode: ViewMode,
+    pub show_hidden_files: bool,
+    pub file_sort: FileSort,
+    pub file_type_filter: FileTypeFilter,
+    pub thumbnail_shape: ThumbnailShape,
+    pub thumbnail_size: ThumbnailSize,
+    pub show_thumbnail_badges: bool,
+    pub default_panel_width: f64,
+    pub enable_animations: bool,
+    pub double_click: DoubleClickSettings,
+}
+
+impl Default for ViewPreferences {
+    fn default() -> Self {
+        Self::from(&SettingsState::default())
+    }
+}
+
+impl From<&SettingsState> for ViewPreferences {
+    fn from(settings: &SettingsState) -> Self {
+        Self {
+            default_view_mode: settings.default_view_mode.clone(),
+            show_hidden_files: settings.show_hidden_files,
+            file_sort: settings.file_sort,
+            file_type_filter: settings.file_type_filter.clone(),
+            thumbnail_shape: settings.thumbnail_shape,
+            thumbnail_size: settings.thumbnail_size,
+            show_thumbnail_badges: settings.show_thumbnail_badges,
+            default_panel_width: settings.default_panel_width,
+            enable_animations: settings.enable_animations,
+            double_click: settings.double_click,
+        }
+    }
+}
+
+/// Contents of a settings export file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SettingsExport {
+    pub version: u32,
+    pub theme: Theme,
+    pub font_family: FontFamily,
+    pub font_size: FontSize,
+    pub custom_css_variables: BTreeMap<String, String>,
+    /// Shortcut overrides by action id, as in [`KeymapState`]
+    pub keymap: BTreeMap<String, Vec<String>>,
+    pub view: ViewPreferences,
+    pub bookmarks: Vec<PathBuf>,
+}
+
+impl Default for SettingsExport {
+    fn default() -> Self {
+        Self::capture(&SettingsSnapshot::default())
+    }
+}
+
+impl SettingsExport {
+    /// The portable part of `snapshot`
+    pub fn capture(snapshot: &SettingsSnapshot) -> Self {
+        let settings = &snapshot.settings;
+        Self {
+            version: SETTINGS_EXPORT_VERSION,
+            theme: settings.theme.clone(),
+            font_family: settings.font_family.clone(),
+            font_size: settings.font_size.clone(),
+            custom_css_variables: settings.custom_css_variables.clone().into_iter().collect(),
+            keymap: snapshot.keymap.bindings.clone(),
+            view: ViewPreferences::from(settings),
+            bookmarks: snapshot.bookmarks.folders.clone(),
+        }
+    }
+
+    /// `current` with everything this export carries replaced
+    pub fn apply_to(&self, current: &SettingsSnapshot) -> SettingsSnapshot {
+        let mut settings = current.settings.clone();
+        settings.theme = self.theme.clone();
+        settings.font_family = self.font_family.clone();
+        settings.font_size = self.font_size.clone();
+        settings.custom_css_variables = self.custom_css_variables.clone().into_iter().collect();
+        settings.default_view_mode = self.view.default_view_mode.clone();
+        settings.show_hidden_files = self.view.show_hidden_files;
+        settings.file_sort = self.view.file_sort;
+        settings.file_type_filter = self.view.file_type_filter.clone();
+        settings.thumbnail_shape = self.view.thumbnail_shape;
+        settings.thumbnail_size = self.view.thumbnail_size;
+        settings.show_thumbnail_badges = self.view.show_thumbnail_badges;
+        settings.default_panel_width = self.view.default_panel_width;
+        settings.enable_animations = self.view.enable_animations;
+        settings.double_click = self.view.double_click;
+
+        SettingsSnapshot {
+            settings,
+            keymap: KeymapState { bindings: self.keymap.clone(), ..KeymapState::default() },
+            bookmarks: BookmarksState { folders: self.bookmarks.clone(), ..BookmarksState::default() },
+        }
+    }
+
+    pub fn to_json(&self) -> SettingsTransferResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Parse and validate an export
+    ///
+    /// The version is checked before anything else, so a file from a newer
+    /// format fails with [`SettingsTransferError::UnsupportedVersion`] rather
+    /// than a confusing field error.
+    pub fn from_json(json: &str) -> SettingsTransferResult<Self> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        let version = value
+            .get("version")
+            .and_then(serde_json::Value::as_u64)
+            .filter(|version| *version > 0)
+            .ok_or(SettingsTransferError::NotSettingsFile)?;
+        if version > u64::from(SETTINGS_EXPORT_VERSION) {
+            return Err(SettingsTransferError::UnsupportedVersion {
+                found: version,
+                supported: SETTINGS_EXPORT_VERSION,
+            });
+        }
+
+        let export: Self = serde_json::from_value(value)?;
+        export.validate()?;
+        Ok(export)
+    }
+
+    /// Reject values that would break the layout or the stylesheet
+    ///
+    /// Shortcut overrides aren't checked here: unreadable ones are skipped
+    /// when the keymap is applied.
+    pub fn validate(&self) -> SettingsTransferResult<()> {
+        if !PANEL_WIDTH_RANGE.contains(&self.view.default_panel_width) {
+            return Err(SettingsTransferError::Invalid(format!(
+                "panel width {} is outside {}..={}",
+                self.view.default_panel_width,
+                PANEL_WIDTH_RANGE.start(),
+                PANEL_WIDTH_RANGE.end()
+            )));
+        }
+        for (name, value) in &self.custom_css_variables {
+            if !name.starts_with("--") || name.len() < 3 {
+                return Err(SettingsTransferError::Invalid(format!("{:?} is not a CSS variable name", name)));
+            }
+            if value.contains([';', '{', '}']) {
+                return Err(SettingsTransferError::Invalid(format!("CSS variable {} has an unsafe value", name)));
+            }
+        }
+        if let Some(folder) = self.bookmarks.iter().find(|folder| !folder.is_absolute()) {
+            return Err(SettingsTransferError::Invalid(format!(
+                "bookmark {} is not an absolute path",
+                folder.display()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Write the portable part of `snapshot` to `path`
+pub fn export_settings(path: &Path, snapshot: &SettingsSnapshot) -> SettingsTransferResult<()> {
+    let json = SettingsExport::capture(snapshot).to_json()?;
+    write_files_atomically(&[(path.to_path_buf(), json)]).map_err(SettingsTransferError::Save)
+}
+
+/// Apply the export at `path` on top of `current` and save the result
+///
+/// Nothing is saved unless the whole file is valid, and the settings,
+/// shortcuts and bookmarks files are replaced together: if one can't be
+/// written, the ones already written are rolled back. Returns the new state
+/// for the caller to show.
+pub fn import_settings(path: &Path, current: &SettingsSnapshot) -> SettingsTransferResult<SettingsSnapshot> {
+    let export = SettingsExport::from_json(&std::fs::read_to_string(path)?)?;
+    let imported = export.apply_to(current);
+    save_transferred_settings(&imported).map_err(SettingsTransferError::Save)?;
+    Ok(imported)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::app_state::SortKey;
+
+    fn customized() -> SettingsSnapshot {
+        let mut snapshot = SettingsSnapshot::default();
+        snapshot.settings.theme = Theme::Light;
+        snapshot.settings.show_hidden_files = true;
+        snapshot.settings.file_sort.key = SortKey::Size;
+        snapshot.settings.default_panel_width = 420.0;
+        snapshot.settings.last_opened_folder = Some(PathBuf::from("/home/me/photos"));
+        snapshot.settings.custom_css_variables.insert("--vscode-font-size".to_string(), "15px".to_string());
+        snapshot.keymap.set("copy", vec!["CmdOrCtrl+Shift+C".to_string()]);
+        snapshot.bookmarks.add(PathBuf::from("/photos"));
+        snapshot
+    }
+
+    #[test]
+    fn test_export_round_trip() {
+        let source = customized();
+        let json = SettingsExport::capture(&source).to_json().unwrap();
+        let export = SettingsExport::from_json(&json).unwrap();
+        assert_eq!(export, SettingsExport::capture(&source));
+
+        let imported = export.apply_to(&SettingsSnapshot::default());
+        assert_eq!(imported.settings.theme, Theme::Light);
+        assert!(imported.settings.show_hidden_files);
+        assert_eq!(imported.settings.file_sort.key, SortKey::Size);
+        assert_eq!(imported.settings.default_panel_width, 420.0);
+        assert_eq!(imported.settings.custom_css_variables, source.settings.custom_css_variables);
+        assert_eq!(imported.keymap, source.keymap);
+        assert_eq!(imported.bookmarks, source.bookmarks);
+        // Machine-specific settings stay as they were
+        assert_eq!(imported.settings.last_opened_folder, None);
+    }
+
+    #[test]
+    fn test_import_defaults_missing_fields_and_ignores_unknown_ones() {
+        let json = r#"{ "version": 1, "theme": "Light", "sidebar_sparkles": true, "view": { "show_hidden_files": true } }"#;
+        let export = SettingsExport::from_json(json).unwrap();
+        assert_eq!(export.theme, Theme::Light);
+        assert!(export.view.show_hidden_files);
+        assert_eq!(export.view.default_panel_width, ViewPreferences::default().default_panel_width);
+        assert!(export.keymap.is_empty() && export.bookmarks.is_empty());
+    }
+
+    #[test]
+    fn test_import_rejects_other_versions_and_invalid_values() {
+        assert!(matches!(
+            SettingsExport::from_json(r#"{ "version": 2, "theme": "Light" }"#),
+            Err(SettingsTransferError::UnsupportedVersion { found: 2, supported: 1 })
+        ));
+        assert!(matches!(SettingsExport::from_json(r#"{ "theme": "Light" }"#), Err(SettingsTransferError::NotSettingsFile)));
+        assert!(matches!(SettingsExport::from_json("[1, 2]"), Err(SettingsTransferError::NotSettingsFile)));
+
+        for json in [
+            r#"{ "version": 1, "view": { "default_panel_width": 5000.0 } }"#,
+            r#"{ "version": 1, "custom_css_variables": { "color": "red" } }"#,
+            r#"{ "version": 1, "custom_css_variables": { "--accent": "red; } body { display: none" } }"#,
+            r#"{ "version": 1, "bookmarks": ["relative/folder"] }"#,
+        ] {
+            assert!(matches!(SettingsExport::from_json(json), Err(SettingsTransferError::Invalid(_))), "{}", json);
+        }
+    }
+}
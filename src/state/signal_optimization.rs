@@ -353,6 +353,14 @@ impl OptimizedCssGenerator {
                 crate::state::Theme::HighContrast => {
                     "--bg-primary: #000000; --bg-secondary: #111111; --text-primary: #ffffff; --text-secondary: #cccccc; --accent: #ffff00;".to_string()
                 },
+                crate::state::Theme::Custom(id) => {
+                    let mut variables: Vec<String> = crate::theme::custom_theme::custom_themes()
+                        .get(id)
+                        .map(|theme| theme.variables.iter().map(|(name, value)| format!("{}: {};", name, value)).collect())
+                        .unwrap_or_default();
+                    variables.sort();
+                    variables.join(" ")
+                },
             }
         })
     }
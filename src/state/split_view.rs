@@ -0,0 +1,342 @@
+//! Split view: two folders side by side, each in its own file pane
+//!
+//! Each pane has its own navigation and selection, independent of each other
+//! and of the main file list. The layout reuses the editor group concepts: a
+//! `SplitHorizontal` split puts the panes left and right, `SplitVertical`
+//! stacks them. Only the layout, pane folders and selections are saved
+//! between runs; directory listings are loaded again.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::services::FileEntry;
+use crate::state::app_state::{EditorGroupPosition, EditorLayoutConfig};
+use crate::state::navigation::{NavigationState, SelectionMode, SelectionState};
+
+/// Current split view session file format
+const SESSION_VERSION: u32 = 1;
+
+/// Smallest share of the space either pane can be given
+pub const MIN_PANE_RATIO: f32 = 0.2;
+
+/// One of the two panes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PaneSide {
+    /// Left, or top in a vertical split
+    Primary,
+    /// Right, or bottom in a vertical split
+    Secondary,
+}
+
+impl PaneSide {
+    pub const ALL: [PaneSide; 2] = [PaneSide::Primary, PaneSide::Secondary];
+
+    fn index(self) -> usize {
+        match self {
+            PaneSide::Primary => 0,
+            PaneSide::Secondary => 1,
+        }
+    }
+
+    /// The pane across from this one
+    pub fn other(self) -> Self {
+        match self {
+            PaneSide::Primary => PaneSide::Secondary,
+            PaneSide::Secondary => PaneSide::Primary,
+        }
+    }
+}
+
+/// A folder shown in one pane and what is selected in it
+#[derive(Debug, Clone)]
+pub struct FilePane {
+    pub navigation: NavigationState,
+    pub selection: SelectionState,
+}
+
+impl FilePane {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            navigation: NavigationState::new(Some(path)),
+            selection: SelectionState::new(),
+        }
+    }
+
+    pub fn current_path(&self) -> &Path {
+        &self.navigation.current_path
+    }
+
+    /// Loaded listing of the shown folder; empty until it has been loaded
+    pub fn entries(&self) -> Vec<FileEntry> {
+        self.navigation
+            .get_directory_contents(&self.navigation.current_path)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// Split view mode and its two panes
+#[derive(Debug, Clone)]
+pub struct SplitViewState {
+    pub enabled: bool,
+    layout: EditorLayoutConfig,
+    /// Share of the space given to the primary pane
+    ratio: f32,
+    panes: [FilePane; 2],
+    pub active: PaneSide,
+}
+
+impl Default for SplitViewState {
+    fn default() -> Self {
+        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
+        Self::new(home.clone(), home)
+    }
+}
+
+impl SplitViewState {
+    /// Panes showing `primary` and `secondary` side by side, not yet enabled
+    pub fn new(primary: PathBuf, secondary: PathBuf) -> Self {
+        Self {
+            enabled: false,
+            layout: EditorLayoutConfig::SplitHorizontal,
+            ratio: 0.5,
+            panes: [FilePane::new(primary), FilePane::new(secondary)],
+            active: PaneSide::Primary,
+        }
+    }
+
+    pub fn pane(&self, side: PaneSide) -> &FilePane {
+        &self.panes[side.index()]
+    }
+
+    pub fn pane_mut(&mut self, side: PaneSide) -> &mut FilePane {
+        &mut self.panes[side.index()]
+    }
+
+    pub fn layout(&self) -> &EditorLayoutConfig {
+        &self.layout
+    }
+
+    /// Use `layout`; only the two-group splits apply to two panes
+    pub fn set_layout(&mut self, layout: EditorLayoutConfig) -> bool {
+        match layout {
+            EditorLayoutConfig::SplitHorizontal | EditorLayoutConfig::SplitVertical => {
+                self.layout = layout;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Switch between side by side and stacked panes
+    pub fn toggle_orientation(&mut self) {
+        self.layout = match self.layout {
+            EditorLayoutConfig::SplitHorizontal => EditorLayoutConfig::SplitVertical,
+            _ => EditorLayoutConfig::SplitHorizontal,
+        };
+    }
+
+    pub fn ratio(&self) -> f32 {
+        self.ratio
+    }
+
+    /// Give the primary pane `ratio` of the space, keeping both panes usable
+    pub fn set_ratio(&mut self, ratio: f32) {
+        self.ratio = if ratio.is_finite() {
+            ratio.clamp(MIN_PANE_RATIO, 1.0 - MIN_PANE_RATIO)
+        } else {
+            0.5
+        };
+    }
+
+    /// Where each pane goes, primary first, as fractions of the available space
+    pub fn positions(&self) -> [EditorGroupPosition; 2] {
+        let ratio = self.ratio;
+        match self.layout {
+            EditorLayoutConfig::SplitVertical => [
+                EditorGroupPosition { x: 0.0, y: 0.0, width: 1.0, height: ratio },
+                EditorGroupPosition { x: 0.0, y: ratio, width: 1.0, height: 1.0 - ratio },
+            ],
+            _ => [
+                EditorGroupPosition { x: 0.0, y: 0.0, width: ratio, height: 1.0 },
+                EditorGroupPosition { x: ratio, y: 0.0, width: 1.0 - ratio, height: 1.0 },
+            ],
+        }
+    }
+}
+
+/// Snapshot of the split layout and its panes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SplitViewSession {
+    pub version: u32,
+    pub enabled: bool,
+    pub layout: EditorLayoutConfig,
+    pub ratio: f32,
+    pub panes: [SessionPane; 2],
+    pub active: PaneSide,
+}
+
+/// One pane in a saved split view
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionPane {
+    pub path: PathBuf,
+    pub selected: Vec<PathBuf>,
+}
+
+impl SplitViewSession {
+    /// Snapshot the parts of `state` worth restoring
+    pub fn capture(state: &SplitViewState) -> Self {
+        let pane = |side| {
+            let pane = state.pane(side);
+            let mut selected = pane.selection.get_selected_paths();
+            selected.sort();
+            SessionPane { path: pane.navigation.current_path.clone(), selected }
+        };
+        Self {
+            version: SESSION_VERSION,
+            enabled: state.enabled,
+            layout: state.layout.clone(),
+            ratio: state.ratio,
+            panes: [pane(PaneSide::Primary), pane(PaneSide::Secondary)],
+            active: state.active,
+        }
+    }
+
+    /// Rebuild the split view, dropping panes whose folder no longer passes `dir_exists`
+    ///
+    /// A pane whose folder is gone shows the other pane's folder instead and
+    /// selected paths that are gone are dropped. Returns `None` when neither
+    /// folder is left.
+    pub fn restore(self, dir_exists: impl Fn(&Path) -> bool) -> Option<SplitViewState> {
+        let [primary, secondary] = self.panes;
+        let (primary_path, secondary_path) = match (dir_exists(&primary.path), dir_exists(&secondary.path)) {
+            (true, true) => (primary.path.clone(), secondary.path.clone()),
+            (true, false) => (primary.path.clone(), primary.path.clone()),
+            (false, true) => (secondary.path.clone(), secondary.path.clone()),
+            (false, false) => return None,
+        };
+
+        let mut state = SplitViewState::new(primary_path, secondary_path);
+        for (side, saved) in PaneSide::ALL.into_iter().zip([primary, secondary]) {
+            let pane = state.pane_mut(side);
+            if pane.navigation.current_path != saved.path {
+                continue;
+            }
+            let selected: Vec<PathBuf> = saved.selected.into_iter().filter(|path| path.exists()).collect();
+            if !selected.is_empty() {
+                pane.selection.select_files(selected, SelectionMode::Replace);
+            }
+        }
+        state.enabled = self.enabled;
+        state.set_layout(self.layout);
+        state.set_ratio(self.ratio);
+        state.active = self.active;
+        Some(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn dirs_in(temp_dir: &TempDir, names: &[&str]) -> Vec<PathBuf> {
+        names
+            .iter()
+            .map(|name| {
+                let dir = temp_dir.path().join(name);
+                std::fs::create_dir(&dir).unwrap();
+                dir
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_panes_keep_independent_navigation_and_selection() {
+        let temp_dir = TempDir::new().unwrap();
+        let dirs = dirs_in(&temp_dir, &["photos", "music", "docs"]);
+
+        let mut state = SplitViewState::new(temp_dir.path().to_path_buf(), temp_dir.path().to_path_buf());
+        state.pane_mut(PaneSide::Primary).navigation.navigate_to(dirs[0].clone()).unwrap();
+        state.pane_mut(PaneSide::Primary).navigation.navigate_to(dirs[1].clone()).unwrap();
+        state.pane_mut(PaneSide::Secondary).navigation.navigate_to(dirs[2].clone()).unwrap();
+        state.pane_mut(PaneSide::Secondary).selection.select_files(vec![dirs[2].join("a.txt")], SelectionMode::Replace);
+
+        let primary = state.pane(PaneSide::Primary);
+        let secondary = state.pane(PaneSide::Secondary);
+        assert_eq!(primary.current_path(), dirs[1]);
+        assert_eq!(secondary.current_path(), dirs[2]);
+        assert_eq!(primary.selection.selection_count(), 0);
+        assert_eq!(secondary.selection.selection_count(), 1);
+
+        // Going back in one pane leaves the other where it was
+        assert_eq!(state.pane_mut(PaneSide::Primary).navigation.go_back(), Some(dirs[0].clone()));
+        assert_eq!(state.pane(PaneSide::Primary).current_path(), dirs[0]);
+        assert_eq!(state.pane(PaneSide::Secondary).current_path(), dirs[2]);
+        assert!(state.pane(PaneSide::Secondary).navigation.can_navigate_back());
+        assert_eq!(PaneSide::Primary.other(), PaneSide::Secondary);
+    }
+
+    #[test]
+    fn test_positions_follow_layout_and_clamped_ratio() {
+        let mut state = SplitViewState::new(PathBuf::from("/a"), PathBuf::from("/b"));
+        state.set_ratio(0.05);
+        assert_eq!(state.ratio(), MIN_PANE_RATIO);
+
+        let [primary, secondary] = state.positions();
+        assert_eq!((primary.width, primary.height), (MIN_PANE_RATIO, 1.0));
+        assert_eq!((secondary.x, secondary.width), (MIN_PANE_RATIO, 1.0 - MIN_PANE_RATIO));
+
+        state.toggle_orientation();
+        assert_eq!(state.layout(), &EditorLayoutConfig::SplitVertical);
+        let [primary, secondary] = state.positions();
+        assert_eq!((primary.width, primary.height), (1.0, MIN_PANE_RATIO));
+        assert_eq!(secondary.y, MIN_PANE_RATIO);
+
+        assert!(!state.set_layout(EditorLayoutConfig::Single));
+        assert_eq!(state.layout(), &EditorLayoutConfig::SplitVertical);
+    }
+
+    #[test]
+    fn test_session_round_trip_restores_layout_and_panes() {
+        let temp_dir = TempDir::new().unwrap();
+        let dirs = dirs_in(&temp_dir, &["left", "right"]);
+        let selected = dirs[1].join("song.mp3");
+        std::fs::write(&selected, b"x").unwrap();
+
+        let mut state = SplitViewState::new(dirs[0].clone(), dirs[1].clone());
+        state.enabled = true;
+        state.toggle_orientation();
+        state.set_ratio(0.3);
+        state.active = PaneSide::Secondary;
+        state.pane_mut(PaneSide::Secondary).selection.select_files(
+            vec![selected.clone(), dirs[1].join("deleted.mp3")],
+            SelectionMode::Replace,
+        );
+
+        let json = serde_json::to_string(&SplitViewSession::capture(&state)).unwrap();
+        let session: SplitViewSession = serde_json::from_str(&json).unwrap();
+        let restored = session.restore(|path| path.is_dir()).unwrap();
+
+        assert!(restored.enabled);
+        assert_eq!(restored.layout(), &EditorLayoutConfig::SplitVertical);
+        assert_eq!(restored.ratio(), 0.3);
+        assert_eq!(restored.active, PaneSide::Secondary);
+        assert_eq!(restored.pane(PaneSide::Primary).current_path(), dirs[0]);
+        assert_eq!(restored.pane(PaneSide::Secondary).current_path(), dirs[1]);
+        assert_eq!(restored.pane(PaneSide::Secondary).selection.get_selected_paths(), vec![selected]);
+    }
+
+    #[test]
+    fn test_restore_replaces_a_missing_pane_folder() {
+        let temp_dir = TempDir::new().unwrap();
+        let dirs = dirs_in(&temp_dir, &["kept"]);
+        let state = SplitViewState::new(temp_dir.path().join("gone"), dirs[0].clone());
+
+        let restored = SplitViewSession::capture(&state).restore(|path| path.is_dir()).unwrap();
+        assert_eq!(restored.pane(PaneSide::Primary).current_path(), dirs[0]);
+        assert_eq!(restored.pane(PaneSide::Secondary).current_path(), dirs[0]);
+
+        assert!(SplitViewSession::capture(&state).restore(|_| false).is_none());
+    }
+}
@@ -0,0 +1,119 @@
+//! Window geometry: where the main window was, and where it can go back to
+//!
+//! The last window position and size are kept in the layout state's
+//! viewport in logical pixels. Monitors can change between runs, so a saved
+//! rectangle is fitted onto the monitors connected now before it is applied:
+//! a window left mostly on a connected monitor is nudged fully onto it, and
+//! one saved on a monitor that is gone is centered on the primary monitor.
+
+/// Part of a window, in both directions, that must overlap a monitor for it
+/// to stay on that monitor; smaller windows must overlap it entirely
+pub const MIN_VISIBLE_EXTENT: f64 = 100.0;
+
+/// Smallest window that is restored
+pub const MIN_WINDOW_SIZE: f64 = 400.0;
+
+/// A rectangle on the virtual desktop in logical pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScreenRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl ScreenRect {
+    pub fn new(x: f64, y: f64, width: f64, height: f64) -> Self {
+        Self { x, y, width, height }
+    }
+
+    pub fn right(&self) -> f64 {
+        self.x + self.width
+    }
+
+    pub fn bottom(&self) -> f64 {
+        self.y + self.height
+    }
+
+    /// Width and height of the part shared with `other`, zero when they don't touch
+    fn overlap(&self, other: &ScreenRect) -> (f64, f64) {
+        let width = (self.right().min(other.right()) - self.x.max(other.x)).max(0.0);
+        let height = (self.bottom().min(other.bottom()) - self.y.max(other.y)).max(0.0);
+        (width, height)
+    }
+}
+
+/// Fit a saved window onto `monitors`, the primary monitor first
+///
+/// The window stays on the monitor it overlaps most if enough of it is
+/// visible there, shrunk to fit and moved fully inside. Otherwise it is
+/// centered on the primary monitor. Without any monitors it is left alone.
+pub fn clamp_to_monitors(window: ScreenRect, monitors: &[ScreenRect]) -> ScreenRect {
+    let Some(primary) = monitors.first() else {
+        return window;
+    };
+    let visible_on = monitors
+        .iter()
+        .map(|monitor| (monitor, window.overlap(monitor)))
+        .filter(|(_, (width, height))| {
+            *width >= MIN_VISIBLE_EXTENT.min(window.width) && *height >= MIN_VISIBLE_EXTENT.min(window.height)
+        })
+        .max_by(|(_, a), (_, b)| (a.0 * a.1).total_cmp(&(b.0 * b.1)))
+        .map(|(monitor, _)| monitor);
+
+    let monitor = visible_on.unwrap_or(primary);
+    let width = window.width.max(MIN_WINDOW_SIZE).min(monitor.width);
+    let height = window.height.max(MIN_WINDOW_SIZE).min(monitor.height);
+    let (x, y) = match visible_on {
+        Some(_) => (
+            window.x.clamp(monitor.x, monitor.right() - width),
+            window.y.clamp(monitor.y, monitor.bottom() - height),
+        ),
+        None => (monitor.x + (monitor.width - width) / 2.0, monitor.y + (monitor.height - height) / 2.0),
+    };
+    ScreenRect::new(x, y, width, height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRIMARY: ScreenRect = ScreenRect { x: 0.0, y: 0.0, width: 1920.0, height: 1080.0 };
+    const RIGHT: ScreenRect = ScreenRect { x: 1920.0, y: 0.0, width: 1280.0, height: 1024.0 };
+
+    #[test]
+    fn test_visible_window_stays_where_it_was() {
+        let window = ScreenRect::new(100.0, 80.0, 1200.0, 800.0);
+        assert_eq!(clamp_to_monitors(window, &[PRIMARY, RIGHT]), window);
+        let on_right = ScreenRect::new(2000.0, 50.0, 1000.0, 700.0);
+        assert_eq!(clamp_to_monitors(on_right, &[PRIMARY, RIGHT]), on_right);
+        // No monitors reported: nothing to fit against
+        assert_eq!(clamp_to_monitors(window, &[]), window);
+    }
+
+    #[test]
+    fn test_partly_off_screen_window_is_pulled_onto_its_monitor() {
+        // Hanging off the bottom right of the primary monitor
+        let window = ScreenRect::new(1500.0, 900.0, 1200.0, 800.0);
+        assert_eq!(clamp_to_monitors(window, &[PRIMARY]), ScreenRect::new(720.0, 280.0, 1200.0, 800.0));
+        // Mostly on the right monitor, so it moves there rather than back
+        let straddling = ScreenRect::new(1800.0, 100.0, 1000.0, 700.0);
+        assert_eq!(clamp_to_monitors(straddling, &[PRIMARY, RIGHT]), ScreenRect::new(1920.0, 100.0, 1000.0, 700.0));
+        // Larger than the monitor: shrunk to fit
+        let huge = ScreenRect::new(-50.0, -50.0, 2500.0, 1500.0);
+        assert_eq!(clamp_to_monitors(huge, &[PRIMARY]), PRIMARY);
+    }
+
+    #[test]
+    fn test_window_on_disconnected_monitor_is_centered_on_primary() {
+        // Saved on the right monitor, which is no longer connected
+        let window = ScreenRect::new(2000.0, 50.0, 1000.0, 700.0);
+        assert_eq!(clamp_to_monitors(window, &[PRIMARY]), ScreenRect::new(460.0, 190.0, 1000.0, 700.0));
+        // A sliver on screen is not enough to find it again
+        let sliver = ScreenRect::new(1880.0, 0.0, 1000.0, 700.0);
+        assert_eq!(clamp_to_monitors(sliver, &[PRIMARY]).x, 460.0);
+        // Tiny saved sizes grow to the minimum
+        let tiny = ScreenRect::new(10.0, 10.0, 50.0, 50.0);
+        assert_eq!(clamp_to_monitors(tiny, &[PRIMARY]), ScreenRect::new(10.0, 10.0, MIN_WINDOW_SIZE, MIN_WINDOW_SIZE));
+    }
+}
@@ -0,0 +1,276 @@
+//! User-defined themes
+//!
+//! Each `*.json` or `*.toml` file in the themes config directory defines one
+//! theme, identified by its file stem:
+//!
+//! ```toml
+//! display_name = "Solarized Dark"
+//!
+//! [variables]
+//! "--vscode-editor-background" = "#002b36"
+//! "--vscode-editor-foreground" = "#839496"
+//! ```
+//!
+//! A file that fails to load is recorded in [`CustomThemes::errors`] and
+//! skipped; the remaining themes are still offered.
+
+use once_cell::sync::OnceCell;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Variables every custom theme must define
+pub const REQUIRED_VARIABLES: [&str; 6] = [
+    "--vscode-editor-background",
+    "--vscode-editor-foreground",
+    "--vscode-panel-background",
+    "--vscode-secondary-background",
+    "--vscode-text-primary",
+    "--vscode-text-secondary",
+];
+
+/// Ids of the built-in themes, which custom themes may not reuse
+const RESERVED_IDS: [&str; 4] = ["dark", "light", "auto", "high-contrast"];
+
+/// Problems found while loading custom themes
+#[derive(Debug, Error)]
+pub enum CustomThemeError {
+    #[error("Could not read {path}: {source}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Could not parse {path}: {message}")]
+    Parse { path: PathBuf, message: String },
+
+    #[error("{path} is missing required variables: {}", .missing.join(", "))]
+    MissingVariables { path: PathBuf, missing: Vec<String> },
+
+    #[error("{path} sets '{name}', which is not a --vscode-* variable")]
+    InvalidVariable { path: PathBuf, name: String },
+
+    #[error("{path} uses the name of the built-in '{id}' theme")]
+    ReservedId { path: PathBuf, id: String },
+}
+
+/// A named set of CSS variables loaded from the themes directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomTheme {
+    /// File stem, stored in settings as `Theme::Custom(id)`
+    pub id: String,
+    pub display_name: String,
+    pub variables: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ThemeFile {
+    display_name: Option<String>,
+    variables: HashMap<String, String>,
+}
+
+impl CustomTheme {
+    /// Parse the contents of a theme file; `path` decides JSON or TOML and the id
+    pub fn parse(path: &Path, contents: &str) -> Result<Self, CustomThemeError> {
+        let parse_error = |message: String| CustomThemeError::Parse { path: path.to_path_buf(), message };
+        let file: ThemeFile = match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => serde_json::from_str(contents).map_err(|e| parse_error(e.to_string()))?,
+            Some("toml") => toml::from_str(contents).map_err(|e| parse_error(e.to_string()))?,
+            _ => return Err(parse_error("expected a .json or .toml file".to_string())),
+        };
+
+        let id = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if RESERVED_IDS.contains(&id.as_str()) {
+            return Err(CustomThemeError::ReservedId { path: path.to_path_buf(), id });
+        }
+        if let Some(name) = file.variables.keys().find(|name| !name.starts_with("--vscode-")) {
+            return Err(CustomThemeError::InvalidVariable { path: path.to_path_buf(), name: name.clone() });
+        }
+        let missing: Vec<String> = REQUIRED_VARIABLES
+            .iter()
+            .filter(|name| !file.variables.contains_key(**name))
+            .map(|name| name.to_string())
+            .collect();
+        if !missing.is_empty() {
+            return Err(CustomThemeError::MissingVariables { path: path.to_path_buf(), missing });
+        }
+
+        Ok(Self {
+            display_name: file.display_name.unwrap_or_else(|| id.clone()),
+            id,
+            variables: file.variables,
+        })
+    }
+
+    /// Load one theme file
+    pub fn load(path: &Path) -> Result<Self, CustomThemeError> {
+        let contents = fs::read_to_string(path)
+            .map_err(|source| CustomThemeError::Read { path: path.to_path_buf(), source })?;
+        Self::parse(path, &contents)
+    }
+
+    /// Whether the editor background is dark; colors other than `#rrggbb` count as dark
+    pub fn is_dark(&self) -> bool {
+        let Some(hex) = self.variables["--vscode-editor-background"]
+            .strip_prefix('#')
+            .filter(|hex| hex.len() == 6 && hex.is_ascii())
+        else {
+            return true;
+        };
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map(f64::from);
+        match (channel(0), channel(2), channel(4)) {
+            (Ok(r), Ok(g), Ok(b)) => 0.299 * r + 0.587 * g + 0.114 * b < 128.0,
+            _ => true,
+        }
+    }
+}
+
+/// Custom themes found in the themes directory
+#[derive(Debug, Default)]
+pub struct CustomThemes {
+    themes: Vec<CustomTheme>,
+    errors: Vec<CustomThemeError>,
+}
+
+impl CustomThemes {
+    /// Load every `*.json` and `*.toml` theme in `dir`, sorted by display name
+    ///
+    /// A missing directory means no custom themes.
+    pub fn load_dir(dir: &Path) -> Self {
+        let mut loaded = Self::default();
+        let mut paths: Vec<PathBuf> = match fs::read_dir(dir) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().is_some_and(|extension| extension == "json" || extension == "toml"))
+                .collect(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return loaded,
+            Err(source) => {
+                loaded.errors.push(CustomThemeError::Read { path: dir.to_path_buf(), source });
+                return loaded;
+            }
+        };
+        paths.sort();
+        for path in paths {
+            match CustomTheme::load(&path) {
+                // A JSON and a TOML file with the same stem: the first one wins
+                Ok(theme) if loaded.get(&theme.id).is_some() => {
+                    tracing::warn!("Ignoring {}: a theme named '{}' is already loaded", path.display(), theme.id);
+                }
+                Ok(theme) => loaded.themes.push(theme),
+                Err(e) => loaded.errors.push(e),
+            }
+        }
+        loaded
+            .themes
+            .sort_by(|a, b| a.display_name.to_lowercase().cmp(&b.display_name.to_lowercase()));
+        loaded
+    }
+
+    pub fn themes(&self) -> &[CustomTheme] {
+        &self.themes
+    }
+
+    pub fn get(&self, id: &str) -> Option<&CustomTheme> {
+        self.themes.iter().find(|theme| theme.id == id)
+    }
+
+    /// Problems found while loading the themes
+    pub fn errors(&self) -> &[CustomThemeError] {
+        &self.errors
+    }
+}
+
+static CUSTOM_THEMES: OnceCell<CustomThemes> = OnceCell::new();
+
+/// Directory custom themes are loaded from
+pub fn default_themes_dir() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("MediaOrganizer")
+        .join("themes")
+}
+
+/// Make `themes` the ones offered app-wide; returns false if they were already set
+pub fn install(themes: CustomThemes) -> bool {
+    CUSTOM_THEMES.set(themes).is_ok()
+}
+
+/// The app-wide custom themes, none until [`install`] is called
+pub fn custom_themes() -> &'static CustomThemes {
+    CUSTOM_THEMES.get_or_init(CustomThemes::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn variables_toml() -> String {
+        REQUIRED_VARIABLES
+            .iter()
+            .map(|name| format!("\"{}\" = \"#102030\"\n", name))
+            .collect()
+    }
+
+    #[test]
+    fn test_themes_load_from_json_and_toml() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("solarized.toml"),
+            format!("display_name = \"Solarized\"\n\n[variables]\n{}", variables_toml()),
+        )
+        .unwrap();
+        let variables: HashMap<&str, &str> = REQUIRED_VARIABLES.iter().map(|name| (*name, "#000000")).collect();
+        let json = serde_json::json!({ "display_name": "Midnight", "variables": variables });
+        fs::write(dir.path().join("midnight.json"), json.to_string()).unwrap();
+        fs::write(dir.path().join("notes.txt"), "not a theme").unwrap();
+
+        let loaded = CustomThemes::load_dir(dir.path());
+        assert!(loaded.errors().is_empty());
+        let names: Vec<&str> = loaded.themes().iter().map(|theme| theme.display_name.as_str()).collect();
+        assert_eq!(names, vec!["Midnight", "Solarized"]);
+        let solarized = loaded.get("solarized").unwrap();
+        assert_eq!(solarized.variables["--vscode-text-primary"], "#102030");
+        assert!(solarized.is_dark());
+
+        assert!(CustomThemes::load_dir(&dir.path().join("missing")).themes().is_empty());
+    }
+
+    #[test]
+    fn test_invalid_theme_files_are_rejected() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("partial.toml"),
+            "[variables]\n\"--vscode-editor-background\" = \"#000000\"\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("dark.toml"),
+            format!("[variables]\n{}", variables_toml()),
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("stray.toml"),
+            format!("[variables]\n{}\"color\" = \"red\"\n", variables_toml()),
+        )
+        .unwrap();
+        fs::write(dir.path().join("broken.json"), "{ not json").unwrap();
+
+        let loaded = CustomThemes::load_dir(dir.path());
+        assert!(loaded.themes().is_empty());
+        assert_eq!(loaded.errors().len(), 4);
+        assert!(loaded.errors().iter().any(|e| matches!(
+            e,
+            CustomThemeError::MissingVariables { missing, .. } if missing.len() == REQUIRED_VARIABLES.len() - 1
+        )));
+        assert!(loaded.errors().iter().any(|e| matches!(e, CustomThemeError::ReservedId { id, .. } if id == "dark")));
+        assert!(loaded.errors().iter().any(|e| matches!(e, CustomThemeError::InvalidVariable { name, .. } if name == "color")));
+        assert!(loaded.errors().iter().any(|e| matches!(e, CustomThemeError::Parse { .. })));
+    }
+}
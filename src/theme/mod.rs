@@ -1,9 +1,13 @@
 pub mod color_theme;
 pub mod theme_implementations;
 pub mod theme_manager;
+pub mod system_theme_watcher;
+pub mod custom_theme;
 
 pub use color_theme::{ColorTheme, ThemeColors, TokenColor, TokenSettings, SemanticColors, SemanticColorRule};
 pub use theme_manager::{VsCodeThemeManager, VsCodeThemeSelector, use_vscode_theme_manager};
+pub use system_theme_watcher::{SystemAppearanceReceiver, SystemThemeWatcher};
+pub use custom_theme::{custom_themes, CustomTheme, CustomThemeError, CustomThemes};
 
 use crate::state::{Theme, SettingsState, save_settings_debounced};
 use crate::performance::rendering_optimizations::{ThemeOptimizer, RenderingProfiler};
@@ -17,6 +21,27 @@ use dioxus::prelude::{spawn, use_signal, Signal, use_effect, Element, rsx, compo
 
 use std::collections::HashMap;
 
+/// Appearance settings reported by the operating system
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SystemAppearance {
+    pub dark: bool,
+    /// The OS accessibility high-contrast (or increase-contrast) mode is on
+    pub high_contrast: bool,
+}
+
+impl SystemAppearance {
+    /// Theme that `Theme::Auto` follows under this appearance
+    pub fn theme(&self) -> Theme {
+        if self.high_contrast {
+            Theme::HighContrast
+        } else if self.dark {
+            Theme::Dark
+        } else {
+            Theme::Light
+        }
+    }
+}
+
 /// Legacy theme management utilities for backwards compatibility
 /// For new development, use VsCodeThemeManager instead
 pub struct ThemeManager {
@@ -70,6 +95,13 @@ impl ThemeManager {
                                 let _ = html.set_attribute("data-theme", "high-contrast");
                                 tracing::debug!("Applied high contrast theme");
                             }
+                            Theme::Custom(id) => {
+                                let _ = html.set_attribute("data-theme", id);
+                                if let Some(custom) = custom_themes().get(id) {
+                                    Self::apply_custom_css_variables(&custom.variables);
+                                }
+                                tracing::debug!("Applied custom theme '{}'", id);
+                            }
                             Theme::Auto => {
                                 // Remove data-theme attribute to let CSS media queries handle it
                                 let _ = html.remove_attribute("data-theme");
@@ -122,13 +154,20 @@ impl ThemeManager {
                     theme_vars.insert("--vscode-text-primary".to_string(), "#ffffff".to_string());
                     theme_vars.insert("--vscode-text-secondary".to_string(), "#ffffff".to_string());
                 }
+                Theme::Custom(id) => {
+                    let Some(custom) = custom_themes().get(id) else {
+                        tracing::warn!("Custom theme '{}' is not loaded, using dark theme", id);
+                        Self::apply_legacy_theme(&Theme::Dark);
+                        return;
+                    };
+                    tracing::debug!("Applied custom theme '{}' (desktop)", id);
+                    theme_vars = custom.variables.clone();
+                }
                 Theme::Auto => {
-                    let system_theme = Self::detect_system_theme();
-                    tracing::info!("Auto theme active (desktop), detected system preference: {}", 
-                                 if system_theme { "dark" } else { "light" });
+                    let appearance = Self::detect_system_appearance();
+                    tracing::info!("Auto theme active (desktop), detected system appearance: {:?}", appearance);
                     // Apply detected theme recursively
-                    let detected_theme = if system_theme { Theme::Dark } else { Theme::Light };
-                    Self::apply_legacy_theme(&detected_theme);
+                    Self::apply_legacy_theme(&appearance.theme());
                     return;
                 }
             }
@@ -288,33 +327,136 @@ impl ThemeManager {
         }
     }
 
+    /// Detect both the dark/light preference and the high-contrast mode
+    pub fn detect_system_appearance() -> SystemAppearance {
+        SystemAppearance {
+            dark: Self::detect_system_theme(),
+            high_contrast: Self::detect_system_high_contrast(),
+        }
+    }
+
+    /// Detect the appearance without the throttled theme cache
+    fn detect_system_appearance_raw() -> SystemAppearance {
+        SystemAppearance {
+            dark: Self::detect_system_theme_raw(),
+            high_contrast: Self::detect_system_high_contrast(),
+        }
+    }
+
+    /// Whether the OS asks for high contrast
+    pub fn detect_system_high_contrast() -> bool {
+        #[cfg(feature = "web")]
+        {
+            if let Some(window) = web_sys::window() {
+                for query in ["(prefers-contrast: more)", "(forced-colors: active)"] {
+                    if let Ok(Some(media_query)) = window.match_media(query) {
+                        if media_query.matches() {
+                            return true;
+                        }
+                    }
+                }
+            }
+            false
+        }
+
+        #[cfg(not(feature = "web"))]
+        {
+            let high_contrast = Self::detect_desktop_high_contrast();
+            tracing::debug!("Desktop high contrast detected: {}", high_contrast);
+            high_contrast
+        }
+    }
+
+    /// Desktop high-contrast detection
+    #[cfg(not(feature = "web"))]
+    fn detect_desktop_high_contrast() -> bool {
+        #[cfg(target_os = "macos")]
+        {
+            // "Increase contrast" in the Accessibility display settings
+            if let Ok(output) = std::process::Command::new("defaults")
+                .args(&["read", "com.apple.universalaccess", "increaseContrast"])
+                .output()
+            {
+                if output.status.success() {
+                    return parse_macos_increase_contrast(&String::from_utf8_lossy(&output.stdout));
+                }
+            }
+            return false;
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            if let Ok(output) = std::process::Command::new("reg")
+                .args(&[
+                    "query",
+                    "HKEY_CURRENT_USER\\Control Panel\\Accessibility\\HighContrast",
+                    "/v", "Flags"
+                ])
+                .output()
+            {
+                if output.status.success() {
+                    return parse_windows_high_contrast_flags(&String::from_utf8_lossy(&output.stdout));
+                }
+            }
+            return false;
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            if let Ok(gtk_theme) = std::env::var("GTK_THEME") {
+                if gtk_theme.to_lowercase().contains("highcontrast") {
+                    return true;
+                }
+            }
+            if let Ok(output) = std::process::Command::new("gsettings")
+                .args(&["get", "org.gnome.desktop.a11y.interface", "high-contrast"])
+                .output()
+            {
+                if output.status.success() {
+                    return String::from_utf8_lossy(&output.stdout).trim() == "true";
+                }
+            }
+            false
+        }
+
+        #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+        {
+            false
+        }
+    }
+
     /// Get the effective theme (resolving Auto to the actual theme)
     pub fn get_effective_theme(theme: &Theme) -> Theme {
+        Self::resolve_theme(theme, Self::detect_system_appearance)
+    }
+
+    /// Resolve Auto with `detect`, which is only called for Auto
+    pub fn resolve_theme(theme: &Theme, detect: impl FnOnce() -> SystemAppearance) -> Theme {
         match theme {
-            Theme::Auto => {
-                if Self::detect_system_theme() {
-                    Theme::Dark
-                } else {
-                    Theme::Light
-                }
-            }
+            Theme::Auto => detect().theme(),
             other => other.clone(),
         }
     }
 
     /// Get theme display name for UI
-    pub fn get_theme_display_name(theme: &Theme) -> &'static str {
+    pub fn get_theme_display_name(theme: &Theme) -> String {
         match theme {
-            Theme::Dark => "Dark",
-            Theme::Light => "Light",
-            Theme::Auto => "Auto",
-            Theme::HighContrast => "High Contrast",
+            Theme::Dark => "Dark".to_string(),
+            Theme::Light => "Light".to_string(),
+            Theme::Auto => "Auto".to_string(),
+            Theme::HighContrast => "High Contrast".to_string(),
+            Theme::Custom(id) => custom_themes()
+                .get(id)
+                .map(|custom| custom.display_name.clone())
+                .unwrap_or_else(|| id.clone()),
         }
     }
 
-    /// Get all available themes
+    /// Get all available themes, built-in ones first, then loaded custom themes
     pub fn get_available_themes() -> Vec<Theme> {
-        vec![Theme::Dark, Theme::Light, Theme::HighContrast, Theme::Auto]
+        let mut themes = vec![Theme::Dark, Theme::Light, Theme::HighContrast, Theme::Auto];
+        themes.extend(custom_themes().themes().iter().map(|custom| Theme::Custom(custom.id.clone())));
+        themes
     }
 
     /// Toggle between themes (Dark ↔ Light, preserving Auto preference)
@@ -330,7 +472,7 @@ impl ThemeManager {
                     Theme::Dark
                 }
             }
-            Theme::HighContrast => Theme::Light, // Go to light from high contrast
+            Theme::HighContrast | Theme::Custom(_) => Theme::Light, // Go to light from high contrast or a custom theme
         }
     }
 
@@ -514,7 +656,7 @@ pub struct ThemeManagerState {
     #[cfg(feature = "web")]
     pub system_theme_listener: Option<js_sys::Function>,
     #[cfg(not(feature = "web"))]
-    pub system_theme_listener: Option<()>,
+    pub system_theme_listener: Option<std::sync::Arc<SystemThemeWatcher>>,
 }
 
 impl ThemeManagerState {
@@ -574,7 +716,7 @@ impl ThemeManagerState {
             Theme::Dark => Theme::Light,
             Theme::Light => Theme::HighContrast,
             Theme::HighContrast => Theme::Auto,
-            Theme::Auto => Theme::Dark,
+            Theme::Auto | Theme::Custom(_) => Theme::Dark,
         };
         self.set_theme_with_override(new_theme, settings, true);
     }
@@ -637,11 +779,32 @@ impl ThemeManagerState {
         ThemeManager::get_effective_theme(&self.current_theme)
     }
 
-    /// Setup system theme preference listener for Auto mode
-    pub fn setup_system_theme_listener(&mut self) {
-        // This would be implemented for production to listen for system theme changes
-        // For now, we'll just detect on initialization
-        tracing::debug!("System theme listener setup (placeholder)");
+    /// Watch the system appearance while the theme is Auto, stopping the watcher otherwise
+    ///
+    /// Returns a receiver of appearance changes while a watcher runs.
+    pub fn setup_system_theme_listener(&mut self) -> Option<SystemAppearanceReceiver> {
+        if !matches!(self.current_theme, Theme::Auto) {
+            #[cfg(not(feature = "web"))]
+            if self.system_theme_listener.take().is_some() {
+                tracing::debug!("System theme listener stopped");
+            }
+            return None;
+        }
+
+        #[cfg(not(feature = "web"))]
+        {
+            let watcher = self.system_theme_listener.get_or_insert_with(|| {
+                tracing::debug!("System theme listener started");
+                std::sync::Arc::new(SystemThemeWatcher::start())
+            });
+            Some(watcher.subscribe())
+        }
+
+        #[cfg(feature = "web")]
+        {
+            // Browsers re-evaluate the prefers-color-scheme media queries themselves
+            None
+        }
     }
     
     /// Get user-friendly description of current theme status
@@ -799,6 +962,8 @@ pub fn EnhancedThemeSelector(
 #[derive(Props, Clone, PartialEq)]
 pub struct DynamicThemeStylesProps {
     pub current_settings: Signal<crate::state::SettingsState>,
+    /// Latest OS appearance reported while the theme is Auto
+    pub system_appearance: Signal<Option<SystemAppearance>>,
 }
 
 /// Dynamic theme styles component that injects CSS variables immediately
@@ -807,15 +972,19 @@ pub fn DynamicThemeStyles(props: DynamicThemeStylesProps) -> Element {
     // Create a signal to hold theme-based CSS
     let mut css_content = use_signal(|| String::new());
     
-    // React to theme changes in settings
-    let current_theme = props.current_settings.read().theme.clone();
-    
     use_effect(move || {
+        // React to theme changes in settings and to system appearance changes under Auto
+        let current_theme = props.current_settings.read().theme.clone();
+        let system_appearance = *props.system_appearance.read();
         tracing::info!("DynamicThemeStyles: Theme changed to {:?}, generating CSS", current_theme);
         
         // Create VSCode theme manager and get variables for the current theme
         let mut theme_manager = crate::theme::theme_manager::VsCodeThemeManager::new();
-        if let Ok(_) = theme_manager.set_theme_from_simple(&current_theme) {
+        let applied = match (&current_theme, system_appearance) {
+            (Theme::Auto, Some(appearance)) => theme_manager.set_auto_theme_for(appearance),
+            _ => theme_manager.set_theme_from_simple(&current_theme),
+        };
+        if let Ok(_) = applied {
             // Get the theme variables directly from the manager
             if let Some(variables) = theme_manager.get_current_theme_variables() {
                 tracing::info!("DynamicThemeStyles: Generated {} theme variables from current theme", variables.len());
@@ -886,3 +1055,56 @@ pub fn DynamicThemeStyles(props: DynamicThemeStylesProps) -> Element {
     }
 }
 
+/// Whether `defaults read com.apple.universalaccess increaseContrast` reports it on
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_macos_increase_contrast(output: &str) -> bool {
+    output.trim() == "1"
+}
+
+/// Whether `reg query ...\HighContrast /v Flags` output has HCF_HIGHCONTRASTON set
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn parse_windows_high_contrast_flags(output: &str) -> bool {
+    const HCF_HIGHCONTRASTON: u32 = 0x1;
+    output
+        .lines()
+        .find(|line| line.trim_start().starts_with("Flags"))
+        .and_then(|line| line.split_whitespace().last())
+        .and_then(|value| value.parse::<u32>().ok())
+        .is_some_and(|flags| flags & HCF_HIGHCONTRASTON != 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn appearance(dark: bool, high_contrast: bool) -> SystemAppearance {
+        SystemAppearance { dark, high_contrast }
+    }
+
+    #[test]
+    fn test_auto_follows_system_high_contrast() {
+        let resolve = |detected| ThemeManager::resolve_theme(&Theme::Auto, move || detected);
+        assert_eq!(resolve(appearance(true, true)), Theme::HighContrast);
+        assert_eq!(resolve(appearance(false, true)), Theme::HighContrast);
+        assert_eq!(resolve(appearance(true, false)), Theme::Dark);
+        assert_eq!(resolve(appearance(false, false)), Theme::Light);
+
+        // An explicit choice never asks the system
+        for theme in [Theme::Dark, Theme::Light, Theme::HighContrast] {
+            let resolved = ThemeManager::resolve_theme(&theme, || panic!("detection should not run"));
+            assert_eq!(resolved, theme);
+        }
+    }
+
+    #[test]
+    fn test_platform_high_contrast_output_is_parsed() {
+        assert!(parse_macos_increase_contrast("1\n"));
+        assert!(!parse_macos_increase_contrast("0\n"));
+
+        let on = "\r\nHKEY_CURRENT_USER\\Control Panel\\Accessibility\\HighContrast\r\n    Flags    REG_SZ    127\r\n";
+        let off = "\r\nHKEY_CURRENT_USER\\Control Panel\\Accessibility\\HighContrast\r\n    Flags    REG_SZ    126\r\n";
+        assert!(parse_windows_high_contrast_flags(on));
+        assert!(!parse_windows_high_contrast_flags(off));
+        assert!(!parse_windows_high_contrast_flags(""));
+    }
+}
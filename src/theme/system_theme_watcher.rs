@@ -0,0 +1,102 @@
+//! Background watcher for OS appearance changes
+//!
+//! Neither macOS nor Windows offers a change notification we can reach from
+//! the webview without extra platform bindings, so the appearance is re-read
+//! on a blocking thread at a fixed interval and only changes are published.
+
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use super::{SystemAppearance, ThemeManager};
+
+/// How often the OS appearance is re-read
+pub const SYSTEM_THEME_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Receives the latest appearance; `None` until it first changes
+pub type SystemAppearanceReceiver = watch::Receiver<Option<SystemAppearance>>;
+
+/// Polls the OS appearance on a background task and publishes changes
+///
+/// The task stops when the watcher is dropped, which also ends every
+/// subscriber's `changed()` loop.
+pub struct SystemThemeWatcher {
+    sender: watch::Sender<Option<SystemAppearance>>,
+    task: JoinHandle<()>,
+}
+
+impl SystemThemeWatcher {
+    /// Watch with the platform's appearance detection
+    pub fn start() -> Self {
+        Self::with_detector(SYSTEM_THEME_POLL_INTERVAL, ThemeManager::detect_system_appearance_raw)
+    }
+
+    /// Watch with `detect`, called on a blocking thread every `interval`
+    pub fn with_detector<F>(interval: Duration, detect: F) -> Self
+    where
+        F: Fn() -> SystemAppearance + Send + Sync + 'static,
+    {
+        let (sender, _) = watch::channel(None);
+        let publisher = sender.clone();
+        let detect = Arc::new(detect);
+        let task = tokio::spawn(async move {
+            let mut last = None;
+            loop {
+                let detect = detect.clone();
+                let Ok(appearance) = tokio::task::spawn_blocking(move || detect()).await else {
+                    break;
+                };
+                // The first reading is the baseline, not a change
+                if last.is_some_and(|last| last != appearance) {
+                    tracing::debug!("System appearance changed: {:?}", appearance);
+                    publisher.send_replace(Some(appearance));
+                }
+                last = Some(appearance);
+                tokio::time::sleep(interval).await;
+            }
+        });
+        Self { sender, task }
+    }
+
+    /// A receiver of appearance changes from now on
+    pub fn subscribe(&self) -> SystemAppearanceReceiver {
+        self.sender.subscribe()
+    }
+}
+
+impl Drop for SystemThemeWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[tokio::test]
+    async fn test_changes_are_published_until_dropped() {
+        let dark = Arc::new(AtomicBool::new(true));
+        let detected = dark.clone();
+        let watcher = SystemThemeWatcher::with_detector(Duration::from_millis(5), move || SystemAppearance {
+            dark: detected.load(Ordering::SeqCst),
+            high_contrast: false,
+        });
+        let mut changes = watcher.subscribe();
+
+        // An unchanged appearance publishes nothing
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert!(!changes.has_changed().unwrap());
+
+        dark.store(false, Ordering::SeqCst);
+        tokio::time::timeout(Duration::from_secs(5), changes.changed()).await.unwrap().unwrap();
+        let appearance = changes.borrow_and_update().unwrap();
+        assert_eq!(appearance.theme(), crate::state::Theme::Light);
+
+        drop(watcher);
+        let ended = tokio::time::timeout(Duration::from_secs(5), changes.changed()).await.unwrap();
+        assert!(ended.is_err());
+    }
+}
@@ -1,4 +1,5 @@
 use super::color_theme::ColorTheme;
+use super::SystemAppearance;
 use crate::state::{Theme, SettingsState, save_settings_debounced};
 use crate::performance::rendering_optimizations::{ThemeOptimizer, RenderingProfiler};
 use dioxus::prelude::*;
@@ -18,6 +19,8 @@ pub struct VsCodeThemeManager {
     optimizer: Option<ThemeOptimizer>,
     /// Current simple theme selection (for backwards compatibility)
     current_simple_theme: Theme,
+    /// Variables of the selected custom theme, layered over `current_theme`
+    custom_variables: Option<HashMap<String, String>>,
 }
 
 impl Default for VsCodeThemeManager {
@@ -34,6 +37,7 @@ impl VsCodeThemeManager {
             current_theme: None,
             optimizer: None,
             current_simple_theme: Theme::Dark,
+            custom_variables: None,
         };
         
         // Initialize optimizer
@@ -124,17 +128,30 @@ impl VsCodeThemeManager {
                     "high-contrast-light"
                 }
             },
-            Theme::Auto => {
-                // Detect system preference and choose appropriate theme
-                let is_dark = Self::detect_system_theme();
-                if is_dark { "dark-plus" } else { "light-plus" }
-            }
+            Theme::Auto => Self::auto_theme_name(crate::theme::ThemeManager::detect_system_appearance()),
+            Theme::Custom(id) => return self.set_custom_theme(id),
         };
         
+        self.custom_variables = None;
         self.current_simple_theme = simple_theme.clone();
         self.set_theme_by_name(theme_name)
     }
     
+    /// Apply a loaded custom theme over the built-in theme closest to it
+    fn set_custom_theme(&mut self, id: &str) -> Result<(), String> {
+        let custom = crate::theme::custom_themes()
+            .get(id)
+            .ok_or_else(|| format!("Custom theme '{}' not found", id))?;
+        let base = if custom.is_dark() { "dark-plus" } else { "light-plus" };
+        
+        self.custom_variables = Some(custom.variables.clone());
+        self.current_simple_theme = Theme::Custom(id.to_string());
+        self.set_theme_by_name(base)?;
+        // set_theme_by_name records the base theme; keep the custom selection
+        self.current_simple_theme = Theme::Custom(id.to_string());
+        Ok(())
+    }
+    
     /// Get current theme
     pub fn get_current_theme(&self) -> Option<&ColorTheme> {
         self.current_theme.as_ref()
@@ -148,7 +165,10 @@ impl VsCodeThemeManager {
     /// Apply the current theme to the UI
     pub fn apply_current_theme(&self) {
         if let Some(theme) = &self.current_theme {
-            let css_vars = theme.to_css_variables();
+            let mut css_vars = theme.to_css_variables();
+            if let Some(custom) = &self.custom_variables {
+                css_vars.extend(custom.clone());
+            }
             self.apply_css_variables(&css_vars);
             
             // Also apply to the legacy theme system
@@ -306,6 +326,11 @@ impl VsCodeThemeManager {
             variables.insert("--vscode-text-primary".to_string(), colors.foreground.clone());
             variables.insert("--vscode-text-secondary".to_string(), colors.foreground_secondary.clone());
             
+            // A custom theme overrides whatever it defines
+            if let Some(custom) = &self.custom_variables {
+                variables.extend(custom.clone());
+            }
+            
             tracing::debug!("Generated {} comprehensive CSS variables from theme colors", variables.len());
             Some(variables)
         } else {
@@ -330,6 +355,7 @@ impl VsCodeThemeManager {
                             Theme::Dark => "dark",
                             Theme::Light => "light", 
                             Theme::HighContrast => "high-contrast",
+                            Theme::Custom(id) => id.as_str(),
                             Theme::Auto => {
                                 // Remove attribute to let CSS media queries handle it
                                 let _ = html.remove_attribute("data-theme");
@@ -509,10 +535,24 @@ impl VsCodeThemeManager {
     
     /// Apply auto theme based on system preference
     pub fn apply_auto_theme(&mut self) -> Result<(), String> {
-        let is_dark = Self::detect_system_theme();
-        let theme_name = if is_dark { "dark-plus" } else { "light-plus" };
+        self.set_auto_theme_for(crate::theme::ThemeManager::detect_system_appearance())
+    }
+    
+    /// Apply Auto for an appearance that was already detected
+    pub fn set_auto_theme_for(&mut self, appearance: SystemAppearance) -> Result<(), String> {
+        self.custom_variables = None;
         self.current_simple_theme = Theme::Auto;
-        self.set_theme_by_name(theme_name)
+        self.set_theme_by_name(Self::auto_theme_name(appearance))
+    }
+    
+    /// Built-in theme that Auto follows, honoring the system high-contrast mode
+    fn auto_theme_name(appearance: SystemAppearance) -> &'static str {
+        match (appearance.high_contrast, appearance.dark) {
+            (true, true) => "high-contrast-dark",
+            (true, false) => "high-contrast-light",
+            (false, true) => "dark-plus",
+            (false, false) => "light-plus",
+        }
     }
     
     /// Get theme suggestions based on current theme
@@ -0,0 +1,270 @@
+use dioxus::prelude::*;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::info;
+
+use crate::services::announcer::Politeness;
+use crate::services::file_system::ConflictResolution;
+use crate::services::archive::{default_archive_path, CompressCommand, ExtractArchiveCommand};
+use crate::services::operations::{
+    CancellationToken, Command, OperationError, ProgressInfo, ProgressTracker,
+};
+use crate::services::progress_hub::{TaskHandle, TaskKind};
+use crate::state::{AppState, OperationState};
+
+/// How long the completion message stays in the status bar
+const COMPLETION_MESSAGE_MS: u64 = 5000;
+
+/// Check whether a path is an archive we can extract
+pub fn is_extractable_archive(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map_or(false, |ext| ext.eq_ignore_ascii_case("zip"))
+}
+
+/// Show a save dialog for the archive the selection is compressed into
+pub async fn show_zip_destination_dialog(selection: &[PathBuf]) -> Option<PathBuf> {
+    use rfd::AsyncFileDialog;
+
+    let mut dialog = AsyncFileDialog::new()
+        .set_title("Compress to Archive")
+        .add_filter("Zip archive", &["zip"]);
+
+    // Suggest a free name next to the selection, after a single selected item
+    if let Some(default_path) = default_archive_path(selection) {
+        if let Some(name) = default_path.file_name() {
+            dialog = dialog.set_file_name(&*name.to_string_lossy());
+        }
+        if let Some(parent) = default_path.parent() {
+            dialog = dialog.set_directory(parent);
+        }
+    }
+
+    dialog.save_file().await.map(|handle| handle.path().to_path_buf())
+}
+
+/// Show a folder picker for "Extract To…"
+pub async fn show_extract_destination_dialog(archive: &Path) -> Option<PathBuf> {
+    use rfd::AsyncFileDialog;
+
+    let mut dialog = AsyncFileDialog::new().set_title("Extract Archive To");
+    if let Some(parent) = archive.parent() {
+        dialog = dialog.set_directory(parent);
+    }
+
+    dialog.pick_folder().await.map(|handle| handle.path().to_path_buf())
+}
+
+/// Ask once how to handle files the extraction would overwrite, if there are any
+///
+/// Closing the dialog skips them, like the copy conflict prompt.
+pub async fn resolve_extract_conflicts(command: ExtractArchiveCommand) -> ExtractArchiveCommand {
+    use rfd::AsyncMessageDialog;
+
+    let conflicts = match command.conflicts() {
+        Ok(conflicts) if !conflicts.is_empty() => conflicts,
+        // A broken archive is reported by the extraction itself
+        _ => return command,
+    };
+
+    let replace = t!("dialog.conflict.replace");
+    let keep_both = t!("dialog.conflict.keep_both");
+    let result = AsyncMessageDialog::new()
+        .set_title(t!("dialog.conflict.title"))
+        .set_description(t!("dialog.conflict.extract_message", count = conflicts.len()))
+        .set_level(rfd::MessageLevel::Warning)
+        .set_buttons(rfd::MessageButtons::YesNoCancelCustom(
+            replace.clone(),
+            keep_both.clone(),
+            t!("dialog.conflict.skip"),
+        ))
+        .show()
+        .await;
+
+    let resolution = match result {
+        rfd::MessageDialogResult::Custom(label) if label == replace => ConflictResolution::Overwrite,
+        rfd::MessageDialogResult::Custom(label) if label == keep_both => ConflictResolution::Rename,
+        _ => ConflictResolution::Skip,
+    };
+    command.with_conflict_policy(resolution.into())
+}
+
+/// Compress files into a zip archive with progress, recording the result in the undo history
+pub async fn compress_to_archive(app_state: AppState, command: CompressCommand) {
+    let mut operation_state = app_state.operation_state;
+    let (file_count, total_bytes) = match command.estimate_work(app_state.file_service.clone()).await {
+        Ok(work) => work,
+        Err(e) => {
+            let message = format!("Compression failed: {}", e);
+            app_state.announcer.assertive(message.clone());
+            info!("{}", message);
+            return;
+        }
+    };
+
+    let cancellation_token = CancellationToken::new();
+    begin_operation(
+        operation_state,
+        "Compress",
+        format!("Zipping {} files...", file_count),
+        &cancellation_token,
+    );
+    let hud_task = register_hud_task(&app_state, "Compress", &cancellation_token);
+
+    let (progress_tx, progress_rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut tracker = ProgressTracker::with_cancellation(file_count, total_bytes, "Compress".to_string(), cancellation_token)
+        .with_callback(Arc::new(move |info| {
+            let _ = progress_tx.send(info);
+        }));
+
+    // Compression writes the archive synchronously, so run it off the async runtime
+    let fs = app_state.file_service.clone();
+    let runtime = tokio::runtime::Handle::current();
+    let compression = tokio::task::spawn_blocking(move || {
+        let mut command = command;
+        let result = runtime.block_on(command.execute_with_progress(fs, Some(&mut tracker)));
+        (command, result)
+    });
+    forward_progress(operation_state, &hud_task, progress_rx).await;
+    drop(hud_task);
+
+    let (message, politeness) = match compression.await {
+        Ok((command, Ok(()))) => {
+            let message = command
+                .summary()
+                .map(|summary| summary.describe())
+                .unwrap_or_else(|| format!("Compressed to {}", command.destination.display()));
+            if let Err(e) = app_state.operation_history.lock().await.add_executed_command(Box::new(command)).await {
+                info!("Failed to record compression in history: {}", e);
+            }
+            (message, Politeness::Polite)
+        }
+        Ok((command, Err(e))) => {
+            app_state.operation_history.lock().await.log_failed_command(&command, &e);
+            match e {
+                OperationError::Cancelled => ("Compression cancelled".to_string(), Politeness::Polite),
+                e => (format!("Compression failed: {}", e), Politeness::Assertive),
+            }
+        }
+        Err(e) => (format!("Compression task failed: {}", e), Politeness::Assertive),
+    };
+
+    app_state.announcer.announce(message.clone(), politeness);
+    finish_operation(&mut operation_state, message).await;
+}
+
+/// Extract an archive with progress, recording the result in the undo history
+pub async fn extract_archive(app_state: AppState, command: ExtractArchiveCommand) {
+    let mut operation_state = app_state.operation_state;
+    let (file_count, total_bytes) = command
+        .estimate_work(app_state.file_service.clone())
+        .await
+        .unwrap_or((0, 0));
+
+    let cancellation_token = CancellationToken::new();
+    begin_operation(
+        operation_state,
+        "Extract Archive",
+        format!("Extracting {}...", command.archive.display()),
+        &cancellation_token,
+    );
+    let hud_task = register_hud_task(&app_state, "Extract Archive", &cancellation_token);
+
+    let (progress_tx, progress_rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut tracker = ProgressTracker::with_cancellation(file_count, total_bytes, "Extract Archive".to_string(), cancellation_token)
+        .with_callback(Arc::new(move |info| {
+            let _ = progress_tx.send(info);
+        }));
+
+    // Extraction reads the archive synchronously, so run it off the async runtime
+    let fs = app_state.file_service.clone();
+    let runtime = tokio::runtime::Handle::current();
+    let extraction = tokio::task::spawn_blocking(move || {
+        let mut command = command;
+        let result = runtime.block_on(command.execute_with_progress(fs, Some(&mut tracker)));
+        (command, result)
+    });
+    forward_progress(operation_state, &hud_task, progress_rx).await;
+    drop(hud_task);
+
+    let (message, politeness) = match extraction.await {
+        Ok((command, Ok(()))) => {
+            let message = format!(
+                "Extracted {} items to {}",
+                command.created_paths().len(),
+                command.destination.display()
+            );
+            if let Err(e) = app_state.operation_history.lock().await.add_executed_command(Box::new(command)).await {
+                info!("Failed to record extraction in history: {}", e);
+            }
+            (message, Politeness::Polite)
+        }
+        Ok((command, Err(e))) => {
+            app_state.operation_history.lock().await.log_failed_command(&command, &e);
+            match e {
+                OperationError::Cancelled => ("Extraction cancelled".to_string(), Politeness::Polite),
+                e => (format!("Extraction failed: {}", e), Politeness::Assertive),
+            }
+        }
+        Err(e) => (format!("Extraction task failed: {}", e), Politeness::Assertive),
+    };
+
+    app_state.announcer.announce(message.clone(), politeness);
+    finish_operation(&mut operation_state, message).await;
+}
+
+fn begin_operation(
+    mut operation_state: Signal<OperationState>,
+    operation_type: &str,
+    message: String,
+    cancellation_token: &CancellationToken,
+) {
+    let mut op_state = operation_state.write();
+    op_state.is_active = true;
+    op_state.operation_type = Some(operation_type.to_string());
+    op_state.progress = 0.0;
+    op_state.status_message = message;
+    op_state.can_cancel = true;
+    op_state.cancellation_token = Some(cancellation_token.clone());
+}
+
+/// Show the operation in the global progress HUD, cancellable from there
+fn register_hud_task(app_state: &AppState, label: &str, cancellation_token: &CancellationToken) -> TaskHandle {
+    let cancellation_token = cancellation_token.clone();
+    app_state.progress_hub.register(
+        label,
+        TaskKind::FileOperation,
+        Some(Arc::new(move || cancellation_token.cancel())),
+    )
+}
+
+/// Mirror tracker updates into the operation state and HUD until the tracker is dropped
+async fn forward_progress(
+    mut operation_state: Signal<OperationState>,
+    hud_task: &TaskHandle,
+    mut progress_rx: tokio::sync::mpsc::UnboundedReceiver<ProgressInfo>,
+) {
+    while let Some(info) = progress_rx.recv().await {
+        hud_task.update_progress_info(&info);
+        let mut op_state = operation_state.write();
+        op_state.progress = info.bytes_percentage() as f32;
+        op_state.status_message = format!("{} ({:.0}%)", info.current_operation, info.bytes_percentage());
+    }
+}
+
+async fn finish_operation(operation_state: &mut Signal<OperationState>, message: String) {
+    info!("{}", message);
+
+    {
+        let mut op_state = operation_state.write();
+        op_state.progress = 100.0;
+        op_state.status_message = message;
+        op_state.can_cancel = false;
+        op_state.cancellation_token = None;
+    }
+
+    tokio::time::sleep(std::time::Duration::from_millis(COMPLETION_MESSAGE_MS)).await;
+    operation_state.write().is_active = false;
+}
@@ -0,0 +1,151 @@
+//! Telling a double click from two single clicks on file list items
+//!
+//! Every plain click selects at once, so single-click selection never waits
+//! on a timer. A second click on the same item soon after the first one
+//! activates it with the configured [`DoubleClickAction`] instead. Clicks
+//! with Shift, Ctrl or Cmd only ever change the selection.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::state::{DoubleClickAction, DoubleClickSettings};
+
+/// Longest gap between the two clicks of a double click
+pub const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// What a click should do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClickIntent {
+    /// Select the item, or extend the selection with modifiers held
+    Select,
+    /// Second click of a double click: run the action for the item
+    Activate(DoubleClickAction),
+}
+
+/// A click on a file list item
+#[derive(Debug, Clone, Copy)]
+pub struct Click<'a> {
+    pub path: &'a Path,
+    pub is_directory: bool,
+    /// Shift, Ctrl or Cmd was held
+    pub with_modifiers: bool,
+    pub at: Instant,
+}
+
+/// The last plain click, waiting to see if a second one follows
+#[derive(Debug, Clone, Default)]
+pub struct ClickTracker {
+    pending: Option<(PathBuf, Instant)>,
+}
+
+impl ClickTracker {
+    /// Record `click` and say what it should do
+    pub fn click(&mut self, click: Click<'_>, settings: &DoubleClickSettings) -> ClickIntent {
+        let previous = self.pending.take();
+        let intent = resolve_click(previous.as_ref().map(|(path, at)| (path.as_path(), *at)), click, settings);
+        // The click completing a double click doesn't start the next one
+        if intent == ClickIntent::Select && !click.with_modifiers {
+            self.pending = Some((click.path.to_path_buf(), click.at));
+        }
+        intent
+    }
+}
+
+/// What `click` does given the plain click before it, if any
+pub fn resolve_click(previous: Option<(&Path, Instant)>, click: Click<'_>, settings: &DoubleClickSettings) -> ClickIntent {
+    if click.with_modifiers {
+        return ClickIntent::Select;
+    }
+    match previous {
+        Some((path, at)) if path == click.path && click.at.saturating_duration_since(at) <= DOUBLE_CLICK_INTERVAL => {
+            ClickIntent::Activate(settings.action_for(click.is_directory))
+        }
+        _ => ClickIntent::Select,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn click(path: &Path, is_directory: bool, at: Instant) -> Click<'_> {
+        Click { path, is_directory, with_modifiers: false, at }
+    }
+
+    #[test]
+    fn test_second_click_in_time_on_same_item_activates() {
+        let settings = DoubleClickSettings::default();
+        let start = Instant::now();
+        let photo = Path::new("/media/photo.jpg");
+        let folder = Path::new("/media/2024");
+
+        let mut tracker = ClickTracker::default();
+        assert_eq!(tracker.click(click(photo, false, start), &settings), ClickIntent::Select);
+        assert_eq!(
+            tracker.click(click(photo, false, start + Duration::from_millis(300)), &settings),
+            ClickIntent::Activate(DoubleClickAction::Preview)
+        );
+        // A third quick click starts over rather than activating again
+        assert_eq!(tracker.click(click(photo, false, start + Duration::from_millis(400)), &settings), ClickIntent::Select);
+
+        assert_eq!(tracker.click(click(folder, true, start + Duration::from_secs(2)), &settings), ClickIntent::Select);
+        assert_eq!(
+            tracker.click(click(folder, true, start + Duration::from_millis(2400)), &settings),
+            ClickIntent::Activate(DoubleClickAction::EnterFolder)
+        );
+    }
+
+    #[test]
+    fn test_slow_other_item_or_modified_clicks_only_select() {
+        let settings = DoubleClickSettings::default();
+        let start = Instant::now();
+        let photo = Path::new("/media/photo.jpg");
+        let other = Path::new("/media/other.jpg");
+
+        // Too slow
+        let previous = Some((photo, start));
+        let late = click(photo, false, start + DOUBLE_CLICK_INTERVAL + Duration::from_millis(1));
+        assert_eq!(resolve_click(previous, late, &settings), ClickIntent::Select);
+        // Exactly at the limit still counts
+        let in_time = click(photo, false, start + DOUBLE_CLICK_INTERVAL);
+        assert_eq!(resolve_click(previous, in_time, &settings), ClickIntent::Activate(DoubleClickAction::Preview));
+        // A different item
+        assert_eq!(resolve_click(previous, click(other, false, start), &settings), ClickIntent::Select);
+        // Shift, Ctrl or Cmd held
+        let modified = Click { with_modifiers: true, ..click(photo, false, start) };
+        assert_eq!(resolve_click(previous, modified, &settings), ClickIntent::Select);
+
+        // A modified click in between breaks up the double click
+        let mut tracker = ClickTracker::default();
+        tracker.click(click(photo, false, start), &settings);
+        tracker.click(modified, &settings);
+        assert_eq!(tracker.click(click(photo, false, start), &settings), ClickIntent::Select);
+    }
+
+    #[test]
+    fn test_files_and_folders_use_their_own_action() {
+        let settings = DoubleClickSettings {
+            files: DoubleClickAction::OpenDefault,
+            folders: DoubleClickAction::OpenDefault,
+        };
+        let start = Instant::now();
+        let photo = Path::new("/media/photo.jpg");
+        let folder = Path::new("/media/2024");
+        assert_eq!(
+            resolve_click(Some((photo, start)), click(photo, false, start), &settings),
+            ClickIntent::Activate(DoubleClickAction::OpenDefault)
+        );
+        assert_eq!(
+            resolve_click(Some((folder, start)), click(folder, true, start), &settings),
+            ClickIntent::Activate(DoubleClickAction::OpenDefault)
+        );
+
+        // Files can't be entered and folders have no Quick Look
+        let unsuited = DoubleClickSettings {
+            files: DoubleClickAction::EnterFolder,
+            folders: DoubleClickAction::Preview,
+        };
+        assert_eq!(unsuited.action_for(false), DoubleClickAction::Preview);
+        assert_eq!(unsuited.action_for(true), DoubleClickAction::EnterFolder);
+    }
+}
@@ -0,0 +1,57 @@
+use std::path::PathBuf;
+
+use crate::services::clipboard::{clipboard_text, copy_file_references, copy_text, PathCopyMode, PathStyle};
+use crate::state::AppState;
+
+/// Selected paths, falling back to the focused file tree entry
+pub fn clipboard_selection(app_state: &AppState) -> Vec<PathBuf> {
+    let mut selected = app_state.get_selected_files();
+    if selected.is_empty() {
+        selected.extend(app_state.get_file_tree_selection());
+    }
+    selected
+}
+
+/// Copy the paths or names of `paths` as newline-separated text, with the separators of `style`
+pub async fn copy_paths(app_state: AppState, paths: Vec<PathBuf>, mode: PathCopyMode, style: PathStyle) {
+    if paths.is_empty() {
+        return;
+    }
+    let root = app_state
+        .get_file_tree_root()
+        .unwrap_or_else(|| app_state.navigation.read().current_path.clone());
+    let text = clipboard_text(&paths, Some(&root), mode, style);
+    let count = paths.len();
+    let result = tokio::task::spawn_blocking(move || copy_text(&text)).await;
+    report(&app_state, result, count);
+}
+
+/// Put the files in `paths` on the clipboard for pasting into other apps
+pub async fn copy_as_file_references(app_state: AppState, paths: Vec<PathBuf>) {
+    if paths.is_empty() {
+        return;
+    }
+    let count = paths.len();
+    let result = tokio::task::spawn_blocking(move || copy_file_references(&paths)).await;
+    report(&app_state, result, count);
+}
+
+fn report(
+    app_state: &AppState,
+    result: Result<crate::services::clipboard::ClipboardResult<()>, tokio::task::JoinError>,
+    count: usize,
+) {
+    match result {
+        Ok(Ok(())) => {
+            app_state.announcer.polite(t!("clipboard.copied", count = count));
+        }
+        Ok(Err(e)) => {
+            tracing::warn!("Copy to clipboard failed: {}", e);
+            app_state.announcer.assertive(t!("clipboard.failed", error = e));
+        }
+        Err(e) => {
+            tracing::warn!("Clipboard task failed: {}", e);
+            app_state.announcer.assertive(t!("clipboard.failed", error = e));
+        }
+    }
+}
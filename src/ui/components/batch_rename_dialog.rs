@@ -0,0 +1,252 @@
+// Batch rename dialog
+// Previews the old and new name of every file before a template or find and replace rename runs
+
+use dioxus::prelude::*;
+
+use crate::services::file_system::FileEntry;
+use crate::services::rename_template::{
+    can_execute_plan, plan_rename, FindReplace, FindReplaceOptions, RenamePlanRow, RenameRule, RenameStatus,
+    RenameTemplate, DEFAULT_RENAME_TEMPLATE,
+};
+use crate::state::use_app_state;
+use crate::ui::components::{modal_title_id, Modal};
+
+/// How the new names are derived
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RenameMode {
+    Template,
+    FindReplace,
+}
+
+/// Label and row class for a preview row's status
+fn status_label(status: &RenameStatus) -> (String, &'static str) {
+    match status {
+        RenameStatus::Ok => (t!("batch_rename.status_ok"), "rename-row-ok"),
+        RenameStatus::Collision => (t!("batch_rename.status_collision"), "rename-row-blocked"),
+        RenameStatus::Invalid(e) => (e.message(), "rename-row-blocked"),
+        RenameStatus::Unchanged => (t!("batch_rename.status_unchanged"), "rename-row-unchanged"),
+    }
+}
+
+/// Batch rename with a live preview; mount it only while it should be shown
+///
+/// Renaming stays disabled while any new name collides or is invalid, and
+/// files whose name would not change are left alone.
+#[component]
+pub fn BatchRenameDialog(files: Vec<FileEntry>, on_close: EventHandler<()>) -> Element {
+    let app_state = use_app_state();
+    let mut mode = use_signal(|| RenameMode::Template);
+    let mut template = use_signal(|| DEFAULT_RENAME_TEMPLATE.to_string());
+    let mut find = use_signal(String::new);
+    let mut replace = use_signal(String::new);
+    let mut options = use_signal(FindReplaceOptions::default);
+    let mut error = use_signal(|| None::<String>);
+    let mut running = use_signal(|| false);
+
+    let files_for_plan = files.clone();
+    let plan = use_memo(move || {
+        let rule = match *mode.read() {
+            RenameMode::Template => RenameTemplate::parse(&template.read()).map(RenameRule::Template).map_err(|e| e.to_string())?,
+            RenameMode::FindReplace => FindReplace::new(&find.read(), &replace.read(), *options.read())
+                .map(RenameRule::FindReplace)
+                .map_err(|e| e.to_string())?,
+        };
+        let rows = plan_rename(&files_for_plan, &rule);
+        Ok::<(RenameRule, Vec<RenamePlanRow>), String>((rule, rows))
+    });
+
+    let dialog_id = "batch-rename-dialog";
+    let (rule_error, rows) = match &*plan.read() {
+        Ok((_, rows)) => (None, rows.clone()),
+        Err(e) => (Some(e.clone()), Vec::new()),
+    };
+    let rule_invalid = rule_error.is_some();
+    let current_options = *options.read();
+    let blocked = rows.iter().filter(|row| row.status.blocks()).count();
+    let can_rename = can_execute_plan(&rows) && !*running.read();
+
+    rsx! {
+        Modal {
+            id: "{dialog_id}",
+            class: "batch-rename-dialog",
+            on_close: move |_| on_close.call(()),
+
+            div {
+                class: "dialog-header",
+                h3 { id: modal_title_id(dialog_id), {t!("dialog.rename_template.title", count = files.len())} }
+            }
+
+            div {
+                class: "dialog-content",
+                div {
+                    class: "batch-rename-modes",
+                    role: "radiogroup",
+                    label {
+                        input {
+                            r#type: "radio",
+                            name: "batch-rename-mode",
+                            checked: *mode.read() == RenameMode::Template,
+                            onchange: move |_| mode.set(RenameMode::Template),
+                        }
+                        {t!("batch_rename.mode_template")}
+                    }
+                    label {
+                        input {
+                            r#type: "radio",
+                            name: "batch-rename-mode",
+                            checked: *mode.read() == RenameMode::FindReplace,
+                            onchange: move |_| mode.set(RenameMode::FindReplace),
+                        }
+                        {t!("batch_rename.mode_find_replace")}
+                    }
+                }
+
+                if *mode.read() == RenameMode::Template {
+                    label {
+                        r#for: "{dialog_id}-template",
+                        class: "dialog-message",
+                        {t!("dialog.rename_template.message")}
+                    }
+                    input {
+                        id: "{dialog_id}-template",
+                        r#type: "text",
+                        class: "dialog-input",
+                        value: "{template}",
+                        "aria-invalid": "{rule_invalid}",
+                        oninput: move |evt| template.set(evt.value()),
+                    }
+                } else {
+                    div {
+                        class: "batch-rename-find-replace",
+                        label {
+                            {t!("batch_rename.find")}
+                            input {
+                                r#type: "text",
+                                class: "dialog-input",
+                                value: "{find}",
+                                "aria-invalid": "{rule_invalid}",
+                                oninput: move |evt| find.set(evt.value()),
+                            }
+                        }
+                        label {
+                            {t!("batch_rename.replace")}
+                            input {
+                                r#type: "text",
+                                class: "dialog-input",
+                                value: "{replace}",
+                                oninput: move |evt| replace.set(evt.value()),
+                            }
+                        }
+                    }
+                    div {
+                        class: "batch-rename-options",
+                        label {
+                            input {
+                                r#type: "checkbox",
+                                checked: current_options.regex,
+                                onchange: move |evt| options.write().regex = evt.checked(),
+                            }
+                            {t!("batch_rename.regex")}
+                        }
+                        label {
+                            input {
+                                r#type: "checkbox",
+                                checked: current_options.case_sensitive,
+                                onchange: move |evt| options.write().case_sensitive = evt.checked(),
+                            }
+                            {t!("batch_rename.match_case")}
+                        }
+                        label {
+                            input {
+                                r#type: "checkbox",
+                                checked: current_options.replace_all,
+                                onchange: move |evt| options.write().replace_all = evt.checked(),
+                            }
+                            {t!("batch_rename.replace_all")}
+                        }
+                    }
+                }
+
+                if let Some(rule_error) = rule_error {
+                    div { class: "dialog-error", role: "alert", {rule_error} }
+                } else {
+                    div {
+                        class: "batch-rename-preview",
+                        table {
+                            class: "log-table",
+                            thead {
+                                tr {
+                                    th { scope: "col", {t!("batch_rename.old_name")} }
+                                    th { scope: "col", {t!("batch_rename.new_name")} }
+                                    th { scope: "col", {t!("batch_rename.status")} }
+                                }
+                            }
+                            tbody {
+                                for row in rows.iter() {
+                                    {
+                                        let (label, class) = status_label(&row.status);
+                                        let old_name = row.old.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+                                        rsx! {
+                                            tr {
+                                                key: "{row.old.display()}",
+                                                class: "{class}",
+                                                td { "{old_name}" }
+                                                td { "{row.new}" }
+                                                td { "{label}" }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    if blocked > 0 {
+                        p { class: "dialog-error", role: "status", {t!("batch_rename.blocked", count = blocked)} }
+                    }
+                }
+
+                if let Some(message) = error.read().as_ref() {
+                    div { class: "dialog-error", role: "alert", {message.clone()} }
+                }
+            }
+
+            div {
+                class: "dialog-actions",
+                button {
+                    class: "button secondary",
+                    onclick: move |_| on_close.call(()),
+                    {t!("dialog.cancel")}
+                }
+                button {
+                    class: "button primary",
+                    disabled: !can_rename,
+                    onclick: move |_| {
+                        let Ok((rule, rows)) = plan.read().clone() else {
+                            return;
+                        };
+                        let mut app_state = app_state.clone();
+                        running.set(true);
+                        spawn(async move {
+                            match app_state.file_operations().rename_with_plan(&rule, &rows).await {
+                                Ok(renamed) => {
+                                    tracing::info!("Renamed {} files", renamed.len());
+                                    app_state.announcer.operation_completed("renamed", renamed.len());
+                                    if let Err(e) = app_state.refresh_current_directory().await {
+                                        tracing::warn!("Error refreshing directory after rename: {}", e);
+                                    }
+                                    on_close.call(());
+                                }
+                                Err(e) => {
+                                    app_state.announcer.assertive(e.clone());
+                                    error.set(Some(e));
+                                    running.set(false);
+                                }
+                            }
+                        });
+                    },
+                    {t!("batch_rename.rename")}
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,155 @@
+// Bookmarks
+// Favorite folders in the sidebar; missing ones can be located again or removed
+
+use dioxus::prelude::*;
+use std::path::PathBuf;
+
+use crate::state::directory_tabs::title_for;
+use crate::state::{use_app_state, AppState};
+use crate::utils::normalize_path_display;
+
+/// Go to a bookmarked folder and list it
+fn open_bookmark(mut app_state: AppState, path: PathBuf) {
+    if !path.is_dir() {
+        // Gone since the list was drawn; re-render so it shows as missing
+        app_state.bookmarks.write();
+        return;
+    }
+    spawn(async move {
+        if let Err(e) = app_state.navigate_to(path.clone()).await {
+            tracing::error!("Failed to navigate to {:?}: {}", path, e);
+            return;
+        }
+        if let Err(e) = app_state.set_root_folder_with_persistence(path.clone()).await {
+            tracing::error!("Failed to load {:?}: {}", path, e);
+        }
+    });
+}
+
+/// Ask where a missing bookmarked folder went
+fn locate_bookmark(mut app_state: AppState, path: PathBuf) {
+    spawn(async move {
+        if let Some(folder) = rfd::AsyncFileDialog::new().pick_folder().await {
+            app_state.relocate_bookmark(&path, folder.path().to_path_buf());
+        }
+    });
+}
+
+/// Sidebar section listing the bookmarked folders
+#[component]
+pub fn BookmarkList() -> Element {
+    let mut app_state = use_app_state();
+    let dragged = use_signal(|| None::<usize>);
+    let current_path = app_state.navigation.read().current_path.clone();
+    let (folders, stale) = {
+        let bookmarks = app_state.bookmarks.read();
+        (bookmarks.folders.clone(), bookmarks.stale(|path| path.is_dir()))
+    };
+    let is_bookmarked = folders.contains(&current_path);
+
+    rsx! {
+        div {
+            class: "bookmark-list",
+            role: "group",
+            "aria-label": t!("bookmarks.title"),
+            div {
+                class: "bookmark-header",
+                span { {t!("bookmarks.title")} }
+                button {
+                    class: "button",
+                    title: t!("bookmarks.add"),
+                    "aria-label": t!("bookmarks.add"),
+                    disabled: is_bookmarked,
+                    onclick: move |_| {
+                        app_state.bookmark_current_folder();
+                    },
+                    "+"
+                }
+            }
+            for (index, path) in folders.into_iter().enumerate() {
+                BookmarkItem {
+                    key: "{path.display()}",
+                    stale: stale.contains(&path),
+                    path,
+                    index,
+                    dragged,
+                }
+            }
+        }
+    }
+}
+
+/// One bookmark; drag it, or press Alt+↑/↓ on it, to reorder
+#[component]
+fn BookmarkItem(path: PathBuf, index: usize, stale: bool, dragged: Signal<Option<usize>>) -> Element {
+    let app_state = use_app_state();
+    let name = title_for(&path);
+    let display_path = normalize_path_display(&path);
+    let open_state = app_state.clone();
+    let open_path = path.clone();
+    let locate_state = app_state.clone();
+    let locate_path = path.clone();
+    let mut remove_state = app_state.clone();
+    let mut drop_state = app_state.clone();
+    let mut reorder_state = app_state.clone();
+
+    rsx! {
+        div {
+            class: if stale { "bookmark-item stale" } else { "bookmark-item" },
+            draggable: true,
+            ondragstart: move |_| dragged.set(Some(index)),
+            ondragover: move |evt| evt.prevent_default(),
+            ondrop: move |evt| {
+                evt.prevent_default();
+                evt.stop_propagation();
+                if let Some(from) = dragged.write().take() {
+                    drop_state.move_bookmark(from, index);
+                }
+            },
+            ondragend: move |_| dragged.set(None),
+            if stale {
+                span {
+                    class: "bookmark-name",
+                    title: t!("bookmarks.missing", path = display_path.clone()),
+                    "📁 {name}"
+                }
+                button {
+                    class: "bookmark-action",
+                    title: t!("bookmarks.locate"),
+                    onclick: move |_| locate_bookmark(locate_state.clone(), locate_path.clone()),
+                    {t!("bookmarks.locate")}
+                }
+            } else {
+                button {
+                    class: "bookmark-name",
+                    title: "{display_path}",
+                    onclick: move |_| open_bookmark(open_state.clone(), open_path.clone()),
+                    onkeydown: move |evt| {
+                        if !evt.data.modifiers().alt() {
+                            return;
+                        }
+                        let target = match evt.data.key() {
+                            dioxus::events::Key::ArrowUp => index.saturating_sub(1),
+                            dioxus::events::Key::ArrowDown => index + 1,
+                            _ => return,
+                        };
+                        // Keep Alt+↑ from also going to the parent folder
+                        evt.prevent_default();
+                        evt.stop_propagation();
+                        reorder_state.move_bookmark(index, target);
+                    },
+                    "⭐ {name}"
+                }
+            }
+            button {
+                class: "bookmark-action",
+                title: t!("bookmarks.remove"),
+                "aria-label": t!("bookmarks.remove"),
+                onclick: move |_| {
+                    remove_state.remove_bookmark(&path);
+                },
+                "×"
+            }
+        }
+    }
+}
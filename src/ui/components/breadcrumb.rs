@@ -0,0 +1,226 @@
+// Breadcrumb
+// Clickable segments of the current folder's path above the file list
+
+use dioxus::prelude::*;
+use std::path::{Component, Path, PathBuf};
+
+use crate::services::clipboard::{PathCopyMode, PathStyle};
+use crate::state::{use_app_state, AppState};
+use crate::ui::clipboard_actions::copy_paths;
+use crate::utils::normalize_path_display;
+
+/// Segments shown before the middle of a longer path collapses into a menu
+pub const MAX_VISIBLE_SEGMENTS: usize = 5;
+
+/// One clickable part of a path
+#[derive(Debug, Clone, PartialEq)]
+pub struct BreadcrumbSegment {
+    pub name: String,
+    /// The path up to and including this segment
+    pub path: PathBuf,
+}
+
+/// Segments split into the shown ends and the collapsed middle
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CollapsedBreadcrumb {
+    pub leading: Vec<BreadcrumbSegment>,
+    /// Shown in the `…` menu; empty when nothing was collapsed
+    pub hidden: Vec<BreadcrumbSegment>,
+    pub trailing: Vec<BreadcrumbSegment>,
+}
+
+/// Segments of `path` from the root down, each with its joined prefix
+///
+/// The root (`/`, or `C:\` with its drive prefix) is one segment.
+pub fn path_segments(path: &Path) -> Vec<BreadcrumbSegment> {
+    let mut segments: Vec<BreadcrumbSegment> = Vec::new();
+    let mut prefix = PathBuf::new();
+    for component in path.components() {
+        prefix.push(component);
+        let segment = BreadcrumbSegment {
+            name: prefix.to_string_lossy().to_string(),
+            path: prefix.clone(),
+        };
+        match component {
+            Component::RootDir if matches!(segments.last(), Some(last) if last.path.parent().is_none()) => {
+                // Join a drive prefix and its root into a single `C:\` segment
+                *segments.last_mut().unwrap() = segment;
+            }
+            Component::Prefix(_) | Component::RootDir => segments.push(segment),
+            _ => segments.push(BreadcrumbSegment {
+                name: component.as_os_str().to_string_lossy().to_string(),
+                path: prefix.clone(),
+            }),
+        }
+    }
+    segments
+}
+
+/// Keep the root and the last segments visible, hiding the middle when
+/// there are more than `max_visible`
+pub fn collapse_segments(segments: Vec<BreadcrumbSegment>, max_visible: usize) -> CollapsedBreadcrumb {
+    if segments.len() <= max_visible || max_visible < 2 {
+        return CollapsedBreadcrumb { leading: segments, ..Default::default() };
+    }
+    let mut leading = segments;
+    let trailing = leading.split_off(leading.len() - (max_visible - 1));
+    let hidden = leading.split_off(1);
+    CollapsedBreadcrumb { leading, hidden, trailing }
+}
+
+/// Go to `path`, keeping the history for Back, and list it
+fn open_segment(mut app_state: AppState, path: PathBuf) {
+    spawn(async move {
+        if let Err(e) = app_state.navigate_to(path.clone()).await {
+            tracing::error!("Failed to navigate to {:?}: {}", path, e);
+            return;
+        }
+        if let Err(e) = app_state.set_root_folder_with_persistence(path.clone()).await {
+            tracing::error!("Failed to load {:?}: {}", path, e);
+        }
+    });
+}
+
+/// Path of the current folder as clickable segments
+#[component]
+pub fn Breadcrumb() -> Element {
+    let app_state = use_app_state();
+    let mut menu_open = use_signal(|| false);
+    let current_path = app_state.navigation.read().current_path.clone();
+    let collapsed = collapse_segments(path_segments(&current_path), MAX_VISIBLE_SEGMENTS);
+    let leading_count = collapsed.leading.len();
+    let last_index = leading_count + collapsed.trailing.len() - 1;
+    let menu_state = app_state.clone();
+
+    rsx! {
+        nav {
+            class: "breadcrumb",
+            "aria-label": t!("breadcrumb.label"),
+            onkeydown: move |evt| {
+                if evt.data.key() == dioxus::events::Key::Escape && *menu_open.read() {
+                    menu_open.set(false);
+                    evt.stop_propagation();
+                }
+            },
+            span { class: "breadcrumb-home", "aria-hidden": "true", "🏠" }
+            for (index, segment) in collapsed.leading.into_iter().enumerate() {
+                BreadcrumbSegmentButton {
+                    key: "{segment.path.display()}",
+                    segment,
+                    separator: index > 0,
+                    is_current: index == last_index,
+                }
+            }
+            if !collapsed.hidden.is_empty() {
+                span { class: "breadcrumb-separator", "aria-hidden": "true", "/" }
+                span {
+                    class: "breadcrumb-overflow",
+                    button {
+                        class: "breadcrumb-segment",
+                        title: t!("breadcrumb.more", count = collapsed.hidden.len()),
+                        "aria-label": t!("breadcrumb.more", count = collapsed.hidden.len()),
+                        "aria-haspopup": "menu",
+                        "aria-expanded": menu_open.read().to_string(),
+                        onclick: move |_| {
+                            let open = *menu_open.read();
+                            menu_open.set(!open);
+                        },
+                        "…"
+                    }
+                    if *menu_open.read() {
+                        div {
+                            class: "breadcrumb-menu",
+                            role: "menu",
+                            for segment in collapsed.hidden {
+                                {
+                                    let app_state = menu_state.clone();
+                                    let path = segment.path.clone();
+                                    rsx! {
+                                        button {
+                                            key: "{segment.path.display()}",
+                                            class: "breadcrumb-menu-item",
+                                            role: "menuitem",
+                                            title: normalize_path_display(&segment.path),
+                                            onclick: move |_| {
+                                                menu_open.set(false);
+                                                open_segment(app_state.clone(), path.clone());
+                                            },
+                                            "{segment.name}"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            for (offset, segment) in collapsed.trailing.into_iter().enumerate() {
+                BreadcrumbSegmentButton {
+                    key: "{segment.path.display()}",
+                    segment,
+                    separator: leading_count + offset > 0,
+                    is_current: leading_count + offset == last_index,
+                }
+            }
+            button {
+                class: "breadcrumb-copy",
+                title: t!("breadcrumb.copy_path"),
+                "aria-label": t!("breadcrumb.copy_path"),
+                onclick: move |_| {
+                    spawn(copy_paths(app_state.clone(), vec![current_path.clone()], PathCopyMode::AbsolutePath, PathStyle::native()));
+                },
+                "⧉"
+            }
+        }
+    }
+}
+
+/// One path segment, preceded by a separator unless it is the root
+#[component]
+fn BreadcrumbSegmentButton(segment: BreadcrumbSegment, separator: bool, is_current: bool) -> Element {
+    let app_state = use_app_state();
+    let path = segment.path.clone();
+
+    rsx! {
+        if separator {
+            span { class: "breadcrumb-separator", "aria-hidden": "true", "/" }
+        }
+        button {
+            class: "breadcrumb-segment",
+            "aria-current": if is_current { "location" } else { "false" },
+            title: t!("breadcrumb.navigate", path = normalize_path_display(&segment.path)),
+            onclick: move |_| open_segment(app_state.clone(), path.clone()),
+            "{segment.name}"
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deep_path_collapses_middle_segments() {
+        let path: PathBuf = ["/", "home", "user", "photos", "2024", "summer", "beach"].iter().collect();
+        let segments = path_segments(&path);
+
+        let names: Vec<&str> = segments.iter().map(|segment| segment.name.as_str()).collect();
+        assert_eq!(names, ["/", "home", "user", "photos", "2024", "summer", "beach"]);
+        // Each segment navigates to its joined prefix
+        assert_eq!(segments[0].path, PathBuf::from("/"));
+        assert_eq!(segments[3].path, PathBuf::from("/home/user/photos"));
+        assert_eq!(segments.last().unwrap().path, path);
+
+        let collapsed = collapse_segments(segments.clone(), 5);
+        let names_of = |segments: &[BreadcrumbSegment]| segments.iter().map(|s| s.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names_of(&collapsed.leading), ["/"]);
+        assert_eq!(names_of(&collapsed.hidden), ["home", "user"]);
+        assert_eq!(names_of(&collapsed.trailing), ["photos", "2024", "summer", "beach"]);
+        assert_eq!(collapsed.trailing.last().unwrap().path, path);
+
+        // Short paths are shown in full
+        let short = collapse_segments(segments[..5].to_vec(), 5);
+        assert_eq!(short.leading.len(), 5);
+        assert!(short.hidden.is_empty() && short.trailing.is_empty());
+    }
+}
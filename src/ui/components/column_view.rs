@@ -0,0 +1,136 @@
+// Column view
+// The path to the selected item as side by side lists, with the selected file's preview on the right
+
+use dioxus::events::Key;
+use dioxus::prelude::*;
+
+use crate::services::{file_system::FileEntry, preview::PreviewData};
+use crate::state::column_view::ColumnStep;
+use crate::state::directory_tabs::title_for;
+use crate::state::{use_app_state, AppState};
+use crate::ui::components::DynamicContentPanel;
+
+/// Id of the scrolling row of columns
+const COLUMN_VIEW_ID: &str = "column-view";
+
+/// Show `entry` as the selected item and load its preview
+fn show_entry(app_state: AppState, mut selected_file: Signal<Option<FileEntry>>, mut preview_data: Signal<Option<PreviewData>>, entry: FileEntry) {
+    selected_file.set(Some(entry.clone()));
+    spawn(async move {
+        match app_state.handle_file_selection(entry.path.clone(), entry.is_directory).await {
+            Ok(preview) => preview_data.set(preview),
+            Err(e) => tracing::warn!("Failed to preview {:?}: {}", entry.path, e),
+        }
+    });
+    // Keep the newest column in view
+    let script = format!(
+        "requestAnimationFrame(() => {{ const row = document.getElementById('{}'); if (row) row.scrollLeft = row.scrollWidth; }});",
+        COLUMN_VIEW_ID
+    );
+    let _ = document::eval(&script);
+}
+
+/// Folders as columns; the arrow keys move up and down a column and between columns
+#[component]
+pub fn ColumnView(selected_file: Signal<Option<FileEntry>>, preview_data: Signal<Option<PreviewData>>) -> Element {
+    let app_state = use_app_state();
+    let listings = app_state.column_view_listings();
+    let (stack, previewed) = {
+        let view = app_state.column_view.read();
+        let stack: Vec<_> = view.path_stack().iter().map(|selection| selection.path.clone()).collect();
+        (stack, view.selected_file().is_some())
+    };
+    let key_state = app_state.clone();
+
+    rsx! {
+        div {
+            id: COLUMN_VIEW_ID,
+            class: "column-view",
+            role: "group",
+            "aria-label": "Column view",
+            tabindex: 0,
+            onkeydown: move |evt| {
+                let step = match evt.key() {
+                    Key::ArrowUp => ColumnStep::Up,
+                    Key::ArrowDown => ColumnStep::Down,
+                    Key::ArrowLeft => ColumnStep::Left,
+                    Key::ArrowRight => ColumnStep::Right,
+                    _ => return,
+                };
+                evt.prevent_default();
+                evt.stop_propagation();
+                let mut app_state = key_state.clone();
+                spawn(async move {
+                    match app_state.step_column_view(step).await {
+                        Ok(Some(entry)) => show_entry(app_state, selected_file, preview_data, entry),
+                        Ok(None) => {}
+                        Err(e) => tracing::warn!("Failed to move in column view: {}", e),
+                    }
+                });
+            },
+
+            for (column, (folder, entries)) in listings.into_iter().enumerate() {
+                {
+                    let selected = stack.get(column).cloned();
+                    let title = title_for(&folder);
+                    rsx! {
+                        ul {
+                            key: "{folder.display()}",
+                            class: "column-view-column",
+                            role: "listbox",
+                            "aria-label": "{title}",
+                            if entries.is_empty() {
+                                li { class: "column-view-empty", "Empty folder" }
+                            }
+                            for entry in entries {
+                                {
+                                    let is_selected = selected.as_ref() == Some(&entry.path);
+                                    let click_state = app_state.clone();
+                                    let clicked = entry.clone();
+                                    rsx! {
+                                        li {
+                                            key: "{entry.path.display()}",
+                                            class: if is_selected { "column-view-item selected" } else { "column-view-item" },
+                                            role: "option",
+                                            "aria-selected": is_selected.to_string(),
+                                            title: "{entry.name}",
+                                            onclick: move |_| {
+                                                let mut app_state = click_state.clone();
+                                                let entry = clicked.clone();
+                                                spawn(async move {
+                                                    match app_state.select_column_entry(column, &entry).await {
+                                                        Ok(()) => show_entry(app_state, selected_file, preview_data, entry),
+                                                        Err(e) => tracing::warn!("Failed to open {:?} in column view: {}", entry.path, e),
+                                                    }
+                                                });
+                                            },
+                                            span {
+                                                class: "column-view-name",
+                                                if entry.is_directory { "📁 " } else { "📄 " }
+                                                "{entry.name}"
+                                            }
+                                            if entry.is_directory {
+                                                span { class: "column-view-chevron", "aria-hidden": "true", "›" }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            // The selected file's preview takes the place of the next column
+            if previewed {
+                div {
+                    class: "column-view-preview",
+                    DynamicContentPanel {
+                        selected_file: selected_file,
+                        preview_data: preview_data,
+                    }
+                }
+            }
+        }
+    }
+}
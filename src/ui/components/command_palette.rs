@@ -3,9 +3,11 @@ use dioxus_free_icons::icons::fa_solid_icons::FaMagnifyingGlass;
 use dioxus_free_icons::Icon;
 use std::collections::HashMap;
 
+use crate::services::output::{run_command, CustomCommand};
+use crate::ui::clipboard_actions;
 use crate::state::{
-    use_app_state, Command, CommandPaletteState, 
-    SystemCommand, EditorCommand, FileCommand, NavigationCommand, ViewCommand
+    use_app_state, Command, CommandPaletteState, NamePattern, PanelTab,
+    SystemCommand, EditorCommand, FileCommand, NavigationCommand, ViewCommand, SelectionCommand, ViewMode
 };
 
 /// Command palette component for VS Code-style command searching and execution
@@ -19,14 +21,35 @@ pub fn CommandPalette() -> Element {
         return rsx! {};
     }
 
+    // A selection command is waiting for the pattern to select by
+    if let Some(command) = palette_state.pattern_prompt.clone() {
+        return rsx! {
+            div {
+                class: "command-palette-overlay",
+                onclick: move |_| {
+                    app_state.command_registry.write().close_command_palette();
+                },
+                div {
+                    class: "command-palette",
+                    role: "dialog",
+                    "aria-modal": "true",
+                    "aria-label": "Select files by pattern",
+                    onclick: move |e| e.stop_propagation(),
+                    PatternPrompt { command }
+                }
+            }
+        };
+    }
+
     // Get filtered commands based on search query
     let filtered_commands = get_filtered_commands(&app_state.command_registry.read().commands, &palette_state.search_query);
+    let has_selection = app_state.get_selection_count() > 0;
     
     rsx! {
         div {
             class: "command-palette-overlay",
             onclick: move |_| {
-                app_state.command_registry.write().palette_state.is_visible = false;
+                app_state.command_registry.write().close_command_palette();
             },
             div {
                 class: "command-palette",
@@ -115,6 +138,7 @@ pub fn CommandPalette() -> Element {
                             CommandItem {
                                 command: command.clone(),
                                 is_selected: index == palette_state.selected_index,
+                                is_available: command.is_available(has_selection),
                                 index: index,
                             }
                         }
@@ -136,9 +160,9 @@ pub fn CommandPalette() -> Element {
     }
 }
 
-/// Individual command item in the palette
+/// Individual command item in the palette; unavailable ones are greyed out
 #[component]
-fn CommandItem(command: Command, is_selected: bool, index: usize) -> Element {
+fn CommandItem(command: Command, is_selected: bool, is_available: bool, index: usize) -> Element {
     let mut app_state = use_app_state();
     
     // Create accessible description
@@ -154,19 +178,30 @@ fn CommandItem(command: Command, is_selected: bool, index: usize) -> Element {
         shortcuts_text
     );
     
+    let mut class = String::from("command-item");
+    if is_selected {
+        class.push_str(" selected");
+    }
+    if !is_available {
+        class.push_str(" disabled");
+    }
+    
     rsx! {
         div {
             id: "command-item-{index}",
-            class: if is_selected { "command-item selected" } else { "command-item" },
+            class: "{class}",
             role: "option",
             "aria-selected": "{is_selected}",
+            "aria-disabled": "{!is_available}",
             "aria-label": "{aria_label}",
             tabindex: if is_selected { "0" } else { "-1" },
             onclick: {
                 let mut app_state_click = app_state.clone();
                 let command_click = command.clone();
                 move |_| {
-                    execute_command(&command_click, &mut app_state_click);
+                    if is_available {
+                        execute_command(&command_click, &mut app_state_click);
+                    }
                 }
             },
             onmouseenter: {
@@ -207,6 +242,82 @@ fn CommandItem(command: Command, is_selected: bool, index: usize) -> Element {
     }
 }
 
+/// Search box of a "Select Files Matching…" command, taking a glob or `/regex/`
+#[component]
+fn PatternPrompt(command: SelectionCommand) -> Element {
+    let mut app_state = use_app_state();
+    let SelectionCommand::SelectMatching { invert, mode } = command;
+    let input = app_state.command_registry.read().palette_state.search_query.clone();
+    let status = if input.trim().is_empty() {
+        "Type a glob like *.jpg, or a /regex/".to_string()
+    } else {
+        match NamePattern::parse(&input) {
+            Ok(pattern) => {
+                let count = app_state
+                    .sorted_file_entries()
+                    .iter()
+                    .filter(|entry| pattern.matches(&entry.name) != invert)
+                    .count();
+                format!("{} {} selected", count, if count == 1 { "item will be" } else { "items will be" })
+            }
+            Err(e) => format!("Invalid regex: {}", e),
+        }
+    };
+
+    rsx! {
+        div {
+            class: "command-palette-search",
+            Icon {
+                width: 16,
+                height: 16,
+                icon: FaMagnifyingGlass,
+                class: "search-icon"
+            }
+            input {
+                r#type: "text",
+                placeholder: if invert { "Select files not matching…" } else { "Select files matching…" },
+                value: "{input}",
+                class: "search-input",
+                "aria-label": "File name pattern",
+                "aria-describedby": "pattern-prompt-status",
+                autofocus: true,
+                oninput: {
+                    let mut app_state = app_state.clone();
+                    move |e: FormEvent| {
+                        app_state.command_registry.write().palette_state.search_query = e.value();
+                    }
+                },
+                onkeydown: move |e| {
+                    match e.key().to_string().as_str() {
+                        "Enter" => {
+                            e.prevent_default();
+                            let input = app_state.command_registry.read().palette_state.search_query.clone();
+                            // An invalid regex keeps the prompt open to be fixed
+                            if let Ok(pattern) = NamePattern::parse(&input) {
+                                let count = app_state.select_matching(&pattern, invert, mode);
+                                tracing::info!("Selected {} items by pattern {:?}", count, input);
+                                app_state.command_registry.write().close_command_palette();
+                            }
+                        }
+                        "Escape" => {
+                            e.prevent_default();
+                            app_state.command_registry.write().close_command_palette();
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        div {
+            id: "pattern-prompt-status",
+            class: "command-palette-footer",
+            role: "status",
+            "aria-live": "polite",
+            span { class: "hint", "{status}" }
+        }
+    }
+}
+
 /// Handle keyboard navigation within the command palette
 fn handle_keyboard_navigation(e: KeyboardEvent, app_state: &mut crate::state::AppState, command_count: usize) {
     if command_count == 0 {
@@ -233,13 +344,14 @@ fn handle_keyboard_navigation(e: KeyboardEvent, app_state: &mut crate::state::Ap
                 &app_state.command_registry.read().palette_state.search_query
             );
             let selected_index = app_state.command_registry.read().palette_state.selected_index;
-            if let Some(command) = commands.get(selected_index) {
+            let has_selection = app_state.get_selection_count() > 0;
+            if let Some(command) = commands.get(selected_index).filter(|command| command.is_available(has_selection)) {
                 execute_command(command, app_state);
             }
         }
         "Escape" => {
             e.prevent_default();
-            app_state.command_registry.write().palette_state.is_visible = false;
+            app_state.command_registry.write().close_command_palette();
         }
         _ => {}
     }
@@ -250,7 +362,7 @@ fn execute_command(command: &Command, app_state: &mut crate::state::AppState) {
     use crate::state::CommandHandler;
     
     // Close the palette first
-    app_state.command_registry.write().palette_state.is_visible = false;
+    app_state.command_registry.write().close_command_palette();
     
     match &command.handler {
         CommandHandler::System(system_cmd) => {
@@ -268,9 +380,34 @@ fn execute_command(command: &Command, app_state: &mut crate::state::AppState) {
         CommandHandler::View(view_cmd) => {
             execute_view_command(view_cmd, app_state);
         }
+        CommandHandler::Shell(custom) => {
+            execute_shell_command(custom, app_state);
+        }
+        CommandHandler::Selection(selection_cmd) => {
+            // The palette stays open for the pattern
+            app_state.command_registry.write().prompt_for_pattern(selection_cmd.clone());
+        }
     }
 }
 
+/// Run a user-defined shell command in the current folder, showing its output
+fn execute_shell_command(custom: &CustomCommand, app_state: &mut crate::state::AppState) {
+    {
+        let mut panel = app_state.panel_state.write();
+        panel.is_visible = true;
+        panel.active_tab = PanelTab::Output;
+    }
+    
+    let cwd = app_state.navigation.read().current_path.clone();
+    let output = app_state.output_log.clone();
+    let custom = custom.clone();
+    std::thread::spawn(move || {
+        if let Err(e) = run_command(&custom, &cwd, &output) {
+            tracing::warn!("Custom command '{}' failed to run: {}", custom.name, e);
+        }
+    });
+}
+
 /// Execute system commands
 fn execute_system_command(command: &SystemCommand, app_state: &mut crate::state::AppState) {
     match command {
@@ -321,6 +458,9 @@ fn execute_editor_command(command: &EditorCommand, app_state: &mut crate::state:
             // TODO: Implement close tabs to right
             println!("Close tabs to right");
         }
+        EditorCommand::ReopenClosedTab => {
+            app_state.editor_state.write().reopen_closed_tab();
+        }
         EditorCommand::ToggleTabPin => {
             // TODO: Implement toggle tab pin
             println!("Toggle tab pin");
@@ -338,6 +478,12 @@ fn execute_editor_command(command: &EditorCommand, app_state: &mut crate::state:
 
 /// Execute file commands
 fn execute_file_command(command: &FileCommand, app_state: &mut crate::state::AppState) {
+    // Operations on the selection run through their menu items, which ask for
+    // destinations and confirmation
+    if let Some(menu_id) = command.menu_id() {
+        app_state.menu_action_request.set(Some(menu_id));
+        return;
+    }
     match command {
         FileCommand::NewFile => {
             // TODO: Implement new file creation
@@ -355,14 +501,6 @@ fn execute_file_command(command: &FileCommand, app_state: &mut crate::state::App
             // TODO: Implement save all files
             println!("Save all files");
         }
-        FileCommand::RenameFile => {
-            // TODO: Implement rename file
-            println!("Rename file");
-        }
-        FileCommand::DeleteFile => {
-            // TODO: Implement delete file
-            println!("Delete file");
-        }
         FileCommand::CopyFile => {
             // TODO: Implement copy file
             println!("Copy file");
@@ -375,6 +513,21 @@ fn execute_file_command(command: &FileCommand, app_state: &mut crate::state::App
             // TODO: Implement paste file
             println!("Paste file");
         }
+        FileCommand::CopyPaths(mode, style) => {
+            let paths = clipboard_actions::clipboard_selection(app_state);
+            spawn(clipboard_actions::copy_paths(app_state.clone(), paths, *mode, *style));
+        }
+        FileCommand::CopyAsFileReference => {
+            let paths = clipboard_actions::clipboard_selection(app_state);
+            spawn(clipboard_actions::copy_as_file_references(app_state.clone(), paths));
+        }
+        FileCommand::RenameFile
+        | FileCommand::DeleteFile
+        | FileCommand::DeletePermanently
+        | FileCommand::CopyTo
+        | FileCommand::MoveTo
+        | FileCommand::Duplicate
+        | FileCommand::Organize(_) => {}
     }
 }
 
@@ -416,8 +569,11 @@ fn execute_view_command(command: &ViewCommand, app_state: &mut crate::state::App
             app_state.panel_state.write().is_visible = !is_visible;
         }
         ViewCommand::ToggleViewMode => {
-            // TODO: Implement view mode toggle
-            println!("Toggle view mode");
+            let next = match *app_state.view_mode.read() {
+                ViewMode::Grid => ViewMode::List,
+                _ => ViewMode::Grid,
+            };
+            app_state.view_mode.set(next);
         }
         ViewCommand::ZoomIn => {
             // TODO: Implement zoom functionality
@@ -431,18 +587,10 @@ fn execute_view_command(command: &ViewCommand, app_state: &mut crate::state::App
             // TODO: Implement zoom reset
             println!("Reset zoom");
         }
-        ViewCommand::GridView => {
-            // TODO: Implement grid view
-            println!("Switch to grid view");
-        }
-        ViewCommand::ListView => {
-            // TODO: Implement list view
-            println!("Switch to list view");
-        }
-        ViewCommand::PreviewView => {
-            // TODO: Implement preview view
-            println!("Switch to preview view");
-        }
+        ViewCommand::GridView => app_state.view_mode.set(ViewMode::Grid),
+        ViewCommand::ListView => app_state.view_mode.set(ViewMode::List),
+        ViewCommand::PreviewView => app_state.view_mode.set(ViewMode::Preview),
+        ViewCommand::ColumnsView => app_state.view_mode.set(ViewMode::Columns),
     }
 }
 
@@ -500,6 +648,23 @@ fn calculate_fuzzy_score(text: &str, query: &str) -> i32 {
         return 500;
     }
     
+    // Match at the start of a later word, e.g. "move" in "Show Move History"
+    let at_word_start = text.match_indices(query).any(|(i, _)| {
+        text[..i].chars().next_back().map_or(true, |c| !c.is_alphanumeric())
+    });
+    if at_word_start {
+        return 400;
+    }
+    
+    // Word initials, e.g. "mt" for "Move To…"
+    let initials: String = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter_map(|word| word.chars().next())
+        .collect();
+    if initials.starts_with(query) {
+        return 300;
+    }
+    
     // Contains match gets medium score
     if text.contains(query) {
         return 250;
@@ -576,8 +741,8 @@ mod tests {
 
     #[test]
     fn test_fuzzy_score_fuzzy_match() {
-        // "goto" should match "GoToFile" with some score
-        let score = calculate_fuzzy_score("gotofile", "goto");
+        // "gtf" should match "gotofile" with some score
+        let score = calculate_fuzzy_score("gotofile", "gtf");
         assert!(score > 0);
         assert!(score < 250); // Less than contains match
     }
@@ -588,17 +753,19 @@ mod tests {
         commands.insert("test1".to_string(), Command {
             id: "test1".to_string(),
             title: "Test Command 1".to_string(),
-            description: "First test".to_string(),
+            description: Some("First test".to_string()),
             category: "Test".to_string(),
             shortcuts: vec![],
+            enabled: true,
             handler: CommandHandler::System(SystemCommand::ShowCommandPalette),
         });
         commands.insert("test2".to_string(), Command {
             id: "test2".to_string(),
             title: "Another Command".to_string(),
-            description: "Second test".to_string(),
+            description: Some("Second test".to_string()),
             category: "Test".to_string(),
             shortcuts: vec![],
+            enabled: true,
             handler: CommandHandler::System(SystemCommand::ToggleSettings),
         });
 
@@ -612,17 +779,19 @@ mod tests {
         commands.insert("test1".to_string(), Command {
             id: "test1".to_string(),
             title: "Test Command".to_string(),
-            description: "First test".to_string(),
+            description: Some("First test".to_string()),
             category: "Test".to_string(),
             shortcuts: vec![],
+            enabled: true,
             handler: CommandHandler::System(SystemCommand::ShowCommandPalette),
         });
         commands.insert("other".to_string(), Command {
             id: "other".to_string(),
             title: "Other Command".to_string(),
-            description: "Different".to_string(),
+            description: Some("Different".to_string()),
             category: "Other".to_string(),
             shortcuts: vec![],
+            enabled: true,
             handler: CommandHandler::System(SystemCommand::ToggleSettings),
         });
 
@@ -630,4 +799,40 @@ mod tests {
         assert_eq!(filtered.len(), 1);
         assert_eq!(filtered[0].title, "Test Command");
     }
-}
\ No newline at end of file
+
+    fn file_command(id: &str, title: &str, handler: FileCommand) -> Command {
+        Command {
+            id: id.to_string(),
+            title: title.to_string(),
+            description: None,
+            category: "File".to_string(),
+            shortcuts: vec![],
+            enabled: true,
+            handler: CommandHandler::File(handler),
+        }
+    }
+
+    #[test]
+    fn test_fuzzy_ranking_prefers_prefixes_then_word_starts() {
+        let mut commands = HashMap::new();
+        for command in [
+            file_command("remove", "Remove Bookmark", FileCommand::NewFile),
+            file_command("history", "Show Move History", FileCommand::NewFile),
+            file_command("move_to", "Move To…", FileCommand::MoveTo),
+            file_command("duplicate", "Duplicate", FileCommand::Duplicate),
+        ] {
+            commands.insert(command.id.clone(), command);
+        }
+
+        let titles = |query: &str| {
+            get_filtered_commands(&commands, query)
+                .into_iter()
+                .map(|command| command.title)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(titles("move"), ["Move To…", "Show Move History", "Remove Bookmark"]);
+        // Initials beat a scattered match
+        assert_eq!(titles("mt"), ["Move To…", "Show Move History"]);
+        assert_eq!(titles("dup"), ["Duplicate"]);
+    }
+}
@@ -4,6 +4,10 @@ use crate::services::FileEntry;
 use crate::state::{use_app_state, use_selection_state};
 use crate::ui::{use_shortcut_handler};
 use crate::ui::shortcuts::ShortcutAction;
+use crate::ui::archive_actions::{self, is_extractable_archive};
+use crate::ui::clipboard_actions;
+use crate::services::clipboard::{PathCopyMode, PathStyle};
+use crate::services::archive::{CompressCommand, ExtractArchiveCommand};
 
 /// Context menu item action types
 #[derive(Debug, Clone, PartialEq)]
@@ -12,6 +16,7 @@ pub enum ContextMenuAction {
     Cut,
     Paste,
     Delete,
+    DeletePermanently,
     Rename,
     Properties,
     NewFolder,
@@ -20,6 +25,17 @@ pub enum ContextMenuAction {
     SelectAll,
     OpenWith,
     OpenInExplorer,
+    ExtractHere,
+    ExtractTo,
+    CompressToArchive,
+    CopyPath,
+    CopyRelativePath,
+    CopyName,
+    CopyNameWithoutExtension,
+    CopyParentPath,
+    /// Full path with `/` separators, offered where the native separator differs
+    CopyPosixPath,
+    CopyAsFileReference,
     Separator, // Visual separator in menu
 }
 
@@ -29,7 +45,8 @@ impl ContextMenuAction {
             ContextMenuAction::Copy => "Copy",
             ContextMenuAction::Cut => "Cut",
             ContextMenuAction::Paste => "Paste",
-            ContextMenuAction::Delete => "Delete",
+            ContextMenuAction::Delete => "Move to Trash",
+            ContextMenuAction::DeletePermanently => "Delete Permanently",
             ContextMenuAction::Rename => "Rename",
             ContextMenuAction::Properties => "Properties",
             ContextMenuAction::NewFolder => "New Folder",
@@ -38,6 +55,16 @@ impl ContextMenuAction {
             ContextMenuAction::SelectAll => "Select All",
             ContextMenuAction::OpenWith => "Open With...",
             ContextMenuAction::OpenInExplorer => "Show in Explorer",
+            ContextMenuAction::ExtractHere => "Extract Here",
+            ContextMenuAction::ExtractTo => "Extract To...",
+            ContextMenuAction::CompressToArchive => "Compress to Archive...",
+            ContextMenuAction::CopyPath => "Copy Path",
+            ContextMenuAction::CopyRelativePath => "Copy Relative Path",
+            ContextMenuAction::CopyName => "Copy Name",
+            ContextMenuAction::CopyNameWithoutExtension => "Copy Name Without Extension",
+            ContextMenuAction::CopyParentPath => "Copy Parent Folder Path",
+            ContextMenuAction::CopyPosixPath => "Copy Path with Forward Slashes",
+            ContextMenuAction::CopyAsFileReference => "Copy as File Reference",
             ContextMenuAction::Separator => "",
         }
     }
@@ -48,6 +75,7 @@ impl ContextMenuAction {
             ContextMenuAction::Cut => "✂️",
             ContextMenuAction::Paste => "📄",
             ContextMenuAction::Delete => "🗑️",
+            ContextMenuAction::DeletePermanently => "⛔",
             ContextMenuAction::Rename => "✏️",
             ContextMenuAction::Properties => "ℹ️",
             ContextMenuAction::NewFolder => "📁",
@@ -56,16 +84,40 @@ impl ContextMenuAction {
             ContextMenuAction::SelectAll => "☑️",
             ContextMenuAction::OpenWith => "🔗",
             ContextMenuAction::OpenInExplorer => "🔍",
+            ContextMenuAction::ExtractHere | ContextMenuAction::ExtractTo => "📦",
+            ContextMenuAction::CompressToArchive => "🗜️",
+            ContextMenuAction::CopyPath
+            | ContextMenuAction::CopyRelativePath
+            | ContextMenuAction::CopyName
+            | ContextMenuAction::CopyNameWithoutExtension
+            | ContextMenuAction::CopyParentPath
+            | ContextMenuAction::CopyPosixPath => "🔤",
+            ContextMenuAction::CopyAsFileReference => "📎",
             ContextMenuAction::Separator => "",
         }
     }
 
+    /// Text copied by the path copy actions, and the separators it uses
+    pub fn path_copy(&self) -> Option<(PathCopyMode, PathStyle)> {
+        let native = PathStyle::native();
+        match self {
+            ContextMenuAction::CopyPath => Some((PathCopyMode::AbsolutePath, native)),
+            ContextMenuAction::CopyRelativePath => Some((PathCopyMode::RelativePath, native)),
+            ContextMenuAction::CopyName => Some((PathCopyMode::Name, native)),
+            ContextMenuAction::CopyNameWithoutExtension => Some((PathCopyMode::NameWithoutExtension, native)),
+            ContextMenuAction::CopyParentPath => Some((PathCopyMode::ParentDirectory, native)),
+            ContextMenuAction::CopyPosixPath => Some((PathCopyMode::AbsolutePath, PathStyle::Posix)),
+            _ => None,
+        }
+    }
+
     pub fn shortcut(&self) -> Option<&'static str> {
         match self {
             ContextMenuAction::Copy => Some("Ctrl+C"),
             ContextMenuAction::Cut => Some("Ctrl+X"),
             ContextMenuAction::Paste => Some("Ctrl+V"),
             ContextMenuAction::Delete => Some("Del"),
+            ContextMenuAction::DeletePermanently => Some("Shift+Del"),
             ContextMenuAction::Rename => Some("F2"),
             ContextMenuAction::Refresh => Some("F5"),
             ContextMenuAction::SelectAll => Some("Ctrl+A"),
@@ -81,6 +133,7 @@ impl ContextMenuAction {
             ContextMenuAction::Cut => Some(ShortcutAction::Cut),
             ContextMenuAction::Paste => Some(ShortcutAction::Paste),
             ContextMenuAction::Delete => Some(ShortcutAction::Delete),
+            ContextMenuAction::DeletePermanently => Some(ShortcutAction::DeletePermanently),
             ContextMenuAction::Rename => Some(ShortcutAction::Rename),
             ContextMenuAction::Refresh => Some(ShortcutAction::Refresh),
             ContextMenuAction::SelectAll => Some(ShortcutAction::SelectAll),
@@ -93,14 +146,29 @@ impl ContextMenuAction {
     /// Check if action is enabled for the current context
     pub fn is_enabled(&self, selected_files: &[PathBuf], has_clipboard: bool) -> bool {
         match self {
-            ContextMenuAction::Copy | ContextMenuAction::Cut | ContextMenuAction::Delete => {
+            ContextMenuAction::Copy
+            | ContextMenuAction::Cut
+            | ContextMenuAction::Delete
+            | ContextMenuAction::DeletePermanently => {
                 !selected_files.is_empty()
             }
+            // These fall back to the right-clicked entry, so they are always available on a file
+            ContextMenuAction::CopyPath
+            | ContextMenuAction::CopyRelativePath
+            | ContextMenuAction::CopyName
+            | ContextMenuAction::CopyNameWithoutExtension
+            | ContextMenuAction::CopyParentPath
+            | ContextMenuAction::CopyPosixPath
+            | ContextMenuAction::CopyAsFileReference
+            | ContextMenuAction::CompressToArchive => true,
             ContextMenuAction::Paste => has_clipboard,
             ContextMenuAction::Rename => selected_files.len() == 1,
             ContextMenuAction::Properties => selected_files.len() == 1,
             ContextMenuAction::OpenWith => selected_files.len() == 1,
             ContextMenuAction::OpenInExplorer => selected_files.len() == 1,
+            ContextMenuAction::ExtractHere | ContextMenuAction::ExtractTo => {
+                selected_files.len() == 1 && is_extractable_archive(&selected_files[0])
+            }
             _ => true, // Actions like New Folder, Refresh, Select All are always enabled
         }
     }
@@ -139,22 +207,47 @@ impl ContextMenuState {
         self.position = MenuPosition { x, y };
         self.target_file = target_file.clone();
         
+        let is_archive = target_file.as_ref()
+            .map_or(false, |file| is_extractable_archive(&file.path));
+        
         // Generate appropriate menu items based on context
         self.menu_items = if target_file.is_some() {
             // File/folder selected
-            vec![
+            let mut items = vec![
                 ContextMenuAction::Copy,
                 ContextMenuAction::Cut,
                 ContextMenuAction::Paste,
                 ContextMenuAction::Separator,
+                ContextMenuAction::CopyPath,
+                ContextMenuAction::CopyRelativePath,
+                ContextMenuAction::CopyName,
+                ContextMenuAction::CopyNameWithoutExtension,
+                ContextMenuAction::CopyParentPath,
+                ContextMenuAction::CopyAsFileReference,
+                ContextMenuAction::Separator,
                 ContextMenuAction::Delete,
+                ContextMenuAction::DeletePermanently,
                 ContextMenuAction::Rename,
                 ContextMenuAction::Separator,
                 ContextMenuAction::OpenWith,
                 ContextMenuAction::OpenInExplorer,
+                ContextMenuAction::CompressToArchive,
                 ContextMenuAction::Separator,
                 ContextMenuAction::Properties,
-            ]
+            ];
+            if PathStyle::native() != PathStyle::Posix {
+                if let Some(index) = items.iter().position(|item| *item == ContextMenuAction::CopyPath) {
+                    items.insert(index + 1, ContextMenuAction::CopyPosixPath);
+                }
+            }
+            if is_archive {
+                items.splice(0..0, [
+                    ContextMenuAction::ExtractHere,
+                    ContextMenuAction::ExtractTo,
+                    ContextMenuAction::Separator,
+                ]);
+            }
+            items
         } else {
             // Empty space / background
             vec![
@@ -279,6 +372,7 @@ pub fn ContextMenu(mut props: ContextMenuProps) -> Element {
 pub fn use_context_menu() -> (Signal<ContextMenuState>, impl Fn(ContextMenuAction)) {
     let menu_state = use_signal(ContextMenuState::default);
     let mut shortcut_handler = use_shortcut_handler();
+    let app_state = use_app_state();
     
     let handle_action = move |action: ContextMenuAction| {
         tracing::info!("Context menu action triggered: {:?}", action);
@@ -293,7 +387,9 @@ pub fn use_context_menu() -> (Signal<ContextMenuState>, impl Fn(ContextMenuActio
             // Handle custom actions that don't have shortcut equivalents
             match action {
                 ContextMenuAction::OpenWith => {
-                    tracing::info!("Open with action - TODO: implement");
+                    // The picker is shown by the menu handler, which owns its dialog
+                    let mut menu_action_request = app_state.menu_action_request;
+                    menu_action_request.set(Some("open_with"));
                 }
                 ContextMenuAction::OpenInExplorer => {
                     tracing::info!("Open in explorer action - TODO: implement");
@@ -301,6 +397,64 @@ pub fn use_context_menu() -> (Signal<ContextMenuState>, impl Fn(ContextMenuActio
                 ContextMenuAction::NewFile => {
                     tracing::info!("New file action - TODO: implement");
                 }
+                ContextMenuAction::ExtractHere | ContextMenuAction::ExtractTo => {
+                    let Some(archive) = menu_state.read().target_file.as_ref().map(|file| file.path.clone()) else {
+                        return;
+                    };
+                    let app_state = app_state.clone();
+                    spawn(async move {
+                        let command = if action == ContextMenuAction::ExtractTo {
+                            match archive_actions::show_extract_destination_dialog(&archive).await {
+                                Some(destination) => {
+                                    archive_actions::resolve_extract_conflicts(ExtractArchiveCommand::new(archive, destination)).await
+                                }
+                                None => return,
+                            }
+                        } else {
+                            ExtractArchiveCommand::extract_here(archive)
+                        };
+                        archive_actions::extract_archive(app_state, command).await;
+                    });
+                }
+                ContextMenuAction::CompressToArchive => {
+                    // Like the copy actions, a click outside the selection compresses just that entry
+                    let selection = clipboard_actions::clipboard_selection(&app_state);
+                    let paths = match menu_state.read().target_file.as_ref() {
+                        Some(target) if !selection.contains(&target.path) => vec![target.path.clone()],
+                        _ => selection,
+                    };
+                    if paths.is_empty() {
+                        return;
+                    }
+                    let app_state = app_state.clone();
+                    spawn(async move {
+                        let Some(destination) = archive_actions::show_zip_destination_dialog(&paths).await else {
+                            return;
+                        };
+                        archive_actions::compress_to_archive(app_state, CompressCommand::new(paths, destination)).await;
+                    });
+                }
+                ContextMenuAction::CopyPath
+                | ContextMenuAction::CopyRelativePath
+                | ContextMenuAction::CopyName
+                | ContextMenuAction::CopyNameWithoutExtension
+                | ContextMenuAction::CopyParentPath
+                | ContextMenuAction::CopyPosixPath
+                | ContextMenuAction::CopyAsFileReference => {
+                    // Copy the selection when the clicked entry is part of it, otherwise just that entry
+                    let selection = clipboard_actions::clipboard_selection(&app_state);
+                    let paths = match menu_state.read().target_file.as_ref() {
+                        Some(target) if !selection.contains(&target.path) => vec![target.path.clone()],
+                        _ => selection,
+                    };
+                    let app_state = app_state.clone();
+                    spawn(async move {
+                        match action.path_copy() {
+                            Some((mode, style)) => clipboard_actions::copy_paths(app_state, paths, mode, style).await,
+                            None => clipboard_actions::copy_as_file_references(app_state, paths).await,
+                        }
+                    });
+                }
                 _ => {
                     tracing::warn!("Unhandled context menu action: {:?}", action);
                 }
@@ -366,6 +520,13 @@ mod tests {
         state.show_at(0.0, 0.0, Some(file_entry));
         assert!(state.menu_items.contains(&ContextMenuAction::Copy));
         assert!(state.menu_items.contains(&ContextMenuAction::Properties));
+        assert!(state.menu_items.contains(&ContextMenuAction::CopyRelativePath));
+        assert!(state.menu_items.contains(&ContextMenuAction::CopyParentPath));
+        assert!(state.menu_items.contains(&ContextMenuAction::CompressToArchive));
+        assert_eq!(
+            ContextMenuAction::CopyPosixPath.path_copy(),
+            Some((PathCopyMode::AbsolutePath, PathStyle::Posix))
+        );
         
         // Test background context menu
         state.show_at(0.0, 0.0, None);
@@ -2,10 +2,16 @@
 // Provides confirmation dialogs, progress dialogs, toast notifications, and operation summaries
 
 use dioxus::prelude::*;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
+use crate::services::open_with::OpenWithApp;
 use crate::services::operations::{
     ErrorSeverity, RecoverySuggestion, ProgressInfo
 };
+use crate::state::use_app_state;
+use crate::ui::components::modal::{Modal, ModalRole, modal_title_id};
+use crate::utils::{format_duration, format_rate, format_size};
 
 /// Types of confirmation dialogs
 #[derive(Debug, Clone, PartialEq)]
@@ -16,14 +22,50 @@ pub enum ConfirmationAction {
     BatchOperation { operation_type: String, count: usize },
 }
 
-/// Confirmation dialog result
+/// Outcome of a dialog that asks the user for a decision or a value
+///
+/// `Declined` means the user answered "no"; `Cancelled` means the dialog was
+/// dismissed without an answer (Escape, close button, backdrop). Callers act
+/// only on `Confirmed`.
 #[derive(Debug, Clone, PartialEq)]
-pub enum ConfirmationResult {
-    Confirmed,
+pub enum DialogResult<T = ()> {
+    Confirmed(T),
+    Declined,
     Cancelled,
-    Pending,
 }
 
+impl<T> DialogResult<T> {
+    pub fn is_confirmed(&self) -> bool {
+        matches!(self, DialogResult::Confirmed(_))
+    }
+
+    /// The confirmed value, or `None` if the user declined or dismissed the dialog
+    pub fn confirmed(self) -> Option<T> {
+        match self {
+            DialogResult::Confirmed(value) => Some(value),
+            DialogResult::Declined | DialogResult::Cancelled => None,
+        }
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> DialogResult<U> {
+        match self {
+            DialogResult::Confirmed(value) => DialogResult::Confirmed(f(value)),
+            DialogResult::Declined => DialogResult::Declined,
+            DialogResult::Cancelled => DialogResult::Cancelled,
+        }
+    }
+}
+
+/// Pickers only report a choice or a dismissal
+impl<T> From<Option<T>> for DialogResult<T> {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(DialogResult::Cancelled, DialogResult::Confirmed)
+    }
+}
+
+/// Confirmation dialog result
+pub type ConfirmationResult = DialogResult;
+
 /// Toast notification types
 #[derive(Debug, Clone, PartialEq)]
 pub enum ToastType {
@@ -99,43 +141,39 @@ pub fn ConfirmationDialog(props: ConfirmationDialogProps) -> Element {
     }
 
     let title = match &props.action {
-        ConfirmationAction::Delete { .. } => "Confirm Delete",
-        ConfirmationAction::Overwrite { .. } => "Confirm Overwrite", 
-        ConfirmationAction::MoveToTrash { .. } => "Move to Trash",
-        ConfirmationAction::BatchOperation { operation_type, .. } => operation_type,
+        ConfirmationAction::Delete { .. } => t!("dialog.confirm_delete.title"),
+        ConfirmationAction::Overwrite { .. } => t!("dialog.confirm_overwrite.title"),
+        ConfirmationAction::MoveToTrash { .. } => t!("dialog.move_to_trash.title"),
+        ConfirmationAction::BatchOperation { operation_type, .. } => operation_type.clone(),
     };
     
+    let item_text = |items: &[String]| {
+        if items.len() == 1 {
+            format!("\"{}\"", items[0])
+        } else {
+            t!("dialog.items", count = items.len())
+        }
+    };
     let message = match &props.action {
         ConfirmationAction::Delete { items, total_size } => {
-            let item_text = if items.len() == 1 {
-                format!("\"{}\"", items[0])
-            } else {
-                format!("{} items", items.len())
-            };
-            let size_text = format_file_size(*total_size);
-            format!("Are you sure you want to delete {}? ({} total)\n\nThis action cannot be undone.", item_text, size_text)
+            t!("dialog.confirm_delete.message", items = item_text(items), size = format_size(*total_size))
         }
         ConfirmationAction::Overwrite { target, source } => {
-            format!("\"{}\" already exists.\n\nDo you want to replace it with \"{}\"?", target, source)
+            t!("dialog.confirm_overwrite.message", target = target, source = source)
         }
         ConfirmationAction::MoveToTrash { items } => {
-            let item_text = if items.len() == 1 {
-                format!("\"{}\"", items[0])
-            } else {
-                format!("{} items", items.len())
-            };
-            format!("Move {} to trash?", item_text)
+            t!("dialog.move_to_trash.message", items = item_text(items))
         }
         ConfirmationAction::BatchOperation { operation_type, count } => {
-            format!("Perform {} operation on {} items?", operation_type.to_lowercase(), count)
+            t!("dialog.batch.message", count = *count, operation = operation_type.to_lowercase())
         }
     };
     
     let button_text = match &props.action {
-        ConfirmationAction::Delete { .. } => "Delete",
-        ConfirmationAction::Overwrite { .. } => "Replace",
-        ConfirmationAction::MoveToTrash { .. } => "Move to Trash",
-        ConfirmationAction::BatchOperation { .. } => "Continue",
+        ConfirmationAction::Delete { .. } => t!("dialog.confirm_delete.button"),
+        ConfirmationAction::Overwrite { .. } => t!("dialog.confirm_overwrite.button"),
+        ConfirmationAction::MoveToTrash { .. } => t!("dialog.move_to_trash.button"),
+        ConfirmationAction::BatchOperation { .. } => t!("dialog.batch.button"),
     };
     
     let icon = match &props.action {
@@ -151,45 +189,46 @@ pub fn ConfirmationDialog(props: ConfirmationDialogProps) -> Element {
         ErrorSeverity::Low => "confirmation-dialog low",
     };
 
+    let dialog_id = "confirmation-dialog";
+
     rsx! {
-        div {
-            class: "dialog-overlay",
-            onclick: move |_| props.on_result.call(ConfirmationResult::Cancelled),
+        Modal {
+            id: "{dialog_id}",
+            role: ModalRole::AlertDialog,
+            class: "{danger_class}",
+            described_by: format!("{}-message", dialog_id),
+            on_close: move |_| props.on_result.call(DialogResult::Cancelled),
+                
+            div {
+                class: "dialog-header",
+                span { class: "dialog-icon", "aria-hidden": "true", {icon} }
+                h3 { id: modal_title_id(dialog_id), {title} }
+            }
             
             div {
-                class: "{danger_class}",
-                onclick: |evt| evt.stop_propagation(),
-                
-                div {
-                    class: "dialog-header",
-                    span { class: "dialog-icon", {icon} }
-                    h3 { {title} }
+                class: "dialog-content",
+                p { 
+                    id: "{dialog_id}-message",
+                    class: "dialog-message",
+                    style: "white-space: pre-line;",
+                    {message}
                 }
                 
-                div {
-                    class: "dialog-content",
-                    p { 
-                        class: "dialog-message",
-                        style: "white-space: pre-line;",
-                        {message}
-                    }
-                    
-                    if props.show_details {
-                        div {
-                            class: "dialog-details",
-                            if let ConfirmationAction::Delete { items, .. } = &props.action {
-                                div {
-                                    h4 { "Items to delete:" }
-                                    ul {
-                                        class: "item-list",
-                                        for item in items.iter().take(10) {
-                                            li { {item.clone()} }
-                                        }
-                                        if items.len() > 10 {
-                                            li { 
-                                                class: "more-items",
-                                                "... and more items"
-                                            }
+                if props.show_details {
+                    div {
+                        class: "dialog-details",
+                        if let ConfirmationAction::Delete { items, .. } = &props.action {
+                            div {
+                                h4 { {t!("dialog.confirm_delete.items_heading")} }
+                                ul {
+                                    class: "item-list",
+                                    for item in items.iter().take(10) {
+                                        li { {item.clone()} }
+                                    }
+                                    if items.len() > 10 {
+                                        li { 
+                                            class: "more-items",
+                                            {t!("dialog.more_items")}
                                         }
                                     }
                                 }
@@ -197,24 +236,355 @@ pub fn ConfirmationDialog(props: ConfirmationDialogProps) -> Element {
                         }
                     }
                 }
-                
-                div {
-                    class: "dialog-actions",
-                    button {
-                        class: "button secondary",
-                        onclick: move |_| props.on_result.call(ConfirmationResult::Cancelled),
-                        "Cancel"
+            }
+            
+            div {
+                class: "dialog-actions",
+                button {
+                    class: "button secondary",
+                    onclick: move |_| props.on_result.call(DialogResult::Declined),
+                    {t!("dialog.cancel")}
+                }
+                button {
+                    class: match props.danger_level {
+                        ErrorSeverity::Critical | ErrorSeverity::High => "button danger",
+                        _ => "button primary"
+                    },
+                    onclick: move |_| props.on_result.call(DialogResult::Confirmed(())),
+                    {button_text}
+                }
+            }
+        }
+    }
+}
+
+/// A pending request for a line of text, answered when the input dialog closes
+#[derive(Clone)]
+pub struct InputDialogRequest {
+    pub title: String,
+    pub message: String,
+    pub initial_value: String,
+    /// Message explaining why typed text can't be submitted, if it can't
+    validate: Option<fn(&str) -> Option<String>>,
+    responder: Arc<Mutex<Option<oneshot::Sender<DialogResult<String>>>>>,
+}
+
+impl InputDialogRequest {
+    /// Create a request and the receiver its answer arrives on
+    ///
+    /// If the request is dropped unanswered the receiver errors, which callers
+    /// treat as `Cancelled`.
+    pub fn new(
+        title: impl Into<String>,
+        message: impl Into<String>,
+        initial_value: impl Into<String>,
+    ) -> (Self, oneshot::Receiver<DialogResult<String>>) {
+        let (sender, receiver) = oneshot::channel();
+        let request = Self {
+            title: title.into(),
+            message: message.into(),
+            initial_value: initial_value.into(),
+            validate: None,
+            responder: Arc::new(Mutex::new(Some(sender))),
+        };
+        (request, receiver)
+    }
+
+    /// Keep the dialog open while `validate` returns a message for the typed text
+    pub fn with_validation(mut self, validate: fn(&str) -> Option<String>) -> Self {
+        self.validate = Some(validate);
+        self
+    }
+
+    /// Why `value` can't be submitted, or `None` if it can
+    pub fn validation_error(&self, value: &str) -> Option<String> {
+        self.validate.and_then(|validate| validate(value))
+    }
+
+    /// Answer the request; only the first answer is delivered
+    pub fn respond(&self, result: DialogResult<String>) {
+        if let Some(sender) = self.responder.lock().ok().and_then(|mut sender| sender.take()) {
+            let _ = sender.send(result);
+        }
+    }
+
+    /// Answer with submitted text; blank input is never passed on as a value
+    pub fn submit(&self, value: &str) {
+        let value = value.trim();
+        if value.is_empty() {
+            self.respond(DialogResult::Declined);
+        } else {
+            self.respond(DialogResult::Confirmed(value.to_string()));
+        }
+    }
+}
+
+/// Props for input dialog
+#[derive(Props, Clone, PartialEq)]
+pub struct InputDialogProps {
+    /// The open request, if any; cleared when the dialog closes
+    pub request: Signal<Option<InputDialogRequest>>,
+}
+
+/// Text input dialog used for naming new files and renaming
+pub fn InputDialog(props: InputDialogProps) -> Element {
+    let request = props.request;
+    let mut value = use_signal(String::new);
+
+    // Start each request from its initial value
+    use_effect(move || {
+        if let Some(current) = request.read().as_ref() {
+            value.set(current.initial_value.clone());
+        }
+    });
+
+    let Some(current) = request.read().clone() else {
+        return rsx! {};
+    };
+
+    let dialog_id = "input-dialog";
+    let error = current.validation_error(&value.read());
+    let can_submit = !value.read().trim().is_empty() && error.is_none();
+
+    rsx! {
+        Modal {
+            id: "{dialog_id}",
+            described_by: format!("{}-message", dialog_id),
+            on_close: move |_| close_input_dialog(request, |pending| pending.respond(DialogResult::Cancelled)),
+
+            div {
+                class: "dialog-header",
+                h3 { id: modal_title_id(dialog_id), {current.title.clone()} }
+            }
+
+            div {
+                class: "dialog-content",
+                label {
+                    id: "{dialog_id}-message",
+                    r#for: "{dialog_id}-value",
+                    class: "dialog-message",
+                    {current.message.clone()}
+                }
+                input {
+                    id: "{dialog_id}-value",
+                    r#type: "text",
+                    class: "dialog-input",
+                    value: "{value}",
+                    "aria-invalid": "{error.is_some()}",
+                    "aria-describedby": if error.is_some() { "{dialog_id}-error" } else { "" },
+                    oninput: move |evt| value.set(evt.value()),
+                    onkeydown: move |evt| {
+                        if evt.data.key() == Key::Enter {
+                            evt.prevent_default();
+                            let submitted = value.read().clone();
+                            submit_input_dialog(request, &submitted);
+                        }
+                    },
+                }
+                if let Some(error) = error {
+                    div {
+                        id: "{dialog_id}-error",
+                        class: "dialog-error",
+                        role: "alert",
+                        {error}
                     }
-                    button {
-                        class: match props.danger_level {
-                            ErrorSeverity::Critical | ErrorSeverity::High => "button danger",
-                            _ => "button primary"
+                }
+            }
+
+            div {
+                class: "dialog-actions",
+                button {
+                    class: "button secondary",
+                    onclick: move |_| close_input_dialog(request, |pending| pending.respond(DialogResult::Declined)),
+                    {t!("dialog.cancel")}
+                }
+                button {
+                    class: "button primary",
+                    disabled: !can_submit,
+                    onclick: move |_| {
+                        let submitted = value.read().clone();
+                        submit_input_dialog(request, &submitted);
+                    },
+                    {t!("dialog.ok")}
+                }
+            }
+        }
+    }
+}
+
+/// Answer the open request with `value` unless its validation rejects it
+fn submit_input_dialog(request: Signal<Option<InputDialogRequest>>, value: &str) {
+    let rejected = request
+        .read()
+        .as_ref()
+        .is_some_and(|pending| pending.validation_error(value).is_some());
+    if !rejected {
+        close_input_dialog(request, |pending| pending.submit(value));
+    }
+}
+
+/// Clear the open request and answer it
+fn close_input_dialog(
+    mut request: Signal<Option<InputDialogRequest>>,
+    answer: impl FnOnce(&InputDialogRequest),
+) {
+    let pending = request.write().take();
+    if let Some(pending) = pending {
+        answer(&pending);
+    }
+}
+
+/// A pending choice of application to open a file with, answered when the picker closes
+#[derive(Clone)]
+pub struct OpenWithRequest {
+    pub file_name: String,
+    /// Applications on offer, the last one used for this kind of file first
+    pub apps: Vec<OpenWithApp>,
+    /// The application last picked for this kind of file, if it's on offer
+    pub last_used: Option<OpenWithApp>,
+    responder: Arc<Mutex<Option<oneshot::Sender<DialogResult<OpenWithApp>>>>>,
+}
+
+impl OpenWithRequest {
+    /// Create a request and the receiver its answer arrives on
+    pub fn new(
+        file_name: impl Into<String>,
+        apps: Vec<OpenWithApp>,
+        last_used: Option<OpenWithApp>,
+    ) -> (Self, oneshot::Receiver<DialogResult<OpenWithApp>>) {
+        let (sender, receiver) = oneshot::channel();
+        let request = Self {
+            file_name: file_name.into(),
+            apps,
+            last_used,
+            responder: Arc::new(Mutex::new(Some(sender))),
+        };
+        (request, receiver)
+    }
+
+    /// Answer the request; only the first answer is delivered
+    pub fn respond(&self, result: DialogResult<OpenWithApp>) {
+        if let Some(sender) = self.responder.lock().ok().and_then(|mut sender| sender.take()) {
+            let _ = sender.send(result);
+        }
+    }
+}
+
+/// Props for the Open With picker
+#[derive(Props, Clone, PartialEq)]
+pub struct OpenWithDialogProps {
+    /// The open request, if any; cleared when the dialog closes
+    pub request: Signal<Option<OpenWithRequest>>,
+}
+
+/// Pick the application to open a file with
+pub fn OpenWithDialog(props: OpenWithDialogProps) -> Element {
+    let mut request = props.request;
+    let mut selected = use_signal(|| 0usize);
+
+    // Start each request on the first application
+    use_effect(move || {
+        if request.read().is_some() {
+            selected.set(0);
+        }
+    });
+
+    let Some(current) = request.read().clone() else {
+        return rsx! {};
+    };
+
+    let dialog_id = "open-with-dialog";
+    let app_count = current.apps.len();
+    let mut close = move |result: Option<usize>| {
+        let pending = request.write().take();
+        if let Some(pending) = pending {
+            match result.and_then(|index| pending.apps.get(index).cloned()) {
+                Some(app) => pending.respond(DialogResult::Confirmed(app)),
+                None => pending.respond(DialogResult::Cancelled),
+            }
+        }
+    };
+
+    rsx! {
+        Modal {
+            id: "{dialog_id}",
+            described_by: format!("{}-message", dialog_id),
+            on_close: move |_| close(None),
+
+            div {
+                class: "dialog-header",
+                h3 { id: modal_title_id(dialog_id), {t!("dialog.open_with.title", name = current.file_name)} }
+            }
+
+            div {
+                class: "dialog-content",
+                p {
+                    id: "{dialog_id}-message",
+                    class: "dialog-message",
+                    if app_count == 0 {
+                        {t!("dialog.open_with.empty")}
+                    } else {
+                        {t!("dialog.open_with.message")}
+                    }
+                }
+                if app_count > 0 {
+                    ul {
+                        class: "open-with-list",
+                        role: "listbox",
+                        tabindex: "0",
+                        "aria-labelledby": "{dialog_id}-message",
+                        "aria-activedescendant": "{dialog_id}-app-{selected}",
+                        onkeydown: move |evt| match evt.data.key() {
+                            Key::ArrowDown => {
+                                evt.prevent_default();
+                                let next = (*selected.peek() + 1).min(app_count - 1);
+                                selected.set(next);
+                            }
+                            Key::ArrowUp => {
+                                evt.prevent_default();
+                                let previous = selected.peek().saturating_sub(1);
+                                selected.set(previous);
+                            }
+                            Key::Enter => {
+                                evt.prevent_default();
+                                close(Some(*selected.peek()));
+                            }
+                            _ => {}
                         },
-                        onclick: move |_| props.on_result.call(ConfirmationResult::Confirmed),
-                        {button_text}
+                        for (index, app) in current.apps.iter().enumerate() {
+                            li {
+                                key: "{app.target.display()}",
+                                id: "{dialog_id}-app-{index}",
+                                class: if index == *selected.read() { "open-with-option selected" } else { "open-with-option" },
+                                role: "option",
+                                "aria-selected": "{index == *selected.read()}",
+                                title: "{app.target.display()}",
+                                onclick: move |_| selected.set(index),
+                                ondoubleclick: move |_| close(Some(index)),
+                                span { "{app.name}" }
+                                if current.last_used.as_ref() == Some(app) {
+                                    span { class: "open-with-last-used", {t!("dialog.open_with.last_used")} }
+                                }
+                            }
+                        }
                     }
                 }
             }
+
+            div {
+                class: "dialog-actions",
+                button {
+                    class: "button secondary",
+                    onclick: move |_| close(None),
+                    {t!("dialog.cancel")}
+                }
+                button {
+                    class: "button primary",
+                    disabled: app_count == 0,
+                    onclick: move |_| close(Some(*selected.peek())),
+                    {t!("dialog.open_with.open")}
+                }
+            }
         }
     }
 }
@@ -234,14 +604,32 @@ pub fn ProgressDialog(props: ProgressDialogProps) -> Element {
     }
 
     let progress = &props.state.progress;
-    let percentage = if progress.total > 0 {
+    // Byte counts move within a large file, so they win over the item count
+    let percentage = if progress.total_bytes > 0 {
+        progress.bytes_percentage().min(100.0)
+    } else if progress.total > 0 {
         (progress.current as f64 / progress.total as f64 * 100.0).min(100.0)
     } else {
         0.0
     };
+    let status_text = if progress.total_bytes > 0 {
+        t!(
+            "dialog.progress.bytes",
+            percentage = format!("{:.1}", percentage),
+            processed = format_size(progress.bytes_processed),
+            total = format_size(progress.total_bytes)
+        )
+    } else {
+        t!(
+            "dialog.progress.status",
+            percentage = format!("{:.1}", percentage),
+            current = progress.current,
+            total = progress.total
+        )
+    };
 
     let speed_text = if progress.speed_bps > 0 {
-        format!(" at {}/s", format_file_size(progress.speed_bps))
+        format!(" at {}", format_rate(progress.speed_bps))
     } else {
         String::new()
     };
@@ -252,86 +640,153 @@ pub fn ProgressDialog(props: ProgressDialogProps) -> Element {
         String::new()
     };
 
+    let dialog_id = "progress-dialog";
+    let cancellable = props.state.cancellable;
+    let on_cancel = props.on_cancel;
+
     rsx! {
-        div {
-            class: "dialog-overlay progress-overlay",
+        Modal {
+            id: "{dialog_id}",
+            class: "progress-dialog",
+            overlay_class: "dialog-overlay progress-overlay",
+            // Only a cancellable operation can be dismissed, and never by a stray click
+            close_on_escape: cancellable,
+            close_on_backdrop: false,
+            on_close: move |_| {
+                if let Some(handler) = &on_cancel {
+                    handler.call(());
+                }
+            },
+                
+            div {
+                class: "dialog-header",
+                span { class: "dialog-icon", "aria-hidden": "true", "⚙️" }
+                h3 { id: modal_title_id(dialog_id), {props.state.title.clone()} }
+            }
             
             div {
-                class: "progress-dialog",
-                onclick: |evt| evt.stop_propagation(),
-                
-                div {
-                    class: "dialog-header",
-                    span { class: "dialog-icon", "⚙️" }
-                    h3 { {props.state.title.clone()} }
+                class: "dialog-content",
+                p { 
+                    class: "operation-description",
+                    {props.state.operation.clone()}
                 }
                 
                 div {
-                    class: "dialog-content",
-                    p { 
-                        class: "operation-description",
-                        {props.state.operation.clone()}
-                    }
-                    
+                    class: "progress-container",
                     div {
-                        class: "progress-container",
+                        class: "progress-bar-background",
                         div {
-                            class: "progress-bar-background",
-                            div {
-                                class: "progress-bar-fill",
-                                style: "width: {percentage}%",
-                            }
-                        }
-                        
-                        div {
-                            class: "progress-text",
-                            {
-                                format!("{:.1}% ({} of {}){}{}", 
-                                    percentage, 
-                                    progress.current, 
-                                    progress.total,
-                                    speed_text,
-                                    eta_text
-                                )
-                            }
+                            class: "progress-bar-fill",
+                            style: "width: {percentage}%",
                         }
                     }
                     
-                    if props.state.error_count > 0 {
-                        div {
-                            class: "progress-errors",
-                            {format!("⚠️ {} errors encountered", props.state.error_count)}
-                        }
+                    div {
+                        class: "progress-text",
+                        {format!("{}{}{}", status_text, speed_text, eta_text)}
                     }
-                    
-                    if !props.state.details.is_empty() {
-                        div {
-                            class: "progress-details",
-                            h4 { "Details:" }
-                            ul {
-                                for detail in props.state.details.iter().rev().take(5) {
-                                    li { {detail.clone()} }
-                                }
-                            }
-                        }
+                }
+                
+                if props.state.error_count > 0 {
+                    div {
+                        class: "progress-errors",
+                        {format!("⚠️ {}", t!("dialog.progress.errors", count = props.state.error_count))}
                     }
                 }
                 
-                div {
-                    class: "dialog-actions",
-                    if props.state.cancellable {
-                        button {
-                            class: "button secondary",
-                            onclick: move |_| {
-                                if let Some(handler) = &props.on_cancel {
-                                    handler.call(());
-                                }
-                            },
-                            "Cancel"
+                if !props.state.details.is_empty() {
+                    div {
+                        class: "progress-details",
+                        h4 { {t!("dialog.progress.details")} }
+                        ul {
+                            for detail in props.state.details.iter().rev().take(5) {
+                                li { {detail.clone()} }
+                            }
                         }
                     }
                 }
             }
+            
+            div {
+                class: "dialog-actions",
+                if props.state.cancellable {
+                    button {
+                        class: "button secondary",
+                        onclick: move |_| {
+                            if let Some(handler) = &props.on_cancel {
+                                handler.call(());
+                            }
+                        },
+                        {t!("dialog.cancel")}
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// How often the file operation progress dialog re-reads its item and byte counts
+const FILE_OPERATION_PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Progress dialog for the file operation in `AppState::file_operation_progress`
+///
+/// Copies and moves show their byte counts, speed and time remaining; other
+/// operations count items. Cancel cancels the operation's token; the
+/// operation stops before its next item, or its next chunk of a large file,
+/// and clears the progress itself.
+#[component]
+pub fn FileOperationProgressDialog() -> Element {
+    let app_state = use_app_state();
+    let running = app_state.file_operation_progress;
+    let mut finished = use_signal(|| 0u64);
+    let mut transferred = use_signal(|| None::<ProgressInfo>);
+
+    use_future(move || async move {
+        loop {
+            let (count, latest) = running
+                .peek()
+                .as_ref()
+                .map(|progress| {
+                    let latest = progress.transfer.as_ref().and_then(|transfer| transfer.latest());
+                    (progress.cancellation.progress_count(), latest)
+                })
+                .unwrap_or((0, None));
+            if count != *finished.peek() {
+                finished.set(count);
+            }
+            if latest != *transferred.peek() {
+                transferred.set(latest);
+            }
+            tokio::time::sleep(FILE_OPERATION_PROGRESS_INTERVAL).await;
+        }
+    });
+
+    let Some(progress) = running.read().clone() else {
+        return rsx! {};
+    };
+    let info = match transferred.read().clone().filter(|info| info.total_bytes > 0) {
+        Some(info) => info,
+        None => {
+            let mut info = ProgressInfo::new(progress.total as u64, 0, progress.operation.clone());
+            info.current = (*finished.read()).min(progress.total as u64);
+            info
+        }
+    };
+    let cancellation = progress.cancellation.clone();
+
+    rsx! {
+        ProgressDialog {
+            state: ProgressDialogState {
+                visible: true,
+                title: progress.title.clone(),
+                operation: progress.operation.clone(),
+                progress: info,
+                // Once cancelled there is nothing more to ask for
+                cancellable: !progress.cancellation.is_cancelled(),
+                details: Vec::new(),
+                error_count: 0,
+            },
+            on_cancel: Some(EventHandler::new(move |_| cancellation.cancel())),
         }
     }
 }
@@ -434,153 +889,115 @@ pub fn OperationSummaryDialog(props: OperationSummaryDialogProps) -> Element {
         "⚠️"
     };
 
+    let dialog_id = "operation-summary-dialog";
+
     rsx! {
-        div {
-            class: "dialog-overlay",
-            onclick: move |_| props.on_close.call(()),
+        Modal {
+            id: "{dialog_id}",
+            class: "operation-summary-dialog",
+            on_close: move |_| props.on_close.call(()),
+                
+            div {
+                class: "dialog-header",
+                span { class: "dialog-icon", "aria-hidden": "true", {status_icon} }
+                h3 { id: modal_title_id(dialog_id), {t!("dialog.summary.title", operation = summary.operation_type)} }
+            }
             
             div {
-                class: "operation-summary-dialog",
-                onclick: |evt| evt.stop_propagation(),
-                
-                div {
-                    class: "dialog-header",
-                    span { class: "dialog-icon", {status_icon} }
-                    h3 { {format!("{} Complete", summary.operation_type)} }
-                }
-                
+                class: "dialog-content",
                 div {
-                    class: "dialog-content",
-                    div {
-                        class: "summary-stats",
-                        div { class: "stat", 
-                            span { class: "stat-label", "Total Items:" }
-                            span { class: "stat-value", {format!("{}", summary.total_items)} }
-                        }
-                        div { class: "stat", 
-                            span { class: "stat-label", "Successful:" }
-                            span { class: "stat-value success", {format!("{}", summary.successful_items)} }
-                        }
-                        if summary.failed_items > 0 {
-                            div { class: "stat", 
-                                span { class: "stat-label", "Failed:" }
-                                span { class: "stat-value error", {format!("{}", summary.failed_items)} }
-                            }
-                        }
-                        div { class: "stat", 
-                            span { class: "stat-label", "Success Rate:" }
-                            span { class: "stat-value", {format!("{:.1}%", success_rate)} }
-                        }
+                    class: "summary-stats",
+                    div { class: "stat", 
+                        span { class: "stat-label", {t!("dialog.summary.total_items")} }
+                        span { class: "stat-value", {format!("{}", summary.total_items)} }
+                    }
+                    div { class: "stat", 
+                        span { class: "stat-label", {t!("dialog.summary.successful")} }
+                        span { class: "stat-value success", {format!("{}", summary.successful_items)} }
+                    }
+                    if summary.failed_items > 0 {
                         div { class: "stat", 
-                            span { class: "stat-label", "Duration:" }
-                            span { class: "stat-value", {format_duration(summary.duration)} }
+                            span { class: "stat-label", {t!("dialog.summary.failed")} }
+                            span { class: "stat-value error", {format!("{}", summary.failed_items)} }
                         }
                     }
-                    
-                    if !summary.errors.is_empty() {
-                        div {
-                            class: "summary-section",
-                            h4 { "Errors:" }
-                            ul {
-                                class: "error-list",
-                                for (item, error) in summary.errors.iter().take(10) {
-                                    li { 
-                                        span { class: "error-item", {item.clone()} }
-                                        span { class: "error-message", {error.clone()} }
-                                    }
+                    div { class: "stat", 
+                        span { class: "stat-label", {t!("dialog.summary.success_rate")} }
+                        span { class: "stat-value", {format!("{:.1}%", success_rate)} }
+                    }
+                    div { class: "stat", 
+                        span { class: "stat-label", {t!("dialog.summary.duration")} }
+                        span { class: "stat-value", {format_duration(summary.duration)} }
+                    }
+                }
+                
+                if !summary.errors.is_empty() {
+                    div {
+                        class: "summary-section",
+                        h4 { {t!("dialog.summary.errors")} }
+                        ul {
+                            class: "error-list",
+                            for (item, error) in summary.errors.iter().take(10) {
+                                li { 
+                                    span { class: "error-item", {item.clone()} }
+                                    span { class: "error-message", {error.clone()} }
                                 }
-                                if summary.errors.len() > 10 {
-                                    li { 
-                                        class: "more-items",
-                                        "... and more errors"
-                                    }
+                            }
+                            if summary.errors.len() > 10 {
+                                li { 
+                                    class: "more-items",
+                                    {t!("dialog.summary.more_errors")}
                                 }
                             }
                         }
                     }
-                    
-                    if !summary.warnings.is_empty() {
-                        div {
-                            class: "summary-section",
-                            h4 { "Warnings:" }
-                            ul {
-                                class: "warning-list",
-                                for (item, warning) in summary.warnings.iter().take(5) {
-                                    li { 
-                                        span { class: "warning-item", {item.clone()} }
-                                        span { class: "warning-message", {warning.clone()} }
-                                    }
+                }
+                
+                if !summary.warnings.is_empty() {
+                    div {
+                        class: "summary-section",
+                        h4 { {t!("dialog.summary.warnings")} }
+                        ul {
+                            class: "warning-list",
+                            for (item, warning) in summary.warnings.iter().take(5) {
+                                li { 
+                                    span { class: "warning-item", {item.clone()} }
+                                    span { class: "warning-message", {warning.clone()} }
                                 }
                             }
                         }
                     }
-                    
-                    if !summary.recovery_suggestions.is_empty() {
-                        div {
-                            class: "summary-section",
-                            h4 { "Suggestions:" }
-                            ul {
-                                class: "suggestion-list",
-                                for suggestion in &summary.recovery_suggestions {
-                                    li { 
-                                        div { class: "suggestion-title", {suggestion.description.clone()} }
-                                        div { class: "suggestion-text", {suggestion.suggestion.clone()} }
-                                    }
+                }
+                
+                if !summary.recovery_suggestions.is_empty() {
+                    div {
+                        class: "summary-section",
+                        h4 { {t!("dialog.summary.suggestions")} }
+                        ul {
+                            class: "suggestion-list",
+                            for suggestion in &summary.recovery_suggestions {
+                                li { 
+                                    div { class: "suggestion-title", {suggestion.description.clone()} }
+                                    div { class: "suggestion-text", {suggestion.suggestion.clone()} }
                                 }
                             }
                         }
                     }
                 }
-                
-                div {
-                    class: "dialog-actions",
-                    button {
-                        class: "button primary",
-                        onclick: move |_| props.on_close.call(()),
-                        "Close"
-                    }
+            }
+            
+            div {
+                class: "dialog-actions",
+                button {
+                    class: "button primary",
+                    onclick: move |_| props.on_close.call(()),
+                    {t!("dialog.close")}
                 }
             }
         }
     }
 }
 
-// Helper functions for formatting
-
-/// Format file size in human-readable format
-fn format_file_size(bytes: u64) -> String {
-    const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
-    let mut size = bytes as f64;
-    let mut unit_index = 0;
-    
-    while size >= 1024.0 && unit_index < UNITS.len() - 1 {
-        size /= 1024.0;
-        unit_index += 1;
-    }
-    
-    if unit_index == 0 {
-        format!("{} {}", size as u64, UNITS[unit_index])
-    } else {
-        format!("{:.1} {}", size, UNITS[unit_index])
-    }
-}
-
-/// Format duration in human-readable format
-fn format_duration(duration: Duration) -> String {
-    let total_seconds = duration.as_secs();
-    let hours = total_seconds / 3600;
-    let minutes = (total_seconds % 3600) / 60;
-    let seconds = total_seconds % 60;
-    
-    if hours > 0 {
-        format!("{}h {}m {}s", hours, minutes, seconds)
-    } else if minutes > 0 {
-        format!("{}m {}s", minutes, seconds)
-    } else {
-        format!("{}s", seconds)
-    }
-}
-
 /// Toast manager for handling multiple toast notifications
 pub struct ToastManager {
     toasts: Vec<ToastNotification>,
@@ -644,4 +1061,51 @@ impl Default for ToastManager {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_confirmed_results_carry_a_value() {
+        assert_eq!(DialogResult::Confirmed("a.txt").confirmed(), Some("a.txt"));
+        assert_eq!(DialogResult::<&str>::Declined.confirmed(), None);
+        assert_eq!(DialogResult::<&str>::Cancelled.confirmed(), None);
+        assert!(ConfirmationResult::Confirmed(()).is_confirmed());
+        assert!(!ConfirmationResult::Declined.is_confirmed());
+
+        // A dismissed picker is a cancellation, not a "no"
+        assert_eq!(DialogResult::from(None::<u8>), DialogResult::Cancelled);
+        assert_eq!(DialogResult::from(Some(1)).map(|n| n + 1), DialogResult::Confirmed(2));
+    }
+
+    #[tokio::test]
+    async fn test_input_request_delivers_each_result_variant() {
+        let (request, response) = InputDialogRequest::new("Rename", "New name:", "photo.jpg");
+        request.submit("  holiday.jpg ");
+        request.respond(DialogResult::Cancelled);
+        assert_eq!(response.await.unwrap(), DialogResult::Confirmed("holiday.jpg".to_string()));
+
+        let (request, response) = InputDialogRequest::new("Rename", "New name:", "photo.jpg");
+        request.submit("   ");
+        assert_eq!(response.await.unwrap(), DialogResult::Declined);
+
+        // Dropping an unanswered request reads as a dismissal
+        let (request, response) = InputDialogRequest::new("Rename", "New name:", "photo.jpg");
+        drop(request);
+        assert_eq!(response.await.unwrap_or(DialogResult::Cancelled), DialogResult::Cancelled);
+    }
+
+    #[test]
+    fn test_input_request_validation() {
+        let (request, _response) = InputDialogRequest::new("Rename", "New name:", "photo.jpg");
+        assert_eq!(request.validation_error("a/b.jpg"), None);
+
+        let request = request.with_validation(|name| {
+            crate::utils::validate_file_name(name).err().map(|_| "invalid".to_string())
+        });
+        assert_eq!(request.validation_error("holiday.jpg"), None);
+        assert_eq!(request.validation_error("a/b.jpg"), Some("invalid".to_string()));
+        assert_eq!(request.validation_error("  "), Some("invalid".to_string()));
+    }
+}
@@ -0,0 +1,122 @@
+// Directory tabs
+// Tab strip above the file list, and the tab actions shared with the keyboard shortcuts
+
+use dioxus::prelude::*;
+
+use crate::state::directory_tabs::title_for;
+use crate::state::{use_app_state, AppState};
+
+/// Id of the scrolling file list whose offset each tab remembers
+pub const DIRECTORY_LISTING_ID: &str = "directory-listing";
+
+/// Current scroll offset of the file list
+async fn listing_scroll_top() -> f64 {
+    let script = format!("return document.getElementById('{}')?.scrollTop ?? 0;", DIRECTORY_LISTING_ID);
+    document::eval(&script).join::<f64>().await.unwrap_or(0.0)
+}
+
+/// Scroll the file list to `scroll_top` once the shown tab has rendered
+pub fn restore_listing_scroll(scroll_top: f64) {
+    let script = format!(
+        "requestAnimationFrame(() => {{ const list = document.getElementById('{}'); if (list) list.scrollTop = {}; }});",
+        DIRECTORY_LISTING_ID, scroll_top
+    );
+    let _ = document::eval(&script);
+}
+
+/// Open the shown folder in a new tab
+pub async fn open_directory_tab(mut app_state: AppState) {
+    let path = app_state.get_current_path();
+    match app_state.open_directory_tab(path, listing_scroll_top().await).await {
+        Ok(scroll_top) => restore_listing_scroll(scroll_top),
+        Err(e) => tracing::warn!("Failed to open directory tab: {}", e),
+    }
+}
+
+/// Close tab `index`, or the active tab when `None`
+pub async fn close_directory_tab(mut app_state: AppState, index: Option<usize>) {
+    let index = index.unwrap_or_else(|| app_state.directory_tabs.peek().active);
+    match app_state.close_directory_tab(index, listing_scroll_top().await).await {
+        Ok(Some(scroll_top)) => restore_listing_scroll(scroll_top),
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Failed to close directory tab: {}", e),
+    }
+}
+
+/// Show tab `index`
+pub async fn switch_directory_tab(mut app_state: AppState, index: usize) {
+    match app_state.switch_directory_tab(index, listing_scroll_top().await).await {
+        Ok(Some(scroll_top)) => restore_listing_scroll(scroll_top),
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Failed to switch directory tab: {}", e),
+    }
+}
+
+/// Open folders as tabs; the active one shows the live current folder
+#[component]
+pub fn DirectoryTabStrip() -> Element {
+    let app_state = use_app_state();
+    let current_path = app_state.navigation.read().current_path.clone();
+    let (tabs, active) = {
+        let state = app_state.directory_tabs.read();
+        let tabs: Vec<_> = state.tabs.iter().map(|tab| (tab.id, tab.navigation.current_path.clone())).collect();
+        (tabs, state.active)
+    };
+    let closable = tabs.len() > 1;
+
+    rsx! {
+        div {
+            class: "directory-tabs",
+            role: "tablist",
+            "aria-label": t!("directory_tabs.label"),
+            for (index, (id, tab_path)) in tabs.into_iter().enumerate() {
+                {
+                    let is_active = index == active;
+                    let path = if is_active { current_path.clone() } else { tab_path };
+                    let title = title_for(&path);
+                    let switch_state = app_state.clone();
+                    let close_state = app_state.clone();
+                    rsx! {
+                        div {
+                            key: "{id}",
+                            class: if is_active { "directory-tab active" } else { "directory-tab" },
+                            button {
+                                class: "directory-tab-title",
+                                role: "tab",
+                                "aria-selected": is_active.to_string(),
+                                title: "{path.display()}",
+                                onclick: move |_| {
+                                    let app_state = switch_state.clone();
+                                    spawn(switch_directory_tab(app_state, index));
+                                },
+                                "{title}"
+                            }
+                            if closable {
+                                button {
+                                    class: "directory-tab-close",
+                                    title: t!("directory_tabs.close", name = title.clone()),
+                                    "aria-label": t!("directory_tabs.close", name = title.clone()),
+                                    onclick: move |evt| {
+                                        evt.stop_propagation();
+                                        let app_state = close_state.clone();
+                                        spawn(close_directory_tab(app_state, Some(index)));
+                                    },
+                                    "×"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            button {
+                class: "directory-tab-new",
+                title: t!("directory_tabs.new"),
+                "aria-label": t!("directory_tabs.new"),
+                onclick: move |_| {
+                    spawn(open_directory_tab(app_state.clone()));
+                },
+                "+"
+            }
+        }
+    }
+}
@@ -1,11 +1,16 @@
 use dioxus::prelude::*;
-use std::path::PathBuf;
-use crate::services::FileEntry;
-use crate::state::{use_app_state};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+use crate::services::{FileEntry, FileOperationResult};
+use crate::services::operations::{Command, CopyCommand, CopyDirectoryCommand, MoveCommand, MoveDirectoryCommand};
+use crate::state::{AppState, SelectionState};
 use crate::performance::rendering_optimizations::{DragOptimizer, RenderingProfiler};
 use std::sync::{Arc, Mutex};
 use std::sync::OnceLock;
 
+/// Pointer travel, in CSS pixels, before a drag may leave the window as a file drag
+pub const DRAG_OUT_THRESHOLD: f64 = 8.0;
+
 /// Global drag optimizer instance
 static GLOBAL_DRAG_OPTIMIZER: OnceLock<Arc<Mutex<DragOptimizer>>> = OnceLock::new();
 
@@ -16,6 +21,14 @@ fn get_drag_optimizer() -> &'static Arc<Mutex<DragOptimizer>> {
     })
 }
 
+/// Whether the drag optimizer lets `event` through; accepted when it is busy
+fn accept_drag_event(event: String) -> bool {
+    get_drag_optimizer()
+        .try_lock()
+        .map(|mut optimizer| optimizer.process_drag_event(event))
+        .unwrap_or(true)
+}
+
 /// Drag and drop operation types
 #[derive(Debug, Clone, PartialEq)]
 pub enum DragOperation {
@@ -35,6 +48,18 @@ impl DragOperation {
         }
     }
 
+    /// Operation for files dropped onto a folder
+    ///
+    /// Folders only take moves and copies: Alt (Option on macOS) or Ctrl
+    /// copies, anything else moves.
+    pub fn for_folder_drop(ctrl: bool, alt: bool) -> Self {
+        if ctrl || alt {
+            DragOperation::Copy
+        } else {
+            DragOperation::Move
+        }
+    }
+
     pub fn cursor_style(&self) -> &'static str {
         match self {
             DragOperation::Move => "move",
@@ -102,16 +127,9 @@ impl DragState {
     }
 
     pub fn update_position(&mut self, x: f64, y: f64) {
-        if self.is_dragging {
-            // Use DragOptimizer to batch position updates
-            if let Ok(mut optimizer) = get_drag_optimizer().try_lock() {
-                if optimizer.process_drag_event(format!("position:{:.1},{:.1}", x, y)) {
-                    self.current_position = (x, y);
-                }
-            } else {
-                // Fallback if optimizer is locked
-                self.current_position = (x, y);
-            }
+        // Use DragOptimizer to batch position updates
+        if self.is_dragging && accept_drag_event(format!("position:{:.1},{:.1}", x, y)) {
+            self.current_position = (x, y);
         }
     }
 
@@ -139,6 +157,12 @@ impl DragState {
         let dy = self.current_position.1 - self.start_position.1;
         (dx * dx + dy * dy).sqrt()
     }
+
+    /// Whether a drag now at `(x, y)` has gone far enough to become a file drag
+    pub fn can_drag_out(&self, x: f64, y: f64) -> bool {
+        let (dx, dy) = (x - self.start_position.0, y - self.start_position.1);
+        self.is_dragging && !self.drag_files.is_empty() && (dx * dx + dy * dy).sqrt() >= DRAG_OUT_THRESHOLD
+    }
 }
 
 /// Drop zone state
@@ -151,6 +175,15 @@ pub enum DropZoneState {
 }
 
 impl DropZoneState {
+    /// Feedback for dragging `drag_files` over `target`
+    pub fn for_target(drag_files: &[FileEntry], target: &Path) -> Self {
+        if is_valid_drop_target(drag_files, &target.to_path_buf()) {
+            DropZoneState::DragOverValid
+        } else {
+            DropZoneState::DragOverInvalid
+        }
+    }
+
     pub fn style_class(&self) -> &'static str {
         match self {
             DropZoneState::Idle => "drop-zone-idle",
@@ -240,6 +273,9 @@ pub fn DragPreview(props: DragPreviewProps) -> Element {
 pub struct DropZoneProps {
     pub drop_state: Signal<DropZoneState>,
     pub target_path: Option<PathBuf>,
+    /// The app's drag; when set, drops are checked against `target_path` and passed to `on_drop`
+    #[props(default)]
+    pub drag_state: Option<Signal<DragState>>,
     pub on_drop: EventHandler<(Vec<FileEntry>, DragOperation, PathBuf)>,
     pub children: Element,
 }
@@ -248,7 +284,9 @@ pub struct DropZoneProps {
 #[component]
 pub fn DropZone(mut props: DropZoneProps) -> Element {
     let drop_state = props.drop_state.read();
-    let _app_state = use_app_state();
+    let drag_state = props.drag_state;
+    let hover_target = props.target_path.clone();
+    let drop_target = props.target_path.clone();
     
     let base_style = format!(
         "position: relative; {}",
@@ -262,7 +300,19 @@ pub fn DropZone(mut props: DropZoneProps) -> Element {
             
             ondragover: move |e| {
                 e.prevent_default();
-                props.drop_state.set(DropZoneState::DragOverValid);
+                let state = match (drag_state, &hover_target) {
+                    (Some(drag), Some(target)) if drag.peek().is_dragging => {
+                        let operation = DragOperation::for_folder_drop(e.modifiers().ctrl(), e.modifiers().alt());
+                        update_drag_operation(drag, operation);
+                        let coordinates = e.client_coordinates();
+                        track_drag_position(drag, coordinates.x, coordinates.y);
+                        DropZoneState::for_target(&drag.peek().drag_files, target)
+                    }
+                    _ => DropZoneState::DragOverValid,
+                };
+                if *props.drop_state.peek() != state {
+                    props.drop_state.set(state);
+                }
             },
             
             ondragenter: move |e| {
@@ -278,11 +328,18 @@ pub fn DropZone(mut props: DropZoneProps) -> Element {
             ondrop: move |e| {
                 e.prevent_default();
                 props.drop_state.set(DropZoneState::Idle);
-                
-                // TODO: Handle actual drop event
-                // For now, this is a placeholder for when we implement
-                // the full drag and drop data transfer
-                tracing::info!("Drop event received on target: {:?}", props.target_path);
+                let (Some(mut drag), Some(target)) = (drag_state, drop_target.clone()) else {
+                    tracing::info!("Drop event received on target: {:?}", drop_target);
+                    return;
+                };
+                let (files, operation) = {
+                    let drag = drag.peek();
+                    (drag.drag_files.clone(), drag.operation.clone())
+                };
+                drag.write().end_drag();
+                if !files.is_empty() && is_valid_drop_target(&files, &target) {
+                    props.on_drop.call((files, operation, target));
+                }
             },
             
             {props.children}
@@ -290,6 +347,226 @@ pub fn DropZone(mut props: DropZoneProps) -> Element {
     }
 }
 
+/// Show `operation` in the drag preview, re-rendering only when it changes
+pub fn update_drag_operation(mut drag_state: Signal<DragState>, operation: DragOperation) {
+    if drag_state.peek().operation != operation {
+        drag_state.write().update_operation(operation);
+    }
+}
+
+/// Move the drag preview to the cursor
+///
+/// Position events are throttled by the drag optimizer before the signal
+/// is written, so the app is not re-rendered for every `drag` event.
+pub fn track_drag_position(mut drag_state: Signal<DragState>, x: f64, y: f64) {
+    // Browsers report (0, 0) for the last event of a drag
+    if !drag_state.peek().is_dragging || (x == 0.0 && y == 0.0) {
+        return;
+    }
+    if accept_drag_event(format!("position:{:.1},{:.1}", x, y)) {
+        drag_state.write().current_position = (x, y);
+    }
+}
+
+/// Files a drag that starts on `entry` carries: the whole selection when
+/// `entry` is part of it, otherwise just `entry`
+pub fn drag_files_for(entry: &FileEntry, selected: Vec<FileEntry>) -> Vec<FileEntry> {
+    if selected.iter().any(|file| file.path == entry.path) {
+        selected
+    } else {
+        vec![entry.clone()]
+    }
+}
+
+/// Entries of the listing `entries` that are selected
+pub fn selected_entries(selection: &SelectionState, entries: &[FileEntry]) -> Vec<FileEntry> {
+    entries
+        .iter()
+        .filter(|entry| selection.selected_files.contains(&entry.path))
+        .cloned()
+        .collect()
+}
+
+/// Paths a file drag to other applications carries
+///
+/// Items that no longer exist are left out, as is anything inside a
+/// dragged folder, which the folder already carries.
+pub fn drag_out_payload(files: &[FileEntry]) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for file in files {
+        if file.path.exists() && !paths.contains(&file.path) {
+            paths.push(file.path.clone());
+        }
+    }
+    let folders: Vec<PathBuf> = paths.iter().filter(|path| path.is_dir()).cloned().collect();
+    paths.retain(|path| !folders.iter().any(|folder| path != folder && path.starts_with(folder)));
+    paths
+}
+
+/// Whether `(x, y)` is on or past the edge of a `width` × `height` viewport
+pub fn is_outside_viewport(x: f64, y: f64, width: f64, height: f64) -> bool {
+    x <= 0.0 || y <= 0.0 || x >= width - 1.0 || y >= height - 1.0
+}
+
+/// Hand an in-app drag that left the window at `(x, y)` to the OS as a file drag
+///
+/// Returns false, leaving the in-app drag alone, while the pointer is still
+/// inside the window, before it has moved `DRAG_OUT_THRESHOLD` pixels, or
+/// where native file drags are unavailable.
+pub fn drag_out_at_window_edge(mut drag_state: Signal<DragState>, x: f64, y: f64) -> bool {
+    let window = dioxus::desktop::window();
+    let viewport = window.inner_size().to_logical::<f64>(window.scale_factor());
+    if !is_outside_viewport(x, y, viewport.width, viewport.height) {
+        return false;
+    }
+    let paths = {
+        let drag = drag_state.peek();
+        if !drag.can_drag_out(x, y) {
+            return false;
+        }
+        drag_out_payload(&drag.drag_files)
+    };
+    if paths.is_empty() {
+        return false;
+    }
+    match native_drag::start_file_drag(paths) {
+        Ok(()) => {
+            drag_state.write().end_drag();
+            true
+        }
+        Err(e) => {
+            tracing::debug!("File drag to other applications unavailable: {}", e);
+            false
+        }
+    }
+}
+
+/// OS file drags, where the platform supports them
+#[cfg(all(feature = "native-drag", any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+mod native_drag {
+    use std::io::Cursor;
+    use std::path::PathBuf;
+
+    /// Start dragging `paths` out of the app window
+    pub fn start_file_drag(paths: Vec<PathBuf>) -> Result<(), String> {
+        let desktop = dioxus::desktop::window();
+        // GTK drags start from the GTK window rather than the raw window handle
+        #[cfg(target_os = "linux")]
+        let window = {
+            use dioxus::desktop::tao::platform::unix::WindowExtUnix;
+            desktop.window.gtk_window()
+        };
+        #[cfg(not(target_os = "linux"))]
+        let window = &*desktop.window;
+
+        let count = paths.len();
+        drag::start_drag(
+            window,
+            drag::DragItem::Files(paths),
+            drag::Image::Raw(drag_image()?),
+            move |result, _cursor| tracing::info!("File drag of {} items ended: {:?}", count, result),
+            drag::Options::default(),
+        )
+        .map_err(|e| e.to_string())
+    }
+
+    /// Small badge shown under the cursor while dragging
+    fn drag_image() -> Result<Vec<u8>, String> {
+        let badge = image::RgbaImage::from_pixel(32, 32, image::Rgba([0, 122, 204, 200]));
+        let mut png = Vec::new();
+        badge
+            .write_to(&mut Cursor::new(&mut png), image::ImageOutputFormat::Png)
+            .map_err(|e| e.to_string())?;
+        Ok(png)
+    }
+}
+
+/// Without native drag support the drag stays inside the app
+#[cfg(not(all(feature = "native-drag", any(target_os = "windows", target_os = "macos", target_os = "linux"))))]
+mod native_drag {
+    use std::path::PathBuf;
+
+    pub fn start_file_drag(_paths: Vec<PathBuf>) -> Result<(), String> {
+        Err("built without native drag support".to_string())
+    }
+}
+
+/// Command that moves or copies `file` into the folder `target`
+///
+/// `None` for a link, which drag and drop does not support, or a file
+/// without a name.
+pub fn drop_command(file: &FileEntry, operation: &DragOperation, target: &Path) -> Option<Box<dyn Command>> {
+    let source = file.path.clone();
+    let destination = target.join(file.path.file_name()?);
+    Some(match (operation, file.is_directory) {
+        (DragOperation::Link, _) => return None,
+        (DragOperation::Copy, true) => Box::new(CopyDirectoryCommand::new(source, destination)),
+        (DragOperation::Copy, false) => Box::new(CopyCommand::new(source, destination)),
+        (DragOperation::Move, true) => Box::new(MoveDirectoryCommand::new(source, destination)),
+        (DragOperation::Move, false) => Box::new(MoveCommand::new(source, destination)),
+    })
+}
+
+/// Move or copy `files` into the folder `target` without refreshing any listing
+///
+/// Each item runs as its own command and is recorded in the undo history,
+/// grouped as one batch when there are several. Returns `None` when the drop
+/// was rejected.
+pub async fn run_drop(app_state: &AppState, files: &[FileEntry], operation: DragOperation, target: &Path) -> Option<FileOperationResult> {
+    if !is_valid_drop_target(files, &target.to_path_buf()) {
+        tracing::warn!("Rejected drop of {} items onto {:?}", files.len(), target);
+        return None;
+    }
+    let verb = match operation {
+        DragOperation::Move => "moved",
+        DragOperation::Copy => "copied",
+        DragOperation::Link => {
+            tracing::warn!("Linking by drag and drop is not supported");
+            return None;
+        }
+    };
+
+    let batch_id = (files.len() > 1).then(Uuid::new_v4);
+    let mut result = FileOperationResult::default();
+    for file in files {
+        let Some(mut command) = drop_command(file, &operation, target) else {
+            result.failed.push((file.path.clone(), format!("Invalid file name for: {:?}", file.path)));
+            continue;
+        };
+        let destination = command.destination_path().map(Path::to_path_buf).unwrap_or_else(|| target.to_path_buf());
+        let outcome = command.execute(app_state.file_service.clone()).await;
+        let mut history = app_state.operation_history.lock().await;
+        match outcome {
+            Ok(()) => {
+                if let Err(e) = history.add_executed_command_in_batch(command, batch_id).await {
+                    tracing::warn!("Failed to record dropped item for undo: {}", e);
+                }
+                result.succeeded.push(destination);
+            }
+            Err(e) => {
+                history.log_failed_command(command.as_ref(), &e);
+                result.failed.push((file.path.clone(), e.to_string()));
+            }
+        }
+    }
+
+    tracing::info!("Dropped onto {:?}: {} {}, {} failed", target, result.succeeded.len(), verb, result.failed.len());
+    app_state.announcer.file_operation_result(verb, &result);
+    Some(result)
+}
+
+/// Move or copy `files` into the folder `target` and refresh the file list
+pub async fn drop_onto_folder(mut app_state: AppState, files: Vec<FileEntry>, operation: DragOperation, target: PathBuf) {
+    let Some(result) = run_drop(&app_state, &files, operation, &target).await else {
+        return;
+    };
+    if !result.succeeded.is_empty() {
+        if let Err(e) = app_state.refresh_current_directory().await {
+            tracing::warn!("Error refreshing directory after drop: {}", e);
+        }
+    }
+}
+
 /// Hook to manage drag and drop state
 pub fn use_drag_drop() -> (Signal<DragState>, impl FnMut(Vec<FileEntry>, f64, f64, DragOperation), impl FnMut(f64, f64), impl FnMut()) {
     let drag_state = use_signal(DragState::default);
@@ -343,6 +620,10 @@ pub fn is_valid_drop_target(drag_files: &[FileEntry], target_path: &PathBuf) ->
         if file.is_directory && target_path.starts_with(&file.path) {
             return false; // Can't drop directory into its own child
         }
+        
+        if file.path.parent() == Some(target_path.as_path()) {
+            return false; // Already in that folder
+        }
     }
     
     true
@@ -351,6 +632,8 @@ pub fn is_valid_drop_target(drag_files: &[FileEntry], target_path: &PathBuf) ->
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::services::operations::{CommandKind, OperationKind};
+    use crate::state::split_view::{PaneSide, SplitViewState};
     use std::time::SystemTime;
 
     fn create_test_file_entry(name: &str, is_directory: bool) -> FileEntry {
@@ -370,6 +653,7 @@ mod tests {
             is_directory,
             is_hidden: false,
             permissions: crate::services::file_system::FilePermissions::read_write(),
+            preview_metadata: None,
         }
     }
 
@@ -380,6 +664,14 @@ mod tests {
         assert_eq!(DragOperation::from_modifiers(false, false, true), DragOperation::Link);
     }
 
+    #[test]
+    fn test_folder_drop_copies_with_alt_or_ctrl() {
+        assert_eq!(DragOperation::for_folder_drop(false, false), DragOperation::Move);
+        assert_eq!(DragOperation::for_folder_drop(false, true), DragOperation::Copy);
+        assert_eq!(DragOperation::for_folder_drop(true, false), DragOperation::Copy);
+        assert_eq!(DragOperation::for_folder_drop(true, true), DragOperation::Copy);
+    }
+
     #[test]
     fn test_drag_state() {
         let mut state = DragState::default();
@@ -429,4 +721,103 @@ mod tests {
         let child_path = PathBuf::from("parent/child");
         assert!(!is_valid_drop_target(&[parent_folder], &child_path)); // Can't drop parent into child
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_drop_into_own_descendant_is_rejected() {
+        let photos = create_test_file_entry("/media/photos", true);
+        let photo = create_test_file_entry("/media/photos/beach.jpg", false);
+
+        // Anywhere below the dragged folder would be a cycle
+        let nested = Path::new("/media/photos/2024/summer");
+        assert!(!is_valid_drop_target(&[photos.clone()], &nested.to_path_buf()));
+        assert_eq!(DropZoneState::for_target(&[photos.clone()], nested), DropZoneState::DragOverInvalid);
+        // One bad item rejects the whole drop
+        assert!(!is_valid_drop_target(&[photo.clone(), photos.clone()], &nested.to_path_buf()));
+        // A sibling that only shares a name prefix is fine
+        let sibling = Path::new("/media/photos-old");
+        assert_eq!(DropZoneState::for_target(&[photos.clone()], sibling), DropZoneState::DragOverValid);
+        // Dropping where the items already are does nothing, so it is rejected too
+        assert!(!is_valid_drop_target(&[photo.clone()], &PathBuf::from("/media/photos")));
+        assert!(is_valid_drop_target(&[photo], &PathBuf::from("/media")));
+    }
+
+    #[test]
+    fn test_drag_out_payload_comes_from_the_selection() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let album = temp_dir.path().join("album");
+        std::fs::create_dir(&album).unwrap();
+        let inside = album.join("cover.jpg");
+        let photo = temp_dir.path().join("photo.jpg");
+        let gone = temp_dir.path().join("gone.jpg");
+        let other = temp_dir.path().join("other.jpg");
+        for path in [&inside, &photo, &gone, &other] {
+            std::fs::write(path, b"jpeg").unwrap();
+        }
+        let entry = |path: &Path, is_directory: bool| FileEntry {
+            path: path.to_path_buf(),
+            ..create_test_file_entry(&path.to_string_lossy(), is_directory)
+        };
+        let listing = vec![entry(&album, true), entry(&inside, false), entry(&photo, false), entry(&gone, false), entry(&other, false)];
+
+        let mut selection = SelectionState::new();
+        selection.select_files(vec![album.clone(), inside.clone(), photo.clone(), gone.clone()], crate::state::navigation::SelectionMode::Replace);
+        std::fs::remove_file(&gone).unwrap();
+
+        // Dragging a selected item carries the selection, minus what is gone or inside a dragged folder
+        let files = drag_files_for(&listing[2], selected_entries(&selection, &listing));
+        assert_eq!(drag_out_payload(&files), [album.clone(), photo.clone()]);
+
+        // Dragging an unselected item carries only that item
+        let files = drag_files_for(&listing[4], selected_entries(&selection, &listing));
+        assert_eq!(drag_out_payload(&files), [other]);
+    }
+
+    #[test]
+    fn test_drag_out_waits_for_the_threshold_and_window_edge() {
+        let mut state = DragState::default();
+        assert!(!state.can_drag_out(50.0, 50.0));
+
+        state.start_drag(vec![create_test_file_entry("a.jpg", false)], 100.0, 100.0, DragOperation::Move);
+        assert!(!state.can_drag_out(103.0, 104.0));
+        assert!(state.can_drag_out(100.0, 100.0 + DRAG_OUT_THRESHOLD));
+
+        assert!(!is_outside_viewport(400.0, 300.0, 800.0, 600.0));
+        assert!(is_outside_viewport(0.0, 300.0, 800.0, 600.0));
+        assert!(is_outside_viewport(400.0, 612.0, 800.0, 600.0));
+    }
+
+    #[test]
+    fn test_dragging_a_selected_file_carries_the_selection() {
+        let a = create_test_file_entry("a.jpg", false);
+        let b = create_test_file_entry("b.jpg", false);
+        let c = create_test_file_entry("c.jpg", false);
+
+        let files = drag_files_for(&a, vec![a.clone(), b.clone()]);
+        assert_eq!(files.len(), 2);
+        let files = drag_files_for(&c, vec![a, b]);
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, c.path);
+    }
+
+    #[test]
+    fn test_cross_pane_move_uses_move_command() {
+        let split_view = SplitViewState::new(PathBuf::from("/photos"), PathBuf::from("/archive"));
+        let mut file = create_test_file_entry("beach.jpg", false);
+        file.path = split_view.pane(PaneSide::Primary).current_path().join("beach.jpg");
+        let target = split_view.pane(PaneSide::Primary.other()).current_path().to_path_buf();
+        assert!(is_valid_drop_target(&[file.clone()], &target));
+
+        let command = drop_command(&file, &DragOperation::Move, &target).unwrap();
+        assert_eq!(command.kind(), OperationKind::Move);
+        assert_eq!(command.destination_path(), Some(Path::new("/archive/beach.jpg")));
+        match command.command_kind() {
+            Some(CommandKind::Move(_)) => {}
+            other => panic!("expected a move command, got {:?}", other),
+        }
+
+        // Copying across panes and linking stay distinct
+        let copy = drop_command(&file, &DragOperation::Copy, &target).unwrap();
+        assert_eq!(copy.kind(), OperationKind::Copy);
+        assert!(drop_command(&file, &DragOperation::Link, &target).is_none());
+    }
+}
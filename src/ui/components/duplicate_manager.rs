@@ -4,15 +4,16 @@ use std::path::PathBuf;
 use crate::services::{
     DuplicateDetector, DuplicateDetectionResults, DuplicateGroup,
     ComparisonMethod, DuplicateDetectionConfig, DetectionProgress,
-    PrimarySelectionStrategy, FileEntry
+    PrimarySelectionStrategy, FileEntry, IoPriority
 };
+use crate::services::duplicate_detection::DEFAULT_PERCEPTUAL_THRESHOLD;
 use crate::state::{use_app_state, use_selection_state};
 use crate::ui::components::{
     ConfirmationDialog, ConfirmationResult,
     ProgressDialog
 };
 use crate::ui::components::dialogs::{ConfirmationAction, ProgressDialogState};
-use crate::utils::normalize_path_display;
+use crate::utils::{format_date_time, format_size, normalize_path_display};
 
 /// State for the duplicate manager
 #[derive(Debug, Clone, PartialEq)]
@@ -39,6 +40,8 @@ pub struct DetectionSettings {
     pub include_extensions: Vec<String>,
     pub exclude_extensions: Vec<String>,
     pub primary_selection: PrimarySelectionStrategy,
+    /// Hamming distance for `ComparisonMethod::Perceptual`
+    pub perceptual_threshold: u32,
 }
 
 impl Default for DetectionSettings {
@@ -56,6 +59,7 @@ impl Default for DetectionSettings {
                 "cache".to_string(),
             ],
             primary_selection: PrimarySelectionStrategy::Oldest,
+            perceptual_threshold: DEFAULT_PERCEPTUAL_THRESHOLD,
         }
     }
 }
@@ -236,7 +240,7 @@ pub fn DuplicateManager(mut props: DuplicateManagerProps) -> Element {
                             _ => crate::services::ErrorSeverity::Medium,
                         },
                         on_result: move |result: ConfirmationResult| {
-                            if result == ConfirmationResult::Confirmed {
+                            if result.is_confirmed() {
                                 execute_duplicate_action(action.clone(), manager_state, detection_results);
                             }
                             confirmation_dialog.set(None);
@@ -375,6 +379,7 @@ fn DuplicateDetectionSetup(
                             "Name" => ComparisonMethod::Name,
                             "SizeAndName" => ComparisonMethod::SizeAndName,
                             "ContentAndSize" => ComparisonMethod::ContentAndSize,
+                            "Perceptual" => ComparisonMethod::Perceptual,
                             _ => ComparisonMethod::Content,
                         };
                         let mut new_settings = local_settings.read().clone();
@@ -387,6 +392,7 @@ fn DuplicateDetectionSetup(
                     option { value: "Name", "File Name" }
                     option { value: "SizeAndName", "Size + Name" }
                     option { value: "ContentAndSize", "Content + Size (Recommended)" }
+                    option { value: "Perceptual", "Visually Similar Images" }
                 }
                 div { style: "margin-top: 8px; font-size: 12px; color: #666;",
                     match local_settings.read().comparison_method {
@@ -395,6 +401,27 @@ fn DuplicateDetectionSetup(
                         ComparisonMethod::Name => "Compares files with identical names.",
                         ComparisonMethod::SizeAndName => "Combines size and name comparison.",
                         ComparisonMethod::ContentAndSize => "Most reliable: combines content hash with size verification.",
+                        ComparisonMethod::Perceptual => "Finds resized or re-encoded copies of the same photo. Only images are compared.",
+                    }
+                }
+                if local_settings.read().comparison_method == ComparisonMethod::Perceptual {
+                    label {
+                        style: "display: block; margin-top: 12px; margin-bottom: 4px; font-weight: 500;",
+                        "Similarity Threshold (0 = identical, 64 = anything)"
+                    }
+                    input {
+                        r#type: "number",
+                        style: "width: 100%; padding: 8px; border: 1px solid #ccc; border-radius: 4px;",
+                        value: "{local_settings.read().perceptual_threshold}",
+                        min: "0",
+                        max: "64",
+                        onchange: move |e| {
+                            let threshold = e.value().parse().unwrap_or(DEFAULT_PERCEPTUAL_THRESHOLD).min(64);
+                            let mut new_settings = local_settings.read().clone();
+                            new_settings.perceptual_threshold = threshold;
+                            local_settings.set(new_settings.clone());
+                            on_settings_change.call(new_settings);
+                        }
                     }
                 }
             }
@@ -438,6 +465,7 @@ fn DuplicateDetectionSetup(
                                     "ShortestPath" => PrimarySelectionStrategy::ShortestPath,
                                     "LongestPath" => PrimarySelectionStrategy::LongestPath,
                                     "First" => PrimarySelectionStrategy::First,
+                                    "HighestResolution" => PrimarySelectionStrategy::HighestResolution,
                                     _ => PrimarySelectionStrategy::Oldest,
                                 };
                                 let mut new_settings = local_settings.read().clone();
@@ -450,6 +478,7 @@ fn DuplicateDetectionSetup(
                             option { value: "ShortestPath", "Shortest Path" }
                             option { value: "LongestPath", "Longest Path" }
                             option { value: "First", "First Found" }
+                            option { value: "HighestResolution", "Highest Resolution" }
                         }
                     }
                 }
@@ -807,6 +836,26 @@ fn spawn_detection_task(
     mut detection_results: Signal<Option<DuplicateDetectionResults>>,
     mut detection_progress: Signal<Option<DetectionProgress>>,
 ) {
+    let app_state = try_consume_context::<crate::state::AppState>();
+    let progress_hub = app_state.as_ref().map(|app_state| app_state.progress_hub.clone());
+    let throttle_policy = app_state.as_ref().map(|app_state| app_state.background_throttle.clone());
+    let (workers, io_priority) = match &app_state {
+        Some(app_state) => {
+            let app_settings = app_state.settings.read();
+            let io_priority = if app_settings.duplicate_scan_low_io_priority {
+                IoPriority::Low
+            } else {
+                IoPriority::Normal
+            };
+            (app_settings.duplicate_scan_workers, io_priority)
+        }
+        None => {
+            let defaults = DuplicateDetectionConfig::default();
+            (defaults.workers, defaults.io_priority)
+        }
+    };
+    let announcer = app_state.map(|app_state| app_state.announcer.clone());
+    
     spawn(async move {
         // Convert settings to detection config
         let config = DuplicateDetectionConfig {
@@ -818,10 +867,17 @@ fn spawn_detection_task(
             exclude_extensions: settings.exclude_extensions,
             primary_selection: settings.primary_selection,
             max_files: None,
+            workers,
+            io_priority,
+            perceptual_threshold: settings.perceptual_threshold,
+            ..DuplicateDetectionConfig::default()
         };
 
         // Create detector
-        let detector = DuplicateDetector::with_config(config);
+        let mut detector = DuplicateDetector::with_config(config);
+        if let Some(throttle_policy) = throttle_policy {
+            detector = detector.with_throttle_policy(throttle_policy);
+        }
 
         // Get files to analyze
         let files_to_analyze = match files {
@@ -839,9 +895,21 @@ fn spawn_detection_task(
             return;
         }
 
+        // Show the scan in the global progress HUD for as long as it runs
+        let hud_task = progress_hub.map(|hub| std::sync::Arc::new(hub.register(
+            "Duplicate scan",
+            crate::services::progress_hub::TaskKind::DuplicateScan,
+            None,
+        )));
+        
         // Create progress callback that is Send + Sync
         let progress_callback: crate::services::duplicate_detection::DetectionProgressCallback = {
+            let hud_task = hud_task.clone();
             std::sync::Arc::new(move |progress: crate::services::duplicate_detection::DetectionProgress| {
+                if let Some(hud_task) = &hud_task {
+                    hud_task.update_detection_progress(&progress);
+                }
+                
                 // Since we can't capture the signal directly in a Send + Sync closure,
                 // we'll spawn a task to update it
                 tokio::spawn(async move {
@@ -856,12 +924,26 @@ fn spawn_detection_task(
         };
 
         // Run detection
-        match detector.detect_duplicates(files_to_analyze, Some(progress_callback)).await {
+        let detection = detector.detect_duplicates(files_to_analyze, Some(progress_callback)).await;
+        drop(hud_task);
+        
+        match detection {
             Ok(results) => {
+                if let Some(announcer) = &announcer {
+                    let group_word = if results.groups.len() == 1 { "group" } else { "groups" };
+                    announcer.polite(format!(
+                        "Duplicate scan complete, {} duplicate {} found",
+                        results.groups.len(),
+                        group_word
+                    ));
+                }
                 detection_results.set(Some(results));
                 manager_state.set(DuplicateManagerState::ReviewingResults);
             }
             Err(error) => {
+                if let Some(announcer) = &announcer {
+                    announcer.operation_failed("Duplicate scan", &error);
+                }
                 manager_state.set(DuplicateManagerState::Error(error.to_string()));
             }
         }
@@ -961,25 +1043,7 @@ fn calculate_selected_savings(
         })
         .sum();
 
-    format_file_size(total_size)
-}
-
-/// Format file size for display
-fn format_file_size(size: u64) -> String {
-    const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
-    let mut size_f = size as f64;
-    let mut unit_index = 0;
-    
-    while size_f >= 1024.0 && unit_index < UNITS.len() - 1 {
-        size_f /= 1024.0;
-        unit_index += 1;
-    }
-    
-    if unit_index == 0 {
-        format!("{} {}", size as u64, UNITS[unit_index])
-    } else {
-        format!("{:.1} {}", size_f, UNITS[unit_index])
-    }
+    format_size(total_size)
 }
 
 /// Individual duplicate group card component
@@ -1039,11 +1103,11 @@ fn DuplicateGroupCard(
                 div { style: "display: flex; align-items: center; gap: 8px;",
                     if let Some(primary) = primary_file {
                         div { style: "font-size: 12px; padding: 4px 8px; background: #e3f2fd; color: #1976d2; border-radius: 12px;",
-                            "📍 Primary: {format_file_size(primary.size())}"
+                            "📍 Primary: {format_size(primary.size())}"
                         }
                     }
                     div { style: "font-size: 12px; color: #666;",
-                        "Modified: {group.files.first().map(|f| format_timestamp(f.modified())).unwrap_or_default()}"
+                        "Modified: {group.files.first().map(|f| format_date_time(f.modified())).unwrap_or_default()}"
                     }
                 }
             }
@@ -1103,9 +1167,9 @@ fn DuplicateGroupCard(
                                     }
 
                                     div { style: "text-align: right; font-size: 12px; color: #666;",
-                                        div { "{format_file_size(file_size)}" }
+                                        div { "{format_size(file_size)}" }
                                         div { style: "margin-top: 2px;",
-                                            "{format_timestamp(file_modified)}"
+                                            "{format_date_time(file_modified)}"
                                         }
                                     }
                                 }
@@ -1164,20 +1228,5 @@ fn calculate_group_savings(group: &DuplicateGroup) -> String {
         .filter(|f| !f.is_primary)
         .map(|f| f.size())
         .sum();
-    format_file_size(duplicate_size)
+    format_size(duplicate_size)
 }
-
-/// Format timestamp for display
-fn format_timestamp(timestamp: std::time::SystemTime) -> String {
-    use std::time::{Duration, UNIX_EPOCH};
-    
-    let duration = timestamp.duration_since(UNIX_EPOCH)
-        .unwrap_or(Duration::from_secs(0));
-    
-    let secs = duration.as_secs();
-    let datetime = chrono::DateTime::from_timestamp(secs as i64, 0)
-        .map(|dt| dt.naive_utc())
-        .unwrap_or_default();
-    
-    datetime.format("%Y-%m-%d %H:%M").to_string()
-}
\ No newline at end of file
@@ -3,17 +3,23 @@ use crate::services::{file_system::FileEntry, preview::PreviewData};
 use crate::utils::{FileTypeDetectionUtil, FilePreviewSupport};
 use crate::ui::components::{PreviewPanel, InfoPanel};
 use crate::ui::components::preview_panel::FileSystemEntry;
+use crate::state::use_app_state;
 
 /// Dynamic Content Panel that switches between PreviewPanel and InfoPanel
-/// based on file type detection and preview support
+/// based on file type detection and preview support; several selected files
+/// always get the InfoPanel's totals
 #[component]
 pub fn DynamicContentPanel(
     selected_file: Signal<Option<FileEntry>>,
     preview_data: Signal<Option<PreviewData>>,
 ) -> Element {
+    let selection = use_app_state().selection;
+    
     // Create a computed signal that determines which panel to show
     let panel_type = use_memo(move || {
-        if let Some(file_entry) = selected_file.read().as_ref() {
+        if selection.read().selection_count() > 1 {
+            PanelType::Info
+        } else if let Some(file_entry) = selected_file.read().as_ref() {
             let support = FileTypeDetectionUtil::detect_preview_support(
                 &file_entry.file_type, 
                 &file_entry.path
@@ -29,43 +35,11 @@ pub fn DynamicContentPanel(
     });
 
     // Create a signal that converts FileEntry to FileSystemEntry for PreviewPanel
-    let mut filesystem_entry_signal = use_signal(|| {
-        selected_file.read().as_ref().map(|entry| {
-            FileSystemEntry {
-                path: entry.path.clone(),
-                name: entry.name.clone(),
-                is_directory: entry.is_directory,
-                size: entry.size,
-                modified: entry.modified,
-                file_type: match &entry.file_type {
-                    crate::services::file_system::FileType::Image(_) => Some("image".to_string()),
-                    crate::services::file_system::FileType::Video(_) => Some("video".to_string()),
-                    crate::services::file_system::FileType::Audio(_) => Some("audio".to_string()),
-                    crate::services::file_system::FileType::Document(_) => Some("document".to_string()),
-                    _ => None,
-                }
-            }
-        })
-    });
+    let mut filesystem_entry_signal = use_signal(|| selected_file.read().as_ref().map(FileSystemEntry::from));
 
     // Update the filesystem entry signal when selected_file changes
     use_effect(move || {
-        let new_value = selected_file.read().as_ref().map(|entry| {
-            FileSystemEntry {
-                path: entry.path.clone(),
-                name: entry.name.clone(),
-                is_directory: entry.is_directory,
-                size: entry.size,
-                modified: entry.modified,
-                file_type: match &entry.file_type {
-                    crate::services::file_system::FileType::Image(_) => Some("image".to_string()),
-                    crate::services::file_system::FileType::Video(_) => Some("video".to_string()),
-                    crate::services::file_system::FileType::Audio(_) => Some("audio".to_string()),
-                    crate::services::file_system::FileType::Document(_) => Some("document".to_string()),
-                    _ => None,
-                }
-            }
-        });
+        let new_value = selected_file.read().as_ref().map(FileSystemEntry::from);
         filesystem_entry_signal.set(new_value);
     });
     
@@ -1,11 +1,55 @@
+use dioxus::html::HasFileData;
 use dioxus::prelude::*;
+use std::path::PathBuf;
 
-/// Empty state component for file tree when no folder is selected
+use crate::state::{use_app_state, AppState, EmptyTreeState};
+use crate::utils::normalize_path_display;
+
+/// Open `path` as the file tree root, as the File menu does
+fn open_folder(mut app_state: AppState, path: PathBuf) {
+    if !path.is_dir() {
+        // Moved or deleted since it was opened last
+        app_state.forget_recent_folder(&path);
+        return;
+    }
+    spawn(async move {
+        if let Err(e) = app_state.navigate_to(path.clone()).await {
+            tracing::error!("Failed to navigate to {:?}: {}", path, e);
+            return;
+        }
+        if let Err(e) = app_state.set_root_folder_with_persistence(path.clone()).await {
+            tracing::error!("Failed to load {:?}: {}", path, e);
+        }
+    });
+}
+
+/// Empty state for the file tree
+///
+/// `state` is `None` while no folder is open; then the folder picker, recent
+/// folders and a drop target for a folder dragged in are offered. An empty
+/// folder and a fully filtered one get their own message and action.
 #[component]
-pub fn EmptyFileTree(on_folder_select: EventHandler<()>) -> Element {
+pub fn EmptyFileTree(state: Option<EmptyTreeState>) -> Element {
+    let app_state = use_app_state();
+    let mut is_drag_over = use_signal(|| false);
+
+    let (icon, title, description) = match state {
+        None => ("📁", "No Folder Selected", "Open a folder to start exploring, or drop one here."),
+        Some(EmptyTreeState::EmptyFolder) => ("📭", "This Folder Is Empty", "There are no files or folders here yet."),
+        Some(EmptyTreeState::AllFiltered) => ("🔍", "No Matching Items", "Every item in this folder is hidden by the current filters."),
+    };
+    let recent_folders = match state {
+        None => app_state.settings.read().recent_folders.folders().to_vec(),
+        Some(_) => Vec::new(),
+    };
+    let mut menu_action_request = app_state.menu_action_request;
+    let mut filter_state = app_state.clone();
+    let drop_state = app_state.clone();
+    let class = if *is_drag_over.read() { "empty-file-tree drag-over" } else { "empty-file-tree" };
+
     rsx! {
         div {
-            class: "empty-file-tree",
+            class: "{class}",
             style: "
                 padding: 40px 20px;
                 text-align: center;
@@ -17,41 +61,87 @@ pub fn EmptyFileTree(on_folder_select: EventHandler<()>) -> Element {
                 justify-content: center;
                 gap: 20px;
             ",
-            
+            ondragover: move |evt| {
+                if state.is_none() {
+                    evt.prevent_default();
+                    is_drag_over.set(true);
+                }
+            },
+            ondragleave: move |_| is_drag_over.set(false),
+            ondrop: move |evt| {
+                is_drag_over.set(false);
+                if state.is_some() {
+                    return;
+                }
+                evt.prevent_default();
+                evt.stop_propagation();
+                let dropped = evt.files().map(|files| files.files()).unwrap_or_default();
+                match dropped.into_iter().map(PathBuf::from).find(|path| path.is_dir()) {
+                    Some(folder) => open_folder(drop_state.clone(), folder),
+                    None => tracing::info!("Nothing to open: no folder among the dropped items"),
+                }
+            },
+
             // Icon
             div {
                 style: "font-size: 48px; opacity: 0.6;",
-                "📁"
+                "aria-hidden": "true",
+                "{icon}"
             }
-            
+
             // Title
             h3 {
                 style: "margin: 0; font-size: 16px; color: var(--vscode-foreground, #cccccc);",
-                "No Folder Selected"
+                "{title}"
             }
-            
+
             // Description
             p {
                 style: "margin: 0; font-size: 14px; max-width: 280px; line-height: 1.4;",
-                "To start exploring files, please select a folder to open."
+                "{description}"
             }
-            
-            // Select Folder Button
-            button {
-                r#type: "button",
-                onclick: move |_| on_folder_select.call(()),
-                style: "
-                    padding: 12px 24px;
-                    background: var(--vscode-button-background, #0e639c);
-                    color: var(--vscode-button-foreground, #ffffff);
-                    border: none;
-                    border-radius: 3px;
-                    font-size: 13px;
-                    cursor: pointer;
-                ",
-                
-                "📂 Select Folder"
+
+            if state == Some(EmptyTreeState::AllFiltered) {
+                button {
+                    r#type: "button",
+                    class: "empty-file-tree-action",
+                    onclick: move |_| filter_state.clear_file_tree_filters(),
+                    "Clear Filters"
+                }
+            } else {
+                // Same folder picker as File > Open Folder
+                button {
+                    r#type: "button",
+                    class: "empty-file-tree-action",
+                    onclick: move |_| menu_action_request.set(Some("open_folder")),
+                    if state.is_none() { "📂 Select Folder" } else { "📂 Open Another Folder" }
+                }
+            }
+
+            if !recent_folders.is_empty() {
+                nav {
+                    class: "empty-file-tree-recent",
+                    "aria-label": "Recent folders",
+                    h4 { "Recent Folders" }
+                    ul {
+                        for folder in recent_folders {
+                            li {
+                                key: "{folder.display()}",
+                                button {
+                                    r#type: "button",
+                                    title: "{normalize_path_display(&folder)}",
+                                    onclick: {
+                                        let app_state = app_state.clone();
+                                        let folder = folder.clone();
+                                        move |_| open_folder(app_state.clone(), folder.clone())
+                                    },
+                                    {folder.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_else(|| normalize_path_display(&folder))}
+                                }
+                            }
+                        }
+                    }
+                }
             }
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,134 @@
+use dioxus::prelude::*;
+use std::time::{Duration, SystemTime};
+
+use crate::services::operations::OperationError;
+use crate::services::{ErrorSeverity, ErrorStatistics};
+use crate::state::use_app_state;
+use crate::utils::format_date_time;
+
+/// How far back the status bar counts errors
+const ERROR_WINDOW: Duration = Duration::from_secs(15 * 60);
+
+/// How often the indicator re-reads the error history
+const ERROR_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Status bar count of recent operation errors by severity, expanding to the
+/// errors and what to do about them on click
+#[component]
+pub fn ErrorStatus() -> Element {
+    let app_state = use_app_state();
+    let mut breakdown = use_signal(Vec::<(ErrorSeverity, usize)>::new);
+    let mut errors = use_signal(Vec::<ErrorRow>::new);
+    let mut expanded = use_signal(|| false);
+
+    let manager = app_state.error_recovery.clone();
+    use_future(move || {
+        let manager = manager.clone();
+        async move {
+            loop {
+                let since = SystemTime::now() - ERROR_WINDOW;
+                let (statistics, recent) = match manager.lock() {
+                    Ok(manager) => (Some(manager.error_statistics(since)), manager.recent_errors(since)),
+                    Err(_) => (None, Vec::new()),
+                };
+                let rows: Vec<ErrorRow> = recent.iter().map(|(time, error)| ErrorRow::new(*time, error)).collect();
+                if rows != *errors.peek() {
+                    breakdown.set(statistics.as_ref().map(ErrorStatistics::severity_breakdown).unwrap_or_default());
+                    errors.set(rows);
+                }
+                tokio::time::sleep(ERROR_REFRESH_INTERVAL).await;
+            }
+        }
+    });
+
+    let counts = breakdown.read().clone();
+    let Some((highest, _)) = counts.first().copied() else {
+        return rsx! {};
+    };
+    let total: usize = counts.iter().map(|(_, count)| count).sum();
+    let summary = counts
+        .iter()
+        .map(|(severity, count)| format!("{} {}", count, severity.label()))
+        .collect::<Vec<_>>()
+        .join(" · ");
+    let error_word = if total == 1 { "error" } else { "errors" };
+    let manager = app_state.error_recovery.clone();
+
+    rsx! {
+        div {
+            class: "error-status",
+
+            button {
+                class: "error-status-summary severity-{highest.label()}",
+                "aria-expanded": "{expanded}",
+                "aria-label": "{total} {error_word} in the last 15 minutes: {summary}",
+                title: "Operation errors in the last 15 minutes",
+                onclick: move |_| {
+                    let is_expanded = *expanded.read();
+                    expanded.set(!is_expanded);
+                },
+                "⚠ {summary}"
+            }
+
+            if *expanded.read() {
+                div {
+                    class: "error-status-panel",
+                    role: "dialog",
+                    "aria-label": "Recent operation errors",
+
+                    ul {
+                        role: "list",
+                        for (index, row) in errors.read().iter().enumerate() {
+                            li {
+                                key: "{index}",
+                                class: "error-status-item",
+                                div {
+                                    class: "error-status-item-header",
+                                    span { class: "error-status-severity severity-{row.severity.label()}", "{row.severity.label()}" }
+                                    span { class: "error-status-time", {format_date_time(row.time)} }
+                                }
+                                div { class: "error-status-description", "{row.description}" }
+                                div { class: "error-status-suggestion", "{row.suggestion}" }
+                            }
+                        }
+                    }
+
+                    button {
+                        class: "button",
+                        onclick: move |_| {
+                            if let Ok(mut manager) = manager.lock() {
+                                manager.clear_history();
+                            }
+                            breakdown.set(Vec::new());
+                            errors.set(Vec::new());
+                            expanded.set(false);
+                        },
+                        "Clear"
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// One recent error as listed in the panel
+#[derive(Debug, Clone, PartialEq)]
+struct ErrorRow {
+    time: SystemTime,
+    severity: ErrorSeverity,
+    description: String,
+    suggestion: String,
+}
+
+impl ErrorRow {
+    fn new(time: SystemTime, error: &OperationError) -> Self {
+        let (severity, _) = error.classify();
+        let suggestion = error.recovery_suggestion();
+        Self {
+            time,
+            severity,
+            description: suggestion.description,
+            suggestion: suggestion.suggestion,
+        }
+    }
+}
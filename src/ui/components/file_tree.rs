@@ -89,7 +89,7 @@ pub fn FileTreeNode(
     let is_loading = tree_state.loading_directories.contains(&path);
     let is_selected = tree_state.selected_path.as_ref().map(|p| p == &path).unwrap_or(false);
     let children = if is_expanded && is_directory {
-        tree_state.directory_children.get(&path).cloned().unwrap_or_default()
+        tree_state.visible_children(&path)
     } else {
         Vec::new()
     };
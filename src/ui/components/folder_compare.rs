@@ -0,0 +1,352 @@
+// Folder comparison tool
+// Streams the differences between two folders into a color-coded tree with per-row copy and delete actions
+
+use dioxus::prelude::*;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::services::announcer::Politeness;
+use crate::services::folder_diff::{
+    compare_folders, diff_tree_rows, CompareDepth, DiffAction, DiffEntry, DiffStatus, DiffTreeRow, FolderDiffError,
+};
+use crate::services::operations::{CancellationToken, OperationResult};
+use crate::services::progress_hub::TaskKind;
+use crate::state::{use_app_state, AppState};
+use crate::ui::components::{modal_title_id, Modal};
+use crate::utils::format_size;
+
+/// Rows rendered at once; the summary still counts every difference
+const MAX_VISIBLE_ROWS: usize = 2000;
+
+/// Differences received from the walk are appended in batches of this size
+const ENTRY_BATCH_SIZE: usize = 256;
+
+/// Folder comparison dialog; mount it only while it should be shown
+#[component]
+pub fn FolderComparePanel(on_close: EventHandler<()>) -> Element {
+    let app_state = use_app_state();
+    let current_path = app_state.navigation.read().current_path.display().to_string();
+    let mut folder_a = use_signal(move || current_path);
+    let mut folder_b = use_signal(String::new);
+    let mut depth = use_signal(CompareDepth::default);
+    let mut entries = use_signal(Vec::<DiffEntry>::new);
+    // Entries already reconciled by a row action
+    let mut resolved = use_signal(HashSet::<usize>::new);
+    // Roots the current entries are relative to
+    let mut roots = use_signal(|| None::<(PathBuf, PathBuf)>);
+    let mut running = use_signal(|| None::<CancellationToken>);
+    let mut status = use_signal(|| None::<String>);
+    let rows = use_memo(move || diff_tree_rows(&entries.read()));
+
+    use_drop(move || {
+        if let Some(token) = running.peek().as_ref() {
+            token.cancel();
+        }
+    });
+
+    let start_compare = {
+        let progress_hub = app_state.progress_hub.clone();
+        move |_| {
+            let root_a = PathBuf::from(folder_a.read().trim());
+            let root_b = PathBuf::from(folder_b.read().trim());
+            if !root_a.is_dir() || !root_b.is_dir() {
+                status.set(Some(t!("compare.not_folders")));
+                return;
+            }
+
+            let token = CancellationToken::new();
+            entries.set(Vec::new());
+            resolved.set(HashSet::new());
+            roots.set(Some((root_a.clone(), root_b.clone())));
+            running.set(Some(token.clone()));
+            status.set(Some(t!("compare.running")));
+
+            let depth = *depth.read();
+            let progress_hub = progress_hub.clone();
+            spawn(async move {
+                let hud_task = progress_hub.register(t!("compare.title"), TaskKind::Other, {
+                    let token = token.clone();
+                    Some(Arc::new(move || token.cancel()))
+                });
+
+                let (entry_tx, mut entry_rx) = tokio::sync::mpsc::unbounded_channel();
+                let walk = tokio::task::spawn_blocking(move || {
+                    compare_folders(&root_a, &root_b, depth, &token, |entry| {
+                        let _ = entry_tx.send(entry);
+                    })
+                });
+
+                // Batching keeps a large tree from re-rendering once per difference
+                let mut batch = Vec::with_capacity(ENTRY_BATCH_SIZE);
+                while entry_rx.recv_many(&mut batch, ENTRY_BATCH_SIZE).await > 0 {
+                    entries.write().append(&mut batch);
+                }
+                drop(hud_task);
+
+                let message = match walk.await {
+                    Ok(Ok(summary)) if summary.only_in_a + summary.only_in_b + summary.differs == 0 => {
+                        t!("compare.identical")
+                    }
+                    Ok(Ok(summary)) => t!(
+                        "compare.summary",
+                        only_a = summary.only_in_a,
+                        only_b = summary.only_in_b,
+                        differs = summary.differs
+                    ),
+                    Ok(Err(FolderDiffError::Cancelled)) => t!("compare.cancelled"),
+                    Ok(Err(e)) => t!("compare.failed", error = e),
+                    Err(e) => t!("compare.failed", error = e),
+                };
+                running.set(None);
+                status.set(Some(message));
+            });
+        }
+    };
+
+    let dialog_id = "folder-compare-dialog";
+    let is_running = running.read().is_some();
+    let row_count = rows.read().len();
+
+    rsx! {
+        Modal {
+            id: "{dialog_id}",
+            class: "folder-compare-dialog",
+            on_close: move |_| on_close.call(()),
+
+            div {
+                class: "dialog-header",
+                h3 { id: modal_title_id(dialog_id), {t!("compare.title")} }
+            }
+
+            div {
+                class: "dialog-content",
+                div {
+                    class: "log-filters",
+                    FolderField { label: t!("compare.folder_a"), value: folder_a }
+                    FolderField { label: t!("compare.folder_b"), value: folder_b }
+                    label {
+                        {t!("compare.depth")}
+                        select {
+                            onchange: move |evt| {
+                                if let Some(d) = CompareDepth::ALL.into_iter().find(|d| format!("{:?}", d) == evt.value()) {
+                                    depth.set(d);
+                                }
+                            },
+                            for d in CompareDepth::ALL {
+                                option {
+                                    value: format!("{:?}", d),
+                                    selected: *depth.read() == d,
+                                    {depth_label(d)}
+                                }
+                            }
+                        }
+                    }
+                }
+
+                if let Some(message) = status.read().as_ref() {
+                    p { class: "dialog-message", role: "status", {message.clone()} }
+                }
+
+                div {
+                    class: "diff-tree",
+                    role: "tree",
+                    "aria-label": t!("compare.title"),
+                    for (position, row) in rows.read().iter().take(MAX_VISIBLE_ROWS).enumerate() {
+                        {
+                            let entry = row.entry.map(|index| entries.read()[index].clone());
+                            let is_resolved = row.entry.is_some_and(|index| resolved.read().contains(&index));
+                            let app_state = app_state.clone();
+                            let index = row.entry;
+                            rsx! {
+                                DiffRow {
+                                    key: "{position}-{row.name}",
+                                    row: row.clone(),
+                                    entry,
+                                    resolved: is_resolved,
+                                    on_action: move |action| {
+                                        let (Some(index), Some((root_a, root_b))) = (index, roots.read().clone()) else {
+                                            return;
+                                        };
+                                        let entry = entries.read()[index].clone();
+                                        let app_state = app_state.clone();
+                                        spawn(async move {
+                                            let path = entry.relative_path.display().to_string();
+                                            let (message, politeness) = match apply_action(&app_state, &entry, action, &root_a, &root_b).await {
+                                                Ok(()) => {
+                                                    resolved.write().insert(index);
+                                                    (t!("compare.action_done", path = path), Politeness::Polite)
+                                                }
+                                                Err(e) => (t!("compare.action_failed", path = path, error = e), Politeness::Assertive),
+                                            };
+                                            app_state.announcer.announce(message.clone(), politeness);
+                                            status.set(Some(message));
+                                        });
+                                    },
+                                }
+                            }
+                        }
+                    }
+                }
+                if row_count > MAX_VISIBLE_ROWS {
+                    p { class: "dialog-message", {t!("compare.truncated", count = MAX_VISIBLE_ROWS)} }
+                }
+            }
+
+            div {
+                class: "dialog-actions",
+                if is_running {
+                    button {
+                        class: "button",
+                        onclick: move |_| {
+                            if let Some(token) = running.read().as_ref() {
+                                token.cancel();
+                            }
+                        },
+                        {t!("compare.cancel")}
+                    }
+                } else {
+                    button {
+                        class: "button",
+                        onclick: start_compare,
+                        {t!("compare.start")}
+                    }
+                }
+                button {
+                    class: "button primary",
+                    onclick: move |_| on_close.call(()),
+                    {t!("dialog.close")}
+                }
+            }
+        }
+    }
+}
+
+/// Path input with a folder picker
+#[component]
+fn FolderField(label: String, value: Signal<String>) -> Element {
+    let mut value = value;
+    rsx! {
+        label {
+            {label.clone()}
+            div {
+                style: "display: flex; gap: 4px;",
+                input {
+                    r#type: "text",
+                    value: "{value}",
+                    style: "min-width: 260px;",
+                    oninput: move |evt| value.set(evt.value()),
+                }
+                button {
+                    class: "button",
+                    onclick: move |_| {
+                        spawn(async move {
+                            if let Some(folder) = rfd::AsyncFileDialog::new().pick_folder().await {
+                                value.set(folder.path().display().to_string());
+                            }
+                        });
+                    },
+                    {t!("compare.browse")}
+                }
+            }
+        }
+    }
+}
+
+/// One row of the results tree: a folder header or a difference with its actions
+#[component]
+fn DiffRow(row: DiffTreeRow, entry: Option<DiffEntry>, resolved: bool, on_action: EventHandler<DiffAction>) -> Element {
+    let indent = row.depth * 16;
+    let Some(entry) = entry else {
+        return rsx! {
+            div {
+                class: "diff-row diff-folder",
+                role: "treeitem",
+                style: "padding-left: {indent}px;",
+                "📁 {row.name}"
+            }
+        };
+    };
+
+    let (status_class, status_label) = match entry.status {
+        DiffStatus::OnlyInA => ("only-a", t!("compare.only_in_a")),
+        DiffStatus::OnlyInB => ("only-b", t!("compare.only_in_b")),
+        DiffStatus::Differs => ("differs", t!("compare.differs")),
+    };
+    let icon = if entry.is_directory { "📁" } else { "📄" };
+    let sizes = [entry.size_a, entry.size_b]
+        .iter()
+        .map(|size| size.map(format_size).unwrap_or_else(|| "—".to_string()))
+        .collect::<Vec<_>>()
+        .join(" / ");
+    let row_class = if resolved {
+        format!("diff-row {} resolved", status_class)
+    } else {
+        format!("diff-row {}", status_class)
+    };
+
+    rsx! {
+        div {
+            class: "{row_class}",
+            role: "treeitem",
+            style: "padding-left: {indent}px;",
+            span { class: "diff-name", title: "{entry.relative_path.display()}", "{icon} {row.name}" }
+            span { class: "diff-status", "{status_label}" }
+            span { class: "diff-sizes", "{sizes}" }
+            span {
+                class: "diff-actions",
+                if resolved {
+                    "✓"
+                } else {
+                    for action in entry.actions().iter().copied() {
+                        button {
+                            class: "button",
+                            onclick: move |_| on_action.call(action),
+                            {action_label(action)}
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn depth_label(depth: CompareDepth) -> String {
+    match depth {
+        CompareDepth::Metadata => t!("compare.depth_metadata"),
+        CompareDepth::Content => t!("compare.depth_content"),
+    }
+}
+
+fn action_label(action: DiffAction) -> String {
+    match action {
+        DiffAction::CopyAToB => t!("compare.copy_a_to_b"),
+        DiffAction::CopyBToA => t!("compare.copy_b_to_a"),
+        DiffAction::Delete => t!("compare.delete"),
+    }
+}
+
+/// Run the commands for `action`, recording each in the undo history as one batch
+async fn apply_action(
+    app_state: &AppState,
+    entry: &DiffEntry,
+    action: DiffAction,
+    root_a: &Path,
+    root_b: &Path,
+) -> OperationResult<()> {
+    let commands = entry.action_commands(action, root_a, root_b)?;
+    let batch_id = (commands.len() > 1).then(Uuid::new_v4);
+    for mut command in commands {
+        let result = command.execute(app_state.file_service.clone()).await;
+        let mut history = app_state.operation_history.lock().await;
+        match result {
+            Ok(()) => history.add_executed_command_in_batch(command, batch_id).await?,
+            Err(e) => {
+                history.log_failed_command(command.as_ref(), &e);
+                return Err(e);
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,219 @@
+// Gallery thumbnail tile
+// A fixed-size tile that fits or square-crops its thumbnail and overlays rating, file type and GPS badges
+
+use dioxus::prelude::*;
+
+use crate::services::file_system::{FileEntry, FileType, ImageFormat};
+use crate::state::{SettingsState, ThumbnailSize};
+
+/// Camera raw extensions shown with a RAW badge
+const RAW_EXTENSIONS: &[&str] = &["cr2", "cr3", "nef", "arw", "dng", "orf", "rw2", "raf", "pef", "srw"];
+
+/// Corner of a tile a badge is pinned to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BadgeCorner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl BadgeCorner {
+    fn class(&self) -> &'static str {
+        match self {
+            BadgeCorner::TopLeft => "top-left",
+            BadgeCorner::TopRight => "top-right",
+            BadgeCorner::BottomLeft => "bottom-left",
+            BadgeCorner::BottomRight => "bottom-right",
+        }
+    }
+}
+
+/// Metadata shown on top of a thumbnail
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThumbnailBadge {
+    /// Star rating from 1 to 5
+    Rating(u8),
+    /// Short file type label such as RAW or JPEG
+    Format(&'static str),
+    /// The photo has a GPS position
+    Gps,
+}
+
+impl ThumbnailBadge {
+    /// Each badge kind has its own corner so badges never overlap
+    pub fn corner(&self) -> BadgeCorner {
+        match self {
+            ThumbnailBadge::Format(_) => BadgeCorner::TopRight,
+            ThumbnailBadge::Rating(_) => BadgeCorner::BottomLeft,
+            ThumbnailBadge::Gps => BadgeCorner::BottomRight,
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            ThumbnailBadge::Rating(stars) => "★".repeat(*stars as usize),
+            ThumbnailBadge::Format(label) => label.to_string(),
+            ThumbnailBadge::Gps => "📍".to_string(),
+        }
+    }
+
+    fn description(&self) -> String {
+        match self {
+            ThumbnailBadge::Rating(stars) => t!("gallery.badge_rating", count = *stars),
+            ThumbnailBadge::Format(label) => label.to_string(),
+            ThumbnailBadge::Gps => t!("gallery.badge_gps"),
+        }
+    }
+}
+
+/// Badges for `entry` at the given tile size; small tiles are too cramped for any
+pub fn thumbnail_badges(entry: &FileEntry, size: ThumbnailSize) -> Vec<ThumbnailBadge> {
+    if size == ThumbnailSize::Small || entry.is_directory {
+        return Vec::new();
+    }
+
+    let mut badges = Vec::new();
+    if let Some(label) = format_label(entry) {
+        badges.push(ThumbnailBadge::Format(label));
+    }
+    let exif = entry
+        .preview_metadata
+        .as_ref()
+        .and_then(|metadata| metadata.exif_data.as_ref());
+    if let Some(exif) = exif {
+        if let Some(stars) = exif.rating.filter(|stars| *stars > 0) {
+            badges.push(ThumbnailBadge::Rating(stars.min(5)));
+        }
+        if exif.gps_latitude.is_some() && exif.gps_longitude.is_some() {
+            badges.push(ThumbnailBadge::Gps);
+        }
+    }
+    badges
+}
+
+fn format_label(entry: &FileEntry) -> Option<&'static str> {
+    if entry
+        .extension()
+        .is_some_and(|ext| RAW_EXTENSIONS.contains(&ext.as_str()))
+    {
+        return Some("RAW");
+    }
+    match &entry.file_type {
+        FileType::Image(format) => Some(match format {
+            ImageFormat::Jpeg => "JPEG",
+            ImageFormat::Png => "PNG",
+            ImageFormat::Gif => "GIF",
+            ImageFormat::WebP => "WEBP",
+            ImageFormat::Tiff => "TIFF",
+            ImageFormat::Bmp => "BMP",
+            ImageFormat::Svg => "SVG",
+        }),
+        _ => None,
+    }
+}
+
+/// One gallery tile; `thumbnail` is the image source, or `None` while it loads
+///
+/// Shape and size only change the tile's CSS, so switching them reuses the
+/// thumbnail already loaded instead of requesting a new one.
+#[component]
+pub fn GalleryThumbnail(entry: FileEntry, thumbnail: Option<String>, settings: Signal<SettingsState>) -> Element {
+    let settings = settings.read();
+    let edge = settings.thumbnail_size.pixels();
+    let object_fit = settings.thumbnail_shape.object_fit();
+    let badges = if settings.show_thumbnail_badges {
+        thumbnail_badges(&entry, settings.thumbnail_size)
+    } else {
+        Vec::new()
+    };
+
+    rsx! {
+        div {
+            class: "gallery-tile",
+            style: "width: {edge}px; height: {edge}px;",
+            title: "{entry.name}",
+            if let Some(src) = thumbnail {
+                img {
+                    class: "gallery-tile-image",
+                    src: "{src}",
+                    alt: "{entry.name}",
+                    style: "object-fit: {object_fit};",
+                }
+            } else {
+                div { class: "gallery-tile-placeholder", "aria-hidden": "true" }
+            }
+            for badge in badges {
+                span {
+                    key: "{badge.corner().class()}",
+                    class: "gallery-badge {badge.corner().class()}",
+                    title: "{badge.description()}",
+                    "aria-label": "{badge.description()}",
+                    "{badge.label()}"
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::file_system::{ExifMetadata, FilePermissions, PreviewMetadata};
+    use std::path::PathBuf;
+    use std::time::SystemTime;
+
+    fn photo(name: &str, file_type: FileType, rating: Option<u8>, gps: bool) -> FileEntry {
+        let exif = ExifMetadata {
+            camera_make: None,
+            camera_model: None,
+            lens_model: None,
+            focal_length: None,
+            aperture: None,
+            shutter_speed: None,
+            iso: None,
+            flash: None,
+            date_taken: None,
+            gps_latitude: gps.then_some(48.1),
+            gps_longitude: gps.then_some(11.6),
+            orientation: None,
+            rating,
+        };
+        FileEntry {
+            path: PathBuf::from(name),
+            name: name.to_string(),
+            file_type,
+            size: 1024,
+            modified: SystemTime::now(),
+            created: SystemTime::now(),
+            is_directory: false,
+            is_hidden: false,
+            permissions: FilePermissions::default(),
+            preview_metadata: Some(PreviewMetadata {
+                exif_data: Some(exif),
+                ..PreviewMetadata::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn test_thumbnail_badges_by_metadata_and_size() {
+        let rated = photo("beach.jpg", FileType::Image(ImageFormat::Jpeg), Some(4), true);
+        let badges = thumbnail_badges(&rated, ThumbnailSize::Medium);
+        assert_eq!(
+            badges,
+            vec![ThumbnailBadge::Format("JPEG"), ThumbnailBadge::Rating(4), ThumbnailBadge::Gps]
+        );
+        let mut corners: Vec<_> = badges.iter().map(ThumbnailBadge::corner).collect();
+        corners.dedup();
+        assert_eq!(corners.len(), badges.len(), "every badge gets its own corner");
+
+        assert!(thumbnail_badges(&rated, ThumbnailSize::Small).is_empty());
+
+        let raw = photo("IMG_0001.CR2", FileType::Other("cr2".to_string()), Some(0), false);
+        assert_eq!(thumbnail_badges(&raw, ThumbnailSize::Large), vec![ThumbnailBadge::Format("RAW")]);
+
+        let text = photo("notes.txt", FileType::Other("txt".to_string()), None, false);
+        assert!(thumbnail_badges(&text, ThumbnailSize::Large).is_empty());
+    }
+}
@@ -0,0 +1,762 @@
+// Gallery view
+// Thumbnail grid of the current folder that only renders the rows scrolled into view;
+// sorted by date, the tiles are grouped by month under headers that stick to the top
+
+use chrono::{DateTime, Local};
+use dioxus::events::Key;
+use dioxus::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+
+use crate::services::file_system::FileEntry;
+use crate::services::preview::thumbnail_service::{ThumbnailService, FALLBACK_THUMBNAIL_FORMAT};
+use crate::services::preview::SupportedFormat;
+use crate::state::persistence::save_settings_debounced;
+use crate::state::{use_app_state, FileSort, SettingsState, SortKey, ThumbnailSize};
+use crate::ui::components::drag_drop::{
+    drag_files_for, drop_onto_folder, selected_entries, track_drag_position, update_drag_operation, DragOperation,
+    DragState, DropZoneState,
+};
+use crate::ui::components::virtual_scroll::VariableVirtualScroll;
+use crate::ui::components::GalleryThumbnail;
+use crate::ui::click_intent::{Click, ClickIntent, ClickTracker};
+use crate::ui::focus;
+use crate::ui::shortcuts::ShortcutAction;
+use crate::ui::use_shortcut_handler;
+
+/// Space between tiles and around the grid, in CSS pixels
+pub const GALLERY_GAP: f64 = 8.0;
+
+/// Height of the file name under each tile
+pub const GALLERY_CAPTION_HEIGHT: f64 = 20.0;
+
+/// Height of the month header starting each group of tiles
+pub const GALLERY_HEADER_HEIGHT: f64 = 32.0;
+
+/// Rows rendered past either edge of the viewport
+const GALLERY_BUFFER_ROWS: usize = 2;
+
+/// How tiles of one size are arranged in a container of a given width
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridLayout {
+    /// Tiles per row, at least one
+    pub columns: usize,
+    /// Height of one row: tile, caption and the gap below
+    pub row_height: f64,
+}
+
+impl GridLayout {
+    /// Fit as many `tile_size` tiles into `container_width` as the gaps allow
+    pub fn new(container_width: f64, tile_size: f64) -> Self {
+        let columns = ((container_width - GALLERY_GAP) / (tile_size + GALLERY_GAP)).floor();
+        let columns = if columns.is_finite() && columns >= 1.0 { columns as usize } else { 1 };
+        Self {
+            columns,
+            row_height: tile_size + GALLERY_CAPTION_HEIGHT + GALLERY_GAP,
+        }
+    }
+
+    pub fn row_count(&self, item_count: usize) -> usize {
+        item_count.div_ceil(self.columns)
+    }
+
+    pub fn row_of(&self, index: usize) -> usize {
+        index / self.columns
+    }
+
+    /// Height of the whole grid, so the scrollbar matches even when only some rows render
+    pub fn total_height(&self, item_count: usize) -> f64 {
+        self.row_count(item_count) as f64 * self.row_height + GALLERY_GAP
+    }
+
+    /// Items laid out in `rows`, clamped to `item_count`
+    pub fn item_range(&self, rows: Range<usize>, item_count: usize) -> Range<usize> {
+        let start = (rows.start * self.columns).min(item_count);
+        let end = (rows.end * self.columns).min(item_count);
+        start..end
+    }
+
+    /// Tile the arrow, Home and End keys move to from `current`
+    ///
+    /// Up and Down stay in the same column; Down from the second-to-last row
+    /// lands on the last tile when that row is shorter.
+    pub fn next_index(&self, key: &Key, current: Option<usize>, item_count: usize) -> Option<usize> {
+        if item_count == 0 {
+            return None;
+        }
+        let last = item_count - 1;
+        let Some(current) = current else {
+            return matches!(key, Key::ArrowLeft | Key::ArrowRight | Key::ArrowUp | Key::ArrowDown | Key::Home | Key::End)
+                .then_some(0);
+        };
+        match key {
+            Key::ArrowRight => Some((current + 1).min(last)),
+            Key::ArrowLeft => Some(current.saturating_sub(1)),
+            Key::ArrowDown if self.row_of(current) < self.row_of(last) => Some((current + self.columns).min(last)),
+            Key::ArrowDown => Some(current),
+            Key::ArrowUp => Some(current.checked_sub(self.columns).unwrap_or(current)),
+            Key::Home => Some(0),
+            Key::End => Some(last),
+            _ => None,
+        }
+    }
+}
+
+/// One row of the gallery: a group's header or a run of tiles
+#[derive(Debug, Clone, PartialEq)]
+pub enum GalleryRow {
+    Header(String),
+    Tiles(Range<usize>),
+}
+
+/// Group an entry is shown under: its month when sorted by date, with
+/// folders on their own when they are listed first
+fn group_of(entry: &FileEntry, sort: &FileSort) -> Option<String> {
+    let time = match sort.key {
+        SortKey::Modified => entry.modified,
+        SortKey::Created => entry.created,
+        _ => return None,
+    };
+    if sort.directories_first && entry.is_directory {
+        return Some(t!("gallery.folders"));
+    }
+    Some(DateTime::<Local>::from(time).format("%B %Y").to_string())
+}
+
+/// Rows for `entries` laid out `columns` wide; each group starts on a new row under its header
+pub fn gallery_rows(entries: &[FileEntry], sort: &FileSort, columns: usize) -> Vec<GalleryRow> {
+    let columns = columns.max(1);
+    let mut rows = Vec::new();
+    let mut start = 0;
+    while start < entries.len() {
+        let group = group_of(&entries[start], sort);
+        let end = entries[start..]
+            .iter()
+            .position(|entry| group_of(entry, sort) != group)
+            .map_or(entries.len(), |len| start + len);
+        if let Some(label) = group {
+            rows.push(GalleryRow::Header(label));
+        }
+        for row_start in (start..end).step_by(columns) {
+            rows.push(GalleryRow::Tiles(row_start..(row_start + columns).min(end)));
+        }
+        start = end;
+    }
+    rows
+}
+
+/// Virtual scrolling over the gallery rows, with the headers pinned
+fn gallery_scroll(layout: &GridLayout, rows: &[GalleryRow], viewport_height: f64) -> VariableVirtualScroll {
+    let headers = rows
+        .iter()
+        .enumerate()
+        .filter(|(_, row)| matches!(row, GalleryRow::Header(_)))
+        .map(|(index, _)| index)
+        .collect();
+    let row_height = |index: usize| match rows[index] {
+        GalleryRow::Header(_) => GALLERY_HEADER_HEIGHT,
+        GalleryRow::Tiles(_) => layout.row_height,
+    };
+    VariableVirtualScroll::new(rows.len(), row_height, viewport_height, GALLERY_BUFFER_ROWS).with_group_headers(headers)
+}
+
+/// Row holding tile `index`
+fn row_of_tile(rows: &[GalleryRow], index: usize) -> Option<usize> {
+    rows.iter().position(|row| matches!(row, GalleryRow::Tiles(range) if range.contains(&index)))
+}
+
+/// Tiles laid out in `visible` rows
+fn visible_items(rows: &[GalleryRow], visible: Range<usize>) -> Range<usize> {
+    let mut tiles = rows[visible].iter().filter_map(|row| match row {
+        GalleryRow::Tiles(range) => Some(range.clone()),
+        GalleryRow::Header(_) => None,
+    });
+    let Some(first) = tiles.next() else {
+        return 0..0;
+    };
+    let end = tiles.last().map_or(first.end, |last| last.end);
+    first.start..end
+}
+
+/// Tile a key moves to from `current`; Up and Down follow the rows, so they
+/// keep the column across group boundaries
+fn next_tile(rows: &[GalleryRow], layout: &GridLayout, key: &Key, current: Option<usize>, item_count: usize) -> Option<usize> {
+    let (Some(current), Key::ArrowUp | Key::ArrowDown) = (current, key) else {
+        return layout.next_index(key, current, item_count);
+    };
+    let tile_rows: Vec<&Range<usize>> = rows
+        .iter()
+        .filter_map(|row| match row {
+            GalleryRow::Tiles(range) => Some(range),
+            GalleryRow::Header(_) => None,
+        })
+        .collect();
+    let Some(position) = tile_rows.iter().position(|range| range.contains(&current)) else {
+        return Some(current);
+    };
+    let column = current - tile_rows[position].start;
+    let target = if *key == Key::ArrowUp { position.checked_sub(1) } else { Some(position + 1) };
+    match target.and_then(|target| tile_rows.get(target)) {
+        Some(range) => Some((range.start + column).min(range.end - 1)),
+        None => Some(current),
+    }
+}
+
+/// Size and scroll position of the gallery's scrolling element
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct GalleryViewport {
+    width: f64,
+    height: f64,
+    scroll_top: f64,
+}
+
+/// DOM id of the scrolling element, used to scroll tiles into view
+const GALLERY_ID: &str = "gallery-view";
+
+/// DOM id of the tile at `index`
+pub(crate) fn gallery_item_id(index: usize) -> String {
+    format!("gallery-item-{}", index)
+}
+
+/// Measure the scrolling element after it mounted, scrolled or resized
+fn measure(container: Option<Rc<MountedData>>, mut viewport: Signal<GalleryViewport>) {
+    let Some(container) = container else { return };
+    spawn(async move {
+        let (Ok(offset), Ok(rect)) = (container.get_scroll_offset().await, container.get_client_rect().await) else {
+            return;
+        };
+        let measured = GalleryViewport {
+            width: rect.size.width,
+            height: rect.size.height,
+            scroll_top: offset.y,
+        };
+        if *viewport.peek() != measured {
+            viewport.set(measured);
+        }
+    });
+}
+
+/// Whether the gallery asks the thumbnail service for this file
+fn is_thumbnail_candidate(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(SupportedFormat::from_extension)
+        .is_some_and(|format| format.is_image())
+}
+
+/// Load the thumbnails of `paths` off the UI thread
+async fn load_thumbnails(
+    thumbnail_service: Arc<ThumbnailService>,
+    paths: Vec<PathBuf>,
+    mut loaded: Signal<HashMap<PathBuf, String>>,
+) {
+    let jobs: Vec<_> = paths
+        .into_iter()
+        .map(|path| {
+            let service = thumbnail_service.clone();
+            let job_path = path.clone();
+            (path, tokio::spawn(async move { service.get_thumbnail(&job_path).await }))
+        })
+        .collect();
+    for (path, job) in jobs {
+        match job.await {
+            Ok(Ok(thumbnail)) if thumbnail.format != FALLBACK_THUMBNAIL_FORMAT => {
+                loaded.write().insert(path, thumbnail.to_data_url());
+            }
+            // Unreadable images keep the placeholder
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) => tracing::debug!("No thumbnail for {:?}: {}", path, e),
+            Err(e) => tracing::warn!("Thumbnail task for {:?} failed: {}", path, e),
+        }
+    }
+}
+
+/// Thumbnail grid of the current folder
+///
+/// Selection, the context menu and dragging behave as in the file list. Only
+/// the rows near the viewport are rendered and only their thumbnails loaded.
+#[component]
+pub fn GalleryView(
+    mut settings: Signal<SettingsState>,
+    mut drag_state: Signal<DragState>,
+    /// Called with the pointer position and the tile's entry on right-click
+    on_context_menu: EventHandler<(f64, f64, FileEntry)>,
+) -> Element {
+    let app_state = use_app_state();
+    let shortcut_handler = use_shortcut_handler();
+    let mut container = use_signal(|| None::<Rc<MountedData>>);
+    let viewport = use_signal(GalleryViewport::default);
+    let loaded = use_signal(HashMap::<PathBuf, String>::new);
+    let mut requested = use_signal(HashSet::<PathBuf>::new);
+    let mut active_index = use_signal(|| None::<usize>);
+    let mut click_tracker = use_signal(ClickTracker::default);
+    // Folder tile under a drag, with whether it would take the dragged files
+    let mut folder_drop_target = use_signal(|| None::<(PathBuf, DropZoneState)>);
+
+    // Request thumbnails for the rows in view whenever they or the listing change
+    use_effect({
+        let app_state = app_state.clone();
+        move || {
+            let entries = app_state.sorted_file_entries();
+            let sort = app_state.file_tree_state.read().sort;
+            let tile_size = settings.read().thumbnail_size.pixels() as f64;
+            let layout = GridLayout::new(viewport().width, tile_size);
+            let rows = gallery_rows(&entries, &sort, layout.columns);
+            let range = gallery_scroll(&layout, &rows, viewport().height).calculate_visible_range(viewport().scroll_top);
+            let wanted: Vec<PathBuf> = entries[visible_items(&rows, range.start_index..range.end_index)]
+                .iter()
+                .map(|entry| entry.path.clone())
+                .filter(|path| is_thumbnail_candidate(path) && !requested.peek().contains(path))
+                .collect();
+            if !wanted.is_empty() {
+                requested.write().extend(wanted.iter().cloned());
+                spawn(load_thumbnails(app_state.thumbnail_service.clone(), wanted, loaded));
+            }
+        }
+    });
+
+    // Warm the cache with the rest of the folder as throttled background work
+    let mut prefetch = use_signal(|| None::<CancellationToken>);
+    use_drop(move || {
+        if let Some(token) = prefetch.peek().as_ref() {
+            token.cancel();
+        }
+    });
+    use_effect({
+        let app_state = app_state.clone();
+        move || {
+            let paths: Vec<PathBuf> = app_state
+                .sorted_file_entries()
+                .iter()
+                .map(|entry| entry.path.clone())
+                .filter(|path| is_thumbnail_candidate(path))
+                .collect();
+            if let Some(previous) = prefetch.peek().as_ref() {
+                previous.cancel();
+            }
+            let token = CancellationToken::new();
+            prefetch.set(Some(token.clone()));
+            let service = app_state.thumbnail_service.clone();
+            tokio::spawn(async move { service.prefetch_thumbnails(&paths, &token).await });
+        }
+    });
+
+    let entries = app_state.sorted_file_entries();
+    let item_count = entries.len();
+    let thumbnail_size = settings.read().thumbnail_size;
+    let edge = thumbnail_size.pixels();
+    let layout = GridLayout::new(viewport().width, edge as f64);
+    let sort = app_state.file_tree_state.read().sort;
+    let rows = Rc::new(gallery_rows(&entries, &sort, layout.columns));
+    let scroll = Rc::new(gallery_scroll(&layout, &rows, viewport().height));
+    let range = scroll.calculate_visible_range(viewport().scroll_top);
+    let offset_top = range.offset_top;
+    let total_height = range.total_height + GALLERY_GAP;
+    let sticky = scroll.sticky_header(viewport().scroll_top).and_then(|header| match &rows[header.index] {
+        GalleryRow::Header(label) => Some((label.clone(), header.offset)),
+        GalleryRow::Tiles(_) => None,
+    });
+    let active = active_index().filter(|&index| index < item_count);
+    let visible: Vec<(usize, GalleryRow)> =
+        (range.start_index..range.end_index).map(|index| (index, rows[index].clone())).collect();
+
+    rsx! {
+        div { class: "gallery-view-container",
+            div { class: "gallery-toolbar",
+                span { class: "gallery-toolbar-label", {t!("gallery.thumbnail_size")} }
+                div { class: "gallery-size-toggle", role: "radiogroup", "aria-label": t!("gallery.thumbnail_size"),
+                    for size in ThumbnailSize::ALL {
+                        button {
+                            key: "{size.as_str()}",
+                            class: if size == thumbnail_size { "gallery-size-option active" } else { "gallery-size-option" },
+                            role: "radio",
+                            "aria-checked": "{size == thumbnail_size}",
+                            onclick: move |_| {
+                                settings.write().thumbnail_size = size;
+                                save_settings_debounced(settings.peek().clone());
+                            },
+                            "{size.display_name()}"
+                        }
+                    }
+                }
+            }
+
+            if item_count == 0 {
+                div { class: "gallery-empty", {t!("gallery.empty")} }
+            }
+
+            div {
+                id: GALLERY_ID,
+                class: "gallery-view",
+                role: "listbox",
+                "aria-label": t!("gallery.label", count = item_count),
+                "aria-multiselectable": "true",
+                onmounted: move |evt: Event<MountedData>| {
+                    let mounted = evt.data();
+                    container.set(Some(mounted.clone()));
+                    measure(Some(mounted), viewport);
+                },
+                onscroll: move |_| measure(container.peek().clone(), viewport),
+                onresize: move |_| measure(container.peek().clone(), viewport),
+
+                // Header of the group at the top, pushed up by the next one
+                if let Some((label, offset)) = sticky {
+                    div { class: "gallery-sticky-header", "aria-hidden": "true",
+                        div { class: "gallery-group-header", style: "transform: translateY({offset}px);", "{label}" }
+                    }
+                }
+
+                div {
+                    class: "gallery-canvas",
+                    style: "height: {total_height}px;",
+                    div {
+                        class: "gallery-rows",
+                        style: "transform: translateY({offset_top}px);",
+                        for (row_index, row) in visible {
+                            match row {
+                                GalleryRow::Header(label) => rsx! {
+                                    div {
+                                        key: "header-{row_index}",
+                                        class: "gallery-group-header",
+                                        role: "presentation",
+                                        style: "height: {GALLERY_HEADER_HEIGHT}px;",
+                                        "{label}"
+                                    }
+                                },
+                                GalleryRow::Tiles(tile_range) => rsx! {
+                                    div {
+                                        key: "row-{tile_range.start}",
+                                        class: "gallery-grid",
+                                        role: "presentation",
+                                        style: "height: {layout.row_height}px; grid-template-columns: repeat({layout.columns}, {edge}px); gap: {GALLERY_GAP}px; padding: {GALLERY_GAP}px {GALLERY_GAP}px 0;",
+                                        for (index, entry) in tile_range.clone().zip(entries[tile_range.clone()].iter().cloned()) {
+                                            {
+                                                let is_selected = app_state.is_selected(&entry.path);
+                                                let drop_state = folder_drop_target.read().as_ref()
+                                                    .filter(|(path, _)| *path == entry.path)
+                                                    .map(|(_, state)| state.style_class());
+                                                let mut tile_class = if is_selected { "gallery-item selected".to_string() } else { "gallery-item".to_string() };
+                                                if let Some(class) = drop_state {
+                                                    tile_class = format!("{} {}", tile_class, class);
+                                                }
+                                                let thumbnail = loaded.read().get(&entry.path).cloned();
+                                                let mut app_state_click = app_state.clone();
+                                                let app_state_key = app_state.clone();
+                                                let app_state_drag = app_state.clone();
+                                                let app_state_drop = app_state.clone();
+                                                let handler_key = shortcut_handler.clone();
+                                                let rows_key = rows.clone();
+                                                let scroll_key = scroll.clone();
+                                                let entry_click = entry.clone();
+                                                let entry_key = entry.clone();
+                                                let entry_menu = entry.clone();
+                                                let entry_drag = entry.clone();
+                                                let target = entry.path.clone();
+                                                let target_leave = entry.path.clone();
+                                                let target_drop = entry.path.clone();
+                                                let is_directory = entry.is_directory;
+                                                let open = move |mut app_state: crate::state::AppState, mut handler: crate::ui::shortcut_handler::ShortcutHandler, entry: FileEntry| {
+                                                    app_state.select_single(entry.path.clone());
+                                                    // Folders open in place; files open in Quick Look
+                                                    let action = if entry.is_directory { ShortcutAction::OpenFile } else { ShortcutAction::ToggleSpace };
+                                                    spawn(async move { handler.execute_action(action).await });
+                                                };
+
+                                                rsx! {
+                                                    div {
+                                                        key: "{entry.path.display()}",
+                                                        id: gallery_item_id(index),
+                                                        class: "{tile_class}",
+                                                        role: "option",
+                                                        "aria-selected": "{is_selected}",
+                                                        "aria-label": "{entry.name}",
+                                                        tabindex: focus::roving_tabindex(index, active),
+                                                        draggable: true,
+
+                                                        onclick: move |evt| {
+                                                            active_index.set(Some(index));
+                                                            let modifiers = evt.modifiers();
+                                                            let click = Click {
+                                                                path: &entry_click.path,
+                                                                is_directory: entry_click.is_directory,
+                                                                with_modifiers: modifiers.shift() || modifiers.ctrl() || modifiers.meta(),
+                                                                at: std::time::Instant::now(),
+                                                            };
+                                                            let double_click = app_state_click.settings.read().double_click;
+                                                            if let ClickIntent::Activate(action) = click_tracker.write().click(click, &double_click) {
+                                                                let mut app_state = app_state_click.clone();
+                                                                let path = entry_click.path.clone();
+                                                                spawn(async move {
+                                                                    if let Err(e) = app_state.activate_entry(path.clone(), action).await {
+                                                                        tracing::error!("Failed to open {:?}: {}", path, e);
+                                                                    }
+                                                                });
+                                                                return;
+                                                            }
+                                                            if modifiers.shift() {
+                                                                app_state_click.select_range_to(entry_click.path.clone());
+                                                            } else if modifiers.ctrl() || modifiers.meta() {
+                                                                app_state_click.toggle_selection(entry_click.path.clone());
+                                                            } else {
+                                                                app_state_click.select_single(entry_click.path.clone());
+                                                            }
+                                                            app_state_click.set_file_tree_selection(Some(entry_click.path.clone()));
+                                                        },
+
+                                                        onkeydown: move |evt| {
+                                                            let key = evt.data.key();
+                                                            if let Some(next) = next_tile(&rows_key, &layout, &key, Some(index), item_count) {
+                                                                evt.prevent_default();
+                                                                active_index.set(Some(next));
+                                                                scroll_tile_into_view(&rows_key, &scroll_key, next);
+                                                                return;
+                                                            }
+                                                            if key == Key::Enter {
+                                                                evt.prevent_default();
+                                                                open(app_state_key.clone(), handler_key.clone(), entry_key.clone());
+                                                            }
+                                                        },
+
+                                                        oncontextmenu: move |evt| {
+                                                            evt.prevent_default();
+                                                            let coordinates = evt.data.client_coordinates();
+                                                            on_context_menu.call((coordinates.x, coordinates.y, entry_menu.clone()));
+                                                        },
+
+                                                        ondragstart: move |evt| {
+                                                            let coordinates = evt.data.client_coordinates();
+                                                            let operation = DragOperation::for_folder_drop(
+                                                                evt.data.modifiers().ctrl(),
+                                                                evt.data.modifiers().alt()
+                                                            );
+                                                            // Dragging a selected file takes the rest of the selection along
+                                                            let selected = selected_entries(
+                                                                &app_state_drag.selection.read(),
+                                                                &app_state_drag.file_entries.read(),
+                                                            );
+                                                            drag_state.write().start_drag(
+                                                                drag_files_for(&entry_drag, selected),
+                                                                coordinates.x,
+                                                                coordinates.y,
+                                                                operation
+                                                            );
+                                                        },
+
+                                                        ondrag: move |evt| {
+                                                            let coordinates = evt.data.client_coordinates();
+                                                            track_drag_position(drag_state, coordinates.x, coordinates.y);
+                                                        },
+
+                                                        ondragend: move |_| {
+                                                            folder_drop_target.set(None);
+                                                            drag_state.write().end_drag();
+                                                        },
+
+                                                        // Folders take the dragged files; Alt or Ctrl copies instead of moving
+                                                        ondragover: move |evt: DragEvent| {
+                                                            if !is_directory || !drag_state.peek().is_dragging {
+                                                                return;
+                                                            }
+                                                            evt.prevent_default();
+                                                            evt.stop_propagation();
+                                                            let operation = DragOperation::for_folder_drop(
+                                                                evt.data.modifiers().ctrl(),
+                                                                evt.data.modifiers().alt()
+                                                            );
+                                                            update_drag_operation(drag_state, operation);
+                                                            let state = DropZoneState::for_target(&drag_state.peek().drag_files, &target);
+                                                            let current = folder_drop_target.peek().clone();
+                                                            if current.as_ref() != Some(&(target.clone(), state.clone())) {
+                                                                folder_drop_target.set(Some((target.clone(), state)));
+                                                            }
+                                                        },
+
+                                                        ondragleave: move |_| {
+                                                            let is_target = matches!(folder_drop_target.peek().as_ref(), Some((path, _)) if *path == target_leave);
+                                                            if is_target {
+                                                                folder_drop_target.set(None);
+                                                            }
+                                                        },
+
+                                                        ondrop: move |evt: DragEvent| {
+                                                            if !is_directory {
+                                                                return;
+                                                            }
+                                                            evt.prevent_default();
+                                                            evt.stop_propagation();
+                                                            folder_drop_target.set(None);
+                                                            let (files, operation) = {
+                                                                let drag = drag_state.peek();
+                                                                (drag.drag_files.clone(), drag.operation.clone())
+                                                            };
+                                                            drag_state.write().end_drag();
+                                                            if DropZoneState::for_target(&files, &target_drop) == DropZoneState::DragOverValid {
+                                                                spawn(drop_onto_folder(app_state_drop.clone(), files, operation, target_drop.clone()));
+                                                            }
+                                                        },
+
+                                                        GalleryThumbnail { entry: entry.clone(), thumbnail, settings }
+                                                        span { class: "gallery-item-name", "{entry.name}" }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                },
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Scroll the gallery so tile `index` is rendered below any sticky header, then focus it
+fn scroll_tile_into_view(rows: &[GalleryRow], scroll: &VariableVirtualScroll, index: usize) {
+    let Some(row) = row_of_tile(rows, index) else {
+        return;
+    };
+    let grouped = rows.iter().any(|row| matches!(row, GalleryRow::Header(_)));
+    let header = if grouped { GALLERY_HEADER_HEIGHT } else { 0.0 };
+    let row_top = scroll.rows().offset(row) - header;
+    let row_bottom = scroll.rows().offset(row) + scroll.rows().height(row);
+    let script = format!(
+        "const gallery = document.getElementById({gallery}); \
+         if (gallery) {{ \
+           if ({top} < gallery.scrollTop) gallery.scrollTop = {top}; \
+           else if ({bottom} > gallery.scrollTop + gallery.clientHeight) gallery.scrollTop = {bottom} - gallery.clientHeight; \
+         }} \
+         requestAnimationFrame(() => requestAnimationFrame(() => document.getElementById({tile})?.focus()));",
+        gallery = serde_json::to_string(GALLERY_ID).unwrap_or_default(),
+        tile = serde_json::to_string(&gallery_item_id(index)).unwrap_or_default(),
+        top = row_top,
+        bottom = row_bottom,
+    );
+    let _ = document::eval(&script);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::file_system::{FilePermissions, FileType, ImageFormat};
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_columns_fit_the_container_width() {
+        // 8px gap on the left of every tile plus one on the right edge
+        assert_eq!(GridLayout::new(8.0 + 4.0 * 136.0, 128.0).columns, 4);
+        assert_eq!(GridLayout::new(8.0 + 4.0 * 136.0 - 1.0, 128.0).columns, 3);
+        assert_eq!(GridLayout::new(800.0, 64.0).columns, 11);
+        assert_eq!(GridLayout::new(800.0, 192.0).columns, 3);
+        // Never fewer than one column, even before the container is measured
+        assert_eq!(GridLayout::new(100.0, 192.0).columns, 1);
+        assert_eq!(GridLayout::new(0.0, 128.0).columns, 1);
+        assert_eq!(GridLayout::new(f64::NAN, 128.0).columns, 1);
+    }
+
+    #[test]
+    fn test_rows_and_item_ranges() {
+        let layout = GridLayout::new(552.0, 128.0);
+        assert_eq!(layout.columns, 4);
+        assert_eq!(layout.row_height, 128.0 + GALLERY_CAPTION_HEIGHT + GALLERY_GAP);
+
+        assert_eq!(layout.row_count(0), 0);
+        assert_eq!(layout.row_count(4), 1);
+        assert_eq!(layout.row_count(5), 2);
+        assert_eq!(layout.row_of(3), 0);
+        assert_eq!(layout.row_of(4), 1);
+        assert_eq!(layout.total_height(5), 2.0 * layout.row_height + GALLERY_GAP);
+
+        assert_eq!(layout.item_range(0..2, 10), 0..8);
+        assert_eq!(layout.item_range(2..5, 10), 8..10);
+        assert_eq!(layout.item_range(3..5, 10), 10..10);
+    }
+
+    #[test]
+    fn test_arrow_keys_move_through_the_grid() {
+        let layout = GridLayout::new(552.0, 128.0);
+        // 10 tiles in rows of 4, 4 and 2
+        assert_eq!(layout.next_index(&Key::ArrowRight, Some(3), 10), Some(4));
+        assert_eq!(layout.next_index(&Key::ArrowLeft, Some(0), 10), Some(0));
+        assert_eq!(layout.next_index(&Key::ArrowDown, Some(1), 10), Some(5));
+        assert_eq!(layout.next_index(&Key::ArrowDown, Some(7), 10), Some(9));
+        assert_eq!(layout.next_index(&Key::ArrowDown, Some(9), 10), Some(9));
+        assert_eq!(layout.next_index(&Key::ArrowUp, Some(9), 10), Some(5));
+        assert_eq!(layout.next_index(&Key::ArrowUp, Some(2), 10), Some(2));
+        assert_eq!(layout.next_index(&Key::End, Some(2), 10), Some(9));
+        assert_eq!(layout.next_index(&Key::ArrowDown, None, 10), Some(0));
+        assert_eq!(layout.next_index(&Key::Enter, Some(2), 10), None);
+        assert_eq!(layout.next_index(&Key::ArrowDown, None, 0), None);
+    }
+
+    fn photo(name: &str, year: i32, month: u32) -> FileEntry {
+        let time = Local.with_ymd_and_hms(year, month, 15, 12, 0, 0).unwrap().into();
+        FileEntry {
+            name: name.to_string(),
+            path: PathBuf::from(format!("/photos/{name}")),
+            file_type: FileType::Image(ImageFormat::Jpeg),
+            size: 10,
+            modified: time,
+            created: time,
+            is_directory: false,
+            is_hidden: false,
+            permissions: FilePermissions::read_write(),
+            preview_metadata: None,
+        }
+    }
+
+    fn by_date() -> FileSort {
+        FileSort { key: SortKey::Modified, directories_first: false, ..FileSort::default() }
+    }
+
+    #[test]
+    fn test_gallery_rows_start_each_month_under_its_header() {
+        let entries = vec![
+            photo("a.jpg", 2024, 5),
+            photo("b.jpg", 2024, 5),
+            photo("c.jpg", 2024, 5),
+            photo("d.jpg", 2024, 3),
+        ];
+        assert_eq!(
+            gallery_rows(&entries, &by_date(), 2),
+            vec![
+                GalleryRow::Header("May 2024".to_string()),
+                GalleryRow::Tiles(0..2),
+                GalleryRow::Tiles(2..3),
+                GalleryRow::Header("March 2024".to_string()),
+                GalleryRow::Tiles(3..4),
+            ]
+        );
+
+        // Sorted by name there are no groups
+        let by_name = FileSort { key: SortKey::Name, ..by_date() };
+        assert_eq!(gallery_rows(&entries, &by_name, 3), vec![GalleryRow::Tiles(0..3), GalleryRow::Tiles(3..4)]);
+        assert!(gallery_rows(&[], &by_date(), 3).is_empty());
+    }
+
+    #[test]
+    fn test_arrow_keys_follow_rows_across_groups() {
+        let layout = GridLayout::new(552.0, 128.0);
+        let rows = vec![
+            GalleryRow::Header("May 2024".to_string()),
+            GalleryRow::Tiles(0..4),
+            GalleryRow::Tiles(4..5),
+            GalleryRow::Header("March 2024".to_string()),
+            GalleryRow::Tiles(5..9),
+        ];
+        assert_eq!(next_tile(&rows, &layout, &Key::ArrowDown, Some(2), 9), Some(4));
+        assert_eq!(next_tile(&rows, &layout, &Key::ArrowDown, Some(4), 9), Some(5));
+        assert_eq!(next_tile(&rows, &layout, &Key::ArrowUp, Some(7), 9), Some(4));
+        assert_eq!(next_tile(&rows, &layout, &Key::ArrowUp, Some(1), 9), Some(1));
+        assert_eq!(next_tile(&rows, &layout, &Key::ArrowDown, Some(8), 9), Some(8));
+        assert_eq!(next_tile(&rows, &layout, &Key::ArrowRight, Some(4), 9), Some(5));
+        assert_eq!(row_of_tile(&rows, 5), Some(4));
+        assert_eq!(visible_items(&rows, 0..3), 0..5);
+        assert_eq!(visible_items(&rows, 3..4), 0..0);
+    }
+}
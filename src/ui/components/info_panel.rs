@@ -1,14 +1,36 @@
 use dioxus::prelude::*;
-use crate::services::file_system::FileEntry;
-use crate::utils::{FileTypeDetectionUtil, FilePreviewSupport, InfoCategory};
+use crate::services::file_system::{compute_directory_size, FileEntry};
+use crate::services::operations::CancellationToken;
+use crate::services::selection_summary::{folder_sizes, summarize_selection, SelectionSummary};
+use crate::state::{use_app_state, AppState};
+use crate::ui::components::drag_drop::selected_entries;
+use crate::utils::{format_date_time, format_relative_time, format_size, FileTypeDetectionUtil, FilePreviewSupport, InfoCategory};
 use std::path::PathBuf;
 
 /// Info Panel component for displaying file metadata and properties
-/// Used when files don't support direct preview or are better shown as info
+/// Used when files don't support direct preview or are better shown as info,
+/// and for the totals of a multi-selection
 #[component]
 pub fn InfoPanel(
     selected_file: Signal<Option<FileEntry>>,
 ) -> Element {
+    let app_state = use_app_state();
+    let selection = app_state.selection;
+    let file_entries = app_state.file_entries;
+    let selected = use_memo(move || selected_entries(&selection.read(), &file_entries.read()));
+    
+    // Folder contents are sized in the background; None until they are
+    let folder_size = use_resource(move || {
+        let entries = selected.read();
+        let folders: Vec<PathBuf> = if entries.len() > 1 {
+            entries.iter().filter(|entry| entry.is_directory).map(|entry| entry.path.clone()).collect()
+        } else {
+            Vec::new()
+        };
+        async move { folder_sizes(folders).await }
+    });
+    let folders_size = *folder_size.read();
+    
     rsx! {
         div {
             class: "info-panel",
@@ -25,8 +47,17 @@ pub fn InfoPanel(
                 padding: 16px;
             ",
             
-            if let Some(file_entry) = selected_file.read().as_ref() {
-                InfoPanelContent { file_entry: file_entry.clone() }
+            if selected.read().len() > 1 {
+                InfoPanelSelection {
+                    summary: summarize_selection(&selected.read()),
+                    folders_size,
+                }
+            } else if let Some(file_entry) = selected_file.read().as_ref() {
+                InfoPanelContent {
+                    key: "{file_entry.path.display()}",
+                    file_entry: file_entry.clone(),
+                    selected_file,
+                }
             } else {
                 div {
                     class: "info-panel-empty",
@@ -47,11 +78,42 @@ pub fn InfoPanel(
 
 /// Content component for displaying file information
 #[component]
-fn InfoPanelContent(file_entry: FileEntry) -> Element {
+fn InfoPanelContent(file_entry: FileEntry, selected_file: Signal<Option<FileEntry>>) -> Element {
     let file_path = &file_entry.path;
     let support_info = FileTypeDetectionUtil::detect_preview_support(&file_entry.file_type, file_path);
     let panel_description = FileTypeDetectionUtil::get_panel_description(&support_info);
     
+    // Folders are sized in the background, or at once from the size cache;
+    // the panel is keyed by path, so the walk stops when another file is shown
+    let token = use_hook(CancellationToken::new);
+    use_drop({
+        let token = token.clone();
+        move || token.cancel()
+    });
+    let folder_size = use_resource({
+        let path = file_entry.path.clone();
+        let is_directory = file_entry.is_directory;
+        move || {
+            let path = path.clone();
+            let token = token.clone();
+            async move {
+                if !is_directory {
+                    return None;
+                }
+                compute_directory_size(&path, token).await.ok()
+            }
+        }
+    });
+    let size = if file_entry.is_directory {
+        match &*folder_size.read() {
+            Some(Some(bytes)) => format_size(*bytes),
+            Some(None) => "Unknown".to_string(),
+            None => "Calculating…".to_string(),
+        }
+    } else {
+        format_size(file_entry.size)
+    };
+    
     rsx! {
         div {
             class: "info-panel-content",
@@ -95,15 +157,10 @@ fn InfoPanelContent(file_entry: FileEntry) -> Element {
                     class: "file-header-info",
                     style: "flex: 1;",
                     
-                    h2 {
-                        style: "
-                            margin: 0 0 4px 0;
-                            font-size: 18px;
-                            font-weight: 600;
-                            color: var(--vscode-text-primary);
-                            word-break: break-all;
-                        ",
-                        {file_entry.name.clone()}
+                    InfoPanelName {
+                        key: "{file_entry.path.display()}",
+                        file_entry: file_entry.clone(),
+                        selected_file,
                     }
                     
                     p {
@@ -133,7 +190,7 @@ fn InfoPanelContent(file_entry: FileEntry) -> Element {
                 InfoPropertyGrid {
                     properties: vec![
                         ("Type".to_string(), get_file_type_description(&file_entry.file_type)),
-                        ("Size".to_string(), format_file_size(file_entry.size)),
+                        ("Size".to_string(), size),
                         ("Location".to_string(), format_file_path(&file_entry.path)),
                         ("Modified".to_string(), format_timestamp(file_entry.modified)),
                         ("Created".to_string(), format_timestamp(file_entry.created)),
@@ -142,6 +199,8 @@ fn InfoPanelContent(file_entry: FileEntry) -> Element {
                 }
             }
             
+            InfoPanelTags { path: file_entry.path.clone() }
+            
             // Category-specific information
             {
                 match &support_info {
@@ -168,6 +227,381 @@ fn InfoPanelContent(file_entry: FileEntry) -> Element {
     }
 }
 
+/// Totals of a multi-selection
+///
+/// `folders_size` is the size of the selected folders' contents, None while
+/// it is still being added up.
+#[component]
+fn InfoPanelSelection(summary: SelectionSummary, folders_size: Option<u64>) -> Element {
+    let sizing = summary.has_folders() && folders_size.is_none();
+    let total_size = summary.files_size + folders_size.unwrap_or(0);
+    let size_text = if sizing {
+        format!("{} + folders", format_size(summary.files_size))
+    } else {
+        format_size(total_size)
+    };
+    let counts = format!(
+        "{} {}, {} {}",
+        summary.file_count,
+        if summary.file_count == 1 { "file" } else { "files" },
+        summary.folder_count,
+        if summary.folder_count == 1 { "folder" } else { "folders" },
+    );
+    let location = summary
+        .common_parent
+        .as_ref()
+        .map(|path| path.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Multiple locations".to_string());
+    let modified = match summary.modified_range {
+        Some((oldest, newest)) if oldest == newest => format_date_time(oldest),
+        Some((oldest, newest)) => format!("{} – {}", format_date_time(oldest), format_date_time(newest)),
+        None => "Unknown".to_string(),
+    };
+    let types: Vec<(String, String)> = summary
+        .type_breakdown
+        .iter()
+        .map(|(category, count)| (category.clone(), count.to_string()))
+        .collect();
+    
+    rsx! {
+        div {
+            class: "info-panel-content info-panel-selection",
+            style: "
+                display: flex;
+                flex-direction: column;
+                gap: 20px;
+                height: 100%;
+                overflow-y: auto;
+            ",
+            
+            div {
+                class: "info-header",
+                style: "
+                    display: flex;
+                    align-items: center;
+                    gap: 12px;
+                    padding-bottom: 16px;
+                    border-bottom: 1px solid var(--vscode-border);
+                ",
+                
+                div {
+                    class: "file-icon",
+                    style: "
+                        width: 48px;
+                        height: 48px;
+                        background-color: var(--vscode-secondary-background);
+                        border-radius: 8px;
+                        display: flex;
+                        align-items: center;
+                        justify-content: center;
+                        font-size: 20px;
+                        font-weight: 600;
+                        color: var(--vscode-accent);
+                    ",
+                    "🗂️"
+                }
+                
+                div {
+                    class: "file-header-info",
+                    style: "flex: 1;",
+                    h2 {
+                        style: "
+                            margin: 0 0 4px 0;
+                            font-size: 18px;
+                            font-weight: 600;
+                            color: var(--vscode-text-primary);
+                        ",
+                        "{summary.count} items selected"
+                    }
+                    p {
+                        style: "
+                            margin: 0;
+                            color: var(--vscode-text-secondary);
+                            font-size: 14px;
+                        ",
+                        "{counts}"
+                    }
+                }
+            }
+            
+            div {
+                class: "info-section",
+                h3 {
+                    style: "
+                        margin: 0 0 12px 0;
+                        font-size: 16px;
+                        font-weight: 600;
+                        color: var(--vscode-text-primary);
+                    ",
+                    "Selection Properties"
+                }
+                
+                div {
+                    class: "selection-size",
+                    style: "
+                        display: grid;
+                        grid-template-columns: 1fr 2fr;
+                        gap: 8px 16px;
+                        font-size: 14px;
+                        padding: 4px 0;
+                    ",
+                    span {
+                        style: "color: var(--vscode-text-secondary); font-weight: 500;",
+                        "Total Size"
+                    }
+                    span {
+                        style: "display: flex; align-items: center; gap: 8px; color: var(--vscode-text-primary);",
+                        "{size_text}"
+                        if sizing {
+                            span {
+                                class: "loading-spinner",
+                                role: "status",
+                                "aria-label": "Calculating folder sizes",
+                                style: "
+                                    width: 12px;
+                                    height: 12px;
+                                    border: 2px solid var(--vscode-border);
+                                    border-top: 2px solid var(--vscode-accent);
+                                    border-radius: 50%;
+                                ",
+                            }
+                        }
+                    }
+                }
+                
+                InfoPropertyGrid {
+                    properties: vec![
+                        ("Location".to_string(), location),
+                        ("Modified".to_string(), modified),
+                    ]
+                }
+            }
+            
+            div {
+                class: "info-section",
+                h3 {
+                    style: "
+                        margin: 0 0 12px 0;
+                        font-size: 16px;
+                        font-weight: 600;
+                        color: var(--vscode-text-primary);
+                    ",
+                    "Types"
+                }
+                InfoPropertyGrid { properties: types }
+            }
+        }
+    }
+}
+
+/// File name heading that turns into a text field for renaming
+///
+/// Enter renames through the undoable rename command; Escape or leaving the
+/// field keeps the old name.
+#[component]
+fn InfoPanelName(file_entry: FileEntry, selected_file: Signal<Option<FileEntry>>) -> Element {
+    let app_state = use_app_state();
+    let mut editing = use_signal(|| false);
+    let mut draft = use_signal(String::new);
+    let error = use_signal(|| None::<String>);
+    let name = file_entry.name.clone();
+    
+    rsx! {
+        if *editing.read() {
+            input {
+                r#type: "text",
+                class: "info-rename-input",
+                "aria-label": "New name",
+                value: "{draft}",
+                autofocus: true,
+                style: "
+                    width: 100%;
+                    margin: 0 0 4px 0;
+                    padding: 2px 4px;
+                    font-size: 16px;
+                    background-color: var(--vscode-input-background, var(--vscode-secondary-background));
+                    color: var(--vscode-text-primary);
+                    border: 1px solid var(--vscode-focus-border, var(--vscode-accent));
+                ",
+                oninput: move |evt| draft.set(evt.value()),
+                onkeydown: move |evt| match evt.data.key() {
+                    Key::Enter => {
+                        editing.set(false);
+                        let new_name = draft.read().trim().to_string();
+                        if !new_name.is_empty() && new_name != file_entry.name {
+                            spawn(rename_entry(app_state.clone(), file_entry.clone(), new_name, selected_file, error));
+                        }
+                    }
+                    Key::Escape => editing.set(false),
+                    _ => {}
+                },
+                onblur: move |_| editing.set(false),
+            }
+        } else {
+            h2 {
+                title: "Click to rename",
+                style: "
+                    margin: 0 0 4px 0;
+                    font-size: 18px;
+                    font-weight: 600;
+                    color: var(--vscode-text-primary);
+                    word-break: break-all;
+                    cursor: text;
+                ",
+                onclick: move |_| {
+                    draft.set(name.clone());
+                    editing.set(true);
+                },
+                {file_entry.name.clone()}
+            }
+        }
+        if let Some(message) = error.read().as_ref() {
+            p {
+                role: "alert",
+                style: "margin: 0 0 4px 0; color: var(--vscode-error); font-size: 12px;",
+                "{message}"
+            }
+        }
+    }
+}
+
+/// Rename `entry`, carry its tags along and show the renamed file
+async fn rename_entry(
+    mut app_state: AppState,
+    entry: FileEntry,
+    new_name: String,
+    mut selected_file: Signal<Option<FileEntry>>,
+    mut error: Signal<Option<String>>,
+) {
+    match app_state.file_operations().rename(&entry.path, &new_name).await {
+        Ok(new_path) => {
+            error.set(None);
+            app_state.follow_rename(&entry.path, &new_path);
+            selected_file.set(Some(FileEntry { name: new_name, path: new_path, ..entry }));
+            if let Err(e) = app_state.refresh_current_directory().await {
+                tracing::warn!("Error refreshing directory after rename: {}", e);
+            }
+        }
+        Err(e) => error.set(Some(e)),
+    }
+}
+
+/// User tags of a file, with a field to add more
+#[component]
+fn InfoPanelTags(path: PathBuf) -> Element {
+    let app_state = use_app_state();
+    let mut draft = use_signal(String::new);
+    
+    // Re-read the store whenever any tag changes
+    app_state.tags_revision.read();
+    let tags = app_state.tag_store.tags_of(&path);
+    let known_tags = app_state.tag_store.all_tags();
+    
+    let mut app_state_add = app_state.clone();
+    let path_add = path.clone();
+    
+    rsx! {
+        div {
+            class: "info-section info-tags",
+            h3 {
+                style: "
+                    margin: 0 0 12px 0;
+                    font-size: 16px;
+                    font-weight: 600;
+                    color: var(--vscode-text-primary);
+                ",
+                "Tags"
+            }
+            
+            div {
+                class: "info-tag-list",
+                style: "display: flex; flex-wrap: wrap; gap: 6px; margin-bottom: 8px;",
+                if tags.is_empty() {
+                    span {
+                        style: "color: var(--vscode-text-secondary); font-size: 13px; font-style: italic;",
+                        "No tags"
+                    }
+                }
+                {tags.into_iter().map(|tag| {
+                    let mut app_state = app_state.clone();
+                    let path = path.clone();
+                    let removed = tag.clone();
+                    let color = app_state.tag_store.tag_color(&tag);
+                    rsx! {
+                        span {
+                            key: "{tag}",
+                            class: "info-tag",
+                            style: "
+                                display: inline-flex;
+                                align-items: center;
+                                gap: 4px;
+                                padding: 2px 8px;
+                                border-radius: 10px;
+                                background-color: var(--vscode-badge-background, var(--vscode-secondary-background));
+                                color: var(--vscode-badge-foreground, var(--vscode-text-primary));
+                                font-size: 12px;
+                            ",
+                            if let Some(color) = color {
+                                span {
+                                    class: "info-tag-color",
+                                    "aria-hidden": "true",
+                                    style: "width: 8px; height: 8px; border-radius: 50%; background-color: {color};",
+                                }
+                            }
+                            "{tag}"
+                            button {
+                                class: "info-tag-remove",
+                                "aria-label": "Remove tag {tag}",
+                                title: "Remove tag",
+                                style: "
+                                    border: none;
+                                    background: transparent;
+                                    color: inherit;
+                                    cursor: pointer;
+                                    padding: 0;
+                                ",
+                                onclick: move |_| app_state.remove_tag(&path, &removed),
+                                "×"
+                            }
+                        }
+                    }
+                })}
+            }
+            
+            input {
+                r#type: "text",
+                class: "info-tag-input",
+                "aria-label": "Add tag",
+                placeholder: "Add tag and press Enter",
+                list: "info-tag-suggestions",
+                value: "{draft}",
+                style: "
+                    width: 100%;
+                    padding: 4px 6px;
+                    font-size: 13px;
+                    background-color: var(--vscode-input-background, var(--vscode-secondary-background));
+                    color: var(--vscode-text-primary);
+                    border: 1px solid var(--vscode-border);
+                ",
+                oninput: move |evt| draft.set(evt.value()),
+                onkeydown: move |evt| {
+                    if evt.data.key() == Key::Enter {
+                        let tag = draft.read().clone();
+                        app_state_add.add_tag(&path_add, &tag);
+                        draft.set(String::new());
+                    }
+                },
+            }
+            datalist {
+                id: "info-tag-suggestions",
+                for tag in known_tags {
+                    option { value: "{tag}" }
+                }
+            }
+        }
+    }
+}
+
 /// Property grid component for displaying key-value pairs
 #[component]
 fn InfoPropertyGrid(properties: Vec<(String, String)>) -> Element {
@@ -337,20 +771,14 @@ fn get_file_type_description(file_type: &crate::services::file_system::FileType)
     }
 }
 
-fn format_file_size(size: u64) -> String {
-    const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
-    let mut size = size as f64;
-    let mut unit_index = 0;
-    
-    while size >= 1024.0 && unit_index < UNITS.len() - 1 {
-        size /= 1024.0;
-        unit_index += 1;
-    }
-    
-    if unit_index == 0 {
-        format!("{} {}", size as u64, UNITS[unit_index])
+/// "3 minutes ago (03/14/2024 2:57 PM)", or just the date once it's too old for a relative time
+fn format_timestamp(time: std::time::SystemTime) -> String {
+    let absolute = format_date_time(time);
+    let relative = format_relative_time(time);
+    if relative == absolute {
+        absolute
     } else {
-        format!("{:.1} {}", size, UNITS[unit_index])
+        format!("{} ({})", relative, absolute)
     }
 }
 
@@ -362,21 +790,6 @@ fn format_file_path(path: &PathBuf) -> String {
     }
 }
 
-fn format_timestamp(timestamp: std::time::SystemTime) -> String {
-    use std::time::{Duration, UNIX_EPOCH};
-    
-    match timestamp.duration_since(UNIX_EPOCH) {
-        Ok(duration) => {
-            let secs = duration.as_secs();
-            let naive_datetime = chrono::DateTime::from_timestamp(secs as i64, 0)
-                .map(|dt| dt.naive_utc())
-                .unwrap_or_else(|| chrono::DateTime::from_timestamp(0, 0).unwrap().naive_utc());
-            naive_datetime.format("%Y-%m-%d %H:%M:%S").to_string()
-        }
-        Err(_) => "Unknown".to_string()
-    }
-}
-
 fn format_permissions(permissions: &crate::services::file_system::FilePermissions) -> String {
     let mut result = String::new();
     
@@ -0,0 +1,57 @@
+use dioxus::prelude::*;
+use std::time::Duration;
+
+use crate::services::announcer::{Announcement, Politeness};
+use crate::state::use_app_state;
+
+/// How often the live regions pick up new announcements
+const LIVE_REGION_REFRESH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Visually hidden ARIA live regions that speak announcements posted to `AppState`
+#[component]
+pub fn LiveRegion() -> Element {
+    let app_state = use_app_state();
+    let mut polite = use_signal(|| None::<Announcement>);
+    let mut assertive = use_signal(|| None::<Announcement>);
+
+    let announcer = app_state.announcer.clone();
+    use_future(move || {
+        let announcer = announcer.clone();
+        async move {
+            loop {
+                // Only write on change so the regions don't re-render every tick
+                let latest_polite = announcer.latest(Politeness::Polite);
+                if latest_polite.as_ref().map(|a| a.id) != polite.peek().as_ref().map(|a| a.id) {
+                    polite.set(latest_polite);
+                }
+                let latest_assertive = announcer.latest(Politeness::Assertive);
+                if latest_assertive.as_ref().map(|a| a.id) != assertive.peek().as_ref().map(|a| a.id) {
+                    assertive.set(latest_assertive);
+                }
+                tokio::time::sleep(LIVE_REGION_REFRESH_INTERVAL).await;
+            }
+        }
+    });
+
+    rsx! {
+        div {
+            class: "sr-only",
+            role: "status",
+            "aria-live": Politeness::Polite.aria_live(),
+            "aria-atomic": "true",
+            if let Some(announcement) = polite.read().as_ref() {
+                // Keyed by id so a repeated message is inserted afresh and spoken again
+                span { key: "{announcement.id}", "{announcement.message}" }
+            }
+        }
+        div {
+            class: "sr-only",
+            role: "alert",
+            "aria-live": Politeness::Assertive.aria_live(),
+            "aria-atomic": "true",
+            if let Some(announcement) = assertive.read().as_ref() {
+                span { key: "{announcement.id}", "{announcement.message}" }
+            }
+        }
+    }
+}
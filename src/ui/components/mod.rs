@@ -7,6 +7,7 @@ pub mod virtual_scroll;
 // pub mod file_tree_simple; // Temporarily disabled due to syntax error
 pub mod working_file_tree;
 pub mod dialogs;
+pub mod modal;
 pub mod context_menu;
 pub mod drag_drop;
 pub mod settings_panel;
@@ -21,21 +22,42 @@ pub mod empty_file_tree;
 pub mod settings_dialog;
 pub mod icon_pack_manager;
 pub mod file_tree;
+pub mod progress_hud;
+pub mod error_status;
+pub mod live_region;
+pub mod operation_log_panel;
+pub mod output_panel;
+pub mod folder_compare;
+pub mod quick_look;
+pub mod gallery_thumbnail;
+pub mod gallery_view;
+pub mod smart_folders;
+pub mod shortcut_recorder;
+pub mod directory_tabs;
+pub mod breadcrumb;
+pub mod bookmarks;
+pub mod trash_panel;
+pub mod split_view;
+pub mod column_view;
+pub mod batch_rename_dialog;
+pub mod tag_collections;
 
 // Re-export only actively used types to reduce warnings
 // pub use file_tree::{FileTree, FileTreeNode}; // Temporarily disabled
 pub use working_file_tree::WorkingFileTree;
 pub use dialogs::{
-    ConfirmationDialog, ConfirmationResult,
-    ProgressDialog
+    ConfirmationDialog, ConfirmationResult, DialogResult,
+    InputDialog, InputDialogRequest, OpenWithDialog, OpenWithRequest,
+    FileOperationProgressDialog, ProgressDialog
 };
+pub use modal::{Modal, ModalRole, modal_title_id};
 pub use context_menu::{
     ContextMenu,
     use_context_menu
 };
 pub use drag_drop::{
     DragPreview, DropZone,
-    DragOperation,
+    DragOperation, DropZoneState,
     use_drag_drop, use_drop_zone
 };
 pub use settings_panel::{SettingsPanel};
@@ -49,6 +71,25 @@ pub use empty_file_tree::{EmptyFileTree};
 pub use settings_dialog::{SettingsDialog};
 pub use icon_pack_manager::{IconPackManager};
 pub use file_tree::{FileTree, FileTreeNode};
+pub use progress_hud::{ProgressHud};
+pub use error_status::{ErrorStatus};
+pub use live_region::{LiveRegion};
+pub use operation_log_panel::{OperationLogPanel};
+pub use output_panel::{OutputView, TerminalView};
+pub use folder_compare::{FolderComparePanel};
+pub use quick_look::{QuickLookOverlay};
+pub use gallery_thumbnail::{GalleryThumbnail};
+pub use gallery_view::{GalleryView};
+pub use smart_folders::{SmartFolderList, SmartFolderResults};
+pub use shortcut_recorder::{ShortcutRecorder};
+pub use directory_tabs::{DirectoryTabStrip};
+pub use breadcrumb::{Breadcrumb};
+pub use bookmarks::{BookmarkList};
+pub use trash_panel::{TrashPanel};
+pub use split_view::{SplitView};
+pub use column_view::{ColumnView};
+pub use batch_rename_dialog::{BatchRenameDialog};
+pub use tag_collections::{TagCollectionsView};
 // Note: duplicate_manager exports are only used internally by phase2_app
 // pub use duplicate_manager::{DuplicateManager};
 
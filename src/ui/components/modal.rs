@@ -0,0 +1,175 @@
+// Accessible modal base shared by all dialogs
+// Provides focus trapping, Escape-to-close, focus restoration and an inert background
+
+use dioxus::prelude::*;
+
+/// Elements that can receive focus inside a modal
+const FOCUSABLE_SELECTOR: &str = "a[href], button:not([disabled]), input:not([disabled]), select:not([disabled]), textarea:not([disabled]), [tabindex]:not([tabindex='-1'])";
+
+/// ARIA role of the modal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ModalRole {
+    #[default]
+    Dialog,
+    /// For confirmations that interrupt the user's work
+    AlertDialog,
+}
+
+impl ModalRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModalRole::Dialog => "dialog",
+            ModalRole::AlertDialog => "alertdialog",
+        }
+    }
+}
+
+/// Id the dialog's heading must use so the modal is labelled by it
+pub fn modal_title_id(modal_id: &str) -> String {
+    format!("{}-title", modal_id)
+}
+
+/// Props for the modal wrapper
+#[derive(Props, Clone, PartialEq)]
+pub struct ModalProps {
+    /// Unique DOM id of the dialog element
+    pub id: String,
+    pub on_close: EventHandler<()>,
+    pub children: Element,
+    #[props(default)]
+    pub role: ModalRole,
+    /// Class of the dialog box
+    #[props(default)]
+    pub class: String,
+    #[props(default)]
+    pub style: String,
+    #[props(default = "dialog-overlay".to_string())]
+    pub overlay_class: String,
+    #[props(default)]
+    pub overlay_style: String,
+    /// Id of the element describing the dialog, if any
+    #[props(default)]
+    pub described_by: Option<String>,
+    #[props(default = true)]
+    pub close_on_escape: bool,
+    #[props(default = true)]
+    pub close_on_backdrop: bool,
+}
+
+/// Modal dialog wrapper
+///
+/// While mounted it remembers the element that opened it, makes the rest of
+/// the page inert, keeps Tab inside the dialog and returns focus to the
+/// opener when it unmounts. Headings should use [`modal_title_id`].
+#[component]
+pub fn Modal(props: ModalProps) -> Element {
+    let open_id = props.id.clone();
+    use_effect(move || {
+        let _ = document::eval(&open_script(&open_id));
+    });
+
+    let close_id = props.id.clone();
+    use_drop(move || {
+        let _ = document::eval(&close_script(&close_id));
+    });
+
+    let close_on_backdrop = props.close_on_backdrop;
+    let close_on_escape = props.close_on_escape;
+    let on_close = props.on_close;
+
+    rsx! {
+        div {
+            class: "{props.overlay_class}",
+            style: "{props.overlay_style}",
+            onclick: move |_| {
+                if close_on_backdrop {
+                    on_close.call(());
+                }
+            },
+
+            div {
+                id: "{props.id}",
+                class: "{props.class}",
+                style: "{props.style}",
+                role: props.role.as_str(),
+                "aria-modal": "true",
+                "aria-labelledby": modal_title_id(&props.id),
+                "aria-describedby": props.described_by.clone(),
+                tabindex: "-1",
+                onclick: |evt| evt.stop_propagation(),
+                onkeydown: move |evt| {
+                    if close_on_escape && evt.data.key() == Key::Escape {
+                        // Keep the app-level Escape handlers from acting on the page behind
+                        evt.stop_propagation();
+                        evt.prevent_default();
+                        on_close.call(());
+                    }
+                },
+
+                {props.children}
+            }
+        }
+    }
+}
+
+/// Script run when a modal opens
+fn open_script(id: &str) -> String {
+    let id = serde_json::to_string(id).unwrap_or_default();
+    let focusable = serde_json::to_string(FOCUSABLE_SELECTOR).unwrap_or_default();
+    format!(
+        r#"(() => {{
+    const dialog = document.getElementById({id});
+    if (!dialog) return;
+    const state = {{ trigger: document.activeElement, inert: [] }};
+    window.__modals = window.__modals || {{}};
+    window.__modals[{id}] = state;
+
+    // Make everything outside the modal inert, walking up from its overlay
+    let node = dialog.parentElement || dialog;
+    while (node && node.parentElement && node !== document.body) {{
+        for (const sibling of node.parentElement.children) {{
+            if (sibling !== node && !sibling.inert && sibling.tagName !== 'STYLE' && sibling.tagName !== 'SCRIPT') {{
+                sibling.inert = true;
+                state.inert.push(sibling);
+            }}
+        }}
+        node = node.parentElement;
+    }}
+
+    const focusable = () => Array.from(dialog.querySelectorAll({focusable}))
+        .filter(el => el.offsetParent !== null);
+    state.onKeydown = (event) => {{
+        if (event.key !== 'Tab') return;
+        const items = focusable();
+        if (items.length === 0) {{ event.preventDefault(); dialog.focus(); return; }}
+        const first = items[0];
+        const last = items[items.length - 1];
+        if (event.shiftKey && (document.activeElement === first || document.activeElement === dialog)) {{
+            event.preventDefault();
+            last.focus();
+        }} else if (!event.shiftKey && document.activeElement === last) {{
+            event.preventDefault();
+            first.focus();
+        }}
+    }};
+    dialog.addEventListener('keydown', state.onKeydown);
+    (focusable()[0] || dialog).focus();
+}})();"#
+    )
+}
+
+/// Script run when a modal closes
+fn close_script(id: &str) -> String {
+    let id = serde_json::to_string(id).unwrap_or_default();
+    format!(
+        r#"(() => {{
+    const state = window.__modals && window.__modals[{id}];
+    if (!state) return;
+    delete window.__modals[{id}];
+    for (const el of state.inert) el.inert = false;
+    const dialog = document.getElementById({id});
+    if (dialog) dialog.removeEventListener('keydown', state.onKeydown);
+    if (state.trigger && document.contains(state.trigger)) state.trigger.focus();
+}})();"#
+    )
+}
@@ -0,0 +1,216 @@
+// Operation log viewer
+// Lists persisted operation outcomes with date, type and result filters and exports the filtered view
+
+use chrono::{Local, NaiveDate, TimeZone, Utc};
+use dioxus::prelude::*;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use crate::services::operation_log::{LogFilter, LogOutcome, OperationLogRecord};
+use crate::services::operations::OperationKind;
+use crate::state::use_app_state;
+use crate::ui::components::{modal_title_id, Modal};
+use crate::utils::format_date_time;
+
+/// Most recent records shown in the table
+const MAX_VISIBLE_RECORDS: usize = 500;
+
+/// Operation log viewer; mount it only while it should be shown
+#[component]
+pub fn OperationLogPanel(on_close: EventHandler<()>) -> Element {
+    let app_state = use_app_state();
+    let mut from_date = use_signal(String::new);
+    let mut to_date = use_signal(String::new);
+    let mut kind = use_signal(|| None::<OperationKind>);
+    let mut outcome = use_signal(|| None::<LogOutcome>);
+    let mut status = use_signal(|| None::<String>);
+
+    let filter = use_memo(move || LogFilter {
+        since: parse_day_start(&from_date.read(), 0),
+        // The "to" date is inclusive, so stop at the start of the following day
+        until: parse_day_start(&to_date.read(), 1),
+        kind: *kind.read(),
+        outcome: *outcome.read(),
+    });
+
+    let log = app_state.operation_log.clone();
+    let records = use_memo(move || log.query(&filter.read()).map_err(|e| e.to_string()));
+
+    let dialog_id = "operation-log-dialog";
+
+    rsx! {
+        Modal {
+            id: "{dialog_id}",
+            class: "operation-log-dialog",
+            on_close: move |_| on_close.call(()),
+
+            div {
+                class: "dialog-header",
+                h3 { id: modal_title_id(dialog_id), {t!("log.title")} }
+            }
+
+            div {
+                class: "dialog-content",
+                div {
+                    class: "log-filters",
+                    label {
+                        {t!("log.from")}
+                        input {
+                            r#type: "date",
+                            value: "{from_date}",
+                            oninput: move |evt| from_date.set(evt.value()),
+                        }
+                    }
+                    label {
+                        {t!("log.to")}
+                        input {
+                            r#type: "date",
+                            value: "{to_date}",
+                            oninput: move |evt| to_date.set(evt.value()),
+                        }
+                    }
+                    label {
+                        {t!("log.type")}
+                        select {
+                            onchange: move |evt| {
+                                kind.set(OperationKind::ALL.into_iter().find(|k| format!("{:?}", k) == evt.value()));
+                            },
+                            option { value: "", {t!("log.all")} }
+                            for k in OperationKind::ALL {
+                                option {
+                                    value: format!("{:?}", k),
+                                    selected: *kind.read() == Some(k),
+                                    {k.display_name()}
+                                }
+                            }
+                        }
+                    }
+                    label {
+                        {t!("log.result")}
+                        select {
+                            onchange: move |evt| {
+                                outcome.set(LogOutcome::ALL.into_iter().find(|o| format!("{:?}", o) == evt.value()));
+                            },
+                            option { value: "", {t!("log.all")} }
+                            for o in LogOutcome::ALL {
+                                option {
+                                    value: format!("{:?}", o),
+                                    selected: *outcome.read() == Some(o),
+                                    {o.display_name()}
+                                }
+                            }
+                        }
+                    }
+                }
+
+                {
+                    match &*records.read() {
+                        Err(e) => rsx! {
+                            p { class: "dialog-message error", role: "alert", {t!("log.load_failed", error = e)} }
+                        },
+                        Ok(records) if records.is_empty() => rsx! {
+                            p { class: "dialog-message", {t!("log.empty")} }
+                        },
+                        Ok(records) => rsx! {
+                            table {
+                                class: "log-table",
+                                thead {
+                                    tr {
+                                        th { scope: "col", {t!("log.time")} }
+                                        th { scope: "col", {t!("log.action")} }
+                                        th { scope: "col", {t!("log.type")} }
+                                        th { scope: "col", {t!("log.source")} }
+                                        th { scope: "col", {t!("log.destination")} }
+                                        th { scope: "col", {t!("log.result")} }
+                                    }
+                                }
+                                tbody {
+                                    // Newest first
+                                    for record in records.iter().rev().take(MAX_VISIBLE_RECORDS) {
+                                        LogRow { key: "{record.id}", record: record.clone() }
+                                    }
+                                }
+                            }
+                        },
+                    }
+                }
+
+                if let Some(message) = status.read().as_ref() {
+                    p { class: "dialog-message", role: "status", {message.clone()} }
+                }
+            }
+
+            div {
+                class: "dialog-actions",
+                button {
+                    class: "button",
+                    onclick: move |_| {
+                        let log = app_state.operation_log.clone();
+                        let filter = filter.read().clone();
+                        spawn(async move {
+                            let Some(destination) = show_export_dialog().await else {
+                                return;
+                            };
+                            let message = match log.export(&filter, &destination) {
+                                Ok(count) => t!("log.exported", count = count, path = destination.display()),
+                                Err(e) => t!("log.export_failed", error = e),
+                            };
+                            status.set(Some(message));
+                        });
+                    },
+                    {t!("log.export")}
+                }
+                button {
+                    class: "button primary",
+                    onclick: move |_| on_close.call(()),
+                    {t!("dialog.close")}
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn LogRow(record: OperationLogRecord) -> Element {
+    let path_text = |path: &Option<PathBuf>| path.as_ref().map(|p| p.display().to_string()).unwrap_or_default();
+    let result_class = match record.outcome {
+        LogOutcome::Success => "log-result success",
+        LogOutcome::Failure => "log-result error",
+    };
+
+    rsx! {
+        tr {
+            td { {format_date_time(SystemTime::from(record.timestamp))} }
+            td { {record.action.display_name()} }
+            td { {record.kind.display_name()} }
+            td { class: "log-path", title: "{record.description}", {path_text(&record.source)} }
+            td { class: "log-path", {path_text(&record.destination)} }
+            td {
+                class: "{result_class}",
+                title: record.error.clone().unwrap_or_default(),
+                {record.outcome.display_name()}
+            }
+        }
+    }
+}
+
+/// Start of the local day `days_after` days after the `YYYY-MM-DD` date in `value`
+fn parse_day_start(value: &str, days_after: u64) -> Option<chrono::DateTime<Utc>> {
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+    let date = date.checked_add_days(chrono::Days::new(days_after))?;
+    let local = Local.from_local_datetime(&date.and_hms_opt(0, 0, 0)?).earliest()?;
+    Some(local.with_timezone(&Utc))
+}
+
+/// Ask where to save an export of the log
+async fn show_export_dialog() -> Option<PathBuf> {
+    use rfd::AsyncFileDialog;
+
+    AsyncFileDialog::new()
+        .set_title(t!("log.export"))
+        .add_filter("JSON Lines", &["jsonl"])
+        .set_file_name("operation_log.jsonl")
+        .save_file()
+        .await
+        .map(|handle| handle.path().to_path_buf())
+}
@@ -0,0 +1,228 @@
+// Output and Terminal tabs of the bottom panel
+// Both poll a bounded OutputLog that is filled from background threads
+
+use dioxus::prelude::*;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::services::output::{OutputLine, OutputLog, OutputStream};
+use crate::services::terminal::TerminalSession;
+use crate::state::use_app_state;
+
+/// How often the views check their log for new lines
+const OUTPUT_REFRESH_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Lines of the log returned by `source`, refreshed only when something was written
+fn use_output_lines(
+    source: impl Fn() -> Option<Arc<OutputLog>> + Clone + 'static,
+) -> Signal<Vec<OutputLine>> {
+    let mut lines = use_signal(Vec::new);
+    use_future(move || {
+        let source = source.clone();
+        async move {
+            let mut seen = None;
+            loop {
+                let current = source();
+                // The pointer changes when a new terminal replaces the old one
+                let latest = current.as_ref().map(|log| (Arc::as_ptr(log), log.latest_id()));
+                if latest != seen {
+                    seen = latest;
+                    lines.set(current.map(|log| log.lines()).unwrap_or_default());
+                }
+                tokio::time::sleep(OUTPUT_REFRESH_INTERVAL).await;
+            }
+        }
+    });
+    lines
+}
+
+fn line_color(stream: OutputStream) -> &'static str {
+    match stream {
+        OutputStream::Stdout => "inherit",
+        OutputStream::Stderr => "var(--vscode-error-foreground, #f48771)",
+        OutputStream::Info => "var(--vscode-text-secondary, #999999)",
+    }
+}
+
+/// Scrolling list of log lines that stays pinned to the newest one
+#[component]
+fn OutputLines(lines: Vec<OutputLine>, show_source: bool) -> Element {
+    rsx! {
+        div {
+            // column-reverse keeps the view scrolled to the bottom as lines arrive
+            style: "
+                flex: 1;
+                min-height: 0;
+                overflow-y: auto;
+                display: flex;
+                flex-direction: column-reverse;
+            ",
+            pre {
+                style: "
+                    font-family: var(--vscode-font-mono);
+                    font-size: var(--vscode-font-size-small);
+                    line-height: 1.4;
+                    margin: 0;
+                    white-space: pre-wrap;
+                    word-break: break-all;
+                ",
+                for line in lines {
+                    div {
+                        key: "{line.id}",
+                        style: "color: {line_color(line.stream)};",
+                        if show_source {
+                            span { style: "opacity: 0.7;", "[{line.source}] " }
+                        }
+                        "{line.text}"
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Output tab: custom command and background job output
+#[component]
+pub fn OutputView() -> Element {
+    let app_state = use_app_state();
+    let output_log = app_state.output_log.clone();
+    let mut lines = use_output_lines(move || Some(output_log.clone()));
+
+    rsx! {
+        div {
+            id: "output-panel",
+            "aria-labelledby": "output-tab",
+            style: "display: flex; flex-direction: column; height: 100%;",
+            div {
+                style: "display: flex; justify-content: flex-end; margin-bottom: 4px;",
+                button {
+                    class: "btn btn-secondary",
+                    title: t!("panel.clear_output"),
+                    onclick: {
+                        let output_log = app_state.output_log.clone();
+                        move |_| {
+                            output_log.clear();
+                            lines.set(Vec::new());
+                        }
+                    },
+                    {t!("panel.clear")}
+                }
+            }
+            if lines.read().is_empty() {
+                p { style: "opacity: 0.7;", {t!("panel.no_output")} }
+            } else {
+                OutputLines { lines: lines.read().clone(), show_source: true }
+            }
+        }
+    }
+}
+
+/// Terminal tab: a shell in the current folder, started on first view
+#[component]
+pub fn TerminalView() -> Element {
+    let app_state = use_app_state();
+    let mut terminal = app_state.terminal;
+    let mut input = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+
+    let navigation = app_state.navigation;
+    let mut start_terminal = move || {
+        let cwd = navigation.read().current_path.clone();
+        match TerminalSession::spawn(&cwd) {
+            Ok(session) => {
+                tracing::info!("Started terminal in {}", cwd.display());
+                terminal.set(Some(Arc::new(session)));
+                error.set(None);
+            }
+            Err(e) => {
+                tracing::warn!("Failed to start terminal: {}", e);
+                error.set(Some(e.to_string()));
+            }
+        }
+    };
+    use_hook(move || {
+        if terminal.peek().is_none() {
+            start_terminal();
+        }
+    });
+
+    let lines = use_output_lines(move || terminal.peek().as_ref().map(|session| session.output().clone()));
+    let session = terminal.read().clone();
+    let is_running = session.as_ref().is_some_and(|session| session.is_running());
+
+    rsx! {
+        div {
+            id: "terminal-panel",
+            "aria-labelledby": "terminal-tab",
+            style: "
+                display: flex;
+                flex-direction: column;
+                height: 100%;
+                background: var(--vscode-terminal-background, #1e1e1e);
+                color: var(--vscode-terminal-foreground, #ffffff);
+                padding: 4px 8px;
+            ",
+            div {
+                style: "display: flex; align-items: center; gap: 8px; margin-bottom: 4px; font-size: var(--vscode-font-size-small);",
+                span {
+                    style: "flex: 1; opacity: 0.7; overflow: hidden; text-overflow: ellipsis; white-space: nowrap;",
+                    if let Some(session) = &session {
+                        "{session.cwd().display()}"
+                    }
+                }
+                if is_running {
+                    button {
+                        class: "btn btn-secondary",
+                        onclick: move |_| {
+                            if let Some(session) = terminal.read().as_ref() {
+                                session.kill();
+                            }
+                            terminal.set(None);
+                        },
+                        {t!("panel.kill_terminal")}
+                    }
+                } else {
+                    button {
+                        class: "btn btn-secondary",
+                        onclick: move |_| start_terminal(),
+                        {t!("panel.new_terminal")}
+                    }
+                }
+            }
+            if let Some(message) = error.read().as_ref() {
+                p { role: "alert", {t!("panel.terminal_failed", error = message)} }
+            }
+            OutputLines { lines: lines.read().clone(), show_source: false }
+            input {
+                r#type: "text",
+                "aria-label": t!("panel.terminal_input"),
+                disabled: !is_running,
+                value: "{input}",
+                style: "
+                    margin-top: 4px;
+                    background: transparent;
+                    color: inherit;
+                    border: 1px solid var(--vscode-border, #464647);
+                    padding: 4px 6px;
+                    font-family: var(--vscode-font-mono);
+                    font-size: var(--vscode-font-size-small);
+                    outline: none;
+                ",
+                oninput: move |evt| input.set(evt.value()),
+                onkeydown: move |evt: KeyboardEvent| {
+                    evt.stop_propagation();
+                    if evt.key() != Key::Enter {
+                        return;
+                    }
+                    let line = input.read().clone();
+                    if let Some(session) = terminal.read().as_ref() {
+                        if let Err(e) = session.send_line(&line) {
+                            error.set(Some(e.to_string()));
+                        }
+                    }
+                    input.set(String::new());
+                },
+            }
+        }
+    }
+}
@@ -4,9 +4,16 @@ use dioxus_elements::geometry::WheelDelta;
 use dioxus_free_icons::icons::fa_solid_icons;
 use dioxus_free_icons::Icon;
 use crate::services::preview::{PreviewData, PreviewContent, SupportedFormat};
+use crate::services::preview::archive::{build_archive_tree, ArchiveEntry, ArchiveNode};
+use crate::services::preview::pdf::{PdfPageRenderer, DEFAULT_PAGE_SCALE};
+use crate::services::preview::ImagePreviewProvider;
+use crate::services::progressive_loader::LoadingStage;
+use once_cell::sync::Lazy;
 use crate::state::use_app_state;
 use std::path::PathBuf;
+use std::sync::atomic::Ordering;
 use std::time::{Duration, Instant};
+use crate::utils::{format_date_time, format_size};
 
 /// Simple file system entry for preview panel
 #[derive(Debug, Clone, PartialEq)]
@@ -19,6 +26,27 @@ pub struct FileSystemEntry {
     pub file_type: Option<String>,
 }
 
+impl From<&crate::services::file_system::FileEntry> for FileSystemEntry {
+    fn from(entry: &crate::services::file_system::FileEntry) -> Self {
+        use crate::services::file_system::FileType;
+
+        Self {
+            path: entry.path.clone(),
+            name: entry.name.clone(),
+            is_directory: entry.is_directory,
+            size: entry.size,
+            modified: entry.modified,
+            file_type: match &entry.file_type {
+                FileType::Image(_) => Some("image".to_string()),
+                FileType::Video(_) => Some("video".to_string()),
+                FileType::Audio(_) => Some("audio".to_string()),
+                FileType::Document(_) => Some("document".to_string()),
+                _ => None,
+            },
+        }
+    }
+}
+
 /// Loading states for progressive rendering
 #[derive(Debug, Clone, PartialEq)]
 pub enum LoadingState {
@@ -664,6 +692,8 @@ pub fn LazyPreviewContentArea(
                         match &data.preview_content {
                             PreviewContent::Image { .. } => rsx! {
                                 LazyImagePreview {
+                                    key: "{data.file_path.display()}",
+                                    file_path: data.file_path.clone(),
                                     format: data.format.clone(),
                                     zoom_level: zoom_level,
                                     pan_x: pan_x,
@@ -678,16 +708,19 @@ pub fn LazyPreviewContentArea(
                                     lazy_loader: lazy_loader,
                                 }
                             },
-                            PreviewContent::Audio { .. } => rsx! {
+                            PreviewContent::Audio { waveform_data, .. } => rsx! {
                                 LazyAudioPreview {
                                     format: data.format.clone(),
                                     duration: data.metadata.duration,
                                     sample_rate: data.metadata.sample_rate,
+                                    waveform: waveform_data.clone(),
                                     lazy_loader: lazy_loader,
                                 }
                             },
                             PreviewContent::Document { .. } => rsx! {
                                 LazyDocumentPreview {
+                                    key: "{data.file_path.display()}",
+                                    file_path: data.file_path.clone(),
                                     format: data.format.clone(),
                                     page_count: data.metadata.page_count,
                                     lazy_loader: lazy_loader,
@@ -701,9 +734,12 @@ pub fn LazyPreviewContentArea(
                                     lazy_loader: lazy_loader,
                                 }
                             },
-                            PreviewContent::Archive { contents, .. } => rsx! {
+                            PreviewContent::Archive { contents, entries, omitted_entries, listing_error, .. } => rsx! {
                                 LazyArchivePreview {
                                     contents: contents.clone(),
+                                    entries: entries.clone(),
+                                    omitted_entries: *omitted_entries,
+                                    listing_error: listing_error.clone(),
                                     file_size: data.metadata.file_size,
                                     lazy_loader: lazy_loader,
                                 }
@@ -829,8 +865,8 @@ pub fn PreviewMetadata(
                                 .and_then(|ext| ext.to_str())
                                 .map(|ext| ext.to_uppercase())
                                 .unwrap_or_else(|| "Unknown".to_string())),
-                            ("Size", format_file_size(file_entry.size)),
-                            ("Modified", format_system_time(file_entry.modified)),
+                            ("Size", format_size(file_entry.size)),
+                            ("Modified", format_date_time(file_entry.modified)),
                             ("Path", format_file_path(&file_entry.path)),
                         ],
                     }
@@ -1514,14 +1550,20 @@ pub fn AudioPreview(
     format: SupportedFormat,
     duration: Option<f64>,
     sample_rate: Option<u32>,
+    /// Peak amplitude per column from the audio provider; a mock without it
+    #[props(default)]
+    waveform: Vec<f32>,
 ) -> Element {
     // Audio playback state
     let is_playing = use_signal(|| false);
     let current_time = use_signal(|| 0.0f64);
     let volume = use_signal(|| 0.7f64);
     
-    // Mock waveform data - in real implementation this would come from audio analysis
-    let waveform_data = use_signal(|| generate_mock_waveform_data(duration.unwrap_or(60.0)));
+    let waveform_data: Vec<f64> = if waveform.is_empty() {
+        generate_mock_waveform_data(duration.unwrap_or(60.0))
+    } else {
+        waveform.iter().map(|peak| f64::from(peak.abs())).collect()
+    };
     
     let total_duration = duration.unwrap_or(60.0);
     let progress_percentage = if total_duration > 0.0 {
@@ -1629,7 +1671,7 @@ pub fn AudioPreview(
                     },
                     
                     // Background waveform (unplayed portion)
-                    for (i, amplitude) in waveform_data.read().iter().enumerate() {
+                    for (i, amplitude) in waveform_data.iter().enumerate() {
                         rect {
                             key: "bg_{i}",
                             x: i as f64 * 2.0,
@@ -1644,7 +1686,7 @@ pub fn AudioPreview(
                     {
                         let progress_x = progress_percentage / 100.0 * 800.0;
                         rsx! {
-                            for (i, amplitude) in waveform_data.read().iter().enumerate() {
+                            for (i, amplitude) in waveform_data.iter().enumerate() {
                                 if (i as f64 * 2.0) <= progress_x {
                                     rect {
                                         key: "progress_{i}",
@@ -1786,11 +1828,30 @@ pub fn AudioPreview(
     }
 }
 
+/// Rendered PDF pages, shared by every document preview so that flipping
+/// back to a page, or reselecting a file, doesn't render it again
+static PDF_PAGES: Lazy<PdfPageRenderer> = Lazy::new(PdfPageRenderer::default);
+
 #[component]
 pub fn DocumentPreview(
+    file_path: PathBuf,
     format: SupportedFormat,
     page_count: Option<u32>,
 ) -> Element {
+    let total_pages = page_count.unwrap_or(1).max(1);
+    let mut current_page = use_signal(|| 0u32);
+    let page = use_resource(move || {
+        let path = file_path.clone();
+        let page_index = current_page();
+        async move {
+            PDF_PAGES
+                .render_page(&path, page_index, DEFAULT_PAGE_SCALE)
+                .await
+                .map(|page| page.to_data_url())
+        }
+    });
+    let page_number = current_page() + 1;
+
     rsx! {
         div {
             class: "document-preview",
@@ -1804,9 +1865,61 @@ pub fn DocumentPreview(
                 gap: var(--spacing-medium, 12px);
             ",
             
-            div {
-                style: "font-size: 64px;",
-                "📄"
+            match &*page.read() {
+                Some(Ok(src)) => rsx! {
+                    img {
+                        class: "document-page",
+                        src: "{src}",
+                        alt: t!("preview.pdf_page_alt", page = page_number),
+                    }
+                },
+                Some(Err(e)) => rsx! {
+                    div {
+                        class: "document-page-fallback",
+                        role: "alert",
+                        div { style: "font-size: 64px;", "📄" }
+                        {t!("preview.pdf_unavailable", reason = e)}
+                    }
+                },
+                None => rsx! {
+                    div {
+                        class: "document-page-loading",
+                        {t!("preview.pdf_rendering")}
+                    }
+                },
+            }
+            
+            if total_pages > 1 {
+                div {
+                    class: "document-page-nav",
+                    button {
+                        title: t!("preview.pdf_previous"),
+                        "aria-label": t!("preview.pdf_previous"),
+                        disabled: page_number <= 1,
+                        onclick: move |_| current_page.set(current_page().saturating_sub(1)),
+                        "‹"
+                    }
+                    input {
+                        r#type: "number",
+                        min: "1",
+                        max: "{total_pages}",
+                        value: "{page_number}",
+                        "aria-label": t!("preview.pdf_page_number"),
+                        onchange: move |evt: Event<FormData>| {
+                            if let Ok(number) = evt.data.value().trim().parse::<u32>() {
+                                current_page.set(number.clamp(1, total_pages) - 1);
+                            }
+                        },
+                    }
+                    span { {t!("preview.pdf_page_total", total = total_pages)} }
+                    button {
+                        title: t!("preview.pdf_next"),
+                        "aria-label": t!("preview.pdf_next"),
+                        disabled: page_number >= total_pages,
+                        onclick: move |_| current_page.set((current_page() + 1).min(total_pages - 1)),
+                        "›"
+                    }
+                }
             }
             
             div {
@@ -1889,10 +2002,15 @@ pub fn TextPreview(
 #[component]
 pub fn ArchivePreview(
     contents: Vec<String>,
+    #[props(default)] entries: Vec<ArchiveEntry>,
+    #[props(default)] omitted_entries: usize,
+    #[props(default)] listing_error: Option<String>,
     file_size: u64,
 ) -> Element {
     let visible_files = contents.iter().take(20).cloned().collect::<Vec<_>>();
     let remaining_count = contents.len().saturating_sub(20);
+    let tree = build_archive_tree(&entries);
+    let total_entries = if entries.is_empty() { contents.len() } else { entries.len() + omitted_entries };
     
     rsx! {
         div {
@@ -1924,43 +2042,102 @@ pub fn ArchivePreview(
                         font-size: var(--font-size-small, 13px);
                         text-align: center;
                     ",
-                    "Archive • {contents.len()} files • {format_file_size(file_size)}"
+                    "Archive • {total_entries} files • {format_size(file_size)}"
                 }
             }
             
-            div {
-                class: "archive-contents",
-                style: "
-                    background: var(--color-bg-secondary, #252526);
-                    border-radius: var(--border-radius-small, 2px);
-                    padding: var(--spacing-medium, 12px);
-                    max-height: 300px;
-                    overflow-y: auto;
-                ",
-                
-                for (index, file_name) in visible_files.iter().enumerate() {
-                    div {
-                        key: "{index}",
-                        style: "
-                            padding: var(--spacing-extra-small, 4px) var(--spacing-small, 8px);
-                            color: var(--color-text-primary, #cccccc);
-                            font-family: var(--vscode-font-mono);
-                            font-size: 12px;
-                        ",
-                        "{file_name}"
+            if let Some(error) = listing_error {
+                div {
+                    class: "archive-listing-error",
+                    role: "alert",
+                    {t!("preview.archive_unreadable", reason = error)}
+                }
+            } else if !tree.is_empty() {
+                div {
+                    class: "archive-tree",
+                    role: "tree",
+                    for node in tree {
+                        ArchiveTreeNode { key: "{node.path}", node: node, depth: 0 }
+                    }
+                    if omitted_entries > 0 {
+                        div {
+                            class: "archive-tree-more",
+                            {t!("preview.archive_more", count = omitted_entries)}
+                        }
                     }
                 }
-                
-                if remaining_count > 0 {
-                    div {
-                        style: "
-                            padding: var(--spacing-small, 8px);
-                            color: var(--color-text-secondary, #999999);
-                            font-style: italic;
-                            text-align: center;
-                        ",
-                        "... and {remaining_count} more files"
+            } else {
+                div {
+                    class: "archive-contents",
+                    style: "
+                        background: var(--color-bg-secondary, #252526);
+                        border-radius: var(--border-radius-small, 2px);
+                        padding: var(--spacing-medium, 12px);
+                        max-height: 300px;
+                        overflow-y: auto;
+                    ",
+                    
+                    for (index, file_name) in visible_files.iter().enumerate() {
+                        div {
+                            key: "{index}",
+                            style: "
+                                padding: var(--spacing-extra-small, 4px) var(--spacing-small, 8px);
+                                color: var(--color-text-primary, #cccccc);
+                                font-family: var(--vscode-font-mono);
+                                font-size: 12px;
+                            ",
+                            "{file_name}"
+                        }
                     }
+                    
+                    if remaining_count > 0 {
+                        div {
+                            style: "
+                                padding: var(--spacing-small, 8px);
+                                color: var(--color-text-secondary, #999999);
+                                font-style: italic;
+                                text-align: center;
+                            ",
+                            "... and {remaining_count} more files"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A folder or file of an archive; folders start collapsed below the top level
+#[component]
+fn ArchiveTreeNode(node: ArchiveNode, depth: usize) -> Element {
+    let indent = depth * 14;
+    if !node.is_dir {
+        return rsx! {
+            div {
+                class: "archive-tree-file",
+                role: "treeitem",
+                style: "padding-left: {indent + 14}px;",
+                title: "{node.path}",
+                span { class: "archive-tree-name", "{node.name}" }
+                span { class: "archive-tree-size", "{format_size(node.size)}" }
+            }
+        };
+    }
+
+    rsx! {
+        details {
+            class: "archive-tree-folder",
+            role: "treeitem",
+            open: depth == 0,
+            summary {
+                style: "padding-left: {indent}px;",
+                title: "{node.path}",
+                "📁 {node.name}"
+            }
+            div {
+                role: "group",
+                for child in node.children {
+                    ArchiveTreeNode { key: "{child.path}", node: child, depth: depth + 1 }
                 }
             }
         }
@@ -2026,29 +2203,6 @@ pub fn UnsupportedPreview(
     }
 }
 
-/// Format file size for display
-fn format_file_size(bytes: u64) -> String {
-    const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
-    
-    if bytes == 0 {
-        return "0 B".to_string();
-    }
-    
-    let mut size = bytes as f64;
-    let mut unit_index = 0;
-    
-    while size >= 1024.0 && unit_index < UNITS.len() - 1 {
-        size /= 1024.0;
-        unit_index += 1;
-    }
-    
-    if unit_index == 0 {
-        format!("{} {}", bytes, UNITS[unit_index])
-    } else {
-        format!("{:.1} {}", size, UNITS[unit_index])
-    }
-}
-
 /// Format time duration for display (MM:SS format)
 fn format_time(seconds: f64) -> String {
     let total_seconds = seconds.max(0.0) as u64;
@@ -2057,8 +2211,7 @@ fn format_time(seconds: f64) -> String {
     format!("{}:{:02}", minutes, seconds)
 }
 
-/// Generate mock waveform data for audio visualization
-/// In a real implementation, this would analyze actual audio data
+/// Placeholder waveform for audio files the provider couldn't analyze
 fn generate_mock_waveform_data(duration: f64) -> Vec<f64> {
     let sample_count = 400; // Number of bars in the waveform
     let mut waveform = Vec::with_capacity(sample_count);
@@ -2125,11 +2278,7 @@ fn build_metadata_sections(preview_data: &PreviewData) -> Vec<(String, Vec<(&'st
         media_fields.push(("Compression", compression.clone()));
     }
     
-    if !media_fields.is_empty() {
-        sections.push(("Media Properties".to_string(), media_fields));
-    }
-    
-    // Audio metadata section
+    // Audio tags
     let mut audio_fields = Vec::new();
     
     if let Some(title) = &preview_data.metadata.title {
@@ -2148,6 +2297,15 @@ fn build_metadata_sections(preview_data: &PreviewData) -> Vec<(String, Vec<(&'st
         audio_fields.push(("Year", year.to_string()));
     }
     
+    // Audio files list their tags with the technical details
+    if preview_data.format.is_audio() {
+        media_fields.append(&mut audio_fields);
+    }
+    
+    if !media_fields.is_empty() {
+        sections.push(("Media Properties".to_string(), media_fields));
+    }
+    
     if !audio_fields.is_empty() {
         sections.push(("Audio Tags".to_string(), audio_fields));
     }
@@ -2189,7 +2347,7 @@ fn build_metadata_sections(preview_data: &PreviewData) -> Vec<(String, Vec<(&'st
         }
         
         if let Some(date_taken) = exif.date_taken {
-            exif_fields.push(("Date Taken", format_system_time(date_taken)));
+            exif_fields.push(("Date Taken", format_date_time(date_taken)));
         }
         
         if let (Some(lat), Some(lng)) = (exif.gps_latitude, exif.gps_longitude) {
@@ -2205,11 +2363,11 @@ fn build_metadata_sections(preview_data: &PreviewData) -> Vec<(String, Vec<(&'st
     let mut timestamp_fields = Vec::new();
     
     if let Some(created) = preview_data.metadata.created {
-        timestamp_fields.push(("Created", format_system_time(created)));
+        timestamp_fields.push(("Created", format_date_time(created)));
     }
     
     if let Some(modified) = preview_data.metadata.modified {
-        timestamp_fields.push(("Modified", format_system_time(modified)));
+        timestamp_fields.push(("Modified", format_date_time(modified)));
     }
     
     if !timestamp_fields.is_empty() {
@@ -2233,21 +2391,6 @@ fn generate_timeline_thumbnails(duration: f64) -> Vec<f64> {
     positions
 }
 
-/// Format system time for display
-fn format_system_time(time: std::time::SystemTime) -> String {
-    use std::time::UNIX_EPOCH;
-    
-    match time.duration_since(UNIX_EPOCH) {
-        Ok(duration) => {
-            let seconds = duration.as_secs();
-            let datetime = chrono::DateTime::from_timestamp(seconds as i64, 0)
-                .unwrap_or_else(|| chrono::Utc::now());
-            datetime.format("%Y-%m-%d %H:%M:%S").to_string()
-        }
-        Err(_) => "Unknown".to_string(),
-    }
-}
-
 /// Format file path for display (truncate if too long)
 fn format_file_path(path: &PathBuf) -> String {
     let path_str = path.to_string_lossy();
@@ -2501,22 +2644,58 @@ pub fn LoadingErrorDisplay(
 }
 
 /// Lazy loading wrapper for image previews
+///
+/// Shows a blurry low-resolution copy as soon as it's decoded and swaps in
+/// the full image when that's ready. Keyed by path, so selecting another file
+/// cancels the load.
 #[component]
 pub fn LazyImagePreview(
+    file_path: PathBuf,
     format: SupportedFormat,
     zoom_level: Signal<f64>,
     pan_x: Signal<f64>,
     pan_y: Signal<f64>,
     lazy_loader: Signal<LazyLoader>,
 ) -> Element {
-    // Use the existing ImagePreview but with lazy loading optimizations
-    rsx! {
-        ImagePreview {
-            format: format,
-            zoom_level: zoom_level,
-            pan_x: pan_x,
-            pan_y: pan_y,
-        }
+    let mut image_src = use_signal(|| None::<String>);
+    let mut is_sharp = use_signal(|| false);
+    let cancellation_token = use_hook(|| {
+        let mut handle = ImagePreviewProvider::new().load_progressive(&file_path);
+        let token = handle.cancellation_token.clone();
+        spawn(async move {
+            while let Some(progress) = handle.next_progress().await {
+                if let (LoadingStage::Preview | LoadingStage::Full, Some(image)) = (&progress.stage, &progress.intermediate_preview) {
+                    image_src.set(Some(image.to_data_url()));
+                    is_sharp.set(progress.stage == LoadingStage::Full);
+                }
+            }
+        });
+        token
+    });
+    use_drop(move || cancellation_token.store(true, Ordering::SeqCst));
+
+    let name = file_path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+    match image_src() {
+        Some(src) => rsx! {
+            div {
+                class: "image-preview",
+                style: "transform: scale({*zoom_level.read()}) translate({*pan_x.read()}px, {*pan_y.read()}px);",
+                img {
+                    class: if is_sharp() { "progressive-image" } else { "progressive-image blurred" },
+                    src: "{src}",
+                    alt: "{name}",
+                    "aria-busy": "{!is_sharp()}",
+                }
+            }
+        },
+        None => rsx! {
+            ImagePreview {
+                format: format,
+                zoom_level: zoom_level,
+                pan_x: pan_x,
+                pan_y: pan_y,
+            }
+        },
     }
 }
 
@@ -2541,6 +2720,7 @@ pub fn LazyAudioPreview(
     format: SupportedFormat,
     duration: Option<f64>,
     sample_rate: Option<u32>,
+    waveform: Vec<f32>,
     lazy_loader: Signal<LazyLoader>,
 ) -> Element {
     rsx! {
@@ -2548,6 +2728,7 @@ pub fn LazyAudioPreview(
             format: format,
             duration: duration,
             sample_rate: sample_rate,
+            waveform: waveform,
         }
     }
 }
@@ -2555,12 +2736,14 @@ pub fn LazyAudioPreview(
 /// Lazy loading wrapper for document previews with page-by-page loading
 #[component]
 pub fn LazyDocumentPreview(
+    file_path: PathBuf,
     format: SupportedFormat,
     page_count: Option<u32>,
     lazy_loader: Signal<LazyLoader>,
 ) -> Element {
     rsx! {
         DocumentPreview {
+            file_path: file_path,
             format: format,
             page_count: page_count,
         }
@@ -2588,12 +2771,18 @@ pub fn LazyTextPreview(
 #[component]
 pub fn LazyArchivePreview(
     contents: Vec<String>,
+    entries: Vec<ArchiveEntry>,
+    omitted_entries: usize,
+    listing_error: Option<String>,
     file_size: Option<u64>,
     lazy_loader: Signal<LazyLoader>,
 ) -> Element {
     rsx! {
         ArchivePreview {
             contents: contents,
+            entries: entries,
+            omitted_entries: omitted_entries,
+            listing_error: listing_error,
             file_size: file_size.unwrap_or(0),
         }
     }
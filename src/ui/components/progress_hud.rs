@@ -0,0 +1,132 @@
+use dioxus::prelude::*;
+use std::time::Duration;
+
+use crate::services::progress_hub::{ProgressSummary, TaskProgress};
+use crate::services::ThrottleState;
+use crate::state::use_app_state;
+
+/// How often the HUD polls the progress hub
+const HUD_REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Format an ETA as a short string, e.g. "1m 20s"
+pub fn format_eta(eta: Duration) -> String {
+    let secs = eta.as_secs();
+    if secs >= 3600 {
+        format!("{}h {}m", secs / 3600, (secs % 3600) / 60)
+    } else if secs >= 60 {
+        format!("{}m {}s", secs / 60, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// Status bar summary of all running tasks, expanding to a cancellable list on click
+#[component]
+pub fn ProgressHud() -> Element {
+    let app_state = use_app_state();
+    let mut summary = use_signal(|| None::<ProgressSummary>);
+    let mut tasks = use_signal(Vec::<TaskProgress>::new);
+    let mut throttle = use_signal(|| ThrottleState::Running);
+    let mut expanded = use_signal(|| false);
+
+    let hub = app_state.progress_hub.clone();
+    let throttle_policy = app_state.background_throttle.clone();
+    use_future(move || {
+        let hub = hub.clone();
+        let throttle_policy = throttle_policy.clone();
+        async move {
+            loop {
+                let current = hub.tasks();
+                summary.set((!current.is_empty()).then(|| crate::services::progress_hub::aggregate(&current)));
+                tasks.set(current);
+                throttle.set(throttle_policy.current_state());
+                tokio::time::sleep(HUD_REFRESH_INTERVAL).await;
+            }
+        }
+    });
+
+    let throttle_state = *throttle.read();
+    let Some(current) = summary.read().clone() else {
+        return rsx! {
+            if throttle_state != ThrottleState::Running {
+                span {
+                    class: "progress-hud-throttle",
+                    title: "Low-priority background tasks are held back",
+                    "{throttle_state.label()}"
+                }
+            }
+        };
+    };
+
+    let eta_text = current.eta.map(|eta| format!(" · {} left", format_eta(eta))).unwrap_or_default();
+    let task_word = if current.running_tasks == 1 { "task" } else { "tasks" };
+    let hub = app_state.progress_hub.clone();
+
+    rsx! {
+        div {
+            class: "progress-hud",
+            style: "position: relative; display: flex; align-items: center;",
+
+            button {
+                class: "progress-hud-summary",
+                style: "background: none; border: none; color: inherit; font: inherit; cursor: pointer; display: flex; align-items: center; gap: 6px;",
+                "aria-expanded": "{expanded}",
+                "aria-label": "{current.running_tasks} {task_word} running, {current.overall_percentage:.0} percent complete",
+                onclick: move |_| {
+                    let is_expanded = *expanded.read();
+                    expanded.set(!is_expanded);
+                },
+
+                progress {
+                    max: "100",
+                    value: "{current.overall_percentage:.0}",
+                    style: "width: 60px; height: 6px;",
+                }
+                "{current.running_tasks} {task_word} · {current.overall_percentage:.0}%{eta_text}"
+            }
+
+            if *expanded.read() {
+                ul {
+                    class: "progress-hud-list",
+                    role: "list",
+                    style: "position: absolute; bottom: 100%; right: 0; min-width: 280px; margin: 0 0 4px; padding: 6px 0; list-style: none; background: var(--vscode-background-secondary, #252526); border: 1px solid var(--vscode-border, #3c3c3c); border-radius: 4px; box-shadow: 0 2px 8px rgba(0,0,0,0.3); z-index: 1000;",
+
+                    for task in tasks.read().iter().cloned() {
+                        li {
+                            key: "{task.id}",
+                            style: "display: flex; align-items: center; gap: 8px; padding: 4px 10px;",
+
+                            span { "{task.kind.icon()}" }
+                            span { style: "flex: 1; overflow: hidden; text-overflow: ellipsis; white-space: nowrap;", "{task.label}" }
+                            span { "{task.percentage():.0}%" }
+
+                            if task.can_cancel() {
+                                button {
+                                    class: "progress-hud-cancel",
+                                    "aria-label": "Cancel {task.label}",
+                                    style: "background: none; border: none; color: inherit; cursor: pointer;",
+                                    onclick: {
+                                        let hub = hub.clone();
+                                        let task_id = task.id;
+                                        move |_| {
+                                            hub.cancel(task_id);
+                                        }
+                                    },
+                                    "✕"
+                                }
+                            }
+                        }
+                    }
+
+                    if throttle_state != ThrottleState::Running {
+                        li {
+                            class: "progress-hud-throttle",
+                            style: "padding: 4px 10px; opacity: 0.8;",
+                            "{throttle_state.label()}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,107 @@
+// Quick Look overlay
+// A large preview of the selection opened with Space and stepped through with the arrow keys
+
+use dioxus::prelude::*;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::services::preview::{FallbackPreviewProvider, PreviewConfig, PreviewData, PreviewProvider};
+use crate::state::quick_look::QUICK_LOOK_PREFETCH_RADIUS;
+use crate::state::use_app_state;
+use crate::ui::components::preview_panel::FileSystemEntry;
+use crate::ui::components::{modal_title_id, Modal, PreviewPanel};
+
+/// Quick Look overlay; renders nothing while it is closed
+#[component]
+pub fn QuickLookOverlay() -> Element {
+    let app_state = use_app_state();
+    let mut quick_look = app_state.quick_look;
+    // Previews of the current file and its neighbors; `None` while loading or if loading failed
+    let mut previews = use_signal(HashMap::<PathBuf, Option<PreviewData>>::new);
+    let mut selected_file = use_signal(|| None::<FileSystemEntry>);
+    let mut preview_data = use_signal(|| None::<PreviewData>);
+
+    // Load previews around the current file so stepping shows them at once
+    let loader_state = app_state.clone();
+    use_effect(move || {
+        let window = quick_look.read().prefetch_window(QUICK_LOOK_PREFETCH_RADIUS);
+        previews.write().retain(|path, _| window.contains(path));
+        for path in window {
+            if previews.peek().contains_key(&path) {
+                continue;
+            }
+            previews.write().insert(path.clone(), None);
+            let app_state = loader_state.clone();
+            spawn(async move {
+                // Goes through the preview service, so thumbnails come from its cache when present
+                let preview = match app_state.generate_preview_for_file(path.clone()).await {
+                    Ok(Some(preview)) => Some(preview),
+                    // Types no provider handles still show the fallback's file details
+                    Ok(None) => FallbackPreviewProvider::new()
+                        .generate_preview(&path, &PreviewConfig::default())
+                        .await
+                        .ok(),
+                    Err(e) => {
+                        tracing::warn!("Quick Look preview failed for {:?}: {}", path, e);
+                        None
+                    }
+                };
+                if let Some(slot) = previews.write().get_mut(&path) {
+                    *slot = preview;
+                }
+            });
+        }
+    });
+
+    let file_entries = app_state.file_entries;
+    use_effect(move || {
+        let current = quick_look.read().current().map(Path::to_path_buf);
+        let entry = current.as_ref().and_then(|path| {
+            file_entries.read().iter().find(|entry| &entry.path == path).map(FileSystemEntry::from)
+        });
+        selected_file.set(entry);
+        preview_data.set(current.and_then(|path| previews.read().get(&path).cloned().flatten()));
+    });
+
+    let state = quick_look.read();
+    let Some(current) = state.current() else {
+        return rsx! {};
+    };
+    let name = current.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let position = t!("quick_look.position", index = state.index + 1, total = state.items.len());
+    let selection_count = state.selection_count;
+    let dialog_id = "quick-look";
+
+    rsx! {
+        Modal {
+            id: "{dialog_id}",
+            class: "quick-look-dialog",
+            on_close: move |_| quick_look.write().close(),
+
+            div {
+                style: "display: flex; flex-direction: column; height: 100%;",
+                onkeydown: move |evt| {
+                    if quick_look.write().handle_key(&evt.data.key().to_string()) {
+                        // The file list behind the overlay must not act on these keys too
+                        evt.stop_propagation();
+                        evt.prevent_default();
+                    }
+                },
+
+                div {
+                    class: "dialog-header",
+                    style: "display: flex; align-items: baseline; gap: 12px;",
+                    h3 { id: modal_title_id(dialog_id), "{name}" }
+                    span { style: "opacity: 0.7;", "{position}" }
+                    if selection_count > 1 {
+                        span { class: "quick-look-count", {t!("quick_look.selected", count = selection_count)} }
+                    }
+                }
+                div {
+                    style: "flex: 1; min-height: 0;",
+                    PreviewPanel { selected_file, preview_data }
+                }
+            }
+        }
+    }
+}
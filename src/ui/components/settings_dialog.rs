@@ -1,7 +1,22 @@
 use dioxus::prelude::*;
-use crate::state::{SettingsState, Theme, FontFamily, FontSize};
+use crate::i18n::Locale;
+use crate::services::background::{QuietHours, ThrottleMode};
+use crate::services::file_system::ContentSniffing;
+use crate::services::output::CustomCommand;
+use crate::services::preview::ThumbnailEncoding;
+use crate::services::operations::{RETRY_ATTEMPTS_RANGE, RETRY_DELAY_MS_RANGE, RETRY_MULTIPLIER_RANGE};
+use crate::services::RetrySettings;
+use crate::utils::{ClockFormat, SizeUnits};
+use crate::state::{use_app_state, DoubleClickAction, SettingsState, Theme, FontFamily, FontSize, TabOverflow, ThumbnailShape, ThumbnailSize};
+use crate::state::settings_transfer::{export_settings, import_settings, SettingsSnapshot};
 use crate::theme::{ThemeManager, EnhancedThemeSelector};
 use crate::ui::components::IconPackManager;
+use crate::ui::components::modal::{Modal, modal_title_id};
+use crate::ui::components::ShortcutRecorder;
+use crate::ui::shortcuts::{
+    action_for_chord, chords_for_action, find_conflicts, rebind, remappable_actions, KeyCombination, Platform,
+    ShortcutAction,
+};
 
 /// Props for settings dialog
 #[derive(Props, Clone, PartialEq)]
@@ -15,6 +30,10 @@ pub struct SettingsDialogProps {
 /// Settings dialog component for application preferences
 #[component]
 pub fn SettingsDialog(props: SettingsDialogProps) -> Element {
+    // Draft of a new custom command; hooks must run before the early return
+    let mut new_command_name = use_signal(String::new);
+    let mut new_command_line = use_signal(String::new);
+
     if !props.visible {
         return rsx! { div {} };
     }
@@ -32,222 +51,681 @@ pub fn SettingsDialog(props: SettingsDialogProps) -> Element {
         props.on_close.call(());
     };
 
+    let dialog_id = "settings-dialog";
+
     rsx! {
-        div {
-            class: "dialog-overlay",
-            onclick: move |_| on_close(()),
+        Modal {
+            id: "{dialog_id}",
+            class: "settings-dialog",
+            style: "
+                background: var(--vscode-background);
+                border: 1px solid var(--vscode-border);
+                border-radius: 8px;
+                padding: 0;
+                max-width: 600px;
+                width: 90vw;
+                max-height: 80vh;
+                overflow: hidden;
+                box-shadow: 0 4px 16px rgba(0, 0, 0, 0.2);
+            ",
+            on_close: move |_| on_close(()),
+                
+            // Dialog Header
+            div {
+                class: "dialog-header",
+                style: "
+                    display: flex;
+                    align-items: center;
+                    justify-content: space-between;
+                    padding: 16px 20px;
+                    border-bottom: 1px solid var(--vscode-border);
+                    background: var(--vscode-secondary-background);
+                ",
+                
+                div {
+                    style: "display: flex; align-items: center; gap: 8px;",
+                    span { 
+                        class: "dialog-icon",
+                        style: "font-size: 18px;",
+                        "aria-hidden": "true",
+                        "⚙️" 
+                    }
+                    h2 { 
+                        id: modal_title_id(dialog_id),
+                        style: "
+                            margin: 0;
+                            font-size: 18px;
+                            font-weight: 600;
+                            color: var(--vscode-text-primary);
+                        ",
+                        {t!("settings.title")}
+                    }
+                }
+                
+                button {
+                    class: "icon-button",
+                    style: "
+                        background: transparent;
+                        border: none;
+                        color: var(--vscode-text-secondary);
+                        cursor: pointer;
+                        padding: 4px;
+                        font-size: 16px;
+                        border-radius: 4px;
+                    ",
+                    onclick: move |_| on_close(()),
+                    title: t!("settings.close"),
+                    "aria-label": t!("settings.close"),
+                    "×"
+                }
+            }
             
+            // Dialog Content
             div {
-                class: "settings-dialog",
-                onclick: |evt| evt.stop_propagation(),
+                class: "dialog-content",
                 style: "
-                    background: var(--vscode-background);
-                    border: 1px solid var(--vscode-border);
-                    border-radius: 8px;
-                    padding: 0;
-                    max-width: 600px;
-                    width: 90vw;
-                    max-height: 80vh;
-                    overflow: hidden;
-                    box-shadow: 0 4px 16px rgba(0, 0, 0, 0.2);
+                    padding: 20px;
+                    overflow-y: auto;
+                    max-height: 60vh;
                 ",
                 
-                // Dialog Header
+                // Appearance Section
                 div {
-                    class: "dialog-header",
+                    class: "settings-section",
                     style: "
-                        display: flex;
-                        align-items: center;
-                        justify-content: space-between;
-                        padding: 16px 20px;
+                        margin-bottom: 24px;
+                        padding-bottom: 20px;
                         border-bottom: 1px solid var(--vscode-border);
-                        background: var(--vscode-secondary-background);
                     ",
                     
+                    h3 {
+                        style: "
+                            margin: 0 0 16px 0;
+                            font-size: 16px;
+                            font-weight: 600;
+                            color: var(--vscode-text-primary);
+                            display: flex;
+                            align-items: center;
+                            gap: 8px;
+                        ",
+                        span { style: "font-size: 14px;", "🎨" }
+                        "Appearance"
+                    }
+                    
                     div {
-                        style: "display: flex; align-items: center; gap: 8px;",
-                        span { 
-                            class: "dialog-icon",
-                            style: "font-size: 18px;",
-                            "⚙️" 
+                        class: "setting-item",
+                        style: "
+                            display: flex;
+                            flex-direction: column;
+                            gap: 8px;
+                            padding: 12px 0;
+                        ",
+                        
+                        label {
+                            style: "
+                                color: var(--vscode-text-primary);
+                                font-size: 14px;
+                                font-weight: 500;
+                            ",
+                            "Theme"
+                        }
+                        
+                        div {
+                            style: "padding-left: 8px;",
+                            EnhancedThemeSelector {
+                                current_theme: props.current_settings.read().theme.clone(),
+                                theme_manager_state: use_signal(|| crate::theme::ThemeManagerState {
+                                    current_theme: props.current_settings.read().theme.clone(),
+                                    is_applying: false,
+                                    manual_override_active: false,
+                                    last_detected_system_theme: ThemeManager::detect_system_theme(),
+                                    system_theme_listener: None,
+                                }),
+                                on_theme_change: on_theme_change,
+                            }
                         }
-                        h2 { 
+                        
+                        p {
+                            style: "
+                                margin: 4px 0 0 8px;
+                                color: var(--vscode-text-secondary);
+                                font-size: 12px;
+                                line-height: 1.4;
+                            ",
+                            "Select your preferred color theme. Auto mode follows your system preference."
+                        }
+                    }
+
+                    // Language Setting
+                    div {
+                        class: "setting-item",
+                        style: "
+                            display: flex;
+                            flex-direction: column;
+                            gap: 8px;
+                            padding: 12px 0;
+                            border-top: 1px solid var(--vscode-border-light);
+                            margin-top: 16px;
+                            padding-top: 16px;
+                        ",
+                        
+                        label {
+                            r#for: "settings-language",
                             style: "
-                                margin: 0;
-                                font-size: 18px;
-                                font-weight: 600;
                                 color: var(--vscode-text-primary);
+                                font-size: 14px;
+                                font-weight: 500;
+                            ",
+                            {t!("settings.language")}
+                        }
+                        
+                        select {
+                            id: "settings-language",
+                            value: "{props.current_settings.read().language.code()}",
+                            style: "
+                                width: 100%;
+                                background-color: var(--vscode-input-background);
+                                color: var(--vscode-input-foreground);
+                                border: 1px solid var(--vscode-input-border);
+                                border-radius: 4px;
+                                padding: 8px 12px;
+                                font-size: 13px;
+                                font-family: var(--vscode-font-family);
+                                outline: none;
+                                cursor: pointer;
                             ",
-                            "Settings" 
+                            onchange: move |evt| {
+                                let language = Locale::from_code(&evt.value());
+                                tracing::info!("Language changed to: {:?}", language);
+                                
+                                props.on_settings_change.call({
+                                    let mut settings = props.current_settings.read().clone();
+                                    settings.language = language;
+                                    settings
+                                });
+                            },
+                            
+                            for locale in Locale::ALL {
+                                option {
+                                    value: "{locale.code()}",
+                                    lang: "{locale.code()}",
+                                    selected: props.current_settings.read().language == locale,
+                                    "{locale.display_name()}"
+                                }
+                            }
                         }
                     }
-                    
-                    button {
-                        class: "icon-button",
+
+                    // Size Units Setting
+                    div {
+                        class: "setting-item",
                         style: "
-                            background: transparent;
-                            border: none;
-                            color: var(--vscode-text-secondary);
-                            cursor: pointer;
-                            padding: 4px;
-                            font-size: 16px;
-                            border-radius: 4px;
+                            display: flex;
+                            flex-direction: column;
+                            gap: 8px;
+                            padding: 12px 0;
                         ",
-                        onclick: move |_| on_close(()),
-                        title: "Close settings",
-                        "×"
+                        
+                        label {
+                            r#for: "settings-size-units",
+                            style: "
+                                color: var(--vscode-text-primary);
+                                font-size: 14px;
+                                font-weight: 500;
+                            ",
+                            {t!("settings.size_units")}
+                        }
+                        
+                        select {
+                            id: "settings-size-units",
+                            value: "{props.current_settings.read().size_units.as_str()}",
+                            style: "
+                                width: 100%;
+                                background-color: var(--vscode-input-background);
+                                color: var(--vscode-input-foreground);
+                                border: 1px solid var(--vscode-input-border);
+                                border-radius: 4px;
+                                padding: 8px 12px;
+                                font-size: 13px;
+                                font-family: var(--vscode-font-family);
+                                outline: none;
+                                cursor: pointer;
+                            ",
+                            onchange: move |evt| {
+                                props.on_settings_change.call({
+                                    let mut settings = props.current_settings.read().clone();
+                                    settings.size_units = SizeUnits::from_str(&evt.value());
+                                    settings
+                                });
+                            },
+                            
+                            for option_value in SizeUnits::ALL {
+                                option {
+                                    value: "{option_value.as_str()}",
+                                    selected: props.current_settings.read().size_units == option_value,
+                                    "{option_value.display_name()}"
+                                }
+                            }
+                        }
                     }
-                }
-                
-                // Dialog Content
-                div {
-                    class: "dialog-content",
-                    style: "
-                        padding: 20px;
-                        overflow-y: auto;
-                        max-height: 60vh;
-                    ",
-                    
-                    // Appearance Section
+
+                    // Clock Format Setting
                     div {
-                        class: "settings-section",
+                        class: "setting-item",
                         style: "
-                            margin-bottom: 24px;
-                            padding-bottom: 20px;
-                            border-bottom: 1px solid var(--vscode-border);
+                            display: flex;
+                            flex-direction: column;
+                            gap: 8px;
+                            padding: 12px 0;
                         ",
                         
-                        h3 {
+                        label {
+                            r#for: "settings-clock-format",
                             style: "
-                                margin: 0 0 16px 0;
-                                font-size: 16px;
-                                font-weight: 600;
                                 color: var(--vscode-text-primary);
-                                display: flex;
-                                align-items: center;
-                                gap: 8px;
+                                font-size: 14px;
+                                font-weight: 500;
                             ",
-                            span { style: "font-size: 14px;", "🎨" }
-                            "Appearance"
+                            {t!("settings.clock_format")}
                         }
                         
-                        div {
-                            class: "setting-item",
+                        select {
+                            id: "settings-clock-format",
+                            value: "{props.current_settings.read().clock_format.as_str()}",
                             style: "
-                                display: flex;
-                                flex-direction: column;
-                                gap: 8px;
-                                padding: 12px 0;
+                                width: 100%;
+                                background-color: var(--vscode-input-background);
+                                color: var(--vscode-input-foreground);
+                                border: 1px solid var(--vscode-input-border);
+                                border-radius: 4px;
+                                padding: 8px 12px;
+                                font-size: 13px;
+                                font-family: var(--vscode-font-family);
+                                outline: none;
+                                cursor: pointer;
                             ",
+                            onchange: move |evt| {
+                                props.on_settings_change.call({
+                                    let mut settings = props.current_settings.read().clone();
+                                    settings.clock_format = ClockFormat::from_str(&evt.value());
+                                    settings
+                                });
+                            },
                             
-                            label {
-                                style: "
-                                    color: var(--vscode-text-primary);
-                                    font-size: 14px;
-                                    font-weight: 500;
-                                ",
-                                "Theme"
+                            for option_value in ClockFormat::ALL {
+                                option {
+                                    value: "{option_value.as_str()}",
+                                    selected: props.current_settings.read().clock_format == option_value,
+                                    "{option_value.display_name()}"
+                                }
                             }
+                        }
+                    }
+
+                    // Tab Overflow Setting
+                    div {
+                        class: "setting-item",
+                        style: "
+                            display: flex;
+                            flex-direction: column;
+                            gap: 8px;
+                            padding: 12px 0;
+                        ",
+                        
+                        label {
+                            r#for: "settings-tab-overflow",
+                            style: "
+                                color: var(--vscode-text-primary);
+                                font-size: 14px;
+                                font-weight: 500;
+                            ",
+                            {t!("settings.tab_overflow")}
+                        }
+                        
+                        select {
+                            id: "settings-tab-overflow",
+                            value: "{props.current_settings.read().tab_overflow.as_str()}",
+                            style: "
+                                width: 100%;
+                                background-color: var(--vscode-input-background);
+                                color: var(--vscode-input-foreground);
+                                border: 1px solid var(--vscode-input-border);
+                                border-radius: 4px;
+                                padding: 8px 12px;
+                                font-size: 13px;
+                                font-family: var(--vscode-font-family);
+                                outline: none;
+                                cursor: pointer;
+                            ",
+                            onchange: move |evt| {
+                                props.on_settings_change.call({
+                                    let mut settings = props.current_settings.read().clone();
+                                    settings.tab_overflow = TabOverflow::from_str(&evt.value());
+                                    settings
+                                });
+                            },
                             
-                            div {
-                                style: "padding-left: 8px;",
-                                EnhancedThemeSelector {
-                                    current_theme: props.current_settings.read().theme.clone(),
-                                    theme_manager_state: use_signal(|| crate::theme::ThemeManagerState {
-                                        current_theme: props.current_settings.read().theme.clone(),
-                                        is_applying: false,
-                                        manual_override_active: false,
-                                        last_detected_system_theme: ThemeManager::detect_system_theme(),
-                                        system_theme_listener: None,
-                                    }),
-                                    on_theme_change: on_theme_change,
+                            for option_value in TabOverflow::ALL {
+                                option {
+                                    value: "{option_value.as_str()}",
+                                    selected: props.current_settings.read().tab_overflow == option_value,
+                                    "{option_value.display_name()}"
                                 }
                             }
+                        }
+                    }
+
+                    // Double-Click on Files Setting
+                    div {
+                        class: "setting-item",
+                        style: "
+                            display: flex;
+                            flex-direction: column;
+                            gap: 8px;
+                            padding: 12px 0;
+                        ",
+                        
+                        label {
+                            r#for: "settings-double-click-files",
+                            style: "
+                                color: var(--vscode-text-primary);
+                                font-size: 14px;
+                                font-weight: 500;
+                            ",
+                            {t!("settings.double_click_files")}
+                        }
+                        
+                        select {
+                            id: "settings-double-click-files",
+                            value: "{props.current_settings.read().double_click.files.as_str()}",
+                            style: "
+                                width: 100%;
+                                background-color: var(--vscode-input-background);
+                                color: var(--vscode-input-foreground);
+                                border: 1px solid var(--vscode-input-border);
+                                border-radius: 4px;
+                                padding: 8px 12px;
+                                font-size: 13px;
+                                font-family: var(--vscode-font-family);
+                                outline: none;
+                                cursor: pointer;
+                            ",
+                            onchange: move |evt| {
+                                let Some(action) = DoubleClickAction::from_str(&evt.value()) else {
+                                    return;
+                                };
+                                props.on_settings_change.call({
+                                    let mut settings = props.current_settings.read().clone();
+                                    settings.double_click.files = action;
+                                    settings
+                                });
+                            },
                             
-                            p {
-                                style: "
-                                    margin: 4px 0 0 8px;
-                                    color: var(--vscode-text-secondary);
-                                    font-size: 12px;
-                                    line-height: 1.4;
-                                ",
-                                "Select your preferred color theme. Auto mode follows your system preference."
+                            for option_value in DoubleClickAction::FOR_FILES {
+                                option {
+                                    value: "{option_value.as_str()}",
+                                    selected: props.current_settings.read().double_click.files == option_value,
+                                    "{option_value.display_name()}"
+                                }
                             }
                         }
+                    }
 
-                        // Font Family Setting
-                        div {
-                            class: "setting-item",
+                    // Double-Click on Folders Setting
+                    div {
+                        class: "setting-item",
+                        style: "
+                            display: flex;
+                            flex-direction: column;
+                            gap: 8px;
+                            padding: 12px 0;
+                        ",
+                        
+                        label {
+                            r#for: "settings-double-click-folders",
                             style: "
-                                display: flex;
-                                flex-direction: column;
-                                gap: 8px;
-                                padding: 12px 0;
-                                border-top: 1px solid var(--vscode-border-light);
-                                margin-top: 16px;
-                                padding-top: 16px;
+                                color: var(--vscode-text-primary);
+                                font-size: 14px;
+                                font-weight: 500;
+                            ",
+                            {t!("settings.double_click_folders")}
+                        }
+                        
+                        select {
+                            id: "settings-double-click-folders",
+                            value: "{props.current_settings.read().double_click.folders.as_str()}",
+                            style: "
+                                width: 100%;
+                                background-color: var(--vscode-input-background);
+                                color: var(--vscode-input-foreground);
+                                border: 1px solid var(--vscode-input-border);
+                                border-radius: 4px;
+                                padding: 8px 12px;
+                                font-size: 13px;
+                                font-family: var(--vscode-font-family);
+                                outline: none;
+                                cursor: pointer;
                             ",
+                            onchange: move |evt| {
+                                let Some(action) = DoubleClickAction::from_str(&evt.value()) else {
+                                    return;
+                                };
+                                props.on_settings_change.call({
+                                    let mut settings = props.current_settings.read().clone();
+                                    settings.double_click.folders = action;
+                                    settings
+                                });
+                            },
                             
-                            label {
-                                style: "
-                                    color: var(--vscode-text-primary);
-                                    font-size: 14px;
-                                    font-weight: 500;
-                                ",
-                                "Font Family"
+                            for option_value in DoubleClickAction::FOR_FOLDERS {
+                                option {
+                                    value: "{option_value.as_str()}",
+                                    selected: props.current_settings.read().double_click.folders == option_value,
+                                    "{option_value.display_name()}"
+                                }
                             }
+                        }
+                    }
+
+                    // Thumbnail Shape Setting
+                    div {
+                        class: "setting-item",
+                        style: "
+                            display: flex;
+                            flex-direction: column;
+                            gap: 8px;
+                            padding: 12px 0;
+                        ",
+                        
+                        label {
+                            r#for: "settings-thumbnail-shape",
+                            style: "
+                                color: var(--vscode-text-primary);
+                                font-size: 14px;
+                                font-weight: 500;
+                            ",
+                            {t!("settings.thumbnail_shape")}
+                        }
+                        
+                        select {
+                            id: "settings-thumbnail-shape",
+                            value: "{props.current_settings.read().thumbnail_shape.as_str()}",
+                            style: "
+                                width: 100%;
+                                background-color: var(--vscode-input-background);
+                                color: var(--vscode-input-foreground);
+                                border: 1px solid var(--vscode-input-border);
+                                border-radius: 4px;
+                                padding: 8px 12px;
+                                font-size: 13px;
+                                font-family: var(--vscode-font-family);
+                                outline: none;
+                                cursor: pointer;
+                            ",
+                            onchange: move |evt| {
+                                props.on_settings_change.call({
+                                    let mut settings = props.current_settings.read().clone();
+                                    settings.thumbnail_shape = ThumbnailShape::from_str(&evt.value());
+                                    settings
+                                });
+                            },
                             
-                            select {
-                                value: "{props.current_settings.read().font_family.as_str()}",
-                                style: "
-                                    width: 100%;
-                                    background-color: var(--vscode-input-background);
-                                    color: var(--vscode-input-foreground);
-                                    border: 1px solid var(--vscode-input-border);
-                                    border-radius: 4px;
-                                    padding: 8px 12px;
-                                    font-size: 13px;
-                                    font-family: var(--vscode-font-family);
-                                    outline: none;
-                                    cursor: pointer;
-                                ",
-                                onchange: move |evt| {
-                                    let font_family = FontFamily::from_str(&evt.value());
-                                    tracing::info!("Font family changed to: {:?}", font_family);
-                                    
-                                    props.on_settings_change.call({
-                                        let mut settings = props.current_settings.read().clone();
-                                        settings.font_family = font_family.clone();
-                                        
-                                        // Update the custom CSS variables in settings
-                                        settings.custom_css_variables.insert("--vscode-font-family".to_string(), font_family.css_value().to_string());
-                                        
-                                        tracing::info!("Font family applied immediately: {:?}", font_family);
-                                        settings
-                                    });
-                                },
-                                
-                                for font in FontFamily::get_all() {
-                                    option {
-                                        value: "{font.as_str()}",
-                                        selected: props.current_settings.read().font_family == font,
-                                        "{font.display_name()}"
-                                    }
+                            for option_value in ThumbnailShape::ALL {
+                                option {
+                                    value: "{option_value.as_str()}",
+                                    selected: props.current_settings.read().thumbnail_shape == option_value,
+                                    "{option_value.display_name()}"
+                                }
+                            }
+                        }
+                    }
+
+                    // Thumbnail Size Setting
+                    div {
+                        class: "setting-item",
+                        style: "
+                            display: flex;
+                            flex-direction: column;
+                            gap: 8px;
+                            padding: 12px 0;
+                        ",
+                        
+                        label {
+                            r#for: "settings-thumbnail-size",
+                            style: "
+                                color: var(--vscode-text-primary);
+                                font-size: 14px;
+                                font-weight: 500;
+                            ",
+                            {t!("settings.thumbnail_size")}
+                        }
+                        
+                        select {
+                            id: "settings-thumbnail-size",
+                            value: "{props.current_settings.read().thumbnail_size.as_str()}",
+                            style: "
+                                width: 100%;
+                                background-color: var(--vscode-input-background);
+                                color: var(--vscode-input-foreground);
+                                border: 1px solid var(--vscode-input-border);
+                                border-radius: 4px;
+                                padding: 8px 12px;
+                                font-size: 13px;
+                                font-family: var(--vscode-font-family);
+                                outline: none;
+                                cursor: pointer;
+                            ",
+                            onchange: move |evt| {
+                                props.on_settings_change.call({
+                                    let mut settings = props.current_settings.read().clone();
+                                    settings.thumbnail_size = ThumbnailSize::from_str(&evt.value());
+                                    settings
+                                });
+                            },
+                            
+                            for option_value in ThumbnailSize::ALL {
+                                option {
+                                    value: "{option_value.as_str()}",
+                                    selected: props.current_settings.read().thumbnail_size == option_value,
+                                    "{option_value.display_name()}"
                                 }
                             }
+                        }
+                    }
+
+                    // Thumbnail Badges Setting
+                    div {
+                        class: "setting-item",
+                        style: "
+                            display: flex;
+                            align-items: center;
+                            justify-content: space-between;
+                            padding: 8px 0;
+                        ",
+                        
+                        label {
+                            r#for: "settings-thumbnail-badges",
+                            style: "
+                                color: var(--vscode-text-primary);
+                                font-size: 14px;
+                                font-weight: 500;
+                            ",
+                            {t!("settings.thumbnail_badges")}
+                        }
+                        
+                        input {
+                            id: "settings-thumbnail-badges",
+                            r#type: "checkbox",
+                            checked: props.current_settings.read().show_thumbnail_badges,
+                            style: "
+                                accent-color: var(--vscode-accent);
+                                transform: scale(1.2);
+                            ",
+                            onchange: move |evt| {
+                                props.on_settings_change.call({
+                                    let mut settings = props.current_settings.read().clone();
+                                    settings.show_thumbnail_badges = evt.checked();
+                                    settings
+                                });
+                            }
+                        }
+                    }
+
+                    // Thumbnail Cache Encoding Setting
+                    div {
+                        class: "setting-item",
+                        style: "
+                            display: flex;
+                            flex-direction: column;
+                            gap: 8px;
+                            padding: 12px 0;
+                        ",
+                        
+                        label {
+                            r#for: "settings-thumbnail-cache-encoding",
+                            style: "
+                                color: var(--vscode-text-primary);
+                                font-size: 14px;
+                                font-weight: 500;
+                            ",
+                            {t!("settings.thumbnail_cache_encoding")}
+                        }
+                        
+                        select {
+                            id: "settings-thumbnail-cache-encoding",
+                            value: "{props.current_settings.read().thumbnail_cache_encoding.as_str()}",
+                            style: "
+                                width: 100%;
+                                background-color: var(--vscode-input-background);
+                                color: var(--vscode-input-foreground);
+                                border: 1px solid var(--vscode-input-border);
+                                border-radius: 4px;
+                                padding: 8px 12px;
+                                font-size: 13px;
+                                font-family: var(--vscode-font-family);
+                                outline: none;
+                                cursor: pointer;
+                            ",
+                            onchange: move |evt| {
+                                props.on_settings_change.call({
+                                    let mut settings = props.current_settings.read().clone();
+                                    settings.thumbnail_cache_encoding = ThumbnailEncoding::from_str(&evt.value());
+                                    settings
+                                });
+                            },
                             
-                            p {
-                                style: "
-                                    margin: 4px 0 0 0;
-                                    color: var(--vscode-text-secondary);
-                                    font-size: 12px;
-                                    line-height: 1.4;
-                                ",
-                                "Choose the font family used throughout the interface."
+                            for option_value in ThumbnailEncoding::ALL {
+                                option {
+                                    value: "{option_value.as_str()}",
+                                    selected: props.current_settings.read().thumbnail_cache_encoding == option_value,
+                                    "{option_value.display_name()}"
+                                }
                             }
                         }
+                    }
 
-                        // Font Size Setting
+                    // Thumbnail Cache Quality Setting
+                    if props.current_settings.read().thumbnail_cache_encoding == ThumbnailEncoding::Jpeg {
                         div {
                             class: "setting-item",
                             style: "
@@ -255,200 +733,461 @@ pub fn SettingsDialog(props: SettingsDialogProps) -> Element {
                                 flex-direction: column;
                                 gap: 8px;
                                 padding: 12px 0;
-                                border-top: 1px solid var(--vscode-border-light);
-                                margin-top: 16px;
-                                padding-top: 16px;
                             ",
                             
                             label {
+                                r#for: "settings-thumbnail-cache-quality",
                                 style: "
                                     color: var(--vscode-text-primary);
                                     font-size: 14px;
                                     font-weight: 500;
                                 ",
-                                "Font Size"
+                                {t!("settings.thumbnail_cache_quality", quality = props.current_settings.read().thumbnail_cache_quality)}
                             }
                             
-                            select {
-                                value: "{props.current_settings.read().font_size.as_str()}",
+                            input {
+                                id: "settings-thumbnail-cache-quality",
+                                r#type: "range",
+                                min: "1",
+                                max: "100",
+                                value: "{props.current_settings.read().thumbnail_cache_quality}",
                                 style: "
                                     width: 100%;
-                                    background-color: var(--vscode-input-background);
-                                    color: var(--vscode-input-foreground);
-                                    border: 1px solid var(--vscode-input-border);
-                                    border-radius: 4px;
-                                    padding: 8px 12px;
-                                    font-size: 13px;
-                                    font-family: var(--vscode-font-family);
-                                    outline: none;
-                                    cursor: pointer;
+                                    accent-color: var(--vscode-accent);
                                 ",
                                 onchange: move |evt| {
-                                    let font_size = FontSize::from_str(&evt.value());
-                                    tracing::info!("Font size changed to: {:?}", font_size);
-                                    
                                     props.on_settings_change.call({
                                         let mut settings = props.current_settings.read().clone();
-                                        settings.font_size = font_size.clone();
-                                        
-                                        // Update the custom CSS variables in settings
-                                        settings.custom_css_variables.insert("--vscode-font-size-normal".to_string(), font_size.css_value().to_string());
-                                        settings.custom_css_variables.insert("--vscode-font-size".to_string(), font_size.css_value().to_string());
-                                        
-                                        tracing::info!("Font size applied immediately: {:?}", font_size);
+                                        if let Ok(quality) = evt.value().parse::<u8>() {
+                                            settings.thumbnail_cache_quality = quality.clamp(1, 100);
+                                        }
                                         settings
                                     });
-                                },
-                                
-                                for size in FontSize::get_all() {
-                                    option {
-                                        value: "{size.as_str()}",
-                                        selected: props.current_settings.read().font_size == size,
-                                        "{size.display_name()}"
-                                    }
                                 }
                             }
-                            
-                            p {
-                                style: "
-                                    margin: 4px 0 0 0;
-                                    color: var(--vscode-text-secondary);
-                                    font-size: 12px;
-                                    line-height: 1.4;
-                                ",
-                                "Adjust the size of text displayed in the interface."
-                            }
                         }
                     }
-                    
-                    // Icon Packs Section
+
+                    // Content Sniffing Setting
                     div {
-                        class: "settings-section",
+                        class: "setting-item",
                         style: "
-                            margin-bottom: 24px;
-                            padding-bottom: 20px;
-                            border-bottom: 1px solid var(--vscode-border);
+                            display: flex;
+                            flex-direction: column;
+                            gap: 8px;
+                            padding: 12px 0;
                         ",
                         
-                        h3 {
+                        label {
+                            r#for: "settings-content-sniffing",
                             style: "
-                                margin: 0 0 16px 0;
-                                font-size: 16px;
-                                font-weight: 600;
                                 color: var(--vscode-text-primary);
-                                display: flex;
-                                align-items: center;
-                                gap: 8px;
+                                font-size: 14px;
+                                font-weight: 500;
                             ",
-                            span { style: "font-size: 14px;", "📦" }
-                            "Icon Packs"
+                            {t!("settings.content_sniffing")}
                         }
                         
-                        div {
+                        select {
+                            id: "settings-content-sniffing",
+                            value: "{props.current_settings.read().content_sniffing.as_str()}",
                             style: "
-                                max-height: 300px;
-                                overflow-y: auto;
-                                margin-top: 8px;
+                                width: 100%;
+                                background-color: var(--vscode-input-background);
+                                color: var(--vscode-input-foreground);
+                                border: 1px solid var(--vscode-input-border);
+                                border-radius: 4px;
+                                padding: 8px 12px;
+                                font-size: 13px;
+                                font-family: var(--vscode-font-family);
+                                outline: none;
+                                cursor: pointer;
                             ",
-                            IconPackManager {}
+                            onchange: move |evt| {
+                                props.on_settings_change.call({
+                                    let mut settings = props.current_settings.read().clone();
+                                    settings.content_sniffing = ContentSniffing::from_str(&evt.value());
+                                    settings
+                                });
+                            },
+                            
+                            for option_value in ContentSniffing::ALL {
+                                option {
+                                    value: "{option_value.as_str()}",
+                                    selected: props.current_settings.read().content_sniffing == option_value,
+                                    "{option_value.display_name()}"
+                                }
+                            }
                         }
                     }
-                    
-                    // File Management Section
+
+                    // Duplicate Scan Workers Setting
                     div {
-                        class: "settings-section",
+                        class: "setting-item",
                         style: "
-                            margin-bottom: 24px;
-                            padding-bottom: 20px;
-                            border-bottom: 1px solid var(--vscode-border);
+                            display: flex;
+                            flex-direction: column;
+                            gap: 8px;
+                            padding: 12px 0;
                         ",
                         
-                        h3 {
+                        label {
+                            r#for: "settings-duplicate-scan-workers",
                             style: "
-                                margin: 0 0 16px 0;
-                                font-size: 16px;
-                                font-weight: 600;
                                 color: var(--vscode-text-primary);
-                                display: flex;
-                                align-items: center;
-                                gap: 8px;
+                                font-size: 14px;
+                                font-weight: 500;
                             ",
-                            span { style: "font-size: 14px;", "📁" }
-                            "File Management"
+                            {t!("settings.duplicate_scan_workers")}
                         }
                         
-                        div {
-                            class: "setting-item",
+                        select {
+                            id: "settings-duplicate-scan-workers",
+                            value: "{props.current_settings.read().duplicate_scan_workers}",
                             style: "
-                                display: flex;
-                                align-items: center;
-                                justify-content: space-between;
-                                padding: 8px 0;
+                                width: 100%;
+                                background-color: var(--vscode-input-background);
+                                color: var(--vscode-input-foreground);
+                                border: 1px solid var(--vscode-input-border);
+                                border-radius: 4px;
+                                padding: 8px 12px;
+                                font-size: 13px;
+                                font-family: var(--vscode-font-family);
+                                outline: none;
+                                cursor: pointer;
                             ",
+                            onchange: move |evt| {
+                                props.on_settings_change.call({
+                                    let mut settings = props.current_settings.read().clone();
+                                    settings.duplicate_scan_workers = evt.value().parse().unwrap_or(1);
+                                    settings
+                                });
+                            },
                             
-                            div {
-                                label {
-                                    style: "
-                                        color: var(--vscode-text-primary);
-                                        font-size: 14px;
-                                        font-weight: 500;
-                                        display: block;
-                                        margin-bottom: 4px;
-                                    ",
-                                    "Remember last folder"
+                            for workers in [1usize, 2, 4, 8] {
+                                option {
+                                    value: "{workers}",
+                                    selected: props.current_settings.read().duplicate_scan_workers == workers,
+                                    "{workers}"
                                 }
-                                p {
-                                    style: "
-                                        margin: 0;
-                                        color: var(--vscode-text-secondary);
-                                        font-size: 12px;
-                                        line-height: 1.4;
-                                    ",
-                                    "Automatically reopen the last viewed folder on startup"
+                            }
+                        }
+                    }
+
+                    // Duplicate Scan I/O Priority Setting
+                    div {
+                        class: "setting-item",
+                        style: "
+                            display: flex;
+                            align-items: center;
+                            justify-content: space-between;
+                            padding: 8px 0;
+                        ",
+                        
+                        label {
+                            r#for: "settings-duplicate-scan-low-io",
+                            style: "
+                                color: var(--vscode-text-primary);
+                                font-size: 14px;
+                                font-weight: 500;
+                            ",
+                            {t!("settings.duplicate_scan_low_io")}
+                        }
+                        
+                        input {
+                            id: "settings-duplicate-scan-low-io",
+                            r#type: "checkbox",
+                            checked: props.current_settings.read().duplicate_scan_low_io_priority,
+                            style: "
+                                accent-color: var(--vscode-accent);
+                                transform: scale(1.2);
+                            ",
+                            onchange: move |evt| {
+                                props.on_settings_change.call({
+                                    let mut settings = props.current_settings.read().clone();
+                                    settings.duplicate_scan_low_io_priority = evt.checked();
+                                    settings
+                                });
+                            }
+                        }
+                    }
+
+                    BackgroundTaskSettings {
+                        current_settings: props.current_settings,
+                        on_settings_change: props.on_settings_change,
+                    }
+
+                    // Font Family Setting
+                    div {
+                        class: "setting-item",
+                        style: "
+                            display: flex;
+                            flex-direction: column;
+                            gap: 8px;
+                            padding: 12px 0;
+                            border-top: 1px solid var(--vscode-border-light);
+                            margin-top: 16px;
+                            padding-top: 16px;
+                        ",
+                        
+                        label {
+                            style: "
+                                color: var(--vscode-text-primary);
+                                font-size: 14px;
+                                font-weight: 500;
+                            ",
+                            "Font Family"
+                        }
+                        
+                        select {
+                            value: "{props.current_settings.read().font_family.as_str()}",
+                            style: "
+                                width: 100%;
+                                background-color: var(--vscode-input-background);
+                                color: var(--vscode-input-foreground);
+                                border: 1px solid var(--vscode-input-border);
+                                border-radius: 4px;
+                                padding: 8px 12px;
+                                font-size: 13px;
+                                font-family: var(--vscode-font-family);
+                                outline: none;
+                                cursor: pointer;
+                            ",
+                            onchange: move |evt| {
+                                let font_family = FontFamily::from_str(&evt.value());
+                                tracing::info!("Font family changed to: {:?}", font_family);
+                                
+                                props.on_settings_change.call({
+                                    let mut settings = props.current_settings.read().clone();
+                                    settings.font_family = font_family.clone();
+                                    
+                                    // Update the custom CSS variables in settings
+                                    settings.custom_css_variables.insert("--vscode-font-family".to_string(), font_family.css_value().to_string());
+                                    
+                                    tracing::info!("Font family applied immediately: {:?}", font_family);
+                                    settings
+                                });
+                            },
+                            
+                            for font in FontFamily::get_all() {
+                                option {
+                                    value: "{font.as_str()}",
+                                    selected: props.current_settings.read().font_family == font,
+                                    "{font.display_name()}"
                                 }
                             }
+                        }
+                        
+                        p {
+                            style: "
+                                margin: 4px 0 0 0;
+                                color: var(--vscode-text-secondary);
+                                font-size: 12px;
+                                line-height: 1.4;
+                            ",
+                            "Choose the font family used throughout the interface."
+                        }
+                    }
+
+                    // Font Size Setting
+                    div {
+                        class: "setting-item",
+                        style: "
+                            display: flex;
+                            flex-direction: column;
+                            gap: 8px;
+                            padding: 12px 0;
+                            border-top: 1px solid var(--vscode-border-light);
+                            margin-top: 16px;
+                            padding-top: 16px;
+                        ",
+                        
+                        label {
+                            style: "
+                                color: var(--vscode-text-primary);
+                                font-size: 14px;
+                                font-weight: 500;
+                            ",
+                            "Font Size"
+                        }
+                        
+                        select {
+                            value: "{props.current_settings.read().font_size.as_str()}",
+                            style: "
+                                width: 100%;
+                                background-color: var(--vscode-input-background);
+                                color: var(--vscode-input-foreground);
+                                border: 1px solid var(--vscode-input-border);
+                                border-radius: 4px;
+                                padding: 8px 12px;
+                                font-size: 13px;
+                                font-family: var(--vscode-font-family);
+                                outline: none;
+                                cursor: pointer;
+                            ",
+                            onchange: move |evt| {
+                                let font_size = FontSize::from_str(&evt.value());
+                                tracing::info!("Font size changed to: {:?}", font_size);
+                                
+                                props.on_settings_change.call({
+                                    let mut settings = props.current_settings.read().clone();
+                                    settings.font_size = font_size.clone();
+                                    
+                                    // Update the custom CSS variables in settings
+                                    settings.custom_css_variables.insert("--vscode-font-size-normal".to_string(), font_size.css_value().to_string());
+                                    settings.custom_css_variables.insert("--vscode-font-size".to_string(), font_size.css_value().to_string());
+                                    
+                                    tracing::info!("Font size applied immediately: {:?}", font_size);
+                                    settings
+                                });
+                            },
                             
-                            input {
-                                r#type: "checkbox",
-                                checked: props.current_settings.read().remember_last_directory,
-                                style: "
-                                    accent-color: var(--vscode-accent);
-                                    transform: scale(1.2);
-                                ",
-                                onchange: move |evt| {
-                                    props.on_settings_change.call({
-                                        let mut settings = props.current_settings.read().clone();
-                                        settings.remember_last_directory = evt.checked();
-                                        tracing::info!("Remember last directory changed to: {}", evt.checked());
-                                        settings
-                                    });
+                            for size in FontSize::get_all() {
+                                option {
+                                    value: "{size.as_str()}",
+                                    selected: props.current_settings.read().font_size == size,
+                                    "{size.display_name()}"
                                 }
                             }
                         }
+                        
+                        p {
+                            style: "
+                                margin: 4px 0 0 0;
+                                color: var(--vscode-text-secondary);
+                                font-size: 12px;
+                                line-height: 1.4;
+                            ",
+                            "Adjust the size of text displayed in the interface."
+                        }
+                    }
+                }
+                
+                // Icon Packs Section
+                div {
+                    class: "settings-section",
+                    style: "
+                        margin-bottom: 24px;
+                        padding-bottom: 20px;
+                        border-bottom: 1px solid var(--vscode-border);
+                    ",
+                    
+                    h3 {
+                        style: "
+                            margin: 0 0 16px 0;
+                            font-size: 16px;
+                            font-weight: 600;
+                            color: var(--vscode-text-primary);
+                            display: flex;
+                            align-items: center;
+                            gap: 8px;
+                        ",
+                        span { style: "font-size: 14px;", "📦" }
+                        "Icon Packs"
                     }
                     
-                    // Advanced Section
                     div {
-                        class: "settings-section",
+                        style: "
+                            max-height: 300px;
+                            overflow-y: auto;
+                            margin-top: 8px;
+                        ",
+                        IconPackManager {}
+                    }
+                }
+                
+                // File Management Section
+                div {
+                    class: "settings-section",
+                    style: "
+                        margin-bottom: 24px;
+                        padding-bottom: 20px;
+                        border-bottom: 1px solid var(--vscode-border);
+                    ",
+                    
+                    h3 {
+                        style: "
+                            margin: 0 0 16px 0;
+                            font-size: 16px;
+                            font-weight: 600;
+                            color: var(--vscode-text-primary);
+                            display: flex;
+                            align-items: center;
+                            gap: 8px;
+                        ",
+                        span { style: "font-size: 14px;", "📁" }
+                        "File Management"
+                    }
+                    
+                    div {
+                        class: "setting-item",
+                        style: "
+                            display: flex;
+                            align-items: center;
+                            justify-content: space-between;
+                            padding: 8px 0;
+                        ",
                         
-                        h3 {
+                        div {
+                            label {
+                                style: "
+                                    color: var(--vscode-text-primary);
+                                    font-size: 14px;
+                                    font-weight: 500;
+                                    display: block;
+                                    margin-bottom: 4px;
+                                ",
+                                "Remember last folder"
+                            }
+                            p {
+                                style: "
+                                    margin: 0;
+                                    color: var(--vscode-text-secondary);
+                                    font-size: 12px;
+                                    line-height: 1.4;
+                                ",
+                                "Automatically reopen the last viewed folder on startup"
+                            }
+                        }
+                        
+                        input {
+                            r#type: "checkbox",
+                            checked: props.current_settings.read().remember_last_directory,
                             style: "
-                                margin: 0 0 16px 0;
-                                font-size: 16px;
-                                font-weight: 600;
-                                color: var(--vscode-text-primary);
-                                display: flex;
-                                align-items: center;
-                                gap: 8px;
+                                accent-color: var(--vscode-accent);
+                                transform: scale(1.2);
                             ",
-                            span { style: "font-size: 14px;", "⚙️" }
-                            "Advanced"
+                            onchange: move |evt| {
+                                props.on_settings_change.call({
+                                    let mut settings = props.current_settings.read().clone();
+                                    settings.remember_last_directory = evt.checked();
+                                    tracing::info!("Remember last directory changed to: {}", evt.checked());
+                                    settings
+                                });
+                            }
                         }
+                    }
+
+                    div {
+                        class: "setting-item",
+                        style: "
+                            display: flex;
+                            align-items: center;
+                            justify-content: space-between;
+                            padding: 8px 0;
+                        ",
                         
                         div {
-                            class: "setting-item",
-                            style: "padding: 8px 0;",
-                            
+                            label {
+                                style: "
+                                    color: var(--vscode-text-primary);
+                                    font-size: 14px;
+                                    font-weight: 500;
+                                    display: block;
+                                    margin-bottom: 4px;
+                                ",
+                                "Allow multiple instances"
+                            }
                             p {
                                 style: "
                                     margin: 0;
@@ -456,12 +1195,847 @@ pub fn SettingsDialog(props: SettingsDialogProps) -> Element {
                                     font-size: 12px;
                                     line-height: 1.4;
                                 ",
-                                "Settings are automatically applied when changed. Use Ctrl+, to quickly open settings."
+                                "Start a separate app for each launch instead of opening folders in the running one. Takes effect on next launch"
+                            }
+                        }
+                        
+                        input {
+                            r#type: "checkbox",
+                            checked: props.current_settings.read().allow_multiple_instances,
+                            style: "
+                                accent-color: var(--vscode-accent);
+                                transform: scale(1.2);
+                            ",
+                            onchange: move |evt| {
+                                props.on_settings_change.call({
+                                    let mut settings = props.current_settings.read().clone();
+                                    settings.allow_multiple_instances = evt.checked();
+                                    tracing::info!("Allow multiple instances changed to: {}", evt.checked());
+                                    settings
+                                });
+                            }
+                        }
+                    }
+                }
+                
+                // Keyboard Shortcuts Section
+                div {
+                    class: "settings-section",
+                    style: "
+                        margin-bottom: 24px;
+                        padding-bottom: 20px;
+                        border-bottom: 1px solid var(--vscode-border);
+                    ",
+                    
+                    h3 {
+                        style: "
+                            margin: 0 0 16px 0;
+                            font-size: 16px;
+                            font-weight: 600;
+                            color: var(--vscode-text-primary);
+                            display: flex;
+                            align-items: center;
+                            gap: 8px;
+                        ",
+                        span { style: "font-size: 14px;", "⌨️" }
+                        {t!("settings.keymap.title")}
+                    }
+                    
+                    KeymapSettings {}
+                }
+                
+                // Advanced Section
+                div {
+                    class: "settings-section",
+                    
+                    h3 {
+                        style: "
+                            margin: 0 0 16px 0;
+                            font-size: 16px;
+                            font-weight: 600;
+                            color: var(--vscode-text-primary);
+                            display: flex;
+                            align-items: center;
+                            gap: 8px;
+                        ",
+                        span { style: "font-size: 14px;", "⚙️" }
+                        "Advanced"
+                    }
+                    
+                    div {
+                        class: "setting-item",
+                        style: "
+                            display: flex;
+                            flex-direction: column;
+                            gap: 8px;
+                            padding: 8px 0;
+                        ",
+                        
+                        label {
+                            style: "
+                                color: var(--vscode-text-primary);
+                                font-size: 14px;
+                                font-weight: 500;
+                            ",
+                            "Custom commands"
+                        }
+                        p {
+                            style: "
+                                margin: 0;
+                                color: var(--vscode-text-secondary);
+                                font-size: 12px;
+                                line-height: 1.4;
+                            ",
+                            "Shell commands listed in the command palette. They run in the current folder and their output appears in the Output panel"
+                        }
+                        
+                        for (index, custom) in props.current_settings.read().custom_commands.iter().enumerate() {
+                            div {
+                                key: "{index}",
+                                style: "display: flex; align-items: center; gap: 8px; font-size: 13px;",
+                                span { style: "font-weight: 500;", "{custom.name}" }
+                                code {
+                                    style: "flex: 1; overflow: hidden; text-overflow: ellipsis; white-space: nowrap; color: var(--vscode-text-secondary);",
+                                    "{custom.command}"
+                                }
+                                button {
+                                    class: "btn btn-secondary",
+                                    "aria-label": "Remove {custom.name}",
+                                    onclick: move |_| {
+                                        props.on_settings_change.call({
+                                            let mut settings = props.current_settings.read().clone();
+                                            settings.custom_commands.remove(index);
+                                            settings
+                                        });
+                                    },
+                                    "Remove"
+                                }
+                            }
+                        }
+                        
+                        div {
+                            style: "display: flex; gap: 8px;",
+                            input {
+                                r#type: "text",
+                                placeholder: "Name",
+                                "aria-label": "Custom command name",
+                                value: "{new_command_name}",
+                                style: "width: 30%;",
+                                oninput: move |evt| new_command_name.set(evt.value()),
+                            }
+                            input {
+                                r#type: "text",
+                                placeholder: "Command",
+                                "aria-label": "Custom command line",
+                                value: "{new_command_line}",
+                                style: "flex: 1; font-family: var(--vscode-font-mono);",
+                                oninput: move |evt| new_command_line.set(evt.value()),
+                            }
+                            button {
+                                class: "btn btn-primary",
+                                disabled: new_command_name.read().trim().is_empty() || new_command_line.read().trim().is_empty(),
+                                onclick: move |_| {
+                                    let custom = CustomCommand::new(
+                                        new_command_name.read().trim(),
+                                        new_command_line.read().trim(),
+                                    );
+                                    props.on_settings_change.call({
+                                        let mut settings = props.current_settings.read().clone();
+                                        settings.custom_commands.push(custom);
+                                        settings
+                                    });
+                                    new_command_name.set(String::new());
+                                    new_command_line.set(String::new());
+                                },
+                                "Add"
+                            }
+                        }
+                    }
+                    
+                    // Copy Verification Setting
+                    div {
+                        class: "setting-item",
+                        style: "
+                            display: flex;
+                            align-items: center;
+                            justify-content: space-between;
+                            padding: 8px 0;
+                        ",
+                        title: t!("settings.verify_copies_hint"),
+                        
+                        label {
+                            r#for: "settings-verify-copies",
+                            style: "
+                                color: var(--vscode-text-primary);
+                                font-size: 14px;
+                                font-weight: 500;
+                            ",
+                            {t!("settings.verify_copies")}
+                        }
+                        
+                        input {
+                            id: "settings-verify-copies",
+                            r#type: "checkbox",
+                            checked: props.current_settings.read().verify_copies,
+                            style: "
+                                accent-color: var(--vscode-accent);
+                                transform: scale(1.2);
+                            ",
+                            onchange: move |evt| {
+                                props.on_settings_change.call({
+                                    let mut settings = props.current_settings.read().clone();
+                                    settings.verify_copies = evt.checked();
+                                    settings
+                                });
                             }
                         }
                     }
+                    
+                    RetryPolicySettings {
+                        current_settings: props.current_settings,
+                        on_settings_change: props.on_settings_change,
+                    }
+                    
+                    SettingsTransfer {
+                        current_settings: props.current_settings,
+                        on_settings_change: props.on_settings_change,
+                    }
+                    
+                    div {
+                        class: "setting-item",
+                        style: "padding: 8px 0;",
+                        
+                        p {
+                            style: "
+                                margin: 0;
+                                color: var(--vscode-text-secondary);
+                                font-size: 12px;
+                                line-height: 1.4;
+                            ",
+                            "Settings are automatically applied when changed. Use Ctrl+, to quickly open settings."
+                        }
+                    }
                 }
             }
         }
     }
-}
\ No newline at end of file
+}
+
+/// Retry attempts and backoff for failed file operations
+///
+/// Values out of range are reported and not saved, so the policy in effect
+/// is always a valid one.
+#[component]
+fn RetryPolicySettings(current_settings: Signal<SettingsState>, on_settings_change: EventHandler<SettingsState>) -> Element {
+    let mut error = use_signal(|| None::<String>);
+
+    let mut apply = move |update: &dyn Fn(&mut RetrySettings)| {
+        let mut settings = current_settings.peek().clone();
+        update(&mut settings.retry_policy);
+        match settings.retry_policy.validate() {
+            Ok(()) => {
+                error.set(None);
+                on_settings_change.call(settings);
+            }
+            Err(e) => error.set(Some(t!("settings.retry.invalid", error = e))),
+        }
+    };
+
+    let policy = current_settings.read().retry_policy.clone();
+    let field_style = "display: flex; align-items: center; justify-content: space-between; gap: 12px; font-size: 13px;";
+    let input_style = "
+        width: 120px;
+        background-color: var(--vscode-input-background);
+        color: var(--vscode-input-foreground);
+        border: 1px solid var(--vscode-input-border);
+        border-radius: 4px;
+        padding: 4px 8px;
+        font-size: 13px;
+    ";
+
+    rsx! {
+        div {
+            class: "setting-item",
+            style: "
+                display: flex;
+                flex-direction: column;
+                gap: 8px;
+                padding: 8px 0;
+            ",
+
+            label {
+                style: "
+                    color: var(--vscode-text-primary);
+                    font-size: 14px;
+                    font-weight: 500;
+                ",
+                {t!("settings.retry.title")}
+            }
+            p {
+                style: "
+                    margin: 0;
+                    color: var(--vscode-text-secondary);
+                    font-size: 12px;
+                    line-height: 1.4;
+                ",
+                {t!("settings.retry.hint")}
+            }
+
+            label {
+                style: "{field_style}",
+                {t!("settings.retry.max_attempts")}
+                input {
+                    r#type: "number",
+                    min: "{RETRY_ATTEMPTS_RANGE.start()}",
+                    max: "{RETRY_ATTEMPTS_RANGE.end()}",
+                    value: "{policy.max_attempts}",
+                    style: "{input_style}",
+                    onchange: move |evt| match evt.value().parse::<u32>() {
+                        Ok(value) => apply(&|policy| policy.max_attempts = value),
+                        Err(_) => error.set(Some(t!("settings.retry.invalid", error = evt.value()))),
+                    },
+                }
+            }
+            label {
+                style: "{field_style}",
+                {t!("settings.retry.initial_delay")}
+                input {
+                    r#type: "number",
+                    min: "{RETRY_DELAY_MS_RANGE.start()}",
+                    max: "{RETRY_DELAY_MS_RANGE.end()}",
+                    value: "{policy.initial_delay_ms}",
+                    style: "{input_style}",
+                    onchange: move |evt| match evt.value().parse::<u64>() {
+                        Ok(value) => apply(&|policy| policy.initial_delay_ms = value),
+                        Err(_) => error.set(Some(t!("settings.retry.invalid", error = evt.value()))),
+                    },
+                }
+            }
+            label {
+                style: "{field_style}",
+                {t!("settings.retry.max_delay")}
+                input {
+                    r#type: "number",
+                    min: "{RETRY_DELAY_MS_RANGE.start()}",
+                    max: "{RETRY_DELAY_MS_RANGE.end()}",
+                    value: "{policy.max_delay_ms}",
+                    style: "{input_style}",
+                    onchange: move |evt| match evt.value().parse::<u64>() {
+                        Ok(value) => apply(&|policy| policy.max_delay_ms = value),
+                        Err(_) => error.set(Some(t!("settings.retry.invalid", error = evt.value()))),
+                    },
+                }
+            }
+            label {
+                style: "{field_style}",
+                {t!("settings.retry.multiplier")}
+                input {
+                    r#type: "number",
+                    min: "{RETRY_MULTIPLIER_RANGE.start()}",
+                    max: "{RETRY_MULTIPLIER_RANGE.end()}",
+                    step: "0.1",
+                    value: "{policy.backoff_multiplier}",
+                    style: "{input_style}",
+                    onchange: move |evt| match evt.value().parse::<f64>() {
+                        Ok(value) => apply(&|policy| policy.backoff_multiplier = value),
+                        Err(_) => error.set(Some(t!("settings.retry.invalid", error = evt.value()))),
+                    },
+                }
+            }
+            label {
+                style: "{field_style}",
+                {t!("settings.retry.jitter")}
+                input {
+                    r#type: "checkbox",
+                    checked: policy.jitter,
+                    style: "
+                        accent-color: var(--vscode-accent);
+                        transform: scale(1.2);
+                    ",
+                    onchange: move |evt| {
+                        let jitter = evt.checked();
+                        apply(&|policy| policy.jitter = jitter);
+                    },
+                }
+            }
+
+            if let Some(message) = error.read().clone() {
+                p {
+                    role: "alert",
+                    style: "margin: 0; font-size: 12px; color: var(--vscode-error);",
+                    "{message}"
+                }
+            }
+        }
+    }
+}
+
+/// When low-priority background work such as thumbnail prefetching is held back
+#[component]
+fn BackgroundTaskSettings(current_settings: Signal<SettingsState>, on_settings_change: EventHandler<SettingsState>) -> Element {
+    let mut error = use_signal(|| None::<String>);
+
+    let mut apply = move |update: &dyn Fn(&mut crate::services::BackgroundThrottleSettings)| {
+        let mut settings = current_settings.peek().clone();
+        update(&mut settings.background_throttle);
+        error.set(None);
+        on_settings_change.call(settings);
+    };
+
+    let throttle = current_settings.read().background_throttle.clone();
+    let quiet_hours = throttle.quiet_hours.unwrap_or(QuietHours { start_hour: 22, end_hour: 7 });
+    let field_style = "display: flex; align-items: center; justify-content: space-between; gap: 12px; font-size: 13px;";
+    let checkbox_style = "
+        accent-color: var(--vscode-accent);
+        transform: scale(1.2);
+    ";
+    let input_style = "
+        width: 120px;
+        background-color: var(--vscode-input-background);
+        color: var(--vscode-input-foreground);
+        border: 1px solid var(--vscode-input-border);
+        border-radius: 4px;
+        padding: 4px 8px;
+        font-size: 13px;
+    ";
+
+    rsx! {
+        div {
+            class: "setting-item",
+            style: "
+                display: flex;
+                flex-direction: column;
+                gap: 8px;
+                padding: 8px 0;
+            ",
+
+            label {
+                style: "{field_style}",
+                span {
+                    style: "
+                        color: var(--vscode-text-primary);
+                        font-size: 14px;
+                        font-weight: 500;
+                    ",
+                    {t!("settings.background.title")}
+                }
+                input {
+                    r#type: "checkbox",
+                    checked: throttle.enabled,
+                    style: "{checkbox_style}",
+                    onchange: move |evt| {
+                        let enabled = evt.checked();
+                        apply(&|throttle| throttle.enabled = enabled);
+                    },
+                }
+            }
+            p {
+                style: "
+                    margin: 0;
+                    color: var(--vscode-text-secondary);
+                    font-size: 12px;
+                    line-height: 1.4;
+                ",
+                {t!("settings.background.hint")}
+            }
+
+            if throttle.enabled {
+                label {
+                    style: "{field_style}",
+                    {t!("settings.background.pause_on_battery")}
+                    input {
+                        r#type: "checkbox",
+                        checked: throttle.pause_on_battery,
+                        style: "{checkbox_style}",
+                        onchange: move |evt| {
+                            let pause_on_battery = evt.checked();
+                            apply(&|throttle| throttle.pause_on_battery = pause_on_battery);
+                        },
+                    }
+                }
+                label {
+                    style: "{field_style}",
+                    {t!("settings.background.pause_when_unfocused")}
+                    input {
+                        r#type: "checkbox",
+                        checked: throttle.pause_when_unfocused,
+                        style: "{checkbox_style}",
+                        onchange: move |evt| {
+                            let pause_when_unfocused = evt.checked();
+                            apply(&|throttle| throttle.pause_when_unfocused = pause_when_unfocused);
+                        },
+                    }
+                }
+                label {
+                    style: "{field_style}",
+                    {t!("settings.background.quiet_hours")}
+                    input {
+                        r#type: "checkbox",
+                        checked: throttle.quiet_hours.is_some(),
+                        style: "{checkbox_style}",
+                        onchange: move |evt| {
+                            let quiet = evt.checked().then_some(quiet_hours);
+                            apply(&|throttle| throttle.quiet_hours = quiet);
+                        },
+                    }
+                }
+                if throttle.quiet_hours.is_some() {
+                    label {
+                        style: "{field_style}",
+                        {t!("settings.background.quiet_hours_from")}
+                        select {
+                            style: "{input_style}",
+                            onchange: move |evt| {
+                                let start_hour = evt.value().parse().unwrap_or(quiet_hours.start_hour);
+                                apply(&|throttle| throttle.quiet_hours = Some(QuietHours { start_hour, ..quiet_hours }));
+                            },
+                            for hour in 0..24u8 {
+                                option {
+                                    value: "{hour}",
+                                    selected: quiet_hours.start_hour == hour,
+                                    "{hour:02}:00"
+                                }
+                            }
+                        }
+                    }
+                    label {
+                        style: "{field_style}",
+                        {t!("settings.background.quiet_hours_until")}
+                        select {
+                            style: "{input_style}",
+                            onchange: move |evt| {
+                                let end_hour = evt.value().parse().unwrap_or(quiet_hours.end_hour);
+                                apply(&|throttle| throttle.quiet_hours = Some(QuietHours { end_hour, ..quiet_hours }));
+                            },
+                            for hour in 0..24u8 {
+                                option {
+                                    value: "{hour}",
+                                    selected: quiet_hours.end_hour == hour,
+                                    "{hour:02}:00"
+                                }
+                            }
+                        }
+                    }
+                }
+                label {
+                    style: "{field_style}",
+                    {t!("settings.background.mode")}
+                    select {
+                        style: "{input_style}",
+                        onchange: move |evt| {
+                            let mode = if evt.value() == "throttle" { ThrottleMode::Throttle } else { ThrottleMode::Pause };
+                            apply(&|throttle| throttle.mode = mode);
+                        },
+                        option {
+                            value: "pause",
+                            selected: throttle.mode == ThrottleMode::Pause,
+                            {t!("settings.background.mode_pause")}
+                        }
+                        option {
+                            value: "throttle",
+                            selected: throttle.mode == ThrottleMode::Throttle,
+                            {t!("settings.background.mode_throttle")}
+                        }
+                    }
+                }
+                if throttle.mode == ThrottleMode::Throttle {
+                    label {
+                        style: "{field_style}",
+                        {t!("settings.background.throttle_delay")}
+                        input {
+                            r#type: "number",
+                            min: "0",
+                            step: "50",
+                            value: "{throttle.throttle_delay_ms}",
+                            style: "{input_style}",
+                            onchange: move |evt| match evt.value().parse::<u64>() {
+                                Ok(delay) => apply(&|throttle| throttle.throttle_delay_ms = delay),
+                                Err(_) => error.set(Some(t!("settings.background.invalid", error = evt.value()))),
+                            },
+                        }
+                    }
+                }
+            }
+
+            if let Some(message) = error.read().clone() {
+                p {
+                    role: "alert",
+                    style: "margin: 0; font-size: 12px; color: var(--vscode-error);",
+                    "{message}"
+                }
+            }
+        }
+    }
+}
+
+/// Export the settings to a file, or import them from one
+#[component]
+fn SettingsTransfer(current_settings: Signal<SettingsState>, on_settings_change: EventHandler<SettingsState>) -> Element {
+    let mut app_state = use_app_state();
+    let mut status = use_signal(|| None::<(String, bool)>);
+
+    let snapshot = move || SettingsSnapshot {
+        settings: current_settings.peek().clone(),
+        keymap: app_state.keymap.peek().clone(),
+        bookmarks: app_state.bookmarks.peek().clone(),
+    };
+
+    rsx! {
+        div {
+            class: "setting-item",
+            style: "
+                display: flex;
+                flex-direction: column;
+                gap: 8px;
+                padding: 8px 0;
+            ",
+
+            label {
+                style: "
+                    color: var(--vscode-text-primary);
+                    font-size: 14px;
+                    font-weight: 500;
+                ",
+                {t!("settings.transfer.title")}
+            }
+            p {
+                style: "
+                    margin: 0;
+                    color: var(--vscode-text-secondary);
+                    font-size: 12px;
+                    line-height: 1.4;
+                ",
+                {t!("settings.transfer.hint")}
+            }
+
+            div {
+                style: "display: flex; gap: 8px;",
+                button {
+                    class: "btn btn-secondary",
+                    onclick: move |_| {
+                        let snapshot = snapshot();
+                        spawn(async move {
+                            let Some(handle) = rfd::AsyncFileDialog::new()
+                                .set_title(t!("settings.transfer.export"))
+                                .add_filter("JSON", &["json"])
+                                .set_file_name("media-organizer-settings.json")
+                                .save_file()
+                                .await
+                            else {
+                                return;
+                            };
+                            let path = handle.path().to_path_buf();
+                            status.set(Some(match export_settings(&path, &snapshot) {
+                                Ok(()) => (t!("settings.transfer.exported", path = path.display()), false),
+                                Err(e) => (t!("settings.transfer.export_failed", error = e), true),
+                            }));
+                        });
+                    },
+                    {t!("settings.transfer.export")}
+                }
+                button {
+                    class: "btn btn-secondary",
+                    onclick: move |_| {
+                        let current = snapshot();
+                        spawn(async move {
+                            let Some(handle) = rfd::AsyncFileDialog::new()
+                                .set_title(t!("settings.transfer.import"))
+                                .add_filter("JSON", &["json"])
+                                .pick_file()
+                                .await
+                            else {
+                                return;
+                            };
+                            match import_settings(handle.path(), &current) {
+                                Ok(imported) => {
+                                    app_state.keymap.set(imported.keymap);
+                                    app_state.bookmarks.set(imported.bookmarks);
+                                    on_settings_change.call(imported.settings);
+                                    status.set(Some((t!("settings.transfer.imported"), false)));
+                                }
+                                Err(e) => status.set(Some((t!("settings.transfer.import_failed", error = e), true))),
+                            }
+                        });
+                    },
+                    {t!("settings.transfer.import")}
+                }
+            }
+
+            if let Some((message, is_error)) = status.read().clone() {
+                p {
+                    role: if is_error { "alert" } else { "status" },
+                    style: if is_error {
+                        "margin: 0; font-size: 12px; color: var(--vscode-error);"
+                    } else {
+                        "margin: 0; font-size: 12px; color: var(--vscode-text-secondary);"
+                    },
+                    "{message}"
+                }
+            }
+        }
+    }
+}
+
+/// A chord the user picked that another action already uses
+#[derive(Clone, PartialEq)]
+struct PendingRebind {
+    action: ShortcutAction,
+    old: Option<KeyCombination>,
+    new: KeyCombination,
+    taken_from: ShortcutAction,
+}
+
+/// Rebind keyboard shortcuts; taking a chord from another action asks first
+#[component]
+fn KeymapSettings() -> Element {
+    let app_state = use_app_state();
+    let keymap_signal = app_state.keymap;
+    let mut pending = use_signal(|| None::<PendingRebind>);
+    let platform = Platform::current();
+
+    let apply = {
+        let mut app_state = app_state.clone();
+        EventHandler::new(move |(action, old, new): (ShortcutAction, Option<KeyCombination>, Option<KeyCombination>)| {
+            app_state.update_keymap(|keymap| rebind(keymap, &action, old.as_ref(), new));
+        })
+    };
+    // Reset one action by id, or every action
+    let reset = {
+        let mut app_state = app_state.clone();
+        EventHandler::new(move |action_id: Option<String>| {
+            app_state.update_keymap(|keymap| match action_id {
+                Some(id) => {
+                    keymap.reset(&id);
+                }
+                None => keymap.reset_all(),
+            });
+        })
+    };
+    let mut request = move |action: ShortcutAction, old: Option<KeyCombination>, new: KeyCombination| {
+        if old.as_ref() == Some(&new) {
+            return;
+        }
+        let taken_from = action_for_chord(&keymap_signal.peek(), &new, &action);
+        match taken_from {
+            Some(taken_from) => pending.set(Some(PendingRebind { action, old, new, taken_from })),
+            None => {
+                pending.set(None);
+                apply.call((action, old, Some(new)));
+            }
+        }
+    };
+
+    let keymap = keymap_signal.read().clone();
+    let conflicts = find_conflicts(&keymap);
+    let chord_text = move |combo: &KeyCombination| combo.display_keys(platform).join("+");
+    let rows: Vec<(ShortcutAction, Vec<KeyCombination>, bool, bool)> = remappable_actions()
+        .into_iter()
+        .map(|action| {
+            let chords = chords_for_action(&keymap, &action);
+            let customized = keymap.overrides(&action.id()).is_some();
+            let conflicting = conflicts.iter().any(|conflict| conflict.actions.contains(&action));
+            (action, chords, customized, conflicting)
+        })
+        .collect();
+    let conflict_messages: Vec<String> = conflicts
+        .iter()
+        .map(|conflict| {
+            t!(
+                "settings.keymap.conflict",
+                chord = chord_text(&conflict.combo),
+                action = conflict.actions[0].description()
+            )
+        })
+        .collect();
+
+    rsx! {
+        div { class: "keymap-settings",
+            p { class: "keymap-hint", {t!("settings.keymap.hint")} }
+
+            if let Some(rebinding) = pending() {
+                div { class: "keymap-conflict", role: "alert",
+                    span {
+                        {t!(
+                            "settings.keymap.in_use",
+                            chord = chord_text(&rebinding.new),
+                            action = rebinding.taken_from.description()
+                        )}
+                    }
+                    button {
+                        class: "btn btn-primary",
+                        onclick: move |_| {
+                            let Some(rebinding) = pending.take() else { return };
+                            apply.call((rebinding.taken_from, Some(rebinding.new.clone()), None));
+                            apply.call((rebinding.action, rebinding.old, Some(rebinding.new)));
+                        },
+                        {t!("settings.keymap.reassign")}
+                    }
+                    button {
+                        class: "btn btn-secondary",
+                        onclick: move |_| pending.set(None),
+                        {t!("settings.keymap.cancel")}
+                    }
+                }
+            }
+
+            for (index, message) in conflict_messages.into_iter().enumerate() {
+                div { key: "conflict-{index}", class: "keymap-conflict", role: "status", "{message}" }
+            }
+
+            for (action, chords, customized, conflicting) in rows {
+                div {
+                    key: "{action.id()}",
+                    class: if conflicting { "keymap-row conflict" } else { "keymap-row" },
+                    span { class: "keymap-action", "{action.description()}" }
+                    div { class: "keymap-chords",
+                        for chord in chords {
+                            ShortcutRecorder {
+                                key: "{chord.accelerator()}",
+                                value: Some(chord.clone()),
+                                label: t!("settings.keymap.chord_label", action = action.description()),
+                                on_change: {
+                                    let action = action.clone();
+                                    let old = chord.clone();
+                                    move |combo| request(action.clone(), Some(old.clone()), combo)
+                                },
+                                on_clear: {
+                                    let action = action.clone();
+                                    let old = chord.clone();
+                                    move |_| apply.call((action.clone(), Some(old.clone()), None))
+                                },
+                            }
+                        }
+                        ShortcutRecorder {
+                            value: None,
+                            label: t!("settings.keymap.add_label", action = action.description()),
+                            on_change: {
+                                let action = action.clone();
+                                move |combo| request(action.clone(), None, combo)
+                            },
+                        }
+                    }
+                    if customized {
+                        button {
+                            class: "btn btn-secondary",
+                            onclick: {
+                                let id = action.id();
+                                move |_| reset.call(Some(id.clone()))
+                            },
+                            {t!("settings.keymap.reset")}
+                        }
+                    }
+                }
+            }
+
+            button {
+                class: "btn btn-secondary",
+                disabled: !keymap.is_customized(),
+                onclick: move |_| {
+                    pending.set(None);
+                    reset.call(None);
+                },
+                {t!("settings.keymap.reset_all")}
+            }
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use dioxus::prelude::*;
-use crate::state::{SettingsState, Theme, ViewMode, FontFamily, FontSize, save_settings_debounced};
+use crate::state::{use_app_state, SettingsState, Theme, ViewMode, FontFamily, FontSize, save_settings_debounced};
 use crate::theme::{ThemeManager, ThemeSelector, EnhancedThemeSelector};
-use crate::ui::shortcuts::ShortcutRegistry;
+use crate::ui::shortcuts::keymap_bindings;
 use crate::ui::components::IconPackManager;
 use std::collections::HashMap;
 
@@ -328,6 +328,7 @@ fn GeneralSettingsTab(
                                 let view_mode = match evt.value().as_str() {
                                     "list" => ViewMode::List,
                                     "preview" => ViewMode::Preview,
+                                    "columns" => ViewMode::Columns,
                                     _ => ViewMode::Grid,
                                 };
                                 settings.write().default_view_mode = view_mode;
@@ -337,6 +338,7 @@ fn GeneralSettingsTab(
                             option { value: "grid", "Grid View" }
                             option { value: "list", "List View" }
                             option { value: "preview", "Preview Mode" }
+                            option { value: "columns", "Column View" }
                         }
                     }
                 }
@@ -599,7 +601,8 @@ fn AppearanceSettingsTab(
 /// Keyboard shortcuts tab content
 #[component]
 fn KeyboardSettingsTab() -> Element {
-    let shortcuts = ShortcutRegistry::new().get_all_shortcuts();
+    let app_state = use_app_state();
+    let shortcuts = keymap_bindings(&app_state.keymap.read());
 
     rsx! {
         div {
@@ -945,6 +948,7 @@ impl ViewMode {
             ViewMode::Grid => "grid",
             ViewMode::List => "list",
             ViewMode::Preview => "preview",
+            ViewMode::Columns => "columns",
         }
     }
 }
\ No newline at end of file
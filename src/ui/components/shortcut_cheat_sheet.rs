@@ -1,7 +1,8 @@
 use dioxus::prelude::*;
 use dioxus::events::KeyboardEvent;
 use crate::state::app_state::CommandRegistry;
-use crate::ui::shortcuts::{ShortcutRegistry, KeyCombination};
+use crate::ui::shortcuts::{ShortcutRegistry, KeyCombination, Platform};
+use crate::ui::components::ShortcutRecorder;
 use std::collections::HashMap;
 
 /// Shortcut cheat sheet component that displays all available keyboard shortcuts
@@ -15,6 +16,8 @@ pub fn ShortcutCheatSheet(
     /// Command registry for getting command information
     command_registry: Signal<CommandRegistry>,
 ) -> Element {
+    let mut key_filter = use_signal(|| None::<KeyCombination>);
+
     if !is_visible {
         return rsx! { div {} };
     }
@@ -23,7 +26,17 @@ pub fn ShortcutCheatSheet(
     let shortcut_handler = crate::ui::use_shortcut_handler();
     
     // Get all shortcuts organized by category (computed fresh each render for now)
-    let shortcut_categories = get_shortcuts_by_category(&command_registry.read(), &shortcut_handler.registry);
+    let mut shortcut_categories = get_shortcuts_by_category(&command_registry.read(), &shortcut_handler.registry);
+
+    // Pressing a chord in the recorder narrows the list to what it triggers
+    if let Some(filter) = key_filter() {
+        let filter_keys = get_platform_aware_keys(&filter);
+        for shortcuts in shortcut_categories.values_mut() {
+            shortcuts.retain(|shortcut| shortcut.display_keys == filter_keys);
+        }
+        shortcut_categories.retain(|_, shortcuts| !shortcuts.is_empty());
+    }
+    let no_matches = key_filter().is_some() && shortcut_categories.is_empty();
 
     rsx! {
         div {
@@ -63,6 +76,21 @@ pub fn ShortcutCheatSheet(
                         class: "cheat-sheet-title", 
                         "Keyboard Shortcuts" 
                     }
+                    div { class: "cheat-sheet-filter",
+                        ShortcutRecorder {
+                            value: key_filter(),
+                            label: t!("shortcut_recorder.find_label"),
+                            on_change: move |combo| key_filter.set(Some(combo)),
+                            on_clear: move |_| key_filter.set(None),
+                        }
+                        if key_filter().is_some() {
+                            button {
+                                class: "button",
+                                onclick: move |_| key_filter.set(None),
+                                {t!("shortcut_recorder.show_all")}
+                            }
+                        }
+                    }
                     button {
                         class: "cheat-sheet-close-button",
                         "aria-label": "Close keyboard shortcuts cheat sheet",
@@ -91,6 +119,9 @@ pub fn ShortcutCheatSheet(
                     class: "cheat-sheet-content",
                     role: "main",
                     "aria-label": "List of keyboard shortcuts organized by category",
+                    if no_matches {
+                        p { class: "cheat-sheet-empty", {t!("shortcut_recorder.no_matches")} }
+                    }
                     for (category, shortcuts) in shortcut_categories.iter() {
                         ShortcutCategory {
                             category_name: category.clone(),
@@ -263,13 +294,17 @@ fn get_category_for_action(action: &crate::ui::shortcuts::ShortcutAction) -> Str
     
     match action {
         ShortcutAction::Copy | ShortcutAction::Paste | ShortcutAction::Cut 
-        | ShortcutAction::Delete | ShortcutAction::Rename => "File Operations".to_string(),
+        | ShortcutAction::Delete | ShortcutAction::DeletePermanently
+        | ShortcutAction::Rename => "File Operations".to_string(),
         
-        ShortcutAction::SelectAll | ShortcutAction::ClearSelection => "Selection".to_string(),
+        ShortcutAction::SelectAll | ShortcutAction::ClearSelection
+        | ShortcutAction::ExtendSelectionUp | ShortcutAction::ExtendSelectionDown => "Selection".to_string(),
         
         ShortcutAction::NavigateUp | ShortcutAction::NavigateBack 
         | ShortcutAction::NavigateForward | ShortcutAction::NavigateHome 
-        | ShortcutAction::Refresh => "Navigation".to_string(),
+        | ShortcutAction::Refresh | ShortcutAction::NewDirectoryTab
+        | ShortcutAction::CloseTab | ShortcutAction::SwitchDirectoryTab(_)
+        | ShortcutAction::BookmarkCurrentFolder => "Navigation".to_string(),
         
         ShortcutAction::OpenFile | ShortcutAction::NewFolder => "File Management".to_string(),
         
@@ -279,7 +314,8 @@ fn get_category_for_action(action: &crate::ui::shortcuts::ShortcutAction) -> Str
         
         ShortcutAction::FocusExplorer | ShortcutAction::FocusEditor1 
         | ShortcutAction::FocusEditor2 | ShortcutAction::FocusEditor3 
-        | ShortcutAction::CloseTab | ShortcutAction::SwitchTab => "Editor".to_string(),
+        | ShortcutAction::ReopenClosedTab
+        | ShortcutAction::SwitchTab => "Editor".to_string(),
         
         ShortcutAction::ShowSettings | ShortcutAction::ShowCommandPalette 
         | ShortcutAction::ShowShortcutCheatSheet | ShortcutAction::ToggleHighContrast => "Application".to_string(),
@@ -290,54 +326,7 @@ fn get_category_for_action(action: &crate::ui::shortcuts::ShortcutAction) -> Str
 
 /// Convert key combination to platform-aware display keys
 fn get_platform_aware_keys(key_combo: &KeyCombination) -> Vec<String> {
-    let mut keys = Vec::new();
-    
-    // Add modifier keys in standard order
-    if key_combo.ctrl {
-        #[cfg(target_os = "macos")]
-        keys.push("⌘".to_string()); // Command symbol on macOS
-        #[cfg(not(target_os = "macos"))]
-        keys.push("Ctrl".to_string());
-    }
-    
-    if key_combo.shift {
-        #[cfg(target_os = "macos")]
-        keys.push("⇧".to_string()); // Shift symbol on macOS
-        #[cfg(not(target_os = "macos"))]
-        keys.push("Shift".to_string());
-    }
-    
-    if key_combo.alt {
-        #[cfg(target_os = "macos")]
-        keys.push("⌥".to_string()); // Option symbol on macOS
-        #[cfg(not(target_os = "macos"))]
-        keys.push("Alt".to_string());
-    }
-    
-    if key_combo.meta {
-        #[cfg(target_os = "macos")]
-        keys.push("⌃".to_string()); // Control symbol on macOS (rarely used)
-        #[cfg(not(target_os = "macos"))]
-        keys.push("Win".to_string());
-    }
-    
-    // Add the main key, with special formatting for certain keys
-    let main_key = match key_combo.key.as_str() {
-        " " => "Space".to_string(),
-        "Enter" => "↵".to_string(),
-        "Tab" => "⇥".to_string(),
-        "Escape" => "Esc".to_string(),
-        "ArrowUp" => "↑".to_string(),
-        "ArrowDown" => "↓".to_string(),
-        "ArrowLeft" => "←".to_string(),
-        "ArrowRight" => "→".to_string(),
-        "Delete" => "⌦".to_string(),
-        "Backspace" => "⌫".to_string(),
-        key => key.to_uppercase(),
-    };
-    
-    keys.push(main_key);
-    keys
+    key_combo.display_keys(Platform::current())
 }
 
 /// Parse a shortcut string into a KeyCombination (simplified parser)
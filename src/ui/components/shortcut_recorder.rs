@@ -0,0 +1,154 @@
+// Shortcut recorder
+// A focusable field that captures the next key chord, shows it with the platform's key names and reports it normalized
+
+use dioxus::events::KeyboardEvent;
+use dioxus::prelude::*;
+
+use crate::ui::shortcuts::{ChordError, KeyChordEvent, KeyCombination, Platform};
+
+/// Capture a keyboard shortcut
+///
+/// While focused every key press is swallowed so it cannot trigger the chord
+/// being recorded. Held modifiers are previewed until a key completes the
+/// chord; Escape gives up recording and Backspace clears the value.
+#[component]
+pub fn ShortcutRecorder(
+    /// Currently assigned shortcut, if any
+    value: Option<KeyCombination>,
+    /// Called with each accepted chord
+    on_change: EventHandler<KeyCombination>,
+    /// Called when Backspace clears the shortcut
+    on_clear: Option<EventHandler<()>>,
+    /// Accessible name of the field
+    #[props(default)]
+    label: String,
+) -> Element {
+    let platform = Platform::current();
+    let mut recording = use_signal(|| false);
+    let mut held = use_signal(|| None::<KeyCombination>);
+    let mut error = use_signal(|| None::<ChordError>);
+
+    let on_keydown = move |e: KeyboardEvent| {
+        if !recording() {
+            if matches!(e.key().to_string().as_str(), "Enter" | " ") {
+                e.prevent_default();
+                recording.set(true);
+            }
+            return;
+        }
+        e.prevent_default();
+        e.stop_propagation();
+
+        let modifiers = e.modifiers();
+        let event = KeyChordEvent {
+            key: e.key().to_string(),
+            code: e.code().to_string(),
+            ctrl: modifiers.ctrl(),
+            shift: modifiers.shift(),
+            alt: modifiers.alt(),
+            meta: modifiers.meta(),
+        };
+        let unmodified = !(event.ctrl || event.shift || event.alt || event.meta);
+        if unmodified && event.key == "Escape" {
+            recording.set(false);
+            held.set(None);
+            error.set(None);
+            return;
+        }
+        if unmodified && event.key == "Backspace" {
+            if let Some(on_clear) = on_clear {
+                on_clear.call(());
+                recording.set(false);
+                error.set(None);
+                return;
+            }
+        }
+
+        match event.normalize(platform) {
+            Ok(combo) => {
+                held.set(None);
+                error.set(None);
+                recording.set(false);
+                on_change.call(combo);
+            }
+            Err(ChordError::ModifierOnly) => {
+                let (ctrl, meta) = match platform {
+                    Platform::Mac => (event.meta, event.ctrl),
+                    Platform::Other => (event.ctrl, event.meta),
+                };
+                held.set(Some(KeyCombination {
+                    key: String::new(),
+                    ctrl,
+                    shift: event.shift,
+                    alt: event.alt,
+                    meta,
+                }));
+            }
+            Err(e) => {
+                held.set(None);
+                error.set(Some(e));
+            }
+        }
+    };
+
+    let keys = if recording() {
+        held().map(|combo| {
+            let mut keys = combo.display_keys(platform);
+            keys.pop();
+            keys
+        })
+    } else {
+        value.as_ref().map(|combo| combo.display_keys(platform))
+    };
+    let placeholder = if recording() {
+        t!("shortcut_recorder.listening")
+    } else {
+        t!("shortcut_recorder.placeholder")
+    };
+    let error_text = error().map(|e| match e {
+        ChordError::ModifierOnly => t!("shortcut_recorder.modifier_only"),
+        ChordError::Unidentified => t!("shortcut_recorder.unidentified"),
+        ChordError::NeedsModifier => t!("shortcut_recorder.needs_modifier"),
+        ChordError::Reserved(chord) => t!("shortcut_recorder.reserved", chord = chord),
+    });
+
+    rsx! {
+        div { class: "shortcut-recorder-field",
+            div {
+                class: if recording() { "shortcut-recorder recording" } else { "shortcut-recorder" },
+                role: "button",
+                tabindex: "0",
+                "aria-label": "{label}",
+                "aria-pressed": "{recording()}",
+                "aria-invalid": "{error_text.is_some()}",
+                onclick: move |_| recording.set(true),
+                onblur: move |_| {
+                    recording.set(false);
+                    held.set(None);
+                },
+                onkeydown: on_keydown,
+                onkeyup: move |_| {
+                    if recording() {
+                        held.set(None);
+                    }
+                },
+                match keys.filter(|keys| !keys.is_empty()) {
+                    Some(keys) => rsx! {
+                        for (i, key_part) in keys.iter().enumerate() {
+                            if i > 0 {
+                                span { class: "key-separator", " + " }
+                            }
+                            kbd { class: "key", "{key_part}" }
+                        }
+                    },
+                    None => rsx! {
+                        span { class: "shortcut-recorder-placeholder", "{placeholder}" }
+                    },
+                }
+            }
+            if let Some(text) = error_text {
+                div { class: "shortcut-recorder-error", role: "alert", "{text}" }
+            }
+        }
+    }
+}
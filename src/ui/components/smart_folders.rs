@@ -0,0 +1,476 @@
+// Smart folders
+// Saved searches listed in the sidebar, their editor, and the read-only listing of their matches
+
+use chrono::{Local, NaiveDate, NaiveTime, TimeZone};
+use dioxus::prelude::*;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use crate::services::announcer::Politeness;
+use crate::services::classifier::classifier;
+use crate::services::file_system::FileEntry;
+use crate::services::operations::{CancellationToken, Command, DeleteCommand, MoveCommand, OperationResult};
+use crate::services::progress_hub::TaskKind;
+use crate::services::smart_folders::{scan_smart_folder, SearchFilters, SmartFolder, SmartFolderError};
+use crate::state::persistence::save_settings_debounced;
+use crate::state::{use_app_state, AppState, SettingsState};
+use crate::ui::components::{modal_title_id, Modal};
+use crate::utils::format_size;
+
+/// Rows rendered at once; the count still includes every match
+const MAX_VISIBLE_ROWS: usize = 2000;
+
+/// Matches received from the scan are appended in batches of this size
+const MATCH_BATCH_SIZE: usize = 256;
+
+const BYTES_PER_MB: f64 = 1024.0 * 1024.0;
+
+/// Sidebar section listing the saved smart folders
+#[component]
+pub fn SmartFolderList(settings: Signal<SettingsState>) -> Element {
+    let app_state = use_app_state();
+    let mut open_smart_folder = app_state.open_smart_folder;
+    let mut editor_visible = use_signal(|| false);
+    let folders = settings.read().smart_folders.clone();
+
+    rsx! {
+        div {
+            class: "smart-folder-list",
+            role: "group",
+            "aria-label": t!("smart_folders.title"),
+            div {
+                class: "smart-folder-header",
+                span { {t!("smart_folders.title")} }
+                button {
+                    class: "button",
+                    title: t!("smart_folders.new"),
+                    "aria-label": t!("smart_folders.new"),
+                    onclick: move |_| editor_visible.set(true),
+                    "+"
+                }
+            }
+            for folder in folders {
+                {
+                    let id = folder.id;
+                    let open = folder.clone();
+                    rsx! {
+                        div {
+                            key: "{id}",
+                            class: "smart-folder-item",
+                            button {
+                                class: "smart-folder-name",
+                                title: "{folder.root.display()}",
+                                onclick: move |_| open_smart_folder.set(Some(open.clone())),
+                                "🔍 {folder.name}"
+                            }
+                            button {
+                                class: "smart-folder-remove",
+                                title: t!("smart_folders.remove"),
+                                "aria-label": t!("smart_folders.remove"),
+                                onclick: move |_| {
+                                    settings.write().smart_folders.retain(|folder| folder.id != id);
+                                    save_settings_debounced(settings.peek().clone());
+                                },
+                                "×"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        if *editor_visible.read() {
+            SmartFolderEditor {
+                on_save: move |folder: SmartFolder| {
+                    settings.write().smart_folders.push(folder.clone());
+                    save_settings_debounced(settings.peek().clone());
+                    editor_visible.set(false);
+                    open_smart_folder.set(Some(folder));
+                },
+                on_close: move |_| editor_visible.set(false),
+            }
+        }
+    }
+}
+
+/// Dialog for naming a new smart folder and choosing its filters
+#[component]
+fn SmartFolderEditor(on_save: EventHandler<SmartFolder>, on_close: EventHandler<()>) -> Element {
+    let app_state = use_app_state();
+    let current_path = app_state.navigation.read().current_path.display().to_string();
+    let mut name = use_signal(String::new);
+    let mut root = use_signal(move || current_path);
+    let mut category = use_signal(String::new);
+    let mut min_size = use_signal(String::new);
+    let mut max_size = use_signal(String::new);
+    let mut modified_from = use_signal(String::new);
+    let mut modified_to = use_signal(String::new);
+    let mut tag = use_signal(String::new);
+    let mut min_rating = use_signal(|| 0u8);
+    let mut include_hidden = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+
+    let save = move |_| {
+        let folder_name = name.read().trim().to_string();
+        let folder_root = PathBuf::from(root.read().trim());
+        if folder_name.is_empty() {
+            error.set(Some(t!("smart_folders.name_required")));
+            return;
+        }
+        if !folder_root.is_dir() {
+            error.set(Some(t!("smart_folders.root_missing")));
+            return;
+        }
+        let filters = SearchFilters {
+            file_types: Some(category.read().clone()).filter(|c| !c.is_empty()).into_iter().collect(),
+            size_range: size_range(&min_size.read(), &max_size.read()),
+            modified_range: date_range(&modified_from.read(), &modified_to.read()),
+            include_hidden: *include_hidden.read(),
+            tag: Some(tag.read().trim().to_string()).filter(|t| !t.is_empty()),
+            min_rating: Some(*min_rating.read()).filter(|r| *r > 0),
+        };
+        on_save.call(SmartFolder::new(folder_name, folder_root, filters));
+    };
+
+    let dialog_id = "smart-folder-editor";
+
+    rsx! {
+        Modal {
+            id: "{dialog_id}",
+            class: "smart-folder-dialog",
+            on_close: move |_| on_close.call(()),
+
+            div {
+                class: "dialog-header",
+                h3 { id: modal_title_id(dialog_id), {t!("smart_folders.new")} }
+            }
+
+            div {
+                class: "dialog-content",
+                div {
+                    class: "log-filters",
+                    label {
+                        {t!("smart_folders.name")}
+                        input { r#type: "text", value: "{name}", oninput: move |evt| name.set(evt.value()) }
+                    }
+                    label {
+                        {t!("smart_folders.root")}
+                        input {
+                            r#type: "text",
+                            value: "{root}",
+                            style: "min-width: 260px;",
+                            oninput: move |evt| root.set(evt.value()),
+                        }
+                    }
+                    label {
+                        {t!("smart_folders.category")}
+                        select {
+                            onchange: move |evt| category.set(evt.value()),
+                            option { value: "", {t!("smart_folders.any")} }
+                            for c in classifier().categories() {
+                                option { value: "{c}", selected: *category.read() == c, "{c}" }
+                            }
+                        }
+                    }
+                    label {
+                        {t!("smart_folders.min_size")}
+                        input { r#type: "number", min: "0", value: "{min_size}", oninput: move |evt| min_size.set(evt.value()) }
+                    }
+                    label {
+                        {t!("smart_folders.max_size")}
+                        input { r#type: "number", min: "0", value: "{max_size}", oninput: move |evt| max_size.set(evt.value()) }
+                    }
+                    label {
+                        {t!("smart_folders.modified_from")}
+                        input { r#type: "date", value: "{modified_from}", oninput: move |evt| modified_from.set(evt.value()) }
+                    }
+                    label {
+                        {t!("smart_folders.modified_to")}
+                        input { r#type: "date", value: "{modified_to}", oninput: move |evt| modified_to.set(evt.value()) }
+                    }
+                    label {
+                        {t!("smart_folders.tag")}
+                        input { r#type: "text", value: "{tag}", oninput: move |evt| tag.set(evt.value()) }
+                    }
+                    label {
+                        {t!("smart_folders.min_rating")}
+                        select {
+                            onchange: move |evt| min_rating.set(evt.value().parse().unwrap_or(0)),
+                            option { value: "0", {t!("smart_folders.any")} }
+                            for stars in 1..=5u8 {
+                                option { value: "{stars}", selected: *min_rating.read() == stars, {"★".repeat(stars as usize)} }
+                            }
+                        }
+                    }
+                    label {
+                        {t!("smart_folders.include_hidden")}
+                        input {
+                            r#type: "checkbox",
+                            checked: *include_hidden.read(),
+                            onchange: move |evt| include_hidden.set(evt.checked()),
+                        }
+                    }
+                }
+
+                if let Some(message) = error.read().as_ref() {
+                    p { class: "dialog-message", role: "alert", {message.clone()} }
+                }
+            }
+
+            div {
+                class: "dialog-actions",
+                button { class: "button", onclick: move |_| on_close.call(()), {t!("dialog.cancel")} }
+                button { class: "button primary", onclick: save, {t!("smart_folders.save")} }
+            }
+        }
+    }
+}
+
+/// Size bounds in bytes from two optional MB fields
+fn size_range(min: &str, max: &str) -> Option<(u64, u64)> {
+    let parse = |value: &str| value.trim().parse::<f64>().ok().map(|mb| (mb * BYTES_PER_MB) as u64);
+    match (parse(min), parse(max)) {
+        (None, None) => None,
+        (min, max) => Some((min.unwrap_or(0), max.unwrap_or(u64::MAX))),
+    }
+}
+
+/// Modification bounds from two optional `YYYY-MM-DD` fields, covering whole local days
+fn date_range(from: &str, to: &str) -> Option<(SystemTime, SystemTime)> {
+    let parse = |value: &str, time: NaiveTime| {
+        let date = NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").ok()?;
+        Local.from_local_datetime(&date.and_time(time)).earliest().map(SystemTime::from)
+    };
+    let end_of_day = NaiveTime::from_hms_opt(23, 59, 59).unwrap_or(NaiveTime::MIN);
+    match (parse(from, NaiveTime::MIN), parse(to, end_of_day)) {
+        (None, None) => None,
+        (from, to) => Some((from.unwrap_or(SystemTime::UNIX_EPOCH), to.unwrap_or_else(far_future))),
+    }
+}
+
+fn far_future() -> SystemTime {
+    SystemTime::now() + std::time::Duration::from_secs(100 * 365 * 24 * 60 * 60)
+}
+
+/// Read-only listing of the files matching the open smart folder
+#[component]
+pub fn SmartFolderResults() -> Element {
+    let app_state = use_app_state();
+    let mut open_smart_folder = app_state.open_smart_folder;
+    let mut entries = use_signal(Vec::<FileEntry>::new);
+    let mut running = use_signal(|| None::<CancellationToken>);
+    let mut status = use_signal(|| None::<String>);
+    // Bumped by the re-run button; the scan effect reads it
+    let mut generation = use_signal(|| 0u32);
+
+    use_drop(move || {
+        if let Some(token) = running.peek().as_ref() {
+            token.cancel();
+        }
+    });
+
+    let progress_hub = app_state.progress_hub.clone();
+    let tag_store = app_state.tag_store.clone();
+    use_effect(move || {
+        generation.read();
+        let Some(folder) = open_smart_folder.read().clone() else {
+            return;
+        };
+        if let Some(previous) = running.peek().as_ref() {
+            previous.cancel();
+        }
+
+        let token = CancellationToken::new();
+        entries.set(Vec::new());
+        running.set(Some(token.clone()));
+        status.set(Some(t!("smart_folders.scanning")));
+
+        let progress_hub = progress_hub.clone();
+        let tag_store = tag_store.clone();
+        spawn(async move {
+            let hud_task = progress_hub.register(folder.name.clone(), TaskKind::Other, {
+                let token = token.clone();
+                Some(Arc::new(move || token.cancel()))
+            });
+
+            let (entry_tx, mut entry_rx) = tokio::sync::mpsc::unbounded_channel();
+            let scan_token = token.clone();
+            let scan = tokio::task::spawn_blocking(move || {
+                scan_smart_folder(&folder, &scan_token, |path| tag_store.tags_of(path), |entry| {
+                    let _ = entry_tx.send(entry);
+                })
+            });
+
+            // Batching keeps a large tree from re-rendering once per match
+            let mut batch = Vec::with_capacity(MATCH_BATCH_SIZE);
+            while entry_rx.recv_many(&mut batch, MATCH_BATCH_SIZE).await > 0 {
+                if token.is_cancelled() {
+                    batch.clear();
+                    continue;
+                }
+                entries.write().append(&mut batch);
+            }
+            drop(hud_task);
+
+            let message = match scan.await {
+                Ok(Ok(count)) => t!("smart_folders.found", count = count),
+                // A newer scan replaced this one and reports for itself
+                Ok(Err(SmartFolderError::Cancelled)) => return,
+                Ok(Err(e)) => t!("smart_folders.failed", error = e),
+                Err(e) => t!("smart_folders.failed", error = e),
+            };
+            running.set(None);
+            status.set(Some(message));
+        });
+    });
+
+    let Some(folder) = open_smart_folder.read().clone() else {
+        return rsx! {};
+    };
+    let dialog_id = "smart-folder-results";
+    let is_running = running.read().is_some();
+    let match_count = entries.read().len();
+
+    rsx! {
+        Modal {
+            id: "{dialog_id}",
+            class: "smart-folder-dialog",
+            on_close: move |_| open_smart_folder.set(None),
+
+            div {
+                class: "dialog-header",
+                h3 { id: modal_title_id(dialog_id), "🔍 {folder.name}" }
+            }
+
+            div {
+                class: "dialog-content",
+                p { class: "dialog-message", "{folder.root.display()}" }
+                if let Some(message) = status.read().as_ref() {
+                    p { class: "dialog-message", role: "status", {message.clone()} }
+                }
+
+                div {
+                    class: "diff-tree",
+                    role: "list",
+                    "aria-label": "{folder.name}",
+                    for (index, entry) in entries.read().iter().take(MAX_VISIBLE_ROWS).enumerate() {
+                        {
+                            let relative = entry.path.strip_prefix(&folder.root).unwrap_or(&entry.path).display().to_string();
+                            let path = entry.path.clone();
+                            let move_path = entry.path.clone();
+                            let app_state = app_state.clone();
+                            let move_state = app_state.clone();
+                            rsx! {
+                                div {
+                                    key: "{index}-{relative}",
+                                    class: "diff-row",
+                                    role: "listitem",
+                                    span { class: "diff-name", title: "{relative}", "📄 {relative}" }
+                                    span { class: "diff-sizes", {format_size(entry.size)} }
+                                    span {
+                                        class: "diff-actions",
+                                        button {
+                                            class: "button",
+                                            onclick: move |_| {
+                                                let source = move_path.clone();
+                                                let app_state = move_state.clone();
+                                                spawn(async move {
+                                                    let Some(target) = rfd::AsyncFileDialog::new().pick_folder().await else {
+                                                        return;
+                                                    };
+                                                    let Some(file_name) = source.file_name() else {
+                                                        return;
+                                                    };
+                                                    let destination = target.path().join(file_name);
+                                                    let command = Box::new(MoveCommand::new(source.clone(), destination));
+                                                    apply_to_result(&app_state, command, source, entries, status).await;
+                                                });
+                                            },
+                                            {t!("smart_folders.move")}
+                                        }
+                                        button {
+                                            class: "button",
+                                            onclick: move |_| {
+                                                let source = path.clone();
+                                                let app_state = app_state.clone();
+                                                spawn(async move {
+                                                    let command = Box::new(DeleteCommand::new(source.clone()));
+                                                    apply_to_result(&app_state, command, source, entries, status).await;
+                                                });
+                                            },
+                                            {t!("smart_folders.delete")}
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                if match_count > MAX_VISIBLE_ROWS {
+                    p { class: "dialog-message", {t!("smart_folders.truncated", count = MAX_VISIBLE_ROWS)} }
+                }
+            }
+
+            div {
+                class: "dialog-actions",
+                if is_running {
+                    button {
+                        class: "button",
+                        onclick: move |_| {
+                            if let Some(token) = running.read().as_ref() {
+                                token.cancel();
+                            }
+                            running.set(None);
+                            status.set(Some(t!("smart_folders.cancelled")));
+                        },
+                        {t!("smart_folders.cancel")}
+                    }
+                } else {
+                    button {
+                        class: "button",
+                        onclick: move |_| *generation.write() += 1,
+                        {t!("smart_folders.rerun")}
+                    }
+                }
+                button {
+                    class: "button primary",
+                    onclick: move |_| open_smart_folder.set(None),
+                    {t!("dialog.close")}
+                }
+            }
+        }
+    }
+}
+
+/// Run `command` on a listed file, record it for undo, and drop the file from the listing
+async fn apply_to_result(
+    app_state: &AppState,
+    command: Box<dyn Command>,
+    path: PathBuf,
+    mut entries: Signal<Vec<FileEntry>>,
+    mut status: Signal<Option<String>>,
+) {
+    let name = path.display().to_string();
+    let (message, politeness) = match execute_recorded(app_state, command).await {
+        Ok(()) => {
+            entries.write().retain(|entry| entry.path != path);
+            (t!("smart_folders.action_done", path = name), Politeness::Polite)
+        }
+        Err(e) => (t!("smart_folders.action_failed", path = name, error = e), Politeness::Assertive),
+    };
+    app_state.announcer.announce(message.clone(), politeness);
+    status.set(Some(message));
+}
+
+async fn execute_recorded(app_state: &AppState, mut command: Box<dyn Command>) -> OperationResult<()> {
+    let result = command.execute(app_state.file_service.clone()).await;
+    let mut history = app_state.operation_history.lock().await;
+    match result {
+        Ok(()) => history.add_executed_command(command).await,
+        Err(e) => {
+            history.log_failed_command(command.as_ref(), &e);
+            Err(e)
+        }
+    }
+}
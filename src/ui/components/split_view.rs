@@ -0,0 +1,257 @@
+// Split view
+// Two folders side by side; files dragged from one pane into the other are moved, or copied with Alt or Ctrl
+
+use dioxus::prelude::*;
+
+use crate::services::FileEntry;
+use crate::state::app_state::EditorLayoutConfig;
+use crate::state::directory_tabs::title_for;
+use crate::state::navigation::SelectionMode;
+use crate::state::split_view::PaneSide;
+use crate::state::{use_app_state, AppState};
+use crate::ui::components::drag_drop::{
+    drag_files_for, run_drop, selected_entries, update_drag_operation, DragOperation, DragState, DropZoneState,
+};
+
+/// Move or copy `files` into the folder shown by the `target` pane and reload both panes
+async fn drop_into_pane(mut app_state: AppState, files: Vec<FileEntry>, operation: DragOperation, target: PaneSide) {
+    let path = app_state.split_view.peek().pane(target).current_path().to_path_buf();
+    let Some(result) = run_drop(&app_state, &files, operation, &path).await else {
+        return;
+    };
+    if result.succeeded.is_empty() {
+        return;
+    }
+    app_state.refresh_split_panes().await;
+    // The file list may show either folder too
+    if let Err(e) = app_state.refresh_current_directory().await {
+        tracing::warn!("Error refreshing directory after split view drop: {}", e);
+    }
+}
+
+/// Two independent file panes laid out like a split editor group
+#[component]
+pub fn SplitView(drag_state: Signal<DragState>) -> Element {
+    let app_state = use_app_state();
+    let (vertical, positions) = {
+        let split_view = app_state.split_view.read();
+        (*split_view.layout() == EditorLayoutConfig::SplitVertical, split_view.positions())
+    };
+    let mut orientation_state = app_state.split_view;
+    let close_state = app_state.clone();
+
+    rsx! {
+        div {
+            class: if vertical { "split-view vertical" } else { "split-view horizontal" },
+            role: "group",
+            "aria-label": t!("split_view.label"),
+            div {
+                class: "split-view-toolbar",
+                button {
+                    r#type: "button",
+                    title: t!("split_view.toggle_orientation"),
+                    "aria-label": t!("split_view.toggle_orientation"),
+                    onclick: move |_| orientation_state.write().toggle_orientation(),
+                    if vertical { "⇆" } else { "⇅" }
+                }
+                button {
+                    r#type: "button",
+                    title: t!("split_view.close"),
+                    "aria-label": t!("split_view.close"),
+                    onclick: move |_| {
+                        let mut app_state = close_state.clone();
+                        spawn(async move { app_state.toggle_split_view().await });
+                    },
+                    "×"
+                }
+            }
+            div {
+                class: "split-view-panes",
+                for (side, position) in PaneSide::ALL.into_iter().zip(positions) {
+                    FilePaneView {
+                        key: "{side:?}",
+                        side,
+                        share: if vertical { position.height } else { position.width },
+                        drag_state,
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// One pane: its folder, its own history and selection, and a drop target for the other pane
+#[component]
+fn FilePaneView(side: PaneSide, share: f32, drag_state: Signal<DragState>) -> Element {
+    let app_state = use_app_state();
+    let mut drag_state = drag_state;
+    let mut drop_zone = use_signal(|| DropZoneState::Idle);
+    let (path, entries, can_go_back, is_active) = {
+        let split_view = app_state.split_view.read();
+        let pane = split_view.pane(side);
+        (pane.current_path().to_path_buf(), pane.entries(), pane.navigation.can_navigate_back(), split_view.active == side)
+    };
+    let title = title_for(&path);
+    let mut class = if is_active { "split-pane active".to_string() } else { "split-pane".to_string() };
+    if *drop_zone.read() != DropZoneState::Idle {
+        class = format!("{} {}", class, drop_zone.read().style_class());
+    }
+    let back_state = app_state.clone();
+    let up_state = app_state.clone();
+    let drop_state = app_state.clone();
+    let mut focus_state = app_state.split_view;
+
+    rsx! {
+        section {
+            class: "{class}",
+            style: "flex: {share} 1 0;",
+            "aria-label": t!("split_view.pane", name = title.clone()),
+            onclick: move |_| {
+                if focus_state.peek().active != side {
+                    focus_state.write().active = side;
+                }
+            },
+            ondragover: {
+                let path = path.clone();
+                move |evt: DragEvent| {
+                    if !drag_state.peek().is_dragging {
+                        return;
+                    }
+                    evt.prevent_default();
+                    evt.stop_propagation();
+                    let modifiers = evt.data.modifiers();
+                    update_drag_operation(drag_state, DragOperation::for_folder_drop(modifiers.ctrl(), modifiers.alt()));
+                    let state = DropZoneState::for_target(&drag_state.peek().drag_files, &path);
+                    if *drop_zone.peek() != state {
+                        drop_zone.set(state);
+                    }
+                }
+            },
+            ondragleave: move |_| drop_zone.set(DropZoneState::Idle),
+            ondrop: move |evt: DragEvent| {
+                evt.prevent_default();
+                evt.stop_propagation();
+                drop_zone.set(DropZoneState::Idle);
+                let (files, operation) = {
+                    let drag = drag_state.peek();
+                    (drag.drag_files.clone(), drag.operation.clone())
+                };
+                drag_state.write().end_drag();
+                if !files.is_empty() {
+                    spawn(drop_into_pane(drop_state.clone(), files, operation, side));
+                }
+            },
+
+            header {
+                class: "split-pane-header",
+                button {
+                    r#type: "button",
+                    disabled: !can_go_back,
+                    title: t!("split_view.back"),
+                    "aria-label": t!("split_view.back"),
+                    onclick: move |_| {
+                        let mut app_state = back_state.clone();
+                        spawn(async move {
+                            if let Err(e) = app_state.split_pane_back(side).await {
+                                tracing::warn!("Failed to go back in split pane: {}", e);
+                            }
+                        });
+                    },
+                    "←"
+                }
+                button {
+                    r#type: "button",
+                    disabled: path.parent().is_none(),
+                    title: t!("split_view.up"),
+                    "aria-label": t!("split_view.up"),
+                    onclick: move |_| {
+                        let mut app_state = up_state.clone();
+                        spawn(async move {
+                            if let Err(e) = app_state.split_pane_up(side).await {
+                                tracing::warn!("Failed to go up in split pane: {}", e);
+                            }
+                        });
+                    },
+                    "↑"
+                }
+                span {
+                    class: "split-pane-path",
+                    title: "{path.display()}",
+                    "{title}"
+                }
+            }
+
+            if entries.is_empty() {
+                p { class: "split-pane-empty", {t!("split_view.empty")} }
+            } else {
+                ul {
+                    class: "split-pane-list",
+                    role: "listbox",
+                    "aria-multiselectable": "true",
+                    for entry in entries.iter().cloned() {
+                        {
+                            let is_selected = app_state.split_view.read().pane(side).selection.is_selected(&entry.path);
+                            let mut select_state = app_state.split_view;
+                            let open_state = app_state.clone();
+                            let drag_entries = entries.clone();
+                            let entry_select = entry.clone();
+                            let entry_open = entry.clone();
+                            let entry_drag = entry.clone();
+                            rsx! {
+                                li {
+                                    key: "{entry.path.display()}",
+                                    class: if is_selected { "split-pane-item selected" } else { "split-pane-item" },
+                                    role: "option",
+                                    "aria-selected": is_selected.to_string(),
+                                    draggable: true,
+                                    onclick: move |evt| {
+                                        let modifiers = evt.modifiers();
+                                        let mut split_view = select_state.write();
+                                        split_view.active = side;
+                                        let selection = &mut split_view.pane_mut(side).selection;
+                                        if modifiers.ctrl() || modifiers.meta() {
+                                            selection.toggle(entry_select.path.clone());
+                                        } else {
+                                            selection.select_files(vec![entry_select.path.clone()], SelectionMode::Replace);
+                                        }
+                                    },
+                                    ondoubleclick: move |_| {
+                                        if !entry_open.is_directory {
+                                            return;
+                                        }
+                                        let mut app_state = open_state.clone();
+                                        let folder = entry_open.path.clone();
+                                        spawn(async move {
+                                            if let Err(e) = app_state.navigate_split_pane(side, folder.clone()).await {
+                                                tracing::warn!("Failed to open {:?} in split pane: {}", folder, e);
+                                            }
+                                        });
+                                    },
+                                    // Dragging a selected item takes the rest of the pane's selection along
+                                    ondragstart: move |evt| {
+                                        let selected = selected_entries(&select_state.peek().pane(side).selection, &drag_entries);
+                                        let coordinates = evt.data.client_coordinates();
+                                        let modifiers = evt.data.modifiers();
+                                        drag_state.write().start_drag(
+                                            drag_files_for(&entry_drag, selected),
+                                            coordinates.x,
+                                            coordinates.y,
+                                            DragOperation::for_folder_drop(modifiers.ctrl(), modifiers.alt()),
+                                        );
+                                    },
+                                    ondragend: move |_| drag_state.write().end_drag(),
+                                    span {
+                                        class: "split-pane-icon",
+                                        "aria-hidden": "true",
+                                        if entry.is_directory { "📁" } else { "📄" }
+                                    }
+                                    span { class: "split-pane-name", "{entry.name}" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,229 @@
+// Tag collections
+// Sidebar view listing the user's tags; picking one lists the files carrying it across the whole root
+
+use dioxus::events::Key;
+use dioxus::prelude::*;
+use std::path::{Path, PathBuf};
+
+use crate::state::{use_app_state, AppState};
+
+/// Swatch color for tags that were never given one
+const DEFAULT_TAG_COLOR: &str = "#808080";
+
+/// Go to the folder holding `path` and select it there
+fn reveal_tagged_file(mut app_state: AppState, path: PathBuf) {
+    let Some(folder) = path.parent().map(Path::to_path_buf) else {
+        return;
+    };
+    spawn(async move {
+        if let Err(e) = app_state.navigate_to(folder.clone()).await {
+            tracing::error!("Failed to navigate to {:?}: {}", folder, e);
+            return;
+        }
+        app_state.select_single(path.clone());
+        app_state.set_file_tree_selection(Some(path));
+    });
+}
+
+/// Rename `old` to the drafted name and stop editing
+fn commit_rename(
+    mut app_state: AppState,
+    mut editing: Signal<Option<String>>,
+    mut selected: Signal<Option<String>>,
+    draft: Signal<String>,
+    old: &str,
+) {
+    let new = draft.peek().trim().to_string();
+    editing.set(None);
+    if new.is_empty() || new == old {
+        return;
+    }
+    if selected.peek().as_deref() == Some(old) {
+        selected.set(Some(new.clone()));
+    }
+    app_state.rename_tag(old, &new);
+}
+
+/// Ask before taking `tag` off every file
+async fn confirm_delete_tag(tag: &str, count: usize) -> bool {
+    let result = rfd::AsyncMessageDialog::new()
+        .set_title(t!("tags.delete_title"))
+        .set_description(t!("tags.delete_message", count = count, tag = tag))
+        .set_level(rfd::MessageLevel::Warning)
+        .set_buttons(rfd::MessageButtons::YesNo)
+        .show()
+        .await;
+    result == rfd::MessageDialogResult::Yes
+}
+
+/// Tags with a count of the files under the root carrying each; the picked
+/// tag also filters the file tree
+#[component]
+pub fn TagCollectionsView() -> Element {
+    let app_state = use_app_state();
+    // Re-read the store whenever any tag changes
+    app_state.tags_revision.read();
+    let index = app_state.tag_store.file_index();
+    let root = app_state.get_file_tree_root();
+    let mut selected = use_signal(|| None::<String>);
+    let mut editing = use_signal(|| None::<String>);
+    let mut draft = use_signal(String::new);
+
+    // A tag that was renamed or deleted elsewhere is no longer picked
+    let selected_tag = selected.read().clone().filter(|tag| index.tags().any(|t| t.name.eq_ignore_ascii_case(tag)));
+    let tagged_files: Vec<PathBuf> = match (&selected_tag, &root) {
+        (Some(tag), Some(root)) => index.files_under(tag, root).map(Path::to_path_buf).collect(),
+        _ => Vec::new(),
+    };
+
+    rsx! {
+        div {
+            class: "tag-collections",
+            role: "region",
+            "aria-label": t!("tags.title"),
+            div { class: "tag-collections-header", {t!("tags.title")} }
+
+            if index.is_empty() {
+                p { class: "tag-collections-empty", {t!("tags.empty")} }
+            }
+
+            ul {
+                class: "tag-list",
+                role: "listbox",
+                "aria-label": t!("tags.title"),
+                for tag in index.tags().cloned() {
+                    {
+                        let name = tag.name.clone();
+                        let count = root.as_deref().map_or(tag.paths.len(), |root| index.count_under(&name, root));
+                        let color = tag.color.clone().unwrap_or_else(|| DEFAULT_TAG_COLOR.to_string());
+                        let is_selected = selected_tag.as_deref().is_some_and(|t| t.eq_ignore_ascii_case(&name));
+                        let is_editing = editing.read().as_deref() == Some(name.as_str());
+                        let pick = name.clone();
+                        let recolor = name.clone();
+                        let rename = name.clone();
+                        let rename_key = name.clone();
+                        let rename_blur = name.clone();
+                        let delete = name.clone();
+                        let mut pick_state = app_state.clone();
+                        let mut color_state = app_state.clone();
+                        let key_state = app_state.clone();
+                        let blur_state = app_state.clone();
+                        let delete_state = app_state.clone();
+                        rsx! {
+                            li {
+                                key: "{name}",
+                                class: if is_selected { "tag-item selected" } else { "tag-item" },
+                                role: "option",
+                                "aria-selected": is_selected.to_string(),
+                                input {
+                                    r#type: "color",
+                                    class: "tag-color",
+                                    value: "{color}",
+                                    title: t!("tags.recolor"),
+                                    "aria-label": t!("tags.recolor_named", tag = name.clone()),
+                                    onchange: move |evt| color_state.set_tag_color(&recolor, Some(&evt.value())),
+                                }
+                                if is_editing {
+                                    input {
+                                        r#type: "text",
+                                        class: "tag-rename-input",
+                                        value: "{draft}",
+                                        "aria-label": t!("tags.rename_named", tag = name.clone()),
+                                        autofocus: true,
+                                        oninput: move |evt| draft.set(evt.value()),
+                                        onkeydown: move |evt| match evt.data.key() {
+                                            Key::Enter => commit_rename(key_state.clone(), editing, selected, draft, &rename_key),
+                                            Key::Escape => editing.set(None),
+                                            _ => {}
+                                        },
+                                        onblur: move |_| {
+                                            if editing.peek().is_some() {
+                                                commit_rename(blur_state.clone(), editing, selected, draft, &rename_blur);
+                                            }
+                                        },
+                                    }
+                                } else {
+                                    button {
+                                        class: "tag-name",
+                                        title: t!("tags.show_files", tag = name.clone()),
+                                        onclick: move |_| {
+                                            let picked = (!is_selected).then(|| pick.clone());
+                                            pick_state.set_tag_filter(picked.iter().cloned().collect());
+                                            selected.set(picked);
+                                        },
+                                        "{name}"
+                                    }
+                                }
+                                span {
+                                    class: "tag-count",
+                                    "aria-label": t!("tags.count", count = count),
+                                    "{count}"
+                                }
+                                button {
+                                    class: "tag-action",
+                                    title: t!("tags.rename"),
+                                    "aria-label": t!("tags.rename_named", tag = name.clone()),
+                                    onclick: move |_| {
+                                        draft.set(rename.clone());
+                                        editing.set(Some(rename.clone()));
+                                    },
+                                    "✎"
+                                }
+                                button {
+                                    class: "tag-action",
+                                    title: t!("tags.delete"),
+                                    "aria-label": t!("tags.delete_named", tag = delete.clone()),
+                                    onclick: move |_| {
+                                        let tag = delete.clone();
+                                        // Deleting reaches files outside the root too
+                                        let total = delete_state.tag_store.files_with_tag(&tag).len();
+                                        let mut app_state = delete_state.clone();
+                                        spawn(async move {
+                                            if confirm_delete_tag(&tag, total).await {
+                                                app_state.delete_tag(&tag);
+                                            }
+                                        });
+                                    },
+                                    "×"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            if let Some(tag) = selected_tag {
+                div {
+                    class: "tag-files",
+                    role: "list",
+                    "aria-label": t!("tags.show_files", tag = tag.clone()),
+                    if tagged_files.is_empty() {
+                        p { class: "tag-collections-empty", {t!("tags.no_files")} }
+                    }
+                    for path in tagged_files {
+                        {
+                            let relative = root
+                                .as_deref()
+                                .and_then(|root| path.strip_prefix(root).ok())
+                                .unwrap_or(&path)
+                                .display()
+                                .to_string();
+                            let open_state = app_state.clone();
+                            let open_path = path.clone();
+                            rsx! {
+                                button {
+                                    key: "{path.display()}",
+                                    class: "tag-file",
+                                    role: "listitem",
+                                    title: "{path.display()}",
+                                    onclick: move |_| reveal_tagged_file(open_state.clone(), open_path.clone()),
+                                    "📄 {relative}"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,201 @@
+// Trash view
+// Lists the items in the system trash and restores or permanently deletes them
+
+use dioxus::prelude::*;
+
+use crate::services::announcer::Politeness;
+use crate::services::trash_bin::{self, TrashBinError, TrashedItem};
+use crate::state::{use_app_state, AppState};
+use crate::ui::components::{modal_title_id, Modal};
+use crate::utils::format_date_time;
+
+/// Trash view; mount it only while it should be shown
+#[component]
+pub fn TrashPanel(on_close: EventHandler<()>) -> Element {
+    let app_state = use_app_state();
+    let mut generation = use_signal(|| 0u64);
+    let mut pending_purge = use_signal(|| None::<TrashedItem>);
+    let status = use_signal(|| None::<String>);
+
+    let items = use_resource(move || async move {
+        let _ = generation();
+        tokio::task::spawn_blocking(trash_bin::list_trash)
+            .await
+            .unwrap_or_else(|e| Err(TrashBinError::Trash(e.to_string())))
+    });
+
+    let dialog_id = "trash-dialog";
+
+    rsx! {
+        Modal {
+            id: "{dialog_id}",
+            class: "trash-dialog",
+            on_close: move |_| on_close.call(()),
+
+            div {
+                class: "dialog-header",
+                h3 { id: modal_title_id(dialog_id), {t!("trash.title")} }
+            }
+
+            div {
+                class: "dialog-content",
+                {
+                    match &*items.read() {
+                        None => rsx! {
+                            p { class: "dialog-message", {t!("trash.loading")} }
+                        },
+                        Some(Err(TrashBinError::Unsupported)) => rsx! {
+                            p { class: "dialog-message", {t!("trash.unsupported")} }
+                        },
+                        Some(Err(e)) => rsx! {
+                            p { class: "dialog-message error", role: "alert", {t!("trash.load_failed", error = e)} }
+                        },
+                        Some(Ok(items)) if items.is_empty() => rsx! {
+                            p { class: "dialog-message", {t!("trash.empty")} }
+                        },
+                        Some(Ok(items)) => rsx! {
+                            table {
+                                class: "log-table",
+                                thead {
+                                    tr {
+                                        th { scope: "col", {t!("trash.name")} }
+                                        th { scope: "col", {t!("trash.original_location")} }
+                                        th { scope: "col", {t!("trash.deleted")} }
+                                        th { scope: "col", span { class: "sr-only", {t!("trash.actions")} } }
+                                    }
+                                }
+                                tbody {
+                                    for item in items.iter().cloned() {
+                                        TrashRow {
+                                            key: "{item.original_path().display()}-{item.time_deleted}",
+                                            item: item.clone(),
+                                            status,
+                                            on_restored: move |_| *generation.write() += 1,
+                                            on_purge: move |item: TrashedItem| pending_purge.set(Some(item)),
+                                        }
+                                    }
+                                }
+                            }
+                        },
+                    }
+                }
+
+                if let Some(item) = pending_purge.read().clone() {
+                    div {
+                        class: "trash-confirm",
+                        role: "alertdialog",
+                        p { {t!("trash.confirm_purge", name = item.name)} }
+                        button {
+                            class: "button",
+                            onclick: move |_| pending_purge.set(None),
+                            {t!("dialog.cancel")}
+                        }
+                        button {
+                            class: "button danger",
+                            onclick: move |_| {
+                                let item = item.clone();
+                                let app_state = app_state.clone();
+                                pending_purge.set(None);
+                                spawn(async move {
+                                    purge(&app_state, item, status).await;
+                                    *generation.write() += 1;
+                                });
+                            },
+                            {t!("trash.purge")}
+                        }
+                    }
+                }
+
+                if let Some(message) = status.read().as_ref() {
+                    p { class: "dialog-message", role: "status", {message.clone()} }
+                }
+            }
+
+            div {
+                class: "dialog-actions",
+                button {
+                    class: "button",
+                    disabled: !trash_bin::SUPPORTED,
+                    onclick: move |_| *generation.write() += 1,
+                    {t!("trash.refresh")}
+                }
+                button {
+                    class: "button primary",
+                    onclick: move |_| on_close.call(()),
+                    {t!("dialog.close")}
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn TrashRow(
+    item: TrashedItem,
+    status: Signal<Option<String>>,
+    on_restored: EventHandler<()>,
+    on_purge: EventHandler<TrashedItem>,
+) -> Element {
+    let app_state = use_app_state();
+    let deleted = item.deleted_at().map(format_date_time).unwrap_or_default();
+    let restore_item = item.clone();
+    let purge_item = item.clone();
+
+    rsx! {
+        tr {
+            td { class: "log-path", title: "{item.name}", {item.name.clone()} }
+            td { class: "log-path", title: "{item.original_parent.display()}", {item.original_parent.display().to_string()} }
+            td { {deleted} }
+            td {
+                class: "trash-actions",
+                button {
+                    class: "button",
+                    onclick: move |_| {
+                        let item = restore_item.clone();
+                        let app_state = app_state.clone();
+                        spawn(async move {
+                            restore(&app_state, item, status).await;
+                            on_restored.call(());
+                        });
+                    },
+                    {t!("trash.restore")}
+                }
+                button {
+                    class: "button",
+                    onclick: move |_| on_purge.call(purge_item.clone()),
+                    {t!("trash.purge")}
+                }
+            }
+        }
+    }
+}
+
+/// Restore `item` as an undoable operation and reload the listing it came back to
+async fn restore(app_state: &AppState, item: TrashedItem, mut status: Signal<Option<String>>) {
+    let result = app_state.file_operations().restore_from_trash(std::slice::from_ref(&item)).await;
+    let (message, politeness) = match result.failed.first() {
+        None => (t!("trash.restored", path = item.original_path().display()), Politeness::Polite),
+        Some((_, error)) => (t!("trash.restore_failed", name = item.name, error = error), Politeness::Assertive),
+    };
+    if !result.succeeded.is_empty() {
+        if let Err(e) = app_state.clone().refresh_current_directory().await {
+            tracing::warn!("Failed to refresh after restoring from trash: {}", e);
+        }
+    }
+    app_state.announcer.announce(message.clone(), politeness);
+    status.set(Some(message));
+}
+
+/// Delete `item` from the trash for good
+async fn purge(app_state: &AppState, item: TrashedItem, mut status: Signal<Option<String>>) {
+    let target = item.clone();
+    let result = tokio::task::spawn_blocking(move || trash_bin::purge_item(&target))
+        .await
+        .unwrap_or_else(|e| Err(TrashBinError::Trash(e.to_string())));
+    let (message, politeness) = match result {
+        Ok(()) => (t!("trash.purged", name = item.name), Politeness::Polite),
+        Err(e) => (t!("trash.purge_failed", name = item.name, error = e), Politeness::Assertive),
+    };
+    app_state.announcer.announce(message.clone(), politeness);
+    status.set(Some(message));
+}
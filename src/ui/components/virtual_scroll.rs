@@ -221,6 +221,151 @@ pub struct PerformanceMetrics {
     pub buffer_utilization: f64, // Buffer size relative to visible items
 }
 
+/// Top offset of every row of a list whose rows differ in height
+///
+/// Offsets are summed once up front, so finding the row at a scroll position
+/// is a binary search rather than a walk over every row above it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowOffsetIndex {
+    /// `offsets[i]` is the top of row `i`; the last entry is the total height
+    offsets: Vec<f64>,
+}
+
+impl RowOffsetIndex {
+    /// Index `row_count` rows, asking `row_height` for the height of each
+    pub fn new(row_count: usize, row_height: impl Fn(usize) -> f64) -> Self {
+        let mut offsets = Vec::with_capacity(row_count + 1);
+        let mut top = 0.0;
+        offsets.push(top);
+        for index in 0..row_count {
+            top += row_height(index).max(0.0);
+            offsets.push(top);
+        }
+        Self { offsets }
+    }
+
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn total_height(&self) -> f64 {
+        self.offsets[self.len()]
+    }
+
+    /// Top of row `index`; past the last row, the total height
+    pub fn offset(&self, index: usize) -> f64 {
+        self.offsets[index.min(self.len())]
+    }
+
+    pub fn height(&self, index: usize) -> f64 {
+        self.offset(index + 1) - self.offset(index)
+    }
+
+    /// Row covering vertical position `y`, clamped to the first and last row
+    pub fn index_at(&self, y: f64) -> Option<usize> {
+        if self.is_empty() {
+            return None;
+        }
+        // Rows starting at or above `y`; the last of them covers it
+        let starting_above = self.offsets[..self.len()].partition_point(|&top| top <= y);
+        Some(starting_above.saturating_sub(1))
+    }
+}
+
+/// The group header pinned to the top of the viewport
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StickyHeader {
+    /// Row index of the header
+    pub index: usize,
+    /// How far the next group's header pushes it up, zero or negative
+    pub offset: f64,
+}
+
+/// Virtual scrolling over rows of differing heights, such as grouped lists
+/// whose section headers are shorter than their rows
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariableVirtualScroll {
+    rows: RowOffsetIndex,
+    /// Total height of the viewport container
+    pub container_height: f64,
+    /// Number of rows to render above and below the visible area
+    pub buffer_size: usize,
+    /// Row indices of the group headers, ascending
+    headers: Vec<usize>,
+}
+
+impl VariableVirtualScroll {
+    pub fn new(row_count: usize, row_height: impl Fn(usize) -> f64, container_height: f64, buffer_size: usize) -> Self {
+        Self {
+            rows: RowOffsetIndex::new(row_count, row_height),
+            container_height,
+            buffer_size,
+            headers: Vec::new(),
+        }
+    }
+
+    /// Mark `headers` as group header rows, which stick to the top while their group is in view
+    pub fn with_group_headers(mut self, mut headers: Vec<usize>) -> Self {
+        headers.retain(|&index| index < self.rows.len());
+        headers.sort_unstable();
+        headers.dedup();
+        self.headers = headers;
+        self
+    }
+
+    pub fn rows(&self) -> &RowOffsetIndex {
+        &self.rows
+    }
+
+    /// Rows to render at `scroll_top`, with `offset_top` the top of the first one
+    pub fn calculate_visible_range(&self, scroll_top: f64) -> VisibleRange {
+        let total_height = self.rows.total_height();
+        let Some(first_visible) = self.rows.index_at(scroll_top.max(0.0)) else {
+            return VisibleRange {
+                start_index: 0,
+                end_index: 0,
+                visible_count: 0,
+                offset_top: 0.0,
+                total_height,
+            };
+        };
+
+        // A row starting exactly at the bottom edge is not on screen yet
+        let bottom = scroll_top + self.container_height;
+        let last_visible = self.rows.index_at(bottom).unwrap_or(first_visible);
+        let end_visible = if self.rows.offset(last_visible) < bottom { last_visible + 1 } else { last_visible };
+        let end_visible = end_visible.clamp(first_visible + 1, self.rows.len());
+
+        let start_index = first_visible.saturating_sub(self.buffer_size);
+        let end_index = cmp::min(end_visible + self.buffer_size, self.rows.len());
+
+        VisibleRange {
+            start_index,
+            end_index,
+            visible_count: end_visible - first_visible,
+            offset_top: self.rows.offset(start_index),
+            total_height,
+        }
+    }
+
+    /// Header of the group at the top of the viewport, if that group has one
+    pub fn sticky_header(&self, scroll_top: f64) -> Option<StickyHeader> {
+        let first_visible = self.rows.index_at(scroll_top.max(0.0))?;
+        let position = self.headers.partition_point(|&header| header <= first_visible);
+        let index = self.headers[position.checked_sub(1)?];
+        // The next header slides this one out as it reaches the top
+        let offset = match self.headers.get(position) {
+            Some(&next) => (self.rows.offset(next) - scroll_top - self.rows.height(index)).min(0.0),
+            None => 0.0,
+        };
+        Some(StickyHeader { index, offset })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -371,4 +516,59 @@ mod tests {
         assert_eq!(calc.item_height, 75.0);
         assert_eq!(calc.calculate_total_height(), 150_000.0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_row_offset_index_with_mixed_heights() {
+        // A 30px header, two 100px rows, another header and a 50px row
+        let heights = [30.0, 100.0, 100.0, 30.0, 50.0];
+        let rows = RowOffsetIndex::new(heights.len(), |index| heights[index]);
+
+        assert_eq!(rows.len(), 5);
+        assert_eq!(rows.total_height(), 310.0);
+        assert_eq!(rows.offset(0), 0.0);
+        assert_eq!(rows.offset(3), 230.0);
+        assert_eq!(rows.offset(99), 310.0);
+        assert_eq!(rows.height(4), 50.0);
+
+        assert_eq!(rows.index_at(-10.0), Some(0));
+        assert_eq!(rows.index_at(29.9), Some(0));
+        assert_eq!(rows.index_at(30.0), Some(1));
+        assert_eq!(rows.index_at(229.0), Some(2));
+        assert_eq!(rows.index_at(260.0), Some(4));
+        assert_eq!(rows.index_at(1000.0), Some(4));
+
+        let empty = RowOffsetIndex::new(0, |_| 10.0);
+        assert!(empty.is_empty());
+        assert_eq!(empty.total_height(), 0.0);
+        assert_eq!(empty.index_at(0.0), None);
+    }
+
+    #[test]
+    fn test_variable_visible_range_and_sticky_header() {
+        let heights = [30.0, 100.0, 100.0, 30.0, 50.0, 50.0, 50.0];
+        let scroll = VariableVirtualScroll::new(heights.len(), |index| heights[index], 120.0, 1)
+            .with_group_headers(vec![3, 0, 42]);
+
+        let range = scroll.calculate_visible_range(0.0);
+        assert_eq!((range.start_index, range.end_index, range.visible_count), (0, 3, 2));
+        assert_eq!(range.total_height, 410.0);
+
+        // 150..270 shows rows 2 to 4; the buffer adds one row either side
+        let range = scroll.calculate_visible_range(150.0);
+        assert_eq!((range.start_index, range.end_index, range.visible_count), (1, 6, 3));
+        assert_eq!(range.offset_top, 30.0);
+
+        // At 140 row 4 starts exactly at the bottom edge, so it is not visible
+        let range = scroll.calculate_visible_range(140.0);
+        assert_eq!(range.visible_count, 2);
+
+        // The first header holds until the second one pushes it out
+        assert_eq!(scroll.sticky_header(50.0), Some(StickyHeader { index: 0, offset: 0.0 }));
+        assert_eq!(scroll.sticky_header(210.0), Some(StickyHeader { index: 0, offset: -10.0 }));
+        assert_eq!(scroll.sticky_header(240.0), Some(StickyHeader { index: 3, offset: 0.0 }));
+
+        let no_headers = VariableVirtualScroll::new(3, |_| 10.0, 20.0, 0);
+        assert_eq!(no_headers.sticky_header(5.0), None);
+        assert_eq!(VariableVirtualScroll::new(0, |_| 10.0, 20.0, 2).calculate_visible_range(0.0).end_index, 0);
+    }
+}
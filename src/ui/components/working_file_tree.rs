@@ -2,12 +2,16 @@ use dioxus::prelude::*;
 use dioxus_free_icons::{Icon, icons::fa_solid_icons};
 use std::path::PathBuf;
 use crate::services::FileEntry;
-use crate::state::{use_file_tree_state, use_app_state};
+use crate::state::{use_file_tree_state, use_app_state, FileTypeFilter};
 use crate::utils::{normalize_path_display, path_to_element_id};
 use crate::ui::components::virtual_scroll::VirtualScrollCalculator;
 use crate::performance::rendering_optimizations::{VirtualScrollOptimizer, RenderingProfiler};
 use crate::ui::icon_packs::FileIconComponent;
 use crate::ui::icon_manager::use_icon_manager;
+use crate::services::preview::SupportedFormat;
+use crate::services::preview::thumbnail_service::{ThumbnailService, FALLBACK_THUMBNAIL_FORMAT};
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
 use std::sync::{Arc, Mutex, OnceLock};
 
 /// Global virtual scroll optimizer for file tree
@@ -20,6 +24,18 @@ fn get_file_tree_virtual_scroll_optimizer() -> &'static Arc<Mutex<VirtualScrollO
     })
 }
 
+/// Approximate height of one tree row, used to work out which rows are on screen
+const TREE_ROW_HEIGHT: f64 = 22.0;
+
+/// Thumbnails of image rows, shared by the tree with its items
+#[derive(Clone, Copy)]
+struct TreeThumbnails {
+    /// Data URLs of the thumbnails loaded so far
+    loaded: Signal<HashMap<PathBuf, String>>,
+    /// Paths already handed to the thumbnail service
+    requested: Signal<HashSet<PathBuf>>,
+}
+
 /// Working file tree component for sidebar navigation
 #[component]
 pub fn WorkingFileTree() -> Element {
@@ -28,6 +44,14 @@ pub fn WorkingFileTree() -> Element {
     let focused_item = use_signal(|| None::<std::path::PathBuf>); // Track focused item for keyboard nav
     let file_service = app_state.file_service.clone(); // Clone early to avoid borrow issues
     
+    let thumbnail_service = app_state.thumbnail_service.clone();
+    let thumbnails = use_context_provider(|| TreeThumbnails {
+        loaded: Signal::new(HashMap::new()),
+        requested: Signal::new(HashSet::new()),
+    });
+    // The scrolling element, measured to find the rows in view
+    let mut tree_container = use_signal(|| None::<Rc<MountedData>>);
+
     // Virtual scrolling optimization signals
     let mut use_virtual_scrolling = use_signal(|| false);
     let mut virtual_scroll_stats = use_signal(|| std::collections::HashMap::<String, f64>::new());
@@ -57,11 +81,21 @@ pub fn WorkingFileTree() -> Element {
         }
     });
     
+    // Rows come into view when folders expand or load, not only on scroll
+    use_effect({
+        let thumbnail_service = thumbnail_service.clone();
+        move || {
+            let _ = file_tree_state.read();
+            if let Some(container) = tree_container.read().clone() {
+                request_visible_thumbnails(container, file_tree_state, thumbnail_service.clone(), thumbnails);
+            }
+        }
+    });
+
     let tree_state = file_tree_state.read();
     let root_dir = tree_state.root_directory.clone();
     let children = root_dir.as_ref()
-        .and_then(|root| tree_state.directory_children.get(root))
-        .cloned()
+        .map(|root| tree_state.visible_children(root))
         .unwrap_or_default();
     drop(tree_state);
     
@@ -110,6 +144,8 @@ pub fn WorkingFileTree() -> Element {
             "aria-label": "File explorer tree - Navigate files and folders",
             "aria-describedby": "file-tree-instructions",
             "aria-multiselectable": "false",
+            // The tree is one tab stop; arrow keys move the active descendant
+            "aria-activedescendant": focused_item.read().as_ref().map(|path| tree_item_element_id(path)).unwrap_or_default(),
             tabindex: "0",
             onkeydown: {
                 let file_service = file_service.clone();
@@ -166,8 +202,7 @@ pub fn WorkingFileTree() -> Element {
                                 let tree_state = file_tree_state.read();
                                 let root_dir = tree_state.root_directory.clone();
                                 let children = root_dir.as_ref()
-                                    .and_then(|root| tree_state.directory_children.get(root))
-                                    .cloned()
+                                    .map(|root| tree_state.visible_children(root))
                                     .unwrap_or_default();
                                 let mut visible_entries = Vec::new();
                                 if let Some(root) = root_dir.as_ref() {
@@ -215,8 +250,7 @@ pub fn WorkingFileTree() -> Element {
                                 let tree_state = file_tree_state.read();
                                 let root_dir = tree_state.root_directory.clone();
                                 let children = root_dir.as_ref()
-                                    .and_then(|root| tree_state.directory_children.get(root))
-                                    .cloned()
+                                    .map(|root| tree_state.visible_children(root))
                                     .unwrap_or_default();
                                 let mut visible_entries = Vec::new();
                                 if let Some(root) = root_dir.as_ref() {
@@ -250,8 +284,7 @@ pub fn WorkingFileTree() -> Element {
                                 let tree_state = file_tree_state.read();
                                 let root_dir = tree_state.root_directory.clone();
                                 let children = root_dir.as_ref()
-                                    .and_then(|root| tree_state.directory_children.get(root))
-                                    .cloned()
+                                    .map(|root| tree_state.visible_children(root))
                                     .unwrap_or_default();
                                 let mut visible_entries = Vec::new();
                                 if let Some(root) = root_dir.as_ref() {
@@ -320,6 +353,16 @@ pub fn WorkingFileTree() -> Element {
             
             if let Some(root) = root_dir {
                 div {
+                    onmounted: move |evt: Event<MountedData>| tree_container.set(Some(evt.data())),
+                    onscroll: {
+                        let thumbnail_service = thumbnail_service.clone();
+                        move |_| {
+                            if let Some(container) = tree_container.read().clone() {
+                                request_visible_thumbnails(container, file_tree_state, thumbnail_service.clone(), thumbnails);
+                            }
+                        }
+                    },
+
                     // Root directory header
                     div {
                         style: "
@@ -331,6 +374,9 @@ pub fn WorkingFileTree() -> Element {
                         ",
                         "{root.file_name().and_then(|n| n.to_str()).unwrap_or(\"Root\")}"
                     }
+
+                    TreeTypeFilter {}
+                    TreeTagFilter {}
                     
                     // Directory contents
                     if children.is_empty() {
@@ -359,13 +405,100 @@ pub fn WorkingFileTree() -> Element {
     }
 }
 
+/// Picks which file types the tree lists
+#[component]
+fn TreeTypeFilter() -> Element {
+    let mut app_state = use_app_state();
+    let filter = app_state.file_tree_state.read().file_type_filter.clone();
+    let custom_extensions = match &filter {
+        FileTypeFilter::Custom(extensions) => Some(extensions.join(", ")),
+        _ => None,
+    };
+
+    rsx! {
+        div {
+            class: "tree-type-filter",
+            select {
+                "aria-label": "Show file types",
+                value: "{filter.as_str()}",
+                onchange: move |evt| {
+                    let filter = match evt.value().as_str() {
+                        "custom" => FileTypeFilter::Custom(Vec::new()),
+                        value => FileTypeFilter::PRESETS
+                            .into_iter()
+                            .find(|preset| preset.as_str() == value)
+                            .unwrap_or_default(),
+                    };
+                    app_state.set_file_type_filter(filter);
+                },
+                for preset in FileTypeFilter::PRESETS {
+                    option { value: "{preset.as_str()}", "{preset.display_name()}" }
+                }
+                option { value: "custom", "{FileTypeFilter::Custom(Vec::new()).display_name()}" }
+            }
+            if let Some(extensions) = custom_extensions {
+                input {
+                    r#type: "text",
+                    "aria-label": "Extensions to show",
+                    placeholder: "jpg, png, heic",
+                    value: "{extensions}",
+                    onchange: move |evt| app_state.set_file_type_filter(FileTypeFilter::custom(&evt.value())),
+                }
+            }
+        }
+    }
+}
+
+/// Limits the tree to files carrying a user tag; hidden until something is tagged
+#[component]
+fn TreeTagFilter() -> Element {
+    let mut app_state = use_app_state();
+    app_state.tags_revision.read();
+    let mut known_tags = app_state.tag_store.all_tags();
+    let active = app_state
+        .file_tree_state
+        .read()
+        .tag_filter
+        .as_ref()
+        .and_then(|filter| filter.tags.first().cloned())
+        .unwrap_or_default();
+
+    if known_tags.is_empty() && active.is_empty() {
+        return rsx! {};
+    }
+    // Keep the active tag selectable after its last file lost it
+    if !active.is_empty() && !known_tags.iter().any(|tag| tag.eq_ignore_ascii_case(&active)) {
+        known_tags.push(active.clone());
+    }
+
+    rsx! {
+        div {
+            class: "tree-type-filter",
+            select {
+                "aria-label": "Show files tagged",
+                value: "{active}",
+                onchange: move |evt| {
+                    let tag = evt.value();
+                    app_state.set_tag_filter(if tag.is_empty() { Vec::new() } else { vec![tag] });
+                },
+                option { value: "", "Any Tags" }
+                for tag in known_tags {
+                    option { value: "{tag}", "Tagged “{tag}”" }
+                }
+            }
+        }
+    }
+}
+
 /// Hierarchical file tree item component with proper nesting depth
 #[component]
 pub fn WorkingFileTreeItem(entry: FileEntry, is_focused: bool, depth: Option<usize>) -> Element {
     let mut file_tree_state = use_file_tree_state();
+    let thumbnails = use_context::<TreeThumbnails>();
     let is_directory = entry.is_directory;
     let name = entry.name.clone();
     let path = entry.path.clone();
+    let thumbnail = thumbnails.loaded.read().get(&path).cloned();
     
     // Calculate nesting depth using our enhanced state management
     let nesting_depth = depth.unwrap_or_else(|| {
@@ -388,7 +521,7 @@ pub fn WorkingFileTreeItem(entry: FileEntry, is_focused: bool, depth: Option<usi
     let has_children = tree_state.has_children(&path);
     let children_count = tree_state.get_children_count(&path);
     let children = if is_expanded && is_directory {
-        tree_state.get_directory_children(&path).cloned().unwrap_or_default()
+        tree_state.visible_children(&path)
     } else {
         Vec::new()
     };
@@ -451,6 +584,7 @@ pub fn WorkingFileTreeItem(entry: FileEntry, is_focused: bool, depth: Option<usi
     
     rsx! {
         div {
+            id: tree_item_element_id(&path),
             class: "file-tree-node",
             role: "treeitem",
             "aria-expanded": if is_directory { is_expanded.to_string() } else { "false".to_string() },
@@ -485,8 +619,6 @@ pub fn WorkingFileTreeItem(entry: FileEntry, is_focused: bool, depth: Option<usi
                     transition: all 0.2s ease;
                     min-height: 22px;
                 ",
-                role: "button",
-                tabindex: "0",
                 "aria-describedby": format!("file-item-{}", path_to_element_id(&path)),
                 onclick: move |_| {
                     file_tree_state.write().selected_path = Some(click_path.clone());
@@ -682,6 +814,12 @@ pub fn WorkingFileTreeItem(entry: FileEntry, is_focused: bool, depth: Option<usi
                                 icon: fa_solid_icons::FaFolder,
                             }
                         }
+                    } else if let Some(thumbnail) = thumbnail {
+                        img {
+                            class: "tree-thumbnail",
+                            src: thumbnail,
+                            alt: "",
+                        }
                     } else {
                         // Use enhanced VS Code icon pack
                         FileIconComponent {
@@ -766,6 +904,66 @@ pub fn WorkingFileTreeItem(entry: FileEntry, is_focused: bool, depth: Option<usi
     }
 }
 
+/// Load thumbnails for the image rows scrolled into view that don't have one yet
+///
+/// Rows outside the optimizer's visible range (plus its buffer) are skipped, so
+/// large folders only decode what the user can see.
+fn request_visible_thumbnails(
+    container: Rc<MountedData>,
+    file_tree_state: Signal<crate::state::FileTreeState>,
+    thumbnail_service: Arc<ThumbnailService>,
+    mut thumbnails: TreeThumbnails,
+) {
+    spawn(async move {
+        let (Ok(offset), Ok(rect)) = (container.get_scroll_offset().await, container.get_client_rect().await) else {
+            return;
+        };
+        let visible_entries = file_tree_state.read().get_visible_entries();
+        let Ok((start, end)) = get_file_tree_virtual_scroll_optimizer().lock().map(|mut optimizer| {
+            optimizer.set_item_height(TREE_ROW_HEIGHT);
+            optimizer.update_viewport(rect.size.height, offset.y);
+            optimizer.set_total_items(visible_entries.len());
+            optimizer.get_visible_range()
+        }) else {
+            return;
+        };
+
+        let mut jobs = Vec::new();
+        for path in visible_entries.get(start..end).unwrap_or_default() {
+            if !is_thumbnail_candidate(path) || !thumbnails.requested.write().insert(path.clone()) {
+                continue;
+            }
+            let service = thumbnail_service.clone();
+            let job_path = path.clone();
+            // Decoding is CPU-bound, so keep it off the UI thread
+            jobs.push((path.clone(), tokio::spawn(async move { service.get_thumbnail(&job_path).await })));
+        }
+        for (path, job) in jobs {
+            match job.await {
+                Ok(Ok(thumbnail)) if thumbnail.format != FALLBACK_THUMBNAIL_FORMAT => {
+                    thumbnails.loaded.write().insert(path, thumbnail.to_data_url());
+                }
+                // Unreadable images keep their file-type icon
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) => tracing::debug!("No thumbnail for {:?}: {}", path, e),
+                Err(e) => tracing::warn!("Thumbnail task for {:?} failed: {}", path, e),
+            }
+        }
+    });
+}
+
+/// Whether the tree shows a thumbnail instead of an icon for this file
+fn is_thumbnail_candidate(path: &std::path::Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(SupportedFormat::from_extension)
+        .is_some_and(|format| format.is_image())
+}
+
+/// DOM id of a tree item, referenced by the tree's `aria-activedescendant`
+fn tree_item_element_id(path: &std::path::Path) -> String {
+    format!("tree-item-{}", path_to_element_id(path))
+}
 
 /// Helper function to collect all visible entries in tree order for keyboard navigation
 fn collect_visible_entries(
@@ -780,9 +978,8 @@ fn collect_visible_entries(
         
         // If this is an expanded directory, add its children recursively
         if child.is_directory && tree_state.expanded_directories.get(&child.path).copied().unwrap_or(false) {
-            if let Some(grandchildren) = tree_state.directory_children.get(&child.path) {
-                collect_visible_entries(tree_state, &child.path, grandchildren, visible_entries);
-            }
+            let grandchildren = tree_state.visible_children(&child.path);
+            collect_visible_entries(tree_state, &child.path, &grandchildren, visible_entries);
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,225 @@
+use dioxus::events::Key;
+use dioxus::prelude::*;
+
+/// DOM id of the application root, the last stop when Escape moves focus outward
+pub const APP_ROOT_ELEMENT_ID: &str = "media-organizer-root";
+
+/// Top-level regions of the window, each a single tab stop
+///
+/// Tab moves between regions, arrow keys move within them and Escape
+/// moves focus outward: from an item to its region, then from a nested
+/// region to the one that contains it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FocusRegion {
+    ActivityBar,
+    Sidebar,
+    Content,
+    Preview,
+    Panel,
+}
+
+impl FocusRegion {
+    /// Regions in visual (and F6 cycling) order
+    pub const ORDER: [FocusRegion; 5] = [
+        FocusRegion::ActivityBar,
+        FocusRegion::Sidebar,
+        FocusRegion::Content,
+        FocusRegion::Preview,
+        FocusRegion::Panel,
+    ];
+
+    /// DOM id of the region's root element
+    pub fn element_id(&self) -> &'static str {
+        match self {
+            FocusRegion::ActivityBar => "focus-region-activity-bar",
+            FocusRegion::Sidebar => "focus-region-sidebar",
+            FocusRegion::Content => "focus-region-content",
+            FocusRegion::Preview => "focus-region-preview",
+            FocusRegion::Panel => "focus-region-panel",
+        }
+    }
+
+    /// Accessible name of the region
+    pub fn label(&self) -> &'static str {
+        match self {
+            FocusRegion::ActivityBar => "Activity bar",
+            FocusRegion::Sidebar => "File explorer",
+            FocusRegion::Content => "Content",
+            FocusRegion::Preview => "Preview",
+            FocusRegion::Panel => "Panel",
+        }
+    }
+
+    /// Region that receives focus when Escape is pressed on this region's root
+    pub fn outer(&self) -> Option<FocusRegion> {
+        match self {
+            FocusRegion::Preview | FocusRegion::Panel => Some(FocusRegion::Content),
+            FocusRegion::Sidebar => Some(FocusRegion::ActivityBar),
+            FocusRegion::ActivityBar | FocusRegion::Content => None,
+        }
+    }
+
+    /// The other regions in cycling order starting after this one
+    pub fn cycle(&self, forward: bool) -> Vec<FocusRegion> {
+        let position = Self::ORDER.iter().position(|r| r == self).unwrap_or(0);
+        let len = Self::ORDER.len();
+        (1..len)
+            .map(|offset| {
+                let index = if forward {
+                    (position + offset) % len
+                } else {
+                    (position + len - offset) % len
+                };
+                Self::ORDER[index]
+            })
+            .collect()
+    }
+}
+
+/// Layout direction of a roving-tabindex list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListOrientation {
+    Vertical,
+    Horizontal,
+}
+
+/// Tabindex for an item in a roving-tabindex list
+///
+/// Only the active item is in the tab order; when nothing is active the
+/// first item takes the tab stop so the list is still reachable.
+pub fn roving_tabindex(index: usize, active: Option<usize>) -> &'static str {
+    if index == active.unwrap_or(0) {
+        "0"
+    } else {
+        "-1"
+    }
+}
+
+/// Index the active item moves to for a navigation key, if the key navigates
+pub fn roving_next_index(
+    key: &Key,
+    current: Option<usize>,
+    len: usize,
+    orientation: ListOrientation,
+) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    let last = len - 1;
+    let (next_key, previous_key) = match orientation {
+        ListOrientation::Vertical => (Key::ArrowDown, Key::ArrowUp),
+        ListOrientation::Horizontal => (Key::ArrowRight, Key::ArrowLeft),
+    };
+
+    match key {
+        k if *k == next_key => Some(current.map_or(0, |i| (i + 1).min(last))),
+        k if *k == previous_key => Some(current.map_or(0, |i| i.saturating_sub(1))),
+        Key::Home => Some(0),
+        Key::End => Some(last),
+        Key::PageDown => Some(current.map_or(0, |i| (i + 10).min(last))),
+        Key::PageUp => Some(current.map_or(0, |i| i.saturating_sub(10))),
+        _ => None,
+    }
+}
+
+/// Move DOM focus to the element with the given id
+pub fn focus_element(element_id: &str) {
+    let script = format!(
+        "document.getElementById({})?.focus();",
+        serde_json::to_string(element_id).unwrap_or_default()
+    );
+    let _ = document::eval(&script);
+}
+
+/// Scroll the element with the given id into view and focus it
+pub fn reveal_element(element_id: &str) {
+    let script = format!(
+        "const el = document.getElementById({}); if (el) {{ el.scrollIntoView({{ block: 'nearest' }}); el.focus(); }}",
+        serde_json::to_string(element_id).unwrap_or_default()
+    );
+    let _ = document::eval(&script);
+}
+
+/// Whether focus is in a text field, where keys like Space are typed rather than shortcuts
+pub async fn text_input_focused() -> bool {
+    let script = "const el = document.activeElement; \
+         return !!el && (el.isContentEditable || ['INPUT', 'TEXTAREA', 'SELECT'].includes(el.tagName));";
+    document::eval(script).join::<bool>().await.unwrap_or(false)
+}
+
+/// Move DOM focus to the first region in `regions` that is present in the window
+pub fn focus_first_region(regions: &[FocusRegion]) {
+    let ids: Vec<&str> = regions.iter().map(|r| r.element_id()).collect();
+    let script = format!(
+        "for (const id of {}) {{ const el = document.getElementById(id); if (el) {{ el.focus(); break; }} }}",
+        serde_json::to_string(&ids).unwrap_or_default()
+    );
+    let _ = document::eval(&script);
+}
+
+/// Move focus to the next (or previous) region, as F6 does in VS Code
+pub fn focus_adjacent_region(from: FocusRegion, forward: bool) {
+    focus_first_region(&from.cycle(forward));
+}
+
+/// Handle Escape inside a region by moving focus one step outward
+///
+/// Focus on a descendant moves to the region's root; focus already on the
+/// root moves to the enclosing region. The enclosing region may not exist in
+/// every layout, so its own outer regions are tried next and the application
+/// root last.
+pub fn focus_outward(from: FocusRegion) {
+    let mut ids: Vec<&str> = Vec::new();
+    let mut current = from;
+    while let Some(outer) = current.outer() {
+        ids.push(outer.element_id());
+        current = outer;
+    }
+    ids.push(APP_ROOT_ELEMENT_ID);
+
+    let script = format!(
+        "const root = document.getElementById({}); \
+         if (root && document.activeElement !== root) {{ root.focus(); }} else {{ \
+             for (const id of {}) {{ const el = document.getElementById(id); if (el) {{ el.focus(); break; }} }} \
+         }}",
+        serde_json::to_string(from.element_id()).unwrap_or_default(),
+        serde_json::to_string(&ids).unwrap_or_default()
+    );
+    let _ = document::eval(&script);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roving_tabindex_has_single_tab_stop() {
+        let stops = |active| (0..5).filter(|&i| roving_tabindex(i, active) == "0").collect::<Vec<_>>();
+        assert_eq!(stops(None), vec![0]);
+        assert_eq!(stops(Some(3)), vec![3]);
+    }
+
+    #[test]
+    fn test_roving_next_index_clamps_and_respects_orientation() {
+        let vertical = ListOrientation::Vertical;
+        assert_eq!(roving_next_index(&Key::ArrowDown, None, 3, vertical), Some(0));
+        assert_eq!(roving_next_index(&Key::ArrowDown, Some(2), 3, vertical), Some(2));
+        assert_eq!(roving_next_index(&Key::ArrowUp, Some(0), 3, vertical), Some(0));
+        assert_eq!(roving_next_index(&Key::End, Some(0), 3, vertical), Some(2));
+        assert_eq!(roving_next_index(&Key::ArrowRight, Some(0), 3, vertical), None);
+        assert_eq!(roving_next_index(&Key::ArrowRight, Some(0), 3, ListOrientation::Horizontal), Some(1));
+        assert_eq!(roving_next_index(&Key::ArrowDown, None, 0, vertical), None);
+    }
+
+    #[test]
+    fn test_escape_moves_outward_and_regions_cycle() {
+        assert_eq!(FocusRegion::Preview.outer(), Some(FocusRegion::Content));
+        assert_eq!(FocusRegion::Sidebar.outer(), Some(FocusRegion::ActivityBar));
+        assert_eq!(FocusRegion::Content.outer(), None);
+
+        let forward = FocusRegion::Panel.cycle(true);
+        assert_eq!(forward.first(), Some(&FocusRegion::ActivityBar));
+        assert!(!forward.contains(&FocusRegion::Panel));
+        assert_eq!(FocusRegion::ActivityBar.cycle(false).first(), Some(&FocusRegion::Panel));
+    }
+}
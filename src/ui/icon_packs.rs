@@ -2,6 +2,8 @@ use dioxus::prelude::*;
 use dioxus_free_icons::{Icon, icons::{fa_solid_icons, fa_regular_icons, fa_brands_icons}};
 use serde::{Serialize, Deserialize};
 
+use crate::services::classifier::classifier;
+
 // Icon Pack Types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum IconPack {
@@ -49,6 +51,13 @@ impl IconPack {
 pub fn FileIconComponent(file_name: String, extension: Option<String>, is_directory: bool, is_expanded: bool, pack: Option<IconPack>) -> Element {
     let icon_pack = pack.unwrap_or(IconPack::VSCode);
 
+    // A user classification rule with an accent colors the file in every pack
+    if !is_directory {
+        if let Some(accent) = classifier().user_classification(&file_name).and_then(|rule| rule.accent) {
+            return rsx! { Icon { width: 14, height: 14, fill: "{accent}", icon: fa_solid_icons::FaFile } };
+        }
+    }
+
     // Render icons directly based on pack and file type
     match icon_pack {
         IconPack::VSCode => {
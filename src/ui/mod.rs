@@ -7,6 +7,10 @@ pub mod vscode_app;
 pub mod icons;
 pub mod icon_packs;
 pub mod icon_manager;
+pub mod archive_actions;
+pub mod clipboard_actions;
+pub mod focus;
+pub mod click_intent;
 
 pub use phase2_app::phase2_app;
 pub use shortcut_handler::{use_shortcut_handler};
@@ -14,4 +18,5 @@ pub use vscode_layout::{VSCodeLayout, ActivityBar, Sidebar, EditorGroups, Panel,
 pub use vscode_app::VSCodeApp;
 pub use icons::{IconType};
 pub use icon_packs::{IconPack, FileIconComponent};
+pub use focus::{FocusRegion, ListOrientation};
 pub use icon_manager::{IconManager, IconSettings, IconManagerProvider, use_icon_manager};
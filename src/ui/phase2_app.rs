@@ -1,18 +1,29 @@
 use dioxus::prelude::*;
 use std::path::PathBuf;
-use crate::state::{save_panel_state_debounced, load_panel_state, use_app_state, use_file_entries, load_settings, save_settings_debounced};
-use crate::theme::{ThemeManager, use_theme_manager, DynamicThemeStyles};
+use crate::state::{save_panel_state_debounced, load_panel_state, use_app_state, use_file_entries, load_settings, save_settings_debounced, ViewMode, ActivityBarView, SidebarContent};
+use crate::theme::{ThemeManager, SystemAppearance, use_theme_manager, DynamicThemeStyles};
 use std::sync::atomic::{AtomicU32, Ordering};
 use crate::services::file_system::{FileEntry};
 use crate::ui::{use_shortcut_handler};
-use crate::utils::normalize_path_display;
+use crate::ui::shortcut_handler::ShortcutHandler;
+use crate::ui::focus::{self, FocusRegion, ListOrientation};
+use crate::ui::click_intent::{Click, ClickIntent, ClickTracker};
+use crate::utils::{format_date_time, format_relative_time, format_size};
 use crate::ui::components::{
     ContextMenu, use_context_menu,
-    DragPreview, DropZone, DragOperation,
+    DragPreview, DropZone, DragOperation, DropZoneState,
     use_drag_drop, use_drop_zone,
     SettingsPanel, CommandPalette, ShortcutCheatSheet,
-    EmptyFileTree, DynamicContentPanel, SettingsDialog
+    EmptyFileTree, DynamicContentPanel, SettingsDialog, ProgressHud, ErrorStatus, LiveRegion,
+    OperationLogPanel, FolderComparePanel, QuickLookOverlay, SmartFolderList, SmartFolderResults,
+    FileOperationProgressDialog, DirectoryTabStrip, Breadcrumb, BookmarkList, GalleryView, TrashPanel, SplitView, ColumnView, BatchRenameDialog,
+    TagCollectionsView,
 };
+use crate::ui::components::directory_tabs::{restore_listing_scroll, DIRECTORY_LISTING_ID};
+use crate::ui::components::drag_drop::{
+    drag_files_for, drag_out_at_window_edge, drop_onto_folder, selected_entries, track_drag_position, update_drag_operation,
+};
+use crate::state::persistence::{save_directory_tabs, save_split_view};
 use crate::ui::icon_manager::{IconManagerProvider, use_icon_manager};
 use crate::ui::icon_packs::FileIconComponent;
 // use crate::ui::components::preview_panel::FileSystemEntry; // No longer needed - using DynamicContentPanel
@@ -41,7 +52,6 @@ fn Phase2AppContent() -> Element {
     let mut app_state = use_app_state();
     let app_state_for_load = app_state.clone();
     let app_state_for_status = app_state.clone();
-    let mut app_state_for_folder_select = app_state.clone();
     let mut app_state_for_startup = app_state.clone();
     let file_entries = use_file_entries();
 
@@ -53,7 +63,8 @@ fn Phase2AppContent() -> Element {
     static THEME_REFRESH_COUNTER: AtomicU32 = AtomicU32::new(0);
     let theme_refresh_trigger = use_signal(|| 0u32);
     
-    // Load settings and restore last opened folder on startup
+    // Load settings and restore the last run's tabs, or the last opened folder, and split view on startup
+    let mut directory_tabs_restored = use_signal(|| false);
     use_future(move || {
         let mut app_state_for_startup = app_state_for_startup.clone();
         async move {
@@ -68,18 +79,61 @@ fn Phase2AppContent() -> Element {
             css_vars.insert("--vscode-font-size".to_string(), settings.font_size.css_value().to_string());
             crate::theme::ThemeManager::apply_custom_css_variables(&css_vars);
             
-            // Try to restore last opened folder if enabled
-            if let Err(e) = app_state_for_startup.restore_last_opened_folder().await {
+            // Reopen the directory tabs, falling back to the last opened folder if enabled
+            if let Some(scroll_top) = app_state_for_startup.restore_directory_tabs().await {
+                restore_listing_scroll(scroll_top);
+            } else if let Err(e) = app_state_for_startup.restore_last_opened_folder().await {
                 tracing::warn!("Failed to restore last opened folder: {}", e);
             }
+            app_state_for_startup.restore_split_view().await;
+            directory_tabs_restored.set(true);
+        }
+    });
+
+    // Save the directory tabs whenever a tab or its folder, selection or view changes
+    let app_state_for_tabs = app_state.clone();
+    let directory_tab_session = use_memo(move || app_state_for_tabs.directory_tab_session());
+    use_effect(move || {
+        // Saving before the restore finishes would overwrite the last session
+        if *directory_tabs_restored.read() {
+            save_directory_tabs(&directory_tab_session.read());
+        }
+    });
+
+    // Save the split layout whenever it is toggled, resized or a pane moves
+    let app_state_for_split = app_state.clone();
+    let split_view_session = use_memo(move || app_state_for_split.split_view_session());
+    use_effect(move || {
+        if *directory_tabs_restored.read() {
+            save_split_view(&split_view_session.read());
         }
     });
     let mut selected_item = use_signal::<Option<FileEntry>>(|| None);
+
+    // Roving tabindex position in the file list and the region that last held focus
+    let mut active_file_index = use_signal::<Option<usize>>(|| None);
+    // Pairs up the two clicks of a double click in the file list
+    let mut click_tracker = use_signal(ClickTracker::default);
+    let mut active_region = use_signal(|| FocusRegion::Sidebar);
+    
+    // Which view the sidebar shows: the file tree or the tags
+    let mut activity_view = app_state.active_activity_view;
+    let sidebar_content = activity_view.read().sidebar_content();
     
     // Initialize theme system
     let mut theme_manager = use_theme_manager();
     let mut current_settings = use_signal(|| load_settings());
     
+    // Resolve strings and format values per the user's preferences; reading
+    // the settings here re-renders the app when they change
+    {
+        let settings = current_settings.read();
+        crate::i18n::set_locale(settings.language);
+        crate::utils::format::set_format_settings(settings.format_settings());
+        crate::services::file_system::set_content_sniffing(settings.content_sniffing);
+        crate::services::preview::thumbnail_encoding::set_thumbnail_cache_format(settings.thumbnail_cache_format());
+    }
+    
     // Initialize keyboard shortcut handler
     let shortcut_handler = use_shortcut_handler();
     
@@ -89,6 +143,8 @@ fn Phase2AppContent() -> Element {
     // Initialize drag-and-drop
     let (mut drag_state, _start_drag, _update_drag, _end_drag) = use_drag_drop();
     let (left_panel_drop_state, _set_left_panel_drop_state) = use_drop_zone();
+    // Folder row under a drag, with whether it would take the dragged files
+    let mut folder_drop_target = use_signal(|| None::<(PathBuf, DropZoneState)>);
     let (right_panel_drop_state, _set_right_panel_drop_state) = use_drop_zone();
     
     // Initialize settings panel state
@@ -107,33 +163,40 @@ fn Phase2AppContent() -> Element {
         tracing::info!("Theme system initialized with theme: {:?}", settings.theme);
     });
     
-    // Periodic system theme check for Auto mode
+    // Follow system appearance changes while the theme is Auto; the watcher
+    // stops as soon as another theme is chosen
+    let mut system_appearance = use_signal(|| None::<SystemAppearance>);
+    let mut system_theme_task = use_signal(|| None::<Task>);
     use_effect(move || {
-        let mut theme_manager_clone = theme_manager.clone();
-        let mut current_settings_clone = current_settings.clone();
-        
-        spawn(async move {
-            loop {
-                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
-                
-                // Check if system theme changed while in Auto mode
-                let mut settings = current_settings_clone.read().clone();
-                let theme_changed = theme_manager_clone.write().check_system_theme_change(&mut settings);
-                
-                if theme_changed {
-                    // Update settings if system theme changed
-                    save_settings_debounced(settings.clone());
-                    current_settings_clone.set(settings);
-                    tracing::info!("System theme change detected and applied");
-                }
+        let theme = current_settings.read().theme.clone();
+        let changes = {
+            let mut manager = theme_manager.write();
+            manager.current_theme = theme;
+            manager.setup_system_theme_listener()
+        };
+        if let Some(task) = system_theme_task.take() {
+            task.cancel();
+        }
+        let Some(mut changes) = changes else {
+            system_appearance.set(None);
+            return;
+        };
+        system_theme_task.set(Some(spawn(async move {
+            while changes.changed().await.is_ok() {
+                let Some(appearance) = *changes.borrow_and_update() else {
+                    continue;
+                };
+                tracing::info!("System appearance changed to {:?}, re-applying Auto theme", appearance);
+                theme_manager.write().last_detected_system_theme = appearance.dark;
+                system_appearance.set(Some(appearance));
             }
-        });
+        })));
     });
 
     // Keyboard shortcut handler for settings panel
     let handle_keydown = {
         let mut settings_panel_visible = settings_panel_visible;
-        let shortcut_handler = shortcut_handler;
+        let shortcut_handler = shortcut_handler.clone();
         let mut app_state_for_shortcuts = app_state.clone();
         
         move |evt: KeyboardEvent| {
@@ -149,16 +212,8 @@ fn Phase2AppContent() -> Element {
             tracing::info!("Keyboard event: {} (ctrl: {}, shift: {}, alt: {}, meta: {})", 
                          key_str, ctrl, shift, alt, meta);
             
-            // Check for settings shortcut (Ctrl+,)
-            if key_str == "," && ctrl && !shift && !alt && !meta {
-                app_state_for_shortcuts.settings_dialog_visible.set(true);
-                evt.prevent_default();
-                tracing::info!("Settings dialog opened via keyboard shortcut");
-                return;
-            }
-            
-            // Check for theme cycle shortcut (Ctrl+T)
-            if key_str == "t" && ctrl && !shift && !alt && !meta {
+            // Check for theme cycle shortcut (Ctrl+Shift+L); Ctrl+T opens a folder tab
+            if key_str.eq_ignore_ascii_case("l") && ctrl && shift && !alt && !meta {
                 let mut settings = {
                     let mut s = current_settings.write();
                     s.clone()
@@ -190,16 +245,14 @@ fn Phase2AppContent() -> Element {
                 return;
             }
             
-            // Check for F1 to toggle shortcut cheat sheet
-            if key_str == "F1" && !ctrl && !shift && !alt && !meta {
-                let current_visibility = *app_state_for_shortcuts.cheat_sheet_visible.read();
-                app_state_for_shortcuts.cheat_sheet_visible.set(!current_visibility);
+            // F6 / Shift+F6 cycle focus between regions
+            if key_str == "F6" && !ctrl && !alt && !meta {
+                focus::focus_adjacent_region(*active_region.read(), !shift);
                 evt.prevent_default();
-                tracing::info!("Shortcut cheat sheet toggled via F1 key: {}", !current_visibility);
                 return;
             }
             
-            // Handle other shortcuts
+            // Everything else goes through the (possibly remapped) keymap
             let mut handler = shortcut_handler.clone();
             spawn(async move {
                 let handled = handler.handle_keyboard_event(&key_str, ctrl, shift, alt, meta).await;
@@ -230,6 +283,12 @@ fn Phase2AppContent() -> Element {
         });
     });
 
+    // Folders get their recursive size while the listing is sorted by size
+    use_effect({
+        let mut app_state = app_state.clone();
+        move || app_state.size_listed_folders()
+    });
+
     // Save state when panel width changes (debounced)
     use_effect(move || {
         let current_width = panel_width.read();
@@ -242,6 +301,19 @@ fn Phase2AppContent() -> Element {
         // Note: In a real app, you'd want to handle this in window.onbeforeunload
     });
 
+    // Auto picks up the system high-contrast mode; otherwise CSS follows the setting
+    let data_theme = use_memo(move || {
+        let theme = current_settings.read().theme.clone();
+        let effective = match (&theme, system_appearance()) {
+            (crate::state::Theme::Auto, Some(appearance)) => appearance.theme(),
+            _ => ThemeManager::get_effective_theme(&theme),
+        };
+        match effective {
+            crate::state::Theme::HighContrast => "high-contrast".to_string(),
+            _ => theme.as_str().to_string(),
+        }
+    });
+
     // Dynamic CSS custom property for the main content grid layout
     let main_content_style = format!("--panel-width: {}px;", panel_width.read());
     
@@ -270,6 +342,7 @@ fn Phase2AppContent() -> Element {
         // Dynamic style for immediate theme changes
         DynamicThemeStyles {
             current_settings: current_settings,
+            system_appearance: system_appearance,
         }
         
         // Hidden element that forces re-render when theme changes
@@ -280,9 +353,26 @@ fn Phase2AppContent() -> Element {
         }
         
         div {
+            id: focus::APP_ROOT_ELEMENT_ID,
             class: "media-organizer-app",
-            tabindex: 0, // Make div focusable for keyboard events
+            "data-theme": data_theme(),
+            lang: current_settings.read().language.code(),
+            tabindex: -1, // Focusable as the outermost Escape target, but not a tab stop
             onkeydown: handle_keydown,
+            // A drag carried past the window edge continues as a file drag into other apps
+            ondragleave: move |evt| {
+                let coordinates = evt.data.client_coordinates();
+                drag_out_at_window_edge(drag_state, coordinates.x, coordinates.y);
+            },
+            onmouseup: move |evt| {
+                // Mouse back/forward buttons walk the folder history
+                let action = evt.trigger_button().and_then(ShortcutHandler::mouse_button_action);
+                if let Some(action) = action {
+                    evt.prevent_default();
+                    let mut handler = shortcut_handler.clone();
+                    spawn(async move { handler.execute_action(action).await });
+                }
+            },
             onmousemove: move |evt| {
                 let current_x = evt.data.client_coordinates().x as f64;
                 let current_y = evt.data.client_coordinates().y as f64;
@@ -350,114 +440,12 @@ fn Phase2AppContent() -> Element {
             }
             
             // Path display bar above main content
-            if let Some(current_path) = app_state.get_file_tree_root() {
+            if app_state.has_file_tree_root() {
                 div {
                     class: "path-display-bar",
                     role: "banner",
                     "aria-label": "Current folder path",
-                    style: "
-                        background: var(--vscode-breadcrumb-background, #2d2d30);
-                        border-bottom: 1px solid var(--vscode-border, #464647);
-                        padding: 8px 16px;
-                        font-size: 0.9em;
-                        color: var(--vscode-breadcrumb-foreground, #cccccc);
-                        display: flex;
-                        align-items: center;
-                        min-height: 32px;
-                        user-select: none;
-                    ",
-                    
-                    // Home icon
-                    span {
-                        style: "margin-right: 8px; font-size: 1.1em;",
-                        "🏠"
-                    }
-                    
-                    // Path segments
-                    {
-                        let path_segments = {
-                            let mut segments = Vec::new();
-                            let mut current = current_path.as_path();
-                            
-                            // Build path segments from current path up to root
-                            while let Some(parent) = current.parent() {
-                                if let Some(name) = current.file_name() {
-                                    segments.push((current.to_path_buf(), name.to_string_lossy().to_string()));
-                                }
-                                current = parent;
-                                
-                                // Stop at root to avoid infinite loop
-                                if parent.parent().is_none() {
-                                    segments.push((parent.to_path_buf(), parent.to_string_lossy().to_string()));
-                                    break;
-                                }
-                            }
-                            
-                            // Reverse to show from root to current
-                            segments.reverse();
-                            segments
-                        };
-                        
-                        path_segments.into_iter().enumerate().map(|(index, (path, name))| {
-                            let path_clone = path.clone();
-                            let app_state_for_nav = app_state.clone();
-                            
-                            rsx! {
-                                span {
-                                    key: "path-segment-{index}",
-                                    style: "display: flex; align-items: center;",
-                                    
-                                    // Path separator (except for first segment)
-                                    if index > 0 {
-                                        span {
-                                            style: "margin: 0 6px; color: var(--vscode-breadcrumb-foreground, #999999);",
-                                            "/"
-                                        }
-                                    }
-                                    
-                                    // Clickable path segment
-                                    button {
-                                        style: "
-                                            background: none;
-                                            border: none;
-                                            color: var(--vscode-breadcrumb-foreground, #cccccc);
-                                            cursor: pointer;
-                                            padding: 4px 6px;
-                                            border-radius: 3px;
-                                            font-size: inherit;
-                                            font-family: inherit;
-                                            transition: background-color 0.2s;
-                                        ",
-                                        onmouseenter: move |_| {
-                                            // Add hover effect via inline style
-                                        },
-                                        onmouseleave: move |_| {
-                                            // Remove hover effect via inline style
-                                        },
-                                        onclick: move |_| {
-                                            let path_to_navigate = path_clone.clone();
-                                            let mut app_state_nav = app_state_for_nav.clone();
-                                            
-                                            tracing::info!("Navigating to path segment: {:?}", path_to_navigate);
-                                            
-                                            spawn(async move {
-                                                match app_state_nav.handle_folder_change(path_to_navigate.clone()).await {
-                                                    Ok(()) => {
-                                                        tracing::info!("Successfully navigated to: {:?}", path_to_navigate);
-                                                    }
-                                                    Err(e) => {
-                                                        tracing::error!("Failed to navigate to {:?}: {}", path_to_navigate, e);
-                                                    }
-                                                }
-                                            });
-                                        },
-                                        title: format!("Navigate to {}", normalize_path_display(&path)),
-                                        {name}
-                                    }
-                                }
-                            }
-                        })
-                    }
+                    Breadcrumb {}
                 }
             }
             
@@ -472,278 +460,289 @@ fn Phase2AppContent() -> Element {
                 DropZone {
                     drop_state: left_panel_drop_state,
                     target_path: Some(app_state.get_current_path()),
-                    on_drop: move |data: (Vec<FileEntry>, DragOperation, PathBuf)| {
-                        let (files, operation, target) = data;
-                        tracing::info!("Files dropped in left panel: {} files with {:?} operation to {:?}", 
-                                     files.len(), operation, target);
-                        // TODO: Handle file drop operation
+                    drag_state: drag_state,
+                    on_drop: {
+                        let app_state = app_state.clone();
+                        move |data: (Vec<FileEntry>, DragOperation, PathBuf)| {
+                            let (files, operation, target) = data;
+                            tracing::info!("Files dropped in left panel: {} files with {:?} operation to {:?}", 
+                                         files.len(), operation, target);
+                            spawn(drop_onto_folder(app_state.clone(), files, operation, target));
+                        }
                     },
                     
                     div {
-                        class: "{panel_class}",
+                        id: FocusRegion::Sidebar.element_id(),
+                        class: "{panel_class} focus-region",
                         role: "navigation",
-                        "aria-label": "File explorer",
+                        "aria-label": FocusRegion::Sidebar.label(),
+                        tabindex: -1,
+                        onfocusin: move |_| active_region.set(FocusRegion::Sidebar),
+                        onkeydown: move |evt| {
+                            if evt.data.key() == dioxus::events::Key::Escape {
+                                focus::focus_outward(FocusRegion::Sidebar);
+                            }
+                        },
                     
                     // File tree header
                     
-                    // Virtual file tree content
+                    // Explorer or Tags, the two views the sidebar can show
                     div {
-                        class: "file-tree-content",
-                        role: "region",
-                        "aria-label": "File list",
-                        style: "height: calc(100vh - 120px); overflow: hidden;", // Reserve space for header and status bar
-                        
-                        // Show empty state if no folder is selected
-                        if !app_state.has_file_tree_root() {
-                            EmptyFileTree {
-                                on_folder_select: move |_| {
-                                    tracing::info!("Folder selection requested from empty state");
-                                    let mut app_state_for_folder_select = app_state_for_folder_select.clone();
-                                    // Open folder selection dialog
-                                    spawn(async move {
-                                        use rfd::AsyncFileDialog;
-                                        
-                                        if let Some(folder) = AsyncFileDialog::new()
-                                            .set_title("Select Folder to Open")
-                                            .pick_folder()
-                                            .await
-                                        {
-                                            let folder_path = folder.path().to_path_buf();
-                                            tracing::info!("User selected folder: {:?}", folder_path);
-                                            
-                                            // Use app state to handle folder change with persistence
-                                            match app_state_for_folder_select.handle_folder_change(folder_path.clone()).await {
-                                                Ok(()) => {
-                                                    tracing::info!("Successfully loaded folder with persistence: {:?}", folder_path);
-                                                }
-                                                Err(e) => {
-                                                    tracing::error!("Failed to load folder {:?}: {}", folder_path, e);
-                                                }
-                                            }
-                                        } else {
-                                            tracing::info!("User cancelled folder selection");
-                                        }
-                                    });
+                        class: "sidebar-views",
+                        role: "tablist",
+                        "aria-label": t!("sidebar.views"),
+                        for (view, label) in [(ActivityBarView::Explorer, t!("sidebar.explorer")), (ActivityBarView::Tags, t!("tags.title"))] {
+                            {
+                                let is_active = sidebar_content == view.sidebar_content();
+                                rsx! {
+                                    button {
+                                        key: "{label}",
+                                        class: if is_active { "sidebar-view active" } else { "sidebar-view" },
+                                        role: "tab",
+                                        "aria-selected": is_active.to_string(),
+                                        onclick: move |_| activity_view.set(view.clone()),
+                                        "{label}"
+                                    }
                                 }
                             }
-                        } else {
-                            // Show the loaded file tree
-                            if let Some(root_path) = app_state.get_file_tree_root() {
-                                div {
-                                    style: "",
+                        }
+                    }
+                    
+                    if sidebar_content == SidebarContent::Tags {
+                        TagCollectionsView {}
+                    } else {
+                        // Favorite folders
+                        BookmarkList {}
+                    
+                        // Saved searches
+                        SmartFolderList { settings: current_settings }
+                    
+                        // System trash
+                        button {
+                            class: "trash-entry",
+                            onclick: move |_| app_state.trash_visible.set(true),
+                            "🗑 Trash"
+                        }
+                    
+                        // Open folders
+                        DirectoryTabStrip {}
+                    
+                        // Virtual file tree content
+                        div {
+                            class: "file-tree-content",
+                            role: "region",
+                            "aria-label": "File list",
+                            style: "height: calc(100vh - 120px); overflow: hidden;", // Reserve space for header and status bar
+                        
+                            // Show empty state if no folder is selected
+                            if !app_state.has_file_tree_root() {
+                                EmptyFileTree { state: None }
+                            } else {
+                                // Show the loaded file tree
+                                if let Some(root_path) = app_state.get_file_tree_root() {
+                                    div {
+                                        style: "",
                                     
                                     
-                                    // Show loading state
-                                    if app_state.is_file_tree_directory_loading(&root_path) {
-                                        div {
-                                            style: "padding: 20px; text-align: center; color: var(--vscode-text-secondary, #999999);",
-                                            "Loading directory contents..."
-                                        }
-                                    }
-                                    // Show error state
-                                    else if let Some(error) = app_state.get_file_tree_directory_error(&root_path) {
-                                        div {
-                                            style: "padding: 20px; text-align: center; color: var(--vscode-error, #f44747);",
-                                            "Error loading directory:"
+                                        // Show loading state
+                                        if app_state.is_file_tree_directory_loading(&root_path) {
                                             div {
-                                                style: "margin-top: 5px; font-size: 0.9em;",
-                                                {error}
+                                                style: "padding: 20px; text-align: center; color: var(--vscode-text-secondary, #999999);",
+                                                "Loading directory contents..."
                                             }
                                         }
-                                    }
-                                    // Show file list
-                                    else if let Some(children) = app_state.get_file_tree_children(&root_path) {
-                                        {
-                                            let children_count = children.len();
-                                            rsx! {
+                                        // Show error state
+                                        else if let Some(error) = app_state.get_file_tree_directory_error(&root_path) {
+                                            div {
+                                                style: "padding: 20px; text-align: center; color: var(--vscode-error, #f44747);",
+                                                "Error loading directory:"
                                                 div {
-                                                    role: "list",
-                                                    "aria-label": format!("Directory contents - {} items", children_count),
-                                                    style: "max-height: calc(100vh - 220px); overflow-y: auto;",
-                                                    {
-                                                        children.into_iter().enumerate().map(|(index, entry)| {
-                                                    let entry_clone = entry.clone();
-                                                    let entry_clone_key = entry.clone();
-                                                    let entry_clone_menu = entry.clone();
-                                                    let entry_clone_drag = entry.clone();
-                                                    let mut drag_state_clone = drag_state.clone();
-                                                    let mut app_state_clone = app_state.clone();
-                                                    let mut app_state_clone_key = app_state.clone();
+                                                    style: "margin-top: 5px; font-size: 0.9em;",
+                                                    {error}
+                                                }
+                                            }
+                                        }
+                                        // Nothing to list: the folder is empty or filtered down to nothing
+                                        else if let Some(empty_state) = app_state.file_tree_empty_state(&root_path) {
+                                            EmptyFileTree { state: Some(empty_state) }
+                                        }
+                                        // Show file list
+                                        else if let Some(children) = app_state.get_file_tree_visible_children(&root_path) {
+                                            {
+                                                let children_count = children.len();
+                                                // Ignore a stale position left over from a longer listing
+                                                let active_index = active_file_index.read().filter(|&i| i < children_count);
+                                                rsx! {
+                                                    div {
+                                                        id: DIRECTORY_LISTING_ID,
+                                                        role: "tree",
+                                                        "aria-label": format!("Directory contents - {} items", children_count),
+                                                        "aria-multiselectable": "true",
+                                                        style: "max-height: calc(100vh - 220px); overflow-y: auto;",
+                                                        {
+                                                            children.into_iter().enumerate().map(|(index, entry)| {
+                                                        let entry_clone = entry.clone();
+                                                        let entry_clone_key = entry.clone();
+                                                        let entry_clone_menu = entry.clone();
+                                                        let entry_clone_drag = entry.clone();
+                                                        let entry_path_drop = entry.path.clone();
+                                                        let mut drag_state_clone = drag_state.clone();
+                                                        let app_state_drag = app_state.clone();
+                                                        let app_state_drop = app_state.clone();
+                                                        let row_drop_state = folder_drop_target.read().as_ref()
+                                                            .filter(|(path, _)| *path == entry.path)
+                                                            .map(|(_, state)| state.clone());
+                                                        let is_selected = app_state.is_selected(&entry.path);
+                                                        let mut row_class = if is_selected { "file-tree-item selected".to_string() } else { "file-tree-item".to_string() };
+                                                        if let Some(state) = &row_drop_state {
+                                                            row_class = format!("{} {}", row_class, state.style_class());
+                                                        }
+                                                        let mut app_state_clone = app_state.clone();
+                                                        let mut app_state_clone_key = app_state.clone();
                                                     
-                                                    rsx! {
-                                                        div {
-                                                            key: "entry-{index}-{entry.path.to_string_lossy()}",
-                                                            class: "file-tree-item",
-                                                            tabindex: 0,
-                                                            role: "listitem",
-                                                            "aria-label": format!("{} {}{}", if entry.is_directory { "Folder" } else { "File" }, entry.name, if entry.size > 0 { format!(", {} bytes", entry.size) } else { String::new() }),
-                                                            "aria-describedby": format!("file-details-{}", index),
-                                                            draggable: true,
+                                                        rsx! {
+                                                            div {
+                                                                key: "entry-{index}-{entry.path.to_string_lossy()}",
+                                                                id: file_list_item_id(index),
+                                                                class: "{row_class}",
+                                                                tabindex: focus::roving_tabindex(index, active_index),
+                                                                role: "treeitem",
+                                                                "aria-level": "1",
+                                                                "aria-selected": is_selected.to_string(),
+                                                                "aria-label": format!("{} {}{}", if entry.is_directory { "Folder" } else { "File" }, entry.name, if entry.size > 0 { format!(", {}", format_size(entry.size)) } else { String::new() }),
+                                                                "aria-describedby": format!("file-details-{}", index),
+                                                                title: if entry.size > 0 {
+                                                                    format!("{} · Modified {}", format_size(entry.size), format_relative_time(entry.modified))
+                                                                } else {
+                                                                    format!("Modified {}", format_relative_time(entry.modified))
+                                                                },
+                                                                draggable: true,
                                                             
-                                                            onclick: move |_| {
-                                                                tracing::info!("File clicked: {}", entry_clone.name);
-                                                                selected_item.set(Some(entry_clone.clone()));
-                                                                app_state_clone.set_file_tree_selection(Some(entry_clone.path.clone()));
+                                                                onclick: move |evt| {
+                                                                    tracing::info!("File clicked: {}", entry_clone.name);
+                                                                    active_file_index.set(Some(index));
+                                                                    let modifiers = evt.modifiers();
+                                                                    let click = Click {
+                                                                        path: &entry_clone.path,
+                                                                        is_directory: entry_clone.is_directory,
+                                                                        with_modifiers: modifiers.shift() || modifiers.ctrl() || modifiers.meta(),
+                                                                        at: std::time::Instant::now(),
+                                                                    };
+                                                                    let double_click = app_state_clone.settings.read().double_click;
+                                                                    // The first click already selected it
+                                                                    if let ClickIntent::Activate(action) = click_tracker.write().click(click, &double_click) {
+                                                                        let mut app_state = app_state_clone.clone();
+                                                                        let path = entry_clone.path.clone();
+                                                                        spawn(async move {
+                                                                            if let Err(e) = app_state.activate_entry(path.clone(), action).await {
+                                                                                tracing::error!("Failed to open {:?}: {}", path, e);
+                                                                            }
+                                                                        });
+                                                                        return;
+                                                                    }
+                                                                    if modifiers.shift() {
+                                                                        app_state_clone.select_range_to(entry_clone.path.clone());
+                                                                    } else if modifiers.ctrl() || modifiers.meta() {
+                                                                        app_state_clone.toggle_selection(entry_clone.path.clone());
+                                                                    } else {
+                                                                        app_state_clone.select_single(entry_clone.path.clone());
+                                                                    }
+                                                                    selected_item.set(Some(entry_clone.clone()));
+                                                                    app_state_clone.set_file_tree_selection(Some(entry_clone.path.clone()));
                                                                 
-                                                                // Generate preview for clicked file
-                                                                let preview_path = entry_clone.path.clone();
-                                                                let is_dir = entry_clone.is_directory;
-                                                                let mut app_state_for_preview = app_state_clone.clone();
-                                                                spawn(async move {
-                                                                    // Enhanced error handling with retry mechanism
-                                                                    let mut retry_count = 0;
-                                                                    const MAX_RETRIES: u8 = 3;
+                                                                    // Generate preview for clicked file
+                                                                    let preview_path = entry_clone.path.clone();
+                                                                    let is_dir = entry_clone.is_directory;
+                                                                    let mut app_state_for_preview = app_state_clone.clone();
+                                                                    spawn(async move {
+                                                                        // Enhanced error handling with retry mechanism
+                                                                        let mut retry_count = 0;
+                                                                        const MAX_RETRIES: u8 = 3;
                                                                     
-                                                                    loop {
-                                                                        match app_state_for_preview.handle_file_selection(preview_path.clone(), is_dir).await {
-                                                                            Ok(maybe_preview) => {
-                                                                                // Update the preview_data signal with the returned preview
-                                                                                app_state_for_preview.preview_data.set(maybe_preview.clone());
+                                                                        loop {
+                                                                            match app_state_for_preview.handle_file_selection(preview_path.clone(), is_dir).await {
+                                                                                Ok(maybe_preview) => {
+                                                                                    // Update the preview_data signal with the returned preview
+                                                                                    app_state_for_preview.preview_data.set(maybe_preview.clone());
                                                                                 
-                                                                                if maybe_preview.is_some() {
-                                                                                    tracing::info!("Preview generated successfully for: {:?}", preview_path);
-                                                                                } else {
-                                                                                    tracing::info!("No preview generated for directory: {:?}", preview_path);
+                                                                                    if maybe_preview.is_some() {
+                                                                                        tracing::info!("Preview generated successfully for: {:?}", preview_path);
+                                                                                    } else {
+                                                                                        tracing::info!("No preview generated for directory: {:?}", preview_path);
+                                                                                    }
+                                                                                    break;
                                                                                 }
-                                                                                break;
-                                                                            }
-                                                                            Err(e) => {
-                                                                                retry_count += 1;
+                                                                                Err(e) => {
+                                                                                    retry_count += 1;
                                                                                 
-                                                                                if retry_count >= MAX_RETRIES {
-                                                                                    tracing::error!("Failed to generate preview after {} attempts for {:?}: {}", MAX_RETRIES, preview_path, e);
+                                                                                    if retry_count >= MAX_RETRIES {
+                                                                                        tracing::error!("Failed to generate preview after {} attempts for {:?}: {}", MAX_RETRIES, preview_path, e);
                                                                                     
-                                                                                    // Create error preview data for better user feedback
-                                                                                    let error_preview = crate::services::preview::PreviewData {
-                                                                                        file_path: preview_path.clone(),
-                                                                                        format: crate::services::preview::SupportedFormat::Text,
-                                                                                        thumbnail_path: None,
-                                                                                        metadata: crate::services::preview::FileMetadata {
-                                                                                            file_size: std::fs::metadata(&preview_path).map(|m| m.len()).unwrap_or(0),
-                                                                                            created: std::fs::metadata(&preview_path).ok().and_then(|m| m.created().ok()),
-                                                                                            modified: std::fs::metadata(&preview_path).ok().and_then(|m| m.modified().ok()),
-                                                                                            width: None, height: None, duration: None, bit_rate: None,
-                                                                                            sample_rate: None, codec: None, title: None, artist: None,
-                                                                                            album: None, year: None, page_count: None, color_space: None,
-                                                                                            compression: None, exif_data: None,
-                                                                                        },
-                                                                                        preview_content: crate::services::preview::PreviewContent::Unsupported {
-                                                                                            file_type: preview_path.extension()
-                                                                                                .and_then(|ext| ext.to_str())
-                                                                                                .unwrap_or("unknown").to_string(),
-                                                                                            reason: format!("Preview generation failed: {}", e),
-                                                                                            suggested_action: Some("Check file permissions or try refreshing".to_string()),
-                                                                                        },
-                                                                                        generated_at: std::time::SystemTime::now(),
-                                                                                    };
+                                                                                        // Create error preview data for better user feedback
+                                                                                        let error_preview = crate::services::preview::PreviewData {
+                                                                                            file_path: preview_path.clone(),
+                                                                                            format: crate::services::preview::SupportedFormat::Text,
+                                                                                            thumbnail_path: None,
+                                                                                            metadata: crate::services::preview::FileMetadata {
+                                                                                                file_size: std::fs::metadata(&preview_path).map(|m| m.len()).unwrap_or(0),
+                                                                                                created: std::fs::metadata(&preview_path).ok().and_then(|m| m.created().ok()),
+                                                                                                modified: std::fs::metadata(&preview_path).ok().and_then(|m| m.modified().ok()),
+                                                                                                width: None, height: None, duration: None, bit_rate: None,
+                                                                                                sample_rate: None, codec: None, title: None, artist: None,
+                                                                                                album: None, year: None, page_count: None, color_space: None,
+                                                                                                compression: None, exif_data: None,
+                                                                                            },
+                                                                                            preview_content: crate::services::preview::PreviewContent::Unsupported {
+                                                                                                file_type: preview_path.extension()
+                                                                                                    .and_then(|ext| ext.to_str())
+                                                                                                    .unwrap_or("unknown").to_string(),
+                                                                                                reason: format!("Preview generation failed: {}", e),
+                                                                                                suggested_action: Some("Check file permissions or try refreshing".to_string()),
+                                                                                            },
+                                                                                            generated_at: std::time::SystemTime::now(),
+                                                                                        };
                                                                                     
-                                                                                    app_state_for_preview.preview_data.set(Some(error_preview));
-                                                                                    break;
-                                                                                } else {
-                                                                                    tracing::warn!("Preview generation failed (attempt {}/{}), retrying: {:?}: {}", retry_count, MAX_RETRIES, preview_path, e);
-                                                                                    // Brief delay before retry to avoid overwhelming the system
-                                                                                    tokio::time::sleep(std::time::Duration::from_millis(100 * retry_count as u64)).await;
-                                                                                }
-                                                                            }
-                                                                        }
-                                                                    }
-                                                                });
-                                                            },
-                                                            
-                                                            onkeydown: move |evt| {
-                                                                let key = evt.data.key();
-                                                                match key {
-                                                                    dioxus::events::Key::Enter => {
-                                                                        tracing::info!("File selected via keyboard: {}", entry_clone_key.name);
-                                                                        selected_item.set(Some(entry_clone_key.clone()));
-                                                                        
-                                                                        // Generate preview for keyboard-selected file
-                                                                        let preview_path = entry_clone_key.path.clone();
-                                                                        let is_dir = entry_clone_key.is_directory;
-                                                                        let mut app_state_for_kb_preview = app_state_clone_key.clone();
-                                                                        spawn(async move {
-                                                                            // Enhanced error handling with retry mechanism for keyboard selection
-                                                                            let mut retry_count = 0;
-                                                                            const MAX_RETRIES: u8 = 3;
-                                                                            
-                                                                            loop {
-                                                                                match app_state_for_kb_preview.handle_file_selection(preview_path.clone(), is_dir).await {
-                                                                                    Ok(maybe_preview) => {
-                                                                                        // Update the preview_data signal with the returned preview
-                                                                                        app_state_for_kb_preview.preview_data.set(maybe_preview.clone());
-                                                                                        
-                                                                                        if maybe_preview.is_some() {
-                                                                                            tracing::info!("Preview generated successfully for: {:?}", preview_path);
-                                                                                        } else {
-                                                                                            tracing::info!("No preview generated for directory: {:?}", preview_path);
-                                                                                        }
+                                                                                        app_state_for_preview.preview_data.set(Some(error_preview));
                                                                                         break;
-                                                                                    }
-                                                                                    Err(e) => {
-                                                                                        retry_count += 1;
-                                                                                        
-                                                                                        if retry_count >= MAX_RETRIES {
-                                                                                            tracing::error!("Failed to generate preview after {} attempts for {:?}: {}", MAX_RETRIES, preview_path, e);
-                                                                                            
-                                                                                            // Create error preview data for better user feedback
-                                                                                            let error_preview = crate::services::preview::PreviewData {
-                                                                                                file_path: preview_path.clone(),
-                                                                                                format: crate::services::preview::SupportedFormat::Text,
-                                                                                                thumbnail_path: None,
-                                                                                                metadata: crate::services::preview::FileMetadata {
-                                                                                                    file_size: std::fs::metadata(&preview_path).map(|m| m.len()).unwrap_or(0),
-                                                                                                    created: std::fs::metadata(&preview_path).ok().and_then(|m| m.created().ok()),
-                                                                                                    modified: std::fs::metadata(&preview_path).ok().and_then(|m| m.modified().ok()),
-                                                                                                    width: None, height: None, duration: None, bit_rate: None,
-                                                                                                    sample_rate: None, codec: None, title: None, artist: None,
-                                                                                                    album: None, year: None, page_count: None, color_space: None,
-                                                                                                    compression: None, exif_data: None,
-                                                                                                },
-                                                                                                preview_content: crate::services::preview::PreviewContent::Unsupported {
-                                                                                                    file_type: preview_path.extension()
-                                                                                                        .and_then(|ext| ext.to_str())
-                                                                                                        .unwrap_or("unknown").to_string(),
-                                                                                                    reason: format!("Preview generation failed: {}", e),
-                                                                                                    suggested_action: Some("Check file permissions or try refreshing".to_string()),
-                                                                                                },
-                                                                                                generated_at: std::time::SystemTime::now(),
-                                                                                            };
-                                                                                            
-                                                                                            app_state_for_kb_preview.preview_data.set(Some(error_preview));
-                                                                                            break;
-                                                                                        } else {
-                                                                                            tracing::warn!("Preview generation failed (attempt {}/{}), retrying: {:?}: {}", retry_count, MAX_RETRIES, preview_path, e);
-                                                                                            // Brief delay before retry
-                                                                                            tokio::time::sleep(std::time::Duration::from_millis(100 * retry_count as u64)).await;
-                                                                                        }
+                                                                                    } else {
+                                                                                        tracing::warn!("Preview generation failed (attempt {}/{}), retrying: {:?}: {}", retry_count, MAX_RETRIES, preview_path, e);
+                                                                                        // Brief delay before retry to avoid overwhelming the system
+                                                                                        tokio::time::sleep(std::time::Duration::from_millis(100 * retry_count as u64)).await;
                                                                                     }
                                                                                 }
                                                                             }
-                                                                        });
-                                                                        
+                                                                        }
+                                                                    });
+                                                                },
+                                                            
+                                                                onkeydown: move |evt| {
+                                                                    let key = evt.data.key();
+                                                                    if let Some(next) = focus::roving_next_index(&key, Some(index), children_count, ListOrientation::Vertical) {
+                                                                        active_file_index.set(Some(next));
+                                                                        focus::focus_element(&file_list_item_id(next));
                                                                         evt.prevent_default();
-                                                                    },
-                                                                    _ => {
-                                                                        let key_str = key.to_string();
-                                                                        if key_str == " " || key_str == "Space" {
+                                                                        return;
+                                                                    }
+                                                                    match key {
+                                                                        dioxus::events::Key::Enter => {
                                                                             tracing::info!("File selected via keyboard: {}", entry_clone_key.name);
                                                                             selected_item.set(Some(entry_clone_key.clone()));
-                                                                            
-                                                                            // Generate preview for space-selected file
+                                                                        
+                                                                            // Generate preview for keyboard-selected file
                                                                             let preview_path = entry_clone_key.path.clone();
                                                                             let is_dir = entry_clone_key.is_directory;
-                                                                            let mut app_state_for_space_preview = app_state_clone_key.clone();
+                                                                            let mut app_state_for_kb_preview = app_state_clone_key.clone();
                                                                             spawn(async move {
-                                                                                // Enhanced error handling with retry mechanism for space selection
+                                                                                // Enhanced error handling with retry mechanism for keyboard selection
                                                                                 let mut retry_count = 0;
                                                                                 const MAX_RETRIES: u8 = 3;
-                                                                                
+                                                                            
                                                                                 loop {
-                                                                                    match app_state_for_space_preview.handle_file_selection(preview_path.clone(), is_dir).await {
+                                                                                    match app_state_for_kb_preview.handle_file_selection(preview_path.clone(), is_dir).await {
                                                                                         Ok(maybe_preview) => {
                                                                                             // Update the preview_data signal with the returned preview
-                                                                                            app_state_for_space_preview.preview_data.set(maybe_preview.clone());
-                                                                                            
+                                                                                            app_state_for_kb_preview.preview_data.set(maybe_preview.clone());
+                                                                                        
                                                                                             if maybe_preview.is_some() {
                                                                                                 tracing::info!("Preview generated successfully for: {:?}", preview_path);
                                                                                             } else {
@@ -753,10 +752,10 @@ fn Phase2AppContent() -> Element {
                                                                                         }
                                                                                         Err(e) => {
                                                                                             retry_count += 1;
-                                                                                            
+                                                                                        
                                                                                             if retry_count >= MAX_RETRIES {
                                                                                                 tracing::error!("Failed to generate preview after {} attempts for {:?}: {}", MAX_RETRIES, preview_path, e);
-                                                                                                
+                                                                                            
                                                                                                 // Create error preview data for better user feedback
                                                                                                 let error_preview = crate::services::preview::PreviewData {
                                                                                                     file_path: preview_path.clone(),
@@ -780,8 +779,8 @@ fn Phase2AppContent() -> Element {
                                                                                                     },
                                                                                                     generated_at: std::time::SystemTime::now(),
                                                                                                 };
-                                                                                                
-                                                                                                app_state_for_space_preview.preview_data.set(Some(error_preview));
+                                                                                            
+                                                                                                app_state_for_kb_preview.preview_data.set(Some(error_preview));
                                                                                                 break;
                                                                                             } else {
                                                                                                 tracing::warn!("Preview generation failed (attempt {}/{}), retrying: {:?}: {}", retry_count, MAX_RETRIES, preview_path, e);
@@ -792,102 +791,246 @@ fn Phase2AppContent() -> Element {
                                                                                     }
                                                                                 }
                                                                             });
-                                                                            
+                                                                        
                                                                             evt.prevent_default();
+                                                                        },
+                                                                        _ => {
+                                                                            let key_str = key.to_string();
+                                                                            if key_str == " " || key_str == "Space" {
+                                                                                tracing::info!("File selected via keyboard: {}", entry_clone_key.name);
+                                                                                selected_item.set(Some(entry_clone_key.clone()));
+                                                                                // Quick Look opens on this selection once the key reaches the app
+                                                                                app_state_clone_key.set_file_tree_selection(Some(entry_clone_key.path.clone()));
+                                                                            
+                                                                                // Generate preview for space-selected file
+                                                                                let preview_path = entry_clone_key.path.clone();
+                                                                                let is_dir = entry_clone_key.is_directory;
+                                                                                let mut app_state_for_space_preview = app_state_clone_key.clone();
+                                                                                spawn(async move {
+                                                                                    // Enhanced error handling with retry mechanism for space selection
+                                                                                    let mut retry_count = 0;
+                                                                                    const MAX_RETRIES: u8 = 3;
+                                                                                
+                                                                                    loop {
+                                                                                        match app_state_for_space_preview.handle_file_selection(preview_path.clone(), is_dir).await {
+                                                                                            Ok(maybe_preview) => {
+                                                                                                // Update the preview_data signal with the returned preview
+                                                                                                app_state_for_space_preview.preview_data.set(maybe_preview.clone());
+                                                                                            
+                                                                                                if maybe_preview.is_some() {
+                                                                                                    tracing::info!("Preview generated successfully for: {:?}", preview_path);
+                                                                                                } else {
+                                                                                                    tracing::info!("No preview generated for directory: {:?}", preview_path);
+                                                                                                }
+                                                                                                break;
+                                                                                            }
+                                                                                            Err(e) => {
+                                                                                                retry_count += 1;
+                                                                                            
+                                                                                                if retry_count >= MAX_RETRIES {
+                                                                                                    tracing::error!("Failed to generate preview after {} attempts for {:?}: {}", MAX_RETRIES, preview_path, e);
+                                                                                                
+                                                                                                    // Create error preview data for better user feedback
+                                                                                                    let error_preview = crate::services::preview::PreviewData {
+                                                                                                        file_path: preview_path.clone(),
+                                                                                                        format: crate::services::preview::SupportedFormat::Text,
+                                                                                                        thumbnail_path: None,
+                                                                                                        metadata: crate::services::preview::FileMetadata {
+                                                                                                            file_size: std::fs::metadata(&preview_path).map(|m| m.len()).unwrap_or(0),
+                                                                                                            created: std::fs::metadata(&preview_path).ok().and_then(|m| m.created().ok()),
+                                                                                                            modified: std::fs::metadata(&preview_path).ok().and_then(|m| m.modified().ok()),
+                                                                                                            width: None, height: None, duration: None, bit_rate: None,
+                                                                                                            sample_rate: None, codec: None, title: None, artist: None,
+                                                                                                            album: None, year: None, page_count: None, color_space: None,
+                                                                                                            compression: None, exif_data: None,
+                                                                                                        },
+                                                                                                        preview_content: crate::services::preview::PreviewContent::Unsupported {
+                                                                                                            file_type: preview_path.extension()
+                                                                                                                .and_then(|ext| ext.to_str())
+                                                                                                                .unwrap_or("unknown").to_string(),
+                                                                                                            reason: format!("Preview generation failed: {}", e),
+                                                                                                            suggested_action: Some("Check file permissions or try refreshing".to_string()),
+                                                                                                        },
+                                                                                                        generated_at: std::time::SystemTime::now(),
+                                                                                                    };
+                                                                                                
+                                                                                                    app_state_for_space_preview.preview_data.set(Some(error_preview));
+                                                                                                    break;
+                                                                                                } else {
+                                                                                                    tracing::warn!("Preview generation failed (attempt {}/{}), retrying: {:?}: {}", retry_count, MAX_RETRIES, preview_path, e);
+                                                                                                    // Brief delay before retry
+                                                                                                    tokio::time::sleep(std::time::Duration::from_millis(100 * retry_count as u64)).await;
+                                                                                                }
+                                                                                            }
+                                                                                        }
+                                                                                    }
+                                                                                });
+                                                                            
+                                                                                evt.prevent_default();
+                                                                            }
                                                                         }
                                                                     }
-                                                                }
-                                                            },
+                                                                },
                                                             
-                                                            oncontextmenu: move |evt| {
-                                                                evt.prevent_default();
-                                                                let client_x = evt.data.client_coordinates().x as f64;
-                                                                let client_y = evt.data.client_coordinates().y as f64;
+                                                                oncontextmenu: move |evt| {
+                                                                    evt.prevent_default();
+                                                                    let client_x = evt.data.client_coordinates().x as f64;
+                                                                    let client_y = evt.data.client_coordinates().y as f64;
                                                                 
-                                                                context_menu_state.write().show_at(
-                                                                    client_x, client_y, Some(entry_clone_menu.clone())
-                                                                );
+                                                                    context_menu_state.write().show_at(
+                                                                        client_x, client_y, Some(entry_clone_menu.clone())
+                                                                    );
                                                                 
-                                                                tracing::info!("Context menu opened for: {}", entry_clone_menu.name);
-                                                            },
+                                                                    tracing::info!("Context menu opened for: {}", entry_clone_menu.name);
+                                                                },
                                                             
-                                                            ondragstart: move |evt| {
-                                                                let client_x = evt.data.client_coordinates().x as f64;
-                                                                let client_y = evt.data.client_coordinates().y as f64;
+                                                                ondragstart: move |evt| {
+                                                                    let client_x = evt.data.client_coordinates().x as f64;
+                                                                    let client_y = evt.data.client_coordinates().y as f64;
                                                                 
-                                                                let operation = DragOperation::from_modifiers(
-                                                                    evt.data.modifiers().ctrl(),
-                                                                    evt.data.modifiers().shift(),
-                                                                    evt.data.modifiers().alt()
-                                                                );
+                                                                    let operation = DragOperation::for_folder_drop(
+                                                                        evt.data.modifiers().ctrl(),
+                                                                        evt.data.modifiers().alt()
+                                                                    );
                                                                 
-                                                                drag_state_clone.write().start_drag(
-                                                                    vec![entry_clone_drag.clone()],
-                                                                    client_x,
-                                                                    client_y,
-                                                                    operation
-                                                                );
-                                                                // Removed redundant logging - already logged optimally in DragState::start_drag
-                                                            },
+                                                                    // Dragging a selected file takes the rest of the selection along
+                                                                    let selected = selected_entries(
+                                                                        &app_state_drag.selection.read(),
+                                                                        &app_state_drag.file_entries.read(),
+                                                                    );
+                                                                    drag_state_clone.write().start_drag(
+                                                                        drag_files_for(&entry_clone_drag, selected),
+                                                                        client_x,
+                                                                        client_y,
+                                                                        operation
+                                                                    );
+                                                                    // Removed redundant logging - already logged optimally in DragState::start_drag
+                                                                },
                                                             
-                                                            // Hidden details for screen readers
-                                                            div {
-                                                                id: format!("file-details-{}", index),
-                                                                class: "sr-only",
-                                                                style: "position: absolute; left: -10000px; width: 1px; height: 1px; overflow: hidden;",
-                                                                {format!("{} type: {}, last modified: recently", 
-                                                                    if entry.is_directory { "Directory" } else { "File" },
-                                                                    if entry.is_directory { "Folder" } else { "Document" }
-                                                                )}
-                                                            }
+                                                                ondrag: move |evt| {
+                                                                    let coordinates = evt.data.client_coordinates();
+                                                                    track_drag_position(drag_state, coordinates.x, coordinates.y);
+                                                                },
                                                             
-                                                            div {
-                                                                style: "
-                                                                    display: inline-flex;
-                                                                    align-items: center;
-                                                                    margin-right: 8px;
-                                                                    pointer-events: none;
-                                                                    width: 16px;
-                                                                    height: 16px;
-                                                                ",
-                                                                "aria-hidden": "true",
-                                                                FileIconComponent {
-                                                                    file_name: entry.name.clone(),
-                                                                    extension: entry.path.extension().and_then(|ext| ext.to_str()).map(|s| s.to_string()),
-                                                                    is_directory: entry.is_directory,
-                                                                    is_expanded: false,
-                                                                    pack: Some(current_icon_pack)
+                                                                ondragend: move |_| {
+                                                                    folder_drop_target.set(None);
+                                                                    drag_state.write().end_drag();
+                                                                },
+                                                            
+                                                                // Folders take the dragged files; Alt or Ctrl copies instead of moving
+                                                                ondragover: {
+                                                                    let target = entry_path_drop.clone();
+                                                                    let is_directory = entry.is_directory;
+                                                                    move |evt: DragEvent| {
+                                                                        if !is_directory || !drag_state.peek().is_dragging {
+                                                                            return;
+                                                                        }
+                                                                        evt.prevent_default();
+                                                                        evt.stop_propagation();
+                                                                        let operation = DragOperation::for_folder_drop(
+                                                                            evt.data.modifiers().ctrl(),
+                                                                            evt.data.modifiers().alt()
+                                                                        );
+                                                                        update_drag_operation(drag_state, operation);
+                                                                        let state = DropZoneState::for_target(&drag_state.peek().drag_files, &target);
+                                                                        let current = folder_drop_target.peek().clone();
+                                                                        if current.as_ref() != Some(&(target.clone(), state.clone())) {
+                                                                            folder_drop_target.set(Some((target.clone(), state)));
+                                                                        }
+                                                                    }
+                                                                },
+                                                            
+                                                                ondragleave: {
+                                                                    let target = entry_path_drop.clone();
+                                                                    move |_| {
+                                                                        let is_target = matches!(folder_drop_target.peek().as_ref(), Some((path, _)) if *path == target);
+                                                                        if is_target {
+                                                                            folder_drop_target.set(None);
+                                                                        }
+                                                                    }
+                                                                },
+                                                            
+                                                                ondrop: {
+                                                                    let target = entry_path_drop.clone();
+                                                                    let is_directory = entry.is_directory;
+                                                                    move |evt: DragEvent| {
+                                                                        if !is_directory {
+                                                                            return;
+                                                                        }
+                                                                        evt.prevent_default();
+                                                                        evt.stop_propagation();
+                                                                        folder_drop_target.set(None);
+                                                                        let (files, operation) = {
+                                                                            let drag = drag_state.peek();
+                                                                            (drag.drag_files.clone(), drag.operation.clone())
+                                                                        };
+                                                                        drag_state.write().end_drag();
+                                                                        if DropZoneState::for_target(&files, &target) == DropZoneState::DragOverValid {
+                                                                            spawn(drop_onto_folder(app_state_drop.clone(), files, operation, target.clone()));
+                                                                        }
+                                                                    }
+                                                                },
+                                                            
+                                                                // Hidden details for screen readers
+                                                                div {
+                                                                    id: format!("file-details-{}", index),
+                                                                    class: "sr-only",
+                                                                    style: "position: absolute; left: -10000px; width: 1px; height: 1px; overflow: hidden;",
+                                                                    {format!("{} type: {}, last modified: {}", 
+                                                                        if entry.is_directory { "Directory" } else { "File" },
+                                                                        if entry.is_directory { "Folder" } else { "Document" },
+                                                                        format_date_time(entry.modified)
+                                                                    )}
                                                                 }
-                                                            }
-                                                            span { 
-                                                                style: "pointer-events: none;",
-                                                                {entry.name.clone()}
-                                                            }
-                                                            if entry.size > 0 {
-                                                                span {
-                                                                    style: "margin-left: 10px; color: var(--vscode-text-muted, #6a6a6a); font-size: 0.9em; pointer-events: none;",
+                                                            
+                                                                div {
+                                                                    style: "
+                                                                        display: inline-flex;
+                                                                        align-items: center;
+                                                                        margin-right: 8px;
+                                                                        pointer-events: none;
+                                                                        width: 16px;
+                                                                        height: 16px;
+                                                                    ",
                                                                     "aria-hidden": "true",
-                                                                    "({entry.size} bytes)"
+                                                                    FileIconComponent {
+                                                                        file_name: entry.name.clone(),
+                                                                        extension: entry.path.extension().and_then(|ext| ext.to_str()).map(|s| s.to_string()),
+                                                                        is_directory: entry.is_directory,
+                                                                        is_expanded: false,
+                                                                        pack: Some(current_icon_pack)
+                                                                    }
+                                                                }
+                                                                span { 
+                                                                    style: "pointer-events: none;",
+                                                                    {entry.name.clone()}
+                                                                }
+                                                                if entry.size > 0 {
+                                                                    span {
+                                                                        style: "margin-left: 10px; color: var(--vscode-text-muted, #6a6a6a); font-size: 0.9em; pointer-events: none;",
+                                                                        "aria-hidden": "true",
+                                                                        "({format_size(entry.size)})"
+                                                                    }
                                                                 }
                                                             }
                                                         }
+                                                    })
+                                                }
                                                     }
-                                                })
-                                            }
                                                 }
                                             }
-                                        }
-                                    } else {
-                                        div {
-                                            style: "padding: 20px; text-align: center; color: var(--vscode-text-secondary, #999999);",
-                                            "Directory is empty"
+                                        } else {
+                                            div {
+                                                style: "padding: 20px; text-align: center; color: var(--vscode-text-secondary, #999999);",
+                                                "Directory is empty"
+                                            }
                                         }
                                     }
-                                }
-                            } else {
-                                div {
-                                    style: "padding: 20px; text-align: center; color: var(--vscode-text-secondary, #999999);",
-                                    "No folder selected"
+                                } else {
+                                    div {
+                                        style: "padding: 20px; text-align: center; color: var(--vscode-text-secondary, #999999);",
+                                        "No folder selected"
+                                    }
                                 }
                             }
                         }
@@ -952,19 +1095,53 @@ fn Phase2AppContent() -> Element {
                     },
                     
                     div {
-                        class: "content-viewer-panel",
+                        id: FocusRegion::Preview.element_id(),
+                        class: "content-viewer-panel focus-region",
                         role: "region",
                         "aria-label": "Content viewer and preview panel",
+                        tabindex: -1,
+                        onfocusin: move |_| active_region.set(FocusRegion::Preview),
+                        onkeydown: move |evt| {
+                            if evt.data.key() == dioxus::events::Key::Escape {
+                                focus::focus_outward(FocusRegion::Preview);
+                            }
+                        },
                         
-                        // Dynamic Content Panel - switches between Preview and Info panels based on file type
-                        DynamicContentPanel {
-                            selected_file: selected_item,
-                            preview_data: app_state.preview_data,
+                        // Split view compares two folders instead of the preview
+                        if app_state.split_view.read().enabled {
+                            SplitView { drag_state: drag_state }
+                        }
+                        // Grid mode shows the folder as a gallery instead of the preview
+                        else if *app_state.view_mode.read() == ViewMode::Grid {
+                            GalleryView {
+                                settings: current_settings,
+                                drag_state: drag_state,
+                                on_context_menu: move |(x, y, entry): (f64, f64, FileEntry)| {
+                                    tracing::info!("Context menu opened for: {}", entry.name);
+                                    context_menu_state.write().show_at(x, y, Some(entry));
+                                },
+                            }
+                        }
+                        // Column view opens each selected folder to the right and previews the selected file
+                        else if *app_state.view_mode.read() == ViewMode::Columns {
+                            ColumnView {
+                                selected_file: selected_item,
+                                preview_data: app_state.preview_data,
+                            }
+                        } else {
+                            // Dynamic Content Panel - switches between Preview and Info panels based on file type
+                            DynamicContentPanel {
+                                selected_file: selected_item,
+                                preview_data: app_state.preview_data,
+                            }
                         }
                     }
                 }
             }
             
+            // Screen-reader announcements for navigation, selection and operation results
+            LiveRegion {}
+            
             // Context Menu
             ContextMenu {
                 menu_state: context_menu_state,
@@ -985,57 +1162,46 @@ fn Phase2AppContent() -> Element {
                 span { 
                     class: "status-bar-left", 
                     {
-                        // Format counts with VS Code-style number formatting (thousands separators)
-                        fn format_number(n: usize) -> String {
-                            if n >= 1000 {
-                                let s = n.to_string();
-                                let mut result = String::new();
-                                for (i, c) in s.chars().rev().enumerate() {
-                                    if i > 0 && i % 3 == 0 {
-                                        result.insert(0, ',');
-                                    }
-                                    result.insert(0, c);
-                                }
-                                result
-                            } else {
-                                n.to_string()
-                            }
-                        }
-                        
-                        // Helper function for pluralization
-                        fn pluralize(count: usize, singular: &str, plural: &str) -> String {
-                            let text = if count == 1 { singular } else { plural };
-                            format!("{} {}", format_number(count), text)
-                        }
-                        
                         let selection = app_state_for_status.selection.read();
                         let search_state = app_state_for_status.search_state.read();
+                        let files = |count: usize| t!("status.files", count = count);
+                        let folders = |count: usize| t!("status.folders", count = count);
                         
                         // Context-aware count display
                         if !selection.selected_files.is_empty() {
                             // Show selection counts
                             let sel_meta = &selection.selection_metadata;
-                            format!("{}, {} selected", 
-                                pluralize(sel_meta.file_count, "file", "files"),
-                                pluralize(sel_meta.directory_count, "folder", "folders")
+                            t!(
+                                "status.selected",
+                                files = files(sel_meta.file_count),
+                                folders = folders(sel_meta.directory_count),
+                                size = format_size(sel_meta.total_size)
                             )
                         } else if search_state.is_active && !search_state.query.is_empty() {
                             // Show filtered results
                             let file_count = search_state.results.iter().filter(|entry| !entry.is_directory).count();
                             let folder_count = search_state.results.iter().filter(|entry| entry.is_directory).count();
-                            format!("{}, {} (filtered)", 
-                                pluralize(file_count, "file", "files"),
-                                pluralize(folder_count, "folder", "folders")
-                            )
+                            t!("status.filtered", files = files(file_count), folders = folders(folder_count))
                         } else {
                             // Show directory totals (default)
                             let entries = file_entries.read();
                             let file_count = entries.iter().filter(|entry| !entry.is_directory).count();
                             let folder_count = entries.iter().filter(|entry| entry.is_directory).count();
-                            format!("{}, {}", 
-                                pluralize(file_count, "file", "files"),
-                                pluralize(folder_count, "folder", "folders")
-                            )
+                            let type_filter = app_state_for_status.file_tree_state.read().file_type_filter.clone();
+                            if type_filter.is_active() {
+                                let shown = entries
+                                    .iter()
+                                    .filter(|entry| !entry.is_directory && type_filter.matches(entry))
+                                    .count();
+                                t!(
+                                    "status.type_filtered",
+                                    shown = shown,
+                                    files = files(file_count),
+                                    folders = folders(folder_count)
+                                )
+                            } else {
+                                t!("status.totals", files = files(file_count), folders = folders(folder_count))
+                            }
                         }
                     }
                 }
@@ -1076,6 +1242,12 @@ fn Phase2AppContent() -> Element {
                     }
                 }
                 
+                // Recent operation errors by severity
+                ErrorStatus {}
+                
+                // Aggregated progress of all running operations
+                ProgressHud {}
+                
                 span {
                     class: "status-bar-right",
                     "Task 10.4: Settings & Theme System ⚙️"
@@ -1170,6 +1342,44 @@ fn Phase2AppContent() -> Element {
                     }
                 }
             }
+            
+            // Operation Log
+            if *app_state.operation_log_visible.read() {
+                OperationLogPanel {
+                    on_close: move |_| app_state.operation_log_visible.set(false),
+                }
+            }
+            
+            // Trash
+            if *app_state.trash_visible.read() {
+                TrashPanel {
+                    on_close: move |_| app_state.trash_visible.set(false),
+                }
+            }
+            
+            // Batch rename preview
+            if let Some(files) = app_state.batch_rename_files.read().clone() {
+                BatchRenameDialog {
+                    files,
+                    on_close: move |_| app_state.batch_rename_files.set(None),
+                }
+            }
+            
+            // Quick Look (Space)
+            QuickLookOverlay {}
+            
+            // Folder Comparison
+            if *app_state.folder_compare_visible.read() {
+                FolderComparePanel {
+                    on_close: move |_| app_state.folder_compare_visible.set(false),
+                }
+            }
+
+            // Smart Folder results
+            SmartFolderResults {}
+
+            // Delete progress with Cancel
+            FileOperationProgressDialog {}
         }
     }
 }
@@ -1203,6 +1413,11 @@ fn DynamicFontStyles(settings: Signal<crate::state::SettingsState>) -> Element {
 }
 
 /// Create demo file entries for testing when real directory loading fails
+/// DOM id of a row in the file list, used to move roving focus
+pub(crate) fn file_list_item_id(index: usize) -> String {
+    format!("file-list-item-{}", index)
+}
+
 fn create_demo_entries() -> Vec<FileEntry> {
     use std::time::SystemTime;
     use crate::services::file_system::{FileType, FilePermissions};
@@ -1,34 +1,140 @@
 use dioxus::prelude::*;
+use dioxus::html::input_data::MouseButton;
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use crate::services::FileEntry;
 use crate::state::{AppState, use_app_state, ViewMode};
-use crate::ui::shortcuts::{ShortcutAction, ShortcutRegistry};
+use crate::ui::components::directory_tabs;
+use crate::ui::focus;
+use crate::ui::shortcuts::{Platform, ShortcutAction, ShortcutRegistry};
+
+/// Pause after which typed characters start a new type-ahead search
+pub const TYPE_AHEAD_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// Characters typed in the file list to jump to a file by name
+#[derive(Debug, Clone, Default)]
+pub struct TypeAhead {
+    buffer: String,
+    last_typed: Option<Instant>,
+}
+
+impl TypeAhead {
+    /// Add `c` to the search, starting over after `TYPE_AHEAD_TIMEOUT` of idle
+    pub fn push(&mut self, c: char, now: Instant) -> &str {
+        let idle = self.last_typed.is_none_or(|last| now.saturating_duration_since(last) > TYPE_AHEAD_TIMEOUT);
+        if idle {
+            self.buffer.clear();
+        }
+        self.buffer.extend(c.to_lowercase());
+        self.last_typed = Some(now);
+        &self.buffer
+    }
+
+    pub fn query(&self) -> &str {
+        &self.buffer
+    }
+}
+
+/// Entry `query` jumps to: the first name starting with it, or when the same
+/// letter is pressed repeatedly, the next name starting with that letter after
+/// `current`, wrapping around
+pub fn type_ahead_match(entries: &[FileEntry], query: &str, current: Option<usize>) -> Option<usize> {
+    let mut chars = query.chars();
+    let first = chars.next()?;
+    let starts_with = |entry: &FileEntry, prefix: &str| entry.name.to_lowercase().starts_with(prefix);
+
+    if !chars.all(|c| c == first) {
+        return entries.iter().position(|entry| starts_with(entry, query));
+    }
+    let letter = first.to_string();
+    // A single press keeps a selection that already matches; repeats move on
+    let skip = match current {
+        Some(current) if query.chars().count() > 1 => current + 1,
+        Some(current) => current,
+        None => 0,
+    };
+    (0..entries.len())
+        .map(|offset| (skip + offset) % entries.len())
+        .find(|&index| starts_with(&entries[index], &letter))
+}
 
 /// Keyboard shortcut action handler that executes actions using app state
 #[derive(Clone)]
 pub struct ShortcutHandler {
     app_state: AppState,
     pub registry: ShortcutRegistry,
+    type_ahead: Signal<TypeAhead>,
 }
 
 impl ShortcutHandler {
-    pub fn new(app_state: AppState, registry: ShortcutRegistry) -> Self {
+    pub fn new(app_state: AppState, registry: ShortcutRegistry, type_ahead: Signal<TypeAhead>) -> Self {
         Self {
             app_state,
             registry,
+            type_ahead,
         }
     }
 
     /// Handle a keyboard event and execute the corresponding action if any
     pub async fn handle_keyboard_event(&mut self, key: &str, ctrl: bool, shift: bool, alt: bool, meta: bool) -> bool {
+        // Shortcuts are stored with Cmd as the primary modifier on macOS
+        let (ctrl, meta) = match Platform::current() {
+            Platform::Mac => (meta, ctrl),
+            Platform::Other => (ctrl, meta),
+        };
         // Try to trigger a shortcut
         if let Some(action) = self.registry.try_trigger(key, ctrl, shift, alt, meta) {
             self.execute_action(action).await;
             true // Event was handled
+        } else if !ctrl && !alt && !meta {
+            self.handle_type_ahead(key).await
         } else {
             false // Event was not handled
         }
     }
 
+    /// Jump to the file whose name starts with the typed characters
+    async fn handle_type_ahead(&mut self, key: &str) -> bool {
+        let mut chars = key.chars();
+        let (Some(c), None) = (chars.next(), chars.next()) else {
+            return false;
+        };
+        if c.is_control() || focus::text_input_focused().await {
+            return false;
+        }
+
+        let entries = self.app_state.sorted_file_entries();
+        let selected = self.app_state.get_file_tree_selection();
+        let current = selected.and_then(|path| entries.iter().position(|entry| entry.path == path));
+        let mut type_ahead = self.type_ahead.write();
+        let query = type_ahead.push(c, Instant::now());
+        let Some(index) = type_ahead_match(&entries, query, current) else {
+            tracing::debug!("Type-ahead found nothing for {:?}", type_ahead.query());
+            return true;
+        };
+        drop(type_ahead);
+
+        let path = entries[index].path.clone();
+        self.app_state.select_single(path.clone());
+        self.app_state.set_file_tree_selection(Some(path));
+        let item_id = if *self.app_state.view_mode.peek() == ViewMode::Grid {
+            crate::ui::components::gallery_view::gallery_item_id(index)
+        } else {
+            crate::ui::phase2_app::file_list_item_id(index)
+        };
+        focus::reveal_element(&item_id);
+        true
+    }
+
+    /// Action for a mouse button, if any: the side buttons go back and forward
+    pub fn mouse_button_action(button: MouseButton) -> Option<ShortcutAction> {
+        match button {
+            MouseButton::Fourth => Some(ShortcutAction::NavigateBack),
+            MouseButton::Fifth => Some(ShortcutAction::NavigateForward),
+            _ => None,
+        }
+    }
+
     /// Execute a shortcut action
     pub async fn execute_action(&mut self, action: ShortcutAction) {
         tracing::info!("Executing shortcut action: {:?}", action);
@@ -38,8 +144,11 @@ impl ShortcutHandler {
             ShortcutAction::Paste => self.handle_paste().await,
             ShortcutAction::Cut => self.handle_cut().await,
             ShortcutAction::Delete => self.handle_delete().await,
+            ShortcutAction::DeletePermanently => self.handle_delete_permanently().await,
             ShortcutAction::SelectAll => self.handle_select_all(),
             ShortcutAction::ClearSelection => self.handle_clear_selection(),
+            ShortcutAction::ExtendSelectionUp => self.handle_extend_selection(-1),
+            ShortcutAction::ExtendSelectionDown => self.handle_extend_selection(1),
             ShortcutAction::Rename => self.handle_rename().await,
             ShortcutAction::NavigateUp => self.handle_navigate_up().await,
             ShortcutAction::NavigateBack => self.handle_navigate_back().await,
@@ -59,10 +168,14 @@ impl ShortcutHandler {
             ShortcutAction::FocusEditor2 => self.handle_focus_editor(2),
             ShortcutAction::FocusEditor3 => self.handle_focus_editor(3),
             ShortcutAction::CloseTab => self.handle_close_tab().await,
+            ShortcutAction::ReopenClosedTab => self.handle_reopen_closed_tab().await,
             ShortcutAction::SwitchTab => self.handle_switch_tab(),
+            ShortcutAction::NewDirectoryTab => self.handle_new_directory_tab().await,
+            ShortcutAction::SwitchDirectoryTab(number) => self.handle_switch_directory_tab(number).await,
+            ShortcutAction::BookmarkCurrentFolder => self.handle_bookmark_current_folder().await,
             ShortcutAction::ZoomIn => self.handle_zoom_in(),
             ShortcutAction::ZoomOut => self.handle_zoom_out(),
-            ShortcutAction::ToggleSpace => self.handle_toggle_space(),
+            ShortcutAction::ToggleSpace => self.handle_toggle_space().await,
             ShortcutAction::ShowShortcutCheatSheet => self.handle_show_shortcut_cheat_sheet(),
             ShortcutAction::ToggleHighContrast => self.handle_toggle_high_contrast(),
             ShortcutAction::Custom(name) => self.handle_custom_action(&name).await,
@@ -109,6 +222,17 @@ impl ShortcutHandler {
         }
     }
 
+    async fn handle_delete_permanently(&mut self) {
+        let selected_files = self.app_state.get_selected_files();
+        if !selected_files.is_empty() {
+            // Permanent deletion runs from the Edit menu, which asks for the stronger confirmation
+            tracing::info!("Delete permanently action: {} files selected", selected_files.len());
+            self.set_operation_feedback("Delete permanently (confirmation needed)", false).await;
+        } else {
+            self.set_operation_feedback("No files selected", true).await;
+        }
+    }
+
     // Selection handlers
     fn handle_select_all(&mut self) {
         let all_paths: Vec<PathBuf> = self.app_state
@@ -123,6 +247,12 @@ impl ShortcutHandler {
         }
     }
 
+    fn handle_extend_selection(&mut self, delta: isize) {
+        if let Some(end) = self.app_state.extend_selection(delta) {
+            tracing::info!("Extended selection to {:?}", end);
+        }
+    }
+
     fn handle_clear_selection(&mut self) {
         let count = self.app_state.get_selection_count();
         self.app_state.clear_selection();
@@ -246,7 +376,7 @@ impl ShortcutHandler {
     fn handle_toggle_preview(&mut self) {
         let current_mode = self.app_state.get_view_mode();
         let new_mode = match current_mode {
-            ViewMode::Preview => ViewMode::Grid,
+            ViewMode::Preview => ViewMode::List,
             _ => ViewMode::Preview,
         };
         
@@ -261,8 +391,8 @@ impl ShortcutHandler {
     }
 
     fn handle_show_settings(&mut self) {
-        // TODO: This will be triggered by the app UI when settings panel is integrated
-        tracing::info!("Show settings action");
+        self.app_state.settings_dialog_visible.set(true);
+        tracing::info!("Settings dialog opened via keyboard shortcut");
     }
 
     fn handle_show_command_palette(&mut self) {
@@ -296,10 +426,36 @@ impl ShortcutHandler {
     }
 
     async fn handle_close_tab(&mut self) {
-        // TODO: Close the currently active tab
-        tracing::info!("Close tab action");
-        self.set_operation_feedback("Close tab", false).await;
-        // This would close the current file tab in the editor area
+        if self.app_state.directory_tabs.read().tabs.len() > 1 {
+            directory_tabs::close_directory_tab(self.app_state.clone(), None).await;
+        } else {
+            self.set_operation_feedback("The last folder tab stays open", true).await;
+        }
+    }
+
+    async fn handle_reopen_closed_tab(&mut self) {
+        if !self.app_state.editor_state.write().reopen_closed_tab() {
+            self.set_operation_feedback("No closed tabs to reopen", true).await;
+        }
+    }
+
+    async fn handle_new_directory_tab(&mut self) {
+        directory_tabs::open_directory_tab(self.app_state.clone()).await;
+    }
+
+    async fn handle_switch_directory_tab(&mut self, number: usize) {
+        let tab_count = self.app_state.directory_tabs.read().tabs.len();
+        // Like browsers, the last number always shows the last tab
+        let index = if number == 9 { tab_count - 1 } else { number.saturating_sub(1) };
+        directory_tabs::switch_directory_tab(self.app_state.clone(), index).await;
+    }
+
+    async fn handle_bookmark_current_folder(&mut self) {
+        if self.app_state.bookmark_current_folder() {
+            self.set_operation_feedback("Folder bookmarked", false).await;
+        } else {
+            self.set_operation_feedback("Folder is already bookmarked", true).await;
+        }
     }
 
     fn handle_switch_tab(&mut self) {
@@ -321,22 +477,31 @@ impl ShortcutHandler {
         // This would decrease the zoom level of the current preview
     }
 
-    fn handle_toggle_space(&mut self) {
-        // This maps to the Space key for toggling preview - different from TogglePreview
-        // Space key typically shows/hides preview for selected file
-        let selected_files = self.app_state.get_selected_files();
-        if !selected_files.is_empty() {
-            // Toggle preview visibility for the selected file
-            let current_mode = self.app_state.get_view_mode();
-            let new_mode = match current_mode {
-                ViewMode::Preview => ViewMode::Grid,
-                _ => ViewMode::Preview,
-            };
-            
-            self.app_state.view_mode.set(new_mode.clone());
-            tracing::info!("Toggled preview via space key: {:?}", new_mode);
+    async fn handle_toggle_space(&mut self) {
+        // Space closes an open Quick Look and otherwise opens it on the selection
+        if self.app_state.quick_look.read().is_open {
+            self.app_state.quick_look.write().close();
+            return;
+        }
+        // Inside an input or rename field Space is just a typed character
+        if focus::text_input_focused().await {
+            return;
+        }
+
+        let mut selected = self.app_state.get_selected_files();
+        if selected.is_empty() {
+            selected.extend(self.app_state.get_file_tree_selection());
+        }
+        let items: Vec<PathBuf> = self.app_state.file_entries.read()
+            .iter()
+            .filter(|entry| !entry.is_directory)
+            .map(|entry| entry.path.clone())
+            .collect();
+
+        if self.app_state.quick_look.write().open(items, &selected) {
+            tracing::info!("Opened Quick Look for {} selected items", selected.len());
         } else {
-            tracing::info!("Space key pressed but no file selected for preview");
+            tracing::info!("Space key pressed but no file selected for Quick Look");
         }
     }
 
@@ -395,21 +560,28 @@ impl ShortcutHandler {
 }
 
 /// Hook to create and use a shortcut handler with current app state
+///
+/// The registry follows the user's keymap; reading it here re-renders the
+/// caller when a shortcut is rebound.
 pub fn use_shortcut_handler() -> ShortcutHandler {
     let app_state = use_app_state();
     let registry = use_signal(ShortcutRegistry::new);
+    let type_ahead = use_signal(TypeAhead::default);
     
     let registry_clone = {
         let registry_ref = registry.read();
+        registry_ref.apply_keymap(&app_state.keymap.read());
         registry_ref.clone()
     };
     
-    ShortcutHandler::new(app_state, registry_clone)
+    ShortcutHandler::new(app_state, registry_clone, type_ahead)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::services::file_system::{FilePermissions, FileType, TextFormat};
+    use std::time::SystemTime;
     // Note: These tests would require a Dioxus component context to work properly
     // In a real application, integration tests with actual components would be preferred
     
@@ -419,4 +591,60 @@ mod tests {
         assert_eq!(ShortcutAction::NavigateHome.description(), "Navigate to home directory");
         assert_eq!(ShortcutAction::TogglePreview.description(), "Toggle preview panel");
     }
+
+    fn entries(names: &[&str]) -> Vec<FileEntry> {
+        names
+            .iter()
+            .map(|name| FileEntry {
+                name: name.to_string(),
+                path: PathBuf::from(format!("/media/{name}")),
+                file_type: FileType::Text(TextFormat::Plain),
+                size: 10,
+                modified: SystemTime::now(),
+                created: SystemTime::now(),
+                is_directory: false,
+                is_hidden: false,
+                permissions: FilePermissions::read_write(),
+                preview_metadata: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_type_ahead_matches_name_prefix() {
+        let entries = entries(&["Beach.jpg", "birthday.png", "Cat.gif", "notes.txt"]);
+        assert_eq!(type_ahead_match(&entries, "bi", None), Some(1));
+        assert_eq!(type_ahead_match(&entries, "c", None), Some(2));
+        assert_eq!(type_ahead_match(&entries, "cat.", Some(0)), Some(2));
+        assert_eq!(type_ahead_match(&entries, "x", None), None);
+        assert_eq!(type_ahead_match(&entries, "", None), None);
+    }
+
+    #[test]
+    fn test_type_ahead_resets_after_idle() {
+        let start = Instant::now();
+        let mut type_ahead = TypeAhead::default();
+        assert_eq!(type_ahead.push('B', start), "b");
+        assert_eq!(type_ahead.push('i', start + Duration::from_millis(300)), "bi");
+        let later = start + Duration::from_millis(300) + TYPE_AHEAD_TIMEOUT + Duration::from_millis(1);
+        assert_eq!(type_ahead.push('c', later), "c");
+    }
+
+    #[test]
+    fn test_repeating_a_letter_cycles_through_matches() {
+        let entries = entries(&["Beach.jpg", "birthday.png", "Cat.gif", "boat.raw"]);
+        let start = Instant::now();
+        let mut type_ahead = TypeAhead::default();
+        let mut current = None;
+        let mut visited = Vec::new();
+        for press in 0..4 {
+            let query = type_ahead.push('b', start + Duration::from_millis(100 * press));
+            current = type_ahead_match(&entries, query, current);
+            visited.extend(current);
+        }
+        assert_eq!(visited, vec![0, 1, 3, 0]);
+        // A first press keeps a selection that already matches
+        assert_eq!(type_ahead_match(&entries, "b", Some(3)), Some(3));
+        assert_eq!(type_ahead_match(&entries, "b", Some(2)), Some(3));
+    }
 }
\ No newline at end of file
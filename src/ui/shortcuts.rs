@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
+use crate::state::keymap::KeymapState;
+
 /// Represents a keyboard shortcut key combination
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct KeyCombination {
@@ -76,15 +78,255 @@ impl KeyCombination {
     }
 }
 
+/// Keyboard conventions a chord is normalized and displayed for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Platform {
+    Mac,
+    Other,
+}
+
+impl Platform {
+    pub fn current() -> Self {
+        if cfg!(target_os = "macos") {
+            Platform::Mac
+        } else {
+            Platform::Other
+        }
+    }
+}
+
+/// Why a captured key press is not usable as a shortcut
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ChordError {
+    /// Only modifiers are held so far; keep listening
+    #[error("Press a key together with the modifiers")]
+    ModifierOnly,
+    #[error("This key cannot be identified on the current keyboard layout")]
+    Unidentified,
+    #[error("Letters, digits and symbols need Ctrl, Alt or Cmd so they can still be typed")]
+    NeedsModifier,
+    #[error("{0} is reserved by the system or for keyboard navigation")]
+    Reserved(String),
+}
+
+/// A key press as reported by the webview, before normalization
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyChordEvent {
+    /// `KeyboardEvent.key`, e.g. "a", "Dead", "Control"
+    pub key: String,
+    /// `KeyboardEvent.code`, the physical key, e.g. "KeyA"
+    pub code: String,
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    pub meta: bool,
+}
+
+const MODIFIER_KEYS: &[&str] = &["Control", "Shift", "Alt", "AltGraph", "Meta", "OS", "Super", "Hyper", "Fn", "CapsLock"];
+
+/// Chords needed to move focus and leave the recorder
+const NAVIGATION_CHORDS: &[&str] = &["Tab", "Shift+Tab", "Escape"];
+
+/// Chords the operating system takes before the app sees them
+fn system_chords(platform: Platform) -> &'static [&'static str] {
+    match platform {
+        Platform::Mac => &["CmdOrCtrl+Q", "CmdOrCtrl+Tab", "CmdOrCtrl+Space", "CmdOrCtrl+Alt+Escape"],
+        Platform::Other => &["Alt+Tab", "Alt+F4", "CmdOrCtrl+Alt+Delete", "CmdOrCtrl+Shift+Escape"],
+    }
+}
+
+impl KeyChordEvent {
+    /// Turn the press into a shortcut for `platform`
+    ///
+    /// On macOS Cmd becomes the primary (`ctrl`) modifier and Control the
+    /// secondary (`meta`) one, matching how shortcuts are registered. Letters
+    /// and digits are taken from the physical key whenever a modifier may have
+    /// changed the character (Alt+C typing "ç", Shift+1 typing "!"), and dead
+    /// keys fall back to the physical key as well.
+    pub fn normalize(&self, platform: Platform) -> Result<KeyCombination, ChordError> {
+        if MODIFIER_KEYS.contains(&self.key.as_str()) {
+            return Err(ChordError::ModifierOnly);
+        }
+
+        let physical = physical_key(&self.code);
+        let key = match self.key.as_str() {
+            "Dead" | "Unidentified" | "Process" | "" => physical.ok_or(ChordError::Unidentified)?,
+            _ if self.shift || self.alt || self.ctrl || self.meta => physical.unwrap_or_else(|| named_key(&self.key)),
+            _ => named_key(&self.key),
+        };
+
+        let (ctrl, meta) = match platform {
+            Platform::Mac => (self.meta, self.ctrl),
+            Platform::Other => (self.ctrl, self.meta),
+        };
+        let combo = KeyCombination { key, ctrl, shift: self.shift, alt: self.alt, meta };
+
+        let accelerator = combo.accelerator();
+        if NAVIGATION_CHORDS.contains(&accelerator.as_str()) || system_chords(platform).contains(&accelerator.as_str()) {
+            return Err(ChordError::Reserved(accelerator));
+        }
+        let printable = combo.key.chars().count() == 1 && combo.key != " ";
+        if printable && !(combo.ctrl || combo.alt || combo.meta) {
+            return Err(ChordError::NeedsModifier);
+        }
+        Ok(combo)
+    }
+}
+
+/// Letter, digit or symbol printed on the physical key, from `KeyboardEvent.code`
+fn physical_key(code: &str) -> Option<String> {
+    if let Some(letter) = code.strip_prefix("Key") {
+        return Some(letter.to_lowercase());
+    }
+    if let Some(digit) = code.strip_prefix("Digit") {
+        return Some(digit.to_string());
+    }
+    let symbol = match code {
+        "Minus" => "-",
+        "Equal" => "=",
+        "BracketLeft" => "[",
+        "BracketRight" => "]",
+        "Backslash" => "\\",
+        "Semicolon" => ";",
+        "Quote" => "'",
+        "Backquote" => "`",
+        "Comma" => ",",
+        "Period" => ".",
+        "Slash" => "/",
+        _ => return None,
+    };
+    Some(symbol.to_string())
+}
+
+/// Key name with legacy spellings replaced and letters lowercased
+fn named_key(key: &str) -> String {
+    match key {
+        "Spacebar" => " ".to_string(),
+        "Esc" => "Escape".to_string(),
+        "Del" => "Delete".to_string(),
+        "Up" | "Down" | "Left" | "Right" => format!("Arrow{}", key),
+        key if key.chars().count() == 1 => key.to_lowercase(),
+        key => key.to_string(),
+    }
+}
+
+impl KeyCombination {
+    /// Normalized text form, e.g. `CmdOrCtrl+Shift+P`; `ctrl` is the primary modifier
+    pub fn accelerator(&self) -> String {
+        let mut parts = Vec::new();
+        if self.ctrl {
+            parts.push("CmdOrCtrl".to_string());
+        }
+        if self.meta {
+            parts.push("Ctrl".to_string());
+        }
+        if self.alt {
+            parts.push("Alt".to_string());
+        }
+        if self.shift {
+            parts.push("Shift".to_string());
+        }
+        parts.push(match self.key.as_str() {
+            " " => "Space".to_string(),
+            key if key.chars().count() == 1 => key.to_uppercase(),
+            key => key.to_string(),
+        });
+        parts.join("+")
+    }
+
+    /// Parse the text form produced by [`KeyCombination::accelerator`]
+    pub fn from_accelerator(accelerator: &str) -> Option<Self> {
+        // "CmdOrCtrl++" binds the plus key itself
+        let (modifiers, key) = match accelerator.strip_suffix("++") {
+            Some(modifiers) => (modifiers, "+"),
+            None if accelerator == "+" => ("", "+"),
+            None => match accelerator.rsplit_once('+') {
+                Some((modifiers, key)) => (modifiers, key),
+                None => ("", accelerator),
+            },
+        };
+        if key.is_empty() {
+            return None;
+        }
+
+        let mut combo = KeyCombination::new(match key {
+            "Space" => " ",
+            key => key,
+        });
+        if combo.key.chars().count() == 1 {
+            combo.key = combo.key.to_lowercase();
+        }
+        for modifier in modifiers.split('+').filter(|m| !m.is_empty()) {
+            match modifier {
+                "CmdOrCtrl" => combo.ctrl = true,
+                "Ctrl" => combo.meta = true,
+                "Alt" => combo.alt = true,
+                "Shift" => combo.shift = true,
+                _ => return None,
+            }
+        }
+        Some(combo)
+    }
+
+    /// Keys to show for this shortcut, with ⌘/⌃/⌥/⇧ symbols on macOS
+    pub fn display_keys(&self, platform: Platform) -> Vec<String> {
+        let mut keys = Vec::new();
+        let mac = platform == Platform::Mac;
+        if self.ctrl {
+            keys.push(if mac { "⌘" } else { "Ctrl" }.to_string());
+        }
+        if self.shift {
+            keys.push(if mac { "⇧" } else { "Shift" }.to_string());
+        }
+        if self.alt {
+            keys.push(if mac { "⌥" } else { "Alt" }.to_string());
+        }
+        if self.meta {
+            keys.push(if mac { "⌃" } else { "Win" }.to_string());
+        }
+        let main_key = match self.key.as_str() {
+            " " => "Space".to_string(),
+            "Enter" => "↵".to_string(),
+            "Tab" => "⇥".to_string(),
+            "Escape" => "Esc".to_string(),
+            "ArrowUp" => "↑".to_string(),
+            "ArrowDown" => "↓".to_string(),
+            "ArrowLeft" => "←".to_string(),
+            "ArrowRight" => "→".to_string(),
+            "Delete" => "⌦".to_string(),
+            "Backspace" => "⌫".to_string(),
+            key => key.to_uppercase(),
+        };
+        keys.push(main_key);
+        keys
+    }
+}
+
+/// Descriptions of `ShortcutAction::SwitchDirectoryTab(1..=9)`
+const SWITCH_DIRECTORY_TAB_DESCRIPTIONS: [&str; 9] = [
+    "Show folder tab 1",
+    "Show folder tab 2",
+    "Show folder tab 3",
+    "Show folder tab 4",
+    "Show folder tab 5",
+    "Show folder tab 6",
+    "Show folder tab 7",
+    "Show folder tab 8",
+    "Show last folder tab",
+];
+
 /// Action that can be triggered by a keyboard shortcut
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ShortcutAction {
     Copy,
     Paste,
     Cut,
     Delete,
+    DeletePermanently,
     SelectAll,
     ClearSelection,
+    ExtendSelectionUp,
+    ExtendSelectionDown,
     Rename,
     NavigateUp,
     NavigateBack,
@@ -104,7 +346,12 @@ pub enum ShortcutAction {
     FocusEditor2,
     FocusEditor3,
     CloseTab,
+    ReopenClosedTab,
     SwitchTab,
+    // Directory tabs; tab 9 is always the last one
+    NewDirectoryTab,
+    SwitchDirectoryTab(usize),
+    BookmarkCurrentFolder,
     ZoomIn,
     ZoomOut,
     ToggleSpace,
@@ -119,9 +366,12 @@ impl ShortcutAction {
             ShortcutAction::Copy => "Copy selected items",
             ShortcutAction::Paste => "Paste from clipboard",
             ShortcutAction::Cut => "Cut selected items",
-            ShortcutAction::Delete => "Delete selected items",
+            ShortcutAction::Delete => "Move selected items to the trash",
+            ShortcutAction::DeletePermanently => "Delete selected items permanently",
             ShortcutAction::SelectAll => "Select all items",
             ShortcutAction::ClearSelection => "Clear selection",
+            ShortcutAction::ExtendSelectionUp => "Extend selection up",
+            ShortcutAction::ExtendSelectionDown => "Extend selection down",
             ShortcutAction::Rename => "Rename selected item",
             ShortcutAction::NavigateUp => "Navigate to parent directory",
             ShortcutAction::NavigateBack => "Navigate back",
@@ -140,16 +390,265 @@ impl ShortcutAction {
             ShortcutAction::FocusEditor1 => "Focus editor group 1",
             ShortcutAction::FocusEditor2 => "Focus editor group 2",
             ShortcutAction::FocusEditor3 => "Focus editor group 3",
-            ShortcutAction::CloseTab => "Close current tab",
+            ShortcutAction::CloseTab => "Close current folder tab",
+            ShortcutAction::ReopenClosedTab => "Reopen closed tab",
             ShortcutAction::SwitchTab => "Switch between tabs",
+            ShortcutAction::NewDirectoryTab => "Open folder in new tab",
+            ShortcutAction::BookmarkCurrentFolder => "Bookmark current folder",
+            ShortcutAction::SwitchDirectoryTab(number) => SWITCH_DIRECTORY_TAB_DESCRIPTIONS
+                .get(number.wrapping_sub(1))
+                .copied()
+                .unwrap_or("Show folder tab"),
             ShortcutAction::ZoomIn => "Zoom in",
             ShortcutAction::ZoomOut => "Zoom out",
-            ShortcutAction::ToggleSpace => "Toggle preview space",
+            ShortcutAction::ToggleSpace => "Quick Look the selected file",
             ShortcutAction::ShowShortcutCheatSheet => "Show shortcut cheat sheet",
             ShortcutAction::ToggleHighContrast => "Toggle high contrast mode",
             ShortcutAction::Custom(_) => "Custom action",
         }
     }
+
+    /// Stable name the keymap stores bindings under, e.g. `show_command_palette`
+    pub fn id(&self) -> String {
+        let id = match self {
+            ShortcutAction::Copy => "copy",
+            ShortcutAction::Paste => "paste",
+            ShortcutAction::Cut => "cut",
+            ShortcutAction::Delete => "delete",
+            ShortcutAction::DeletePermanently => "delete_permanently",
+            ShortcutAction::SelectAll => "select_all",
+            ShortcutAction::ClearSelection => "clear_selection",
+            ShortcutAction::ExtendSelectionUp => "extend_selection_up",
+            ShortcutAction::ExtendSelectionDown => "extend_selection_down",
+            ShortcutAction::Rename => "rename",
+            ShortcutAction::NavigateUp => "navigate_up",
+            ShortcutAction::NavigateBack => "navigate_back",
+            ShortcutAction::NavigateForward => "navigate_forward",
+            ShortcutAction::NavigateHome => "navigate_home",
+            ShortcutAction::Refresh => "refresh",
+            ShortcutAction::OpenFile => "open_file",
+            ShortcutAction::ShowProperties => "show_properties",
+            ShortcutAction::TogglePreview => "toggle_preview",
+            ShortcutAction::ToggleSearch => "toggle_search",
+            ShortcutAction::NewFolder => "new_folder",
+            ShortcutAction::ShowSettings => "show_settings",
+            ShortcutAction::ShowCommandPalette => "show_command_palette",
+            ShortcutAction::FocusExplorer => "focus_explorer",
+            ShortcutAction::FocusEditor1 => "focus_editor_1",
+            ShortcutAction::FocusEditor2 => "focus_editor_2",
+            ShortcutAction::FocusEditor3 => "focus_editor_3",
+            ShortcutAction::CloseTab => "close_tab",
+            ShortcutAction::ReopenClosedTab => "reopen_closed_tab",
+            ShortcutAction::SwitchTab => "switch_tab",
+            ShortcutAction::NewDirectoryTab => "new_directory_tab",
+            ShortcutAction::SwitchDirectoryTab(number) => return format!("switch_directory_tab_{}", number),
+            ShortcutAction::BookmarkCurrentFolder => "bookmark_current_folder",
+            ShortcutAction::ZoomIn => "zoom_in",
+            ShortcutAction::ZoomOut => "zoom_out",
+            ShortcutAction::ToggleSpace => "quick_look",
+            ShortcutAction::ShowShortcutCheatSheet => "show_shortcut_cheat_sheet",
+            ShortcutAction::ToggleHighContrast => "toggle_high_contrast",
+            ShortcutAction::Custom(name) => return format!("custom:{}", name),
+        };
+        id.to_string()
+    }
+}
+
+/// Shortcuts the app ships with, in the order they are listed
+pub fn default_bindings() -> Vec<(KeyCombination, ShortcutAction)> {
+    let mut bindings = vec![
+        // File operations
+        (KeyCombination::new("c").with_ctrl(), ShortcutAction::Copy),
+        (KeyCombination::new("v").with_ctrl(), ShortcutAction::Paste),
+        (KeyCombination::new("x").with_ctrl(), ShortcutAction::Cut),
+        (KeyCombination::new("Delete"), ShortcutAction::Delete),
+        (KeyCombination::new("Delete").with_shift(), ShortcutAction::DeletePermanently),
+        (KeyCombination::new("a").with_ctrl(), ShortcutAction::SelectAll),
+        (KeyCombination::new("Escape"), ShortcutAction::ClearSelection),
+        (KeyCombination::new("ArrowUp").with_shift(), ShortcutAction::ExtendSelectionUp),
+        (KeyCombination::new("ArrowDown").with_shift(), ShortcutAction::ExtendSelectionDown),
+        (KeyCombination::new("F2"), ShortcutAction::Rename),
+        
+        // Navigation
+        (KeyCombination::new("ArrowUp").with_alt(), ShortcutAction::NavigateUp),
+        (KeyCombination::new("ArrowLeft").with_alt(), ShortcutAction::NavigateBack),
+        (KeyCombination::new("ArrowRight").with_alt(), ShortcutAction::NavigateForward),
+        (KeyCombination::new("[").with_meta(), ShortcutAction::NavigateBack),
+        (KeyCombination::new("]").with_meta(), ShortcutAction::NavigateForward),
+        (KeyCombination::new("[").with_ctrl(), ShortcutAction::NavigateBack),
+        (KeyCombination::new("]").with_ctrl(), ShortcutAction::NavigateForward),
+        (KeyCombination::new("h").with_ctrl(), ShortcutAction::NavigateHome),
+        (KeyCombination::new("d").with_ctrl(), ShortcutAction::BookmarkCurrentFolder),
+        (KeyCombination::new("F5"), ShortcutAction::Refresh),
+        (KeyCombination::new("r").with_ctrl(), ShortcutAction::Refresh),
+        (KeyCombination::new("Enter"), ShortcutAction::OpenFile),
+        
+        // View operations
+        (KeyCombination::new("p").with_ctrl(), ShortcutAction::TogglePreview),
+        (KeyCombination::new("f").with_ctrl(), ShortcutAction::ToggleSearch),
+        (KeyCombination::new("n").with_ctrl().with_shift(), ShortcutAction::NewFolder),
+        
+        // Properties
+        (KeyCombination::new("i").with_alt().with_ctrl(), ShortcutAction::ShowProperties),
+        
+        // Settings and Command Palette
+        (KeyCombination::new(",").with_ctrl(), ShortcutAction::ShowSettings),
+        (KeyCombination::new("p").with_ctrl().with_shift(), ShortcutAction::ShowCommandPalette),
+        
+        // VS Code compatibility shortcuts
+        (KeyCombination::new("e").with_ctrl().with_shift(), ShortcutAction::FocusExplorer),
+        (KeyCombination::new("1").with_ctrl().with_alt(), ShortcutAction::FocusEditor1),
+        (KeyCombination::new("2").with_ctrl().with_alt(), ShortcutAction::FocusEditor2),
+        (KeyCombination::new("3").with_ctrl().with_alt(), ShortcutAction::FocusEditor3),
+        (KeyCombination::new("w").with_ctrl(), ShortcutAction::CloseTab),
+        (KeyCombination::new("t").with_ctrl().with_shift(), ShortcutAction::ReopenClosedTab),
+        (KeyCombination::new("Tab").with_ctrl(), ShortcutAction::SwitchTab),
+        (KeyCombination::new(" "), ShortcutAction::ToggleSpace),
+        (KeyCombination::new("=").with_ctrl(), ShortcutAction::ZoomIn),
+        (KeyCombination::new("+").with_ctrl(), ShortcutAction::ZoomIn),
+        (KeyCombination::new("-").with_ctrl(), ShortcutAction::ZoomOut),
+        
+        // Help and utility shortcuts
+        (KeyCombination::new("F1"), ShortcutAction::ShowShortcutCheatSheet),
+        
+        // Accessibility shortcuts
+        (KeyCombination::new("h").with_ctrl().with_shift(), ShortcutAction::ToggleHighContrast),
+    ];
+
+    // Directory tabs: Ctrl/Cmd+T opens one, Ctrl/Cmd+1…9 switch between them
+    bindings.push((KeyCombination::new("t").with_ctrl(), ShortcutAction::NewDirectoryTab));
+    for number in 1..=9 {
+        bindings.push((
+            KeyCombination::new(&number.to_string()).with_ctrl(),
+            ShortcutAction::SwitchDirectoryTab(number),
+        ));
+    }
+    bindings
+}
+
+/// Actions that can be rebound, each once, in the order of the defaults
+pub fn remappable_actions() -> Vec<ShortcutAction> {
+    let mut actions: Vec<ShortcutAction> = Vec::new();
+    for (_, action) in default_bindings() {
+        if !actions.contains(&action) {
+            actions.push(action);
+        }
+    }
+    actions
+}
+
+/// Action a keymap id refers to; custom actions cannot be rebound
+pub fn action_from_id(id: &str) -> Option<ShortcutAction> {
+    remappable_actions().into_iter().find(|action| action.id() == id)
+}
+
+/// Shortcuts in effect once the overrides in `keymap` replace the defaults
+///
+/// Overrides that cannot be parsed, or name an unknown action, are skipped.
+pub fn keymap_bindings(keymap: &KeymapState) -> Vec<(KeyCombination, ShortcutAction)> {
+    let mut bindings = Vec::new();
+    let mut overridden: Vec<ShortcutAction> = Vec::new();
+    for (combo, action) in default_bindings() {
+        let Some(chords) = keymap.overrides(&action.id()) else {
+            bindings.push((combo, action));
+            continue;
+        };
+        if overridden.contains(&action) {
+            continue;
+        }
+        for chord in chords {
+            match KeyCombination::from_accelerator(chord) {
+                Some(combo) => bindings.push((combo, action.clone())),
+                None => tracing::warn!("Ignoring unreadable shortcut {:?} for {}", chord, action.id()),
+            }
+        }
+        overridden.push(action);
+    }
+    bindings
+}
+
+/// Chords of `action` under `keymap`
+pub fn chords_for_action(keymap: &KeymapState, action: &ShortcutAction) -> Vec<KeyCombination> {
+    keymap_bindings(keymap)
+        .into_iter()
+        .filter(|(_, bound)| bound == action)
+        .map(|(combo, _)| combo)
+        .collect()
+}
+
+/// Action other than `except` that `combo` already triggers under `keymap`
+pub fn action_for_chord(
+    keymap: &KeymapState,
+    combo: &KeyCombination,
+    except: &ShortcutAction,
+) -> Option<ShortcutAction> {
+    keymap_bindings(keymap)
+        .into_iter()
+        .find(|(bound, action)| bound == combo && action != except)
+        .map(|(_, action)| action)
+}
+
+/// Replace `old` with `new` among the chords of `action`
+///
+/// `old: None` adds a chord and `new: None` removes one. If the result is the
+/// default set again, in any order, the override is dropped.
+pub fn rebind(
+    keymap: &mut KeymapState,
+    action: &ShortcutAction,
+    old: Option<&KeyCombination>,
+    new: Option<KeyCombination>,
+) {
+    let mut chords = chords_for_action(keymap, action);
+    match (old.and_then(|old| chords.iter().position(|c| c == old)), new) {
+        (Some(index), Some(new)) => chords[index] = new,
+        (Some(index), None) => {
+            chords.remove(index);
+        }
+        (None, Some(new)) => chords.push(new),
+        (None, None) => return,
+    }
+    let mut unique: Vec<KeyCombination> = Vec::new();
+    for chord in chords {
+        if !unique.contains(&chord) {
+            unique.push(chord);
+        }
+    }
+    let chords = unique;
+
+    let id = action.id();
+    keymap.reset(&id);
+    let defaults = chords_for_action(keymap, action);
+    if chords.len() != defaults.len() || !chords.iter().all(|chord| defaults.contains(chord)) {
+        keymap.set(&id, chords.iter().map(KeyCombination::accelerator).collect());
+    }
+}
+
+/// A chord bound to more than one action
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeymapConflict {
+    pub combo: KeyCombination,
+    pub actions: Vec<ShortcutAction>,
+}
+
+/// Chords that trigger more than one action under `keymap`
+pub fn find_conflicts(keymap: &KeymapState) -> Vec<KeymapConflict> {
+    let mut conflicts: Vec<KeymapConflict> = Vec::new();
+    let bindings = keymap_bindings(keymap);
+    for (index, (combo, action)) in bindings.iter().enumerate() {
+        if bindings[..index].iter().any(|(earlier, _)| earlier == combo) {
+            continue;
+        }
+        let mut actions = vec![action.clone()];
+        for (other_combo, other) in &bindings[index + 1..] {
+            if other_combo == combo && !actions.contains(other) {
+                actions.push(other.clone());
+            }
+        }
+        if actions.len() > 1 {
+            conflicts.push(KeymapConflict { combo: combo.clone(), actions });
+        }
+    }
+    conflicts
 }
 
 /// Keyboard shortcut registry for managing shortcuts and preventing conflicts
@@ -167,65 +666,18 @@ impl ShortcutRegistry {
         };
         
         // Register default shortcuts
-        registry.register_defaults();
+        registry.apply_keymap(&KeymapState::default());
         registry
     }
 
-    /// Register default keyboard shortcuts
-    fn register_defaults(&self) {
-        let shortcuts = [
-            // File operations
-            (KeyCombination::new("c").with_ctrl(), ShortcutAction::Copy),
-            (KeyCombination::new("v").with_ctrl(), ShortcutAction::Paste),
-            (KeyCombination::new("x").with_ctrl(), ShortcutAction::Cut),
-            (KeyCombination::new("Delete"), ShortcutAction::Delete),
-            (KeyCombination::new("a").with_ctrl(), ShortcutAction::SelectAll),
-            (KeyCombination::new("Escape"), ShortcutAction::ClearSelection),
-            (KeyCombination::new("F2"), ShortcutAction::Rename),
-            
-            // Navigation
-            (KeyCombination::new("ArrowUp").with_alt(), ShortcutAction::NavigateUp),
-            (KeyCombination::new("ArrowLeft").with_alt(), ShortcutAction::NavigateBack),
-            (KeyCombination::new("ArrowRight").with_alt(), ShortcutAction::NavigateForward),
-            (KeyCombination::new("h").with_ctrl(), ShortcutAction::NavigateHome),
-            (KeyCombination::new("F5"), ShortcutAction::Refresh),
-            (KeyCombination::new("r").with_ctrl(), ShortcutAction::Refresh),
-            (KeyCombination::new("Enter"), ShortcutAction::OpenFile),
-            
-            // View operations
-            (KeyCombination::new("p").with_ctrl(), ShortcutAction::TogglePreview),
-            (KeyCombination::new("f").with_ctrl(), ShortcutAction::ToggleSearch),
-            (KeyCombination::new("n").with_ctrl().with_shift(), ShortcutAction::NewFolder),
-            
-            // Properties
-            (KeyCombination::new("i").with_alt().with_ctrl(), ShortcutAction::ShowProperties),
-            
-            // Settings and Command Palette
-            (KeyCombination::new(",").with_ctrl(), ShortcutAction::ShowSettings),
-            (KeyCombination::new("p").with_ctrl().with_shift(), ShortcutAction::ShowCommandPalette),
-            
-            // VS Code compatibility shortcuts
-            (KeyCombination::new("e").with_ctrl().with_shift(), ShortcutAction::FocusExplorer),
-            (KeyCombination::new("1").with_ctrl(), ShortcutAction::FocusEditor1),
-            (KeyCombination::new("2").with_ctrl(), ShortcutAction::FocusEditor2),
-            (KeyCombination::new("3").with_ctrl(), ShortcutAction::FocusEditor3),
-            (KeyCombination::new("w").with_ctrl(), ShortcutAction::CloseTab),
-            (KeyCombination::new("Tab").with_ctrl(), ShortcutAction::SwitchTab),
-            (KeyCombination::new(" "), ShortcutAction::ToggleSpace),
-            (KeyCombination::new("=").with_ctrl(), ShortcutAction::ZoomIn),
-            (KeyCombination::new("+").with_ctrl(), ShortcutAction::ZoomIn),
-            (KeyCombination::new("-").with_ctrl(), ShortcutAction::ZoomOut),
-            
-            // Help and utility shortcuts
-            (KeyCombination::new("F1"), ShortcutAction::ShowShortcutCheatSheet),
-            
-            // Accessibility shortcuts
-            (KeyCombination::new("h").with_ctrl().with_shift(), ShortcutAction::ToggleHighContrast),
-        ];
-
+    /// Replace all shortcuts with the defaults as changed by `keymap`
+    ///
+    /// When a chord is bound to several actions the first one listed wins.
+    pub fn apply_keymap(&self, keymap: &KeymapState) {
         if let Ok(mut map) = self.shortcuts.lock() {
-            for (key_combo, action) in shortcuts {
-                map.insert(key_combo, action);
+            map.clear();
+            for (key_combo, action) in keymap_bindings(keymap) {
+                map.entry(key_combo).or_insert(action);
             }
         }
     }
@@ -344,6 +796,74 @@ mod tests {
         assert!(!combo.matches("d", true, false, false, false));
     }
 
+    fn press(key: &str, code: &str) -> KeyChordEvent {
+        KeyChordEvent {
+            key: key.to_string(),
+            code: code.to_string(),
+            ctrl: false,
+            shift: false,
+            alt: false,
+            meta: false,
+        }
+    }
+
+    #[test]
+    fn test_chord_normalization() {
+        let ctrl_shift_p = KeyChordEvent { ctrl: true, shift: true, ..press("P", "KeyP") };
+        let combo = ctrl_shift_p.normalize(Platform::Other).unwrap();
+        assert_eq!(combo, KeyCombination::new("p").with_ctrl().with_shift());
+        assert_eq!(combo.accelerator(), "CmdOrCtrl+Shift+P");
+        assert_eq!(combo.display_keys(Platform::Other), vec!["Ctrl", "Shift", "P"]);
+        assert_eq!(combo.display_keys(Platform::Mac), vec!["⌘", "⇧", "P"]);
+
+        // Cmd is the primary modifier on macOS, Control the secondary one
+        let cmd_k = KeyChordEvent { meta: true, ..press("k", "KeyK") };
+        assert_eq!(cmd_k.normalize(Platform::Mac).unwrap().accelerator(), "CmdOrCtrl+K");
+        let control_k = KeyChordEvent { ctrl: true, ..press("k", "KeyK") };
+        assert_eq!(control_k.normalize(Platform::Mac).unwrap().accelerator(), "Ctrl+K");
+
+        // Characters changed by Alt/Shift and dead keys use the physical key
+        let alt_c = KeyChordEvent { alt: true, ..press("ç", "KeyC") };
+        assert_eq!(alt_c.normalize(Platform::Mac).unwrap().accelerator(), "Alt+C");
+        let ctrl_shift_1 = KeyChordEvent { ctrl: true, shift: true, ..press("!", "Digit1") };
+        assert_eq!(ctrl_shift_1.normalize(Platform::Other).unwrap().accelerator(), "CmdOrCtrl+Shift+1");
+        let dead_alt_e = KeyChordEvent { alt: true, ..press("Dead", "KeyE") };
+        assert_eq!(dead_alt_e.normalize(Platform::Mac).unwrap().accelerator(), "Alt+E");
+        let dead_unknown = KeyChordEvent { alt: true, ..press("Dead", "IntlBackslash") };
+        assert_eq!(dead_unknown.normalize(Platform::Other), Err(ChordError::Unidentified));
+
+        assert_eq!(press("F5", "F5").normalize(Platform::Other).unwrap(), KeyCombination::new("F5"));
+        assert_eq!(press("Esc", "Escape").normalize(Platform::Other).unwrap_err(), ChordError::Reserved("Escape".to_string()));
+        let modifier_only = KeyChordEvent { ctrl: true, ..press("Control", "ControlLeft") };
+        assert_eq!(modifier_only.normalize(Platform::Other), Err(ChordError::ModifierOnly));
+    }
+
+    #[test]
+    fn test_reserved_and_invalid_chords_are_rejected() {
+        let rejected = [
+            (press("Tab", "Tab"), Platform::Other),
+            (KeyChordEvent { shift: true, ..press("Tab", "Tab") }, Platform::Other),
+            (KeyChordEvent { alt: true, ..press("F4", "F4") }, Platform::Other),
+            (KeyChordEvent { ctrl: true, alt: true, ..press("Delete", "Delete") }, Platform::Other),
+            (KeyChordEvent { meta: true, ..press("q", "KeyQ") }, Platform::Mac),
+            (KeyChordEvent { meta: true, ..press(" ", "Space") }, Platform::Mac),
+        ];
+        for (event, platform) in rejected {
+            assert!(
+                matches!(event.normalize(platform), Err(ChordError::Reserved(_))),
+                "{:?} should be reserved",
+                event
+            );
+        }
+
+        assert_eq!(press("a", "KeyA").normalize(Platform::Other), Err(ChordError::NeedsModifier));
+        let shift_a = KeyChordEvent { shift: true, ..press("A", "KeyA") };
+        assert_eq!(shift_a.normalize(Platform::Other), Err(ChordError::NeedsModifier));
+        assert!(press(" ", "Space").normalize(Platform::Other).is_ok(), "Space alone is a valid shortcut");
+        let ctrl_q = KeyChordEvent { ctrl: true, ..press("q", "KeyQ") };
+        assert!(ctrl_q.normalize(Platform::Other).is_ok(), "Ctrl+Q is only reserved as Cmd+Q on macOS");
+    }
+
     #[test]
     fn test_shortcut_registry() {
         let registry = ShortcutRegistry::new();
@@ -406,7 +926,7 @@ mod tests {
         // Test new VS Code shortcuts
         let vscode_shortcuts = vec![
             ("e", true, true, false, false, "FocusExplorer"),    // Ctrl+Shift+E
-            ("1", true, false, false, false, "FocusEditor"),     // Ctrl+1
+            ("1", true, false, true, false, "FocusEditor"),      // Ctrl+Alt+1
             ("w", true, false, false, false, "CloseTab"),        // Ctrl+W
             (" ", false, false, false, false, "ToggleSpace"),    // Space
             ("=", true, false, false, false, "ZoomIn"),          // Ctrl+=
@@ -424,6 +944,127 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_directory_tab_shortcuts() {
+        let registry = ShortcutRegistry::new();
+
+        assert!(matches!(registry.try_trigger("t", true, false, false, false), Some(ShortcutAction::NewDirectoryTab)));
+        assert!(matches!(registry.try_trigger("w", true, false, false, false), Some(ShortcutAction::CloseTab)));
+        for number in 1..=9 {
+            let triggered = registry.try_trigger(&number.to_string(), true, false, false, false);
+            assert!(
+                matches!(triggered, Some(ShortcutAction::SwitchDirectoryTab(n)) if n == number),
+                "Ctrl+{} should switch directory tabs, got {:?}",
+                number,
+                triggered
+            );
+        }
+        // Reopening a closed editor tab keeps Ctrl+Shift+T
+        assert!(matches!(registry.try_trigger("t", true, true, false, false), Some(ShortcutAction::ReopenClosedTab)));
+        assert_eq!(ShortcutAction::SwitchDirectoryTab(9).description(), "Show last folder tab");
+    }
+
+    #[test]
+    fn test_bracket_shortcuts_navigate_history() {
+        let registry = ShortcutRegistry::new();
+
+        assert!(matches!(registry.try_trigger("[", false, false, false, true), Some(ShortcutAction::NavigateBack)));
+        assert!(matches!(registry.try_trigger("]", false, false, false, true), Some(ShortcutAction::NavigateForward)));
+        assert!(matches!(registry.try_trigger("[", true, false, false, false), Some(ShortcutAction::NavigateBack)));
+        assert!(matches!(registry.try_trigger("]", true, false, false, false), Some(ShortcutAction::NavigateForward)));
+    }
+
+    #[test]
+    fn test_bookmark_shortcut() {
+        let registry = ShortcutRegistry::new();
+
+        assert!(matches!(registry.try_trigger("d", true, false, false, false), Some(ShortcutAction::BookmarkCurrentFolder)));
+        assert_eq!(ShortcutAction::BookmarkCurrentFolder.description(), "Bookmark current folder");
+    }
+
+    #[test]
+    fn test_shift_arrows_extend_selection() {
+        let registry = ShortcutRegistry::new();
+
+        assert!(matches!(registry.try_trigger("ArrowUp", false, true, false, false), Some(ShortcutAction::ExtendSelectionUp)));
+        assert!(matches!(registry.try_trigger("ArrowDown", false, true, false, false), Some(ShortcutAction::ExtendSelectionDown)));
+        // Plain arrows stay with the file list's own focus movement
+        assert!(registry.try_trigger("ArrowDown", false, false, false, false).is_none());
+    }
+
+    #[test]
+    fn test_accelerator_round_trip() {
+        for (combo, _) in default_bindings() {
+            let accelerator = combo.accelerator();
+            assert_eq!(KeyCombination::from_accelerator(&accelerator), Some(combo), "{}", accelerator);
+        }
+        assert_eq!(KeyCombination::from_accelerator("CmdOrCtrl++"), Some(KeyCombination::new("+").with_ctrl()));
+        assert_eq!(KeyCombination::from_accelerator("Hyper+K"), None);
+        assert_eq!(KeyCombination::from_accelerator(""), None);
+    }
+
+    #[test]
+    fn test_action_ids_are_unique_and_resolve() {
+        let actions = remappable_actions();
+        for action in &actions {
+            assert_eq!(action_from_id(&action.id()).as_ref(), Some(action));
+        }
+        let mut ids: Vec<String> = actions.iter().map(ShortcutAction::id).collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), actions.len());
+        assert_eq!(action_from_id("custom:anything"), None);
+    }
+
+    #[test]
+    fn test_keymap_overrides_replace_defaults() {
+        let mut keymap = KeymapState::default();
+        let registry = ShortcutRegistry::new();
+
+        rebind(&mut keymap, &ShortcutAction::Copy, Some(&KeyCombination::new("c").with_ctrl()), Some(KeyCombination::new("c").with_ctrl().with_shift()));
+        rebind(&mut keymap, &ShortcutAction::Refresh, Some(&KeyCombination::new("F5")), None);
+        registry.apply_keymap(&keymap);
+
+        assert_eq!(registry.try_trigger("c", true, true, false, false), Some(ShortcutAction::Copy));
+        assert_eq!(registry.try_trigger("c", true, false, false, false), None);
+        assert_eq!(registry.try_trigger("F5", false, false, false, false), None);
+        assert_eq!(registry.try_trigger("r", true, false, false, false), Some(ShortcutAction::Refresh));
+        assert_eq!(keymap.overrides("refresh"), Some(&["CmdOrCtrl+R".to_string()][..]));
+
+        // Binding the default chords again drops the override
+        rebind(&mut keymap, &ShortcutAction::Refresh, None, Some(KeyCombination::new("F5")));
+        assert_eq!(keymap.overrides("refresh"), None);
+
+        keymap.reset_all();
+        registry.apply_keymap(&keymap);
+        assert_eq!(registry.try_trigger("c", true, false, false, false), Some(ShortcutAction::Copy));
+    }
+
+    #[test]
+    fn test_keymap_conflict_detection() {
+        assert_eq!(find_conflicts(&KeymapState::default()), Vec::new(), "the defaults never conflict");
+
+        let mut keymap = KeymapState::default();
+        let ctrl_p = KeyCombination::new("p").with_ctrl();
+        assert_eq!(action_for_chord(&keymap, &ctrl_p, &ShortcutAction::Copy), Some(ShortcutAction::TogglePreview));
+        assert_eq!(action_for_chord(&keymap, &ctrl_p, &ShortcutAction::TogglePreview), None);
+
+        rebind(&mut keymap, &ShortcutAction::Copy, None, Some(ctrl_p.clone()));
+        assert_eq!(
+            find_conflicts(&keymap),
+            vec![KeymapConflict { combo: ctrl_p.clone(), actions: vec![ShortcutAction::Copy, ShortcutAction::TogglePreview] }]
+        );
+        // The action listed first keeps the chord
+        let registry = ShortcutRegistry::new();
+        registry.apply_keymap(&keymap);
+        assert_eq!(registry.try_trigger("p", true, false, false, false), Some(ShortcutAction::Copy));
+
+        // Reassigning takes the chord away from the other action
+        rebind(&mut keymap, &ShortcutAction::TogglePreview, Some(&ctrl_p), None);
+        assert!(find_conflicts(&keymap).is_empty());
+        assert!(chords_for_action(&keymap, &ShortcutAction::TogglePreview).is_empty());
+    }
+
     #[test]
     fn test_total_shortcuts_count() {
         let registry = ShortcutRegistry::new();
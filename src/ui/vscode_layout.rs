@@ -1,10 +1,11 @@
 use dioxus::prelude::*;
 use dioxus::events::{MouseEvent, DragData, MouseData};
 use dioxus_free_icons::{Icon, icons::fa_solid_icons};
-use crate::state::{ActivityBarView, use_activity_bar_view, use_sidebar_state, use_editor_state, use_panel_state, TabDragOperation, TabContextMenu};
+use crate::state::{ActivityBarView, use_activity_bar_view, use_sidebar_state, use_editor_state, use_panel_state, TabDragOperation, TabContextMenu, TabDropTarget, SplitEdge};
 // Removed unused preview imports
-use crate::ui::components::{WorkingFileTree, PreviewPanel};
+use crate::ui::components::{WorkingFileTree, TagCollectionsView, PreviewPanel, OutputView, TerminalView, ErrorStatus};
 use crate::ui::components::preview_panel::FileSystemEntry;
+use crate::ui::focus;
 
 use std::sync::{Mutex, OnceLock};
 
@@ -84,7 +85,7 @@ pub fn VSCodeLayout(
             role: "application",
             "aria-label": "File Manager - VS Code style interface",
             "aria-describedby": "app-instructions",
-            tabindex: "0",
+            tabindex: "-1", // Receives bubbled key events without adding a tab stop
             onkeydown: move |evt| {
                 // Handle global keyboard shortcuts following VS Code patterns
                 match evt.data.key() {
@@ -133,21 +134,8 @@ pub fn VSCodeLayout(
                     Key::Escape => {
                         // Escape key - return focus to main content area
                         evt.prevent_default();
-                        // Focus the main editor area or first interactive element
-                        // Web-only functionality, skip for desktop builds
-                        #[cfg(feature = "web")]
-                        if let Some(window) = web_sys::window() {
-                            if let Some(document) = window.document() {
-                                // Try to focus the editor groups container first
-                                if let Ok(Some(editor_area)) = document.query_selector(".editor-groups") {
-                                    let _ = editor_area.focus();
-                                    tracing::info!("Focus returned to editor area via Escape");
-                                } else if let Ok(Some(main_area)) = document.query_selector("[role=\"main\"]") {
-                                    let _ = main_area.focus();
-                                    tracing::info!("Focus returned to main content via Escape");
-                                }
-                            }
-                        }
+                        focus::focus_element("main-content");
+                        tracing::info!("Focus returned to main content via Escape");
                     },
                     Key::F1 => {
                         // F1 - Command palette (VS Code standard)
@@ -305,6 +293,7 @@ pub fn ActivityBar(
         (ActivityBarView::SourceControl, "source-control", "Source Control - Git integration and version management (Ctrl+3)"),
         (ActivityBarView::Debug, "debug-alt", "Run and Debug - Execute applications and debug code (Ctrl+4)"),
         (ActivityBarView::Extensions, "extensions", "Extensions - Install and manage VS Code extensions (Ctrl+5)"),
+        (ActivityBarView::Tags, "tags", "Tags - Browse files by tag (Ctrl+6)"),
     ];
     
     // Handle keyboard activation of focused item
@@ -394,6 +383,12 @@ pub fn ActivityBar(
                                 focused_item.set(4); // Update focus indicator
                                 tracing::info!("Activity Bar: Activated Extensions via Ctrl+5");
                             },
+                            "6" => {
+                                evt.prevent_default();
+                                activity_view.set(ActivityBarView::Tags);
+                                focused_item.set(5); // Update focus indicator
+                                tracing::info!("Activity Bar: Activated Tags via Ctrl+6");
+                            },
                             _ => {}
                         }
                     },
@@ -426,7 +421,7 @@ pub fn ActivityBar(
                     white-space: nowrap;
                     border: 0;
                 ",
-                "Use arrow keys to navigate between tools. Press Enter or Space to activate a tool. Direct access: Ctrl+1 for Explorer, Ctrl+2 for Search, Ctrl+3 for Source Control, Ctrl+4 for Debug, Ctrl+5 for Extensions. Ctrl+Shift+E to toggle sidebar."
+                "Use arrow keys to navigate between tools. Press Enter or Space to activate a tool. Direct access: Ctrl+1 for Explorer, Ctrl+2 for Search, Ctrl+3 for Source Control, Ctrl+4 for Debug, Ctrl+5 for Extensions, Ctrl+6 for Tags. Ctrl+Shift+E to toggle sidebar."
             }
             
             // Activity Bar Items
@@ -584,7 +579,7 @@ pub fn Sidebar(
             ),
             role: "complementary",
             "aria-label": "File explorer sidebar",
-            tabindex: "0",
+            tabindex: "-1", // The tree inside is the region's tab stop
             
             if !is_collapsed {
                 
@@ -603,6 +598,9 @@ pub fn Sidebar(
                         ActivityBarView::Explorer => rsx! {
                             WorkingFileTree {}
                         },
+                        ActivityBarView::Tags => rsx! {
+                            TagCollectionsView {}
+                        },
                         _ => rsx! {
                             div {
                                 style: "padding: 16px; color: var(--vscode-foreground, #cccccc); text-align: center;",
@@ -828,6 +826,15 @@ pub fn EditorGroups(
                         tracing::info!("Closed active tab via Ctrl+W");
                     }
                 },
+                // Ctrl+Shift+T: reopen the most recently closed tab
+                Key::Character(ch) if ch == "t" || ch == "T" => {
+                    if evt.data.modifiers().ctrl() && evt.data.modifiers().shift() {
+                        evt.prevent_default();
+                        if editor_state.write().reopen_closed_tab() {
+                            tracing::info!("Reopened closed tab via Ctrl+Shift+T");
+                        }
+                    }
+                },
                 // Ctrl+K, P: toggle pin for active tab (simplified as Ctrl+P for now)
                 Key::Character(ch) if ch == "p" || ch == "P" => {
                     if evt.data.modifiers().ctrl() {
@@ -934,7 +941,7 @@ pub fn Panel(
             role: "complementary",
             "aria-label": "Bottom panel with terminal and output",
             "aria-labelledby": "panel-header",
-            tabindex: "0",
+            tabindex: "-1", // The tab list inside is the region's tab stop
             
             // Resize handle at the top
             div {
@@ -1016,6 +1023,20 @@ pub fn Panel(
 pub fn StatusBar(
     app_state: Signal<crate::state::AppState>,
 ) -> Element {
+    // Background task throttle state, refreshed periodically
+    let throttle_policy = app_state.read().background_throttle.clone();
+    let mut throttle_state = use_signal(|| throttle_policy.current_state());
+    use_future(move || {
+        let throttle_policy = throttle_policy.clone();
+        async move {
+            loop {
+                throttle_state.set(throttle_policy.current_state());
+                tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+            }
+        }
+    });
+    let throttle = *throttle_state.read();
+    
     rsx! {
         footer {
             class: "status-bar",
@@ -1042,6 +1063,14 @@ pub fn StatusBar(
                 
                 span { "Ready" }
                 span { "v0.1.0" }
+                if throttle != crate::services::ThrottleState::Running {
+                    span {
+                        class: "status-bar-background-tasks",
+                        title: "Low-priority background tasks are held back",
+                        "{throttle.label()}"
+                    }
+                }
+                ErrorStatus {}
             }
             
             // Spacer
@@ -1185,9 +1214,23 @@ pub fn EditorGroupComponent(
     focused_tab_index: Signal<usize>,
     #[props(default = String::new())] style: String,
 ) -> Element {
-    let is_active_group = {
+    let (is_active_group, drop_target) = {
         let editor_data = editor_state.read();
-        editor_data.active_group == group_index
+        (
+            editor_data.active_group == group_index,
+            editor_data.drag_operation.as_ref().map(|op| op.drop_target),
+        )
+    };
+    let tab_count = group.tabs.len();
+    
+    // Tabs that do not fit go into the overflow menu unless the strip scrolls
+    let app_state = crate::state::use_app_state();
+    let tab_overflow = app_state.settings.read().tab_overflow;
+    let mut strip_width = use_signal(|| f64::INFINITY);
+    let mut overflow_open = use_signal(|| false);
+    let hidden_tabs = match tab_overflow {
+        crate::state::TabOverflow::Menu => group.overflow_tabs(*strip_width.read()),
+        crate::state::TabOverflow::Scroll => Vec::new(),
     };
     
     rsx! {
@@ -1196,6 +1239,7 @@ pub fn EditorGroupComponent(
             style: format!("
                 display: flex;
                 flex-direction: column;
+                position: relative;
                 background: var(--vscode-background, #1e1e1e);
                 border: 1px solid var(--vscode-border, #464647);
                 {}
@@ -1206,29 +1250,33 @@ pub fn EditorGroupComponent(
             // Tab bar
             div {
                 class: "editor-group-tabs",
-                style: "
+                style: format!("
                     display: flex;
                     height: 35px;
+                    position: relative;
+                    overflow-x: {};
+                    overflow-y: hidden;
                     background: var(--vscode-tab-inactive-background, #2d2d30);
                     border-bottom: 1px solid var(--vscode-border, #464647);
-                ",
+                ", if tab_overflow == crate::state::TabOverflow::Scroll { "auto" } else { "hidden" }),
                 role: "tablist",
                 "aria-label": "Editor tabs",
-                ondragover: {
-                    move |evt: Event<DragData>| {
-                        evt.prevent_default();
-                        // Note: set_drop_effect not available in current Dioxus version
+                onresize: move |evt: Event<ResizeData>| {
+                    if let Ok(size) = evt.data().get_content_box_size() {
+                        strip_width.set(size.width);
                     }
                 },
-                ondrop: {
-                    let mut editor_state = editor_state.clone();
-                    move |evt: Event<DragData>| {
-                        evt.prevent_default();
-                        handle_tab_drop(evt, editor_state.clone(), group_index);
-                    }
+                // Dropping past the last tab appends to this group
+                ondragover: move |evt: Event<DragData>| {
+                    evt.prevent_default();
+                    set_drop_target(editor_state, TabDropTarget::Tab { group_index, tab_index: tab_count });
+                },
+                ondrop: move |evt: Event<DragData>| {
+                    evt.prevent_default();
+                    handle_tab_drop(editor_state, focused_tab_index, TabDropTarget::Tab { group_index, tab_index: tab_count });
                 },
                 
-                for (tab_index, tab) in group.tabs.iter().enumerate() {
+                for (tab_index, tab) in group.tabs.iter().enumerate().filter(|(i, _)| !hidden_tabs.contains(i)) {
                     EditorTabComponent {
                         key: "{tab.id}",
                         tab: tab.clone(),
@@ -1236,6 +1284,83 @@ pub fn EditorGroupComponent(
                         group_index: group_index,
                         is_focused: is_active_group && *focused_tab_index.read() == tab_index,
                         editor_state: editor_state,
+                        focused_tab_index: focused_tab_index,
+                    }
+                }
+                
+                if !hidden_tabs.is_empty() {
+                    button {
+                        class: "tab-overflow-button",
+                        style: format!("
+                            margin-left: auto;
+                            width: {}px;
+                            flex-shrink: 0;
+                            border: none;
+                            background: transparent;
+                            color: var(--vscode-foreground, #cccccc);
+                            cursor: pointer;
+                        ", crate::state::TAB_OVERFLOW_BUTTON_WIDTH),
+                        title: format!("{} more tabs", hidden_tabs.len()),
+                        "aria-label": format!("Show {} hidden tabs", hidden_tabs.len()),
+                        "aria-haspopup": "menu",
+                        "aria-expanded": if *overflow_open.read() { "true" } else { "false" },
+                        onclick: move |evt: Event<MouseData>| {
+                            evt.stop_propagation();
+                            let open = *overflow_open.read();
+                            overflow_open.set(!open);
+                        },
+                        Icon { icon: fa_solid_icons::FaChevronDown, width: 12, height: 12 }
+                    }
+                }
+            }
+            
+            if *overflow_open.read() && !hidden_tabs.is_empty() {
+                div {
+                    class: "tab-overflow-menu",
+                    style: "
+                        position: absolute;
+                        right: 4px;
+                        margin-top: 35px;
+                        background: var(--vscode-menu-background, #2c2c2c);
+                        border: 1px solid var(--vscode-menu-border, #454545);
+                        border-radius: 3px;
+                        box-shadow: 0 2px 8px rgba(0, 0, 0, 0.3);
+                        z-index: 1000;
+                        min-width: 200px;
+                        max-height: 300px;
+                        overflow-y: auto;
+                        padding: 4px 0;
+                    ",
+                    role: "menu",
+                    "aria-label": "Hidden tabs",
+                    for tab_index in hidden_tabs.iter().copied() {
+                        div {
+                            key: "{group.tabs[tab_index].id}",
+                            class: "menu-item",
+                            style: "
+                                padding: 6px 12px;
+                                cursor: pointer;
+                                display: flex;
+                                align-items: center;
+                                gap: 8px;
+                            ",
+                            role: "menuitem",
+                            tabindex: "0",
+                            onclick: move |_| {
+                                overflow_open.set(false);
+                                let mut editor_data = editor_state.write();
+                                if let Some(group) = editor_data.editor_groups.get_mut(group_index) {
+                                    group.active_tab = tab_index;
+                                    for (i, t) in group.tabs.iter_mut().enumerate() {
+                                        t.is_active = i == tab_index;
+                                    }
+                                }
+                                editor_data.active_group = group_index;
+                                focused_tab_index.set(tab_index);
+                            },
+                            {get_tab_icon(&group.tabs[tab_index].tab_type)}
+                            "{group.tabs[tab_index].title}"
+                        }
                     }
                 }
             }
@@ -1246,6 +1371,7 @@ pub fn EditorGroupComponent(
                 style: "
                     flex: 1;
                     overflow: hidden;
+                    position: relative;
                     background: var(--vscode-background, #1e1e1e);
                 ",
                 role: "tabpanel",
@@ -1257,6 +1383,25 @@ pub fn EditorGroupComponent(
                         group_index: group_index,
                     }
                 }
+                
+                // While a tab is dragged, each edge splits it off into a new group
+                if let Some(drop_target) = drop_target {
+                    for edge in SplitEdge::ALL {
+                        div {
+                            key: "{edge:?}",
+                            class: "tab-drop-zone",
+                            style: drop_zone_style(edge, drop_target == Some(TabDropTarget::Edge { group_index, edge })),
+                            ondragover: move |evt: Event<DragData>| {
+                                evt.prevent_default();
+                                set_drop_target(editor_state, TabDropTarget::Edge { group_index, edge });
+                            },
+                            ondrop: move |evt: Event<DragData>| {
+                                evt.prevent_default();
+                                handle_tab_drop(editor_state, focused_tab_index, TabDropTarget::Edge { group_index, edge });
+                            },
+                        }
+                    }
+                }
             }
         }
     }
@@ -1270,22 +1415,41 @@ pub fn EditorTabComponent(
     group_index: usize,
     is_focused: bool,
     editor_state: Signal<crate::state::EditorState>,
+    focused_tab_index: Signal<usize>,
 ) -> Element {
+    // Line on the side where the dragged tab would be inserted
+    let drop_indicator = editor_state.read().drag_operation.as_ref().and_then(|op| {
+        if op.drop_target != Some(TabDropTarget::Tab { group_index, tab_index }) {
+            return None;
+        }
+        let moves_right = op.source_group_id == group_index && op.tab_index < tab_index;
+        Some(if moves_right {
+            "inset -2px 0 0 var(--vscode-accent, #007acc)"
+        } else {
+            "inset 2px 0 0 var(--vscode-accent, #007acc)"
+        })
+    });
     let tab_style = format!(
         "
             display: flex;
             align-items: center;
+            justify-content: {};
             height: 35px;
-            min-width: 120px;
-            max-width: 200px;
-            padding: 0 12px;
+            width: {}px;
+            flex-shrink: 0;
+            padding: 0 {};
             background: {};
             color: {};
             border-right: 1px solid var(--vscode-border, #464647);
             cursor: pointer;
             position: relative;
             outline: {};
+            box-shadow: {};
         ",
+        // Pinned tabs are compact and only show their icon
+        if tab.is_pinned { "center" } else { "flex-start" },
+        if tab.is_pinned { crate::state::PINNED_TAB_WIDTH } else { crate::state::TAB_WIDTH },
+        if tab.is_pinned { "6px" } else { "12px" },
         if tab.is_active { 
             "var(--vscode-tab-active-background, #1e1e1e)" 
         } else if is_focused {
@@ -1298,7 +1462,8 @@ pub fn EditorTabComponent(
         } else { 
             "var(--vscode-text-secondary, #999999)" 
         },
-        if is_focused { "2px solid var(--vscode-focusBorder, #007acc)" } else { "none" }
+        if is_focused { "2px solid var(--vscode-focusBorder, #007acc)" } else { "none" },
+        drop_indicator.unwrap_or("none")
     );
     
     rsx! {
@@ -1312,6 +1477,15 @@ pub fn EditorTabComponent(
             "data-tab-id": tab.id,
             title: tab.title.clone(),
             draggable: "true",
+            // Middle-click closes the tab
+            onmousedown: move |evt: Event<MouseData>| {
+                if evt.data.trigger_button() == Some(dioxus::html::input_data::MouseButton::Auxiliary) {
+                    evt.prevent_default();
+                    if editor_state.write().close_tab(group_index, tab_index) {
+                        tracing::info!("Closed tab {} in group {} with middle click", tab_index, group_index);
+                    }
+                }
+            },
             onclick: {
                 let mut editor_state = editor_state.clone();
                 move |_| {
@@ -1339,6 +1513,7 @@ pub fn EditorTabComponent(
                         tab_id,
                         tab_index,
                         source_group_id: group_index,
+                        drop_target: None,
                     });
                     
                     // Note: Dioxus 0.6 DragData methods may be different
@@ -1356,6 +1531,17 @@ pub fn EditorTabComponent(
                     tracing::info!("Drag operation ended");
                 }
             },
+            // Dropping on a tab puts the dragged tab in its place
+            ondragover: move |evt: Event<DragData>| {
+                evt.prevent_default();
+                evt.stop_propagation();
+                set_drop_target(editor_state, TabDropTarget::Tab { group_index, tab_index });
+            },
+            ondrop: move |evt: Event<DragData>| {
+                evt.prevent_default();
+                evt.stop_propagation();
+                handle_tab_drop(editor_state, focused_tab_index, TabDropTarget::Tab { group_index, tab_index });
+            },
             oncontextmenu: {
                 let mut editor_state = editor_state.clone();
                 let tab_id = tab.id;
@@ -1381,22 +1567,24 @@ pub fn EditorTabComponent(
             // Tab icon
             div {
                 class: "tab-icon",
-                style: "margin-right: 6px; display: flex; align-items: center;",
+                style: if tab.is_pinned { "display: flex; align-items: center;" } else { "margin-right: 6px; display: flex; align-items: center;" },
                 {get_tab_icon(&tab.tab_type)}
             }
             
             // Tab title
-            span {
-                class: "tab-title",
-                style: "
-                    flex: 1;
-                    overflow: hidden;
-                    text-overflow: ellipsis;
-                    white-space: nowrap;
-                ",
-                "{tab.title}"
-                if tab.is_dirty {
-                    " •"
+            if !tab.is_pinned {
+                span {
+                    class: "tab-title",
+                    style: "
+                        flex: 1;
+                        overflow: hidden;
+                        text-overflow: ellipsis;
+                        white-space: nowrap;
+                    ",
+                    "{tab.title}"
+                    if tab.is_dirty {
+                        " •"
+                    }
                 }
             }
             
@@ -1424,46 +1612,36 @@ pub fn EditorTabComponent(
                 }
             }
             
-            // Close button
-            button {
-                class: "tab-close",
-                style: "
-                    margin-left: 4px;
-                    width: 16px;
-                    height: 16px;
-                    border: none;
-                    background: transparent;
-                    color: inherit;
-                    cursor: pointer;
-                    display: flex;
-                    align-items: center;
-                    justify-content: center;
-                    border-radius: 2px;
-                ",
-                title: "Close tab",
-                "aria-label": format!("Close {}", tab.title),
-                onclick: {
-                    let mut editor_state = editor_state.clone();
-                    move |evt: Event<MouseData>| {
-                        evt.stop_propagation();
-                        let mut editor_data = editor_state.write();
-                        if let Some(group) = editor_data.editor_groups.get_mut(group_index) {
-                            if group.tabs.len() > 1 {
-                                group.tabs.remove(tab_index);
-                                // Adjust active tab if necessary
-                                if group.active_tab >= group.tabs.len() {
-                                    group.active_tab = group.tabs.len().saturating_sub(1);
-                                }
-                                // Update active states
-                                for (i, t) in group.tabs.iter_mut().enumerate() {
-                                    t.is_active = i == group.active_tab;
-                                }
+            // Close button; pinned tabs are closed from the context menu or with a middle click
+            if !tab.is_pinned {
+                button {
+                    class: "tab-close",
+                    style: "
+                        margin-left: 4px;
+                        width: 16px;
+                        height: 16px;
+                        border: none;
+                        background: transparent;
+                        color: inherit;
+                        cursor: pointer;
+                        display: flex;
+                        align-items: center;
+                        justify-content: center;
+                        border-radius: 2px;
+                    ",
+                    title: "Close tab",
+                    "aria-label": format!("Close {}", tab.title),
+                    onclick: {
+                        let mut editor_state = editor_state.clone();
+                        move |evt: Event<MouseData>| {
+                            evt.stop_propagation();
+                            if editor_state.write().close_tab(group_index, tab_index) {
                                 tracing::info!("Closed tab {} in group {}", tab_index, group_index);
                             }
                         }
-                    }
-                },
-                "×"
+                    },
+                    "×"
+                }
             }
         }
     }
@@ -1607,6 +1785,29 @@ pub fn TabContextMenuComponent(
                     "Close Tabs to the Right"
                 }
                 
+                // Close All Unpinned
+                div {
+                    class: "menu-item",
+                    style: "
+                        padding: 8px 12px;
+                        cursor: pointer;
+                        display: flex;
+                        align-items: center;
+                        gap: 8px;
+                        border-radius: 2px;
+                        transition: background-color 0.1s ease;
+                    ",
+                    role: "menuitem",
+                    onclick: {
+                        let group_index = context_menu.group_index;
+                        move |_| {
+                            close_all_unpinned(editor_state, group_index);
+                        }
+                    },
+                    Icon { icon: fa_solid_icons::FaXmark, width: 12, height: 12 }
+                    "Close All Unpinned"
+                }
+                
                 // Pin/Unpin Tab (moved up to group with close actions)
                 div {
                     class: "menu-item",
@@ -1779,7 +1980,13 @@ pub fn EditorTabContentComponent(
                             white-space: pre-wrap;
                             line-height: 1.4;
                         ",
-                        "{content}"
+                        match (&tab.file_path, content.is_empty()) {
+                            // Restored tabs carry no content; read it now that the tab is shown
+                            (Some(path), true) => rsx! {
+                                RestoredFileContent { key: "{tab.id}", path: path.clone() }
+                            },
+                            _ => rsx! { "{content}" },
+                        }
                     }
                 },
                 crate::state::TabType::Preview { preview_type } => rsx! {
@@ -1834,6 +2041,21 @@ pub fn EditorTabContentComponent(
     }
 }
 
+/// File contents for a tab restored from the last session, read on first display
+#[component]
+fn RestoredFileContent(path: std::path::PathBuf) -> Element {
+    let content = use_resource(move || {
+        let path = path.clone();
+        async move { tokio::fs::read_to_string(&path).await }
+    });
+
+    match &*content.read() {
+        Some(Ok(text)) => rsx! { "{text}" },
+        Some(Err(e)) => rsx! { span { role: "alert", "Could not read file: {e}" } },
+        None => rsx! { span { "Loading..." } },
+    }
+}
+
 /// Helper function to get the appropriate icon for different tab types
 fn get_tab_icon(tab_type: &crate::state::TabType) -> Element {
     match tab_type {
@@ -1967,7 +2189,6 @@ fn PanelTabButton(
             border: none;
             border-right: 1px solid var(--vscode-border, #464647);
             cursor: pointer;
-            outline: none;
         ",
         if is_active { 
             "var(--vscode-tab-active-background, #1e1e1e)" 
@@ -2016,42 +2237,10 @@ fn get_panel_content(active_tab: crate::state::PanelTab) -> Element {
                 }
             },
             crate::state::PanelTab::Output => rsx! {
-                div {
-                    id: "output-panel",
-                    "aria-labelledby": "output-tab",
-                    h3 { style: "margin-bottom: 8px;", "Output" }
-                    pre {
-                        style: "
-                            font-family: var(--vscode-font-mono);
-                            font-size: var(--vscode-font-size-small);
-                            line-height: 1.4;
-                            margin: 0;
-                        ",
-                        "File Manager v0.1.0\n"
-                        "Initialized VS Code-style interface\n"
-                        "Ready for file operations...\n"
-                    }
-                }
+                OutputView {}
             },
             crate::state::PanelTab::Terminal => rsx! {
-                div {
-                    id: "terminal-panel",
-                    "aria-labelledby": "terminal-tab",
-                    h3 { style: "margin-bottom: 8px;", "Terminal" }
-                    div {
-                        style: "
-                            background: var(--vscode-terminal-background, #1e1e1e);
-                            border: 1px solid var(--vscode-border, #464647);
-                            border-radius: 4px;
-                            padding: 12px;
-                            font-family: var(--vscode-font-mono);
-                            font-size: var(--vscode-font-size-normal);
-                            color: var(--vscode-terminal-foreground, #ffffff);
-                        ",
-                        p { "Terminal integration coming soon..." }
-                        p { style: "margin-top: 8px; opacity: 0.7;", "$ " }
-                    }
-                }
+                TerminalView {}
             },
             crate::state::PanelTab::Debug => rsx! {
                 div {
@@ -2108,6 +2297,14 @@ fn get_activity_bar_icon(icon_name: &str) -> Element {
                 icon: fa_solid_icons::FaPuzzlePiece,
             }
         },
+        "tags" => rsx! {
+            Icon {
+                width: 16,
+                height: 16,
+                fill: "currentColor",
+                icon: fa_solid_icons::FaTags,
+            }
+        },
         "settings-gear" => rsx! {
             Icon {
                 width: 16,
@@ -2175,138 +2372,69 @@ fn handle_global_mouse_up(panel_state: Signal<crate::state::PanelState>) {
         
         let panel_height = panel_state.read().height;
         tracing::info!("Panel resizing ended, final height: {}px", panel_height);
+        crate::state::persistence::save_bottom_panel(&panel_state.read());
     }
 }
 
-/// Handle tab drop for reordering and moving tabs between groups
-fn handle_tab_drop(evt: Event<DragData>, mut editor_state: Signal<crate::state::EditorState>, target_group_index: usize) {
+/// Drop the dragged tab on `target` and keep keyboard focus on it
+fn handle_tab_drop(
+    mut editor_state: Signal<crate::state::EditorState>,
+    mut focused_tab_index: Signal<usize>,
+    target: TabDropTarget,
+) {
     let mut editor_data = editor_state.write();
-    
-    // Get the drag operation
-    let drag_op = match &editor_data.drag_operation {
-        Some(op) => op.clone(),
-        None => {
-            tracing::warn!("No drag operation in progress");
-            return;
-        }
+    let Some(drag_op) = editor_data.drag_operation.take() else {
+        tracing::warn!("No drag operation in progress");
+        return;
     };
     
     let source_group_index = drag_op.source_group_id;
     let source_tab_index = drag_op.tab_index;
+    let moved = match target {
+        TabDropTarget::Tab { group_index, tab_index } => {
+            editor_data.move_tab(source_group_index, source_tab_index, group_index, tab_index)
+        }
+        TabDropTarget::Edge { group_index, edge } => {
+            editor_data.split_tab(source_group_index, source_tab_index, group_index, edge)
+        }
+    };
     
-    // Calculate drop position based on mouse coordinates
-    // For now, we'll append to the target group - a more sophisticated implementation
-    // would calculate the exact insertion position based on mouse coordinates
-    
-    if source_group_index == target_group_index {
-        // Reordering within the same group
-        handle_tab_reorder_within_group(&mut editor_data, source_group_index, source_tab_index, evt);
-    } else {
-        // Moving between different groups
-        handle_tab_move_between_groups(&mut editor_data, source_group_index, target_group_index, source_tab_index);
+    if moved {
+        let active_tab = editor_data.editor_groups[editor_data.active_group].active_tab;
+        focused_tab_index.set(active_tab);
+        tracing::info!("Moved tab {} from group {} to {:?}", source_tab_index, source_group_index, target);
     }
 }
 
-/// Handle tab reordering within the same group
-fn handle_tab_reorder_within_group(
-    editor_data: &mut crate::state::EditorState,
-    group_index: usize,
-    source_tab_index: usize,
-    _evt: Event<DragData>
-) {
-    // For now, we'll implement a simple reordering logic
-    // A more sophisticated version would calculate exact drop position
-    if let Some(group) = editor_data.editor_groups.get_mut(group_index) {
-        if source_tab_index < group.tabs.len() {
-            // For simplicity, move the tab to the end of the group
-            let target_index = group.tabs.len() - 1;
-            
-            if source_tab_index != target_index {
-                let tab = group.tabs.remove(source_tab_index);
-                group.tabs.insert(target_index, tab);
-                
-                // Update active tab index if needed
-                if group.active_tab == source_tab_index {
-                    group.active_tab = target_index;
-                } else if group.active_tab > source_tab_index && group.active_tab <= target_index {
-                    group.active_tab -= 1;
-                } else if group.active_tab < source_tab_index && group.active_tab >= target_index {
-                    group.active_tab += 1;
-                }
-                
-                // Update all tabs' active state
-                for (i, tab) in group.tabs.iter_mut().enumerate() {
-                    tab.is_active = i == group.active_tab;
-                }
-                
-                tracing::info!("Reordered tab from index {} to {} within group {}", source_tab_index, target_index, group_index);
-            }
+/// Remember where the dragged tab would land so the drop indicator can show it
+fn set_drop_target(mut editor_state: Signal<crate::state::EditorState>, target: TabDropTarget) {
+    // dragover fires continuously; only write when the target changes
+    let changed = editor_state
+        .peek()
+        .drag_operation
+        .as_ref()
+        .is_some_and(|op| op.drop_target != Some(target));
+    if changed {
+        if let Some(op) = editor_state.write().drag_operation.as_mut() {
+            op.drop_target = Some(target);
         }
     }
 }
 
-/// Handle tab moving between different groups
-fn handle_tab_move_between_groups(
-    editor_data: &mut crate::state::EditorState,
-    source_group_index: usize,
-    target_group_index: usize,
-    source_tab_index: usize
-) {
-    // Ensure both groups exist
-    if source_group_index >= editor_data.editor_groups.len() || target_group_index >= editor_data.editor_groups.len() {
-        tracing::warn!("Invalid group indices for tab move: source={}, target={}", source_group_index, target_group_index);
-        return;
-    }
-    
-    // Remove tab from source group
-    let moved_tab = {
-        let source_group = &mut editor_data.editor_groups[source_group_index];
-        if source_tab_index >= source_group.tabs.len() {
-            tracing::warn!("Invalid tab index {} in source group {}", source_tab_index, source_group_index);
-            return;
-        }
-        
-        let tab = source_group.tabs.remove(source_tab_index);
-        
-        // Update source group's active tab
-        if source_group.tabs.is_empty() {
-            // If no tabs left, we might need to handle this case
-            source_group.active_tab = 0;
-        } else if source_group.active_tab >= source_tab_index && source_group.active_tab > 0 {
-            source_group.active_tab -= 1;
-        }
-        
-        // Update active states in source group
-        for (i, t) in source_group.tabs.iter_mut().enumerate() {
-            t.is_active = i == source_group.active_tab;
-        }
-        
-        tab
+/// Style for one edge drop zone of an editor group, highlighted while hovered
+fn drop_zone_style(edge: SplitEdge, active: bool) -> String {
+    let placement = match edge {
+        SplitEdge::Left => "top: 0; bottom: 0; left: 0; width: 25%;",
+        SplitEdge::Right => "top: 0; bottom: 0; right: 0; width: 25%;",
+        SplitEdge::Top => "top: 0; left: 25%; right: 25%; height: 25%;",
+        SplitEdge::Bottom => "bottom: 0; left: 25%; right: 25%; height: 25%;",
     };
-    
-    // Add tab to target group
-    {
-        let target_group = &mut editor_data.editor_groups[target_group_index];
-        
-        // Add to end of target group
-        target_group.tabs.push(moved_tab);
-        let new_tab_index = target_group.tabs.len() - 1;
-        
-        // Make the moved tab active in the target group
-        target_group.active_tab = new_tab_index;
-        
-        // Update active states in target group
-        for (i, tab) in target_group.tabs.iter_mut().enumerate() {
-            tab.is_active = i == new_tab_index;
-        }
-        
-        // Set target group as the active group
-        editor_data.active_group = target_group_index;
-    }
-    
-    tracing::info!("Moved tab from group {} index {} to group {} (new index: {})", 
-        source_group_index, source_tab_index, target_group_index, 
-        editor_data.editor_groups[target_group_index].tabs.len() - 1);
+    let background = if active {
+        "rgba(0, 122, 204, 0.25)"
+    } else {
+        "transparent"
+    };
+    format!("position: absolute; {} background: {}; z-index: 10;", placement, background)
 }
 
 // Context menu action helper functions
@@ -2316,73 +2444,45 @@ fn close_tab(mut editor_state: Signal<crate::state::EditorState>, group_index: u
     let mut editor_data = editor_state.write();
     editor_data.context_menu = None; // Close context menu
     
-    if let Some(group) = editor_data.editor_groups.get_mut(group_index) {
-        if group.tabs.len() > 1 && tab_index < group.tabs.len() {
-            group.tabs.remove(tab_index);
-            // Adjust active tab if necessary
-            if group.active_tab >= group.tabs.len() {
-                group.active_tab = group.tabs.len().saturating_sub(1);
-            }
-            // Update active states
-            for (i, t) in group.tabs.iter_mut().enumerate() {
-                t.is_active = i == group.active_tab;
-            }
-            tracing::info!("Closed tab {} in group {} via context menu", tab_index, group_index);
-        }
+    if editor_data.close_tab(group_index, tab_index) {
+        tracing::info!("Closed tab {} in group {} via context menu", tab_index, group_index);
     }
 }
 
-/// Close all tabs except the specified one
+/// Close all unpinned tabs except the specified one
 fn close_others(mut editor_state: Signal<crate::state::EditorState>, group_index: usize, keep_tab_index: usize) {
     let mut editor_data = editor_state.write();
     editor_data.context_menu = None; // Close context menu
     
-    if let Some(group) = editor_data.editor_groups.get_mut(group_index) {
-        if keep_tab_index < group.tabs.len() {
-            let kept_tab = group.tabs[keep_tab_index].clone();
-            group.tabs = vec![kept_tab];
-            group.active_tab = 0;
-            group.tabs[0].is_active = true;
-            tracing::info!("Closed all other tabs in group {}, kept tab {}", group_index, keep_tab_index);
-        }
-    }
+    let closed = editor_data.close_others(group_index, keep_tab_index);
+    tracing::info!("Closed {} other tabs in group {}, kept tab {}", closed, group_index, keep_tab_index);
 }
 
-/// Close all tabs to the right of the specified tab
+/// Close all unpinned tabs to the right of the specified tab
 fn close_tabs_to_right(mut editor_state: Signal<crate::state::EditorState>, group_index: usize, tab_index: usize) {
     let mut editor_data = editor_state.write();
     editor_data.context_menu = None; // Close context menu
     
-    if let Some(group) = editor_data.editor_groups.get_mut(group_index) {
-        if tab_index < group.tabs.len() && tab_index < group.tabs.len() - 1 {
-            let keep_count = tab_index + 1;
-            group.tabs.truncate(keep_count);
-            
-            // Adjust active tab if it was removed
-            if group.active_tab >= keep_count {
-                group.active_tab = keep_count - 1;
-            }
-            
-            // Update active states
-            for (i, t) in group.tabs.iter_mut().enumerate() {
-                t.is_active = i == group.active_tab;
-            }
-            
-            tracing::info!("Closed tabs to the right of tab {} in group {}", tab_index, group_index);
-        }
-    }
+    let closed = editor_data.close_tabs_to_right(group_index, tab_index);
+    tracing::info!("Closed {} tabs to the right of tab {} in group {}", closed, tab_index, group_index);
 }
 
-/// Toggle pin status of a tab
+/// Close every unpinned tab in a group
+fn close_all_unpinned(mut editor_state: Signal<crate::state::EditorState>, group_index: usize) {
+    let mut editor_data = editor_state.write();
+    editor_data.context_menu = None; // Close context menu
+    
+    let closed = editor_data.close_all_unpinned(group_index);
+    tracing::info!("Closed {} unpinned tabs in group {}", closed, group_index);
+}
+
+/// Toggle pin status of a tab, moving it into or out of the pinned block
 fn toggle_pin_tab(mut editor_state: Signal<crate::state::EditorState>, group_index: usize, tab_index: usize) {
     let mut editor_data = editor_state.write();
     editor_data.context_menu = None; // Close context menu
     
-    if let Some(group) = editor_data.editor_groups.get_mut(group_index) {
-        if let Some(tab) = group.tabs.get_mut(tab_index) {
-            tab.is_pinned = !tab.is_pinned;
-            tracing::info!("Toggled pin for tab {} in group {}: {}", tab_index, group_index, tab.is_pinned);
-        }
+    if editor_data.toggle_pin(group_index, tab_index) {
+        tracing::info!("Toggled pin for tab {} in group {}", tab_index, group_index);
     }
 }
 
@@ -5,7 +5,14 @@
 
 pub mod path_utils;
 pub mod file_type_detection;
+pub mod format;
 
 // Re-export commonly used utilities
-pub use path_utils::{normalize_path_display, normalize_path_string, path_to_element_id};
-pub use file_type_detection::{FileTypeDetectionUtil, FilePreviewSupport, PreviewCategory, InfoCategory};
\ No newline at end of file
+pub use path_utils::{
+    natural_cmp, normalize_path_display, normalize_path_string, path_to_element_id, validate_file_name, NameError,
+};
+pub use file_type_detection::{FileTypeDetectionUtil, FilePreviewSupport, PreviewCategory, InfoCategory};
+pub use format::{
+    format_date, format_date_time, format_duration, format_number, format_rate, format_relative_time, format_size,
+    ClockFormat, FormatSettings, SizeUnits,
+};
\ No newline at end of file
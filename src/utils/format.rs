@@ -0,0 +1,378 @@
+//! Locale-aware formatting of sizes, rates, durations, numbers and dates
+//!
+//! Every human-readable size or timestamp in the UI goes through these
+//! helpers so the unit and clock preferences apply consistently. The active
+//! [`FormatSettings`] are global, like the UI locale, and are updated from
+//! the user's settings.
+
+use crate::i18n::{self, Locale};
+use chrono::{DateTime, Local, NaiveDateTime};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+use std::time::{Duration, SystemTime};
+
+/// How byte sizes are scaled and labelled
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SizeUnits {
+    /// Powers of 1024 labelled KiB, MiB, ...
+    #[default]
+    Binary,
+    /// Powers of 1000 labelled KB, MB, ...
+    Decimal,
+}
+
+impl SizeUnits {
+    pub const ALL: [SizeUnits; 2] = [SizeUnits::Binary, SizeUnits::Decimal];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SizeUnits::Binary => "binary",
+            SizeUnits::Decimal => "decimal",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "decimal" => SizeUnits::Decimal,
+            _ => SizeUnits::Binary,
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            SizeUnits::Binary => "Binary (KiB, MiB)",
+            SizeUnits::Decimal => "Decimal (KB, MB)",
+        }
+    }
+
+    fn base(&self) -> f64 {
+        match self {
+            SizeUnits::Binary => 1024.0,
+            SizeUnits::Decimal => 1000.0,
+        }
+    }
+
+    fn labels(&self) -> &'static [&'static str] {
+        match self {
+            SizeUnits::Binary => &["B", "KiB", "MiB", "GiB", "TiB", "PiB"],
+            SizeUnits::Decimal => &["B", "KB", "MB", "GB", "TB", "PB"],
+        }
+    }
+}
+
+/// 12- or 24-hour clock preference
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClockFormat {
+    /// Follow the convention of the display language
+    #[default]
+    Auto,
+    TwelveHour,
+    TwentyFourHour,
+}
+
+impl ClockFormat {
+    pub const ALL: [ClockFormat; 3] = [ClockFormat::Auto, ClockFormat::TwelveHour, ClockFormat::TwentyFourHour];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ClockFormat::Auto => "auto",
+            ClockFormat::TwelveHour => "12h",
+            ClockFormat::TwentyFourHour => "24h",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "12h" => ClockFormat::TwelveHour,
+            "24h" => ClockFormat::TwentyFourHour,
+            _ => ClockFormat::Auto,
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ClockFormat::Auto => "Language Default",
+            ClockFormat::TwelveHour => "12-hour",
+            ClockFormat::TwentyFourHour => "24-hour",
+        }
+    }
+
+    fn is_twelve_hour(&self, locale: Locale) -> bool {
+        match self {
+            ClockFormat::TwelveHour => true,
+            ClockFormat::TwentyFourHour => false,
+            ClockFormat::Auto => locale == Locale::English,
+        }
+    }
+}
+
+/// Preferences that control how values are displayed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FormatSettings {
+    pub locale: Locale,
+    pub size_units: SizeUnits,
+    pub clock: ClockFormat,
+}
+
+impl FormatSettings {
+    pub fn new(locale: Locale) -> Self {
+        Self {
+            locale,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_size_units(mut self, size_units: SizeUnits) -> Self {
+        self.size_units = size_units;
+        self
+    }
+
+    pub fn with_clock(mut self, clock: ClockFormat) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn decimal_separator(&self) -> char {
+        match self.locale {
+            Locale::English => '.',
+            Locale::German => ',',
+        }
+    }
+
+    fn date_pattern(&self) -> &'static str {
+        match self.locale {
+            Locale::English => "%m/%d/%Y",
+            Locale::German => "%d.%m.%Y",
+        }
+    }
+
+    fn time_pattern(&self) -> &'static str {
+        if self.clock.is_twelve_hour(self.locale) {
+            "%-I:%M %p"
+        } else {
+            "%H:%M"
+        }
+    }
+}
+
+static FORMAT_SETTINGS: Lazy<RwLock<FormatSettings>> = Lazy::new(|| RwLock::new(FormatSettings::default()));
+
+/// Replace the preferences used by the formatting helpers
+pub fn set_format_settings(settings: FormatSettings) {
+    if let Ok(mut current) = FORMAT_SETTINGS.write() {
+        *current = settings;
+    }
+}
+
+pub fn format_settings() -> FormatSettings {
+    FORMAT_SETTINGS.read().map(|settings| *settings).unwrap_or_default()
+}
+
+/// Format a number with one decimal place and the locale's decimal separator
+fn format_one_decimal(value: f64, settings: &FormatSettings) -> String {
+    format!("{:.1}", value).replace('.', &settings.decimal_separator().to_string())
+}
+
+/// Human-readable byte size, e.g. "1.5 KiB"
+pub fn format_size(bytes: u64) -> String {
+    format_size_with(bytes, &format_settings())
+}
+
+pub fn format_size_with(bytes: u64, settings: &FormatSettings) -> String {
+    let units = settings.size_units;
+    let labels = units.labels();
+    let mut value = bytes as f64;
+    let mut unit_index = 0;
+    // Compare the value as it will be shown, so 1023.96 KiB reads "1.0 MiB"
+    let shown = |value: f64, unit_index: usize| if unit_index == 0 { value } else { (value * 10.0).round() / 10.0 };
+    while shown(value, unit_index) >= units.base() && unit_index < labels.len() - 1 {
+        value /= units.base();
+        unit_index += 1;
+    }
+
+    if unit_index == 0 {
+        format!("{} {}", i18n::format_count(settings.locale, bytes), labels[0])
+    } else {
+        format!("{} {}", format_one_decimal(value, settings), labels[unit_index])
+    }
+}
+
+/// Human-readable transfer rate, e.g. "2.0 MiB/s"
+pub fn format_rate(bytes_per_second: u64) -> String {
+    format_rate_with(bytes_per_second, &format_settings())
+}
+
+pub fn format_rate_with(bytes_per_second: u64, settings: &FormatSettings) -> String {
+    format!("{}/s", format_size_with(bytes_per_second, settings))
+}
+
+/// Compact duration, e.g. "1h 1m 1s"
+pub fn format_duration(duration: Duration) -> String {
+    let total_seconds = duration.as_secs();
+    let hours = total_seconds / 3600;
+    let minutes = (total_seconds % 3600) / 60;
+    let seconds = total_seconds % 60;
+
+    if hours > 0 {
+        format!("{}h {}m {}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m {}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// Integer with the locale's digit grouping, e.g. "12,345"
+pub fn format_number(value: u64) -> String {
+    i18n::format_count(format_settings().locale, value)
+}
+
+/// Local date and time, e.g. "03/14/2024 9:05 PM" or "14.03.2024 21:05"
+pub fn format_date_time(time: SystemTime) -> String {
+    format_date_time_with(time, &format_settings())
+}
+
+pub fn format_date_time_with(time: SystemTime, settings: &FormatSettings) -> String {
+    format_naive_date_time(&DateTime::<Local>::from(time).naive_local(), settings)
+}
+
+/// Local date only, e.g. "03/14/2024"
+pub fn format_date(time: SystemTime) -> String {
+    let settings = format_settings();
+    DateTime::<Local>::from(time).format(settings.date_pattern()).to_string()
+}
+
+/// How long ago `time` was, e.g. "just now", "3 minutes ago" or "yesterday"
+///
+/// Times in the future or more than a week back are shown as a date and time
+/// instead.
+pub fn format_relative_time(time: SystemTime) -> String {
+    format_relative_time_with(time, SystemTime::now(), &format_settings())
+}
+
+pub fn format_relative_time_with(time: SystemTime, now: SystemTime, settings: &FormatSettings) -> String {
+    format_naive_relative_time(
+        &DateTime::<Local>::from(time).naive_local(),
+        &DateTime::<Local>::from(now).naive_local(),
+        settings,
+    )
+}
+
+/// Relative form of `then`, with "yesterday" and day counts going by calendar date
+fn format_naive_relative_time(then: &NaiveDateTime, now: &NaiveDateTime, settings: &FormatSettings) -> String {
+    let elapsed = now.signed_duration_since(*then);
+    if elapsed < chrono::Duration::zero() {
+        return format_naive_date_time(then, settings);
+    }
+
+    let translate = |key: &str, count: i64| i18n::translate_in(settings.locale, key, Some(count as u64), &[]);
+    let days = now.date().signed_duration_since(then.date()).num_days();
+    if elapsed.num_minutes() < 1 {
+        i18n::translate_in(settings.locale, "time.just_now", None, &[])
+    } else if elapsed.num_hours() < 1 {
+        translate("time.minutes_ago", elapsed.num_minutes())
+    } else if days == 0 {
+        translate("time.hours_ago", elapsed.num_hours())
+    } else if days == 1 {
+        i18n::translate_in(settings.locale, "time.yesterday", None, &[])
+    } else if days < 7 {
+        translate("time.days_ago", days)
+    } else {
+        format_naive_date_time(then, settings)
+    }
+}
+
+fn format_naive_date_time(date_time: &NaiveDateTime, settings: &FormatSettings) -> String {
+    format!(
+        "{} {}",
+        date_time.format(settings.date_pattern()),
+        date_time.format(settings.time_pattern())
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_size_units_scale_and_label() {
+        let binary = FormatSettings::new(Locale::English);
+        let decimal = binary.with_size_units(SizeUnits::Decimal);
+
+        assert_eq!(format_size_with(512, &binary), "512 B");
+        assert_eq!(format_size_with(1536, &binary), "1.5 KiB");
+        assert_eq!(format_size_with(1024 * 1024, &binary), "1.0 MiB");
+        assert_eq!(format_size_with(1500, &decimal), "1.5 KB");
+        assert_eq!(format_size_with(1024, &decimal), "1.0 KB");
+        assert_eq!(format_size_with(3_000_000_000, &decimal), "3.0 GB");
+        assert_eq!(format_rate_with(2 * 1024 * 1024, &binary), "2.0 MiB/s");
+
+        // Locale decides the separators, not the units
+        let german = FormatSettings::new(Locale::German).with_size_units(SizeUnits::Decimal);
+        assert_eq!(format_size_with(1500, &german), "1,5 KB");
+        assert_eq!(format_size_with(999, &german), "999 B");
+    }
+
+    #[test]
+    fn test_size_unit_boundaries() {
+        let binary = FormatSettings::new(Locale::English);
+        let decimal = binary.with_size_units(SizeUnits::Decimal);
+
+        assert_eq!(format_size_with(0, &binary), "0 B");
+        assert_eq!(format_size_with(1023, &binary), "1,023 B");
+        assert_eq!(format_size_with(1024, &binary), "1.0 KiB");
+        assert_eq!(format_size_with(1024 * 1024 - 1, &binary), "1.0 MiB");
+        assert_eq!(format_size_with(999, &decimal), "999 B");
+        assert_eq!(format_size_with(1000, &decimal), "1.0 KB");
+        assert_eq!(format_size_with(u64::MAX, &decimal), "18446.7 PB");
+    }
+
+    #[test]
+    fn test_relative_times_at_their_boundaries() {
+        let english = FormatSettings::new(Locale::English);
+        let now = NaiveDate::from_ymd_opt(2024, 3, 14).unwrap().and_hms_opt(15, 0, 0).unwrap();
+        let ago = |seconds: i64| format_naive_relative_time(&(now - chrono::Duration::seconds(seconds)), &now, &english);
+
+        assert_eq!(ago(0), "just now");
+        assert_eq!(ago(59), "just now");
+        assert_eq!(ago(60), "1 minute ago");
+        assert_eq!(ago(59 * 60 + 59), "59 minutes ago");
+        assert_eq!(ago(60 * 60), "1 hour ago");
+        assert_eq!(ago(15 * 60 * 60), "15 hours ago");
+        // Just before midnight is yesterday, not "15 hours ago"
+        assert_eq!(ago(15 * 60 * 60 + 1), "yesterday");
+        assert_eq!(ago(39 * 60 * 60), "yesterday");
+        assert_eq!(ago(39 * 60 * 60 + 1), "2 days ago");
+        assert_eq!(ago(6 * 24 * 60 * 60), "6 days ago");
+        assert_eq!(ago(7 * 24 * 60 * 60), "03/07/2024 3:00 PM");
+
+        // The future is never "ago"
+        let later = now + chrono::Duration::minutes(5);
+        assert_eq!(format_naive_relative_time(&later, &now, &english), "03/14/2024 3:05 PM");
+
+        let german = FormatSettings::new(Locale::German);
+        assert_eq!(format_naive_relative_time(&(now - chrono::Duration::minutes(1)), &now, &german), "vor 1 Minute");
+        assert_eq!(format_naive_relative_time(&(now - chrono::Duration::days(1)), &now, &german), "gestern");
+    }
+
+    #[test]
+    fn test_dates_follow_locale_and_clock_preference() {
+        let date_time = NaiveDate::from_ymd_opt(2024, 3, 14).unwrap().and_hms_opt(21, 5, 0).unwrap();
+
+        let english = FormatSettings::new(Locale::English);
+        assert_eq!(format_naive_date_time(&date_time, &english), "03/14/2024 9:05 PM");
+        assert_eq!(
+            format_naive_date_time(&date_time, &english.with_clock(ClockFormat::TwentyFourHour)),
+            "03/14/2024 21:05"
+        );
+
+        let german = FormatSettings::new(Locale::German);
+        assert_eq!(format_naive_date_time(&date_time, &german), "14.03.2024 21:05");
+        assert_eq!(
+            format_naive_date_time(&date_time, &german.with_clock(ClockFormat::TwelveHour)),
+            "14.03.2024 9:05 PM"
+        );
+    }
+}
@@ -3,6 +3,7 @@
 //! This module provides utilities to clean up path displays by removing redundant
 //! slashes and ensuring consistent path formatting across different platforms.
 
+use std::cmp::Ordering;
 use std::path::{Path, PathBuf};
 
 /// Normalize a path string by removing redundant slashes while preserving important path semantics
@@ -117,6 +118,102 @@ pub fn path_to_element_id(path: &Path) -> String {
         .replace('.', "_")
 }
 
+/// Characters that can't appear in file or folder names on at least one supported platform
+pub const INVALID_NAME_CHARS: [char; 9] = ['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+/// Why a typed file or folder name can't be used
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NameError {
+    Empty,
+    /// The first disallowed character in the name
+    InvalidCharacter(char),
+}
+
+impl NameError {
+    /// Explanation shown next to the name field
+    pub fn message(&self) -> String {
+        match self {
+            NameError::Empty => t!("name_error.empty"),
+            NameError::InvalidCharacter(character) => t!(
+                "name_error.invalid_character",
+                character = character,
+                characters = INVALID_NAME_CHARS.iter().collect::<String>()
+            ),
+        }
+    }
+}
+
+/// Check a file or folder name typed by the user, ignoring surrounding whitespace
+///
+/// # Examples
+/// ```
+/// use media_organizer::utils::{validate_file_name, NameError};
+///
+/// assert_eq!(validate_file_name("holiday.jpg"), Ok(()));
+/// assert_eq!(validate_file_name("a/b"), Err(NameError::InvalidCharacter('/')));
+/// ```
+pub fn validate_file_name(name: &str) -> Result<(), NameError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(NameError::Empty);
+    }
+    match name.chars().find(|character| INVALID_NAME_CHARS.contains(character)) {
+        Some(character) => Err(NameError::InvalidCharacter(character)),
+        None => Ok(()),
+    }
+}
+
+/// Compare file names the way people read them: case-insensitively, with runs
+/// of digits compared by value
+///
+/// # Examples
+/// ```
+/// use media_organizer::utils::natural_cmp;
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(natural_cmp("file2.jpg", "file10.jpg"), Ordering::Less);
+/// assert_eq!(natural_cmp("IMG_007", "img_7"), Ordering::Equal);
+/// ```
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut a = a.chars().peekable();
+    let mut b = b.chars().peekable();
+    loop {
+        match (a.peek().copied(), b.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let x = take_number(&mut a);
+                let y = take_number(&mut b);
+                // Without leading zeros, a longer run is a larger number
+                let by_value = x.len().cmp(&y.len()).then_with(|| x.cmp(&y));
+                if by_value != Ordering::Equal {
+                    return by_value;
+                }
+            }
+            (Some(x), Some(y)) => {
+                let by_char = x.to_lowercase().cmp(y.to_lowercase());
+                if by_char != Ordering::Equal {
+                    return by_char;
+                }
+                a.next();
+                b.next();
+            }
+        }
+    }
+}
+
+/// Consume a run of digits and return it without leading zeros
+fn take_number(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut digits = String::new();
+    while let Some(digit) = chars.next_if(|c| c.is_ascii_digit()) {
+        if !(digits.is_empty() && digit == '0') {
+            digits.push(digit);
+        }
+    }
+    digits
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -202,4 +299,27 @@ mod tests {
         // Ensure no double separators in the ID
         assert!(!result.contains("--"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_validate_file_name() {
+        assert_eq!(validate_file_name(" holiday.jpg "), Ok(()));
+        assert_eq!(validate_file_name("   "), Err(NameError::Empty));
+        assert_eq!(validate_file_name("a:b?.txt"), Err(NameError::InvalidCharacter(':')));
+        for character in INVALID_NAME_CHARS {
+            assert_eq!(validate_file_name(&format!("x{}y", character)), Err(NameError::InvalidCharacter(character)));
+        }
+    }
+
+    #[test]
+    fn test_natural_cmp_orders_numbers_by_value() {
+        let mut names = vec!["file10.txt", "File2.txt", "file1.txt", "file02b.txt", "file2a.txt", "notes", "file"];
+        names.sort_by(|a, b| natural_cmp(a, b));
+        assert_eq!(
+            names,
+            vec!["file", "file1.txt", "File2.txt", "file2a.txt", "file02b.txt", "file10.txt", "notes"]
+        );
+        assert_eq!(natural_cmp("track 9", "track 10"), Ordering::Less);
+        assert_eq!(natural_cmp("a100", "a99"), Ordering::Greater);
+        assert_eq!(natural_cmp("Photo", "photo"), Ordering::Equal);
+    }
+}
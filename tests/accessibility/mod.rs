@@ -137,6 +137,53 @@ impl AccessibilityTester {
         Ok(self.parse_axe_results(url, results, test_duration))
     }
 
+    /// Open a page and wait for the app to render, for tests that drive it directly
+    pub async fn open_page(&self, url: &str) -> Result<std::sync::Arc<Tab>, Box<dyn std::error::Error>> {
+        let tab = self.browser.new_tab()?;
+        tab.navigate_to(url)?;
+        tab.wait_until_navigated()?;
+        sleep(Duration::from_secs(2)).await;
+        Ok(tab)
+    }
+
+    /// Press keys on the page, then run axe with custom run options
+    ///
+    /// `test_page` only runs the WCAG-tagged rules, which skips keyboard
+    /// best-practice rules such as `focus-order-semantics`; pass them here.
+    pub async fn test_page_with_options(
+        &self,
+        url: &str,
+        run_options: Value,
+        key_presses: &[&str],
+    ) -> Result<AccessibilityTestResult, Box<dyn std::error::Error>> {
+        let start_time = std::time::Instant::now();
+
+        let tab = self.browser.new_tab()?;
+        tab.navigate_to(url)?;
+        tab.wait_until_navigated()?;
+        sleep(Duration::from_secs(2)).await;
+
+        // Drive focus through the UI so focus-dependent rules see real state
+        for key in key_presses {
+            tab.press_key(key)?;
+        }
+
+        tab.evaluate(&self.axe_script, false)?;
+
+        let axe_results = tab.evaluate(
+            &format!(
+                "axe.run(document, {}).then(results => JSON.stringify(results))",
+                serde_json::to_string(&run_options)?
+            ),
+            true
+        )?.value.unwrap().as_str().unwrap().to_string();
+
+        let results: Value = serde_json::from_str(&axe_results)?;
+        let test_duration = start_time.elapsed().as_millis() as u64;
+
+        Ok(self.parse_axe_results(url, results, test_duration))
+    }
+
     /// Get axe-core configuration with MediaOrganizer-specific rules
     fn get_axe_configuration(&self) -> Value {
         json!({
@@ -115,6 +115,108 @@ mod accessibility_tests {
         }
     }
 
+    /// Test that keyboard focus management has no keyboard or focus-order violations
+    #[tokio::test]
+    async fn test_focus_management_has_no_keyboard_violations() {
+        let tester = match AccessibilityTester::new() {
+            Ok(tester) => tester,
+            Err(_) => {
+                println!("⚠️ Focus management test skipped: Chrome not available");
+                return;
+            }
+        };
+
+        // Tab into the tree, move within it, cycle regions and back out again
+        let key_presses = ["Tab", "Tab", "ArrowDown", "ArrowDown", "End", "F6", "Escape", "Escape"];
+        let run_options = serde_json::json!({
+            "runOnly": { "type": "tag", "values": ["cat.keyboard"] },
+            "rules": { "focus-order-semantics": { "enabled": true } }
+        });
+        let result = tester
+            .test_page_with_options("http://localhost:3000", run_options, &key_presses)
+            .await;
+
+        match result {
+            Ok(test_result) => {
+                let report = tester.generate_report(&test_result);
+                save_test_report("focus_management_test", &report);
+
+                let focus_violations: Vec<_> = test_result.violations
+                    .iter()
+                    .filter(|v| v.id == "focus-order-semantics" || v.tags.iter().any(|t| t == "cat.keyboard"))
+                    .collect();
+
+                assert!(
+                    focus_violations.is_empty(),
+                    "Found keyboard/focus-order violations: {:?}",
+                    focus_violations.iter()
+                        .map(|v| format!("{} ({})", v.id, v.nodes.iter().map(|n| n.target.join(" > ")).collect::<Vec<_>>().join(", ")))
+                        .collect::<Vec<_>>()
+                );
+
+                println!("✅ Focus management test passed");
+            }
+            Err(e) => {
+                println!("⚠️ Focus management test skipped: {}", e);
+                println!("   This is expected if the application isn't running on localhost:3000");
+            }
+        }
+    }
+
+    /// Test that the settings dialog traps focus and returns it to its trigger on close
+    #[tokio::test]
+    async fn test_settings_dialog_traps_and_restores_focus() {
+        let tester = match AccessibilityTester::new() {
+            Ok(tester) => tester,
+            Err(_) => {
+                println!("⚠️ Modal focus test skipped: Chrome not available");
+                return;
+            }
+        };
+
+        let tab = match tester.open_page("http://localhost:3000").await {
+            Ok(tab) => tab,
+            Err(e) => {
+                println!("⚠️ Modal focus test skipped: {}", e);
+                println!("   This is expected if the application isn't running on localhost:3000");
+                return;
+            }
+        };
+
+        let active_element_check = |tab: &headless_chrome::Tab, script: &str| -> bool {
+            tab.evaluate(script, false)
+                .ok()
+                .and_then(|result| result.value)
+                .and_then(|value| value.as_bool())
+                .unwrap_or(false)
+        };
+        let focus_in_dialog = "document.activeElement?.closest('[role=\"dialog\"][aria-modal=\"true\"]') !== null";
+        let focus_on_trigger = "document.activeElement?.title?.startsWith('Open settings') === true";
+
+        tab.find_element("button[title^='Open settings']")
+            .expect("Settings button not found")
+            .click()
+            .expect("Failed to open settings");
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        assert!(active_element_check(&tab, focus_in_dialog), "Focus did not move into the settings dialog");
+
+        // Tab well past the number of controls in both directions; focus must stay inside
+        for _ in 0..25 {
+            tab.press_key("Tab").expect("Failed to press Tab");
+        }
+        assert!(active_element_check(&tab, focus_in_dialog), "Tab moved focus out of the settings dialog");
+
+        tab.press_key("Escape").expect("Failed to press Escape");
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        assert!(
+            active_element_check(&tab, "document.querySelector('[role=\"dialog\"][aria-modal=\"true\"]') === null"),
+            "Escape did not close the settings dialog"
+        );
+        assert!(active_element_check(&tab, focus_on_trigger), "Focus was not returned to the settings button");
+
+        println!("✅ Settings dialog focus trap test passed");
+    }
+
     /// Test file tree accessibility
     #[tokio::test]
     async fn test_file_tree_accessibility() {
@@ -0,0 +1,86 @@
+/*!
+ * Headless CLI Tests for MediaOrganizer
+ *
+ * Runs the real binary with headless subcommands on temporary trees and
+ * checks output, exit codes and that dry runs leave files untouched.
+ */
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+fn media_organizer() -> Command {
+    Command::cargo_bin("media-organizer").unwrap()
+}
+
+#[test]
+fn test_scan_duplicates_reports_and_only_deletes_with_apply() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().join("photos");
+    fs::create_dir_all(root.join("backup")).unwrap();
+    fs::write(root.join("beach.jpg"), b"same picture").unwrap();
+    fs::write(root.join("backup").join("beach.jpg"), b"same picture").unwrap();
+    fs::write(root.join("forest.jpg"), b"different picture").unwrap();
+    let report_path = temp_dir.path().join("report.json");
+    let log_path = temp_dir.path().join("operation_log.jsonl");
+    // Keep trashed files out of the user's real trash
+    let trash_dir = temp_dir.path().join("trash");
+
+    // Dry run: report the pair, change nothing
+    media_organizer()
+        .arg("--scan-duplicates")
+        .arg(&root)
+        .arg("--report")
+        .arg(&report_path)
+        .arg("--log")
+        .arg(&log_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1 duplicate groups in 3 files"));
+
+    let report: serde_json::Value = serde_json::from_str(&fs::read_to_string(&report_path).unwrap()).unwrap();
+    assert_eq!(report["files_analyzed"], 3);
+    assert_eq!(report["applied"], false);
+    let groups = report["groups"].as_array().unwrap();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0]["duplicates"].as_array().unwrap().len(), 1);
+    assert!(root.join("beach.jpg").exists());
+    assert!(root.join("backup").join("beach.jpg").exists());
+    assert!(!log_path.exists());
+
+    // Apply: exactly one copy of the pair is left
+    media_organizer()
+        .arg("--scan-duplicates")
+        .arg(&root)
+        .arg("--log")
+        .arg(&log_path)
+        .arg("--trash-dir")
+        .arg(&trash_dir)
+        .arg("--apply")
+        .assert()
+        .success();
+
+    let remaining = [root.join("beach.jpg"), root.join("backup").join("beach.jpg")]
+        .iter()
+        .filter(|path| path.exists())
+        .count();
+    assert_eq!(remaining, 1);
+    assert!(root.join("forest.jpg").exists());
+    assert_eq!(fs::read_dir(&trash_dir).unwrap().count(), 1);
+    assert_eq!(fs::read_to_string(&log_path).unwrap().lines().count(), 1);
+}
+
+#[test]
+fn test_invalid_arguments_exit_with_usage_error() {
+    media_organizer()
+        .args(["--scan-duplicates"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Usage:"));
+
+    media_organizer()
+        .args(["--hash", "/definitely/not/here"])
+        .assert()
+        .code(1);
+}
@@ -0,0 +1,97 @@
+//! Preview latency while a duplicate scan hashes in the background
+//!
+//! Run with `cargo test --release --test duplicate_scan_benchmarks -- --ignored --nocapture`.
+//! Set `SCAN_BENCH_DIR` to a folder on the disk under test; the default temp
+//! directory is often served from the page cache and shows little contention.
+
+use media_organizer::services::{BackgroundProcessor, BackgroundProgressInfo, HashingTask, IoPriority};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const SCAN_FILES: usize = 64;
+const SCAN_FILE_SIZE: usize = 8 * 1024 * 1024;
+const PREVIEW_FILES: usize = 16;
+const PREVIEW_READ: usize = 256 * 1024;
+
+fn write_files(dir: &Path, prefix: &str, count: usize, size: usize) -> Vec<PathBuf> {
+    (0..count)
+        .map(|i| {
+            let path = dir.join(format!("{}{}.bin", prefix, i));
+            let content: Vec<u8> = (0..size).map(|b| (b * 31 + i) as u8).collect();
+            std::fs::write(&path, content).unwrap();
+            path
+        })
+        .collect()
+}
+
+/// Time reading the head of each preview file, as the preview pane does
+async fn preview_latencies(previews: &[PathBuf], rounds: usize) -> Vec<Duration> {
+    let mut latencies = Vec::new();
+    for round in 0..rounds {
+        let path = previews[round % previews.len()].clone();
+        let start = Instant::now();
+        tokio::task::spawn_blocking(move || {
+            let mut buffer = vec![0u8; PREVIEW_READ];
+            let mut file = std::fs::File::open(path).unwrap();
+            let _ = file.read(&mut buffer).unwrap();
+        })
+        .await
+        .unwrap();
+        latencies.push(start.elapsed());
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    latencies
+}
+
+fn percentile(sorted: &[Duration], p: f64) -> f64 {
+    let index = ((sorted.len() - 1) as f64 * p).round() as usize;
+    sorted[index].as_secs_f64() * 1000.0
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[ignore = "benchmark; writes several hundred megabytes"]
+async fn bench_preview_latency_during_scan() {
+    let root = std::env::var_os("SCAN_BENCH_DIR").map(PathBuf::from).unwrap_or_else(std::env::temp_dir);
+    let dir = tempfile::TempDir::new_in(root).unwrap();
+    let scan_files = write_files(dir.path(), "scan", SCAN_FILES, SCAN_FILE_SIZE);
+    let previews = write_files(dir.path(), "preview", PREVIEW_FILES, PREVIEW_READ);
+    let total_size = (SCAN_FILES * SCAN_FILE_SIZE) as u64;
+
+    let mut idle = preview_latencies(&previews, 50).await;
+    idle.sort();
+    println!("{:>8} {:>8} {:>10} {:>10} {:>10}", "workers", "io", "p50 ms", "p95 ms", "scan s");
+    println!("{:>8} {:>8} {:>10.2} {:>10.2} {:>10}", "-", "idle", percentile(&idle, 0.5), percentile(&idle, 0.95), "-");
+
+    let processor = BackgroundProcessor::default();
+    for workers in [1, 2, 4, 8] {
+        for io_priority in [IoPriority::Normal, IoPriority::Low] {
+            let task = HashingTask::new(scan_files.clone(), total_size, Arc::new(|_progress: BackgroundProgressInfo| {}))
+                .with_concurrency(workers)
+                .with_io_priority(io_priority);
+            let started = Instant::now();
+            let task_id = processor.start_hashing_task(task).await.unwrap();
+
+            let mut latencies = Vec::new();
+            while processor.is_task_running(task_id).await {
+                latencies.extend(preview_latencies(&previews, 5).await);
+            }
+            let scan_time = started.elapsed();
+            assert!(processor.get_task_result(task_id).await.is_some());
+            if latencies.is_empty() {
+                continue;
+            }
+
+            latencies.sort();
+            println!(
+                "{:>8} {:>8} {:>10.2} {:>10.2} {:>10.2}",
+                workers,
+                format!("{:?}", io_priority),
+                percentile(&latencies, 0.5),
+                percentile(&latencies, 0.95),
+                scan_time.as_secs_f64()
+            );
+        }
+    }
+}